@@ -0,0 +1,54 @@
+//! Income, expenses, and net for each month of a fiscal year, leaving out
+//! any tags given after the year.
+//!
+//!     cargo run --example monthly_pnl -- 2025 personal
+//!
+//! Reads the book `nigel` is pointed at; set NIGEL_DB_PASSWORD for an
+//! encrypted one.
+
+use chrono::{Datelike, Months};
+use nigel::api::{Book, Period, PnlParams};
+use nigel::error::{NigelError, Result};
+use nigel::fmt::money;
+
+fn main() -> Result<()> {
+    let book = match std::env::var("NIGEL_DB_PASSWORD") {
+        Ok(password) => Book::open_with_password(nigel::settings::get_data_dir(), &password)?,
+        Err(_) => Book::open_default()?,
+    };
+    let mut args = std::env::args().skip(1);
+    let fiscal = book.fiscal_year();
+    let year = match args.next() {
+        Some(arg) => arg
+            .parse()
+            .map_err(|_| NigelError::Other(format!("Not a year: {arg}")))?,
+        None => fiscal.current(),
+    };
+    let exclude_tags: Vec<String> = args.collect();
+
+    let (start, _) = fiscal.bounds(year);
+    println!(
+        "{:<10} {:>12} {:>12} {:>12}",
+        "Month", "Income", "Expenses", "Net"
+    );
+    for offset in 0..12 {
+        let first = start + Months::new(offset);
+        let params = PnlParams {
+            period: Period::Month {
+                year: first.year(),
+                month: first.month(),
+            },
+            exclude_tags: exclude_tags.clone(),
+            ..PnlParams::default()
+        };
+        let pnl = book.pnl(&params)?;
+        println!(
+            "{:<10} {:>12} {:>12} {:>12}",
+            first.format("%b %Y").to_string(),
+            money(pnl.total_income),
+            money(pnl.total_expenses),
+            money(pnl.net)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! Spending per vendor for a fiscal year, largest first.
+//!
+//!     cargo run --example vendor_spend -- 2025
+//!
+//! Reads the book `nigel` is pointed at; set NIGEL_DB_PASSWORD for an
+//! encrypted one.
+
+use std::collections::HashMap;
+
+use nigel::api::{Book, Period, RegisterFilter};
+use nigel::error::Result;
+use nigel::fmt::money;
+
+fn main() -> Result<()> {
+    let book = match std::env::var("NIGEL_DB_PASSWORD") {
+        Ok(password) => Book::open_with_password(nigel::settings::get_data_dir(), &password)?,
+        Err(_) => Book::open_default()?,
+    };
+    let year = match std::env::args().nth(1) {
+        Some(arg) => arg
+            .parse()
+            .map_err(|_| nigel::error::NigelError::Other(format!("Not a year: {arg}")))?,
+        None => book.fiscal_year().current(),
+    };
+
+    let rows = book.register(
+        &RegisterFilter::new()
+            .period(Period::FiscalYear(year))
+            .amount_between(f64::MIN, 0.0),
+    )?;
+    let mut spend: HashMap<String, (f64, usize)> = HashMap::new();
+    for row in &rows {
+        let vendor = row
+            .vendor
+            .clone()
+            .unwrap_or_else(|| row.description.clone());
+        let entry = spend.entry(vendor).or_default();
+        entry.0 -= row.amount;
+        entry.1 += 1;
+    }
+    let mut spend: Vec<_> = spend.into_iter().collect();
+    spend.sort_by(|a, b| b.1 .0.total_cmp(&a.1 .0));
+
+    println!("Spending by vendor, {}", book.fiscal_year().label(year));
+    for (vendor, (total, count)) in spend.iter().take(20) {
+        println!("{vendor:<40} {:>12}  ({count})", money(*total));
+    }
+    Ok(())
+}
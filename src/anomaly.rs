@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+
+use crate::error::Result;
+use crate::fmt::money;
+
+/// Prefix of every `flag_reason` set by `flag_anomalies()`.
+pub const ANOMALY_PREFIX: &str = "Unusual";
+
+/// Other charges a vendor needs before one can stand out from them.
+pub const MIN_HISTORY: usize = 3;
+
+/// How many times the vendor's usual (median) amount a charge must reach.
+pub const OUTLIER_FACTOR: f64 = 5.0;
+
+/// Smallest gap above the usual amount worth flagging, so a $3 charge from
+/// a vendor that usually bills 50 cents passes quietly.
+pub const MIN_OUTLIER_GAP: f64 = 50.0;
+
+/// Rows `flag_anomalies()` looks at: categorized, unflagged, and not yet checked.
+const CANDIDATES: &str = "anomaly_checked = 0 AND is_flagged = 0 AND category_id IS NOT NULL";
+
+struct Txn {
+    id: i64,
+    date: String,
+    amount: f64,
+    /// Upper-cased vendor, or description when no rule named a vendor.
+    key: String,
+}
+
+fn cents(amount: f64) -> i64 {
+    (amount * 100.0).round() as i64
+}
+
+/// Median of `sorted` with the element at `skip` left out.
+fn median_without(sorted: &[f64], skip: usize) -> Option<f64> {
+    let n = sorted.len().checked_sub(1).filter(|n| *n > 0)?;
+    let at = |k: usize| if k < skip { sorted[k] } else { sorted[k + 1] };
+    Some(if n % 2 == 1 {
+        at(n / 2)
+    } else {
+        (at(n / 2 - 1) + at(n / 2)) / 2.0
+    })
+}
+
+/// Why `txn` stands out, if it does: another charge from the same vendor for
+/// the same amount on the same day (the later one is flagged), or an amount
+/// `OUTLIER_FACTOR` times the vendor's usual one, judged against at least
+/// `MIN_HISTORY` other charges of the same sign.
+fn anomaly_reason(
+    txn: &Txn,
+    same_day: &HashMap<(&str, &str, i64), Vec<i64>>,
+    history: &HashMap<(&str, bool), Vec<f64>>,
+) -> Option<String> {
+    let twins = &same_day[&(txn.key.as_str(), txn.date.as_str(), cents(txn.amount))];
+    if let Some(first) = twins.iter().find(|id| **id < txn.id) {
+        return Some(format!(
+            "{ANOMALY_PREFIX}: same amount and vendor as #{first} that day"
+        ));
+    }
+
+    let amounts = &history[&(txn.key.as_str(), txn.amount < 0.0)];
+    if amounts.len() <= MIN_HISTORY {
+        return None;
+    }
+    let amount = txn.amount.abs();
+    let skip = amounts.partition_point(|a| *a < amount);
+    let usual = median_without(amounts, skip)?;
+    if usual > 0.0 && amount >= usual * OUTLIER_FACTOR && amount - usual >= MIN_OUTLIER_GAP {
+        Some(format!(
+            "{ANOMALY_PREFIX}: {} is {:.1}x the usual {}",
+            money(amount),
+            amount / usual,
+            money(usual)
+        ))
+    } else {
+        None
+    }
+}
+
+/// Flag categorized transactions that look wrong for their vendor: a likely
+/// double charge or an amount far above the usual. Each transaction is
+/// checked once, so clearing one of these flags is final. Returns the number
+/// newly flagged.
+pub fn flag_anomalies(conn: &Connection) -> Result<usize> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, date, amount, UPPER(COALESCE(vendor, description)), {CANDIDATES} \
+         FROM transactions WHERE deleted_at IS NULL"
+    ))?;
+    let rows: Vec<(Txn, bool)> = stmt
+        .query_map([], |row| {
+            Ok((
+                Txn {
+                    id: row.get(0)?,
+                    date: row.get(1)?,
+                    amount: row.get(2)?,
+                    key: row.get(3)?,
+                },
+                row.get(4)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    drop(stmt);
+    if !rows.iter().any(|(_, candidate)| *candidate) {
+        return Ok(0);
+    }
+
+    let mut same_day: HashMap<(&str, &str, i64), Vec<i64>> = HashMap::new();
+    let mut history: HashMap<(&str, bool), Vec<f64>> = HashMap::new();
+    for (txn, _) in &rows {
+        same_day
+            .entry((txn.key.as_str(), txn.date.as_str(), cents(txn.amount)))
+            .or_default()
+            .push(txn.id);
+        history
+            .entry((txn.key.as_str(), txn.amount < 0.0))
+            .or_default()
+            .push(txn.amount.abs());
+    }
+    for amounts in history.values_mut() {
+        amounts.sort_by(f64::total_cmp);
+    }
+
+    let mut update = conn.prepare(
+        "UPDATE transactions SET is_flagged = 1, flag_reason = ?1, anomaly_checked = 1 \
+         WHERE id = ?2",
+    )?;
+    let mut flagged = 0;
+    for (txn, _) in rows.iter().filter(|(_, candidate)| *candidate) {
+        if let Some(reason) = anomaly_reason(txn, &same_day, &history) {
+            update.execute(rusqlite::params![reason, txn.id])?;
+            flagged += 1;
+        }
+    }
+    Ok(flagged)
+}
+
+/// Mark every transaction `flag_anomalies()` would look at as checked, so
+/// turning the checks off and on again doesn't sweep up old transactions.
+pub fn mark_checked(conn: &Connection) -> Result<()> {
+    conn.execute(
+        &format!("UPDATE transactions SET anomaly_checked = 1 WHERE {CANDIDATES}"),
+        [],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Card', 'credit_card')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn insert(conn: &Connection, date: &str, description: &str, amount: f64) -> i64 {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, ?1, ?2, ?3, (SELECT id FROM categories WHERE name = 'Software & Subscriptions'))",
+            rusqlite::params![date, description, amount],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn reason(conn: &Connection, id: i64) -> Option<String> {
+        conn.query_row(
+            "SELECT flag_reason FROM transactions WHERE id = ?1 AND is_flagged = 1",
+            [id],
+            |r| r.get(0),
+        )
+        .ok()
+    }
+
+    #[test]
+    fn flags_a_charge_far_above_the_usual() {
+        let (_dir, conn) = test_db();
+        for month in 1..=4 {
+            insert(&conn, &format!("2025-0{month}-03"), "FIGMA", -45.0);
+        }
+        let spike = insert(&conn, "2025-05-03", "FIGMA", -450.0);
+        // Five times a small amount isn't worth a flag
+        for month in 1..=4 {
+            insert(&conn, &format!("2025-0{month}-09"), "BANK FEE", -2.0);
+        }
+        let small = insert(&conn, "2025-05-09", "BANK FEE", -12.0);
+
+        assert_eq!(flag_anomalies(&conn).unwrap(), 1);
+        assert_eq!(
+            reason(&conn, spike).unwrap(),
+            "Unusual: $450.00 is 10.0x the usual $45.00"
+        );
+        assert!(reason(&conn, small).is_none());
+    }
+
+    #[test]
+    fn flags_the_later_of_two_same_day_charges() {
+        let (_dir, conn) = test_db();
+        let first = insert(&conn, "2025-03-10", "NOTION LABS", -16.0);
+        let second = insert(&conn, "2025-03-10", "Notion Labs", -16.0);
+        insert(&conn, "2025-03-11", "NOTION LABS", -16.0);
+
+        assert_eq!(flag_anomalies(&conn).unwrap(), 1);
+        assert!(reason(&conn, first).is_none());
+        assert_eq!(
+            reason(&conn, second).unwrap(),
+            format!("Unusual: same amount and vendor as #{first} that day")
+        );
+    }
+
+    #[test]
+    fn checked_transactions_are_not_flagged_again() {
+        let (_dir, conn) = test_db();
+        let first = insert(&conn, "2025-03-10", "NOTION LABS", -16.0);
+        mark_checked(&conn).unwrap();
+        let second = insert(&conn, "2025-03-10", "NOTION LABS", -16.0);
+        conn.execute(
+            "UPDATE transactions SET anomaly_checked = 1 WHERE id = ?1",
+            [second],
+        )
+        .unwrap();
+
+        assert_eq!(flag_anomalies(&conn).unwrap(), 0);
+        assert!(reason(&conn, first).is_none());
+    }
+
+    #[test]
+    fn median_leaves_out_the_transaction_itself() {
+        assert_eq!(median_without(&[1.0, 2.0, 100.0], 2), Some(1.5));
+        assert_eq!(median_without(&[1.0, 2.0, 3.0, 100.0], 3), Some(2.0));
+        assert_eq!(median_without(&[5.0], 0), None);
+    }
+}
@@ -0,0 +1,510 @@
+//! Typed, read-only access to a book for one-off analysis programs.
+//!
+//! Open a [`Book`], describe what you want with a [`RegisterFilter`] or a
+//! report's parameter struct, and get back the same rows and reports the
+//! CLI prints — no SQL against the tables required. The book is opened
+//! read-only, so nothing here can change it.
+//!
+//! ```no_run
+//! use nigel::api::{Book, Period, PnlParams, RegisterFilter};
+//!
+//! let book = Book::open_default()?;
+//! let meals = book.register(
+//!     &RegisterFilter::new()
+//!         .period(Period::FiscalYear(2025))
+//!         .category("Meals"),
+//! )?;
+//! let total: f64 = meals.iter().map(|row| row.amount).sum();
+//! println!("{} meals, {total:.2}", meals.len());
+//!
+//! let pnl = book.pnl(&PnlParams::new(Period::Month { year: 2025, month: 3 }))?;
+//! println!("March net: {:.2}", pnl.net);
+//! # Ok::<(), nigel::error::NigelError>(())
+//! ```
+//!
+//! See `examples/` for complete programs.
+
+use std::path::Path;
+
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::db::{get_metadata, open_read_only};
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::reports::{self, RegisterQuery};
+
+pub use crate::models::Account;
+pub use crate::reports::{
+    BalanceReport, CashflowMonth, CashflowReport, ExpenseBreakdown, K1PrepReport, PnlItem,
+    PnlReport, RegisterRow, TagItem, TaxSummary,
+};
+
+/// The dates a query or report covers.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Period {
+    /// Every transaction in the book.
+    #[default]
+    All,
+    /// A fiscal year, by the year it ends in (see the book's fiscal year
+    /// start month).
+    FiscalYear(i32),
+    /// A calendar month.
+    Month { year: i32, month: u32 },
+    /// An inclusive date range.
+    Range { from: NaiveDate, to: NaiveDate },
+}
+
+/// A period as the report functions take it: year, month, from, and to.
+type PeriodArgs = (Option<i32>, Option<u32>, Option<String>, Option<String>);
+
+impl Period {
+    fn args(&self) -> Result<PeriodArgs> {
+        Ok(match *self {
+            Period::All => (None, None, None, None),
+            Period::FiscalYear(year) => (Some(year), None, None, None),
+            Period::Month { year, month } => {
+                if !(1..=12).contains(&month) {
+                    return Err(NigelError::Other(format!(
+                        "Month must be 1-12, got {month}"
+                    )));
+                }
+                (Some(year), Some(month), None, None)
+            }
+            Period::Range { from, to } => {
+                if from > to {
+                    return Err(NigelError::Other(format!(
+                        "Range starts after it ends: {from} to {to}"
+                    )));
+                }
+                (None, None, Some(from.to_string()), Some(to.to_string()))
+            }
+        })
+    }
+
+    /// Year and month only, for reports that don't take a date range.
+    fn year_month(&self, report: &str) -> Result<(Option<i32>, Option<u32>)> {
+        if matches!(self, Period::Range { .. }) {
+            return Err(NigelError::Other(format!(
+                "The {report} takes a fiscal year or month, not a date range"
+            )));
+        }
+        let (year, month, _, _) = self.args()?;
+        Ok((year, month))
+    }
+}
+
+/// Which register rows to return. Every filter set must match; string
+/// matches ignore case except account and category names, which must be
+/// exact.
+#[derive(Debug, Clone, Default)]
+pub struct RegisterFilter {
+    period: Period,
+    account: Option<String>,
+    category: Option<String>,
+    uncategorized: bool,
+    tag: Option<String>,
+    vendor: Option<String>,
+    client: Option<String>,
+    min_amount: Option<f64>,
+    max_amount: Option<f64>,
+    flagged: Option<bool>,
+}
+
+impl RegisterFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn period(mut self, period: Period) -> Self {
+        self.period = period;
+        self
+    }
+
+    pub fn account(mut self, name: impl Into<String>) -> Self {
+        self.account = Some(name.into());
+        self
+    }
+
+    /// Rows filed under this category itself (not its sub-categories).
+    pub fn category(mut self, name: impl Into<String>) -> Self {
+        self.category = Some(name.into());
+        self
+    }
+
+    /// Rows with no category yet.
+    pub fn uncategorized(mut self) -> Self {
+        self.uncategorized = true;
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Rows whose vendor — or description, when there's no vendor —
+    /// contains `text`.
+    pub fn vendor(mut self, text: impl Into<String>) -> Self {
+        self.vendor = Some(text.into().to_lowercase());
+        self
+    }
+
+    pub fn client(mut self, name: impl Into<String>) -> Self {
+        self.client = Some(name.into());
+        self
+    }
+
+    /// Rows whose home-currency amount is within `min..=max`; spending is
+    /// negative.
+    pub fn amount_between(mut self, min: f64, max: f64) -> Self {
+        self.min_amount = Some(min);
+        self.max_amount = Some(max);
+        self
+    }
+
+    /// Only flagged rows (`true`) or only unflagged ones (`false`).
+    pub fn flagged(mut self, flagged: bool) -> Self {
+        self.flagged = Some(flagged);
+        self
+    }
+
+    /// Filters applied to each row after the query.
+    fn keeps(&self, row: &RegisterRow) -> bool {
+        if self.uncategorized && row.category.is_some() {
+            return false;
+        }
+        if let Some(text) = &self.vendor {
+            let payee = row.vendor.as_deref().unwrap_or(&row.description);
+            if !payee.to_lowercase().contains(text) {
+                return false;
+            }
+        }
+        if let Some(client) = &self.client {
+            if !row
+                .client
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(client))
+            {
+                return false;
+            }
+        }
+        if self.min_amount.is_some_and(|min| row.amount < min)
+            || self.max_amount.is_some_and(|max| row.amount > max)
+        {
+            return false;
+        }
+        self.flagged.is_none_or(|f| row.is_flagged == f)
+    }
+}
+
+/// Parameters for the P&L and the expense breakdown.
+#[derive(Debug, Clone, Default)]
+pub struct PnlParams {
+    pub period: Period,
+    /// Roll sub-categories up to this level (1 = top-level categories).
+    pub depth: Option<usize>,
+    /// Leave out transactions with any of these tags.
+    pub exclude_tags: Vec<String>,
+}
+
+impl PnlParams {
+    pub fn new(period: Period) -> Self {
+        Self {
+            period,
+            ..Self::default()
+        }
+    }
+
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.exclude_tags.push(tag.into());
+        self
+    }
+}
+
+/// Parameters for the cash flow report.
+#[derive(Debug, Clone, Default)]
+pub struct CashflowParams {
+    pub period: Period,
+    /// Leave out transactions with any of these tags.
+    pub exclude_tags: Vec<String>,
+}
+
+impl CashflowParams {
+    pub fn new(period: Period) -> Self {
+        Self {
+            period,
+            ..Self::default()
+        }
+    }
+
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.exclude_tags.push(tag.into());
+        self
+    }
+}
+
+/// A book opened read-only. Archived years are read along with the rest,
+/// just as in the CLI's reports.
+pub struct Book {
+    conn: Connection,
+}
+
+impl Book {
+    /// The book `nigel` is pointed at, per settings.json.
+    pub fn open_default() -> Result<Self> {
+        Self::open(crate::settings::get_data_dir())
+    }
+
+    /// The book in a data directory (the folder holding `nigel.db`).
+    pub fn open(data_dir: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with(data_dir.as_ref(), None)
+    }
+
+    /// An encrypted book.
+    pub fn open_with_password(data_dir: impl AsRef<Path>, password: &str) -> Result<Self> {
+        Self::open_with(data_dir.as_ref(), Some(password))
+    }
+
+    fn open_with(data_dir: &Path, password: Option<&str>) -> Result<Self> {
+        let db_path = data_dir.join("nigel.db");
+        if !db_path.exists() {
+            return Err(NigelError::Other(format!(
+                "No book at {} (nigel.db not found)",
+                data_dir.display()
+            )));
+        }
+        let conn = open_read_only(&db_path, password)?;
+        // Fails here, not on the first query, when the password is wrong
+        conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+        Ok(Self { conn })
+    }
+
+    /// The underlying connection, for anything this API doesn't cover. It
+    /// is read-only; `nigel db schema` describes the tables.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn company_name(&self) -> Option<String> {
+        get_metadata(&self.conn, "company_name")
+    }
+
+    pub fn home_currency(&self) -> String {
+        crate::currency::home_currency(&self.conn)
+    }
+
+    pub fn fiscal_year(&self) -> FiscalYear {
+        FiscalYear::load(&self.conn)
+    }
+
+    pub fn accounts(&self) -> Result<Vec<Account>> {
+        crate::cli::accounts::list_accounts(&self.conn)
+    }
+
+    /// Register rows matching `filter`, oldest first.
+    pub fn register(&self, filter: &RegisterFilter) -> Result<Vec<RegisterRow>> {
+        let (year, month, from_date, to_date) = filter.period.args()?;
+        let category_id = match &filter.category {
+            Some(name) => Some(
+                self.conn
+                    .query_row("SELECT id FROM categories WHERE name = ?1", [name], |r| {
+                        r.get(0)
+                    })
+                    .optional()?
+                    .ok_or_else(|| NigelError::UnknownCategory(name.clone()))?,
+            ),
+            None => None,
+        };
+        let query = RegisterQuery {
+            year,
+            month,
+            from_date,
+            to_date,
+            account: filter.account.clone(),
+            tag: filter.tag.clone(),
+            category_id,
+            ..Default::default()
+        };
+        reports::register_rows_where(&self.conn, &query, reports::REGISTER_ORDER, |row| {
+            filter.keeps(row)
+        })
+    }
+
+    pub fn pnl(&self, params: &PnlParams) -> Result<PnlReport> {
+        let (year, month, from, to) = params.period.args()?;
+        reports::get_pnl(
+            &self.conn,
+            year,
+            month,
+            from.as_deref(),
+            to.as_deref(),
+            params.depth,
+            &params.exclude_tags,
+        )
+    }
+
+    pub fn expenses(&self, params: &PnlParams) -> Result<ExpenseBreakdown> {
+        let (year, month) = params.period.year_month("expense breakdown")?;
+        reports::get_expense_breakdown(&self.conn, year, month, params.depth, &params.exclude_tags)
+    }
+
+    pub fn cashflow(&self, params: &CashflowParams) -> Result<CashflowReport> {
+        let (year, month) = params.period.year_month("cash flow")?;
+        reports::get_cashflow(&self.conn, year, month, &params.exclude_tags)
+    }
+
+    /// Totals by tax line for a fiscal year.
+    pub fn tax_summary(&self, fiscal_year: i32) -> Result<TaxSummary> {
+        reports::get_tax_summary(&self.conn, Some(fiscal_year))
+    }
+
+    /// The K-1 prep worksheet for a fiscal year.
+    pub fn k1_prep(&self, fiscal_year: i32) -> Result<K1PrepReport> {
+        reports::get_k1_prep(&self.conn, Some(fiscal_year))
+    }
+
+    /// Account balances as of today; closed accounts only with
+    /// `include_archived`.
+    pub fn balances(&self, include_archived: bool) -> Result<BalanceReport> {
+        reports::get_balance(&self.conn, include_archived)
+    }
+
+    /// Income and spending per tag.
+    pub fn tags(&self, period: Period) -> Result<Vec<TagItem>> {
+        let (year, month) = period.year_month("tags report")?;
+        reports::get_tags_report(&self.conn, year, month)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    /// A book with a checking account and four transactions in March 2025.
+    fn test_book() -> (tempfile::TempDir, Book) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("nigel.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        for (date, description, vendor, amount, category, flagged) in [
+            (
+                "2025-03-02",
+                "SQ *BLUE BOTTLE",
+                Some("Blue Bottle"),
+                -12.5,
+                Some("Meals"),
+                0,
+            ),
+            ("2025-03-09", "DOORDASH 1234", None, -48.0, Some("Meals"), 1),
+            (
+                "2025-03-15",
+                "STRIPE PAYOUT",
+                None,
+                2500.0,
+                Some("Client Services"),
+                0,
+            ),
+            ("2025-03-20", "MYSTERY CHARGE", None, -7.0, None, 1),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, vendor, amount, category_id, is_flagged) \
+                 VALUES (1, ?1, ?2, ?3, ?4, (SELECT id FROM categories WHERE name = ?5), ?6)",
+                rusqlite::params![date, description, vendor, amount, category, flagged],
+            )
+            .unwrap();
+        }
+        drop(conn);
+        let book = Book::open(dir.path()).unwrap();
+        (dir, book)
+    }
+
+    fn descriptions(rows: &[RegisterRow]) -> Vec<&str> {
+        rows.iter().map(|r| r.description.as_str()).collect()
+    }
+
+    #[test]
+    fn register_filters_combine() {
+        let (_dir, book) = test_book();
+        let march = RegisterFilter::new().period(Period::Month {
+            year: 2025,
+            month: 3,
+        });
+        assert_eq!(book.register(&march).unwrap().len(), 4);
+
+        let meals = book.register(&march.clone().category("Meals")).unwrap();
+        assert_eq!(descriptions(&meals), ["SQ *BLUE BOTTLE", "DOORDASH 1234"]);
+        // Vendor matches fall back to the description
+        let rows = book.register(&march.clone().vendor("doordash")).unwrap();
+        assert_eq!(descriptions(&rows), ["DOORDASH 1234"]);
+        let rows = book.register(&march.clone().vendor("blue")).unwrap();
+        assert_eq!(descriptions(&rows), ["SQ *BLUE BOTTLE"]);
+
+        let rows = book
+            .register(&march.clone().flagged(true).amount_between(-20.0, 0.0))
+            .unwrap();
+        assert_eq!(descriptions(&rows), ["MYSTERY CHARGE"]);
+        let rows = book.register(&march.clone().uncategorized()).unwrap();
+        assert_eq!(descriptions(&rows), ["MYSTERY CHARGE"]);
+
+        let april = RegisterFilter::new().period(Period::Range {
+            from: NaiveDate::from_ymd_opt(2025, 4, 1).unwrap(),
+            to: NaiveDate::from_ymd_opt(2025, 4, 30).unwrap(),
+        });
+        assert!(book.register(&april).unwrap().is_empty());
+
+        let err = book.register(&march.category("Snacks")).err().unwrap();
+        assert_eq!(err.to_string(), "Unknown category: Snacks");
+    }
+
+    #[test]
+    fn report_params_reach_the_reports() {
+        let (_dir, book) = test_book();
+        let pnl = book
+            .pnl(&PnlParams::new(Period::Month {
+                year: 2025,
+                month: 3,
+            }))
+            .unwrap();
+        assert_eq!(pnl.total_income, 2500.0);
+        // Uncategorized rows stay out of the P&L
+        assert_eq!(pnl.net, 2500.0 - 12.5 - 48.0);
+
+        let range = Period::Range {
+            from: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            to: NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+        };
+        assert_eq!(
+            book.pnl(&PnlParams::new(range)).unwrap().total_income,
+            2500.0
+        );
+        let err = book.cashflow(&CashflowParams::new(range)).err().unwrap();
+        assert!(err.to_string().contains("not a date range"));
+
+        let bad = Period::Month {
+            year: 2025,
+            month: 13,
+        };
+        assert!(book.pnl(&PnlParams::new(bad)).is_err());
+    }
+
+    #[test]
+    fn book_is_read_only() {
+        let (_dir, book) = test_book();
+        assert!(book
+            .connection()
+            .execute("DELETE FROM transactions", [])
+            .is_err());
+        assert!(Book::open(_dir.path().join("missing")).is_err());
+    }
+}
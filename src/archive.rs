@@ -0,0 +1,623 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+
+#[derive(Debug)]
+pub struct ArchiveInfo {
+    pub year: i32,
+    pub filename: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub transaction_count: i64,
+    pub archived_at: String,
+}
+
+/// Directory holding the per-year archives of the database at `db_path`.
+pub fn archives_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("archives")
+}
+
+/// Archive files next to the database at `db_path`, oldest year first.
+pub fn archive_files(db_path: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(archives_dir(db_path))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "db"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn db_path(conn: &Connection) -> Result<PathBuf> {
+    conn.path()
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| NigelError::Other("Archives need a file-backed database".into()))
+}
+
+fn schema_name(year: i32) -> String {
+    format!("archive_{year}")
+}
+
+fn remove_db_file(path: &Path) {
+    let _ = std::fs::remove_file(path);
+    let _ = std::fs::remove_file(path.with_extension("db-wal"));
+    let _ = std::fs::remove_file(path.with_extension("db-shm"));
+}
+
+fn columns(conn: &Connection, schema: &str, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA {schema}.table_info({table})"))?;
+    let cols = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(cols)
+}
+
+/// `transactions` columns the hot database and `schema` have in common, so
+/// an archive written by an older schema version can still be copied back.
+fn shared_columns(conn: &Connection, schema: &str) -> Result<String> {
+    let theirs = columns(conn, schema, "transactions")?;
+    Ok(columns(conn, "main", "transactions")?
+        .into_iter()
+        .filter(|c| theirs.contains(c))
+        .collect::<Vec<_>>()
+        .join(", "))
+}
+
+fn attached_archives(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("PRAGMA database_list")?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(names
+        .into_iter()
+        .filter(|n| n.starts_with("archive_"))
+        .collect())
+}
+
+/// Attach `path` as `archive`, run `f`, and detach again whatever `f` returns.
+/// An attached file shares the main database's key, so a new archive is
+/// encrypted exactly like the hot database.
+fn with_attached<T>(conn: &Connection, path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    conn.execute("ATTACH DATABASE ?1 AS archive", [path.to_string_lossy()])?;
+    let result = f();
+    conn.execute_batch("DETACH DATABASE archive")?;
+    result
+}
+
+pub fn list(conn: &Connection) -> Result<Vec<ArchiveInfo>> {
+    let mut stmt = conn.prepare(
+        "SELECT year, filename, start_date, end_date, transaction_count, archived_at \
+         FROM main.archives ORDER BY year",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(ArchiveInfo {
+                year: row.get(0)?,
+                filename: row.get(1)?,
+                start_date: row.get(2)?,
+                end_date: row.get(3)?,
+                transaction_count: row.get(4)?,
+                archived_at: row.get(5)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+fn find(conn: &Connection, year: i32) -> Result<Option<ArchiveInfo>> {
+    Ok(list(conn)?.into_iter().find(|a| a.year == year))
+}
+
+/// Check that fiscal year `year` can be archived: it is closed, not archived
+/// yet, and has nothing flagged or in the trash. Returns how many transactions
+/// would move.
+pub fn check_archivable(conn: &Connection, year: i32) -> Result<i64> {
+    let fiscal = FiscalYear::load(conn);
+    let label = fiscal.label(year);
+    if year >= fiscal.current() {
+        return Err(NigelError::Other(format!(
+            "{label} is still open; only past years can be archived."
+        )));
+    }
+    if find(conn, year)?.is_some() {
+        return Err(NigelError::Other(format!("{label} is already archived.")));
+    }
+    let (start, end) = fiscal.bounds(year);
+    let (start, end) = (start.to_string(), end.to_string());
+    let (count, flagged, trashed, last_id): (i64, i64, i64, Option<i64>) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(is_flagged), 0), \
+         COALESCE(SUM(deleted_at IS NOT NULL), 0), MAX(id) FROM main.transactions \
+         WHERE date BETWEEN ?1 AND ?2",
+        [&start, &end],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+    )?;
+    if count == 0 {
+        return Err(NigelError::Other(format!(
+            "No transactions in {label} to archive."
+        )));
+    }
+    if trashed > 0 {
+        return Err(NigelError::Other(format!(
+            "{label} has {trashed} transaction(s) in the trash. \
+             Restore them or run `nigel trash empty` before archiving."
+        )));
+    }
+    if flagged > 0 {
+        return Err(NigelError::Other(format!(
+            "{label} has {flagged} flagged transaction(s). Review them before archiving."
+        )));
+    }
+    let unreimbursed: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM main.reimbursables r \
+         JOIN main.transactions t ON t.id = r.transaction_id \
+         WHERE t.date BETWEEN ?1 AND ?2 AND r.reimbursed_on IS NULL",
+        [&start, &end],
+        |row| row.get(0),
+    )?;
+    if unreimbursed > 0 {
+        return Err(NigelError::Other(format!(
+            "{label} has {unreimbursed} reimbursable expense(s) not yet paid back. \
+             Record the reimbursements before archiving."
+        )));
+    }
+    // Rowids are reused from MAX(id) + 1, so moving the newest row out would
+    // let the next import take an archived transaction's ID.
+    let newest: Option<i64> =
+        conn.query_row("SELECT MAX(id) FROM main.transactions", [], |r| r.get(0))?;
+    if newest == last_id {
+        return Err(NigelError::Other(format!(
+            "The most recently added transaction is dated in {label}. \
+             Archive it once a later transaction has been imported."
+        )));
+    }
+    Ok(count)
+}
+
+/// Move fiscal year `year`'s transactions and their tags out of the hot
+/// database into `archives/nigel-<year>.db`.
+pub fn archive_year(conn: &Connection, year: i32) -> Result<ArchiveInfo> {
+    check_archivable(conn, year)?;
+    let fiscal = FiscalYear::load(conn);
+    let label = fiscal.label(year);
+    let (start, end) = fiscal.bounds(year);
+    let (start, end) = (start.to_string(), end.to_string());
+
+    let dir = archives_dir(&db_path(conn)?);
+    let filename = format!("nigel-{year}.db");
+    let path = dir.join(&filename);
+    if path.exists() {
+        return Err(NigelError::Other(format!(
+            "{} already exists. Move it aside before archiving {label}.",
+            path.display()
+        )));
+    }
+    std::fs::create_dir_all(&dir)?;
+    crate::settings::restrict_dir_permissions(&dir)?;
+
+    // The archive has no accounts or categories of its own, so its foreign
+    // keys can't be enforced while copying.
+    conn.execute_batch("PRAGMA foreign_keys=OFF")?;
+    let moved = with_attached(conn, &path, || {
+        let tx = conn.unchecked_transaction()?;
+        // Reuse the hot database's own DDL so migrated columns come along
+        for table in ["transactions", "tags", "transaction_tags"] {
+            let sql: String = tx.query_row(
+                "SELECT sql FROM main.sqlite_master WHERE type = 'table' AND name = ?1",
+                [table],
+                |r| r.get(0),
+            )?;
+            tx.execute_batch(&sql.replacen(table, &format!("archive.{table}"), 1))?;
+        }
+        let cols = shared_columns(conn, "archive")?;
+        tx.execute(
+            &format!(
+                "INSERT INTO archive.transactions ({cols}) \
+                 SELECT {cols} FROM main.transactions WHERE date BETWEEN ?1 AND ?2"
+            ),
+            [&start, &end],
+        )?;
+        tx.execute(
+            "INSERT OR IGNORE INTO archive.tags (name) \
+             SELECT DISTINCT g.name FROM main.tags g \
+             JOIN main.transaction_tags tt ON tt.tag_id = g.id \
+             JOIN main.transactions t ON t.id = tt.transaction_id \
+             WHERE t.date BETWEEN ?1 AND ?2",
+            [&start, &end],
+        )?;
+        tx.execute(
+            "INSERT INTO archive.transaction_tags (transaction_id, tag_id) \
+             SELECT tt.transaction_id, ag.id FROM main.transaction_tags tt \
+             JOIN main.tags g ON g.id = tt.tag_id \
+             JOIN archive.tags ag ON ag.name = g.name \
+             JOIN main.transactions t ON t.id = tt.transaction_id \
+             WHERE t.date BETWEEN ?1 AND ?2",
+            [&start, &end],
+        )?;
+        tx.execute(
+            "DELETE FROM main.transaction_tags WHERE transaction_id IN \
+             (SELECT id FROM main.transactions WHERE date BETWEEN ?1 AND ?2)",
+            [&start, &end],
+        )?;
+        let moved = tx.execute(
+            "DELETE FROM main.transactions WHERE date BETWEEN ?1 AND ?2",
+            [&start, &end],
+        )?;
+        tx.execute(
+            "DELETE FROM main.tags WHERE id NOT IN (SELECT tag_id FROM main.transaction_tags)",
+            [],
+        )?;
+        tx.execute(
+            "INSERT INTO main.archives (year, filename, start_date, end_date, transaction_count) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![year, filename, start, end, moved as i64],
+        )?;
+        tx.commit()?;
+        Ok(moved)
+    });
+    conn.execute_batch("PRAGMA foreign_keys=ON")?;
+    if let Err(e) = moved {
+        remove_db_file(&path);
+        return Err(e);
+    }
+    crate::settings::restrict_file_permissions(&path)?;
+    // Hand the freed pages back so the hot database actually shrinks
+    conn.execute_batch("VACUUM")?;
+    find(conn, year)?.ok_or_else(|| NigelError::Other(format!("{label} archive was not recorded")))
+}
+
+/// Move an archived year back into the hot database and delete its archive.
+/// Returns the number of transactions restored.
+pub fn restore_year(conn: &Connection, year: i32) -> Result<usize> {
+    let label = FiscalYear::load(conn).label(year);
+    let info =
+        find(conn, year)?.ok_or_else(|| NigelError::Other(format!("{label} is not archived.")))?;
+    let path = archives_dir(&db_path(conn)?).join(&info.filename);
+    if !path.exists() {
+        return Err(NigelError::Other(format!(
+            "Archive for {label} is missing: {}",
+            path.display()
+        )));
+    }
+    let restored = with_attached(conn, &path, || {
+        let clashes: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM archive.transactions WHERE id IN (SELECT id FROM main.transactions)",
+            [],
+            |r| r.get(0),
+        )?;
+        if clashes > 0 {
+            return Err(NigelError::Other(format!(
+                "{clashes} archived transaction ID(s) from {label} are already in use; \
+                 the archive can't be restored."
+            )));
+        }
+        let tx = conn.unchecked_transaction()?;
+        let cols = shared_columns(conn, "archive")?;
+        let restored = tx.execute(
+            &format!(
+                "INSERT INTO main.transactions ({cols}) SELECT {cols} FROM archive.transactions"
+            ),
+            [],
+        )?;
+        tx.execute(
+            "INSERT OR IGNORE INTO main.tags (name) SELECT name FROM archive.tags",
+            [],
+        )?;
+        tx.execute(
+            "INSERT INTO main.transaction_tags (transaction_id, tag_id) \
+             SELECT tt.transaction_id, g.id FROM archive.transaction_tags tt \
+             JOIN archive.tags ag ON ag.id = tt.tag_id \
+             JOIN main.tags g ON g.name = ag.name",
+            [],
+        )?;
+        tx.execute("DELETE FROM main.archives WHERE year = ?1", [year])?;
+        tx.commit()?;
+        Ok(restored)
+    })?;
+    remove_db_file(&path);
+    Ok(restored)
+}
+
+/// Whether transaction `id`, as listed by a report, lives in an archive
+/// rather than the hot database. Archived rows are read-only until their
+/// year is restored.
+pub fn is_archived(conn: &Connection, id: i64) -> Result<bool> {
+    let hot: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM main.transactions WHERE id = ?1)",
+        [id],
+        |r| r.get(0),
+    )?;
+    Ok(!hot)
+}
+
+/// Error for edits to an archived transaction, which would otherwise update
+/// nothing.
+pub fn ensure_not_archived(conn: &Connection, id: i64) -> Result<()> {
+    if is_archived(conn, id)? {
+        return Err(NigelError::Other(format!(
+            "Transaction #{id} is archived. Restore its year with `nigel archive restore` to edit it."
+        )));
+    }
+    Ok(())
+}
+
+/// Archives covering part of a report's period, attached for as long as the
+/// scope lives. While any archive is attached, `transactions`, `tags` and
+/// `transaction_tags` resolve to TEMP views over the hot database plus the
+/// archives, so report SQL reads archived years unchanged. Tags are keyed by
+/// name in those views, since each file numbers its own tags.
+pub struct ArchiveScope<'a> {
+    conn: &'a Connection,
+    attached: Vec<String>,
+}
+
+/// Attach the archives overlapping `from..=to` (either end open). Scopes
+/// nest: an archive an outer scope already attached is left to that scope.
+pub fn scope<'a>(
+    conn: &'a Connection,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<ArchiveScope<'a>> {
+    let mut scope = ArchiveScope {
+        conn,
+        attached: Vec::new(),
+    };
+    let wanted: Vec<(i32, String)> = {
+        let mut stmt = conn.prepare(
+            "SELECT year, filename FROM main.archives \
+             WHERE (?1 IS NULL OR end_date >= ?1) AND (?2 IS NULL OR start_date <= ?2) \
+             ORDER BY year",
+        )?;
+        let rows = stmt
+            .query_map(rusqlite::params![from, to], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        rows
+    };
+    if wanted.is_empty() {
+        return Ok(scope);
+    }
+    let dir = archives_dir(&db_path(conn)?);
+    let present = attached_archives(conn)?;
+    for (year, filename) in wanted {
+        let schema = schema_name(year);
+        if present.contains(&schema) {
+            continue;
+        }
+        let path = dir.join(&filename);
+        if !path.exists() {
+            return Err(NigelError::Other(format!(
+                "Archive for {} is missing: {}",
+                FiscalYear::load(conn).label(year),
+                path.display()
+            )));
+        }
+        conn.execute(
+            &format!("ATTACH DATABASE ?1 AS {schema}"),
+            [path.to_string_lossy()],
+        )?;
+        scope.attached.push(schema);
+    }
+    if !scope.attached.is_empty() {
+        rebuild_views(conn)?;
+    }
+    Ok(scope)
+}
+
+/// Recreate the TEMP views over every attached archive, or drop them when
+/// none is left.
+fn rebuild_views(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "DROP VIEW IF EXISTS temp.transactions;
+         DROP VIEW IF EXISTS temp.transaction_tags;
+         DROP VIEW IF EXISTS temp.tags;",
+    )?;
+    let schemas = attached_archives(conn)?;
+    if schemas.is_empty() {
+        return Ok(());
+    }
+    let cols = columns(conn, "main", "transactions")?;
+    let mut transactions = vec![format!("SELECT {} FROM main.transactions", cols.join(", "))];
+    let mut tags = vec!["SELECT name AS id, name FROM main.tags".to_string()];
+    let mut links = vec!["SELECT tt.transaction_id, g.name AS tag_id \
+         FROM main.transaction_tags tt JOIN main.tags g ON g.id = tt.tag_id"
+        .to_string()];
+    for schema in &schemas {
+        // An archive written before a later migration lacks the new columns
+        let have = columns(conn, schema, "transactions")?;
+        let select = cols
+            .iter()
+            .map(|c| {
+                if have.contains(c) {
+                    c.clone()
+                } else {
+                    format!("NULL AS {c}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        transactions.push(format!("SELECT {select} FROM {schema}.transactions"));
+        tags.push(format!("SELECT name, name FROM {schema}.tags"));
+        links.push(format!(
+            "SELECT tt.transaction_id, g.name FROM {schema}.transaction_tags tt \
+             JOIN {schema}.tags g ON g.id = tt.tag_id"
+        ));
+    }
+    conn.execute_batch(&format!(
+        "CREATE TEMP VIEW transactions AS {};
+         CREATE TEMP VIEW tags AS {};
+         CREATE TEMP VIEW transaction_tags AS {};",
+        transactions.join(" UNION ALL "),
+        tags.join(" UNION "),
+        links.join(" UNION ALL "),
+    ))?;
+    Ok(())
+}
+
+impl Drop for ArchiveScope<'_> {
+    fn drop(&mut self) {
+        if self.attached.is_empty() {
+            return;
+        }
+        let _ = self.conn.execute_batch(
+            "DROP VIEW IF EXISTS temp.transactions;
+             DROP VIEW IF EXISTS temp.transaction_tags;
+             DROP VIEW IF EXISTS temp.tags;",
+        );
+        for schema in &self.attached {
+            let _ = self
+                .conn
+                .execute_batch(&format!("DETACH DATABASE {schema}"));
+        }
+        let _ = rebuild_views(self.conn);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{init_db, is_encrypted, open_connection};
+    use crate::reports::{get_pnl, get_register};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        test_db_with_password(None)
+    }
+
+    fn test_db_with_password(password: Option<&str>) -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = open_connection(&dir.path().join("nigel.db"), password).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        let income: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Client Services'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        let this_year = FiscalYear::load(&conn).current();
+        for (date, amount) in [
+            ("2020-03-01", 500.0),
+            ("2020-06-01", 700.0),
+            ("2021-02-01", 300.0),
+            (&format!("{this_year}-01-05"), 100.0),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+                 VALUES (1, ?1, 'CLIENT', ?2, ?3)",
+                rusqlite::params![date, amount, income],
+            )
+            .unwrap();
+        }
+        crate::tags::set_transaction_tags(&conn, 1, &["client-acme".to_string()]).unwrap();
+        (dir, conn)
+    }
+
+    fn hot_count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM transactions", [], |r| r.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_archive_then_reports_read_through_and_restore() {
+        let (dir, conn) = test_db();
+        let info = archive_year(&conn, 2020).unwrap();
+        assert_eq!(info.transaction_count, 2);
+        assert!(dir.path().join("archives/nigel-2020.db").exists());
+        assert_eq!(hot_count(&conn), 2);
+        assert!(is_archived(&conn, 1).unwrap());
+        assert!(!is_archived(&conn, 3).unwrap());
+
+        // Reports over an archived year attach it transparently
+        let pnl = get_pnl(&conn, Some(2020), None, None, None, None, &[]).unwrap();
+        assert!((pnl.total_income - 1200.0).abs() < 0.01);
+        let register = get_register(&conn, None, None, None, None, None, None).unwrap();
+        assert_eq!(register.rows.len(), 4);
+        assert_eq!(register.rows[0].tags, vec!["client-acme"]);
+        let tagged =
+            get_register(&conn, None, None, None, None, None, Some("client-acme")).unwrap();
+        assert_eq!(tagged.rows.len(), 1);
+
+        // ...and detach afterwards, leaving only hot rows visible
+        assert_eq!(hot_count(&conn), 2);
+        assert!(attached_archives(&conn).unwrap().is_empty());
+
+        assert_eq!(restore_year(&conn, 2020).unwrap(), 2);
+        assert_eq!(hot_count(&conn), 4);
+        assert!(list(&conn).unwrap().is_empty());
+        assert!(!dir.path().join("archives/nigel-2020.db").exists());
+        assert_eq!(
+            crate::reviewer::get_transaction_by_id(&conn, 1)
+                .unwrap()
+                .tags,
+            vec!["client-acme"]
+        );
+    }
+
+    #[test]
+    fn test_nested_scopes_keep_outer_archives() {
+        let (_dir, conn) = test_db();
+        archive_year(&conn, 2020).unwrap();
+        archive_year(&conn, 2021).unwrap();
+        let outer = scope(&conn, None, None).unwrap();
+        assert_eq!(hot_count(&conn), 4);
+        {
+            let _inner = scope(&conn, Some("2020-01-01"), Some("2020-12-31")).unwrap();
+            assert_eq!(hot_count(&conn), 4);
+        }
+        assert_eq!(hot_count(&conn), 4);
+        drop(outer);
+        assert_eq!(hot_count(&conn), 1);
+    }
+
+    #[test]
+    fn test_archive_of_encrypted_book_is_encrypted() {
+        let (dir, conn) = test_db_with_password(Some("secret"));
+        archive_year(&conn, 2020).unwrap();
+        assert!(is_encrypted(&dir.path().join("archives/nigel-2020.db")).unwrap());
+        let register = get_register(&conn, Some(2020), None, None, None, None, None).unwrap();
+        assert_eq!(register.rows.len(), 2);
+    }
+
+    #[test]
+    fn test_archive_refusals() {
+        let (_dir, conn) = test_db();
+        let this_year = FiscalYear::load(&conn).current();
+        assert!(archive_year(&conn, this_year).is_err());
+        assert!(archive_year(&conn, 2019).is_err());
+
+        conn.execute("UPDATE transactions SET is_flagged = 1 WHERE id = 3", [])
+            .unwrap();
+        let err = archive_year(&conn, 2021).unwrap_err().to_string();
+        assert!(err.contains("flagged"), "{err}");
+        conn.execute("UPDATE transactions SET is_flagged = 0 WHERE id = 3", [])
+            .unwrap();
+        conn.execute(
+            "INSERT INTO reimbursables (transaction_id, client) VALUES (3, 'acme')",
+            [],
+        )
+        .unwrap();
+        let err = archive_year(&conn, 2021).unwrap_err().to_string();
+        assert!(err.contains("not yet paid back"), "{err}");
+
+        archive_year(&conn, 2020).unwrap();
+        let err = archive_year(&conn, 2020).unwrap_err().to_string();
+        assert!(err.contains("already archived"), "{err}");
+        assert!(restore_year(&conn, 2021).is_err());
+    }
+}
@@ -0,0 +1,14 @@
+use rusqlite::Connection;
+
+use crate::error::Result;
+
+/// Note a change that rewrites existing records (re-pointed transactions,
+/// merged categories) in `audit_log`, so it can be traced later with
+/// `nigel query "SELECT * FROM audit_log"`.
+pub fn record(conn: &Connection, action: &str, detail: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO audit_log (action, detail) VALUES (?1, ?2)",
+        [action, detail],
+    )?;
+    Ok(())
+}
@@ -1,21 +1,119 @@
+use std::cmp::Ordering;
 use std::io;
+use std::path::{Path, PathBuf};
 
 use chrono::Local;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Layout},
     style::{Color, Style},
-    text::{Line, Span},
+    text::{Line, Span, Text},
     widgets::{Cell, Paragraph, Row, Table, TableState},
     DefaultTerminal, Frame,
 };
 
-use crate::fmt::money;
-use crate::reports::RegisterRow;
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
+use crate::filter::RowFilter;
+use crate::fmt::{self, money, money_in};
+use crate::reports::{self, RegisterQuery, RegisterRow};
 use crate::reviewer::CategoryChoice;
+use crate::tabular::{self, TabularFormat};
 use crate::tui::{self, FOOTER_STYLE, HEADER_STYLE, SELECTED_STYLE};
 
 const PAGE_SIZE: usize = 20;
+/// Registers with more rows than this are paged from the database instead
+/// of loaded whole.
+const PAGED_THRESHOLD: usize = 5_000;
+/// Rows held in memory around the visible ones while paging.
+const WINDOW_SIZE: usize = 1_000;
+
+/// Optional register columns; flag, ID, date, description, and amount always show.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Native,
+    Category,
+    Vendor,
+    Account,
+}
+
+impl Column {
+    const ALL: [Column; 4] = [
+        Column::Native,
+        Column::Category,
+        Column::Vendor,
+        Column::Account,
+    ];
+
+    fn header(self) -> &'static str {
+        match self {
+            Column::Native => "Native",
+            Column::Category => "Category",
+            Column::Vendor => "Vendor",
+            Column::Account => "Account",
+        }
+    }
+
+    fn width(self) -> u16 {
+        match self {
+            Column::Native => 16,
+            Column::Category => 28,
+            Column::Vendor | Column::Account => 20,
+        }
+    }
+
+    fn from_key(c: char) -> Option<Self> {
+        match c {
+            'n' => Some(Column::Native),
+            'c' => Some(Column::Category),
+            'v' => Some(Column::Vendor),
+            'a' => Some(Column::Account),
+            _ => None,
+        }
+    }
+}
+
+/// Column the loaded rows are ordered by; `s` cycles through these in order.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortKey {
+    #[default]
+    Date,
+    Amount,
+    Category,
+    Vendor,
+    Account,
+}
+
+impl SortKey {
+    fn next(self) -> Self {
+        match self {
+            SortKey::Date => SortKey::Amount,
+            SortKey::Amount => SortKey::Category,
+            SortKey::Category => SortKey::Vendor,
+            SortKey::Vendor => SortKey::Account,
+            SortKey::Account => SortKey::Date,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SortKey::Date => "date",
+            SortKey::Amount => "amount",
+            SortKey::Category => "category",
+            SortKey::Vendor => "vendor",
+            SortKey::Account => "account",
+        }
+    }
+}
+
+/// Sort order and column choices. The dashboard keeps the last one so the
+/// browser reopens the way it was left for the rest of the session.
+#[derive(Debug, Clone, Default)]
+pub struct BrowseLayout {
+    pub sort: SortKey,
+    pub descending: bool,
+    /// Show/hide per `Column::ALL` entry; None follows the terminal width.
+    columns: [Option<bool>; 4],
+}
 
 enum BrowseMode {
     Normal,
@@ -23,8 +121,31 @@ enum BrowseMode {
     GotoDate(String),
     FindId(String),
     Search(String),
-    EditCategory { query: String, selection: usize },
+    EditCategory {
+        query: String,
+        selection: usize,
+    },
     EditVendor(String),
+    EditMemo(String),
+    /// Space- or comma-separated tags; replaces the row's tags on Enter.
+    EditTags(String),
+    /// Client name; blank unassigns the row.
+    EditClient(String),
+    /// Pattern for a new `contains` rule filing matches like the selected row.
+    CreateRule(String),
+    /// Waiting for c/x to pick the export format.
+    ExportFormat,
+    /// Toggling optional columns with n/c/v/a until Esc.
+    Columns,
+    /// Typing a filter expression; rows narrow as it parses. `previous` is
+    /// restored on Esc.
+    Filter {
+        input: String,
+        error: Option<String>,
+        previous: String,
+    },
+    /// Waiting for y to move the selected transaction to the trash.
+    ConfirmTrash,
 }
 
 pub enum BrowseAction {
@@ -32,6 +153,37 @@ pub enum BrowseAction {
     Close,
     CommitEdit,
     ToggleFlag,
+    CreateRule,
+    /// Move the selected transaction to the trash.
+    Trash,
+    /// A paged register needs `fetch()` before the next frame.
+    Fetch,
+}
+
+/// Where a paged register is: `rows` holds a window of it starting at
+/// `start`, and `offset`/`selected` stay positions in the whole register.
+struct Pager {
+    query: RegisterQuery,
+    len: usize,
+    total: f64,
+    start: usize,
+    window: usize,
+    /// Work that needs the database, run by the next `fetch()`.
+    pending: Vec<Fetch>,
+    /// Transaction to keep selected through a queued re-sort.
+    anchor: Option<i64>,
+}
+
+enum Fetch {
+    Date(String),
+    Id(i64),
+    Today,
+    Resort,
+    Search(String),
+    /// Filter the whole register into memory; an empty expression goes back
+    /// to paging.
+    Filter(String),
+    Export(TabularFormat),
 }
 
 pub struct RegisterBrowser {
@@ -47,10 +199,29 @@ pub struct RegisterBrowser {
     cat_labels: Vec<String>,
     pending_category_idx: Option<usize>,
     pending_vendor: Option<String>,
+    /// Memo text to save on the next commit; empty clears it.
+    pending_memo: Option<String>,
+    /// Tags to save on the next commit, already normalized.
+    pending_tags: Option<Vec<String>>,
+    /// Client name to save on the next commit; blank unassigns.
+    pending_client: Option<String>,
+    /// Pattern typed at the `R` prompt, saved by `create_rule()`.
+    pending_rule: Option<String>,
+    /// Show memos in full instead of truncated to one line.
+    expand_memos: bool,
     table_state: TableState,
     search_matches: Vec<usize>,
     search_index: usize,
     search_query: String,
+    show_native: bool,
+    layout: BrowseLayout,
+    /// Width class of the last frame, so column toggles flip what's on screen.
+    narrow: bool,
+    /// All loaded rows and their net while `F` narrows `rows`; None when unfiltered.
+    unfiltered: Option<(Vec<RegisterRow>, f64)>,
+    filter_expr: String,
+    /// Set for a register too large to load whole.
+    pager: Option<Box<Pager>>,
 }
 
 impl RegisterBrowser {
@@ -63,14 +234,15 @@ impl RegisterBrowser {
         let cat_labels: Vec<String> = categories
             .iter()
             .map(|c| {
-                let tag = if c.category_type == "income" {
-                    "inc"
-                } else {
-                    "exp"
+                let tag = match c.category_type.as_str() {
+                    "income" => "inc",
+                    "transfer" => "xfer",
+                    _ => "exp",
                 };
                 format!("{} ({})", c.name, tag)
             })
             .collect();
+        let show_native = rows.iter().any(|r| r.foreign.is_some());
         Self {
             rows,
             total,
@@ -84,24 +256,385 @@ impl RegisterBrowser {
             cat_labels,
             pending_category_idx: None,
             pending_vendor: None,
+            pending_memo: None,
+            pending_tags: None,
+            pending_client: None,
+            pending_rule: None,
+            expand_memos: false,
             table_state: TableState::default(),
             search_matches: Vec::new(),
             search_index: 0,
             search_query: String::new(),
+            show_native,
+            layout: BrowseLayout::default(),
+            narrow: false,
+            unfiltered: None,
+            filter_expr: String::new(),
+            pager: None,
+        }
+    }
+
+    /// Open the register `query` selects, loading it whole when it's small
+    /// and paging it from the database past `PAGED_THRESHOLD` rows.
+    pub fn open(
+        conn: &rusqlite::Connection,
+        query: RegisterQuery,
+        filters_desc: String,
+        categories: Vec<CategoryChoice>,
+    ) -> crate::error::Result<Self> {
+        let summary = reports::register_summary(conn, &query)?;
+        if summary.count <= PAGED_THRESHOLD {
+            let rows =
+                reports::register_rows_where(conn, &query, reports::REGISTER_ORDER, |_| true)?;
+            return Ok(Self::new(rows, summary.total, filters_desc, categories));
+        }
+        Self::paged(conn, query, filters_desc, categories, WINDOW_SIZE)
+    }
+
+    fn paged(
+        conn: &rusqlite::Connection,
+        query: RegisterQuery,
+        filters_desc: String,
+        categories: Vec<CategoryChoice>,
+        window: usize,
+    ) -> crate::error::Result<Self> {
+        let summary = reports::register_summary(conn, &query)?;
+        let mut browser = Self::new(Vec::new(), summary.total, filters_desc, categories);
+        browser.show_native = summary.has_foreign;
+        browser.pager = Some(Box::new(Pager {
+            query,
+            len: summary.count,
+            total: summary.total,
+            start: 0,
+            window,
+            pending: Vec::new(),
+            anchor: None,
+        }));
+        browser.fetch(conn)?;
+        Ok(browser)
+    }
+
+    /// The pager, unless a filter has pulled its matches into memory.
+    fn paging(&self) -> Option<&Pager> {
+        self.pager
+            .as_deref()
+            .filter(|_| self.filter_expr.is_empty())
+    }
+
+    fn queue(&mut self, job: Fetch) {
+        if let Some(pager) = &mut self.pager {
+            pager.pending.push(job);
+        }
+    }
+
+    /// Rows in the register (or its filtered matches), loaded or not.
+    fn len(&self) -> usize {
+        self.paging().map_or(self.rows.len(), |p| p.len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn row(&self, idx: usize) -> Option<&RegisterRow> {
+        let start = self.paging().map_or(0, |p| p.start);
+        idx.checked_sub(start).and_then(|i| self.rows.get(i))
+    }
+
+    fn row_mut(&mut self, idx: usize) -> Option<&mut RegisterRow> {
+        let start = self.paging().map_or(0, |p| p.start);
+        idx.checked_sub(start).and_then(|i| self.rows.get_mut(i))
+    }
+
+    fn selected_row(&self) -> Option<&RegisterRow> {
+        self.row(self.offset + self.selected)
+    }
+
+    /// Whether `fetch()` has queued work or the loaded window no longer
+    /// covers the screen.
+    pub fn needs_fetch(&self) -> bool {
+        if self.pager.as_ref().is_some_and(|p| !p.pending.is_empty()) {
+            return true;
+        }
+        self.paging().is_some_and(|p| {
+            let end = (self.offset + self.visible_count * 2).min(p.len);
+            self.offset < p.start || end > p.start + self.rows.len()
+        })
+    }
+
+    /// Run queued jumps, sorts, searches, and filters against the database
+    /// and load the window around the screen. A no-op for a register loaded
+    /// whole.
+    pub fn fetch(&mut self, conn: &rusqlite::Connection) -> crate::error::Result<()> {
+        let Some(pager) = &mut self.pager else {
+            return Ok(());
+        };
+        for job in std::mem::take(&mut pager.pending) {
+            self.run_fetch(conn, job)?;
+        }
+        if self.needs_fetch() {
+            let order = order_sql(self.layout.sort, self.layout.descending);
+            let span = self.visible_count * 4;
+            if let Some(pager) = &mut self.pager {
+                let size = pager.window.max(span);
+                pager.start = self.offset.saturating_sub(size / 4);
+                self.rows = reports::register_page(conn, &pager.query, &order, pager.start, size)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn run_fetch(&mut self, conn: &rusqlite::Connection, job: Fetch) -> crate::error::Result<()> {
+        let order = order_sql(self.layout.sort, self.layout.descending);
+        let Some(query) = self.pager.as_ref().map(|p| p.query.clone()) else {
+            return Ok(());
+        };
+        match job {
+            Fetch::Date(target) => {
+                let on_or_before = self.layout.sort == SortKey::Date && self.layout.descending;
+                match reports::register_position_of_date(
+                    conn,
+                    &query,
+                    &order,
+                    &target,
+                    on_or_before,
+                )? {
+                    Some(idx) => {
+                        self.offset = idx;
+                        self.selected = 0;
+                    }
+                    None => {
+                        self.status_message = Some(format!("No transactions on or after {target}"))
+                    }
+                }
+            }
+            Fetch::Id(id) => match reports::register_position_of_id(conn, &query, &order, id)? {
+                Some(idx) => {
+                    self.offset = idx;
+                    self.selected = 0;
+                }
+                None => self.status_message = Some(format!("Transaction #{id} not found")),
+            },
+            Fetch::Today => {
+                let today = Local::now().format("%Y-%m-%d").to_string();
+                let id = reports::register_last_on_or_before(conn, &query, &today)?;
+                let idx = match id {
+                    Some(id) => reports::register_position_of_id(conn, &query, &order, id)?,
+                    None => None,
+                };
+                self.place_today(idx);
+                if let (Some(pager), Some(id)) = (&mut self.pager, id) {
+                    pager.anchor = Some(id);
+                }
+            }
+            Fetch::Resort => {
+                // The loaded window is in the old order
+                self.rows.clear();
+                self.offset = 0;
+                self.selected = 0;
+                if let Some(id) = self.pager.as_mut().and_then(|p| p.anchor.take()) {
+                    if let Some(idx) = reports::register_position_of_id(conn, &query, &order, id)? {
+                        self.scroll_to_row(idx);
+                    }
+                }
+                if !self.search_query.is_empty() {
+                    self.search_matches =
+                        reports::register_search(conn, &query, &order, &self.search_query)?;
+                    self.search_index = 0;
+                }
+            }
+            Fetch::Search(needle) => {
+                self.search_matches = reports::register_search(conn, &query, &order, &needle)?;
+                self.search_index = 0;
+                if let Some(&idx) = self.search_matches.first() {
+                    self.scroll_to_row(idx);
+                }
+            }
+            Fetch::Filter(expr) => {
+                let filter = RowFilter::parse(&expr)?;
+                let selected_id = self.selected_row().map(|r| r.id);
+                self.search_matches.clear();
+                self.search_query.clear();
+                self.search_index = 0;
+                self.offset = 0;
+                self.selected = 0;
+                if filter.is_empty() {
+                    self.filter_expr.clear();
+                    self.rows.clear();
+                    self.total = self.pager.as_ref().map_or(0.0, |p| p.total);
+                    let idx = match selected_id {
+                        Some(id) => reports::register_position_of_id(conn, &query, &order, id)?,
+                        None => None,
+                    };
+                    if let Some(idx) = idx {
+                        self.scroll_to_row(idx);
+                    }
+                } else {
+                    self.rows =
+                        reports::register_rows_where(conn, &query, &order, |r| filter.matches(r))?;
+                    self.total = self.rows.iter().map(|r| r.amount).sum();
+                    self.filter_expr = expr;
+                    if let Some(idx) =
+                        selected_id.and_then(|id| self.rows.iter().position(|r| r.id == id))
+                    {
+                        self.scroll_to_row(idx);
+                    }
+                }
+            }
+            Fetch::Export(format) => {
+                let rows = reports::register_rows_where(conn, &query, &order, |_| true)?;
+                let dir = crate::settings::get_data_dir().join("exports");
+                self.status_message = Some(match write_export(&rows, &dir, format) {
+                    Ok(path) => format!("Exported {}", path.display()),
+                    Err(e) => format!("Export failed: {e}"),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn layout(&self) -> BrowseLayout {
+        self.layout.clone()
+    }
+
+    /// Restore a layout from earlier in the session, re-sorting the rows.
+    pub fn apply_layout(&mut self, layout: BrowseLayout) {
+        self.layout = layout;
+        self.sort_rows();
+    }
+
+    fn title(&self) -> String {
+        let BrowseLayout {
+            sort, descending, ..
+        } = self.layout;
+        if sort == SortKey::Date && !descending {
+            return " Transaction Register".to_string();
+        }
+        let arrow = if descending { "\u{2193}" } else { "\u{2191}" };
+        format!(
+            " Transaction Register \u{2014} sorted by {} {arrow}",
+            sort.label()
+        )
+    }
+
+    /// Narrow the displayed rows to those matching `expr` (an empty one shows
+    /// everything again). On a parse error nothing changes.
+    fn apply_filter(&mut self, expr: &str) -> crate::error::Result<()> {
+        let filter = RowFilter::parse(expr)?;
+        if self.pager.is_some() {
+            self.queue(Fetch::Filter(expr.trim().to_string()));
+            return Ok(());
+        }
+        let selected_id = self.selected_row().map(|r| r.id);
+        let (all, all_total) = match self.unfiltered.take() {
+            Some(saved) => saved,
+            None => (std::mem::take(&mut self.rows), self.total),
+        };
+        if filter.is_empty() {
+            self.rows = all;
+            self.total = all_total;
+            self.filter_expr.clear();
+        } else {
+            self.rows = all.iter().filter(|r| filter.matches(r)).cloned().collect();
+            self.total = self.rows.iter().map(|r| r.amount).sum();
+            self.unfiltered = Some((all, all_total));
+            self.filter_expr = expr.trim().to_string();
+        }
+        // Match indices point into the old rows
+        self.search_matches.clear();
+        self.search_query.clear();
+        self.search_index = 0;
+        self.offset = 0;
+        self.selected = 0;
+        if let Some(idx) = selected_id.and_then(|id| self.rows.iter().position(|r| r.id == id)) {
+            self.scroll_to_row(idx);
+        }
+        Ok(())
+    }
+
+    /// Copy an edited row back into the unfiltered set so clearing the filter
+    /// doesn't undo it on screen.
+    fn sync_unfiltered(&mut self, abs_idx: usize) {
+        let (Some(row), Some((all, _))) = (self.rows.get(abs_idx), &mut self.unfiltered) else {
+            return;
+        };
+        if let Some(orig) = all.iter_mut().find(|r| r.id == row.id) {
+            *orig = row.clone();
+        }
+    }
+
+    fn column_visible(&self, col: Column, narrow: bool) -> bool {
+        if col == Column::Native && !self.show_native {
+            return false;
+        }
+        let idx = Column::ALL.iter().position(|c| *c == col).unwrap_or(0);
+        // Narrow terminals default to just the category
+        self.layout.columns[idx].unwrap_or(!narrow || col == Column::Category)
+    }
+
+    fn toggle_column(&mut self, col: Column, narrow: bool) {
+        let idx = Column::ALL.iter().position(|c| *c == col).unwrap_or(0);
+        self.layout.columns[idx] = Some(!self.column_visible(col, narrow));
+    }
+
+    /// Reorder the loaded rows by the layout's sort key without re-querying,
+    /// keeping the selected transaction and any search matches in view.
+    fn sort_rows(&mut self) {
+        let selected_id = self.selected_row().map(|r| r.id);
+        if self.paging().is_some() {
+            if let Some(pager) = &mut self.pager {
+                pager.anchor = selected_id;
+            }
+            self.queue(Fetch::Resort);
+            return;
+        }
+        let match_ids: Vec<i64> = self
+            .search_matches
+            .iter()
+            .filter_map(|&i| self.rows.get(i).map(|r| r.id))
+            .collect();
+
+        let (key, descending) = (self.layout.sort, self.layout.descending);
+        self.rows
+            .sort_by(|a, b| compare_rows(a, b, key, descending));
+        if let Some((all, _)) = &mut self.unfiltered {
+            all.sort_by(|a, b| compare_rows(a, b, key, descending));
+        }
+
+        if !match_ids.is_empty() {
+            self.search_matches = self
+                .rows
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| match_ids.contains(&r.id))
+                .map(|(i, _)| i)
+                .collect();
+            self.search_index = 0;
+        }
+        if let Some(idx) = selected_id.and_then(|id| self.rows.iter().position(|r| r.id == id)) {
+            self.scroll_to_row(idx);
         }
     }
 
     /// Scroll so that the last transaction on or before today is visible.
     /// Relies on rows being sorted by date ASC (as returned by get_register).
     pub fn scroll_to_today(&mut self) {
+        if self.paging().is_some() {
+            self.queue(Fetch::Today);
+            return;
+        }
         let today = Local::now().format("%Y-%m-%d").to_string();
         let idx = self.rows.iter().rposition(|r| r.date <= today);
+        self.place_today(idx);
+    }
+
+    fn place_today(&mut self, idx: Option<usize>) {
         if let Some(i) = idx {
             // Position that row on screen (offset so it's visible, near middle)
             self.offset = i.saturating_sub(PAGE_SIZE / 2);
             let max_sel = self.visible_count.saturating_sub(1);
             self.selected = (i - self.offset).min(max_sel);
-        } else if !self.rows.is_empty() {
+        } else if !self.is_empty() {
             // All transactions are in the future — start at the beginning
             self.offset = 0;
             self.selected = 0;
@@ -109,7 +642,7 @@ impl RegisterBrowser {
     }
 
     pub fn run(&mut self, conn: &rusqlite::Connection) -> io::Result<()> {
-        if self.rows.is_empty() {
+        if self.is_empty() {
             println!("No transactions found.");
             return Ok(());
         }
@@ -130,13 +663,18 @@ impl RegisterBrowser {
     pub fn draw_frame(&mut self, frame: &mut Frame) {
         let area = frame.area();
         let narrow = area.width < 120;
+        self.narrow = narrow;
 
         let edit_height: u16 = match &self.mode {
             BrowseMode::EditCategory { .. } => {
                 let matches = self.filtered_categories().len();
                 1 + matches.min(9) as u16
             }
-            BrowseMode::EditVendor(_) => 1,
+            BrowseMode::EditVendor(_)
+            | BrowseMode::EditMemo(_)
+            | BrowseMode::EditTags(_)
+            | BrowseMode::EditClient(_) => 1,
+            BrowseMode::CreateRule(_) => 2,
             _ => 0,
         };
 
@@ -157,10 +695,11 @@ impl RegisterBrowser {
         let keys_area = areas[5];
 
         // Title
-        frame.render_widget(
-            Paragraph::new(" Transaction Register").style(HEADER_STYLE),
-            title_area,
-        );
+        let mut title = self.title();
+        if crate::db::is_read_only() {
+            title.push_str(crate::tui::READ_ONLY_TAG);
+        }
+        frame.render_widget(Paragraph::new(title).style(HEADER_STYLE), title_area);
 
         // Separator
         frame.render_widget(
@@ -169,12 +708,12 @@ impl RegisterBrowser {
         );
 
         // Compute description column width from fixed columns + spacing
-        let (fixed_cols, num_cols): (u16, u16) = if narrow {
-            (2 + 6 + 10 + 12 + 28, 6)
-        } else {
-            (2 + 6 + 10 + 12 + 28 + 20 + 20, 8)
-        };
-        let spacing = num_cols - 1;
+        let columns: Vec<Column> = Column::ALL
+            .into_iter()
+            .filter(|c| self.column_visible(*c, narrow))
+            .collect();
+        let fixed_cols = 2 + 6 + 10 + 12 + columns.iter().map(|c| c.width()).sum::<u16>();
+        let spacing = 4 + columns.len() as u16;
         let desc_width = table_area.width.saturating_sub(fixed_cols + spacing) as usize;
         let desc_width = desc_width.max(10);
 
@@ -185,44 +724,82 @@ impl RegisterBrowser {
         let mut total_height = 0usize;
         let mut vis = 0usize;
 
-        for row_data in self.rows.iter().skip(self.offset) {
-            let (wrapped_desc, line_count) = tui::wrap_text(&row_data.description, desc_width);
+        let start = self.paging().map_or(0, |p| p.start);
+        for row_data in self.rows.iter().skip(self.offset.saturating_sub(start)) {
+            let (wrapped_desc, mut line_count) = tui::wrap_text(&row_data.description, desc_width);
+            let mut desc_lines: Vec<Line> = wrapped_desc
+                .lines()
+                .map(|l| Line::from(l.to_string()))
+                .collect();
+            if !row_data.tags.is_empty() || row_data.client.is_some() {
+                let labels: Vec<String> = row_data
+                    .client
+                    .iter()
+                    .map(|c| format!("client: {c}"))
+                    .chain((!row_data.tags.is_empty()).then(|| crate::tags::label(&row_data.tags)))
+                    .collect();
+                let labels = labels.join("  ");
+                desc_lines.push(Line::styled(
+                    tui::truncate(&labels, desc_width),
+                    FOOTER_STYLE,
+                ));
+                line_count += 1;
+            }
+            if let Some(memo) = &row_data.memo {
+                let memo = format!("\u{21b3} {memo}");
+                if self.expand_memos {
+                    let (wrapped, n) = tui::wrap_text(&memo, desc_width);
+                    desc_lines.extend(
+                        wrapped
+                            .lines()
+                            .map(|l| Line::styled(l.to_string(), FOOTER_STYLE)),
+                    );
+                    line_count += n;
+                } else {
+                    desc_lines.push(Line::styled(tui::truncate(&memo, desc_width), FOOTER_STYLE));
+                    line_count += 1;
+                }
+            }
+            let desc_cell = Cell::from(Text::from(desc_lines));
             let h = line_count as usize;
 
             if total_height + h > available_height && vis > 0 {
                 break;
             }
 
-            let cat = row_data
-                .category
-                .as_deref()
-                .unwrap_or("\u{2014}")
-                .to_string();
             let amt = tui::money_span(row_data.amount);
             let flag_cell = Cell::from(if row_data.is_flagged { "!" } else { "" });
 
-            let cells: Vec<Cell> = if narrow {
-                vec![
-                    flag_cell,
-                    Cell::from(row_data.id.to_string()),
-                    Cell::from(row_data.date.clone()),
-                    Cell::from(wrapped_desc),
-                    Cell::from(amt),
-                    Cell::from(cat),
-                ]
-            } else {
-                let vendor = row_data.vendor.as_deref().unwrap_or("").to_string();
-                vec![
-                    flag_cell,
-                    Cell::from(row_data.id.to_string()),
-                    Cell::from(row_data.date.clone()),
-                    Cell::from(wrapped_desc),
-                    Cell::from(amt),
-                    Cell::from(cat),
-                    Cell::from(vendor),
-                    Cell::from(row_data.account_name.clone()),
-                ]
-            };
+            let mut cells = vec![
+                flag_cell,
+                Cell::from(row_data.id.to_string()),
+                Cell::from(fmt::date(&row_data.date)),
+                desc_cell,
+                Cell::from(amt),
+            ];
+            for col in &columns {
+                cells.push(match col {
+                    Column::Native => Cell::from(
+                        row_data
+                            .foreign
+                            .as_ref()
+                            .map(|f| money_in(f.amount, &f.currency))
+                            .unwrap_or_default(),
+                    )
+                    .style(FOOTER_STYLE),
+                    Column::Category => Cell::from(
+                        row_data
+                            .category
+                            .as_deref()
+                            .unwrap_or("\u{2014}")
+                            .to_string(),
+                    ),
+                    Column::Vendor => {
+                        Cell::from(row_data.vendor.as_deref().unwrap_or("").to_string())
+                    }
+                    Column::Account => Cell::from(row_data.account_name.clone()),
+                });
+            }
 
             rendered_rows.push(Row::new(cells).height(line_count));
             total_height += h;
@@ -233,42 +810,17 @@ impl RegisterBrowser {
         self.selected = self.selected.min(self.visible_count.saturating_sub(1));
 
         // Table column constraints
-        let widths: Vec<Constraint> = if narrow {
-            vec![
-                Constraint::Length(2),
-                Constraint::Length(6),
-                Constraint::Length(10),
-                Constraint::Fill(1),
-                Constraint::Length(12),
-                Constraint::Length(28),
-            ]
-        } else {
-            vec![
-                Constraint::Length(2),
-                Constraint::Length(6),
-                Constraint::Length(10),
-                Constraint::Fill(1),
-                Constraint::Length(12),
-                Constraint::Length(28),
-                Constraint::Length(20),
-                Constraint::Length(20),
-            ]
-        };
-
-        let header_cells: Vec<&str> = if narrow {
-            vec!["", "ID", "Date", "Description", "Amount", "Category"]
-        } else {
-            vec![
-                "",
-                "ID",
-                "Date",
-                "Description",
-                "Amount",
-                "Category",
-                "Vendor",
-                "Account",
-            ]
-        };
+        let mut widths = vec![
+            Constraint::Length(2),
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Fill(1),
+            Constraint::Length(12),
+        ];
+        widths.extend(columns.iter().map(|c| Constraint::Length(c.width())));
+
+        let mut header_cells = vec!["", "ID", "Date", "Description", "Amount"];
+        header_cells.extend(columns.iter().map(|c| c.header()));
 
         self.table_state.select(Some(self.selected));
         let table = Table::new(rendered_rows, widths)
@@ -302,18 +854,46 @@ impl RegisterBrowser {
                         "  Vendor (Enter to skip): {input}\u{2588}"
                     ))]
                 }
+                BrowseMode::EditMemo(input) => {
+                    vec![Line::from(format!("  Memo: {input}\u{2588}"))]
+                }
+                BrowseMode::EditTags(input) => {
+                    vec![Line::from(format!("  Tags: {input}\u{2588}"))]
+                }
+                BrowseMode::EditClient(input) => {
+                    vec![Line::from(format!("  Client: {input}\u{2588}"))]
+                }
+                BrowseMode::CreateRule(input) => {
+                    let row = self.selected_row();
+                    let files_as = row.map_or(String::new(), |r| {
+                        let category = r.category.as_deref().unwrap_or_default();
+                        match r.vendor.as_deref() {
+                            Some(v) => format!("    \u{2192} {category} (vendor: {v})"),
+                            None => format!("    \u{2192} {category}"),
+                        }
+                    });
+                    vec![
+                        Line::from(format!("  Rule pattern (contains): {input}\u{2588}")),
+                        Line::from(Span::styled(files_as, Style::default().fg(Color::DarkGray))),
+                    ]
+                }
                 _ => vec![],
             };
             frame.render_widget(Paragraph::new(edit_lines), edit_area);
         }
 
         // Status line
-        let end_row = (self.offset + self.visible_count).min(self.rows.len());
+        let end_row = (self.offset + self.visible_count).min(self.len());
         let filters = if self.filters_desc.is_empty() {
             String::new()
         } else {
             format!(" | {}", self.filters_desc)
         };
+        let filters = if self.filter_expr.is_empty() {
+            filters
+        } else {
+            format!("{filters} | filter: {}", self.filter_expr)
+        };
         let search_info = if !self.search_matches.is_empty() {
             format!(
                 " | {} of {} matches",
@@ -330,7 +910,7 @@ impl RegisterBrowser {
                 "Rows {}-{} of {} | Net: {}{}{} | {}",
                 self.offset + 1,
                 end_row,
-                self.rows.len(),
+                self.len(),
                 money(self.total),
                 filters,
                 search_info,
@@ -341,7 +921,7 @@ impl RegisterBrowser {
                 "Rows {}-{} of {} | Net: {}{}{}",
                 self.offset + 1,
                 end_row,
-                self.rows.len(),
+                self.len(),
                 money(self.total),
                 filters,
                 search_info,
@@ -357,8 +937,18 @@ impl RegisterBrowser {
                 } else {
                     "  n:next match  N:prev match"
                 };
+                let filter_keys = if !self.filter_expr.is_empty() {
+                    "  Bksp:clear filter"
+                } else {
+                    ""
+                };
+                let (edit_keys, tag_keys) = if crate::db::is_read_only() {
+                    ("", "")
+                } else {
+                    ("e:edit  R:rule  m:memo  ", "t:tags  C:client  f:flag  D:trash  ")
+                };
                 Paragraph::new(format!(
-                    "\u{2191}/\u{2193}:select  e:edit  f:flag  \u{2192}:next  \u{2190}:prev  g:page  d:date  i:id  /:search{search_keys}  q:quit"
+                    "\u{2191}/\u{2193}:select  {edit_keys}M:expand memos  {tag_keys}s/S:sort  c:columns  F:filter{filter_keys}  x:export  \u{2192}:next  \u{2190}:prev  g:page  d:date  i:id  /:search{search_keys}  q:quit"
                 ))
                 .style(FOOTER_STYLE)
             }
@@ -376,21 +966,73 @@ impl RegisterBrowser {
             BrowseMode::EditVendor(_) => {
                 Paragraph::new("Enter=confirm (empty to skip), Esc=cancel").style(FOOTER_STYLE)
             }
+            BrowseMode::EditMemo(_) => {
+                Paragraph::new("Enter=save (empty to clear), Esc=cancel").style(FOOTER_STYLE)
+            }
+            BrowseMode::EditTags(_) => Paragraph::new(
+                "Space-separated, e.g. #client-acme travel  Enter=save (empty to clear), Esc=cancel",
+            )
+            .style(FOOTER_STYLE),
+            BrowseMode::EditClient(_) => Paragraph::new(
+                "New names are added as clients  Enter=save (empty to unassign), Esc=cancel",
+            )
+            .style(FOOTER_STYLE),
+            BrowseMode::CreateRule(_) => {
+                Paragraph::new("Enter=create rule (non-empty required), Esc=cancel").style(FOOTER_STYLE)
+            }
+            BrowseMode::Filter { input, error, .. } => {
+                let mut spans = vec![Span::raw(format!("Filter: {input}\u{2588}"))];
+                match error {
+                    Some(e) => spans.push(Span::styled(
+                        format!("  {e}"),
+                        Style::default().fg(Color::Red),
+                    )),
+                    None => spans.push(Span::styled(
+                        "  cat: vendor: account: tag: amount>N words  Enter=keep  Esc=cancel",
+                        FOOTER_STYLE,
+                    )),
+                }
+                Paragraph::new(Line::from(spans))
+            }
+            BrowseMode::Columns => Paragraph::new(
+                "Toggle columns: c=category  v=vendor  a=account  n=native amount  Esc=done",
+            )
+            .style(FOOTER_STYLE),
+            BrowseMode::ExportFormat => Paragraph::new(format!(
+                "Export {} rows: c=CSV  x=XLSX  Esc=cancel",
+                self.len()
+            )),
+            BrowseMode::ConfirmTrash => Paragraph::new(format!(
+                "Move #{} to the trash? y=yes  any other key=cancel",
+                self.selected_row().map_or(0, |r| r.id)
+            )),
         };
         frame.render_widget(keys_widget, keys_area);
     }
 
     /// Handle a key event. Returns a BrowseAction indicating what the caller should do.
     pub fn handle_key_event(&mut self, code: KeyCode) -> BrowseAction {
+        match self.handle_key(code) {
+            BrowseAction::Continue if self.needs_fetch() => BrowseAction::Fetch,
+            action => action,
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> BrowseAction {
         self.status_message = None;
 
         match &self.mode {
             BrowseMode::Normal => match code {
                 KeyCode::Char('q') | KeyCode::Esc => return BrowseAction::Close,
+                KeyCode::Char('e' | 'f' | 'm' | 'R' | 't' | 'C' | 'D') | KeyCode::Enter
+                    if crate::db::is_read_only() =>
+                {
+                    self.status_message = Some(crate::tui::READ_ONLY_STATUS.into());
+                }
                 KeyCode::Down => {
-                    if self.selected + 1 < self.visible_count.min(self.rows.len() - self.offset) {
+                    if self.selected + 1 < self.visible_count.min(self.len() - self.offset) {
                         self.selected += 1;
-                    } else if self.offset + self.visible_count < self.rows.len() {
+                    } else if self.offset + self.visible_count < self.len() {
                         self.offset += 1;
                     }
                 }
@@ -446,8 +1088,98 @@ impl RegisterBrowser {
                 KeyCode::Char('f') => {
                     return BrowseAction::ToggleFlag;
                 }
+                KeyCode::Char('m') => {
+                    if let Some(row) = self.selected_row() {
+                        self.mode = BrowseMode::EditMemo(row.memo.clone().unwrap_or_default());
+                    }
+                }
+                KeyCode::Char('M') => {
+                    self.expand_memos = !self.expand_memos;
+                }
+                KeyCode::Char('R') => {
+                    if let Some(row) = self.selected_row() {
+                        if row.category_id.is_some() {
+                            self.mode = BrowseMode::CreateRule(
+                                crate::reviewer::suggest_rule_pattern(&row.description),
+                            );
+                        } else {
+                            self.status_message =
+                                Some("Categorize it first (e), then press R".into());
+                        }
+                    }
+                }
+                KeyCode::Char('t') => {
+                    if let Some(row) = self.selected_row() {
+                        self.mode = BrowseMode::EditTags(crate::tags::label(&row.tags));
+                    }
+                }
+                KeyCode::Char('C') => {
+                    if let Some(row) = self.selected_row() {
+                        self.mode = BrowseMode::EditClient(row.client.clone().unwrap_or_default());
+                    }
+                }
+                KeyCode::Char('D') if self.selected_row().is_some() => {
+                    self.mode = BrowseMode::ConfirmTrash;
+                }
+                KeyCode::Char('s') => {
+                    self.layout.sort = self.layout.sort.next();
+                    self.sort_rows();
+                }
+                KeyCode::Char('S') => {
+                    self.layout.descending = !self.layout.descending;
+                    self.sort_rows();
+                }
+                KeyCode::Char('c') => {
+                    self.mode = BrowseMode::Columns;
+                }
+                KeyCode::Char('F') => {
+                    self.mode = BrowseMode::Filter {
+                        input: self.filter_expr.clone(),
+                        error: None,
+                        previous: self.filter_expr.clone(),
+                    };
+                }
+                KeyCode::Backspace if !self.filter_expr.is_empty() => {
+                    // Can't fail: the empty expression always parses
+                    let _ = self.apply_filter("");
+                }
+                KeyCode::Char('x') => {
+                    self.mode = BrowseMode::ExportFormat;
+                }
                 _ => {}
             },
+            BrowseMode::Filter { .. } => self.handle_filter_key(code),
+            BrowseMode::ConfirmTrash => {
+                self.mode = BrowseMode::Normal;
+                if code == KeyCode::Char('y') {
+                    return BrowseAction::Trash;
+                }
+            }
+            BrowseMode::Columns => match code {
+                KeyCode::Char(c) if Column::from_key(c).is_some() => {
+                    if let Some(col) = Column::from_key(c) {
+                        self.toggle_column(col, self.narrow);
+                    }
+                }
+                _ => self.mode = BrowseMode::Normal,
+            },
+            BrowseMode::ExportFormat => {
+                let format = match code {
+                    KeyCode::Char('c') => Some(TabularFormat::Csv),
+                    KeyCode::Char('x') => Some(TabularFormat::Xlsx),
+                    _ => None,
+                };
+                self.mode = BrowseMode::Normal;
+                if let Some(format) = format.filter(|_| self.paging().is_some()) {
+                    self.queue(Fetch::Export(format));
+                } else if let Some(format) = format {
+                    let dir = crate::settings::get_data_dir().join("exports");
+                    self.status_message = Some(match self.export_rows(&dir, format) {
+                        Ok(path) => format!("Exported {}", path.display()),
+                        Err(e) => format!("Export failed: {e}"),
+                    });
+                }
+            }
             BrowseMode::GotoPage(_) | BrowseMode::GotoDate(_) | BrowseMode::FindId(_) => match code
             {
                 KeyCode::Esc => self.mode = BrowseMode::Normal,
@@ -486,11 +1218,93 @@ impl RegisterBrowser {
             BrowseMode::EditVendor(_) => {
                 return self.handle_edit_vendor_key(code);
             }
+            BrowseMode::EditMemo(_) => match code {
+                KeyCode::Esc => self.mode = BrowseMode::Normal,
+                KeyCode::Enter => {
+                    if let BrowseMode::EditMemo(input) =
+                        std::mem::replace(&mut self.mode, BrowseMode::Normal)
+                    {
+                        self.pending_memo = Some(input);
+                        return BrowseAction::CommitEdit;
+                    }
+                }
+                KeyCode::Backspace => {
+                    if let BrowseMode::EditMemo(input) = &mut self.mode {
+                        input.pop();
+                    }
+                }
+                KeyCode::Char(c) => {
+                    if let BrowseMode::EditMemo(input) = &mut self.mode {
+                        input.push(c);
+                    }
+                }
+                _ => {}
+            },
+            BrowseMode::CreateRule(input) => match code {
+                KeyCode::Esc => self.mode = BrowseMode::Normal,
+                KeyCode::Enter if !input.trim().is_empty() => {
+                    self.pending_rule = Some(input.trim().to_string());
+                    self.mode = BrowseMode::Normal;
+                    return BrowseAction::CreateRule;
+                }
+                KeyCode::Backspace => {
+                    if let BrowseMode::CreateRule(input) = &mut self.mode {
+                        input.pop();
+                    }
+                }
+                KeyCode::Char(c) => {
+                    if let BrowseMode::CreateRule(input) = &mut self.mode {
+                        input.push(c);
+                    }
+                }
+                _ => {}
+            },
+            BrowseMode::EditTags(input) => match code {
+                KeyCode::Esc => self.mode = BrowseMode::Normal,
+                KeyCode::Enter => match crate::tags::parse_tags(input) {
+                    Ok(tags) => {
+                        self.mode = BrowseMode::Normal;
+                        self.pending_tags = Some(tags);
+                        return BrowseAction::CommitEdit;
+                    }
+                    Err(e) => self.status_message = Some(e.to_string()),
+                },
+                KeyCode::Backspace => {
+                    if let BrowseMode::EditTags(input) = &mut self.mode {
+                        input.pop();
+                    }
+                }
+                KeyCode::Char(c) => {
+                    if let BrowseMode::EditTags(input) = &mut self.mode {
+                        input.push(c);
+                    }
+                }
+                _ => {}
+            },
+            BrowseMode::EditClient(input) => match code {
+                KeyCode::Esc => self.mode = BrowseMode::Normal,
+                KeyCode::Enter => {
+                    self.pending_client = Some(input.clone());
+                    self.mode = BrowseMode::Normal;
+                    return BrowseAction::CommitEdit;
+                }
+                KeyCode::Backspace => {
+                    if let BrowseMode::EditClient(input) = &mut self.mode {
+                        input.pop();
+                    }
+                }
+                KeyCode::Char(c) => {
+                    if let BrowseMode::EditClient(input) = &mut self.mode {
+                        input.push(c);
+                    }
+                }
+                _ => {}
+            },
         }
         BrowseAction::Continue
     }
 
-    fn event_loop(
+    pub(crate) fn event_loop(
         &mut self,
         terminal: &mut DefaultTerminal,
         conn: &rusqlite::Connection,
@@ -526,6 +1340,21 @@ impl RegisterBrowser {
                             self.status_message = Some(format!("Flag toggle failed: {e}"));
                         }
                     }
+                    BrowseAction::CreateRule => {
+                        if let Err(e) = self.create_rule(conn) {
+                            self.status_message = Some(format!("Rule not added: {e}"));
+                        }
+                    }
+                    BrowseAction::Trash => {
+                        if let Err(e) = self.trash_selected(conn) {
+                            self.status_message = Some(format!("Not trashed: {e}"));
+                        }
+                    }
+                    BrowseAction::Fetch => {
+                        if let Err(e) = self.fetch(conn) {
+                            self.status_message = Some(format!("Could not load rows: {e}"));
+                        }
+                    }
                 }
             }
         }
@@ -534,7 +1363,7 @@ impl RegisterBrowser {
 
     fn scroll_down(&mut self) {
         let new_offset = self.offset + self.visible_count;
-        if new_offset < self.rows.len() {
+        if new_offset < self.len() {
             self.offset = new_offset;
         }
     }
@@ -544,7 +1373,7 @@ impl RegisterBrowser {
     }
 
     fn scroll_to_end(&mut self) {
-        self.offset = self.rows.len().saturating_sub(PAGE_SIZE);
+        self.offset = self.len().saturating_sub(PAGE_SIZE);
     }
 
     fn recompute_search_matches(&mut self) {
@@ -559,23 +1388,20 @@ impl RegisterBrowser {
         if query.is_empty() {
             return;
         }
+        if self.paging().is_some() {
+            self.queue(Fetch::Search(query));
+            return;
+        }
 
         let q = query.to_lowercase();
         for (i, row) in self.rows.iter().enumerate() {
-            if row.description.to_lowercase().contains(&q)
-                || row
-                    .vendor
-                    .as_deref()
-                    .unwrap_or("")
-                    .to_lowercase()
-                    .contains(&q)
-                || row
-                    .category
-                    .as_deref()
-                    .unwrap_or("")
-                    .to_lowercase()
-                    .contains(&q)
-            {
+            let fields = [
+                Some(row.description.as_str()),
+                row.vendor.as_deref(),
+                row.memo.as_deref(),
+                row.category.as_deref(),
+            ];
+            if crate::search::contains(&q, fields) {
                 self.search_matches.push(i);
             }
         }
@@ -615,7 +1441,7 @@ impl RegisterBrowser {
         } else {
             // Center the row on screen
             self.offset = row_idx.saturating_sub(self.visible_count / 2);
-            let max_offset = self.rows.len().saturating_sub(self.visible_count);
+            let max_offset = self.len().saturating_sub(self.visible_count);
             self.offset = self.offset.min(max_offset);
             self.selected = row_idx.saturating_sub(self.offset);
         }
@@ -646,15 +1472,23 @@ impl RegisterBrowser {
                 if let Ok(page) = input.trim().parse::<usize>() {
                     if page >= 1 {
                         let target = (page - 1) * PAGE_SIZE;
-                        self.offset = target.min(self.rows.len().saturating_sub(1));
+                        self.offset = target.min(self.len().saturating_sub(1));
                         self.selected = 0;
                     }
                 }
             }
             BrowseMode::GotoDate(input) => {
                 let target = input.trim();
-                if !target.is_empty() {
-                    if let Some(idx) = self.rows.iter().position(|r| r.date.as_str() >= target) {
+                if !target.is_empty() && self.paging().is_some() {
+                    self.queue(Fetch::Date(target.to_string()));
+                } else if !target.is_empty() {
+                    // Newest-first order finds the first row on or before the date
+                    let found = if self.layout.sort == SortKey::Date && self.layout.descending {
+                        self.rows.iter().position(|r| r.date.as_str() <= target)
+                    } else {
+                        self.rows.iter().position(|r| r.date.as_str() >= target)
+                    };
+                    if let Some(idx) = found {
                         self.offset = idx;
                         self.selected = 0;
                     } else {
@@ -664,7 +1498,9 @@ impl RegisterBrowser {
             }
             BrowseMode::FindId(input) => {
                 if let Ok(id) = input.trim().parse::<i64>() {
-                    if let Some(idx) = self.rows.iter().position(|r| r.id == id) {
+                    if self.paging().is_some() {
+                        self.queue(Fetch::Id(id));
+                    } else if let Some(idx) = self.rows.iter().position(|r| r.id == id) {
                         self.offset = idx;
                         self.selected = 0;
                     } else {
@@ -737,11 +1573,43 @@ impl RegisterBrowser {
             }
             KeyCode::Esc => {
                 self.mode = BrowseMode::Normal;
-                self.pending_category_idx = None;
+                self.pending_category_idx = None;
+            }
+            _ => {}
+        }
+        BrowseAction::Continue
+    }
+
+    fn handle_filter_key(&mut self, code: KeyCode) {
+        let BrowseMode::Filter {
+            input, previous, ..
+        } = &mut self.mode
+        else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => {
+                let previous = std::mem::take(previous);
+                self.mode = BrowseMode::Normal;
+                let _ = self.apply_filter(&previous);
+                return;
             }
-            _ => {}
+            // Keep the last expression that parsed
+            KeyCode::Enter => {
+                self.mode = BrowseMode::Normal;
+                return;
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => return,
+        }
+        let expr = input.clone();
+        let result = self.apply_filter(&expr);
+        if let BrowseMode::Filter { error, .. } = &mut self.mode {
+            *error = result.err().map(|e| e.to_string());
         }
-        BrowseAction::Continue
     }
 
     fn handle_edit_vendor_key(&mut self, code: KeyCode) -> BrowseAction {
@@ -784,35 +1652,51 @@ impl RegisterBrowser {
     fn apply_edit_to_local_row(&mut self) {
         let abs_idx = self.offset + self.selected;
         if let Some(cat_idx) = self.pending_category_idx {
-            if let Some(row) = self.rows.get_mut(abs_idx) {
-                let cat = &self.categories[cat_idx];
-                row.category = Some(cat.name.clone());
-                row.category_id = Some(cat.id);
-                if let Some(ref v) = self.pending_vendor {
-                    row.vendor = Some(v.clone());
-                } else {
-                    row.vendor = None;
-                }
+            let cat = &self.categories[cat_idx];
+            let (name, id) = (cat.name.clone(), cat.id);
+            let vendor = self.pending_vendor.clone();
+            if let Some(row) = self.row_mut(abs_idx) {
+                row.category = Some(name);
+                row.category_id = Some(id);
+                row.vendor = vendor;
+            }
+        }
+        if let Some(memo) = self.pending_memo.take() {
+            if let Some(row) = self.row_mut(abs_idx) {
+                let memo = memo.trim();
+                row.memo = (!memo.is_empty()).then(|| memo.to_string());
+            }
+        }
+        if let Some(tags) = self.pending_tags.take() {
+            if let Some(row) = self.row_mut(abs_idx) {
+                row.tags = tags;
+            }
+        }
+        if let Some(client) = self.pending_client.take() {
+            if let Some(row) = self.row_mut(abs_idx) {
+                row.client = crate::clients::clean_name(&client).ok();
             }
         }
         self.pending_category_idx = None;
         self.pending_vendor = None;
+        self.sync_unfiltered(abs_idx);
     }
 
     fn apply_flag_toggle_to_local_row(&mut self, new_state: bool) {
         let abs_idx = self.offset + self.selected;
-        if let Some(row) = self.rows.get_mut(abs_idx) {
+        if let Some(row) = self.row_mut(abs_idx) {
             row.is_flagged = new_state;
         }
+        self.sync_unfiltered(abs_idx);
     }
 
     pub fn commit_edit(&mut self, conn: &rusqlite::Connection) -> crate::error::Result<()> {
         let abs_idx = self.offset + self.selected;
         let row = self
-            .rows
-            .get(abs_idx)
+            .row(abs_idx)
             .ok_or_else(|| crate::error::NigelError::Other("No row selected".into()))?;
         let txn_id = row.id;
+        crate::archive::ensure_not_archived(conn, txn_id)?;
 
         if let Some(cat_idx) = self.pending_category_idx {
             let cat_id = self.categories[cat_idx].id;
@@ -823,26 +1707,129 @@ impl RegisterBrowser {
                 crate::reviewer::update_transaction_vendor(conn, txn_id, None)?;
             }
         }
+        if let Some(ref memo) = self.pending_memo {
+            crate::reviewer::update_transaction_memo(conn, txn_id, Some(memo))?;
+        }
+        if let Some(ref tags) = self.pending_tags {
+            crate::tags::set_transaction_tags(conn, txn_id, tags)?;
+        }
+        if let Some(ref client) = self.pending_client {
+            // Keep the stored spelling when the name matched an existing client
+            let stored = crate::clients::assign_input(conn, &[txn_id], client)?;
+            self.pending_client = Some(stored.unwrap_or_default());
+        }
 
+        let categorized = self.pending_category_idx.is_some();
         self.apply_edit_to_local_row();
-        self.status_message = Some(format!("Updated transaction #{txn_id}"));
+        self.status_message = Some(if categorized {
+            format!("Updated transaction #{txn_id} (R to make a rule from it)")
+        } else {
+            format!("Updated transaction #{txn_id}")
+        });
         Ok(())
     }
 
-    /// Toggle the flag on the selected transaction.
-    /// Flags are non-destructive metadata — single-keypress toggle is intentional
-    /// since it's instantly reversible (press `f` again).
+    /// Add a `contains` rule from the `R` prompt that files matches under the
+    /// selected row's category and vendor, so later imports aren't flagged.
+    pub fn create_rule(&mut self, conn: &rusqlite::Connection) -> crate::error::Result<()> {
+        let pattern = self
+            .pending_rule
+            .take()
+            .ok_or_else(|| crate::error::NigelError::Other("No rule pattern entered".into()))?;
+        let row = self
+            .selected_row()
+            .ok_or_else(|| crate::error::NigelError::Other("No row selected".into()))?;
+        let (Some(category_id), Some(category)) = (row.category_id, row.category.as_deref()) else {
+            return Err(crate::error::NigelError::Other(
+                "the transaction has no category".into(),
+            ));
+        };
+        if let Some(dup) =
+            crate::categorizer::find_duplicate_rule(conn, &pattern, category_id, None)?
+        {
+            return Err(crate::error::NigelError::Other(format!(
+                "rule {} already files '{}' under {}",
+                dup.id, dup.pattern, dup.category
+            )));
+        }
+        let id = crate::cli::rules::add_rule(
+            conn,
+            &pattern,
+            "contains",
+            row.vendor.as_deref(),
+            category_id,
+            0,
+        )?;
+        self.status_message = Some(format!("Added rule #{id}: '{pattern}' \u{2192} {category}"));
+        Ok(())
+    }
+
+    /// Write every loaded row, in display order and with any edits made in
+    /// this session, to `dir` as CSV or XLSX.
+    pub fn export_rows(&self, dir: &Path, format: TabularFormat) -> crate::error::Result<PathBuf> {
+        write_export(&self.rows, dir, format)
+    }
+
     pub fn set_status(&mut self, msg: String) {
         self.status_message = Some(msg);
     }
 
+    /// Move the selected transaction to the trash and drop it from the
+    /// register. `nigel trash restore` brings it back.
+    pub fn trash_selected(&mut self, conn: &rusqlite::Connection) -> crate::error::Result<()> {
+        let abs_idx = self.offset + self.selected;
+        let row = self
+            .row(abs_idx)
+            .ok_or_else(|| crate::error::NigelError::Other("No row selected".into()))?;
+        let txn_id = row.id;
+        crate::trash::trash(conn, &[txn_id])?;
+        self.remove_row(abs_idx);
+        self.status_message = Some(format!(
+            "Moved #{txn_id} to the trash (nigel trash restore {txn_id} to undo)"
+        ));
+        Ok(())
+    }
+
+    /// Drop a row from the register on screen, keeping the net, the paged
+    /// register's size, and any unfiltered set in step.
+    fn remove_row(&mut self, abs_idx: usize) {
+        let start = self.paging().map_or(0, |p| p.start);
+        let Some(i) = abs_idx.checked_sub(start).filter(|&i| i < self.rows.len()) else {
+            return;
+        };
+        let row = self.rows.remove(i);
+        self.total -= row.amount;
+        if let Some(pager) = &mut self.pager {
+            pager.len = pager.len.saturating_sub(1);
+            pager.total -= row.amount;
+        }
+        if let Some((all, all_total)) = &mut self.unfiltered {
+            all.retain(|r| r.id != row.id);
+            *all_total -= row.amount;
+        }
+        // Match indices point past the removed row
+        self.search_matches.clear();
+        self.search_query.clear();
+        self.search_index = 0;
+        if self.offset + self.selected >= self.len() {
+            if self.selected > 0 {
+                self.selected -= 1;
+            } else {
+                self.offset = self.offset.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Toggle the flag on the selected transaction.
+    /// Flags are non-destructive metadata — single-keypress toggle is intentional
+    /// since it's instantly reversible (press `f` again).
     pub fn toggle_flag(&mut self, conn: &rusqlite::Connection) -> crate::error::Result<()> {
         let abs_idx = self.offset + self.selected;
         let row = self
-            .rows
-            .get(abs_idx)
+            .row(abs_idx)
             .ok_or_else(|| crate::error::NigelError::Other("No row selected".into()))?;
         let txn_id = row.id;
+        crate::archive::ensure_not_archived(conn, txn_id)?;
         let new_state = crate::reviewer::toggle_transaction_flag(conn, txn_id)?;
         self.apply_flag_toggle_to_local_row(new_state);
         let label = if new_state { "flagged" } else { "unflagged" };
@@ -851,6 +1838,144 @@ impl RegisterBrowser {
     }
 }
 
+fn write_export(
+    rows: &[RegisterRow],
+    dir: &Path,
+    format: TabularFormat,
+) -> crate::error::Result<PathBuf> {
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    let path = dir.join(format!("register-{date}.{}", format.extension()));
+    tabular::write(&tabular::register_sheet(rows), &path, format)?;
+    Ok(path)
+}
+
+/// `compare_rows` as an SQL ORDER BY, for paging a register from the database.
+fn order_sql(key: SortKey, descending: bool) -> String {
+    let dir = if descending { " DESC" } else { "" };
+    let text = |col: &str| format!("{col} IS NULL, lower({col}){dir}, ");
+    let primary = match key {
+        SortKey::Date => format!("t.date{dir}, "),
+        SortKey::Amount => format!("{}{dir}, ", crate::currency::HOME_AMOUNT_SQL),
+        SortKey::Category => text("c.name"),
+        SortKey::Vendor => text("t.vendor"),
+        SortKey::Account => text("a.name"),
+    };
+    format!("{primary}{}", reports::REGISTER_ORDER)
+}
+
+/// Order two rows by `key`, with blank categories/vendors last either way and
+/// ties broken by date then ID so the order is stable across re-sorts.
+fn compare_rows(a: &RegisterRow, b: &RegisterRow, key: SortKey, descending: bool) -> Ordering {
+    let dir = |o: Ordering| if descending { o.reverse() } else { o };
+    let text = |a: Option<&str>, b: Option<&str>| match (a, b) {
+        (Some(a), Some(b)) => dir(a.to_lowercase().cmp(&b.to_lowercase())),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    let primary = match key {
+        SortKey::Date => dir(a.date.cmp(&b.date)),
+        SortKey::Amount => dir(a.amount.total_cmp(&b.amount)),
+        SortKey::Category => text(a.category.as_deref(), b.category.as_deref()),
+        SortKey::Vendor => text(a.vendor.as_deref(), b.vendor.as_deref()),
+        SortKey::Account => text(Some(&a.account_name), Some(&b.account_name)),
+    };
+    primary
+        .then_with(|| a.date.cmp(&b.date))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+impl RegisterBrowser {
+    /// Open the register at today in the session's last layout. On failure
+    /// the reason goes to the home screen's status line.
+    pub fn open_screen(
+        ctx: &mut ScreenContext,
+        query: RegisterQuery,
+        filters_desc: String,
+    ) -> Option<Self> {
+        let categories = match crate::reviewer::get_categories(ctx.conn) {
+            Ok(c) => {
+                ctx.session.status_message = None;
+                c
+            }
+            Err(e) => {
+                ctx.session.status_message =
+                    Some(format!("Warning: could not load categories: {e}"));
+                vec![]
+            }
+        };
+        let opened = Self::open(ctx.conn, query, filters_desc, categories).and_then(|mut b| {
+            b.scroll_to_today();
+            b.apply_layout(ctx.session.browse_layout.clone());
+            b.fetch(ctx.conn)?;
+            Ok(b)
+        });
+        match opened {
+            Ok(browser) => Some(browser),
+            Err(e) => {
+                ctx.session.status_message = Some(format!("Could not load register: {e}"));
+                None
+            }
+        }
+    }
+}
+
+impl screen::Screen for RegisterBrowser {
+    fn draw(&mut self, frame: &mut Frame) {
+        self.draw_frame(frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> crate::error::Result<Nav> {
+        let conn = ctx.conn;
+        let (result, what, change) = match self.handle_key_event(code) {
+            BrowseAction::Close => return Ok(Nav::Pop),
+            BrowseAction::Continue => return Ok(Nav::Stay),
+            BrowseAction::CommitEdit => (
+                self.commit_edit(conn),
+                "Edit failed",
+                Some(DataChange::Transactions),
+            ),
+            BrowseAction::ToggleFlag => (
+                self.toggle_flag(conn),
+                "Flag toggle failed",
+                Some(DataChange::Transactions),
+            ),
+            BrowseAction::CreateRule => (
+                self.create_rule(conn),
+                "Rule not added",
+                Some(DataChange::Rules),
+            ),
+            BrowseAction::Trash => (
+                self.trash_selected(conn),
+                "Not trashed",
+                Some(DataChange::Transactions),
+            ),
+            BrowseAction::Fetch => (self.fetch(conn), "Could not load rows", None),
+        };
+        match (result, change) {
+            (Err(e), _) => self.set_status(format!("{what}: {e}")),
+            (Ok(()), Some(change)) => ctx.publish(change),
+            (Ok(()), None) => {}
+        }
+        Ok(Nav::Stay)
+    }
+
+    /// The sort and columns carry over to the next register opened.
+    fn on_exit(&mut self, ctx: &mut ScreenContext) {
+        ctx.session.browse_layout = self.layout();
+    }
+
+    /// Reload the rows when the screen opened over this one changed them.
+    fn on_data_changed(&mut self, change: DataChange, ctx: &mut ScreenContext) {
+        if change == DataChange::Settings {
+            return;
+        }
+        if let Err(e) = self.fetch(ctx.conn) {
+            self.set_status(format!("Could not load rows: {e}"));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -864,11 +1989,15 @@ mod tests {
                 date: format!("2025-01-{:02}", (i % 28) + 1),
                 description: format!("Transaction {}", i + 1),
                 amount: if i % 2 == 0 { 100.0 } else { -50.0 },
+                foreign: None,
                 category: Some("Test Category".to_string()),
                 category_id: Some(1),
                 vendor: None,
                 account_name: "Test Account".to_string(),
                 is_flagged: false,
+                memo: None,
+                tags: Vec::new(),
+                client: None,
             })
             .collect()
     }
@@ -1136,6 +2265,163 @@ mod tests {
         assert_eq!(browser.rows[0].category_id, Some(1));
     }
 
+    #[test]
+    fn test_memo_edit_prefills_and_commits() {
+        let mut rows = make_rows(3);
+        rows[1].memo = Some("old".to_string());
+        let mut browser = RegisterBrowser::new(rows, 0.0, String::new(), vec![]);
+        browser.selected = 1;
+
+        browser.handle_key_event(KeyCode::Char('m'));
+        assert!(matches!(&browser.mode, BrowseMode::EditMemo(s) if s == "old"));
+        for _ in 0..3 {
+            browser.handle_key_event(KeyCode::Backspace);
+        }
+        for c in " lunch w/ client ".chars() {
+            browser.handle_key_event(KeyCode::Char(c));
+        }
+        let action = browser.handle_key_event(KeyCode::Enter);
+        assert!(matches!(action, BrowseAction::CommitEdit));
+
+        browser.apply_edit_to_local_row();
+        assert_eq!(browser.rows[1].memo.as_deref(), Some("lunch w/ client"));
+        // A memo-only edit leaves the category alone
+        assert_eq!(browser.rows[1].category.as_deref(), Some("Test Category"));
+
+        // Saving an empty memo clears it
+        browser.mode = BrowseMode::EditMemo(String::new());
+        browser.handle_key_event(KeyCode::Enter);
+        browser.apply_edit_to_local_row();
+        assert_eq!(browser.rows[1].memo, None);
+    }
+
+    #[test]
+    fn test_trash_confirms_and_drops_row() {
+        let mut browser = RegisterBrowser::new(make_rows(3), 150.0, String::new(), vec![]);
+        browser.selected = 2;
+
+        browser.handle_key_event(KeyCode::Char('D'));
+        assert!(matches!(browser.mode, BrowseMode::ConfirmTrash));
+        // Anything but y cancels
+        let action = browser.handle_key_event(KeyCode::Char('n'));
+        assert!(matches!(action, BrowseAction::Continue));
+        assert!(matches!(browser.mode, BrowseMode::Normal));
+
+        browser.handle_key_event(KeyCode::Char('D'));
+        let action = browser.handle_key_event(KeyCode::Char('y'));
+        assert!(matches!(action, BrowseAction::Trash));
+
+        browser.remove_row(2);
+        assert_eq!(browser.rows.len(), 2);
+        assert_eq!(browser.total, 50.0);
+        // Selection moves up off the removed last row
+        assert_eq!(browser.selected_row().map(|r| r.id), Some(2));
+    }
+
+    #[test]
+    fn test_tags_edit_prefills_and_validates() {
+        let mut rows = make_rows(3);
+        rows[1].tags = vec!["travel".to_string()];
+        let mut browser = RegisterBrowser::new(rows, 0.0, String::new(), vec![]);
+        browser.selected = 1;
+
+        browser.handle_key_event(KeyCode::Char('t'));
+        assert!(matches!(&browser.mode, BrowseMode::EditTags(s) if s == "#travel"));
+        for c in " bad!tag".chars() {
+            browser.handle_key_event(KeyCode::Char(c));
+        }
+        // An invalid tag keeps the prompt open
+        let action = browser.handle_key_event(KeyCode::Enter);
+        assert!(matches!(action, BrowseAction::Continue));
+        assert!(matches!(browser.mode, BrowseMode::EditTags(_)));
+
+        browser.mode = BrowseMode::EditTags("#travel #Client-Acme".to_string());
+        let action = browser.handle_key_event(KeyCode::Enter);
+        assert!(matches!(action, BrowseAction::CommitEdit));
+        browser.apply_edit_to_local_row();
+        assert_eq!(browser.rows[1].tags, vec!["client-acme", "travel"]);
+        assert_eq!(browser.rows[1].category.as_deref(), Some("Test Category"));
+    }
+
+    #[test]
+    fn test_client_edit_prefills_and_unassigns() {
+        let mut rows = make_rows(2);
+        rows[0].client = Some("Acme Corp".to_string());
+        let mut browser = RegisterBrowser::new(rows, 0.0, String::new(), vec![]);
+
+        browser.handle_key_event(KeyCode::Char('C'));
+        assert!(matches!(&browser.mode, BrowseMode::EditClient(s) if s == "Acme Corp"));
+        for _ in 0.."Acme Corp".len() {
+            browser.handle_key_event(KeyCode::Backspace);
+        }
+        let action = browser.handle_key_event(KeyCode::Enter);
+        assert!(matches!(action, BrowseAction::CommitEdit));
+        browser.apply_edit_to_local_row();
+        assert_eq!(browser.rows[0].client, None);
+
+        browser.mode = BrowseMode::EditClient("  Globex ".to_string());
+        browser.handle_key_event(KeyCode::Enter);
+        browser.apply_edit_to_local_row();
+        assert_eq!(browser.rows[0].client.as_deref(), Some("Globex"));
+    }
+
+    #[test]
+    fn test_export_rows_writes_loaded_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut browser = RegisterBrowser::new(make_rows(3), 0.0, String::new(), vec![]);
+        browser.apply_flag_toggle_to_local_row(true);
+
+        browser.handle_key_event(KeyCode::Char('x'));
+        assert!(matches!(browser.mode, BrowseMode::ExportFormat));
+        browser.handle_key_event(KeyCode::Esc);
+        assert!(matches!(browser.mode, BrowseMode::Normal));
+
+        let path = browser.export_rows(dir.path(), TabularFormat::Csv).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("1,2025-01-01,Transaction 1,100.00"));
+        assert!(lines[1].ends_with(",yes,,"));
+    }
+
+    #[test]
+    fn test_create_rule_from_row() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::get_connection(&dir.path().join("test.db")).unwrap();
+        crate::db::init_db(&conn).unwrap();
+        let mut rows = make_rows(2);
+        rows[0].description = "ADOBE CREATIVE CLOUD 0412".to_string();
+        rows[0].vendor = Some("Adobe".to_string());
+        rows[1].category = None;
+        rows[1].category_id = None;
+        let mut browser = RegisterBrowser::new(rows, 0.0, String::new(), vec![]);
+
+        browser.handle_key_event(KeyCode::Char('R'));
+        assert!(matches!(&browser.mode, BrowseMode::CreateRule(p) if p == "ADOBE CREATIVE"));
+        let action = browser.handle_key_event(KeyCode::Enter);
+        assert!(matches!(action, BrowseAction::CreateRule));
+        browser.create_rule(&conn).unwrap();
+        let (pattern, vendor, category_id): (String, Option<String>, i64) = conn
+            .query_row("SELECT pattern, vendor, category_id FROM rules", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+            })
+            .unwrap();
+        assert_eq!(pattern, "ADOBE CREATIVE");
+        assert_eq!(vendor.as_deref(), Some("Adobe"));
+        assert_eq!(category_id, 1);
+
+        // The same rule again is refused
+        browser.handle_key_event(KeyCode::Char('R'));
+        browser.handle_key_event(KeyCode::Enter);
+        assert!(browser.create_rule(&conn).is_err());
+
+        // An uncategorized row has nothing to file under
+        browser.selected = 1;
+        browser.handle_key_event(KeyCode::Char('R'));
+        assert!(matches!(browser.mode, BrowseMode::Normal));
+        assert!(browser.status_message.is_some());
+    }
+
     #[test]
     fn test_toggle_flag_updates_row() {
         let rows = make_rows(5);
@@ -1156,11 +2442,15 @@ mod tests {
                 date: format!("2025-06-{:02}", (i % 28) + 1),
                 description: format!("Txn {}", i + 1),
                 amount: 100.0,
+                foreign: None,
                 category: None,
                 category_id: None,
                 vendor: None,
                 account_name: "Test".to_string(),
                 is_flagged: false,
+                memo: None,
+                tags: Vec::new(),
+                client: None,
             })
             .collect();
         // Ensure there's a row matching "today" far into the list
@@ -1188,55 +2478,75 @@ mod tests {
                 date: "2025-01-01".to_string(),
                 description: "ADOBE CREATIVE CLOUD".to_string(),
                 amount: -54.99,
+                foreign: None,
                 category: Some("Software & Subscriptions".to_string()),
                 category_id: Some(1),
                 vendor: Some("Adobe".to_string()),
                 account_name: "BofA Checking".to_string(),
                 is_flagged: false,
+                memo: None,
+                tags: Vec::new(),
+                client: None,
             },
             RegisterRow {
                 id: 2,
                 date: "2025-01-05".to_string(),
                 description: "CLIENT PAYMENT ACME CORP".to_string(),
                 amount: 5000.0,
+                foreign: None,
                 category: Some("Client Services".to_string()),
                 category_id: Some(2),
                 vendor: Some("Acme Corp".to_string()),
                 account_name: "BofA Checking".to_string(),
                 is_flagged: false,
+                memo: None,
+                tags: Vec::new(),
+                client: None,
             },
             RegisterRow {
                 id: 3,
                 date: "2025-01-10".to_string(),
                 description: "GITHUB PRO SUBSCRIPTION".to_string(),
                 amount: -4.0,
+                foreign: None,
                 category: Some("Software & Subscriptions".to_string()),
                 category_id: Some(1),
                 vendor: Some("GitHub".to_string()),
                 account_name: "BofA Checking".to_string(),
                 is_flagged: false,
+                memo: None,
+                tags: Vec::new(),
+                client: None,
             },
             RegisterRow {
                 id: 4,
                 date: "2025-01-15".to_string(),
                 description: "OFFICE SUPPLIES STAPLES".to_string(),
                 amount: -89.50,
+                foreign: None,
                 category: Some("Office Supplies".to_string()),
                 category_id: Some(3),
                 vendor: None,
                 account_name: "BofA Credit Card".to_string(),
                 is_flagged: false,
+                memo: None,
+                tags: Vec::new(),
+                client: None,
             },
             RegisterRow {
                 id: 5,
                 date: "2025-01-20".to_string(),
                 description: "ADOBE ACROBAT PRO".to_string(),
                 amount: -19.99,
+                foreign: None,
                 category: Some("Software & Subscriptions".to_string()),
                 category_id: Some(1),
                 vendor: Some("Adobe".to_string()),
                 account_name: "BofA Checking".to_string(),
                 is_flagged: false,
+                memo: None,
+                tags: Vec::new(),
+                client: None,
             },
         ]
     }
@@ -1541,4 +2851,388 @@ mod tests {
         assert_eq!(browser.offset, 30); // clamped to 50-20
         assert_eq!(browser.selected, 18); // 48-30
     }
+
+    /// Render the browser and return one string per screen row with one char
+    /// per cell; double-width glyphs are replaced by `#` so that string
+    /// offsets equal screen columns.
+    fn render_lines(browser: &mut RegisterBrowser, width: u16, height: u16) -> Vec<String> {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|frame| browser.draw_frame(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        let symbol = buffer[(x, y)].symbol();
+                        if tui::display_width(symbol) > 1 {
+                            "#".to_string()
+                        } else {
+                            symbol.to_string()
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_non_ascii_descriptions_keep_columns_aligned() {
+        for width in [120, 70] {
+            let mut rows = make_rows(2);
+            rows[0].description = "株式会社東京商事 月額利用料 🍎".to_string();
+            rows[0].amount = -1234.5;
+            rows[1].description = "Plain ASCII".to_string();
+            rows[1].amount = -99.0;
+            let mut browser = RegisterBrowser::new(rows, 0.0, String::new(), vec![]);
+            let lines = render_lines(&mut browser, width, 20);
+            let cjk = lines.iter().find(|l| l.contains("1,234.50")).unwrap();
+            let ascii = lines.iter().find(|l| l.contains("99.00")).unwrap();
+            assert!(cjk.contains("# # #"), "description not rendered: {cjk}");
+            assert_eq!(
+                cjk[..cjk.find("1,234.50").unwrap()].chars().count(),
+                ascii[..ascii.find("99.00").unwrap()].chars().count(),
+                "amount column misaligned at width {width}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sort_cycles_and_reverses_keeping_selection() {
+        let mut rows = make_rows(4);
+        rows[0].amount = -10.0;
+        rows[1].amount = 500.0;
+        rows[2].amount = -300.0;
+        rows[3].amount = 20.0;
+        let mut browser = RegisterBrowser::new(rows, 0.0, String::new(), vec![]);
+        browser.selected = 2; // id 3, the -300.00 row
+
+        browser.handle_key_event(KeyCode::Char('s'));
+        assert_eq!(browser.layout.sort, SortKey::Amount);
+        let ids: Vec<i64> = browser.rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![3, 1, 4, 2]);
+        assert_eq!(browser.rows[browser.offset + browser.selected].id, 3);
+
+        browser.handle_key_event(KeyCode::Char('S'));
+        let ids: Vec<i64> = browser.rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 4, 1, 3]);
+        assert_eq!(browser.rows[browser.offset + browser.selected].id, 3);
+
+        // Cycling all the way round returns to date order (still reversed)
+        for _ in 0..4 {
+            browser.handle_key_event(KeyCode::Char('s'));
+        }
+        assert_eq!(browser.layout.sort, SortKey::Date);
+        let ids: Vec<i64> = browser.rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_sort_by_vendor_puts_blanks_last() {
+        let mut rows = make_rows(3);
+        rows[0].vendor = Some("zeta".to_string());
+        rows[2].vendor = Some("Acme".to_string());
+        let mut browser = RegisterBrowser::new(rows, 0.0, String::new(), vec![]);
+        browser.apply_layout(BrowseLayout {
+            sort: SortKey::Vendor,
+            ..BrowseLayout::default()
+        });
+        let ids: Vec<i64> = browser.rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+
+        browser.handle_key_event(KeyCode::Char('S'));
+        let ids: Vec<i64> = browser.rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_column_toggles_on_narrow_terminal() {
+        let mut rows = make_rows(2);
+        rows[0].vendor = Some("Acme Vendor".to_string());
+        let mut browser = RegisterBrowser::new(rows, 0.0, String::new(), vec![]);
+
+        let header = |lines: &[String]| lines.iter().find(|l| l.contains("Date")).unwrap().clone();
+        let lines = render_lines(&mut browser, 90, 20);
+        assert!(header(&lines).contains("Category"));
+        assert!(!header(&lines).contains("Vendor"));
+
+        // Show vendor, hide category
+        browser.handle_key_event(KeyCode::Char('c'));
+        assert!(matches!(browser.mode, BrowseMode::Columns));
+        browser.handle_key_event(KeyCode::Char('v'));
+        browser.handle_key_event(KeyCode::Char('c'));
+        browser.handle_key_event(KeyCode::Esc);
+        assert!(matches!(browser.mode, BrowseMode::Normal));
+
+        let lines = render_lines(&mut browser, 90, 20);
+        assert!(header(&lines).contains("Vendor"));
+        assert!(!header(&lines).contains("Category"));
+        assert!(lines.iter().any(|l| l.contains("Acme Vendor")));
+    }
+
+    fn type_filter(browser: &mut RegisterBrowser, expr: &str) {
+        browser.handle_key_event(KeyCode::Char('F'));
+        for c in expr.chars() {
+            browser.handle_key_event(KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_filter_narrows_rows_and_total_live() {
+        let mut rows = make_rows(4);
+        rows[2].vendor = Some("Adobe".to_string());
+        let mut browser = RegisterBrowser::new(rows, 100.0, String::new(), vec![]);
+
+        type_filter(&mut browser, "amount>60");
+        assert!(matches!(
+            &browser.mode,
+            BrowseMode::Filter { error: None, .. }
+        ));
+        let ids: Vec<i64> = browser.rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert!((browser.total - 200.0).abs() < 0.001);
+
+        for c in " vendor:adobe".chars() {
+            browser.handle_key_event(KeyCode::Char(c));
+        }
+        browser.handle_key_event(KeyCode::Enter);
+        assert!(matches!(browser.mode, BrowseMode::Normal));
+        assert_eq!(browser.rows.len(), 1);
+        assert_eq!(browser.rows[0].id, 3);
+
+        let lines = render_lines(&mut browser, 120, 20);
+        assert!(lines
+            .iter()
+            .any(|l| l.contains("filter: amount>60 vendor:adobe")));
+
+        // Backspace in normal mode clears the filter and restores everything
+        browser.handle_key_event(KeyCode::Backspace);
+        assert_eq!(browser.rows.len(), 4);
+        assert!((browser.total - 100.0).abs() < 0.001);
+        assert!(browser.filter_expr.is_empty());
+    }
+
+    #[test]
+    fn test_filter_parse_error_keeps_last_rows_and_esc_reverts() {
+        let mut browser = RegisterBrowser::new(make_rows(4), 100.0, String::new(), vec![]);
+        type_filter(&mut browser, "amount<60");
+        browser.handle_key_event(KeyCode::Enter);
+        assert_eq!(browser.rows.len(), 2);
+
+        // "amount<60a" doesn't parse; rows stay as they were
+        type_filter(&mut browser, "a");
+        assert!(matches!(
+            &browser.mode,
+            BrowseMode::Filter { error: Some(_), .. }
+        ));
+        assert_eq!(browser.rows.len(), 2);
+
+        // Fixing it to "amount<60 acme" applies live
+        browser.handle_key_event(KeyCode::Backspace);
+        for c in " acme".chars() {
+            browser.handle_key_event(KeyCode::Char(c));
+        }
+        assert!(browser.rows.is_empty());
+        browser.handle_key_event(KeyCode::Esc);
+        assert_eq!(browser.rows.len(), 2);
+        assert_eq!(browser.filter_expr, "amount<60");
+    }
+
+    #[test]
+    fn test_edits_and_sort_survive_clearing_filter() {
+        let mut browser = RegisterBrowser::new(make_rows(4), 100.0, String::new(), vec![]);
+        type_filter(&mut browser, "amount>60");
+        browser.handle_key_event(KeyCode::Enter);
+        browser.selected = 1; // id 3
+
+        browser.apply_flag_toggle_to_local_row(true);
+        browser.handle_key_event(KeyCode::Char('S'));
+        let ids: Vec<i64> = browser.rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![3, 1]);
+
+        browser.handle_key_event(KeyCode::Backspace);
+        let ids: Vec<i64> = browser.rows.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![4, 3, 2, 1]);
+        assert!(browser.rows[1].is_flagged);
+        // Selection follows the row it was on
+        assert_eq!(browser.rows[browser.offset + browser.selected].id, 3);
+    }
+
+    /// A register of `n` transactions, one a day from 2025-01-01, with every
+    /// tenth one filed under Software & Subscriptions.
+    fn paged_db(n: usize) -> (tempfile::TempDir, rusqlite::Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = crate::db::get_connection(&dir.path().join("test.db")).unwrap();
+        crate::db::init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        let acct = conn.last_insert_rowid();
+        let software: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        let start = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        for i in 0..n {
+            let date = start + chrono::Duration::days(i as i64);
+            let category = (i % 10 == 0).then_some(software);
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    acct,
+                    date.format("%Y-%m-%d").to_string(),
+                    format!("Charge {}", i + 1),
+                    -((i + 1) as f64),
+                    category
+                ],
+            )
+            .unwrap();
+        }
+        (dir, conn)
+    }
+
+    fn run_key(browser: &mut RegisterBrowser, conn: &rusqlite::Connection, code: KeyCode) {
+        if let BrowseAction::Fetch = browser.handle_key_event(code) {
+            browser.fetch(conn).unwrap();
+        }
+    }
+
+    fn selected_id(browser: &RegisterBrowser) -> i64 {
+        browser.selected_row().unwrap().id
+    }
+
+    #[test]
+    fn test_small_register_loads_whole() {
+        let (_dir, conn) = paged_db(30);
+        let browser =
+            RegisterBrowser::open(&conn, RegisterQuery::default(), String::new(), vec![]).unwrap();
+        assert!(browser.pager.is_none());
+        assert_eq!(browser.rows.len(), 30);
+        assert_eq!(browser.total, -465.0);
+    }
+
+    #[test]
+    fn test_paged_register_loads_a_window() {
+        let (_dir, conn) = paged_db(400);
+        let mut browser =
+            RegisterBrowser::paged(&conn, RegisterQuery::default(), String::new(), vec![], 100)
+                .unwrap();
+        assert_eq!(browser.rows.len(), 100);
+        assert_eq!(browser.len(), 400);
+        assert_eq!(browser.total, -80_200.0);
+
+        // Paging past the window asks for a fetch, then shows the right rows
+        for _ in 0..5 {
+            run_key(&mut browser, &conn, KeyCode::PageDown);
+        }
+        assert_eq!(browser.offset, 5 * PAGE_SIZE);
+        assert_eq!(selected_id(&browser), 101);
+        assert!(!browser.needs_fetch());
+
+        run_key(&mut browser, &conn, KeyCode::End);
+        assert_eq!(selected_id(&browser), 381);
+        run_key(&mut browser, &conn, KeyCode::Home);
+        assert_eq!(selected_id(&browser), 1);
+    }
+
+    #[test]
+    fn test_paged_jumps_use_targeted_queries() {
+        let (_dir, conn) = paged_db(400);
+        let mut browser =
+            RegisterBrowser::paged(&conn, RegisterQuery::default(), String::new(), vec![], 100)
+                .unwrap();
+
+        browser.mode = BrowseMode::FindId("350".to_string());
+        run_key(&mut browser, &conn, KeyCode::Enter);
+        assert_eq!(selected_id(&browser), 350);
+
+        browser.mode = BrowseMode::GotoDate("2025-03-01".to_string());
+        run_key(&mut browser, &conn, KeyCode::Enter);
+        assert_eq!(browser.selected_row().unwrap().date, "2025-03-01");
+
+        browser.mode = BrowseMode::FindId("999".to_string());
+        run_key(&mut browser, &conn, KeyCode::Enter);
+        assert!(browser.status_message.as_ref().unwrap().contains("999"));
+
+        browser.mode = BrowseMode::Search(String::new());
+        for c in "charge 39".chars() {
+            run_key(&mut browser, &conn, KeyCode::Char(c));
+        }
+        // "Charge 39" and "Charge 390".."Charge 399"
+        assert_eq!(browser.search_matches.len(), 11);
+        assert_eq!(selected_id(&browser), 39);
+        run_key(&mut browser, &conn, KeyCode::Enter);
+        run_key(&mut browser, &conn, KeyCode::Char('N'));
+        assert_eq!(selected_id(&browser), 399);
+    }
+
+    #[test]
+    fn test_paged_sort_keeps_selection() {
+        let (_dir, conn) = paged_db(400);
+        let mut browser =
+            RegisterBrowser::paged(&conn, RegisterQuery::default(), String::new(), vec![], 100)
+                .unwrap();
+        browser.mode = BrowseMode::FindId("250".to_string());
+        run_key(&mut browser, &conn, KeyCode::Enter);
+
+        // Amount ascending puts the largest charge first
+        run_key(&mut browser, &conn, KeyCode::Char('s'));
+        assert_eq!(selected_id(&browser), 250);
+        assert_eq!(browser.offset + browser.selected, 150);
+        assert_eq!(browser.row(0).map(|r| r.id), None);
+        run_key(&mut browser, &conn, KeyCode::Home);
+        assert_eq!(selected_id(&browser), 400);
+    }
+
+    #[test]
+    fn test_paged_filter_loads_matches_and_clears_back() {
+        let (_dir, conn) = paged_db(400);
+        let mut browser =
+            RegisterBrowser::paged(&conn, RegisterQuery::default(), String::new(), vec![], 100)
+                .unwrap();
+        browser.mode = BrowseMode::Filter {
+            input: String::new(),
+            error: None,
+            previous: String::new(),
+        };
+        for c in "cat:software".chars() {
+            run_key(&mut browser, &conn, KeyCode::Char(c));
+        }
+        run_key(&mut browser, &conn, KeyCode::Enter);
+        assert_eq!(browser.len(), 40);
+        assert_eq!(browser.rows.len(), 40);
+        assert_eq!(browser.filter_expr, "cat:software");
+
+        run_key(&mut browser, &conn, KeyCode::End);
+        assert_eq!(selected_id(&browser), 201);
+        run_key(&mut browser, &conn, KeyCode::Backspace);
+        assert!(browser.filter_expr.is_empty());
+        assert_eq!(browser.len(), 400);
+        assert_eq!(browser.total, -80_200.0);
+        // Selection follows the row it was on
+        assert_eq!(selected_id(&browser), 201);
+    }
+
+    #[test]
+    fn test_paged_scroll_to_today_with_saved_layout() {
+        let (_dir, conn) = paged_db(400);
+        let mut browser =
+            RegisterBrowser::paged(&conn, RegisterQuery::default(), String::new(), vec![], 100)
+                .unwrap();
+        browser.scroll_to_today();
+        browser.apply_layout(BrowseLayout {
+            descending: true,
+            ..Default::default()
+        });
+        browser.fetch(&conn).unwrap();
+        // Every row is in the past, so the newest one is selected, at the top
+        assert_eq!(selected_id(&browser), 400);
+        assert_eq!(browser.offset + browser.selected, 0);
+    }
 }
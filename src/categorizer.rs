@@ -1,80 +1,368 @@
+use std::collections::{HashMap, HashSet};
+
 use regex::Regex;
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 
+use crate::anomaly;
+use crate::data_quality::{self, DateBounds};
 use crate::error::Result;
+use crate::settings::load_settings;
+
+/// A rule pattern prepared for matching many descriptions: the pattern is
+/// uppercased or its regex compiled once. Contains and starts-with ignore
+/// case; regexes match the description as written. An invalid regex or
+/// unknown match type matches nothing.
+pub enum Matcher {
+    Contains(String),
+    StartsWith(String),
+    Regex(Regex),
+    Never,
+}
+
+impl Matcher {
+    pub fn new(pattern: &str, match_type: &str) -> Self {
+        match match_type {
+            "contains" => Matcher::Contains(pattern.to_uppercase()),
+            "starts_with" => Matcher::StartsWith(pattern.to_uppercase()),
+            "regex" => Regex::new(pattern).map_or(Matcher::Never, Matcher::Regex),
+            _ => Matcher::Never,
+        }
+    }
 
-pub fn matches(description: &str, pattern: &str, match_type: &str) -> bool {
-    let desc_upper = description.to_uppercase();
-    let pat_upper = pattern.to_uppercase();
-    match match_type {
-        "contains" => desc_upper.contains(&pat_upper),
-        "starts_with" => desc_upper.starts_with(&pat_upper),
-        "regex" => Regex::new(pattern)
-            .map(|re| re.is_match(description))
-            .unwrap_or(false),
-        _ => false,
+    pub fn is_match(&self, description: &str) -> bool {
+        self.is_match_upper(description, &description.to_uppercase())
+    }
+
+    /// Match with the description already uppercased, so callers trying
+    /// many rules against one description only uppercase it once.
+    pub fn is_match_upper(&self, description: &str, upper: &str) -> bool {
+        match self {
+            Matcher::Contains(p) => upper.contains(p.as_str()),
+            Matcher::StartsWith(p) => upper.starts_with(p.as_str()),
+            Matcher::Regex(re) => re.is_match(description),
+            Matcher::Never => false,
+        }
     }
 }
 
+/// Non-P&L category for paying a credit card from a bank account.
+pub const CARD_PAYMENT_CATEGORY: &str = "Credit Card Payment";
+
+/// Most days allowed between a payment leaving the bank and the credit
+/// posting on the card.
+pub const CARD_PAYMENT_WINDOW_DAYS: i64 = 5;
+
 pub struct CategorizeResult {
     pub categorized: usize,
     pub still_flagged: usize,
+    /// Bank/card payment pairs matched (each pair is two categorized rows).
+    pub card_payments: usize,
+    /// Categorized transactions newly flagged by `flag_sign_mismatches()`.
+    pub sign_warnings: usize,
+    /// Transactions newly flagged by `anomaly::flag_anomalies()`.
+    pub anomalies: usize,
+    /// Transactions newly flagged by `data_quality::flag_out_of_range()`.
+    pub out_of_range: usize,
 }
 
-pub fn categorize_transactions(conn: &Connection) -> Result<CategorizeResult> {
-    let mut rule_stmt = conn.prepare(
+/// Prefix of the `flag_reason` set by `flag_sign_mismatches()`.
+pub const SIGN_MISMATCH_PREFIX: &str = "Sign mismatch";
+
+/// Why `amount` looks wrong for a category of `category_type`: money coming
+/// in on an expense (an unfiled refund?) or going out on income. None when
+/// the sign agrees or the category is neither income nor expense.
+pub fn sign_mismatch_reason(amount: f64, category: &str, category_type: &str) -> Option<String> {
+    match category_type {
+        "expense" if amount > 0.0 => Some(format!(
+            "{SIGN_MISMATCH_PREFIX}: money in, but {category} is an expense category"
+        )),
+        "income" if amount < 0.0 => Some(format!(
+            "{SIGN_MISMATCH_PREFIX}: money out, but {category} is an income category"
+        )),
+        _ => None,
+    }
+}
+
+/// Flag categorized transactions whose sign disagrees with their category's
+/// type. They keep their category, so this is a soft warning: reviewing one
+/// (or clearing its flag in the browser) sets `sign_ok` and it isn't flagged
+/// again. Returns the number newly flagged.
+pub fn flag_sign_mismatches(conn: &Connection) -> Result<usize> {
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.amount, c.name, c.category_type FROM transactions t \
+         JOIN categories c ON t.category_id = c.id \
+         WHERE t.is_flagged = 0 AND t.sign_ok = 0 AND t.deleted_at IS NULL \
+           AND ((c.category_type = 'expense' AND t.amount > 0) \
+             OR (c.category_type = 'income' AND t.amount < 0))",
+    )?;
+    let rows: Vec<(i64, f64, String, String)> = stmt
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut update =
+        conn.prepare("UPDATE transactions SET is_flagged = 1, flag_reason = ?1 WHERE id = ?2")?;
+    let mut flagged = 0;
+    for (id, amount, category, category_type) in rows {
+        if let Some(reason) = sign_mismatch_reason(amount, &category, &category_type) {
+            update.execute(rusqlite::params![reason, id])?;
+            flagged += 1;
+        }
+    }
+    Ok(flagged)
+}
+
+/// Pair uncategorized bank outflows with credit card credits of the same
+/// amount and currency posted within `CARD_PAYMENT_WINDOW_DAYS`, and file
+/// both sides under `CARD_PAYMENT_CATEGORY`. Closest dates pair first and
+/// each transaction joins at most one pair. Returns the number of pairs.
+pub fn match_card_payments(conn: &Connection) -> Result<usize> {
+    let category_id: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM categories WHERE name = ?1 AND is_active = 1",
+            [CARD_PAYMENT_CATEGORY],
+            |r| r.get(0),
+        )
+        .optional()?;
+    let Some(category_id) = category_id else {
+        return Ok(0);
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT b.id, c.id FROM transactions b \
+         JOIN accounts ba ON ba.id = b.account_id \
+         JOIN transactions c ON c.account_id != b.account_id \
+         JOIN accounts ca ON ca.id = c.account_id \
+         WHERE ba.account_type != 'credit_card' AND ca.account_type = 'credit_card' \
+           AND ba.currency = ca.currency \
+           AND b.amount < 0 AND c.amount > 0 AND abs(b.amount + c.amount) < 0.005 \
+           AND b.category_id IS NULL AND c.category_id IS NULL \
+           AND b.deleted_at IS NULL AND c.deleted_at IS NULL \
+           AND abs(julianday(c.date) - julianday(b.date)) <= ?1 \
+         ORDER BY abs(julianday(c.date) - julianday(b.date)), b.date, b.id, c.id",
+    )?;
+    let candidates: Vec<(i64, i64)> = stmt
+        .query_map([CARD_PAYMENT_WINDOW_DAYS], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut update = conn.prepare(
+        "UPDATE transactions SET category_id = ?1, is_flagged = 0, flag_reason = NULL \
+         WHERE id IN (?2, ?3)",
+    )?;
+    let mut used = HashSet::new();
+    let mut pairs = 0usize;
+    for (bank_id, card_id) in candidates {
+        if used.contains(&bank_id) || used.contains(&card_id) {
+            continue;
+        }
+        update.execute(rusqlite::params![category_id, bank_id, card_id])?;
+        used.insert(bank_id);
+        used.insert(card_id);
+        pairs += 1;
+    }
+    Ok(pairs)
+}
+
+/// An active rule with its pattern compiled, in the order rules are tried.
+struct CompiledRule {
+    id: i64,
+    matcher: Matcher,
+    vendor: Option<String>,
+    category_id: i64,
+}
+
+fn load_compiled_rules(conn: &Connection) -> Result<Vec<CompiledRule>> {
+    let mut stmt = conn.prepare(
         "SELECT id, pattern, match_type, vendor, category_id FROM rules \
-         WHERE is_active = 1 ORDER BY priority DESC",
+         WHERE is_active = 1 ORDER BY priority DESC, id",
     )?;
-    let rules: Vec<(i64, String, String, Option<String>, i64)> = rule_stmt
+    let rules = stmt
         .query_map([], |row| {
-            Ok((
-                row.get(0)?,
-                row.get(1)?,
-                row.get(2)?,
-                row.get(3)?,
-                row.get(4)?,
-            ))
+            let pattern: String = row.get(1)?;
+            let match_type: String = row.get(2)?;
+            Ok(CompiledRule {
+                id: row.get(0)?,
+                matcher: Matcher::new(&pattern, &match_type),
+                vendor: row.get(3)?,
+                category_id: row.get(4)?,
+            })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rules)
+}
 
-    let mut txn_stmt =
-        conn.prepare("SELECT id, description FROM transactions WHERE category_id IS NULL")?;
-    let flagged: Vec<(i64, String)> = txn_stmt
+/// The rule `categorize_transactions()` would file a description under.
+pub struct RuleMatch {
+    pub rule_id: i64,
+    pub pattern: String,
+    pub category: String,
+}
+
+/// The first active rule matching each of `descriptions`, in rule order,
+/// without writing anything — for import dry runs.
+pub fn preview_matches(conn: &Connection, descriptions: &[&str]) -> Result<Vec<Option<RuleMatch>>> {
+    let rules = load_compiled_rules(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT r.id, r.pattern, c.name FROM rules r JOIN categories c ON c.id = r.category_id",
+    )?;
+    let names: HashMap<i64, (String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?
+        .collect::<std::result::Result<_, _>>()?;
+
+    Ok(descriptions
+        .iter()
+        .map(|description| {
+            let upper = description.to_uppercase();
+            let rule = rules
+                .iter()
+                .find(|rule| rule.matcher.is_match_upper(description, &upper))?;
+            let (pattern, category) = names.get(&rule.id)?.clone();
+            Some(RuleMatch {
+                rule_id: rule.id,
+                pattern,
+                category,
+            })
+        })
+        .collect())
+}
+
+/// Categorize every uncategorized transaction. Rules are loaded and
+/// compiled once and matched in memory (each distinct description only
+/// once), and all updates are written in a single transaction, so a large
+/// import categorizes in one pass instead of a query per row and rule.
+/// Unusual-transaction checks follow the `anomaly_checks` setting, and new
+/// transactions dated outside the `max_future_days` and `date_epoch`
+/// settings are flagged.
+pub fn categorize_transactions(conn: &Connection) -> Result<CategorizeResult> {
+    let settings = load_settings();
+    let today = chrono::Local::now().date_naive();
+    categorize_with(
+        conn,
+        settings.anomaly_checks,
+        &DateBounds::from_settings(&settings, today),
+    )
+}
+
+/// `categorize_transactions()`, running the unusual-transaction checks only
+/// when `anomaly_checks` is set and flagging dates outside `dates`.
+pub fn categorize_with(
+    conn: &Connection,
+    anomaly_checks: bool,
+    dates: &DateBounds,
+) -> Result<CategorizeResult> {
+    let tx = conn.unchecked_transaction()?;
+
+    // Payment pairs first: matching both sides is stronger evidence than a
+    // description pattern on either one
+    let card_payments = match_card_payments(&tx)?;
+
+    let rules = load_compiled_rules(&tx)?;
+
+    let mut txn_stmt = tx.prepare(
+        "SELECT id, description FROM transactions \
+         WHERE category_id IS NULL AND deleted_at IS NULL",
+    )?;
+    let uncategorized: Vec<(i64, String)> = txn_stmt
         .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
         .collect::<std::result::Result<Vec<_>, _>>()?;
+    drop(txn_stmt);
 
-    let mut categorized = 0usize;
+    let mut update = tx.prepare(
+        "UPDATE transactions SET category_id = ?1, vendor = ?2, is_flagged = 0, \
+         flag_reason = NULL WHERE id = ?3",
+    )?;
+    // Index of the first matching rule per distinct description
+    let mut first_match: HashMap<&str, Option<usize>> = HashMap::new();
+    let mut hits: HashMap<i64, i64> = HashMap::new();
+    let mut categorized = card_payments * 2;
     let mut still_flagged = 0usize;
 
-    for (txn_id, description) in &flagged {
-        let mut matched = false;
-        for (rule_id, pattern, match_type, vendor, category_id) in &rules {
-            if matches(description, pattern, match_type) {
-                conn.execute(
-                    "UPDATE transactions SET category_id = ?1, vendor = ?2, is_flagged = 0, flag_reason = NULL WHERE id = ?3",
-                    rusqlite::params![category_id, vendor, txn_id],
-                )?;
-                conn.execute(
-                    "UPDATE rules SET hit_count = hit_count + 1 WHERE id = ?1",
-                    [rule_id],
-                )?;
+    for (txn_id, description) in &uncategorized {
+        let matched = *first_match.entry(description.as_str()).or_insert_with(|| {
+            let upper = description.to_uppercase();
+            rules
+                .iter()
+                .position(|rule| rule.matcher.is_match_upper(description, &upper))
+        });
+        match matched {
+            Some(idx) => {
+                let rule = &rules[idx];
+                update.execute(rusqlite::params![rule.category_id, rule.vendor, txn_id])?;
+                *hits.entry(rule.id).or_default() += 1;
                 categorized += 1;
-                matched = true;
-                break;
             }
-        }
-        if !matched {
-            still_flagged += 1;
+            None => still_flagged += 1,
         }
     }
+    drop(update);
+
+    let mut hit_stmt = tx.prepare("UPDATE rules SET hit_count = hit_count + ?1 WHERE id = ?2")?;
+    for (rule_id, count) in &hits {
+        hit_stmt.execute([count, rule_id])?;
+    }
+    drop(hit_stmt);
+
+    let sign_warnings = flag_sign_mismatches(&tx)?;
+    let out_of_range = data_quality::flag_out_of_range(&tx, dates)?;
+    let anomalies = if anomaly_checks {
+        anomaly::flag_anomalies(&tx)?
+    } else {
+        0
+    };
+    anomaly::mark_checked(&tx)?;
+    tx.commit()?;
 
     Ok(CategorizeResult {
         categorized,
         still_flagged,
+        card_payments,
+        sign_warnings,
+        anomalies,
+        out_of_range,
     })
 }
 
+/// An active rule that already files the same pattern under the same category.
+#[derive(Debug)]
+pub struct DuplicateRule {
+    pub id: i64,
+    pub pattern: String,
+    pub match_type: String,
+    pub category: String,
+}
+
+/// Find an active rule whose pattern matches `pattern` case-insensitively and
+/// that files under `category_id`. `exclude` skips the rule being edited.
+pub fn find_duplicate_rule(
+    conn: &Connection,
+    pattern: &str,
+    category_id: i64,
+    exclude: Option<i64>,
+) -> Result<Option<DuplicateRule>> {
+    let dup = conn
+        .query_row(
+            "SELECT r.id, r.pattern, r.match_type, c.name FROM rules r \
+             JOIN categories c ON r.category_id = c.id \
+             WHERE r.is_active = 1 AND r.category_id = ?1 \
+               AND LOWER(TRIM(r.pattern)) = LOWER(TRIM(?2)) AND r.id != ?3 \
+             ORDER BY r.id LIMIT 1",
+            rusqlite::params![category_id, pattern, exclude.unwrap_or(-1)],
+            |row| {
+                Ok(DuplicateRule {
+                    id: row.get(0)?,
+                    pattern: row.get(1)?,
+                    match_type: row.get(2)?,
+                    category: row.get(3)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(dup)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +455,74 @@ mod tests {
         assert_eq!(cat_name, "Client Services");
     }
 
+    #[test]
+    fn test_invalid_regex_matches_nothing() {
+        assert!(!Matcher::new("([", "regex").is_match("AWS 123"));
+        assert!(!Matcher::new("AWS", "bogus").is_match("AWS 123"));
+        let m = Matcher::new("aws", "starts_with");
+        assert!(m.is_match("Aws Services"));
+        assert!(!m.is_match("Paid AWS"));
+    }
+
+    #[test]
+    fn test_large_batch_categorized_in_one_pass() {
+        let (_dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Test', 'checking')",
+            [],
+        )
+        .unwrap();
+        let tx = conn.unchecked_transaction().unwrap();
+        {
+            let mut insert = tx
+                .prepare(
+                    "INSERT INTO transactions (account_id, date, description, amount, is_flagged) \
+                     VALUES (1, '2025-01-15', ?1, -10.0, 1)",
+                )
+                .unwrap();
+            for i in 0..20_000 {
+                let desc = match i % 4 {
+                    0 => format!("ADOBE *CC {i}"),
+                    1 => format!("AWS BILL {i}"),
+                    2 => "GITHUB".to_string(),
+                    _ => format!("MYSTERY {i}"),
+                };
+                insert.execute([desc]).unwrap();
+            }
+        }
+        tx.commit().unwrap();
+        for i in 0..200 {
+            add_rule(
+                &conn,
+                &format!("NOMATCH{i}"),
+                "contains",
+                "Software & Subscriptions",
+                0,
+            );
+        }
+        add_rule(&conn, "ADOBE", "contains", "Software & Subscriptions", 0);
+        add_rule(
+            &conn,
+            r"^AWS BILL \d+$",
+            "regex",
+            "Hosting & Infrastructure",
+            0,
+        );
+        add_rule(&conn, "GIT", "starts_with", "Software & Subscriptions", 0);
+
+        let result = categorize_transactions(&conn).unwrap();
+        assert_eq!(result.categorized, 15_000);
+        assert_eq!(result.still_flagged, 5_000);
+        let hits: i64 = conn
+            .query_row(
+                "SELECT hit_count FROM rules WHERE pattern = 'GIT'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(hits, 5_000);
+    }
+
     #[test]
     fn test_unmatched_stays_flagged() {
         let (_dir, conn) = test_db();
@@ -187,4 +543,216 @@ mod tests {
             .unwrap();
         assert_eq!(hit_count, 2);
     }
+
+    fn add_txn(conn: &Connection, account_id: i64, date: &str, desc: &str, amount: f64) -> i64 {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, is_flagged, flag_reason) \
+             VALUES (?1, ?2, ?3, ?4, 1, 'No matching rule')",
+            rusqlite::params![account_id, date, desc, amount],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn add_account(conn: &Connection, name: &str, account_type: &str) -> i64 {
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES (?1, ?2)",
+            [name, account_type],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn category_of(conn: &Connection, txn_id: i64) -> Option<String> {
+        conn.query_row(
+            "SELECT c.name FROM transactions t JOIN categories c ON t.category_id = c.id \
+             WHERE t.id = ?1",
+            [txn_id],
+            |r| r.get(0),
+        )
+        .optional()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_card_payment_pairs_matched() {
+        let (_dir, conn) = test_db();
+        let checking = add_account(&conn, "Checking", "checking");
+        let card = add_account(&conn, "Visa", "credit_card");
+        let paid = add_txn(&conn, checking, "2025-03-01", "ONLINE PMT VISA", -500.0);
+        let credit = add_txn(&conn, card, "2025-03-03", "PAYMENT - THANK YOU", 500.0);
+        // Same amount but too far apart to be the same payment
+        let late = add_txn(&conn, card, "2025-03-20", "PAYMENT - THANK YOU", 250.0);
+        let early = add_txn(&conn, checking, "2025-03-02", "ONLINE PMT VISA", -250.0);
+        // A card refund with no bank-side payment stays flagged
+        let refund = add_txn(&conn, card, "2025-03-04", "REFUND", 42.0);
+
+        let result = categorize_transactions(&conn).unwrap();
+        assert_eq!(result.card_payments, 1);
+        assert_eq!(result.categorized, 2);
+        assert_eq!(result.still_flagged, 3);
+        assert_eq!(
+            category_of(&conn, paid).as_deref(),
+            Some(CARD_PAYMENT_CATEGORY)
+        );
+        assert_eq!(
+            category_of(&conn, credit).as_deref(),
+            Some(CARD_PAYMENT_CATEGORY)
+        );
+        for id in [late, early, refund] {
+            assert_eq!(category_of(&conn, id), None);
+        }
+        let flagged: i64 = conn
+            .query_row(
+                "SELECT is_flagged FROM transactions WHERE id = ?1",
+                [paid],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(flagged, 0);
+    }
+
+    #[test]
+    fn test_card_payment_pairs_closest_date_first() {
+        let (_dir, conn) = test_db();
+        let checking = add_account(&conn, "Checking", "checking");
+        let card = add_account(&conn, "Visa", "credit_card");
+        let first = add_txn(&conn, checking, "2025-03-01", "PMT", -100.0);
+        let second = add_txn(&conn, checking, "2025-03-05", "PMT", -100.0);
+        let credit = add_txn(&conn, card, "2025-03-05", "PAYMENT", 100.0);
+
+        assert_eq!(match_card_payments(&conn).unwrap(), 1);
+        assert_eq!(
+            category_of(&conn, second).as_deref(),
+            Some(CARD_PAYMENT_CATEGORY)
+        );
+        assert_eq!(
+            category_of(&conn, credit).as_deref(),
+            Some(CARD_PAYMENT_CATEGORY)
+        );
+        assert_eq!(category_of(&conn, first), None);
+    }
+
+    #[test]
+    fn test_card_payments_excluded_from_pnl() {
+        let (_dir, conn) = test_db();
+        let checking = add_account(&conn, "Checking", "checking");
+        let card = add_account(&conn, "Visa", "credit_card");
+        add_txn(&conn, checking, "2025-03-01", "PMT", -500.0);
+        add_txn(&conn, card, "2025-03-02", "PAYMENT", 500.0);
+        categorize_transactions(&conn).unwrap();
+        let pnl = crate::reports::get_pnl(&conn, Some(2025), None, None, None, None, &[]).unwrap();
+        assert_eq!(pnl.total_expenses, 0.0);
+        assert_eq!(pnl.total_income, 0.0);
+    }
+
+    #[test]
+    fn test_find_duplicate_rule_ignores_case() {
+        let (_dir, conn) = test_db();
+        add_rule(&conn, "Adobe", "contains", "Software & Subscriptions", 0);
+        let software: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        let dup = find_duplicate_rule(&conn, " ADOBE ", software, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(dup.pattern, "Adobe");
+        assert_eq!(dup.category, "Software & Subscriptions");
+
+        // The rule itself, another category, or an inactive rule is not a duplicate
+        assert!(find_duplicate_rule(&conn, "adobe", software, Some(dup.id))
+            .unwrap()
+            .is_none());
+        assert!(find_duplicate_rule(&conn, "adobe", software + 1, None)
+            .unwrap()
+            .is_none());
+        conn.execute("UPDATE rules SET is_active = 0", []).unwrap();
+        assert!(find_duplicate_rule(&conn, "adobe", software, None)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_sign_mismatches_flagged_once() {
+        let (_dir, conn) = test_db();
+        let checking = add_account(&conn, "Checking", "checking");
+        add_rule(&conn, "ADOBE", "contains", "Software & Subscriptions", 0);
+        add_rule(&conn, "CLIENT", "contains", "Client Services", 0);
+        let charge = add_txn(&conn, checking, "2025-03-01", "ADOBE CC", -54.99);
+        let refund = add_txn(&conn, checking, "2025-03-05", "ADOBE REFUND", 54.99);
+        let bounce = add_txn(
+            &conn,
+            checking,
+            "2025-03-07",
+            "CLIENT ACME REVERSAL",
+            -800.0,
+        );
+
+        let result = categorize_transactions(&conn).unwrap();
+        assert_eq!(result.categorized, 3);
+        assert_eq!(result.sign_warnings, 2);
+
+        let flag = |id: i64| -> (bool, Option<String>) {
+            conn.query_row(
+                "SELECT is_flagged, flag_reason FROM transactions WHERE id = ?1",
+                [id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap()
+        };
+        assert_eq!(flag(charge), (false, None));
+        let (flagged, reason) = flag(refund);
+        assert!(flagged);
+        assert_eq!(
+            reason.as_deref(),
+            Some("Sign mismatch: money in, but Software & Subscriptions is an expense category")
+        );
+        // The category stays; it's only a warning
+        assert_eq!(
+            category_of(&conn, refund).as_deref(),
+            Some("Software & Subscriptions")
+        );
+        assert!(flag(bounce).1.unwrap().contains("money out"));
+
+        // Dismissing the flag or confirming the category in review sticks
+        crate::reviewer::toggle_transaction_flag(&conn, refund).unwrap();
+        let client: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Client Services'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        crate::reviewer::apply_review(&conn, &[bounce], client, None, false, None).unwrap();
+        assert_eq!(flag_sign_mismatches(&conn).unwrap(), 0);
+        assert!(!flag(refund).0);
+        assert!(!flag(bounce).0);
+    }
+
+    #[test]
+    fn test_preview_matches_reports_first_rule_without_writing() {
+        let (_dir, conn) = test_db();
+        setup_account_and_txns(&conn, &["ADOBE CREATIVE CLOUD"]);
+        add_rule(&conn, "adobe", "contains", "Software & Subscriptions", 0);
+        add_rule(&conn, "adobe creative", "contains", "Office Expense", 5);
+
+        let matches = preview_matches(&conn, &["Adobe Creative Cloud", "UNKNOWN"]).unwrap();
+        let first = matches[0].as_ref().unwrap();
+        assert_eq!(first.category, "Office Expense");
+        assert_eq!(first.pattern, "adobe creative");
+        assert!(matches[1].is_none());
+
+        let uncategorized: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM transactions WHERE category_id IS NULL",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(uncategorized, 1);
+    }
 }
@@ -6,10 +6,14 @@ use ratatui::{
     Frame,
 };
 use rusqlite::Connection;
+use zeroize::Zeroize;
 
 use crate::cli::accounts;
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
 use crate::models::Account;
+use crate::settings::get_data_dir;
 use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
+use crate::vault::{self, RevealedNumbers};
 
 const ACCOUNT_TYPES: &[&str] = &["checking", "credit_card", "line_of_credit", "payroll"];
 
@@ -18,6 +22,16 @@ const NAME_IDX: usize = 0;
 const TYPE_IDX: usize = 1;
 const INST_IDX: usize = 2;
 const LAST_IDX: usize = 3;
+const ADD_OPENING_BALANCE_IDX: usize = 4;
+const ADD_OPENING_DATE_IDX: usize = 5;
+
+// Field indices for AccountForm::new_opening()
+const OPENING_BALANCE_IDX: usize = 0;
+const OPENING_DATE_IDX: usize = 1;
+
+// Field indices for AccountForm::new_numbers()
+const ACCOUNT_NUMBER_IDX: usize = 0;
+const ROUTING_NUMBER_IDX: usize = 1;
 
 pub enum AccountAction {
     Continue,
@@ -28,9 +42,27 @@ enum Screen {
     List,
     Add(AccountForm),
     Rename(AccountForm),
+    Numbers(AccountForm),
+    Opening(AccountForm),
+    Reveal(RevealPrompt),
     ConfirmDelete,
 }
 
+/// Password confirmation before showing full account numbers. The typed
+/// password is wiped on drop; revealed numbers are dropped (and wiped) as
+/// soon as the screen closes.
+struct RevealPrompt {
+    password: String,
+    revealed: Option<RevealedNumbers>,
+    error: Option<String>,
+}
+
+impl Drop for RevealPrompt {
+    fn drop(&mut self) {
+        self.password.zeroize();
+    }
+}
+
 struct AccountForm {
     fields: Vec<FormField>,
     focused: usize,
@@ -44,12 +76,24 @@ struct FormField {
 
 enum FieldKind {
     Text,
+    /// Text shown masked except for the last four characters.
+    Secret,
     Selector {
         options: Vec<String>,
         selected: usize,
     },
 }
 
+impl Drop for AccountForm {
+    fn drop(&mut self) {
+        for field in &mut self.fields {
+            if let FieldKind::Secret = field.kind {
+                field.value.zeroize();
+            }
+        }
+    }
+}
+
 impl AccountForm {
     fn new_add() -> Self {
         Self {
@@ -77,6 +121,79 @@ impl AccountForm {
                     value: String::new(),
                     kind: FieldKind::Text,
                 },
+                FormField {
+                    label: "Opening Bal.",
+                    value: String::new(),
+                    kind: FieldKind::Text,
+                },
+                FormField {
+                    label: "Opening Date",
+                    value: String::new(),
+                    kind: FieldKind::Text,
+                },
+            ],
+            focused: 0,
+        }
+    }
+
+    fn new_opening(balance: f64, date: Option<&str>) -> Self {
+        Self {
+            fields: vec![
+                FormField {
+                    label: "Opening Bal.",
+                    value: format!("{balance:.2}"),
+                    kind: FieldKind::Text,
+                },
+                FormField {
+                    label: "Opening Date",
+                    value: date.unwrap_or_default().to_string(),
+                    kind: FieldKind::Text,
+                },
+            ],
+            focused: 0,
+        }
+    }
+
+    /// The opening balance and date fields: a blank balance is zero, a
+    /// blank date means from the beginning.
+    fn opening(
+        &self,
+        balance_idx: usize,
+        date_idx: usize,
+    ) -> Result<(f64, Option<String>), String> {
+        let balance = self.fields[balance_idx]
+            .value
+            .trim()
+            .replace([',', '$'], "");
+        let balance = if balance.is_empty() {
+            0.0
+        } else {
+            balance
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid opening balance: {balance}"))?
+        };
+        let date = self.fields[date_idx].value.trim();
+        if date.is_empty() {
+            return Ok((balance, None));
+        }
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| "Opening date must be YYYY-MM-DD".to_string())?;
+        Ok((balance, Some(date.to_string())))
+    }
+
+    fn new_numbers() -> Self {
+        Self {
+            fields: vec![
+                FormField {
+                    label: "Account #",
+                    value: String::new(),
+                    kind: FieldKind::Secret,
+                },
+                FormField {
+                    label: "Routing #",
+                    value: String::new(),
+                    kind: FieldKind::Secret,
+                },
             ],
             focused: 0,
         }
@@ -102,6 +219,8 @@ pub struct AccountManager {
     /// Remaining keypresses before the status message is cleared.
     status_ttl: u8,
     greeting: String,
+    /// Set by a save, until the dashboard is told.
+    changed: bool,
 }
 
 impl AccountManager {
@@ -114,9 +233,15 @@ impl AccountManager {
             status_message: None,
             status_ttl: 0,
             greeting: greeting.to_string(),
+            changed: false,
         }
     }
 
+    /// Open straight into the add-account form.
+    pub fn start_add(&mut self) {
+        self.screen = Screen::Add(AccountForm::new_add());
+    }
+
     fn reload(&mut self, conn: &Connection) {
         self.accounts = accounts::list_accounts(conn).unwrap_or_default();
         if !self.accounts.is_empty() {
@@ -128,9 +253,11 @@ impl AccountManager {
 
     pub fn draw(&self, frame: &mut Frame) {
         match &self.screen {
-            Screen::List | Screen::ConfirmDelete => self.draw_list(frame),
+            Screen::List | Screen::ConfirmDelete | Screen::Reveal(_) => self.draw_list(frame),
             Screen::Add(form) => self.draw_form(frame, "Add Account", form),
             Screen::Rename(form) => self.draw_form(frame, "Rename Account", form),
+            Screen::Numbers(form) => self.draw_form(frame, "Account & Routing Numbers", form),
+            Screen::Opening(form) => self.draw_form(frame, "Opening Balance", form),
         }
     }
 
@@ -148,7 +275,7 @@ impl AccountManager {
 
         // Header
         frame.render_widget(
-            Paragraph::new(format!(" Nigel: {}", self.greeting)).style(HEADER_STYLE),
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
             header_area,
         );
 
@@ -174,7 +301,7 @@ impl AccountManager {
                 Span::styled(
                     format!(
                         "{:<24} {:<18} {:<20} {}",
-                        "Name", "Type", "Institution", "Last Four"
+                        "Name", "Type", "Institution", "Account #"
                     ),
                     Style::default()
                         .fg(Color::DarkGray)
@@ -184,23 +311,76 @@ impl AccountManager {
 
             for (i, account) in self.accounts.iter().enumerate() {
                 let marker = if i == self.selection { " > " } else { "   " };
-                let style = if i == self.selection {
+                let mut style = if i == self.selection {
                     Style::default().add_modifier(Modifier::BOLD)
                 } else {
                     Style::default()
                 };
+                if account.is_archived {
+                    style = style.fg(Color::DarkGray);
+                }
                 let inst = account.institution.as_deref().unwrap_or("");
-                let last = account.last_four.as_deref().unwrap_or("");
+                let last = account
+                    .last_four
+                    .as_deref()
+                    .map(vault::mask)
+                    .unwrap_or_default();
+                let archived = if account.is_archived {
+                    "  (archived)"
+                } else {
+                    ""
+                };
                 lines.push(Line::from(Span::styled(
                     format!(
-                        "{marker}{:<24} {:<18} {:<20} {}",
-                        account.name, account.account_type, inst, last
+                        "{marker}{:<24} {:<18} {:<20} {last}{archived}",
+                        account.name, account.account_type, inst
                     ),
                     style,
                 )));
             }
         }
 
+        if let Screen::Reveal(prompt) = &self.screen {
+            if let Some(account) = self.accounts.get(self.selection) {
+                lines.push(Line::from(""));
+                match &prompt.revealed {
+                    None => {
+                        lines.push(Line::from(vec![
+                            Span::styled(
+                                format!("   Password to reveal '{}': ", account.name),
+                                Style::default().fg(Color::Yellow),
+                            ),
+                            Span::styled(
+                                format!("{}_", "*".repeat(prompt.password.chars().count())),
+                                Style::default().fg(Color::Cyan),
+                            ),
+                        ]));
+                        if let Some(err) = &prompt.error {
+                            lines.push(Line::from(Span::styled(
+                                format!("   {err}"),
+                                Style::default().fg(Color::Red),
+                            )));
+                        }
+                    }
+                    Some(numbers) => {
+                        let show = |n: &Option<zeroize::Zeroizing<String>>| {
+                            n.as_deref()
+                                .map(|s| s.to_string())
+                                .unwrap_or_else(|| "(not stored)".into())
+                        };
+                        lines.push(Line::from(format!(
+                            "   Account number  {}",
+                            show(&numbers.account_number)
+                        )));
+                        lines.push(Line::from(format!(
+                            "   Routing number  {}",
+                            show(&numbers.routing_number)
+                        )));
+                    }
+                }
+            }
+        }
+
         // Delete confirmation inline
         if let Screen::ConfirmDelete = &self.screen {
             if let Some(account) = self.accounts.get(self.selection) {
@@ -225,11 +405,20 @@ impl AccountManager {
                 Paragraph::new(" y=confirm  n=cancel").style(FOOTER_STYLE),
                 hints_area,
             );
+        } else if let Screen::Reveal(prompt) = &self.screen {
+            let hint = if prompt.revealed.is_some() {
+                " Esc=hide"
+            } else {
+                " Enter=reveal  Esc=cancel"
+            };
+            frame.render_widget(Paragraph::new(hint).style(FOOTER_STYLE), hints_area);
         } else {
-            frame.render_widget(
-                Paragraph::new(" a=add  r=rename  d=delete  Esc=back  q=quit").style(FOOTER_STYLE),
-                hints_area,
-            );
+            let hints = if crate::db::is_read_only() {
+                " v=reveal  Esc=back  q=quit"
+            } else {
+                " a=add  r=rename  o=opening  n=numbers  v=reveal  x=archive  d=delete  Esc=back  q=quit"
+            };
+            frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
         }
     }
 
@@ -246,7 +435,7 @@ impl AccountManager {
         .areas(area);
 
         frame.render_widget(
-            Paragraph::new(format!(" Nigel: {}", self.greeting)).style(HEADER_STYLE),
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
             header_area,
         );
 
@@ -285,6 +474,23 @@ impl AccountManager {
                         ),
                     ]));
                 }
+                FieldKind::Secret => {
+                    let cursor = if is_focused { "_" } else { "" };
+                    let n = field.value.chars().count();
+                    let tail: String = field.value.chars().skip(n.saturating_sub(4)).collect();
+                    let masked = format!("{}{tail}", "*".repeat(n.saturating_sub(4)));
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("   {:<14} ", field.label), label_style),
+                        Span::styled(
+                            format!("{masked}{cursor}"),
+                            if is_focused {
+                                Style::default().fg(Color::Cyan)
+                            } else {
+                                Style::default()
+                            },
+                        ),
+                    ]));
+                }
                 FieldKind::Selector { options, selected } => {
                     let arrows = if is_focused {
                         ("< ", " >")
@@ -344,6 +550,9 @@ impl AccountManager {
             Screen::List => self.handle_list_key(code, conn),
             Screen::Add(_) => self.handle_form_key(code, conn, FormMode::Add),
             Screen::Rename(_) => self.handle_form_key(code, conn, FormMode::Rename),
+            Screen::Numbers(_) => self.handle_form_key(code, conn, FormMode::Numbers),
+            Screen::Opening(_) => self.handle_form_key(code, conn, FormMode::Opening),
+            Screen::Reveal(_) => self.handle_reveal_key(code, conn),
             Screen::ConfirmDelete => self.handle_delete_key(code, conn),
         }
     }
@@ -355,6 +564,9 @@ impl AccountManager {
     ) -> AccountAction {
         use crossterm::event::KeyCode::*;
         match code {
+            Char('a' | 'r' | 'o' | 'n' | 'x' | 'd') if crate::db::is_read_only() => {
+                self.set_status(crate::tui::READ_ONLY_STATUS.into());
+            }
             Up => {
                 self.selection = self.selection.saturating_sub(1);
             }
@@ -363,14 +575,51 @@ impl AccountManager {
                     self.selection = (self.selection + 1).min(self.accounts.len() - 1);
                 }
             }
-            Char('a') => {
-                self.screen = Screen::Add(AccountForm::new_add());
-            }
+            Char('a') => self.start_add(),
             Char('r') => {
                 if let Some(account) = self.accounts.get(self.selection) {
                     self.screen = Screen::Rename(AccountForm::new_rename(&account.name));
                 }
             }
+            Char('o') => {
+                if let Some(account) = self.accounts.get(self.selection) {
+                    self.screen = Screen::Opening(AccountForm::new_opening(
+                        account.opening_balance,
+                        account.opening_date.as_deref(),
+                    ));
+                }
+            }
+            Char('n') if !self.accounts.is_empty() => {
+                match vault::require_encrypted(&get_data_dir().join("nigel.db")) {
+                    Ok(()) => self.screen = Screen::Numbers(AccountForm::new_numbers()),
+                    Err(e) => self.set_status(e.to_string()),
+                }
+            }
+            Char('x') => {
+                if let Some(account) = self.accounts.get(self.selection) {
+                    let archive = !account.is_archived;
+                    let name = account.name.clone();
+                    match accounts::set_archived(conn, account.id, archive) {
+                        Ok(()) => {
+                            self.reload(conn);
+                            self.changed = true;
+                            self.set_status(if archive {
+                                format!("Archived {name}")
+                            } else {
+                                format!("Unarchived {name}")
+                            });
+                        }
+                        Err(e) => self.set_status(format!("Error: {e}")),
+                    }
+                }
+            }
+            Char('v') if !self.accounts.is_empty() => {
+                self.screen = Screen::Reveal(RevealPrompt {
+                    password: String::new(),
+                    revealed: None,
+                    error: None,
+                });
+            }
             Char('d') => {
                 if !self.accounts.is_empty() {
                     // Check transaction count first to give immediate feedback
@@ -412,7 +661,7 @@ impl AccountManager {
 
         // We need to temporarily take the screen to get mutable access to the form
         let form = match &mut self.screen {
-            Screen::Add(f) | Screen::Rename(f) => f,
+            Screen::Add(f) | Screen::Rename(f) | Screen::Numbers(f) | Screen::Opening(f) => f,
             _ => return AccountAction::Continue,
         };
 
@@ -452,12 +701,12 @@ impl AccountManager {
                 }
             }
             Char(c) => {
-                if let FieldKind::Text = &form.fields[form.focused].kind {
+                if let FieldKind::Text | FieldKind::Secret = &form.fields[form.focused].kind {
                     form.fields[form.focused].value.push(c);
                 }
             }
             Backspace => {
-                if let FieldKind::Text = &form.fields[form.focused].kind {
+                if let FieldKind::Text | FieldKind::Secret = &form.fields[form.focused].kind {
                     form.fields[form.focused].value.pop();
                 }
             }
@@ -488,15 +737,34 @@ impl AccountManager {
                             Some(v)
                         }
                     };
-                    match accounts::add_account(
+                    let opening = match form.opening(ADD_OPENING_BALANCE_IDX, ADD_OPENING_DATE_IDX)
+                    {
+                        Ok(opening) => opening,
+                        Err(msg) => {
+                            self.set_status(msg);
+                            return AccountAction::Continue;
+                        }
+                    };
+                    let added = accounts::add_account(
                         conn,
                         &name,
                         &acct_type,
                         institution.as_deref(),
                         last_four.as_deref(),
-                    ) {
+                    )
+                    .and_then(|()| match opening {
+                        (0.0, None) => Ok(()),
+                        (balance, date) => accounts::set_opening_balance(
+                            conn,
+                            conn.last_insert_rowid(),
+                            balance,
+                            date.as_deref(),
+                        ),
+                    });
+                    match added {
                         Ok(()) => {
                             self.reload(conn);
+                            self.changed = true;
                             self.screen = Screen::List;
                             self.set_status(format!("Added account: {name}"));
                         }
@@ -505,12 +773,65 @@ impl AccountManager {
                         }
                     }
                 }
+                FormMode::Numbers => {
+                    let Some(account) = self.accounts.get(self.selection) else {
+                        return AccountAction::Continue;
+                    };
+                    let (id, name) = (account.id, account.name.clone());
+                    let non_empty = |i: usize| {
+                        let v = form.fields[i].value.trim();
+                        (!v.is_empty()).then(|| v.to_string())
+                    };
+                    let number = non_empty(ACCOUNT_NUMBER_IDX);
+                    let routing = non_empty(ROUTING_NUMBER_IDX);
+                    let result =
+                        vault::set_numbers(conn, id, number.as_deref(), routing.as_deref());
+                    for mut v in [number, routing].into_iter().flatten() {
+                        v.zeroize();
+                    }
+                    match result {
+                        Ok(()) => {
+                            self.reload(conn);
+                            self.changed = true;
+                            self.screen = Screen::List;
+                            self.set_status(format!("Saved numbers for {name}"));
+                        }
+                        Err(e) => self.set_status(e.to_string()),
+                    }
+                }
+                FormMode::Opening => {
+                    let Some(account) = self.accounts.get(self.selection) else {
+                        return AccountAction::Continue;
+                    };
+                    let (id, name) = (account.id, account.name.clone());
+                    let (balance, date) = match form.opening(OPENING_BALANCE_IDX, OPENING_DATE_IDX)
+                    {
+                        Ok(opening) => opening,
+                        Err(msg) => {
+                            self.set_status(msg);
+                            return AccountAction::Continue;
+                        }
+                    };
+                    match accounts::set_opening_balance(conn, id, balance, date.as_deref()) {
+                        Ok(()) => {
+                            self.reload(conn);
+                            self.changed = true;
+                            self.screen = Screen::List;
+                            self.set_status(format!(
+                                "Opening balance for {name}: {}",
+                                accounts::opening_label(balance, date.as_deref())
+                            ));
+                        }
+                        Err(e) => self.set_status(e.to_string()),
+                    }
+                }
                 FormMode::Rename => {
                     let new_name = form.fields[NAME_IDX].value.trim().to_string();
                     if let Some(account) = self.accounts.get(self.selection) {
                         match accounts::rename_account(conn, account.id, &new_name) {
                             Ok(()) => {
                                 self.reload(conn);
+                                self.changed = true;
                                 self.screen = Screen::List;
                                 self.set_status(format!("Renamed to: {new_name}"));
                             }
@@ -526,6 +847,45 @@ impl AccountManager {
         AccountAction::Continue
     }
 
+    fn handle_reveal_key(
+        &mut self,
+        code: crossterm::event::KeyCode,
+        conn: &Connection,
+    ) -> AccountAction {
+        use crossterm::event::KeyCode::*;
+        let Screen::Reveal(prompt) = &mut self.screen else {
+            return AccountAction::Continue;
+        };
+        if prompt.revealed.is_some() {
+            // Any key hides the numbers again
+            self.screen = Screen::List;
+            return AccountAction::Continue;
+        }
+        match code {
+            Esc => self.screen = Screen::List,
+            Char(c) => {
+                prompt.password.push(c);
+                prompt.error = None;
+            }
+            Backspace => {
+                prompt.password.pop();
+            }
+            Enter => {
+                let Some(account) = self.accounts.get(self.selection) else {
+                    return AccountAction::Continue;
+                };
+                let db_path = get_data_dir().join("nigel.db");
+                match vault::reveal(conn, &db_path, account.id, &prompt.password) {
+                    Ok(numbers) => prompt.revealed = Some(numbers),
+                    Err(e) => prompt.error = Some(e.to_string()),
+                }
+                prompt.password.zeroize();
+            }
+            _ => {}
+        }
+        AccountAction::Continue
+    }
+
     fn handle_delete_key(
         &mut self,
         code: crossterm::event::KeyCode,
@@ -539,6 +899,7 @@ impl AccountManager {
                     match accounts::delete_account(conn, account.id) {
                         Ok(()) => {
                             self.reload(conn);
+                            self.changed = true;
                             self.screen = Screen::List;
                             self.set_status(format!("Deleted account: {name}"));
                         }
@@ -561,4 +922,27 @@ impl AccountManager {
 enum FormMode {
     Add,
     Rename,
+    Numbers,
+    Opening,
+}
+
+impl screen::Screen for AccountManager {
+    fn draw(&mut self, frame: &mut Frame) {
+        AccountManager::draw(self, frame);
+    }
+
+    fn handle_key(
+        &mut self,
+        code: crossterm::event::KeyCode,
+        ctx: &mut ScreenContext,
+    ) -> crate::error::Result<Nav> {
+        let action = AccountManager::handle_key(self, code, ctx.conn);
+        if std::mem::take(&mut self.changed) {
+            ctx.publish(DataChange::Accounts);
+        }
+        Ok(match action {
+            AccountAction::Close => Nav::Pop,
+            AccountAction::Continue => Nav::Stay,
+        })
+    }
 }
@@ -3,56 +3,194 @@ use rusqlite::Connection;
 
 use crate::db::get_connection;
 use crate::error::{NigelError, Result};
+use crate::fmt::money;
 use crate::models::Account;
 use crate::settings::get_data_dir;
+use crate::vault;
+use zeroize::Zeroizing;
 
 pub fn add(
     name: &str,
     account_type: &str,
     institution: Option<&str>,
     last_four: Option<&str>,
+    currency: Option<&str>,
+    opening_balance: Option<f64>,
+    opening_date: Option<&str>,
 ) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let currency = match currency {
+        Some(code) => crate::currency::normalize_code(code)?,
+        None => crate::currency::home_currency(&conn),
+    };
+    if let Some(date) = opening_date {
+        validate_opening_date(date)?;
+    }
     conn.execute(
-        "INSERT INTO accounts (name, account_type, institution, last_four) VALUES (?1, ?2, ?3, ?4)",
-        rusqlite::params![name, account_type, institution, last_four],
+        "INSERT INTO accounts (name, account_type, institution, last_four, currency, \
+         opening_balance, opening_date) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            name,
+            account_type,
+            institution,
+            last_four,
+            currency,
+            opening_balance.unwrap_or(0.0),
+            opening_date
+        ],
     )?;
-    println!("Added account: {name}");
+    println!("Added account: {name} ({currency})");
+    if let Some(balance) = opening_balance {
+        println!(
+            "  Opening balance: {}",
+            opening_label(balance, opening_date)
+        );
+    }
     Ok(())
 }
 
-pub fn list() -> Result<()> {
+pub fn opening_balance(name: &str, balance: f64, date: Option<&str>) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    let mut stmt =
-        conn.prepare("SELECT id, name, account_type, institution, last_four FROM accounts")?;
-    #[allow(clippy::type_complexity)]
-    let rows: Vec<(i64, String, String, Option<String>, Option<String>)> = stmt
-        .query_map([], |row| {
-            Ok((
-                row.get(0)?,
-                row.get(1)?,
-                row.get(2)?,
-                row.get(3)?,
-                row.get(4)?,
-            ))
-        })?
-        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let id = account_id(&conn, name)?;
+    set_opening_balance(&conn, id, balance, date)?;
+    println!(
+        "Opening balance for {name}: {}",
+        opening_label(balance, date)
+    );
+    Ok(())
+}
 
+/// "$1,234.56 on 2025-01-01", or just the amount without a date.
+pub fn opening_label(balance: f64, date: Option<&str>) -> String {
+    match date {
+        Some(date) => format!("{} on {date}", money(balance)),
+        None => money(balance),
+    }
+}
+
+pub fn list() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let mut table = Table::new();
-    table.set_header(vec!["ID", "Name", "Type", "Institution", "Last Four"]);
-    for (id, name, acct_type, inst, last) in rows {
+    table.set_header(vec![
+        "ID",
+        "Name",
+        "Type",
+        "Institution",
+        "Account #",
+        "Routing #",
+        "Currency",
+        "Opening",
+        "Status",
+    ]);
+    for account in list_accounts(&conn)? {
+        let (_, routing) = vault::masked_numbers(&conn, account.id)?;
         table.add_row(vec![
-            Cell::new(id),
-            Cell::new(name),
-            Cell::new(acct_type),
-            Cell::new(inst.unwrap_or_default()),
-            Cell::new(last.unwrap_or_default()),
+            Cell::new(account.id),
+            Cell::new(account.name),
+            Cell::new(account.account_type),
+            Cell::new(account.institution.unwrap_or_default()),
+            Cell::new(
+                account
+                    .last_four
+                    .as_deref()
+                    .map(vault::mask)
+                    .unwrap_or_default(),
+            ),
+            Cell::new(routing.unwrap_or_default()),
+            Cell::new(&account.currency),
+            Cell::new(
+                if account.opening_balance != 0.0 || account.opening_date.is_some() {
+                    opening_label(account.opening_balance, account.opening_date.as_deref())
+                } else {
+                    String::new()
+                },
+            ),
+            Cell::new(if account.is_archived { "archived" } else { "" }),
         ]);
     }
     println!("Accounts\n{table}");
     Ok(())
 }
 
+/// Prompt (hidden) for the full account and routing numbers and store them.
+/// Blank answers leave the stored value unchanged.
+pub fn set_number(name: &str) -> Result<()> {
+    let db_path = get_data_dir().join("nigel.db");
+    vault::require_encrypted(&db_path)?;
+    let conn = get_connection(&db_path)?;
+    let id = account_id(&conn, name)?;
+    let account = Zeroizing::new(prompt("Account number (blank to keep): ")?);
+    let routing = Zeroizing::new(prompt("Routing number (blank to keep): ")?);
+    let non_empty = |s: &str| Some(s.trim()).filter(|s| !s.is_empty()).map(str::to_string);
+    let account = non_empty(&account).map(Zeroizing::new);
+    let routing = non_empty(&routing).map(Zeroizing::new);
+    vault::set_numbers(
+        &conn,
+        id,
+        account.as_deref().map(|s| s.as_str()),
+        routing.as_deref().map(|s| s.as_str()),
+    )?;
+    let (acct, rout) = vault::masked_numbers(&conn, id)?;
+    println!(
+        "Saved numbers for {name}: account {}, routing {}",
+        acct.unwrap_or_else(|| "none".into()),
+        rout.unwrap_or_else(|| "none".into())
+    );
+    Ok(())
+}
+
+/// Print the full numbers after re-entering the database password.
+pub fn reveal(name: &str) -> Result<()> {
+    let db_path = get_data_dir().join("nigel.db");
+    let conn = get_connection(&db_path)?;
+    let id = account_id(&conn, name)?;
+    let password = Zeroizing::new(prompt("Database password: ")?);
+    let numbers = vault::reveal(&conn, &db_path, id, &password)?;
+    let show = |n: &Option<Zeroizing<String>>| {
+        n.as_deref()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "(not stored)".into())
+    };
+    println!("{name}");
+    println!("  Account number: {}", show(&numbers.account_number));
+    println!("  Routing number: {}", show(&numbers.routing_number));
+    Ok(())
+}
+
+pub fn clear_number(name: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let id = account_id(&conn, name)?;
+    vault::clear_numbers(&conn, id)?;
+    println!("Removed stored numbers for {name}");
+    Ok(())
+}
+
+fn prompt(msg: &str) -> Result<String> {
+    rpassword::prompt_password(msg).map_err(|e| NigelError::Other(e.to_string()))
+}
+
+fn account_id(conn: &Connection, name: &str) -> Result<i64> {
+    conn.query_row("SELECT id FROM accounts WHERE name = ?1", [name], |row| {
+        row.get(0)
+    })
+    .map_err(|_| NigelError::UnknownAccount(name.to_string()))
+}
+
+pub fn archive(name: &str, archived: bool) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let id = account_id(&conn, name)?;
+    set_archived(&conn, id, archived)?;
+    if archived {
+        println!(
+            "Archived account: {name} (hidden from pickers and the balance report; \
+             its transactions stay in reports)"
+        );
+    } else {
+        println!("Unarchived account: {name}");
+    }
+    Ok(())
+}
+
 pub fn rename(id: i64, new_name: &str) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     rename_account(&conn, id, new_name)?;
@@ -73,7 +211,8 @@ pub fn delete(id: i64) -> Result<()> {
 
 pub fn list_accounts(conn: &Connection) -> Result<Vec<Account>> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, account_type, institution, last_four FROM accounts ORDER BY name",
+        "SELECT id, name, account_type, institution, last_four, currency, is_archived, \
+         opening_balance, opening_date FROM accounts ORDER BY name",
     )?;
     let accounts = stmt
         .query_map([], |row| {
@@ -83,6 +222,10 @@ pub fn list_accounts(conn: &Connection) -> Result<Vec<Account>> {
                 account_type: row.get(2)?,
                 institution: row.get(3)?,
                 last_four: row.get(4)?,
+                currency: row.get(5)?,
+                is_archived: row.get(6)?,
+                opening_balance: row.get(7)?,
+                opening_date: row.get(8)?,
             })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -146,8 +289,10 @@ pub fn transaction_count(conn: &Connection, account_id: i64) -> Result<i64> {
     Ok(count)
 }
 
+/// Names of the accounts that aren't archived, for the import and reconcile
+/// pickers and the register's default account.
 pub fn account_names(conn: &Connection) -> Result<Vec<String>> {
-    let mut stmt = conn.prepare("SELECT name FROM accounts ORDER BY name")?;
+    let mut stmt = conn.prepare("SELECT name FROM accounts WHERE is_archived = 0 ORDER BY name")?;
     let rows = stmt.query_map([], |row| row.get(0))?;
     let mut names = Vec::new();
     for row in rows {
@@ -156,6 +301,53 @@ pub fn account_names(conn: &Connection) -> Result<Vec<String>> {
     Ok(names)
 }
 
+/// Archive or unarchive an account. Its transactions stay in every
+/// historical report; only pickers and the balance report hide it.
+pub fn set_archived(conn: &Connection, id: i64, archived: bool) -> Result<()> {
+    let updated = conn.execute(
+        "UPDATE accounts SET is_archived = ?1 WHERE id = ?2",
+        rusqlite::params![archived, id],
+    )?;
+    if updated == 0 {
+        return Err(NigelError::Other(format!("Account not found: id {id}")));
+    }
+    Ok(())
+}
+
+fn validate_opening_date(date: &str) -> Result<()> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+        NigelError::Other(format!(
+            "Invalid opening date: '{date}' (expected YYYY-MM-DD)"
+        ))
+    })?;
+    Ok(())
+}
+
+/// Set an account's opening balance: its balance before the first
+/// transaction on `date`. Transactions dated earlier are treated as already
+/// counted in it. Without a date it applies from the beginning.
+pub fn set_opening_balance(
+    conn: &Connection,
+    id: i64,
+    balance: f64,
+    date: Option<&str>,
+) -> Result<()> {
+    if !balance.is_finite() {
+        return Err(NigelError::Other(format!("Invalid balance: {balance}")));
+    }
+    if let Some(date) = date {
+        validate_opening_date(date)?;
+    }
+    let updated = conn.execute(
+        "UPDATE accounts SET opening_balance = ?1, opening_date = ?2 WHERE id = ?3",
+        rusqlite::params![balance, date, id],
+    )?;
+    if updated == 0 {
+        return Err(NigelError::Other(format!("Account not found: id {id}")));
+    }
+    Ok(())
+}
+
 pub fn delete_account(conn: &Connection, id: i64) -> Result<()> {
     let count = transaction_count(conn, id)?;
     if count > 0 {
@@ -170,10 +362,16 @@ pub fn delete_account(conn: &Connection, id: i64) -> Result<()> {
     }
     // Clean up reconciliations; null out imports to preserve checksums for duplicate detection
     conn.execute("DELETE FROM reconciliations WHERE account_id = ?1", [id])?;
+    conn.execute("DELETE FROM balance_assertions WHERE account_id = ?1", [id])?;
     conn.execute(
         "UPDATE imports SET account_id = NULL WHERE account_id = ?1",
         [id],
     )?;
+    // Bank sync links fall back to unmapped
+    conn.execute(
+        "UPDATE sync_accounts SET account_id = NULL WHERE account_id = ?1",
+        [id],
+    )?;
     let deleted = conn.execute("DELETE FROM accounts WHERE id = ?1", [id])?;
     if deleted == 0 {
         return Err(NigelError::Other(format!("Account not found: id {id}")));
@@ -311,6 +509,24 @@ mod tests {
         assert_eq!(transaction_count(&conn, id).unwrap(), 1);
     }
 
+    #[test]
+    fn test_archived_account_hidden_from_pickers() {
+        let (_dir, conn) = test_conn();
+        add_account(&conn, "Old Checking", "checking", None, None).unwrap();
+        add_account(&conn, "New Checking", "checking", None, None).unwrap();
+        let old = list_accounts(&conn).unwrap()[1].id;
+
+        set_archived(&conn, old, true).unwrap();
+        assert_eq!(account_names(&conn).unwrap(), vec!["New Checking"]);
+        let accounts = list_accounts(&conn).unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert!(accounts[1].is_archived);
+
+        set_archived(&conn, old, false).unwrap();
+        assert_eq!(account_names(&conn).unwrap().len(), 2);
+        assert!(set_archived(&conn, 9999, true).is_err());
+    }
+
     #[test]
     fn test_delete_nonexistent_account() {
         let (_dir, conn) = test_conn();
@@ -0,0 +1,74 @@
+use comfy_table::{Cell, Table};
+
+use crate::db::get_connection;
+use crate::error::Result;
+use crate::fmt::money;
+use crate::settings::get_data_dir;
+use crate::vendor_alerts;
+
+pub fn add(vendor: &str, over: Option<f64>, increase: Option<f64>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let id = vendor_alerts::add_alert(&conn, vendor, over, increase)?;
+    println!(
+        "Added vendor alert #{id} for {}: {}",
+        vendor.trim(),
+        thresholds(over, increase)
+    );
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let alerts = vendor_alerts::list_alerts(&conn)?;
+    if alerts.is_empty() {
+        println!("No vendor alerts.");
+        return Ok(());
+    }
+
+    let today = chrono::Local::now().date_naive();
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Vendor",
+        "Alert when",
+        "This month",
+        "6-mo avg",
+        "Status",
+    ]);
+    for a in &alerts {
+        let spend = vendor_alerts::vendor_spend(&conn, &a.vendor, today)?;
+        table.add_row(vec![
+            Cell::new(a.id),
+            Cell::new(&a.vendor),
+            Cell::new(thresholds(a.monthly_limit, a.increase_pct)),
+            Cell::new(money(spend.month_spend)),
+            Cell::new(spend.average.map(money).unwrap_or_default()),
+            Cell::new(if a.over_limit(&spend) || a.over_increase(&spend) {
+                "ALERT"
+            } else {
+                "ok"
+            }),
+        ]);
+    }
+    println!("Vendor Alerts\n{table}");
+    Ok(())
+}
+
+pub fn remove(id: i64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    vendor_alerts::remove_alert(&conn, id)?;
+    println!("Removed vendor alert #{id}");
+    Ok(())
+}
+
+/// "over $100.00 or up >10%".
+fn thresholds(over: Option<f64>, increase: Option<f64>) -> String {
+    let mut parts = Vec::new();
+    if let Some(limit) = over {
+        parts.push(format!("over {}", money(limit)));
+    }
+    if let Some(pct) = increase {
+        parts.push(format!("up >{pct}%"));
+    }
+    parts.join(" or ")
+}
@@ -0,0 +1,87 @@
+use std::io::{self, BufRead, Write};
+
+use comfy_table::{Cell, Table};
+
+use crate::archive;
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::settings::get_data_dir;
+
+fn confirm(question: &str) -> Result<bool> {
+    print!("{question} [y/N] ");
+    io::stdout().flush()?;
+    let line = io::stdin()
+        .lock()
+        .lines()
+        .next()
+        .unwrap_or(Ok(String::new()))
+        .map_err(|e| NigelError::Other(e.to_string()))?;
+    Ok(line.trim().eq_ignore_ascii_case("y"))
+}
+
+pub fn create(year: i32) -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let label = FiscalYear::load(&conn).label(year);
+    let count = archive::check_archivable(&conn, year)?;
+    println!("Archiving moves {label}'s {count} transactions into archives/nigel-{year}.db.");
+    println!("Reports still include them; edits need `nigel archive restore {year}` first.");
+    if !confirm(&format!("Archive {label}?"))? {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    let snap_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "archive")?;
+    println!("Pre-archive snapshot saved to {}", snap_path.display());
+    let info = archive::archive_year(&conn, year)?;
+    println!(
+        "Archived {label}: {} transactions moved to archives/{}",
+        info.transaction_count, info.filename
+    );
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let archives = archive::list(&conn)?;
+    if archives.is_empty() {
+        println!("No archived years.");
+        return Ok(());
+    }
+
+    let fiscal = FiscalYear::load(&conn);
+    let mut table = Table::new();
+    table.set_header(vec!["Year", "Period", "Transactions", "File", "Archived"]);
+    for a in &archives {
+        table.add_row(vec![
+            Cell::new(fiscal.label(a.year)),
+            Cell::new(format!("{} to {}", a.start_date, a.end_date)),
+            Cell::new(a.transaction_count),
+            Cell::new(format!("archives/{}", a.filename)),
+            Cell::new(&a.archived_at),
+        ]);
+    }
+    println!("Archived Years\n{table}");
+    Ok(())
+}
+
+pub fn restore(year: i32) -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    if !archive::list(&conn)?.iter().any(|a| a.year == year) {
+        return Err(NigelError::Other(format!(
+            "{} is not archived. Run `nigel archive list` to see archived years.",
+            FiscalYear::load(&conn).label(year)
+        )));
+    }
+    let snap_path =
+        crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "archive-restore")?;
+    println!("Pre-restore snapshot saved to {}", snap_path.display());
+    let restored = archive::restore_year(&conn, year)?;
+    println!(
+        "Restored {}: {restored} transactions moved back into nigel.db",
+        FiscalYear::load(&conn).label(year)
+    );
+    Ok(())
+}
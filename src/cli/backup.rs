@@ -1,10 +1,13 @@
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 
 use rusqlite::backup::Backup;
 
 use crate::db::get_connection;
-use crate::error::Result;
+use crate::error::{NigelError, Result};
 use crate::fmt::format_bytes;
+use crate::health::record_backup;
+use crate::offsite::{self, Destination};
 use crate::settings::{get_data_dir, restrict_dir_permissions, restrict_file_permissions};
 
 /// Copy the database to `dest_path` using SQLite's online-backup API.
@@ -36,27 +39,231 @@ pub fn snapshot_with_password(
     Ok(())
 }
 
-pub fn run(output: Option<String>) -> Result<()> {
+/// Snapshot into `<data_dir>/backups/nigel-YYYYMMDD-HHMMSS.db` and record the
+/// backup time. Returns the path written.
+pub fn backup_to_default(conn: &rusqlite::Connection, data_dir: &Path) -> Result<PathBuf> {
+    let backups_dir = data_dir.join("backups");
+    std::fs::create_dir_all(&backups_dir)?;
+    restrict_dir_permissions(&backups_dir)?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let dest_path = backups_dir.join(format!("nigel-{stamp}.db"));
+    snapshot(conn, &dest_path)?;
+    record_backup(conn)?;
+    Ok(dest_path)
+}
+
+/// Default for the `snapshot_keep` setting: automatic snapshots kept per
+/// operation before older ones are pruned.
+pub const SNAPSHOT_KEEP: usize = 10;
+
+/// Snapshot into `<data_dir>/snapshots/pre-{operation}-YYYYMMDD-HHMMSS.db`
+/// before a bulk or destructive change, then prune that operation's older
+/// snapshots. Returns the path written.
+pub fn pre_operation_snapshot(
+    conn: &rusqlite::Connection,
+    data_dir: &Path,
+    operation: &str,
+) -> Result<PathBuf> {
+    let snapshots_dir = data_dir.join("snapshots");
+    std::fs::create_dir_all(&snapshots_dir)?;
+    restrict_dir_permissions(&snapshots_dir)?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let dest_path = snapshots_dir.join(format!("pre-{operation}-{stamp}.db"));
+    snapshot(conn, &dest_path)?;
+    prune_snapshots(
+        &snapshots_dir,
+        operation,
+        crate::settings::load_settings().snapshot_keep(),
+    )?;
+    Ok(dest_path)
+}
+
+/// Delete all but the newest `keep` `pre-{operation}-*.db` snapshots in `dir`.
+/// Other operations' snapshots are left alone. Returns how many were removed.
+pub fn prune_snapshots(dir: &Path, operation: &str, keep: usize) -> Result<usize> {
+    let prefix = format!("pre-{operation}-");
+    let mut names: Vec<String> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|n| {
+            n.strip_prefix(&prefix)
+                .and_then(|rest| rest.strip_suffix(".db"))
+                // The stamp is all digits and a dash, so `pre-import-` never
+                // matches a hypothetical `pre-import-foo-` operation
+                .is_some_and(|stamp| stamp.chars().all(|c| c.is_ascii_digit() || c == '-'))
+        })
+        .collect();
+    // YYYYMMDD-HHMMSS stamps sort chronologically as strings
+    names.sort_unstable_by(|a, b| b.cmp(a));
+    let mut removed = 0;
+    for name in names.into_iter().skip(keep) {
+        std::fs::remove_file(dir.join(name))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Whether `stamp` looks like the `YYYYMMDD-HHMMSS` in snapshot and backup
+/// file names.
+fn is_stamp(stamp: &str) -> bool {
+    stamp.len() == 15 && stamp.chars().all(|c| c.is_ascii_digit() || c == '-')
+}
+
+/// Prune every operation's snapshots in `dir` to the newest `keep`, as
+/// `pre_operation_snapshot()` does for one. Returns how many were removed.
+pub fn prune_all_snapshots(dir: &Path, keep: usize) -> Result<usize> {
+    let mut operations: Vec<String> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            let rest = name.strip_prefix("pre-")?.strip_suffix(".db")?;
+            let (operation, stamp) = rest.split_at_checked(rest.len().checked_sub(15)?)?;
+            let operation = operation.strip_suffix('-')?;
+            (is_stamp(stamp) && !operation.is_empty()).then(|| operation.to_string())
+        })
+        .collect();
+    operations.sort_unstable();
+    operations.dedup();
+    let mut removed = 0;
+    for operation in operations {
+        removed += prune_snapshots(dir, &operation, keep)?;
+    }
+    Ok(removed)
+}
+
+/// Delete all but the newest `keep` `nigel-*.db` backups in `dir`, the ones
+/// `backup_to_default()` writes. Returns how many were removed.
+pub fn prune_backups(dir: &Path, keep: usize) -> Result<usize> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|n| {
+            n.strip_prefix("nigel-")
+                .and_then(|rest| rest.strip_suffix(".db"))
+                .is_some_and(is_stamp)
+        })
+        .collect();
+    names.sort_unstable_by(|a, b| b.cmp(a));
+    let mut removed = 0;
+    for name in names.into_iter().skip(keep) {
+        std::fs::remove_file(dir.join(name))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+pub fn run(
+    output: Option<String>,
+    to: Option<String>,
+    encrypt: bool,
+    local_only: bool,
+) -> Result<()> {
     let data_dir = get_data_dir();
     let db_path = data_dir.join("nigel.db");
     let conn = get_connection(&db_path)?;
+    let settings = crate::settings::load_settings();
+    let destination = if local_only {
+        None
+    } else {
+        to.or(settings.backup_destination.clone())
+            .map(|spec| Destination::parse(&spec))
+            .transpose()?
+    };
+    // Asked before the backup is written, so a typo doesn't leave one behind
+    let passphrase = match &destination {
+        Some(_) if encrypt || settings.backup_encrypt => Some(backup_passphrase()?),
+        _ => None,
+    };
 
     let dest_path = match output {
-        Some(p) => PathBuf::from(p),
-        None => {
-            let backups_dir = data_dir.join("backups");
-            std::fs::create_dir_all(&backups_dir)?;
-            restrict_dir_permissions(&backups_dir)?;
-            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
-            backups_dir.join(format!("nigel-{stamp}.db"))
+        Some(p) => {
+            let dest_path = PathBuf::from(p);
+            snapshot(&conn, &dest_path)?;
+            record_backup(&conn)?;
+            dest_path
         }
+        None => backup_to_default(&conn, &data_dir)?,
     };
 
-    snapshot(&conn, &dest_path)?;
-
     let size = std::fs::metadata(&dest_path)?.len();
     println!("Backup saved to {}", dest_path.display());
     println!("Size: {}", format_bytes(size));
+
+    if let Some(destination) = destination {
+        let copy = offsite::copy(&conn, &dest_path, &destination, passphrase.as_deref())?;
+        println!(
+            "Copied to {} ({}{})",
+            destination.location(&copy.name),
+            format_bytes(copy.size),
+            if copy.encrypted {
+                ", encrypted with your backup passphrase"
+            } else {
+                ""
+            }
+        );
+        println!("SHA-256: {}", copy.sha256);
+    }
+    Ok(())
+}
+
+/// The passphrase for an encrypted copy: `NIGEL_BACKUP_PASSPHRASE` for
+/// unattended runs, else asked for twice.
+fn backup_passphrase() -> Result<String> {
+    if let Some(pw) = std::env::var("NIGEL_BACKUP_PASSPHRASE")
+        .ok()
+        .filter(|p| !p.is_empty())
+    {
+        return Ok(pw);
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(NigelError::Other(
+            "An encrypted backup copy needs a passphrase: set NIGEL_BACKUP_PASSPHRASE or run it in a terminal."
+                .into(),
+        ));
+    }
+    let prompt =
+        |msg: &str| rpassword::prompt_password(msg).map_err(|e| NigelError::Other(e.to_string()));
+    let pw = prompt("Backup passphrase: ")?;
+    if pw.is_empty() {
+        return Err(NigelError::Other("Passphrase cannot be empty.".into()));
+    }
+    if prompt("Confirm passphrase: ")? != pw {
+        return Err(NigelError::Other("Passphrases do not match.".into()));
+    }
+    Ok(pw)
+}
+
+/// `nigel backup verify`: read a copy back from the destination and compare
+/// its checksum. A mismatch is an error, so scripts can alert on it.
+pub fn verify(name: Option<String>, to: Option<String>) -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let spec = to
+        .or(crate::settings::load_settings().backup_destination)
+        .ok_or_else(|| {
+            NigelError::Other(
+                "No backup destination. Pass --to, or set backup_destination in settings.json."
+                    .into(),
+            )
+        })?;
+    let destination = Destination::parse(&spec)?;
+    let name = name.or_else(|| offsite::last_copy(&conn)).ok_or_else(|| {
+        NigelError::Other("No copy has been made yet. Name the one to check.".into())
+    })?;
+    let check = offsite::verify(&conn, &destination, &name)?;
+    println!("{} ({})", check.location, format_bytes(check.size));
+    println!("  Expected: {}", check.expected);
+    println!("  Actual:   {}", check.actual);
+    if !check.is_ok() {
+        return Err(NigelError::Other(
+            "Checksum mismatch: the copy is damaged or was changed.".into(),
+        ));
+    }
+    if check.recorded {
+        println!("OK: matches the checksum recorded when it was copied.");
+    } else {
+        println!("OK: matches the .sha256 file beside it.");
+    }
     Ok(())
 }
 
@@ -110,4 +317,22 @@ mod tests {
 
         assert!(!is_encrypted(&dst_path).unwrap());
     }
+
+    #[test]
+    fn test_prune_snapshots_keeps_newest_per_operation() {
+        let dir = tempfile::tempdir().unwrap();
+        for stamp in ["20250101-090000", "20250102-090000", "20250103-090000"] {
+            std::fs::write(dir.path().join(format!("pre-import-{stamp}.db")), b"").unwrap();
+        }
+        std::fs::write(dir.path().join("pre-undo-20240101-090000.db"), b"").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"").unwrap();
+
+        assert_eq!(prune_snapshots(dir.path(), "import", 2).unwrap(), 1);
+        assert!(!dir.path().join("pre-import-20250101-090000.db").exists());
+        assert!(dir.path().join("pre-import-20250102-090000.db").exists());
+        assert!(dir.path().join("pre-import-20250103-090000.db").exists());
+        // Other operations and unrelated files are untouched
+        assert!(dir.path().join("pre-undo-20240101-090000.db").exists());
+        assert!(dir.path().join("notes.txt").exists());
+    }
 }
@@ -2,7 +2,7 @@ use super::parse_month_opt;
 use crate::browser::RegisterBrowser;
 use crate::db::get_connection;
 use crate::error::Result;
-use crate::reports;
+use crate::reports::RegisterQuery;
 use crate::reviewer::get_categories;
 use crate::settings::get_data_dir;
 
@@ -12,18 +12,21 @@ pub fn register(
     from_date: Option<String>,
     to_date: Option<String>,
     account: Option<String>,
+    tag: Option<String>,
 ) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let (my, mm) = parse_month_opt(&month);
     let y = year.or(my);
-    let data = reports::get_register(
-        &conn,
-        y,
-        mm,
-        from_date.as_deref(),
-        to_date.as_deref(),
-        account.as_deref(),
-    )?;
+    let no_date_filters = y.is_none() && mm.is_none() && from_date.is_none() && to_date.is_none();
+    let query = RegisterQuery {
+        year: y,
+        month: mm,
+        from_date: from_date.clone(),
+        to_date: to_date.clone(),
+        account: account.clone(),
+        tag: tag.clone(),
+        ..Default::default()
+    };
 
     // Build filters description — show effective values
     let mut filters = Vec::new();
@@ -47,20 +50,22 @@ pub fn register(
     if let Some(ref acct) = account {
         filters.push(format!("account: {acct}"));
     }
+    if let Some(ref t) = tag {
+        filters.push(format!("tag: #{}", t.trim_start_matches('#')));
+    }
     let filters_desc = filters.join(", ");
 
-    let no_date_filters = y.is_none() && mm.is_none() && from_date.is_none() && to_date.is_none();
-    let total = data.total;
     let categories = get_categories(&conn).unwrap_or_default();
     let desc = if filters_desc.is_empty() {
         "all transactions".to_string()
     } else {
         filters_desc
     };
-    let mut browser = RegisterBrowser::new(data.rows, total, desc, categories);
+    let mut browser = RegisterBrowser::open(&conn, query, desc, categories)?;
     if no_date_filters {
         browser.scroll_to_today();
     }
+    browser.fetch(&conn)?;
     browser.run(&conn)?;
     Ok(())
 }
@@ -1,10 +1,18 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
 use comfy_table::{Cell, Table};
 use rusqlite::Connection;
 
 use crate::db::get_connection;
 use crate::error::{NigelError, Result};
+use crate::reports::{category_paths, CATEGORY_PATH_SEP};
 use crate::settings::get_data_dir;
 
+/// `transfer` categories move money between the business's own accounts and
+/// stay off the P&L and tax summary.
+pub const CATEGORY_TYPES: &[&str] = &["expense", "income", "transfer"];
+
 #[derive(Debug, Clone)]
 pub struct CategoryRow {
     pub id: i64,
@@ -12,6 +20,25 @@ pub struct CategoryRow {
     pub category_type: String,
     pub tax_line: Option<String>,
     pub form_line: Option<String>,
+    pub parent_id: Option<i64>,
+    /// Full name including ancestors, e.g. `Software > Design tools`.
+    pub path: String,
+}
+
+/// Everything that keeps a category from being deleted, for the usage
+/// drill-down shown when a delete is blocked.
+#[derive(Debug, Default)]
+pub struct CategoryUsage {
+    pub transactions: i64,
+    /// Active rules as `(id, match_type, pattern)`.
+    pub rules: Vec<(i64, String, String)>,
+    pub children: Vec<String>,
+}
+
+impl CategoryUsage {
+    pub fn is_empty(&self) -> bool {
+        self.transactions == 0 && self.rules.is_empty() && self.children.is_empty()
+    }
 }
 
 pub fn add(
@@ -19,9 +46,10 @@ pub fn add(
     category_type: &str,
     tax_line: Option<&str>,
     form_line: Option<&str>,
+    parent_id: Option<i64>,
 ) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    add_category(&conn, name, category_type, tax_line, form_line)?;
+    add_category(&conn, name, category_type, tax_line, form_line, parent_id)?;
     println!("Added category: {name}");
     Ok(())
 }
@@ -31,11 +59,24 @@ pub fn list() -> Result<()> {
     let categories = list_categories(&conn)?;
 
     let mut table = Table::new();
-    table.set_header(vec!["ID", "Name", "Type", "Tax Line", "Form Line"]);
+    table.set_header(vec![
+        "ID",
+        "Name",
+        "Parent",
+        "Type",
+        "Tax Line",
+        "Form Line",
+    ]);
     for cat in categories {
+        let parent = cat
+            .path
+            .rsplit_once(CATEGORY_PATH_SEP)
+            .map(|(parent, _)| parent.to_string())
+            .unwrap_or_default();
         table.add_row(vec![
             Cell::new(cat.id),
             Cell::new(cat.name),
+            Cell::new(parent),
             Cell::new(cat.category_type),
             Cell::new(cat.tax_line.unwrap_or_default()),
             Cell::new(cat.form_line.unwrap_or_default()),
@@ -58,31 +99,104 @@ pub fn update(
     category_type: &str,
     tax_line: Option<&str>,
     form_line: Option<&str>,
+    parent_id: Option<i64>,
 ) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    update_category(&conn, id, name, category_type, tax_line, form_line)?;
+    update_category(
+        &conn,
+        id,
+        name,
+        category_type,
+        tax_line,
+        form_line,
+        parent_id,
+    )?;
     println!("Updated category {id}: {name}");
     Ok(())
 }
 
 pub fn delete(id: i64) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let usage = category_usage(&conn, id)?;
+    if !usage.is_empty() {
+        println!("Category {id} is still in use:");
+        if usage.transactions > 0 {
+            println!(
+                "  {} transactions (recategorize them in `nigel browse register` or the dashboard's Categories screen)",
+                usage.transactions
+            );
+        }
+        for (rule_id, match_type, pattern) in &usage.rules {
+            println!("  rule {rule_id}: {match_type} \"{pattern}\"");
+        }
+        for child in &usage.children {
+            println!("  sub-category: {child}");
+        }
+    }
     delete_category(&conn, id)?;
     println!("Deleted category {id}");
     Ok(())
 }
 
+pub fn merge(from: &str, into: &str) -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let from = find_category(&conn, from)?;
+    let into = find_category(&conn, into)?;
+    let cross_type = from.category_type != into.category_type;
+    if cross_type {
+        println!(
+            "'{}' is {} and '{}' is {}: its transactions will report as {}.",
+            from.path, from.category_type, into.path, into.category_type, into.category_type
+        );
+        print!("Merge anyway? [y/N] ");
+        io::stdout().flush()?;
+        let line = io::stdin()
+            .lock()
+            .lines()
+            .next()
+            .unwrap_or(Ok(String::new()))
+            .map_err(|e| NigelError::Other(e.to_string()))?;
+        if !line.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let snap_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "merge")?;
+    println!("Pre-merge snapshot saved to {}", snap_path.display());
+    let outcome = merge_category(&conn, from.id, into.id, cross_type)?;
+    println!("{}", outcome.summary());
+    Ok(())
+}
+
+pub fn loc_interest(category: Option<&str>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    match category {
+        Some(name) => {
+            crate::importer::set_loc_interest_category(&conn, name)?;
+            println!("Line of credit interest and fees will be categorized as {name}");
+        }
+        None => println!(
+            "Line of credit interest category: {}",
+            crate::importer::loc_interest_category(&conn)
+        ),
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Data-layer functions for TUI category management
 // ---------------------------------------------------------------------------
 
+/// Active categories, income first, each sub-category directly after its parent.
 pub fn list_categories(conn: &Connection) -> Result<Vec<CategoryRow>> {
+    let paths = category_paths(conn)?;
     let mut stmt = conn.prepare(
-        "SELECT id, name, category_type, tax_line, form_line \
-         FROM categories WHERE is_active = 1 \
-         ORDER BY CASE category_type WHEN 'income' THEN 0 ELSE 1 END, name ASC",
+        "SELECT id, name, category_type, tax_line, form_line, parent_id \
+         FROM categories WHERE is_active = 1",
     )?;
-    let categories = stmt
+    let mut categories = stmt
         .query_map([], |row| {
             Ok(CategoryRow {
                 id: row.get(0)?,
@@ -90,26 +204,100 @@ pub fn list_categories(conn: &Connection) -> Result<Vec<CategoryRow>> {
                 category_type: row.get(2)?,
                 tax_line: row.get(3)?,
                 form_line: row.get(4)?,
+                parent_id: row.get(5)?,
+                path: String::new(),
             })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
+    let mut sort_keys = HashMap::new();
+    for cat in &mut categories {
+        let path = paths.get(&cat.id).cloned().unwrap_or_default();
+        cat.path = path.join(CATEGORY_PATH_SEP);
+        sort_keys.insert(cat.id, path);
+    }
+    categories.sort_by(|a, b| {
+        let rank = |c: &CategoryRow| if c.category_type == "income" { 0 } else { 1 };
+        rank(a)
+            .cmp(&rank(b))
+            .then_with(|| sort_keys[&a.id].cmp(&sort_keys[&b.id]))
+    });
     Ok(categories)
 }
 
+/// Check that `parent_id` can be the parent of category `id` (None when adding):
+/// it must exist, share the category type, and not be the category itself
+/// or one of its descendants.
+fn validate_parent(
+    conn: &Connection,
+    id: Option<i64>,
+    parent_id: i64,
+    category_type: &str,
+) -> Result<()> {
+    let parent_type: String = conn
+        .query_row(
+            "SELECT category_type FROM categories WHERE id = ?1 AND is_active = 1",
+            [parent_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                NigelError::Other(format!("Parent category not found: id {parent_id}"))
+            }
+            other => NigelError::Db(other),
+        })?;
+    if parent_type != category_type {
+        return Err(NigelError::Other(format!(
+            "Parent category must also be {category_type}"
+        )));
+    }
+    let Some(id) = id else {
+        return Ok(());
+    };
+    // Walk up from the proposed parent; reaching `id` would create a cycle.
+    let mut current = Some(parent_id);
+    let mut steps = 0;
+    while let Some(cid) = current {
+        if cid == id {
+            return Err(NigelError::Other(
+                "A category cannot be nested under itself or its own sub-category".into(),
+            ));
+        }
+        steps += 1;
+        if steps > 64 {
+            break;
+        }
+        current = conn.query_row(
+            "SELECT parent_id FROM categories WHERE id = ?1",
+            [cid],
+            |row| row.get(0),
+        )?;
+    }
+    Ok(())
+}
+
+fn validate_category_type(category_type: &str) -> Result<()> {
+    if !CATEGORY_TYPES.contains(&category_type) {
+        return Err(NigelError::Other(format!(
+            "Invalid category type: {category_type} (must be 'income', 'expense', or 'transfer')"
+        )));
+    }
+    Ok(())
+}
+
 pub fn add_category(
     conn: &Connection,
     name: &str,
     category_type: &str,
     tax_line: Option<&str>,
     form_line: Option<&str>,
+    parent_id: Option<i64>,
 ) -> Result<()> {
     if name.trim().is_empty() {
         return Err(NigelError::Other("Name is required".into()));
     }
-    if category_type != "income" && category_type != "expense" {
-        return Err(NigelError::Other(format!(
-            "Invalid category type: {category_type} (must be 'income' or 'expense')"
-        )));
+    validate_category_type(category_type)?;
+    if let Some(parent_id) = parent_id {
+        validate_parent(conn, None, parent_id, category_type)?;
     }
     let exists: bool = conn.query_row(
         "SELECT EXISTS(SELECT 1 FROM categories WHERE name = ?1 AND is_active = 1)",
@@ -122,19 +310,26 @@ pub fn add_category(
         )));
     }
     conn.execute(
-        "INSERT INTO categories (name, category_type, tax_line, form_line) VALUES (?1, ?2, ?3, ?4)",
-        rusqlite::params![name, category_type, tax_line, form_line],
+        "INSERT INTO categories (name, category_type, tax_line, form_line, parent_id) \
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![name, category_type, tax_line, form_line, parent_id],
     )?;
     Ok(())
 }
 
 pub fn rename_category(conn: &Connection, id: i64, new_name: &str) -> Result<()> {
     // Fetch the existing row so we can delegate to update_category with current values
-    let (cat_type, tax_line, form_line): (String, Option<String>, Option<String>) = conn
+    #[allow(clippy::type_complexity)]
+    let (cat_type, tax_line, form_line, parent_id): (
+        String,
+        Option<String>,
+        Option<String>,
+        Option<i64>,
+    ) = conn
         .query_row(
-            "SELECT category_type, tax_line, form_line FROM categories WHERE id = ?1 AND is_active = 1",
+            "SELECT category_type, tax_line, form_line, parent_id FROM categories WHERE id = ?1 AND is_active = 1",
             [id],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
         )
         .map_err(|e| match e {
             rusqlite::Error::QueryReturnedNoRows => {
@@ -149,6 +344,7 @@ pub fn rename_category(conn: &Connection, id: i64, new_name: &str) -> Result<()>
         &cat_type,
         tax_line.as_deref(),
         form_line.as_deref(),
+        parent_id,
     )
 }
 
@@ -159,14 +355,25 @@ pub fn update_category(
     category_type: &str,
     tax_line: Option<&str>,
     form_line: Option<&str>,
+    parent_id: Option<i64>,
 ) -> Result<()> {
     if name.trim().is_empty() {
         return Err(NigelError::Other("Name is required".into()));
     }
-    if category_type != "income" && category_type != "expense" {
-        return Err(NigelError::Other(format!(
-            "Invalid category type: {category_type} (must be 'income' or 'expense')"
-        )));
+    validate_category_type(category_type)?;
+    if let Some(parent_id) = parent_id {
+        validate_parent(conn, Some(id), parent_id, category_type)?;
+    }
+    let mismatched_child: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM categories \
+         WHERE parent_id = ?1 AND is_active = 1 AND category_type != ?2)",
+        rusqlite::params![id, category_type],
+        |row| row.get(0),
+    )?;
+    if mismatched_child {
+        return Err(NigelError::Other(
+            "Cannot change type: category has sub-categories of the current type".into(),
+        ));
     }
     let exists: bool = conn.query_row(
         "SELECT EXISTS(SELECT 1 FROM categories WHERE name = ?1 AND is_active = 1 AND id != ?2)",
@@ -179,8 +386,9 @@ pub fn update_category(
         )));
     }
     let updated = conn.execute(
-        "UPDATE categories SET name = ?1, category_type = ?2, tax_line = ?3, form_line = ?4 WHERE id = ?5 AND is_active = 1",
-        rusqlite::params![name, category_type, tax_line, form_line, id],
+        "UPDATE categories SET name = ?1, category_type = ?2, tax_line = ?3, form_line = ?4, \
+         parent_id = ?5 WHERE id = ?6 AND is_active = 1",
+        rusqlite::params![name, category_type, tax_line, form_line, parent_id, id],
     )?;
     if updated == 0 {
         return Err(NigelError::Other(format!("Category not found: id {id}")));
@@ -208,6 +416,21 @@ pub fn blocking_reason(conn: &Connection, id: i64) -> Result<Option<String>> {
             "Cannot delete: category has {rule_count} active {noun}"
         )));
     }
+    let child_count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM categories WHERE parent_id = ?1 AND is_active = 1",
+        [id],
+        |row| row.get(0),
+    )?;
+    if child_count > 0 {
+        let noun = if child_count == 1 {
+            "sub-category"
+        } else {
+            "sub-categories"
+        };
+        return Ok(Some(format!(
+            "Cannot delete: category has {child_count} {noun}"
+        )));
+    }
     Ok(None)
 }
 
@@ -225,6 +448,152 @@ pub fn delete_category(conn: &Connection, id: i64) -> Result<()> {
     Ok(())
 }
 
+/// What a category merge moved, for the confirmation and the audit log.
+#[derive(Debug)]
+pub struct MergeOutcome {
+    pub from: String,
+    pub into: String,
+    pub transactions: usize,
+    pub rules: usize,
+    pub sub_categories: usize,
+}
+
+impl MergeOutcome {
+    pub fn summary(&self) -> String {
+        format!(
+            "Merged '{}' into '{}': {} transactions, {} rules, {} sub-categories moved",
+            self.from, self.into, self.transactions, self.rules, self.sub_categories
+        )
+    }
+}
+
+/// An active category by ID or by name (ignoring case).
+pub fn find_category(conn: &Connection, key: &str) -> Result<CategoryRow> {
+    let key = key.trim();
+    let id = key.parse::<i64>().ok();
+    list_categories(conn)?
+        .into_iter()
+        .find(|c| Some(c.id) == id || c.name.eq_ignore_ascii_case(key))
+        .ok_or_else(|| NigelError::UnknownCategory(key.to_string()))
+}
+
+/// Fold category `from_id` into `into_id`: its transactions (trashed ones
+/// too), rules, and sub-categories move over, a line of credit interest
+/// setting naming it follows, and it's deactivated, all in one transaction
+/// recorded in the audit log. Merging across types changes how the moved
+/// transactions report, so it's refused unless `allow_type_change`.
+pub fn merge_category(
+    conn: &Connection,
+    from_id: i64,
+    into_id: i64,
+    allow_type_change: bool,
+) -> Result<MergeOutcome> {
+    let find = |id: i64| {
+        list_categories(conn)?
+            .into_iter()
+            .find(|c| c.id == id)
+            .ok_or_else(|| NigelError::Other(format!("Category not found: id {id}")))
+    };
+    let from = find(from_id)?;
+    let into = find(into_id)?;
+    if from.id == into.id {
+        return Err(NigelError::Other(
+            "Cannot merge a category into itself".into(),
+        ));
+    }
+    let children = category_usage(conn, from.id)?.children.len();
+    if from.category_type != into.category_type {
+        if !allow_type_change {
+            return Err(NigelError::Other(format!(
+                "'{}' is {} and '{}' is {}; merging changes how its transactions report",
+                from.name, from.category_type, into.name, into.category_type
+            )));
+        }
+        if children > 0 {
+            return Err(NigelError::Other(format!(
+                "'{}' has sub-categories of type {}; move them before merging into a {} category",
+                from.name, from.category_type, into.category_type
+            )));
+        }
+    }
+    // Walk up from the target; reaching `from` means it's a sub-category
+    let mut current = into.parent_id;
+    while let Some(id) = current {
+        if id == from.id {
+            return Err(NigelError::Other(format!(
+                "Cannot merge '{}' into its own sub-category",
+                from.name
+            )));
+        }
+        current = conn.query_row(
+            "SELECT parent_id FROM categories WHERE id = ?1",
+            [id],
+            |row| row.get(0),
+        )?;
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let transactions = tx.execute(
+        "UPDATE transactions SET category_id = ?1 WHERE category_id = ?2",
+        [into.id, from.id],
+    )?;
+    let rules = tx.execute(
+        "UPDATE rules SET category_id = ?1 WHERE category_id = ?2 AND is_active = 1",
+        [into.id, from.id],
+    )?;
+    let sub_categories = tx.execute(
+        "UPDATE categories SET parent_id = ?1 WHERE parent_id = ?2 AND is_active = 1",
+        [into.id, from.id],
+    )?;
+    // A limit follows the merge unless the target has its own
+    tx.execute(
+        "UPDATE OR IGNORE expense_limits SET category_id = ?1 WHERE category_id = ?2",
+        [into.id, from.id],
+    )?;
+    tx.execute(
+        "UPDATE categories SET is_active = 0 WHERE id = ?1",
+        [from.id],
+    )?;
+    if crate::db::get_metadata(&tx, "loc_interest_category").as_deref() == Some(&from.name)
+        && into.category_type == "expense"
+    {
+        crate::db::set_metadata(&tx, "loc_interest_category", &into.name)?;
+    }
+    let outcome = MergeOutcome {
+        from: from.path,
+        into: into.path,
+        transactions,
+        rules,
+        sub_categories,
+    };
+    crate::audit::record(&tx, "category.merge", &outcome.summary())?;
+    tx.commit()?;
+    Ok(outcome)
+}
+
+pub fn category_usage(conn: &Connection, id: i64) -> Result<CategoryUsage> {
+    let (transactions, _) = usage_count(conn, id)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, COALESCE(match_type, 'contains'), pattern FROM rules
+         WHERE category_id = ?1 AND is_active = 1
+         ORDER BY priority DESC, id",
+    )?;
+    let rules = stmt
+        .query_map([id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let mut stmt = conn.prepare(
+        "SELECT name FROM categories WHERE parent_id = ?1 AND is_active = 1 ORDER BY name",
+    )?;
+    let children = stmt
+        .query_map([id], |row| row.get(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(CategoryUsage {
+        transactions,
+        rules,
+        children,
+    })
+}
+
 pub fn usage_count(conn: &Connection, id: i64) -> Result<(i64, i64)> {
     let txn_count: i64 = conn.query_row(
         "SELECT COUNT(*) FROM transactions WHERE category_id = ?1",
@@ -277,7 +646,7 @@ mod tests {
     #[test]
     fn test_add_category_and_list() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "New Category", "income", Some("Line 1"), None).unwrap();
+        add_category(&conn, "New Category", "income", Some("Line 1"), None, None).unwrap();
         let categories = list_categories(&conn).unwrap();
         let found = categories.iter().find(|c| c.name == "New Category");
         assert!(
@@ -293,22 +662,22 @@ mod tests {
     #[test]
     fn test_add_duplicate_name_rejected() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "Test Cat", "expense", None, None).unwrap();
-        let err = add_category(&conn, "Test Cat", "income", None, None).unwrap_err();
+        add_category(&conn, "Test Cat", "expense", None, None, None).unwrap();
+        let err = add_category(&conn, "Test Cat", "income", None, None, None).unwrap_err();
         assert!(err.to_string().contains("already exists"));
     }
 
     #[test]
     fn test_add_invalid_type_rejected() {
         let (_dir, conn) = test_conn();
-        let err = add_category(&conn, "Bad Type", "revenue", None, None).unwrap_err();
+        let err = add_category(&conn, "Bad Type", "revenue", None, None, None).unwrap_err();
         assert!(err.to_string().contains("Invalid category type"));
     }
 
     #[test]
     fn test_add_empty_name_rejected() {
         let (_dir, conn) = test_conn();
-        let err = add_category(&conn, "  ", "income", None, None).unwrap_err();
+        let err = add_category(&conn, "  ", "income", None, None, None).unwrap_err();
         assert!(err.to_string().contains("Name is required"));
     }
 
@@ -321,6 +690,7 @@ mod tests {
             "expense",
             Some("Line 8"),
             Some("1120S-16"),
+            None,
         )
         .unwrap();
         let id = list_categories(&conn)
@@ -337,6 +707,7 @@ mod tests {
             "income",
             Some("Gross receipts"),
             Some("K-4"),
+            None,
         )
         .unwrap();
 
@@ -354,7 +725,15 @@ mod tests {
     #[test]
     fn test_rename_category_changes_only_name() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "Before", "expense", Some("Line 8"), Some("1120S-16")).unwrap();
+        add_category(
+            &conn,
+            "Before",
+            "expense",
+            Some("Line 8"),
+            Some("1120S-16"),
+            None,
+        )
+        .unwrap();
         let id = list_categories(&conn)
             .unwrap()
             .iter()
@@ -378,7 +757,7 @@ mod tests {
     #[test]
     fn test_name_uniqueness_excludes_self_on_update() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "Self Update", "expense", None, None).unwrap();
+        add_category(&conn, "Self Update", "expense", None, None, None).unwrap();
         let id = list_categories(&conn)
             .unwrap()
             .iter()
@@ -387,14 +766,14 @@ mod tests {
             .id;
 
         // Updating to the same name should succeed
-        update_category(&conn, id, "Self Update", "income", None, None).unwrap();
+        update_category(&conn, id, "Self Update", "income", None, None, None).unwrap();
     }
 
     #[test]
     fn test_update_duplicate_name_rejected() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "Cat A", "expense", None, None).unwrap();
-        add_category(&conn, "Cat B", "expense", None, None).unwrap();
+        add_category(&conn, "Cat A", "expense", None, None, None).unwrap();
+        add_category(&conn, "Cat B", "expense", None, None, None).unwrap();
         let id_b = list_categories(&conn)
             .unwrap()
             .iter()
@@ -403,14 +782,14 @@ mod tests {
             .id;
 
         // Updating Cat B to Cat A's name should fail
-        let err = update_category(&conn, id_b, "Cat A", "expense", None, None).unwrap_err();
+        let err = update_category(&conn, id_b, "Cat A", "expense", None, None, None).unwrap_err();
         assert!(err.to_string().contains("already exists"));
     }
 
     #[test]
     fn test_delete_unused_category() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "To Delete", "expense", None, None).unwrap();
+        add_category(&conn, "To Delete", "expense", None, None, None).unwrap();
         let id = list_categories(&conn)
             .unwrap()
             .iter()
@@ -427,7 +806,7 @@ mod tests {
     #[test]
     fn test_delete_category_with_transactions_blocked() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "Has Txns", "expense", None, None).unwrap();
+        add_category(&conn, "Has Txns", "expense", None, None, None).unwrap();
         let cat_id = list_categories(&conn)
             .unwrap()
             .iter()
@@ -463,7 +842,7 @@ mod tests {
     #[test]
     fn test_delete_category_with_active_rules_blocked() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "Has Rules", "expense", None, None).unwrap();
+        add_category(&conn, "Has Rules", "expense", None, None, None).unwrap();
         let cat_id = list_categories(&conn)
             .unwrap()
             .iter()
@@ -485,7 +864,7 @@ mod tests {
     #[test]
     fn test_usage_count() {
         let (_dir, conn) = test_conn();
-        add_category(&conn, "Counted", "expense", None, None).unwrap();
+        add_category(&conn, "Counted", "expense", None, None, None).unwrap();
         let cat_id = list_categories(&conn)
             .unwrap()
             .iter()
@@ -556,7 +935,202 @@ mod tests {
     #[test]
     fn test_update_nonexistent_category() {
         let (_dir, conn) = test_conn();
-        let err = update_category(&conn, 99999, "Nope", "income", None, None).unwrap_err();
+        let err = update_category(&conn, 99999, "Nope", "income", None, None, None).unwrap_err();
         assert!(err.to_string().contains("Category not found"));
     }
+
+    fn id_of(conn: &Connection, name: &str) -> i64 {
+        list_categories(conn)
+            .unwrap()
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap()
+            .id
+    }
+
+    #[test]
+    fn test_sub_category_listed_after_parent_with_path() {
+        let (_dir, conn) = test_conn();
+        add_category(&conn, "Software", "expense", None, None, None).unwrap();
+        let parent = id_of(&conn, "Software");
+        add_category(&conn, "Design tools", "expense", None, None, Some(parent)).unwrap();
+
+        let categories = list_categories(&conn).unwrap();
+        let parent_idx = categories.iter().position(|c| c.id == parent).unwrap();
+        let child = &categories[parent_idx + 1];
+        assert_eq!(child.name, "Design tools");
+        assert_eq!(child.parent_id, Some(parent));
+        assert_eq!(child.path, "Software > Design tools");
+    }
+
+    #[test]
+    fn test_parent_must_share_type() {
+        let (_dir, conn) = test_conn();
+        let income = id_of(&conn, "Client Services");
+        let err = add_category(&conn, "Tools", "expense", None, None, Some(income)).unwrap_err();
+        assert!(err.to_string().contains("must also be expense"));
+    }
+
+    #[test]
+    fn test_parent_cycle_rejected() {
+        let (_dir, conn) = test_conn();
+        add_category(&conn, "Software", "expense", None, None, None).unwrap();
+        let parent = id_of(&conn, "Software");
+        add_category(&conn, "Design tools", "expense", None, None, Some(parent)).unwrap();
+        let child = id_of(&conn, "Design tools");
+
+        let err = update_category(
+            &conn,
+            parent,
+            "Software",
+            "expense",
+            None,
+            None,
+            Some(child),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("nested under itself"));
+        let err = update_category(
+            &conn,
+            parent,
+            "Software",
+            "expense",
+            None,
+            None,
+            Some(parent),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("nested under itself"));
+    }
+
+    #[test]
+    fn test_rename_keeps_parent() {
+        let (_dir, conn) = test_conn();
+        add_category(&conn, "Software", "expense", None, None, None).unwrap();
+        let parent = id_of(&conn, "Software");
+        add_category(&conn, "Design", "expense", None, None, Some(parent)).unwrap();
+        let child = id_of(&conn, "Design");
+        rename_category(&conn, child, "Design tools").unwrap();
+        let cat = list_categories(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|c| c.id == child)
+            .unwrap();
+        assert_eq!(cat.parent_id, Some(parent));
+    }
+
+    #[test]
+    fn test_delete_blocked_by_sub_categories() {
+        let (_dir, conn) = test_conn();
+        add_category(&conn, "Software", "expense", None, None, None).unwrap();
+        let parent = id_of(&conn, "Software");
+        add_category(&conn, "Design tools", "expense", None, None, Some(parent)).unwrap();
+        let reason = blocking_reason(&conn, parent).unwrap().unwrap();
+        assert_eq!(reason, "Cannot delete: category has 1 sub-category");
+        assert!(delete_category(&conn, parent).is_err());
+    }
+
+    #[test]
+    fn test_category_usage_lists_rules_and_children() {
+        let (_dir, conn) = test_conn();
+        add_category(&conn, "Software", "expense", None, None, None).unwrap();
+        let parent = id_of(&conn, "Software");
+        assert!(category_usage(&conn, parent).unwrap().is_empty());
+
+        add_category(&conn, "Design tools", "expense", None, None, Some(parent)).unwrap();
+        conn.execute(
+            "INSERT INTO rules (pattern, match_type, category_id, priority) VALUES ('ADOBE', 'contains', ?1, 5)",
+            [parent],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO rules (pattern, category_id, is_active) VALUES ('OLD', ?1, 0)",
+            [parent],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, '2025-01-05', 'ADOBE', -54.99, ?1)",
+            [parent],
+        )
+        .unwrap();
+
+        let usage = category_usage(&conn, parent).unwrap();
+        assert_eq!(usage.transactions, 1);
+        assert_eq!(usage.rules.len(), 1, "inactive rules don't count");
+        assert_eq!(usage.rules[0].1, "contains");
+        assert_eq!(usage.rules[0].2, "ADOBE");
+        assert_eq!(usage.children, vec!["Design tools".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_moves_transactions_rules_and_children() {
+        let (_dir, conn) = test_conn();
+        add_category(&conn, "Software", "expense", None, None, None).unwrap();
+        add_category(&conn, "Subscriptions", "expense", None, None, None).unwrap();
+        let (from, into) = (id_of(&conn, "Software"), id_of(&conn, "Subscriptions"));
+        add_category(&conn, "Design tools", "expense", None, None, Some(from)).unwrap();
+        conn.execute(
+            "INSERT INTO rules (pattern, category_id) VALUES ('ADOBE', ?1)",
+            [from],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, '2025-01-05', 'ADOBE', -54.99, ?1)",
+            [from],
+        )
+        .unwrap();
+
+        assert!(merge_category(&conn, from, from, false).is_err());
+        let outcome = merge_category(&conn, from, into, false).unwrap();
+        assert_eq!(
+            (outcome.transactions, outcome.rules, outcome.sub_categories),
+            (1, 1, 1)
+        );
+        assert!(find_category(&conn, "software").is_err());
+        assert_eq!(
+            find_category(&conn, "design TOOLS").unwrap().parent_id,
+            Some(into)
+        );
+        let usage = category_usage(&conn, into).unwrap();
+        assert_eq!((usage.transactions, usage.rules.len()), (1, 1));
+        let logged: String = conn
+            .query_row(
+                "SELECT detail FROM audit_log WHERE action = 'category.merge'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(logged, outcome.summary());
+    }
+
+    #[test]
+    fn test_merge_across_types_needs_allowing() {
+        let (_dir, conn) = test_conn();
+        add_category(&conn, "Refunds", "income", None, None, None).unwrap();
+        add_category(&conn, "Returns", "expense", None, None, None).unwrap();
+        let (from, into) = (id_of(&conn, "Refunds"), id_of(&conn, "Returns"));
+        let err = merge_category(&conn, from, into, false).unwrap_err();
+        assert!(err.to_string().contains("'Refunds' is income"));
+        merge_category(&conn, from, into, true).unwrap();
+        assert!(find_category(&conn, "Refunds").is_err());
+
+        // Nor into one of its own sub-categories
+        add_category(&conn, "Trips", "expense", None, None, None).unwrap();
+        let trips = id_of(&conn, "Trips");
+        add_category(&conn, "Flights", "expense", None, None, Some(trips)).unwrap();
+        let err = merge_category(&conn, trips, id_of(&conn, "Flights"), false).unwrap_err();
+        assert!(err.to_string().contains("its own sub-category"));
+    }
 }
@@ -4,11 +4,38 @@ use crate::error::Result;
 use crate::settings::get_data_dir;
 
 pub fn run() -> Result<()> {
-    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let snap_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "categorize")?;
+    println!("Pre-categorize snapshot saved to {}", snap_path.display());
     let result = categorize_transactions(&conn)?;
     println!(
         "{} categorized, {} still flagged",
         result.categorized, result.still_flagged
     );
+    if result.card_payments > 0 {
+        println!(
+            "{} credit card payments matched across accounts",
+            result.card_payments
+        );
+    }
+    if result.sign_warnings > 0 {
+        println!(
+            "{} flagged for a sign that doesn't match the category (see `nigel report flagged`)",
+            result.sign_warnings
+        );
+    }
+    if result.anomalies > 0 {
+        println!(
+            "{} flagged as unusual for their vendor (see `nigel report flagged`)",
+            result.anomalies
+        );
+    }
+    if result.out_of_range > 0 {
+        println!(
+            "{} flagged for a date out of range (see `nigel report quality`)",
+            result.out_of_range
+        );
+    }
     Ok(())
 }
@@ -8,20 +8,28 @@ use ratatui::{
 };
 use rusqlite::Connection;
 
-use crate::cli::categories::{self, CategoryRow};
-use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
-
-const CATEGORY_TYPES: &[&str] = &["expense", "income"];
+use crate::browser::RegisterBrowser;
+use crate::cli::categories::{self, CategoryRow, CategoryUsage, CATEGORY_TYPES};
+use crate::cli::rules_manager::RulesManager;
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
+use crate::error::Result;
+use crate::reports::RegisterQuery;
+use crate::reports::CATEGORY_PATH_SEP;
+use crate::tui::{truncate, FOOTER_STYLE, HEADER_STYLE};
 
 // Field indices for CategoryForm — keep in sync with field order
 const NAME_IDX: usize = 0;
 const TYPE_IDX: usize = 1;
-const TAX_LINE_IDX: usize = 2;
-const FORM_LINE_IDX: usize = 3;
+const PARENT_IDX: usize = 2;
+const TAX_LINE_IDX: usize = 3;
+const FORM_LINE_IDX: usize = 4;
 
 pub enum CategoryAction {
     Continue,
     Close,
+    /// Open the register filtered to this category (id, name).
+    BrowseCategory(i64, String),
+    OpenRules,
 }
 
 enum Screen {
@@ -29,11 +37,22 @@ enum Screen {
     Add(CategoryForm),
     Edit(CategoryForm),
     ConfirmDelete,
+    /// Delete was blocked; shows what still uses the selected category.
+    Usage(CategoryUsage),
+    /// Picking the category to merge the selected one into (an index into
+    /// `categories`); `confirm_type` once Enter was pressed on a target of
+    /// another type.
+    Merge {
+        target: usize,
+        confirm_type: bool,
+    },
 }
 
 struct CategoryForm {
     fields: Vec<FormField>,
     focused: usize,
+    /// Category IDs behind the Parent selector's options (None = top level).
+    parent_ids: Vec<Option<i64>>,
 }
 
 struct FormField {
@@ -50,8 +69,37 @@ enum FieldKind {
     },
 }
 
+/// Parent selector options: "(none)" then every category that may become
+/// the parent of `exclude` — i.e. not the category itself or its descendants.
+fn parent_field(
+    categories: &[CategoryRow],
+    exclude: Option<&CategoryRow>,
+    current: Option<i64>,
+) -> (FormField, Vec<Option<i64>>) {
+    let mut options = vec!["(none)".to_string()];
+    let mut ids = vec![None];
+    for cat in categories {
+        if let Some(ex) = exclude {
+            let subtree = format!("{}{}", ex.path, CATEGORY_PATH_SEP);
+            if cat.id == ex.id || cat.path.starts_with(&subtree) {
+                continue;
+            }
+        }
+        options.push(cat.path.clone());
+        ids.push(Some(cat.id));
+    }
+    let selected = ids.iter().position(|id| *id == current).unwrap_or(0);
+    let field = FormField {
+        label: "Parent",
+        value: options[selected].clone(),
+        kind: FieldKind::Selector { options, selected },
+    };
+    (field, ids)
+}
+
 impl CategoryForm {
-    fn new_add() -> Self {
+    fn new_add(categories: &[CategoryRow]) -> Self {
+        let (parent, parent_ids) = parent_field(categories, None, None);
         Self {
             fields: vec![
                 FormField {
@@ -67,6 +115,7 @@ impl CategoryForm {
                         selected: 0,
                     },
                 },
+                parent,
                 FormField {
                     label: "Tax Line",
                     value: String::new(),
@@ -79,14 +128,16 @@ impl CategoryForm {
                 },
             ],
             focused: 0,
+            parent_ids,
         }
     }
 
-    fn new_edit(cat: &CategoryRow) -> Self {
+    fn new_edit(cat: &CategoryRow, categories: &[CategoryRow]) -> Self {
         let type_idx = CATEGORY_TYPES
             .iter()
             .position(|t| *t == cat.category_type)
             .unwrap_or(0);
+        let (parent, parent_ids) = parent_field(categories, Some(cat), cat.parent_id);
         Self {
             fields: vec![
                 FormField {
@@ -102,6 +153,7 @@ impl CategoryForm {
                         selected: type_idx,
                     },
                 },
+                parent,
                 FormField {
                     label: "Tax Line",
                     value: cat.tax_line.clone().unwrap_or_default(),
@@ -114,6 +166,7 @@ impl CategoryForm {
                 },
             ],
             focused: 0,
+            parent_ids,
         }
     }
 }
@@ -128,6 +181,10 @@ pub struct CategoryManager {
     /// Remaining keypresses before the status message is cleared.
     status_ttl: u8,
     greeting: String,
+    /// First-run review of the seeded chart before any data exists.
+    setup: bool,
+    /// Set by a save, until the dashboard is told.
+    changed: bool,
 }
 
 impl CategoryManager {
@@ -142,6 +199,17 @@ impl CategoryManager {
             status_message: None,
             status_ttl: 0,
             greeting: greeting.to_string(),
+            setup: false,
+            changed: false,
+        }
+    }
+
+    /// The onboarding step after "Start from scratch": the same list, framed
+    /// as a review of the seeded categories, with Enter to finish.
+    pub fn for_setup(conn: &Connection, greeting: &str) -> Self {
+        Self {
+            setup: true,
+            ..Self::new(conn, greeting)
         }
     }
 
@@ -154,9 +222,32 @@ impl CategoryManager {
         }
     }
 
+    /// Back from browsing or rules after a blocked delete: refresh the usage,
+    /// and offer the delete once nothing uses the category any more.
+    pub fn resume(&mut self, conn: &Connection) {
+        self.reload(conn);
+        if !matches!(self.screen, Screen::Usage(_)) {
+            return;
+        }
+        let Some(cat) = self.categories.get(self.selection) else {
+            self.screen = Screen::List;
+            return;
+        };
+        match categories::category_usage(conn, cat.id) {
+            Ok(usage) if usage.is_empty() => self.screen = Screen::ConfirmDelete,
+            Ok(usage) => self.screen = Screen::Usage(usage),
+            Err(e) => {
+                self.screen = Screen::List;
+                self.set_status(format!("Error: {e}"));
+            }
+        }
+    }
+
     pub fn draw(&mut self, frame: &mut Frame) {
         match &self.screen {
-            Screen::List | Screen::ConfirmDelete => self.draw_list(frame),
+            Screen::List | Screen::ConfirmDelete | Screen::Usage(_) | Screen::Merge { .. } => {
+                self.draw_list(frame)
+            }
             Screen::Add(form) => self.draw_form(frame, "Add Category", form),
             Screen::Edit(form) => self.draw_form(frame, "Edit Category", form),
         }
@@ -176,27 +267,42 @@ impl CategoryManager {
 
         // Header
         frame.render_widget(
-            Paragraph::new(format!(" Nigel: {}", self.greeting)).style(HEADER_STYLE),
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
             header_area,
         );
 
         let sep_line = "\u{2501}".repeat(area.width as usize);
         frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
 
-        // Content: title + table
+        // Content: title + table, with room kept below for the delete panel
+        let panel = self.delete_panel();
         let visible_height = content_area.height as usize;
         // 3 lines for title area + 1 for column header = 4 lines overhead
-        let data_rows = visible_height.saturating_sub(4);
+        let data_rows = visible_height.saturating_sub(4 + panel.len());
         self.last_visible_rows = data_rows;
 
-        let mut lines = vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                format!(" Chart of Accounts ({})", self.categories.len()),
-                Style::default().add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-        ];
+        let mut lines = if self.setup {
+            vec![
+                Line::from(Span::styled(
+                    format!(" Review Your Chart of Accounts ({})", self.categories.len()),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::from(Span::styled(
+                    " Nothing is imported yet: rename, remove, or add categories now so the books start clean.",
+                    Style::default().fg(Color::DarkGray),
+                )),
+                Line::from(""),
+            ]
+        } else {
+            vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!(" Chart of Accounts ({})", self.categories.len()),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+            ]
+        };
 
         if self.categories.is_empty() {
             lines.push(Line::from("   No categories. Press 'a' to add one."));
@@ -219,7 +325,11 @@ impl CategoryManager {
             let end = (self.scroll_offset + data_rows).min(self.categories.len());
             for i in self.scroll_offset..end {
                 let cat = &self.categories[i];
-                let marker = if i == self.selection { " > " } else { "   " };
+                let marker = match &self.screen {
+                    Screen::Merge { target, .. } if *target == i => " \u{2192} ",
+                    _ if i == self.selection => " > ",
+                    _ => "   ",
+                };
                 let style = if i == self.selection {
                     Style::default().add_modifier(Modifier::BOLD)
                 } else {
@@ -227,10 +337,13 @@ impl CategoryManager {
                 };
                 let tax = cat.tax_line.as_deref().unwrap_or("");
                 let form = cat.form_line.as_deref().unwrap_or("");
+                // Sub-categories are indented under their parent
+                let level = cat.path.matches(CATEGORY_PATH_SEP).count();
+                let name = format!("{}{}", "  ".repeat(level), cat.name);
                 lines.push(Line::from(Span::styled(
                     format!(
                         "{marker}{:<28} {:<10} {:<20} {}",
-                        truncate(&cat.name, 26),
+                        truncate(&name, 26),
                         cat.category_type,
                         truncate(tax, 18),
                         form
@@ -240,17 +353,7 @@ impl CategoryManager {
             }
         }
 
-        // Delete confirmation inline
-        if let Screen::ConfirmDelete = &self.screen {
-            if let Some(cat) = self.categories.get(self.selection) {
-                lines.push(Line::from(""));
-                lines.push(Line::from(Span::styled(
-                    format!("   Delete '{}'? (y/n)", cat.name),
-                    Style::default().fg(Color::Yellow),
-                )));
-            }
-        }
-
+        lines.extend(panel);
         frame.render_widget(Paragraph::new(lines), content_area);
 
         // Hints / status
@@ -264,14 +367,118 @@ impl CategoryManager {
                 Paragraph::new(" y=confirm  n=cancel").style(FOOTER_STYLE),
                 hints_area,
             );
+        } else if let Screen::Merge { .. } = &self.screen {
+            frame.render_widget(
+                Paragraph::new(" Up/Down=choose target  Enter=merge  Esc=cancel")
+                    .style(FOOTER_STYLE),
+                hints_area,
+            );
+        } else if let Screen::Usage(usage) = &self.screen {
+            let browse = if usage.transactions > 0 {
+                "b=browse its transactions  "
+            } else {
+                ""
+            };
+            let rules = if usage.rules.is_empty() {
+                ""
+            } else {
+                "r=open rules  "
+            };
+            frame.render_widget(
+                Paragraph::new(format!(" {browse}{rules}Esc=back")).style(FOOTER_STYLE),
+                hints_area,
+            );
+        } else if self.setup {
+            frame.render_widget(
+                Paragraph::new(" e=rename/edit  d=remove  a=add  Enter=done").style(FOOTER_STYLE),
+                hints_area,
+            );
+        } else if crate::db::is_read_only() {
+            frame.render_widget(
+                Paragraph::new(" Esc=back  q=quit").style(FOOTER_STYLE),
+                hints_area,
+            );
         } else {
             frame.render_widget(
-                Paragraph::new(" a=add  e=edit  d=delete  Esc=back  q=quit").style(FOOTER_STYLE),
+                Paragraph::new(" a=add  e=edit  d=delete  m=merge  Esc=back  q=quit")
+                    .style(FOOTER_STYLE),
                 hints_area,
             );
         }
     }
 
+    /// Lines under the table for the delete confirmation or usage drill-down.
+    fn delete_panel(&self) -> Vec<Line<'static>> {
+        let Some(cat) = self.categories.get(self.selection) else {
+            return vec![];
+        };
+        let warn = Style::default().fg(Color::Yellow);
+        match &self.screen {
+            Screen::ConfirmDelete => vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!("   Delete '{}'? (y/n)", cat.name),
+                    warn,
+                )),
+            ],
+            Screen::Merge {
+                target,
+                confirm_type,
+            } => {
+                let Some(into) = self.categories.get(*target) else {
+                    return vec![];
+                };
+                let mut lines = vec![
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        format!(
+                            "   Merge '{}' into '{}'? Its transactions and rules move over.",
+                            cat.name, into.name
+                        ),
+                        warn,
+                    )),
+                ];
+                if *confirm_type {
+                    lines.push(Line::from(format!(
+                        "     '{}' is {} and '{}' is {} \u{2014} press Enter again to merge anyway",
+                        cat.name, cat.category_type, into.name, into.category_type
+                    )));
+                }
+                lines
+            }
+            Screen::Usage(usage) => {
+                let mut lines = vec![
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        format!("   '{}' can't be deleted while it's in use:", cat.name),
+                        warn,
+                    )),
+                ];
+                if usage.transactions > 0 {
+                    let noun = if usage.transactions == 1 {
+                        "transaction"
+                    } else {
+                        "transactions"
+                    };
+                    lines.push(Line::from(format!(
+                        "     {} {noun} \u{2014} press b to recategorize them",
+                        usage.transactions
+                    )));
+                }
+                for (id, match_type, pattern) in &usage.rules {
+                    lines.push(Line::from(format!(
+                        "     Rule #{id}: {match_type} \"{pattern}\""
+                    )));
+                }
+                for child in &usage.children {
+                    lines.push(Line::from(format!("     Sub-category: {child}")));
+                }
+                lines
+            }
+            _ => vec![],
+        }
+    }
+
     fn draw_form(&self, frame: &mut Frame, title: &str, form: &CategoryForm) {
         let area = frame.area();
         let border_style = Style::default().fg(Color::DarkGray);
@@ -285,7 +492,7 @@ impl CategoryManager {
         .areas(area);
 
         frame.render_widget(
-            Paragraph::new(format!(" Nigel: {}", self.greeting)).style(HEADER_STYLE),
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
             header_area,
         );
 
@@ -390,11 +597,16 @@ impl CategoryManager {
             Screen::Add(_) => self.handle_form_key(code, conn, FormMode::Add),
             Screen::Edit(_) => self.handle_form_key(code, conn, FormMode::Edit),
             Screen::ConfirmDelete => self.handle_delete_key(code, conn),
+            Screen::Usage(_) => self.handle_usage_key(code),
+            Screen::Merge { .. } => self.handle_merge_key(code, conn),
         }
     }
 
     fn handle_list_key(&mut self, code: KeyCode, conn: &Connection) -> CategoryAction {
         match code {
+            KeyCode::Char('a' | 'e' | 'd' | 'm') if crate::db::is_read_only() => {
+                self.set_status(crate::tui::READ_ONLY_STATUS.into());
+            }
             KeyCode::Up => {
                 self.selection = self.selection.saturating_sub(1);
                 self.ensure_visible(self.last_visible_rows);
@@ -406,23 +618,31 @@ impl CategoryManager {
                 }
             }
             KeyCode::Char('a') => {
-                self.screen = Screen::Add(CategoryForm::new_add());
+                self.screen = Screen::Add(CategoryForm::new_add(&self.categories));
             }
             KeyCode::Char('e') => {
                 if let Some(cat) = self.categories.get(self.selection) {
-                    self.screen = Screen::Edit(CategoryForm::new_edit(cat));
+                    self.screen = Screen::Edit(CategoryForm::new_edit(cat, &self.categories));
                 }
             }
             KeyCode::Char('d') => {
                 if let Some(cat) = self.categories.get(self.selection) {
-                    match categories::blocking_reason(conn, cat.id) {
-                        Ok(Some(reason)) => self.set_status(reason),
-                        Ok(None) => self.screen = Screen::ConfirmDelete,
+                    match categories::category_usage(conn, cat.id) {
+                        Ok(usage) if usage.is_empty() => self.screen = Screen::ConfirmDelete,
+                        Ok(usage) => self.screen = Screen::Usage(usage),
                         Err(e) => self.set_status(format!("Error: {e}")),
                     }
                 }
             }
+            KeyCode::Char('m') if self.categories.len() > 1 => {
+                let target = if self.selection == 0 { 1 } else { 0 };
+                self.screen = Screen::Merge {
+                    target,
+                    confirm_type: false,
+                };
+            }
             KeyCode::Char('q') | KeyCode::Esc => return CategoryAction::Close,
+            KeyCode::Enter if self.setup => return CategoryAction::Close,
             _ => {}
         }
         CategoryAction::Continue
@@ -491,6 +711,12 @@ impl CategoryManager {
                     return CategoryAction::Continue;
                 }
                 let cat_type = form.fields[TYPE_IDX].value.clone();
+                let parent_id = match &form.fields[PARENT_IDX].kind {
+                    FieldKind::Selector { selected, .. } => {
+                        form.parent_ids.get(*selected).copied().flatten()
+                    }
+                    FieldKind::Text => None,
+                };
                 let tax_line = {
                     let v = form.fields[TAX_LINE_IDX].value.trim().to_string();
                     if v.is_empty() {
@@ -516,9 +742,11 @@ impl CategoryManager {
                             &cat_type,
                             tax_line.as_deref(),
                             form_line.as_deref(),
+                            parent_id,
                         ) {
                             Ok(()) => {
                                 self.reload(conn);
+                                self.changed = true;
                                 self.screen = Screen::List;
                                 self.set_status(format!("Added category: {name}"));
                             }
@@ -536,9 +764,11 @@ impl CategoryManager {
                                 &cat_type,
                                 tax_line.as_deref(),
                                 form_line.as_deref(),
+                                parent_id,
                             ) {
                                 Ok(()) => {
                                     self.reload(conn);
+                                    self.changed = true;
                                     self.screen = Screen::List;
                                     self.set_status(format!("Updated category: {name}"));
                                 }
@@ -555,6 +785,86 @@ impl CategoryManager {
         CategoryAction::Continue
     }
 
+    fn handle_usage_key(&mut self, code: KeyCode) -> CategoryAction {
+        let Screen::Usage(usage) = &self.screen else {
+            return CategoryAction::Continue;
+        };
+        match code {
+            KeyCode::Char('b') if usage.transactions > 0 => {
+                if let Some(cat) = self.categories.get(self.selection) {
+                    return CategoryAction::BrowseCategory(cat.id, cat.path.clone());
+                }
+            }
+            KeyCode::Char('r') if !usage.rules.is_empty() => return CategoryAction::OpenRules,
+            KeyCode::Esc | KeyCode::Char('q') => self.screen = Screen::List,
+            _ => {}
+        }
+        CategoryAction::Continue
+    }
+
+    fn handle_merge_key(&mut self, code: KeyCode, conn: &Connection) -> CategoryAction {
+        let Screen::Merge {
+            target,
+            confirm_type,
+        } = &mut self.screen
+        else {
+            return CategoryAction::Continue;
+        };
+        let last = self.categories.len().saturating_sub(1);
+        match code {
+            KeyCode::Up => {
+                let mut next = target.saturating_sub(1);
+                if next == self.selection {
+                    next = next.checked_sub(1).unwrap_or(*target);
+                }
+                *target = next;
+                *confirm_type = false;
+            }
+            KeyCode::Down => {
+                let mut next = (*target + 1).min(last);
+                if next == self.selection {
+                    next = if next < last { next + 1 } else { *target };
+                }
+                *target = next;
+                *confirm_type = false;
+            }
+            KeyCode::Enter => {
+                let (Some(from), Some(into)) = (
+                    self.categories.get(self.selection),
+                    self.categories.get(*target),
+                ) else {
+                    return CategoryAction::Continue;
+                };
+                let cross_type = from.category_type != into.category_type;
+                if cross_type && !*confirm_type {
+                    *confirm_type = true;
+                    return CategoryAction::Continue;
+                }
+                // Snapshot beside the book, as the CLI merge does
+                let data_dir = conn
+                    .path()
+                    .filter(|p| !p.is_empty())
+                    .and_then(|p| std::path::Path::new(p).parent())
+                    .map(std::path::Path::to_path_buf)
+                    .unwrap_or_else(crate::settings::get_data_dir);
+                let result = crate::cli::backup::pre_operation_snapshot(conn, &data_dir, "merge")
+                    .and_then(|_| categories::merge_category(conn, from.id, into.id, cross_type));
+                self.screen = Screen::List;
+                match result {
+                    Ok(outcome) => {
+                        self.reload(conn);
+                        self.changed = true;
+                        self.set_status(outcome.summary());
+                    }
+                    Err(e) => self.set_status(e.to_string()),
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.screen = Screen::List,
+            _ => {}
+        }
+        CategoryAction::Continue
+    }
+
     fn handle_delete_key(&mut self, code: KeyCode, conn: &Connection) -> CategoryAction {
         match code {
             KeyCode::Char('y') => {
@@ -563,6 +873,7 @@ impl CategoryManager {
                     match categories::delete_category(conn, cat.id) {
                         Ok(()) => {
                             self.reload(conn);
+                            self.changed = true;
                             self.screen = Screen::List;
                             self.set_status(format!("Deleted category: {name}"));
                         }
@@ -587,11 +898,159 @@ enum FormMode {
     Edit,
 }
 
-fn truncate(s: &str, max: usize) -> String {
-    if s.chars().count() <= max {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max - 1).collect();
-        format!("{truncated}\u{2026}")
+impl screen::Screen for CategoryManager {
+    fn draw(&mut self, frame: &mut Frame) {
+        CategoryManager::draw(self, frame);
+    }
+
+    /// The usage drill-down opens the register or rules over this screen,
+    /// so closing them comes back here.
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        let action = CategoryManager::handle_key(self, code, ctx.conn);
+        if std::mem::take(&mut self.changed) {
+            ctx.publish(DataChange::Categories);
+        }
+        Ok(match action {
+            CategoryAction::Continue => Nav::Stay,
+            CategoryAction::Close => Nav::Pop,
+            CategoryAction::BrowseCategory(id, name) => {
+                let query = RegisterQuery {
+                    category_id: Some(id),
+                    ..Default::default()
+                };
+                match RegisterBrowser::open_screen(ctx, query, format!("category: {name}")) {
+                    Some(browser) => Nav::Push(Box::new(browser)),
+                    None => Nav::Home,
+                }
+            }
+            CategoryAction::OpenRules => {
+                Nav::Push(Box::new(RulesManager::new(ctx.conn, &self.greeting)))
+            }
+        })
+    }
+
+    /// Edits made in the register or rules opened from the usage
+    /// drill-down may have freed the category for deleting.
+    fn on_data_changed(&mut self, _change: DataChange, ctx: &mut ScreenContext) {
+        self.resume(ctx.conn);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn test_blocked_delete_drills_into_usage() {
+        let (_dir, conn) = test_db();
+        categories::add_category(&conn, "Doomed", "expense", None, None, None).unwrap();
+        let mut mgr = CategoryManager::new(&conn, "Hi");
+        mgr.selection = mgr
+            .categories
+            .iter()
+            .position(|c| c.name == "Doomed")
+            .unwrap();
+        let cat_id = mgr.categories[mgr.selection].id;
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, '2025-01-05', 'COFFEE', -4.5, ?1)",
+            [cat_id],
+        )
+        .unwrap();
+
+        mgr.handle_key(KeyCode::Char('d'), &conn);
+        assert!(matches!(&mgr.screen, Screen::Usage(u) if u.transactions == 1));
+        // No rules, so `r` does nothing
+        assert!(matches!(
+            mgr.handle_key(KeyCode::Char('r'), &conn),
+            CategoryAction::Continue
+        ));
+        assert!(matches!(
+            mgr.handle_key(KeyCode::Char('b'), &conn),
+            CategoryAction::BrowseCategory(id, _) if id == cat_id
+        ));
+
+        // Once the transaction is re-homed, coming back offers the delete
+        conn.execute("UPDATE transactions SET category_id = NULL", [])
+            .unwrap();
+        mgr.resume(&conn);
+        assert!(matches!(mgr.screen, Screen::ConfirmDelete));
+        mgr.handle_key(KeyCode::Char('y'), &conn);
+        assert!(!mgr.categories.iter().any(|c| c.name == "Doomed"));
+    }
+
+    #[test]
+    fn test_setup_review_renames_removes_and_finishes() {
+        let (_dir, conn) = test_db();
+        let mut mgr = CategoryManager::for_setup(&conn, "Hi");
+        let seeded = mgr.categories.len();
+        assert!(seeded > 0);
+
+        // Rename the first seeded category
+        mgr.handle_key(KeyCode::Char('e'), &conn);
+        if let Screen::Edit(form) = &mut mgr.screen {
+            form.fields[NAME_IDX].value = "Consulting Fees".to_string();
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(mgr.categories.iter().any(|c| c.name == "Consulting Fees"));
+
+        // Remove one; nothing uses it yet, so it goes straight to the confirm
+        mgr.selection = mgr.categories.len() - 1;
+        let doomed = mgr.categories[mgr.selection].name.clone();
+        mgr.handle_key(KeyCode::Char('d'), &conn);
+        assert!(matches!(mgr.screen, Screen::ConfirmDelete));
+        mgr.handle_key(KeyCode::Char('y'), &conn);
+        assert!(!mgr.categories.iter().any(|c| c.name == doomed));
+        assert_eq!(mgr.categories.len(), seeded - 1);
+
+        assert!(matches!(
+            mgr.handle_key(KeyCode::Enter, &conn),
+            CategoryAction::Close
+        ));
+    }
+    #[test]
+    fn test_merge_asks_again_across_types() {
+        let (_dir, conn) = test_db();
+        categories::add_category(&conn, "Old Income", "income", None, None, None).unwrap();
+        let mut mgr = CategoryManager::new(&conn, "Hi");
+        mgr.selection = mgr
+            .categories
+            .iter()
+            .position(|c| c.name == "Old Income")
+            .unwrap();
+        mgr.handle_key(KeyCode::Char('m'), &conn);
+        let expense = mgr
+            .categories
+            .iter()
+            .position(|c| c.category_type == "expense")
+            .unwrap();
+        if let Screen::Merge { target, .. } = &mut mgr.screen {
+            *target = expense;
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(
+            mgr.screen,
+            Screen::Merge {
+                confirm_type: true,
+                ..
+            }
+        ));
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(mgr.screen, Screen::List));
+        assert!(!mgr.categories.iter().any(|c| c.name == "Old Income"));
+        assert!(mgr.changed);
     }
 }
@@ -0,0 +1,58 @@
+use comfy_table::{Cell, Table};
+
+use crate::clients;
+use crate::db::get_connection;
+use crate::error::Result;
+use crate::settings::get_data_dir;
+
+pub fn list() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let clients = clients::list(&conn)?;
+    if clients.is_empty() {
+        println!("No clients. Add one with `nigel clients add <name>`.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Client", "Transactions"]);
+    for c in &clients {
+        table.add_row(vec![Cell::new(&c.name), Cell::new(c.transactions)]);
+    }
+    println!("Clients\n{table}");
+    Ok(())
+}
+
+pub fn add(name: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let name = clients::add(&conn, name)?;
+    println!("Added client {name}");
+    Ok(())
+}
+
+pub fn remove(name: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let unassigned = clients::remove(&conn, name)?;
+    println!(
+        "Removed client {} ({} unassigned)",
+        name.trim(),
+        transactions(unassigned)
+    );
+    Ok(())
+}
+
+pub fn assign(ids: &[i64], client: Option<&str>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    match clients::assign(&conn, ids, client)? {
+        Some(name) => println!("Assigned {} to {name}", transactions(ids.len())),
+        None => println!("Unassigned {}", transactions(ids.len())),
+    }
+    Ok(())
+}
+
+fn transactions(n: usize) -> String {
+    if n == 1 {
+        "1 transaction".to_string()
+    } else {
+        format!("{n} transactions")
+    }
+}
@@ -0,0 +1,103 @@
+use comfy_table::{Cell, Table};
+
+use crate::db::get_connection;
+use crate::digest;
+use crate::error::{NigelError, Result};
+use crate::fmt::money;
+use crate::month_close::{self, ClosePacket};
+use crate::settings::{get_data_dir, restrict_file_permissions};
+
+/// PDF when this build can write one, otherwise HTML.
+fn default_format() -> &'static str {
+    if cfg!(feature = "pdf") {
+        "pdf"
+    } else {
+        "html"
+    }
+}
+
+/// Close a month: check it, write its packet to `closes/`, and record it.
+pub fn run(month: Option<String>, format: Option<String>) -> Result<()> {
+    let (year, month) = match month {
+        Some(m) => digest::parse_month(&m)?,
+        None => digest::last_month(chrono::Local::now().date_naive()),
+    };
+    let format = format.unwrap_or_else(|| default_format().to_string());
+    if format != "pdf" && format != "html" {
+        return Err(NigelError::Other(format!(
+            "Unknown format '{format}'; use pdf or html"
+        )));
+    }
+
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let check = month_close::check(&conn, year, month)?;
+    let packet = ClosePacket::build(&conn, check)?;
+    let bytes = match format.as_str() {
+        "pdf" => render_pdf(&packet)?,
+        _ => packet.to_html().into_bytes(),
+    };
+
+    let path = month_close::packet_path(&data_dir, &packet.period(), &format);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, bytes)?;
+    restrict_file_permissions(&path)?;
+    let id = month_close::record(&conn, &data_dir, &packet, &path)?;
+
+    println!(
+        "Closed {}: {} account(s) reconciled, nothing flagged, net {}",
+        packet.label(),
+        packet.certificates.len(),
+        money(packet.pnl.net)
+    );
+    println!("Close packet #{id} saved to {}", path.display());
+    Ok(())
+}
+
+#[cfg(feature = "pdf")]
+fn render_pdf(packet: &ClosePacket) -> Result<Vec<u8>> {
+    crate::pdf::render_close(packet)
+}
+
+#[cfg(not(feature = "pdf"))]
+fn render_pdf(_packet: &ClosePacket) -> Result<Vec<u8>> {
+    Err(NigelError::Other(
+        "PDF export requires the 'pdf' feature; use --format html".into(),
+    ))
+}
+
+pub fn list() -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let closes = month_close::list(&conn)?;
+    if closes.is_empty() {
+        println!("No months closed yet. Close one with `nigel close --month YYYY-MM`.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Month",
+        "Closed",
+        "Accounts",
+        "Transactions",
+        "Net",
+        "Packet",
+    ]);
+    for c in &closes {
+        table.add_row(vec![
+            Cell::new(c.id),
+            Cell::new(&c.month),
+            Cell::new(&c.closed_at),
+            Cell::new(c.accounts_reconciled),
+            Cell::new(c.transactions),
+            Cell::new(money(c.net)),
+            Cell::new(data_dir.join(&c.packet_path).display()),
+        ]);
+    }
+    println!("Month Closes\n{table}");
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use comfy_table::{Cell, Table};
+use rusqlite::OptionalExtension;
+
+use crate::cli::report::defaults::{self, Field};
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::settings::{get_data_dir, load_settings, save_settings};
+
+pub fn list() -> Result<()> {
+    let settings = load_settings();
+    let mut table = Table::new();
+    table.set_header(vec!["Key", "Value"]);
+    let mut count = 0;
+    for report in settings.report_defaults.keys() {
+        for field in Field::ALL {
+            if let Some(value) = defaults::get(&settings.report_defaults, report, field) {
+                table.add_row(vec![
+                    Cell::new(format!("report.{report}.{}", field.key())),
+                    Cell::new(value),
+                ]);
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        println!(
+            "No report defaults set. Set one with \
+             `nigel config set report.register.default_period month`."
+        );
+        return Ok(());
+    }
+    println!("Report Defaults\n{table}");
+    Ok(())
+}
+
+pub fn get(key: &str) -> Result<()> {
+    let (report, field) = defaults::parse_key(key)?;
+    match defaults::get(&load_settings().report_defaults, report, field) {
+        Some(value) => println!("{value}"),
+        None => println!("(not set)"),
+    }
+    Ok(())
+}
+
+pub fn set(key: &str, value: &str) -> Result<()> {
+    let (report, field) = defaults::parse_key(key)?;
+    if field == Field::Account {
+        check_account(value.trim())?;
+    }
+    let mut settings = load_settings();
+    defaults::set(&mut settings.report_defaults, report, field, Some(value))?;
+    save_settings(&settings)?;
+    println!("Set {key} = {}", value.trim());
+    Ok(())
+}
+
+pub fn unset(key: &str) -> Result<()> {
+    let (report, field) = defaults::parse_key(key)?;
+    let mut settings = load_settings();
+    if defaults::get(&settings.report_defaults, report, field).is_none() {
+        println!("{key} is not set.");
+        return Ok(());
+    }
+    defaults::set(&mut settings.report_defaults, report, field, None)?;
+    save_settings(&settings)?;
+    println!("Cleared {key}");
+    Ok(())
+}
+
+/// A default account filter has to name an account the register can match.
+fn check_account(name: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let found: Option<i64> = conn
+        .query_row("SELECT id FROM accounts WHERE name = ?1", [name], |r| {
+            r.get(0)
+        })
+        .optional()?;
+    if found.is_none() {
+        return Err(NigelError::Other(format!(
+            "No account named '{name}'. `nigel accounts list` shows them."
+        )));
+    }
+    Ok(())
+}
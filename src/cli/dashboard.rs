@@ -1,32 +1,46 @@
-use chrono::Datelike;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use rand::seq::SliceRandom;
 use ratatui::{
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Bar, BarChart, BarGroup, Paragraph},
     Frame,
 };
 
-use crate::browser::{BrowseAction, RegisterBrowser};
-use crate::cli::account_manager::{AccountAction, AccountManager};
-use crate::cli::category_manager::{CategoryAction, CategoryManager};
-use crate::cli::import_manager::{ImportAction, ImportScreen};
-use crate::cli::load_manager::{LoadAction, LoadScreen};
-use crate::cli::reconcile_manager::{ReconcileAction, ReconcileScreen};
-use crate::cli::review::{HandleResult, TransactionReviewer};
-use crate::cli::rules_manager::{RulesAction, RulesManager};
-use crate::cli::settings_manager::{SettingsAction, SettingsManager};
-use crate::cli::snake::{SnakeAction, SnakeGame};
-use crate::cli::undo_manager::{UndoAction, UndoScreen};
+use crate::browser::RegisterBrowser;
+use crate::cli::account_manager::AccountManager;
+use crate::cli::category_manager::CategoryManager;
+use crate::cli::dashboard_widgets::{parse_widgets, row_constraint, DashboardWidget};
+use crate::cli::docs_viewer::DocsViewer;
+use crate::cli::import_history::ImportHistory;
+use crate::cli::import_manager::ImportScreen;
+use crate::cli::load_manager::LoadScreen;
+use crate::cli::palette::{Palette, PaletteAction, PaletteRegistry, PaletteResult};
+use crate::cli::reconcile_manager::ReconcileScreen;
+use crate::cli::report::defaults::{default_for, default_period, Field, Period};
+use crate::cli::report::ReportParams;
+use crate::cli::review::TransactionReviewer;
+use crate::cli::rules_manager::RulesManager;
+use crate::cli::screen::{DataChange, Nav, Screen, ScreenContext, Session};
+use crate::cli::settings_manager::SettingsManager;
+use crate::cli::tutorial::{Step as TutorialStep, Tutorial};
+use crate::cli::undo_manager::UndoScreen;
 use crate::db::get_connection;
 use crate::error::Result;
-use crate::fmt::number;
-use crate::reports;
-use crate::reviewer::{get_categories, get_flagged_transactions};
-use crate::settings::{get_data_dir, load_settings, save_settings, settings_file_exists};
-use crate::tui::{money_span, ReportView, ReportViewAction, FOOTER_STYLE, HEADER_STYLE};
+use crate::fiscal::FiscalYear;
+use crate::fmt::{money_compact, number};
+#[cfg(feature = "games")]
+use crate::games::Arcade;
+use crate::health::{self, HealthAction, HealthWarning};
+use crate::locales::{t, t_args};
+use crate::reports::{self, Granularity};
+use crate::reviewer::get_flagged_transactions;
+use crate::settings::{get_data_dir, load_settings, save_settings, settings_file_exists, Persona};
+use crate::tui::{
+    display_width, money_span, pad_right, truncate, y_axis_ticks, ReportView, ReportViewAction,
+    FOOTER_STYLE, HEADER_STYLE,
+};
 
 const GREETINGS: &[&str] = &[
     "Kettle's on.",
@@ -45,46 +59,134 @@ const GREETINGS: &[&str] = &[
     "Right then, where were we?",
 ];
 
+/// Each menu item's message key (see `locales`) and shortcut.
+#[cfg(feature = "games")]
+const MENU_ITEMS: &[(&str, char)] = &[
+    ("menu.browse", 'b'),
+    ("menu.import", 'i'),
+    ("menu.review", 'r'),
+    ("menu.reconcile", 'c'),
+    ("menu.accounts", 'a'),
+    ("menu.categories", 't'),
+    ("menu.rules", 'u'),
+    ("menu.undo", 'z'),
+    ("menu.history", 'h'),
+    ("menu.view_report", 'v'),
+    ("menu.export_report", 'e'),
+    ("menu.load", 'l'),
+    ("menu.settings", 'p'),
+    ("menu.help", 'd'),
+    ("menu.arcade", 'g'),
+];
+#[cfg(not(feature = "games"))]
 const MENU_ITEMS: &[(&str, char)] = &[
-    ("[b] Browse the register", 'b'),
-    ("[i] Import a statement", 'i'),
-    ("[r] Review flagged transactions", 'r'),
-    ("[c] Reconcile an account", 'c'),
-    ("[a] Add or modify accounts", 'a'),
-    ("[t] Edit chart of accounts", 't'),
-    ("[u] View or edit categorization rules", 'u'),
-    ("[z] Undo last import", 'z'),
-    ("[v] View a report", 'v'),
-    ("[e] Export a report", 'e'),
-    ("[l] Load a different data file", 'l'),
-    ("[p] Settings", 'p'),
-    ("[s] Snake", 's'),
+    ("menu.browse", 'b'),
+    ("menu.import", 'i'),
+    ("menu.review", 'r'),
+    ("menu.reconcile", 'c'),
+    ("menu.accounts", 'a'),
+    ("menu.categories", 't'),
+    ("menu.rules", 'u'),
+    ("menu.undo", 'z'),
+    ("menu.history", 'h'),
+    ("menu.view_report", 'v'),
+    ("menu.export_report", 'e'),
+    ("menu.load", 'l'),
+    ("menu.settings", 'p'),
+    ("menu.help", 'd'),
 ];
 
+/// Menu items that only make changes, closed in read-only mode: import,
+/// review, reconcile, undo, and settings.
+const EDIT_ONLY_MENU_ITEMS: [usize; 5] = [1, 2, 3, 7, 12];
+
 /// Number of menu items in the left column; remainder goes in the right column.
-const MENU_LEFT_COUNT: usize = 7;
+const MENU_LEFT_COUNT: usize = 8;
+
+/// Menu items hidden in simple mode: reconcile.
+const ADVANCED_MENU_ITEMS: [usize; 1] = [3];
+
+/// Reports hidden from the pickers and palette in simple mode: K-1 prep and
+/// tags. Indexed like REPORT_TYPES and EXPORT_TYPES.
+const ADVANCED_REPORTS: [usize; 2] = [7, 10];
+
+/// Whether the report pickers offer report `idx`.
+fn report_shown(simple_mode: bool, idx: usize) -> bool {
+    !(simple_mode && ADVANCED_REPORTS.contains(&idx))
+}
 
+/// Move from `from` to the next (or previous) index below `len` that `shown`
+/// allows, staying put at either end.
+fn step_shown(from: usize, len: usize, down: bool, shown: impl Fn(usize) -> bool) -> usize {
+    let next = if down {
+        (from + 1..len).find(|&i| shown(i))
+    } else {
+        (0..from).rev().find(|&i| shown(i))
+    };
+    next.unwrap_or(from)
+}
+
+/// Message keys of the reports on the view picker.
 const REPORT_TYPES: &[&str] = &[
-    "Profit & Loss",
-    "Expense Breakdown",
-    "Tax Summary",
-    "Cash Flow",
-    "Transaction Register",
-    "Flagged Transactions",
-    "Cash Position",
-    "K-1 Prep (1120-S)",
+    "report.pnl",
+    "report.expenses",
+    "report.tax",
+    "report.cashflow",
+    "report.register",
+    "report.flagged",
+    "report.balance",
+    "report.k1",
+    "report.deductions",
+    "report.compensation",
+    "report.tags",
+    "report.reimbursables",
+    "report.digest",
+    "report.clients",
+    "report.books",
+    "report.quality",
 ];
 
+/// Short names for each report (matching `nigel report <type>`), used as
+/// palette keywords. Indexed like EXPORT_TYPES.
+const REPORT_ALIASES: &[&str] = &[
+    "pnl",
+    "expenses",
+    "tax",
+    "cashflow",
+    "register",
+    "flagged",
+    "balance",
+    "k1",
+    "deductions",
+    "compensation",
+    "tags",
+    "reimbursables",
+    "digest",
+    "clients",
+    "books",
+    "quality",
+    "all",
+];
+
+/// Message keys of the reports on the export picker.
 const EXPORT_TYPES: &[&str] = &[
-    "Profit & Loss",
-    "Expense Breakdown",
-    "Tax Summary",
-    "Cash Flow",
-    "Transaction Register",
-    "Flagged Transactions",
-    "Cash Position",
-    "K-1 Prep (1120-S)",
-    "All Reports",
+    "report.pnl",
+    "report.expenses",
+    "report.tax",
+    "report.cashflow",
+    "report.register",
+    "report.flagged",
+    "report.balance",
+    "report.k1",
+    "report.deductions",
+    "report.compensation",
+    "report.tags",
+    "report.reimbursables",
+    "report.digest",
+    "report.clients",
+    "report.books",
+    "report.quality",
+    "report.all",
 ];
 
 #[derive(Clone, Copy)]
@@ -98,28 +200,14 @@ const EXPORT_FORMATS: &[&str] = &["PDF", "Text"];
 #[cfg(not(feature = "pdf"))]
 const EXPORT_FORMATS: &[&str] = &["Text"];
 
-enum DashboardScreen {
-    Home,
-    Browse(RegisterBrowser),
-    Import(ImportScreen),
-    Review(TransactionReviewer),
-    Accounts(AccountManager),
-    Categories(CategoryManager),
-    Rules(RulesManager),
-    Reconcile(ReconcileScreen),
-    Load(LoadScreen),
-    ReportPicker {
-        selection: usize,
-        mode: ReportPickerMode,
-    },
-    ExportFormatPicker {
-        report_idx: usize,
-        selection: usize,
-    },
-    ReportView(Box<dyn ReportView>),
-    Undo(UndoScreen),
-    Settings(SettingsManager),
-    Snake(SnakeGame),
+/// Preselect the format picker row matching the `export_format` setting; the
+/// picker only offers PDF and text, so csv/xlsx start on the first row.
+fn default_export_selection() -> usize {
+    let format = load_settings().export_format().to_string();
+    EXPORT_FORMATS
+        .iter()
+        .position(|f| f.eq_ignore_ascii_case(&format))
+        .unwrap_or(0)
 }
 
 struct HomeData {
@@ -134,69 +222,241 @@ struct HomeData {
     cashflow_expenses: Vec<u64>,
     cashflow_year_range: String,
     top_expenses: Vec<(String, f64)>,
+    /// Most recent flagged transactions: (date, description, amount)
+    recent_flagged: Vec<(String, String, f64)>,
+    /// Expenses spent so far this month (positive)
+    month_spent: f64,
+    /// Average monthly expenses over the previous three months (positive)
+    budget_avg: f64,
+    /// Recurring charges: (vendor, average monthly amount)
+    recurring: Vec<(String, f64)>,
+    /// Scheduled report exports, soonest next run first
+    schedules: Vec<crate::schedule::ReportSchedule>,
+    /// Top clients by fiscal YTD revenue: (name, revenue, margin %)
+    top_clients: Vec<(String, f64, Option<f64>)>,
 }
 
 struct Dashboard {
-    screen: DashboardScreen,
-    greeting: String,
+    /// Screens open over the home screen; the last one is drawn and gets
+    /// the keys.
+    stack: Vec<Box<dyn Screen>>,
+    session: Session,
     menu_selection: usize,
     home_data: Option<HomeData>,
-    pending_report_view: Option<usize>,
-    pending_export: Option<usize>,
-    pending_text_export: Option<usize>,
-    status_message: Option<String>,
+    /// Set when a screen publishes a change; the home figures reload once
+    /// the home screen shows again.
+    home_stale: bool,
     needs_reload: bool,
-    /// Tracks which report index is currently displayed (for reload on date change)
-    current_report_idx: Option<usize>,
     update_notification: Option<String>,
+    /// Home screen widgets in display order, from settings
+    widgets: Vec<DashboardWidget>,
+    /// Command palette overlay, open over any screen
+    palette: Option<Palette>,
+    /// Startup health checks, shown above the widgets until dismissed
+    health_warnings: Vec<HealthWarning>,
+    warnings_dismissed: bool,
+}
+
+/// Most warnings shown at once; each gets a digit key for its jump.
+const MAX_HEALTH_WARNINGS: usize = 9;
+
+/// Header line shown on every screen. `Minimal` keeps the name but drops the
+/// quip; `Off` is just the app name, safe for screen-sharing.
+fn build_greeting(persona: Persona, user_name: Option<&str>, random_greeting: &str) -> String {
+    let first_name = user_name
+        .and_then(|n| n.split_whitespace().next())
+        .unwrap_or("");
+    match persona {
+        Persona::Off => "Nigel".to_string(),
+        Persona::Minimal if first_name.is_empty() => "Nigel".to_string(),
+        Persona::Minimal => format!("Hello, {first_name}."),
+        Persona::Full if first_name.is_empty() => format!("Nigel: {random_greeting}"),
+        Persona::Full => format!("Hello, {first_name}. {random_greeting}"),
+    }
 }
 
 impl Dashboard {
     fn new(user_name: Option<String>, update_notification: Option<String>) -> Self {
         let mut rng = rand::thread_rng();
-        let random_greeting = GREETINGS.choose(&mut rng).unwrap_or(&"Hello.").to_string();
-        let first_name = user_name
-            .as_deref()
-            .and_then(|n| n.split_whitespace().next())
-            .unwrap_or("");
-        let greeting = if first_name.is_empty() {
-            format!("Nigel: {random_greeting}")
-        } else {
-            format!("Hello, {first_name}. {random_greeting}")
-        };
+        let random_greeting = GREETINGS.choose(&mut rng).unwrap_or(&"Hello.");
+        // Every screen shows the greeting as its header
+        let greeting = build_greeting(
+            load_settings().persona(),
+            user_name.as_deref(),
+            random_greeting,
+        );
         Self {
-            screen: DashboardScreen::Home,
-            greeting,
+            stack: Vec::new(),
+            session: Session::new(greeting),
             menu_selection: 0,
             home_data: None,
-            pending_report_view: None,
-            pending_export: None,
-            pending_text_export: None,
-            status_message: None,
+            home_stale: true,
             needs_reload: false,
-            current_report_idx: None,
             update_notification,
+            widgets: parse_widgets(&load_settings().dashboard_widgets),
+            palette: None,
+            health_warnings: Vec::new(),
+            warnings_dismissed: false,
+        }
+    }
+
+    fn context<'a>(&'a mut self, conn: &'a rusqlite::Connection) -> ScreenContext<'a> {
+        ScreenContext {
+            conn,
+            session: &mut self.session,
+        }
+    }
+
+    /// True while the top screen needs every key (a running game).
+    fn captures_keys(&self) -> bool {
+        self.stack.last().is_some_and(|s| s.captures_keys())
+    }
+
+    /// Open `screen` over the current one.
+    fn open(&mut self, screen: impl Screen + 'static, conn: &rusqlite::Connection) {
+        self.navigate(Nav::Push(Box::new(screen)), conn);
+    }
+
+    /// Close every open screen, leaving the home screen.
+    fn close_all(&mut self, conn: &rusqlite::Connection) {
+        while let Some(mut screen) = self.stack.pop() {
+            screen.on_exit(&mut self.context(conn));
+        }
+    }
+
+    /// Move between screens as a screen asked: the one left on top gets
+    /// `on_enter()` and each one closed gets `on_exit()`. Back on the home
+    /// screen, its data is refreshed if anything changed.
+    fn navigate(&mut self, nav: Nav, conn: &rusqlite::Connection) {
+        let next = match nav {
+            Nav::Stay => return,
+            Nav::Reload => {
+                self.needs_reload = true;
+                return;
+            }
+            Nav::Home => {
+                self.close_all(conn);
+                self.refresh(conn);
+                return;
+            }
+            Nav::Push(screen) => self.push(screen, conn),
+            Nav::Replace(screen) => {
+                if let Some(mut top) = self.stack.pop() {
+                    top.on_exit(&mut self.context(conn));
+                }
+                self.push(screen, conn)
+            }
+            Nav::Pop => {
+                if let Some(mut top) = self.stack.pop() {
+                    top.on_exit(&mut self.context(conn));
+                }
+                let Some(top) = self.stack.last_mut() else {
+                    self.refresh(conn);
+                    return;
+                };
+                top.on_enter(&mut ScreenContext {
+                    conn,
+                    session: &mut self.session,
+                })
+            }
+        };
+        self.navigate(next, conn);
+    }
+
+    fn push(&mut self, mut screen: Box<dyn Screen>, conn: &rusqlite::Connection) -> Nav {
+        let next = screen.on_enter(&mut self.context(conn));
+        self.stack.push(screen);
+        next
+    }
+
+    /// Send a key to the top screen and go where it leads.
+    fn screen_key(&mut self, code: KeyCode, conn: &rusqlite::Connection) -> Result<()> {
+        let Some(screen) = self.stack.last_mut() else {
+            return Ok(());
+        };
+        let nav = screen.handle_key(
+            code,
+            &mut ScreenContext {
+                conn,
+                session: &mut self.session,
+            },
+        )?;
+        // Deliver before the screen that published closes
+        self.refresh(conn);
+        self.navigate(nav, conn);
+        Ok(())
+    }
+
+    /// Hand the changes the top screen published to the screens beneath
+    /// it, and reload the home figures if they're stale and showing.
+    fn refresh(&mut self, conn: &rusqlite::Connection) {
+        let changes = std::mem::take(&mut self.session.changes);
+        if !changes.is_empty() {
+            self.home_stale = true;
+            let beneath = self.stack.len().saturating_sub(1);
+            for screen in &mut self.stack[..beneath] {
+                for &change in &changes {
+                    screen.on_data_changed(
+                        change,
+                        &mut ScreenContext {
+                            conn,
+                            session: &mut self.session,
+                        },
+                    );
+                }
+            }
+        }
+        if self.stack.is_empty() && self.home_stale {
+            let _ = self.load_data(conn);
         }
     }
 
     fn load_data(&mut self, conn: &rusqlite::Connection) -> Result<()> {
         let now = chrono::Local::now();
-        let year = now.year();
+        // YTD figures cover the current fiscal year
+        let year = FiscalYear::load(conn).current();
 
-        let pnl = reports::get_pnl(conn, Some(year), None, None, None)?;
-        let balance = reports::get_balance(conn)?;
-        let cashflow = reports::get_cashflow(conn, None, None)?;
+        // Pick up widget and simple mode changes made in the settings screen
+        let settings = load_settings();
+        self.widgets = parse_widgets(&settings.dashboard_widgets);
+        self.session.simple_mode = settings.simple_mode;
+        if !self.menu_item_shown(self.menu_selection) {
+            self.menu_selection = 0;
+        }
+
+        let pnl = reports::get_pnl(conn, Some(year), None, None, None, None, &[])?;
+        let balance = reports::get_balance(conn, false)?;
+        let cashflow = reports::get_cashflow(conn, None, None, &[])?;
         let flagged = reports::get_flagged(conn)?;
 
         // Top expenses: rolling 3 months
         let three_months_ago = now - chrono::Duration::days(90);
         let expense_from = format!("{}", three_months_ago.format("%Y-%m-01"));
         let expense_to = format!("{}", now.format("%Y-%m-%d"));
-        let recent_pnl =
-            reports::get_pnl(conn, None, None, Some(&expense_from), Some(&expense_to))?;
+        let recent_pnl = reports::get_pnl(
+            conn,
+            None,
+            None,
+            Some(&expense_from),
+            Some(&expense_to),
+            None,
+            &[],
+        )?;
+
+        let txn_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM transactions WHERE deleted_at IS NULL",
+            [],
+            |row| row.get(0),
+        )?;
 
-        let txn_count: i64 =
-            conn.query_row("SELECT COUNT(*) FROM transactions", [], |row| row.get(0))?;
+        let settings = load_settings();
+        self.health_warnings = health::check(
+            conn,
+            &std::path::PathBuf::from(&settings.data_dir).join("backups"),
+            now.date_naive(),
+            settings.backup_reminder_days(),
+        )?;
+        self.health_warnings.truncate(MAX_HEALTH_WARNINGS);
 
         let balances: Vec<(String, f64)> = balance
             .accounts
@@ -271,6 +531,59 @@ impl Dashboard {
             .map(|e| (e.name.clone(), e.total.abs()))
             .collect();
 
+        let recent_flagged: Vec<(String, String, f64)> = flagged
+            .iter()
+            .rev()
+            .take(5)
+            .map(|f| (f.date.clone(), f.description.clone(), f.amount))
+            .collect();
+
+        // Budget: this month's expenses vs the average of the three months before it
+        let this_month = now.format("%Y-%m").to_string();
+        let month_spent = cashflow
+            .months
+            .iter()
+            .find(|m| m.month == this_month)
+            .map(|m| m.outflows.abs())
+            .unwrap_or(0.0);
+        let prior: Vec<f64> = cashflow
+            .months
+            .iter()
+            .filter(|m| m.month < this_month)
+            .rev()
+            .take(3)
+            .map(|m| m.outflows.abs())
+            .collect();
+        let budget_avg = if prior.is_empty() {
+            0.0
+        } else {
+            prior.iter().sum::<f64>() / prior.len() as f64
+        };
+
+        let six_months_ago = now - chrono::Duration::days(182);
+        let recurring_from = format!("{}", six_months_ago.format("%Y-%m-01"));
+        let recurring: Vec<(String, f64)> =
+            reports::get_recurring_charges(conn, &recurring_from, &expense_to, 3)?
+                .into_iter()
+                .take(5)
+                .map(|r| (r.name, r.average))
+                .collect();
+
+        let schedules: Vec<_> = crate::schedule::list_schedules(conn)?
+            .into_iter()
+            .take(5)
+            .collect();
+
+        let top_clients: Vec<(String, f64, Option<f64>)> = reports::get_clients(conn, Some(year))?
+            .clients
+            .into_iter()
+            .take(5)
+            .map(|c| {
+                let pct = c.margin_pct();
+                (c.name, c.revenue, pct)
+            })
+            .collect();
+
         self.home_data = Some(HomeData {
             total_income: pnl.total_income,
             total_expenses: pnl.total_expenses,
@@ -283,72 +596,28 @@ impl Dashboard {
             cashflow_expenses,
             cashflow_year_range,
             top_expenses,
+            recent_flagged,
+            month_spent,
+            budget_avg,
+            recurring,
+            schedules,
+            top_clients,
         });
+        self.home_stale = false;
         Ok(())
     }
 
     fn draw(&mut self, frame: &mut Frame) {
-        if let DashboardScreen::Browse(ref mut browser) = self.screen {
-            browser.draw_frame(frame);
-            return;
-        }
-        if let DashboardScreen::Import(ref import) = self.screen {
-            import.draw(frame);
-            return;
+        match self.stack.last_mut() {
+            Some(screen) => screen.draw(frame),
+            None => self.draw_home(frame),
         }
-        if let DashboardScreen::Review(ref reviewer) = self.screen {
-            reviewer.draw(frame);
-            return;
-        }
-        if let DashboardScreen::Accounts(ref manager) = self.screen {
-            manager.draw(frame);
-            return;
-        }
-        if let DashboardScreen::Categories(ref mut manager) = self.screen {
-            manager.draw(frame);
-            return;
-        }
-        if let DashboardScreen::Rules(ref mut rules) = self.screen {
-            rules.draw(frame);
-            return;
-        }
-        if let DashboardScreen::Reconcile(ref reconcile) = self.screen {
-            reconcile.draw(frame);
-            return;
-        }
-        if let DashboardScreen::Load(ref load) = self.screen {
-            load.draw(frame);
-            return;
-        }
-        if let DashboardScreen::ReportView(ref mut view) = self.screen {
-            view.draw(frame);
-            return;
+        if let Some(tutorial) = &self.session.tutorial {
+            tutorial.draw(frame);
         }
-        if let DashboardScreen::ReportPicker { selection, mode } = self.screen {
-            let (title, items) = match mode {
-                ReportPickerMode::View => ("Select a report to view", REPORT_TYPES as &[&str]),
-                ReportPickerMode::Export => ("Select a report to export", EXPORT_TYPES as &[&str]),
-            };
-            self.draw_picker(frame, title, items, selection);
-            return;
+        if let Some(palette) = &self.palette {
+            palette.draw(frame, frame.area());
         }
-        if let DashboardScreen::ExportFormatPicker { selection, .. } = self.screen {
-            self.draw_picker(frame, "Select export format", EXPORT_FORMATS, selection);
-            return;
-        }
-        if let DashboardScreen::Undo(ref undo) = self.screen {
-            undo.draw(frame);
-            return;
-        }
-        if let DashboardScreen::Settings(ref mgr) = self.screen {
-            mgr.draw(frame);
-            return;
-        }
-        if let DashboardScreen::Snake(ref mut game) = self.screen {
-            game.draw(frame);
-            return;
-        }
-        self.draw_home(frame);
     }
 
     fn draw_home(&self, frame: &mut Frame) {
@@ -357,20 +626,38 @@ impl Dashboard {
 
         let menu_rows = MENU_LEFT_COUNT as u16 + 1;
         let has_update = self.update_notification.is_some();
+        let warning_rows = if self.warnings_dismissed || self.health_warnings.is_empty() {
+            0
+        } else {
+            self.health_warnings.len() as u16 + 1
+        };
 
-        let [header_area, sep1, update_area, stats_area, sep2, charts_area, sep3, menu_area, hints_area] =
-            Layout::vertical([
-                Constraint::Length(1),
-                Constraint::Length(1),
-                Constraint::Length(if has_update { 1 } else { 0 }),
-                Constraint::Length(5),
-                Constraint::Length(1),
-                Constraint::Fill(1),
-                Constraint::Length(1),
-                Constraint::Length(menu_rows),
-                Constraint::Length(1),
-            ])
-            .areas(area);
+        // Widgets sit two per row in settings order, separated by rules. A
+        // spacer takes up slack when no row stretches.
+        let rows: Vec<&[DashboardWidget]> = self.widgets.chunks(2).collect();
+        let mut constraints = vec![
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(if has_update { 1 } else { 0 }),
+            Constraint::Length(warning_rows),
+        ];
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                constraints.push(Constraint::Length(1));
+            }
+            constraints.push(row_constraint(row));
+        }
+        if !constraints.contains(&Constraint::Fill(1)) {
+            constraints.push(Constraint::Fill(1));
+        }
+        constraints.push(Constraint::Length(1));
+        constraints.push(Constraint::Length(menu_rows));
+        constraints.push(Constraint::Length(1));
+        let areas = Layout::vertical(constraints).split(area);
+        let (header_area, sep1, update_area, warnings_area) =
+            (areas[0], areas[1], areas[2], areas[3]);
+        let menu_area = areas[areas.len() - 2];
+        let hints_area = areas[areas.len() - 1];
 
         if let Some(msg) = &self.update_notification {
             frame.render_widget(
@@ -379,169 +666,48 @@ impl Dashboard {
             );
         }
 
+        if warning_rows > 0 {
+            self.draw_health_warnings(frame, warnings_area);
+        }
+
         // Header
         frame.render_widget(
-            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
+            Paragraph::new(format!(" {}", self.session.greeting)).style(HEADER_STYLE),
             header_area,
         );
+        if let Some(badge) = &self.session.high_score_badge {
+            frame.render_widget(
+                Paragraph::new(format!("\u{2605} {badge} "))
+                    .style(
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .alignment(ratatui::layout::Alignment::Right),
+                header_area,
+            );
+        }
 
         // Thick separator lines
         let sep_line = "━".repeat(area.width as usize);
         let sep_widget = Paragraph::new(sep_line.as_str()).style(border_style);
         frame.render_widget(sep_widget.clone(), sep1);
-        frame.render_widget(sep_widget.clone(), sep2);
-        frame.render_widget(sep_widget.clone(), sep3);
+        frame.render_widget(sep_widget.clone(), areas[areas.len() - 3]);
 
-        if let Some(data) = &self.home_data {
-            // Stats + Balances — same 50/50 split used for charts below
+        for (i, row) in rows.iter().enumerate() {
+            let row_area = areas[4 + i * 2];
+            if i > 0 {
+                frame.render_widget(sep_widget.clone(), areas[3 + i * 2]);
+            }
+            let Some(data) = &self.home_data else {
+                continue;
+            };
+            // Same 50/50 split on every row so columns line up
             let [left_area, right_area] =
                 Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
-                    .areas(stats_area);
-
-            // YTD summary — 1-space indent to align with "N" in " Nigel:"
-            let stats_lines = vec![
-                Line::from(vec![
-                    Span::raw(" YTD Income     "),
-                    money_span(data.total_income),
-                ]),
-                Line::from(vec![
-                    Span::raw(" YTD Expenses   "),
-                    money_span(data.total_expenses),
-                ]),
-                Line::from(vec![Span::raw(" Net Profit     "), money_span(data.net)]),
-                Line::from(format!(" Transactions   {}", number(data.txn_count))),
-                Line::from(format!(" Flagged        {}", data.flagged_count)),
-            ];
-            frame.render_widget(Paragraph::new(stats_lines), left_area);
-
-            // Account balances
-            let mut balance_lines = vec![Line::from(Span::styled(
-                " Account Balances",
-                Style::default().add_modifier(Modifier::BOLD),
-            ))];
-            for (name, bal) in &data.balances {
-                balance_lines.push(Line::from(vec![
-                    Span::raw(format!(" {:<20}", name)),
-                    money_span(*bal),
-                ]));
-            }
-            frame.render_widget(Paragraph::new(balance_lines), right_area);
-
-            // Charts — same 50/50 split so right column aligns with Account Balances
-            let [chart_left, chart_right] =
-                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
-                    .areas(charts_area);
-
-            // Monthly Cash Flow bar chart with y-axis labels
-            if !data.cashflow_labels.is_empty() {
-                let income_style = Style::default().fg(crate::tui::GREEN);
-                let expense_style = Style::default().fg(Color::Red);
-
-                // Split: title row, gap, then chart
-                let [chart_title_area, _chart_gap, chart_body] = Layout::vertical([
-                    Constraint::Length(1),
-                    Constraint::Length(1),
-                    Constraint::Fill(1),
-                ])
-                .areas(chart_left);
-
-                let chart_title = if data.cashflow_year_range.is_empty() {
-                    " Monthly Cash Flow".to_string()
-                } else {
-                    format!(" Monthly Cash Flow {}", data.cashflow_year_range)
-                };
-                frame.render_widget(
-                    Paragraph::new(Span::styled(
-                        chart_title,
-                        Style::default().add_modifier(Modifier::BOLD),
-                    )),
-                    chart_title_area,
-                );
-
-                // Pick round y-axis tick values based on max data value
-                let max_val = data
-                    .cashflow_income
-                    .iter()
-                    .chain(data.cashflow_expenses.iter())
-                    .copied()
-                    .max()
-                    .unwrap_or(1) as f64;
-
-                // Round ticks: pick nice round numbers for the axis
-                let (top_tick, mid_tick) = y_axis_ticks(max_val);
-                let top_label = format_k(top_tick);
-                let mid_label = format_k(mid_tick);
-                let y_label_width = top_label.len().max(mid_label.len()) as u16 + 1;
-
-                let [y_axis_area, bar_area] =
-                    Layout::horizontal([Constraint::Length(y_label_width), Constraint::Fill(1)])
-                        .areas(chart_body);
-
-                // Y-axis labels: top tick near top, mid tick at middle
-                let inner_height = bar_area.height.saturating_sub(1); // month labels
-                let mid_row = inner_height / 2;
-                let mut y_lines: Vec<Line> = Vec::new();
-                for row in 0..inner_height {
-                    if row == 0 {
-                        y_lines.push(Line::from(Span::styled(
-                            format!("{:>width$}", top_label, width = y_label_width as usize),
-                            FOOTER_STYLE,
-                        )));
-                    } else if row == mid_row {
-                        y_lines.push(Line::from(Span::styled(
-                            format!("{:>width$}", mid_label, width = y_label_width as usize),
-                            FOOTER_STYLE,
-                        )));
-                    } else {
-                        y_lines.push(Line::from(""));
-                    }
-                }
-                frame.render_widget(Paragraph::new(y_lines), y_axis_area);
-
-                let groups: Vec<BarGroup> = data
-                    .cashflow_labels
-                    .iter()
-                    .enumerate()
-                    .map(|(i, label)| {
-                        let inc = data.cashflow_income.get(i).copied().unwrap_or(0);
-                        let exp = data.cashflow_expenses.get(i).copied().unwrap_or(0);
-                        let bars = vec![
-                            Bar::default().value(inc).style(income_style),
-                            Bar::default().value(exp).style(expense_style),
-                        ];
-                        BarGroup::default()
-                            .label(Line::from(label.as_str()))
-                            .bars(&bars)
-                    })
-                    .collect();
-
-                let mut chart = BarChart::default().bar_width(2).bar_gap(0).group_gap(1);
-                for group in &groups {
-                    chart = chart.data(group.clone());
-                }
-                frame.render_widget(chart, bar_area);
-            }
-
-            // Top Expenses — simple text table (no bars)
-            if !data.top_expenses.is_empty() {
-                let name_width = data
-                    .top_expenses
-                    .iter()
-                    .map(|(n, _)| n.len())
-                    .max()
-                    .unwrap_or(10);
-
-                let mut lines = vec![Line::from(Span::styled(
-                    " Top Expenses (3 months)",
-                    Style::default().add_modifier(Modifier::BOLD),
-                ))];
-                for (name, val) in &data.top_expenses {
-                    lines.push(Line::from(vec![
-                        Span::raw(format!(" {:<width$}  ", name, width = name_width)),
-                        money_span(-val), // negative to show as expense (red)
-                    ]));
-                }
-                frame.render_widget(Paragraph::new(lines), chart_right);
+                    .areas(row_area);
+            for (widget, widget_area) in row.iter().zip([left_area, right_area]) {
+                render_widget(frame, *widget, widget_area, data);
             }
         }
 
@@ -568,149 +734,248 @@ impl Dashboard {
                 .areas(menu_cols_area);
 
         let left_lines: Vec<Line> = (0..MENU_LEFT_COUNT)
+            .filter(|&i| self.menu_item_shown(i))
             .map(|i| self.menu_item_line(i, flagged_count))
             .collect();
         frame.render_widget(Paragraph::new(left_lines), menu_left);
 
         let right_lines: Vec<Line> = (MENU_LEFT_COUNT..MENU_ITEMS.len())
+            .filter(|&i| self.menu_item_shown(i))
             .map(|i| self.menu_item_line(i, flagged_count))
             .collect();
         frame.render_widget(Paragraph::new(right_lines), menu_right);
 
         // Hints / status message
-        if let Some(msg) = &self.status_message {
+        if let Some(msg) = &self.session.status_message {
             frame.render_widget(
                 Paragraph::new(format!(" {msg}")).style(Style::default().fg(Color::Yellow)),
                 hints_area,
             );
         } else {
             frame.render_widget(
-                Paragraph::new(" Up/Down=navigate  Enter=select  F5=refresh  q=quit")
-                    .style(FOOTER_STYLE),
+                Paragraph::new(format!(" {}", t("hints.dashboard"))).style(FOOTER_STYLE),
                 hints_area,
             );
         }
     }
 
-    fn draw_picker(&self, frame: &mut Frame, title: &str, items: &[&str], selection: usize) {
-        let area = frame.area();
-        let border_style = Style::default().fg(Color::DarkGray);
-
-        let [header_area, sep, content_area, hints_area] = Layout::vertical([
-            Constraint::Length(1),
-            Constraint::Length(1),
-            Constraint::Fill(1),
-            Constraint::Length(1),
-        ])
-        .areas(area);
-
-        frame.render_widget(
-            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
-            header_area,
-        );
-
-        let sep_line = "━".repeat(area.width as usize);
-        frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
-
-        let mut lines = vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                format!(" {title}"),
-                Style::default().add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-        ];
-        for (i, item) in items.iter().enumerate() {
-            let marker = if i == selection { ">" } else { " " };
-            let style = if i == selection {
-                Style::default().add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
+    fn draw_health_warnings(&self, frame: &mut Frame, area: Rect) {
+        let warn_style = Style::default().fg(Color::Yellow);
+        let mut lines = vec![Line::from(vec![
+            Span::styled(" Heads up", warn_style.add_modifier(Modifier::BOLD)),
+            Span::styled("  (1-9=go  x=dismiss)", FOOTER_STYLE),
+        ])];
+        for (i, warning) in self.health_warnings.iter().enumerate() {
+            let jump = match warning.action {
+                HealthAction::Backup => "  → back up now",
+                HealthAction::Review => "  → review",
+                HealthAction::Import => "  → import",
+                HealthAction::Compensation => "  → compensation report",
+                HealthAction::ReconcileNote(_) => "  → reconcile",
+                HealthAction::None => "",
             };
-            lines.push(Line::from(Span::styled(format!(" {marker} {item}"), style)));
+            lines.push(Line::from(vec![
+                Span::styled(format!(" [{}] {}", i + 1, warning.message), warn_style),
+                Span::styled(jump, FOOTER_STYLE),
+            ]));
         }
-        frame.render_widget(Paragraph::new(lines), content_area);
+        frame.render_widget(Paragraph::new(lines), area);
+    }
 
-        frame.render_widget(
-            Paragraph::new(" Up/Down=navigate  Enter=select  Esc/q=back").style(FOOTER_STYLE),
-            hints_area,
-        );
+    /// Follow the one-key jump for the `idx`th health warning.
+    fn run_health_action(&mut self, idx: usize, conn: &rusqlite::Connection) {
+        let Some(warning) = self.health_warnings.get(idx) else {
+            return;
+        };
+        if crate::db::is_read_only()
+            && matches!(
+                warning.action,
+                HealthAction::Backup | HealthAction::ReconcileNote(_)
+            )
+        {
+            self.session.status_message = Some(crate::tui::READ_ONLY_STATUS.into());
+            return;
+        }
+        match warning.action {
+            HealthAction::Backup => {
+                match crate::cli::backup::backup_to_default(conn, &get_data_dir()) {
+                    Ok(path) => {
+                        self.session.status_message =
+                            Some(format!("Backup saved to {}", path.display()));
+                        self.health_warnings.remove(idx);
+                    }
+                    Err(e) => self.session.status_message = Some(format!("Backup failed: {e}")),
+                }
+            }
+            // Menu indices for Review and Import
+            HealthAction::Review => self.activate_menu_item(2, conn),
+            HealthAction::Import => self.activate_menu_item(1, conn),
+            // Index of "Compensation" in REPORT_TYPES
+            HealthAction::Compensation => {
+                if let Some(screen) = open_report(9, &mut self.context(conn)) {
+                    self.navigate(Nav::Push(screen), conn);
+                }
+            }
+            HealthAction::ReconcileNote(id) => {
+                let greeting = &self.session.greeting;
+                let screen = crate::reconciler::open_notes(conn).and_then(|notes| {
+                    match notes.iter().find(|n| n.id == id) {
+                        Some(note) => ReconcileScreen::for_note(conn, greeting, note),
+                        None => ReconcileScreen::new(conn, greeting),
+                    }
+                });
+                match screen {
+                    Ok(screen) => self.open(screen, conn),
+                    Err(e) => self.session.status_message = Some(format!("Error: {e}")),
+                }
+            }
+            HealthAction::None => {}
+        }
+    }
+
+    fn menu_item_shown(&self, i: usize) -> bool {
+        !(self.session.simple_mode && ADVANCED_MENU_ITEMS.contains(&i))
     }
 
     fn menu_item_line(&self, i: usize, flagged_count: usize) -> Line<'static> {
         let marker = if i == self.menu_selection { ">" } else { " " };
-        let (item, _) = MENU_ITEMS[i];
+        let (item, key) = MENU_ITEMS[i];
         let label = if i == 2 {
-            format!(" {marker} {item} ({flagged_count})")
+            format!(" {marker} [{key}] {} ({flagged_count})", t(item))
         } else {
-            format!(" {marker} {item}")
+            format!(" {marker} [{key}] {}", t(item))
         };
-        let style = if i == self.menu_selection {
+        let mut style = if i == self.menu_selection {
             Style::default().add_modifier(Modifier::BOLD)
         } else {
             Style::default()
         };
+        if crate::db::is_read_only() && EDIT_ONLY_MENU_ITEMS.contains(&i) {
+            style = style.fg(Color::DarkGray);
+        }
         Line::from(Span::styled(label, style))
     }
 
     fn activate_menu_item(&mut self, idx: usize, conn: &rusqlite::Connection) {
+        if crate::db::is_read_only() && EDIT_ONLY_MENU_ITEMS.contains(&idx) {
+            self.session.status_message = Some(crate::tui::READ_ONLY_STATUS.into());
+            return;
+        }
+        let greeting = self.session.greeting.clone();
         match idx {
-            0 => self.screen = self.enter_browse(conn),
-            1 => match ImportScreen::new(conn, &self.greeting) {
-                Ok(screen) => self.screen = DashboardScreen::Import(screen),
-                Err(e) => self.status_message = Some(format!("Error: {e}")),
-            },
-            2 => self.screen = self.enter_review(conn),
-            3 => match ReconcileScreen::new(conn, &self.greeting) {
-                Ok(screen) => self.screen = DashboardScreen::Reconcile(screen),
-                Err(e) => self.status_message = Some(format!("Error: {e}")),
-            },
-            4 => self.screen = DashboardScreen::Accounts(AccountManager::new(conn, &self.greeting)),
-            5 => {
-                self.screen =
-                    DashboardScreen::Categories(CategoryManager::new(conn, &self.greeting))
-            }
-            6 => self.screen = DashboardScreen::Rules(RulesManager::new(conn, &self.greeting)),
-            7 => match UndoScreen::new(conn, &self.greeting) {
-                Ok(screen) => self.screen = DashboardScreen::Undo(screen),
-                Err(e) => self.status_message = Some(format!("Error: {e}")),
-            },
-            8 => {
-                self.screen = DashboardScreen::ReportPicker {
-                    selection: 0,
-                    mode: ReportPickerMode::View,
+            0 => {
+                let query = reports::RegisterQuery::default();
+                let desc = "all transactions".to_string();
+                if let Some(browser) =
+                    RegisterBrowser::open_screen(&mut self.context(conn), query, desc)
+                {
+                    self.open(browser, conn);
                 }
             }
-            9 => {
-                self.screen = DashboardScreen::ReportPicker {
-                    selection: 0,
-                    mode: ReportPickerMode::Export,
+            1 => match ImportScreen::new(conn, &greeting) {
+                Ok(mut screen) => {
+                    // The tutorial's first step imports its sample statement
+                    if let Some(tutorial) = &self.session.tutorial {
+                        if tutorial.current() == Some(TutorialStep::Import) {
+                            screen = screen.with_file(&tutorial.sample_file().to_string_lossy());
+                        }
+                    }
+                    self.open(screen, conn)
+                }
+                Err(e) => self.session.status_message = Some(format!("Error: {e}")),
+            },
+            2 => {
+                if let Some(reviewer) = self.reviewer(conn) {
+                    self.open(reviewer, conn);
                 }
             }
-            10 => self.screen = DashboardScreen::Load(LoadScreen::new(&self.greeting)),
-            11 => match SettingsManager::new(conn, &self.greeting) {
-                Ok(mgr) => self.screen = DashboardScreen::Settings(mgr),
-                Err(e) => self.status_message = Some(format!("Error: {e}")),
+            3 => match ReconcileScreen::new(conn, &greeting) {
+                Ok(screen) => self.open(screen, conn),
+                Err(e) => self.session.status_message = Some(format!("Error: {e}")),
             },
-            12 => self.screen = DashboardScreen::Snake(SnakeGame::new()),
+            4 => self.open(AccountManager::new(conn, &greeting), conn),
+            5 => self.open(CategoryManager::new(conn, &greeting), conn),
+            6 => self.open(RulesManager::new(conn, &greeting), conn),
+            7 => match UndoScreen::new(conn, &greeting) {
+                Ok(screen) => self.open(screen, conn),
+                Err(e) => self.session.status_message = Some(format!("Error: {e}")),
+            },
+            8 => self.open(ImportHistory::new(conn, &greeting), conn),
+            9 => self.open(
+                ReportPicker::new(&self.session, 0, ReportPickerMode::View),
+                conn,
+            ),
+            10 => self.open(
+                ReportPicker::new(&self.session, 0, ReportPickerMode::Export),
+                conn,
+            ),
+            11 => self.open(LoadScreen::new(&greeting), conn),
+            12 => match SettingsManager::new(conn, &greeting) {
+                Ok(mgr) => self.open(mgr, conn),
+                Err(e) => self.session.status_message = Some(format!("Error: {e}")),
+            },
+            13 => self.open(DocsViewer::new(&greeting), conn),
+            #[cfg(feature = "games")]
+            14 => self.open(Arcade::new(conn, &greeting), conn),
             _ => {}
         }
     }
 
+    /// The review queue, or `None` with the reason on the status line when
+    /// there's nothing to review.
+    fn reviewer(&mut self, conn: &rusqlite::Connection) -> Option<TransactionReviewer> {
+        let flagged = match get_flagged_transactions(conn) {
+            Ok(f) => f,
+            Err(e) => {
+                self.session.status_message =
+                    Some(format!("Could not load flagged transactions: {e}"));
+                return None;
+            }
+        };
+        if flagged.is_empty() {
+            self.session.status_message = Some("No flagged transactions to review.".to_string());
+            return None;
+        }
+        let categories = match crate::reviewer::get_categories(conn) {
+            Ok(c) => c,
+            Err(e) => {
+                self.session.status_message = Some(format!("Could not load categories: {e}"));
+                return None;
+            }
+        };
+        self.session.status_message = None;
+        Some(TransactionReviewer::new(flagged, categories))
+    }
+
     fn handle_home_key(&mut self, code: KeyCode, conn: &rusqlite::Connection) -> bool {
-        self.status_message = None;
+        self.session.status_message = None;
         match code {
-            KeyCode::Up => {
-                self.menu_selection = self.menu_selection.saturating_sub(1);
-            }
-            KeyCode::Down => {
-                self.menu_selection = (self.menu_selection + 1).min(MENU_ITEMS.len() - 1);
+            KeyCode::Up | KeyCode::Down => {
+                self.menu_selection = step_shown(
+                    self.menu_selection,
+                    MENU_ITEMS.len(),
+                    code == KeyCode::Down,
+                    |i| self.menu_item_shown(i),
+                );
             }
             KeyCode::Char('q') => return true,
+            KeyCode::Char(':') => self.open_palette(conn),
             KeyCode::Enter => self.activate_menu_item(self.menu_selection, conn),
+            KeyCode::Char('x') if !self.warnings_dismissed && !self.health_warnings.is_empty() => {
+                self.warnings_dismissed = true;
+            }
+            KeyCode::Char(ch @ '1'..='9') if !self.warnings_dismissed => {
+                self.run_health_action(ch as usize - '1' as usize, conn);
+            }
             KeyCode::Char(ch) => {
                 if let Some(idx) = MENU_ITEMS.iter().position(|(_, key)| *key == ch) {
-                    self.activate_menu_item(idx, conn);
+                    if self.menu_item_shown(idx) {
+                        self.activate_menu_item(idx, conn);
+                    } else {
+                        self.session.status_message = Some(
+                            "Hidden in simple mode. Turn it off in Settings (p) to use it.".into(),
+                        );
+                    }
                 }
             }
             _ => {}
@@ -718,184 +983,823 @@ impl Dashboard {
         false
     }
 
-    fn enter_browse(&mut self, conn: &rusqlite::Connection) -> DashboardScreen {
-        match reports::get_register(conn, None, None, None, None, None) {
-            Ok(data) => {
-                let categories = match get_categories(conn) {
-                    Ok(c) => c,
-                    Err(e) => {
-                        self.status_message =
-                            Some(format!("Warning: could not load categories: {e}"));
-                        vec![]
-                    }
-                };
-                self.status_message = None;
-                let mut browser = RegisterBrowser::new(
-                    data.rows,
-                    data.total,
-                    "all transactions".to_string(),
-                    categories,
-                );
-                browser.scroll_to_today();
-                DashboardScreen::Browse(browser)
+    /// Collect palette commands from the menu, the report pickers, and the
+    /// account list.
+    fn palette_registry(&self, conn: &rusqlite::Connection) -> PaletteRegistry {
+        let mut registry = PaletteRegistry::default();
+        for (i, (item, _)) in MENU_ITEMS.iter().enumerate() {
+            if !self.menu_item_shown(i) {
+                continue;
             }
-            Err(e) => {
-                self.status_message = Some(format!("Could not load register: {e}"));
-                DashboardScreen::Home
+            registry.register(t(item), "", PaletteAction::Menu(i));
+        }
+        for (i, name) in REPORT_TYPES.iter().enumerate() {
+            if !report_shown(self.session.simple_mode, i) {
+                continue;
             }
+            registry.register(
+                t_args("palette.view_report", &[("name", &t(name))]),
+                REPORT_ALIASES[i],
+                PaletteAction::ViewReport(i),
+            );
         }
+        for (i, name) in EXPORT_TYPES.iter().enumerate() {
+            if !report_shown(self.session.simple_mode, i) {
+                continue;
+            }
+            registry.register(
+                t_args("palette.export_report", &[("name", &t(name))]),
+                REPORT_ALIASES[i],
+                PaletteAction::ExportReport(i),
+            );
+        }
+        registry.register("Add an account", "new", PaletteAction::AddAccount);
+        if let Ok(accounts) = crate::cli::accounts::list_accounts(conn) {
+            for account in accounts {
+                registry.register(
+                    format!("Jump to account: {}", account.name),
+                    "browse register",
+                    PaletteAction::BrowseAccount(account.name),
+                );
+            }
+        }
+        registry
     }
 
-    fn enter_review(&mut self, conn: &rusqlite::Connection) -> DashboardScreen {
-        let flagged = match get_flagged_transactions(conn) {
-            Ok(f) => f,
-            Err(e) => {
-                self.status_message = Some(format!("Could not load flagged transactions: {e}"));
-                return DashboardScreen::Home;
+    fn open_palette(&mut self, conn: &rusqlite::Connection) {
+        self.palette = Some(Palette::new(self.palette_registry(conn)));
+    }
+
+    fn run_palette_action(&mut self, action: PaletteAction, conn: &rusqlite::Connection) {
+        // Jumping elsewhere closes whatever was open, drill-downs included
+        self.close_all(conn);
+        match action {
+            PaletteAction::Menu(idx) => self.activate_menu_item(idx, conn),
+            PaletteAction::ViewReport(idx) => {
+                if let Some(screen) = open_report(idx, &mut self.context(conn)) {
+                    self.navigate(Nav::Push(screen), conn);
+                }
             }
-        };
-        if flagged.is_empty() {
-            self.status_message = Some("No flagged transactions to review.".to_string());
-            return DashboardScreen::Home;
-        }
-        let categories = match get_categories(conn) {
-            Ok(c) => c,
-            Err(e) => {
-                self.status_message = Some(format!("Could not load categories: {e}"));
-                return DashboardScreen::Home;
+            PaletteAction::ExportReport(idx) => {
+                // Esc on the format picker goes back to the report picker
+                self.open(
+                    ReportPicker::new(&self.session, idx, ReportPickerMode::Export),
+                    conn,
+                );
+                self.open(ExportFormatPicker::new(&self.session, idx), conn);
             }
-        };
-        self.status_message = None;
-        DashboardScreen::Review(TransactionReviewer::new(flagged, categories))
+            PaletteAction::AddAccount if crate::db::is_read_only() => {
+                self.session.status_message = Some(crate::tui::READ_ONLY_STATUS.into());
+            }
+            PaletteAction::AddAccount => {
+                let mut manager = AccountManager::new(conn, &self.session.greeting);
+                manager.start_add();
+                self.open(manager, conn);
+            }
+            PaletteAction::BrowseAccount(name) => {
+                let query = reports::RegisterQuery {
+                    account: Some(name.clone()),
+                    ..Default::default()
+                };
+                if let Some(browser) =
+                    RegisterBrowser::open_screen(&mut self.context(conn), query, name)
+                {
+                    self.open(browser, conn);
+                }
+            }
+        }
     }
+}
+
+// ---------------------------------------------------------------------------
+// Report screens
+// ---------------------------------------------------------------------------
+
+/// The report list, for viewing or exporting.
+struct ReportPicker {
+    selection: usize,
+    mode: ReportPickerMode,
+    greeting: String,
+    simple_mode: bool,
+}
 
-    fn enter_report_view(&mut self, idx: usize, conn: &rusqlite::Connection) -> DashboardScreen {
-        self.enter_report_view_with_date(idx, conn, None, None)
+impl ReportPicker {
+    fn new(session: &Session, selection: usize, mode: ReportPickerMode) -> Self {
+        Self {
+            selection,
+            mode,
+            greeting: session.greeting.clone(),
+            simple_mode: session.simple_mode,
+        }
     }
 
-    fn enter_report_view_with_date(
-        &mut self,
-        idx: usize,
-        conn: &rusqlite::Connection,
-        year: Option<i32>,
-        month: Option<String>,
-    ) -> DashboardScreen {
-        // Register (idx 4) delegates to the interactive browser
-        if idx == 4 {
-            return self.enter_browse(conn);
+    fn items(&self) -> &'static [&'static str] {
+        match self.mode {
+            ReportPickerMode::View => REPORT_TYPES,
+            ReportPickerMode::Export => EXPORT_TYPES,
         }
-        let year = year.or_else(|| Some(chrono::Local::now().year()));
-        let result = match idx {
-            0 => super::report::view::build_pnl(month.clone(), year, None, None),
-            1 => super::report::view::build_expenses(month.clone(), year),
-            2 => super::report::view::build_tax(year),
-            3 => super::report::view::build_cashflow(month.clone(), year),
-            5 => super::report::view::build_flagged(),
-            6 => super::report::view::build_balance(),
-            7 => super::report::view::build_k1(year),
-            _ => return DashboardScreen::Home,
+    }
+}
+
+impl Screen for ReportPicker {
+    fn draw(&mut self, frame: &mut Frame) {
+        let title = match self.mode {
+            ReportPickerMode::View => "Select a report to view",
+            ReportPickerMode::Export => "Select a report to export",
         };
-        match result {
-            Ok(view) => {
-                self.status_message = None;
-                DashboardScreen::ReportView(view)
+        let items = self.items();
+        let shown: Vec<usize> = (0..items.len())
+            .filter(|&i| report_shown(self.simple_mode, i))
+            .collect();
+        let labels: Vec<String> = shown.iter().map(|&i| t(items[i])).collect();
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        let row = shown.iter().position(|&i| i == self.selection).unwrap_or(0);
+        draw_picker(frame, &self.greeting, title, &labels, row);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        Ok(match code {
+            KeyCode::Up | KeyCode::Down => {
+                let simple_mode = self.simple_mode;
+                self.selection = step_shown(
+                    self.selection,
+                    self.items().len(),
+                    code == KeyCode::Down,
+                    |i| report_shown(simple_mode, i),
+                );
+                Nav::Stay
             }
-            Err(e) => {
-                self.status_message = Some(format!("Error: {e}"));
-                DashboardScreen::Home
+            KeyCode::Esc | KeyCode::Char('q') => Nav::Pop,
+            KeyCode::Enter => match self.mode {
+                ReportPickerMode::View => match open_report(self.selection, ctx) {
+                    Some(screen) => Nav::Replace(screen),
+                    None => Nav::Home,
+                },
+                ReportPickerMode::Export => Nav::Push(Box::new(ExportFormatPicker::new(
+                    ctx.session,
+                    self.selection,
+                ))),
+            },
+            _ => Nav::Stay,
+        })
+    }
+}
+
+/// PDF or text, for the report chosen on the export picker.
+struct ExportFormatPicker {
+    report_idx: usize,
+    selection: usize,
+    greeting: String,
+}
+
+impl ExportFormatPicker {
+    fn new(session: &Session, report_idx: usize) -> Self {
+        Self {
+            report_idx,
+            selection: default_export_selection(),
+            greeting: session.greeting.clone(),
+        }
+    }
+}
+
+impl Screen for ExportFormatPicker {
+    fn draw(&mut self, frame: &mut Frame) {
+        draw_picker(
+            frame,
+            &self.greeting,
+            "Select export format",
+            EXPORT_FORMATS,
+            self.selection,
+        );
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        let max_idx = EXPORT_FORMATS.len() - 1;
+        Ok(match code {
+            KeyCode::Up => {
+                self.selection = self.selection.saturating_sub(1);
+                Nav::Stay
+            }
+            KeyCode::Down => {
+                self.selection = (self.selection + 1).min(max_idx);
+                Nav::Stay
+            }
+            KeyCode::Esc | KeyCode::Char('q') => Nav::Pop,
+            KeyCode::Enter => {
+                let text = EXPORT_FORMATS[self.selection] == "Text";
+                let result = if text {
+                    do_text_export(ctx.conn, self.report_idx, None, None)
+                } else {
+                    do_export(ctx.conn, self.report_idx, None, None)
+                };
+                ctx.session.status_message = Some(match result {
+                    Ok(msg) => {
+                        // PDF builds finish the tutorial with a PDF
+                        if !text || cfg!(not(feature = "pdf")) {
+                            if let Some(tutorial) = ctx.session.tutorial.as_mut() {
+                                tutorial.complete(TutorialStep::Export, ctx.conn);
+                            }
+                        }
+                        msg
+                    }
+                    Err(e) => format!("Export failed: {e}"),
+                });
+                Nav::Home
             }
+            _ => Nav::Stay,
+        })
+    }
+}
+
+/// A report open for viewing; it rebuilds itself when its period or
+/// toggles change.
+struct ReportScreen {
+    /// Index into REPORT_TYPES.
+    idx: usize,
+    view: Box<dyn ReportView>,
+}
+
+impl ReportScreen {
+    /// Build the report again for the view's current period and toggles.
+    fn rebuild(&mut self, conn: &rusqlite::Connection) -> Result<()> {
+        let (year, month) = self.view.date_params();
+        let (compare, chart) = (self.view.compare(), self.view.chart());
+        let granularity = self.view.granularity();
+        self.view = build_report(self.idx, conn, year, month, compare, chart, granularity)?;
+        Ok(())
+    }
+}
+
+impl Screen for ReportScreen {
+    fn draw(&mut self, frame: &mut Frame) {
+        self.view.draw(frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        Ok(match self.view.handle_key(code) {
+            ReportViewAction::Close => Nav::Pop,
+            ReportViewAction::Continue => Nav::Stay,
+            ReportViewAction::Reload => match self.rebuild(ctx.conn) {
+                Ok(()) => Nav::Stay,
+                Err(e) => {
+                    ctx.session.status_message = Some(format!("Error: {e}"));
+                    Nav::Home
+                }
+            },
+            // Closing the register comes back to the report
+            ReportViewAction::Browse(query, desc) => {
+                match RegisterBrowser::open_screen(ctx, query, desc) {
+                    Some(browser) => Nav::Push(Box::new(browser)),
+                    None => Nav::Home,
+                }
+            }
+        })
+    }
+
+    /// Keep the figures current; on failure the old ones stay up.
+    fn on_data_changed(&mut self, _change: DataChange, ctx: &mut ScreenContext) {
+        let _ = self.rebuild(ctx.conn);
+    }
+}
+
+/// Open a report from the pickers or palette at its configured default
+/// period; the register opens in the browser. `None` with the reason on the
+/// status line when it can't be built.
+fn open_report(idx: usize, ctx: &mut ScreenContext) -> Option<Box<dyn Screen>> {
+    if idx == 0 {
+        if let Some(tutorial) = ctx.session.tutorial.as_mut() {
+            tutorial.complete(TutorialStep::Pnl, ctx.conn);
+        }
+    }
+    let fiscal = FiscalYear::load(ctx.conn);
+    let period = REPORT_ALIASES
+        .get(idx)
+        .and_then(|alias| default_period(alias, fiscal));
+    // Register (idx 4) delegates to the interactive browser
+    if idx == 4 {
+        let (query, filters_desc) = match period {
+            Some(Period::Month(y, m)) => (
+                reports::RegisterQuery {
+                    year: Some(y),
+                    month: Some(m),
+                    ..Default::default()
+                },
+                format!("{y}-{m:02}"),
+            ),
+            Some(Period::Year(fy)) => (
+                reports::RegisterQuery {
+                    year: Some(fy),
+                    ..Default::default()
+                },
+                fiscal.label(fy),
+            ),
+            None => (
+                reports::RegisterQuery::default(),
+                "all transactions".to_string(),
+            ),
+        };
+        let (query, filters_desc) = match default_for("register", Field::Account) {
+            Some(account) => (
+                reports::RegisterQuery {
+                    account: Some(account.clone()),
+                    ..query
+                },
+                format!("{filters_desc}, account: {account}"),
+            ),
+            None => (query, filters_desc),
+        };
+        let browser = RegisterBrowser::open_screen(ctx, query, filters_desc)?;
+        return Some(Box::new(browser));
+    }
+    let (year, month) = period.map(Period::params).unwrap_or_default();
+    match build_report(idx, ctx.conn, year, month, false, false, Granularity::Month) {
+        Ok(view) => {
+            ctx.session.status_message = None;
+            Some(Box::new(ReportScreen { idx, view }))
+        }
+        Err(e) => {
+            ctx.session.status_message = Some(format!("Error: {e}"));
+            None
         }
     }
 }
 
+fn build_report(
+    idx: usize,
+    conn: &rusqlite::Connection,
+    year: Option<i32>,
+    month: Option<String>,
+    compare: bool,
+    chart: bool,
+    granularity: Granularity,
+) -> Result<Box<dyn ReportView>> {
+    let year = year.or_else(|| Some(FiscalYear::load(conn).current()));
+    let params = ReportParams {
+        compare,
+        granularity,
+        ..ReportParams::period(year, month.as_deref())
+    };
+    match idx {
+        0 => super::report::view::build_pnl(&params),
+        1 => super::report::view::build_expenses(&params),
+        2 => super::report::view::build_tax(year),
+        3 => super::report::view::build_cashflow(&params, chart),
+        5 => super::report::view::build_flagged(),
+        6 => super::report::view::build_balance(false),
+        7 => super::report::view::build_k1(year),
+        8 => super::report::view::build_deductions(year),
+        9 => super::report::view::build_compensation(year),
+        10 => super::report::view::build_tags(&params),
+        11 => super::report::view::build_reimbursables(None, false),
+        12 => super::report::view::build_digest(month),
+        13 => super::report::view::build_clients(year),
+        14 => super::report::view::build_books(),
+        15 => super::report::view::build_quality(),
+        _ => Err(crate::error::NigelError::Other(format!(
+            "No report view for {}",
+            REPORT_TYPES
+                .get(idx)
+                .map_or("that report".to_string(), |key| t(key))
+        ))),
+    }
+}
+
+fn draw_picker(frame: &mut Frame, greeting: &str, title: &str, items: &[&str], selection: usize) {
+    let area = frame.area();
+    let border_style = Style::default().fg(Color::DarkGray);
+
+    let [header_area, sep, content_area, hints_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(area);
+
+    frame.render_widget(
+        Paragraph::new(format!(" {greeting}")).style(HEADER_STYLE),
+        header_area,
+    );
+
+    let sep_line = "━".repeat(area.width as usize);
+    frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(" {title}"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    for (i, item) in items.iter().enumerate() {
+        let marker = if i == selection { ">" } else { " " };
+        let style = if i == selection {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(format!(" {marker} {item}"), style)));
+    }
+    frame.render_widget(Paragraph::new(lines), content_area);
+
+    frame.render_widget(
+        Paragraph::new(" Up/Down=navigate  Enter=select  Esc/q=back").style(FOOTER_STYLE),
+        hints_area,
+    );
+}
+
 /// Pick nice round y-axis tick values (top and mid) given a max data value.
-fn y_axis_ticks(max_val: f64) -> (f64, f64) {
-    // Round steps: 1k, 2.5k, 5k, 10k, 25k, 50k, 100k, 250k, ...
-    let steps = [
-        1000.0, 2500.0, 5000.0, 10000.0, 25000.0, 50000.0, 100000.0, 250000.0, 500000.0, 1000000.0,
-        2500000.0, 5000000.0, 10000000.0,
+fn render_widget(frame: &mut Frame, widget: DashboardWidget, area: Rect, data: &HomeData) {
+    match widget {
+        DashboardWidget::Summary => render_summary(frame, area, data),
+        DashboardWidget::Balances => render_balances(frame, area, data),
+        DashboardWidget::Cashflow => render_cashflow(frame, area, data),
+        DashboardWidget::TopExpenses => render_top_expenses(frame, area, data),
+        DashboardWidget::Flagged => render_flagged(frame, area, data),
+        DashboardWidget::Budget => render_budget(frame, area, data),
+        DashboardWidget::Recurring => render_recurring(frame, area, data),
+        DashboardWidget::Schedules => render_schedules(frame, area, data),
+        DashboardWidget::TopClients => render_top_clients(frame, area, data),
+    }
+}
+
+fn widget_title(title: impl Into<String>) -> Line<'static> {
+    Line::from(Span::styled(
+        title.into(),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn render_summary(frame: &mut Frame, area: Rect, data: &HomeData) {
+    // YTD summary — 1-space indent to align with "N" in " Nigel:"
+    let stats_lines = vec![
+        Line::from(vec![
+            Span::raw(" YTD Income     "),
+            money_span(data.total_income),
+        ]),
+        Line::from(vec![
+            Span::raw(" YTD Expenses   "),
+            money_span(data.total_expenses),
+        ]),
+        Line::from(vec![Span::raw(" Net Profit     "), money_span(data.net)]),
+        Line::from(format!(" Transactions   {}", number(data.txn_count))),
+        Line::from(format!(" Flagged        {}", data.flagged_count)),
     ];
-    let top = steps
-        .iter()
-        .copied()
-        .find(|&s| s >= max_val)
-        .unwrap_or(max_val);
-    let mid = top / 2.0;
-    (top, mid)
+    frame.render_widget(Paragraph::new(stats_lines), area);
 }
 
-/// Format a dollar amount as compact "$Xk" or "$X.Xk" for thousands, "$XM" for millions.
-fn format_k(val: f64) -> String {
-    if val >= 1_000_000.0 {
-        let m = val / 1_000_000.0;
-        if m == m.floor() {
-            format!("${}M", m as u64)
-        } else {
-            format!("${:.1}M", m)
-        }
-    } else if val >= 1000.0 {
-        let k = val / 1000.0;
-        if k == k.floor() {
-            format!("${}k", k as u64)
+fn render_balances(frame: &mut Frame, area: Rect, data: &HomeData) {
+    let mut balance_lines = vec![widget_title(" Account Balances")];
+    for (name, bal) in &data.balances {
+        balance_lines.push(Line::from(vec![
+            Span::raw(format!(" {}", pad_right(name, 20))),
+            money_span(*bal),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(balance_lines), area);
+}
+
+/// Monthly Cash Flow bar chart with y-axis labels
+fn render_cashflow(frame: &mut Frame, area: Rect, data: &HomeData) {
+    if data.cashflow_labels.is_empty() {
+        return;
+    }
+    let income_style = Style::default().fg(crate::tui::GREEN);
+    let expense_style = Style::default().fg(Color::Red);
+
+    // Split: title row, gap, then chart
+    let [chart_title_area, _chart_gap, chart_body] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+
+    let chart_title = if data.cashflow_year_range.is_empty() {
+        " Monthly Cash Flow".to_string()
+    } else {
+        format!(" Monthly Cash Flow {}", data.cashflow_year_range)
+    };
+    frame.render_widget(Paragraph::new(widget_title(chart_title)), chart_title_area);
+
+    // Pick round y-axis tick values based on max data value
+    let max_val = data
+        .cashflow_income
+        .iter()
+        .chain(data.cashflow_expenses.iter())
+        .copied()
+        .max()
+        .unwrap_or(1) as f64;
+
+    // Round ticks: pick nice round numbers for the axis
+    let (top_tick, mid_tick) = y_axis_ticks(max_val);
+    let top_label = money_compact(top_tick);
+    let mid_label = money_compact(mid_tick);
+    let y_label_width = top_label.len().max(mid_label.len()) as u16 + 1;
+
+    let [y_axis_area, bar_area] =
+        Layout::horizontal([Constraint::Length(y_label_width), Constraint::Fill(1)])
+            .areas(chart_body);
+
+    // Y-axis labels: top tick near top, mid tick at middle
+    let inner_height = bar_area.height.saturating_sub(1); // month labels
+    let mid_row = inner_height / 2;
+    let mut y_lines: Vec<Line> = Vec::new();
+    for row in 0..inner_height {
+        if row == 0 {
+            y_lines.push(Line::from(Span::styled(
+                format!("{:>width$}", top_label, width = y_label_width as usize),
+                FOOTER_STYLE,
+            )));
+        } else if row == mid_row {
+            y_lines.push(Line::from(Span::styled(
+                format!("{:>width$}", mid_label, width = y_label_width as usize),
+                FOOTER_STYLE,
+            )));
         } else {
-            format!("${:.1}k", k)
+            y_lines.push(Line::from(""));
         }
+    }
+    frame.render_widget(Paragraph::new(y_lines), y_axis_area);
+
+    let groups: Vec<BarGroup> = data
+        .cashflow_labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let inc = data.cashflow_income.get(i).copied().unwrap_or(0);
+            let exp = data.cashflow_expenses.get(i).copied().unwrap_or(0);
+            let bars = vec![
+                Bar::default().value(inc).style(income_style),
+                Bar::default().value(exp).style(expense_style),
+            ];
+            BarGroup::default()
+                .label(Line::from(label.as_str()))
+                .bars(&bars)
+        })
+        .collect();
+
+    let mut chart = BarChart::default().bar_width(2).bar_gap(0).group_gap(1);
+    for group in &groups {
+        chart = chart.data(group.clone());
+    }
+    frame.render_widget(chart, bar_area);
+}
+
+/// Top Expenses — simple text table (no bars)
+fn render_top_expenses(frame: &mut Frame, area: Rect, data: &HomeData) {
+    if data.top_expenses.is_empty() {
+        return;
+    }
+    let name_width = data
+        .top_expenses
+        .iter()
+        .map(|(n, _)| display_width(n))
+        .max()
+        .unwrap_or(10);
+
+    let mut lines = vec![widget_title(" Top Expenses (3 months)")];
+    for (name, val) in &data.top_expenses {
+        lines.push(Line::from(vec![
+            Span::raw(format!(" {}  ", pad_right(name, name_width))),
+            money_span(-val), // negative to show as expense (red)
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Flagged count plus the most recent flagged transactions
+fn render_flagged(frame: &mut Frame, area: Rect, data: &HomeData) {
+    let mut lines = vec![widget_title(format!(
+        " Flagged Transactions ({})",
+        data.flagged_count
+    ))];
+    if data.recent_flagged.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " Nothing to review.",
+            FOOTER_STYLE,
+        )));
+    }
+    for (date, desc, amount) in &data.recent_flagged {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {date}  "), FOOTER_STYLE),
+            Span::raw(format!("{}  ", pad_right(&truncate(desc, 24), 24))),
+            money_span(*amount),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// This month's spending against the average of the previous three months
+fn render_budget(frame: &mut Frame, area: Rect, data: &HomeData) {
+    let mut lines = vec![widget_title(" Budget Status (vs 3-month avg)")];
+    if data.budget_avg <= 0.0 {
+        lines.push(Line::from(Span::styled(
+            " Not enough history yet.",
+            FOOTER_STYLE,
+        )));
+        frame.render_widget(Paragraph::new(lines), area);
+        return;
+    }
+    let pct = data.month_spent / data.budget_avg * 100.0;
+    let style = if pct > 100.0 {
+        Style::default().fg(Color::Red)
     } else {
-        format!("${}", val as u64)
+        Style::default().fg(crate::tui::GREEN)
+    };
+    lines.push(Line::from(vec![
+        Span::raw(" Spent this month  "),
+        money_span(-data.month_spent),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw(" Monthly average   "),
+        money_span(-data.budget_avg),
+    ]));
+    let bar_width = 20;
+    let filled = ((pct / 100.0 * bar_width as f64).round() as usize).min(bar_width);
+    lines.push(Line::from(vec![
+        Span::raw(" "),
+        Span::styled("█".repeat(filled), style),
+        Span::styled("░".repeat(bar_width - filled), FOOTER_STYLE),
+        Span::styled(format!(" {pct:.0}%"), style),
+    ]));
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Vendors charged in at least three of the last six months
+fn render_recurring(frame: &mut Frame, area: Rect, data: &HomeData) {
+    let mut lines = vec![widget_title(" Recurring Charges (6 months)")];
+    if data.recurring.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No recurring charges found.",
+            FOOTER_STYLE,
+        )));
+    }
+    let name_width = data
+        .recurring
+        .iter()
+        .map(|(n, _)| display_width(n).min(24))
+        .max()
+        .unwrap_or(10);
+    for (name, avg) in &data.recurring {
+        lines.push(Line::from(vec![
+            Span::raw(format!(" {}  ", pad_right(&truncate(name, 24), name_width))),
+            money_span(*avg),
+            Span::styled("/mo", FOOTER_STYLE),
+        ]));
     }
+    frame.render_widget(Paragraph::new(lines), area);
 }
 
-fn do_export(idx: usize, year: Option<i32>, month: Option<String>) -> Result<String> {
+/// Next run and last result of each scheduled report export
+fn render_schedules(frame: &mut Frame, area: Rect, data: &HomeData) {
+    let mut lines = vec![widget_title(" Scheduled Reports")];
+    if data.schedules.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " None yet: nigel schedule add",
+            FOOTER_STYLE,
+        )));
+    }
+    for s in &data.schedules {
+        let (mark, style) = match s.last_ok {
+            Some(true) => ("ok", Style::default().fg(crate::tui::GREEN)),
+            Some(false) => ("failed", Style::default().fg(Color::Red)),
+            None => ("not run", FOOTER_STYLE),
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                " {}  ",
+                pad_right(&format!("{} {}", s.report, s.cadence.as_str()), 20)
+            )),
+            Span::styled(format!("next {}  ", s.next_run), FOOTER_STYLE),
+            Span::styled(mark, style),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Clients with the most revenue this fiscal year, with their margin
+fn render_top_clients(frame: &mut Frame, area: Rect, data: &HomeData) {
+    let mut lines = vec![widget_title(" Top Clients (YTD)")];
+    if data.top_clients.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " None yet: nigel clients assign",
+            FOOTER_STYLE,
+        )));
+    }
+    let name_width = data
+        .top_clients
+        .iter()
+        .map(|(n, _, _)| display_width(n).min(24))
+        .max()
+        .unwrap_or(10);
+    for (name, revenue, pct) in &data.top_clients {
+        let margin = pct.map(|p| format!("  {p:.0}% margin")).unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::raw(format!(" {}  ", pad_right(&truncate(name, 24), name_width))),
+            money_span(*revenue),
+            Span::styled(margin, FOOTER_STYLE),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn do_export(
+    conn: &rusqlite::Connection,
+    idx: usize,
+    year: Option<i32>,
+    month: Option<String>,
+) -> Result<String> {
     #[cfg(not(feature = "pdf"))]
     {
-        let _ = (idx, year, month);
+        let _ = (conn, idx, year, month);
         return Err(crate::error::NigelError::Other(
             "PDF export requires the 'pdf' feature".into(),
         ));
     }
     #[cfg(feature = "pdf")]
     {
-        let year = year.or_else(|| Some(chrono::Local::now().year()));
+        let year = year.or_else(|| Some(FiscalYear::load(conn).current()));
+        let params = ReportParams::period(year, month.as_deref());
         let path = match idx {
-            0 => super::export::pnl(month.clone(), year, None, None, None)?,
-            1 => super::export::expenses(month.clone(), year, None)?,
+            0 => super::export::pnl(&params, None)?,
+            1 => super::export::expenses(&params, None)?,
             2 => super::export::tax(year, None)?,
-            3 => super::export::cashflow(month.clone(), year, None)?,
-            4 => super::export::register(month.clone(), year, None, None, None, None)?,
+            3 => super::export::cashflow(&params, None)?,
+            4 => super::export::register(&params, None)?,
             5 => super::export::flagged(None)?,
-            6 => super::export::balance(None)?,
+            6 => super::export::balance(false, None)?,
             7 => super::export::k1(year, None)?,
-            8 => return super::export::all(year, None),
+            8 => super::export::deductions(year, None)?,
+            9 => super::export::compensation(year, None)?,
+            10 => super::export::tags(&params, None)?,
+            11 => super::export::reimbursables(None, false, None)?,
+            12 => {
+                let (y, m) = super::digest::resolve_month(month.as_deref())?;
+                super::export::digest(y, m, None)?
+            }
+            13 => super::export::clients(year, None)?,
+            14 => super::export::books(None)?,
+            15 => super::export::quality(None)?,
+            16 => return super::export::all(year, None),
             _ => return Ok(String::new()),
         };
         Ok(format!("Exported {path}"))
     }
 }
 
-fn do_text_export(idx: usize, year: Option<i32>, month: Option<String>) -> Result<String> {
-    let year = year.or_else(|| Some(chrono::Local::now().year()));
+fn do_text_export(
+    conn: &rusqlite::Connection,
+    idx: usize,
+    year: Option<i32>,
+    month: Option<String>,
+) -> Result<String> {
+    let year = year.or_else(|| Some(FiscalYear::load(conn).current()));
+    let params = ReportParams::period(year, month.as_deref());
     let names = [
-        "pnl", "expenses", "tax", "cashflow", "register", "flagged", "balance", "k1-prep",
+        "pnl",
+        "expenses",
+        "tax",
+        "cashflow",
+        "register",
+        "flagged",
+        "balance",
+        "k1-prep",
+        "deductions",
+        "compensation",
+        "tags",
+        "reimbursables",
+        "digest",
+        "clients",
+        "books",
+        "quality",
     ];
 
-    if idx == 8 {
+    if idx == 16 {
         // "All Reports" text export
         let date = chrono::Local::now().format("%Y-%m-%d").to_string();
         let dir = crate::settings::get_data_dir().join("exports");
         std::fs::create_dir_all(&dir)?;
         let mut count = 0;
+        let params = ReportParams::period(year, None);
         let reports: Vec<(&str, Result<String>)> = vec![
-            ("pnl", super::report::text::pnl(None, year, None, None)),
-            ("expenses", super::report::text::expenses(None, year)),
+            ("pnl", super::report::text::pnl(&params)),
+            ("expenses", super::report::text::expenses(&params)),
             ("tax", super::report::text::tax(year)),
-            ("cashflow", super::report::text::cashflow(None, year)),
-            (
-                "register",
-                super::report::text::register(None, year, None, None, None),
-            ),
+            ("cashflow", super::report::text::cashflow(&params)),
+            ("register", super::report::text::register(&params)),
             ("flagged", super::report::text::flagged()),
-            ("balance", super::report::text::balance()),
+            ("balance", super::report::text::balance(false)),
             ("k1-prep", super::report::text::k1(year)),
+            ("deductions", super::report::text::deductions(year)),
+            ("compensation", super::report::text::compensation(year)),
+            ("tags", super::report::text::tags(&params)),
+            (
+                "reimbursables",
+                super::report::text::reimbursables(None, false),
+            ),
+            ("clients", super::report::text::clients(year)),
         ];
         let mut failed = Vec::new();
         for (name, result) in reports {
@@ -917,14 +1821,25 @@ fn do_text_export(idx: usize, year: Option<i32>, month: Option<String>) -> Resul
 
     let name = names.get(idx).unwrap_or(&"report");
     let content = match idx {
-        0 => super::report::text::pnl(month, year, None, None)?,
-        1 => super::report::text::expenses(month, year)?,
+        0 => super::report::text::pnl(&params)?,
+        1 => super::report::text::expenses(&params)?,
         2 => super::report::text::tax(year)?,
-        3 => super::report::text::cashflow(month, year)?,
-        4 => super::report::text::register(month, year, None, None, None)?,
+        3 => super::report::text::cashflow(&params)?,
+        4 => super::report::text::register(&params)?,
         5 => super::report::text::flagged()?,
-        6 => super::report::text::balance()?,
+        6 => super::report::text::balance(false)?,
         7 => super::report::text::k1(year)?,
+        8 => super::report::text::deductions(year)?,
+        9 => super::report::text::compensation(year)?,
+        10 => super::report::text::tags(&params)?,
+        11 => super::report::text::reimbursables(None, false)?,
+        12 => {
+            let (y, m) = super::digest::resolve_month(month.as_deref())?;
+            super::digest::text(y, m)?
+        }
+        13 => super::report::text::clients(year)?,
+        14 => super::report::text::books()?,
+        15 => super::report::text::quality()?,
         _ => return Ok(String::new()),
     };
 
@@ -940,14 +1855,29 @@ fn do_text_export(idx: usize, year: Option<i32>, month: Option<String>) -> Resul
 // Main entry point
 // ---------------------------------------------------------------------------
 
-pub fn run() -> Result<()> {
+pub fn run(no_splash: bool, mut tutorial: Option<Tutorial>) -> Result<()> {
+    // Returning users whose data directory has moved pick where it went
+    // before anything is created in its old place
+    if let Some(missing) = super::load::missing_data_dir() {
+        match super::relocate::run(&missing)? {
+            super::relocate::RelocateOutcome::Quit => return Ok(()),
+            super::relocate::RelocateOutcome::Switched
+            | super::relocate::RelocateOutcome::StartFresh => {}
+        }
+    }
+
     // Returning users: show splash screen before dashboard
     let is_first_run = !settings_file_exists();
     if !is_first_run {
         let settings = load_settings();
         let db_path = std::path::PathBuf::from(&settings.data_dir).join("nigel.db");
         let needs_password = db_path.exists() && crate::db::is_encrypted(&db_path)?;
-        if needs_password {
+        if no_splash {
+            // Skip the animation but still unlock an encrypted book
+            if needs_password {
+                crate::db::prompt_password_if_needed(&db_path)?;
+            }
+        } else if needs_password {
             super::splash::run_with_password(&db_path)?;
         } else {
             super::splash::run()?;
@@ -957,18 +1887,22 @@ pub fn run() -> Result<()> {
     // First-run: show onboarding, then ensure data dir + DB exist
     let mut post_setup_action = None;
     let mut onboarding_company = None;
+    let mut template = crate::templates::DEFAULT_TEMPLATE;
     if is_first_run {
         if let Some(result) = super::onboarding::run()? {
             let mut settings = load_settings();
             if !result.user_name.is_empty() {
                 settings.user_name = result.user_name;
             }
+            // New users start with the advanced features tucked away
+            settings.simple_mode = true;
             save_settings(&settings)?;
 
             if !result.company_name.is_empty() {
                 onboarding_company = Some(result.company_name);
             }
             post_setup_action = Some(result.action);
+            template = result.template;
 
             if let Some(ref pw) = result.password {
                 crate::db::set_db_password(Some(pw.clone()));
@@ -990,22 +1924,29 @@ pub fn run() -> Result<()> {
     let backups_dir = data_dir.join("backups");
     std::fs::create_dir_all(&backups_dir)?;
     crate::settings::restrict_dir_permissions(&backups_dir)?;
-    let conn = crate::db::get_connection(&data_dir.join("nigel.db"))?;
-    crate::db::init_db(&conn)?;
-
-    // Save company_name from onboarding to DB metadata
-    if let Some(company) = onboarding_company {
-        crate::db::set_metadata(&conn, "company_name", &company)?;
-    }
+    let db_path = data_dir.join("nigel.db");
+    if crate::db::is_read_only() {
+        // Nothing to set up or migrate; the book must already exist
+        if !db_path.exists() {
+            return Err(crate::error::NigelError::NotInitialized);
+        }
+    } else {
+        let conn = crate::db::get_connection(&db_path)?;
+        crate::db::init_db_with_template(&conn, template)?;
+        super::trash::empty_expired(&conn);
 
-    // Migrate legacy company_name from settings.json → DB metadata
-    if crate::db::get_metadata(&conn, "company_name").is_none() {
-        if let Some(company) = crate::settings::migrate_company_name() {
+        // Save company_name from onboarding to DB metadata
+        if let Some(company) = onboarding_company {
             crate::db::set_metadata(&conn, "company_name", &company)?;
         }
-    }
 
-    drop(conn);
+        // Migrate legacy company_name from settings.json → DB metadata
+        if crate::db::get_metadata(&conn, "company_name").is_none() {
+            if let Some(company) = crate::settings::migrate_company_name() {
+                crate::db::set_metadata(&conn, "company_name", &company)?;
+            }
+        }
+    }
 
     // Handle post-setup action from onboarding
     if let Some(action) = post_setup_action {
@@ -1013,6 +1954,9 @@ pub fn run() -> Result<()> {
             super::onboarding::PostSetupAction::Demo => {
                 super::demo::setup_demo()?;
             }
+            super::onboarding::PostSetupAction::Tutorial => {
+                tutorial = Some(super::tutorial::start()?);
+            }
             super::onboarding::PostSetupAction::Import => {
                 // User chose "Load existing" during onboarding — prompt for path
                 print!(
@@ -1028,7 +1972,8 @@ pub fn run() -> Result<()> {
                 }
             }
             super::onboarding::PostSetupAction::StartFresh => {
-                // Nothing extra — DB is already initialized above
+                // DB is already initialized above; the dashboard opens on the
+                // chart-of-accounts review
             }
         }
     }
@@ -1042,10 +1987,21 @@ pub fn run() -> Result<()> {
     // Non-blocking update check for dashboard
     let update_notification = super::update::check_and_notify();
 
+    // Starting from scratch opens on a review of the seeded categories
+    let mut review_chart = matches!(
+        post_setup_action,
+        Some(super::onboarding::PostSetupAction::StartFresh)
+    );
     loop {
         let conn = get_connection(&get_data_dir().join("nigel.db"))?;
         let mut dashboard = Dashboard::new(user_name.clone(), update_notification.clone());
         dashboard.load_data(&conn)?;
+        // Carried across reloads so a book switch doesn't end the tour
+        dashboard.session.tutorial = tutorial.take();
+        if std::mem::take(&mut review_chart) {
+            let manager = CategoryManager::for_setup(&conn, &dashboard.session.greeting);
+            dashboard.open(manager, &conn);
+        }
 
         let mut terminal = ratatui::init();
 
@@ -1054,18 +2010,23 @@ pub fn run() -> Result<()> {
                 break Err(e.into());
             }
 
-            if let DashboardScreen::Snake(ref mut game) = dashboard.screen {
-                let timeout = game.tick_rate();
-                match crossterm::event::poll(timeout) {
-                    Ok(true) => {
-                        // Key is available, fall through to event::read() below
-                    }
-                    Ok(false) => {
-                        // No input within timeout — advance game tick
-                        game.do_tick();
-                        continue;
+            // Screens that animate (a running game) tick when no key arrives
+            if let Some(screen) = dashboard.stack.last_mut() {
+                if let Some(timeout) = screen.tick_rate() {
+                    match crossterm::event::poll(timeout) {
+                        Ok(true) => {
+                            // Key is available, fall through to event::read() below
+                        }
+                        Ok(false) => {
+                            screen.tick(&mut ScreenContext {
+                                conn: &conn,
+                                session: &mut dashboard.session,
+                            });
+                            dashboard.refresh(&conn);
+                            continue;
+                        }
+                        Err(e) => break Err(e.into()),
                     }
-                    Err(e) => break Err(e.into()),
                 }
             }
 
@@ -1081,257 +2042,72 @@ pub fn run() -> Result<()> {
                         break Ok(true);
                     }
 
-                    let mut return_home = false;
-                    let mut pending_reload: Option<(usize, Option<i32>, Option<String>)> = None;
-                    let should_quit = match &mut dashboard.screen {
-                        DashboardScreen::Home => {
-                            if key.code == KeyCode::F(5) {
-                                let _ = dashboard.load_data(&conn);
-                                false
-                            } else {
-                                dashboard.handle_home_key(key.code, &conn)
-                            }
-                        }
-                        DashboardScreen::Import(ref mut import) => {
-                            match import.handle_key(key.code, &conn) {
-                                ImportAction::Close => {
-                                    return_home = true;
-                                }
-                                ImportAction::Continue => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::Browse(browser) => {
-                            match browser.handle_key_event(key.code) {
-                                BrowseAction::Close => {
-                                    return_home = true;
-                                }
-                                BrowseAction::Continue => {}
-                                BrowseAction::CommitEdit => {
-                                    if let Err(e) = browser.commit_edit(&conn) {
-                                        browser.set_status(format!("Edit failed: {e}"));
-                                    }
-                                }
-                                BrowseAction::ToggleFlag => {
-                                    if let Err(e) = browser.toggle_flag(&conn) {
-                                        browser.set_status(format!("Flag toggle failed: {e}"));
-                                    }
-                                }
-                            }
-                            false
-                        }
-                        DashboardScreen::Review(reviewer) => {
-                            match reviewer.handle_key(key.code) {
-                                HandleResult::Continue => {}
-                                HandleResult::CommitAndAdvance => {
-                                    if let Err(e) = reviewer.commit_review(&conn) {
-                                        break Err(e);
-                                    }
-                                    if reviewer.is_done() {
-                                        return_home = true;
-                                    }
-                                }
-                                HandleResult::UndoPrevious => {
-                                    if let Err(e) = reviewer.undo_previous(&conn) {
-                                        break Err(e);
-                                    }
-                                }
-                                HandleResult::Done => {
-                                    return_home = true;
-                                }
-                            }
-                            false
-                        }
-                        DashboardScreen::Accounts(ref mut manager) => {
-                            match manager.handle_key(key.code, &conn) {
-                                AccountAction::Close => {
-                                    return_home = true;
-                                }
-                                AccountAction::Continue => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::Categories(ref mut manager) => {
-                            match manager.handle_key(key.code, &conn) {
-                                CategoryAction::Close => {
-                                    return_home = true;
-                                }
-                                CategoryAction::Continue => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::Rules(ref mut rules) => {
-                            match rules.handle_key(key.code, &conn) {
-                                RulesAction::Close => {
-                                    return_home = true;
-                                }
-                                RulesAction::Continue => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::Reconcile(ref mut reconcile) => {
-                            match reconcile.handle_key(key.code, &conn) {
-                                ReconcileAction::Close => {
-                                    return_home = true;
-                                }
-                                ReconcileAction::Continue => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::Load(ref mut load) => {
-                            match load.handle_key(key.code) {
-                                LoadAction::Close => {
-                                    return_home = true;
-                                }
-                                LoadAction::Reload => {
-                                    dashboard.needs_reload = true;
-                                }
-                                LoadAction::Continue => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::Undo(ref mut undo) => {
-                            match undo.handle_key(key.code, &conn) {
-                                UndoAction::Close => {
-                                    return_home = true;
-                                }
-                                UndoAction::Continue => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::ReportView(ref mut view) => {
-                            let action = view.handle_key(key.code);
-                            match action {
-                                ReportViewAction::Close => {
-                                    dashboard.current_report_idx = None;
-                                    return_home = true;
-                                }
-                                ReportViewAction::Continue => {}
-                                ReportViewAction::Reload => {
-                                    // Stash reload info; handled below after borrow ends
-                                    if let Some(idx) = dashboard.current_report_idx {
-                                        let (year, month) = view.date_params();
-                                        pending_reload = Some((idx, year, month));
-                                    }
-                                }
-                            }
-                            false
-                        }
-                        DashboardScreen::ReportPicker { selection, mode } => {
-                            let max_idx = match mode {
-                                ReportPickerMode::View => REPORT_TYPES.len() - 1,
-                                ReportPickerMode::Export => EXPORT_TYPES.len() - 1,
-                            };
-                            match key.code {
-                                KeyCode::Up => *selection = selection.saturating_sub(1),
-                                KeyCode::Down => *selection = (*selection + 1).min(max_idx),
-                                KeyCode::Esc | KeyCode::Char('q') => return_home = true,
-                                KeyCode::Enter => match mode {
-                                    ReportPickerMode::View => {
-                                        dashboard.pending_report_view = Some(*selection);
-                                    }
-                                    ReportPickerMode::Export => {
-                                        dashboard.screen = DashboardScreen::ExportFormatPicker {
-                                            report_idx: *selection,
-                                            selection: 0,
-                                        };
-                                    }
-                                },
-                                _ => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::ExportFormatPicker {
-                            report_idx,
-                            selection,
-                        } => {
-                            let max_idx = EXPORT_FORMATS.len() - 1;
+                    // Ctrl+P opens the command palette from any screen but a
+                    // running game
+                    if key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('p')
+                        && !dashboard.captures_keys()
+                    {
+                        dashboard.open_palette(&conn);
+                        continue;
+                    }
+
+                    // F1-F3 drive the tutorial overlay
+                    if dashboard.palette.is_none() && !dashboard.captures_keys() {
+                        if let Some(tutorial) = dashboard.session.tutorial.as_mut() {
                             match key.code {
-                                KeyCode::Up => *selection = selection.saturating_sub(1),
-                                KeyCode::Down => *selection = (*selection + 1).min(max_idx),
-                                KeyCode::Esc | KeyCode::Char('q') => {
-                                    dashboard.screen = DashboardScreen::ReportPicker {
-                                        selection: *report_idx,
-                                        mode: ReportPickerMode::Export,
-                                    };
+                                KeyCode::F(1) if tutorial.is_finished() => {
+                                    dashboard.session.tutorial = None;
+                                    continue;
                                 }
-                                KeyCode::Enter => {
-                                    let format = EXPORT_FORMATS[*selection];
-                                    if format == "Text" {
-                                        dashboard.pending_text_export = Some(*report_idx);
-                                    } else {
-                                        dashboard.pending_export = Some(*report_idx);
-                                    }
+                                KeyCode::F(1) => {
+                                    tutorial.minimized = !tutorial.minimized;
+                                    continue;
                                 }
-                                _ => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::Settings(ref mut mgr) => {
-                            match mgr.handle_key(key.code, &conn) {
-                                SettingsAction::Close => {
-                                    return_home = true;
+                                KeyCode::F(2) => {
+                                    tutorial.skip(&conn);
+                                    continue;
                                 }
-                                SettingsAction::Continue => {}
-                            }
-                            false
-                        }
-                        DashboardScreen::Snake(ref mut game) => {
-                            match game.handle_key(key.code) {
-                                SnakeAction::Quit => {
-                                    return_home = true;
+                                KeyCode::F(3) => {
+                                    dashboard.session.tutorial = None;
+                                    continue;
                                 }
-                                SnakeAction::Continue => {}
+                                _ => {}
                             }
-                            false
                         }
-                    };
-
-                    if let Some((idx, year, month)) = pending_reload {
-                        dashboard.screen =
-                            dashboard.enter_report_view_with_date(idx, &conn, year, month);
                     }
 
-                    if return_home {
-                        dashboard.screen = DashboardScreen::Home;
-                        let _ = dashboard.load_data(&conn);
-                    }
-
-                    if let Some(idx) = dashboard.pending_report_view.take() {
-                        dashboard.current_report_idx = Some(idx);
-                        dashboard.screen = dashboard.enter_report_view(idx, &conn);
-                    }
-
-                    if let Some(idx) = dashboard.pending_export.take() {
-                        let (year, month) =
-                            if let DashboardScreen::ReportView(ref view) = dashboard.screen {
-                                view.date_params()
-                            } else {
-                                (None, None)
-                            };
-                        match do_export(idx, year, month) {
-                            Ok(msg) => dashboard.status_message = Some(msg),
-                            Err(e) => {
-                                dashboard.status_message = Some(format!("Export failed: {e}"))
+                    let palette_handled =
+                        match dashboard.palette.as_mut().map(|p| p.handle_key(key.code)) {
+                            None => false,
+                            Some(PaletteResult::Continue) => true,
+                            Some(PaletteResult::Close) => {
+                                dashboard.palette = None;
+                                true
                             }
-                        }
-                        dashboard.screen = DashboardScreen::Home;
-                    }
-
-                    if let Some(idx) = dashboard.pending_text_export.take() {
-                        let (year, month) =
-                            if let DashboardScreen::ReportView(ref view) = dashboard.screen {
-                                view.date_params()
-                            } else {
-                                (None, None)
-                            };
-                        match do_text_export(idx, year, month) {
-                            Ok(msg) => dashboard.status_message = Some(msg),
-                            Err(e) => {
-                                dashboard.status_message = Some(format!("Export failed: {e}"))
+                            Some(PaletteResult::Execute(action)) => {
+                                dashboard.palette = None;
+                                dashboard.run_palette_action(action, &conn);
+                                true
                             }
+                        };
+                    let should_quit = if palette_handled {
+                        false
+                    } else if !dashboard.stack.is_empty() {
+                        if let Err(e) = dashboard.screen_key(key.code, &conn) {
+                            break Err(e);
                         }
-                        dashboard.screen = DashboardScreen::Home;
+                        false
+                    } else if key.code == KeyCode::F(5) {
+                        let _ = dashboard.load_data(&conn);
+                        false
+                    } else {
+                        dashboard.handle_home_key(key.code, &conn)
+                    };
+                    dashboard.refresh(&conn);
+
+                    if let Some(tutorial) = dashboard.session.tutorial.as_mut() {
+                        tutorial.observe(&conn);
                     }
 
                     if dashboard.needs_reload {
@@ -1346,16 +2122,178 @@ pub fn run() -> Result<()> {
             }
         };
 
+        // Let open screens save what they carry across the session
+        dashboard.close_all(&conn);
         drop(terminal);
         ratatui::restore();
+        tutorial = dashboard.session.tutorial.take();
 
         match exit {
             Err(e) => return Err(e),
             Ok(true) => {
-                let _ = super::goodbye::run();
+                if !no_splash && load_settings().persona() == Persona::Full {
+                    let _ = super::goodbye::run();
+                }
                 return Ok(());
             }
             Ok(false) => continue, // reload (data directory changed)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_build_greeting_by_persona() {
+        let quip = "Kettle's on.";
+        assert_eq!(
+            build_greeting(Persona::Full, Some("Ada Lovelace"), quip),
+            "Hello, Ada. Kettle's on."
+        );
+        assert_eq!(
+            build_greeting(Persona::Full, None, quip),
+            "Nigel: Kettle's on."
+        );
+        assert_eq!(
+            build_greeting(Persona::Minimal, Some("Ada Lovelace"), quip),
+            "Hello, Ada."
+        );
+        assert_eq!(build_greeting(Persona::Minimal, Some(""), quip), "Nigel");
+        assert_eq!(build_greeting(Persona::Off, Some("Ada"), quip), "Nigel");
+    }
+
+    #[test]
+    fn test_simple_mode_pickers_skip_advanced_reports() {
+        assert_eq!(REPORT_TYPES[7], "report.k1");
+        assert_eq!(REPORT_TYPES[10], "report.tags");
+        let shown = |i| report_shown(true, i);
+        assert_eq!(step_shown(6, REPORT_TYPES.len(), true, shown), 8);
+        assert_eq!(step_shown(11, REPORT_TYPES.len(), false, shown), 9);
+        // The last row stays put
+        let last = EXPORT_TYPES.len() - 1;
+        assert_eq!(step_shown(last, EXPORT_TYPES.len(), true, shown), last);
+        assert_eq!(
+            step_shown(6, REPORT_TYPES.len(), true, |i| report_shown(false, i)),
+            7
+        );
+        assert_eq!(MENU_ITEMS[ADVANCED_MENU_ITEMS[0]].1, 'c');
+    }
+
+    #[test]
+    fn test_menu_and_report_keys_have_messages() {
+        let keys = MENU_ITEMS.iter().map(|(key, _)| *key);
+        for key in keys
+            .chain(REPORT_TYPES.iter().copied())
+            .chain(EXPORT_TYPES.iter().copied())
+        {
+            assert_ne!(t(key), key);
+        }
+    }
+
+    /// Records its enter and exit calls into a shared log.
+    struct Probe {
+        name: &'static str,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Screen for Probe {
+        fn draw(&mut self, _frame: &mut Frame) {}
+
+        /// `w` writes; `q` writes and closes.
+        fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+            ctx.publish(DataChange::Transactions);
+            Ok(match code {
+                KeyCode::Char('q') => Nav::Pop,
+                _ => Nav::Stay,
+            })
+        }
+
+        fn on_enter(&mut self, _ctx: &mut ScreenContext) -> Nav {
+            self.log.borrow_mut().push(format!("enter {}", self.name));
+            Nav::Stay
+        }
+
+        fn on_exit(&mut self, _ctx: &mut ScreenContext) {
+            self.log.borrow_mut().push(format!("exit {}", self.name));
+        }
+
+        fn on_data_changed(&mut self, change: DataChange, _ctx: &mut ScreenContext) {
+            self.log
+                .borrow_mut()
+                .push(format!("{} saw {change:?}", self.name));
+        }
+    }
+
+    #[test]
+    fn test_screen_stack_enters_and_exits_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        crate::db::init_db(&conn).unwrap();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let probe = |name| Probe {
+            name,
+            log: log.clone(),
+        };
+        let mut dashboard = Dashboard::new(None, None);
+
+        dashboard.open(probe("a"), &conn);
+        dashboard.open(probe("b"), &conn);
+        dashboard.navigate(Nav::Pop, &conn);
+        assert_eq!(dashboard.stack.len(), 1);
+        dashboard.navigate(Nav::Replace(Box::new(probe("c"))), &conn);
+        dashboard.open(probe("d"), &conn);
+        dashboard.navigate(Nav::Home, &conn);
+        assert!(dashboard.stack.is_empty());
+        assert!(dashboard.home_data.is_some());
+        assert_eq!(
+            *log.borrow(),
+            [
+                "enter a", "enter b", "exit b", "enter a", "exit a", "enter c", "enter d",
+                "exit d", "exit c",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_changes_reach_the_screens_beneath() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        crate::db::init_db(&conn).unwrap();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let probe = |name| Probe {
+            name,
+            log: log.clone(),
+        };
+        let mut dashboard = Dashboard::new(None, None);
+        dashboard.load_data(&conn).unwrap();
+        dashboard.open(probe("a"), &conn);
+        dashboard.open(probe("b"), &conn);
+        log.borrow_mut().clear();
+
+        // The publisher isn't told of its own change; the home waits
+        dashboard.screen_key(KeyCode::Char('w'), &conn).unwrap();
+        assert_eq!(*log.borrow(), ["a saw Transactions"]);
+        assert!(dashboard.home_stale);
+
+        // A change made on the way out still reaches the screen beneath
+        dashboard.screen_key(KeyCode::Char('q'), &conn).unwrap();
+        assert_eq!(
+            *log.borrow(),
+            [
+                "a saw Transactions",
+                "a saw Transactions",
+                "exit b",
+                "enter a"
+            ]
+        );
+
+        // Back home, the stale figures reload
+        dashboard.navigate(Nav::Pop, &conn);
+        assert!(dashboard.session.changes.is_empty());
+        assert!(!dashboard.home_stale);
+    }
+}
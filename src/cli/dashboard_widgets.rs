@@ -0,0 +1,221 @@
+use ratatui::layout::Constraint;
+
+/// A panel that can be placed on the dashboard home screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardWidget {
+    Summary,
+    Balances,
+    Cashflow,
+    TopExpenses,
+    Flagged,
+    Budget,
+    Recurring,
+    Schedules,
+    TopClients,
+}
+
+impl DashboardWidget {
+    pub const ALL: [DashboardWidget; 9] = [
+        DashboardWidget::Summary,
+        DashboardWidget::Balances,
+        DashboardWidget::Cashflow,
+        DashboardWidget::TopExpenses,
+        DashboardWidget::Flagged,
+        DashboardWidget::Budget,
+        DashboardWidget::Recurring,
+        DashboardWidget::Schedules,
+        DashboardWidget::TopClients,
+    ];
+
+    /// Key stored in settings.json `dashboard_widgets`.
+    pub fn key(self) -> &'static str {
+        match self {
+            DashboardWidget::Summary => "summary",
+            DashboardWidget::Balances => "balances",
+            DashboardWidget::Cashflow => "cashflow",
+            DashboardWidget::TopExpenses => "top_expenses",
+            DashboardWidget::Flagged => "flagged",
+            DashboardWidget::Budget => "budget",
+            DashboardWidget::Recurring => "recurring",
+            DashboardWidget::Schedules => "schedules",
+            DashboardWidget::TopClients => "top_clients",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DashboardWidget::Summary => "YTD Summary",
+            DashboardWidget::Balances => "Account Balances",
+            DashboardWidget::Cashflow => "Monthly Cash Flow",
+            DashboardWidget::TopExpenses => "Top Expenses",
+            DashboardWidget::Flagged => "Flagged Transactions",
+            DashboardWidget::Budget => "Budget Status",
+            DashboardWidget::Recurring => "Recurring Charges",
+            DashboardWidget::Schedules => "Scheduled Reports",
+            DashboardWidget::TopClients => "Top Clients",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|w| w.key() == key)
+    }
+
+    /// Rows the widget needs, or None if it stretches to fill spare space.
+    fn height(self) -> Option<u16> {
+        match self {
+            DashboardWidget::Summary | DashboardWidget::Balances => Some(5),
+            DashboardWidget::Budget => Some(4),
+            DashboardWidget::TopExpenses
+            | DashboardWidget::Flagged
+            | DashboardWidget::Recurring
+            | DashboardWidget::Schedules
+            | DashboardWidget::TopClients => Some(6),
+            DashboardWidget::Cashflow => None,
+        }
+    }
+}
+
+/// Parse widget keys from settings, skipping unknown keys and duplicates.
+pub fn parse_widgets(keys: &[String]) -> Vec<DashboardWidget> {
+    let mut widgets = Vec::new();
+    for key in keys {
+        if let Some(w) = DashboardWidget::from_key(key.trim()) {
+            if !widgets.contains(&w) {
+                widgets.push(w);
+            }
+        }
+    }
+    widgets
+}
+
+/// Height constraint for a row of side-by-side widgets: the tallest widget
+/// wins, and a row holding a stretchy widget fills the remaining space.
+pub fn row_constraint(row: &[DashboardWidget]) -> Constraint {
+    let mut height = 0;
+    for w in row {
+        match w.height() {
+            Some(h) => height = height.max(h),
+            None => return Constraint::Fill(1),
+        }
+    }
+    Constraint::Length(height)
+}
+
+/// Every widget with its enabled state, enabled widgets first in display
+/// order. Backs the dashboard widgets screen in settings.
+pub struct WidgetToggles {
+    pub items: Vec<(DashboardWidget, bool)>,
+}
+
+impl WidgetToggles {
+    pub fn from_keys(keys: &[String]) -> Self {
+        let enabled = parse_widgets(keys);
+        let mut items: Vec<(DashboardWidget, bool)> = enabled.iter().map(|w| (*w, true)).collect();
+        for w in DashboardWidget::ALL {
+            if !enabled.contains(&w) {
+                items.push((w, false));
+            }
+        }
+        Self { items }
+    }
+
+    pub fn toggle(&mut self, idx: usize) {
+        if let Some(item) = self.items.get_mut(idx) {
+            item.1 = !item.1;
+        }
+    }
+
+    /// Swap the item at `idx` with its neighbour above. Returns the new index.
+    pub fn move_up(&mut self, idx: usize) -> usize {
+        if idx == 0 || idx >= self.items.len() {
+            return idx;
+        }
+        self.items.swap(idx, idx - 1);
+        idx - 1
+    }
+
+    /// Swap the item at `idx` with its neighbour below. Returns the new index.
+    pub fn move_down(&mut self, idx: usize) -> usize {
+        if idx + 1 >= self.items.len() {
+            return idx;
+        }
+        self.items.swap(idx, idx + 1);
+        idx + 1
+    }
+
+    pub fn enabled_keys(&self) -> Vec<String> {
+        self.items
+            .iter()
+            .filter(|(_, on)| *on)
+            .map(|(w, _)| w.key().to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::DEFAULT_DASHBOARD_WIDGETS;
+
+    fn keys(list: &[&str]) -> Vec<String> {
+        list.iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn key_roundtrip() {
+        for w in DashboardWidget::ALL {
+            assert_eq!(DashboardWidget::from_key(w.key()), Some(w));
+        }
+        assert_eq!(DashboardWidget::from_key("nope"), None);
+    }
+
+    #[test]
+    fn parse_skips_unknown_and_duplicates() {
+        let widgets = parse_widgets(&keys(&["flagged", "bogus", "cashflow", "flagged"]));
+        assert_eq!(
+            widgets,
+            vec![DashboardWidget::Flagged, DashboardWidget::Cashflow]
+        );
+    }
+
+    #[test]
+    fn default_layout_matches_classic_dashboard() {
+        let defaults: Vec<String> = DEFAULT_DASHBOARD_WIDGETS
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        let widgets = parse_widgets(&defaults);
+        let rows: Vec<_> = widgets.chunks(2).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(row_constraint(rows[0]), Constraint::Length(5));
+        assert_eq!(row_constraint(rows[1]), Constraint::Fill(1));
+    }
+
+    #[test]
+    fn row_constraint_uses_tallest_widget() {
+        let row = [DashboardWidget::Budget, DashboardWidget::Recurring];
+        assert_eq!(row_constraint(&row), Constraint::Length(6));
+    }
+
+    #[test]
+    fn toggles_list_enabled_first() {
+        let toggles = WidgetToggles::from_keys(&keys(&["recurring", "summary"]));
+        assert_eq!(toggles.items.len(), DashboardWidget::ALL.len());
+        assert_eq!(toggles.items[0], (DashboardWidget::Recurring, true));
+        assert_eq!(toggles.items[1], (DashboardWidget::Summary, true));
+        assert!(!toggles.items[2].1);
+        assert_eq!(toggles.enabled_keys(), keys(&["recurring", "summary"]));
+    }
+
+    #[test]
+    fn toggle_and_reorder() {
+        let mut toggles = WidgetToggles::from_keys(&keys(&["summary", "balances"]));
+        assert_eq!(toggles.move_up(0), 0);
+        assert_eq!(toggles.move_down(0), 1);
+        assert_eq!(toggles.enabled_keys(), keys(&["balances", "summary"]));
+        toggles.toggle(0);
+        assert_eq!(toggles.enabled_keys(), keys(&["summary"]));
+        let last = toggles.items.len() - 1;
+        assert_eq!(toggles.move_down(last), last);
+    }
+}
@@ -0,0 +1,68 @@
+use std::io::Write;
+
+use crate::db::{get_connection, get_db_password, open_read_only};
+use crate::error::{NigelError, Result};
+use crate::fmt::format_bytes;
+use crate::maintenance;
+use crate::schema;
+use crate::settings::{get_data_dir, load_settings};
+
+/// Print the book's schema, with descriptions, as JSON or commented SQL.
+pub fn schema(format: &str) -> Result<()> {
+    if !matches!(format, "json" | "sql") {
+        return Err(NigelError::Other(format!(
+            "Unknown --format '{format}'. Expected 'json' or 'sql'."
+        )));
+    }
+    let conn = open_read_only(
+        &get_data_dir().join("nigel.db"),
+        get_db_password().as_deref(),
+    )?;
+    let schema = schema::describe(&conn)?;
+    let out = if format == "sql" {
+        schema.to_sql()
+    } else {
+        let json = serde_json::to_string_pretty(&schema.to_json())
+            .map_err(|e| NigelError::Other(e.to_string()))?;
+        format!("{json}\n")
+    };
+    std::io::stdout().lock().write_all(out.as_bytes())?;
+    Ok(())
+}
+
+/// Compact and tidy the book, printing what it did. With `if_due`, does
+/// nothing until `maintenance_days` have passed since the last run.
+pub fn maintain(if_due: bool) -> Result<()> {
+    let data_dir = get_data_dir();
+    let db_path = data_dir.join("nigel.db");
+    let conn = get_connection(&db_path)?;
+    let settings = load_settings();
+    if if_due {
+        let today = chrono::Local::now().date_naive();
+        if !maintenance::is_due(&conn, settings.maintenance_days(), today) {
+            println!("Maintenance not due.");
+            return Ok(());
+        }
+    }
+    let report = maintenance::maintain(
+        &conn,
+        &db_path,
+        &data_dir,
+        settings.snapshot_keep(),
+        settings.backup_keep(),
+    )?;
+    println!(
+        "Database: {} -> {} ({} reclaimed)",
+        format_bytes(report.size_before),
+        format_bytes(report.size_after),
+        format_bytes(report.reclaimed())
+    );
+    println!("Rebuilt indexes and refreshed query statistics.");
+    let plural = |n: usize, word: &str| format!("{n} {word}{}", if n == 1 { "" } else { "s" });
+    println!(
+        "Pruned {} and {}.",
+        plural(report.snapshots_pruned, "snapshot"),
+        plural(report.backups_pruned, "backup")
+    );
+    Ok(())
+}
@@ -1,11 +1,11 @@
 use std::path::PathBuf;
 
 use chrono::{Datelike, Local, NaiveDate};
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 
-use crate::categorizer::categorize_transactions;
+use crate::categorizer::{categorize_transactions, CategorizeResult};
 use crate::db::{get_connection, init_db};
-use crate::error::Result;
+use crate::error::{NigelError, Result};
 use crate::settings::load_settings;
 
 const ACCOUNT_NAME: &str = "BofA Checking";
@@ -347,7 +347,41 @@ fn insert_demo_data(conn: &Connection) -> Result<usize> {
     Ok(txn_count)
 }
 
-pub fn run() -> Result<()> {
+/// Metadata key marking a database created by `nigel demo` as a demo book.
+const DEMO_BOOK_KEY: &str = "demo_book";
+/// Metadata key (in a demo book) holding the data directory to return to.
+const DEMO_RETURN_KEY: &str = "demo_return_dir";
+/// Metadata key recording that demo data was loaded into a real book with
+/// `--in-place`.
+const DEMO_INJECTED_KEY: &str = "demo_injected";
+const DEMO_COMPANY: &str = "Acme Consulting LLC";
+
+fn demo_loaded(conn: &Connection) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM accounts WHERE name = ?1)",
+        [ACCOUNT_NAME],
+        |r| r.get(0),
+    )?)
+}
+
+pub fn is_demo_book(conn: &Connection) -> bool {
+    crate::db::get_metadata(conn, DEMO_BOOK_KEY).is_some()
+}
+
+fn print_next_steps() {
+    println!();
+    println!("Try these next:");
+    println!("  nigel accounts list");
+    println!("  nigel rules list");
+    println!("  nigel report pnl");
+    println!("  nigel report flagged");
+    println!("  nigel review");
+}
+
+/// `nigel demo`: by default the sample data goes into a separate demo book
+/// so the current books are untouched; `in_place` loads it into the current
+/// book instead, and `clean` removes data loaded that way.
+pub fn run(in_place: bool, clean: bool) -> Result<()> {
     let settings = load_settings();
     let db_path = PathBuf::from(&settings.data_dir).join("nigel.db");
 
@@ -356,16 +390,18 @@ pub fn run() -> Result<()> {
         std::process::exit(1);
     }
 
+    if clean {
+        return run_clean(&db_path);
+    }
+    if !in_place {
+        return run_isolated();
+    }
+
     let conn = get_connection(&db_path)?;
     init_db(&conn)?;
 
     // Idempotency guard
-    let exists: bool = conn.query_row(
-        "SELECT EXISTS(SELECT 1 FROM accounts WHERE name = ?1)",
-        [ACCOUNT_NAME],
-        |r| r.get(0),
-    )?;
-    if exists {
+    if demo_loaded(&conn)? {
         println!(
             "Demo data already loaded (account '{}' exists).",
             ACCOUNT_NAME
@@ -374,35 +410,69 @@ pub fn run() -> Result<()> {
     }
 
     let txn_count = insert_demo_data(&conn)?;
-    crate::db::set_metadata(&conn, "company_name", "Acme Consulting LLC")?;
+    crate::db::set_metadata(&conn, "company_name", DEMO_COMPANY)?;
+    if !is_demo_book(&conn) {
+        crate::db::set_metadata(&conn, DEMO_INJECTED_KEY, "1")?;
+    }
     let result = categorize_transactions(&conn)?;
 
     println!("Demo data loaded!");
+    print_summary(txn_count, &result);
+    println!();
+    println!("Remove it again with `nigel demo --clean`.");
+    print_next_steps();
+
+    Ok(())
+}
+
+fn print_summary(txn_count: usize, result: &CategorizeResult) {
     println!("  Account:      {ACCOUNT_NAME}");
     println!("  Transactions: {txn_count}");
     println!("  Rules:        {}", RULES.len());
     println!("  Categorized:  {}", result.categorized);
     println!("  Flagged:      {}", result.still_flagged);
-    println!();
-    println!("Try these next:");
-    println!("  nigel accounts list");
-    println!("  nigel rules list");
-    println!("  nigel report pnl");
-    println!("  nigel report flagged");
-    println!("  nigel review");
+}
+
+fn run_isolated() -> Result<()> {
+    let previous = load_settings().data_dir;
+    let (demo_dir, loaded) = setup_demo()?;
+    let Some((txn_count, result)) = loaded else {
+        println!(
+            "Demo data already loaded (demo book at {}).",
+            demo_dir.display()
+        );
+        return Ok(());
+    };
 
+    println!("Demo data loaded into a separate demo book!");
+    println!("  Location:     {}", demo_dir.display());
+    print_summary(txn_count, &result);
+    println!();
+    println!("Your books at {previous} were not changed.");
+    println!("Switch back with `nigel load {previous}`.");
+    print_next_steps();
     Ok(())
 }
 
 /// Create a demo data directory with its own nigel.db and switch settings
-/// to point at it. Used by the onboarding flow so the user's real books
-/// stay clean.
-pub fn setup_demo() -> Result<()> {
+/// to point at it, so the user's real books stay clean. Reuses the current
+/// book if it already is the demo book. Returns the demo directory and, if
+/// sample data was loaded by this call, its transaction count and
+/// categorization result.
+pub fn setup_demo() -> Result<(PathBuf, Option<(usize, CategorizeResult)>)> {
     use crate::settings::save_settings;
 
     let mut settings = load_settings();
     let base_dir = PathBuf::from(&settings.data_dir);
-    let demo_dir = base_dir.join("demo");
+    let current_db = base_dir.join("nigel.db");
+    // An encrypted book can't be read without a password; it is never a demo book
+    let already_demo =
+        current_db.exists() && get_connection(&current_db).is_ok_and(|conn| is_demo_book(&conn));
+    let demo_dir = if already_demo {
+        base_dir.clone()
+    } else {
+        base_dir.join("demo")
+    };
     std::fs::create_dir_all(&demo_dir)?;
     crate::settings::restrict_dir_permissions(&demo_dir)?;
     let exports_dir = demo_dir.join("exports");
@@ -412,22 +482,136 @@ pub fn setup_demo() -> Result<()> {
     let db_path = demo_dir.join("nigel.db");
     let conn = get_connection(&db_path)?;
     init_db(&conn)?;
-
-    let exists: bool = conn.query_row(
-        "SELECT EXISTS(SELECT 1 FROM accounts WHERE name = ?1)",
-        [ACCOUNT_NAME],
-        |r| r.get(0),
-    )?;
-    if !exists {
-        insert_demo_data(&conn)?;
-        crate::db::set_metadata(&conn, "company_name", "Acme Consulting LLC")?;
-        categorize_transactions(&conn)?;
+    crate::db::set_metadata(&conn, DEMO_BOOK_KEY, "1")?;
+    if !already_demo {
+        crate::db::set_metadata(&conn, DEMO_RETURN_KEY, &settings.data_dir)?;
     }
 
+    let loaded = if demo_loaded(&conn)? {
+        None
+    } else {
+        let txn_count = insert_demo_data(&conn)?;
+        crate::db::set_metadata(&conn, "company_name", DEMO_COMPANY)?;
+        Some((txn_count, categorize_transactions(&conn)?))
+    };
+
     // Point settings at the demo directory
     settings.data_dir = demo_dir.to_string_lossy().to_string();
     save_settings(&settings)?;
 
+    Ok((demo_dir, loaded))
+}
+
+/// What `clean_demo_data` removed.
+#[derive(Debug, Default, PartialEq)]
+pub struct CleanResult {
+    pub transactions: usize,
+    pub account_removed: bool,
+    pub rules: usize,
+    /// Transactions left on the demo account because they did not come
+    /// from the demo (imported, or not a demo description).
+    pub kept_transactions: usize,
+}
+
+/// Remove demo data previously loaded into this book with `--in-place`.
+/// Only unimported transactions with demo descriptions on the demo account
+/// are deleted; the account itself is removed only once nothing else
+/// references it. Demo rules are removed when they match the demo rule set
+/// exactly.
+pub fn clean_demo_data(conn: &Connection) -> Result<CleanResult> {
+    let mut result = CleanResult::default();
+    let account_id: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM accounts WHERE name = ?1",
+            [ACCOUNT_NAME],
+            |r| r.get(0),
+        )
+        .optional()?;
+    let injected = crate::db::get_metadata(conn, DEMO_INJECTED_KEY).is_some();
+    if account_id.is_none() && !injected {
+        return Ok(result);
+    }
+
+    let mut descriptions: Vec<&str> = vec!["STRIPE TRANSFER", "INTEREST PAYMENT"];
+    descriptions.extend(RECURRING.iter().map(|r| r.description));
+    descriptions.extend(ROTATING.iter().map(|r| r.description));
+    for (a, b) in MEALS {
+        descriptions.extend([*a, *b]);
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    if let Some(account_id) = account_id {
+        for desc in &descriptions {
+            result.transactions += tx.execute(
+                "DELETE FROM transactions \
+                 WHERE account_id = ?1 AND import_id IS NULL AND description = ?2",
+                rusqlite::params![account_id, desc],
+            )?;
+        }
+        result.kept_transactions = tx.query_row(
+            "SELECT COUNT(*) FROM transactions WHERE account_id = ?1",
+            [account_id],
+            |r| r.get::<_, i64>(0),
+        )? as usize;
+        let referenced: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM imports WHERE account_id = ?1) \
+             OR EXISTS(SELECT 1 FROM reconciliations WHERE account_id = ?1)",
+            [account_id],
+            |r| r.get(0),
+        )?;
+        if result.kept_transactions == 0 && !referenced {
+            tx.execute("DELETE FROM accounts WHERE id = ?1", [account_id])?;
+            result.account_removed = true;
+        }
+    }
+
+    for rule in RULES {
+        result.rules += tx.execute(
+            "DELETE FROM rules WHERE pattern = ?1 AND match_type = 'contains' AND vendor = ?2 \
+             AND category_id = (SELECT id FROM categories WHERE name = ?3)",
+            rusqlite::params![rule.pattern, rule.vendor, rule.category],
+        )?;
+    }
+
+    tx.execute(
+        "DELETE FROM metadata WHERE key = 'company_name' AND value = ?1",
+        [DEMO_COMPANY],
+    )?;
+    tx.execute("DELETE FROM metadata WHERE key = ?1", [DEMO_INJECTED_KEY])?;
+    tx.commit()?;
+    Ok(result)
+}
+
+fn run_clean(db_path: &std::path::Path) -> Result<()> {
+    let conn = get_connection(db_path)?;
+    init_db(&conn)?;
+    if is_demo_book(&conn) {
+        let back = crate::db::get_metadata(&conn, DEMO_RETURN_KEY)
+            .map(|dir| format!(" Switch back with `nigel load {dir}`;"))
+            .unwrap_or_default();
+        return Err(NigelError::Other(format!(
+            "This is the separate demo book, not your books.{back} \
+             the demo book can be removed by deleting {}.",
+            db_path.parent().unwrap_or(db_path).display()
+        )));
+    }
+
+    let result = clean_demo_data(&conn)?;
+    if result == CleanResult::default() {
+        println!("No demo data found in this book.");
+        return Ok(());
+    }
+    println!("Removed demo data:");
+    println!("  Transactions: {}", result.transactions);
+    println!("  Rules:        {}", result.rules);
+    if result.account_removed {
+        println!("  Account:      {ACCOUNT_NAME}");
+    } else if result.kept_transactions > 0 {
+        println!(
+            "Kept account '{ACCOUNT_NAME}': it still has {} transaction(s) that are not demo data.",
+            result.kept_transactions
+        );
+    }
     Ok(())
 }
 
@@ -616,4 +800,84 @@ mod tests {
             assert!(parsed.is_ok(), "invalid date: {}", txn.date);
         }
     }
+
+    #[test]
+    fn test_clean_removes_demo_data_only() {
+        let (_dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Real Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        let real_acct = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (?1, '2025-01-02', 'STRIPE TRANSFER', 900.0)",
+            [real_acct],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO rules (pattern, match_type, category_id) \
+             VALUES ('MY VENDOR', 'contains', 1)",
+            [],
+        )
+        .unwrap();
+        let txn_count = insert_demo_data(&conn).unwrap();
+        crate::db::set_metadata(&conn, "company_name", DEMO_COMPANY).unwrap();
+
+        let result = clean_demo_data(&conn).unwrap();
+        assert_eq!(result.transactions, txn_count);
+        assert_eq!(result.rules, RULES.len());
+        assert!(result.account_removed);
+
+        let accounts: Vec<String> = conn
+            .prepare("SELECT name FROM accounts")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(accounts, vec!["Real Checking"]);
+        let remaining: i64 = conn
+            .query_row("SELECT count(*) FROM transactions", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(remaining, 1, "real transaction kept");
+        let rules: i64 = conn
+            .query_row("SELECT count(*) FROM rules", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rules, 1, "user rule kept");
+        assert!(crate::db::get_metadata(&conn, "company_name").is_none());
+
+        // Nothing left to clean
+        assert_eq!(clean_demo_data(&conn).unwrap(), CleanResult::default());
+    }
+
+    #[test]
+    fn test_clean_keeps_account_with_imported_transactions() {
+        let (_dir, conn) = test_db();
+        insert_demo_data(&conn).unwrap();
+        let acct: i64 = conn
+            .query_row(
+                "SELECT id FROM accounts WHERE name = ?1",
+                [ACCOUNT_NAME],
+                |r| r.get(0),
+            )
+            .unwrap();
+        conn.execute(
+            "INSERT INTO imports (filename, account_id, checksum) VALUES ('real.csv', ?1, 'abc')",
+            [acct],
+        )
+        .unwrap();
+        let import_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, import_id) \
+             VALUES (?1, '2025-01-02', 'STRIPE TRANSFER', 900.0, ?2)",
+            rusqlite::params![acct, import_id],
+        )
+        .unwrap();
+
+        let result = clean_demo_data(&conn).unwrap();
+        assert!(!result.account_removed);
+        assert_eq!(result.kept_transactions, 1);
+    }
 }
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use crate::db::{get_connection, get_metadata};
+use crate::digest::{self, Digest};
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::settings::get_data_dir;
+
+use super::report::defaults::{default_period, Period};
+
+/// The month asked for, else the configured digest default, else last month.
+pub fn resolve_month(month: Option<&str>) -> Result<(i32, u32)> {
+    if let Some(m) = month {
+        return digest::parse_month(m);
+    }
+    match default_period("digest", FiscalYear::CALENDAR) {
+        Some(Period::Month(y, m)) => Ok((y, m)),
+        _ => Ok(digest::last_month(chrono::Local::now().date_naive())),
+    }
+}
+
+fn load(year: i32, month: u32) -> Result<(Digest, String)> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    Ok((digest::build(&conn, year, month)?, company))
+}
+
+/// The digest as plain text, under the company name.
+pub fn text(year: i32, month: u32) -> Result<String> {
+    let (digest, company) = load(year, month)?;
+    Ok(super::report::text::with_header(&company, digest.to_text()))
+}
+
+pub fn markdown(year: i32, month: u32) -> Result<String> {
+    let (digest, company) = load(year, month)?;
+    Ok(digest.to_markdown(&company))
+}
+
+pub fn run(month: Option<String>, format: &str, output: Option<String>) -> Result<()> {
+    let (year, month) = resolve_month(month.as_deref())?;
+    let content = match format {
+        "text" => text(year, month)?,
+        "markdown" | "md" => markdown(year, month)?,
+        "pdf" => {
+            #[cfg(feature = "pdf")]
+            {
+                super::export::digest(year, month, output)?;
+                return Ok(());
+            }
+            #[cfg(not(feature = "pdf"))]
+            return Err(NigelError::Other(
+                "PDF export requires the 'pdf' feature".into(),
+            ));
+        }
+        other => {
+            return Err(NigelError::Other(format!(
+                "Unknown format '{other}'; use text, markdown, or pdf"
+            )))
+        }
+    };
+    match output {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, format!("{content}\n"))?;
+            crate::settings::restrict_file_permissions(&path)?;
+            println!("Wrote {}", path.display());
+        }
+        None => println!("{content}"),
+    }
+    Ok(())
+}
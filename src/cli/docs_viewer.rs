@@ -0,0 +1,343 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::cli::screen::{self, Nav, ScreenContext};
+use crate::error::Result;
+use crate::help::{self, DocLine, LineKind, TOPICS};
+use crate::tui::{truncate, FOOTER_STYLE, HEADER_STYLE};
+
+const TOPIC_LIST_WIDTH: u16 = 34;
+const CODE_STYLE: Style = Style::new().fg(Color::Cyan);
+const MATCH_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+pub enum DocsAction {
+    Continue,
+    Close,
+}
+
+/// Built-in help: a topic list beside the selected topic, with search
+/// across every topic.
+pub struct DocsViewer {
+    topic: usize,
+    scroll: usize,
+    /// Each topic laid out at `width`; redone when the terminal resizes.
+    lines: Vec<Vec<DocLine>>,
+    width: usize,
+    last_visible_rows: usize,
+    /// Search text while it's being typed.
+    input: Option<String>,
+    query: String,
+    /// `(topic, line)` of every line matching `query`, in reading order.
+    matches: Vec<(usize, usize)>,
+    current_match: Option<usize>,
+    status_message: Option<String>,
+    greeting: String,
+}
+
+impl DocsViewer {
+    pub fn new(greeting: &str) -> Self {
+        Self {
+            topic: 0,
+            scroll: 0,
+            lines: Vec::new(),
+            width: 0,
+            last_visible_rows: 20,
+            input: None,
+            query: String::new(),
+            matches: Vec::new(),
+            current_match: None,
+            status_message: None,
+            greeting: greeting.to_string(),
+        }
+    }
+
+    fn relayout(&mut self, width: usize) {
+        if width == self.width && !self.lines.is_empty() {
+            return;
+        }
+        self.width = width;
+        self.lines = TOPICS
+            .iter()
+            .map(|t| help::layout(t.source, width))
+            .collect();
+        self.refresh_matches();
+    }
+
+    fn refresh_matches(&mut self) {
+        self.matches = self
+            .lines
+            .iter()
+            .enumerate()
+            .flat_map(|(t, lines)| {
+                help::find(lines, &self.query)
+                    .into_iter()
+                    .map(move |l| (t, l))
+            })
+            .collect();
+        self.current_match = None;
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let border_style = Style::default().fg(Color::DarkGray);
+
+        let [header_area, sep, body_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
+            header_area,
+        );
+        let sep_line = "\u{2501}".repeat(area.width as usize);
+        frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
+
+        let [list_area, content_area] =
+            Layout::horizontal([Constraint::Length(TOPIC_LIST_WIDTH), Constraint::Fill(1)])
+                .areas(body_area);
+
+        // One column of margin on each side of the text
+        self.relayout((content_area.width as usize).saturating_sub(2));
+        let visible = (content_area.height as usize).saturating_sub(1);
+        self.last_visible_rows = visible;
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        self.draw_topic_list(frame, list_area);
+
+        let current_line = self
+            .current_match
+            .map(|i| self.matches[i])
+            .filter(|(t, _)| *t == self.topic)
+            .map(|(_, l)| l);
+        let mut lines = vec![Line::from("")];
+        for (i, line) in self.lines[self.topic]
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(visible)
+        {
+            lines.push(self.styled_line(line, current_line == Some(i)));
+        }
+        frame.render_widget(Paragraph::new(lines), content_area);
+
+        let hints = if let Some(input) = &self.input {
+            Line::from(vec![
+                Span::raw(format!(" Search: {input}")),
+                Span::styled("\u{2588}", Style::default().fg(Color::Yellow)),
+                Span::styled("  Enter=done  Esc=cancel", FOOTER_STYLE),
+            ])
+        } else if let Some(msg) = &self.status_message {
+            Line::from(Span::styled(
+                format!(" {msg}"),
+                Style::default().fg(Color::Yellow),
+            ))
+        } else if !self.query.is_empty() {
+            Line::from(Span::styled(
+                " Up/Down=scroll  n/N=next/prev match  /=search  Esc=clear search",
+                FOOTER_STYLE,
+            ))
+        } else {
+            Line::from(Span::styled(
+                " Up/Down=scroll  PgUp/PgDn=page  Left/Right or 1-9=topic  /=search  Esc=back",
+                FOOTER_STYLE,
+            ))
+        };
+        frame.render_widget(Paragraph::new(hints), hints_area);
+    }
+
+    fn draw_topic_list(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                " Help topics",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        for (i, topic) in TOPICS.iter().enumerate() {
+            let marker = if i == self.topic { ">" } else { " " };
+            let mut style = if i == self.topic {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let hits = self.matches.iter().filter(|(t, _)| *t == i).count();
+            let count = if self.query.is_empty() {
+                String::new()
+            } else {
+                if hits == 0 {
+                    style = style.fg(Color::DarkGray);
+                }
+                format!(" ({hits})")
+            };
+            let max = (TOPIC_LIST_WIDTH as usize).saturating_sub(6 + count.len());
+            lines.push(Line::from(Span::styled(
+                format!(
+                    " {marker} {} {}{count}",
+                    i + 1,
+                    truncate(topic.title(), max)
+                ),
+                style,
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    /// A laid-out line with headings, code, and search matches styled.
+    fn styled_line(&self, line: &DocLine, current: bool) -> Line<'static> {
+        let base = match line.kind {
+            LineKind::Title => HEADER_STYLE,
+            LineKind::Heading => Style::default().add_modifier(Modifier::BOLD),
+            _ => Style::default(),
+        };
+        let mut spans = vec![Span::raw(" ")];
+        for segment in &line.segments {
+            let style = if segment.code { CODE_STYLE } else { base };
+            let mut at = 0;
+            for (start, end) in help::match_ranges(&segment.text, &self.query) {
+                spans.push(Span::styled(segment.text[at..start].to_string(), style));
+                let highlight = if current {
+                    MATCH_STYLE.add_modifier(Modifier::BOLD)
+                } else {
+                    MATCH_STYLE
+                };
+                spans.push(Span::styled(
+                    segment.text[start..end].to_string(),
+                    highlight,
+                ));
+                at = end;
+            }
+            spans.push(Span::styled(segment.text[at..].to_string(), style));
+        }
+        if current {
+            spans.push(Span::styled(
+                "  \u{25c0}",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines
+            .get(self.topic)
+            .map_or(0, |l| l.len().saturating_sub(self.last_visible_rows))
+    }
+
+    fn select_topic(&mut self, topic: usize) {
+        if topic < TOPICS.len() && topic != self.topic {
+            self.topic = topic;
+            self.scroll = 0;
+        }
+    }
+
+    /// Move to the next (or previous) match after the one shown, wrapping
+    /// around the last topic.
+    fn step_match(&mut self, forward: bool) {
+        if self.matches.is_empty() {
+            self.status_message = Some(format!("No matches for \"{}\"", self.query));
+            return;
+        }
+        let n = self.matches.len();
+        let next = match self.current_match {
+            Some(i) if forward => (i + 1) % n,
+            Some(i) => (i + n - 1) % n,
+            // First match at or after the top of the screen
+            None => {
+                let here = (self.topic, self.scroll);
+                let after = self.matches.iter().position(|m| *m >= here);
+                if forward {
+                    after.unwrap_or(0)
+                } else {
+                    after.map_or(n - 1, |i| (i + n - 1) % n)
+                }
+            }
+        };
+        self.current_match = Some(next);
+        let (topic, line) = self.matches[next];
+        self.topic = topic;
+        // Keep a little context above the match
+        if line < self.scroll || line >= self.scroll + self.last_visible_rows {
+            self.scroll = line.saturating_sub(2);
+        }
+        self.status_message = Some(format!("Match {} of {n}", next + 1));
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> DocsAction {
+        self.status_message = None;
+        if let Some(input) = &mut self.input {
+            match code {
+                KeyCode::Esc => {
+                    self.input = None;
+                    self.query.clear();
+                    self.refresh_matches();
+                }
+                KeyCode::Enter => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                    self.query = input.clone();
+                    self.refresh_matches();
+                }
+                KeyCode::Char(c) => {
+                    input.push(c);
+                    self.query = input.clone();
+                    self.refresh_matches();
+                    // Follow the search as it's typed
+                    if !self.matches.is_empty() {
+                        self.step_match(true);
+                    }
+                }
+                _ => {}
+            }
+            return DocsAction::Continue;
+        }
+
+        let page = self.last_visible_rows.saturating_sub(1).max(1);
+        match code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = (self.scroll + 1).min(self.max_scroll()),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(page),
+            KeyCode::PageDown | KeyCode::Char(' ') => {
+                self.scroll = (self.scroll + page).min(self.max_scroll())
+            }
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = self.max_scroll(),
+            KeyCode::Left => self.select_topic(self.topic.saturating_sub(1)),
+            KeyCode::Right | KeyCode::Tab => self.select_topic(self.topic + 1),
+            KeyCode::Char(c @ '1'..='9') => self.select_topic(c as usize - '1' as usize),
+            KeyCode::Char('/') => self.input = Some(String::new()),
+            KeyCode::Char('n') if !self.query.is_empty() => self.step_match(true),
+            KeyCode::Char('N') if !self.query.is_empty() => self.step_match(false),
+            KeyCode::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.refresh_matches();
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return DocsAction::Close,
+            _ => {}
+        }
+        DocsAction::Continue
+    }
+}
+
+impl screen::Screen for DocsViewer {
+    fn draw(&mut self, frame: &mut Frame) {
+        DocsViewer::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, _ctx: &mut ScreenContext) -> Result<Nav> {
+        Ok(match DocsViewer::handle_key(self, code) {
+            DocsAction::Close => Nav::Pop,
+            DocsAction::Continue => Nav::Stay,
+        })
+    }
+}
@@ -2,7 +2,7 @@
 use std::path::PathBuf;
 
 #[cfg(feature = "pdf")]
-use crate::cli::parse_month_opt;
+use crate::cli::report::ReportParams;
 #[cfg(feature = "pdf")]
 use crate::cli::ReportCommands;
 #[cfg(feature = "pdf")]
@@ -12,16 +12,19 @@ use crate::error::Result;
 #[cfg(feature = "pdf")]
 use crate::settings::get_data_dir;
 
+/// The fiscal year label for reports that only take `--year`.
 #[cfg(feature = "pdf")]
-fn date_range_label(month: &Option<String>, year: &Option<i32>) -> String {
-    if let Some(m) = month {
-        return m.clone();
-    }
-    if let Some(y) = year {
-        return format!("FY {y}");
+fn year_label(conn: &rusqlite::Connection, year: Option<i32>) -> String {
+    ReportParams::period(year, None).period_label(conn)
+}
+
+/// Period label with any excluded tags after it: `FY 2025, excluding #one-off`.
+#[cfg(feature = "pdf")]
+fn with_exclusions(range: String, exclude_tags: &[String]) -> String {
+    match crate::reports::excluded_tags_label(exclude_tags) {
+        Some(label) => format!("{range}, excluding {label}"),
+        None => range,
     }
-    let y = chrono::Datelike::year(&chrono::Local::now());
-    format!("FY {y}")
 }
 
 #[cfg(feature = "pdf")]
@@ -46,67 +49,82 @@ fn write_pdf(bytes: &[u8], path: &PathBuf) -> Result<String> {
 
 /// Dispatch a report command as PDF export. Returns the written path(s).
 #[cfg(feature = "pdf")]
-pub fn dispatch_pdf(cmd: ReportCommands, output: Option<String>) -> Result<String> {
+pub fn dispatch_pdf(
+    cmd: ReportCommands,
+    params: &ReportParams,
+    output: Option<String>,
+) -> Result<String> {
     match cmd {
-        ReportCommands::Pnl {
-            month,
-            year,
-            from_date,
-            to_date,
-            ..
-        } => pnl(month, year, from_date, to_date, output),
-        ReportCommands::Expenses { month, year, .. } => expenses(month, year, output),
-        ReportCommands::Tax { year, .. } => tax(year, output),
-        ReportCommands::Cashflow { month, year, .. } => cashflow(month, year, output),
-        ReportCommands::Register {
-            month,
-            year,
-            from_date,
-            to_date,
-            account,
-            ..
-        } => register(month, year, from_date, to_date, account, output),
+        ReportCommands::Pnl { .. } => pnl(params, output),
+        ReportCommands::Expenses { .. } => expenses(params, output),
+        ReportCommands::Tax { .. } => tax(params.year, output),
+        ReportCommands::Cashflow { .. } => cashflow(params, output),
+        ReportCommands::Register { .. } => register(params, output),
         ReportCommands::Flagged { .. } => flagged(output),
-        ReportCommands::Balance { .. } => balance(output),
-        ReportCommands::K1 { year, .. } => k1(year, output),
-        ReportCommands::All {
-            year, output_dir, ..
-        } => all(year, output_dir),
+        ReportCommands::Reimbursables { client, all, .. } => {
+            reimbursables(client.as_deref(), all, output)
+        }
+        ReportCommands::Balance {
+            include_archived, ..
+        } => balance(include_archived, output),
+        ReportCommands::BalanceHistory { .. } => balance_history(params, output),
+        ReportCommands::K1 { .. } => k1(params.year, output),
+        ReportCommands::Deductions { .. } => deductions(params.year, output),
+        ReportCommands::Compensation { .. } => compensation(params.year, output),
+        ReportCommands::Tags { .. } => tags(params, output),
+        ReportCommands::Clients { .. } => clients(params.year, output),
+        ReportCommands::Books { .. } => books(output),
+        ReportCommands::Quality { .. } => quality(output),
+        ReportCommands::All { output_dir, .. } => all(params.year, output_dir),
     }
 }
 
 #[cfg(feature = "pdf")]
-pub fn pnl(
-    month: Option<String>,
-    year: Option<i32>,
-    from_date: Option<String>,
-    to_date: Option<String>,
-    output: Option<String>,
-) -> Result<String> {
+pub fn pnl(params: &ReportParams, output: Option<String>) -> Result<String> {
     let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
-    let (my, mm) = parse_month_opt(&month);
-    let y = year.or(my);
-    let report = crate::reports::get_pnl(&conn, y, mm, from_date.as_deref(), to_date.as_deref())?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let range = date_range_label(&month, &year.or(my));
-    let bytes = crate::pdf::render_pnl(&report, &company, &range)?;
     let path = output
         .map(PathBuf::from)
         .unwrap_or_else(|| default_path("pnl"));
+    if params.compare {
+        let report = crate::reports::get_pnl_comparison(
+            &conn,
+            params.year,
+            params.month,
+            params.from_date.as_deref(),
+            params.to_date.as_deref(),
+            params.depth,
+            &params.exclude_tags,
+        )?;
+        let bytes = crate::pdf::render_pnl_comparison(&report, &company)?;
+        return write_pdf(&bytes, &path);
+    }
+    let report = crate::reports::get_pnl(
+        &conn,
+        params.year,
+        params.month,
+        params.from_date.as_deref(),
+        params.to_date.as_deref(),
+        params.depth,
+        &params.exclude_tags,
+    )?;
+    let range = with_exclusions(params.period_label(&conn), &params.exclude_tags);
+    let bytes = crate::pdf::render_pnl(&report, &company, &range)?;
     write_pdf(&bytes, &path)
 }
 
 #[cfg(feature = "pdf")]
-pub fn expenses(
-    month: Option<String>,
-    year: Option<i32>,
-    output: Option<String>,
-) -> Result<String> {
+pub fn expenses(params: &ReportParams, output: Option<String>) -> Result<String> {
     let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
-    let (my, mm) = parse_month_opt(&month);
-    let report = crate::reports::get_expense_breakdown(&conn, year.or(my), mm)?;
+    let report = crate::reports::get_expense_breakdown(
+        &conn,
+        params.year,
+        params.month,
+        params.depth,
+        &params.exclude_tags,
+    )?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let range = date_range_label(&month, &year.or(my));
+    let range = with_exclusions(params.period_label(&conn), &params.exclude_tags);
     let bytes = crate::pdf::render_expenses(&report, &company, &range)?;
     let path = output
         .map(PathBuf::from)
@@ -119,7 +137,7 @@ pub fn tax(year: Option<i32>, output: Option<String>) -> Result<String> {
     let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
     let report = crate::reports::get_tax_summary(&conn, year)?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let range = date_range_label(&None, &year);
+    let range = year_label(&conn, year);
     let bytes = crate::pdf::render_tax(&report, &company, &range)?;
     let path = output
         .map(PathBuf::from)
@@ -128,16 +146,17 @@ pub fn tax(year: Option<i32>, output: Option<String>) -> Result<String> {
 }
 
 #[cfg(feature = "pdf")]
-pub fn cashflow(
-    month: Option<String>,
-    year: Option<i32>,
-    output: Option<String>,
-) -> Result<String> {
+pub fn cashflow(params: &ReportParams, output: Option<String>) -> Result<String> {
     let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
-    let (my, mm) = parse_month_opt(&month);
-    let report = crate::reports::get_cashflow(&conn, year.or(my), mm)?;
+    let report = crate::reports::get_cashflow_by(
+        &conn,
+        params.year,
+        params.month,
+        &params.exclude_tags,
+        params.granularity,
+    )?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let range = date_range_label(&month, &year.or(my));
+    let range = with_exclusions(params.period_label(&conn), &params.exclude_tags);
     let bytes = crate::pdf::render_cashflow(&report, &company, &range)?;
     let path = output
         .map(PathBuf::from)
@@ -146,27 +165,19 @@ pub fn cashflow(
 }
 
 #[cfg(feature = "pdf")]
-pub fn register(
-    month: Option<String>,
-    year: Option<i32>,
-    from_date: Option<String>,
-    to_date: Option<String>,
-    account: Option<String>,
-    output: Option<String>,
-) -> Result<String> {
+pub fn register(params: &ReportParams, output: Option<String>) -> Result<String> {
     let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
-    let (my, mm) = parse_month_opt(&month);
-    let y = year.or(my);
     let report = crate::reports::get_register(
         &conn,
-        y,
-        mm,
-        from_date.as_deref(),
-        to_date.as_deref(),
-        account.as_deref(),
+        params.year,
+        params.month,
+        params.from_date.as_deref(),
+        params.to_date.as_deref(),
+        params.account.as_deref(),
+        params.tag.as_deref(),
     )?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let range = date_range_label(&month, &year.or(my));
+    let range = params.period_label(&conn);
     let bytes = crate::pdf::render_register(&report, &company, &range)?;
     let path = output
         .map(PathBuf::from)
@@ -187,9 +198,45 @@ pub fn flagged(output: Option<String>) -> Result<String> {
 }
 
 #[cfg(feature = "pdf")]
-pub fn balance(output: Option<String>) -> Result<String> {
+pub fn reimbursables(client: Option<&str>, all: bool, output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let report = crate::reimbursables::get_report(&conn, client, all)?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let bytes = crate::pdf::render_reimbursables(&report, &company)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("reimbursables"));
+    write_pdf(&bytes, &path)
+}
+
+#[cfg(feature = "pdf")]
+pub fn digest(year: i32, month: u32, output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let digest = crate::digest::build(&conn, year, month)?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let bytes = crate::pdf::render_digest(&digest, &company)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("digest"));
+    write_pdf(&bytes, &path)
+}
+
+#[cfg(feature = "pdf")]
+pub fn setup_docs(output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let docs = crate::setup_docs::build(&conn, chrono::Local::now().date_naive())?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let bytes = crate::pdf::render_setup_docs(&docs, &company)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("setup-docs"));
+    write_pdf(&bytes, &path)
+}
+
+#[cfg(feature = "pdf")]
+pub fn balance(include_archived: bool, output: Option<String>) -> Result<String> {
     let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
-    let report = crate::reports::get_balance(&conn)?;
+    let report = crate::reports::get_balance(&conn, include_archived)?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
     let bytes = crate::pdf::render_balance(&report, &company)?;
     let path = output
@@ -198,12 +245,42 @@ pub fn balance(output: Option<String>) -> Result<String> {
     write_pdf(&bytes, &path)
 }
 
+#[cfg(feature = "pdf")]
+pub fn balance_history(params: &ReportParams, output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let report = crate::reports::get_balance_history(
+        &conn,
+        params.account.as_deref().unwrap_or_default(),
+        params.year,
+        params.month,
+        params.from_date.as_deref(),
+        params.to_date.as_deref(),
+        params.interval,
+        params.opening_balance,
+    )?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let mut range = format!(
+        "{} to {}, by {}",
+        crate::fmt::date(&report.from),
+        crate::fmt::date(&report.to),
+        report.interval.key()
+    );
+    if report.with_opening {
+        range.push_str(", with opening balance");
+    }
+    let bytes = crate::pdf::render_balance_history(&report, &company, &range)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("balance-history"));
+    write_pdf(&bytes, &path)
+}
+
 #[cfg(feature = "pdf")]
 pub fn k1(year: Option<i32>, output: Option<String>) -> Result<String> {
     let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
     let report = crate::reports::get_k1_prep(&conn, year)?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let range = date_range_label(&None, &year);
+    let range = year_label(&conn, year);
     let bytes = crate::pdf::render_k1(&report, &company, &range)?;
     let path = output
         .map(PathBuf::from)
@@ -211,12 +288,90 @@ pub fn k1(year: Option<i32>, output: Option<String>) -> Result<String> {
     write_pdf(&bytes, &path)
 }
 
+#[cfg(feature = "pdf")]
+pub fn deductions(year: Option<i32>, output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let report = crate::reports::get_deductions(&conn, year)?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let range = year_label(&conn, year);
+    let bytes = crate::pdf::render_deductions(&report, &company, &range)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("deductions"));
+    write_pdf(&bytes, &path)
+}
+
+#[cfg(feature = "pdf")]
+pub fn compensation(year: Option<i32>, output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let report = crate::reports::get_compensation(&conn, year)?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let year = year.or_else(|| Some(crate::fiscal::FiscalYear::load(&conn).current()));
+    let range = year_label(&conn, year);
+    let bytes = crate::pdf::render_compensation(&report, &company, &range)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("compensation"));
+    write_pdf(&bytes, &path)
+}
+
+#[cfg(feature = "pdf")]
+pub fn tags(params: &ReportParams, output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let report = crate::reports::get_tags_report(&conn, params.year, params.month)?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let range = params.period_label(&conn);
+    let bytes = crate::pdf::render_tags(&report, &company, &range)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("tags"));
+    write_pdf(&bytes, &path)
+}
+
+#[cfg(feature = "pdf")]
+pub fn clients(year: Option<i32>, output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let report = crate::reports::get_clients(&conn, year)?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let year = year.or_else(|| Some(crate::fiscal::FiscalYear::load(&conn).current()));
+    let range = year_label(&conn, year);
+    let bytes = crate::pdf::render_clients(&report, &company, &range)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("clients"));
+    write_pdf(&bytes, &path)
+}
+
+#[cfg(feature = "pdf")]
+pub fn books(output: Option<String>) -> Result<String> {
+    let settings = crate::settings::load_settings();
+    let overview = crate::overview::get_overview(&get_data_dir(), &settings.overview_books);
+    let bytes = crate::pdf::render_books(&overview)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("books"));
+    write_pdf(&bytes, &path)
+}
+
+#[cfg(feature = "pdf")]
+pub fn quality(output: Option<String>) -> Result<String> {
+    let conn = crate::db::get_connection(&get_data_dir().join("nigel.db"))?;
+    let report =
+        crate::data_quality::get_report(&conn, crate::data_quality::DateBounds::current())?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let bytes = crate::pdf::render_quality(&report, &company)?;
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_path("quality"));
+    write_pdf(&bytes, &path)
+}
+
 #[cfg(feature = "pdf")]
 pub fn all(year: Option<i32>, output_dir: Option<String>) -> Result<String> {
     let data_dir = get_data_dir();
     let conn = crate::db::get_connection(&data_dir.join("nigel.db"))?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let range = date_range_label(&None, &year);
+    let range = year_label(&conn, year);
     let date = chrono::Local::now().format("%Y-%m-%d").to_string();
 
     let is_default_dir = output_dir.is_none();
@@ -230,13 +385,13 @@ pub fn all(year: Option<i32>, output_dir: Option<String>) -> Result<String> {
 
     let path = |name: &str| dir.join(format!("{name}-{date}.pdf"));
 
-    let report = crate::reports::get_pnl(&conn, year, None, None, None)?;
+    let report = crate::reports::get_pnl(&conn, year, None, None, None, None, &[])?;
     write_pdf(
         &crate::pdf::render_pnl(&report, &company, &range)?,
         &path("pnl"),
     )?;
 
-    let report = crate::reports::get_expense_breakdown(&conn, year, None)?;
+    let report = crate::reports::get_expense_breakdown(&conn, year, None, None, &[])?;
     write_pdf(
         &crate::pdf::render_expenses(&report, &company, &range)?,
         &path("expenses"),
@@ -248,13 +403,13 @@ pub fn all(year: Option<i32>, output_dir: Option<String>) -> Result<String> {
         &path("tax"),
     )?;
 
-    let report = crate::reports::get_cashflow(&conn, year, None)?;
+    let report = crate::reports::get_cashflow(&conn, year, None, &[])?;
     write_pdf(
         &crate::pdf::render_cashflow(&report, &company, &range)?,
         &path("cashflow"),
     )?;
 
-    let register = crate::reports::get_register(&conn, year, None, None, None, None)?;
+    let register = crate::reports::get_register(&conn, year, None, None, None, None, None)?;
     write_pdf(
         &crate::pdf::render_register(&register, &company, &range)?,
         &path("register"),
@@ -266,7 +421,13 @@ pub fn all(year: Option<i32>, output_dir: Option<String>) -> Result<String> {
         &path("flagged"),
     )?;
 
-    let report = crate::reports::get_balance(&conn)?;
+    let report = crate::reimbursables::get_report(&conn, None, false)?;
+    write_pdf(
+        &crate::pdf::render_reimbursables(&report, &company)?,
+        &path("reimbursables"),
+    )?;
+
+    let report = crate::reports::get_balance(&conn, false)?;
     write_pdf(
         &crate::pdf::render_balance(&report, &company)?,
         &path("balance"),
@@ -278,5 +439,29 @@ pub fn all(year: Option<i32>, output_dir: Option<String>) -> Result<String> {
         &path("k1-prep"),
     )?;
 
+    let report = crate::reports::get_deductions(&conn, year)?;
+    write_pdf(
+        &crate::pdf::render_deductions(&report, &company, &range)?,
+        &path("deductions"),
+    )?;
+
+    let report = crate::reports::get_compensation(&conn, year)?;
+    write_pdf(
+        &crate::pdf::render_compensation(&report, &company, &range)?,
+        &path("compensation"),
+    )?;
+
+    let report = crate::reports::get_tags_report(&conn, year, None)?;
+    write_pdf(
+        &crate::pdf::render_tags(&report, &company, &range)?,
+        &path("tags"),
+    )?;
+
+    let report = crate::reports::get_clients(&conn, year)?;
+    write_pdf(
+        &crate::pdf::render_clients(&report, &company, &range)?,
+        &path("clients"),
+    )?;
+
     Ok(format!("All reports exported to {}", dir.display()))
 }
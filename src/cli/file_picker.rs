@@ -0,0 +1,355 @@
+use std::path::{Path, PathBuf};
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::tui::{truncate, FOOTER_STYLE, SELECTED_STYLE};
+
+/// Rows moved by PageUp/PageDown.
+const PAGE_STEP: usize = 10;
+
+pub enum PickerAction {
+    Continue,
+    Cancel,
+    Picked(PathBuf),
+}
+
+struct Entry {
+    name: String,
+    is_dir: bool,
+}
+
+/// A directory listing to choose a file from, for screens that would
+/// otherwise need a typed path. Folders come first; files are narrowed to
+/// the given extensions.
+pub struct FilePicker {
+    title: String,
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    selection: usize,
+    /// Lowercase extensions without the dot; empty shows every file.
+    extensions: Vec<&'static str>,
+    show_hidden: bool,
+    status_message: Option<String>,
+}
+
+impl FilePicker {
+    /// Open at `start`, or the nearest folder above it that exists. A file
+    /// path opens its folder with the file selected.
+    pub fn new(title: &str, start: &Path, extensions: &[&'static str]) -> Self {
+        let mut dir = start.to_path_buf();
+        let mut select = None;
+        while !dir.is_dir() {
+            if dir.is_file() {
+                select = dir.file_name().map(|n| n.to_string_lossy().into_owned());
+            }
+            if !dir.pop() {
+                dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+                break;
+            }
+        }
+        let mut picker = Self {
+            title: title.to_string(),
+            dir,
+            entries: Vec::new(),
+            selection: 0,
+            extensions: extensions
+                .iter()
+                .map(|e| e.trim_start_matches('.'))
+                .collect(),
+            show_hidden: false,
+            status_message: None,
+        };
+        picker.read_dir(select.as_deref());
+        picker
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn shows_file(&self, name: &str) -> bool {
+        self.extensions.is_empty()
+            || Path::new(name)
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| self.extensions.iter().any(|x| x.eq_ignore_ascii_case(e)))
+    }
+
+    /// List the current folder, selecting `select` if it's there.
+    fn read_dir(&mut self, select: Option<&str>) {
+        self.entries.clear();
+        self.status_message = None;
+        if self.dir.parent().is_some() {
+            self.entries.push(Entry {
+                name: "..".to_string(),
+                is_dir: true,
+            });
+        }
+        let mut listed = Vec::new();
+        match std::fs::read_dir(&self.dir) {
+            Ok(read) => {
+                for item in read.flatten() {
+                    let name = item.file_name().to_string_lossy().into_owned();
+                    if name.starts_with('.') && !self.show_hidden {
+                        continue;
+                    }
+                    // Follow symlinks so linked folders open like real ones
+                    let is_dir = item.path().is_dir();
+                    if is_dir || self.shows_file(&name) {
+                        listed.push(Entry { name, is_dir });
+                    }
+                }
+            }
+            Err(e) => self.status_message = Some(format!("Could not read folder: {e}")),
+        }
+        listed.sort_by_cached_key(|e| (!e.is_dir, e.name.to_lowercase()));
+        self.entries.extend(listed);
+        self.selection = select
+            .and_then(|name| self.entries.iter().position(|e| e.name == name))
+            .unwrap_or(0);
+    }
+
+    /// Go up a folder, keeping the one just left selected.
+    fn open_parent(&mut self) {
+        let Some(left) = self
+            .dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+        else {
+            return;
+        };
+        if self.dir.pop() {
+            self.read_dir(Some(&left));
+        }
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> PickerAction {
+        let last = self.entries.len().saturating_sub(1);
+        match code {
+            KeyCode::Esc => return PickerAction::Cancel,
+            KeyCode::Up => self.selection = self.selection.saturating_sub(1),
+            KeyCode::Down => self.selection = (self.selection + 1).min(last),
+            KeyCode::PageUp => self.selection = self.selection.saturating_sub(PAGE_STEP),
+            KeyCode::PageDown => self.selection = (self.selection + PAGE_STEP).min(last),
+            KeyCode::Home => self.selection = 0,
+            KeyCode::End => self.selection = last,
+            KeyCode::Backspace | KeyCode::Left => self.open_parent(),
+            KeyCode::Char('.') => {
+                self.show_hidden = !self.show_hidden;
+                let current = self.entries.get(self.selection).map(|e| e.name.clone());
+                self.read_dir(current.as_deref());
+            }
+            KeyCode::Char('~') => {
+                if let Some(home) = dirs::home_dir() {
+                    self.dir = home;
+                    self.read_dir(None);
+                }
+            }
+            KeyCode::Enter | KeyCode::Right => {
+                let Some(entry) = self.entries.get(self.selection) else {
+                    return PickerAction::Continue;
+                };
+                if entry.name == ".." {
+                    self.open_parent();
+                } else if entry.is_dir {
+                    self.dir.push(&entry.name);
+                    self.read_dir(None);
+                } else if code == KeyCode::Enter {
+                    return PickerAction::Picked(self.dir.join(&entry.name));
+                }
+            }
+            _ => {}
+        }
+        PickerAction::Continue
+    }
+
+    /// The folder as `~ › Documents › statements`, dropping leading parts
+    /// to fit `width`.
+    fn breadcrumbs(&self, width: usize) -> String {
+        let home = dirs::home_dir();
+        let (root, rest) = match home.as_deref().and_then(|h| self.dir.strip_prefix(h).ok()) {
+            Some(rest) => ("~".to_string(), rest),
+            None => (String::new(), self.dir.as_path()),
+        };
+        let mut parts: Vec<String> = rest
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        if !root.is_empty() {
+            parts.insert(0, root);
+        }
+        let mut crumbs = parts.join(" \u{203a} ");
+        while crumbs.chars().count() > width && parts.len() > 1 {
+            parts.remove(0);
+            crumbs = format!("\u{2026} \u{203a} {}", parts.join(" \u{203a} "));
+        }
+        crumbs
+    }
+
+    /// Fill `content_area` with the breadcrumbs and listing, and
+    /// `hints_area` with the keys.
+    pub fn draw(&self, frame: &mut Frame, content_area: Rect, hints_area: Rect) {
+        let [title_area, crumb_area, _, list_area, status_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(content_area);
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!(" {}", self.title),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+            ]),
+            title_area,
+        );
+        let width = crumb_area.width.saturating_sub(3) as usize;
+        frame.render_widget(
+            Paragraph::new(format!("   {}", self.breadcrumbs(width)))
+                .style(Style::default().fg(Color::Cyan)),
+            crumb_area,
+        );
+
+        let rows = list_area.height as usize;
+        let start = if rows == 0 {
+            0
+        } else {
+            self.selection.saturating_sub(rows - 1)
+        };
+        let name_width = list_area.width.saturating_sub(6) as usize;
+        let mut lines = Vec::new();
+        if self.entries.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   Nothing here to choose",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for (i, entry) in self.entries.iter().enumerate().skip(start).take(rows) {
+            let name = if entry.is_dir && entry.name != ".." {
+                format!("{}/", entry.name)
+            } else {
+                entry.name.clone()
+            };
+            let style = if i == self.selection {
+                SELECTED_STYLE
+            } else if entry.is_dir {
+                Style::default().fg(Color::Blue)
+            } else {
+                Style::default()
+            };
+            let marker = if i == self.selection { ">" } else { " " };
+            lines.push(Line::from(Span::styled(
+                format!(" {marker} {}", truncate(&name, name_width)),
+                style,
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines), list_area);
+
+        if let Some(msg) = &self.status_message {
+            frame.render_widget(
+                Paragraph::new(format!("   {msg}")).style(Style::default().fg(Color::Red)),
+                status_area,
+            );
+        }
+
+        let hidden = if self.show_hidden {
+            ".=hide hidden"
+        } else {
+            ".=show hidden"
+        };
+        frame.render_widget(
+            Paragraph::new(format!(
+                " Enter=open/choose  Backspace=up  ~=home  {hidden}  Esc=back"
+            ))
+            .style(FOOTER_STYLE),
+            hints_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(picker: &FilePicker) -> Vec<&str> {
+        picker.entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn lists_folders_then_matching_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("statements")).unwrap();
+        std::fs::create_dir(dir.path().join(".cache")).unwrap();
+        for file in ["b.CSV", "a.xlsx", "notes.txt", ".hidden.csv"] {
+            std::fs::write(dir.path().join(file), "").unwrap();
+        }
+        let mut picker = FilePicker::new("Pick", dir.path(), &["csv", "xlsx"]);
+        assert_eq!(names(&picker), ["..", "statements", "a.xlsx", "b.CSV"]);
+
+        picker.handle_key(KeyCode::Char('.'));
+        assert_eq!(
+            names(&picker),
+            [
+                "..",
+                ".cache",
+                "statements",
+                ".hidden.csv",
+                "a.xlsx",
+                "b.CSV"
+            ]
+        );
+    }
+
+    #[test]
+    fn walks_folders_and_picks_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("statements");
+        std::fs::create_dir(&sub).unwrap();
+        std::fs::write(sub.join("jan.csv"), "").unwrap();
+
+        // A missing file starts in the nearest folder that exists
+        let mut picker = FilePicker::new("Pick", &sub.join("gone").join("x.csv"), &["csv"]);
+        assert_eq!(picker.dir(), sub);
+        picker.handle_key(KeyCode::Backspace);
+        assert_eq!(picker.dir(), dir.path());
+        assert_eq!(names(&picker)[picker.selection], "statements");
+
+        picker.handle_key(KeyCode::Enter);
+        assert_eq!(picker.dir(), sub);
+        picker.handle_key(KeyCode::Down);
+        match picker.handle_key(KeyCode::Enter) {
+            PickerAction::Picked(path) => assert_eq!(path, sub.join("jan.csv")),
+            _ => panic!("expected a pick"),
+        }
+        assert!(matches!(
+            picker.handle_key(KeyCode::Esc),
+            PickerAction::Cancel
+        ));
+    }
+
+    #[test]
+    fn breadcrumbs_drop_leading_folders_to_fit() {
+        let dir = tempfile::tempdir().unwrap();
+        let deep = dir.path().join("one").join("two").join("three");
+        std::fs::create_dir_all(&deep).unwrap();
+        let picker = FilePicker::new("Pick", &deep, &[]);
+        let full = picker.breadcrumbs(500);
+        assert!(full.ends_with("one \u{203a} two \u{203a} three"), "{full}");
+        assert_eq!(
+            picker.breadcrumbs(20),
+            "\u{2026} \u{203a} two \u{203a} three"
+        );
+    }
+}
@@ -1,11 +1,18 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use crate::categorizer::categorize_transactions;
+use rusqlite::Connection;
+
+use crate::categorizer::{categorize_transactions, preview_matches};
 use crate::cli::backup;
 use crate::db::get_connection;
 use crate::error::Result;
-use crate::importer::{import_file, save_csv_profile, GenericCsvConfig};
-use crate::settings::get_data_dir;
+use crate::importer::{
+    find_prior_import, import_file, save_csv_profile, GenericCsvConfig, PreviewRow,
+};
+use crate::settings::{get_data_dir, load_settings};
+use crate::statements;
+use crate::tui::truncate;
 
 pub struct ImportOpts<'a> {
     pub format: Option<&'a str>,
@@ -15,6 +22,9 @@ pub struct ImportOpts<'a> {
     pub amount_col: Option<usize>,
     pub date_format: Option<&'a str>,
     pub save_profile: Option<&'a str>,
+    pub keep_file: bool,
+    pub force_reimport: bool,
+    pub detailed: bool,
 }
 
 pub fn run(file: &str, account: &str, opts: ImportOpts<'_>) -> Result<()> {
@@ -23,7 +33,8 @@ pub fn run(file: &str, account: &str, opts: ImportOpts<'_>) -> Result<()> {
     let conn = get_connection(&data_dir.join("nigel.db"))?;
 
     // Build inline config when column flags are provided without --format
-    let inline_config = if opts.format.is_none() && opts.date_col.is_some() {
+    let mut format = opts.format;
+    let mut inline_config = if opts.format.is_none() && opts.date_col.is_some() {
         Some(build_generic_config(
             opts.date_col,
             opts.desc_col,
@@ -52,13 +63,24 @@ pub fn run(file: &str, account: &str, opts: ImportOpts<'_>) -> Result<()> {
             )?;
             save_csv_profile(&conn, profile_name, &config)?;
             println!("Saved profile '{profile_name}'");
+            // Import through the profile so the account remembers it
+            if inline_config.take().is_some() {
+                format = Some(profile_name);
+            }
+        }
+    }
+
+    // Stop before snapshotting when this exact file is already in the books
+    if !opts.force_reimport {
+        if let Some(prior) = find_prior_import(&conn, &file_path)? {
+            println!("{}", prior.describe());
+            println!("Run again with --force-reimport to import it as a new batch.");
+            return Ok(());
         }
     }
 
     if !opts.dry_run {
-        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
-        let snap_path = data_dir.join(format!("snapshots/pre-import-{stamp}.db"));
-        backup::snapshot(&conn, &snap_path)?;
+        let snap_path = backup::pre_operation_snapshot(&conn, &data_dir, "import")?;
         println!("Pre-import snapshot saved to {}", snap_path.display());
     }
 
@@ -66,15 +88,20 @@ pub fn run(file: &str, account: &str, opts: ImportOpts<'_>) -> Result<()> {
         &conn,
         &file_path,
         account,
-        opts.format,
+        format,
         opts.dry_run,
         inline_config.as_ref(),
+        opts.force_reimport,
+        opts.detailed || load_settings().gusto_detailed,
     )?;
 
-    if result.duplicate_file {
-        println!("This file has already been imported (duplicate checksum).");
+    if let Some(prior) = &result.duplicate_of {
+        println!("{}", prior.describe());
         return Ok(());
     }
+    if let Some(key) = &result.remembered_format {
+        println!("Using {account}'s remembered format '{key}' (--format auto to detect)");
+    }
 
     if opts.dry_run {
         println!("Dry run \u{2014} no changes made");
@@ -89,18 +116,7 @@ pub fn run(file: &str, account: &str, opts: ImportOpts<'_>) -> Result<()> {
                 result.imported, result.skipped
             );
         }
-        if !result.sample.is_empty() {
-            println!("\nSample transactions:");
-            for row in &result.sample {
-                let sign = if row.amount >= 0.0 { "+" } else { "" };
-                println!(
-                    "  {}  {:40} {:>10}",
-                    row.date,
-                    row.description,
-                    format!("{sign}{:.2}", row.amount)
-                );
-            }
-        }
+        print!("{}", dry_run_diff(&conn, &result.preview)?);
         return Ok(());
     }
 
@@ -116,15 +132,105 @@ pub fn run(file: &str, account: &str, opts: ImportOpts<'_>) -> Result<()> {
         );
     }
 
+    if let Some(import_id) = result.import_id {
+        if opts.keep_file || load_settings().keep_statements {
+            let stored = statements::retain(&conn, &data_dir, import_id, &file_path)?;
+            println!("Stored a copy at {}", stored.display());
+        }
+    }
+
     let cat_result = categorize_transactions(&conn)?;
     println!(
         "{} categorized, {} still flagged",
         cat_result.categorized, cat_result.still_flagged
     );
+    if cat_result.card_payments > 0 {
+        println!(
+            "{} credit card payments matched across accounts",
+            cat_result.card_payments
+        );
+    }
+    if cat_result.sign_warnings > 0 {
+        println!(
+            "{} flagged for a sign that doesn't match the category (see `nigel report flagged`)",
+            cat_result.sign_warnings
+        );
+    }
+    if cat_result.anomalies > 0 {
+        println!(
+            "{} flagged as unusual for their vendor (see `nigel report flagged`)",
+            cat_result.anomalies
+        );
+    }
+    if cat_result.out_of_range > 0 {
+        println!(
+            "{} flagged for a date out of range (see `nigel report quality`)",
+            cat_result.out_of_range
+        );
+    }
 
     Ok(())
 }
 
+/// A diff of what the import would do: `+` rows would be inserted (with the
+/// category their rule gives, or flagged for review), `=` rows are already in
+/// the account and would be skipped; then the new rows counted per category.
+fn dry_run_diff(conn: &Connection, preview: &[PreviewRow]) -> Result<String> {
+    let new: Vec<&PreviewRow> = preview.iter().filter(|p| !p.duplicate).collect();
+    let descriptions: Vec<&str> = new.iter().map(|p| p.row.description.as_str()).collect();
+    let matches = preview_matches(conn, &descriptions)?;
+
+    let mut out = String::new();
+    let mut matched = matches.iter().map(Option::as_ref);
+    for p in preview {
+        let (marker, note) = if p.duplicate {
+            ('=', "duplicate, skipped".to_string())
+        } else {
+            match matched.next().flatten() {
+                Some(m) => ('+', m.category.clone()),
+                None => ('+', "no matching rule, flagged".to_string()),
+            }
+        };
+        out.push_str(&format!(
+            "{marker} {}  {:40} {:>10}  {note}\n",
+            p.row.date,
+            truncate(&p.row.description, 40),
+            format!("{:+.2}", p.row.amount)
+        ));
+    }
+
+    // Category -> (rows, rules that put them there)
+    let mut by_category: BTreeMap<&str, (usize, Vec<String>)> = BTreeMap::new();
+    let mut unmatched = 0;
+    for m in &matches {
+        match m {
+            Some(m) => {
+                let entry = by_category.entry(m.category.as_str()).or_default();
+                entry.0 += 1;
+                let rule = format!("#{} \"{}\"", m.rule_id, m.pattern);
+                if !entry.1.contains(&rule) {
+                    entry.1.push(rule);
+                }
+            }
+            None => unmatched += 1,
+        }
+    }
+    if !new.is_empty() {
+        out.push_str("\nRules applied:\n");
+        for (category, (count, rules)) in &by_category {
+            out.push_str(&format!(
+                "  {:30} {count:>4}  {}\n",
+                truncate(category, 30),
+                rules.join(", ")
+            ));
+        }
+        if unmatched > 0 {
+            out.push_str(&format!("  {:30} {unmatched:>4}\n", "(flagged for review)"));
+        }
+    }
+    Ok(out)
+}
+
 fn build_generic_config(
     date_col: Option<usize>,
     desc_col: Option<usize>,
@@ -142,6 +248,8 @@ fn build_generic_config(
         amount_col: amount_col.ok_or_else(|| {
             crate::error::NigelError::Other(format!("--amount-col is required with {context}"))
         })?,
-        date_format: date_format.unwrap_or("%m/%d/%Y").to_string(),
+        date_format: date_format
+            .map(str::to_string)
+            .unwrap_or_else(|| crate::importer::default_csv_date_format().to_string()),
     })
 }
@@ -0,0 +1,228 @@
+use std::path::PathBuf;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use rusqlite::Connection;
+
+use crate::cli::screen::{self, Nav, ScreenContext};
+use crate::error::Result;
+use crate::settings::get_data_dir;
+use crate::statements::{self, ImportRecord};
+use crate::tui::{truncate, FOOTER_STYLE, HEADER_STYLE};
+
+pub enum ImportHistoryAction {
+    Continue,
+    Close,
+}
+
+/// Read-only list of past imports; retained statement files can be opened
+/// with the system viewer.
+pub struct ImportHistory {
+    imports: Vec<ImportRecord>,
+    data_dir: PathBuf,
+    selection: usize,
+    scroll_offset: usize,
+    last_visible_rows: usize,
+    status_message: Option<String>,
+    status_ttl: u8,
+    greeting: String,
+}
+
+impl ImportHistory {
+    pub fn new(conn: &Connection, greeting: &str) -> Self {
+        Self {
+            imports: statements::list_imports(conn).unwrap_or_default(),
+            data_dir: get_data_dir(),
+            selection: 0,
+            scroll_offset: 0,
+            last_visible_rows: 20,
+            status_message: None,
+            status_ttl: 0,
+            greeting: greeting.to_string(),
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        let border_style = Style::default().fg(Color::DarkGray);
+
+        let [header_area, sep, content_area, detail_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
+            header_area,
+        );
+
+        let sep_line = "\u{2501}".repeat(area.width as usize);
+        frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
+
+        // 3 lines for title area + 1 for column header = 4 lines overhead
+        let data_rows = (content_area.height as usize).saturating_sub(4);
+        self.last_visible_rows = data_rows;
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(" Import History ({})", self.imports.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+
+        if self.imports.is_empty() {
+            lines.push(Line::from("   No imports yet."));
+        } else {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "   {:<5} {:<11} {:<20} {:<30} {:>6}  {:<23} {}",
+                    "ID", "Imported", "Account", "File", "Rows", "Date Range", "Stored"
+                ),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )));
+
+            let end = (self.scroll_offset + data_rows).min(self.imports.len());
+            for i in self.scroll_offset..end {
+                let imp = &self.imports[i];
+                let marker = if i == self.selection { " > " } else { "   " };
+                let style = if i == self.selection {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                let range = match (&imp.date_range_start, &imp.date_range_end) {
+                    (Some(start), Some(end)) => format!("{start} \u{2013} {end}"),
+                    _ => String::new(),
+                };
+                let stored = if imp.stored_path.is_some() {
+                    "\u{2713}"
+                } else {
+                    ""
+                };
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "{marker}{:<5} {:<11} {:<20} {:<30} {:>6}  {:<23} {stored}",
+                        imp.id,
+                        imp.import_date.get(..10).unwrap_or(&imp.import_date),
+                        truncate(&imp.account_name, 18),
+                        truncate(&imp.filename, 28),
+                        imp.record_count,
+                        range,
+                    ),
+                    style,
+                )));
+            }
+        }
+
+        frame.render_widget(Paragraph::new(lines), content_area);
+
+        // Stored path of the selected import
+        let detail = match self.imports.get(self.selection) {
+            Some(ImportRecord {
+                stored_path: Some(path),
+                ..
+            }) => format!(
+                "   Stored copy: {}",
+                statements::resolve(&self.data_dir, path).display()
+            ),
+            Some(_) => "   No stored copy (enable keep_statements or use --keep-file)".to_string(),
+            None => String::new(),
+        };
+        frame.render_widget(
+            Paragraph::new(detail).style(Style::default().fg(Color::DarkGray)),
+            detail_area,
+        );
+
+        if let Some(msg) = &self.status_message {
+            frame.render_widget(
+                Paragraph::new(format!(" {msg}")).style(Style::default().fg(Color::Yellow)),
+                hints_area,
+            );
+        } else {
+            frame.render_widget(
+                Paragraph::new(" Up/Down=navigate  o=open stored copy  Esc=back")
+                    .style(FOOTER_STYLE),
+                hints_area,
+            );
+        }
+    }
+
+    fn set_status(&mut self, msg: String) {
+        self.status_message = Some(msg);
+        self.status_ttl = 3;
+    }
+
+    fn ensure_visible(&mut self, visible_rows: usize) {
+        if self.selection < self.scroll_offset {
+            self.scroll_offset = self.selection;
+        } else if self.selection >= self.scroll_offset + visible_rows {
+            self.scroll_offset = self.selection - visible_rows + 1;
+        }
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> ImportHistoryAction {
+        if self.status_ttl > 0 {
+            self.status_ttl -= 1;
+            if self.status_ttl == 0 {
+                self.status_message = None;
+            }
+        }
+
+        match code {
+            KeyCode::Up => {
+                self.selection = self.selection.saturating_sub(1);
+                self.ensure_visible(self.last_visible_rows);
+            }
+            KeyCode::Down if !self.imports.is_empty() => {
+                self.selection = (self.selection + 1).min(self.imports.len() - 1);
+                self.ensure_visible(self.last_visible_rows);
+            }
+            KeyCode::Char('o') | KeyCode::Enter => self.open_selected(),
+            KeyCode::Char('q') | KeyCode::Esc => return ImportHistoryAction::Close,
+            _ => {}
+        }
+        ImportHistoryAction::Continue
+    }
+
+    fn open_selected(&mut self) {
+        let Some(imp) = self.imports.get(self.selection) else {
+            return;
+        };
+        let Some(stored) = &imp.stored_path else {
+            self.set_status(format!("No stored copy of {}", imp.filename));
+            return;
+        };
+        let path = statements::resolve(&self.data_dir, stored);
+        match statements::open_with_system(&path) {
+            Ok(()) => self.set_status(format!("Opened {}", path.display())),
+            Err(e) => self.set_status(e.to_string()),
+        }
+    }
+}
+
+impl screen::Screen for ImportHistory {
+    fn draw(&mut self, frame: &mut Frame) {
+        ImportHistory::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, _ctx: &mut ScreenContext) -> Result<Nav> {
+        Ok(match ImportHistory::handle_key(self, code) {
+            ImportHistoryAction::Close => Nav::Pop,
+            ImportHistoryAction::Continue => Nav::Stay,
+        })
+    }
+}
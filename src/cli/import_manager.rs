@@ -11,11 +11,20 @@ use ratatui::{
 use rusqlite::Connection;
 
 use crate::categorizer::categorize_transactions;
+use crate::categorizer::preview_matches;
 use crate::cli::accounts;
+use crate::cli::file_picker::{FilePicker, PickerAction};
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
 use crate::error::Result;
-use crate::importer::import_file;
-use crate::settings::{get_data_dir, shellexpand_path};
-use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
+use crate::fmt::money;
+use crate::importer::{
+    find_prior_import, import_defaults, importer_keys, list_csv_profiles, parse_amount,
+    parse_date_mdy, preview_rows, read_import, ImportDefaults, ParsedImport, PriorImport,
+    AUTO_FORMAT, FILE_EXTENSIONS,
+};
+use crate::models::ParsedRow;
+use crate::settings::{get_data_dir, load_settings, shellexpand_path};
+use crate::tui::{display_width, pad_right, truncate, FOOTER_STYLE, HEADER_STYLE};
 
 pub enum ImportAction {
     Continue,
@@ -24,9 +33,56 @@ pub enum ImportAction {
 
 enum Screen {
     Form,
+    Browse(FilePicker),
+    Preview(Box<ImportPreview>),
+    Duplicate(DuplicateFile),
     Result(ImportResult),
 }
 
+/// The chosen file matches an earlier import; the user can go back or
+/// re-import it anyway as a new batch.
+struct DuplicateFile {
+    file_path: PathBuf,
+    account_name: String,
+    format: String,
+    prior: PriorImport,
+}
+
+/// Rows read from the statement, shown before anything is written. The user
+/// can leave rows out and fix a misread date or amount; Enter then commits
+/// the rest as one batch, in a single transaction.
+struct ImportPreview {
+    file_path: PathBuf,
+    account_name: String,
+    /// The rows themselves, edited in place.
+    parsed: ParsedImport,
+    entries: Vec<PreviewEntry>,
+    selected: usize,
+    edit: Option<RowEdit>,
+    message: Option<String>,
+}
+
+/// How a parsed row stands on the preview.
+struct PreviewEntry {
+    include: bool,
+    /// Already in the account (or earlier in the file); never imported.
+    duplicate: bool,
+    edited: bool,
+    /// The category the current rules would give it.
+    category: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum EditField {
+    Date,
+    Amount,
+}
+
+struct RowEdit {
+    field: EditField,
+    input: String,
+}
+
 struct ImportResult {
     message: String,
     is_error: bool,
@@ -34,11 +90,21 @@ struct ImportResult {
 
 const FIELD_FILE: usize = 0;
 const FIELD_ACCOUNT: usize = 1;
+const FIELD_FORMAT: usize = 2;
+const FIELD_COUNT: usize = 3;
 
 pub struct ImportScreen {
     accounts: Vec<String>,
     account_idx: usize,
     file_path: String,
+    /// `AUTO_FORMAT`, then the built-in importers, then saved CSV profiles.
+    formats: Vec<String>,
+    format_idx: usize,
+    /// What the selected account remembers from its last import.
+    defaults: ImportDefaults,
+    /// The folder put in the file path for the selected account, replaced
+    /// when the account changes unless the user has typed over it.
+    prefilled_dir: Option<String>,
     focused: usize,
     screen: Screen,
     status_message: Option<String>,
@@ -48,15 +114,69 @@ pub struct ImportScreen {
 impl ImportScreen {
     pub fn new(conn: &Connection, greeting: &str) -> Result<Self> {
         let accounts = accounts::account_names(conn)?;
-        Ok(Self {
+        let mut formats = vec![AUTO_FORMAT.to_string()];
+        formats.extend(importer_keys().into_iter().map(String::from));
+        formats.extend(list_csv_profiles(conn)?);
+        let mut screen = Self {
             accounts,
             account_idx: 0,
             file_path: String::new(),
+            formats,
+            format_idx: 0,
+            defaults: ImportDefaults::default(),
+            prefilled_dir: None,
             focused: FIELD_FILE,
             screen: Screen::Form,
             status_message: None,
             greeting: greeting.to_string(),
-        })
+        };
+        screen.load_account_defaults(conn);
+        Ok(screen)
+    }
+
+    /// Start with the file path filled in.
+    pub fn with_file(mut self, path: &str) -> Self {
+        self.file_path = path.to_string();
+        self.prefilled_dir = None;
+        self
+    }
+
+    /// Pre-select the account's remembered format and start the file path
+    /// in the folder it last imported from.
+    fn load_account_defaults(&mut self, conn: &Connection) {
+        let Some(account) = self.accounts.get(self.account_idx) else {
+            return;
+        };
+        self.defaults = import_defaults(conn, account).unwrap_or_default();
+        self.format_idx = self
+            .defaults
+            .default_format
+            .as_ref()
+            .and_then(|key| self.formats.iter().position(|f| f == key))
+            .unwrap_or(0);
+
+        let untouched = self.file_path.is_empty()
+            || self.prefilled_dir.as_deref() == Some(self.file_path.as_str());
+        if untouched {
+            self.prefilled_dir = self.defaults.last_import_dir.as_ref().map(|dir| {
+                let sep = std::path::MAIN_SEPARATOR;
+                format!("{}{sep}", dir.trim_end_matches(sep))
+            });
+            self.file_path = self.prefilled_dir.clone().unwrap_or_default();
+        }
+    }
+
+    fn format_label(&self) -> String {
+        let key = &self.formats[self.format_idx];
+        let mut label = if key == AUTO_FORMAT {
+            "auto-detect".to_string()
+        } else {
+            key.clone()
+        };
+        if self.defaults.default_format.as_ref() == Some(key) {
+            label.push_str(" (remembered)");
+        }
+        label
     }
 
     pub fn draw(&self, frame: &mut Frame) {
@@ -81,6 +201,9 @@ impl ImportScreen {
 
         match &self.screen {
             Screen::Form => self.draw_form(frame, content_area, hints_area),
+            Screen::Browse(picker) => picker.draw(frame, content_area, hints_area),
+            Screen::Preview(preview) => draw_preview(frame, content_area, hints_area, preview),
+            Screen::Duplicate(dup) => draw_duplicate(frame, content_area, hints_area, dup),
             Screen::Result(result) => self.draw_result(frame, content_area, hints_area, result),
         }
     }
@@ -147,6 +270,30 @@ impl ImportScreen {
                     },
                 ),
             ]));
+
+            // Format selector
+            let is_focused = self.focused == FIELD_FORMAT;
+            let label_style = if is_focused {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let arrows = if is_focused {
+                ("< ", " >")
+            } else {
+                ("  ", "  ")
+            };
+            lines.push(Line::from(vec![
+                Span::styled("   Format         ", label_style),
+                Span::styled(
+                    format!("{}{}{}", arrows.0, self.format_label(), arrows.1),
+                    if is_focused {
+                        Style::default().fg(Color::Cyan)
+                    } else {
+                        Style::default()
+                    },
+                ),
+            ]));
         }
 
         if let Some(msg) = &self.status_message {
@@ -160,8 +307,10 @@ impl ImportScreen {
         frame.render_widget(Paragraph::new(lines), content_area);
 
         frame.render_widget(
-            Paragraph::new(" Tab/Up/Down=fields  Left/Right=account  Enter=import  Esc=back")
-                .style(FOOTER_STYLE),
+            Paragraph::new(
+                " Tab/Up/Down=fields  Left/Right=choose  Enter=import (a folder: browse)  Esc=back",
+            )
+            .style(FOOTER_STYLE),
             hints_area,
         );
     }
@@ -206,6 +355,9 @@ impl ImportScreen {
     pub fn handle_key(&mut self, code: KeyCode, conn: &Connection) -> ImportAction {
         match &self.screen {
             Screen::Form => self.handle_form_key(code, conn),
+            Screen::Browse(_) => self.handle_browse_key(code),
+            Screen::Preview(_) => self.handle_preview_key(code, conn),
+            Screen::Duplicate(_) => self.handle_duplicate_key(code, conn),
             Screen::Result(_) => match code {
                 KeyCode::Esc => ImportAction::Close,
                 _ => ImportAction::Continue,
@@ -218,12 +370,12 @@ impl ImportScreen {
             KeyCode::Esc => return ImportAction::Close,
             KeyCode::Tab | KeyCode::Down => {
                 if !self.accounts.is_empty() {
-                    self.focused = (self.focused + 1) % 2;
+                    self.focused = (self.focused + 1) % FIELD_COUNT;
                 }
             }
             KeyCode::BackTab | KeyCode::Up => {
                 if !self.accounts.is_empty() {
-                    self.focused = if self.focused == 0 { 1 } else { 0 };
+                    self.focused = (self.focused + FIELD_COUNT - 1) % FIELD_COUNT;
                 }
             }
             KeyCode::Left => {
@@ -233,11 +385,21 @@ impl ImportScreen {
                     } else {
                         self.account_idx - 1
                     };
+                    self.load_account_defaults(conn);
+                } else if self.focused == FIELD_FORMAT {
+                    self.format_idx = if self.format_idx == 0 {
+                        self.formats.len() - 1
+                    } else {
+                        self.format_idx - 1
+                    };
                 }
             }
             KeyCode::Right => {
                 if self.focused == FIELD_ACCOUNT && !self.accounts.is_empty() {
                     self.account_idx = (self.account_idx + 1) % self.accounts.len();
+                    self.load_account_defaults(conn);
+                } else if self.focused == FIELD_FORMAT {
+                    self.format_idx = (self.format_idx + 1) % self.formats.len();
                 }
             }
             KeyCode::Char(c) => {
@@ -257,19 +419,135 @@ impl ImportScreen {
                     return ImportAction::Continue;
                 }
                 let path_str = self.file_path.trim().to_string();
+                // No path yet, or a folder: browse for the file
                 if path_str.is_empty() {
-                    self.status_message = Some("File path is required".into());
+                    self.open_picker(&default_browse_dir());
                     return ImportAction::Continue;
                 }
 
                 let file_path = PathBuf::from(shellexpand_path(&path_str));
+                if file_path.is_dir() {
+                    self.open_picker(&file_path);
+                    return ImportAction::Continue;
+                }
+
                 if !file_path.exists() {
                     self.status_message = Some(format!("File not found: {}", file_path.display()));
                     return ImportAction::Continue;
                 }
 
                 let account_name = self.accounts[self.account_idx].clone();
-                self.screen = Screen::Result(run_import(conn, &file_path, &account_name));
+                let format = self.formats[self.format_idx].clone();
+                match find_prior_import(conn, &file_path) {
+                    Ok(Some(prior)) => {
+                        self.screen = Screen::Duplicate(DuplicateFile {
+                            file_path,
+                            account_name,
+                            format,
+                            prior,
+                        })
+                    }
+                    Ok(None) => self.start_import(conn, file_path, account_name, &format, false),
+                    Err(e) => self.status_message = Some(format!("Could not read file: {e}")),
+                }
+            }
+            _ => {}
+        }
+        ImportAction::Continue
+    }
+
+    fn open_picker(&mut self, start: &Path) {
+        self.status_message = None;
+        self.screen = Screen::Browse(FilePicker::new(
+            "Choose a statement",
+            start,
+            FILE_EXTENSIONS,
+        ));
+    }
+
+    /// A picked file fills in the path; Enter on the form imports it.
+    fn handle_browse_key(&mut self, code: KeyCode) -> ImportAction {
+        let Screen::Browse(picker) = &mut self.screen else {
+            return ImportAction::Continue;
+        };
+        match picker.handle_key(code) {
+            PickerAction::Continue => return ImportAction::Continue,
+            PickerAction::Cancel => {}
+            PickerAction::Picked(path) => {
+                self.file_path = path.to_string_lossy().into_owned();
+                self.prefilled_dir = None;
+            }
+        }
+        self.screen = Screen::Form;
+        ImportAction::Continue
+    }
+
+    /// Read the statement with the chosen format and open its preview.
+    fn start_import(
+        &mut self,
+        conn: &Connection,
+        file_path: PathBuf,
+        account_name: String,
+        format: &str,
+        force_reimport: bool,
+    ) {
+        self.screen = match build_preview(conn, &file_path, &account_name, format, force_reimport) {
+            Ok(preview) => Screen::Preview(Box::new(preview)),
+            Err(result) => Screen::Result(result),
+        };
+    }
+
+    fn handle_duplicate_key(&mut self, code: KeyCode, conn: &Connection) -> ImportAction {
+        match code {
+            KeyCode::Esc => self.screen = Screen::Form,
+            KeyCode::Char('r') => {
+                if let Screen::Duplicate(dup) = std::mem::replace(&mut self.screen, Screen::Form) {
+                    self.start_import(conn, dup.file_path, dup.account_name, &dup.format, true);
+                }
+            }
+            _ => {}
+        }
+        ImportAction::Continue
+    }
+
+    fn handle_preview_key(&mut self, code: KeyCode, conn: &Connection) -> ImportAction {
+        let Screen::Preview(preview) = &mut self.screen else {
+            return ImportAction::Continue;
+        };
+        if let Some(edit) = &mut preview.edit {
+            match code {
+                KeyCode::Esc => preview.edit = None,
+                KeyCode::Enter => preview.apply_edit(conn),
+                KeyCode::Backspace => {
+                    edit.input.pop();
+                }
+                KeyCode::Char(c) => edit.input.push(c),
+                _ => {}
+            }
+            return ImportAction::Continue;
+        }
+
+        preview.message = None;
+        let last = preview.entries.len().saturating_sub(1);
+        match code {
+            KeyCode::Esc => self.screen = Screen::Form,
+            KeyCode::Up => preview.selected = preview.selected.saturating_sub(1),
+            KeyCode::Down => preview.selected = (preview.selected + 1).min(last),
+            KeyCode::PageUp => preview.selected = preview.selected.saturating_sub(10),
+            KeyCode::PageDown => preview.selected = (preview.selected + 10).min(last),
+            KeyCode::Char(' ') => preview.toggle(),
+            KeyCode::Char('d') => preview.start_edit(EditField::Date),
+            KeyCode::Char('a') => preview.start_edit(EditField::Amount),
+            KeyCode::Enter => {
+                if preview.chosen_rows().is_empty() {
+                    preview.message = Some(
+                        "Nothing to import: every row is left out or already in the register"
+                            .into(),
+                    );
+                } else {
+                    let result = run_import(conn, preview);
+                    self.screen = Screen::Result(result);
+                }
             }
             _ => {}
         }
@@ -277,42 +555,390 @@ impl ImportScreen {
     }
 }
 
-fn run_import(conn: &Connection, file_path: &Path, account_name: &str) -> ImportResult {
+impl ImportPreview {
+    fn toggle(&mut self) {
+        let Some(entry) = self.entries.get_mut(self.selected) else {
+            return;
+        };
+        if entry.duplicate {
+            self.message = Some("Already in the register, so it is always skipped".into());
+        } else {
+            entry.include = !entry.include;
+        }
+    }
+
+    fn start_edit(&mut self, field: EditField) {
+        let Some(row) = self.parsed.rows.get(self.selected) else {
+            return;
+        };
+        let input = match field {
+            EditField::Date => row.date.clone(),
+            EditField::Amount => format!("{:.2}", row.amount),
+        };
+        self.edit = Some(RowEdit { field, input });
+    }
+
+    /// Save the edit to the selected row if it parses, then re-check
+    /// duplicates, which depend on both date and amount.
+    fn apply_edit(&mut self, conn: &Connection) {
+        let Some(edit) = &self.edit else {
+            return;
+        };
+        let input = edit.input.trim();
+        let row = &mut self.parsed.rows[self.selected];
+        match edit.field {
+            EditField::Date => match parse_edited_date(input) {
+                Some(date) => row.date = date,
+                None => {
+                    let typed = match crate::fmt::display_format().date_format {
+                        "YYYY-MM-DD" => "MM/DD/YYYY",
+                        other => other,
+                    };
+                    self.message = Some(format!("Not a date: use YYYY-MM-DD or {typed}"));
+                    return;
+                }
+            },
+            EditField::Amount => match parse_amount(input) {
+                Some(amount) => row.amount = amount,
+                None => {
+                    self.message = Some("Not an amount: e.g. -42.50".into());
+                    return;
+                }
+            },
+        }
+        self.edit = None;
+        self.message = None;
+        self.entries[self.selected].edited = true;
+        if let Err(e) = self.refresh_duplicates(conn) {
+            self.message = Some(format!("Could not check for duplicates: {e}"));
+        }
+    }
+
+    /// Re-run the duplicate check; a row that becomes (or stops being) a
+    /// duplicate is left out (or put back).
+    fn refresh_duplicates(&mut self, conn: &Connection) -> Result<()> {
+        let checked = preview_rows(conn, self.parsed.account_id, &self.parsed.rows)?;
+        for (entry, row) in self.entries.iter_mut().zip(checked) {
+            if entry.duplicate != row.duplicate {
+                entry.duplicate = row.duplicate;
+                entry.include = !row.duplicate;
+            }
+        }
+        Ok(())
+    }
+
+    /// The rows Enter would import.
+    fn chosen_rows(&self) -> Vec<ParsedRow> {
+        self.entries
+            .iter()
+            .zip(&self.parsed.rows)
+            .filter(|(entry, _)| entry.include && !entry.duplicate)
+            .map(|(_, row)| row.clone())
+            .collect()
+    }
+
+    fn duplicates(&self) -> usize {
+        self.entries.iter().filter(|e| e.duplicate).count()
+    }
+
+    fn left_out(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| !e.include && !e.duplicate)
+            .count()
+    }
+}
+
+/// A corrected date, as YYYY-MM-DD or the statements' MM/DD/YYYY.
+/// Where browsing starts for an account with no remembered import folder:
+/// Downloads, where bank statements usually land, else home.
+fn default_browse_dir() -> PathBuf {
+    dirs::download_dir()
+        .filter(|d| d.is_dir())
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// YYYY-MM-DD or the `date_format` setting's form, else MM/DD/YYYY.
+fn parse_edited_date(input: &str) -> Option<String> {
+    crate::fmt::display_format()
+        .parse_date(input)
+        .or_else(|| parse_date_mdy(input))
+}
+
+/// Read the statement, check each row for duplicates, and predict its
+/// category from the current rules. The format is always explicit here, so
+/// committing remembers it (or, for `AUTO_FORMAT`, forgets the old one).
+fn build_preview(
+    conn: &Connection,
+    file_path: &Path,
+    account_name: &str,
+    format: &str,
+    force_reimport: bool,
+) -> std::result::Result<ImportPreview, ImportResult> {
+    let failed = |e: crate::error::NigelError| ImportResult {
+        message: format!("Import failed: {e}"),
+        is_error: true,
+    };
+    let parsed = read_import(
+        conn,
+        file_path,
+        account_name,
+        Some(format),
+        None,
+        force_reimport,
+        load_settings().gusto_detailed,
+    )
+    .map_err(failed)?;
+    if let Some(prior) = parsed.duplicate_of {
+        return Err(ImportResult {
+            message: prior.describe(),
+            is_error: false,
+        });
+    }
+    if parsed.rows.is_empty() {
+        let mut message = format!("No transactions found in {}", file_path.display());
+        if parsed.malformed > 0 {
+            message.push_str(&format!("\n{} lines couldn't be read", parsed.malformed));
+        }
+        return Err(ImportResult {
+            message,
+            is_error: true,
+        });
+    }
+
+    let checked = preview_rows(conn, parsed.account_id, &parsed.rows).map_err(failed)?;
+    let descriptions: Vec<&str> = parsed.rows.iter().map(|r| r.description.as_str()).collect();
+    let matches = preview_matches(conn, &descriptions).map_err(failed)?;
+    let entries = checked
+        .into_iter()
+        .zip(matches)
+        .map(|(row, rule)| PreviewEntry {
+            include: !row.duplicate,
+            duplicate: row.duplicate,
+            edited: false,
+            category: rule.map(|m| m.category),
+        })
+        .collect();
+    Ok(ImportPreview {
+        file_path: file_path.to_path_buf(),
+        account_name: account_name.to_string(),
+        parsed,
+        entries,
+        selected: 0,
+        edit: None,
+        message: None,
+    })
+}
+
+fn draw_preview(
+    frame: &mut Frame,
+    content_area: ratatui::layout::Rect,
+    hints_area: ratatui::layout::Rect,
+    preview: &ImportPreview,
+) {
+    let file_name = preview
+        .file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut summary = format!(
+        "{} to import, {} duplicates",
+        preview.chosen_rows().len(),
+        preview.duplicates()
+    );
+    let left_out = preview.left_out();
+    if left_out > 0 {
+        summary.push_str(&format!(", {left_out} left out"));
+    }
+    if preview.parsed.malformed > 0 {
+        summary.push_str(&format!(
+            ", {} lines couldn't be read",
+            preview.parsed.malformed
+        ));
+    }
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                " Preview: {} transactions from {file_name} into {}",
+                preview.entries.len(),
+                preview.account_name
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(format!("   {summary}"), FOOTER_STYLE)),
+        Line::from(""),
+    ];
+
+    let width = content_area.width as usize;
+    let amount_w = 14;
+    let category_w = 22;
+    let desc_w = width.saturating_sub(2 + 4 + 10 + 2 + 2 + amount_w + 2 + category_w);
+    // Leave a line for the edit prompt or message
+    let visible = (content_area.height as usize)
+        .saturating_sub(lines.len() + 2)
+        .max(1);
+    let offset = (preview.selected + 1).saturating_sub(visible);
+    let edited_style = Style::default().fg(Color::Yellow);
+    for (i, (entry, row)) in preview
+        .entries
+        .iter()
+        .zip(&preview.parsed.rows)
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+    {
+        let is_selected = i == preview.selected;
+        let check = if entry.duplicate {
+            " = "
+        } else if entry.include {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+        let desc = pad_right(&truncate(&row.description, desc_w), desc_w);
+        let amount = money(row.amount);
+        let pad = amount_w.saturating_sub(display_width(&amount));
+        let category = if entry.duplicate {
+            "duplicate".to_string()
+        } else {
+            entry.category.clone().unwrap_or_else(|| "(flagged)".into())
+        };
+        let (date_style, amount_style) = if entry.edited {
+            (edited_style, edited_style)
+        } else if row.amount < 0.0 {
+            (Style::default(), Style::default().fg(Color::Red))
+        } else {
+            (Style::default(), Style::default().fg(Color::Green))
+        };
+        let mut line = Line::from(vec![
+            Span::raw(format!(" {} {check} ", if is_selected { ">" } else { " " })),
+            Span::styled(row.date.clone(), date_style),
+            Span::raw(format!("  {desc}  {}", " ".repeat(pad))),
+            Span::styled(amount, amount_style),
+            Span::styled(
+                format!("  {}", truncate(&category, category_w)),
+                FOOTER_STYLE,
+            ),
+        ]);
+        if entry.duplicate || !entry.include {
+            line = line.patch_style(Style::default().add_modifier(Modifier::DIM));
+        }
+        if is_selected {
+            line = line.patch_style(Style::default().add_modifier(Modifier::BOLD));
+        }
+        lines.push(line);
+    }
+
+    lines.push(Line::from(""));
+    if let Some(edit) = &preview.edit {
+        let label = match edit.field {
+            EditField::Date => "Date",
+            EditField::Amount => "Amount",
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("   {label}: "),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("{}_", edit.input), Style::default().fg(Color::Cyan)),
+        ]));
+    }
+    if let Some(msg) = &preview.message {
+        lines.push(Line::from(Span::styled(
+            format!("   {msg}"),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines), content_area);
+
+    let hints = if preview.edit.is_some() {
+        " Enter=save  Esc=cancel edit"
+    } else {
+        " Enter=import  Space=include/skip  d=date  a=amount  Up/Down/PgUp/PgDn=move  Esc=cancel"
+    };
+    frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
+}
+
+fn draw_duplicate(
+    frame: &mut Frame,
+    content_area: ratatui::layout::Rect,
+    hints_area: ratatui::layout::Rect,
+    dup: &DuplicateFile,
+) {
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " Already Imported",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("   {}", dup.prior.describe()),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(format!("   Earlier file: {}", dup.prior.filename)),
+        Line::from(""),
+        Line::from(format!(
+            "   Re-importing into {} creates a new batch; transactions already",
+            dup.account_name
+        )),
+        Line::from("   in the register are still skipped as duplicates."),
+    ];
+    frame.render_widget(Paragraph::new(lines), content_area);
+    frame.render_widget(
+        Paragraph::new(" r=re-import anyway  Esc=back").style(FOOTER_STYLE),
+        hints_area,
+    );
+}
+
+/// Commit the preview's chosen rows, then store the statement and categorize
+/// as a CLI import does.
+fn run_import(conn: &Connection, preview: &ImportPreview) -> ImportResult {
     // Snapshot before import
     let data_dir = get_data_dir();
-    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
-    let snap_path = data_dir.join(format!("snapshots/pre-import-{stamp}.db"));
-    if let Err(e) = crate::cli::backup::snapshot(conn, &snap_path) {
+    if let Err(e) = crate::cli::backup::pre_operation_snapshot(conn, &data_dir, "import") {
         return ImportResult {
             message: format!("Snapshot failed: {e}"),
             is_error: true,
         };
     }
 
-    match import_file(conn, file_path, account_name, None, false, None) {
+    match preview.parsed.commit(conn, &preview.chosen_rows()) {
         Err(e) => ImportResult {
             message: format!("Import failed: {e}"),
             is_error: true,
         },
-        Ok(result) => {
-            if result.duplicate_file {
-                return ImportResult {
-                    message: "This file has already been imported (duplicate checksum).".into(),
-                    is_error: false,
-                };
+        Ok(batch) => {
+            let mut msg = format!(
+                "{} imported, {} skipped (duplicates)",
+                batch.imported,
+                batch.skipped + preview.duplicates()
+            );
+            let left_out = preview.left_out();
+            if left_out > 0 {
+                msg.push_str(&format!(", {left_out} left out"));
+            }
+            if preview.parsed.malformed > 0 {
+                msg.push_str(&format!(
+                    ", {} skipped (malformed data)",
+                    preview.parsed.malformed
+                ));
             }
 
-            let mut msg = if result.malformed > 0 {
-                format!(
-                    "{} imported, {} skipped (duplicates), {} skipped (malformed data)",
-                    result.imported, result.skipped, result.malformed
-                )
-            } else {
-                format!(
-                    "{} imported, {} skipped (duplicates)",
-                    result.imported, result.skipped
-                )
-            };
+            if load_settings().keep_statements {
+                match crate::statements::retain(
+                    conn,
+                    &data_dir,
+                    batch.import_id,
+                    &preview.file_path,
+                ) {
+                    Ok(stored) => msg.push_str(&format!("\nStored a copy at {}", stored.display())),
+                    Err(e) => msg.push_str(&format!("\nCould not store a copy: {e}")),
+                }
+            }
 
             match categorize_transactions(conn) {
                 Ok(cat) => {
@@ -320,6 +946,30 @@ fn run_import(conn: &Connection, file_path: &Path, account_name: &str) -> Import
                         "\n{} categorized, {} still flagged",
                         cat.categorized, cat.still_flagged
                     ));
+                    if cat.card_payments > 0 {
+                        msg.push_str(&format!(
+                            "\n{} credit card payments matched across accounts",
+                            cat.card_payments
+                        ));
+                    }
+                    if cat.sign_warnings > 0 {
+                        msg.push_str(&format!(
+                            "\n{} flagged for a sign that doesn't match the category",
+                            cat.sign_warnings
+                        ));
+                    }
+                    if cat.anomalies > 0 {
+                        msg.push_str(&format!(
+                            "\n{} flagged as unusual for their vendor",
+                            cat.anomalies
+                        ));
+                    }
+                    if cat.out_of_range > 0 {
+                        msg.push_str(&format!(
+                            "\n{} flagged for a date out of range",
+                            cat.out_of_range
+                        ));
+                    }
                 }
                 Err(e) => {
                     msg.push_str(&format!("\nCategorization error: {e}"));
@@ -333,3 +983,215 @@ fn run_import(conn: &Connection, file_path: &Path, account_name: &str) -> Import
         }
     }
 }
+
+impl screen::Screen for ImportScreen {
+    fn draw(&mut self, frame: &mut Frame) {
+        ImportScreen::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        let had_result = matches!(self.screen, Screen::Result(_));
+        let action = ImportScreen::handle_key(self, code, ctx.conn);
+        // Reaching the result screen means the import ran
+        if !had_result && matches!(self.screen, Screen::Result(_)) {
+            ctx.publish(DataChange::Transactions);
+        }
+        Ok(match action {
+            ImportAction::Close => Nav::Pop,
+            ImportAction::Continue => Nav::Stay,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, '2025-01-02', 'COFFEE', -4.5)",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn preview(screen: &ImportScreen) -> &ImportPreview {
+        match &screen.screen {
+            Screen::Preview(preview) => preview,
+            _ => panic!("not on the preview"),
+        }
+    }
+
+    fn type_text(screen: &mut ImportScreen, conn: &Connection, text: &str) {
+        for c in text.chars() {
+            screen.handle_key(KeyCode::Char(c), conn);
+        }
+    }
+
+    #[test]
+    fn preview_edits_and_deselects_before_commit() {
+        let (dir, conn) = test_db();
+        let csv = dir.path().join("stmt.csv");
+        std::fs::write(
+            &csv,
+            "Date,Description,Amount,Running Bal.\n\
+             01/02/2025,COFFEE,-4.50,0.00\n\
+             01/03/2025,STRIPE PAYOUT,900.00,0.00\n\
+             01/05/2025,COFFEE,-45.00,0.00\n",
+        )
+        .unwrap();
+        let mut screen = ImportScreen::new(&conn, "")
+            .unwrap()
+            .with_file(&csv.to_string_lossy());
+        screen.handle_key(KeyCode::Enter, &conn);
+        let p = preview(&screen);
+        assert_eq!(p.entries.len(), 3);
+        assert!(p.entries[0].duplicate && !p.entries[0].include);
+        assert_eq!(p.chosen_rows().len(), 2);
+
+        // The duplicate can't be picked; the payout is left out
+        screen.handle_key(KeyCode::Char(' '), &conn);
+        assert!(preview(&screen).message.is_some());
+        screen.handle_key(KeyCode::Down, &conn);
+        screen.handle_key(KeyCode::Char(' '), &conn);
+
+        // A misread amount is fixed; a bad one is refused
+        screen.handle_key(KeyCode::Down, &conn);
+        screen.handle_key(KeyCode::Char('a'), &conn);
+        for _ in 0..6 {
+            screen.handle_key(KeyCode::Backspace, &conn);
+        }
+        type_text(&mut screen, &conn, "4.5x");
+        screen.handle_key(KeyCode::Enter, &conn);
+        assert!(preview(&screen).edit.is_some());
+        screen.handle_key(KeyCode::Backspace, &conn);
+        type_text(&mut screen, &conn, "0");
+        screen.handle_key(KeyCode::Enter, &conn);
+        let p = preview(&screen);
+        assert!(p.edit.is_none());
+        assert_eq!(p.parsed.rows[2].amount, 4.5);
+        assert!(p.entries[2].edited && p.entries[2].include);
+
+        // Moving its date onto the existing coffee makes it a duplicate too
+        screen.handle_key(KeyCode::Char('d'), &conn);
+        for _ in 0..10 {
+            screen.handle_key(KeyCode::Backspace, &conn);
+        }
+        type_text(&mut screen, &conn, "2025-01-02");
+        screen.handle_key(KeyCode::Char('a'), &conn);
+        screen.handle_key(KeyCode::Esc, &conn);
+        assert_eq!(preview(&screen).parsed.rows[2].date, "2025-01-05");
+        screen.handle_key(KeyCode::Char('a'), &conn);
+        for _ in 0..4 {
+            screen.handle_key(KeyCode::Backspace, &conn);
+        }
+        type_text(&mut screen, &conn, "-4.50");
+        screen.handle_key(KeyCode::Enter, &conn);
+        screen.handle_key(KeyCode::Char('d'), &conn);
+        for _ in 0..10 {
+            screen.handle_key(KeyCode::Backspace, &conn);
+        }
+        type_text(&mut screen, &conn, "1/2/2025");
+        screen.handle_key(KeyCode::Enter, &conn);
+        let p = preview(&screen);
+        assert_eq!(p.parsed.rows[2].date, "2025-01-02");
+        assert!(p.entries[2].duplicate && !p.entries[2].include);
+
+        // Nothing left to import
+        assert!(p.chosen_rows().is_empty());
+        screen.handle_key(KeyCode::Enter, &conn);
+        assert!(preview(&screen).message.is_some());
+    }
+
+    #[test]
+    fn form_starts_from_the_accounts_last_import() {
+        let (dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Card', 'credit_card')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE accounts SET default_format = 'bofa_checking', last_import_dir = ?1 \
+             WHERE name = 'Checking'",
+            [dir.path().to_string_lossy()],
+        )
+        .unwrap();
+        let mut screen = ImportScreen::new(&conn, "").unwrap();
+        let folder = format!("{}{}", dir.path().display(), std::path::MAIN_SEPARATOR);
+        assert_eq!(screen.accounts[screen.account_idx], "Card");
+        assert_eq!(screen.file_path, "");
+        assert_eq!(screen.formats[screen.format_idx], AUTO_FORMAT);
+
+        screen.handle_key(KeyCode::Tab, &conn);
+        screen.handle_key(KeyCode::Right, &conn);
+        assert_eq!(screen.accounts[screen.account_idx], "Checking");
+        assert_eq!(screen.file_path, folder);
+        assert_eq!(screen.format_label(), "bofa_checking (remembered)");
+
+        // Back to an account with nothing remembered clears the prefill,
+        // but not a path the user typed
+        screen.handle_key(KeyCode::Left, &conn);
+        assert_eq!(screen.file_path, "");
+        screen.handle_key(KeyCode::BackTab, &conn);
+        type_text(&mut screen, &conn, "stmt.csv");
+        screen.handle_key(KeyCode::Tab, &conn);
+        screen.handle_key(KeyCode::Right, &conn);
+        assert_eq!(screen.file_path, "stmt.csv");
+
+        // Picking auto-detect and importing forgets the format
+        let csv = dir.path().join("stmt.csv");
+        std::fs::write(
+            &csv,
+            "Date,Description,Amount,Running Bal.\n01/03/2025,STRIPE PAYOUT,900.00,0.00\n",
+        )
+        .unwrap();
+        screen.file_path = csv.to_string_lossy().into_owned();
+        screen.handle_key(KeyCode::Tab, &conn);
+        while screen.format_idx != 0 {
+            screen.handle_key(KeyCode::Left, &conn);
+        }
+        screen.handle_key(KeyCode::Enter, &conn);
+        screen.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(screen.screen, Screen::Result(_)));
+        assert!(import_defaults(&conn, "Checking")
+            .unwrap()
+            .default_format
+            .is_none());
+    }
+
+    #[test]
+    fn enter_on_a_folder_browses_for_the_file() {
+        let (dir, conn) = test_db();
+        std::fs::write(dir.path().join("stmt.csv"), "").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        let mut screen = ImportScreen::new(&conn, "").unwrap();
+        screen.file_path = dir.path().to_string_lossy().into_owned();
+
+        screen.handle_key(KeyCode::Enter, &conn);
+        let Screen::Browse(picker) = &screen.screen else {
+            panic!("expected the file picker");
+        };
+        assert_eq!(picker.dir(), dir.path());
+
+        // "..", then the statement; the text file isn't offered
+        screen.handle_key(KeyCode::Down, &conn);
+        screen.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(screen.screen, Screen::Form));
+        assert_eq!(
+            PathBuf::from(&screen.file_path),
+            dir.path().join("stmt.csv")
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    DefaultTerminal, Frame,
+};
+
+use crate::cli::screen::{self, Nav, ScreenContext};
+use crate::effects::{self, Particle};
+use crate::error::Result;
+use crate::reviewer::ReviewSummary;
+use crate::tui::FOOTER_STYLE;
+
+const TICK_INTERVAL: Duration = Duration::from_millis(50);
+/// Categories listed by name before the rest are counted as "and N more".
+const MAX_CATEGORIES: usize = 5;
+
+/// Shown when a review clears the last flagged transaction: what the session
+/// did, over the splash screen's particles. Any key dismisses it.
+pub struct InboxZero {
+    summary: ReviewSummary,
+    phase: f64,
+    particles: Vec<Particle>,
+    width: u16,
+    height: u16,
+}
+
+impl InboxZero {
+    pub fn new(summary: ReviewSummary) -> Self {
+        let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+        Self {
+            summary,
+            phase: 0.0,
+            particles: effects::pre_seed_particles(width, height),
+            width,
+            height,
+        }
+    }
+
+    fn tick(&mut self) {
+        self.phase += 1.0 / 70.0;
+        effects::tick_particles(&mut self.particles, self.width, self.height);
+    }
+
+    fn summary_lines(&self) -> Vec<Line<'static>> {
+        let s = &self.summary;
+        let count =
+            |n: usize, one: &str, many: &str| format!("{n} {}", if n == 1 { one } else { many });
+        let mut lines = vec![Line::from(format!(
+            "{} reviewed in {}",
+            count(s.reviewed, "transaction", "transactions"),
+            s.elapsed_label()
+        ))];
+        if s.skipped > 0 || s.rules_created > 0 {
+            lines.push(Line::from(format!(
+                "{} skipped along the way, {} added",
+                s.skipped,
+                count(s.rules_created, "rule", "rules")
+            )));
+        }
+        lines.push(Line::from(""));
+        for (name, n) in s.categories.iter().take(MAX_CATEGORIES) {
+            lines.push(Line::from(format!("{name}  {n}")));
+        }
+        if s.categories.len() > MAX_CATEGORIES {
+            lines.push(Line::from(format!(
+                "and {} more",
+                s.categories.len() - MAX_CATEGORIES
+            )));
+        }
+        lines
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        self.width = area.width;
+        self.height = area.height;
+
+        effects::render_particles(&self.particles, frame, area);
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "Inbox zero!",
+                Style::default()
+                    .fg(effects::gradient_color(self.phase))
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("Every flagged transaction has a category."),
+            Line::from(""),
+        ];
+        lines.extend(self.summary_lines());
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Press any key to continue",
+            FOOTER_STYLE,
+        )));
+
+        let [_top, content, _bottom] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(lines.len() as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), content);
+    }
+}
+
+/// Celebrate on `nigel review`'s own terminal until a key is pressed.
+pub fn show(terminal: &mut DefaultTerminal, summary: ReviewSummary) -> Result<()> {
+    let mut screen = InboxZero::new(summary);
+    loop {
+        terminal.draw(|frame| screen.draw(frame))?;
+        if event::poll(TICK_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    return Ok(());
+                }
+            }
+        }
+        screen.tick();
+    }
+}
+
+impl screen::Screen for InboxZero {
+    fn draw(&mut self, frame: &mut Frame) {
+        InboxZero::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, _code: KeyCode, _ctx: &mut ScreenContext) -> Result<Nav> {
+        Ok(Nav::Pop)
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(TICK_INTERVAL)
+    }
+
+    fn tick(&mut self, _ctx: &mut ScreenContext) {
+        InboxZero::tick(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(categories: usize) -> ReviewSummary {
+        ReviewSummary {
+            reviewed: 12,
+            skipped: 2,
+            categories: (0..categories)
+                .map(|i| (format!("Category {i}"), categories - i))
+                .collect(),
+            rules_created: 1,
+            elapsed: Duration::from_secs(75),
+        }
+    }
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn summary_lines_cap_the_category_list() {
+        let screen = InboxZero::new(summary(7));
+        let lines = text(&screen.summary_lines());
+        assert_eq!(lines[0], "12 transactions reviewed in 1m 15s");
+        assert_eq!(lines[1], "2 skipped along the way, 1 rule added");
+        assert_eq!(lines[3], "Category 0  7");
+        assert_eq!(lines.last().unwrap(), "and 2 more");
+        assert_eq!(lines.len(), 3 + MAX_CATEGORIES + 1);
+    }
+
+    #[test]
+    fn tick_advances_phase() {
+        let mut screen = InboxZero::new(summary(1));
+        screen.tick();
+        assert!(screen.phase > 0.0);
+    }
+}
@@ -1,18 +1,24 @@
 use std::path::PathBuf;
 
-use crate::db::{get_connection, init_db};
+use crate::db::{get_connection, get_metadata, init_db_with_template};
 use crate::error::Result;
 use crate::settings::{
     load_settings, restrict_dir_permissions, save_settings, shellexpand_path, Settings,
 };
 
-pub fn run(data_dir: Option<String>) -> Result<()> {
+pub fn run(data_dir: Option<String>, template: Option<String>) -> Result<()> {
+    // Reject a bad template before touching settings or the disk
+    if let Some(key) = &template {
+        crate::templates::find(key)?;
+    }
     let mut settings = load_settings();
     let defaults = Settings::default();
+    let first_run =
+        settings.data_dir == defaults.data_dir && settings.user_name == defaults.user_name;
 
     if let Some(dir) = data_dir {
         settings.data_dir = shellexpand_path(&dir);
-    } else if settings.data_dir == defaults.data_dir && settings.user_name == defaults.user_name {
+    } else if first_run {
         // First run — prompt for data dir
         let default = &settings.data_dir;
         println!("Data directory [{}]: ", default);
@@ -24,9 +30,13 @@ pub fn run(data_dir: Option<String>) -> Result<()> {
         }
     }
 
+    let resolved = PathBuf::from(&settings.data_dir);
+    // New users start with the advanced features tucked away
+    if first_run && !resolved.join("nigel.db").exists() {
+        settings.simple_mode = true;
+    }
     save_settings(&settings)?;
 
-    let resolved = PathBuf::from(&settings.data_dir);
     std::fs::create_dir_all(&resolved)?;
     restrict_dir_permissions(&resolved)?;
     let exports_dir = resolved.join("exports");
@@ -34,8 +44,16 @@ pub fn run(data_dir: Option<String>) -> Result<()> {
     restrict_dir_permissions(&exports_dir)?;
 
     let conn = get_connection(&resolved.join("nigel.db"))?;
-    init_db(&conn)?;
+    let key = template
+        .as_deref()
+        .unwrap_or(crate::templates::DEFAULT_TEMPLATE);
+    init_db_with_template(&conn, key)?;
 
     println!("Initialized nigel at {}", resolved.display());
+    if let Some(requested) = template {
+        if get_metadata(&conn, "category_template").as_deref() != Some(requested.as_str()) {
+            println!("The book already has categories; --template {requested} was not applied.");
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,71 @@
+use comfy_table::{Cell, Table};
+
+use crate::db::get_connection;
+use crate::error::Result;
+use crate::expense_limits;
+use crate::fiscal::FiscalYear;
+use crate::fmt::money;
+use crate::reports;
+use crate::settings::get_data_dir;
+
+pub fn add(category: &str, per_person: f64, note: Option<&str>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let (id, category) = expense_limits::add_limit(&conn, category, per_person, note)?;
+    println!(
+        "Added limit #{id}: {category}, {} per person per year. Tag each transaction with \
+         who it was for (#jane-doe) to have it checked.",
+        money(per_person)
+    );
+    Ok(())
+}
+
+/// Each limit with the year's spend against it, then every warning.
+pub fn list(year: Option<i32>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    let year = year.unwrap_or_else(|| fiscal.current());
+    let summary = reports::get_tax_summary(&conn, Some(year))?;
+    if summary.limits.is_empty() {
+        println!(
+            "No expense limits. Add one with `nigel limits add <category> --per-person {}`.",
+            expense_limits::GIFT_LIMIT
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Category",
+        "Per person",
+        "People",
+        "Over",
+        "Not deductible",
+        "Untagged",
+        "Note",
+    ]);
+    for check in &summary.limits {
+        table.add_row(vec![
+            Cell::new(check.limit.id),
+            Cell::new(&check.limit.category),
+            Cell::new(money(check.limit.per_person)),
+            Cell::new(check.counterparties.len()),
+            Cell::new(check.over().count()),
+            Cell::new(money(check.excess())),
+            Cell::new(money(check.untagged)),
+            Cell::new(check.limit.note.as_deref().unwrap_or("")),
+        ]);
+    }
+    println!("Expense Limits, {}\n{table}", fiscal.label(year));
+    for warning in summary.limit_warnings() {
+        println!("  {warning}");
+    }
+    Ok(())
+}
+
+pub fn remove(id: i64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    expense_limits::remove_limit(&conn, id)?;
+    println!("Removed expense limit #{id}");
+    Ok(())
+}
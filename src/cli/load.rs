@@ -1,12 +1,211 @@
-use std::path::PathBuf;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
 
+use rusqlite::OptionalExtension;
+
+use crate::db::{get_db_password, get_metadata, is_encrypted, open_connection};
 use crate::error::{NigelError, Result};
-use crate::settings::{load_settings, save_settings, shellexpand_path};
+use crate::settings::{get_data_dir, load_settings, save_settings, shellexpand_path};
+
+/// What a data directory's database holds, shown before switching to it.
+pub enum BookSummary {
+    Open {
+        company_name: Option<String>,
+        demo: bool,
+        accounts: i64,
+        transactions: i64,
+        /// (filename, import date) of the most recent import.
+        last_import: Option<(String, String)>,
+    },
+    /// Encrypted with a password other than the current book's.
+    Locked,
+}
+
+impl BookSummary {
+    pub fn read(db_path: &Path) -> Result<Self> {
+        // A plaintext database can't be opened with a key set
+        let password = if is_encrypted(db_path)? {
+            match get_db_password() {
+                Some(pw) => Some(pw),
+                None => return Ok(BookSummary::Locked),
+            }
+        } else {
+            None
+        };
+        let conn = open_connection(db_path, password.as_deref())?;
+        let count = |table: &str| -> rusqlite::Result<i64> {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |r| r.get(0))
+        };
+        let accounts = match count("accounts") {
+            Ok(n) => n,
+            Err(_) if password.is_some() => return Ok(BookSummary::Locked),
+            Err(e) => return Err(e.into()),
+        };
+        let transactions = count("transactions")?;
+        let last_import = conn
+            .query_row(
+                "SELECT filename, COALESCE(import_date, '') FROM imports ORDER BY id DESC LIMIT 1",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?;
+        Ok(BookSummary::Open {
+            company_name: get_metadata(&conn, "company_name").filter(|n| !n.is_empty()),
+            demo: crate::cli::demo::is_demo_book(&conn),
+            accounts,
+            transactions,
+            last_import,
+        })
+    }
+
+    /// Summary lines, without indentation.
+    pub fn lines(&self) -> Vec<String> {
+        let BookSummary::Open {
+            company_name,
+            demo,
+            accounts,
+            transactions,
+            last_import,
+        } = self
+        else {
+            return vec![
+                "Encrypted with a different password; you'll be asked for it on the next launch."
+                    .into(),
+            ];
+        };
+        let mut company = company_name.clone().unwrap_or_else(|| "(not set)".into());
+        if *demo {
+            company.push_str(" (demo book)");
+        }
+        let last = match last_import {
+            Some((file, date)) => {
+                let day = date.get(..10).unwrap_or(date);
+                format!("{file} on {day}")
+            }
+            None => "never".into(),
+        };
+        let mut lines = vec![
+            format!("Company:       {company}"),
+            format!("Accounts:      {accounts}"),
+            format!("Transactions:  {transactions}"),
+            format!("Last import:   {last}"),
+        ];
+        if *transactions == 0 {
+            lines.push("This book has no transactions.".into());
+        }
+        lines
+    }
+}
+
+/// Folders under the home directory where books usually live, searched when
+/// the configured data directory has gone missing.
+const SEARCH_ROOTS: &[&str] = &[
+    "Documents",
+    "Desktop",
+    "Dropbox",
+    "OneDrive",
+    "Google Drive",
+    "Library/Mobile Documents/com~apple~CloudDocs",
+    "",
+];
+
+/// How deep below each search root to look for `nigel.db`.
+const SEARCH_DEPTH: usize = 3;
+
+/// Directories never worth descending into.
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "Library", "Applications"];
+
+/// The configured data directory when it no longer exists, e.g. after the
+/// folder was renamed or the books moved to another machine. None on first
+/// run (no settings yet) or when the directory is there.
+pub fn missing_data_dir() -> Option<PathBuf> {
+    if !crate::settings::settings_file_exists() {
+        return None;
+    }
+    let dir = get_data_dir();
+    (!dir.exists()).then_some(dir)
+}
+
+/// Look for data directories holding a `nigel.db`: the common folders under
+/// `home`, a few levels deep, plus the nearest surviving parent of `missing`
+/// (a renamed folder usually stays put). Hidden directories are skipped.
+/// Newest database first.
+pub fn find_books(home: &Path, missing: &Path) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = SEARCH_ROOTS.iter().map(|r| home.join(r)).collect();
+    if let Some(parent) = missing.ancestors().skip(1).find(|p| p.is_dir()) {
+        roots.insert(0, parent.to_path_buf());
+    }
+
+    let mut found = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for root in roots {
+        let mut stack = vec![(root, 0usize)];
+        while let Some((dir, depth)) = stack.pop() {
+            if !seen.insert(dir.clone()) {
+                continue;
+            }
+            if dir.join("nigel.db").is_file() {
+                found.push(dir.clone());
+            }
+            if depth == SEARCH_DEPTH {
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with('.') || SKIP_DIRS.contains(&name.as_str()) {
+                    continue;
+                }
+                if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    stack.push((entry.path(), depth + 1));
+                }
+            }
+        }
+    }
+
+    let modified = |dir: &PathBuf| {
+        std::fs::metadata(dir.join("nigel.db"))
+            .and_then(|m| m.modified())
+            .ok()
+    };
+    found.sort_by(|a, b| modified(b).cmp(&modified(a)).then_with(|| a.cmp(b)));
+    found
+}
+
+/// The error for a command run while the data directory is missing, naming
+/// any books found elsewhere.
+pub fn missing_data_dir_error(missing: &Path) -> NigelError {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    let found = find_books(&home, missing);
+    let mut msg = format!(
+        "Data directory {} is missing (moved or renamed?).",
+        missing.display()
+    );
+    if found.is_empty() {
+        msg.push_str(
+            "\nRun `nigel` to search for it, or `nigel load <path>` if you know where it went.",
+        );
+    } else {
+        msg.push_str("\nFound books at:");
+        for dir in &found {
+            msg.push_str(&format!("\n  {}", dir.display()));
+        }
+        msg.push_str("\nRun `nigel load <path>` to switch to one, or `nigel` to choose.");
+    }
+    NigelError::Settings(msg)
+}
+
+pub fn switch_to(data_dir: &Path) -> Result<()> {
+    let mut settings = load_settings();
+    settings.data_dir = data_dir.to_string_lossy().to_string();
+    save_settings(&settings)
+}
 
 pub fn run(path: &str) -> Result<()> {
     let resolved = PathBuf::from(shellexpand_path(path));
     let db_path = resolved.join("nigel.db");
-
     if !db_path.exists() {
         return Err(NigelError::Settings(format!(
             "No database found at {}\nRun `nigel init --data-dir {}` to create one.",
@@ -14,11 +213,97 @@ pub fn run(path: &str) -> Result<()> {
             resolved.display()
         )));
     }
+    if resolved == get_data_dir() {
+        println!("Already using {}", resolved.display());
+        return Ok(());
+    }
 
-    let mut settings = load_settings();
-    settings.data_dir = resolved.to_string_lossy().to_string();
-    save_settings(&settings)?;
+    let summary = BookSummary::read(&db_path)?;
+    println!("Switch to {}", resolved.display());
+    for line in summary.lines() {
+        println!("  {line}");
+    }
+    println!();
+    print!("Load this book? [y/N] ");
+    io::stdout().flush()?;
+    let line = io::stdin()
+        .lock()
+        .lines()
+        .next()
+        .unwrap_or(Ok(String::new()))
+        .map_err(|e| NigelError::Other(e.to_string()))?;
+    if !line.trim().eq_ignore_ascii_case("y") {
+        println!("Cancelled.");
+        return Ok(());
+    }
 
+    switch_to(&resolved)?;
     println!("Switched to {}", resolved.display());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    #[test]
+    fn find_books_searches_common_folders_and_the_missing_parent() {
+        let home = tempfile::tempdir().unwrap();
+        let touch = |rel: &str| {
+            let dir = home.path().join(rel);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("nigel.db"), b"").unwrap();
+            dir
+        };
+        let moved = touch("Dropbox/books/acme");
+        let renamed = touch("work/acme-books");
+        touch(".cache/nigel");
+        touch("Documents/a/b/c/too-deep");
+
+        let found = find_books(home.path(), &home.path().join("work/acme"));
+        assert!(found.contains(&moved));
+        assert!(found.contains(&renamed));
+        assert_eq!(found.len(), 2, "{found:?}");
+    }
+
+    #[test]
+    fn summary_counts_and_last_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("nigel.db");
+        let conn = get_connection(&db_path).unwrap();
+        init_db(&conn).unwrap();
+        let lines = BookSummary::read(&db_path).unwrap().lines();
+        assert_eq!(lines[0], "Company:       (not set)");
+        assert_eq!(lines[3], "Last import:   never");
+        assert_eq!(lines[4], "This book has no transactions.");
+
+        crate::db::set_metadata(&conn, "company_name", "Acme").unwrap();
+        crate::db::set_metadata(&conn, "demo_book", "1").unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Test', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO imports (filename, account_id, import_date) VALUES ('jan.csv', 1, '2025-02-01 10:00:00')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) VALUES (1, '2025-01-15', 'X', -1.0)",
+            [],
+        )
+        .unwrap();
+        let lines = BookSummary::read(&db_path).unwrap().lines();
+        assert_eq!(
+            lines,
+            vec![
+                "Company:       Acme (demo book)",
+                "Accounts:      1",
+                "Transactions:  1",
+                "Last import:   jan.csv on 2025-02-01",
+            ]
+        );
+    }
+}
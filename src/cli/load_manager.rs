@@ -9,7 +9,11 @@ use ratatui::{
     Frame,
 };
 
-use crate::settings::{get_data_dir, load_settings, save_settings, shellexpand_path};
+use crate::cli::file_picker::{FilePicker, PickerAction};
+use crate::cli::load::{switch_to, BookSummary};
+use crate::cli::screen::{self, Nav, ScreenContext};
+use crate::error::Result;
+use crate::settings::{get_data_dir, shellexpand_path};
 use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
 
 pub enum LoadAction {
@@ -24,6 +28,10 @@ pub struct LoadScreen {
     path: String,
     status_message: Option<(String, bool)>, // (message, is_error)
     greeting: String,
+    /// Target directory and its summary, awaiting y/n.
+    confirm: Option<(PathBuf, Vec<String>)>,
+    /// Browsing for a book's `nigel.db`.
+    picker: Option<FilePicker>,
     done: bool,
 }
 
@@ -35,6 +43,8 @@ impl LoadScreen {
             path: String::new(),
             status_message: None,
             greeting: greeting.to_string(),
+            confirm: None,
+            picker: None,
             done: false,
         }
     }
@@ -59,7 +69,16 @@ impl LoadScreen {
         let sep_line = "\u{2501}".repeat(area.width as usize);
         frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
 
-        let cursor = if self.done { "" } else { "_" };
+        if let Some(picker) = &self.picker {
+            picker.draw(frame, content_area, hints_area);
+            return;
+        }
+
+        let cursor = if self.done || self.confirm.is_some() {
+            ""
+        } else {
+            "_"
+        };
 
         let mut lines = vec![
             Line::from(""),
@@ -82,6 +101,18 @@ impl LoadScreen {
             ]),
         ];
 
+        if let Some((_, summary)) = &self.confirm {
+            lines.push(Line::from(""));
+            for line in summary {
+                lines.push(Line::from(format!("   {line}")));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "   Load this book?",
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+
         if let Some((msg, is_error)) = &self.status_message {
             let color = if *is_error { Color::Red } else { Color::Green };
             lines.push(Line::from(""));
@@ -95,8 +126,10 @@ impl LoadScreen {
 
         let hints = if self.done {
             " Esc=back to dashboard"
+        } else if self.confirm.is_some() {
+            " y=load  n=cancel"
         } else {
-            " Enter=load  Esc=back"
+            " Enter=load (empty or a folder: browse)  Esc=back"
         };
         frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
     }
@@ -109,6 +142,41 @@ impl LoadScreen {
             };
         }
 
+        if let Some((target, _)) = &self.confirm {
+            match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    match switch_to(target) {
+                        Ok(()) => {
+                            self.status_message =
+                                Some((format!("Switched to {}", target.display()), false));
+                            self.done = true;
+                        }
+                        Err(e) => {
+                            self.status_message = Some((format!("Error: {e}"), true));
+                        }
+                    }
+                    self.confirm = None;
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => self.confirm = None,
+                _ => {}
+            }
+            return LoadAction::Continue;
+        }
+
+        if let Some(picker) = &mut self.picker {
+            match picker.handle_key(code) {
+                PickerAction::Continue => return LoadAction::Continue,
+                PickerAction::Cancel => self.picker = None,
+                PickerAction::Picked(db_path) => {
+                    self.picker = None;
+                    let dir = db_path.parent().unwrap_or(&db_path);
+                    self.path = dir.to_string_lossy().into_owned();
+                    self.submit();
+                }
+            }
+            return LoadAction::Continue;
+        }
+
         match code {
             KeyCode::Esc => LoadAction::Close,
             KeyCode::Char(c) => {
@@ -122,36 +190,63 @@ impl LoadScreen {
                 LoadAction::Continue
             }
             KeyCode::Enter => {
-                let trimmed = self.path.trim().to_string();
-                if trimmed.is_empty() {
-                    self.status_message = Some(("Path is required".into(), true));
-                    return LoadAction::Continue;
-                }
+                self.submit();
+                LoadAction::Continue
+            }
+            _ => LoadAction::Continue,
+        }
+    }
 
-                let resolved = PathBuf::from(shellexpand_path(&trimmed));
-                let db_path = resolved.join("nigel.db");
+    fn open_picker(&mut self, start: &std::path::Path) {
+        self.status_message = None;
+        self.picker = Some(FilePicker::new("Choose a book's nigel.db", start, &["db"]));
+    }
 
-                if !db_path.exists() {
-                    self.status_message =
-                        Some((format!("No database found at {}", db_path.display()), true));
-                    return LoadAction::Continue;
-                }
+    /// Check the typed folder holds a book and ask to load it. An empty
+    /// path, or a folder without a book, opens the picker there instead.
+    fn submit(&mut self) {
+        let trimmed = self.path.trim().to_string();
+        if trimmed.is_empty() {
+            // Books usually sit side by side, so start beside this one
+            let current = PathBuf::from(&self.current_dir);
+            self.open_picker(current.parent().unwrap_or(&current));
+            return;
+        }
 
-                let mut settings = load_settings();
-                settings.data_dir = resolved.to_string_lossy().to_string();
-                match save_settings(&settings) {
-                    Ok(()) => {
-                        self.status_message =
-                            Some((format!("Switched to {}", resolved.display()), false));
-                        self.done = true;
-                    }
-                    Err(e) => {
-                        self.status_message = Some((format!("Error: {e}"), true));
-                    }
-                }
-                LoadAction::Continue
+        let resolved = PathBuf::from(shellexpand_path(&trimmed));
+        let db_path = resolved.join("nigel.db");
+
+        if !db_path.exists() {
+            if resolved.is_dir() {
+                self.open_picker(&resolved);
+            } else {
+                self.status_message =
+                    Some((format!("No database found at {}", db_path.display()), true));
             }
-            _ => LoadAction::Continue,
+            return;
+        }
+        if resolved.to_string_lossy() == self.current_dir {
+            self.status_message = Some(("That's the current data file".into(), true));
+            return;
+        }
+
+        match BookSummary::read(&db_path) {
+            Ok(summary) => self.confirm = Some((resolved, summary.lines())),
+            Err(e) => self.status_message = Some((format!("Error: {e}"), true)),
         }
     }
 }
+
+impl screen::Screen for LoadScreen {
+    fn draw(&mut self, frame: &mut Frame) {
+        LoadScreen::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, _ctx: &mut ScreenContext) -> Result<Nav> {
+        Ok(match LoadScreen::handle_key(self, code) {
+            LoadAction::Close => Nav::Pop,
+            LoadAction::Reload => Nav::Reload,
+            LoadAction::Continue => Nav::Stay,
+        })
+    }
+}
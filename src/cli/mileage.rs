@@ -0,0 +1,59 @@
+use comfy_table::{Cell, Table};
+
+use crate::db::get_connection;
+use crate::deductions;
+use crate::error::Result;
+use crate::fmt::money;
+use crate::settings::get_data_dir;
+
+pub fn add(date: &str, miles: f64, purpose: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let id = deductions::add_mileage(&conn, date, miles, purpose)?;
+    let rate = deductions::mileage_rate(date);
+    println!(
+        "Logged trip {id}: {miles} mi on {date} ({} at ${rate:.3}/mi)",
+        money(miles * rate)
+    );
+    Ok(())
+}
+
+pub fn list(year: Option<i32>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let trips = deductions::list_mileage(&conn, year)?;
+    if trips.is_empty() {
+        println!("No mileage logged.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["ID", "Date", "Purpose", "Miles", "Rate", "Deduction"]);
+    for t in &trips {
+        table.add_row(vec![
+            Cell::new(t.id),
+            Cell::new(&t.date),
+            Cell::new(&t.purpose),
+            Cell::new(format!("{:.1}", t.miles)),
+            Cell::new(format!("${:.3}", t.rate)),
+            Cell::new(money(t.deduction)),
+        ]);
+    }
+    let miles: f64 = trips.iter().map(|t| t.miles).sum();
+    let total: f64 = trips.iter().map(|t| t.deduction).sum();
+    table.add_row(vec![
+        Cell::new(""),
+        Cell::new("Total"),
+        Cell::new(""),
+        Cell::new(format!("{miles:.1}")),
+        Cell::new(""),
+        Cell::new(money(total)),
+    ]);
+    println!("Mileage Log\n{table}");
+    Ok(())
+}
+
+pub fn delete(id: i64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    deductions::delete_mileage(&conn, id)?;
+    println!("Deleted mileage entry {id}");
+    Ok(())
+}
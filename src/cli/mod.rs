@@ -1,38 +1,114 @@
 pub mod account_manager;
 pub mod accounts;
+pub mod alerts;
+pub mod archive;
 pub mod backup;
 pub mod browse;
 pub mod categories;
 pub mod categorize;
 pub mod category_manager;
+pub mod clients;
+pub mod close;
+pub mod config;
 pub mod dashboard;
+pub mod dashboard_widgets;
+pub mod db;
 pub mod demo;
+pub mod digest;
+pub mod docs_viewer;
 pub mod export;
+pub mod file_picker;
 pub mod goodbye;
 pub mod import;
+pub mod import_history;
 pub mod import_manager;
+pub mod inbox_zero;
 pub mod init;
+pub mod limits;
 pub mod load;
 pub mod load_manager;
+pub mod mileage;
 pub mod onboarding;
+pub mod package;
+pub mod palette;
 pub mod password;
 pub mod password_manager;
+pub mod payplan;
+pub mod query;
+pub mod rates;
 pub mod reconcile;
 pub mod reconcile_manager;
+pub mod reimbursables;
+pub mod relocate;
 pub mod report;
 pub mod restore;
 pub mod review;
 pub mod rules;
+pub mod rules_cleanup;
 pub mod rules_manager;
+pub mod schedule;
+pub mod screen;
 pub mod settings_manager;
-pub mod snake;
+pub mod setup_docs;
+pub mod shareholders;
+pub mod sharing;
+pub mod signoff;
 pub mod splash;
 pub mod status;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod trash;
+pub mod tutorial;
 pub mod undo;
 pub mod undo_manager;
 pub mod update;
 
-use clap::{Args, Parser, Subcommand};
+use std::io::{IsTerminal, Write};
+
+use clap::{Args, CommandFactory, Parser, Subcommand};
+
+use crate::error::Result;
+use crate::settings::{check_settings, quarantine_corrupt_settings, SettingsHealth};
+
+/// Detect a corrupt settings.json at startup and offer to restore the last good
+/// version from settings.json.bak. Declining (or having no backup) moves the
+/// corrupt file aside so Nigel starts from defaults instead of silently
+/// overwriting it. Non-interactive runs leave the file alone; `load_settings()`
+/// reads the backup in memory.
+pub fn recover_corrupt_settings() -> Result<()> {
+    let SettingsHealth::Corrupt { backup } = check_settings() else {
+        return Ok(());
+    };
+    eprintln!("Warning: settings.json could not be read (it may have been truncated).");
+    if !std::io::stdin().is_terminal() {
+        if backup.is_some() {
+            eprintln!("Using settings.json.bak for this run.");
+        }
+        return Ok(());
+    }
+    if let Some(backup) = backup {
+        eprintln!("A backup from the last successful save is available:");
+        eprintln!("  Data directory: {}", backup.data_dir);
+        if !backup.user_name.is_empty() {
+            eprintln!("  User name:      {}", backup.user_name);
+        }
+        eprint!("Restore settings from backup? [Y/n] ");
+        std::io::stderr().flush()?;
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("n") {
+            crate::settings::restore_settings_backup()?;
+            eprintln!("Settings restored from backup.");
+            return Ok(());
+        }
+    }
+    let moved = quarantine_corrupt_settings()?;
+    eprintln!(
+        "Moved unreadable settings to {}; starting from defaults.",
+        moved.display()
+    );
+    Ok(())
+}
 
 pub(crate) fn parse_month_opt(month: &Option<String>) -> (Option<i32>, Option<u32>) {
     if let Some(m) = month {
@@ -52,10 +128,49 @@ pub(crate) fn parse_month_opt(month: &Option<String>) -> (Option<i32>, Option<u3
     about = "Cash-basis bookkeeping CLI for small consultancies."
 )]
 pub struct Cli {
+    /// Skip the splash and goodbye animations when opening the dashboard.
+    #[arg(long = "no-splash")]
+    pub no_splash: bool,
+    /// Open the book read-only: reports and browsing work, edits are refused.
+    #[arg(long = "read-only", global = true)]
+    pub read_only: bool,
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+/// Commands and report types left out of `nigel --help` in simple mode.
+/// They still run when typed.
+const ADVANCED_COMMANDS: &[&str] = &["reconcile", "assert-balance", "shareholders"];
+const ADVANCED_REPORTS: &[&str] = &["k1", "tags"];
+
+/// The command line definition, with reconciliation, shareholders, K-1
+/// prep, the tags report, and rule priorities hidden from help when `simple_mode` is on.
+pub fn command(simple_mode: bool) -> clap::Command {
+    let cmd = Cli::command();
+    if !simple_mode {
+        return cmd;
+    }
+    let hide = |c: clap::Command| c.hide(true);
+    let hide_priority = |c: clap::Command| c.mut_arg("priority", |a| a.hide(true));
+    ADVANCED_COMMANDS
+        .iter()
+        .fold(cmd, |cmd, name| cmd.mut_subcommand(name, hide))
+        .mut_subcommand("report", |report| {
+            ADVANCED_REPORTS
+                .iter()
+                .fold(report, |report, name| report.mut_subcommand(name, hide))
+        })
+        .mut_subcommand("rules", |rules| {
+            rules
+                .mut_subcommand("add", hide_priority)
+                .mut_subcommand("update", hide_priority)
+        })
+        .after_help(
+            "Simple mode hides reconciliation, shareholders, K-1 prep, tags, and rule priorities. \
+             Turn it off on the Settings screen or set \"simple_mode\": false in settings.json.",
+        )
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Set up Nigel: choose a data directory and initialize the database.
@@ -63,6 +178,9 @@ pub enum Commands {
         /// Path for Nigel data (default: ~/Documents/nigel)
         #[arg(long = "data-dir")]
         data_dir: Option<String>,
+        /// Starting categories for a new book: s-corp (default), schedule-c, partnership, or nonprofit
+        #[arg(long)]
+        template: Option<String>,
     },
     /// Manage accounts.
     Accounts {
@@ -76,12 +194,14 @@ pub enum Commands {
     },
     /// Import a CSV/XLSX file and auto-categorize transactions.
     Import {
-        /// Path to CSV or XLSX file to import
+        /// Path to CSV, XLSX, or PDF statement file to import
         file: String,
         /// Account name to import into
         #[arg(long)]
         account: String,
-        /// Importer format key (e.g. bofa_checking, or a saved profile name)
+        /// Importer format key (e.g. bofa_checking, or a saved profile name);
+        /// remembered for the account, so later imports can leave it off.
+        /// `auto` detects the format and forgets the remembered one
         #[arg(long)]
         format: Option<String>,
         /// Preview import without writing to database
@@ -96,12 +216,26 @@ pub enum Commands {
         /// Column index for amount (0-based, used with generic CSV)
         #[arg(long)]
         amount_col: Option<usize>,
-        /// Date format string (default: %m/%d/%Y, used with generic CSV)
+        /// Date format string (default: %m/%d/%Y, or %d/%m/%Y with the
+        /// DD/MM/YYYY `date_format` setting; used with generic CSV)
         #[arg(long)]
         date_format: Option<String>,
         /// Save column mapping as a reusable profile name
         #[arg(long)]
         save_profile: Option<String>,
+        /// Keep a copy of the file in <data_dir>/statements/ (always on with
+        /// `keep_statements` in settings.json)
+        #[arg(long)]
+        keep_file: bool,
+        /// Import again even if this exact file was imported before; creates
+        /// a new batch (rows already in the register are still skipped)
+        #[arg(long)]
+        force_reimport: bool,
+        /// Split a Gusto payroll export into net pay, employee withholding,
+        /// benefit deductions, and reimbursements (always on with
+        /// `gusto_detailed` in settings.json)
+        #[arg(long)]
+        detailed: bool,
     },
     /// Re-run categorization rules on uncategorized transactions.
     Categorize,
@@ -112,27 +246,108 @@ pub enum Commands {
     },
     /// Interactively review flagged transactions.
     Review {
+        #[command(subcommand)]
+        command: Option<ReviewCommands>,
         /// Review a specific transaction by ID.
         #[arg(long)]
         id: Option<i64>,
+        /// Review similar transactions in groups, one category per group.
+        #[arg(long, conflicts_with = "id")]
+        batch: bool,
     },
     /// Generate, view, or export reports.
     Report {
         #[command(subcommand)]
         command: ReportCommands,
     },
-    /// Load sample data (account, transactions, rules) to explore Nigel.
-    Demo,
+    /// Load sample data (account, transactions, rules) into a separate demo
+    /// book to explore Nigel.
+    Demo {
+        /// Load the sample data into the current book instead
+        #[arg(long = "in-place", conflicts_with = "clean")]
+        in_place: bool,
+        /// Remove sample data previously loaded into the current book
+        #[arg(long)]
+        clean: bool,
+    },
+    /// Walk through importing, reviewing, rules, and reports on the demo
+    /// book with step-by-step help in the dashboard.
+    Tutorial,
     /// Reconcile an account against a statement balance.
     Reconcile {
         /// Account name
-        account: String,
+        #[arg(required_unless_present_any = ["notes", "resolve", "tui"])]
+        account: Option<String>,
         /// Month: YYYY-MM
-        #[arg(long)]
-        month: String,
+        #[arg(long, required_unless_present_any = ["notes", "resolve", "tui"])]
+        month: Option<String>,
         /// Statement ending balance
+        #[arg(long, required_unless_present_any = ["notes", "resolve", "tui"])]
+        balance: Option<f64>,
+        /// Accept a difference for now with this note (shown on the
+        /// dashboard until the month balances or the note is resolved)
         #[arg(long)]
-        balance: f64,
+        note: Option<String>,
+        /// Follow-up date for the note: YYYY-MM-DD
+        #[arg(long = "follow-up", requires = "note")]
+        follow_up: Option<String>,
+        /// List unresolved reconciliation notes
+        #[arg(long, conflicts_with_all = ["account", "month", "balance", "note", "resolve"])]
+        notes: bool,
+        /// Mark a reconciliation note resolved by ID
+        #[arg(long, conflicts_with_all = ["account", "month", "balance", "note"])]
+        resolve: Option<i64>,
+        /// Open the reconcile screen instead, with any account and month
+        /// filled in
+        #[arg(long, conflicts_with_all = ["balance", "note", "notes", "resolve"])]
+        tui: bool,
+    },
+    /// Close a month once nothing in it is flagged and every account with
+    /// activity has balanced: writes a close packet (P&L, cash flow,
+    /// reconciliation certificates) to closes/ and records it.
+    Close {
+        /// Month: YYYY-MM (default: last month)
+        #[arg(long)]
+        month: Option<String>,
+        /// Packet format: pdf (default when built with PDF support) or html
+        #[arg(long)]
+        format: Option<String>,
+        /// List months already closed
+        #[arg(long, conflicts_with_all = ["month", "format"])]
+        list: bool,
+    },
+    /// Walk the year's category totals before filing, largest first, with
+    /// the transactions behind each, and sign off on each one.
+    Signoff {
+        /// Fiscal year (default: last year)
+        #[arg(long)]
+        year: Option<i32>,
+        /// Print each category's sign-off instead of opening the review
+        #[arg(long)]
+        list: bool,
+    },
+    /// Assert an account's balance on a date, e.g. from a statement. The
+    /// dashboard warns whenever the books stop agreeing with it.
+    AssertBalance {
+        /// Account name
+        #[arg(required_unless_present_any = ["list", "remove"])]
+        account: Option<String>,
+        /// Date: YYYY-MM-DD (the balance at the end of that day)
+        #[arg(long, required_unless_present_any = ["list", "remove"])]
+        date: Option<String>,
+        /// Expected balance
+        #[arg(
+            long,
+            allow_negative_numbers = true,
+            required_unless_present_any = ["list", "remove"]
+        )]
+        balance: Option<f64>,
+        /// List assertions and whether the books still agree
+        #[arg(long, conflicts_with_all = ["account", "date", "balance", "remove"])]
+        list: bool,
+        /// Remove an assertion by ID
+        #[arg(long, conflicts_with_all = ["account", "date", "balance"])]
+        remove: Option<i64>,
     },
     /// Switch to an existing Nigel data directory.
     Load {
@@ -141,14 +356,35 @@ pub enum Commands {
     },
     /// Back up the database.
     Backup {
+        #[command(subcommand)]
+        command: Option<BackupCommands>,
         /// Output path (default: <data_dir>/backups/nigel-YYYYMMDD-HHMMSS.db)
         #[arg(long)]
         output: Option<String>,
+        /// Also copy the backup here: a folder (e.g. one Dropbox syncs) or
+        /// s3://bucket/prefix (default: the backup_destination setting)
+        #[arg(long)]
+        to: Option<String>,
+        /// Encrypt the copy with a passphrase (default: the backup_encrypt setting)
+        #[arg(long)]
+        encrypt: bool,
+        /// Skip the copy to the backup_destination setting
+        #[arg(long = "local-only", conflicts_with_all = ["to", "encrypt"])]
+        local_only: bool,
     },
     /// Restore a database from a backup file.
     Restore {
         /// Path to the backup file to restore
         path: String,
+        /// Restore only the rows the book is missing, keeping work added since the backup
+        #[arg(long)]
+        merge: bool,
+        /// With --merge, also overwrite rows that differ from the backup
+        #[arg(long = "overwrite-changed", requires = "merge")]
+        overwrite_changed: bool,
+        /// With --merge, show what would be restored without saving
+        #[arg(long = "dry-run", requires = "merge")]
+        dry_run: bool,
     },
     /// Interactively browse data.
     Browse {
@@ -156,7 +392,12 @@ pub enum Commands {
         command: BrowseCommands,
     },
     /// Show current database and summary statistics.
-    Status,
+    Status {
+        /// Print a one-line JSON health summary for shell prompts and scripts
+        /// (never prompts for a password)
+        #[arg(long)]
+        badge: bool,
+    },
     /// Manage database password (encrypt, change, or remove).
     Password {
         #[command(subcommand)]
@@ -166,6 +407,123 @@ pub enum Commands {
     Undo,
     /// Check for and install updates from GitHub Releases.
     Update,
+    /// Manage the home currency and exchange rates.
+    Rates {
+        #[command(subcommand)]
+        command: RatesCommands,
+    },
+    /// Log business mileage for the standard mileage deduction.
+    Mileage {
+        #[command(subcommand)]
+        command: MileageCommands,
+    },
+    /// Move closed years into per-year archive databases.
+    Archive {
+        #[command(subcommand)]
+        command: ArchiveCommands,
+    },
+    /// Export reports automatically on a monthly, quarterly, or yearly cadence.
+    Schedule {
+        #[command(subcommand)]
+        command: ScheduleCommands,
+    },
+    /// Warn when monthly spend with a vendor goes over a limit or creeps up.
+    Alerts {
+        #[command(subcommand)]
+        command: AlertsCommands,
+    },
+    /// Per-person yearly caps on what a category can deduct, such as $25 per
+    /// recipient for business gifts, checked in the tax summary.
+    Limits {
+        #[command(subcommand)]
+        command: LimitsCommands,
+    },
+    /// Work out how much is safe to pay yourself this month, and track
+    /// planned vs. actual distributions.
+    Payplan {
+        #[command(subcommand)]
+        command: PayplanCommands,
+    },
+    /// Track expenses to bill back to clients through invoicing and
+    /// repayment (see `nigel report reimbursables`).
+    Reimbursable {
+        #[command(subcommand)]
+        command: ReimbursableCommands,
+    },
+    /// Assign income and expenses to clients or projects for per-client
+    /// margins (see `nigel report clients`).
+    Clients {
+        #[command(subcommand)]
+        command: ClientsCommands,
+    },
+    /// S-corp owners and their stakes, for splitting the K-1 worksheet
+    /// (see `nigel report k1`).
+    Shareholders {
+        #[command(subcommand)]
+        command: ShareholdersCommands,
+    },
+    /// Trashed transactions (`D` in the browser): list, restore, or delete
+    /// them for good. Emptied automatically after `trash_retention_days`.
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommands,
+    },
+    /// A one-page monthly summary: money in and out, biggest vendors, what
+    /// changed since the month before, and what still needs review.
+    Digest {
+        /// Month: YYYY-MM (default: last month)
+        #[arg(long)]
+        month: Option<String>,
+        /// Format: text (default), markdown, or pdf
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Write to this file instead of printing (PDFs default to exports/)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Bundle the books for someone else, such as your accountant.
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+    /// Run one SQL statement against the database (not archived years) and
+    /// print the results. Read-only unless --allow-writes is given.
+    Query {
+        /// SQL to run, or the name of a saved query
+        #[arg(required_unless_present_any = ["list", "forget"])]
+        sql: Option<String>,
+        /// Format: text (default), csv, or json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Allow statements that change the database (a snapshot is taken first)
+        #[arg(long)]
+        allow_writes: bool,
+        /// Save the SQL under this name in settings.json instead of running it
+        #[arg(long, requires = "sql")]
+        save: Option<String>,
+        /// List saved queries
+        #[arg(long, conflicts_with_all = ["sql", "forget"])]
+        list: bool,
+        /// Delete a saved query
+        #[arg(long, conflicts_with = "sql")]
+        forget: Option<String>,
+    },
+    /// Read or change settings, such as per-report defaults.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Describe the database for scripts and other tools that read it.
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+    /// Pull transactions directly from your bank via SimpleFIN Bridge.
+    #[cfg(feature = "sync")]
+    Sync {
+        #[command(subcommand)]
+        command: SyncCommands,
+    },
     /// Generate shell completions script.
     Completions {
         /// Shell: bash, zsh, fish, powershell
@@ -185,6 +543,8 @@ pub enum PasswordCommand {
 
 #[derive(Subcommand)]
 pub enum AccountsCommands {
+    /// Open the account manager screen on its own.
+    Manage,
     /// Add a new account.
     Add {
         /// Account name, e.g. 'BofA Checking'
@@ -198,6 +558,15 @@ pub enum AccountsCommands {
         /// Last 4 digits of account number
         #[arg(long = "last-four")]
         last_four: Option<String>,
+        /// Currency code, e.g. EUR (default: the home currency)
+        #[arg(long)]
+        currency: Option<String>,
+        /// Balance before the first transaction on --opening-date
+        #[arg(long = "opening-balance", allow_negative_numbers = true)]
+        opening_balance: Option<f64>,
+        /// Date the opening balance applies from: YYYY-MM-DD
+        #[arg(long = "opening-date", requires = "opening_balance")]
+        opening_date: Option<String>,
     },
     /// List all accounts.
     List,
@@ -213,17 +582,481 @@ pub enum AccountsCommands {
         /// Account ID
         id: i64,
     },
+    /// Archive a closed account: hidden from the import and reconcile pickers
+    /// and the balance report; its transactions stay in historical reports.
+    Archive {
+        /// Account name
+        name: String,
+    },
+    /// Bring an archived account back.
+    Unarchive {
+        /// Account name
+        name: String,
+    },
+    /// Set an account's opening balance. Transactions before --date are
+    /// treated as already counted in it.
+    OpeningBalance {
+        /// Account name
+        name: String,
+        /// Balance before the first transaction on --date
+        #[arg(long, allow_negative_numbers = true)]
+        balance: f64,
+        /// Date the balance applies from: YYYY-MM-DD (default: the beginning)
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Store the full account and routing numbers (prompted, hidden input).
+    /// Requires a password-protected database.
+    SetNumber {
+        /// Account name
+        name: String,
+    },
+    /// Show the full account and routing numbers after password confirmation.
+    Reveal {
+        /// Account name
+        name: String,
+    },
+    /// Remove stored account and routing numbers.
+    ClearNumber {
+        /// Account name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MileageCommands {
+    /// Log a business trip.
+    Add {
+        /// Trip date: YYYY-MM-DD
+        #[arg(long)]
+        date: String,
+        /// Miles driven
+        #[arg(long)]
+        miles: f64,
+        /// Business purpose of the trip
+        #[arg(long)]
+        purpose: String,
+    },
+    /// List logged trips with the standard-rate deduction for each.
+    List {
+        /// Year filter: YYYY
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    /// Delete a logged trip.
+    Delete {
+        /// Trip ID (shown in `nigel mileage list`)
+        id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ArchiveCommands {
+    /// Archive a closed fiscal year (moves its transactions out of nigel.db).
+    Create {
+        /// Fiscal year: YYYY
+        year: i32,
+    },
+    /// List archived years.
+    List,
+    /// Move an archived year back into nigel.db.
+    Restore {
+        /// Fiscal year: YYYY
+        year: i32,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AlertsCommands {
+    /// Add a vendor alert, e.g. `"Adobe" --over 100 --increase 10`.
+    Add {
+        /// Vendor name; also matches transactions whose description contains it
+        vendor: String,
+        /// Warn when this month's spend with the vendor is over this amount
+        #[arg(long)]
+        over: Option<f64>,
+        /// Warn when this month's spend is more than this percent above the
+        /// vendor's 6-month average
+        #[arg(long)]
+        increase: Option<f64>,
+    },
+    /// List vendor alerts with this month's spend and the 6-month average.
+    List,
+    /// Remove a vendor alert.
+    Remove {
+        /// Alert ID (shown in `nigel alerts list`)
+        id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LimitsCommands {
+    /// Cap a category per person, e.g. `"Client Gifts" --per-person 25`.
+    Add {
+        /// Expense category name or ID
+        category: String,
+        /// Most deductible per tagged recipient per year
+        #[arg(long)]
+        per_person: f64,
+        /// Why the limit applies, e.g. "IRC 274(b) business gifts"
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// List limits with the year's spend per person and any warnings.
+    List {
+        /// Fiscal year (default: current)
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    /// Remove a limit.
+    Remove {
+        /// Limit ID (shown in `nigel limits list`)
+        id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PayplanCommands {
+    /// Show the safe distribution for a month and the math behind it.
+    Show {
+        /// Month: YYYY-MM (default: this month)
+        #[arg(long)]
+        month: Option<String>,
+    },
+    /// Set the planner inputs: annual salary, tax rate, and cash reserve.
+    Set {
+        /// Annual officer salary paid through payroll
+        #[arg(long)]
+        salary: Option<f64>,
+        /// Percent of profit set aside for estimated taxes (default 30)
+        #[arg(long = "tax-rate")]
+        tax_rate: Option<f64>,
+        /// Cash to keep in the bank before any distribution
+        #[arg(long)]
+        reserve: Option<f64>,
+    },
+    /// Record the distribution you plan to take in a month.
+    Plan {
+        /// Planned amount
+        #[arg(required_unless_present = "clear")]
+        amount: Option<f64>,
+        /// Month: YYYY-MM (default: this month)
+        #[arg(long)]
+        month: Option<String>,
+        /// Remove the month's plan instead
+        #[arg(long, conflicts_with = "amount")]
+        clear: bool,
+    },
+    /// Planned vs. actual distributions for each month of a fiscal year.
+    History {
+        /// Fiscal year (default: the current one)
+        #[arg(long)]
+        year: Option<i32>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReimbursableCommands {
+    /// Mark expenses as reimbursable by a client, e.g. `412 415 --client acme`.
+    /// The client is also added to each transaction as a tag.
+    Mark {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
+        /// Client, in tag form (`acme` or `#client-acme`)
+        #[arg(long)]
+        client: String,
+    },
+    /// Stop tracking expenses as reimbursable.
+    Unmark {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
+    },
+    /// Record that expenses were billed to the client.
+    Invoice {
+        /// Transaction IDs
+        #[arg(required_unless_present = "client")]
+        ids: Vec<i64>,
+        /// Every expense for this client not yet invoiced, instead of IDs
+        #[arg(long, conflicts_with = "ids")]
+        client: Option<String>,
+        /// Invoice number or reference
+        #[arg(long = "ref")]
+        reference: Option<String>,
+        /// Date invoiced: YYYY-MM-DD (default: today)
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Record that the client paid expenses back.
+    Paid {
+        /// Transaction IDs
+        #[arg(required_unless_present = "client")]
+        ids: Vec<i64>,
+        /// Every outstanding expense for this client, instead of IDs
+        #[arg(long, conflicts_with = "ids")]
+        client: Option<String>,
+        /// Date repaid: YYYY-MM-DD (default: today)
+        #[arg(long)]
+        date: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ClientsCommands {
+    /// List clients with how many transactions each has.
+    List,
+    /// Add a client.
+    Add {
+        /// Client or project name
+        name: String,
+    },
+    /// Delete a client; its transactions become unassigned.
+    Remove {
+        /// Client or project name
+        name: String,
+    },
+    /// Assign transactions to a client, e.g. `412 415 --client "Acme Corp"`.
+    /// A name not seen before adds the client.
+    Assign {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
+        #[arg(long)]
+        client: String,
+    },
+    /// Unassign transactions from their client.
+    Unassign {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ShareholdersCommands {
+    /// List shareholders, largest stake first.
+    List,
+    /// Add a shareholder, e.g. `"Jane Doe" --pct 60`.
+    Add {
+        /// Shareholder name
+        name: String,
+        /// Ownership percentage; all stakes together can't pass 100
+        #[arg(long)]
+        pct: f64,
+    },
+    /// Change a shareholder's ownership percentage.
+    Set {
+        /// Shareholder name
+        name: String,
+        #[arg(long)]
+        pct: f64,
+    },
+    /// Delete a shareholder.
+    Remove {
+        /// Shareholder name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TrashCommands {
+    /// List trashed transactions, most recently trashed first.
+    List,
+    /// Take transactions back out of the trash.
+    Restore {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
+    },
+    /// Permanently delete everything in the trash (asks first).
+    Empty,
+}
+
+#[derive(Subcommand)]
+pub enum ScheduleCommands {
+    /// Schedule a report export, e.g. `--report pnl --cadence monthly`.
+    Add {
+        /// Report: pnl, expenses, tax, cashflow, register, flagged, balance,
+        /// k1, deductions, compensation, tags, clients, reimbursables
+        #[arg(long)]
+        report: String,
+        /// Cadence: monthly, quarterly, or yearly (quarters and years follow
+        /// the fiscal year)
+        #[arg(long)]
+        cadence: String,
+        /// Export format: pdf or text; the register and k1 also take csv or
+        /// xlsx, and k1 takes json
+        #[arg(long, default_value = "pdf")]
+        format: String,
+        /// Directory the reports are written to
+        #[arg(long = "output-dir")]
+        output_dir: String,
+    },
+    /// List scheduled reports with their next run and last result.
+    List,
+    /// Remove a scheduled report.
+    Remove {
+        /// Schedule ID (shown in `nigel schedule list`)
+        id: i64,
+    },
+    /// Generate every scheduled report that is due (run this from cron).
+    Run,
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommands {
+    /// One ZIP for tax prep: report PDFs, transactions and K-1 lines as CSV,
+    /// the chart of accounts, reconciliations, a statement manifest, and a
+    /// cover README.
+    Package {
+        /// Fiscal year (default: the last full fiscal year)
+        #[arg(long)]
+        year: Option<i32>,
+        /// Output file (default: exports/package-fy<year>-<date>.zip)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// The chart of accounts with its tax mappings and the active rules,
+    /// as a document for a new bookkeeper or the corporate records binder.
+    SetupDocs {
+        /// Format: text (default) or pdf
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Write to this file instead of printing (PDFs default to exports/)
+        #[arg(long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// List the settings made with `config set`.
+    List,
+    /// Print one setting.
+    Get {
+        /// Setting key, e.g. report.register.default_period
+        key: String,
+    },
+    /// Change a setting. Report keys are report.<report>.<field>, with a
+    /// field of default_period (month, last_month, year, prior_year),
+    /// default_until (1-12), default_account (register only), or
+    /// default_format (pdf, text; csv and xlsx for the register and k1).
+    Set {
+        /// Setting key, e.g. report.register.default_period
+        key: String,
+        /// New value, e.g. month
+        value: String,
+    },
+    /// Clear a setting back to the built-in default.
+    Unset {
+        /// Setting key, e.g. report.pnl.default_format
+        key: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Print the schema, with a description of every table and column and
+    /// the schema version, so scripts can adapt when migrations change it.
+    Schema {
+        /// Format: json (default) or sql
+        #[arg(long, default_value = "json")]
+        format: String,
+    },
+    /// Compact the database (VACUUM), rebuild its indexes, refresh query
+    /// statistics (ANALYZE), and prune old snapshots and backups.
+    Maintain {
+        /// Only run if `maintenance_days` have passed since the last run
+        /// (for cron; `nigel schedule run` checks this too)
+        #[arg(long = "if-due")]
+        if_due: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BackupCommands {
+    /// Read a copy back from the backup destination and check its checksum.
+    Verify {
+        /// Copy to check, e.g. nigel-20250301-120000.db (default: the last one copied)
+        name: Option<String>,
+        /// Destination to check (default: the backup_destination setting)
+        #[arg(long)]
+        to: Option<String>,
+    },
+}
+
+#[cfg(feature = "sync")]
+#[derive(Subcommand)]
+pub enum SyncCommands {
+    /// Connect a SimpleFIN Bridge account with a setup token.
+    Setup {
+        /// Setup token from SimpleFIN Bridge (prompted, hidden, if omitted)
+        token: Option<String>,
+    },
+    /// List synced accounts and the Nigel accounts they import into.
+    Accounts,
+    /// Map a synced account to a Nigel account.
+    Map {
+        /// Synced account ID (shown in `nigel sync accounts`)
+        id: i64,
+        /// Nigel account name
+        account: Option<String>,
+        /// Stop syncing this account
+        #[arg(long, conflicts_with = "account")]
+        unmap: bool,
+    },
+    /// Fetch new transactions for mapped accounts, then categorize them.
+    Run {
+        /// Show what would be imported without writing
+        #[arg(long)]
+        dry_run: bool,
+        /// Ignore saved cursors and re-fetch the full window (duplicates are skipped)
+        #[arg(long)]
+        full: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RatesCommands {
+    /// List recorded exchange rates.
+    List,
+    /// Record an exchange rate (home-currency units per one unit of CURRENCY).
+    Set {
+        /// Currency code, e.g. EUR
+        currency: String,
+        /// Home-currency value of one unit of CURRENCY
+        rate: f64,
+        /// Effective date: YYYY-MM-DD (default: today)
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Delete a recorded exchange rate.
+    Delete {
+        /// Currency code, e.g. EUR
+        currency: String,
+        /// Effective date of the rate: YYYY-MM-DD
+        #[arg(long)]
+        date: String,
+    },
+    /// Show or set the home currency that reports convert into.
+    Home {
+        /// New home currency code (omit to show the current one)
+        currency: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum CategoriesCommands {
     /// List all categories.
     List,
+    /// Open the category manager screen on its own.
+    Manage,
     /// Add a new category.
     Add {
         /// Category name
         name: String,
-        /// Category type: income or expense
+        /// Category type: income, expense, or transfer (kept off the P&L)
         #[arg(long = "type")]
         category_type: String,
         /// IRS tax line mapping
@@ -232,6 +1065,9 @@ pub enum CategoriesCommands {
         /// Form 1120-S line mapping
         #[arg(long = "form-line")]
         form_line: Option<String>,
+        /// Parent category ID (makes this a sub-category)
+        #[arg(long)]
+        parent: Option<i64>,
     },
     /// Rename a category by ID.
     Rename {
@@ -246,7 +1082,7 @@ pub enum CategoriesCommands {
         id: i64,
         /// Category name
         name: String,
-        /// Category type: income or expense
+        /// Category type: income, expense, or transfer (kept off the P&L)
         #[arg(long = "type")]
         category_type: String,
         /// IRS tax line mapping
@@ -255,16 +1091,74 @@ pub enum CategoriesCommands {
         /// Form 1120-S line mapping
         #[arg(long = "form-line")]
         form_line: Option<String>,
+        /// Parent category ID (makes this a sub-category)
+        #[arg(long)]
+        parent: Option<i64>,
     },
     /// Delete (deactivate) a category by ID.
     Delete {
         /// Category ID
         id: i64,
     },
+    /// Fold one category into another: its transactions, rules, and
+    /// sub-categories move over and it's removed. Asks first when the
+    /// types differ.
+    Merge {
+        /// Category to merge away (ID or name)
+        from: String,
+        /// Category that takes its place (ID or name)
+        into: String,
+    },
+    /// Show or set the expense category for line-of-credit interest and fees.
+    LocInterest {
+        /// Category name (omit to show the current one)
+        category: Option<String>,
+    },
+    /// Write the chart of accounts to a .toml or .json file.
+    Export {
+        /// Output file path (.toml or .json)
+        path: String,
+    },
+    /// Load a chart of accounts from a .toml or .json file.
+    Import {
+        /// Chart file path (.toml or .json)
+        path: String,
+        /// Add missing categories and leave existing ones alone (default)
+        #[arg(long, conflicts_with = "replace")]
+        merge: bool,
+        /// Make the book match the file: update differing categories and
+        /// remove unused ones the file doesn't list
+        #[arg(long)]
+        replace: bool,
+        /// Report what would change without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReviewCommands {
+    /// Write flagged transactions to a CSV with category, vendor, and memo
+    /// columns to fill in, for review in a spreadsheet.
+    Export {
+        /// Output file path (.csv)
+        path: String,
+    },
+    /// Apply the categories filled into an exported CSV; rows that don't
+    /// check out are reported and stay flagged.
+    Import {
+        /// CSV file path
+        path: String,
+        /// Check every row and report without saving
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum RulesCommands {
+    /// Open the rules manager screen on its own.
+    Manage,
     /// Add a categorization rule.
     Add {
         /// Pattern to match against transaction descriptions
@@ -317,6 +1211,43 @@ pub enum RulesCommands {
         #[arg(long = "match-type", default_value = "contains")]
         match_type: String,
     },
+    /// Walk through rules that never matched, are superseded by a
+    /// higher-priority rule, or match everything.
+    Cleanup,
+    /// Write all active rules to a .toml or .json file.
+    Export {
+        /// Output file path (.toml or .json)
+        path: String,
+    },
+    /// Load rules from a .toml or .json file.
+    Import {
+        /// Rules file path (.toml or .json)
+        path: String,
+        /// Add the file's rules alongside the existing ones (default)
+        #[arg(long, conflicts_with = "replace")]
+        merge: bool,
+        /// Deactivate all existing rules first
+        #[arg(long)]
+        replace: bool,
+        /// Create unknown categories (as expense) instead of skipping their rules
+        #[arg(long)]
+        create_categories: bool,
+        /// Report what would change without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Add a curated starter rule pack (e.g. `saas`), after a preview. Your
+    /// existing rules are never changed. Lists the packs without a name.
+    InstallPack {
+        /// Pack name
+        name: Option<String>,
+        /// Create missing categories (as expense) instead of skipping their rules
+        #[arg(long)]
+        create_categories: bool,
+        /// Show the preview without adding anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Shared output arguments for report subcommands.
@@ -325,10 +1256,11 @@ pub struct ReportOutputArgs {
     /// Mode: view (default, interactive) or export (write to file)
     #[arg(long)]
     pub mode: Option<String>,
-    /// Export format: pdf (default) or text
+    /// Export format: pdf (default) or text; the register and k1 also take csv
+    /// or xlsx, and k1 takes json
     #[arg(long)]
     pub format: Option<String>,
-    /// Output file path (implies --mode export)
+    /// Output file path (implies --mode export); `-` streams text or CSV to stdout
     #[arg(long)]
     pub output: Option<String>,
 }
@@ -349,6 +1281,18 @@ pub enum ReportCommands {
         /// End date: YYYY-MM-DD
         #[arg(long = "to")]
         to_date: Option<String>,
+        /// Roll sub-category totals up to this many levels (1 = top level)
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Show only top-level group totals, for a one-page summary
+        #[arg(long, conflicts_with = "depth")]
+        collapse: bool,
+        /// Compare with the prior period and the same period last year
+        #[arg(long)]
+        compare: bool,
+        /// Leave out transactions with this tag (repeatable)
+        #[arg(long = "exclude-tag")]
+        exclude_tags: Vec<String>,
         #[command(flatten)]
         output: ReportOutputArgs,
     },
@@ -358,6 +1302,12 @@ pub enum ReportCommands {
         month: Option<String>,
         #[arg(long)]
         year: Option<i32>,
+        /// Roll sub-category totals up to this many levels (1 = top level)
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Leave out transactions with this tag (repeatable)
+        #[arg(long = "exclude-tag")]
+        exclude_tags: Vec<String>,
         #[command(flatten)]
         output: ReportOutputArgs,
     },
@@ -374,6 +1324,12 @@ pub enum ReportCommands {
         month: Option<String>,
         #[arg(long)]
         year: Option<i32>,
+        /// Leave out transactions with this tag (repeatable)
+        #[arg(long = "exclude-tag")]
+        exclude_tags: Vec<String>,
+        /// Rows per period: week, month (default), or quarter (fiscal)
+        #[arg(long)]
+        granularity: Option<String>,
         #[command(flatten)]
         output: ReportOutputArgs,
     },
@@ -390,6 +1346,9 @@ pub enum ReportCommands {
         /// Filter by account name
         #[arg(long)]
         account: Option<String>,
+        /// Only transactions with this tag
+        #[arg(long)]
+        tag: Option<String>,
         #[command(flatten)]
         output: ReportOutputArgs,
     },
@@ -398,8 +1357,44 @@ pub enum ReportCommands {
         #[command(flatten)]
         output: ReportOutputArgs,
     },
+    /// Expenses still owed back by clients, by client.
+    Reimbursables {
+        /// Only this client
+        #[arg(long)]
+        client: Option<String>,
+        /// Also list expenses already reimbursed
+        #[arg(long)]
+        all: bool,
+        #[command(flatten)]
+        output: ReportOutputArgs,
+    },
     /// Cash position snapshot.
     Balance {
+        /// Also list archived accounts (otherwise folded into one line)
+        #[arg(long = "include-archived")]
+        include_archived: bool,
+        #[command(flatten)]
+        output: ReportOutputArgs,
+    },
+    /// Running balance of one account by day or week.
+    BalanceHistory {
+        /// Account name
+        #[arg(long)]
+        account: String,
+        #[arg(long)]
+        month: Option<String>,
+        #[arg(long)]
+        year: Option<i32>,
+        #[arg(long = "from")]
+        from_date: Option<String>,
+        #[arg(long = "to")]
+        to_date: Option<String>,
+        /// Points per day (default) or week
+        #[arg(long)]
+        interval: Option<String>,
+        /// Start from the account's opening balance on its opening date
+        #[arg(long = "opening-balance")]
+        opening_balance: bool,
         #[command(flatten)]
         output: ReportOutputArgs,
     },
@@ -410,6 +1405,51 @@ pub enum ReportCommands {
         #[command(flatten)]
         output: ReportOutputArgs,
     },
+    /// Standard mileage and home-office deductions.
+    Deductions {
+        #[arg(long)]
+        year: Option<i32>,
+        #[command(flatten)]
+        output: ReportOutputArgs,
+    },
+    /// Officer compensation vs. distributions by quarter, against the
+    /// target share of owner pay set on the Settings screen.
+    Compensation {
+        /// Fiscal year (default: current)
+        #[arg(long)]
+        year: Option<i32>,
+        #[command(flatten)]
+        output: ReportOutputArgs,
+    },
+    /// Income and spend per transaction tag.
+    Tags {
+        #[arg(long)]
+        month: Option<String>,
+        #[arg(long)]
+        year: Option<i32>,
+        #[command(flatten)]
+        output: ReportOutputArgs,
+    },
+    /// Revenue, direct costs, and margin per client.
+    Clients {
+        /// Fiscal year (default: current)
+        #[arg(long)]
+        year: Option<i32>,
+        #[command(flatten)]
+        output: ReportOutputArgs,
+    },
+    /// Cash and YTD net for this book beside the other books listed under
+    /// `overview_books` in settings.json, each opened read-only.
+    Books {
+        #[command(flatten)]
+        output: ReportOutputArgs,
+    },
+    /// Transactions dated more than `max_future_days` ahead or before
+    /// `date_epoch` (settings.json), likely a mistyped year.
+    Quality {
+        #[command(flatten)]
+        output: ReportOutputArgs,
+    },
     /// Export all reports (export-only).
     /// Note: All uses top-level fields instead of ReportOutputArgs because it has
     /// output_dir (not output) and is always export mode (no --mode flag needed).
@@ -434,8 +1474,16 @@ impl ReportCommands {
             Self::Cashflow { output, .. } => output.clone(),
             Self::Register { output, .. } => output.clone(),
             Self::Flagged { output, .. } => output.clone(),
+            Self::Reimbursables { output, .. } => output.clone(),
             Self::Balance { output, .. } => output.clone(),
+            Self::BalanceHistory { output, .. } => output.clone(),
             Self::K1 { output, .. } => output.clone(),
+            Self::Deductions { output, .. } => output.clone(),
+            Self::Compensation { output, .. } => output.clone(),
+            Self::Tags { output, .. } => output.clone(),
+            Self::Clients { output, .. } => output.clone(),
+            Self::Books { output } => output.clone(),
+            Self::Quality { output } => output.clone(),
             Self::All { format, .. } => ReportOutputArgs {
                 mode: Some("export".to_string()),
                 format: format.clone(),
@@ -452,8 +1500,16 @@ impl ReportCommands {
             Self::Cashflow { .. } => "cashflow",
             Self::Register { .. } => "register",
             Self::Flagged { .. } => "flagged",
+            Self::Reimbursables { .. } => "reimbursables",
             Self::Balance { .. } => "balance",
+            Self::BalanceHistory { .. } => "balance-history",
             Self::K1 { .. } => "k1-prep",
+            Self::Deductions { .. } => "deductions",
+            Self::Compensation { .. } => "compensation",
+            Self::Tags { .. } => "tags",
+            Self::Clients { .. } => "clients",
+            Self::Books { .. } => "books",
+            Self::Quality { .. } => "quality",
             Self::All { .. } => "all",
         }
     }
@@ -474,5 +1530,8 @@ pub enum BrowseCommands {
         /// Filter by account name
         #[arg(long)]
         account: Option<String>,
+        /// Only transactions with this tag
+        #[arg(long)]
+        tag: Option<String>,
     },
 }
@@ -12,22 +12,28 @@ use zeroize::Zeroize;
 
 use crate::effects::{self, Particle, LOGO};
 use crate::error::Result;
+use crate::templates::{DEFAULT_TEMPLATE, TEMPLATES};
 use crate::tui::{self, FOOTER_STYLE, HEADER_STYLE, SELECTED_STYLE};
 
 /// What the user chose to do after onboarding.
 #[derive(Clone, Copy)]
 pub enum PostSetupAction {
     Demo,
+    Tutorial,
     StartFresh,
     Import,
 }
 
 const ACTION_ITEMS: &[&str] = &[
     "View the demo",
+    "Take the guided tutorial",
     "Start from scratch",
     "Load existing data directory",
 ];
 
+/// Index of "Start from scratch", which goes on to pick a category template.
+const ACTION_START_FRESH: usize = 2;
+
 /// Intro animation timing (milliseconds)
 const INTRO_PARTICLES_MS: f64 = 500.0;
 const INTRO_REVEAL_MS: f64 = 500.0;
@@ -43,6 +49,7 @@ enum Screen {
     NameInput,
     ConfirmPassword,
     ActionPicker,
+    TemplatePicker,
 }
 
 enum StepResult {
@@ -57,6 +64,8 @@ pub struct OnboardingResult {
     pub company_name: String,
     pub password: Option<String>,
     pub action: PostSetupAction,
+    /// Category template to seed a fresh book with.
+    pub template: &'static str,
 }
 
 struct Onboarding {
@@ -69,6 +78,7 @@ struct Onboarding {
     active_field: usize,
     cursor_pos: usize,
     action_selection: usize,
+    template_selection: usize,
     screen: Screen,
     phase: f64,
     particles: Vec<Particle>,
@@ -92,6 +102,7 @@ impl Onboarding {
             active_field: 0,
             cursor_pos: 0,
             action_selection: 0,
+            template_selection: 0,
             screen: Screen::NameInput,
             phase: 0.0,
             particles: effects::pre_seed_particles(width, height),
@@ -144,6 +155,7 @@ impl Onboarding {
             Screen::NameInput => self.draw_name_input(frame, area),
             Screen::ConfirmPassword => self.draw_confirm_password(frame, area),
             Screen::ActionPicker => self.draw_action_picker(frame, area),
+            Screen::TemplatePicker => self.draw_template_picker(frame, area),
         }
     }
 
@@ -313,6 +325,75 @@ impl Onboarding {
         tui::render_version(frame, version_area);
     }
 
+    fn draw_template_picker(&self, frame: &mut Frame, area: Rect) {
+        let logo_height = LOGO.len() as u16;
+        // Each template takes a title line and a description line
+        let menu_height = TEMPLATES.len() as u16 * 2;
+        let [_top_pad, logo_area, _gap1, prompt_area, _gap2, menu_area, _gap3, hints_area, _bottom_pad, version_area] =
+            Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(logo_height),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(menu_height),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+
+        effects::render_logo(self.phase, frame, logo_area);
+
+        frame.render_widget(
+            Paragraph::new(Span::styled("What kind of business is this?", HEADER_STYLE))
+                .alignment(ratatui::layout::Alignment::Center),
+            prompt_area,
+        );
+
+        let menu_width = 70u16.min(area.width.saturating_sub(4));
+        let menu_x = area.x + (area.width.saturating_sub(menu_width)) / 2;
+        let centered_menu = Rect::new(menu_x, menu_area.y, menu_width, menu_area.height);
+
+        let menu_lines: Vec<Line> = TEMPLATES
+            .iter()
+            .enumerate()
+            .flat_map(|(i, template)| {
+                let marker = if i == self.template_selection {
+                    ">"
+                } else {
+                    " "
+                };
+                let style = if i == self.template_selection {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                [
+                    Line::from(Span::styled(
+                        format!(" {marker} {}", template.title()),
+                        style,
+                    )),
+                    Line::from(Span::styled(
+                        format!("   {}", template.summary()),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ]
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(menu_lines), centered_menu);
+
+        frame.render_widget(
+            Paragraph::new(" Up/Down=navigate  Enter=select  Esc=back")
+                .style(FOOTER_STYLE)
+                .alignment(ratatui::layout::Alignment::Center),
+            hints_area,
+        );
+
+        tui::render_version(frame, version_area);
+    }
+
     fn draw_confirm_password(&self, frame: &mut Frame, area: Rect) {
         let logo_height = LOGO.len() as u16;
         let error_height = if self.confirm_mismatch { 1 } else { 0 };
@@ -573,12 +654,39 @@ impl Onboarding {
             KeyCode::Down => {
                 self.action_selection = (self.action_selection + 1).min(ACTION_ITEMS.len() - 1);
             }
+            KeyCode::Enter if self.action_selection == ACTION_START_FRESH => {
+                return StepResult::NextScreen
+            }
             KeyCode::Enter => return StepResult::Finish,
             KeyCode::Esc => return StepResult::Skip,
             _ => {}
         }
         StepResult::Continue
     }
+
+    fn handle_template_key(&mut self, code: KeyCode) -> StepResult {
+        match code {
+            KeyCode::Up => {
+                self.template_selection = self.template_selection.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.template_selection = (self.template_selection + 1).min(TEMPLATES.len() - 1);
+            }
+            KeyCode::Enter => return StepResult::Finish,
+            KeyCode::Esc => self.screen = Screen::ActionPicker,
+            _ => {}
+        }
+        StepResult::Continue
+    }
+
+    /// The chosen template, or the default when not starting from scratch.
+    fn template(&self) -> &'static str {
+        if self.action_selection == ACTION_START_FRESH {
+            TEMPLATES[self.template_selection].key
+        } else {
+            DEFAULT_TEMPLATE
+        }
+    }
 }
 
 /// Convert a char-index cursor position to a byte offset.
@@ -645,6 +753,7 @@ pub fn run() -> Result<Option<OnboardingResult>> {
                         Screen::NameInput => onboarding.handle_name_key(key.code),
                         Screen::ConfirmPassword => onboarding.handle_confirm_key(key.code),
                         Screen::ActionPicker => onboarding.handle_action_key(key.code),
+                        Screen::TemplatePicker => onboarding.handle_template_key(key.code),
                     };
 
                     match step {
@@ -663,12 +772,16 @@ pub fn run() -> Result<Option<OnboardingResult>> {
                             Screen::ConfirmPassword => {
                                 onboarding.screen = Screen::ActionPicker;
                             }
-                            Screen::ActionPicker => {}
+                            Screen::ActionPicker => {
+                                onboarding.screen = Screen::TemplatePicker;
+                            }
+                            Screen::TemplatePicker => {}
                         },
                         StepResult::Finish => {
                             let action = match onboarding.action_selection {
                                 0 => PostSetupAction::Demo,
-                                1 => PostSetupAction::StartFresh,
+                                1 => PostSetupAction::Tutorial,
+                                2 => PostSetupAction::StartFresh,
                                 _ => PostSetupAction::Import,
                             };
                             let pw = onboarding.password.trim().to_string();
@@ -677,6 +790,7 @@ pub fn run() -> Result<Option<OnboardingResult>> {
                                 company_name: onboarding.company_name.trim().to_string(),
                                 password: if pw.is_empty() { None } else { Some(pw) },
                                 action,
+                                template: onboarding.template(),
                             }));
                         }
                         StepResult::Skip => break Ok(None),
@@ -709,6 +823,7 @@ mod tests {
             active_field: 0,
             cursor_pos: 0,
             action_selection: 0,
+            template_selection: 0,
             screen: Screen::NameInput,
             phase: 0.0,
             particles: vec![],
@@ -769,7 +884,8 @@ mod tests {
     fn finish_result(ob: &Onboarding) -> OnboardingResult {
         let action = match ob.action_selection {
             0 => PostSetupAction::Demo,
-            1 => PostSetupAction::StartFresh,
+            1 => PostSetupAction::Tutorial,
+            2 => PostSetupAction::StartFresh,
             _ => PostSetupAction::Import,
         };
         let pw = ob.password.trim().to_string();
@@ -778,6 +894,7 @@ mod tests {
             company_name: ob.company_name.trim().to_string(),
             password: if pw.is_empty() { None } else { Some(pw) },
             action,
+            template: ob.template(),
         }
     }
 
@@ -919,4 +1036,37 @@ mod tests {
         let result = ob.handle_confirm_key(KeyCode::Enter);
         assert!(matches!(result, StepResult::NextScreen));
     }
+
+    #[test]
+    fn start_fresh_picks_a_template() {
+        let mut ob = make_onboarding();
+        ob.screen = Screen::ActionPicker;
+        ob.action_selection = ACTION_START_FRESH;
+        assert!(matches!(
+            ob.handle_action_key(KeyCode::Enter),
+            StepResult::NextScreen
+        ));
+        ob.screen = Screen::TemplatePicker;
+        ob.handle_template_key(KeyCode::Down);
+        assert!(matches!(
+            ob.handle_template_key(KeyCode::Enter),
+            StepResult::Finish
+        ));
+        assert_eq!(finish_result(&ob).template, "schedule-c");
+    }
+
+    #[test]
+    fn other_actions_keep_the_default_template() {
+        let mut ob = make_onboarding();
+        ob.screen = Screen::ActionPicker;
+        ob.template_selection = 3;
+        assert!(matches!(
+            ob.handle_action_key(KeyCode::Enter),
+            StepResult::Finish
+        ));
+        assert_eq!(finish_result(&ob).template, DEFAULT_TEMPLATE);
+        ob.screen = Screen::TemplatePicker;
+        ob.handle_template_key(KeyCode::Esc);
+        assert!(matches!(ob.screen, Screen::ActionPicker));
+    }
 }
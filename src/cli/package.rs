@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use crate::db::{get_connection, get_metadata};
+use crate::error::Result;
+use crate::fiscal::FiscalYear;
+use crate::package::{self, Cover, PackageFile};
+use crate::reports;
+use crate::settings::get_data_dir;
+use crate::tabular;
+
+/// Build one ZIP with everything an accountant asks for at tax time: the
+/// year's reports, raw data as CSV, the chart of accounts, reconciliations,
+/// and a manifest of imported statements, under a cover README.
+pub fn run(year: Option<i32>, output: Option<String>) -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    // Packages go out after the year closes, so default to the last one
+    let fy = year.unwrap_or_else(|| fiscal.current() - 1);
+    let (start, end) = fiscal.bounds(fy);
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+
+    let pnl = reports::get_pnl(&conn, Some(fy), None, None, None, None, &[])?;
+    let register = reports::get_register(&conn, Some(fy), None, None, None, None, None)?;
+    let k1 = reports::get_k1_prep(&conn, Some(fy))?;
+
+    let mut files = report_files(&conn, fy, &company, &pnl, &register, &k1)?;
+    files.push(PackageFile::csv(
+        "data/transactions.csv",
+        "Every transaction in the year, with category, vendor, and tags",
+        &tabular::register_sheet(&register.rows),
+    )?);
+    files.push(PackageFile::csv(
+        "data/k1-lines.csv",
+        "K-1 prep worksheet totals by form line",
+        &tabular::k1_sheet(&reports::k1_line_totals(&k1)),
+    )?);
+    files.push(PackageFile::csv(
+        "data/chart-of-accounts.csv",
+        "Categories with their tax and form lines",
+        &package::chart_sheet(&conn)?,
+    )?);
+    files.push(PackageFile::csv(
+        "data/reconciliations.csv",
+        "Monthly account reconciliations and open discrepancy notes",
+        &package::reconciliation_sheet(&conn, start, end)?,
+    )?);
+    files.push(PackageFile::csv(
+        "data/attachments.csv",
+        "Statement files imported for the year, and where copies are kept",
+        &package::attachments_sheet(&crate::statements::list_imports(&conn)?, start, end),
+    )?);
+
+    let accounts = crate::cli::accounts::list_accounts(&conn)?
+        .into_iter()
+        .map(|a| {
+            let mut details = vec![a.account_type];
+            if let Some(last_four) = a.last_four {
+                details.push(format!("ending {last_four}"));
+            }
+            if a.is_archived {
+                details.push("closed".into());
+            }
+            format!("{} ({})", a.name, details.join(", "))
+        })
+        .collect();
+    let cover = Cover {
+        company: &company,
+        period: fiscal.label(fy),
+        start,
+        end,
+        currency: crate::currency::home_currency(&conn),
+        total_income: pnl.total_income,
+        total_expenses: pnl.total_expenses,
+        net: pnl.net,
+        transactions: &register.rows,
+        accounts,
+        prepared: chrono::Local::now().date_naive(),
+    };
+    let readme = package::cover_readme(&cover, &files);
+    files.insert(
+        0,
+        PackageFile::new("README.txt", "This file", readme.into_bytes()),
+    );
+
+    let path = output.map(PathBuf::from).unwrap_or_else(|| {
+        let date = chrono::Local::now().format("%Y-%m-%d");
+        data_dir
+            .join("exports")
+            .join(format!("package-fy{fy}-{date}.zip"))
+    });
+    package::write_zip(&files, &path)?;
+    println!(
+        "Wrote {} ({} files for {})",
+        path.display(),
+        files.len(),
+        fiscal.label(fy)
+    );
+    Ok(())
+}
+
+/// The P&L, tax summary, K-1 prep, and register as PDFs.
+#[cfg(feature = "pdf")]
+fn report_files(
+    conn: &rusqlite::Connection,
+    fy: i32,
+    company: &str,
+    pnl: &reports::PnlReport,
+    register: &reports::RegisterReport,
+    k1: &reports::K1PrepReport,
+) -> Result<Vec<PackageFile>> {
+    use crate::pdf;
+    let range = FiscalYear::load(conn).label(fy);
+    let tax = reports::get_tax_summary(conn, Some(fy))?;
+    Ok(vec![
+        PackageFile::new(
+            "reports/pnl.pdf",
+            "Profit & Loss",
+            pdf::render_pnl(pnl, company, &range)?,
+        ),
+        PackageFile::new(
+            "reports/tax.pdf",
+            "Tax summary by form line",
+            pdf::render_tax(&tax, company, &range)?,
+        ),
+        PackageFile::new(
+            "reports/k1-prep.pdf",
+            "K-1 prep worksheet",
+            pdf::render_k1(k1, company, &range)?,
+        ),
+        PackageFile::new(
+            "reports/register.pdf",
+            "Transaction register",
+            pdf::render_register(register, company, &range)?,
+        ),
+    ])
+}
+
+/// Without PDF support the reports go in as text.
+#[cfg(not(feature = "pdf"))]
+fn report_files(
+    _conn: &rusqlite::Connection,
+    fy: i32,
+    _company: &str,
+    _pnl: &reports::PnlReport,
+    _register: &reports::RegisterReport,
+    _k1: &reports::K1PrepReport,
+) -> Result<Vec<PackageFile>> {
+    use crate::cli::report::{text, ReportParams};
+    let year = Some(fy);
+    let params = ReportParams::period(year, None);
+    Ok(vec![
+        PackageFile::new(
+            "reports/pnl.txt",
+            "Profit & Loss",
+            text::pnl(&params)?.into_bytes(),
+        ),
+        PackageFile::new(
+            "reports/tax.txt",
+            "Tax summary by form line",
+            text::tax(year)?.into_bytes(),
+        ),
+        PackageFile::new(
+            "reports/k1-prep.txt",
+            "K-1 prep worksheet",
+            text::k1(year)?.into_bytes(),
+        ),
+        PackageFile::new(
+            "reports/register.txt",
+            "Transaction register",
+            text::register(&params)?.into_bytes(),
+        ),
+    ])
+}
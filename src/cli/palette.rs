@@ -0,0 +1,335 @@
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::tui::{truncate, FOOTER_STYLE, SELECTED_STYLE};
+
+/// Most matches shown at once; the list scrolls with the selection.
+const MAX_VISIBLE: usize = 10;
+
+/// Something the dashboard can do when a palette entry is chosen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteAction {
+    /// Activate a main menu item by index.
+    Menu(usize),
+    ViewReport(usize),
+    ExportReport(usize),
+    /// Open the register browser filtered to one account.
+    BrowseAccount(String),
+    AddAccount,
+}
+
+pub struct PaletteCommand {
+    pub label: String,
+    /// Extra words that match but aren't shown (e.g. "pnl" for Profit & Loss).
+    pub keywords: String,
+    pub action: PaletteAction,
+}
+
+/// Commands contributed by the dashboard and its screens, in display order
+/// for an empty query.
+#[derive(Default)]
+pub struct PaletteRegistry {
+    commands: Vec<PaletteCommand>,
+}
+
+impl PaletteRegistry {
+    pub fn register(&mut self, label: impl Into<String>, keywords: &str, action: PaletteAction) {
+        self.commands.push(PaletteCommand {
+            label: label.into(),
+            keywords: keywords.to_string(),
+            action,
+        });
+    }
+}
+
+pub enum PaletteResult {
+    Continue,
+    Close,
+    Execute(PaletteAction),
+}
+
+/// Score how well `query` fuzzy-matches `text`. Every whitespace-separated
+/// query word must appear as a case-insensitive subsequence of `text`;
+/// consecutive characters and word starts score higher. None if no match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let haystack: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0i64;
+    for word in query.split_whitespace() {
+        let first = lower(word.chars().next()?);
+        score += (0..haystack.len())
+            .filter(|&i| haystack[i] == first)
+            .filter_map(|start| score_word(&haystack, word, start))
+            .max()?;
+    }
+    Some(score)
+}
+
+fn lower(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+fn find_from(haystack: &[char], ch: char, from: usize) -> Option<usize> {
+    let ch = lower(ch);
+    haystack
+        .iter()
+        .skip(from)
+        .position(|c| *c == ch)
+        .map(|p| p + from)
+}
+
+fn score_word(haystack: &[char], word: &str, start: usize) -> Option<i64> {
+    let mut score = 0i64;
+    let mut idx = start;
+    let mut last: Option<usize> = None;
+    for ch in word.chars() {
+        let found = find_from(haystack, ch, idx)?;
+        score += 1;
+        if last.is_some_and(|l| l + 1 == found) {
+            score += 5; // consecutive
+        }
+        if found == 0 || !haystack[found - 1].is_alphanumeric() {
+            score += 3; // start of a word
+        }
+        if let Some(l) = last {
+            score -= (found - l - 1).min(5) as i64; // gap penalty
+        }
+        last = Some(found);
+        idx = found + 1;
+    }
+    Some(score)
+}
+
+pub struct Palette {
+    commands: Vec<PaletteCommand>,
+    query: String,
+    /// Indices into `commands`, best match first.
+    matches: Vec<usize>,
+    selection: usize,
+}
+
+impl Palette {
+    pub fn new(registry: PaletteRegistry) -> Self {
+        let mut palette = Self {
+            commands: registry.commands,
+            query: String::new(),
+            matches: vec![],
+            selection: 0,
+        };
+        palette.refilter();
+        palette
+    }
+
+    fn refilter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                let text = format!("{} {}", c.label, c.keywords);
+                fuzzy_score(&self.query, &text).map(|s| (s, i))
+            })
+            .collect();
+        // Highest score first; ties keep registration order
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.selection = 0;
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> PaletteResult {
+        match code {
+            KeyCode::Esc => return PaletteResult::Close,
+            KeyCode::Enter => {
+                if let Some(&idx) = self.matches.get(self.selection) {
+                    return PaletteResult::Execute(self.commands[idx].action.clone());
+                }
+            }
+            KeyCode::Up => self.selection = self.selection.saturating_sub(1),
+            KeyCode::Down => {
+                self.selection = (self.selection + 1).min(self.matches.len().saturating_sub(1))
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refilter();
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.refilter();
+            }
+            _ => {}
+        }
+        PaletteResult::Continue
+    }
+
+    /// Draw the palette as a popup near the top of `area`.
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let width = area.width.saturating_sub(4).min(70);
+        let height = (MAX_VISIBLE as u16 + 4).min(area.height.saturating_sub(2));
+        let popup = Rect::new(
+            area.x + (area.width.saturating_sub(width)) / 2,
+            area.y + 2.min(area.height),
+            width,
+            height,
+        );
+        frame.render_widget(Clear, popup);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray))
+            .title(Span::styled(
+                " Command Palette ",
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let [input_area, list_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(" : ", Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{}_", self.query)),
+            ])),
+            input_area,
+        );
+
+        let visible = (list_area.height as usize).max(1);
+        let offset = self.selection.saturating_sub(visible - 1);
+        let label_width = (list_area.width as usize).saturating_sub(3);
+        let lines: Vec<Line> = if self.matches.is_empty() {
+            vec![Line::from(Span::styled(
+                " No matching commands.",
+                FOOTER_STYLE,
+            ))]
+        } else {
+            self.matches
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(visible)
+                .map(|(i, &idx)| {
+                    let label = truncate(&self.commands[idx].label, label_width);
+                    if i == self.selection {
+                        Line::from(Span::styled(format!(" > {label}"), SELECTED_STYLE))
+                    } else {
+                        Line::from(format!("   {label}"))
+                    }
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), list_area);
+
+        frame.render_widget(
+            Paragraph::new(" Type to filter  Up/Down=select  Enter=run  Esc=close")
+                .style(FOOTER_STYLE),
+            hints_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> PaletteRegistry {
+        let mut r = PaletteRegistry::default();
+        r.register("Review flagged transactions", "", PaletteAction::Menu(2));
+        r.register(
+            "View report: Profit & Loss",
+            "pnl",
+            PaletteAction::ViewReport(0),
+        );
+        r.register(
+            "Export report: Profit & Loss",
+            "pnl pdf",
+            PaletteAction::ExportReport(0),
+        );
+        r.register(
+            "Browse account: BofA Checking",
+            "jump register",
+            PaletteAction::BrowseAccount("BofA Checking".into()),
+        );
+        r
+    }
+
+    fn type_query(p: &mut Palette, q: &str) {
+        for c in q.chars() {
+            p.handle_key(KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn fuzzy_score_requires_every_word() {
+        assert!(fuzzy_score("exp pnl", "Export report: Profit & Loss pnl").is_some());
+        assert!(fuzzy_score("exp xyz", "Export report: Profit & Loss pnl").is_none());
+        assert!(fuzzy_score("", "anything").is_some());
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_consecutive_and_word_starts() {
+        let tight = fuzzy_score("rev", "Review flagged").unwrap();
+        let loose = fuzzy_score("rev", "Reconcile every account").unwrap();
+        assert!(tight > loose);
+    }
+
+    #[test]
+    fn empty_query_lists_all_in_order() {
+        let p = Palette::new(registry());
+        assert_eq!(p.matches, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn export_pnl_selects_export() {
+        let mut p = Palette::new(registry());
+        type_query(&mut p, "export pnl");
+        match p.handle_key(KeyCode::Enter) {
+            PaletteResult::Execute(action) => assert_eq!(action, PaletteAction::ExportReport(0)),
+            _ => panic!("expected execute"),
+        }
+    }
+
+    #[test]
+    fn jump_to_account() {
+        let mut p = Palette::new(registry());
+        type_query(&mut p, "jump bofa");
+        match p.handle_key(KeyCode::Enter) {
+            PaletteResult::Execute(action) => {
+                assert_eq!(action, PaletteAction::BrowseAccount("BofA Checking".into()))
+            }
+            _ => panic!("expected execute"),
+        }
+    }
+
+    #[test]
+    fn no_match_enter_does_nothing_and_esc_closes() {
+        let mut p = Palette::new(registry());
+        type_query(&mut p, "zzzz");
+        assert!(p.matches.is_empty());
+        assert!(matches!(
+            p.handle_key(KeyCode::Enter),
+            PaletteResult::Continue
+        ));
+        p.handle_key(KeyCode::Backspace);
+        assert!(matches!(p.handle_key(KeyCode::Esc), PaletteResult::Close));
+    }
+
+    #[test]
+    fn selection_clamps() {
+        let mut p = Palette::new(registry());
+        p.handle_key(KeyCode::Up);
+        assert_eq!(p.selection, 0);
+        for _ in 0..10 {
+            p.handle_key(KeyCode::Down);
+        }
+        assert_eq!(p.selection, 3);
+    }
+}
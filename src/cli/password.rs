@@ -4,9 +4,16 @@ use crate::db::{is_encrypted, open_connection, set_db_password};
 use crate::error::Result;
 use crate::settings::get_data_dir;
 
-/// Encrypt an unencrypted database with a new password.
-/// Uses ATTACH + sqlcipher_export since the backup API requires matching keys.
+/// Encrypt an unencrypted database, and its year archives, with a new password.
 pub fn encrypt_database(db_path: &Path, new_password: &str) -> Result<()> {
+    for archive in crate::archive::archive_files(db_path) {
+        encrypt_file(&archive, new_password)?;
+    }
+    encrypt_file(db_path, new_password)
+}
+
+/// Uses ATTACH + sqlcipher_export since the backup API requires matching keys.
+fn encrypt_file(db_path: &Path, new_password: &str) -> Result<()> {
     let tmp_path = db_path.with_extension("db.encrypting");
     let tmp_str = tmp_path.to_string_lossy();
     let conn = open_connection(db_path, None)?;
@@ -25,9 +32,25 @@ pub fn encrypt_database(db_path: &Path, new_password: &str) -> Result<()> {
     Ok(())
 }
 
-/// Decrypt an encrypted database (remove password).
-/// Uses ATTACH + sqlcipher_export since the backup API requires matching keys.
+/// Decrypt an encrypted database and its year archives (remove password).
 pub fn decrypt_database(db_path: &Path, current_password: &str) -> Result<()> {
+    {
+        let conn = open_connection(db_path, Some(current_password))?;
+        conn.execute_batch("SELECT count(*) FROM sqlite_master;")?;
+        if crate::vault::has_stored_numbers(&conn) {
+            return Err(crate::error::NigelError::Other(
+                "Stored account numbers would be left unencrypted. Remove them first with `nigel accounts clear-number`.".into(),
+            ));
+        }
+    }
+    for archive in crate::archive::archive_files(db_path) {
+        decrypt_file(&archive, current_password)?;
+    }
+    decrypt_file(db_path, current_password)
+}
+
+/// Uses ATTACH + sqlcipher_export since the backup API requires matching keys.
+fn decrypt_file(db_path: &Path, current_password: &str) -> Result<()> {
     let tmp_path = db_path.with_extension("db.decrypting");
     let tmp_str = tmp_path.to_string_lossy();
     let conn = open_connection(db_path, Some(current_password))?;
@@ -47,8 +70,18 @@ pub fn decrypt_database(db_path: &Path, current_password: &str) -> Result<()> {
     Ok(())
 }
 
-/// Change the password on an already-encrypted database.
+/// Change the password on an already-encrypted database and its year archives.
 pub fn rekey_database(db_path: &Path, current_password: &str, new_password: &str) -> Result<()> {
+    // Check the password against the main database before touching archives
+    open_connection(db_path, Some(current_password))?
+        .execute_batch("SELECT count(*) FROM sqlite_master;")?;
+    for archive in crate::archive::archive_files(db_path) {
+        rekey_file(&archive, current_password, new_password)?;
+    }
+    rekey_file(db_path, current_password, new_password)
+}
+
+fn rekey_file(db_path: &Path, current_password: &str, new_password: &str) -> Result<()> {
     let conn = open_connection(db_path, Some(current_password))?;
     conn.execute_batch("SELECT count(*) FROM sqlite_master;")?;
     conn.pragma_update(None, "rekey", new_password)?;
@@ -166,6 +199,52 @@ mod tests {
         assert!(count > 0);
     }
 
+    #[test]
+    fn test_password_changes_cover_archives() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let archive_path = crate::archive::archives_dir(&db_path).join("nigel-2020.db");
+        std::fs::create_dir_all(archive_path.parent().unwrap()).unwrap();
+        for path in [&db_path, &archive_path] {
+            let conn = open_connection(path, None).unwrap();
+            init_db(&conn).unwrap();
+        }
+
+        encrypt_database(&db_path, "mypass").unwrap();
+        assert!(is_encrypted(&archive_path).unwrap());
+
+        rekey_database(&db_path, "mypass", "newpass").unwrap();
+        let conn = open_connection(&archive_path, Some("newpass")).unwrap();
+        conn.execute_batch("SELECT count(*) FROM sqlite_master;")
+            .unwrap();
+        drop(conn);
+
+        decrypt_database(&db_path, "newpass").unwrap();
+        assert!(!is_encrypted(&archive_path).unwrap());
+    }
+
+    #[test]
+    fn test_decrypt_refused_while_account_numbers_stored() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let conn = open_connection(&db_path, Some("mypass")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        crate::vault::set_numbers(&conn, 1, Some("435012345678"), None).unwrap();
+
+        assert!(decrypt_database(&db_path, "mypass").is_err());
+        assert!(is_encrypted(&db_path).unwrap());
+
+        crate::vault::clear_numbers(&conn, 1).unwrap();
+        drop(conn);
+        decrypt_database(&db_path, "mypass").unwrap();
+        assert!(!is_encrypted(&db_path).unwrap());
+    }
+
     #[test]
     fn test_rekey_changes_password() {
         let dir = tempfile::tempdir().unwrap();
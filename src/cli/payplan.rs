@@ -0,0 +1,158 @@
+use comfy_table::{Cell, Table};
+
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::fmt::money;
+use crate::payplan;
+use crate::settings::get_data_dir;
+
+fn this_month() -> String {
+    chrono::Local::now().format("%Y-%m").to_string()
+}
+
+pub fn show(month: Option<String>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let month = month.unwrap_or_else(this_month);
+    let plan = payplan::plan_for_month(&conn, &month)?;
+    let s = plan.settings;
+
+    let mut table = Table::new();
+    table.set_header(vec!["", "Amount", "How"]);
+    let rows: Vec<(&str, f64, String)> = vec![
+        (
+            "YTD profit",
+            plan.profit,
+            "P&L net with distributions added back".into(),
+        ),
+        (
+            "Estimated taxes",
+            -plan.taxes,
+            format!("{}% of profit", s.tax_pct),
+        ),
+        (
+            "Salary still due",
+            -plan.salary_due,
+            format!(
+                "{} to date of {}/yr, {} paid",
+                money(plan.salary_to_date),
+                money(s.salary),
+                money(plan.officer_comp)
+            ),
+        ),
+        (
+            "Distributions taken",
+            -plan.distributions,
+            "this fiscal year".into(),
+        ),
+        (
+            "Available from profit",
+            plan.available_from_profit,
+            String::new(),
+        ),
+        ("Cash position", plan.cash, "all accounts today".into()),
+        ("Reserve", -s.reserve, "kept in the bank".into()),
+        ("Tax set-aside", -plan.taxes, String::new()),
+        ("Salary still due", -plan.salary_due, String::new()),
+        ("Available from cash", plan.cash_available, String::new()),
+        (
+            "Safe to distribute",
+            plan.safe,
+            "the lesser of the two".into(),
+        ),
+    ];
+    for (label, amount, how) in rows {
+        table.add_row(vec![
+            Cell::new(label),
+            Cell::new(money(amount)),
+            Cell::new(how),
+        ]);
+    }
+    println!(
+        "Pay Yourself Plan: {} ({}, month {} of 12)\n{table}",
+        plan.month, plan.fiscal_label, plan.months_elapsed
+    );
+    match plan.planned {
+        Some(planned) if planned > plan.safe => println!(
+            "Planned for {}: {} ({} more than is safe)",
+            plan.month,
+            money(planned),
+            money(planned - plan.safe)
+        ),
+        Some(planned) => println!("Planned for {}: {}", plan.month, money(planned)),
+        None => println!(
+            "Nothing planned for {}. Record a plan with `nigel payplan plan <amount>`.",
+            plan.month
+        ),
+    }
+    if s.salary == 0.0 {
+        println!("No salary set; add one with `nigel payplan set --salary <annual>`.");
+    }
+    Ok(())
+}
+
+pub fn set(salary: Option<f64>, tax_rate: Option<f64>, reserve: Option<f64>) -> Result<()> {
+    if salary.is_none() && tax_rate.is_none() && reserve.is_none() {
+        return Err(NigelError::Other(
+            "Nothing to set: give --salary, --tax-rate, or --reserve".into(),
+        ));
+    }
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    payplan::save_settings(&conn, salary, tax_rate, reserve)?;
+    let s = payplan::load_settings(&conn);
+    println!(
+        "Pay plan: salary {}/yr, taxes {}% of profit, reserve {}",
+        money(s.salary),
+        s.tax_pct,
+        money(s.reserve)
+    );
+    Ok(())
+}
+
+pub fn plan(amount: Option<f64>, month: Option<String>, clear: bool) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let month = month.unwrap_or_else(this_month);
+    if clear {
+        payplan::clear_planned(&conn, &month)?;
+        println!("Removed the planned distribution for {month}");
+        return Ok(());
+    }
+    let amount = amount.unwrap_or_default();
+    payplan::set_planned(&conn, &month, amount)?;
+    let safe = payplan::plan_for_month(&conn, &month)?.safe;
+    println!(
+        "Planned {} for {month} (safe to distribute: {})",
+        money(amount),
+        money(safe)
+    );
+    Ok(())
+}
+
+pub fn history(year: Option<i32>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    let fy = year.unwrap_or_else(|| fiscal.current());
+    let months = payplan::history(&conn, fy)?;
+
+    let mut table = Table::new();
+    table.set_header(vec!["Month", "Planned", "Actual", "Difference"]);
+    let (mut planned_total, mut actual_total) = (0.0, 0.0);
+    for m in &months {
+        planned_total += m.planned.unwrap_or(0.0);
+        actual_total += m.actual;
+        table.add_row(vec![
+            Cell::new(&m.month),
+            Cell::new(m.planned.map(money).unwrap_or_default()),
+            Cell::new(money(m.actual)),
+            Cell::new(m.difference().map(money).unwrap_or_default()),
+        ]);
+    }
+    table.add_row(vec![
+        Cell::new("Total"),
+        Cell::new(money(planned_total)),
+        Cell::new(money(actual_total)),
+        Cell::new(money(actual_total - planned_total)),
+    ]);
+    println!("Distributions: {}\n{table}", fiscal.label(fy));
+    Ok(())
+}
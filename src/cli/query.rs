@@ -0,0 +1,110 @@
+use std::io::Write;
+
+use comfy_table::{Cell, Table};
+
+use crate::db::{get_connection, get_db_password, open_read_only};
+use crate::error::{NigelError, Result};
+use crate::query::{self, QueryOutcome};
+use crate::settings::{get_data_dir, load_settings, save_settings};
+
+/// Run SQL, or a saved query by name, and print the result.
+pub fn run(input: &str, format: &str, allow_writes: bool) -> Result<()> {
+    if !matches!(format, "text" | "csv" | "json") {
+        return Err(NigelError::Other(format!(
+            "Unknown --format '{format}'. Expected 'text', 'csv', or 'json'."
+        )));
+    }
+    let sql = load_settings()
+        .saved_queries
+        .get(input.trim())
+        .cloned()
+        .unwrap_or_else(|| input.to_string());
+
+    let data_dir = get_data_dir();
+    let db_path = data_dir.join("nigel.db");
+    let outcome = if allow_writes {
+        let conn = get_connection(&db_path)?;
+        let snap_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "query")?;
+        // stderr, so csv and json output stays clean for piping
+        eprintln!("Pre-query snapshot saved to {}", snap_path.display());
+        query::run(&conn, &sql, true)?
+    } else {
+        let conn = open_read_only(&db_path, get_db_password().as_deref())?;
+        query::run(&conn, &sql, false)?
+    };
+
+    let result = match outcome {
+        QueryOutcome::Changed(n) => {
+            println!("{n} row(s) changed");
+            return Ok(());
+        }
+        QueryOutcome::Rows(result) => result,
+    };
+    match format {
+        "csv" => crate::tabular::write_csv_to(&result.sheet(), std::io::stdout().lock()),
+        "json" => {
+            let json = serde_json::to_string_pretty(&result.to_json())
+                .map_err(|e| NigelError::Other(e.to_string()))?;
+            writeln!(std::io::stdout().lock(), "{json}")?;
+            Ok(())
+        }
+        _ => {
+            if result.rows.is_empty() {
+                println!("No rows.");
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.set_header(&result.columns);
+            for row in &result.rows {
+                table.add_row(row.iter().map(|v| Cell::new(query::cell_text(v))));
+            }
+            let n = result.rows.len();
+            println!("{table}\n{n} row{}", if n == 1 { "" } else { "s" });
+            Ok(())
+        }
+    }
+}
+
+pub fn save(name: &str, sql: &str) -> Result<()> {
+    let name = query::validate_name(name)?;
+    let mut settings = load_settings();
+    let replaced = settings
+        .saved_queries
+        .insert(name.clone(), sql.trim().to_string())
+        .is_some();
+    save_settings(&settings)?;
+    if replaced {
+        println!("Updated saved query '{name}'. Run it with `nigel query {name}`.");
+    } else {
+        println!("Saved query '{name}'. Run it with `nigel query {name}`.");
+    }
+    Ok(())
+}
+
+pub fn forget(name: &str) -> Result<()> {
+    let mut settings = load_settings();
+    if settings.saved_queries.remove(name.trim()).is_none() {
+        return Err(NigelError::Other(format!(
+            "No saved query named '{}'",
+            name.trim()
+        )));
+    }
+    save_settings(&settings)?;
+    println!("Deleted saved query '{}'", name.trim());
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let settings = load_settings();
+    if settings.saved_queries.is_empty() {
+        println!("No saved queries. Save one with `nigel query \"SELECT ...\" --save <name>`.");
+        return Ok(());
+    }
+    let mut table = Table::new();
+    table.set_header(vec!["Name", "SQL"]);
+    for (name, sql) in &settings.saved_queries {
+        table.add_row(vec![Cell::new(name), Cell::new(sql)]);
+    }
+    println!("Saved Queries\n{table}");
+    Ok(())
+}
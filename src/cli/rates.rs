@@ -0,0 +1,57 @@
+use comfy_table::{Cell, Table};
+
+use crate::currency;
+use crate::db::get_connection;
+use crate::error::Result;
+use crate::settings::get_data_dir;
+
+pub fn list() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let home = currency::home_currency(&conn);
+    let rates = currency::list_rates(&conn)?;
+    if rates.is_empty() {
+        println!("Home currency: {home}\nNo exchange rates recorded.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Currency", "Date", &format!("Rate ({home})")]);
+    for r in rates {
+        table.add_row(vec![
+            Cell::new(r.currency),
+            Cell::new(r.date),
+            Cell::new(format!("{:.6}", r.rate)),
+        ]);
+    }
+    println!("Exchange Rates (home currency: {home})\n{table}");
+    Ok(())
+}
+
+pub fn set(code: &str, rate: f64, date: Option<&str>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let date = date.unwrap_or(&today);
+    let code = currency::set_rate(&conn, code, date, rate)?;
+    let home = currency::home_currency(&conn);
+    println!("Recorded 1 {code} = {rate} {home} as of {date}");
+    Ok(())
+}
+
+pub fn delete(code: &str, date: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    currency::delete_rate(&conn, code, date)?;
+    println!("Deleted {} rate for {date}", code.to_ascii_uppercase());
+    Ok(())
+}
+
+pub fn home(code: Option<&str>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    match code {
+        Some(code) => {
+            let code = currency::set_home_currency(&conn, code)?;
+            println!("Home currency set to {code}");
+        }
+        None => println!("Home currency: {}", currency::home_currency(&conn)),
+    }
+    Ok(())
+}
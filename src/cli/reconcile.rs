@@ -1,10 +1,18 @@
+use comfy_table::{Cell, Table};
+
 use crate::db::get_connection;
 use crate::error::Result;
 use crate::fmt::money;
 use crate::reconciler;
 use crate::settings::get_data_dir;
 
-pub fn run(account: &str, month: &str, balance: f64) -> Result<()> {
+pub fn run(
+    account: &str,
+    month: &str,
+    balance: f64,
+    note: Option<&str>,
+    follow_up: Option<&str>,
+) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let result = reconciler::reconcile(&conn, account, month, balance)?;
 
@@ -13,6 +21,9 @@ pub fn run(account: &str, month: &str, balance: f64) -> Result<()> {
             "Reconciled! Calculated: {}",
             money(result.calculated_balance)
         );
+        if note.is_some() {
+            println!("Note not saved: the account balances.");
+        }
     } else {
         println!(
             "DISCREPANCY: {}\n  Statement:  {}\n  Calculated: {}",
@@ -20,6 +31,121 @@ pub fn run(account: &str, month: &str, balance: f64) -> Result<()> {
             money(result.statement_balance),
             money(result.calculated_balance)
         );
+        match note {
+            Some(note) => {
+                reconciler::add_note(&conn, result.id, note, follow_up)?;
+                println!("Accepted for now with note #{}.", result.id);
+            }
+            None => println!(
+                "To accept it for now, re-run with --note \"...\" (and optionally --follow-up YYYY-MM-DD)."
+            ),
+        }
     }
     Ok(())
 }
+
+pub fn list_notes() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let notes = reconciler::open_notes(&conn)?;
+    if notes.is_empty() {
+        println!("No open reconciliation notes.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Account",
+        "Month",
+        "Difference",
+        "Follow up",
+        "Note",
+    ]);
+    for n in &notes {
+        table.add_row(vec![
+            Cell::new(n.id),
+            Cell::new(&n.account_name),
+            Cell::new(&n.month),
+            Cell::new(money(n.discrepancy)),
+            Cell::new(n.follow_up.as_deref().unwrap_or("")),
+            Cell::new(&n.note),
+        ]);
+    }
+    println!("Open Reconciliation Notes\n{table}");
+    Ok(())
+}
+
+pub fn assert_balance(account: &str, date: &str, balance: f64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let assertion = reconciler::add_assertion(&conn, account, date, balance)?;
+    if assertion.holds() {
+        println!(
+            "OK: {account} balance on {date} is {} (assertion #{})",
+            money(assertion.calculated),
+            assertion.id
+        );
+    } else {
+        println!(
+            "MISMATCH: {}\n  Asserted:   {}\n  Calculated: {}",
+            money(assertion.difference().abs()),
+            money(assertion.balance),
+            money(assertion.calculated)
+        );
+        println!(
+            "Saved as assertion #{}; the dashboard will warn until the books agree. \
+             Check for missing imports or set an opening balance with \
+             `nigel accounts opening-balance`.",
+            assertion.id
+        );
+    }
+    Ok(())
+}
+
+pub fn list_assertions() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let assertions = reconciler::list_assertions(&conn)?;
+    if assertions.is_empty() {
+        println!("No balance assertions.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Account",
+        "Date",
+        "Asserted",
+        "Calculated",
+        "Status",
+    ]);
+    for a in &assertions {
+        table.add_row(vec![
+            Cell::new(a.id),
+            Cell::new(&a.account_name),
+            Cell::new(&a.date),
+            Cell::new(money(a.balance)),
+            Cell::new(money(a.calculated)),
+            Cell::new(if a.holds() {
+                "ok".to_string()
+            } else {
+                format!("off by {}", money(a.difference().abs()))
+            }),
+        ]);
+    }
+    println!("Balance Assertions\n{table}");
+    Ok(())
+}
+
+pub fn remove_assertion(id: i64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    reconciler::remove_assertion(&conn, id)?;
+    println!("Removed balance assertion #{id}");
+    Ok(())
+}
+
+pub fn resolve(id: i64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    reconciler::resolve_note(&conn, id)?;
+    println!("Resolved reconciliation note #{id}");
+    Ok(())
+}
@@ -9,7 +9,8 @@ use ratatui::{
 use rusqlite::Connection;
 
 use crate::cli::accounts;
-use crate::error::Result;
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
+use crate::error::{NigelError, Result};
 use crate::fmt::money;
 use crate::reconciler;
 use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
@@ -22,19 +23,27 @@ pub enum ReconcileAction {
 enum Screen {
     Form,
     Result(ReconcileResult),
+    /// Accepting a difference for now: note and follow-up date inputs.
+    Note(ReconcileResult),
 }
 
 struct ReconcileResult {
+    id: i64,
     is_reconciled: bool,
     statement_balance: f64,
     calculated_balance: f64,
     discrepancy: f64,
+    /// A note was saved for this difference.
+    noted: bool,
 }
 
 const FIELD_ACCOUNT: usize = 0;
 const FIELD_MONTH: usize = 1;
 const FIELD_BALANCE: usize = 2;
 
+const FIELD_NOTE: usize = 0;
+const FIELD_FOLLOW_UP: usize = 1;
+
 pub struct ReconcileScreen {
     accounts: Vec<String>,
     account_idx: usize,
@@ -44,6 +53,11 @@ pub struct ReconcileScreen {
     screen: Screen,
     status_message: Option<String>,
     greeting: String,
+    /// Open note this screen was opened from, shown under the form.
+    open_note: Option<String>,
+    note: String,
+    follow_up: String,
+    note_field: usize,
 }
 
 impl ReconcileScreen {
@@ -58,9 +72,53 @@ impl ReconcileScreen {
             screen: Screen::Form,
             status_message: None,
             greeting: greeting.to_string(),
+            open_note: None,
+            note: String::new(),
+            follow_up: String::new(),
+            note_field: FIELD_NOTE,
         })
     }
 
+    /// Start on an account and month given on the command line
+    /// (`nigel reconcile --tui`), focused on the first field left to fill.
+    pub fn prefilled(
+        conn: &Connection,
+        greeting: &str,
+        account: Option<&str>,
+        month: Option<&str>,
+    ) -> Result<Self> {
+        let mut screen = Self::new(conn, greeting)?;
+        if let Some(account) = account {
+            screen.account_idx = screen
+                .accounts
+                .iter()
+                .position(|a| a == account)
+                .ok_or_else(|| NigelError::UnknownAccount(account.to_string()))?;
+            screen.focused = FIELD_MONTH;
+        }
+        if let Some(month) = month {
+            screen.month = month.to_string();
+            screen.focused = FIELD_BALANCE;
+        }
+        Ok(screen)
+    }
+
+    /// Start from an open note's account and month, ready for a new balance.
+    pub fn for_note(
+        conn: &Connection,
+        greeting: &str,
+        note: &reconciler::OpenNote,
+    ) -> Result<Self> {
+        let mut screen = Self::new(conn, greeting)?;
+        if let Some(idx) = screen.accounts.iter().position(|a| *a == note.account_name) {
+            screen.account_idx = idx;
+        }
+        screen.month = note.month.clone();
+        screen.focused = FIELD_BALANCE;
+        screen.open_note = Some(format!("Note #{}: {}", note.id, note.note));
+        Ok(screen)
+    }
+
     pub fn draw(&self, frame: &mut Frame) {
         let area = frame.area();
         let border_style = Style::default().fg(Color::DarkGray);
@@ -84,6 +142,7 @@ impl ReconcileScreen {
         match &self.screen {
             Screen::Form => self.draw_form(frame, content_area, hints_area),
             Screen::Result(result) => self.draw_result(frame, content_area, hints_area, result),
+            Screen::Note(result) => self.draw_note(frame, content_area, hints_area, result),
         }
     }
 
@@ -176,6 +235,18 @@ impl ReconcileScreen {
             ]));
         }
 
+        if let Some(note) = &self.open_note {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("   {note}"),
+                Style::default().fg(Color::Yellow),
+            )));
+            lines.push(Line::from(Span::styled(
+                "   Reconciling cleanly resolves it.",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
         if let Some(msg) = &self.status_message {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
@@ -243,12 +314,98 @@ impl ReconcileScreen {
                 format!("   Difference:  {}", money(result.discrepancy)),
                 Style::default().fg(Color::Red),
             )));
+            if result.noted {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "   Accepted for now. Note #{} stays on the dashboard until resolved.",
+                        result.id
+                    ),
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
         }
 
         frame.render_widget(Paragraph::new(lines), content_area);
 
+        let hints = if result.is_reconciled || result.noted {
+            " Esc=back to dashboard"
+        } else {
+            " n=accept with a note  Esc=back to dashboard"
+        };
+        frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
+    }
+
+    fn draw_note(
+        &self,
+        frame: &mut Frame,
+        content_area: ratatui::layout::Rect,
+        hints_area: ratatui::layout::Rect,
+        result: &ReconcileResult,
+    ) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                " Accept Difference for Now",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(format!(
+                "   {} {}: {} off",
+                self.accounts
+                    .get(self.account_idx)
+                    .map(|s| s.as_str())
+                    .unwrap_or("?"),
+                self.month,
+                money(result.discrepancy)
+            )),
+            Line::from(""),
+        ];
+        for (field, label, value, placeholder) in [
+            (FIELD_NOTE, "   Note           ", &self.note, ""),
+            (
+                FIELD_FOLLOW_UP,
+                "   Follow up      ",
+                &self.follow_up,
+                "YYYY-MM-DD (optional)",
+            ),
+        ] {
+            let is_focused = self.note_field == field;
+            let label_style = if is_focused {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let cursor = if is_focused { "_" } else { "" };
+            let placeholder = if value.is_empty() && !is_focused {
+                placeholder
+            } else {
+                ""
+            };
+            lines.push(Line::from(vec![
+                Span::styled(label, label_style),
+                Span::styled(
+                    format!("{value}{cursor}{placeholder}"),
+                    if is_focused {
+                        Style::default().fg(Color::Cyan)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    },
+                ),
+            ]));
+        }
+
+        if let Some(msg) = &self.status_message {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("   {msg}"),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+
+        frame.render_widget(Paragraph::new(lines), content_area);
         frame.render_widget(
-            Paragraph::new(" Esc=back to dashboard").style(FOOTER_STYLE),
+            Paragraph::new(" Tab=next field  Enter=save  Esc=cancel").style(FOOTER_STYLE),
             hints_area,
         );
     }
@@ -256,13 +413,71 @@ impl ReconcileScreen {
     pub fn handle_key(&mut self, code: KeyCode, conn: &Connection) -> ReconcileAction {
         match &self.screen {
             Screen::Form => self.handle_form_key(code, conn),
-            Screen::Result(_) => match code {
+            Screen::Result(result) => match code {
                 KeyCode::Esc => ReconcileAction::Close,
+                KeyCode::Char('n') if !result.is_reconciled && !result.noted => {
+                    if let Screen::Result(result) =
+                        std::mem::replace(&mut self.screen, Screen::Form)
+                    {
+                        self.screen = Screen::Note(result);
+                    }
+                    ReconcileAction::Continue
+                }
                 _ => ReconcileAction::Continue,
             },
+            Screen::Note(_) => {
+                self.handle_note_key(code, conn);
+                ReconcileAction::Continue
+            }
         }
     }
 
+    fn handle_note_key(&mut self, code: KeyCode, conn: &Connection) {
+        match code {
+            KeyCode::Esc => self.back_to_result(false),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.note_field = 1 - self.note_field;
+            }
+            KeyCode::Char(c) => {
+                match self.note_field {
+                    FIELD_NOTE => self.note.push(c),
+                    _ => {
+                        if c.is_ascii_digit() || c == '-' {
+                            self.follow_up.push(c);
+                        }
+                    }
+                }
+                self.status_message = None;
+            }
+            KeyCode::Backspace => {
+                match self.note_field {
+                    FIELD_NOTE => self.note.pop(),
+                    _ => self.follow_up.pop(),
+                };
+                self.status_message = None;
+            }
+            KeyCode::Enter => {
+                let Screen::Note(result) = &self.screen else {
+                    return;
+                };
+                let follow_up = Some(self.follow_up.trim()).filter(|d| !d.is_empty());
+                match reconciler::add_note(conn, result.id, &self.note, follow_up) {
+                    Ok(()) => self.back_to_result(true),
+                    Err(e) => self.status_message = Some(format!("Error: {e}")),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn back_to_result(&mut self, noted: bool) {
+        if let Screen::Note(mut result) = std::mem::replace(&mut self.screen, Screen::Form) {
+            result.noted = noted;
+            self.screen = Screen::Result(result);
+        }
+        self.status_message = None;
+    }
+
     fn handle_form_key(&mut self, code: KeyCode, conn: &Connection) -> ReconcileAction {
         match code {
             KeyCode::Esc | KeyCode::Char('q') => return ReconcileAction::Close,
@@ -345,10 +560,12 @@ impl ReconcileScreen {
                 match reconciler::reconcile(conn, account_name, &self.month, balance) {
                     Ok(r) => {
                         self.screen = Screen::Result(ReconcileResult {
+                            id: r.id,
                             is_reconciled: r.is_reconciled,
                             statement_balance: r.statement_balance,
                             calculated_balance: r.calculated_balance,
                             discrepancy: r.discrepancy,
+                            noted: false,
                         });
                     }
                     Err(e) => {
@@ -361,3 +578,22 @@ impl ReconcileScreen {
         ReconcileAction::Continue
     }
 }
+
+impl screen::Screen for ReconcileScreen {
+    fn draw(&mut self, frame: &mut Frame) {
+        ReconcileScreen::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        let had_result = matches!(self.screen, Screen::Result(_));
+        let action = ReconcileScreen::handle_key(self, code, ctx.conn);
+        // The result screen follows a saved reconciliation or note
+        if !had_result && matches!(self.screen, Screen::Result(_)) {
+            ctx.publish(DataChange::Accounts);
+        }
+        Ok(match action {
+            ReconcileAction::Close => Nav::Pop,
+            ReconcileAction::Continue => Nav::Stay,
+        })
+    }
+}
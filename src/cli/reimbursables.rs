@@ -0,0 +1,96 @@
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::fmt::money;
+use crate::reimbursables;
+use crate::settings::get_data_dir;
+
+pub fn mark(ids: &[i64], client: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let client = reimbursables::mark(&conn, ids, client)?;
+    println!(
+        "Marked {} as reimbursable by #{client}",
+        transactions(ids.len())
+    );
+    Ok(())
+}
+
+pub fn unmark(ids: &[i64]) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let removed = reimbursables::unmark(&conn, ids)?;
+    println!("Unmarked {}", transactions(removed));
+    Ok(())
+}
+
+pub fn invoice(
+    ids: Vec<i64>,
+    client: Option<String>,
+    reference: Option<String>,
+    date: Option<String>,
+) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let ids = match &client {
+        Some(c) => reimbursables::outstanding_ids(&conn, c, true)?,
+        None => ids,
+    };
+    if ids.is_empty() {
+        return Err(NigelError::Other(
+            "Nothing to invoice: the client has no expenses waiting to be billed".into(),
+        ));
+    }
+    let date = date.unwrap_or_else(today);
+    let n = reimbursables::record_invoice(&conn, &ids, reference.as_deref(), &date)?;
+    let total = billed_total(&conn, &ids)?;
+    match reference {
+        Some(r) => println!(
+            "Invoiced {} ({}) on {date} as {r}",
+            transactions(n),
+            money(total)
+        ),
+        None => println!("Invoiced {} ({}) on {date}", transactions(n), money(total)),
+    }
+    Ok(())
+}
+
+pub fn paid(ids: Vec<i64>, client: Option<String>, date: Option<String>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let ids = match &client {
+        Some(c) => reimbursables::outstanding_ids(&conn, c, false)?,
+        None => ids,
+    };
+    if ids.is_empty() {
+        return Err(NigelError::Other(
+            "Nothing to mark paid: the client has no outstanding expenses".into(),
+        ));
+    }
+    let date = date.unwrap_or_else(today);
+    let n = reimbursables::record_reimbursed(&conn, &ids, &date)?;
+    let total = billed_total(&conn, &ids)?;
+    println!(
+        "Marked {} ({}) reimbursed on {date}",
+        transactions(n),
+        money(total)
+    );
+    Ok(())
+}
+
+fn billed_total(conn: &rusqlite::Connection, ids: &[i64]) -> Result<f64> {
+    let report = reimbursables::get_report(conn, None, true)?;
+    Ok(report
+        .items
+        .iter()
+        .filter(|i| ids.contains(&i.transaction_id))
+        .map(|i| i.amount)
+        .sum())
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+fn transactions(n: usize) -> String {
+    if n == 1 {
+        "1 transaction".to_string()
+    } else {
+        format!("{n} transactions")
+    }
+}
@@ -0,0 +1,369 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::cli::load::{find_books, switch_to, BookSummary};
+use crate::error::Result;
+use crate::settings::shellexpand_path;
+use crate::tui::{FOOTER_STYLE, HEADER_STYLE, SELECTED_STYLE};
+
+/// What the user chose on the recovery screen.
+pub enum RelocateOutcome {
+    /// Settings now point at the chosen data directory.
+    Switched,
+    /// Create a new, empty book at the configured location.
+    StartFresh,
+    Quit,
+}
+
+/// Shown at launch when the configured data directory is gone: lists books
+/// found in the usual places, takes a path typed by hand (Tab completes
+/// folder names), or starts a new book where the old one was.
+pub struct RelocateScreen {
+    missing: PathBuf,
+    /// Found data directories with their summary lines.
+    found: Vec<(PathBuf, Vec<String>)>,
+    selection: usize,
+    /// Path being typed, when browsing by hand.
+    path: Option<String>,
+    /// Tab completion candidates for the typed path.
+    completions: Vec<String>,
+    status_message: Option<String>,
+}
+
+impl RelocateScreen {
+    pub fn new(missing: &Path) -> Self {
+        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        let found = find_books(&home, missing)
+            .into_iter()
+            .map(|dir| {
+                let lines = BookSummary::read(&dir.join("nigel.db"))
+                    .map(|s| s.lines())
+                    .unwrap_or_else(|e| vec![format!("Could not read: {e}")]);
+                (dir, lines)
+            })
+            .collect();
+        Self {
+            missing: missing.to_path_buf(),
+            found,
+            selection: 0,
+            path: None,
+            completions: Vec::new(),
+            status_message: None,
+        }
+    }
+
+    /// Rows in the list: each found book, then "enter a path", then "start new".
+    fn item_count(&self) -> usize {
+        self.found.len() + 2
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let border_style = Style::default().fg(Color::DarkGray);
+
+        let [header_area, sep, content_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(" Nigel \u{2014} data directory not found").style(HEADER_STYLE),
+            header_area,
+        );
+        let sep_line = "\u{2501}".repeat(area.width as usize);
+        frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(" Where did your books go?", bold)),
+            Line::from(""),
+            Line::from(format!("   {} no longer exists.", self.missing.display())),
+            Line::from(""),
+        ];
+
+        if self.found.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   No books found in the usual places.",
+                FOOTER_STYLE,
+            )));
+        } else {
+            lines.push(Line::from("   Found:"));
+        }
+        for (i, (dir, _)) in self.found.iter().enumerate() {
+            lines.push(self.item_line(i, dir.to_string_lossy().to_string()));
+        }
+        lines.push(Line::from(""));
+        lines.push(self.item_line(self.found.len(), "Enter a path\u{2026}".into()));
+        lines.push(self.item_line(
+            self.found.len() + 1,
+            format!("Start a new book at {}", self.missing.display()),
+        ));
+
+        if let Some(path) = &self.path {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("   Path  ", bold),
+                Span::styled(format!("{path}_"), Style::default().fg(Color::Cyan)),
+            ]));
+            if !self.completions.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    format!("         {}", self.completions.join("  ")),
+                    FOOTER_STYLE,
+                )));
+            }
+        } else if let Some((_, summary)) = self.found.get(self.selection) {
+            lines.push(Line::from(""));
+            for line in summary {
+                lines.push(Line::from(format!("   {line}")));
+            }
+        }
+
+        if let Some(msg) = &self.status_message {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("   {msg}"),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        frame.render_widget(Paragraph::new(lines), content_area);
+
+        let hints = if self.path.is_some() {
+            " Enter=load  Tab=complete  Esc=back"
+        } else {
+            " \u{2191}\u{2193}=select  Enter=choose  q=quit"
+        };
+        frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
+    }
+
+    fn item_line(&self, idx: usize, label: String) -> Line<'static> {
+        if self.path.is_none() && idx == self.selection {
+            Line::from(Span::styled(format!(" \u{25b8} {label}"), SELECTED_STYLE))
+        } else {
+            Line::from(format!("   {label}"))
+        }
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> Option<RelocateOutcome> {
+        if self.path.is_some() {
+            return self.handle_path_key(code);
+        }
+        self.status_message = None;
+        match code {
+            KeyCode::Up => self.selection = self.selection.saturating_sub(1),
+            KeyCode::Down => self.selection = (self.selection + 1).min(self.item_count() - 1),
+            KeyCode::Esc | KeyCode::Char('q') => return Some(RelocateOutcome::Quit),
+            KeyCode::Enter => {
+                if let Some((dir, _)) = self.found.get(self.selection) {
+                    let dir = dir.clone();
+                    return self.switch(dir);
+                }
+                if self.selection == self.found.len() {
+                    self.path = Some(String::new());
+                } else {
+                    return Some(RelocateOutcome::StartFresh);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_path_key(&mut self, code: KeyCode) -> Option<RelocateOutcome> {
+        let path = self.path.as_mut()?;
+        self.status_message = None;
+        match code {
+            KeyCode::Esc => {
+                self.path = None;
+                self.completions.clear();
+            }
+            KeyCode::Char(c) => {
+                path.push(c);
+                self.completions.clear();
+            }
+            KeyCode::Backspace => {
+                path.pop();
+                self.completions.clear();
+            }
+            KeyCode::Tab => {
+                let (completed, candidates) = complete_dir(path);
+                *path = completed;
+                self.completions = candidates;
+            }
+            KeyCode::Enter => {
+                let trimmed = path.trim().to_string();
+                if trimmed.is_empty() {
+                    self.status_message = Some("Path is required".into());
+                    return None;
+                }
+                let resolved = PathBuf::from(shellexpand_path(&trimmed));
+                if !resolved.join("nigel.db").is_file() {
+                    self.status_message = Some(format!(
+                        "No database found at {}",
+                        resolved.join("nigel.db").display()
+                    ));
+                    return None;
+                }
+                return self.switch(resolved);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn switch(&mut self, dir: PathBuf) -> Option<RelocateOutcome> {
+        match switch_to(&dir) {
+            Ok(()) => Some(RelocateOutcome::Switched),
+            Err(e) => {
+                self.status_message = Some(format!("Error: {e}"));
+                None
+            }
+        }
+    }
+}
+
+/// Complete the last component of a typed directory path. Returns the new
+/// input (extended to the longest common prefix, with a trailing `/` once a
+/// single folder matches) and the candidate folder names when several match.
+fn complete_dir(input: &str) -> (String, Vec<String>) {
+    let expanded = match input.strip_prefix('~') {
+        Some(rest) => match dirs::home_dir() {
+            Some(home) => format!("{}{rest}", home.to_string_lossy()),
+            None => input.to_string(),
+        },
+        None => input.to_string(),
+    };
+    let (parent, prefix) = match expanded.rfind('/') {
+        Some(i) => (&expanded[..=i], &expanded[i + 1..]),
+        None => ("./", expanded.as_str()),
+    };
+    let Ok(entries) = std::fs::read_dir(parent) else {
+        return (input.to_string(), Vec::new());
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|n| n.starts_with(prefix) && (prefix.starts_with('.') || !n.starts_with('.')))
+        .collect();
+    names.sort();
+
+    let base = if expanded.contains('/') { parent } else { "" };
+    match names.as_slice() {
+        [] => (input.to_string(), Vec::new()),
+        [only] => (format!("{base}{only}/"), Vec::new()),
+        [first, rest @ ..] => {
+            let mut common = first.clone();
+            for name in rest {
+                while !name.starts_with(&common) {
+                    common.pop();
+                }
+            }
+            (format!("{base}{common}"), names)
+        }
+    }
+}
+
+/// Run the recovery screen until the user picks a book, starts fresh, or quits.
+pub fn run(missing: &Path) -> Result<RelocateOutcome> {
+    let mut screen = RelocateScreen::new(missing);
+    let mut terminal = ratatui::init();
+
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| screen.draw(frame)) {
+            break Err(e.into());
+        }
+        if event::poll(Duration::from_millis(50))? {
+            match event::read() {
+                Err(e) => break Err(e.into()),
+                Ok(Event::Key(key)) => {
+                    if key.kind != KeyEventKind::Press {
+                        continue;
+                    }
+                    if key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('c')
+                    {
+                        break Ok(RelocateOutcome::Quit);
+                    }
+                    if let Some(outcome) = screen.handle_key(key.code) {
+                        break Ok(outcome);
+                    }
+                }
+                _ => {}
+            }
+        }
+    };
+
+    drop(terminal);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complete_dir_extends_to_common_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["books-2024", "books-2025", "archive", ".hidden"] {
+            std::fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        let base = format!("{}/", dir.path().display());
+
+        let (input, candidates) = complete_dir(&format!("{base}bo"));
+        assert_eq!(input, format!("{base}books-202"));
+        assert_eq!(candidates, vec!["books-2024", "books-2025"]);
+
+        let (input, candidates) = complete_dir(&format!("{base}ar"));
+        assert_eq!(input, format!("{base}archive/"));
+        assert!(candidates.is_empty());
+
+        let (input, _) = complete_dir(&format!("{base}.h"));
+        assert_eq!(input, format!("{base}.hidden/"));
+    }
+
+    #[test]
+    fn enter_path_refuses_a_folder_without_a_book() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut screen = RelocateScreen {
+            missing: dir.path().join("gone"),
+            found: Vec::new(),
+            selection: 0,
+            path: None,
+            completions: Vec::new(),
+            status_message: None,
+        };
+        // With nothing found, the first row is "Enter a path"
+        assert!(screen.handle_key(KeyCode::Enter).is_none());
+        for c in dir.path().to_string_lossy().chars() {
+            screen.handle_key(KeyCode::Char(c));
+        }
+        assert!(screen.handle_key(KeyCode::Enter).is_none());
+        assert!(screen
+            .status_message
+            .as_deref()
+            .unwrap()
+            .starts_with("No database found"));
+
+        screen.handle_key(KeyCode::Esc);
+        screen.handle_key(KeyCode::Down);
+        assert!(matches!(
+            screen.handle_key(KeyCode::Enter),
+            Some(RelocateOutcome::StartFresh)
+        ));
+    }
+}
@@ -0,0 +1,483 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::cli::ReportCommands;
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::settings::{get_data_dir, load_settings, ReportDefault};
+
+/// Reports that take a single fiscal year.
+const YEAR_ONLY: &[&str] = &["tax", "k1", "deductions", "compensation", "clients"];
+
+/// Reports that take a calendar month or a fiscal year.
+const MONTH_OR_YEAR: &[&str] = &["pnl", "expenses", "cashflow", "register", "tags"];
+
+/// Reports that can have defaults, keyed like `nigel report <name>` (`k1`
+/// for K-1 prep); `digest` is `nigel digest`.
+pub const REPORTS: &[&str] = &[
+    "pnl",
+    "expenses",
+    "tax",
+    "cashflow",
+    "register",
+    "flagged",
+    "reimbursables",
+    "balance",
+    "balance-history",
+    "k1",
+    "deductions",
+    "compensation",
+    "tags",
+    "clients",
+    "books",
+    "quality",
+    "digest",
+];
+
+/// Values a default period can take.
+pub const PERIODS: &[&str] = &["month", "last_month", "year", "prior_year"];
+
+/// One of a report's defaults, as named in `report.<name>.<field>` keys.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    Period,
+    Until,
+    Account,
+    Format,
+}
+
+impl Field {
+    pub const ALL: [Field; 4] = [Field::Period, Field::Until, Field::Account, Field::Format];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Period => "default_period",
+            Self::Until => "default_until",
+            Self::Account => "default_account",
+            Self::Format => "default_format",
+        }
+    }
+}
+
+/// Split a `report.<name>.<field>` settings key.
+pub fn parse_key(key: &str) -> Result<(&str, Field)> {
+    let fields = Field::ALL.map(Field::key).join(", ");
+    let mut parts = key.splitn(3, '.');
+    let (Some("report"), Some(report), Some(field)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(NigelError::Other(format!(
+            "Unknown setting '{key}'. Keys look like report.<report>.<field>, \
+             with a field of {fields}."
+        )));
+    };
+    if !REPORTS.contains(&report) {
+        return Err(NigelError::Other(format!(
+            "Unknown report '{report}'. Expected one of: {}.",
+            REPORTS.join(", ")
+        )));
+    }
+    let field = Field::ALL
+        .into_iter()
+        .find(|f| f.key() == field)
+        .ok_or_else(|| {
+            NigelError::Other(format!(
+                "Unknown field '{field}'. Expected one of: {fields}."
+            ))
+        })?;
+    Ok((report, field))
+}
+
+/// Whether `report` takes a period at all.
+pub fn takes_period(report: &str) -> bool {
+    YEAR_ONLY.contains(&report) || MONTH_OR_YEAR.contains(&report) || report == "digest"
+}
+
+/// Periods `report` accepts: the digest covers a month, never a year.
+pub fn periods(report: &str) -> &'static [&'static str] {
+    match report {
+        "digest" => &PERIODS[..2],
+        r if takes_period(r) => PERIODS,
+        _ => &[],
+    }
+}
+
+/// `--format` values `report` accepts, PDF first.
+pub fn formats(report: &str) -> &'static [&'static str] {
+    match report {
+        "register" => &["pdf", "text", "csv", "xlsx"],
+        "k1" => &["pdf", "text", "csv", "xlsx", "json"],
+        "digest" => &[],
+        _ => &["pdf", "text"],
+    }
+}
+
+/// Whether `report` takes an account filter (only the register does).
+pub fn takes_account(report: &str) -> bool {
+    report == "register"
+}
+
+/// `report`'s default for `field`, if set.
+pub fn get(
+    defaults: &BTreeMap<String, ReportDefault>,
+    report: &str,
+    field: Field,
+) -> Option<String> {
+    let default = defaults.get(report)?;
+    match field {
+        Field::Period => (!default.period.is_empty()).then(|| default.period.clone()),
+        Field::Until => default.until.map(|m| m.to_string()),
+        Field::Account => default.account.clone(),
+        Field::Format => default.format.clone(),
+    }
+}
+
+/// Check `value` fits `report` and store it, or clear the default when
+/// `value` is `None`. An entry left with nothing set is removed. Account
+/// names aren't checked here; the caller has the book.
+pub fn set(
+    defaults: &mut BTreeMap<String, ReportDefault>,
+    report: &str,
+    field: Field,
+    value: Option<&str>,
+) -> Result<()> {
+    let value = value.map(str::trim);
+    let unsupported =
+        |what: &str| NigelError::Other(format!("The {report} report doesn't take {what}."));
+    let mut entry = defaults.get(report).cloned().unwrap_or_default();
+    match field {
+        Field::Period => {
+            let allowed = periods(report);
+            if allowed.is_empty() {
+                return Err(unsupported("a period"));
+            }
+            match value {
+                Some(v) if !allowed.contains(&v) => {
+                    return Err(NigelError::Other(format!(
+                        "Unknown period '{v}' for {report}. Expected one of: {}.",
+                        allowed.join(", ")
+                    )));
+                }
+                v => entry.period = v.unwrap_or_default().to_string(),
+            }
+        }
+        Field::Until => {
+            if !takes_period(report) {
+                return Err(unsupported("a period"));
+            }
+            entry.until = match value {
+                None => None,
+                Some(v) => match v.parse::<u32>() {
+                    Ok(m @ 1..=12) => Some(m),
+                    _ => {
+                        return Err(NigelError::Other(format!(
+                            "default_until is a month number, 1-12 (got '{v}')."
+                        )))
+                    }
+                },
+            };
+        }
+        Field::Account => {
+            if !takes_account(report) {
+                return Err(unsupported("an account filter"));
+            }
+            entry.account = value.map(str::to_string);
+        }
+        Field::Format => {
+            let allowed = formats(report);
+            if allowed.is_empty() {
+                return Err(unsupported("a format"));
+            }
+            match value {
+                Some(v) if !allowed.contains(&v) => {
+                    return Err(NigelError::Other(format!(
+                        "Unknown format '{v}' for {report}. Expected one of: {}.",
+                        allowed.join(", ")
+                    )));
+                }
+                v => entry.format = v.map(str::to_string),
+            }
+        }
+    }
+    if entry == ReportDefault::default() {
+        defaults.remove(report);
+    } else {
+        defaults.insert(report.to_string(), entry);
+    }
+    Ok(())
+}
+
+/// A resolved default period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Period {
+    /// Fiscal year.
+    Year(i32),
+    /// Calendar month.
+    Month(i32, u32),
+}
+
+impl Period {
+    /// `(year, month)` in the shape the report builders and
+    /// `TableReportView::date_params()` use.
+    pub fn params(self) -> (Option<i32>, Option<String>) {
+        match self {
+            Self::Year(fy) => (Some(fy), None),
+            Self::Month(y, m) => (Some(y), Some(format!("{y}-{m:02}"))),
+        }
+    }
+}
+
+/// The default period `defaults` gives `report` on `today`, fitted to what
+/// the report accepts: month periods on a year-only report become the fiscal
+/// year holding that month, and the digest ignores year periods. Unknown
+/// reports and periods, and an `until` month already past, give `None`.
+pub fn resolve(
+    report: &str,
+    defaults: &BTreeMap<String, ReportDefault>,
+    fiscal: FiscalYear,
+    today: NaiveDate,
+) -> Option<Period> {
+    let default = defaults.get(report)?;
+    if default.until.is_some_and(|until| today.month() > until) {
+        return None;
+    }
+    let period = match default.period.as_str() {
+        "month" => Period::Month(today.year(), today.month()),
+        "last_month" => {
+            let (y, m) = crate::digest::last_month(today);
+            Period::Month(y, m)
+        }
+        "year" => Period::Year(fiscal.year_of(today)),
+        "prior_year" => Period::Year(fiscal.year_of(today) - 1),
+        _ => return None,
+    };
+    if YEAR_ONLY.contains(&report) {
+        return Some(match period {
+            Period::Month(y, m) => Period::Year(fiscal.year_of_month(y, m)),
+            year => year,
+        });
+    }
+    if report == "digest" {
+        return matches!(period, Period::Month(..)).then_some(period);
+    }
+    MONTH_OR_YEAR.contains(&report).then_some(period)
+}
+
+/// `resolve()` with the saved settings and today's date.
+pub fn default_period(report: &str, fiscal: FiscalYear) -> Option<Period> {
+    let settings = load_settings();
+    resolve(
+        report,
+        &settings.report_defaults,
+        fiscal,
+        chrono::Local::now().date_naive(),
+    )
+}
+
+/// The key `cmd`'s defaults are stored under.
+pub fn report_key(cmd: &ReportCommands) -> &'static str {
+    match cmd {
+        ReportCommands::K1 { .. } => "k1",
+        other => other.report_name(),
+    }
+}
+
+/// The configured default for `field` of `report`, from the saved settings.
+pub fn default_for(report: &str, field: Field) -> Option<String> {
+    get(&load_settings().report_defaults, report, field)
+}
+
+/// Fill in the configured defaults for flags `nigel report` was run
+/// without: the register's account filter, and the period when no date
+/// flags were given at all. The export format is filled in at export time.
+pub fn apply(cmd: &mut ReportCommands) {
+    let key = report_key(cmd);
+    if let ReportCommands::Register { account, .. } = cmd {
+        if account.is_none() {
+            *account = default_for(key, Field::Account);
+        }
+    }
+    let (month, year) = match cmd {
+        ReportCommands::Pnl {
+            month,
+            year,
+            from_date: None,
+            to_date: None,
+            ..
+        }
+        | ReportCommands::Register {
+            month,
+            year,
+            from_date: None,
+            to_date: None,
+            ..
+        }
+        | ReportCommands::Expenses { month, year, .. }
+        | ReportCommands::Cashflow { month, year, .. }
+        | ReportCommands::Tags { month, year, .. } => (Some(month), year),
+        ReportCommands::Tax { year, .. }
+        | ReportCommands::K1 { year, .. }
+        | ReportCommands::Deductions { year, .. }
+        | ReportCommands::Compensation { year, .. }
+        | ReportCommands::Clients { year, .. } => (None, year),
+        _ => return,
+    };
+    if year.is_some() || month.as_ref().is_some_and(|m| m.is_some()) {
+        return;
+    }
+    let Ok(conn) = get_connection(&get_data_dir().join("nigel.db")) else {
+        return;
+    };
+    match (default_period(key, FiscalYear::load(&conn)), month) {
+        (Some(Period::Month(y, m)), Some(month)) => *month = Some(format!("{y}-{m:02}")),
+        (Some(Period::Year(fy)), _) => *year = Some(fy),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults(entries: &[(&str, &str, Option<u32>)]) -> BTreeMap<String, ReportDefault> {
+        entries
+            .iter()
+            .map(|(report, period, until)| {
+                (
+                    report.to_string(),
+                    ReportDefault {
+                        period: period.to_string(),
+                        until: *until,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn resolves_periods_for_each_kind_of_report() {
+        let d = defaults(&[
+            ("pnl", "month", None),
+            ("register", "last_month", None),
+            ("expenses", "prior_year", None),
+            ("k1", "month", None),
+            ("digest", "year", None),
+            ("flagged", "month", None),
+            ("tags", "fortnight", None),
+        ]);
+        let fiscal = FiscalYear::CALENDAR;
+        let today = date(2026, 1, 15);
+
+        assert_eq!(
+            resolve("pnl", &d, fiscal, today),
+            Some(Period::Month(2026, 1))
+        );
+        assert_eq!(
+            resolve("register", &d, fiscal, today),
+            Some(Period::Month(2025, 12))
+        );
+        assert_eq!(
+            resolve("expenses", &d, fiscal, today),
+            Some(Period::Year(2025))
+        );
+        assert_eq!(resolve("k1", &d, fiscal, today), Some(Period::Year(2026)));
+        // Year periods don't fit the digest; the flagged list takes no date
+        assert_eq!(resolve("digest", &d, fiscal, today), None);
+        assert_eq!(resolve("flagged", &d, fiscal, today), None);
+        assert_eq!(resolve("tags", &d, fiscal, today), None);
+        assert_eq!(resolve("cashflow", &d, fiscal, today), None);
+    }
+
+    #[test]
+    fn until_limits_a_default_to_the_start_of_the_year() {
+        let d = defaults(&[("tax", "prior_year", Some(4))]);
+        let fiscal = FiscalYear::CALENDAR;
+
+        assert_eq!(
+            resolve("tax", &d, fiscal, date(2026, 4, 30)),
+            Some(Period::Year(2025))
+        );
+        assert_eq!(resolve("tax", &d, fiscal, date(2026, 5, 1)), None);
+    }
+
+    #[test]
+    fn fiscal_year_periods_follow_the_fiscal_start() {
+        let d = defaults(&[("compensation", "year", None), ("k1", "month", None)]);
+        let fiscal = FiscalYear::new(7);
+        let today = date(2025, 8, 1);
+
+        assert_eq!(
+            resolve("compensation", &d, fiscal, today),
+            Some(Period::Year(2026))
+        );
+        assert_eq!(resolve("k1", &d, fiscal, today), Some(Period::Year(2026)));
+        assert_eq!(
+            Period::Month(2025, 8).params(),
+            (Some(2025), Some("2025-08".to_string()))
+        );
+    }
+
+    #[test]
+    fn set_checks_each_field_against_the_report() {
+        let mut d = BTreeMap::new();
+        set(&mut d, "register", Field::Period, Some("month")).unwrap();
+        set(&mut d, "register", Field::Account, Some("Checking")).unwrap();
+        set(&mut d, "pnl", Field::Period, Some("year")).unwrap();
+        set(&mut d, "pnl", Field::Format, Some("text")).unwrap();
+        set(&mut d, "tax", Field::Until, Some("4")).unwrap();
+        assert_eq!(get(&d, "register", Field::Period).as_deref(), Some("month"));
+        assert_eq!(
+            get(&d, "register", Field::Account).as_deref(),
+            Some("Checking")
+        );
+        assert_eq!(get(&d, "pnl", Field::Format).as_deref(), Some("text"));
+        assert_eq!(d["tax"].until, Some(4));
+
+        for (report, field, value) in [
+            ("pnl", Field::Format, "csv"),
+            ("pnl", Field::Account, "Checking"),
+            ("pnl", Field::Period, "fortnight"),
+            ("digest", Field::Period, "year"),
+            ("balance", Field::Period, "month"),
+            ("tax", Field::Until, "13"),
+        ] {
+            assert!(
+                set(&mut d, report, field, Some(value)).is_err(),
+                "{report} {value}"
+            );
+        }
+
+        // Clearing the last field drops the entry
+        set(&mut d, "tax", Field::Until, None).unwrap();
+        assert!(!d.contains_key("tax"));
+        set(&mut d, "pnl", Field::Format, None).unwrap();
+        assert_eq!(get(&d, "pnl", Field::Format), None);
+        assert!(d.contains_key("pnl"));
+    }
+
+    #[test]
+    fn parse_key_names_the_report_and_field() {
+        assert_eq!(
+            parse_key("report.register.default_period").unwrap(),
+            ("register", Field::Period)
+        );
+        assert_eq!(
+            parse_key("report.k1.default_format").unwrap(),
+            ("k1", Field::Format)
+        );
+        for key in [
+            "report.register",
+            "reports.pnl.default_period",
+            "report.nope.default_period",
+            "report.pnl.period",
+        ] {
+            assert!(parse_key(key).is_err(), "{key}");
+        }
+    }
+}
@@ -1,15 +1,21 @@
+pub mod defaults;
+mod params;
 pub mod text;
 pub mod view;
 
-use std::io::IsTerminal;
+pub use params::ReportParams;
+
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
 
 use crate::cli::ReportOutputArgs;
 use crate::error::Result;
+use crate::tabular::{self, TabularFormat};
 
 use super::ReportCommands;
 
 pub fn dispatch(cmd: ReportCommands) -> Result<()> {
+    let params = ReportParams::from_command(&cmd)?;
     let args = cmd.output_args();
 
     // Validate --mode and --format values
@@ -21,90 +27,151 @@ pub fn dispatch(cmd: ReportCommands) -> Result<()> {
         }
     }
     if let Some(ref format) = args.format {
-        if format != "pdf" && format != "text" {
+        if !matches!(format.as_str(), "pdf" | "text" | "csv" | "xlsx" | "json") {
+            return Err(crate::error::NigelError::Other(format!(
+                "Unknown --format '{format}'. Expected 'pdf', 'text', 'csv', 'xlsx', or 'json'."
+            )));
+        }
+        // Only the register and the K-1 lines are row-shaped enough for a
+        // spreadsheet; JSON is for feeding K-1 numbers to tax software
+        let is_k1 = matches!(cmd, ReportCommands::K1 { .. });
+        if matches!(format.as_str(), "csv" | "xlsx")
+            && !is_k1
+            && !matches!(cmd, ReportCommands::Register { .. })
+        {
             return Err(crate::error::NigelError::Other(format!(
-                "Unknown --format '{format}'. Expected 'pdf' or 'text'."
+                "--format {format} is only supported for `report register` and `report k1`"
             )));
         }
+        if format == "json" && !is_k1 {
+            return Err(crate::error::NigelError::Other(
+                "--format json is only supported for `report k1`".into(),
+            ));
+        }
+    }
+
+    if args.output.as_deref() == Some("-") {
+        return dispatch_stdout(cmd, &params, args.format.as_deref());
     }
 
     // `report all` is always an export
     if matches!(cmd, ReportCommands::All { .. }) {
-        return dispatch_export(cmd, args);
+        return dispatch_export(cmd, &params, args);
     }
 
     if args.output.is_some() || args.mode.as_deref() == Some("export") {
-        dispatch_export(cmd, args)
+        dispatch_export(cmd, &params, args)
     } else if args.mode.as_deref() == Some("view") || std::io::stdout().is_terminal() {
-        dispatch_view(cmd)
+        dispatch_view(cmd, params)
     } else {
         // Non-TTY: plain text to stdout
-        let s = dispatch_text(&cmd)?;
+        let s = dispatch_text(&cmd, &params)?;
         println!("{s}");
         Ok(())
     }
 }
 
-fn dispatch_view(cmd: ReportCommands) -> Result<()> {
-    view::dispatch(cmd)
+/// `--output -`: stream one report to stdout for piping. Only the plain-text
+/// renderings (text, CSV for the register and K-1, JSON for the K-1) make
+/// sense on a pipe.
+fn dispatch_stdout(cmd: ReportCommands, params: &ReportParams, format: Option<&str>) -> Result<()> {
+    match format {
+        None | Some("text") => {
+            let s = dispatch_text(&cmd, params)?;
+            ignore_broken_pipe(writeln!(std::io::stdout().lock(), "{s}"))
+        }
+        Some("csv") => export_tabular(cmd, params, TabularFormat::Csv, Some("-".into())),
+        Some("json") => export_k1_json(params, Some("-".into())),
+        Some(f) => Err(crate::error::NigelError::Other(format!(
+            "--output - streams text, csv, or json; --format {f} needs a file path"
+        ))),
+    }
 }
 
-pub(crate) fn dispatch_text(cmd: &ReportCommands) -> Result<String> {
+/// A reader like `head` closing the pipe early isn't an error worth reporting.
+fn ignore_broken_pipe(res: std::io::Result<()>) -> Result<()> {
+    match res {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        other => Ok(other?),
+    }
+}
+
+fn dispatch_view(cmd: ReportCommands, params: ReportParams) -> Result<()> {
+    view::dispatch(cmd, params)
+}
+
+pub(crate) fn dispatch_text(cmd: &ReportCommands, params: &ReportParams) -> Result<String> {
     match cmd {
-        ReportCommands::Pnl {
-            month,
-            year,
-            from_date,
-            to_date,
-            ..
-        } => text::pnl(month.clone(), *year, from_date.clone(), to_date.clone()),
-        ReportCommands::Expenses { month, year, .. } => text::expenses(month.clone(), *year),
-        ReportCommands::Tax { year, .. } => text::tax(*year),
-        ReportCommands::Cashflow { month, year, .. } => text::cashflow(month.clone(), *year),
-        ReportCommands::Register {
-            month,
-            year,
-            from_date,
-            to_date,
-            account,
-            ..
-        } => text::register(
-            month.clone(),
-            *year,
-            from_date.clone(),
-            to_date.clone(),
-            account.clone(),
-        ),
+        ReportCommands::Pnl { .. } => text::pnl(params),
+        ReportCommands::Expenses { .. } => text::expenses(params),
+        ReportCommands::Tax { .. } => text::tax(params.year),
+        ReportCommands::Cashflow { .. } => text::cashflow(params),
+        ReportCommands::Register { .. } => text::register(params),
         ReportCommands::Flagged { .. } => text::flagged(),
-        ReportCommands::Balance { .. } => text::balance(),
-        ReportCommands::K1 { year, .. } => text::k1(*year),
+        ReportCommands::Reimbursables { client, all, .. } => {
+            text::reimbursables(client.clone(), *all)
+        }
+        ReportCommands::Balance {
+            include_archived, ..
+        } => text::balance(*include_archived),
+        ReportCommands::BalanceHistory { .. } => text::balance_history(params),
+        ReportCommands::K1 { .. } => text::k1(params.year),
+        ReportCommands::Deductions { .. } => text::deductions(params.year),
+        ReportCommands::Compensation { .. } => text::compensation(params.year),
+        ReportCommands::Tags { .. } => text::tags(params),
+        ReportCommands::Clients { .. } => text::clients(params.year),
+        ReportCommands::Books { .. } => text::books(),
+        ReportCommands::Quality { .. } => text::quality(),
         ReportCommands::All { .. } => Err(crate::error::NigelError::Other(
             "`report all` is export-only".into(),
         )),
     }
 }
 
-fn dispatch_export(cmd: ReportCommands, args: ReportOutputArgs) -> Result<()> {
-    let is_text = args.format.as_deref() == Some("text");
+fn dispatch_export(
+    cmd: ReportCommands,
+    params: &ReportParams,
+    args: ReportOutputArgs,
+) -> Result<()> {
+    let format = args.format.clone().or_else(|| default_export_format(&cmd));
+    let is_text = format.as_deref() == Some("text");
 
     if is_text {
-        return export_text(cmd, args.output);
+        return export_text(cmd, params, args.output);
+    }
+    if format.as_deref() == Some("json") {
+        return export_k1_json(params, args.output);
+    }
+    if let Some(format) = format.as_deref().and_then(TabularFormat::parse) {
+        return export_tabular(cmd, params, format, args.output);
     }
 
     // PDF export
-    dispatch_pdf_export(cmd, args.output)
+    dispatch_pdf_export(cmd, params, args.output)
 }
 
-fn export_text(cmd: ReportCommands, output: Option<String>) -> Result<()> {
-    if let ReportCommands::All {
-        year, output_dir, ..
-    } = cmd
+/// The report's own default format, or else the `export_format` setting,
+/// when `--format` is omitted. PDF is the built-in default (None here), and
+/// the setting's csv/xlsx only apply to the register.
+fn default_export_format(cmd: &ReportCommands) -> Option<String> {
+    if let Some(format) = defaults::default_for(defaults::report_key(cmd), defaults::Field::Format)
     {
-        return export_all_text(year, output_dir);
+        return (format != "pdf").then_some(format);
+    }
+    match crate::settings::load_settings().export_format() {
+        "text" => Some("text".into()),
+        f @ ("csv" | "xlsx") if matches!(cmd, ReportCommands::Register { .. }) => Some(f.into()),
+        _ => None,
+    }
+}
+
+fn export_text(cmd: ReportCommands, params: &ReportParams, output: Option<String>) -> Result<()> {
+    if let ReportCommands::All { output_dir, .. } = cmd {
+        return export_all_text(params.year, output_dir);
     }
 
     let name = cmd.report_name();
-    let s = dispatch_text(&cmd)?;
+    let s = dispatch_text(&cmd, params)?;
     let path = output.unwrap_or_else(|| default_text_path(name));
     let p = PathBuf::from(&path);
     if let Some(parent) = p.parent() {
@@ -116,6 +183,83 @@ fn export_text(cmd: ReportCommands, output: Option<String>) -> Result<()> {
     Ok(())
 }
 
+fn export_tabular(
+    cmd: ReportCommands,
+    params: &ReportParams,
+    format: TabularFormat,
+    output: Option<String>,
+) -> Result<()> {
+    let name = cmd.report_name();
+    let conn = crate::db::get_connection(&crate::settings::get_data_dir().join("nigel.db"))?;
+    let sheet = match cmd {
+        ReportCommands::Register { .. } => {
+            let data = crate::reports::get_register(
+                &conn,
+                params.year,
+                params.month,
+                params.from_date.as_deref(),
+                params.to_date.as_deref(),
+                params.account.as_deref(),
+                params.tag.as_deref(),
+            )?;
+            tabular::register_sheet(&data.rows)
+        }
+        ReportCommands::K1 { .. } => {
+            let data = crate::reports::get_k1_prep(&conn, params.year)?;
+            tabular::k1_sheet(&crate::reports::k1_line_totals(&data))
+        }
+        _ => unreachable!("dispatch() rejects csv/xlsx for other reports"),
+    };
+    if output.as_deref() == Some("-") {
+        return match tabular::write_csv_to(&sheet, std::io::stdout().lock()) {
+            Err(crate::error::NigelError::Csv(e))
+                if matches!(e.kind(), csv::ErrorKind::Io(io)
+                    if io.kind() == std::io::ErrorKind::BrokenPipe) =>
+            {
+                Ok(())
+            }
+            other => other,
+        };
+    }
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_export_path(name, format.extension()));
+    tabular::write(&sheet, &path, format)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// `report k1 --format json`: the worksheet lines keyed by form and line for
+/// tax software, plus the checks a preparer should see before using them.
+fn export_k1_json(params: &ReportParams, output: Option<String>) -> Result<()> {
+    let year = params.year;
+    let conn = crate::db::get_connection(&crate::settings::get_data_dir().join("nigel.db"))?;
+    let data = crate::reports::get_k1_prep(&conn, year)?;
+    let doc = serde_json::json!({
+        "form": "1120-S",
+        "year": year,
+        "company": crate::db::get_metadata(&conn, "company_name").unwrap_or_default(),
+        "lines": crate::reports::k1_line_totals(&data),
+        "uncategorized_count": data.validation.uncategorized_count,
+        "below_comp_target": data.validation.below_comp_target,
+    });
+    let json = serde_json::to_string_pretty(&doc)
+        .map_err(|e| crate::error::NigelError::Other(e.to_string()))?;
+    if output.as_deref() == Some("-") {
+        return ignore_broken_pipe(writeln!(std::io::stdout().lock(), "{json}"));
+    }
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_export_path("k1-prep", "json"));
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, format!("{json}\n"))?;
+    crate::settings::restrict_file_permissions(&path)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
 fn export_all_text(year: Option<i32>, output_dir: Option<String>) -> Result<()> {
     let data_dir = crate::settings::get_data_dir();
     let date = chrono::Local::now().format("%Y-%m-%d").to_string();
@@ -128,15 +272,21 @@ fn export_all_text(year: Option<i32>, output_dir: Option<String>) -> Result<()>
         crate::settings::restrict_dir_permissions(&dir)?;
     }
 
+    let params = ReportParams::period(year, None);
     let reports: Vec<(&str, Result<String>)> = vec![
-        ("pnl", text::pnl(None, year, None, None)),
-        ("expenses", text::expenses(None, year)),
+        ("pnl", text::pnl(&params)),
+        ("expenses", text::expenses(&params)),
         ("tax", text::tax(year)),
-        ("cashflow", text::cashflow(None, year)),
-        ("register", text::register(None, year, None, None, None)),
+        ("cashflow", text::cashflow(&params)),
+        ("register", text::register(&params)),
         ("flagged", text::flagged()),
-        ("balance", text::balance()),
+        ("reimbursables", text::reimbursables(None, false)),
+        ("balance", text::balance(false)),
         ("k1-prep", text::k1(year)),
+        ("deductions", text::deductions(year)),
+        ("compensation", text::compensation(year)),
+        ("tags", text::tags(&params)),
+        ("clients", text::clients(year)),
     ];
 
     for (name, result) in reports {
@@ -153,10 +303,14 @@ fn export_all_text(year: Option<i32>, output_dir: Option<String>) -> Result<()>
     Ok(())
 }
 
-fn dispatch_pdf_export(cmd: ReportCommands, output: Option<String>) -> Result<()> {
+fn dispatch_pdf_export(
+    cmd: ReportCommands,
+    params: &ReportParams,
+    output: Option<String>,
+) -> Result<()> {
     #[cfg(not(feature = "pdf"))]
     {
-        let _ = (cmd, output);
+        let _ = (cmd, params, output);
         return Err(crate::error::NigelError::Other(
             "PDF export requires the 'pdf' feature — build with `cargo build --features pdf`"
                 .into(),
@@ -165,16 +319,20 @@ fn dispatch_pdf_export(cmd: ReportCommands, output: Option<String>) -> Result<()
 
     #[cfg(feature = "pdf")]
     {
-        crate::cli::export::dispatch_pdf(cmd, output)?;
+        crate::cli::export::dispatch_pdf(cmd, params, output)?;
         Ok(())
     }
 }
 
-fn default_text_path(name: &str) -> String {
+fn default_export_path(name: &str, extension: &str) -> PathBuf {
     let date = chrono::Local::now().format("%Y-%m-%d").to_string();
     crate::settings::get_data_dir()
         .join("exports")
-        .join(format!("{name}-{date}.txt"))
+        .join(format!("{name}-{date}.{extension}"))
+}
+
+fn default_text_path(name: &str) -> String {
+    default_export_path(name, "txt")
         .to_string_lossy()
         .into_owned()
 }
@@ -0,0 +1,348 @@
+use chrono::NaiveDate;
+
+use crate::cli::ReportCommands;
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::reports::{BalanceInterval, Granularity};
+
+/// The period and filters a report runs over. Parsed and checked once in
+/// `dispatch`, then handed to whichever renderer (text, view, PDF, CSV)
+/// the output flags pick, so they all read the same period.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReportParams {
+    /// `--year`, else the calendar year of `--month`.
+    pub year: Option<i32>,
+    /// Month number (1-12) from `--month YYYY-MM`.
+    pub month: Option<u32>,
+    /// `--from`/`--to`, both or neither; a range wins over the year and month.
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+    pub account: Option<String>,
+    pub tag: Option<String>,
+    pub depth: Option<usize>,
+    pub compare: bool,
+    pub exclude_tags: Vec<String>,
+    /// Cash flow rows per week, month, or quarter (`--granularity`).
+    pub granularity: Granularity,
+    /// Balance history points per day or week (`--interval`).
+    pub interval: BalanceInterval,
+    /// Balance history counts the account's opening balance.
+    pub opening_balance: bool,
+}
+
+impl ReportParams {
+    /// The parameters of a `nigel report` subcommand, with the month parsed
+    /// and the dates and depth checked.
+    pub fn from_command(cmd: &ReportCommands) -> Result<Self> {
+        let (month, mut params) = match cmd {
+            ReportCommands::Pnl {
+                month,
+                year,
+                from_date,
+                to_date,
+                depth,
+                collapse,
+                compare,
+                exclude_tags,
+                ..
+            } => (
+                month.as_deref(),
+                Self {
+                    year: *year,
+                    from_date: from_date.clone(),
+                    to_date: to_date.clone(),
+                    // A collapsed P&L is rolled up to its top-level groups
+                    depth: if *collapse { Some(1) } else { *depth },
+                    compare: *compare,
+                    exclude_tags: exclude_tags.clone(),
+                    ..Self::default()
+                },
+            ),
+            ReportCommands::Expenses {
+                month,
+                year,
+                depth,
+                exclude_tags,
+                ..
+            } => (
+                month.as_deref(),
+                Self {
+                    year: *year,
+                    depth: *depth,
+                    exclude_tags: exclude_tags.clone(),
+                    ..Self::default()
+                },
+            ),
+            ReportCommands::Cashflow {
+                month,
+                year,
+                exclude_tags,
+                granularity,
+                ..
+            } => (
+                month.as_deref(),
+                Self {
+                    year: *year,
+                    exclude_tags: exclude_tags.clone(),
+                    granularity: granularity
+                        .as_deref()
+                        .map(Granularity::parse)
+                        .transpose()?
+                        .unwrap_or_default(),
+                    ..Self::default()
+                },
+            ),
+            ReportCommands::Register {
+                month,
+                year,
+                from_date,
+                to_date,
+                account,
+                tag,
+                ..
+            } => (
+                month.as_deref(),
+                Self {
+                    year: *year,
+                    from_date: from_date.clone(),
+                    to_date: to_date.clone(),
+                    account: account.clone(),
+                    tag: tag.clone(),
+                    ..Self::default()
+                },
+            ),
+            ReportCommands::BalanceHistory {
+                account,
+                month,
+                year,
+                from_date,
+                to_date,
+                interval,
+                opening_balance,
+                ..
+            } => (
+                month.as_deref(),
+                Self {
+                    year: *year,
+                    from_date: from_date.clone(),
+                    to_date: to_date.clone(),
+                    account: Some(account.clone()),
+                    interval: interval
+                        .as_deref()
+                        .map(BalanceInterval::parse)
+                        .transpose()?
+                        .unwrap_or_default(),
+                    opening_balance: *opening_balance,
+                    ..Self::default()
+                },
+            ),
+            ReportCommands::Tags { month, year, .. } => (
+                month.as_deref(),
+                Self {
+                    year: *year,
+                    ..Self::default()
+                },
+            ),
+            ReportCommands::Tax { year, .. }
+            | ReportCommands::K1 { year, .. }
+            | ReportCommands::Deductions { year, .. }
+            | ReportCommands::Compensation { year, .. }
+            | ReportCommands::Clients { year, .. }
+            | ReportCommands::All { year, .. } => (
+                None,
+                Self {
+                    year: *year,
+                    ..Self::default()
+                },
+            ),
+            ReportCommands::Flagged { .. }
+            | ReportCommands::Reimbursables { .. }
+            | ReportCommands::Balance { .. }
+            | ReportCommands::Books { .. }
+            | ReportCommands::Quality { .. } => (None, Self::default()),
+        };
+        if let Some(month) = month {
+            let (y, m) = parse_month(month)?;
+            params.year = params.year.or(Some(y));
+            params.month = Some(m);
+        }
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// A year, or a `YYYY-MM` month, with no other filters: the period a
+    /// dashboard report or a view's date navigation lands on.
+    pub fn period(year: Option<i32>, month: Option<&str>) -> Self {
+        let mut params = Self {
+            year,
+            ..Self::default()
+        };
+        if let Some((y, m)) = month.and_then(|m| parse_month(m).ok()) {
+            params.year = year.or(Some(y));
+            params.month = Some(m);
+        }
+        params
+    }
+
+    /// These filters over another year or month, keeping any range.
+    pub fn with_period(&self, year: Option<i32>, month: Option<&str>) -> Self {
+        let period = Self::period(year, month);
+        Self {
+            year: period.year,
+            month: period.month,
+            ..self.clone()
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.depth == Some(0) {
+            return Err(NigelError::Other("--depth must be at least 1".into()));
+        }
+        let from = self
+            .from_date
+            .as_deref()
+            .map(|d| parse_date("--from", d))
+            .transpose()?;
+        let to = self
+            .to_date
+            .as_deref()
+            .map(|d| parse_date("--to", d))
+            .transpose()?;
+        match (from, to) {
+            (Some(from), Some(to)) if from > to => Err(NigelError::Other(format!(
+                "--from {from} is after --to {to}"
+            ))),
+            (Some(_), None) => Err(NigelError::Other(
+                "--from requires --to (both date boundaries must be specified)".into(),
+            )),
+            (None, Some(_)) => Err(NigelError::Other(
+                "--to requires --from (both date boundaries must be specified)".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// `2025-01-01 to 2025-03-31` when a range was given.
+    pub fn range_label(&self) -> Option<String> {
+        match (&self.from_date, &self.to_date) {
+            (Some(from), Some(to)) => Some(format!(
+                "{} to {}",
+                crate::fmt::date(from),
+                crate::fmt::date(to)
+            )),
+            _ => None,
+        }
+    }
+
+    /// The period under a report's title: the range, `YYYY-MM`, or the
+    /// fiscal year (the current one when none was given).
+    pub fn period_label(&self, conn: &rusqlite::Connection) -> String {
+        if let Some(range) = self.range_label() {
+            return range;
+        }
+        let fiscal = FiscalYear::load(conn);
+        match (self.year, self.month) {
+            (Some(y), Some(m)) => format!("{y}-{m:02}"),
+            (year, _) => fiscal.label(year.unwrap_or_else(|| fiscal.current())),
+        }
+    }
+}
+
+fn parse_month(month: &str) -> Result<(i32, u32)> {
+    let date = NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+        .map_err(|_| NigelError::Other(format!("Invalid --month: '{month}' (expected YYYY-MM)")))?;
+    Ok((
+        chrono::Datelike::year(&date),
+        chrono::Datelike::month(&date),
+    ))
+}
+
+fn parse_date(flag: &str, date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+        NigelError::Other(format!(
+            "Invalid {flag} date: '{date}' (expected YYYY-MM-DD)"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::cli::{Cli, Commands};
+    use crate::db::{get_connection, init_db};
+
+    fn parse(args: &[&str]) -> Result<ReportParams> {
+        let cli = Cli::try_parse_from(["nigel", "report"].iter().chain(args)).unwrap();
+        let Some(Commands::Report { command }) = cli.command else {
+            panic!("expected a report command");
+        };
+        ReportParams::from_command(&command)
+    }
+
+    #[test]
+    fn month_sets_the_year_unless_one_is_given() {
+        let params = parse(&["pnl", "--month", "2025-03"]).unwrap();
+        assert_eq!((params.year, params.month), (Some(2025), Some(3)));
+        let params = parse(&["tags", "--month", "2025-03", "--year", "2024"]).unwrap();
+        assert_eq!((params.year, params.month), (Some(2024), Some(3)));
+        let params = parse(&["tax", "--year", "2024"]).unwrap();
+        assert_eq!((params.year, params.month), (Some(2024), None));
+    }
+
+    #[test]
+    fn collapse_rolls_up_to_the_top_level() {
+        assert_eq!(parse(&["pnl", "--collapse"]).unwrap().depth, Some(1));
+        assert_eq!(parse(&["pnl", "--depth", "2"]).unwrap().depth, Some(2));
+    }
+
+    #[test]
+    fn rejects_bad_periods() {
+        let err = |args: &[&str]| parse(args).unwrap_err().to_string();
+        assert!(err(&["pnl", "--month", "2025-13"]).contains("Invalid --month"));
+        assert!(err(&["expenses", "--month", "March"]).contains("Invalid --month"));
+        assert!(
+            err(&["register", "--from", "2025-02-30", "--to", "2025-03-31"])
+                .contains("Invalid --from date")
+        );
+        assert!(err(&["pnl", "--from", "2025-01-01"]).contains("--from requires --to"));
+        assert!(err(&["register", "--to", "2025-01-01"]).contains("--to requires --from"));
+        assert!(err(&["pnl", "--from", "2025-04-01", "--to", "2025-03-31"]).contains("is after"));
+        assert!(err(&["expenses", "--depth", "0"]).contains("--depth must be at least 1"));
+    }
+
+    #[test]
+    fn period_label_prefers_the_range_then_the_month() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+
+        let params = parse(&[
+            "register",
+            "--year",
+            "2025",
+            "--from",
+            "2025-01-01",
+            "--to",
+            "2025-03-31",
+        ])
+        .unwrap();
+        assert_eq!(params.period_label(&conn), "2025-01-01 to 2025-03-31");
+        let params = params.with_period(Some(2025), Some("2025-06"));
+        assert_eq!(params.month, Some(6));
+        assert_eq!(
+            params.range_label().as_deref(),
+            Some("2025-01-01 to 2025-03-31")
+        );
+
+        assert_eq!(
+            ReportParams::period(None, Some("2025-06")).period_label(&conn),
+            "2025-06"
+        );
+        assert_eq!(
+            ReportParams::period(Some(2024), None).period_label(&conn),
+            "FY 2024"
+        );
+    }
+}
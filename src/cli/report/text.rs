@@ -1,15 +1,26 @@
 use colored::Colorize;
 use comfy_table::{Cell, Table};
 
-use crate::cli::parse_month_opt;
+use crate::cli::report::ReportParams;
+use crate::data_quality::{DateBounds, QualityReport};
 use crate::db::{get_connection, get_metadata};
 use crate::error::Result;
-use crate::fmt::money;
+use crate::fmt::{self, compare_columns, money, money_in};
+use crate::locales::t;
+use crate::overview::{BookStatus, Overview};
 use crate::reports;
-use crate::settings::get_data_dir;
+use crate::settings::{get_data_dir, load_settings};
+
+/// Note under a report body which tags were left out, if any.
+fn with_exclusions(body: String, exclude_tags: &[String]) -> String {
+    match reports::excluded_tags_label(exclude_tags) {
+        Some(label) => format!("{body}\nExcluding {label}"),
+        None => body,
+    }
+}
 
 /// Prepend company name as a header line if non-empty.
-fn with_header(company_name: &str, body: String) -> String {
+pub(crate) fn with_header(company_name: &str, body: String) -> String {
     if company_name.is_empty() {
         body
     } else {
@@ -21,26 +32,54 @@ fn with_header(company_name: &str, body: String) -> String {
 // Data-fetching + formatting wrappers (used by dispatch)
 // ---------------------------------------------------------------------------
 
-pub fn pnl(
-    month: Option<String>,
-    year: Option<i32>,
-    from_date: Option<String>,
-    to_date: Option<String>,
-) -> Result<String> {
+pub fn pnl(params: &ReportParams) -> Result<String> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let (my, mm) = parse_month_opt(&month);
-    let y = year.or(my);
-    let data = reports::get_pnl(&conn, y, mm, from_date.as_deref(), to_date.as_deref())?;
-    Ok(with_header(&company, format_pnl(&data)))
+    let exclude_tags = &params.exclude_tags;
+    if params.compare {
+        let data = reports::get_pnl_comparison(
+            &conn,
+            params.year,
+            params.month,
+            params.from_date.as_deref(),
+            params.to_date.as_deref(),
+            params.depth,
+            exclude_tags,
+        )?;
+        return Ok(with_header(
+            &company,
+            with_exclusions(format_pnl_comparison(&data), exclude_tags),
+        ));
+    }
+    let data = reports::get_pnl(
+        &conn,
+        params.year,
+        params.month,
+        params.from_date.as_deref(),
+        params.to_date.as_deref(),
+        params.depth,
+        exclude_tags,
+    )?;
+    Ok(with_header(
+        &company,
+        with_exclusions(format_pnl(&data), exclude_tags),
+    ))
 }
 
-pub fn expenses(month: Option<String>, year: Option<i32>) -> Result<String> {
+pub fn expenses(params: &ReportParams) -> Result<String> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let (my, mm) = parse_month_opt(&month);
-    let data = reports::get_expense_breakdown(&conn, year.or(my), mm)?;
-    Ok(with_header(&company, format_expenses(&data)))
+    let data = reports::get_expense_breakdown(
+        &conn,
+        params.year,
+        params.month,
+        params.depth,
+        &params.exclude_tags,
+    )?;
+    Ok(with_header(
+        &company,
+        with_exclusions(format_expenses(&data), &params.exclude_tags),
+    ))
 }
 
 pub fn tax(year: Option<i32>) -> Result<String> {
@@ -50,32 +89,33 @@ pub fn tax(year: Option<i32>) -> Result<String> {
     Ok(with_header(&company, format_tax(&data)))
 }
 
-pub fn cashflow(month: Option<String>, year: Option<i32>) -> Result<String> {
+pub fn cashflow(params: &ReportParams) -> Result<String> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let (my, mm) = parse_month_opt(&month);
-    let data = reports::get_cashflow(&conn, year.or(my), mm)?;
-    Ok(with_header(&company, format_cashflow(&data)))
-}
-
-pub fn register(
-    month: Option<String>,
-    year: Option<i32>,
-    from_date: Option<String>,
-    to_date: Option<String>,
-    account: Option<String>,
-) -> Result<String> {
+    let data = reports::get_cashflow_by(
+        &conn,
+        params.year,
+        params.month,
+        &params.exclude_tags,
+        params.granularity,
+    )?;
+    Ok(with_header(
+        &company,
+        with_exclusions(format_cashflow(&data), &params.exclude_tags),
+    ))
+}
+
+pub fn register(params: &ReportParams) -> Result<String> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let (my, mm) = parse_month_opt(&month);
-    let y = year.or(my);
     let data = reports::get_register(
         &conn,
-        y,
-        mm,
-        from_date.as_deref(),
-        to_date.as_deref(),
-        account.as_deref(),
+        params.year,
+        params.month,
+        params.from_date.as_deref(),
+        params.to_date.as_deref(),
+        params.account.as_deref(),
+        params.tag.as_deref(),
     )?;
     Ok(with_header(&company, format_register(&data)))
 }
@@ -87,13 +127,36 @@ pub fn flagged() -> Result<String> {
     Ok(with_header(&company, format_flagged(&rows)))
 }
 
-pub fn balance() -> Result<String> {
+pub fn reimbursables(client: Option<String>, all: bool) -> Result<String> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let report = crate::reimbursables::get_report(&conn, client.as_deref(), all)?;
+    Ok(with_header(&company, format_reimbursables(&report)))
+}
+
+pub fn balance(include_archived: bool) -> Result<String> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
-    let data = reports::get_balance(&conn)?;
+    let data = reports::get_balance(&conn, include_archived)?;
     Ok(with_header(&company, format_balance(&data)))
 }
 
+pub fn balance_history(params: &ReportParams) -> Result<String> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let data = reports::get_balance_history(
+        &conn,
+        params.account.as_deref().unwrap_or_default(),
+        params.year,
+        params.month,
+        params.from_date.as_deref(),
+        params.to_date.as_deref(),
+        params.interval,
+        params.opening_balance,
+    )?;
+    Ok(with_header(&company, format_balance_history(&data)))
+}
+
 pub fn k1(year: Option<i32>) -> Result<String> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let company = get_metadata(&conn, "company_name").unwrap_or_default();
@@ -101,22 +164,82 @@ pub fn k1(year: Option<i32>) -> Result<String> {
     Ok(with_header(&company, format_k1(&data)))
 }
 
+pub fn deductions(year: Option<i32>) -> Result<String> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let data = reports::get_deductions(&conn, year)?;
+    Ok(with_header(&company, format_deductions(&data)))
+}
+
+pub fn compensation(year: Option<i32>) -> Result<String> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let data = reports::get_compensation(&conn, year)?;
+    Ok(with_header(&company, format_compensation(&data)))
+}
+
+pub fn clients(year: Option<i32>) -> Result<String> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let data = reports::get_clients(&conn, year)?;
+    Ok(with_header(&company, format_clients(&data)))
+}
+
+pub fn books() -> Result<String> {
+    let data = crate::overview::get_overview(&get_data_dir(), &load_settings().overview_books);
+    Ok(format_books(&data))
+}
+
+pub fn quality() -> Result<String> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let data = crate::data_quality::get_report(&conn, DateBounds::current())?;
+    Ok(with_header(&company, format_quality(&data)))
+}
+
+pub fn tags(params: &ReportParams) -> Result<String> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let data = reports::get_tags_report(&conn, params.year, params.month)?;
+    Ok(with_header(&company, format_tags(&data)))
+}
+
 // ---------------------------------------------------------------------------
 // Pure formatting functions (report data → String)
 // ---------------------------------------------------------------------------
 
+/// One side of the P&L: a top-level category on its own line, or a group
+/// header with its sub-categories indented below and a subtotal.
+fn add_pnl_groups(table: &mut Table, items: &[reports::PnlItem], amount: fn(f64) -> f64) {
+    for group in reports::pnl_groups(items) {
+        if group.is_single() {
+            table.add_row(vec![
+                Cell::new(format!("  {}", group.name)),
+                Cell::new(money(amount(group.total))),
+            ]);
+            continue;
+        }
+        table.add_row(vec![Cell::new(format!("  {}", group.name)), Cell::new("")]);
+        for (label, total) in &group.items {
+            table.add_row(vec![
+                Cell::new(format!("    {label}")),
+                Cell::new(money(amount(*total))),
+            ]);
+        }
+        table.add_row(vec![
+            Cell::new(format!("  Total {}", group.name).bold()),
+            Cell::new(money(amount(group.total))),
+        ]);
+    }
+}
+
 pub fn format_pnl(pnl: &reports::PnlReport) -> String {
     let mut table = Table::new();
     table.set_header(vec!["Category", "Amount"]);
 
     if !pnl.income.is_empty() {
         table.add_row(vec![Cell::new("INCOME".green().bold()), Cell::new("")]);
-        for item in &pnl.income {
-            table.add_row(vec![
-                Cell::new(format!("  {}", item.name)),
-                Cell::new(money(item.total)),
-            ]);
-        }
+        add_pnl_groups(&mut table, &pnl.income, |t| t);
         table.add_row(vec![
             Cell::new("Total Income".bold()),
             Cell::new(money(pnl.total_income)),
@@ -126,12 +249,7 @@ pub fn format_pnl(pnl: &reports::PnlReport) -> String {
 
     if !pnl.expenses.is_empty() {
         table.add_row(vec![Cell::new("EXPENSES".red().bold()), Cell::new("")]);
-        for item in &pnl.expenses {
-            table.add_row(vec![
-                Cell::new(format!("  {}", item.name)),
-                Cell::new(money(item.total.abs())),
-            ]);
-        }
+        add_pnl_groups(&mut table, &pnl.expenses, f64::abs);
         table.add_row(vec![
             Cell::new("Total Expenses".bold()),
             Cell::new(money(pnl.total_expenses.abs())),
@@ -146,7 +264,57 @@ pub fn format_pnl(pnl: &reports::PnlReport) -> String {
     };
     table.add_row(vec![Cell::new(net_label), Cell::new(money(pnl.net))]);
 
-    format!("Profit & Loss\n{table}")
+    format!("{}\n{table}", t("report.pnl"))
+}
+
+pub fn format_pnl_comparison(cmp: &reports::PnlComparison) -> String {
+    let mut header = vec!["Category".to_string(), cmp.periods[0].clone()];
+    for label in &cmp.periods[1..] {
+        header.extend([label.clone(), "$ Chg".to_string(), "% Chg".to_string()]);
+    }
+    let blank = vec![String::new(); header.len()];
+    let mut table = Table::new();
+    table.set_header(header);
+
+    let row = |label: Cell, values: &[f64]| {
+        let mut cells = vec![label];
+        cells.extend(compare_columns(values).into_iter().map(Cell::new));
+        cells
+    };
+    let abs = |values: &[f64]| values.iter().map(|v| v.abs()).collect::<Vec<_>>();
+
+    if !cmp.income.is_empty() {
+        table.add_row(vec![Cell::new("INCOME".green().bold())]);
+        for item in &cmp.income {
+            table.add_row(row(Cell::new(format!("  {}", item.name)), &item.totals));
+        }
+        table.add_row(row(Cell::new("Total Income".bold()), &cmp.total_income));
+        table.add_row(blank.clone());
+    }
+
+    if !cmp.expenses.is_empty() {
+        table.add_row(vec![Cell::new("EXPENSES".red().bold())]);
+        for item in &cmp.expenses {
+            table.add_row(row(
+                Cell::new(format!("  {}", item.name)),
+                &abs(&item.totals),
+            ));
+        }
+        table.add_row(row(
+            Cell::new("Total Expenses".bold()),
+            &abs(&cmp.total_expenses),
+        ));
+        table.add_row(blank);
+    }
+
+    let net_label = if cmp.net[0] >= 0.0 {
+        "NET".green().bold()
+    } else {
+        "NET".red().bold()
+    };
+    table.add_row(row(Cell::new(net_label), &cmp.net));
+
+    format!("Profit & Loss \u{2014} Comparison\n{table}")
 }
 
 pub fn format_expenses(data: &reports::ExpenseBreakdown) -> String {
@@ -166,7 +334,7 @@ pub fn format_expenses(data: &reports::ExpenseBreakdown) -> String {
         Cell::new(""),
         Cell::new(""),
     ]);
-    let mut out = format!("Expense Breakdown\n{table}");
+    let mut out = format!("{}\n{table}", t("report.expenses"));
 
     if !data.top_vendors.is_empty() {
         let mut vtable = Table::new();
@@ -194,12 +362,30 @@ pub fn format_tax(data: &reports::TaxSummary) -> String {
             Cell::new(money(item.total.abs())),
         ]);
     }
-    format!("Tax Summary\n{table}")
+    let mut out = format!("{}\n{table}", t("report.tax"));
+    let warnings = data.limit_warnings();
+    if !warnings.is_empty() {
+        out.push_str(&format!("\n\n{}", "Over a deduction limit".yellow()));
+        for w in &warnings {
+            out.push_str(&format!("\n  {w}"));
+        }
+        out.push_str(&format!(
+            "\n{}",
+            crate::expense_limits::ADJUST_NOTE.dimmed()
+        ));
+    }
+    out
 }
 
 pub fn format_cashflow(data: &reports::CashflowReport) -> String {
     let mut table = Table::new();
-    table.set_header(vec!["Month", "Inflows", "Outflows", "Net", "Running"]);
+    table.set_header(vec![
+        data.granularity.heading(),
+        "Inflows",
+        "Outflows",
+        "Net",
+        "Running",
+    ]);
     for m in &data.months {
         let net_str = if m.net >= 0.0 {
             money(m.net).green().to_string()
@@ -207,14 +393,14 @@ pub fn format_cashflow(data: &reports::CashflowReport) -> String {
             money(m.net).red().to_string()
         };
         table.add_row(vec![
-            Cell::new(&m.month),
+            Cell::new(&m.label),
             Cell::new(money(m.inflows)),
             Cell::new(money(m.outflows.abs())),
             Cell::new(net_str),
             Cell::new(money(m.running_balance)),
         ]);
     }
-    format!("Cash Flow\n{table}")
+    format!("{}\n{table}", t("report.cashflow"))
 }
 
 pub fn format_register(data: &reports::RegisterReport) -> String {
@@ -222,16 +408,14 @@ pub fn format_register(data: &reports::RegisterReport) -> String {
         return "No transactions found.".to_string();
     }
 
+    let show_native = data.rows.iter().any(|r| r.foreign.is_some());
     let mut table = Table::new();
-    table.set_header(vec![
-        "ID",
-        "Date",
-        "Description",
-        "Amount",
-        "Category",
-        "Vendor",
-        "Account",
-    ]);
+    let mut header = vec!["ID", "Date", "Description", "Amount"];
+    if show_native {
+        header.push("Native");
+    }
+    header.extend(["Category", "Vendor", "Account"]);
+    table.set_header(header);
     for r in &data.rows {
         let amt = if r.amount < 0.0 {
             money(r.amount.abs()).red().to_string()
@@ -240,15 +424,33 @@ pub fn format_register(data: &reports::RegisterReport) -> String {
         };
         let cat = r.category.as_deref().unwrap_or("\u{2014}");
         let vendor = r.vendor.as_deref().unwrap_or("");
-        table.add_row(vec![
+        let mut description = r.description.clone();
+        if !r.tags.is_empty() {
+            description.push_str(&format!("\n  {}", crate::tags::label(&r.tags).dimmed()));
+        }
+        if let Some(memo) = &r.memo {
+            description.push_str(&format!("\n  {}", memo.dimmed()));
+        }
+        let mut cells = vec![
             Cell::new(r.id),
-            Cell::new(&r.date),
-            Cell::new(&r.description),
+            Cell::new(fmt::date(&r.date)),
+            Cell::new(description),
             Cell::new(amt),
+        ];
+        if show_native {
+            let native = r
+                .foreign
+                .as_ref()
+                .map(|f| money_in(f.amount, &f.currency))
+                .unwrap_or_default();
+            cells.push(Cell::new(native));
+        }
+        cells.extend([
             Cell::new(cat),
             Cell::new(vendor),
             Cell::new(&r.account_name),
         ]);
+        table.add_row(cells);
     }
     format!(
         "Transaction Register ({} transactions, net: {})\n{table}",
@@ -257,50 +459,205 @@ pub fn format_register(data: &reports::RegisterReport) -> String {
     )
 }
 
+pub fn format_balance_history(data: &reports::BalanceHistory) -> String {
+    let title = format!(
+        "Balance History: {} ({} to {}, by {})",
+        data.account,
+        fmt::date(&data.from),
+        fmt::date(&data.to),
+        data.interval.key()
+    );
+    let mut table = Table::new();
+    table.set_header(vec![data.interval.heading(), "Change", "Balance"]);
+    for p in data.table_points() {
+        let change = if p.change < 0.0 {
+            data.money(p.change).red().to_string()
+        } else {
+            data.money(p.change).green().to_string()
+        };
+        table.add_row(vec![
+            Cell::new(fmt::date(&p.date)),
+            Cell::new(change),
+            Cell::new(data.money(p.balance)),
+        ]);
+    }
+    let mut out = format!(
+        "{title}\nStarting balance: {}\n{table}\nEnding balance: {}",
+        data.money(data.starting_balance),
+        data.money(data.ending_balance())
+    );
+    if let (Some(low), Some(high)) = (data.low(), data.high()) {
+        out.push_str(&format!(
+            "\nLow: {} on {}  High: {} on {}",
+            data.money(low.balance),
+            fmt::date(&low.date),
+            data.money(high.balance),
+            fmt::date(&high.date)
+        ));
+    }
+    if data.with_opening {
+        out.push_str(&format!(
+            "\n{}",
+            "Includes the account's opening balance.".dimmed()
+        ));
+    }
+    out
+}
+
 pub fn format_flagged(rows: &[reports::FlaggedTransaction]) -> String {
     if rows.is_empty() {
         return "No flagged transactions.".to_string();
     }
 
+    let (unusual, review): (Vec<_>, Vec<_>) = rows.iter().partition(|r| r.is_unusual());
+    let table = |rows: &[&reports::FlaggedTransaction]| {
+        let mut table = Table::new();
+        table.set_header(vec![
+            "ID",
+            "Date",
+            "Description",
+            "Amount",
+            "Account",
+            "Reason",
+        ]);
+        for r in rows {
+            let amt = if r.amount < 0.0 {
+                money(r.amount.abs()).red().to_string()
+            } else {
+                money(r.amount.abs()).green().to_string()
+            };
+            table.add_row(vec![
+                Cell::new(r.id),
+                Cell::new(fmt::date(&r.date)),
+                Cell::new(&r.description),
+                Cell::new(amt),
+                Cell::new(&r.account_name),
+                Cell::new(r.flag_reason.as_deref().unwrap_or("")),
+            ]);
+        }
+        table
+    };
+
+    let mut out = format!("Flagged Transactions ({})", rows.len());
+    if !review.is_empty() {
+        out.push_str(&format!("\n{}", table(&review)));
+    }
+    if !unusual.is_empty() {
+        out.push_str(&format!(
+            "\n\nUnusual Transactions ({})\n{}",
+            unusual.len(),
+            table(&unusual)
+        ));
+    }
+    out
+}
+
+pub fn format_reimbursables(report: &crate::reimbursables::ReimbursablesReport) -> String {
+    if report.items.is_empty() {
+        return "No outstanding reimbursables.".to_string();
+    }
+
+    let mut out = String::new();
+    if !report.clients.is_empty() {
+        let mut table = Table::new();
+        table.set_header(vec![
+            "Client",
+            "Count",
+            "Not invoiced",
+            "Invoiced",
+            "Outstanding",
+            "Oldest",
+        ]);
+        for c in &report.clients {
+            table.add_row(vec![
+                Cell::new(format!("#{}", c.client)),
+                Cell::new(c.count),
+                Cell::new(money(c.not_invoiced)),
+                Cell::new(money(c.invoiced)),
+                Cell::new(money(c.total())),
+                Cell::new(&c.oldest),
+            ]);
+        }
+        out.push_str(&format!("Outstanding Reimbursables\n{table}\n\n"));
+    }
+
     let mut table = Table::new();
-    table.set_header(vec!["ID", "Date", "Description", "Amount", "Account"]);
-    for r in rows {
-        let amt = if r.amount < 0.0 {
-            money(r.amount.abs()).red().to_string()
-        } else {
-            money(r.amount.abs()).green().to_string()
-        };
+    table.set_header(vec![
+        "ID",
+        "Date",
+        "Description",
+        "Amount",
+        "Client",
+        "Status",
+    ]);
+    for r in &report.items {
         table.add_row(vec![
-            Cell::new(r.id),
-            Cell::new(&r.date),
+            Cell::new(r.transaction_id),
+            Cell::new(fmt::date(&r.date)),
             Cell::new(&r.description),
-            Cell::new(amt),
-            Cell::new(&r.account_name),
+            Cell::new(money(r.amount)),
+            Cell::new(format!("#{}", r.client)),
+            Cell::new(r.status()),
         ]);
     }
-    format!("Flagged Transactions ({})\n{table}", rows.len())
+    out.push_str(&format!(
+        "Reimbursable Expenses ({})\n{table}",
+        report.items.len()
+    ));
+    out
 }
 
 pub fn format_balance(data: &reports::BalanceReport) -> String {
+    let show_native = data
+        .accounts
+        .iter()
+        .any(|a| a.currency != data.home_currency);
     let mut table = Table::new();
-    table.set_header(vec!["Account", "Type", "Balance"]);
+    let mut header = vec!["Account", "Type", "Balance"];
+    if show_native {
+        header.push("Native");
+    }
+    table.set_header(header);
     for a in &data.accounts {
         let bal = if a.balance >= 0.0 {
             money(a.balance).green().to_string()
         } else {
             money(a.balance).red().to_string()
         };
-        table.add_row(vec![
-            Cell::new(&a.name),
+        let mut cells = vec![
+            Cell::new(a.label()),
             Cell::new(&a.account_type),
             Cell::new(bal),
-        ]);
+        ];
+        if show_native {
+            if a.currency != data.home_currency {
+                cells.push(Cell::new(money_in(a.native_balance, &a.currency)));
+            } else {
+                cells.push(Cell::new(""));
+            }
+        }
+        table.add_row(cells);
     }
-    table.add_row(vec![
+    if let Some(label) = data.archived_line() {
+        let mut cells = vec![
+            Cell::new(label),
+            Cell::new(""),
+            Cell::new(money(data.archived_balance)),
+        ];
+        if show_native {
+            cells.push(Cell::new(""));
+        }
+        table.add_row(cells);
+    }
+    let mut total_row = vec![
         Cell::new("Total".bold()),
         Cell::new(""),
         Cell::new(money(data.total)),
-    ]);
+    ];
+    if show_native {
+        total_row.push(Cell::new(""));
+    }
+    table.add_row(total_row);
     format!(
         "Cash Position\n{table}\n\nYTD Net Income: {}",
         money(data.ytd_net_income)
@@ -390,7 +747,34 @@ pub fn format_k1(data: &reports::K1PrepReport) -> String {
         out.push_str(&format!("\n\nLine 19 \u{2014} Other Deductions\n{od}"));
     }
 
-    // 5. Validation warnings
+    // 5. Shareholder allocations
+    for holder in &data.shareholders {
+        let mut k1 = Table::new();
+        k1.set_header(vec!["Line", "Item", "Amount"]);
+        k1.add_row(vec![
+            Cell::new("1"),
+            Cell::new("Ordinary business income (loss)"),
+            Cell::new(money(holder.ordinary_business_income)),
+        ]);
+        for item in &holder.schedule_k_items {
+            k1.add_row(vec![
+                Cell::new(item.form_line.trim_start_matches("K-")),
+                Cell::new(&item.category_name),
+                Cell::new(money(item.total)),
+            ]);
+        }
+        k1.add_row(vec![
+            Cell::new("16d"),
+            Cell::new("Distributions"),
+            Cell::new(money(holder.distributions)),
+        ]);
+        out.push_str(&format!(
+            "\n\nSchedule K-1 \u{2014} {} ({}%)\n{k1}",
+            holder.name, holder.ownership_pct
+        ));
+    }
+
+    // 6. Validation warnings
     if data.validation.uncategorized_count > 0 {
         out.push_str(&format!(
             "\n{}",
@@ -401,26 +785,311 @@ pub fn format_k1(data: &reports::K1PrepReport) -> String {
             .yellow()
         ));
     }
-    if let Some(ratio) = data.validation.comp_dist_ratio {
-        if ratio < 1.0 {
-            out.push_str(&format!(
-                "\n{}",
-                format!(
-                    "Warning: Officer compensation ({}) is less than distributions ({}) \u{2014} review reasonable comp",
-                    money(data.validation.officer_comp),
-                    money(data.validation.distributions)
-                )
+    if let Some(pct) = data
+        .validation
+        .ownership_pct_total
+        .filter(|_| data.validation.ownership_incomplete())
+    {
+        out.push_str(&format!(
+            "\n{}",
+            format!(
+                "Warning: Shareholders own {pct}% in total \u{2014} run `nigel shareholders set` so stakes add up to 100%"
+            )
+            .yellow()
+        ));
+    }
+    if data.validation.below_comp_target {
+        out.push_str(&format!(
+            "\n{}",
+            format!(
+                "Warning: Officer compensation ({}) is under {}% of owner pay (distributions {}) \u{2014} review reasonable comp",
+                money(data.validation.officer_comp),
+                data.validation.comp_target_pct,
+                money(data.validation.distributions)
+            )
+            .yellow()
+        ));
+    }
+
+    out
+}
+
+pub fn format_deductions(data: &reports::DeductionsReport) -> String {
+    let mut out = String::from("Deductions (Standard Mileage & Home Office)");
+
+    // 1. Mileage log
+    if data.trips.is_empty() {
+        out.push_str("\n\nNo mileage logged \u{2014} add trips with `nigel mileage add`");
+    } else {
+        let mut trips = Table::new();
+        trips.set_header(vec!["Date", "Purpose", "Miles", "Rate", "Deduction"]);
+        for t in &data.trips {
+            trips.add_row(vec![
+                Cell::new(fmt::date(&t.date)),
+                Cell::new(&t.purpose),
+                Cell::new(format!("{:.1}", t.miles)),
+                Cell::new(format!("${:.3}", t.rate)),
+                Cell::new(money(t.deduction)),
+            ]);
+        }
+        trips.add_row(vec![
+            Cell::new("Total".bold()),
+            Cell::new(""),
+            Cell::new(format!("{:.1}", data.total_miles)),
+            Cell::new(""),
+            Cell::new(money(data.mileage_deduction)),
+        ]);
+        out.push_str(&format!("\n\nStandard Mileage\n{trips}"));
+    }
+
+    // 2. Home office allocation
+    let mut home = Table::new();
+    home.set_header(vec!["Item", "Amount"]);
+    home.add_row(vec![
+        Cell::new("Annual Home Costs"),
+        Cell::new(money(data.home_office.annual_costs)),
+    ]);
+    home.add_row(vec![
+        Cell::new("Business Use"),
+        Cell::new(format!("{}%", data.home_office.percent)),
+    ]);
+    home.add_row(vec![
+        Cell::new("Home Office Allocation".bold()),
+        Cell::new(money(data.home_office_deduction)),
+    ]);
+    out.push_str(&format!("\n\nHome Office\n{home}"));
+
+    out.push_str(&format!(
+        "\n\nTotal Deductions: {}",
+        money(data.total).green().bold()
+    ));
+    out
+}
+
+pub fn format_compensation(data: &reports::CompensationReport) -> String {
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Quarter",
+        "Officer comp",
+        "Distributions",
+        "YTD comp",
+        "YTD distributions",
+    ]);
+    for q in &data.quarters {
+        table.add_row(vec![
+            Cell::new(&q.label),
+            Cell::new(money(q.officer_comp)),
+            Cell::new(money(q.distributions)),
+            Cell::new(money(q.cumulative_comp)),
+            Cell::new(money(q.cumulative_distributions)),
+        ]);
+    }
+    table.add_row(vec![
+        Cell::new("Total".bold()),
+        Cell::new(money(data.officer_comp)),
+        Cell::new(money(data.distributions)),
+        Cell::new(""),
+        Cell::new(""),
+    ]);
+
+    let share = data
+        .officer_share
+        .map(|s| format!("{s:.0}%"))
+        .unwrap_or_else(|| "\u{2014}".into());
+    let mut out = format!(
+        "Officer Compensation vs. Distributions\n{table}\nOfficer share of owner pay: {share} (target {}%)",
+        data.target_pct
+    );
+    if data.below_target() {
+        out.push_str(&format!(
+            "\n{}",
+            "Warning: Distributions are outpacing officer pay \u{2014} review reasonable comp"
                 .yellow()
-            ));
+        ));
+    }
+    out
+}
+
+pub fn format_tags(items: &[reports::TagItem]) -> String {
+    if items.is_empty() {
+        return "No tagged transactions.".to_string();
+    }
+    let mut table = Table::new();
+    table.set_header(vec!["Tag", "Count", "Income", "Expenses", "Net"]);
+    for item in items {
+        let net_str = if item.net >= 0.0 {
+            money(item.net).green().to_string()
+        } else {
+            money(item.net).red().to_string()
+        };
+        table.add_row(vec![
+            Cell::new(format!("#{}", item.tag)),
+            Cell::new(item.count),
+            Cell::new(money(item.income)),
+            Cell::new(money(item.expenses.abs())),
+            Cell::new(net_str),
+        ]);
+    }
+    format!(
+        "Tags\n{table}\n{}",
+        "Transactions with several tags count toward each.".dimmed()
+    )
+}
+
+pub fn format_clients(data: &reports::ClientsReport) -> String {
+    if data.clients.is_empty() {
+        return "No income or expenses assigned to clients.".to_string();
+    }
+    let mut table = Table::new();
+    table.set_header(vec![
+        "Client",
+        "Revenue",
+        "Direct costs",
+        "Margin",
+        "Margin %",
+    ]);
+    let total = data.total();
+    for (i, c) in data.clients.iter().chain([&total]).enumerate() {
+        let margin = if c.margin() >= 0.0 {
+            money(c.margin()).green().to_string()
+        } else {
+            money(c.margin()).red().to_string()
+        };
+        let pct = c
+            .margin_pct()
+            .map(|p| format!("{p:.0}%"))
+            .unwrap_or_else(|| "\u{2014}".into());
+        let name = if i == data.clients.len() {
+            c.name.bold().to_string()
+        } else {
+            c.name.clone()
+        };
+        table.add_row(vec![
+            Cell::new(name),
+            Cell::new(money(c.revenue)),
+            Cell::new(money(c.direct_costs)),
+            Cell::new(margin),
+            Cell::new(pct),
+        ]);
+    }
+    let mut out = format!("Client Profitability\n{table}");
+    if data.unassigned_revenue.abs() >= 0.005 {
+        out.push_str(&format!(
+            "\n{}",
+            format!(
+                "Income not assigned to a client: {}",
+                money(data.unassigned_revenue)
+            )
+            .dimmed()
+        ));
+    }
+    out
+}
+
+pub fn format_books(data: &Overview) -> String {
+    let mut table = Table::new();
+    table.set_header(vec!["Book", "Cash", "YTD net", "Year"]);
+    for book in &data.books {
+        let mut label = book.label();
+        if book.current {
+            label.push_str(" (current)");
         }
+        let cells = match &book.status {
+            BookStatus::Open {
+                home_currency,
+                cash,
+                ytd_net,
+                fiscal_label,
+                ..
+            } => {
+                let net = data.money(*ytd_net, home_currency);
+                let net = if *ytd_net >= 0.0 {
+                    net.green().to_string()
+                } else {
+                    net.red().to_string()
+                };
+                vec![
+                    Cell::new(label),
+                    Cell::new(data.money(*cash, home_currency)),
+                    Cell::new(net),
+                    Cell::new(fiscal_label),
+                ]
+            }
+            status => vec![
+                Cell::new(label),
+                Cell::new(status.note().unwrap_or_default().dimmed()),
+                Cell::new(""),
+                Cell::new(""),
+            ],
+        };
+        table.add_row(cells);
+    }
+    let combined = data.combined();
+    if let Some((currency, cash, net)) = &combined {
+        table.add_row(vec![
+            Cell::new("Combined".bold()),
+            Cell::new(data.money(*cash, currency)),
+            Cell::new(data.money(*net, currency)),
+            Cell::new(""),
+        ]);
     }
 
+    let mut out = format!("Books Overview\n{table}");
+    if data.books.len() == 1 {
+        out.push_str(&format!(
+            "\n{}",
+            "List other books' data directories under `overview_books` in settings.json to see them here."
+                .dimmed()
+        ));
+    } else if data.open_count() > 1 && combined.is_none() {
+        out.push_str(&format!(
+            "\n{}",
+            "The books use different home currencies, so they aren't combined.".dimmed()
+        ));
+    }
+    out
+}
+
+pub fn format_quality(data: &QualityReport) -> String {
+    let mut out = format!("Data Quality ({} out of range)", data.total());
+    if data.total() == 0 {
+        out.push_str(&format!("\n{}", data.clean_note()));
+        return out;
+    }
+    let sections = [
+        (data.future_heading(), &data.future),
+        (data.before_epoch_heading(), &data.before_epoch),
+    ];
+    for (heading, rows) in sections {
+        if rows.is_empty() {
+            continue;
+        }
+        let mut table = Table::new();
+        table.set_header(vec!["ID", "Date", "Description", "Amount", "Account"]);
+        for r in rows {
+            table.add_row(vec![
+                Cell::new(r.id),
+                Cell::new(fmt::date(&r.date)),
+                Cell::new(&r.description),
+                Cell::new(money(r.amount)),
+                Cell::new(&r.account_name),
+            ]);
+        }
+        out.push_str(&format!("\n\n{heading} ({})\n{table}", rows.len()));
+    }
+    out.push_str(&format!("\n{}", crate::data_quality::FIX_NOTE.dimmed()));
     out
 }
 
 #[cfg(test)]
 mod tests {
-    use super::with_header;
+    use super::{
+        format_books, format_clients, format_compensation, format_flagged, format_pnl_comparison,
+        format_register, format_reimbursables, format_tags, with_header,
+    };
+    use crate::overview::{BookLine, BookStatus, Overview};
+    use crate::reports;
+    use crate::reports::{PnlCompareItem, PnlComparison};
 
     #[test]
     fn with_header_prepends_when_set() {
@@ -434,4 +1103,212 @@ mod tests {
     fn with_header_passthrough_when_empty() {
         assert_eq!(with_header("", "Report".into()), "Report");
     }
+
+    #[test]
+    fn format_pnl_comparison_shows_spend_change() {
+        let data = PnlComparison {
+            periods: vec!["FY 2025".into(), "FY 2024".into()],
+            income: vec![],
+            expenses: vec![PnlCompareItem {
+                name: "Software".into(),
+                totals: vec![-120.0, -100.0],
+            }],
+            total_income: vec![0.0, 0.0],
+            total_expenses: vec![-120.0, -100.0],
+            net: vec![-120.0, -100.0],
+        };
+        let out = format_pnl_comparison(&data);
+        assert!(out.contains("FY 2024"));
+        // Expense changes are measured on the spend: up $20, +20%
+        let line = out.lines().find(|l| l.contains("Software")).unwrap();
+        assert!(line.contains("$20.00") && line.contains("+20.0%"), "{line}");
+    }
+
+    #[test]
+    fn format_books_combines_open_books() {
+        let open = |name: &str, cash: f64, current: bool| BookLine {
+            dir: std::path::PathBuf::from(format!("/books/{name}")),
+            current,
+            status: BookStatus::Open {
+                company_name: Some(name.into()),
+                home_currency: "USD".into(),
+                cash,
+                ytd_net: cash / 2.0,
+                fiscal_label: "FY 2025".into(),
+            },
+        };
+        let data = Overview {
+            books: vec![
+                open("Acme LLC", 1000.0, true),
+                open("Household", 500.0, false),
+                BookLine {
+                    dir: "/books/old".into(),
+                    current: false,
+                    status: BookStatus::Missing,
+                },
+            ],
+        };
+        let out = format_books(&data);
+        assert!(out.contains("Acme LLC (current)"));
+        let combined = out.lines().find(|l| l.contains("Combined")).unwrap();
+        assert!(
+            combined.contains("$1,500.00") && combined.contains("$750.00"),
+            "{combined}"
+        );
+        let old = out.lines().find(|l| l.starts_with("| old ")).unwrap();
+        assert!(old.contains("No book found here"), "{old}");
+
+        // A lone book points at the setting for adding others
+        let lone = Overview {
+            books: vec![open("Acme LLC", 1000.0, true)],
+        };
+        let out = format_books(&lone);
+        assert!(out.contains("overview_books") && !out.contains("Combined"));
+    }
+
+    #[test]
+    fn format_clients_totals_and_notes_unassigned_income() {
+        let data = reports::ClientsReport {
+            clients: vec![
+                reports::ClientProfit {
+                    name: "Acme".into(),
+                    revenue: 5000.0,
+                    direct_costs: 1000.0,
+                },
+                reports::ClientProfit {
+                    name: "Globex".into(),
+                    revenue: 0.0,
+                    direct_costs: 250.0,
+                },
+            ],
+            unassigned_revenue: 700.0,
+        };
+        let out = format_clients(&data);
+        let acme = out.lines().find(|l| l.contains("Acme")).unwrap();
+        assert!(acme.contains("$4,000.00") && acme.contains("80%"), "{acme}");
+        let total = out.lines().find(|l| l.contains("Total")).unwrap();
+        assert!(
+            total.contains("$3,750.00") && total.contains("75%"),
+            "{total}"
+        );
+        assert!(out.contains("Income not assigned to a client: $700.00"));
+        assert_eq!(
+            format_clients(&reports::ClientsReport::default()),
+            "No income or expenses assigned to clients."
+        );
+    }
+
+    #[test]
+    fn format_compensation_warns_below_target() {
+        let data = reports::CompensationReport {
+            quarters: vec![reports::CompQuarter {
+                label: "Q1 (Jan\u{2013}Mar)".into(),
+                officer_comp: 10_000.0,
+                distributions: 30_000.0,
+                cumulative_comp: 10_000.0,
+                cumulative_distributions: 30_000.0,
+            }],
+            officer_comp: 10_000.0,
+            distributions: 30_000.0,
+            officer_share: Some(25.0),
+            target_pct: 50.0,
+        };
+        let out = format_compensation(&data);
+        assert!(out.contains("Q1"));
+        assert!(out.contains("25% (target 50%)"));
+        assert!(out.contains("outpacing officer pay"));
+    }
+
+    #[test]
+    fn format_register_shows_tags_and_memo_under_description() {
+        let data = reports::RegisterReport {
+            rows: vec![reports::RegisterRow {
+                id: 1,
+                date: "2025-02-01".into(),
+                description: "DELTA AIR".into(),
+                amount: -412.0,
+                foreign: None,
+                category: None,
+                category_id: None,
+                vendor: None,
+                account_name: "Checking".into(),
+                is_flagged: false,
+                memo: Some("Client kickoff".into()),
+                tags: vec!["conference2025".into()],
+                client: None,
+            }],
+            total: -412.0,
+        };
+        let out = format_register(&data);
+        assert!(out.contains("DELTA AIR"));
+        assert!(out.contains("Client kickoff"));
+        assert!(out.contains("#conference2025"));
+    }
+
+    #[test]
+    fn format_tags_lists_each_tag() {
+        let items = vec![reports::TagItem {
+            tag: "client-acme".into(),
+            count: 2,
+            income: 1000.0,
+            expenses: -50.0,
+            net: 950.0,
+        }];
+        let out = format_tags(&items);
+        assert!(out.contains("#client-acme"));
+        assert!(out.contains("$950.00"));
+        assert_eq!(format_tags(&[]), "No tagged transactions.");
+    }
+
+    #[test]
+    fn format_reimbursables_shows_clients_and_status() {
+        use crate::reimbursables::{ClientOutstanding, Reimbursable, ReimbursablesReport};
+        let report = ReimbursablesReport {
+            clients: vec![ClientOutstanding {
+                client: "acme".into(),
+                count: 1,
+                not_invoiced: 0.0,
+                invoiced: 42.5,
+                oldest: "2025-03-02".into(),
+            }],
+            items: vec![Reimbursable {
+                transaction_id: 7,
+                date: "2025-03-02".into(),
+                description: "Stock photos".into(),
+                amount: 42.5,
+                client: "acme".into(),
+                invoice: Some("INV-9".into()),
+                invoiced_on: Some("2025-03-31".into()),
+                reimbursed_on: None,
+            }],
+        };
+        let out = format_reimbursables(&report);
+        assert!(out.contains("#acme"));
+        assert!(out.contains("$42.50"));
+        assert!(out.contains("invoiced 2025-03-31 (INV-9)"));
+        assert_eq!(
+            format_reimbursables(&ReimbursablesReport::default()),
+            "No outstanding reimbursables."
+        );
+    }
+
+    #[test]
+    fn format_flagged_lists_unusual_transactions_separately() {
+        let row = |id: i64, reason: &str| reports::FlaggedTransaction {
+            id,
+            date: "2025-03-10".into(),
+            description: format!("TXN {id}"),
+            amount: -16.0,
+            account_name: "Card".into(),
+            flag_reason: Some(reason.into()),
+        };
+        let out = format_flagged(&[
+            row(1, "No matching rule"),
+            row(2, "Unusual: same amount and vendor as #1 that day"),
+        ]);
+        assert!(out.starts_with("Flagged Transactions (2)"));
+        let (review, unusual) = out.split_once("Unusual Transactions (1)").unwrap();
+        assert!(review.contains("TXN 1") && !review.contains("TXN 2"));
+        assert!(unusual.contains("TXN 2"));
+    }
 }
@@ -1,22 +1,31 @@
+use std::collections::{HashMap, HashSet};
+
 use ratatui::{
     layout::{Constraint, Layout},
     style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::Line,
     text::Span,
-    widgets::{Cell, Paragraph, Row, Table},
+    widgets::{Axis, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table},
     Frame,
 };
 
 use crossterm::event::KeyCode;
 
-use crate::cli::{parse_month_opt, ReportCommands};
+use crate::cli::report::ReportParams;
+use crate::cli::ReportCommands;
+use crate::data_quality;
 use crate::db::get_connection;
 use crate::error::Result;
-use crate::fmt::money;
+use crate::fiscal::FiscalYear;
+use crate::fmt::{self, money, money_compact, money_in, pct_change};
+use crate::locales::t;
+use crate::overview::{self, BookStatus};
 use crate::reports;
-use crate::settings::get_data_dir;
+use crate::settings::{get_data_dir, load_settings};
 use crate::tui::{
-    money_span, run_report_view, ReportView, ReportViewAction, AMOUNT_NEG_STYLE, AMOUNT_POS_STYLE,
-    FOOTER_STYLE, HEADER_STYLE,
+    display_width, money_span, run_report_view, truncate, y_axis_ticks, ReportView,
+    ReportViewAction, AMOUNT_NEG_STYLE, AMOUNT_POS_STYLE, FOOTER_STYLE, HEADER_STYLE,
 };
 
 // ---------------------------------------------------------------------------
@@ -26,11 +35,14 @@ use crate::tui::{
 /// What date navigation granularities a report supports.
 #[derive(Clone, Copy, PartialEq)]
 pub(crate) enum DateGranularity {
-    /// Supports both month and year navigation (P&L, Expenses, Cash Flow)
+    /// Supports both month and year navigation (P&L, Expenses, Cash Flow, Tags)
     MonthAndYear,
-    /// Supports only year navigation (Tax, K-1)
+    /// Supports only year navigation (Tax, K-1, Deductions, Compensation,
+    /// Clients)
     YearOnly,
-    /// No date navigation (Flagged, Balance)
+    /// Supports only month navigation (Monthly Summary)
+    MonthOnly,
+    /// No date navigation (Flagged, Reimbursables, Balance, Books, Quality)
     None,
 }
 
@@ -61,37 +73,100 @@ const MONTH_NAMES: &[&str] = &[
 // ---------------------------------------------------------------------------
 
 /// Dispatch a report command to an interactive ratatui view.
-pub fn dispatch(cmd: ReportCommands) -> Result<()> {
+pub fn dispatch(cmd: ReportCommands, params: ReportParams) -> Result<()> {
     // Register delegates to the interactive RegisterBrowser (standalone)
     if matches!(cmd, ReportCommands::Register { .. }) {
-        return register_standalone(cmd);
+        return register_standalone(&params);
     }
     if matches!(cmd, ReportCommands::All { .. }) {
         return Err(crate::error::NigelError::Other(
             "`report all` is export-only — use `--mode export`".into(),
         ));
     }
-    let mut view = build_view(&cmd)?;
-    run_report_view(view.as_mut())
+    let mut view = build_view(&cmd, &params)?;
+    let initial_period = view.date_params();
+    run_report_view(
+        &mut view,
+        &|current| {
+            rebuild_view(
+                &cmd,
+                &params,
+                current,
+                current.date_params() != initial_period,
+            )
+        },
+        &browse_drill,
+    )
+}
+
+/// Run the register over a report line's transactions until it is closed.
+/// True when anything was edited there, so the report is rebuilt.
+fn browse_drill(
+    terminal: &mut ratatui::DefaultTerminal,
+    query: reports::RegisterQuery,
+    desc: String,
+) -> Result<bool> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let categories = crate::reviewer::get_categories(&conn).unwrap_or_default();
+    let mut browser = crate::browser::RegisterBrowser::open(&conn, query, desc, categories)?;
+    let changes =
+        || -> Result<i64> { Ok(conn.query_row("SELECT total_changes()", [], |r| r.get(0))?) };
+    let before = changes()?;
+    browser.event_loop(terminal, &conn)?;
+    Ok(changes()? != before)
+}
+
+/// Rebuild a standalone view for its current period and comparison toggle.
+/// Once the period has been navigated away from the command line's, any
+/// `--from`/`--to` range no longer applies.
+fn rebuild_view(
+    cmd: &ReportCommands,
+    params: &ReportParams,
+    view: &dyn ReportView,
+    period_changed: bool,
+) -> Result<Box<dyn ReportView>> {
+    let (year, month) = view.date_params();
+    let mut params = params.with_period(year, month.as_deref());
+    if period_changed {
+        params.from_date = None;
+        params.to_date = None;
+    }
+    params.compare = view.compare();
+    params.granularity = view.granularity();
+    params.interval = view.interval();
+    match cmd {
+        ReportCommands::Cashflow { .. } => build_cashflow(&params, view.chart()),
+        ReportCommands::BalanceHistory { .. } => build_balance_history(&params, view.chart()),
+        _ => build_view(cmd, &params),
+    }
 }
 
 /// Build a report view from a command. Used by both CLI dispatch and dashboard.
 /// Does NOT handle Register (which uses RegisterBrowser) or All (export-only).
-pub(crate) fn build_view(cmd: &ReportCommands) -> Result<Box<dyn ReportView>> {
+pub(crate) fn build_view(
+    cmd: &ReportCommands,
+    params: &ReportParams,
+) -> Result<Box<dyn ReportView>> {
     match cmd {
-        ReportCommands::Pnl {
-            month,
-            year,
-            from_date,
-            to_date,
-            ..
-        } => build_pnl(month.clone(), *year, from_date.clone(), to_date.clone()),
-        ReportCommands::Expenses { month, year, .. } => build_expenses(month.clone(), *year),
-        ReportCommands::Tax { year, .. } => build_tax(*year),
-        ReportCommands::Cashflow { month, year, .. } => build_cashflow(month.clone(), *year),
+        ReportCommands::Pnl { .. } => build_pnl(params),
+        ReportCommands::Expenses { .. } => build_expenses(params),
+        ReportCommands::Tax { .. } => build_tax(params.year),
+        ReportCommands::Cashflow { .. } => build_cashflow(params, false),
         ReportCommands::Flagged { .. } => build_flagged(),
-        ReportCommands::Balance { .. } => build_balance(),
-        ReportCommands::K1 { year, .. } => build_k1(*year),
+        ReportCommands::Reimbursables { client, all, .. } => {
+            build_reimbursables(client.as_deref(), *all)
+        }
+        ReportCommands::Balance {
+            include_archived, ..
+        } => build_balance(*include_archived),
+        ReportCommands::BalanceHistory { .. } => build_balance_history(params, true),
+        ReportCommands::K1 { .. } => build_k1(params.year),
+        ReportCommands::Deductions { .. } => build_deductions(params.year),
+        ReportCommands::Compensation { .. } => build_compensation(params.year),
+        ReportCommands::Tags { .. } => build_tags(params),
+        ReportCommands::Clients { .. } => build_clients(params.year),
+        ReportCommands::Books { .. } => build_books(),
+        ReportCommands::Quality { .. } => build_quality(),
         _ => Err(crate::error::NigelError::Other(
             "Unsupported report for view mode".into(),
         )),
@@ -115,11 +190,26 @@ pub(crate) struct TableReportView {
     widths: Vec<Constraint>,
     offset: usize,
     visible_count: usize,
+    /// Extra key hint shown before the scroll hint (e.g. drill-down keys).
+    key_hint: &'static str,
     // Date navigation state
     granularity: DateGranularity,
     period_mode: PeriodMode,
+    fiscal: FiscalYear,
+    /// Fiscal year in Year mode, calendar year of `month` in Month mode.
     year: i32,
     month: u32, // 1-12
+    /// Period comparison toggle (`c`); `None` when the report has no
+    /// comparison layout.
+    compare: Option<bool>,
+    /// A `--from`/`--to` range shown in place of the period, until the
+    /// period is navigated.
+    range: Option<String>,
+    /// Category lines that open their transactions with Enter, by row
+    /// index. When there are any, Up/Down move `cursor` between them.
+    drills: Vec<(usize, Drill)>,
+    /// Index into `drills`.
+    cursor: usize,
 }
 
 impl TableReportView {
@@ -137,15 +227,66 @@ impl TableReportView {
             widths,
             offset: 0,
             visible_count: 20,
+            key_hint: "",
             granularity: DateGranularity::None,
             period_mode: PeriodMode::Year,
+            fiscal: FiscalYear::CALENDAR,
             year: chrono::Datelike::year(&now),
             month: chrono::Datelike::month(&now),
+            compare: None,
+            range: None,
+            drills: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    fn with_drills(mut self, drills: Vec<(usize, Drill)>) -> Self {
+        if !drills.is_empty() {
+            self.key_hint = "Enter=transactions  ";
+        }
+        self.drills = drills;
+        self.cursor = 0;
+        self
+    }
+
+    /// Move the cursor to the next or previous drillable line, scrolling to
+    /// keep it in view. Past the first or last one, scroll instead.
+    fn move_drill(&mut self, delta: isize) {
+        let last = self.drills.len() - 1;
+        let cursor = self.cursor.saturating_add_signed(delta).min(last);
+        let max = self.rows.len().saturating_sub(self.visible_count);
+        if cursor == self.cursor {
+            self.offset = self.offset.saturating_add_signed(delta).min(max);
+            return;
         }
+        self.cursor = cursor;
+        let row = self.drills[cursor].0;
+        if row < self.offset {
+            self.offset = row;
+        } else if row >= self.offset + self.visible_count {
+            self.offset = (row + 1 - self.visible_count).min(max);
+        }
+    }
+
+    fn with_range(mut self, range: Option<String>) -> Self {
+        self.range = range;
+        self
+    }
+
+    fn with_compare(mut self, on: bool) -> Self {
+        self.compare = Some(on);
+        self
     }
 
-    fn with_date(mut self, granularity: DateGranularity, year: i32, month: Option<u32>) -> Self {
+    fn with_date(
+        mut self,
+        granularity: DateGranularity,
+        fiscal: FiscalYear,
+        year: i32,
+        month: Option<u32>,
+    ) -> Self {
         self.granularity = granularity;
+        self.fiscal = fiscal;
         self.year = year;
         if let Some(m) = month {
             self.month = m;
@@ -161,6 +302,10 @@ impl TableReportView {
         match self.granularity {
             DateGranularity::None => (None, None),
             DateGranularity::YearOnly => (Some(self.year), None),
+            DateGranularity::MonthOnly => (
+                Some(self.year),
+                Some(format!("{}-{:02}", self.year, self.month)),
+            ),
             DateGranularity::MonthAndYear => match self.period_mode {
                 PeriodMode::Year => (Some(self.year), None),
                 PeriodMode::Month => (
@@ -172,52 +317,79 @@ impl TableReportView {
     }
 
     fn period_label(&self) -> String {
+        if let Some(range) = &self.range {
+            return format!(" \u{2014} {range}");
+        }
         match self.granularity {
             DateGranularity::None => String::new(),
-            DateGranularity::YearOnly => format!(" \u{2014} FY {}", self.year),
+            DateGranularity::YearOnly => format!(" \u{2014} {}", self.fiscal.label(self.year)),
+            DateGranularity::MonthOnly => self.month_label(),
             DateGranularity::MonthAndYear => match self.period_mode {
-                PeriodMode::Year => format!(" \u{2014} FY {}", self.year),
-                PeriodMode::Month => {
-                    let name = MONTH_NAMES.get((self.month - 1) as usize).unwrap_or(&"???");
-                    format!(" \u{2014} {} {}", name, self.year)
-                }
+                PeriodMode::Year => format!(" \u{2014} {}", self.fiscal.label(self.year)),
+                PeriodMode::Month => self.month_label(),
             },
         }
     }
 
+    /// Footer hint for the date navigation keys.
+    fn nav_hint(&self) -> &'static str {
+        match self.granularity {
+            DateGranularity::MonthAndYear => "\u{2190}/\u{2192}=period  m=month/year  ",
+            DateGranularity::YearOnly => "\u{2190}/\u{2192}=year  ",
+            DateGranularity::MonthOnly => "\u{2190}/\u{2192}=month  ",
+            DateGranularity::None => "",
+        }
+    }
+
+    fn month_label(&self) -> String {
+        let name = MONTH_NAMES.get((self.month - 1) as usize).unwrap_or(&"???");
+        format!(" \u{2014} {} {}", name, self.year)
+    }
+
+    fn step_month(&mut self, delta: i32) {
+        let mut m = self.month as i32 + delta;
+        let mut y = self.year;
+        while m < 1 {
+            m += 12;
+            y -= 1;
+        }
+        while m > 12 {
+            m -= 12;
+            y += 1;
+        }
+        self.month = m as u32;
+        self.year = y.max(2000);
+    }
+
     fn navigate_period(&mut self, delta: i32) {
         match self.granularity {
             DateGranularity::None => {}
             DateGranularity::YearOnly => {
                 self.year = (self.year + delta).max(2000);
             }
+            DateGranularity::MonthOnly => self.step_month(delta),
             DateGranularity::MonthAndYear => match self.period_mode {
                 PeriodMode::Year => {
                     self.year = (self.year + delta).max(2000);
                 }
-                PeriodMode::Month => {
-                    let mut m = self.month as i32 + delta;
-                    let mut y = self.year;
-                    while m < 1 {
-                        m += 12;
-                        y -= 1;
-                    }
-                    while m > 12 {
-                        m -= 12;
-                        y += 1;
-                    }
-                    self.month = m as u32;
-                    self.year = y.max(2000);
-                }
+                PeriodMode::Month => self.step_month(delta),
             },
         }
     }
 
+    /// Switch between month and year views, keeping the year pointed at
+    /// the fiscal year that contains the selected month.
     fn toggle_period_mode(&mut self) {
         if self.granularity == DateGranularity::MonthAndYear {
-            self.period_mode = match self.period_mode {
-                PeriodMode::Year => PeriodMode::Month,
-                PeriodMode::Month => PeriodMode::Year,
+            (self.period_mode, self.year) = match self.period_mode {
+                PeriodMode::Year => (
+                    PeriodMode::Month,
+                    self.fiscal.calendar_year_of(self.year, self.month),
+                ),
+                PeriodMode::Month => (
+                    PeriodMode::Year,
+                    self.fiscal.year_of_month(self.year, self.month),
+                ),
             };
         }
     }
@@ -251,12 +423,20 @@ impl ReportView for TableReportView {
         let visible = content_area.height.saturating_sub(header_overhead) as usize;
         self.visible_count = visible.max(1);
 
+        let selected = self.drills.get(self.cursor).map(|(row, _)| *row);
         let visible_rows: Vec<Row> = self
             .rows
             .iter()
+            .enumerate()
             .skip(self.offset)
             .take(visible)
-            .cloned()
+            .map(|(i, row)| {
+                if Some(i) == selected {
+                    row.clone().style(CURSOR_STYLE)
+                } else {
+                    row.clone()
+                }
+            })
             .collect();
 
         let table = Table::new(visible_rows, self.widths.clone())
@@ -272,14 +452,16 @@ impl ReportView for TableReportView {
         } else {
             String::new()
         };
-        let nav_hint = match self.granularity {
-            DateGranularity::MonthAndYear => "\u{2190}/\u{2192}=period  m=month/year  ",
-            DateGranularity::YearOnly => "\u{2190}/\u{2192}=year  ",
-            DateGranularity::None => "",
+        let nav_hint = self.nav_hint();
+        let compare_hint = match self.compare {
+            Some(true) => "c=single period  ",
+            Some(false) => "c=compare  ",
+            None => "",
         };
         frame.render_widget(
             Paragraph::new(format!(
-                " {nav_hint}\u{2191}/\u{2193}=scroll  q/Esc=close{pos_info}"
+                " {nav_hint}{compare_hint}{}\u{2191}/\u{2193}=scroll  q/Esc=close{pos_info}",
+                self.key_hint
             ))
             .style(FOOTER_STYLE),
             footer_area,
@@ -291,6 +473,18 @@ impl ReportView for TableReportView {
         let max = self.rows.len().saturating_sub(page);
         match code {
             KeyCode::Char('q') | KeyCode::Esc => ReportViewAction::Close,
+            KeyCode::Up | KeyCode::Char('k') if !self.drills.is_empty() => {
+                self.move_drill(-1);
+                ReportViewAction::Continue
+            }
+            KeyCode::Down | KeyCode::Char('j') if !self.drills.is_empty() => {
+                self.move_drill(1);
+                ReportViewAction::Continue
+            }
+            KeyCode::Enter => match self.drills.get(self.cursor) {
+                Some((_, drill)) => drill.browse(),
+                None => ReportViewAction::Continue,
+            },
             KeyCode::Up | KeyCode::Char('k') => {
                 self.offset = self.offset.saturating_sub(1);
                 ReportViewAction::Continue
@@ -330,6 +524,11 @@ impl ReportView for TableReportView {
                 self.offset = 0;
                 ReportViewAction::Reload
             }
+            KeyCode::Char('c') if self.compare.is_some() => {
+                self.compare = self.compare.map(|on| !on);
+                self.offset = 0;
+                ReportViewAction::Reload
+            }
             _ => ReportViewAction::Continue,
         }
     }
@@ -337,6 +536,10 @@ impl ReportView for TableReportView {
     fn date_params(&self) -> (Option<i32>, Option<String>) {
         self.date_params()
     }
+
+    fn compare(&self) -> bool {
+        self.compare == Some(true)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -367,24 +570,136 @@ fn blank_row(num_cols: usize) -> Row<'static> {
     Row::new(vec![Cell::from(""); num_cols])
 }
 
+// ---------------------------------------------------------------------------
+// Drill-down from category lines to the register
+// ---------------------------------------------------------------------------
+
+/// The transactions behind a report line, opened in the register.
+#[derive(Clone)]
+pub(crate) struct Drill {
+    query: reports::RegisterQuery,
+    desc: String,
+}
+
+impl Drill {
+    fn browse(&self) -> ReportViewAction {
+        ReportViewAction::Browse(self.query.clone(), self.desc.clone())
+    }
+}
+
+/// Makes `Drill`s for the category lines of one report: its period and
+/// excluded tags, and category IDs by full path.
+#[derive(Default)]
+struct DrillScope {
+    base: reports::RegisterQuery,
+    period: String,
+    ids: HashMap<String, i64>,
+    depth: Option<usize>,
+}
+
+impl DrillScope {
+    fn new(conn: &rusqlite::Connection, params: &ReportParams) -> Result<Self> {
+        let period = match (params.range_label(), params.year, params.month) {
+            (Some(range), _, _) => range,
+            (None, Some(y), Some(m)) => format!("{y}-{m:02}"),
+            (None, Some(y), None) => FiscalYear::load(conn).label(y),
+            (None, None, _) => "all dates".to_string(),
+        };
+        Ok(Self {
+            base: reports::RegisterQuery {
+                year: params.year,
+                month: params.month,
+                from_date: params.from_date.clone(),
+                to_date: params.to_date.clone(),
+                exclude_tags: params.exclude_tags.clone(),
+                ..Default::default()
+            },
+            period,
+            ids: reports::category_paths(conn)?
+                .into_iter()
+                .map(|(id, path)| (path.join(reports::CATEGORY_PATH_SEP), id))
+                .collect(),
+            depth: params.depth,
+        })
+    }
+
+    /// Row `row` showing the category at `path` (`Software > Design`). A
+    /// line rolled up to `--depth` takes in its sub-categories.
+    fn line(&self, row: usize, path: &str) -> Option<(usize, Drill)> {
+        let levels = path.split(reports::CATEGORY_PATH_SEP).count();
+        self.drill(path, self.depth == Some(levels))
+            .map(|drill| (row, drill))
+    }
+
+    /// Row `row` heading the group of lines under the category at `path`.
+    fn group(&self, row: usize, path: &str) -> Option<(usize, Drill)> {
+        self.drill(path, true).map(|drill| (row, drill))
+    }
+
+    fn drill(&self, path: &str, subcategories: bool) -> Option<Drill> {
+        Some(Drill {
+            query: reports::RegisterQuery {
+                category_id: Some(*self.ids.get(path)?),
+                subcategories,
+                ..self.base.clone()
+            },
+            desc: format!("category: {path}, {}", self.period),
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Report builders
 // ---------------------------------------------------------------------------
 
-pub(crate) fn build_pnl(
-    month: Option<String>,
-    year: Option<i32>,
-    from_date: Option<String>,
-    to_date: Option<String>,
-) -> Result<Box<dyn ReportView>> {
+/// Report title, naming any tags the report leaves out.
+fn title_with_exclusions(title: &str, exclude_tags: &[String]) -> String {
+    match reports::excluded_tags_label(exclude_tags) {
+        Some(label) => format!("{title} (excluding {label})"),
+        None => title.to_string(),
+    }
+}
+
+pub(crate) fn build_pnl(params: &ReportParams) -> Result<Box<dyn ReportView>> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    let (my, mm) = parse_month_opt(&month);
+    let fiscal = FiscalYear::load(&conn);
+    let effective_year = params.year.unwrap_or_else(|| fiscal.current());
+    let exclude_tags = &params.exclude_tags;
+    if params.compare {
+        let data = reports::get_pnl_comparison(
+            &conn,
+            params.year,
+            params.month,
+            params.from_date.as_deref(),
+            params.to_date.as_deref(),
+            params.depth,
+            exclude_tags,
+        )?;
+        let scope = DrillScope::new(&conn, params)?;
+        let mut drills = Vec::new();
+        let (header, rows, widths) = pnl_comparison_table(&data, &scope, &mut drills);
+        let title = title_with_exclusions("Profit & Loss Comparison", exclude_tags);
+        return Ok(Box::new(
+            TableReportView::new(title, header, rows, widths)
+                .with_date(
+                    DateGranularity::MonthAndYear,
+                    fiscal,
+                    effective_year,
+                    params.month,
+                )
+                .with_range(params.range_label())
+                .with_compare(true)
+                .with_drills(drills),
+        ));
+    }
     let data = reports::get_pnl(
         &conn,
-        year.or(my),
-        mm,
-        from_date.as_deref(),
-        to_date.as_deref(),
+        params.year,
+        params.month,
+        params.from_date.as_deref(),
+        params.to_date.as_deref(),
+        params.depth,
+        exclude_tags,
     )?;
 
     let widths = vec![Constraint::Fill(1), Constraint::Length(14)];
@@ -392,16 +707,13 @@ pub(crate) fn build_pnl(
         .style(HEADER_ROW_STYLE)
         .bottom_margin(1);
 
+    let scope = DrillScope::new(&conn, params)?;
     let mut rows = Vec::new();
+    let mut drills = Vec::new();
 
     if !data.income.is_empty() {
         rows.push(section_row("INCOME", 2));
-        for item in &data.income {
-            rows.push(Row::new([
-                text_cell(format!("  {}", item.name)),
-                money_cell(item.total),
-            ]));
-        }
+        pnl_group_rows(&mut rows, &mut drills, &scope, &data.income, |t| t);
         rows.push(Row::new([
             bold_cell("  Total Income"),
             money_cell(data.total_income),
@@ -411,12 +723,7 @@ pub(crate) fn build_pnl(
 
     if !data.expenses.is_empty() {
         rows.push(section_row("EXPENSES", 2));
-        for item in &data.expenses {
-            rows.push(Row::new([
-                text_cell(format!("  {}", item.name)),
-                money_cell(-item.total.abs()),
-            ]));
-        }
+        pnl_group_rows(&mut rows, &mut drills, &scope, &data.expenses, |t| -t.abs());
         rows.push(Row::new([
             bold_cell("  Total Expenses"),
             money_cell(-data.total_expenses.abs()),
@@ -426,45 +733,232 @@ pub(crate) fn build_pnl(
 
     rows.push(Row::new([bold_cell("NET"), money_cell(data.net)]));
 
-    let effective_year = year
-        .or(my)
-        .unwrap_or_else(|| chrono::Datelike::year(&chrono::Local::now()));
     Ok(Box::new(
-        TableReportView::new("Profit & Loss", header, rows, widths).with_date(
+        TableReportView::new(
+            title_with_exclusions(&t("report.pnl"), exclude_tags),
+            header,
+            rows,
+            widths,
+        )
+        .with_date(
             DateGranularity::MonthAndYear,
+            fiscal,
             effective_year,
-            mm,
-        ),
+            params.month,
+        )
+        .with_range(params.range_label())
+        .with_compare(false)
+        .with_drills(drills),
     ))
 }
 
-pub(crate) fn build_expenses(
-    month: Option<String>,
-    year: Option<i32>,
-) -> Result<Box<dyn ReportView>> {
+/// One side of the P&L: a top-level category on its own row, or a group
+/// header with its sub-categories indented below and a subtotal. Each
+/// category line, and each group header, drills down to its transactions.
+fn pnl_group_rows(
+    rows: &mut Vec<Row<'static>>,
+    drills: &mut Vec<(usize, Drill)>,
+    scope: &DrillScope,
+    items: &[reports::PnlItem],
+    amount: fn(f64) -> f64,
+) {
+    for group in reports::pnl_groups(items) {
+        if group.is_single() {
+            drills.extend(scope.line(rows.len(), group.name));
+            rows.push(Row::new([
+                text_cell(format!("  {}", group.name)),
+                money_cell(amount(group.total)),
+            ]));
+            continue;
+        }
+        drills.extend(scope.group(rows.len(), group.name));
+        rows.push(Row::new([
+            text_cell(format!("  {}", group.name)),
+            text_cell(String::new()),
+        ]));
+        for (label, total) in &group.items {
+            let path = match *label {
+                "(direct)" => group.name.to_string(),
+                _ => format!("{}{}{label}", group.name, reports::CATEGORY_PATH_SEP),
+            };
+            drills.extend(scope.line(rows.len(), &path));
+            rows.push(Row::new([
+                text_cell(format!("    {label}")),
+                money_cell(amount(*total)),
+            ]));
+        }
+        rows.push(Row::new([
+            bold_cell(format!("  Total {}", group.name)),
+            money_cell(amount(group.total)),
+        ]));
+    }
+}
+
+/// Selected period, then amount / $ change / % change per comparison period.
+/// Category lines drill down to their transactions in the selected period.
+fn pnl_comparison_table(
+    data: &reports::PnlComparison,
+    scope: &DrillScope,
+    drills: &mut Vec<(usize, Drill)>,
+) -> (Row<'static>, Vec<Row<'static>>, Vec<Constraint>) {
+    let mut widths = vec![Constraint::Fill(1), Constraint::Length(14)];
+    let mut labels = vec!["Category".to_string(), data.periods[0].clone()];
+    for label in &data.periods[1..] {
+        widths.extend([
+            Constraint::Length(14),
+            Constraint::Length(13),
+            Constraint::Length(8),
+        ]);
+        labels.extend([label.clone(), "$ Chg".to_string(), "% Chg".to_string()]);
+    }
+    let num_cols = widths.len();
+    let header = Row::new(labels).style(HEADER_ROW_STYLE).bottom_margin(1);
+
+    // Expenses are shown as negatives like the single-period view, but their
+    // change is measured on the spend (as in text and PDF), so a bigger bill
+    // reads as an increase. Changes are left uncolored for that reason.
+    let line = |label: Cell<'static>, values: &[f64], sign: f64| {
+        let mut cells = vec![label, money_cell(sign * values[0])];
+        for &base in &values[1..] {
+            let (diff, pct) = reports::period_change(values[0], base);
+            cells.push(money_cell(sign * base));
+            cells.push(text_cell(money(diff)));
+            cells.push(text_cell(pct_change(pct)));
+        }
+        Row::new(cells)
+    };
+    let abs = |values: &[f64]| values.iter().map(|v| v.abs()).collect::<Vec<_>>();
+
+    let mut rows = Vec::new();
+    if !data.income.is_empty() {
+        rows.push(section_row("INCOME", num_cols));
+        for item in &data.income {
+            drills.extend(scope.line(rows.len(), &item.name));
+            rows.push(line(
+                text_cell(format!("  {}", item.name)),
+                &item.totals,
+                1.0,
+            ));
+        }
+        rows.push(line(bold_cell("  Total Income"), &data.total_income, 1.0));
+        rows.push(blank_row(num_cols));
+    }
+    if !data.expenses.is_empty() {
+        rows.push(section_row("EXPENSES", num_cols));
+        for item in &data.expenses {
+            drills.extend(scope.line(rows.len(), &item.name));
+            rows.push(line(
+                text_cell(format!("  {}", item.name)),
+                &abs(&item.totals),
+                -1.0,
+            ));
+        }
+        rows.push(line(
+            bold_cell("  Total Expenses"),
+            &abs(&data.total_expenses),
+            -1.0,
+        ));
+        rows.push(blank_row(num_cols));
+    }
+    rows.push(line(bold_cell("NET"), &data.net, 1.0));
+    (header, rows, widths)
+}
+
+pub(crate) fn build_expenses(params: &ReportParams) -> Result<Box<dyn ReportView>> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    let (my, mm) = parse_month_opt(&month);
-    let data = reports::get_expense_breakdown(&conn, year.or(my), mm)?;
+    let fiscal = FiscalYear::load(&conn);
+    // Fetch every category separately; rolling up happens in the view so
+    // rolled-up rows can be expanded again.
+    let data = reports::get_expense_breakdown(
+        &conn,
+        params.year,
+        params.month,
+        None,
+        &params.exclude_tags,
+    )?;
 
-    let widths = vec![
+    let scope = DrillScope::new(&conn, params)?;
+    let effective_year = params.year.unwrap_or_else(|| fiscal.current());
+    let table = TableReportView::new(
+        title_with_exclusions(&t("report.expenses"), &params.exclude_tags),
+        expense_header(),
+        Vec::new(),
+        expense_widths(),
+    )
+    .with_date(
+        DateGranularity::MonthAndYear,
+        fiscal,
+        effective_year,
+        params.month,
+    );
+
+    let Some(depth) = params.depth else {
+        let drills = data
+            .categories
+            .iter()
+            .enumerate()
+            .filter_map(|(row, item)| scope.line(row, &item.name))
+            .collect();
+        let mut table = table.with_drills(drills);
+        table.rows = expense_rows(
+            data.categories.iter().map(|i| (i.name.clone(), i)),
+            &data,
+            None,
+        );
+        return Ok(Box::new(table));
+    };
+    let mut view = ExpenseDrillView {
+        table: TableReportView {
+            key_hint: "Enter=expand/transactions  ",
+            ..table
+        },
+        data,
+        scope,
+        depth,
+        expanded: HashSet::new(),
+        lines: Vec::new(),
+        cursor: 0,
+    };
+    view.rebuild();
+    Ok(Box::new(view))
+}
+
+fn expense_widths() -> Vec<Constraint> {
+    vec![
         Constraint::Fill(1),
         Constraint::Length(14),
         Constraint::Length(8),
         Constraint::Length(8),
-    ];
-    let header = Row::new(["Category", "Amount", "%", "Count"])
+    ]
+}
+
+fn expense_header() -> Row<'static> {
+    Row::new(["Category", "Amount", "%", "Count"])
         .style(HEADER_ROW_STYLE)
-        .bottom_margin(1);
+        .bottom_margin(1)
+}
 
+/// Category rows (with their display labels) followed by the total and the
+/// top-vendor section. `cursor` highlights one category row.
+fn expense_rows<'a>(
+    items: impl Iterator<Item = (String, &'a reports::ExpenseItem)>,
+    data: &reports::ExpenseBreakdown,
+    cursor: Option<usize>,
+) -> Vec<Row<'static>> {
     let mut rows = Vec::new();
 
-    for item in &data.categories {
-        rows.push(Row::new([
-            text_cell(&item.name),
+    for (i, (label, item)) in items.enumerate() {
+        let row = Row::new([
+            text_cell(label),
             money_cell(-item.total.abs()),
             text_cell(format!("{:.1}%", item.pct)),
             text_cell(item.count.to_string()),
-        ]));
+        ]);
+        rows.push(if cursor == Some(i) {
+            row.style(CURSOR_STYLE)
+        } else {
+            row
+        });
     }
 
     rows.push(blank_row(4));
@@ -495,21 +989,165 @@ pub(crate) fn build_expenses(
             ]));
         }
     }
+    rows
+}
 
-    let effective_year = year
-        .or(my)
-        .unwrap_or_else(|| chrono::Datelike::year(&chrono::Local::now()));
-    Ok(Box::new(
-        TableReportView::new("Expense Breakdown", header, rows, widths).with_date(
-            DateGranularity::MonthAndYear,
-            effective_year,
-            mm,
-        ),
-    ))
+const CURSOR_STYLE: Style = Style::new().add_modifier(Modifier::REVERSED);
+
+/// One visible category row in the drill-down view.
+struct DrillLine {
+    item: reports::ExpenseItem,
+    level: usize,
+    /// Transactions booked on the parent category itself, shown when it is expanded.
+    direct: bool,
+    expandable: bool,
+}
+
+/// Expense breakdown rolled up to `--depth`, where Enter expands a row into
+/// its next level of sub-categories, and opens the transactions of a row
+/// with none.
+pub(crate) struct ExpenseDrillView {
+    table: TableReportView,
+    /// Unrolled breakdown: one item per category.
+    data: reports::ExpenseBreakdown,
+    scope: DrillScope,
+    depth: usize,
+    expanded: HashSet<Vec<String>>,
+    lines: Vec<DrillLine>,
+    cursor: usize,
+}
+
+impl ExpenseDrillView {
+    fn rebuild(&mut self) {
+        let top =
+            reports::roll_up_expenses(&self.data.categories, Some(self.depth), self.data.total);
+        let mut lines = Vec::new();
+        for item in top {
+            self.push_line(&mut lines, item, 0, false);
+        }
+        self.lines = lines;
+        self.cursor = self.cursor.min(self.lines.len().saturating_sub(1));
+
+        let labels = self.lines.iter().map(|l| {
+            let marker = match (l.expandable, self.expanded.contains(&l.item.path)) {
+                (false, _) => "  ",
+                (true, false) => "\u{25b8} ",
+                (true, true) => "\u{25be} ",
+            };
+            let name = if l.direct {
+                format!(
+                    "{} (direct)",
+                    l.item.path.last().map(String::as_str).unwrap_or("")
+                )
+            } else if l.level == 0 {
+                l.item.name.clone()
+            } else {
+                l.item.path.last().cloned().unwrap_or_default()
+            };
+            (format!("{}{marker}{name}", "  ".repeat(l.level)), &l.item)
+        });
+        let cursor = (!self.lines.is_empty()).then_some(self.cursor);
+        self.table.rows = expense_rows(labels, &self.data, cursor);
+    }
+
+    fn push_line(
+        &self,
+        lines: &mut Vec<DrillLine>,
+        item: reports::ExpenseItem,
+        level: usize,
+        direct: bool,
+    ) {
+        let prefix_len = item.path.len();
+        let expandable = !direct
+            && self
+                .data
+                .categories
+                .iter()
+                .any(|c| c.path.len() > prefix_len && c.path.starts_with(&item.path));
+        let open = expandable && self.expanded.contains(&item.path);
+        let path = item.path.clone();
+        lines.push(DrillLine {
+            item,
+            level,
+            direct,
+            expandable,
+        });
+        if !open {
+            return;
+        }
+        let below: Vec<reports::ExpenseItem> = self
+            .data
+            .categories
+            .iter()
+            .filter(|c| c.path.starts_with(&path))
+            .cloned()
+            .collect();
+        for child in reports::roll_up_expenses(&below, Some(prefix_len + 1), self.data.total) {
+            let direct = child.path.len() == prefix_len;
+            self.push_line(lines, child, level + 1, direct);
+        }
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        if self.lines.is_empty() {
+            return;
+        }
+        let last = self.lines.len() - 1;
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+        let visible = self.table.visible_count.max(1);
+        if self.cursor < self.table.offset {
+            self.table.offset = self.cursor;
+        } else if self.cursor >= self.table.offset + visible {
+            self.table.offset = self.cursor + 1 - visible;
+        }
+        self.rebuild();
+    }
+
+    /// Expand or collapse the selected row, or browse its transactions
+    /// when it has no sub-categories to show.
+    fn toggle(&mut self) -> ReportViewAction {
+        let Some(line) = self.lines.get(self.cursor) else {
+            return ReportViewAction::Continue;
+        };
+        if !line.expandable {
+            let path = line.item.path.join(reports::CATEGORY_PATH_SEP);
+            return match self.scope.drill(&path, false) {
+                Some(drill) => drill.browse(),
+                None => ReportViewAction::Continue,
+            };
+        }
+        let path = line.item.path.clone();
+        if !self.expanded.remove(&path) {
+            self.expanded.insert(path);
+        }
+        self.rebuild();
+        ReportViewAction::Continue
+    }
+}
+
+impl ReportView for ExpenseDrillView {
+    fn draw(&mut self, frame: &mut Frame) {
+        self.table.draw(frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> ReportViewAction {
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Enter | KeyCode::Char(' ') => return self.toggle(),
+            _ => return self.table.handle_key(code),
+        }
+        ReportViewAction::Continue
+    }
+
+    fn date_params(&self) -> (Option<i32>, Option<String>) {
+        self.table.date_params()
+    }
 }
 
 pub(crate) fn build_tax(year: Option<i32>) -> Result<Box<dyn ReportView>> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
     let data = reports::get_tax_summary(&conn, year)?;
 
     let widths = vec![
@@ -537,24 +1175,43 @@ pub(crate) fn build_tax(year: Option<i32>) -> Result<Box<dyn ReportView>> {
             Cell::from(Span::styled(money(item.total.abs()), style)),
         ]));
     }
+    let warnings = data.limit_warnings();
+    if !warnings.is_empty() {
+        rows.push(blank_row(4));
+        rows.push(section_row("OVER A DEDUCTION LIMIT", 4));
+        let dim = Style::default().fg(Color::DarkGray);
+        for line in warnings
+            .iter()
+            .map(|w| Span::styled(w.clone(), Style::default().fg(Color::Yellow)))
+            .chain([Span::styled(crate::expense_limits::ADJUST_NOTE, dim)])
+        {
+            rows.push(Row::new([
+                Cell::from(line),
+                Cell::from(""),
+                Cell::from(""),
+                Cell::from(""),
+            ]));
+        }
+    }
 
-    let effective_year = year.unwrap_or_else(|| chrono::Datelike::year(&chrono::Local::now()));
+    let effective_year = year.unwrap_or_else(|| fiscal.current());
     Ok(Box::new(
-        TableReportView::new("Tax Summary", header, rows, widths).with_date(
+        TableReportView::new(t("report.tax"), header, rows, widths).with_date(
             DateGranularity::YearOnly,
+            fiscal,
             effective_year,
             None,
         ),
     ))
 }
 
-pub(crate) fn build_cashflow(
-    month: Option<String>,
-    year: Option<i32>,
-) -> Result<Box<dyn ReportView>> {
+pub(crate) fn build_cashflow(params: &ReportParams, chart: bool) -> Result<Box<dyn ReportView>> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    let (my, mm) = parse_month_opt(&month);
-    let data = reports::get_cashflow(&conn, year.or(my), mm)?;
+    let fiscal = FiscalYear::load(&conn);
+    let mm = params.month;
+    let exclude_tags = &params.exclude_tags;
+    let granularity = params.granularity;
+    let data = reports::get_cashflow_by(&conn, params.year, mm, exclude_tags, granularity)?;
 
     let widths = vec![
         Constraint::Length(12),
@@ -563,15 +1220,21 @@ pub(crate) fn build_cashflow(
         Constraint::Length(14),
         Constraint::Length(14),
     ];
-    let header = Row::new(["Month", "Inflows", "Outflows", "Net", "Running"])
-        .style(HEADER_ROW_STYLE)
-        .bottom_margin(1);
+    let header = Row::new([
+        granularity.heading(),
+        "Inflows",
+        "Outflows",
+        "Net",
+        "Running",
+    ])
+    .style(HEADER_ROW_STYLE)
+    .bottom_margin(1);
 
     let mut rows = Vec::new();
 
     for m in &data.months {
         rows.push(Row::new([
-            text_cell(&m.month),
+            text_cell(&m.label),
             Cell::from(Span::styled(money(m.inflows), AMOUNT_POS_STYLE)),
             Cell::from(Span::styled(money(m.outflows.abs()), AMOUNT_NEG_STYLE)),
             money_cell(m.net),
@@ -579,34 +1242,543 @@ pub(crate) fn build_cashflow(
         ]));
     }
 
-    let effective_year = year
-        .or(my)
-        .unwrap_or_else(|| chrono::Datelike::year(&chrono::Local::now()));
-    Ok(Box::new(
-        TableReportView::new("Cash Flow", header, rows, widths).with_date(
-            DateGranularity::MonthAndYear,
+    let effective_year = params.year.unwrap_or_else(|| fiscal.current());
+    let table = TableReportView {
+        key_hint: "g=chart  w=week/month/quarter  ",
+        ..TableReportView::new(
+            title_with_exclusions(&t("report.cashflow"), exclude_tags),
+            header,
+            rows,
+            widths,
+        )
+        .with_date(DateGranularity::MonthAndYear, fiscal, effective_year, mm)
+    };
+    // The chart always covers a fiscal year by month
+    let months = if mm.is_none() {
+        let monthly = if granularity == reports::Granularity::Month {
+            data
+        } else {
+            reports::get_cashflow(&conn, params.year, None, exclude_tags)?
+        };
+        chart_months(
+            fiscal,
             effective_year,
-            mm,
-        ),
-    ))
+            &monthly,
+            chrono::Local::now().date_naive(),
+        )
+    } else {
+        Vec::new()
+    };
+    Ok(Box::new(CashflowView {
+        table,
+        months,
+        chart: chart && mm.is_none(),
+        granularity,
+    }))
 }
 
-pub(crate) fn build_flagged() -> Result<Box<dyn ReportView>> {
-    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    let data = reports::get_flagged(&conn)?;
+/// One month on the cash flow chart.
+struct ChartMonth {
+    month: u32,
+    inflows: f64,
+    outflows: f64,
+    net: f64,
+}
 
-    let widths = vec![
-        Constraint::Length(6),
-        Constraint::Length(10),
-        Constraint::Fill(1),
-        Constraint::Length(12),
-        Constraint::Length(20),
-    ];
-    let header = Row::new(["ID", "Date", "Description", "Amount", "Account"])
-        .style(HEADER_ROW_STYLE)
-        .bottom_margin(1);
+/// Every month of fiscal year `fy` up to `today`'s month, including months
+/// with no transactions.
+fn chart_months(
+    fiscal: FiscalYear,
+    fy: i32,
+    data: &reports::CashflowReport,
+    today: chrono::NaiveDate,
+) -> Vec<ChartMonth> {
+    let (start, end) = fiscal.bounds(fy);
+    let end = end.min(today);
+    let mut months = Vec::new();
+    let mut date = start;
+    while date <= end {
+        let key = date.format("%Y-%m").to_string();
+        let found = data.months.iter().find(|m| m.month == key);
+        months.push(ChartMonth {
+            month: chrono::Datelike::month(&date),
+            inflows: found.map_or(0.0, |m| m.inflows),
+            outflows: found.map_or(0.0, |m| m.outflows),
+            net: found.map_or(0.0, |m| m.net),
+        });
+        let Some(next) = date.checked_add_months(chrono::Months::new(1)) else {
+            break;
+        };
+        date = next;
+    }
+    months
+}
 
-    let mut rows = Vec::new();
+const NET_LINE_STYLE: Style = Style::new().fg(Color::Yellow);
+
+/// Cash flow table that `g` turns into a chart: inflow bars above zero,
+/// outflow bars below, and a line for each month's net.
+pub(crate) struct CashflowView {
+    table: TableReportView,
+    /// The fiscal year's months for the chart; empty in month mode.
+    months: Vec<ChartMonth>,
+    chart: bool,
+    /// Table rows per week, month, or quarter (`w`).
+    granularity: reports::Granularity,
+}
+
+impl CashflowView {
+    fn draw_chart(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let [header_area, sep_area, legend_area, chart_area, labels_area, footer_area] =
+            Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                " {}{}",
+                self.table.title,
+                self.table.period_label()
+            ))
+            .style(HEADER_STYLE),
+            header_area,
+        );
+        frame.render_widget(
+            Paragraph::new("\u{2501}".repeat(area.width as usize)).style(FOOTER_STYLE),
+            sep_area,
+        );
+        frame.render_widget(
+            Paragraph::new(" \u{2190}/\u{2192}=year  g=table  q/Esc=close").style(FOOTER_STYLE),
+            footer_area,
+        );
+
+        if self.months.is_empty() {
+            frame.render_widget(
+                Paragraph::new("   No months to chart yet in this fiscal year."),
+                chart_area,
+            );
+            return;
+        }
+
+        let total = |f: fn(&ChartMonth) -> f64| self.months.iter().map(f).sum::<f64>();
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(" \u{2588} Inflows ", AMOUNT_POS_STYLE),
+                Span::raw(money(total(|m| m.inflows))),
+                Span::styled("   \u{2588} Outflows ", AMOUNT_NEG_STYLE),
+                Span::raw(money(total(|m| m.outflows).abs())),
+                Span::styled("   \u{2500} Net ", NET_LINE_STYLE),
+                money_span(total(|m| m.net)),
+            ])),
+            legend_area,
+        );
+
+        // Symmetric bounds keep zero on the middle axis label
+        let peak = self
+            .months
+            .iter()
+            .flat_map(|m| [m.inflows, m.outflows.abs(), m.net.abs()])
+            .fold(0.0, f64::max);
+        let (top, _) = y_axis_ticks(peak);
+        let y_labels = [money_compact(-top), money_compact(0.0), money_compact(top)];
+        let label_width = y_labels.iter().map(|l| display_width(l)).max().unwrap_or(0);
+        // The y axis takes the label column plus one for its line
+        let graph_x = chart_area.x + label_width as u16 + 1;
+        let graph_width = chart_area
+            .width
+            .saturating_sub(label_width as u16 + 1)
+            .max(1);
+
+        let n = self.months.len();
+        let x_bounds = [-0.5, n as f64 - 0.5];
+        // One point per cell column across 60% of each month's slot
+        let step = n as f64 / graph_width as f64;
+        let bar = |value: fn(&ChartMonth) -> f64| -> Vec<(f64, f64)> {
+            let mut points = Vec::new();
+            for (i, m) in self.months.iter().enumerate() {
+                let mut x = i as f64 - 0.3;
+                while x <= i as f64 + 0.3 {
+                    points.push((x, value(m)));
+                    x += step;
+                }
+                points.push((i as f64, value(m)));
+            }
+            points
+        };
+        let inflows = bar(|m| m.inflows);
+        let outflows = bar(|m| m.outflows);
+        let net: Vec<(f64, f64)> = self
+            .months
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (i as f64, m.net))
+            .collect();
+
+        let datasets = vec![
+            Dataset::default()
+                .marker(Marker::HalfBlock)
+                .graph_type(GraphType::Bar)
+                .style(AMOUNT_POS_STYLE)
+                .data(&inflows),
+            Dataset::default()
+                .marker(Marker::HalfBlock)
+                .graph_type(GraphType::Bar)
+                .style(AMOUNT_NEG_STYLE)
+                .data(&outflows),
+            Dataset::default()
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(NET_LINE_STYLE)
+                .data(&net),
+        ];
+        let chart = Chart::new(datasets)
+            .x_axis(Axis::default().bounds(x_bounds))
+            .y_axis(
+                Axis::default()
+                    .bounds([-top, top])
+                    .labels(y_labels.map(Span::raw))
+                    .style(FOOTER_STYLE),
+            )
+            .legend_position(None);
+        frame.render_widget(chart, chart_area);
+
+        // Month names centered under each month's bars
+        let slot = graph_width as f64 / n as f64;
+        let mut labels = String::new();
+        for (i, m) in self.months.iter().enumerate() {
+            let name = crate::fiscal::month_abbr(m.month);
+            let name = if slot >= 4.0 { name } else { &name[..1] };
+            let center = (graph_x - labels_area.x) as f64 + (i as f64 + 0.5) * slot;
+            let start = (center - display_width(name) as f64 / 2.0).round().max(0.0) as usize;
+            let pad = start.saturating_sub(display_width(&labels));
+            labels.push_str(&" ".repeat(pad));
+            labels.push_str(name);
+        }
+        frame.render_widget(Paragraph::new(labels).style(FOOTER_STYLE), labels_area);
+    }
+}
+
+impl ReportView for CashflowView {
+    fn draw(&mut self, frame: &mut Frame) {
+        if self.chart {
+            self.draw_chart(frame);
+        } else {
+            self.table.draw(frame);
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> ReportViewAction {
+        match code {
+            KeyCode::Char('g') if self.table.period_mode == PeriodMode::Month => {
+                // Chart the fiscal year holding the month
+                self.table.toggle_period_mode();
+                self.chart = true;
+                ReportViewAction::Reload
+            }
+            KeyCode::Char('g') => {
+                self.chart = !self.chart;
+                ReportViewAction::Continue
+            }
+            KeyCode::Char('w') if !self.chart => {
+                self.granularity = self.granularity.next();
+                // A quarter doesn't fit in a month; show the year's
+                if self.granularity == reports::Granularity::Quarter
+                    && self.table.period_mode == PeriodMode::Month
+                {
+                    self.table.toggle_period_mode();
+                }
+                ReportViewAction::Reload
+            }
+            KeyCode::Char('m')
+            | KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+                if self.chart =>
+            {
+                ReportViewAction::Continue
+            }
+            _ => self.table.handle_key(code),
+        }
+    }
+
+    fn date_params(&self) -> (Option<i32>, Option<String>) {
+        self.table.date_params()
+    }
+
+    fn chart(&self) -> bool {
+        self.chart
+    }
+
+    fn granularity(&self) -> reports::Granularity {
+        self.granularity
+    }
+}
+
+pub(crate) fn build_balance_history(
+    params: &ReportParams,
+    chart: bool,
+) -> Result<Box<dyn ReportView>> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    let data = reports::get_balance_history(
+        &conn,
+        params.account.as_deref().unwrap_or_default(),
+        params.year,
+        params.month,
+        params.from_date.as_deref(),
+        params.to_date.as_deref(),
+        params.interval,
+        params.opening_balance,
+    )?;
+
+    let widths = vec![
+        Constraint::Length(14),
+        Constraint::Length(18),
+        Constraint::Length(18),
+    ];
+    let header = Row::new([data.interval.heading(), "Change", "Balance"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
+    let mut rows = vec![Row::new([
+        text_cell("Starting"),
+        text_cell(""),
+        text_cell(data.money(data.starting_balance)),
+    ])];
+    for p in data.table_points() {
+        let style = if p.change < 0.0 {
+            AMOUNT_NEG_STYLE
+        } else {
+            AMOUNT_POS_STYLE
+        };
+        rows.push(Row::new([
+            text_cell(fmt::date(&p.date)),
+            Cell::from(Span::styled(data.money(p.change), style)),
+            text_cell(data.money(p.balance)),
+        ]));
+    }
+
+    let effective_year = params.year.unwrap_or_else(|| fiscal.current());
+    let table = TableReportView {
+        key_hint: "g=chart  w=day/week  ",
+        ..TableReportView::new(
+            format!("Balance History: {}", data.account),
+            header,
+            rows,
+            widths,
+        )
+        .with_date(
+            DateGranularity::MonthAndYear,
+            fiscal,
+            effective_year,
+            params.month,
+        )
+        .with_range(params.range_label())
+    };
+    Ok(Box::new(BalanceHistoryView { table, data, chart }))
+}
+
+/// An account's running balance as a line chart, or as a table (`g`).
+pub(crate) struct BalanceHistoryView {
+    table: TableReportView,
+    data: reports::BalanceHistory,
+    chart: bool,
+}
+
+impl BalanceHistoryView {
+    fn draw_chart(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let [header_area, sep_area, legend_area, chart_area, labels_area, footer_area] =
+            Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                " {}{}",
+                self.table.title,
+                self.table.period_label()
+            ))
+            .style(HEADER_STYLE),
+            header_area,
+        );
+        frame.render_widget(
+            Paragraph::new("\u{2501}".repeat(area.width as usize)).style(FOOTER_STYLE),
+            sep_area,
+        );
+        frame.render_widget(
+            Paragraph::new(format!(
+                " {}g=table  w=day/week  q/Esc=close",
+                self.table.nav_hint()
+            ))
+            .style(FOOTER_STYLE),
+            footer_area,
+        );
+
+        let data = &self.data;
+        let (Some(low), Some(high)) = (data.low(), data.high()) else {
+            frame.render_widget(
+                Paragraph::new("   No days to chart in this period."),
+                chart_area,
+            );
+            return;
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(" \u{2500} Balance ", NET_LINE_STYLE),
+                Span::raw(data.money(data.ending_balance())),
+                Span::styled("   Low ", FOOTER_STYLE),
+                Span::raw(format!(
+                    "{} ({})",
+                    data.money(low.balance),
+                    fmt::date(&low.date)
+                )),
+                Span::styled("   High ", FOOTER_STYLE),
+                Span::raw(format!(
+                    "{} ({})",
+                    data.money(high.balance),
+                    fmt::date(&high.date)
+                )),
+            ])),
+            legend_area,
+        );
+
+        // Pad a flat line so it sits inside the chart instead of on its edge
+        let (mut bottom, mut top) = (low.balance.min(0.0), high.balance.max(0.0));
+        if top - bottom < 1.0 {
+            bottom -= 1.0;
+            top += 1.0;
+        }
+        let y_labels = [
+            money_compact(bottom),
+            money_compact((bottom + top) / 2.0),
+            money_compact(top),
+        ];
+        let label_width = y_labels.iter().map(|l| display_width(l)).max().unwrap_or(0);
+        let graph_x = chart_area.x + label_width as u16 + 1;
+        let graph_width = chart_area.width.saturating_sub(label_width as u16 + 1);
+
+        let points: Vec<(f64, f64)> = data
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i as f64, p.balance))
+            .collect();
+        let last = (points.len() - 1).max(1) as f64;
+        let mut datasets = vec![Dataset::default()
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(NET_LINE_STYLE)
+            .data(&points)];
+        let zero = [(0.0, 0.0), (last, 0.0)];
+        if bottom < 0.0 {
+            datasets.insert(
+                0,
+                Dataset::default()
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(FOOTER_STYLE)
+                    .data(&zero),
+            );
+        }
+        let chart = Chart::new(datasets)
+            .x_axis(Axis::default().bounds([0.0, last]))
+            .y_axis(
+                Axis::default()
+                    .bounds([bottom, top])
+                    .labels(y_labels.map(Span::raw))
+                    .style(FOOTER_STYLE),
+            )
+            .legend_position(None);
+        frame.render_widget(chart, chart_area);
+
+        // First and last dates under the ends of the line
+        let first = fmt::date(&data.points[0].date);
+        let end = fmt::date(&data.points[points.len() - 1].date);
+        let indent = (graph_x - labels_area.x) as usize;
+        let gap =
+            (graph_width as usize).saturating_sub(display_width(&first) + display_width(&end));
+        frame.render_widget(
+            Paragraph::new(format!(
+                "{}{first}{}{end}",
+                " ".repeat(indent),
+                " ".repeat(gap)
+            ))
+            .style(FOOTER_STYLE),
+            labels_area,
+        );
+    }
+}
+
+impl ReportView for BalanceHistoryView {
+    fn draw(&mut self, frame: &mut Frame) {
+        if self.chart {
+            self.draw_chart(frame);
+        } else {
+            self.table.draw(frame);
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> ReportViewAction {
+        match code {
+            KeyCode::Char('g') => {
+                self.chart = !self.chart;
+                ReportViewAction::Continue
+            }
+            KeyCode::Char('w') => {
+                self.data.interval = self.data.interval.next();
+                ReportViewAction::Reload
+            }
+            KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown if self.chart => {
+                ReportViewAction::Continue
+            }
+            _ => self.table.handle_key(code),
+        }
+    }
+
+    fn date_params(&self) -> (Option<i32>, Option<String>) {
+        self.table.date_params()
+    }
+
+    fn chart(&self) -> bool {
+        self.chart
+    }
+
+    fn interval(&self) -> reports::BalanceInterval {
+        self.data.interval
+    }
+}
+
+pub(crate) fn build_flagged() -> Result<Box<dyn ReportView>> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let data = reports::get_flagged(&conn)?;
+
+    let widths = vec![
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Fill(1),
+        Constraint::Length(12),
+        Constraint::Length(20),
+        Constraint::Fill(1),
+    ];
+    let header = Row::new(["ID", "Date", "Description", "Amount", "Account", "Reason"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
+
+    let mut rows = Vec::new();
 
     if data.is_empty() {
         rows.push(Row::new([
@@ -615,24 +1787,36 @@ pub(crate) fn build_flagged() -> Result<Box<dyn ReportView>> {
             text_cell("No flagged transactions."),
             Cell::from(""),
             Cell::from(""),
+            Cell::from(""),
         ]));
     } else {
-        for r in &data {
-            rows.push(Row::new([
+        let (unusual, review): (Vec<_>, Vec<_>) = data.iter().partition(|r| r.is_unusual());
+        let flagged_row = |r: &reports::FlaggedTransaction| {
+            Row::new([
                 text_cell(r.id.to_string()),
-                text_cell(&r.date),
+                text_cell(fmt::date(&r.date)),
                 text_cell(truncate(&r.description, 50)),
                 money_cell(r.amount),
                 text_cell(&r.account_name),
-            ]));
+                text_cell(r.flag_reason.as_deref().unwrap_or("")),
+            ])
+        };
+        rows.extend(review.into_iter().map(flagged_row));
+        if !unusual.is_empty() {
+            if !rows.is_empty() {
+                rows.push(blank_row(6));
+            }
+            rows.push(section_row("UNUSUAL", 6));
+            rows.extend(unusual.into_iter().map(flagged_row));
         }
-        rows.push(blank_row(5));
+        rows.push(blank_row(6));
         rows.push(Row::new([
             bold_cell(format!("Total: {}", data.len())),
             Cell::from(""),
             Cell::from(""),
             Cell::from(""),
             Cell::from(""),
+            Cell::from(""),
         ]));
     }
 
@@ -644,37 +1828,187 @@ pub(crate) fn build_flagged() -> Result<Box<dyn ReportView>> {
     )))
 }
 
-pub(crate) fn build_balance() -> Result<Box<dyn ReportView>> {
+pub(crate) fn build_reimbursables(client: Option<&str>, all: bool) -> Result<Box<dyn ReportView>> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let data = crate::reimbursables::get_report(&conn, client, all)?;
+
+    let widths = vec![
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Fill(1),
+        Constraint::Length(12),
+        Constraint::Length(16),
+        Constraint::Fill(1),
+    ];
+    let header = Row::new(["ID", "Date", "Description", "Amount", "Client", "Status"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
+
+    let mut rows = Vec::new();
+
+    if data.items.is_empty() {
+        rows.push(Row::new([
+            Cell::from(""),
+            Cell::from(""),
+            text_cell("No outstanding reimbursables."),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+        ]));
+    } else {
+        for r in &data.items {
+            rows.push(Row::new([
+                text_cell(r.transaction_id.to_string()),
+                text_cell(fmt::date(&r.date)),
+                text_cell(truncate(&r.description, 50)),
+                money_cell(r.amount),
+                text_cell(format!("#{}", r.client)),
+                text_cell(r.status()),
+            ]));
+        }
+        if !data.clients.is_empty() {
+            rows.push(blank_row(6));
+            rows.push(section_row("OUTSTANDING BY CLIENT", 6));
+            for c in &data.clients {
+                rows.push(Row::new([
+                    Cell::from(""),
+                    text_cell(&c.oldest),
+                    text_cell(format!("  #{} ({})", c.client, c.count)),
+                    money_cell(c.total()),
+                    Cell::from(""),
+                    text_cell(format!("{} not invoiced", money(c.not_invoiced))),
+                ]));
+            }
+        }
+    }
+
+    Ok(Box::new(TableReportView::new(
+        "Reimbursables",
+        header,
+        rows,
+        widths,
+    )))
+}
+
+pub(crate) fn build_digest(month: Option<String>) -> Result<Box<dyn ReportView>> {
+    let (year, month) = crate::cli::digest::resolve_month(month.as_deref())?;
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    let data = reports::get_balance(&conn)?;
+    let data = crate::digest::build(&conn, year, month)?;
 
     let widths = vec![
+        Constraint::Fill(1),
+        Constraint::Length(14),
+        Constraint::Length(14),
+    ];
+    let header = Row::new(["", "This month", "Prior month"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
+
+    let mut rows = vec![
+        Row::new([
+            text_cell("Income"),
+            money_cell(data.income),
+            money_cell(data.prior_income),
+        ]),
+        Row::new([
+            text_cell("Expenses"),
+            money_cell(-data.expenses),
+            money_cell(-data.prior_expenses),
+        ]),
+        Row::new([
+            bold_cell("Net"),
+            money_cell(data.net),
+            money_cell(data.prior_net),
+        ]),
+        blank_row(3),
+    ];
+    for block in data.narrative() {
+        let lines = match block {
+            crate::digest::Block::Paragraph(p) => vec![p],
+            crate::digest::Block::Points(points) => points
+                .into_iter()
+                .map(|p| format!("  \u{2022} {p}"))
+                .collect(),
+        };
+        for line in lines {
+            let (wrapped, height) = crate::tui::wrap_text(&line, 80);
+            rows.push(
+                Row::new([text_cell(wrapped), Cell::from(""), Cell::from("")]).height(height),
+            );
+        }
+        rows.push(blank_row(3));
+    }
+
+    Ok(Box::new(
+        TableReportView::new("Monthly Summary", header, rows, widths).with_date(
+            DateGranularity::MonthOnly,
+            FiscalYear::CALENDAR,
+            year,
+            Some(month),
+        ),
+    ))
+}
+
+pub(crate) fn build_balance(include_archived: bool) -> Result<Box<dyn ReportView>> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let data = reports::get_balance(&conn, include_archived)?;
+
+    let show_native = data
+        .accounts
+        .iter()
+        .any(|a| a.currency != data.home_currency);
+    let mut widths = vec![
         Constraint::Fill(1),
         Constraint::Length(20),
         Constraint::Length(14),
     ];
-    let header = Row::new(["Account", "Type", "Balance"])
+    let mut header_cells = vec!["Account", "Type", "Balance"];
+    if show_native {
+        widths.push(Constraint::Length(18));
+        header_cells.push("Native");
+    }
+    let cols = widths.len();
+    let header = Row::new(header_cells)
         .style(HEADER_ROW_STYLE)
         .bottom_margin(1);
 
     let mut rows = Vec::new();
 
     for a in &data.accounts {
-        rows.push(Row::new([
-            text_cell(&a.name),
+        let mut cells = vec![
+            text_cell(a.label()),
             text_cell(&a.account_type),
             money_cell(a.balance),
-        ]));
+        ];
+        if show_native {
+            if a.currency != data.home_currency {
+                cells.push(text_cell(money_in(a.native_balance, &a.currency)));
+            } else {
+                cells.push(Cell::from(""));
+            }
+        }
+        rows.push(Row::new(cells));
     }
 
-    rows.push(blank_row(3));
-    rows.push(Row::new([
+    let pad = |mut cells: Vec<Cell<'static>>| {
+        cells.resize(cols, Cell::from(""));
+        Row::new(cells)
+    };
+    if let Some(label) = data.archived_line() {
+        rows.push(pad(vec![
+            text_cell(label),
+            Cell::from(""),
+            money_cell(data.archived_balance),
+        ]));
+    }
+    rows.push(blank_row(cols));
+    rows.push(pad(vec![
         bold_cell("Total"),
         Cell::from(""),
         money_cell(data.total),
     ]));
-    rows.push(blank_row(3));
-    rows.push(Row::new([
+    rows.push(blank_row(cols));
+    rows.push(pad(vec![
         bold_cell("YTD Net Income"),
         Cell::from(""),
         money_cell(data.ytd_net_income),
@@ -690,6 +2024,7 @@ pub(crate) fn build_balance() -> Result<Box<dyn ReportView>> {
 
 pub(crate) fn build_k1(year: Option<i32>) -> Result<Box<dyn ReportView>> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
     let data = reports::get_k1_prep(&conn, year)?;
 
     let widths = vec![
@@ -794,6 +2129,36 @@ pub(crate) fn build_k1(year: Option<i32>) -> Result<Box<dyn ReportView>> {
         ]));
     }
 
+    // Shareholder allocations
+    for holder in &data.shareholders {
+        rows.push(blank_row(3));
+        rows.push(section_row(
+            &format!(
+                "SCHEDULE K-1 \u{2014} {} ({}%)",
+                holder.name.to_uppercase(),
+                holder.ownership_pct
+            ),
+            3,
+        ));
+        rows.push(Row::new([
+            text_cell("1"),
+            text_cell("Ordinary business income (loss)"),
+            money_cell(holder.ordinary_business_income),
+        ]));
+        for item in &holder.schedule_k_items {
+            rows.push(Row::new([
+                text_cell(item.form_line.trim_start_matches("K-")),
+                text_cell(&item.category_name),
+                money_cell(item.total),
+            ]));
+        }
+        rows.push(Row::new([
+            text_cell("16d"),
+            text_cell("Distributions"),
+            money_cell(holder.distributions),
+        ]));
+    }
+
     // Validation warnings
     if data.validation.uncategorized_count > 0 {
         rows.push(blank_row(3));
@@ -810,25 +2175,39 @@ pub(crate) fn build_k1(year: Option<i32>) -> Result<Box<dyn ReportView>> {
             Cell::from(""),
         ]));
     }
-    if let Some(ratio) = data.validation.comp_dist_ratio {
-        if ratio < 1.0 {
-            let warn_style = Style::default().fg(Color::Yellow);
-            rows.push(Row::new([
-                Cell::from(Span::styled(
-                    format!(
-                        "Warning: Officer comp ({}) < distributions ({}) — review reasonable comp",
-                        money(data.validation.officer_comp),
-                        money(data.validation.distributions)
-                    ),
-                    warn_style,
-                )),
-                Cell::from(""),
-                Cell::from(""),
-            ]));
-        }
+    if let Some(pct) = data
+        .validation
+        .ownership_pct_total
+        .filter(|_| data.validation.ownership_incomplete())
+    {
+        let warn_style = Style::default().fg(Color::Yellow);
+        rows.push(Row::new([
+            Cell::from(Span::styled(
+                format!("Warning: Shareholders own {pct}% in total — stakes should add up to 100%"),
+                warn_style,
+            )),
+            Cell::from(""),
+            Cell::from(""),
+        ]));
+    }
+    if data.validation.below_comp_target {
+        let warn_style = Style::default().fg(Color::Yellow);
+        rows.push(Row::new([
+            Cell::from(Span::styled(
+                format!(
+                    "Warning: Officer comp ({}) is under {}% of owner pay (distributions {}) — review reasonable comp",
+                    money(data.validation.officer_comp),
+                    data.validation.comp_target_pct,
+                    money(data.validation.distributions)
+                ),
+                warn_style,
+            )),
+            Cell::from(""),
+            Cell::from(""),
+        ]));
     }
 
-    let effective_year = year.unwrap_or_else(|| chrono::Datelike::year(&chrono::Local::now()));
+    let effective_year = year.unwrap_or_else(|| fiscal.current());
     Ok(Box::new(
         TableReportView::new(
             "K-1 Preparation Worksheet (Form 1120-S)",
@@ -836,55 +2215,490 @@ pub(crate) fn build_k1(year: Option<i32>) -> Result<Box<dyn ReportView>> {
             rows,
             widths,
         )
-        .with_date(DateGranularity::YearOnly, effective_year, None),
+        .with_date(DateGranularity::YearOnly, fiscal, effective_year, None),
     ))
 }
 
-// ---------------------------------------------------------------------------
-// Register (standalone — delegates to RegisterBrowser)
-// ---------------------------------------------------------------------------
+pub(crate) fn build_deductions(year: Option<i32>) -> Result<Box<dyn ReportView>> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    let data = reports::get_deductions(&conn, year)?;
 
-fn register_standalone(cmd: ReportCommands) -> Result<()> {
-    let ReportCommands::Register {
-        month,
-        year,
-        from_date,
-        to_date,
-        account,
-        ..
-    } = cmd
-    else {
-        return Ok(());
+    let widths = vec![
+        Constraint::Length(12),
+        Constraint::Fill(1),
+        Constraint::Length(10),
+        Constraint::Length(8),
+        Constraint::Length(14),
+    ];
+    let header = Row::new(["Date", "Item", "Miles", "Rate", "Amount"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
+
+    // Standard mileage
+    let mut rows = vec![section_row("STANDARD MILEAGE", 5)];
+    if data.trips.is_empty() {
+        rows.push(Row::new([
+            Cell::from(""),
+            text_cell("No mileage logged \u{2014} use `nigel mileage add`"),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+        ]));
+    }
+    for t in &data.trips {
+        rows.push(Row::new([
+            text_cell(fmt::date(&t.date)),
+            text_cell(&t.purpose),
+            text_cell(format!("{:.1}", t.miles)),
+            text_cell(format!("${:.3}", t.rate)),
+            text_cell(money(t.deduction)),
+        ]));
+    }
+    rows.push(Row::new([
+        Cell::from(""),
+        bold_cell("Total Mileage"),
+        bold_cell(format!("{:.1}", data.total_miles)),
+        Cell::from(""),
+        bold_cell(money(data.mileage_deduction)),
+    ]));
+
+    // Home office
+    rows.push(blank_row(5));
+    rows.push(section_row("HOME OFFICE", 5));
+    rows.push(Row::new([
+        Cell::from(""),
+        text_cell("Annual Home Costs"),
+        Cell::from(""),
+        Cell::from(""),
+        text_cell(money(data.home_office.annual_costs)),
+    ]));
+    rows.push(Row::new([
+        Cell::from(""),
+        text_cell("Business Use"),
+        Cell::from(""),
+        Cell::from(""),
+        text_cell(format!("{}%", data.home_office.percent)),
+    ]));
+    rows.push(Row::new([
+        Cell::from(""),
+        bold_cell("Home Office Allocation"),
+        Cell::from(""),
+        Cell::from(""),
+        bold_cell(money(data.home_office_deduction)),
+    ]));
+
+    rows.push(blank_row(5));
+    rows.push(Row::new([
+        Cell::from(""),
+        bold_cell("Total Deductions"),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(Span::styled(money(data.total), AMOUNT_POS_STYLE)),
+    ]));
+
+    let effective_year = year.unwrap_or_else(|| fiscal.current());
+    Ok(Box::new(
+        TableReportView::new("Deductions", header, rows, widths).with_date(
+            DateGranularity::YearOnly,
+            fiscal,
+            effective_year,
+            None,
+        ),
+    ))
+}
+
+pub(crate) fn build_compensation(year: Option<i32>) -> Result<Box<dyn ReportView>> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    let data = reports::get_compensation(&conn, year)?;
+
+    let widths = vec![
+        Constraint::Fill(1),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(18),
+    ];
+    let header = Row::new([
+        "Quarter",
+        "Officer comp",
+        "Distributions",
+        "YTD comp",
+        "YTD distributions",
+    ])
+    .style(HEADER_ROW_STYLE)
+    .bottom_margin(1);
+
+    let mut rows: Vec<Row> = data
+        .quarters
+        .iter()
+        .map(|q| {
+            Row::new([
+                text_cell(&q.label),
+                text_cell(money(q.officer_comp)),
+                text_cell(money(q.distributions)),
+                text_cell(money(q.cumulative_comp)),
+                text_cell(money(q.cumulative_distributions)),
+            ])
+        })
+        .collect();
+
+    rows.push(blank_row(5));
+    rows.push(Row::new([
+        bold_cell("Total"),
+        bold_cell(money(data.officer_comp)),
+        bold_cell(money(data.distributions)),
+        Cell::from(""),
+        Cell::from(""),
+    ]));
+    let share = data
+        .officer_share
+        .map(|s| format!("{s:.0}%"))
+        .unwrap_or_else(|| "\u{2014}".into());
+    rows.push(Row::new([
+        text_cell(format!(
+            "Officer share of owner pay: {share} (target {}%)",
+            data.target_pct
+        )),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(""),
+    ]));
+    if data.below_target() {
+        rows.push(Row::new([
+            Cell::from(Span::styled(
+                "Warning: Distributions are outpacing officer pay \u{2014} review reasonable comp",
+                Style::default().fg(Color::Yellow),
+            )),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+        ]));
+    }
+
+    let effective_year = year.unwrap_or_else(|| fiscal.current());
+    Ok(Box::new(
+        TableReportView::new("Compensation", header, rows, widths).with_date(
+            DateGranularity::YearOnly,
+            fiscal,
+            effective_year,
+            None,
+        ),
+    ))
+}
+
+pub(crate) fn build_tags(params: &ReportParams) -> Result<Box<dyn ReportView>> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    let data = reports::get_tags_report(&conn, params.year, params.month)?;
+
+    let widths = vec![
+        Constraint::Fill(1),
+        Constraint::Length(8),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(14),
+    ];
+    let header = Row::new(["Tag", "Count", "Income", "Expenses", "Net"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
+
+    let mut rows: Vec<Row> = data
+        .iter()
+        .map(|item| {
+            Row::new([
+                text_cell(format!("#{}", item.tag)),
+                text_cell(item.count.to_string()),
+                Cell::from(Span::styled(money(item.income), AMOUNT_POS_STYLE)),
+                Cell::from(Span::styled(money(item.expenses.abs()), AMOUNT_NEG_STYLE)),
+                money_cell(item.net),
+            ])
+        })
+        .collect();
+    if rows.is_empty() {
+        rows.push(Row::new([
+            text_cell("No tagged transactions."),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+        ]));
+    }
+
+    let effective_year = params.year.unwrap_or_else(|| fiscal.current());
+    Ok(Box::new(
+        TableReportView::new("Tags", header, rows, widths).with_date(
+            DateGranularity::MonthAndYear,
+            fiscal,
+            effective_year,
+            params.month,
+        ),
+    ))
+}
+
+pub(crate) fn build_clients(year: Option<i32>) -> Result<Box<dyn ReportView>> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    let data = reports::get_clients(&conn, year)?;
+
+    let widths = vec![
+        Constraint::Fill(1),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(10),
+    ];
+    let header = Row::new(["Client", "Revenue", "Direct costs", "Margin", "Margin %"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
+
+    let pct = |c: &reports::ClientProfit| {
+        c.margin_pct()
+            .map(|p| format!("{p:.0}%"))
+            .unwrap_or_else(|| "\u{2014}".into())
     };
+    let mut rows: Vec<Row> = data
+        .clients
+        .iter()
+        .map(|c| {
+            Row::new([
+                text_cell(&c.name),
+                text_cell(money(c.revenue)),
+                text_cell(money(c.direct_costs)),
+                money_cell(c.margin()),
+                text_cell(pct(c)),
+            ])
+        })
+        .collect();
+    if rows.is_empty() {
+        rows.push(Row::new([
+            text_cell("No income or expenses assigned to clients."),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+        ]));
+    } else {
+        let total = data.total();
+        rows.push(blank_row(5));
+        rows.push(Row::new([
+            bold_cell("Total"),
+            bold_cell(money(total.revenue)),
+            bold_cell(money(total.direct_costs)),
+            money_cell(total.margin()),
+            bold_cell(pct(&total)),
+        ]));
+    }
+    if data.unassigned_revenue.abs() >= 0.005 {
+        rows.push(Row::new([
+            Cell::from(Span::styled(
+                format!(
+                    "Income not assigned to a client: {}",
+                    money(data.unassigned_revenue)
+                ),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(""),
+        ]));
+    }
+
+    let effective_year = year.unwrap_or_else(|| fiscal.current());
+    Ok(Box::new(
+        TableReportView::new("Clients", header, rows, widths).with_date(
+            DateGranularity::YearOnly,
+            fiscal,
+            effective_year,
+            None,
+        ),
+    ))
+}
+
+pub(crate) fn build_books() -> Result<Box<dyn ReportView>> {
+    let data = overview::get_overview(&get_data_dir(), &load_settings().overview_books);
+
+    let widths = vec![
+        Constraint::Fill(1),
+        Constraint::Length(16),
+        Constraint::Length(16),
+        Constraint::Length(18),
+    ];
+    let header = Row::new(["Book", "Cash", "YTD net", "Year"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
 
+    let mut rows = Vec::new();
+    for book in &data.books {
+        let mut label = book.label();
+        if book.current {
+            label.push_str(" (current)");
+        }
+        rows.push(match &book.status {
+            BookStatus::Open {
+                home_currency,
+                cash,
+                ytd_net,
+                fiscal_label,
+                ..
+            } => {
+                let net_style = if *ytd_net < 0.0 {
+                    AMOUNT_NEG_STYLE
+                } else {
+                    AMOUNT_POS_STYLE
+                };
+                Row::new([
+                    text_cell(label),
+                    text_cell(data.money(*cash, home_currency)),
+                    Cell::from(Span::styled(data.money(*ytd_net, home_currency), net_style)),
+                    text_cell(fiscal_label),
+                ])
+            }
+            status => Row::new([
+                text_cell(label),
+                Cell::from(Span::styled(
+                    status.note().unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                )),
+                Cell::from(""),
+                Cell::from(""),
+            ]),
+        });
+    }
+    if let Some((currency, cash, net)) = data.combined() {
+        rows.push(blank_row(4));
+        rows.push(Row::new([
+            bold_cell("Combined"),
+            bold_cell(data.money(cash, &currency)),
+            bold_cell(data.money(net, &currency)),
+            Cell::from(""),
+        ]));
+    } else {
+        let note = if data.books.len() == 1 {
+            Some("List other books' data directories under `overview_books` in settings.json to see them here.")
+        } else if data.open_count() > 1 {
+            Some("The books use different home currencies, so they aren't combined.")
+        } else {
+            None
+        };
+        if let Some(note) = note {
+            rows.push(blank_row(4));
+            rows.push(Row::new([
+                Cell::from(Span::styled(note, Style::default().fg(Color::DarkGray))),
+                Cell::from(""),
+                Cell::from(""),
+                Cell::from(""),
+            ]));
+        }
+    }
+
+    Ok(Box::new(TableReportView::new(
+        "Books Overview",
+        header,
+        rows,
+        widths,
+    )))
+}
+
+pub(crate) fn build_quality() -> Result<Box<dyn ReportView>> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    let (my, mm) = parse_month_opt(&month);
-    let y = year.or(my);
-    let data = reports::get_register(
-        &conn,
-        y,
-        mm,
-        from_date.as_deref(),
-        to_date.as_deref(),
-        account.as_deref(),
-    )?;
+    let data = data_quality::get_report(&conn, data_quality::DateBounds::current())?;
 
-    if data.rows.is_empty() {
-        println!("No transactions found.");
-        return Ok(());
+    let widths = vec![
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Fill(1),
+        Constraint::Length(12),
+        Constraint::Length(20),
+    ];
+    let header = Row::new(["ID", "Date", "Description", "Amount", "Account"])
+        .style(HEADER_ROW_STYLE)
+        .bottom_margin(1);
+
+    let note_row = |note: String| {
+        Row::new([
+            Cell::from(""),
+            Cell::from(""),
+            Cell::from(Span::styled(note, Style::default().fg(Color::DarkGray))),
+            Cell::from(""),
+            Cell::from(""),
+        ])
+    };
+    let mut rows = Vec::new();
+    if data.total() == 0 {
+        rows.push(note_row(data.clean_note()));
+    } else {
+        let sections = [
+            (data.future_heading(), &data.future),
+            (data.before_epoch_heading(), &data.before_epoch),
+        ];
+        for (heading, dated) in sections {
+            if dated.is_empty() {
+                continue;
+            }
+            if !rows.is_empty() {
+                rows.push(blank_row(5));
+            }
+            rows.push(section_row(&heading.to_uppercase(), 5));
+            rows.extend(dated.iter().map(|r| {
+                Row::new([
+                    text_cell(r.id.to_string()),
+                    text_cell(fmt::date(&r.date)),
+                    text_cell(truncate(&r.description, 50)),
+                    money_cell(r.amount),
+                    text_cell(&r.account_name),
+                ])
+            }));
+        }
+        rows.push(blank_row(5));
+        rows.push(note_row(data_quality::FIX_NOTE.to_string()));
     }
 
+    Ok(Box::new(TableReportView::new(
+        format!("Data Quality ({} out of range)", data.total()),
+        header,
+        rows,
+        widths,
+    )))
+}
+
+// ---------------------------------------------------------------------------
+// Register (standalone — delegates to RegisterBrowser)
+// ---------------------------------------------------------------------------
+
+fn register_standalone(params: &ReportParams) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let query = reports::RegisterQuery {
+        year: params.year,
+        month: params.month,
+        from_date: params.from_date.clone(),
+        to_date: params.to_date.clone(),
+        account: params.account.clone(),
+        tag: params.tag.clone(),
+        ..Default::default()
+    };
     let categories = crate::reviewer::get_categories(&conn).unwrap_or_default();
-    let filter_desc = if let Some(ref a) = account {
+    let filter_desc = if let Some(ref a) = params.account {
         format!("account: {a}")
-    } else if let Some(y) = y {
+    } else if let Some(ref t) = params.tag {
+        format!("tag: #{}", t.trim_start_matches('#'))
+    } else if let Some(range) = params.range_label() {
+        range
+    } else if let Some(y) = params.year {
         format!("year: {y}")
     } else {
         "all".to_string()
     };
 
-    let mut browser =
-        crate::browser::RegisterBrowser::new(data.rows, data.total, filter_desc, categories);
+    let mut browser = crate::browser::RegisterBrowser::open(&conn, query, filter_desc, categories)?;
+    if browser.is_empty() {
+        println!("No transactions found.");
+        return Ok(());
+    }
 
     let hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
@@ -925,6 +2739,21 @@ fn register_standalone(cmd: ReportCommands) -> Result<()> {
                             browser.set_status(format!("Flag toggle failed: {e}"));
                         }
                     }
+                    BrowseAction::CreateRule => {
+                        if let Err(e) = browser.create_rule(&conn) {
+                            browser.set_status(format!("Rule not added: {e}"));
+                        }
+                    }
+                    BrowseAction::Trash => {
+                        if let Err(e) = browser.trash_selected(&conn) {
+                            browser.set_status(format!("Not trashed: {e}"));
+                        }
+                    }
+                    BrowseAction::Fetch => {
+                        if let Err(e) = browser.fetch(&conn) {
+                            browser.set_status(format!("Could not load rows: {e}"));
+                        }
+                    }
                 }
             }
             _ => {}
@@ -936,16 +2765,353 @@ fn register_standalone(cmd: ReportCommands) -> Result<()> {
     result
 }
 
-// ---------------------------------------------------------------------------
-// Helpers
-// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One string per screen row, one char per cell; double-width glyphs
+    /// become `#` so string offsets equal screen columns.
+    fn render_lines(view: &mut dyn ReportView, width: u16, height: u16) -> Vec<String> {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|frame| view.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        let symbol = buffer[(x, y)].symbol();
+                        if crate::tui::display_width(symbol) > 1 {
+                            "#".to_string()
+                        } else {
+                            symbol.to_string()
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect()
+    }
 
-fn truncate(s: &str, max: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max - 1).collect();
-        format!("{truncated}\u{2026}")
+    #[test]
+    fn non_ascii_text_keeps_amount_column_aligned() {
+        let widths = vec![
+            Constraint::Length(6),
+            Constraint::Fill(1),
+            Constraint::Length(12),
+        ];
+        let header = Row::new(["ID", "Description", "Amount"]);
+        let long_cjk = "株式会社東京商事".repeat(5);
+        let rows = vec![
+            Row::new([
+                text_cell("1"),
+                text_cell(truncate(&long_cjk, 50)),
+                money_cell(-1234.5),
+            ]),
+            Row::new([
+                text_cell("2"),
+                text_cell(truncate("Café Crème 🍎", 50)),
+                money_cell(-99.0),
+            ]),
+        ];
+        let mut view = TableReportView::new("Flagged", header, rows, widths);
+        let lines = render_lines(&mut view, 90, 10);
+        let cjk = lines.iter().find(|l| l.contains("1,234.50")).unwrap();
+        let accented = lines.iter().find(|l| l.contains("99.00")).unwrap();
+        assert!(cjk.contains('\u{2026}'), "long text not truncated: {cjk}");
+        assert!(accented.contains("Café Crème"));
+        let column = |line: &str, needle: &str| line[..line.find(needle).unwrap()].chars().count();
+        assert_eq!(column(cjk, "1,234.50"), column(accented, "99.00"));
+    }
+
+    #[test]
+    fn compare_key_toggles_only_when_supported() {
+        let header = Row::new(["Category", "Amount"]);
+        let widths = vec![Constraint::Fill(1), Constraint::Length(14)];
+        let mut view =
+            TableReportView::new("P&L", header.clone(), vec![], widths.clone()).with_compare(false);
+        assert!(!ReportView::compare(&view));
+        assert!(matches!(
+            view.handle_key(KeyCode::Char('c')),
+            ReportViewAction::Reload
+        ));
+        assert!(ReportView::compare(&view));
+
+        let mut plain = TableReportView::new("Tax", header, vec![], widths);
+        assert!(matches!(
+            plain.handle_key(KeyCode::Char('c')),
+            ReportViewAction::Continue
+        ));
+        assert!(!ReportView::compare(&plain));
+    }
+
+    #[test]
+    fn pnl_comparison_table_has_change_columns() {
+        let data = reports::PnlComparison {
+            periods: vec!["Feb 2025".into(), "Jan 2025".into(), "Feb 2024".into()],
+            income: vec![reports::PnlCompareItem {
+                name: "Consulting".into(),
+                totals: vec![1500.0, 1000.0, 0.0],
+            }],
+            expenses: vec![],
+            total_income: vec![1500.0, 1000.0, 0.0],
+            total_expenses: vec![0.0, 0.0, 0.0],
+            net: vec![1500.0, 1000.0, 0.0],
+        };
+        let (header, rows, widths) =
+            pnl_comparison_table(&data, &DrillScope::default(), &mut Vec::new());
+        assert_eq!(widths.len(), 8);
+        let mut view = TableReportView::new("P&L", header, rows, widths);
+        let lines = render_lines(&mut view, 120, 12);
+        let consulting = lines.iter().find(|l| l.contains("Consulting")).unwrap();
+        assert!(consulting.contains("$500.00"), "{consulting}");
+        assert!(consulting.contains("+50.0%"), "{consulting}");
+        assert!(consulting.contains("n/a"), "{consulting}");
+    }
+
+    fn expense_item(path: &[&str], total: f64) -> reports::ExpenseItem {
+        let path: Vec<String> = path.iter().map(|s| s.to_string()).collect();
+        reports::ExpenseItem {
+            name: path.join(reports::CATEGORY_PATH_SEP),
+            path,
+            total,
+            count: 1,
+            pct: 0.0,
+        }
+    }
+
+    #[test]
+    fn expense_drill_down_expands_rolled_up_rows() {
+        let data = reports::ExpenseBreakdown {
+            categories: vec![
+                expense_item(&["Software"], -10.0),
+                expense_item(&["Software", "Design tools"], -30.0),
+                expense_item(&["Travel"], -60.0),
+            ],
+            total: -100.0,
+            top_vendors: Vec::new(),
+        };
+        let table = TableReportView::new(
+            "Expense Breakdown",
+            expense_header(),
+            Vec::new(),
+            expense_widths(),
+        );
+        let mut view = ExpenseDrillView {
+            table,
+            data,
+            scope: software_scope(),
+            depth: 1,
+            expanded: HashSet::new(),
+            lines: Vec::new(),
+            cursor: 0,
+        };
+        view.rebuild();
+        // Travel (−60) sorts before Software (−40 rolled up)
+        assert_eq!(view.lines.len(), 2);
+        assert!(!view.lines[0].expandable);
+        assert_eq!(view.lines[1].item.total, -40.0);
+
+        view.handle_key(KeyCode::Down);
+        view.handle_key(KeyCode::Enter);
+        let labels: Vec<String> = view.lines.iter().map(|l| l.item.name.clone()).collect();
+        assert_eq!(
+            labels,
+            ["Travel", "Software", "Software > Design tools", "Software"]
+        );
+        assert!(view.lines[3].direct);
+        assert_eq!(view.lines[2].level, 1);
+
+        // Design tools has no sub-categories: Enter opens its transactions
+        view.handle_key(KeyCode::Down);
+        let ReportViewAction::Browse(query, desc) = view.handle_key(KeyCode::Enter) else {
+            panic!("expected the register");
+        };
+        assert_eq!((query.category_id, query.subcategories), (Some(4), false));
+        assert_eq!(desc, "category: Software > Design tools, 2025");
+
+        view.handle_key(KeyCode::Up);
+        view.handle_key(KeyCode::Enter);
+        assert_eq!(view.lines.len(), 2);
+    }
+
+    fn software_scope() -> DrillScope {
+        DrillScope {
+            base: reports::RegisterQuery {
+                year: Some(2025),
+                ..Default::default()
+            },
+            period: "2025".into(),
+            ids: HashMap::from([
+                ("Software".to_string(), 3),
+                ("Software > Design tools".to_string(), 4),
+            ]),
+            depth: None,
+        }
+    }
+
+    #[test]
+    fn pnl_lines_drill_down_to_their_transactions() {
+        let items = [
+            reports::PnlItem {
+                name: "Software".into(),
+                total: -10.0,
+            },
+            reports::PnlItem {
+                name: "Software > Design tools".into(),
+                total: -30.0,
+            },
+        ];
+        let (mut rows, mut drills) = (Vec::new(), Vec::new());
+        pnl_group_rows(&mut rows, &mut drills, &software_scope(), &items, |t| t);
+        // Header, (direct), Design tools, subtotal
+        assert_eq!(rows.len(), 4);
+        let targets: Vec<(usize, Option<i64>, bool)> = drills
+            .iter()
+            .map(|(row, d)| (*row, d.query.category_id, d.query.subcategories))
+            .collect();
+        assert_eq!(
+            targets,
+            [(0, Some(3), true), (1, Some(3), false), (2, Some(4), false)]
+        );
+
+        let mut view = TableReportView::new(
+            "P&L",
+            Row::new(["Category", "Amount"]),
+            rows,
+            vec![Constraint::Fill(1), Constraint::Length(14)],
+        )
+        .with_drills(drills);
+        view.handle_key(KeyCode::Down);
+        view.handle_key(KeyCode::Down);
+        let ReportViewAction::Browse(query, _) = view.handle_key(KeyCode::Enter) else {
+            panic!("expected the register");
+        };
+        assert_eq!(query.category_id, Some(4));
+        assert_eq!(query.year, Some(2025));
+
+        // A line rolled up to --depth takes in its sub-categories
+        let scope = DrillScope {
+            depth: Some(1),
+            ..software_scope()
+        };
+        let (_, drill) = scope.line(0, "Software").unwrap();
+        assert!(drill.query.subcategories);
+        assert!(scope.line(0, "Travel").is_none());
+    }
+
+    fn cashflow_month(month: &str, inflows: f64, outflows: f64) -> reports::CashflowMonth {
+        reports::CashflowMonth {
+            month: month.into(),
+            label: month.into(),
+            inflows,
+            outflows,
+            net: inflows + outflows,
+            running_balance: 0.0,
+        }
+    }
+
+    #[test]
+    fn chart_months_fill_gaps_up_to_today() {
+        let data = reports::CashflowReport {
+            granularity: reports::Granularity::Month,
+            months: vec![
+                cashflow_month("2024-08", 500.0, -200.0),
+                cashflow_month("2024-10", 0.0, -50.0),
+            ],
+        };
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 11, 15).unwrap();
+        // FY2025 starting in July runs Jul 2024 - Jun 2025
+        let months = chart_months(FiscalYear::new(7), 2025, &data, today);
+        let names: Vec<u32> = months.iter().map(|m| m.month).collect();
+        assert_eq!(names, vec![7, 8, 9, 10, 11]);
+        assert_eq!(months[1].inflows, 500.0);
+        assert_eq!(months[1].net, 300.0);
+        assert_eq!(months[2].outflows, 0.0);
+        assert_eq!(months[3].outflows, -50.0);
+    }
+
+    fn cashflow_view(month: Option<u32>) -> CashflowView {
+        let data = reports::CashflowReport {
+            granularity: reports::Granularity::Month,
+            months: (1..=12)
+                .map(|m| cashflow_month(&format!("2024-{m:02}"), 1000.0 * m as f64, -800.0))
+                .collect(),
+        };
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let table = TableReportView::new(
+            "Cash Flow",
+            Row::new(["Month"]),
+            vec![Row::new([text_cell("2024-01")])],
+            vec![Constraint::Length(12)],
+        )
+        .with_date(
+            DateGranularity::MonthAndYear,
+            FiscalYear::CALENDAR,
+            2024,
+            month,
+        );
+        CashflowView {
+            table,
+            months: chart_months(FiscalYear::CALENDAR, 2024, &data, today),
+            chart: false,
+            granularity: reports::Granularity::Month,
+        }
+    }
+
+    #[test]
+    fn cashflow_chart_shows_legend_and_month_labels() {
+        let mut view = cashflow_view(None);
+        assert!(matches!(
+            view.handle_key(KeyCode::Char('g')),
+            ReportViewAction::Continue
+        ));
+        assert!(view.chart());
+
+        let lines = render_lines(&mut view, 100, 24);
+        let legend = &lines[2];
+        assert!(legend.contains("Inflows $78,000.00"), "{legend}");
+        assert!(legend.contains("Outflows $9,600.00"), "{legend}");
+        assert!(legend.contains("Net $68,400.00"), "{legend}");
+        // Zero sits on the middle axis label
+        assert!(lines.iter().any(|l| l.trim_start().starts_with("$0")));
+        let labels = &lines[22];
+        let jan = labels.find("Jan").unwrap();
+        let dec = labels.find("Dec").unwrap();
+        assert!(jan < labels.find("Jun").unwrap() && dec > jan, "{labels}");
+        assert!(lines[23].contains("g=table"));
+
+        // Up/Down don't scroll the hidden table; g goes back to it
+        assert!(matches!(
+            view.handle_key(KeyCode::Down),
+            ReportViewAction::Continue
+        ));
+        view.handle_key(KeyCode::Char('g'));
+        assert!(!view.chart());
+        let lines = render_lines(&mut view, 100, 24);
+        assert!(lines.iter().any(|l| l.contains("2024-01")));
+    }
+
+    #[test]
+    fn cashflow_chart_from_month_mode_reloads_the_year() {
+        let mut view = cashflow_view(Some(3));
+        assert!(matches!(
+            view.handle_key(KeyCode::Char('g')),
+            ReportViewAction::Reload
+        ));
+        assert!(view.chart());
+        assert_eq!(view.date_params(), (Some(2024), None));
+    }
+
+    #[test]
+    fn cashflow_w_cycles_granularity_and_quarters_show_the_year() {
+        let mut view = cashflow_view(Some(3));
+        assert!(matches!(
+            view.handle_key(KeyCode::Char('w')),
+            ReportViewAction::Reload
+        ));
+        assert_eq!(view.granularity(), reports::Granularity::Quarter);
+        assert_eq!(view.date_params(), (Some(2024), None));
+        view.handle_key(KeyCode::Char('w'));
+        assert_eq!(view.granularity(), reports::Granularity::Week);
     }
 }
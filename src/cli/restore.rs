@@ -1,11 +1,13 @@
 use std::path::PathBuf;
 
-use crate::db::{get_connection, init_db};
+use crate::db::{get_connection, get_db_password, init_db, open_read_only};
 use crate::error::Result;
 use crate::fmt::format_bytes;
+use crate::merge::MergeReport;
 use crate::settings::{get_data_dir, restrict_file_permissions, shellexpand_path};
 
-pub fn run(path: &str) -> Result<()> {
+/// Check that `path` is a readable Nigel database and return it expanded.
+fn validate_backup(path: &str) -> Result<PathBuf> {
     let backup_path = PathBuf::from(shellexpand_path(path));
 
     // 1. Validate the backup file exists
@@ -51,6 +53,12 @@ pub fn run(path: &str) -> Result<()> {
         ));
     }
 
+    Ok(backup_path)
+}
+
+pub fn run(path: &str) -> Result<()> {
+    let backup_path = validate_backup(path)?;
+
     let data_dir = get_data_dir();
     let db_path = data_dir.join("nigel.db");
 
@@ -66,16 +74,10 @@ pub fn run(path: &str) -> Result<()> {
         }
     }
 
-    // 4. Create a safety backup of the current database
-    let backups_dir = data_dir.join("backups");
-    std::fs::create_dir_all(&backups_dir)?;
-    crate::settings::restrict_dir_permissions(&backups_dir)?;
-    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
-    let safety_path = backups_dir.join(format!("nigel-pre-restore-{stamp}.db"));
-
+    // 4. Create a safety snapshot of the current database
     if db_path.exists() {
         let conn = get_connection(&db_path)?;
-        crate::cli::backup::snapshot(&conn, &safety_path)?;
+        let safety_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "restore")?;
         drop(conn);
         println!("Safety backup saved to {}", safety_path.display());
     }
@@ -106,6 +108,108 @@ pub fn run(path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Rows of each kind listed in the preview before the rest are counted.
+const PREVIEW_ROWS: usize = 10;
+
+/// Merge a backup into the current book: show the rows it has that the book
+/// doesn't (and rows that differ), then restore them on confirmation. Work
+/// added since the backup is kept.
+pub fn merge(path: &str, overwrite_changed: bool, dry_run: bool) -> Result<()> {
+    let backup_path = validate_backup(path)?;
+    let data_dir = get_data_dir();
+    let db_path = data_dir.join("nigel.db");
+    if !db_path.exists() {
+        return Err(crate::error::NigelError::NotInitialized);
+    }
+    let conn = get_connection(&db_path)?;
+    let backup = open_read_only(&backup_path, get_db_password().as_deref())?;
+
+    let preview = crate::merge::merge(&conn, &backup, overwrite_changed, true)?;
+    println!("Compared with {}", backup_path.display());
+    print_preview(&preview, overwrite_changed);
+    let writes = preview.missing()
+        + if overwrite_changed {
+            preview.changed()
+        } else {
+            0
+        };
+    if dry_run {
+        println!("Dry run: nothing was saved.");
+        return Ok(());
+    }
+    if writes == 0 {
+        println!("Nothing to restore.");
+        return Ok(());
+    }
+
+    print!("Restore {writes} rows into the current book? [y/N] ");
+    std::io::Write::flush(&mut std::io::stdout())?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    if !input.trim().eq_ignore_ascii_case("y") {
+        println!("Restore cancelled.");
+        return Ok(());
+    }
+
+    let safety_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "restore")?;
+    println!("Safety backup saved to {}", safety_path.display());
+    let report = crate::merge::merge(&conn, &backup, overwrite_changed, false)?;
+    let mut summary = format!("Restored {} missing rows", report.missing());
+    if overwrite_changed {
+        summary.push_str(&format!(", overwrote {} changed", report.changed()));
+    }
+    println!("{summary}");
+    Ok(())
+}
+
+fn print_preview(report: &MergeReport, overwrite_changed: bool) {
+    if report.tables.is_empty() {
+        println!("The book already has everything in the backup.");
+        return;
+    }
+    for table in &report.tables {
+        let mut counts = vec![format!("{} missing", table.missing.len())];
+        if !table.changed.is_empty() {
+            counts.push(format!("{} changed", table.changed.len()));
+        }
+        if !table.skipped.is_empty() {
+            counts.push(format!("{} can't be restored", table.skipped.len()));
+        }
+        counts.push(format!("{} the same", table.unchanged));
+        println!("{}: {}", table.table, counts.join(", "));
+
+        let changed: Vec<String> = table
+            .changed
+            .iter()
+            .map(|row| {
+                let columns: Vec<&str> = row
+                    .columns
+                    .iter()
+                    .map(|c| c.strip_suffix("_id").unwrap_or(c))
+                    .collect();
+                format!("{} ({})", row.label, columns.join(", "))
+            })
+            .collect();
+        for (marker, lines) in [
+            ("+", &table.missing),
+            ("~", &changed),
+            ("!", &table.skipped),
+        ] {
+            for line in lines.iter().take(PREVIEW_ROWS) {
+                println!("  {marker} {line}");
+            }
+            if lines.len() > PREVIEW_ROWS {
+                println!("    ... and {} more", lines.len() - PREVIEW_ROWS);
+            }
+        }
+    }
+    if report.changed() > 0 && !overwrite_changed {
+        println!(
+            "Changed rows (~) stay as the book has them; --overwrite-changed takes the backup's version."
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Layout},
@@ -7,26 +11,50 @@ use ratatui::{
     Frame,
 };
 
+use crate::categorizer::SIGN_MISMATCH_PREFIX;
+use crate::cli::inbox_zero::{self, InboxZero};
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
 use crate::db::get_connection;
 use crate::error::{NigelError, Result};
 use crate::reviewer::{
-    apply_review, get_categories, get_flagged_transactions, get_transaction_by_id, undo_review,
-    CategoryChoice, FlaggedTxn,
+    apply_review, flagged_count, get_categories, get_flagged_transactions, get_transaction_by_id,
+    group_key, suggest_rule_pattern, undo_review, update_transaction_memo, CategoryChoice,
+    FlaggedTxn, ReviewSummary,
 };
 use crate::settings::get_data_dir;
-use crate::tui::{money_span, FOOTER_STYLE, HEADER_STYLE};
+use crate::tui::{display_width, money_span, pad_right, FOOTER_STYLE, HEADER_STYLE};
 
 enum ReviewState {
     PickCategory,
     InputVendor,
     ConfirmRule,
     InputRulePattern,
+    InputMemo,
+    InputTags,
+    InputClient,
 }
 
 /// Tracks a review decision so it can be undone when navigating back.
-struct ReviewDecision {
-    transaction_id: i64,
-    rule_id: Option<i64>,
+enum ReviewDecision {
+    /// Skipped this many transactions (a whole group in batch mode).
+    Skipped(usize),
+    Applied {
+        transaction_ids: Vec<i64>,
+        rule_id: Option<i64>,
+        /// Category name, for the session summary.
+        category: String,
+    },
+}
+
+impl ReviewDecision {
+    fn len(&self) -> usize {
+        match self {
+            ReviewDecision::Skipped(n) => *n,
+            ReviewDecision::Applied {
+                transaction_ids, ..
+            } => transaction_ids.len(),
+        }
+    }
 }
 
 pub struct TransactionReviewer {
@@ -41,8 +69,26 @@ pub struct TransactionReviewer {
     confirm_value: bool,
     selected_category_idx: Option<usize>,
     vendor: Option<String>,
-    /// Stack of decisions for undo; None = skipped transaction
-    decisions: Vec<Option<ReviewDecision>>,
+    /// Memo typed with `m`; None leaves the stored memo untouched.
+    memo: Option<String>,
+    memo_input: String,
+    /// Tags typed with `t`; None leaves the stored tags untouched.
+    tags: Option<Vec<String>>,
+    tags_input: String,
+    /// Parse error for `tags_input`, shown under the prompt.
+    tags_error: Option<String>,
+    /// Client typed with `c`; empty unassigns, None leaves it untouched.
+    client: Option<String>,
+    client_input: String,
+    /// Stack of decisions for undo
+    decisions: Vec<ReviewDecision>,
+    /// Batch mode: transactions sharing the current one's `group_key()` are
+    /// pulled up behind it and reviewed together.
+    batch: bool,
+    /// Transactions in the current step, starting at `current_txn`.
+    group_len: usize,
+    /// When the session started, for the inbox-zero summary.
+    started: Instant,
 }
 
 impl TransactionReviewer {
@@ -50,10 +96,10 @@ impl TransactionReviewer {
         let labels: Vec<String> = categories
             .iter()
             .map(|c| {
-                let tag = if c.category_type == "income" {
-                    "inc"
-                } else {
-                    "exp"
+                let tag = match c.category_type.as_str() {
+                    "income" => "inc",
+                    "transfer" => "xfer",
+                    _ => "exp",
                 };
                 format!("{} ({})", c.name, tag)
             })
@@ -70,10 +116,50 @@ impl TransactionReviewer {
             confirm_value: false,
             selected_category_idx: None,
             vendor: None,
+            memo: None,
+            memo_input: String::new(),
+            tags: None,
+            tags_input: String::new(),
+            tags_error: None,
+            client: None,
+            client_input: String::new(),
             decisions: Vec::new(),
+            batch: false,
+            group_len: 1,
+            started: Instant::now(),
         }
     }
 
+    /// Start in batch mode (`nigel review --batch`).
+    pub fn with_batch(mut self) -> Self {
+        self.batch = true;
+        self.gather_group();
+        self
+    }
+
+    /// Size the current step. In batch mode the not-yet-reviewed transactions
+    /// matching the current one are moved up behind it, keeping date order
+    /// within each side; reviewed ones are never reordered, so undo still
+    /// lines up.
+    fn gather_group(&mut self) {
+        self.group_len = 1;
+        if !self.batch || self.is_done() {
+            return;
+        }
+        let key = group_key(&self.flagged[self.current_txn].description);
+        let rest = self.flagged.split_off(self.current_txn + 1);
+        let (same, other): (Vec<_>, Vec<_>) = rest
+            .into_iter()
+            .partition(|t| group_key(&t.description) == key);
+        self.group_len += same.len();
+        self.flagged.extend(same);
+        self.flagged.extend(other);
+    }
+
+    fn group(&self) -> &[FlaggedTxn] {
+        &self.flagged[self.current_txn..self.current_txn + self.group_len]
+    }
+
     fn filtered_categories(&self) -> Vec<(usize, &str)> {
         if self.cat_query.is_empty() {
             return vec![];
@@ -98,7 +184,13 @@ impl TransactionReviewer {
         let total = self.flagged.len();
 
         // Compute category chart rows dynamically
-        let col_width = self.labels.iter().map(|e| e.len()).max().unwrap_or(20) + 2;
+        let col_width = self
+            .labels
+            .iter()
+            .map(|e| display_width(e))
+            .max()
+            .unwrap_or(20)
+            + 2;
         let cols = (area.width as usize / col_width).max(1);
         let chart_rows = self.labels.len().div_ceil(cols) as u16 + 1; // +1 for "Categories" header
 
@@ -108,17 +200,19 @@ impl TransactionReviewer {
                 Constraint::Length(1),
                 Constraint::Length(chart_rows),
                 Constraint::Length(1),
-                Constraint::Length(6),
+                Constraint::Length(7),
                 Constraint::Fill(1),
                 Constraint::Length(1),
             ])
             .areas(area);
 
         // Header
-        frame.render_widget(
-            Paragraph::new(" Review Transactions").style(HEADER_STYLE),
-            header_area,
-        );
+        let title = if self.batch {
+            " Review Transactions (batch)"
+        } else {
+            " Review Transactions"
+        };
+        frame.render_widget(Paragraph::new(title).style(HEADER_STYLE), header_area);
 
         // Separator
         frame.render_widget(
@@ -138,7 +232,7 @@ impl TransactionReviewer {
                 let idx = col * rows + row;
                 if let Some(entry) = self.labels.get(idx) {
                     spans.push(Span::styled(
-                        format!("{:<width$}", entry, width = col_width),
+                        pad_right(entry, col_width),
                         Style::default().fg(Color::DarkGray),
                     ));
                 }
@@ -148,24 +242,55 @@ impl TransactionReviewer {
         frame.render_widget(Paragraph::new(chart_lines), chart_area);
 
         // Progress bar
-        let ratio = (self.current_txn + 1) as f64 / total as f64;
+        let last = self.current_txn + self.group_len;
+        let ratio = last as f64 / total as f64;
+        let label = if self.group_len > 1 {
+            format!("{}-{}/{}", self.current_txn + 1, last, total)
+        } else {
+            format!("{last}/{total}")
+        };
         let gauge = LineGauge::default()
-            .label(format!("{}/{}", self.current_txn + 1, total))
+            .label(label)
             .ratio(ratio)
             .filled_style(Style::default().fg(crate::tui::GREEN).bold())
             .unfilled_style(Style::default().fg(Color::Rgb(60, 60, 60)))
             .line_set(ratatui::symbols::line::DOUBLE);
         frame.render_widget(gauge, progress_area);
 
-        // Transaction details
-        let detail_lines = vec![
-            Line::from(""),
-            Line::from(format!("  Date:        {}", txn.date)),
-            Line::from(format!("  Description: {}", txn.description)),
-            Line::from(vec![Span::raw("  Amount:      "), money_span(txn.amount)]),
-            Line::from(format!("  Account:     {}", txn.account_name)),
-            Line::from(""),
-        ];
+        // Transaction details, or a summary of the group in batch mode
+        let detail_lines = if self.group_len > 1 {
+            self.group_lines()
+        } else {
+            vec![
+                // Why a categorized transaction is back in review
+                match txn.flag_reason.as_deref() {
+                    Some(reason) if reason.starts_with(SIGN_MISMATCH_PREFIX) => Line::from(
+                        Span::styled(format!("  {reason}"), Style::default().fg(Color::Yellow)),
+                    ),
+                    _ => Line::from(""),
+                },
+                Line::from(format!("  Date:        {}", txn.date)),
+                Line::from(format!("  Description: {}", txn.description)),
+                Line::from(vec![Span::raw("  Amount:      "), money_span(txn.amount)]),
+                Line::from(format!("  Account:     {}", txn.account_name)),
+                match self.memo.as_deref().or(txn.memo.as_deref()) {
+                    Some(memo) if !memo.trim().is_empty() => {
+                        Line::from(format!("  Memo:        {memo}"))
+                    }
+                    _ => Line::from(""),
+                },
+                match self.tags.as_deref().unwrap_or(&txn.tags) {
+                    [] => Line::from(""),
+                    tags => Line::from(format!("  Tags:        {}", crate::tags::label(tags))),
+                },
+                match self.client.as_deref().or(txn.client.as_deref()) {
+                    Some(client) if !client.trim().is_empty() => {
+                        Line::from(format!("  Client:      {}", client.trim()))
+                    }
+                    _ => Line::from(""),
+                },
+            ]
+        };
         frame.render_widget(Paragraph::new(detail_lines), detail_area);
 
         // Interaction area — changes per state
@@ -207,17 +332,48 @@ impl TransactionReviewer {
                         Style::default().fg(Color::Black).bg(Color::Gray),
                     )
                 };
-                vec![Line::from(vec![
-                    Span::raw("  Create rule for future matches?  "),
-                    Span::styled(" Yes ", yes_style),
-                    Span::raw("  "),
-                    Span::styled(" No ", no_style),
-                ])]
+                vec![
+                    Line::from(vec![
+                        Span::raw("  Create a rule from this?  "),
+                        Span::styled(" Yes ", yes_style),
+                        Span::raw("  "),
+                        Span::styled(" No ", no_style),
+                    ]),
+                    Line::from(Span::styled(
+                        self.rule_target(),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ]
             }
             ReviewState::InputRulePattern => {
+                vec![
+                    Line::from(format!(
+                        "  Rule pattern (contains): {}\u{2588}",
+                        self.text_input
+                    )),
+                    Line::from(Span::styled(
+                        self.rule_target(),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ]
+            }
+            ReviewState::InputMemo => {
+                vec![Line::from(format!("  Memo: {}\u{2588}", self.memo_input))]
+            }
+            ReviewState::InputTags => {
+                let mut lines = vec![Line::from(format!("  Tags: {}\u{2588}", self.tags_input))];
+                if let Some(e) = &self.tags_error {
+                    lines.push(Line::from(Span::styled(
+                        format!("    {e}"),
+                        Style::default().fg(Color::Red),
+                    )));
+                }
+                lines
+            }
+            ReviewState::InputClient => {
                 vec![Line::from(format!(
-                    "  Rule pattern: {}\u{2588}",
-                    self.text_input
+                    "  Client: {}\u{2588}",
+                    self.client_input
                 ))]
             }
         };
@@ -225,22 +381,35 @@ impl TransactionReviewer {
 
         // Hints — vary by state and whether back is available
         let hints = match &self.state {
-            ReviewState::PickCategory => {
-                if self.allow_back() {
-                    "Type to filter, Enter=select, Tab=skip, Esc=back, Ctrl+C=quit"
-                } else {
-                    "Type to filter, Enter=select, Tab=skip, Ctrl+C=quit"
+            ReviewState::PickCategory => match (self.batch, self.allow_back()) {
+                (false, true) => "Type to filter, Enter=select, Tab=skip, *=batch, Esc=back, Ctrl+C=quit",
+                (false, false) => "Type to filter, Enter=select, Tab=skip, *=batch, Ctrl+C=quit",
+                (true, true) => {
+                    "Type to filter, Enter=select, Tab=skip group, *=one at a time, Esc=back, Ctrl+C=quit"
                 }
-            }
+                (true, false) => {
+                    "Type to filter, Enter=select, Tab=skip group, *=one at a time, Ctrl+C=quit"
+                }
+            },
             ReviewState::InputVendor => {
                 "Enter=confirm (empty to skip), Esc=back to category, Ctrl+C=quit"
             }
+            ReviewState::ConfirmRule if self.group_len > 1 => {
+                "y/n or Left/Right to toggle, Enter=confirm for the group, R=rule, c=client, Esc=back to category, Ctrl+C=quit"
+            }
             ReviewState::ConfirmRule => {
-                "y/n or Left/Right to toggle, Enter=confirm, Esc=back to category, Ctrl+C=quit"
+                "y/n or Left/Right to toggle, Enter=confirm, R=rule, m=memo, t=tags, c=client, Esc=back to category, Ctrl+C=quit"
             }
             ReviewState::InputRulePattern => {
                 "Enter=confirm (non-empty required), Esc=back to category, Ctrl+C=quit"
             }
+            ReviewState::InputMemo => "Enter=save (empty to clear), Esc=cancel, Ctrl+C=quit",
+            ReviewState::InputTags => {
+                "Space-separated, e.g. #client-acme travel  Enter=save (empty to clear), Esc=cancel, Ctrl+C=quit"
+            }
+            ReviewState::InputClient => {
+                "New names are added as clients  Enter=save (empty to unassign), Esc=cancel, Ctrl+C=quit"
+            }
         };
         frame.render_widget(
             Paragraph::new(hints).style(Style::default().fg(Color::DarkGray)),
@@ -251,6 +420,12 @@ impl TransactionReviewer {
     pub fn handle_key(&mut self, code: KeyCode) -> HandleResult {
         match &self.state {
             ReviewState::PickCategory => match code {
+                // `*` on an empty filter toggles batch mode
+                KeyCode::Char('*') if self.cat_query.is_empty() => {
+                    self.batch = !self.batch;
+                    self.gather_group();
+                    HandleResult::Continue
+                }
                 KeyCode::Char(c) => {
                     self.cat_query.push(c);
                     self.cat_selection = 0;
@@ -284,7 +459,7 @@ impl TransactionReviewer {
                 }
                 // Tab = skip (advance without categorizing)
                 KeyCode::Tab => {
-                    self.decisions.push(None);
+                    self.decisions.push(ReviewDecision::Skipped(self.group_len));
                     self.advance();
                     HandleResult::check_done(self)
                 }
@@ -333,17 +508,47 @@ impl TransactionReviewer {
                     self.confirm_value = !self.confirm_value;
                     HandleResult::Continue
                 }
+                // R = straight to the pattern, as in the register browser
+                KeyCode::Char('r') | KeyCode::Char('R') => {
+                    self.confirm_value = true;
+                    self.start_rule_pattern();
+                    HandleResult::Continue
+                }
+                // Memos and tags are per transaction, so not offered for a group
+                KeyCode::Char('m' | 'M' | 't' | 'T') if self.group_len > 1 => {
+                    HandleResult::Continue
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    let txn = &self.flagged[self.current_txn];
+                    self.memo_input = self
+                        .memo
+                        .clone()
+                        .or_else(|| txn.memo.clone())
+                        .unwrap_or_default();
+                    self.state = ReviewState::InputMemo;
+                    HandleResult::Continue
+                }
+                KeyCode::Char('t') | KeyCode::Char('T') => {
+                    let txn = &self.flagged[self.current_txn];
+                    self.tags_input = crate::tags::label(self.tags.as_deref().unwrap_or(&txn.tags));
+                    self.tags_error = None;
+                    self.state = ReviewState::InputTags;
+                    HandleResult::Continue
+                }
+                // The client applies to the whole group
+                KeyCode::Char('c') | KeyCode::Char('C') => {
+                    let txn = &self.flagged[self.current_txn];
+                    self.client_input = self
+                        .client
+                        .clone()
+                        .or_else(|| txn.client.clone())
+                        .unwrap_or_default();
+                    self.state = ReviewState::InputClient;
+                    HandleResult::Continue
+                }
                 KeyCode::Enter => {
                     if self.confirm_value {
-                        // Prefill rule pattern with first 2 words
-                        let txn = &self.flagged[self.current_txn];
-                        let words: Vec<&str> = txn.description.split_whitespace().collect();
-                        self.text_input = if words.len() >= 2 {
-                            format!("{} {}", words[0], words[1])
-                        } else {
-                            words.first().unwrap_or(&"").to_string()
-                        };
-                        self.state = ReviewState::InputRulePattern;
+                        self.start_rule_pattern();
                     } else {
                         return HandleResult::CommitAndAdvance;
                     }
@@ -377,11 +582,84 @@ impl TransactionReviewer {
                 }
                 _ => HandleResult::Continue,
             },
+            ReviewState::InputMemo => match code {
+                KeyCode::Char(c) => {
+                    self.memo_input.push(c);
+                    HandleResult::Continue
+                }
+                KeyCode::Backspace => {
+                    self.memo_input.pop();
+                    HandleResult::Continue
+                }
+                KeyCode::Enter => {
+                    self.memo = Some(std::mem::take(&mut self.memo_input));
+                    self.state = ReviewState::ConfirmRule;
+                    HandleResult::Continue
+                }
+                // Esc = discard this edit, back to the rule prompt
+                KeyCode::Esc => {
+                    self.memo_input.clear();
+                    self.state = ReviewState::ConfirmRule;
+                    HandleResult::Continue
+                }
+                _ => HandleResult::Continue,
+            },
+            ReviewState::InputTags => match code {
+                KeyCode::Char(c) => {
+                    self.tags_input.push(c);
+                    HandleResult::Continue
+                }
+                KeyCode::Backspace => {
+                    self.tags_input.pop();
+                    HandleResult::Continue
+                }
+                KeyCode::Enter => {
+                    match crate::tags::parse_tags(&self.tags_input) {
+                        Ok(tags) => {
+                            self.tags = Some(tags);
+                            self.tags_input.clear();
+                            self.tags_error = None;
+                            self.state = ReviewState::ConfirmRule;
+                        }
+                        Err(e) => self.tags_error = Some(e.to_string()),
+                    }
+                    HandleResult::Continue
+                }
+                KeyCode::Esc => {
+                    self.tags_input.clear();
+                    self.tags_error = None;
+                    self.state = ReviewState::ConfirmRule;
+                    HandleResult::Continue
+                }
+                _ => HandleResult::Continue,
+            },
+            ReviewState::InputClient => match code {
+                KeyCode::Char(c) => {
+                    self.client_input.push(c);
+                    HandleResult::Continue
+                }
+                KeyCode::Backspace => {
+                    self.client_input.pop();
+                    HandleResult::Continue
+                }
+                KeyCode::Enter => {
+                    self.client = Some(std::mem::take(&mut self.client_input));
+                    self.state = ReviewState::ConfirmRule;
+                    HandleResult::Continue
+                }
+                KeyCode::Esc => {
+                    self.client_input.clear();
+                    self.state = ReviewState::ConfirmRule;
+                    HandleResult::Continue
+                }
+                _ => HandleResult::Continue,
+            },
         }
     }
 
     pub fn commit_review(&mut self, conn: &rusqlite::Connection) -> Result<()> {
-        let txn = &self.flagged[self.current_txn];
+        let txn_id = self.flagged[self.current_txn].id;
+        let transaction_ids: Vec<i64> = self.group().iter().map(|t| t.id).collect();
         let cat_idx = self
             .selected_category_idx
             .ok_or_else(|| NigelError::Other("commit_review called without category".into()))?;
@@ -396,32 +674,75 @@ impl TransactionReviewer {
 
         let rule_id = apply_review(
             conn,
-            txn.id,
+            &transaction_ids,
             cat.id,
             self.vendor.as_deref(),
             create_rule,
             rule_pattern,
         )?;
+        if let Some(memo) = self.memo.take() {
+            update_transaction_memo(conn, txn_id, Some(&memo))?;
+            let memo = memo.trim();
+            self.flagged[self.current_txn].memo = (!memo.is_empty()).then(|| memo.to_string());
+        }
+        if let Some(tags) = self.tags.take() {
+            crate::tags::set_transaction_tags(conn, txn_id, &tags)?;
+            self.flagged[self.current_txn].tags = tags;
+        }
+        if let Some(client) = self.client.take() {
+            let client = crate::clients::assign_input(conn, &transaction_ids, &client)?;
+            for txn in &mut self.flagged[self.current_txn..self.current_txn + self.group_len] {
+                txn.client = client.clone();
+            }
+        }
 
-        self.decisions.push(Some(ReviewDecision {
-            transaction_id: txn.id,
+        self.decisions.push(ReviewDecision::Applied {
+            transaction_ids,
             rule_id,
-        }));
+            category: cat.name.clone(),
+        });
 
         self.advance();
         Ok(())
     }
 
     pub fn undo_previous(&mut self, conn: &rusqlite::Connection) -> Result<()> {
-        self.current_txn -= 1;
-        if let Some(Some(decision)) = self.decisions.pop() {
-            undo_review(conn, decision.transaction_id, decision.rule_id)?;
+        let Some(decision) = self.decisions.pop() else {
+            return Ok(());
+        };
+        self.current_txn -= decision.len();
+        // A skipped step has nothing to undo in the database
+        if let ReviewDecision::Applied {
+            transaction_ids,
+            rule_id,
+            ..
+        } = decision
+        {
+            undo_review(conn, &transaction_ids, rule_id)?;
         }
-        // If pop() returned Some(None), it was a skipped transaction — already popped, nothing to undo.
         self.reset_to_pick_category();
+        self.gather_group();
         Ok(())
     }
 
+    /// Prefill the rule pattern from the description and ask for it.
+    fn start_rule_pattern(&mut self) {
+        let txn = &self.flagged[self.current_txn];
+        self.text_input = suggest_rule_pattern(&txn.description);
+        self.state = ReviewState::InputRulePattern;
+    }
+
+    /// What a rule made now would do: `→ Category (vendor: X)`.
+    fn rule_target(&self) -> String {
+        let category = self
+            .selected_category_idx
+            .map_or("", |i| self.categories[i].name.as_str());
+        match self.vendor.as_deref() {
+            Some(v) => format!("    \u{2192} {category} (vendor: {v})"),
+            None => format!("    \u{2192} {category}"),
+        }
+    }
+
     /// Reset interaction state back to category picker for the current transaction.
     fn reset_to_pick_category(&mut self) {
         self.state = ReviewState::PickCategory;
@@ -431,16 +752,98 @@ impl TransactionReviewer {
         self.confirm_value = false;
         self.selected_category_idx = None;
         self.vendor = None;
+        self.memo = None;
+        self.memo_input.clear();
+        self.tags = None;
+        self.tags_input.clear();
+        self.tags_error = None;
+        self.client = None;
+        self.client_input.clear();
     }
 
     fn advance(&mut self) {
-        self.current_txn += 1;
+        self.current_txn += self.group_len;
         self.reset_to_pick_category();
+        self.gather_group();
+    }
+
+    /// Detail lines for a batch group: what matched, when, how much, and a
+    /// couple of the descriptions.
+    fn group_lines(&self) -> Vec<Line<'static>> {
+        let group = self.group();
+        let first = &group[0];
+        let last = &group[group.len() - 1];
+        let total: f64 = group.iter().map(|t| t.amount).sum();
+        let mut accounts: Vec<&str> = group.iter().map(|t| t.account_name.as_str()).collect();
+        accounts.sort();
+        accounts.dedup();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!(
+                "  Group:       {} ({} transactions)",
+                group_key(&first.description),
+                group.len()
+            )),
+            Line::from(format!("  Dates:       {} to {}", first.date, last.date)),
+            Line::from(vec![Span::raw("  Total:       "), money_span(total)]),
+            Line::from(format!("  Account:     {}", accounts.join(", "))),
+        ];
+        for (i, txn) in group.iter().take(2).enumerate() {
+            let label = if i == 0 {
+                "  Examples:    "
+            } else {
+                "               "
+            };
+            lines.push(Line::from(format!("{label}{}", txn.description)));
+        }
+        lines
     }
 
     pub fn is_done(&self) -> bool {
         self.current_txn >= self.flagged.len()
     }
+
+    /// What the decisions still standing add up to.
+    pub fn summary(&self) -> ReviewSummary {
+        let mut summary = ReviewSummary {
+            elapsed: self.started.elapsed(),
+            ..Default::default()
+        };
+        let mut categories: HashMap<&str, usize> = HashMap::new();
+        for decision in &self.decisions {
+            match decision {
+                ReviewDecision::Skipped(n) => summary.skipped += n,
+                ReviewDecision::Applied {
+                    transaction_ids,
+                    rule_id,
+                    category,
+                } => {
+                    summary.reviewed += transaction_ids.len();
+                    summary.rules_created += usize::from(rule_id.is_some());
+                    *categories.entry(category).or_default() += transaction_ids.len();
+                }
+            }
+        }
+        summary.categories = categories
+            .into_iter()
+            .map(|(name, n)| (name.to_string(), n))
+            .collect();
+        summary
+            .categories
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        summary
+    }
+
+    /// Once the last flagged transaction is reviewed, record the session in
+    /// the audit log and return its summary to celebrate.
+    fn finish(&self, conn: &rusqlite::Connection) -> Result<Option<ReviewSummary>> {
+        let summary = self.summary();
+        if !self.is_done() || summary.reviewed == 0 || flagged_count(conn)? > 0 {
+            return Ok(None);
+        }
+        summary.record(conn)?;
+        Ok(Some(summary))
+    }
 }
 
 pub enum HandleResult {
@@ -460,7 +863,7 @@ impl HandleResult {
     }
 }
 
-pub fn run(id: Option<i64>) -> Result<()> {
+pub fn run(id: Option<i64>, batch: bool) -> Result<()> {
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
     let flagged = if let Some(txn_id) = id {
         vec![get_transaction_by_id(&conn, txn_id)?]
@@ -477,6 +880,9 @@ pub fn run(id: Option<i64>) -> Result<()> {
     let total = flagged.len();
 
     let mut reviewer = TransactionReviewer::new(flagged, categories);
+    if batch {
+        reviewer = reviewer.with_batch();
+    }
     let mut terminal = ratatui::init();
     let mut interrupted = false;
 
@@ -503,7 +909,11 @@ pub fn run(id: Option<i64>) -> Result<()> {
                             break Err(e);
                         }
                         if reviewer.is_done() {
-                            break Ok(());
+                            break match reviewer.finish(&conn) {
+                                Ok(Some(summary)) => inbox_zero::show(&mut terminal, summary),
+                                Ok(None) => Ok(()),
+                                Err(e) => Err(e),
+                            };
                         }
                     }
                     HandleResult::UndoPrevious => {
@@ -536,3 +946,223 @@ pub fn run(id: Option<i64>) -> Result<()> {
     }
     result
 }
+
+/// `nigel review export`: flagged transactions out to a CSV to fill in.
+pub fn export(path: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    if flagged_count(&conn)? == 0 {
+        println!("No flagged transactions to review.");
+        return Ok(());
+    }
+    let count = crate::review_csv::export(&conn, Path::new(path))?;
+    println!("Exported {count} flagged transactions to {path}");
+    println!("Fill in the category column (vendor and memo are optional), then run:");
+    println!("  nigel review import {path}");
+    Ok(())
+}
+
+/// `nigel review import`: apply the filled-in CSV, reporting rows by number.
+pub fn import(path: &str, dry_run: bool) -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    if !dry_run {
+        let snap_path =
+            crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "review-import")?;
+        println!("Pre-import snapshot saved to {}", snap_path.display());
+    }
+    let report = crate::review_csv::import(&conn, Path::new(path), dry_run)?;
+    if dry_run {
+        println!("Dry run: nothing was saved.");
+    }
+    println!(
+        "Reviewed {} transactions; {} left blank, {} already reviewed",
+        report.applied, report.blank, report.already_reviewed
+    );
+    if !report.errors.is_empty() {
+        println!("Not applied (still flagged):");
+        for error in &report.errors {
+            println!("  {error}");
+        }
+    }
+    Ok(())
+}
+
+impl screen::Screen for TransactionReviewer {
+    fn draw(&mut self, frame: &mut Frame) {
+        TransactionReviewer::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        match TransactionReviewer::handle_key(self, code) {
+            HandleResult::Continue => {}
+            HandleResult::CommitAndAdvance => {
+                self.commit_review(ctx.conn)?;
+                ctx.publish(DataChange::Transactions);
+                if self.is_done() {
+                    return Ok(match self.finish(ctx.conn)? {
+                        Some(summary) => Nav::Replace(Box::new(InboxZero::new(summary))),
+                        None => Nav::Pop,
+                    });
+                }
+            }
+            HandleResult::UndoPrevious => {
+                self.undo_previous(ctx.conn)?;
+                ctx.publish(DataChange::Transactions);
+            }
+            HandleResult::Done => return Ok(Nav::Pop),
+        }
+        Ok(Nav::Stay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn txn(id: i64, date: &str, description: &str) -> FlaggedTxn {
+        FlaggedTxn {
+            id,
+            date: date.into(),
+            description: description.into(),
+            amount: -10.0,
+            account_name: "Checking".into(),
+            memo: None,
+            tags: vec![],
+            flag_reason: None,
+            client: None,
+        }
+    }
+
+    #[test]
+    fn test_batch_mode_groups_and_steps_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+
+        let flagged = vec![
+            txn(1, "2025-01-02", "SQ *BLUE BOTTLE 0412"),
+            txn(2, "2025-01-05", "SHELL OIL 5521"),
+            txn(3, "2025-01-09", "SQ *BLUE BOTTLE 0519"),
+            txn(4, "2025-01-12", "SHELL OIL 5530"),
+        ];
+        let mut reviewer = TransactionReviewer::new(flagged, get_categories(&conn).unwrap());
+        reviewer.handle_key(KeyCode::Char('*'));
+        assert!(reviewer.batch);
+        let ids: Vec<i64> = reviewer.group().iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+
+        // Tab skips the whole group
+        reviewer.handle_key(KeyCode::Tab);
+        assert_eq!(reviewer.current_txn, 2);
+        let ids: Vec<i64> = reviewer.group().iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![2, 4]);
+
+        reviewer.undo_previous(&conn).unwrap();
+        assert_eq!(reviewer.current_txn, 0);
+        assert_eq!(reviewer.group_len, 2);
+
+        // Back to one at a time
+        reviewer.handle_key(KeyCode::Char('*'));
+        assert_eq!(reviewer.group_len, 1);
+    }
+
+    #[test]
+    fn test_client_applies_to_the_whole_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        for (date, description) in [
+            ("2025-01-02", "ACME PAYMENT 1"),
+            ("2025-02-02", "ACME PAYMENT 2"),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount, is_flagged) \
+                 VALUES (1, ?1, ?2, 500.0, 1)",
+                [date, description],
+            )
+            .unwrap();
+        }
+
+        let flagged = get_flagged_transactions(&conn).unwrap();
+        let mut reviewer =
+            TransactionReviewer::new(flagged, get_categories(&conn).unwrap()).with_batch();
+        assert_eq!(reviewer.group_len, 2);
+        for c in "client services".chars() {
+            reviewer.handle_key(KeyCode::Char(c));
+        }
+        reviewer.handle_key(KeyCode::Enter);
+        reviewer.handle_key(KeyCode::Enter);
+        reviewer.handle_key(KeyCode::Char('c'));
+        for c in "Acme Corp".chars() {
+            reviewer.handle_key(KeyCode::Char(c));
+        }
+        reviewer.handle_key(KeyCode::Enter);
+        assert!(matches!(
+            reviewer.handle_key(KeyCode::Enter),
+            HandleResult::CommitAndAdvance
+        ));
+        reviewer.commit_review(&conn).unwrap();
+
+        for id in [1, 2] {
+            let txn = crate::reviewer::get_transaction_by_id(&conn, id).unwrap();
+            assert_eq!(txn.client.as_deref(), Some("Acme Corp"));
+        }
+    }
+
+    #[test]
+    fn test_clearing_the_last_flag_records_the_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        for description in ["ADOBE CREATIVE", "GITHUB INC"] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount, is_flagged) \
+                 VALUES (1, '2025-01-02', ?1, -20.0, 1)",
+                [description],
+            )
+            .unwrap();
+        }
+
+        let flagged = get_flagged_transactions(&conn).unwrap();
+        let mut reviewer = TransactionReviewer::new(flagged, get_categories(&conn).unwrap());
+        let review = |reviewer: &mut TransactionReviewer| {
+            for c in "software".chars() {
+                reviewer.handle_key(KeyCode::Char(c));
+            }
+            reviewer.handle_key(KeyCode::Enter);
+            reviewer.handle_key(KeyCode::Enter);
+            reviewer.handle_key(KeyCode::Enter);
+            reviewer.commit_review(&conn).unwrap();
+        };
+        review(&mut reviewer);
+        assert!(reviewer.finish(&conn).unwrap().is_none());
+        review(&mut reviewer);
+        assert!(reviewer.is_done());
+
+        let summary = reviewer.finish(&conn).unwrap().unwrap();
+        assert_eq!(summary.reviewed, 2);
+        assert_eq!(
+            summary.categories,
+            vec![("Software & Subscriptions".to_string(), 2)]
+        );
+        let detail: String = conn
+            .query_row(
+                "SELECT detail FROM audit_log WHERE action = 'review.session'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert!(detail.starts_with("Reviewed 2 transactions into 1 category"));
+    }
+}
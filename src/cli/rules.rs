@@ -2,12 +2,30 @@ use std::collections::HashMap;
 
 use comfy_table::{Cell, Table};
 use regex::Regex;
+use rusqlite::Connection;
 
-use crate::categorizer::matches as rule_matches;
+use crate::categorizer::{find_duplicate_rule, Matcher};
 use crate::db::get_connection;
 use crate::error::{NigelError, Result};
 use crate::settings::get_data_dir;
 
+/// Match types a rule may use.
+pub const MATCH_TYPES: &[&str] = &["contains", "starts_with", "regex"];
+
+/// Check a rule's match type, and that a regex pattern compiles.
+pub fn validate_pattern(pattern: &str, match_type: &str) -> Result<()> {
+    if !MATCH_TYPES.contains(&match_type) {
+        return Err(NigelError::Other(format!(
+            "Invalid match type: {match_type}. Must be one of: {}",
+            MATCH_TYPES.join(", ")
+        )));
+    }
+    if match_type == "regex" {
+        Regex::new(pattern).map_err(|e| NigelError::Other(format!("Invalid regex: {e}")))?;
+    }
+    Ok(())
+}
+
 pub fn add(
     pattern: &str,
     category: &str,
@@ -25,11 +43,58 @@ pub fn add(
         )
         .map_err(|_| NigelError::UnknownCategory(category.to_string()))?;
 
+    if let Some(dup) = find_duplicate_rule(&conn, pattern, cat_id, None)? {
+        return Err(NigelError::Other(format!(
+            "Rule {id} already files '{}' under {} ({}). Edit it instead with `nigel rules update {id}`.",
+            dup.pattern,
+            dup.category,
+            dup.match_type,
+            id = dup.id,
+        )));
+    }
+
+    add_rule(&conn, pattern, match_type, vendor, cat_id, priority)?;
+    println!("Added rule: '{pattern}' \u{2192} {category}");
+    Ok(())
+}
+
+/// Insert an active rule after validating its pattern. Duplicate checks are
+/// the caller's job (see `categorizer::find_duplicate_rule`).
+pub fn add_rule(
+    conn: &Connection,
+    pattern: &str,
+    match_type: &str,
+    vendor: Option<&str>,
+    category_id: i64,
+    priority: i64,
+) -> Result<i64> {
+    validate_pattern(pattern, match_type)?;
     conn.execute(
         "INSERT INTO rules (pattern, match_type, vendor, category_id, priority) VALUES (?1, ?2, ?3, ?4, ?5)",
-        rusqlite::params![pattern, match_type, vendor, cat_id, priority],
+        rusqlite::params![pattern, match_type, vendor, category_id, priority],
     )?;
-    println!("Added rule: '{pattern}' \u{2192} {category}");
+    Ok(conn.last_insert_rowid())
+}
+
+/// Replace every editable field of rule `id`.
+pub fn update_rule(
+    conn: &Connection,
+    id: i64,
+    pattern: &str,
+    match_type: &str,
+    vendor: Option<&str>,
+    category_id: i64,
+    priority: i64,
+) -> Result<()> {
+    validate_pattern(pattern, match_type)?;
+    let changed = conn.execute(
+        "UPDATE rules SET pattern = ?1, match_type = ?2, vendor = ?3, category_id = ?4, priority = ?5 \
+         WHERE id = ?6 AND is_active = 1",
+        rusqlite::params![pattern, match_type, vendor, category_id, priority, id],
+    )?;
+    if changed == 0 {
+        return Err(NigelError::Other(format!("No active rule with ID {id}")));
+    }
     Ok(())
 }
 
@@ -177,27 +242,18 @@ pub fn delete(id: i64) -> Result<()> {
 }
 
 pub fn test(pattern: &str, match_type: &str) -> Result<()> {
-    let valid_types = ["contains", "starts_with", "regex"];
-    if !valid_types.contains(&match_type) {
-        return Err(NigelError::Other(format!(
-            "Invalid match type: {match_type}. Must be one of: {}",
-            valid_types.join(", ")
-        )));
-    }
-
-    if match_type == "regex" {
-        Regex::new(pattern).map_err(|e| NigelError::Other(format!("Invalid regex: {e}")))?;
-    }
+    validate_pattern(pattern, match_type)?;
 
     let conn = get_connection(&get_data_dir().join("nigel.db"))?;
-    let mut stmt = conn.prepare("SELECT description FROM transactions")?;
+    let mut stmt = conn.prepare("SELECT description FROM transactions WHERE deleted_at IS NULL")?;
     let descriptions: Vec<String> = stmt
         .query_map([], |row| row.get(0))?
         .collect::<std::result::Result<Vec<_>, _>>()?;
 
+    let matcher = Matcher::new(pattern, match_type);
     let mut match_counts: HashMap<String, usize> = HashMap::new();
     for desc in &descriptions {
-        if rule_matches(desc, pattern, match_type) {
+        if matcher.is_match(desc) {
             *match_counts.entry(desc.clone()).or_default() += 1;
         }
     }
@@ -0,0 +1,403 @@
+use std::collections::HashSet;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{LineGauge, Paragraph},
+    Frame,
+};
+use rusqlite::Connection;
+
+use crate::db::get_connection;
+use crate::error::Result;
+use crate::rule_audit::{self, CleanupAction, RuleIssue, RuleProblem};
+use crate::settings::get_data_dir;
+use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
+
+pub enum CleanupScreenAction {
+    Continue,
+    Close,
+}
+
+/// Walks through `rule_audit::audit_rules()` one rule at a time. After every
+/// change the audit is re-run, since deleting or reprioritizing one rule can
+/// fix (or expose) problems in others; rules already seen are not shown again.
+pub struct RulesCleanup {
+    greeting: String,
+    data_dir: PathBuf,
+    /// Remaining issues; the first is the one on screen.
+    issues: Vec<RuleIssue>,
+    /// Rule IDs already shown (acted on or skipped).
+    seen: HashSet<i64>,
+    snapshot_taken: bool,
+    deleted: usize,
+    reprioritized: usize,
+    skipped: usize,
+    status_message: Option<String>,
+}
+
+impl RulesCleanup {
+    pub fn new(conn: &Connection, greeting: &str, data_dir: PathBuf) -> Result<Self> {
+        Ok(Self {
+            greeting: greeting.to_string(),
+            data_dir,
+            issues: rule_audit::audit_rules(conn)?,
+            seen: HashSet::new(),
+            snapshot_taken: false,
+            deleted: 0,
+            reprioritized: 0,
+            skipped: 0,
+            status_message: None,
+        })
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// "Deleted 2, reprioritized 1, skipped 3" — or None if nothing was shown.
+    pub fn summary(&self) -> Option<String> {
+        if self.seen.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Deleted {}, reprioritized {}, skipped {}",
+            self.deleted, self.reprioritized, self.skipped
+        ))
+    }
+
+    fn current(&self) -> Option<&RuleIssue> {
+        self.issues.first()
+    }
+
+    /// Raising priority only helps a rule that something else shadows.
+    fn priority_action(issue: &RuleIssue) -> Option<CleanupAction> {
+        match issue.problem {
+            RuleProblem::Superseded { by_priority, .. } => {
+                Some(CleanupAction::SetPriority(by_priority + 1))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let [header_area, sep_area, progress_area, content_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
+            header_area,
+        );
+        frame.render_widget(
+            Paragraph::new("\u{2501}".repeat(area.width as usize)).style(FOOTER_STYLE),
+            sep_area,
+        );
+
+        let Some(issue) = self.current() else {
+            let mut lines = vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    " Clean Up Rules",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+            ];
+            match self.summary() {
+                Some(summary) => lines.push(Line::from(Span::styled(
+                    format!("   All done. {summary}."),
+                    Style::default().fg(Color::Green),
+                ))),
+                None => lines.push(Line::from("   No problem rules found.")),
+            }
+            frame.render_widget(Paragraph::new(lines), content_area);
+            frame.render_widget(Paragraph::new(" Esc=back").style(FOOTER_STYLE), hints_area);
+            return;
+        };
+
+        // Progress across rules already seen plus those left
+        let total = self.seen.len() + self.issues.len();
+        let position = self.seen.len() + 1;
+        let gauge = LineGauge::default()
+            .label(format!("{position}/{total}"))
+            .ratio(position as f64 / total as f64)
+            .filled_style(Style::default().fg(crate::tui::GREEN).bold())
+            .unfilled_style(Style::default().fg(Color::Rgb(60, 60, 60)))
+            .line_set(ratatui::symbols::line::DOUBLE);
+        frame.render_widget(gauge, progress_area);
+
+        let rule = &issue.rule;
+        let title = match issue.problem {
+            RuleProblem::MatchesEverything => "Matches everything",
+            RuleProblem::Superseded { .. } => "Superseded",
+            RuleProblem::NeverMatched => "Never matched",
+        };
+        let recommended = issue.recommended();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(
+                    format!(" Rule {}: ", rule.id),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(title, Style::default().fg(Color::Yellow)),
+            ]),
+            Line::from(""),
+            Line::from(format!("  Pattern:     {}", rule.pattern)),
+            Line::from(format!("  Match type:  {}", rule.match_type)),
+            Line::from(format!(
+                "  Vendor:      {}",
+                rule.vendor.as_deref().unwrap_or("")
+            )),
+            Line::from(format!("  Category:    {}", rule.category)),
+            Line::from(format!("  Priority:    {}", rule.priority)),
+            Line::from(format!(
+                "  Hits:        {} ({} matching now)",
+                rule.hit_count, rule.matching
+            )),
+            Line::from(""),
+            Line::from(format!("  {}", issue.explanation())),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("  Recommended: "),
+                Span::styled(recommended.label(), Style::default().fg(Color::Cyan)),
+            ]),
+        ];
+        if let Some(msg) = &self.status_message {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("  {msg}"),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines), content_area);
+
+        let hints = if Self::priority_action(issue).is_some() {
+            " Enter=recommended  d=delete  p=raise priority  Tab=skip  Esc=stop"
+        } else {
+            " Enter=recommended  d=delete  Tab=skip  Esc=stop"
+        };
+        frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode, conn: &Connection) -> CleanupScreenAction {
+        let Some(issue) = self.current() else {
+            return match code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => CleanupScreenAction::Close,
+                _ => CleanupScreenAction::Continue,
+            };
+        };
+        let action = match code {
+            KeyCode::Esc | KeyCode::Char('q') => return CleanupScreenAction::Close,
+            KeyCode::Enter => Some(issue.recommended()),
+            KeyCode::Char('d') => Some(CleanupAction::Delete),
+            KeyCode::Char('p') => match Self::priority_action(issue) {
+                Some(action) => Some(action),
+                None => return CleanupScreenAction::Continue,
+            },
+            KeyCode::Tab | KeyCode::Char('s') => None,
+            _ => return CleanupScreenAction::Continue,
+        };
+        if let Err(e) = self.decide(conn, action) {
+            self.status_message = Some(format!("Error: {e}"));
+        }
+        CleanupScreenAction::Continue
+    }
+
+    /// Apply `action` (None = skip) to the current rule and move on.
+    fn decide(&mut self, conn: &Connection, action: Option<CleanupAction>) -> Result<()> {
+        let Some(issue) = self.issues.first() else {
+            return Ok(());
+        };
+        let id = issue.rule.id;
+        match action {
+            None => {
+                self.skipped += 1;
+                self.seen.insert(id);
+                self.issues.remove(0);
+            }
+            Some(action) => {
+                if !self.snapshot_taken {
+                    crate::cli::backup::pre_operation_snapshot(
+                        conn,
+                        &self.data_dir,
+                        "rules-cleanup",
+                    )?;
+                    self.snapshot_taken = true;
+                }
+                rule_audit::apply(conn, id, action)?;
+                match action {
+                    CleanupAction::Delete => self.deleted += 1,
+                    CleanupAction::SetPriority(_) => self.reprioritized += 1,
+                }
+                self.seen.insert(id);
+                self.issues = rule_audit::audit_rules(conn)?
+                    .into_iter()
+                    .filter(|i| !self.seen.contains(&i.rule.id))
+                    .collect();
+            }
+        }
+        self.status_message = None;
+        Ok(())
+    }
+}
+
+/// `nigel rules cleanup`: the wizard in its own terminal session, or a plain
+/// list of problem rules when there is no terminal.
+pub fn run() -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let mut wizard = RulesCleanup::new(&conn, "Clean Up Rules", data_dir)?;
+    if wizard.is_done() {
+        println!("No problem rules found.");
+        return Ok(());
+    }
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        for issue in &wizard.issues {
+            println!(
+                "Rule {} '{}' \u{2192} {}: {} Recommended: {}.",
+                issue.rule.id,
+                issue.rule.pattern,
+                issue.rule.category,
+                issue.explanation(),
+                issue.recommended().label().to_lowercase()
+            );
+        }
+        return Ok(());
+    }
+
+    let mut terminal = ratatui::init();
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| wizard.draw(frame)) {
+            break Err(e.into());
+        }
+        match event::read() {
+            Err(e) => break Err(e.into()),
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                    break Ok(());
+                }
+                if let CleanupScreenAction::Close = wizard.handle_key(key.code, &conn) {
+                    break Ok(());
+                }
+            }
+            _ => {}
+        }
+    };
+    ratatui::restore();
+
+    if let (Ok(()), Some(summary)) = (&result, wizard.summary()) {
+        println!("{summary}.");
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("nigel.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Test', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, '2025-01-15', 'ADOBE STOCK', -20.0)",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn add_rule(conn: &Connection, pattern: &str, match_type: &str, priority: i64) -> i64 {
+        conn.execute(
+            "INSERT INTO rules (pattern, match_type, category_id, priority) \
+             VALUES (?1, ?2, (SELECT id FROM categories WHERE name = 'Software & Subscriptions'), ?3)",
+            rusqlite::params![pattern, match_type, priority],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn is_active(conn: &Connection, id: i64) -> bool {
+        conn.query_row("SELECT is_active FROM rules WHERE id = ?1", [id], |r| {
+            r.get::<_, i64>(0)
+        })
+        .unwrap()
+            == 1
+    }
+
+    #[test]
+    fn recommended_delete_snapshots_then_reaudits() {
+        let (dir, conn) = test_db();
+        let everything = add_rule(&conn, ".*", "regex", 9);
+        let shadowed = add_rule(&conn, "ADOBE", "contains", 0);
+        let mut wizard = RulesCleanup::new(&conn, "Hi", dir.path().to_path_buf()).unwrap();
+        assert_eq!(wizard.issues.len(), 2);
+
+        wizard.handle_key(KeyCode::Enter, &conn);
+        assert!(!is_active(&conn, everything));
+        assert!(is_active(&conn, shadowed));
+        // With the catch-all gone, the ADOBE rule is fine again
+        assert!(wizard.is_done());
+        assert_eq!(
+            wizard.summary().as_deref(),
+            Some("Deleted 1, reprioritized 0, skipped 0")
+        );
+        let snapshots = std::fs::read_dir(dir.path().join("snapshots")).unwrap();
+        assert_eq!(snapshots.count(), 1);
+    }
+
+    #[test]
+    fn skip_and_raise_priority() {
+        let (dir, conn) = test_db();
+        add_rule(&conn, "FIGMA", "contains", 0);
+        let broad = add_rule(&conn, "ADOBE", "contains", 5);
+        let narrow = add_rule(&conn, "ADOBE STOCK", "contains", 1);
+        let mut wizard = RulesCleanup::new(&conn, "Hi", dir.path().to_path_buf()).unwrap();
+
+        // Superseded (priority 1) comes before never matched (priority 0)
+        assert_eq!(wizard.current().unwrap().rule.id, narrow);
+        wizard.handle_key(KeyCode::Char('p'), &conn);
+        let priority: i64 = conn
+            .query_row("SELECT priority FROM rules WHERE id = ?1", [narrow], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(priority, 6);
+
+        // The re-audit now finds the broad rule shadowed by the narrow one
+        assert_eq!(wizard.current().unwrap().rule.id, broad);
+        wizard.handle_key(KeyCode::Tab, &conn);
+        // `p` does nothing for a never-matched rule
+        wizard.handle_key(KeyCode::Char('p'), &conn);
+        assert!(!wizard.is_done());
+        wizard.handle_key(KeyCode::Tab, &conn);
+        assert!(wizard.is_done());
+        assert_eq!(
+            wizard.summary().as_deref(),
+            Some("Deleted 0, reprioritized 1, skipped 2")
+        );
+        assert!(matches!(
+            wizard.handle_key(KeyCode::Esc, &conn),
+            CleanupScreenAction::Close
+        ));
+    }
+}
@@ -8,7 +8,21 @@ use ratatui::{
 };
 use rusqlite::Connection;
 
-use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
+use crate::categorizer::{find_duplicate_rule, DuplicateRule};
+use crate::cli::categories::{self, CategoryRow};
+use crate::cli::rules::{self, MATCH_TYPES};
+use crate::cli::rules_cleanup::{CleanupScreenAction, RulesCleanup};
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
+use crate::error::Result;
+use crate::settings::{get_data_dir, load_settings};
+use crate::tui::{truncate, FOOTER_STYLE, HEADER_STYLE};
+
+// Field indices for RuleForm — keep in sync with field order
+const PATTERN_IDX: usize = 0;
+const MATCH_TYPE_IDX: usize = 1;
+const VENDOR_IDX: usize = 2;
+const CATEGORY_IDX: usize = 3;
+const PRIORITY_IDX: usize = 4;
 
 pub enum RulesAction {
     Continue,
@@ -20,6 +34,7 @@ struct RuleRow {
     pattern: String,
     match_type: String,
     vendor: String,
+    category_id: i64,
     category: String,
     priority: i64,
     hits: i64,
@@ -27,11 +42,137 @@ struct RuleRow {
 
 enum Screen {
     List,
+    Add(RuleForm),
+    /// Editing the rule with this ID.
+    Edit(i64, RuleForm),
+    /// A new rule would repeat an existing one; offer to edit that instead.
+    Duplicate(RuleForm, DuplicateRule),
     ConfirmDelete,
+    /// The rules cleanup wizard.
+    Cleanup(RulesCleanup),
+}
+
+struct RuleForm {
+    fields: Vec<FormField>,
+    focused: usize,
+    /// Category IDs behind the Category selector's options.
+    category_ids: Vec<i64>,
+    /// The rule's priority, saved unchanged when simple mode leaves the
+    /// Priority field off the form.
+    kept_priority: i64,
+}
+
+struct FormField {
+    label: &'static str,
+    value: String,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    Text,
+    Selector {
+        options: Vec<String>,
+        selected: usize,
+    },
+}
+
+/// Values read back from a `RuleForm` on save.
+struct RuleInput {
+    pattern: String,
+    match_type: String,
+    vendor: Option<String>,
+    category_id: i64,
+    category: String,
+    priority: i64,
+}
+
+fn selector(label: &'static str, options: Vec<String>, selected: usize) -> FormField {
+    FormField {
+        label,
+        value: options.get(selected).cloned().unwrap_or_default(),
+        kind: FieldKind::Selector { options, selected },
+    }
+}
+
+fn text(label: &'static str, value: String) -> FormField {
+    FormField {
+        label,
+        value,
+        kind: FieldKind::Text,
+    }
+}
+
+impl RuleForm {
+    fn new(categories: &[CategoryRow], rule: Option<&RuleRow>, simple_mode: bool) -> Self {
+        let match_idx = rule
+            .and_then(|r| MATCH_TYPES.iter().position(|t| *t == r.match_type))
+            .unwrap_or(0);
+        let category_idx = rule
+            .and_then(|r| categories.iter().position(|c| c.id == r.category_id))
+            .unwrap_or(0);
+        let kept_priority = rule.map(|r| r.priority).unwrap_or(0);
+        let mut fields = vec![
+            text(
+                "Pattern",
+                rule.map(|r| r.pattern.clone()).unwrap_or_default(),
+            ),
+            selector(
+                "Match Type",
+                MATCH_TYPES.iter().map(|t| t.to_string()).collect(),
+                match_idx,
+            ),
+            text("Vendor", rule.map(|r| r.vendor.clone()).unwrap_or_default()),
+            selector(
+                "Category",
+                categories.iter().map(|c| c.path.clone()).collect(),
+                category_idx,
+            ),
+            text("Priority", kept_priority.to_string()),
+        ];
+        if simple_mode {
+            fields.truncate(PRIORITY_IDX);
+        }
+        Self {
+            fields,
+            focused: 0,
+            category_ids: categories.iter().map(|c| c.id).collect(),
+            kept_priority,
+        }
+    }
+
+    fn input(&self) -> std::result::Result<RuleInput, String> {
+        let pattern = self.fields[PATTERN_IDX].value.trim().to_string();
+        if pattern.is_empty() {
+            return Err("Pattern is required".into());
+        }
+        let FieldKind::Selector { selected, .. } = &self.fields[CATEGORY_IDX].kind else {
+            return Err("Category is required".into());
+        };
+        let Some(&category_id) = self.category_ids.get(*selected) else {
+            return Err("Category is required".into());
+        };
+        let priority = match self.fields.get(PRIORITY_IDX).map(|f| f.value.trim()) {
+            None => self.kept_priority,
+            Some("") => 0,
+            Some(priority) => priority
+                .parse()
+                .map_err(|_| format!("Priority must be a whole number: {priority}"))?,
+        };
+        let vendor = self.fields[VENDOR_IDX].value.trim();
+        Ok(RuleInput {
+            pattern,
+            match_type: self.fields[MATCH_TYPE_IDX].value.clone(),
+            vendor: (!vendor.is_empty()).then(|| vendor.to_string()),
+            category_id,
+            category: self.fields[CATEGORY_IDX].value.clone(),
+            priority,
+        })
+    }
 }
 
 pub struct RulesManager {
     rules: Vec<RuleRow>,
+    categories: Vec<CategoryRow>,
     selection: usize,
     scroll_offset: usize,
     last_visible_rows: usize,
@@ -39,6 +180,10 @@ pub struct RulesManager {
     status_message: Option<String>,
     status_ttl: u8,
     greeting: String,
+    /// Rule priorities hidden from the list and form.
+    simple_mode: bool,
+    /// Set by a save, until the dashboard is told.
+    changed: bool,
 }
 
 impl RulesManager {
@@ -46,6 +191,7 @@ impl RulesManager {
         let rules = load_rules(conn);
         Self {
             rules,
+            categories: categories::list_categories(conn).unwrap_or_default(),
             selection: 0,
             scroll_offset: 0,
             last_visible_rows: 20,
@@ -53,6 +199,17 @@ impl RulesManager {
             status_message: None,
             status_ttl: 0,
             greeting: greeting.to_string(),
+            simple_mode: load_settings().simple_mode,
+            changed: false,
+        }
+    }
+
+    /// A padded list column for the priority, empty in simple mode.
+    fn priority_column(&self, value: &str) -> String {
+        if self.simple_mode {
+            String::new()
+        } else {
+            format!("{value:<5} ")
         }
     }
 
@@ -66,6 +223,16 @@ impl RulesManager {
     }
 
     pub fn draw(&mut self, frame: &mut Frame) {
+        match &self.screen {
+            Screen::Add(form) => self.draw_form(frame, "Add Rule", form, None),
+            Screen::Edit(id, form) => self.draw_form(frame, &format!("Edit Rule {id}"), form, None),
+            Screen::Duplicate(form, dup) => self.draw_form(frame, "Add Rule", form, Some(dup)),
+            Screen::Cleanup(wizard) => wizard.draw(frame),
+            Screen::List | Screen::ConfirmDelete => self.draw_list(frame),
+        }
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame) {
         let area = frame.area();
         let border_style = Style::default().fg(Color::DarkGray);
 
@@ -106,8 +273,14 @@ impl RulesManager {
             // Column header
             lines.push(Line::from(Span::styled(
                 format!(
-                    "   {:<5} {:<24} {:<10} {:<16} {:<24} {:<5} {}",
-                    "ID", "Pattern", "Type", "Vendor", "Category", "Pri", "Hits"
+                    "   {:<5} {:<24} {:<10} {:<16} {:<24} {}{}",
+                    "ID",
+                    "Pattern",
+                    "Type",
+                    "Vendor",
+                    "Category",
+                    self.priority_column("Pri"),
+                    "Hits"
                 ),
                 Style::default()
                     .fg(Color::DarkGray)
@@ -130,13 +303,13 @@ impl RulesManager {
 
                 lines.push(Line::from(Span::styled(
                     format!(
-                        "{marker}{:<5} {:<24} {:<10} {:<16} {:<24} {:<5} {}",
+                        "{marker}{:<5} {:<24} {:<10} {:<16} {:<24} {}{}",
                         rule.id,
                         pattern_display,
                         rule.match_type,
                         vendor_display,
                         category_display,
-                        rule.priority,
+                        self.priority_column(&rule.priority.to_string()),
                         rule.hits
                     ),
                     style,
@@ -172,11 +345,114 @@ impl RulesManager {
                 hints_area,
             );
         } else {
-            frame.render_widget(
-                Paragraph::new(" Up/Down=navigate  d=delete  Esc=back").style(FOOTER_STYLE),
-                hints_area,
-            );
+            let hints = if crate::db::is_read_only() {
+                " Up/Down=navigate  Esc=back"
+            } else {
+                " Up/Down=navigate  a=add  e=edit  d=delete  c=clean up  Esc=back"
+            };
+            frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
+        }
+    }
+
+    fn draw_form(
+        &self,
+        frame: &mut Frame,
+        title: &str,
+        form: &RuleForm,
+        duplicate: Option<&DuplicateRule>,
+    ) {
+        let area = frame.area();
+        let border_style = Style::default().fg(Color::DarkGray);
+
+        let [header_area, sep, content_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
+            header_area,
+        );
+
+        let sep_line = "\u{2501}".repeat(area.width as usize);
+        frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(" {title}"),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+
+        for (i, field) in form.fields.iter().enumerate() {
+            let is_focused = i == form.focused && duplicate.is_none();
+            let label_style = if is_focused {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let value = match &field.kind {
+                FieldKind::Text => {
+                    let cursor = if is_focused { "_" } else { "" };
+                    format!("{}{cursor}", field.value)
+                }
+                FieldKind::Selector { options, selected } => {
+                    let (left, right) = if is_focused {
+                        ("< ", " >")
+                    } else {
+                        ("  ", "  ")
+                    };
+                    let option = options.get(*selected).map(String::as_str).unwrap_or("");
+                    format!("{left}{option}{right}")
+                }
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {:<14} ", field.label), label_style),
+                Span::styled(
+                    value,
+                    if is_focused {
+                        Style::default().fg(Color::Cyan)
+                    } else {
+                        Style::default()
+                    },
+                ),
+            ]));
         }
+
+        if let Some(dup) = duplicate {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "   Rule {} already files '{}' ({}) under {}.",
+                    dup.id, dup.pattern, dup.match_type, dup.category
+                ),
+                Style::default().fg(Color::Yellow),
+            )));
+            lines.push(Line::from(Span::styled(
+                "   Edit that rule instead of adding another?",
+                Style::default().fg(Color::Yellow),
+            )));
+        } else if let Some(msg) = &self.status_message {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("   {msg}"),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+
+        frame.render_widget(Paragraph::new(lines), content_area);
+
+        let hints = if duplicate.is_some() {
+            " e=edit existing rule  a=add anyway  Esc=back to form"
+        } else {
+            " Tab=next field  Left/Right=choose  Enter=save  Esc=cancel"
+        };
+        frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
     }
 
     fn set_status(&mut self, msg: String) {
@@ -202,12 +478,18 @@ impl RulesManager {
 
         match &self.screen {
             Screen::List => self.handle_list_key(code, conn),
+            Screen::Add(_) | Screen::Edit(..) => self.handle_form_key(code, conn),
+            Screen::Duplicate(..) => self.handle_duplicate_key(code, conn),
             Screen::ConfirmDelete => self.handle_delete_key(code, conn),
+            Screen::Cleanup(_) => self.handle_cleanup_key(code, conn),
         }
     }
 
-    fn handle_list_key(&mut self, code: KeyCode, _conn: &Connection) -> RulesAction {
+    fn handle_list_key(&mut self, code: KeyCode, conn: &Connection) -> RulesAction {
         match code {
+            KeyCode::Char('a' | 'e' | 'd' | 'c') if crate::db::is_read_only() => {
+                self.set_status(crate::tui::READ_ONLY_STATUS.into());
+            }
             KeyCode::Up => {
                 self.selection = self.selection.saturating_sub(1);
                 self.ensure_visible(self.last_visible_rows);
@@ -218,17 +500,214 @@ impl RulesManager {
                     self.ensure_visible(self.last_visible_rows);
                 }
             }
+            KeyCode::Char('a') => {
+                if self.categories.is_empty() {
+                    self.set_status("Add a category before adding rules".into());
+                } else {
+                    self.screen =
+                        Screen::Add(RuleForm::new(&self.categories, None, self.simple_mode));
+                }
+            }
+            KeyCode::Char('e') => {
+                if let Some(rule) = self.rules.get(self.selection) {
+                    self.screen = Screen::Edit(
+                        rule.id,
+                        RuleForm::new(&self.categories, Some(rule), self.simple_mode),
+                    );
+                }
+            }
             KeyCode::Char('d') => {
                 if !self.rules.is_empty() {
                     self.screen = Screen::ConfirmDelete;
                 }
             }
+            KeyCode::Char('c') => match RulesCleanup::new(conn, &self.greeting, get_data_dir()) {
+                Ok(wizard) if wizard.is_done() => self.set_status("No problem rules found".into()),
+                Ok(wizard) => self.screen = Screen::Cleanup(wizard),
+                Err(e) => self.set_status(format!("Error: {e}")),
+            },
             KeyCode::Char('q') | KeyCode::Esc => return RulesAction::Close,
             _ => {}
         }
         RulesAction::Continue
     }
 
+    fn handle_cleanup_key(&mut self, code: KeyCode, conn: &Connection) -> RulesAction {
+        let Screen::Cleanup(wizard) = &mut self.screen else {
+            return RulesAction::Continue;
+        };
+        if let CleanupScreenAction::Close = wizard.handle_key(code, conn) {
+            let summary = wizard.summary();
+            self.screen = Screen::List;
+            self.reload(conn);
+            self.changed = true;
+            if let Some(summary) = summary {
+                self.set_status(summary);
+            }
+        }
+        RulesAction::Continue
+    }
+
+    fn handle_form_key(&mut self, code: KeyCode, conn: &Connection) -> RulesAction {
+        let form = match &mut self.screen {
+            Screen::Add(f) | Screen::Edit(_, f) => f,
+            _ => return RulesAction::Continue,
+        };
+
+        match code {
+            KeyCode::Esc => {
+                self.screen = Screen::List;
+            }
+            KeyCode::Tab | KeyCode::Down => {
+                form.focused = (form.focused + 1) % form.fields.len();
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                form.focused = if form.focused == 0 {
+                    form.fields.len() - 1
+                } else {
+                    form.focused - 1
+                };
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let field = &mut form.fields[form.focused];
+                if let FieldKind::Selector { options, selected } = &mut field.kind {
+                    *selected = if code == KeyCode::Left {
+                        (*selected + options.len() - 1) % options.len()
+                    } else {
+                        (*selected + 1) % options.len()
+                    };
+                    field.value = options[*selected].clone();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let FieldKind::Text = &form.fields[form.focused].kind {
+                    form.fields[form.focused].value.push(c);
+                }
+            }
+            KeyCode::Backspace => {
+                if let FieldKind::Text = &form.fields[form.focused].kind {
+                    form.fields[form.focused].value.pop();
+                }
+            }
+            KeyCode::Enter => self.save_form(conn, false),
+            _ => {}
+        }
+        RulesAction::Continue
+    }
+
+    /// Save the open add/edit form. A new rule that repeats an existing
+    /// pattern + category opens the duplicate prompt unless `allow_duplicate`.
+    fn save_form(&mut self, conn: &Connection, allow_duplicate: bool) {
+        let (form, editing) = match &self.screen {
+            Screen::Add(f) | Screen::Duplicate(f, _) => (f, None),
+            Screen::Edit(id, f) => (f, Some(*id)),
+            _ => return,
+        };
+        let input = match form.input() {
+            Ok(input) => input,
+            Err(msg) => return self.set_status(msg),
+        };
+        let duplicate = if allow_duplicate {
+            None
+        } else {
+            match find_duplicate_rule(conn, &input.pattern, input.category_id, editing) {
+                Ok(dup) => dup,
+                Err(e) => return self.set_status(format!("Error: {e}")),
+            }
+        };
+        if let Some(dup) = duplicate {
+            if editing.is_some() {
+                // Editing into a copy of another rule: say which, stay in the form
+                return self.set_status(format!(
+                    "Rule {} already files '{}' under {}",
+                    dup.id, dup.pattern, dup.category
+                ));
+            }
+            if let Screen::Add(form) = std::mem::replace(&mut self.screen, Screen::List) {
+                self.screen = Screen::Duplicate(form, dup);
+            }
+            return;
+        }
+
+        let result = match editing {
+            Some(id) => rules::update_rule(
+                conn,
+                id,
+                &input.pattern,
+                &input.match_type,
+                input.vendor.as_deref(),
+                input.category_id,
+                input.priority,
+            )
+            .map(|()| id),
+            None => rules::add_rule(
+                conn,
+                &input.pattern,
+                &input.match_type,
+                input.vendor.as_deref(),
+                input.category_id,
+                input.priority,
+            ),
+        };
+        match result {
+            Ok(id) => {
+                self.reload(conn);
+                self.changed = true;
+                self.select_rule(id);
+                self.screen = Screen::List;
+                let verb = if editing.is_some() {
+                    "Updated"
+                } else {
+                    "Added"
+                };
+                self.set_status(format!(
+                    "{verb} rule {id}: '{}' \u{2192} {}",
+                    input.pattern, input.category
+                ));
+            }
+            Err(e) => self.set_status(e.to_string()),
+        }
+    }
+
+    fn handle_duplicate_key(&mut self, code: KeyCode, conn: &Connection) -> RulesAction {
+        let Screen::Duplicate(_, dup) = &self.screen else {
+            return RulesAction::Continue;
+        };
+        match code {
+            KeyCode::Char('e') => {
+                let id = dup.id;
+                self.select_rule(id);
+                match self.rules.iter().find(|r| r.id == id) {
+                    Some(rule) => {
+                        self.screen = Screen::Edit(
+                            id,
+                            RuleForm::new(&self.categories, Some(rule), self.simple_mode),
+                        );
+                    }
+                    None => self.screen = Screen::List,
+                }
+            }
+            KeyCode::Char('a') => self.save_form(conn, true),
+            KeyCode::Esc => {
+                if let Screen::Duplicate(form, _) =
+                    std::mem::replace(&mut self.screen, Screen::List)
+                {
+                    self.screen = Screen::Add(form);
+                }
+            }
+            _ => {}
+        }
+        RulesAction::Continue
+    }
+
+    /// Move the selection to rule `id` if it is listed.
+    fn select_rule(&mut self, id: i64) {
+        if let Some(pos) = self.rules.iter().position(|r| r.id == id) {
+            self.selection = pos;
+            self.ensure_visible(self.last_visible_rows);
+        }
+    }
+
     fn handle_delete_key(&mut self, code: KeyCode, conn: &Connection) -> RulesAction {
         match code {
             KeyCode::Char('y') => {
@@ -238,6 +717,7 @@ impl RulesManager {
                     match conn.execute("UPDATE rules SET is_active = 0 WHERE id = ?1", [id]) {
                         Ok(_) => {
                             self.reload(conn);
+                            self.changed = true;
                             self.screen = Screen::List;
                             self.set_status(format!("Deleted rule {id}: '{pattern}'"));
                         }
@@ -259,9 +739,9 @@ impl RulesManager {
 
 fn load_rules(conn: &Connection) -> Vec<RuleRow> {
     let mut stmt = match conn.prepare(
-        "SELECT r.id, r.pattern, r.match_type, r.vendor, c.name, r.priority, r.hit_count \
+        "SELECT r.id, r.pattern, r.match_type, r.vendor, r.category_id, c.name, r.priority, r.hit_count \
          FROM rules r JOIN categories c ON r.category_id = c.id \
-         WHERE r.is_active = 1 ORDER BY r.priority DESC",
+         WHERE r.is_active = 1 ORDER BY r.priority DESC, r.id",
     ) {
         Ok(s) => s,
         Err(_) => return vec![],
@@ -273,20 +753,198 @@ fn load_rules(conn: &Connection) -> Vec<RuleRow> {
             pattern: row.get(1)?,
             match_type: row.get(2)?,
             vendor: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-            category: row.get(4)?,
-            priority: row.get(5)?,
-            hits: row.get(6)?,
+            category_id: row.get(4)?,
+            category: row.get(5)?,
+            priority: row.get(6)?,
+            hits: row.get(7)?,
         })
     })
     .map(|rows| rows.filter_map(|r| r.ok()).collect())
     .unwrap_or_default()
 }
 
-fn truncate(s: &str, max: usize) -> String {
-    if s.chars().count() <= max {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max - 1).collect();
-        format!("{truncated}\u{2026}")
+impl screen::Screen for RulesManager {
+    fn draw(&mut self, frame: &mut Frame) {
+        RulesManager::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        let action = RulesManager::handle_key(self, code, ctx.conn);
+        if std::mem::take(&mut self.changed) {
+            ctx.publish(DataChange::Rules);
+        }
+        Ok(match action {
+            RulesAction::Close => Nav::Pop,
+            RulesAction::Continue => Nav::Stay,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    fn software_id(conn: &Connection) -> i64 {
+        conn.query_row(
+            "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap()
+    }
+
+    /// Open the add form and fill in a pattern filed under Software.
+    fn start_add(mgr: &mut RulesManager, conn: &Connection, pattern: &str) {
+        mgr.handle_key(KeyCode::Char('a'), conn);
+        for c in pattern.chars() {
+            mgr.handle_key(KeyCode::Char(c), conn);
+        }
+        let software = software_id(conn);
+        let Screen::Add(form) = &mut mgr.screen else {
+            panic!("add form not open");
+        };
+        let selected = form
+            .category_ids
+            .iter()
+            .position(|id| *id == software)
+            .unwrap();
+        if let FieldKind::Selector { selected: s, .. } = &mut form.fields[CATEGORY_IDX].kind {
+            *s = selected;
+        }
+    }
+
+    fn rule_count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM rules WHERE is_active = 1", [], |r| {
+            r.get(0)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn add_rule_from_form() {
+        let (_dir, conn) = test_db();
+        let mut mgr = RulesManager::new(&conn, "Hello");
+        start_add(&mut mgr, &conn, "ADOBE");
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(mgr.screen, Screen::List));
+        assert_eq!(mgr.rules.len(), 1);
+        assert_eq!(mgr.rules[0].pattern, "ADOBE");
+        assert_eq!(mgr.rules[0].category_id, software_id(&conn));
+    }
+
+    #[test]
+    fn duplicate_add_offers_to_edit_existing() {
+        let (_dir, conn) = test_db();
+        let existing =
+            rules::add_rule(&conn, "Adobe", "contains", None, software_id(&conn), 0).unwrap();
+        let mut mgr = RulesManager::new(&conn, "Hello");
+
+        start_add(&mut mgr, &conn, "adobe");
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(&mgr.screen, Screen::Duplicate(_, dup) if dup.id == existing));
+        assert_eq!(rule_count(&conn), 1);
+
+        // Esc goes back to the form with the input kept
+        mgr.handle_key(KeyCode::Esc, &conn);
+        let Screen::Add(form) = &mgr.screen else {
+            panic!("expected the add form");
+        };
+        assert_eq!(form.fields[PATTERN_IDX].value, "adobe");
+
+        mgr.handle_key(KeyCode::Enter, &conn);
+        mgr.handle_key(KeyCode::Char('e'), &conn);
+        assert!(matches!(mgr.screen, Screen::Edit(id, _) if id == existing));
+        assert_eq!(rule_count(&conn), 1);
+    }
+
+    #[test]
+    fn duplicate_add_anyway() {
+        let (_dir, conn) = test_db();
+        rules::add_rule(&conn, "ADOBE", "contains", None, software_id(&conn), 0).unwrap();
+        let mut mgr = RulesManager::new(&conn, "Hello");
+
+        start_add(&mut mgr, &conn, "Adobe");
+        mgr.handle_key(KeyCode::Enter, &conn);
+        mgr.handle_key(KeyCode::Char('a'), &conn);
+        assert!(matches!(mgr.screen, Screen::List));
+        assert_eq!(rule_count(&conn), 2);
+    }
+
+    #[test]
+    fn simple_mode_edit_keeps_priority() {
+        let (_dir, conn) = test_db();
+        let id = rules::add_rule(&conn, "ADOBE", "contains", None, software_id(&conn), 5).unwrap();
+        let mut mgr = RulesManager::new(&conn, "Hello");
+        mgr.simple_mode = true;
+
+        mgr.handle_key(KeyCode::Char('e'), &conn);
+        let Screen::Edit(_, form) = &mgr.screen else {
+            panic!("edit form not open");
+        };
+        assert_eq!(form.fields.len(), PRIORITY_IDX);
+        mgr.handle_key(KeyCode::Char('X'), &conn);
+        mgr.handle_key(KeyCode::Enter, &conn);
+        let (pattern, priority): (String, i64) = conn
+            .query_row(
+                "SELECT pattern, priority FROM rules WHERE id = ?1",
+                [id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((pattern.as_str(), priority), ("ADOBEX", 5));
+    }
+
+    #[test]
+    fn edit_into_duplicate_is_refused() {
+        let (_dir, conn) = test_db();
+        let software = software_id(&conn);
+        rules::add_rule(&conn, "ADOBE", "contains", None, software, 5).unwrap();
+        rules::add_rule(&conn, "FIGMA", "contains", None, software, 0).unwrap();
+        let mut mgr = RulesManager::new(&conn, "Hello");
+
+        // Rules are listed by priority, so FIGMA is second
+        mgr.selection = 1;
+        mgr.handle_key(KeyCode::Char('e'), &conn);
+        for _ in 0.."FIGMA".len() {
+            mgr.handle_key(KeyCode::Backspace, &conn);
+        }
+        for c in "adobe".chars() {
+            mgr.handle_key(KeyCode::Char(c), &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(mgr.screen, Screen::Edit(..)));
+        assert!(mgr
+            .status_message
+            .as_deref()
+            .unwrap()
+            .contains("already files"));
+    }
+
+    #[test]
+    fn cleanup_opens_only_with_problem_rules() {
+        let (_dir, conn) = test_db();
+        let mut mgr = RulesManager::new(&conn, "Hello");
+        mgr.handle_key(KeyCode::Char('c'), &conn);
+        assert!(matches!(mgr.screen, Screen::List));
+        assert_eq!(
+            mgr.status_message.as_deref(),
+            Some("No problem rules found")
+        );
+
+        // A rule with no transactions to match is flagged as never matched
+        rules::add_rule(&conn, "FIGMA", "contains", None, software_id(&conn), 0).unwrap();
+        mgr.handle_key(KeyCode::Char('c'), &conn);
+        assert!(matches!(mgr.screen, Screen::Cleanup(_)));
+        mgr.handle_key(KeyCode::Esc, &conn);
+        assert!(matches!(mgr.screen, Screen::List));
+        assert_eq!(rule_count(&conn), 1);
     }
 }
@@ -0,0 +1,274 @@
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use comfy_table::{Cell, Table};
+
+use crate::cli::{ReportCommands, ReportOutputArgs};
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::schedule::{self, Cadence, Period, ReportSchedule};
+use crate::settings::{get_data_dir, load_settings, shellexpand_path};
+
+pub fn add(report: &str, cadence: &str, format: &str, output_dir: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let cadence = Cadence::parse(cadence)?;
+    // Cron runs from another working directory, so store an absolute path
+    let dir = PathBuf::from(shellexpand_path(output_dir));
+    let dir = if dir.is_absolute() {
+        dir
+    } else {
+        std::env::current_dir()?.join(dir)
+    };
+    let today = chrono::Local::now().date_naive();
+    let id = schedule::add_schedule(
+        &conn,
+        report,
+        cadence,
+        format,
+        &dir.to_string_lossy(),
+        today,
+    )?;
+    let fiscal = FiscalYear::load(&conn);
+    let next = schedule::next_run_after(cadence, fiscal, today);
+    let period = schedule::period_before(cadence, fiscal, next);
+    println!(
+        "Scheduled report #{id}: {report} ({}, {format}) into {}",
+        cadence.as_str(),
+        dir.display()
+    );
+    println!(
+        "First run on or after {next} covers {} to {}. Run `nigel schedule run` from cron.",
+        period.from, period.to
+    );
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let schedules = schedule::list_schedules(&conn)?;
+    if schedules.is_empty() {
+        println!("No scheduled reports.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Report",
+        "Cadence",
+        "Format",
+        "Output dir",
+        "Next run",
+        "Last run",
+        "Last result",
+    ]);
+    for s in &schedules {
+        table.add_row(vec![
+            Cell::new(s.id),
+            Cell::new(&s.report),
+            Cell::new(s.cadence.as_str()),
+            Cell::new(&s.format),
+            Cell::new(&s.output_dir),
+            Cell::new(s.next_run),
+            Cell::new(s.last_run_at.as_deref().unwrap_or("never")),
+            Cell::new(last_result_label(s)),
+        ]);
+    }
+    println!("Scheduled Reports\n{table}");
+    Ok(())
+}
+
+pub fn remove(id: i64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    schedule::remove_schedule(&conn, id)?;
+    println!("Removed scheduled report #{id}");
+    Ok(())
+}
+
+/// Generate every report that has come due, catching up on periods missed
+/// while the machine was off. Exits non-zero if any report failed so cron
+/// mails the error.
+pub fn run() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let today = chrono::Local::now().date_naive();
+    // Book maintenance rides along every `maintenance_days`
+    if crate::maintenance::is_due(&conn, load_settings().maintenance_days(), today) {
+        if let Err(e) = super::db::maintain(false) {
+            eprintln!("Scheduled maintenance failed: {e}");
+        }
+    }
+    let fiscal = FiscalYear::load(&conn);
+    let due = schedule::due_schedules(&conn, today)?;
+    if due.is_empty() {
+        println!("No scheduled reports due.");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for s in &due {
+        let mut next = s.next_run;
+        while next <= today {
+            let period = schedule::period_before(s.cadence, fiscal, next);
+            match generate(s, &period, fiscal, today) {
+                Ok(path) => {
+                    next = if schedule::is_snapshot(&s.report) {
+                        schedule::next_run_after(s.cadence, fiscal, today)
+                    } else {
+                        schedule::next_run_after(s.cadence, fiscal, next)
+                    };
+                    schedule::record_run(&conn, s.id, true, &format!("Wrote {path}"), next)?;
+                }
+                Err(e) => {
+                    failed += 1;
+                    eprintln!("Scheduled report #{} ({}) failed: {e}", s.id, s.report);
+                    schedule::record_run(&conn, s.id, false, &e.to_string(), next)?;
+                    break;
+                }
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(NigelError::Other(format!(
+            "{failed} scheduled report(s) failed; they will be retried on the next run"
+        )));
+    }
+    Ok(())
+}
+
+/// "ok", "failed: ...", or "" before the first run.
+pub fn last_result_label(s: &ReportSchedule) -> String {
+    match (s.last_ok, &s.last_result) {
+        (Some(true), _) => "ok".into(),
+        (Some(false), Some(msg)) => format!("failed: {msg}"),
+        (Some(false), None) => "failed".into(),
+        (None, _) => String::new(),
+    }
+}
+
+/// Write one period of a schedule's report into its output directory and
+/// return the file path.
+fn generate(
+    s: &ReportSchedule,
+    period: &Period,
+    fiscal: FiscalYear,
+    today: NaiveDate,
+) -> Result<String> {
+    let dir = PathBuf::from(&s.output_dir);
+    std::fs::create_dir_all(&dir)?;
+    let label = if schedule::is_snapshot(&s.report) {
+        today.to_string()
+    } else {
+        period.label.clone()
+    };
+    let ext = if s.format == "text" { "txt" } else { &s.format };
+    // Same base names as `nigel report ... --mode export`
+    let name = if s.report == "k1" {
+        "k1-prep"
+    } else {
+        &s.report
+    };
+    let path = dir
+        .join(format!("{name}-{label}.{ext}"))
+        .to_string_lossy()
+        .into_owned();
+    let cmd = report_command(
+        s,
+        period,
+        fiscal,
+        ReportOutputArgs {
+            mode: Some("export".into()),
+            format: Some(s.format.clone()),
+            output: Some(path.clone()),
+        },
+    )?;
+    crate::cli::report::dispatch(cmd)?;
+    Ok(path)
+}
+
+/// The `nigel report` command for one period. Reports that take a month or a
+/// date range get exactly the period; the rest get its fiscal year.
+fn report_command(
+    s: &ReportSchedule,
+    period: &Period,
+    fiscal: FiscalYear,
+    output: ReportOutputArgs,
+) -> Result<ReportCommands> {
+    let month = (s.cadence == Cadence::Monthly).then(|| period.from.format("%Y-%m").to_string());
+    let year = Some(fiscal.year_of(period.to));
+    let (from_date, to_date) = if s.cadence == Cadence::Quarterly {
+        (Some(period.from.to_string()), Some(period.to.to_string()))
+    } else {
+        (None, None)
+    };
+    // A month or range wins over the year
+    let ranged_year = if month.is_some() || from_date.is_some() {
+        None
+    } else {
+        year
+    };
+    let month_year = if month.is_some() { None } else { year };
+
+    Ok(match s.report.as_str() {
+        "pnl" => ReportCommands::Pnl {
+            month,
+            year: ranged_year,
+            from_date,
+            to_date,
+            depth: None,
+            collapse: false,
+            compare: false,
+            exclude_tags: Vec::new(),
+            output,
+        },
+        "register" => ReportCommands::Register {
+            month,
+            year: ranged_year,
+            from_date,
+            to_date,
+            account: None,
+            tag: None,
+            output,
+        },
+        "expenses" => ReportCommands::Expenses {
+            month,
+            year: month_year,
+            depth: None,
+            exclude_tags: Vec::new(),
+            output,
+        },
+        "cashflow" => ReportCommands::Cashflow {
+            month,
+            year: month_year,
+            exclude_tags: Vec::new(),
+            granularity: None,
+            output,
+        },
+        "tags" => ReportCommands::Tags {
+            month,
+            year: month_year,
+            output,
+        },
+        "tax" => ReportCommands::Tax { year, output },
+        "k1" => ReportCommands::K1 { year, output },
+        "deductions" => ReportCommands::Deductions { year, output },
+        "compensation" => ReportCommands::Compensation { year, output },
+        "clients" => ReportCommands::Clients { year, output },
+        "flagged" => ReportCommands::Flagged { output },
+        "reimbursables" => ReportCommands::Reimbursables {
+            client: None,
+            all: false,
+            output,
+        },
+        "balance" => ReportCommands::Balance {
+            include_archived: false,
+            output,
+        },
+        other => {
+            return Err(NigelError::Other(format!(
+                "Unknown report '{other}' in schedule #{}",
+                s.id
+            )))
+        }
+    })
+}
@@ -0,0 +1,274 @@
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::Frame;
+use rusqlite::Connection;
+
+use crate::browser::BrowseLayout;
+use crate::cli::tutorial::Tutorial;
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::settings::get_data_dir;
+
+/// A full-window dashboard screen. The dashboard keeps open screens on a
+/// stack above its home screen: it draws and sends keys to the top one, and
+/// moves between them by the `Nav` each returns.
+pub trait Screen {
+    fn draw(&mut self, frame: &mut Frame);
+
+    /// Handle a key press. An error ends the dashboard.
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav>;
+
+    /// Called when the screen comes to the top of the stack: when it is
+    /// opened, and again when a screen it opened closes.
+    fn on_enter(&mut self, _ctx: &mut ScreenContext) -> Nav {
+        Nav::Stay
+    }
+
+    /// Called when the screen leaves the stack.
+    fn on_exit(&mut self, _ctx: &mut ScreenContext) {}
+
+    /// Called for each change another screen published while this one was
+    /// open beneath it, so it can refresh what it shows.
+    fn on_data_changed(&mut self, _change: DataChange, _ctx: &mut ScreenContext) {}
+
+    /// Time until the next `tick()`, for screens that animate.
+    fn tick_rate(&self) -> Option<Duration> {
+        None
+    }
+
+    fn tick(&mut self, _ctx: &mut ScreenContext) {}
+
+    /// True while the screen needs every key (a running game), so the
+    /// command palette and tutorial keys stay out of its way.
+    fn captures_keys(&self) -> bool {
+        false
+    }
+}
+
+/// Where to go after a screen handles a key.
+pub enum Nav {
+    Stay,
+    /// Open a screen over this one.
+    Push(Box<dyn Screen>),
+    /// Swap this screen for another.
+    Replace(Box<dyn Screen>),
+    /// Close this screen, back to the one that opened it.
+    Pop,
+    /// Close every screen, back to the home screen.
+    Home,
+    /// Close the dashboard and open it again, after the data directory or
+    /// settings change.
+    Reload,
+}
+
+/// What a change to the book touched. Screens publish one after they
+/// write, and the dashboard hands it to the screens open beneath and marks
+/// the home screen's figures stale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataChange {
+    /// Transactions added, edited, flagged, trashed, or rolled back.
+    Transactions,
+    Categories,
+    /// Rules added or changed; they can also recategorize transactions.
+    Rules,
+    /// Accounts, their opening balances, or reconciliations.
+    Accounts,
+    /// settings.json or the company name.
+    Settings,
+}
+
+/// Dashboard state every screen can read and change, kept until the
+/// dashboard exits.
+pub struct Session {
+    /// Header line drawn on every screen.
+    pub greeting: String,
+    /// Advanced menu items and reports hidden, from settings.
+    pub simple_mode: bool,
+    /// Shown on the home screen's footer.
+    pub status_message: Option<String>,
+    /// Register browser sort and columns.
+    pub browse_layout: BrowseLayout,
+    /// Set when an arcade game beats its high score; shown in the home
+    /// header for the rest of the session.
+    pub high_score_badge: Option<String>,
+    /// Guided tutorial overlay, from `nigel tutorial` or onboarding.
+    pub tutorial: Option<Tutorial>,
+    /// Changes published since the dashboard last delivered them.
+    pub changes: Vec<DataChange>,
+}
+
+impl Session {
+    /// A fresh session with `greeting` as the header, tagged in read-only
+    /// mode.
+    pub fn new(mut greeting: String) -> Self {
+        if crate::db::is_read_only() {
+            greeting.push_str(crate::tui::READ_ONLY_TAG);
+        }
+        Self {
+            greeting,
+            simple_mode: false,
+            status_message: None,
+            browse_layout: BrowseLayout::default(),
+            high_score_badge: None,
+            tutorial: None,
+            changes: Vec::new(),
+        }
+    }
+}
+
+/// What a screen is handed with each call.
+pub struct ScreenContext<'a> {
+    pub conn: &'a Connection,
+    pub session: &'a mut Session,
+}
+
+impl ScreenContext<'_> {
+    /// Tell the other open screens and the home screen that `change`
+    /// happened. Repeats before the next delivery are dropped.
+    pub fn publish(&mut self, change: DataChange) {
+        if !self.session.changes.contains(&change) {
+            self.session.changes.push(change);
+        }
+    }
+}
+
+/// Run one screen on its own, outside the dashboard, for commands like
+/// `nigel accounts manage`. Screens it opens stack above it as they would
+/// on the dashboard; closing it, going home, or Ctrl+C ends the command.
+pub fn run_standalone<S: Screen + 'static>(
+    open: impl FnOnce(&Connection, &str) -> Result<S>,
+) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let mut session = Session::new("Nigel".to_string());
+    let mut stack: Vec<Box<dyn Screen>> = Vec::new();
+    let first = open(&conn, &session.greeting)?;
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(NigelError::Other(
+            "This screen needs an interactive terminal.".into(),
+        ));
+    }
+    let mut pending = Some(Nav::Push(Box::new(first)));
+
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        ratatui::restore();
+        hook(info);
+    }));
+    let mut terminal = ratatui::init();
+
+    let result: Result<()> = loop {
+        if let Some(nav) = pending.take() {
+            if !standalone_navigate(&mut stack, nav, &conn, &mut session) {
+                break Ok(());
+            }
+            continue;
+        }
+        let Some(screen) = stack.last_mut() else {
+            break Ok(());
+        };
+        if let Err(e) = terminal.draw(|frame| screen.draw(frame)) {
+            break Err(e.into());
+        }
+
+        if let Some(timeout) = screen.tick_rate() {
+            match event::poll(timeout) {
+                Ok(true) => {}
+                Ok(false) => {
+                    screen.tick(&mut ScreenContext {
+                        conn: &conn,
+                        session: &mut session,
+                    });
+                    deliver_changes(&mut stack, &conn, &mut session);
+                    continue;
+                }
+                Err(e) => break Err(e.into()),
+            }
+        }
+
+        match event::read() {
+            Err(e) => break Err(e.into()),
+            Ok(Event::Key(key)) => {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                    break Ok(());
+                }
+                let nav = match screen.handle_key(
+                    key.code,
+                    &mut ScreenContext {
+                        conn: &conn,
+                        session: &mut session,
+                    },
+                ) {
+                    Ok(nav) => nav,
+                    Err(e) => break Err(e),
+                };
+                deliver_changes(&mut stack, &conn, &mut session);
+                pending = Some(nav);
+            }
+            _ => {}
+        }
+    };
+
+    while let Some(mut screen) = stack.pop() {
+        screen.on_exit(&mut ScreenContext {
+            conn: &conn,
+            session: &mut session,
+        });
+    }
+    drop(terminal);
+    ratatui::restore();
+    result
+}
+
+/// Apply `nav` to a standalone stack. False once nothing is left to show:
+/// the first screen closed, or a screen asked for the (absent) dashboard.
+fn standalone_navigate(
+    stack: &mut Vec<Box<dyn Screen>>,
+    nav: Nav,
+    conn: &Connection,
+    session: &mut Session,
+) -> bool {
+    let mut ctx = ScreenContext { conn, session };
+    let next = match nav {
+        Nav::Stay => return true,
+        Nav::Home | Nav::Reload => return false,
+        Nav::Push(mut screen) => {
+            let next = screen.on_enter(&mut ctx);
+            stack.push(screen);
+            next
+        }
+        Nav::Replace(mut screen) => {
+            if let Some(mut top) = stack.pop() {
+                top.on_exit(&mut ctx);
+            }
+            let next = screen.on_enter(&mut ctx);
+            stack.push(screen);
+            next
+        }
+        Nav::Pop => {
+            if let Some(mut top) = stack.pop() {
+                top.on_exit(&mut ctx);
+            }
+            match stack.last_mut() {
+                Some(top) => top.on_enter(&mut ctx),
+                None => return false,
+            }
+        }
+    };
+    standalone_navigate(stack, next, conn, session)
+}
+
+/// Hand the changes the top screen published to the screens beneath it.
+fn deliver_changes(stack: &mut [Box<dyn Screen>], conn: &Connection, session: &mut Session) {
+    let changes = std::mem::take(&mut session.changes);
+    let beneath = stack.len().saturating_sub(1);
+    for screen in &mut stack[..beneath] {
+        for &change in &changes {
+            screen.on_data_changed(change, &mut ScreenContext { conn, session });
+        }
+    }
+}
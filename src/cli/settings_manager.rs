@@ -8,39 +8,107 @@ use ratatui::{
 };
 use rusqlite::Connection;
 
+use crate::cli::dashboard_widgets::WidgetToggles;
 use crate::cli::password_manager::{PasswordAction, PasswordManager};
+use crate::cli::report::defaults::{self as report_defaults, Field};
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
+use crate::compensation;
 use crate::db;
+use crate::deductions;
 use crate::error::Result;
-use crate::settings::{get_data_dir, load_settings, save_settings};
+use crate::fiscal::{self, FiscalYear};
+use crate::fmt::{self, money, DisplayFormat, DATE_FORMATS, DECIMAL_SEPARATORS};
+use crate::locales::{self, LOCALES};
+use crate::settings::{
+    get_data_dir, load_settings, save_settings, shellexpand_path, Persona, Settings,
+    BACKUP_REMINDER_RANGE, EXPORT_FORMATS, SNAPSHOT_KEEP_RANGE, TRASH_RETENTION_RANGE,
+};
 use crate::tui::{FOOTER_STYLE, HEADER_STYLE, SELECTED_STYLE};
 
 pub enum SettingsAction {
     Continue,
     Close,
+    /// The data directory was switched; the dashboard must reopen the book.
+    Reload,
 }
 
 enum Screen {
     Main,
-    EditingName,
+    /// Editing a text field: business name, your name, data directory, or
+    /// currency symbol (menu index of the row).
+    EditingText(usize),
+    /// Editing a numeric field: backup policy, home office, or comp target
+    /// (menu index of the row).
+    EditingNumber(usize),
     Password(PasswordManager),
+    Widgets {
+        selection: usize,
+    },
+    /// Per-report defaults (row in `defaults::REPORTS`).
+    ReportDefaults {
+        selection: usize,
+    },
 }
 
 /// Menu items on the main settings screen.
 const MENU_BUSINESS_NAME: usize = 0;
-const MENU_PASSWORD: usize = 1;
-const MENU_UPDATE_CHECK: usize = 2;
-const MENU_LAST: usize = MENU_UPDATE_CHECK;
+const MENU_USER_NAME: usize = 1;
+const MENU_DATA_DIR: usize = 2;
+const MENU_PASSWORD: usize = 3;
+const MENU_UPDATE_CHECK: usize = 4;
+const MENU_KEEP_STATEMENTS: usize = 5;
+const MENU_GUSTO_DETAILED: usize = 6;
+const MENU_ANOMALY_CHECKS: usize = 7;
+const MENU_SIMPLE_MODE: usize = 8;
+const MENU_PERSONA: usize = 9;
+const MENU_LANGUAGE: usize = 10;
+const MENU_CURRENCY_SYMBOL: usize = 11;
+const MENU_DECIMAL_SEPARATOR: usize = 12;
+const MENU_DATE_FORMAT: usize = 13;
+const MENU_DASHBOARD_WIDGETS: usize = 14;
+const MENU_EXPORT_FORMAT: usize = 15;
+const MENU_REPORT_DEFAULTS: usize = 16;
+const MENU_SNAPSHOT_KEEP: usize = 17;
+const MENU_BACKUP_REMINDER: usize = 18;
+const MENU_TRASH_RETENTION: usize = 19;
+const MENU_HOME_OFFICE_PCT: usize = 20;
+const MENU_HOME_OFFICE_COSTS: usize = 21;
+const MENU_FISCAL_START: usize = 22;
+const MENU_COMP_TARGET: usize = 23;
+const MENU_LAST: usize = MENU_COMP_TARGET;
 
 pub struct SettingsManager {
     greeting: String,
     screen: Screen,
     selection: usize,
     company_name: String,
+    user_name: String,
+    data_dir: String,
+    /// Set once the data directory is switched; leaving the screen reloads.
+    data_dir_changed: bool,
+    /// Set by a save, until the dashboard is told.
+    changed: bool,
     edit_buffer: String,
     status_message: Option<(String, bool)>,
     status_ttl: u8,
     encrypted: bool,
     update_check: bool,
+    keep_statements: bool,
+    gusto_detailed: bool,
+    anomaly_checks: bool,
+    simple_mode: bool,
+    persona: Persona,
+    locale: String,
+    display: DisplayFormat,
+    widgets: WidgetToggles,
+    export_format: String,
+    snapshot_keep: usize,
+    backup_reminder_days: i64,
+    trash_retention_days: i64,
+    home_office_pct: f64,
+    home_office_costs: f64,
+    fiscal: FiscalYear,
+    comp_target_pct: f64,
 }
 
 impl SettingsManager {
@@ -49,16 +117,37 @@ impl SettingsManager {
         let db_path = get_data_dir().join("nigel.db");
         let encrypted = db::is_encrypted(&db_path)?;
         let settings = load_settings();
+        let home_office = deductions::home_office(conn);
         Ok(Self {
             greeting: greeting.to_string(),
             screen: Screen::Main,
             selection: 0,
             company_name,
+            user_name: settings.user_name.clone(),
+            data_dir: settings.data_dir.clone(),
+            data_dir_changed: false,
+            changed: false,
             edit_buffer: String::new(),
             status_message: None,
             status_ttl: 0,
             encrypted,
             update_check: settings.update_check,
+            keep_statements: settings.keep_statements,
+            gusto_detailed: settings.gusto_detailed,
+            anomaly_checks: settings.anomaly_checks,
+            simple_mode: settings.simple_mode,
+            persona: settings.persona(),
+            locale: settings.locale.clone(),
+            display: DisplayFormat::from_settings(&settings),
+            widgets: WidgetToggles::from_keys(&settings.dashboard_widgets),
+            export_format: settings.export_format().to_string(),
+            snapshot_keep: settings.snapshot_keep(),
+            backup_reminder_days: settings.backup_reminder_days(),
+            trash_retention_days: settings.trash_retention_days(),
+            home_office_pct: home_office.percent,
+            home_office_costs: home_office.annual_costs,
+            fiscal: FiscalYear::load(conn),
+            comp_target_pct: compensation::target_pct(conn),
         })
     }
 
@@ -79,8 +168,10 @@ impl SettingsManager {
     pub fn draw(&self, frame: &mut Frame) {
         match &self.screen {
             Screen::Main => self.draw_main(frame),
-            Screen::EditingName => self.draw_main(frame),
+            Screen::EditingText(_) | Screen::EditingNumber(_) => self.draw_main(frame),
             Screen::Password(mgr) => mgr.draw(frame),
+            Screen::Widgets { selection } => self.draw_widgets(frame, *selection),
+            Screen::ReportDefaults { selection } => self.draw_report_defaults(frame, *selection),
         }
     }
 
@@ -104,6 +195,32 @@ impl SettingsManager {
         ])
     }
 
+    /// A main-screen row, showing the edit buffer while that row is being edited.
+    fn row(&self, idx: usize, label: &str, value: &str) -> Line<'static> {
+        let editing = matches!(
+            self.screen,
+            Screen::EditingText(i) | Screen::EditingNumber(i) if i == idx
+        );
+        if !editing {
+            return Self::menu_row(label, value, self.selection == idx);
+        }
+        Line::from(vec![
+            Span::styled(
+                format!(" > {label:<17}"),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("{}_", self.edit_buffer), SELECTED_STYLE),
+        ])
+    }
+
+    fn enabled_label(on: bool) -> &'static str {
+        if on {
+            "(enabled)"
+        } else {
+            "(disabled)"
+        }
+    }
+
     fn draw_main(&self, frame: &mut Frame) {
         let area = frame.area();
         let border_style = Style::default().fg(Color::DarkGray);
@@ -118,7 +235,7 @@ impl SettingsManager {
 
         // Header
         frame.render_widget(
-            Paragraph::new(format!(" Nigel: {}", self.greeting)).style(HEADER_STYLE),
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
             header_area,
         );
 
@@ -135,56 +252,155 @@ impl SettingsManager {
             Line::from(""),
         ];
 
-        // Business Name row
-        let name_selected = self.selection == MENU_BUSINESS_NAME;
-        if let Screen::EditingName = &self.screen {
-            let marker = if name_selected { ">" } else { " " };
-            let label_style = if name_selected {
-                Style::default().add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
-            lines.push(Line::from(vec![
-                Span::styled(format!(" {marker} Business Name    "), label_style),
-                Span::styled(format!("{}_", self.edit_buffer), SELECTED_STYLE),
-            ]));
+        // Who and where: business name, your name, data directory
+        let business = if self.company_name.is_empty() {
+            "(not set)"
         } else {
-            let display_name = if self.company_name.is_empty() {
-                "(not set)"
-            } else {
-                &self.company_name
-            };
-            lines.push(Self::menu_row("Business Name", display_name, name_selected));
-        }
+            &self.company_name
+        };
+        let user = if self.user_name.is_empty() {
+            "(not set)"
+        } else {
+            &self.user_name
+        };
+        lines.push(self.row(MENU_BUSINESS_NAME, "Business Name", business));
+        lines.push(self.row(MENU_USER_NAME, "Your name", user));
+        lines.push(self.row(MENU_DATA_DIR, "Data directory", &self.data_dir));
 
         lines.push(Line::from(""));
 
-        // Password section
+        // Password and feature toggles
         let pw_status = if self.encrypted {
             "(encrypted)"
         } else {
             "(not set)"
         };
-        lines.push(Self::menu_row(
-            "Password",
-            pw_status,
-            self.selection == MENU_PASSWORD,
+        lines.push(self.row(MENU_PASSWORD, "Password", pw_status));
+        lines.push(self.row(
+            MENU_UPDATE_CHECK,
+            "Auto-update check",
+            Self::enabled_label(self.update_check),
+        ));
+        lines.push(self.row(
+            MENU_KEEP_STATEMENTS,
+            "Keep statements",
+            Self::enabled_label(self.keep_statements),
+        ));
+        lines.push(self.row(
+            MENU_GUSTO_DETAILED,
+            "Detailed payroll",
+            Self::enabled_label(self.gusto_detailed),
+        ));
+        lines.push(self.row(
+            MENU_ANOMALY_CHECKS,
+            "Flag unusual txns",
+            Self::enabled_label(self.anomaly_checks),
+        ));
+        lines.push(self.row(
+            MENU_SIMPLE_MODE,
+            "Simple mode",
+            Self::enabled_label(self.simple_mode),
+        ));
+        // Persona flavor (greeting and goodbye screen)
+        lines.push(self.row(
+            MENU_PERSONA,
+            "Persona",
+            &format!("({})", self.persona.key()),
+        ));
+        let language = LOCALES
+            .iter()
+            .find(|l| l.key == self.locale)
+            .map_or(self.locale.as_str(), |l| l.name);
+        lines.push(self.row(MENU_LANGUAGE, "Language", &format!("({language})")));
+        lines.push(self.row(
+            MENU_CURRENCY_SYMBOL,
+            "Currency symbol",
+            &self.display.currency_symbol,
+        ));
+        lines.push(self.row(
+            MENU_DECIMAL_SEPARATOR,
+            "Decimal separator",
+            &format!("({})", self.display.money(1234.56)),
+        ));
+        lines.push(self.row(
+            MENU_DATE_FORMAT,
+            "Date format",
+            &format!("({})", self.display.date_format),
+        ));
+        let shown = self.widgets.items.iter().filter(|(_, on)| *on).count();
+        lines.push(self.row(
+            MENU_DASHBOARD_WIDGETS,
+            "Dashboard widgets",
+            &format!("({shown} of {} shown)", self.widgets.items.len()),
         ));
 
         lines.push(Line::from(""));
 
-        // Update check toggle
-        let uc_status = if self.update_check {
-            "(enabled)"
+        // Exports and backup policy
+        lines.push(self.row(
+            MENU_EXPORT_FORMAT,
+            "Export format",
+            &format!("({})", self.export_format),
+        ));
+        lines.push(self.row(
+            MENU_REPORT_DEFAULTS,
+            "Report defaults",
+            &format!("({} set)", load_settings().report_defaults.len()),
+        ));
+        lines.push(self.row(
+            MENU_SNAPSHOT_KEEP,
+            "Snapshots kept",
+            &format!("{} per operation", self.snapshot_keep),
+        ));
+        lines.push(self.row(
+            MENU_BACKUP_REMINDER,
+            "Backup reminder",
+            &format!("after {} days", self.backup_reminder_days),
+        ));
+        lines.push(self.row(
+            MENU_TRASH_RETENTION,
+            "Trash kept",
+            &format!("{} days", self.trash_retention_days),
+        ));
+
+        lines.push(Line::from(""));
+
+        // Home office allocation (feeds `nigel report deductions` and K-1 prep)
+        let pct = if self.home_office_pct > 0.0 {
+            format!("{}%", self.home_office_pct)
         } else {
-            "(disabled)"
+            "(not set)".to_string()
         };
-        lines.push(Self::menu_row(
-            "Auto-update check",
-            uc_status,
-            self.selection == MENU_UPDATE_CHECK,
+        let costs = if self.home_office_costs > 0.0 {
+            money(self.home_office_costs)
+        } else {
+            "(not set)".to_string()
+        };
+        lines.push(self.row(MENU_HOME_OFFICE_PCT, "Home office %", &pct));
+        lines.push(self.row(MENU_HOME_OFFICE_COSTS, "Home costs/yr", &costs));
+
+        lines.push(Line::from(""));
+
+        // Fiscal year start (reports, YTD figures, and year navigation)
+        let current_fy = self.fiscal.current();
+        lines.push(self.row(
+            MENU_FISCAL_START,
+            "Fiscal year",
+            &format!(
+                "starts {} ({})",
+                fiscal::month_abbr(self.fiscal.start_month()),
+                self.fiscal.label(current_fy)
+            ),
         ));
 
+        // Minimum officer-pay share before compensation warnings fire
+        let comp = if self.comp_target_pct > 0.0 {
+            format!("{}% of owner pay", self.comp_target_pct)
+        } else {
+            "(off)".to_string()
+        };
+        lines.push(self.row(MENU_COMP_TARGET, "Officer pay target", &comp));
+
         // Status message
         if let Some((msg, success)) = &self.status_message {
             lines.push(Line::from(""));
@@ -199,7 +415,7 @@ impl SettingsManager {
 
         // Hints
         let hints = match &self.screen {
-            Screen::EditingName => "Enter=save  Esc=cancel",
+            Screen::EditingText(_) | Screen::EditingNumber(_) => "Enter=save  Esc=cancel",
             _ => "Enter=select  Esc=back  q=quit",
         };
         frame.render_widget(
@@ -208,12 +424,136 @@ impl SettingsManager {
         );
     }
 
+    fn draw_widgets(&self, frame: &mut Frame, selection: usize) {
+        let area = frame.area();
+        let border_style = Style::default().fg(Color::DarkGray);
+
+        let [header_area, sep, content_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
+            header_area,
+        );
+
+        let sep_line = "\u{2501}".repeat(area.width as usize);
+        frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                " Dashboard Widgets",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                " Shown widgets fill the dashboard two per row, top to bottom.",
+                FOOTER_STYLE,
+            )),
+            Line::from(""),
+        ];
+        for (i, (widget, enabled)) in self.widgets.items.iter().enumerate() {
+            let check = if *enabled { "[x]" } else { "[ ]" };
+            lines.push(Self::menu_row(widget.label(), check, i == selection));
+        }
+
+        if let Some((msg, success)) = &self.status_message {
+            lines.push(Line::from(""));
+            let color = if *success { Color::Green } else { Color::Red };
+            lines.push(Line::from(Span::styled(
+                format!("   {msg}"),
+                Style::default().fg(color),
+            )));
+        }
+
+        frame.render_widget(Paragraph::new(lines), content_area);
+        frame.render_widget(
+            Paragraph::new(" Enter/Space=show/hide  [/]=move up/down  Esc=back")
+                .style(FOOTER_STYLE),
+            hints_area,
+        );
+    }
+
+    fn draw_report_defaults(&self, frame: &mut Frame, selection: usize) {
+        let area = frame.area();
+        let border_style = Style::default().fg(Color::DarkGray);
+
+        let [header_area, sep, content_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
+            header_area,
+        );
+
+        let sep_line = "\u{2501}".repeat(area.width as usize);
+        frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                " Report Defaults",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                " Used when a report is run without --month/--year, --format, or --account.",
+                FOOTER_STYLE,
+            )),
+            Line::from(""),
+        ];
+        let defaults = load_settings().report_defaults;
+        for (i, report) in report_defaults::REPORTS.iter().enumerate() {
+            let parts: Vec<String> = [Field::Period, Field::Format, Field::Account]
+                .into_iter()
+                .filter_map(|field| report_defaults::get(&defaults, report, field))
+                .collect();
+            let value = if parts.is_empty() {
+                "(none)".to_string()
+            } else {
+                parts.join(", ")
+            };
+            lines.push(Self::menu_row(report, &value, i == selection));
+        }
+
+        if let Some((msg, success)) = &self.status_message {
+            lines.push(Line::from(""));
+            let color = if *success { Color::Green } else { Color::Red };
+            lines.push(Line::from(Span::styled(
+                format!("   {msg}"),
+                Style::default().fg(color),
+            )));
+        }
+
+        frame.render_widget(Paragraph::new(lines), content_area);
+        frame.render_widget(
+            Paragraph::new(" p=period  f=format  a=account (register)  Esc=back")
+                .style(FOOTER_STYLE),
+            hints_area,
+        );
+    }
+
     pub fn handle_key(&mut self, code: KeyCode, conn: &Connection) -> SettingsAction {
         self.tick_status();
 
         match &mut self.screen {
             Screen::Main => self.handle_main_key(code, conn),
-            Screen::EditingName => self.handle_edit_name_key(code, conn),
+            Screen::EditingText(idx) => {
+                let idx = *idx;
+                self.handle_edit_text_key(code, idx, conn)
+            }
+            Screen::EditingNumber(idx) => {
+                let idx = *idx;
+                self.handle_edit_number_key(code, idx, conn)
+            }
             Screen::Password(mgr) => {
                 match mgr.handle_key(code) {
                     PasswordAction::Close => {
@@ -235,11 +575,132 @@ impl SettingsManager {
                 }
                 SettingsAction::Continue
             }
+            Screen::Widgets { selection } => {
+                let selection = *selection;
+                self.handle_widgets_key(code, selection)
+            }
+            Screen::ReportDefaults { selection } => {
+                let selection = *selection;
+                self.handle_report_defaults_key(code, selection, conn)
+            }
+        }
+    }
+
+    fn handle_report_defaults_key(
+        &mut self,
+        code: KeyCode,
+        selection: usize,
+        conn: &Connection,
+    ) -> SettingsAction {
+        let report = report_defaults::REPORTS[selection];
+        let (field, options): (Field, Vec<String>) = match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.screen = Screen::Main;
+                return SettingsAction::Continue;
+            }
+            KeyCode::Up => {
+                self.screen = Screen::ReportDefaults {
+                    selection: selection.saturating_sub(1),
+                };
+                return SettingsAction::Continue;
+            }
+            KeyCode::Down => {
+                self.screen = Screen::ReportDefaults {
+                    selection: (selection + 1).min(report_defaults::REPORTS.len() - 1),
+                };
+                return SettingsAction::Continue;
+            }
+            KeyCode::Char('p') => (
+                Field::Period,
+                report_defaults::periods(report)
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect(),
+            ),
+            KeyCode::Char('f') => (
+                Field::Format,
+                report_defaults::formats(report)
+                    .iter()
+                    .map(|f| f.to_string())
+                    .collect(),
+            ),
+            KeyCode::Char('a') if report_defaults::takes_account(report) => {
+                match crate::cli::accounts::account_names(conn) {
+                    Ok(names) => (Field::Account, names),
+                    Err(e) => {
+                        self.set_status(format!("Could not load accounts: {e}"), false);
+                        return SettingsAction::Continue;
+                    }
+                }
+            }
+            _ => return SettingsAction::Continue,
+        };
+        if options.is_empty() {
+            let what = if field == Field::Period {
+                "period"
+            } else {
+                "format"
+            };
+            self.set_status(format!("The {report} report doesn't take a {what}."), false);
+            return SettingsAction::Continue;
+        }
+        // Step through the options, then back to no default
+        let mut defaults = load_settings().report_defaults;
+        let current = report_defaults::get(&defaults, report, field);
+        let next = match current.and_then(|c| options.iter().position(|o| *o == c)) {
+            None => options.first(),
+            Some(i) => options.get(i + 1),
+        };
+        if let Err(e) = report_defaults::set(&mut defaults, report, field, next.map(String::as_str))
+        {
+            self.set_status(e.to_string(), false);
+            return SettingsAction::Continue;
+        }
+        if self.persist(|s| s.report_defaults = defaults) {
+            let shown = next.map_or("none", String::as_str);
+            self.set_status(format!("report.{report}.{} = {shown}", field.key()), true);
+        }
+        SettingsAction::Continue
+    }
+
+    fn handle_widgets_key(&mut self, code: KeyCode, selection: usize) -> SettingsAction {
+        let last = self.widgets.items.len().saturating_sub(1);
+        let previous = self.widgets.items.clone();
+        let new_selection = match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.screen = Screen::Main;
+                return SettingsAction::Continue;
+            }
+            KeyCode::Up => selection.saturating_sub(1),
+            KeyCode::Down => (selection + 1).min(last),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                self.widgets.toggle(selection);
+                selection
+            }
+            KeyCode::Char('[') => self.widgets.move_up(selection),
+            KeyCode::Char(']') => self.widgets.move_down(selection),
+            _ => selection,
+        };
+        if self.widgets.items != previous {
+            let mut settings = load_settings();
+            settings.dashboard_widgets = self.widgets.enabled_keys();
+            if let Err(e) = save_settings(&settings) {
+                // Revert on save failure
+                self.widgets.items = previous;
+                self.set_status(format!("Could not save setting: {e}"), false);
+                return SettingsAction::Continue;
+            }
+            self.changed = true;
         }
+        self.screen = Screen::Widgets {
+            selection: new_selection,
+        };
+        SettingsAction::Continue
     }
 
-    fn handle_main_key(&mut self, code: KeyCode, _conn: &Connection) -> SettingsAction {
+    fn handle_main_key(&mut self, code: KeyCode, conn: &Connection) -> SettingsAction {
         match code {
+            KeyCode::Esc | KeyCode::Char('q') if self.data_dir_changed => SettingsAction::Reload,
             KeyCode::Esc | KeyCode::Char('q') => SettingsAction::Close,
             KeyCode::Up => {
                 self.selection = self.selection.saturating_sub(1);
@@ -251,9 +712,14 @@ impl SettingsManager {
             }
             KeyCode::Enter => {
                 match self.selection {
-                    MENU_BUSINESS_NAME => {
-                        self.edit_buffer = self.company_name.clone();
-                        self.screen = Screen::EditingName;
+                    MENU_BUSINESS_NAME | MENU_USER_NAME | MENU_DATA_DIR | MENU_CURRENCY_SYMBOL => {
+                        self.edit_buffer = match self.selection {
+                            MENU_BUSINESS_NAME => self.company_name.clone(),
+                            MENU_USER_NAME => self.user_name.clone(),
+                            MENU_CURRENCY_SYMBOL => self.display.currency_symbol.clone(),
+                            _ => self.data_dir.clone(),
+                        };
+                        self.screen = Screen::EditingText(self.selection);
                     }
                     MENU_PASSWORD => match PasswordManager::new(&self.greeting) {
                         Ok(mgr) => self.screen = Screen::Password(mgr),
@@ -261,24 +727,93 @@ impl SettingsManager {
                             self.set_status(format!("Could not open password settings: {e}"), false)
                         }
                     },
+                    MENU_DASHBOARD_WIDGETS => {
+                        self.screen = Screen::Widgets { selection: 0 };
+                    }
+                    MENU_REPORT_DEFAULTS => {
+                        self.screen = Screen::ReportDefaults { selection: 0 };
+                    }
+                    MENU_PERSONA => self.cycle_persona(),
+                    MENU_LANGUAGE => self.cycle_locale(),
+                    MENU_DECIMAL_SEPARATOR => self.cycle_decimal_separator(),
+                    MENU_DATE_FORMAT => self.cycle_date_format(),
+                    MENU_EXPORT_FORMAT => self.cycle_export_format(),
+                    MENU_FISCAL_START => self.cycle_fiscal_start(conn),
+                    MENU_SNAPSHOT_KEEP
+                    | MENU_BACKUP_REMINDER
+                    | MENU_TRASH_RETENTION
+                    | MENU_HOME_OFFICE_PCT
+                    | MENU_HOME_OFFICE_COSTS
+                    | MENU_COMP_TARGET => {
+                        let current = match self.selection {
+                            MENU_SNAPSHOT_KEEP => self.snapshot_keep as f64,
+                            MENU_BACKUP_REMINDER => self.backup_reminder_days as f64,
+                            MENU_TRASH_RETENTION => self.trash_retention_days as f64,
+                            MENU_HOME_OFFICE_PCT => self.home_office_pct,
+                            MENU_HOME_OFFICE_COSTS => self.home_office_costs,
+                            _ => self.comp_target_pct,
+                        };
+                        self.edit_buffer = if current > 0.0 {
+                            current.to_string()
+                        } else {
+                            String::new()
+                        };
+                        self.screen = Screen::EditingNumber(self.selection);
+                    }
                     MENU_UPDATE_CHECK => {
-                        self.update_check = !self.update_check;
-                        let mut settings = load_settings();
-                        settings.update_check = self.update_check;
-                        match save_settings(&settings) {
-                            Ok(()) => {
-                                let state = if self.update_check {
-                                    "enabled"
-                                } else {
-                                    "disabled"
-                                };
-                                self.set_status(format!("Auto-update check {state}."), true);
-                            }
-                            Err(e) => {
-                                // Revert on save failure
-                                self.update_check = !self.update_check;
-                                self.set_status(format!("Could not save setting: {e}"), false);
-                            }
+                        let on = !self.update_check;
+                        if self.persist(|s| s.update_check = on) {
+                            self.update_check = on;
+                            let state = if on { "enabled" } else { "disabled" };
+                            self.set_status(format!("Auto-update check {state}."), true);
+                        }
+                    }
+                    MENU_KEEP_STATEMENTS => {
+                        let on = !self.keep_statements;
+                        if self.persist(|s| s.keep_statements = on) {
+                            self.keep_statements = on;
+                            let msg = if on {
+                                "Imported statements will be kept in the data directory."
+                            } else {
+                                "Imported statements will no longer be kept."
+                            };
+                            self.set_status(msg.into(), true);
+                        }
+                    }
+                    MENU_GUSTO_DETAILED => {
+                        let on = !self.gusto_detailed;
+                        if self.persist(|s| s.gusto_detailed = on) {
+                            self.gusto_detailed = on;
+                            let msg = if on {
+                                "Gusto imports will split out withholding, deductions, and reimbursements."
+                            } else {
+                                "Gusto imports will record wages and employer taxes only."
+                            };
+                            self.set_status(msg.into(), true);
+                        }
+                    }
+                    MENU_ANOMALY_CHECKS => {
+                        let on = !self.anomaly_checks;
+                        if self.persist(|s| s.anomaly_checks = on) {
+                            self.anomaly_checks = on;
+                            let msg = if on {
+                                "Likely double charges and unusual amounts will be flagged."
+                            } else {
+                                "Unusual transactions will no longer be flagged."
+                            };
+                            self.set_status(msg.into(), true);
+                        }
+                    }
+                    MENU_SIMPLE_MODE => {
+                        let on = !self.simple_mode;
+                        if self.persist(|s| s.simple_mode = on) {
+                            self.simple_mode = on;
+                            let msg = if on {
+                                "Reconciliation, K-1 prep, tags, and rule priorities are hidden."
+                            } else {
+                                "All features are shown."
+                            };
+                            self.set_status(msg.into(), true);
                         }
                     }
                     _ => {}
@@ -289,27 +824,309 @@ impl SettingsManager {
         }
     }
 
-    fn handle_edit_name_key(&mut self, code: KeyCode, conn: &Connection) -> SettingsAction {
+    /// Apply `update` to the settings on disk and save. On failure the status
+    /// line shows the error and false is returned so the caller keeps its
+    /// previous value.
+    fn persist(&mut self, update: impl FnOnce(&mut Settings)) -> bool {
+        let mut settings = load_settings();
+        update(&mut settings);
+        match save_settings(&settings) {
+            Ok(()) => {
+                self.changed = true;
+                true
+            }
+            Err(e) => {
+                self.set_status(format!("Could not save setting: {e}"), false);
+                false
+            }
+        }
+    }
+
+    /// Step through `EXPORT_FORMATS` and persist.
+    fn cycle_export_format(&mut self) {
+        let pos = EXPORT_FORMATS
+            .iter()
+            .position(|f| *f == self.export_format)
+            .unwrap_or(0);
+        let next = EXPORT_FORMATS[(pos + 1) % EXPORT_FORMATS.len()];
+        if self.persist(|s| s.export_format = next.to_string()) {
+            self.export_format = next.to_string();
+            let note = if matches!(next, "csv" | "xlsx") {
+                " (register only; other reports use pdf)"
+            } else {
+                ""
+            };
+            self.set_status(format!("Reports now export as {next}{note}."), true);
+        }
+    }
+
+    /// Step full -> minimal -> off -> full and persist. The dashboard header
+    /// is built at startup, so the change shows on the next launch.
+    fn cycle_persona(&mut self) {
+        let pos = Persona::ALL
+            .iter()
+            .position(|p| *p == self.persona)
+            .unwrap_or(0);
+        let next = Persona::ALL[(pos + 1) % Persona::ALL.len()];
+        let mut settings = load_settings();
+        settings.persona = next.key().to_string();
+        match save_settings(&settings) {
+            Ok(()) => {
+                self.changed = true;
+                self.persona = next;
+                self.set_status(
+                    format!("Persona set to {} (applies next launch).", next.key()),
+                    true,
+                );
+            }
+            Err(e) => self.set_status(format!("Could not save setting: {e}"), false),
+        }
+    }
+
+    /// Step through the built-in locales and persist. Messages switch at
+    /// once. A translation that isn't built in is picked by setting
+    /// `locale` in settings.json.
+    fn cycle_locale(&mut self) {
+        let pos = LOCALES
+            .iter()
+            .position(|l| l.key == self.locale)
+            .map_or(0, |p| p + 1);
+        let next = &LOCALES[pos % LOCALES.len()];
+        if let Err(e) = locales::set_locale(next.key) {
+            self.set_status(e.to_string(), false);
+            return;
+        }
+        if self.persist(|s| s.locale = next.key.to_string()) {
+            self.locale = next.key.to_string();
+            self.set_status(format!("Language set to {}.", next.name), true);
+        }
+    }
+
+    /// Save the number and date settings and switch the display format, so
+    /// amounts and dates change at once.
+    fn persist_display(&mut self, display: DisplayFormat, status: String) {
+        let saved = self.persist(|s| {
+            s.currency_symbol = display.currency_symbol.clone();
+            s.decimal_separator = display.decimal_separator.to_string();
+            s.date_format = display.date_format.to_string();
+        });
+        if saved {
+            fmt::set_display_format(display.clone());
+            self.display = display;
+            self.set_status(status, true);
+        }
+    }
+
+    /// Step through `DECIMAL_SEPARATORS`; the thousands separator follows.
+    fn cycle_decimal_separator(&mut self) {
+        let pos = DECIMAL_SEPARATORS
+            .iter()
+            .position(|c| *c == self.display.decimal_separator)
+            .unwrap_or(0);
+        let display = DisplayFormat {
+            decimal_separator: DECIMAL_SEPARATORS[(pos + 1) % DECIMAL_SEPARATORS.len()],
+            ..self.display.clone()
+        };
+        let status = format!("Amounts now show as {}.", display.money(1234.56));
+        self.persist_display(display, status);
+    }
+
+    /// Step through `DATE_FORMATS`. Stored dates stay YYYY-MM-DD.
+    fn cycle_date_format(&mut self) {
+        let pos = DATE_FORMATS
+            .iter()
+            .position(|f| *f == self.display.date_format)
+            .unwrap_or(0);
+        let display = DisplayFormat {
+            date_format: DATE_FORMATS[(pos + 1) % DATE_FORMATS.len()],
+            ..self.display.clone()
+        };
+        let status = format!("Dates now show as {}.", display.date_format);
+        self.persist_display(display, status);
+    }
+
+    /// Advance the fiscal year start by one month (Dec wraps to Jan).
+    fn cycle_fiscal_start(&mut self, conn: &Connection) {
+        let next = self.fiscal.start_month() % 12 + 1;
+        match fiscal::set_start_month(conn, next) {
+            Ok(()) => {
+                self.changed = true;
+                self.fiscal = FiscalYear::new(next);
+                self.set_status(
+                    format!("Fiscal year now starts in {}.", fiscal::month_abbr(next)),
+                    true,
+                );
+            }
+            Err(e) => self.set_status(format!("Could not save setting: {e}"), false),
+        }
+    }
+
+    fn handle_edit_text_key(
+        &mut self,
+        code: KeyCode,
+        idx: usize,
+        conn: &Connection,
+    ) -> SettingsAction {
         match code {
             KeyCode::Esc => {
                 self.edit_buffer.clear();
                 self.screen = Screen::Main;
             }
             KeyCode::Enter => {
-                let new_name = self.edit_buffer.trim().to_string();
-                match db::set_metadata(conn, "company_name", &new_name) {
-                    Ok(()) => {
-                        self.company_name = new_name;
-                        self.set_status("Business name saved.".into(), true);
-                    }
-                    Err(e) => {
-                        self.set_status(format!("Could not save business name: {e}"), false);
-                    }
+                let value = self.edit_buffer.trim().to_string();
+                let saved = match idx {
+                    MENU_BUSINESS_NAME => self.save_business_name(conn, value),
+                    MENU_USER_NAME => self.save_user_name(value),
+                    MENU_CURRENCY_SYMBOL => self.save_currency_symbol(value),
+                    _ => self.switch_data_dir(&value),
+                };
+                if saved {
+                    self.edit_buffer.clear();
+                    self.screen = Screen::Main;
                 }
+            }
+            KeyCode::Char(c) => {
+                self.edit_buffer.push(c);
+            }
+            KeyCode::Backspace => {
+                self.edit_buffer.pop();
+            }
+            _ => {}
+        }
+        SettingsAction::Continue
+    }
+
+    fn save_business_name(&mut self, conn: &Connection, name: String) -> bool {
+        match db::set_metadata(conn, "company_name", &name) {
+            Ok(()) => {
+                self.changed = true;
+                self.company_name = name;
+                self.set_status("Business name saved.".into(), true);
+            }
+            Err(e) => {
+                self.set_status(format!("Could not save business name: {e}"), false);
+            }
+        }
+        true
+    }
+
+    /// The dashboard greeting is built at startup, so the new name shows on
+    /// the next launch.
+    fn save_user_name(&mut self, name: String) -> bool {
+        if self.persist(|s| s.user_name = name.clone()) {
+            self.user_name = name;
+            self.set_status("Name saved (greeting updates next launch).".into(), true);
+        }
+        true
+    }
+
+    fn save_currency_symbol(&mut self, symbol: String) -> bool {
+        if symbol.is_empty() {
+            self.set_status("Currency symbol is required".into(), false);
+            return false;
+        }
+        let display = DisplayFormat {
+            currency_symbol: symbol,
+            ..self.display.clone()
+        };
+        let status = format!("Amounts now show as {}.", display.money(1234.56));
+        self.persist_display(display, status);
+        true
+    }
+
+    /// Point settings at another existing data directory, as the Load screen
+    /// does. Returns false (staying in the editor) when there is no book there.
+    fn switch_data_dir(&mut self, path: &str) -> bool {
+        if path.is_empty() {
+            self.set_status("Path is required".into(), false);
+            return false;
+        }
+        let resolved = shellexpand_path(path);
+        if resolved == self.data_dir {
+            return true;
+        }
+        let db_path = std::path::Path::new(&resolved).join("nigel.db");
+        if !db_path.exists() {
+            self.set_status(format!("No database found at {}", db_path.display()), false);
+            return false;
+        }
+        if self.persist(|s| s.data_dir = resolved.clone()) {
+            self.data_dir = resolved;
+            self.data_dir_changed = true;
+            self.set_status(
+                "Data directory switched; the dashboard reloads when you leave Settings.".into(),
+                true,
+            );
+        }
+        true
+    }
+
+    fn handle_edit_number_key(
+        &mut self,
+        code: KeyCode,
+        idx: usize,
+        conn: &Connection,
+    ) -> SettingsAction {
+        match code {
+            KeyCode::Esc => {
                 self.edit_buffer.clear();
                 self.screen = Screen::Main;
             }
-            KeyCode::Char(c) => {
+            KeyCode::Enter => {
+                let cleaned: String = self
+                    .edit_buffer
+                    .chars()
+                    .filter(|c| !matches!(c, '$' | ',' | '%' | ' '))
+                    .collect();
+                let value = if cleaned.is_empty() {
+                    Ok(0.0)
+                } else {
+                    cleaned.parse::<f64>()
+                };
+                let Ok(value) = value else {
+                    self.set_status(format!("Not a number: {}", self.edit_buffer), false);
+                    return SettingsAction::Continue;
+                };
+                if matches!(
+                    idx,
+                    MENU_SNAPSHOT_KEEP | MENU_BACKUP_REMINDER | MENU_TRASH_RETENTION
+                ) {
+                    if self.save_backup_policy(idx, value) {
+                        self.edit_buffer.clear();
+                        self.screen = Screen::Main;
+                    }
+                    return SettingsAction::Continue;
+                }
+                let result = match idx {
+                    MENU_HOME_OFFICE_PCT => deductions::set_home_office_percent(conn, value),
+                    MENU_HOME_OFFICE_COSTS => deductions::set_home_office_costs(conn, value),
+                    _ => compensation::set_target_pct(conn, value),
+                };
+                match result {
+                    Ok(()) => {
+                        self.changed = true;
+                        let saved = match idx {
+                            MENU_HOME_OFFICE_PCT => {
+                                self.home_office_pct = value;
+                                "Home office settings saved."
+                            }
+                            MENU_HOME_OFFICE_COSTS => {
+                                self.home_office_costs = value;
+                                "Home office settings saved."
+                            }
+                            _ => {
+                                self.comp_target_pct = value;
+                                "Compensation target saved."
+                            }
+                        };
+                        self.set_status(saved.into(), true);
+                        self.edit_buffer.clear();
+                        self.screen = Screen::Main;
+                    }
+                    Err(e) => self.set_status(e.to_string(), false),
+                }
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, '.' | ',' | '$' | '%') => {
                 self.edit_buffer.push(c);
             }
             KeyCode::Backspace => {
@@ -319,6 +1136,81 @@ impl SettingsManager {
         }
         SettingsAction::Continue
     }
+
+    /// Save snapshot retention, the backup reminder interval, or trash
+    /// retention, all whole numbers within their `settings` ranges. Returns
+    /// false on a rejected value.
+    fn save_backup_policy(&mut self, idx: usize, value: f64) -> bool {
+        let (range, unit) = match idx {
+            MENU_SNAPSHOT_KEEP => (
+                *SNAPSHOT_KEEP_RANGE.start() as f64..=*SNAPSHOT_KEEP_RANGE.end() as f64,
+                "snapshots",
+            ),
+            MENU_TRASH_RETENTION => (
+                *TRASH_RETENTION_RANGE.start() as f64..=*TRASH_RETENTION_RANGE.end() as f64,
+                "days",
+            ),
+            _ => (
+                *BACKUP_REMINDER_RANGE.start() as f64..=*BACKUP_REMINDER_RANGE.end() as f64,
+                "days",
+            ),
+        };
+        if value.fract() != 0.0 || !range.contains(&value) {
+            self.set_status(
+                format!(
+                    "Enter a whole number from {} to {} {unit}.",
+                    range.start(),
+                    range.end()
+                ),
+                false,
+            );
+            return false;
+        }
+        if idx == MENU_SNAPSHOT_KEEP {
+            let keep = value as usize;
+            if self.persist(|s| s.snapshot_keep = keep) {
+                self.snapshot_keep = keep;
+                self.set_status(format!("Keeping {keep} snapshots per operation."), true);
+            }
+        } else if idx == MENU_TRASH_RETENTION {
+            let days = value as i64;
+            if self.persist(|s| s.trash_retention_days = days) {
+                self.trash_retention_days = days;
+                self.set_status(
+                    format!("Trashed transactions are deleted after {days} days."),
+                    true,
+                );
+            }
+        } else {
+            let days = value as i64;
+            if self.persist(|s| s.backup_reminder_days = days) {
+                self.backup_reminder_days = days;
+                self.set_status(
+                    format!("Backup reminder after {days} days without a backup."),
+                    true,
+                );
+            }
+        }
+        true
+    }
+}
+
+impl screen::Screen for SettingsManager {
+    fn draw(&mut self, frame: &mut Frame) {
+        SettingsManager::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        let action = SettingsManager::handle_key(self, code, ctx.conn);
+        if std::mem::take(&mut self.changed) {
+            ctx.publish(DataChange::Settings);
+        }
+        Ok(match action {
+            SettingsAction::Close => Nav::Pop,
+            SettingsAction::Reload => Nav::Reload,
+            SettingsAction::Continue => Nav::Stay,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -368,16 +1260,18 @@ mod tests {
         let (_dir, conn) = test_db();
         let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
         assert_eq!(mgr.selection, MENU_BUSINESS_NAME);
+        for expected in MENU_USER_NAME..=MENU_COMP_TARGET {
+            mgr.handle_key(KeyCode::Down, &conn);
+            assert_eq!(mgr.selection, expected);
+        }
         mgr.handle_key(KeyCode::Down, &conn);
-        assert_eq!(mgr.selection, MENU_PASSWORD);
-        mgr.handle_key(KeyCode::Down, &conn);
-        assert_eq!(mgr.selection, MENU_UPDATE_CHECK);
-        mgr.handle_key(KeyCode::Down, &conn);
-        assert_eq!(mgr.selection, MENU_UPDATE_CHECK); // clamped
-        mgr.handle_key(KeyCode::Up, &conn);
-        assert_eq!(mgr.selection, MENU_PASSWORD);
-        mgr.handle_key(KeyCode::Up, &conn);
+        assert_eq!(mgr.selection, MENU_COMP_TARGET); // clamped
+        for _ in 0..MENU_LAST {
+            mgr.handle_key(KeyCode::Up, &conn);
+        }
         assert_eq!(mgr.selection, MENU_BUSINESS_NAME);
+        mgr.handle_key(KeyCode::Up, &conn);
+        assert_eq!(mgr.selection, MENU_BUSINESS_NAME); // clamped
     }
 
     #[test]
@@ -387,7 +1281,10 @@ mod tests {
 
         // Enter edit mode
         mgr.handle_key(KeyCode::Enter, &conn);
-        assert!(matches!(mgr.screen, Screen::EditingName));
+        assert!(matches!(
+            mgr.screen,
+            Screen::EditingText(MENU_BUSINESS_NAME)
+        ));
 
         // Type a name
         for c in "Test Corp".chars() {
@@ -446,7 +1343,9 @@ mod tests {
         let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
 
         // Navigate to password
-        mgr.handle_key(KeyCode::Down, &conn);
+        for _ in 0..MENU_PASSWORD {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
         assert_eq!(mgr.selection, MENU_PASSWORD);
 
         // Enter password manager
@@ -495,7 +1394,10 @@ mod tests {
         let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
 
         mgr.handle_key(KeyCode::Enter, &conn);
-        assert!(matches!(mgr.screen, Screen::EditingName));
+        assert!(matches!(
+            mgr.screen,
+            Screen::EditingText(MENU_BUSINESS_NAME)
+        ));
         assert_eq!(mgr.edit_buffer, "Existing Corp");
     }
 
@@ -530,8 +1432,9 @@ mod tests {
         assert!(mgr.update_check);
 
         // Navigate to update check menu item
-        mgr.handle_key(KeyCode::Down, &conn);
-        mgr.handle_key(KeyCode::Down, &conn);
+        for _ in 0..MENU_UPDATE_CHECK {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
         assert_eq!(mgr.selection, MENU_UPDATE_CHECK);
 
         // Toggle off
@@ -543,6 +1446,39 @@ mod tests {
         assert!(mgr.update_check);
     }
 
+    #[test]
+    fn cycle_persona_wraps() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+        let start = mgr.persona;
+
+        for _ in 0..MENU_PERSONA {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert_ne!(mgr.persona, start);
+        mgr.handle_key(KeyCode::Enter, &conn);
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert_eq!(mgr.persona, start);
+    }
+
+    #[test]
+    fn cycle_fiscal_start_saves_to_metadata() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+        for _ in 0..MENU_FISCAL_START {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert_eq!(FiscalYear::load(&conn).start_month(), 2);
+
+        fiscal::set_start_month(&conn, 12).unwrap();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+        mgr.selection = MENU_FISCAL_START;
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert_eq!(FiscalYear::load(&conn), FiscalYear::CALENDAR);
+    }
+
     #[test]
     fn update_check_loads_from_settings() {
         let (_dir, conn) = test_db();
@@ -550,4 +1486,211 @@ mod tests {
         // update_check defaults to true from settings
         assert!(mgr.update_check);
     }
+
+    #[test]
+    fn widgets_screen_navigation() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+
+        for _ in 0..MENU_DASHBOARD_WIDGETS {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(mgr.screen, Screen::Widgets { selection: 0 }));
+
+        mgr.handle_key(KeyCode::Up, &conn);
+        assert!(matches!(mgr.screen, Screen::Widgets { selection: 0 })); // clamped
+        mgr.handle_key(KeyCode::Down, &conn);
+        assert!(matches!(mgr.screen, Screen::Widgets { selection: 1 }));
+
+        // Esc returns to main
+        mgr.handle_key(KeyCode::Esc, &conn);
+        assert!(matches!(mgr.screen, Screen::Main));
+        assert_eq!(mgr.selection, MENU_DASHBOARD_WIDGETS);
+    }
+
+    #[test]
+    fn report_defaults_screen_navigation() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+
+        mgr.selection = MENU_REPORT_DEFAULTS;
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(
+            mgr.screen,
+            Screen::ReportDefaults { selection: 0 }
+        ));
+        let last = report_defaults::REPORTS.len() - 1;
+        for _ in 0..=last {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
+        assert!(matches!(mgr.screen, Screen::ReportDefaults { selection } if selection == last));
+
+        // The balance report takes no period, so nothing is saved
+        let balance = report_defaults::REPORTS
+            .iter()
+            .position(|r| *r == "balance")
+            .unwrap();
+        mgr.screen = Screen::ReportDefaults { selection: balance };
+        mgr.handle_key(KeyCode::Char('p'), &conn);
+        let (msg, success) = mgr.status_message.clone().unwrap();
+        assert!(!success);
+        assert_eq!(msg, "The balance report doesn't take a period.");
+
+        mgr.handle_key(KeyCode::Esc, &conn);
+        assert!(matches!(mgr.screen, Screen::Main));
+        assert_eq!(mgr.selection, MENU_REPORT_DEFAULTS);
+    }
+
+    #[test]
+    fn edit_home_office_saves_to_metadata() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+
+        for _ in 0..MENU_HOME_OFFICE_PCT {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(
+            mgr.screen,
+            Screen::EditingNumber(MENU_HOME_OFFICE_PCT)
+        ));
+        for c in "12.5%".chars() {
+            mgr.handle_key(KeyCode::Char(c), &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(mgr.screen, Screen::Main));
+
+        mgr.handle_key(KeyCode::Down, &conn);
+        mgr.handle_key(KeyCode::Enter, &conn);
+        for c in "$24,000".chars() {
+            mgr.handle_key(KeyCode::Char(c), &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+
+        let ho = deductions::home_office(&conn);
+        assert_eq!(ho.percent, 12.5);
+        assert_eq!(ho.annual_costs, 24000.0);
+    }
+
+    #[test]
+    fn edit_home_office_rejects_out_of_range() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+
+        for _ in 0..MENU_HOME_OFFICE_PCT {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        for c in "150".chars() {
+            mgr.handle_key(KeyCode::Char(c), &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        // Stays in edit mode with an error
+        assert!(matches!(
+            mgr.screen,
+            Screen::EditingNumber(MENU_HOME_OFFICE_PCT)
+        ));
+        assert!(matches!(mgr.status_message, Some((_, false))));
+        assert_eq!(deductions::home_office(&conn).percent, 0.0);
+    }
+
+    #[test]
+    fn edit_comp_target_saves_to_metadata() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+        assert_eq!(mgr.comp_target_pct, compensation::DEFAULT_TARGET_PCT);
+
+        for _ in 0..MENU_COMP_TARGET {
+            mgr.handle_key(KeyCode::Down, &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(
+            mgr.screen,
+            Screen::EditingNumber(MENU_COMP_TARGET)
+        ));
+        for _ in 0..4 {
+            mgr.handle_key(KeyCode::Backspace, &conn);
+        }
+        for c in "60%".chars() {
+            mgr.handle_key(KeyCode::Char(c), &conn);
+        }
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(mgr.screen, Screen::Main));
+        assert_eq!(compensation::target_pct(&conn), 60.0);
+    }
+
+    #[test]
+    fn data_dir_without_book_is_rejected() {
+        let (dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+        let before = mgr.data_dir.clone();
+
+        mgr.selection = MENU_DATA_DIR;
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert_eq!(mgr.edit_buffer, before);
+        mgr.edit_buffer = dir.path().join("missing").to_string_lossy().to_string();
+        mgr.handle_key(KeyCode::Enter, &conn);
+
+        // Stays in the editor with an error; nothing switched
+        assert!(matches!(mgr.screen, Screen::EditingText(MENU_DATA_DIR)));
+        assert!(matches!(mgr.status_message, Some((ref m, false)) if m.contains("No database")));
+        assert_eq!(mgr.data_dir, before);
+        assert!(matches!(
+            mgr.handle_key(KeyCode::Esc, &conn),
+            SettingsAction::Continue
+        ));
+        assert!(matches!(
+            mgr.handle_key(KeyCode::Esc, &conn),
+            SettingsAction::Close
+        ));
+    }
+
+    #[test]
+    fn backup_policy_rejects_out_of_range() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+        let keep = mgr.snapshot_keep;
+
+        mgr.selection = MENU_SNAPSHOT_KEEP;
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert_eq!(mgr.edit_buffer, keep.to_string());
+        mgr.edit_buffer = "0".into();
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(
+            mgr.screen,
+            Screen::EditingNumber(MENU_SNAPSHOT_KEEP)
+        ));
+        assert!(matches!(mgr.status_message, Some((_, false))));
+
+        mgr.edit_buffer = "2.5".into();
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(mgr.status_message, Some((_, false))));
+        assert_eq!(mgr.snapshot_keep, keep);
+
+        mgr.handle_key(KeyCode::Esc, &conn);
+        mgr.selection = MENU_BACKUP_REMINDER;
+        mgr.handle_key(KeyCode::Enter, &conn);
+        mgr.edit_buffer = "366".into();
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert!(matches!(
+            mgr.screen,
+            Screen::EditingNumber(MENU_BACKUP_REMINDER)
+        ));
+    }
+
+    #[test]
+    fn cycle_export_format_wraps() {
+        let (_dir, conn) = test_db();
+        let mut mgr = SettingsManager::new(&conn, "Hello").unwrap();
+        let start = mgr.export_format.clone();
+
+        mgr.selection = MENU_EXPORT_FORMAT;
+        mgr.handle_key(KeyCode::Enter, &conn);
+        assert_ne!(mgr.export_format, start);
+        for _ in 1..EXPORT_FORMATS.len() {
+            mgr.handle_key(KeyCode::Enter, &conn);
+        }
+        assert_eq!(mgr.export_format, start);
+    }
 }
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use crate::db::{get_connection, get_metadata};
+use crate::error::{NigelError, Result};
+use crate::settings::get_data_dir;
+use crate::setup_docs;
+
+/// The setup document as plain text, under the company name.
+pub fn text() -> Result<String> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let company = get_metadata(&conn, "company_name").unwrap_or_default();
+    let docs = setup_docs::build(&conn, chrono::Local::now().date_naive())?;
+    Ok(super::report::text::with_header(&company, docs.to_text()))
+}
+
+pub fn run(format: &str, output: Option<String>) -> Result<()> {
+    let content = match format {
+        "text" => text()?,
+        "pdf" => {
+            #[cfg(feature = "pdf")]
+            {
+                super::export::setup_docs(output)?;
+                return Ok(());
+            }
+            #[cfg(not(feature = "pdf"))]
+            return Err(NigelError::Other(
+                "PDF export requires the 'pdf' feature".into(),
+            ));
+        }
+        other => {
+            return Err(NigelError::Other(format!(
+                "Unknown format '{other}'; use text or pdf"
+            )))
+        }
+    };
+    match output {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, format!("{content}\n"))?;
+            crate::settings::restrict_file_permissions(&path)?;
+            println!("Wrote {}", path.display());
+        }
+        None => println!("{content}"),
+    }
+    Ok(())
+}
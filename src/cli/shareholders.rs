@@ -0,0 +1,51 @@
+use comfy_table::{Cell, Table};
+
+use crate::db::get_connection;
+use crate::error::Result;
+use crate::settings::get_data_dir;
+use crate::shareholders;
+
+pub fn list() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let holders = shareholders::list(&conn)?;
+    if holders.is_empty() {
+        println!("No shareholders. Add one with `nigel shareholders add <name> --pct <percent>`.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Shareholder", "Ownership"]);
+    for s in &holders {
+        table.add_row(vec![
+            Cell::new(&s.name),
+            Cell::new(format!("{}%", s.ownership_pct)),
+        ]);
+    }
+    let total: f64 = holders.iter().map(|s| s.ownership_pct).sum();
+    println!("Shareholders\n{table}");
+    if (total - 100.0).abs() > 1e-9 {
+        println!("Stakes total {total}%; K-1 allocations leave the rest unassigned.");
+    }
+    Ok(())
+}
+
+pub fn add(name: &str, pct: f64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let name = shareholders::add(&conn, name, pct)?;
+    println!("Added shareholder {name} ({pct}%)");
+    Ok(())
+}
+
+pub fn set(name: &str, pct: f64) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let name = shareholders::set_ownership(&conn, name, pct)?;
+    println!("{name} now owns {pct}%");
+    Ok(())
+}
+
+pub fn remove(name: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let name = shareholders::remove(&conn, name)?;
+    println!("Removed shareholder {name}");
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::rule_packs::{self, PACKS};
+use crate::settings::get_data_dir;
+use crate::sharing::{self, ChartFile, ImportMode, ImportReport, RulesFile};
+
+fn mode(replace: bool) -> ImportMode {
+    if replace {
+        ImportMode::Replace
+    } else {
+        ImportMode::Merge
+    }
+}
+
+pub fn export_rules(path: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let file = sharing::export_rules(&conn)?;
+    sharing::write_file(Path::new(path), &file)?;
+    println!("Exported {} rules to {path}", file.rules.len());
+    Ok(())
+}
+
+pub fn export_chart(path: &str) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let file = sharing::export_chart(&conn)?;
+    sharing::write_file(Path::new(path), &file)?;
+    println!("Exported {} categories to {path}", file.categories.len());
+    Ok(())
+}
+
+pub fn import_rules(
+    path: &str,
+    replace: bool,
+    create_categories: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let file: RulesFile = sharing::read_file(Path::new(path))?;
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    if !dry_run {
+        let snap_path =
+            crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "rules-import")?;
+        println!("Pre-import snapshot saved to {}", snap_path.display());
+    }
+    let report = sharing::import_rules(&conn, &file, mode(replace), create_categories, dry_run)?;
+    print_report(&report, "rules", dry_run);
+    if !report.skipped.is_empty() && !create_categories {
+        println!("Use --create-categories to add missing categories instead of skipping.");
+    }
+    Ok(())
+}
+
+pub fn import_chart(path: &str, replace: bool, dry_run: bool) -> Result<()> {
+    let file: ChartFile = sharing::read_file(Path::new(path))?;
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    if !dry_run {
+        let snap_path =
+            crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "categories-import")?;
+        println!("Pre-import snapshot saved to {}", snap_path.display());
+    }
+    let report = sharing::import_chart(&conn, &file, mode(replace), dry_run)?;
+    print_report(&report, "categories", dry_run);
+    Ok(())
+}
+
+pub fn list_packs() -> Result<()> {
+    println!("Rule packs:");
+    for pack in PACKS {
+        let file = pack.parse()?;
+        println!(
+            "  {:<8} {} ({} rules) — {}",
+            pack.key,
+            file.name,
+            file.rules.len(),
+            file.description
+        );
+    }
+    println!("Install one with `nigel rules install-pack <name>`.");
+    Ok(())
+}
+
+/// Preview what a rule pack would add, then add it after confirmation.
+/// Merge only: a pack pattern the book already files elsewhere is left as
+/// the book has it.
+pub fn install_pack(name: &str, create_categories: bool, dry_run: bool) -> Result<()> {
+    let pack = rule_packs::find(name)?.parse()?;
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let file = rule_packs::rules_for(&conn, &pack)?;
+    let preview = sharing::import_rules(&conn, &file, ImportMode::Merge, create_categories, true)?;
+
+    println!("{}: {}", pack.name, pack.description);
+    if preview.added_rules.is_empty() {
+        println!("Would add no rules; {} already present", preview.unchanged);
+    } else {
+        println!("Would add {} rules:", preview.added);
+        for rule in &preview.added_rules {
+            println!("  {rule}");
+        }
+        println!("{} already present", preview.unchanged);
+    }
+    if !preview.created_categories.is_empty() {
+        println!(
+            "Would create categories: {}",
+            preview.created_categories.join(", ")
+        );
+    }
+    for (title, lines) in [
+        ("Conflicts (your rules are kept)", &preview.conflicts),
+        ("Skipped", &preview.skipped),
+    ] {
+        if lines.is_empty() {
+            continue;
+        }
+        println!("{title}:");
+        for line in lines {
+            println!("  {line}");
+        }
+    }
+    if !preview.skipped.is_empty() && !create_categories {
+        println!("Use --create-categories to add missing categories instead of skipping.");
+    }
+    if dry_run || preview.added == 0 {
+        return Ok(());
+    }
+
+    print!("Add {} rules? [y/N] ", preview.added);
+    io::stdout().flush()?;
+    let line = io::stdin()
+        .lock()
+        .lines()
+        .next()
+        .unwrap_or(Ok(String::new()))
+        .map_err(|e| NigelError::Other(e.to_string()))?;
+    if !line.trim().eq_ignore_ascii_case("y") {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    let snap_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "rules-pack")?;
+    println!("Pre-install snapshot saved to {}", snap_path.display());
+    let report = sharing::import_rules(&conn, &file, ImportMode::Merge, create_categories, false)?;
+    println!(
+        "Added {} rules from {}. Run `nigel categorize` to apply them to uncategorized transactions.",
+        report.added, pack.name
+    );
+    Ok(())
+}
+
+fn print_report(report: &ImportReport, noun: &str, dry_run: bool) {
+    if dry_run {
+        println!("Dry run: nothing was saved.");
+    }
+    let mut summary = format!("Added {} {noun}", report.added);
+    if report.updated > 0 {
+        summary.push_str(&format!(", updated {}", report.updated));
+    }
+    if report.removed > 0 {
+        summary.push_str(&format!(", removed {}", report.removed));
+    }
+    println!("{summary}; {} already present", report.unchanged);
+    if !report.created_categories.is_empty() {
+        println!(
+            "Created categories: {}",
+            report.created_categories.join(", ")
+        );
+    }
+    for (title, lines) in [
+        ("Conflicts (left as the book has them)", &report.conflicts),
+        ("Skipped", &report.skipped),
+        ("Kept (still in use)", &report.kept),
+    ] {
+        if lines.is_empty() {
+            continue;
+        }
+        println!("{title}:");
+        for line in lines {
+            println!("  {line}");
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use std::io::IsTerminal;
+
+use comfy_table::{Cell, Table};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use rusqlite::Connection;
+
+use crate::db::get_connection;
+use crate::error::Result;
+use crate::fiscal::FiscalYear;
+use crate::fmt::money;
+use crate::settings::get_data_dir;
+use crate::signoff::{self, CategoryTotal, SignoffStatus, TopTransaction, TOP_TRANSACTIONS};
+use crate::tui::{money_span, pad_right, truncate, FOOTER_STYLE, HEADER_STYLE, SELECTED_STYLE};
+
+/// Walks the year's category totals, largest first, showing the top
+/// transactions behind the selected one. `a` approves it, `i` marks it for
+/// investigation with a note, `u` takes the decision back.
+pub struct SignoffScreen {
+    year: i32,
+    label: String,
+    totals: Vec<CategoryTotal>,
+    selected: usize,
+    top: Vec<TopTransaction>,
+    /// Typing the investigate note when set.
+    note_input: Option<String>,
+    status_message: Option<String>,
+}
+
+impl SignoffScreen {
+    pub fn new(conn: &Connection, year: i32) -> Result<Self> {
+        let mut screen = Self {
+            year,
+            label: FiscalYear::load(conn).label(year),
+            totals: Vec::new(),
+            selected: 0,
+            top: Vec::new(),
+            note_input: None,
+            status_message: None,
+        };
+        screen.reload(conn)?;
+        screen.selected = screen.next_open(0).unwrap_or(0);
+        screen.load_top(conn)?;
+        Ok(screen)
+    }
+
+    fn reload(&mut self, conn: &Connection) -> Result<()> {
+        self.totals = signoff::year_totals(conn, self.year)?;
+        self.selected = self.selected.min(self.totals.len().saturating_sub(1));
+        Ok(())
+    }
+
+    fn load_top(&mut self, conn: &Connection) -> Result<()> {
+        self.top = match self.totals.get(self.selected) {
+            Some(t) => signoff::top_transactions(conn, self.year, t.category_id, TOP_TRANSACTIONS)?,
+            None => Vec::new(),
+        };
+        Ok(())
+    }
+
+    /// The first category from `from` on that isn't approved at its current
+    /// total, wrapping around.
+    fn next_open(&self, from: usize) -> Option<usize> {
+        let n = self.totals.len();
+        (0..n)
+            .map(|i| (from + i) % n)
+            .find(|&i| !self.totals[i].is_approved())
+    }
+
+    fn progress(&self) -> String {
+        let count = |f: fn(&CategoryTotal) -> bool| self.totals.iter().filter(|t| f(t)).count();
+        let mut parts = vec![format!(
+            "{}/{} approved",
+            count(CategoryTotal::is_approved),
+            self.totals.len()
+        )];
+        let investigating = count(CategoryTotal::is_investigating);
+        if investigating > 0 {
+            parts.push(format!("{investigating} to investigate"));
+        }
+        let stale = count(CategoryTotal::is_stale);
+        if stale > 0 {
+            parts.push(format!("{stale} changed since sign-off"));
+        }
+        parts.join(", ")
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let top_rows = TOP_TRANSACTIONS as u16 + 4;
+        let [header_area, sep_area, list_area, detail_area, status_area, hints_area] =
+            Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Length(top_rows),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                " Pre-filing Review \u{2014} {}  ({})",
+                self.label,
+                self.progress()
+            ))
+            .style(HEADER_STYLE),
+            header_area,
+        );
+        frame.render_widget(
+            Paragraph::new("\u{2501}".repeat(area.width as usize)).style(FOOTER_STYLE),
+            sep_area,
+        );
+
+        if self.totals.is_empty() {
+            frame.render_widget(
+                Paragraph::new(format!(
+                    "\n   Nothing is categorized in {}. Import and review first.",
+                    self.label
+                )),
+                list_area,
+            );
+        } else {
+            self.draw_list(frame, list_area);
+            self.draw_detail(frame, detail_area);
+        }
+
+        let status = match (&self.note_input, &self.status_message) {
+            (Some(note), _) => Line::from(vec![
+                Span::styled(" Why investigate? ", Style::default().fg(Color::Yellow)),
+                Span::raw(format!("{note}_")),
+            ]),
+            (None, Some(msg)) => Line::from(Span::styled(
+                format!(" {msg}"),
+                Style::default().fg(Color::Yellow),
+            )),
+            (None, None) => Line::from(""),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+
+        let hints = if self.note_input.is_some() {
+            " Enter=save  Esc=cancel"
+        } else {
+            " Up/Down=category  a=approve  i=investigate  u=undo sign-off  n=next open  Esc=quit"
+        };
+        frame.render_widget(Paragraph::new(hints).style(FOOTER_STYLE), hints_area);
+    }
+
+    fn draw_list(&self, frame: &mut Frame, area: Rect) {
+        let height = area.height as usize;
+        let offset = (self.selected + 1).saturating_sub(height);
+        let name_width = (area.width as usize).saturating_sub(34).max(10);
+        let lines: Vec<Line> = self
+            .totals
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .map(|(i, t)| {
+                let (mark, color) = if t.is_stale() {
+                    ("!", Color::Yellow)
+                } else if t.is_approved() {
+                    ("\u{2713}", Color::Green)
+                } else if t.is_investigating() {
+                    ("?", Color::Red)
+                } else {
+                    (" ", Color::DarkGray)
+                };
+                let line = Line::from(vec![
+                    Span::styled(format!(" {mark} "), Style::default().fg(color)),
+                    Span::raw(pad_right(&truncate(&t.name, name_width), name_width)),
+                    Span::raw(format!("{:>5} txns  ", t.count)),
+                    money_span(t.total),
+                ]);
+                if i == self.selected {
+                    line.style(SELECTED_STYLE)
+                } else {
+                    line
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn draw_detail(&self, frame: &mut Frame, area: Rect) {
+        let Some(current) = self.totals.get(self.selected) else {
+            return;
+        };
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                " Largest transactions in {} ({} of {})",
+                current.name,
+                self.top.len(),
+                current.count
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))];
+        let desc_width = (area.width as usize).saturating_sub(40).max(10);
+        for t in &self.top {
+            lines.push(Line::from(vec![
+                Span::raw(format!("   {}  ", t.date)),
+                Span::raw(pad_right(&truncate(&t.description, desc_width), desc_width)),
+                Span::styled(
+                    format!(" {}  ", pad_right(&truncate(&t.account, 12), 12)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                money_span(t.amount),
+            ]));
+        }
+        if let Some(s) = &current.signoff {
+            let mut text = format!(
+                " {} at {} on {}",
+                match s.status {
+                    SignoffStatus::Approved => "Approved",
+                    SignoffStatus::Investigate => "Marked to investigate",
+                },
+                money(s.total),
+                s.signed_at
+            );
+            if let Some(note) = &s.note {
+                text.push_str(&format!(": {note}"));
+            }
+            if current.is_stale() {
+                text.push_str(&format!(" \u{2014} now {}", money(current.total)));
+            }
+            lines.push(Line::from(Span::styled(
+                text,
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    /// Handle a key; false once the review is closed.
+    pub fn handle_key(&mut self, code: KeyCode, conn: &Connection) -> Result<bool> {
+        if let Some(note) = &mut self.note_input {
+            match code {
+                KeyCode::Esc => self.note_input = None,
+                KeyCode::Backspace => {
+                    note.pop();
+                }
+                KeyCode::Char(c) => note.push(c),
+                KeyCode::Enter => {
+                    let note = self.note_input.take().unwrap_or_default();
+                    let note = Some(note.trim()).filter(|n| !n.is_empty());
+                    self.decide(conn, SignoffStatus::Investigate, note)?;
+                }
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        self.status_message = None;
+        let before = self.selected;
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(false),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.totals.len() => {
+                self.selected += 1;
+            }
+            KeyCode::Char('n') if !self.totals.is_empty() => {
+                match self.next_open(self.selected + 1) {
+                    Some(i) => self.selected = i,
+                    None => self.status_message = Some("Every category is approved.".into()),
+                }
+            }
+            KeyCode::Char('a') if !self.totals.is_empty() => {
+                self.decide(conn, SignoffStatus::Approved, None)?;
+            }
+            KeyCode::Char('i') if !self.totals.is_empty() => {
+                self.note_input = Some(String::new());
+            }
+            KeyCode::Char('u') if !self.totals.is_empty() => {
+                let current = &self.totals[self.selected];
+                if signoff::clear(conn, self.year, current.category_id)? {
+                    self.status_message = Some(format!("Sign-off on {} taken back", current.name));
+                    self.reload(conn)?;
+                }
+            }
+            _ => {}
+        }
+        if self.selected != before {
+            self.load_top(conn)?;
+        }
+        Ok(true)
+    }
+
+    /// Record a decision on the selected category, then move on to the next
+    /// one still open.
+    fn decide(
+        &mut self,
+        conn: &Connection,
+        status: SignoffStatus,
+        note: Option<&str>,
+    ) -> Result<()> {
+        let current = &self.totals[self.selected];
+        signoff::record(
+            conn,
+            self.year,
+            current.category_id,
+            status,
+            current.total,
+            note,
+        )?;
+        self.reload(conn)?;
+        match self.next_open(self.selected) {
+            Some(i) => self.selected = i,
+            None => {
+                self.status_message = Some(format!(
+                    "Every category in {} is approved and ready to file.",
+                    self.label
+                ))
+            }
+        }
+        self.load_top(conn)
+    }
+}
+
+/// Run the pre-filing review for a fiscal year (default: last year). Without
+/// a terminal it prints the list instead.
+pub fn run(year: Option<i32>) -> Result<()> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return list(year);
+    }
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let year = year.unwrap_or_else(|| FiscalYear::load(&conn).current() - 1);
+    let mut review = SignoffScreen::new(&conn, year)?;
+    if review.totals.is_empty() {
+        println!("Nothing is categorized in {}.", review.label);
+        return Ok(());
+    }
+
+    let mut terminal = ratatui::init();
+    let mut interrupted = false;
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| review.draw(frame)) {
+            break Err(e.into());
+        }
+        match event::read() {
+            Err(e) => break Err(e.into()),
+            Ok(Event::Key(key)) => {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                    interrupted = true;
+                    break Ok(());
+                }
+                match review.handle_key(key.code, &conn) {
+                    Ok(true) => {}
+                    Ok(false) => break Ok(()),
+                    Err(e) => break Err(e),
+                }
+            }
+            _ => {}
+        }
+    };
+    ratatui::restore();
+
+    result?;
+    if interrupted {
+        std::process::exit(130);
+    }
+    println!("{}: {}", review.label, review.progress());
+    Ok(())
+}
+
+/// Print each category's total and sign-off for the year.
+pub fn list(year: Option<i32>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let fiscal = FiscalYear::load(&conn);
+    let year = year.unwrap_or_else(|| fiscal.current() - 1);
+    let totals = signoff::year_totals(&conn, year)?;
+    if totals.is_empty() {
+        println!("Nothing is categorized in {}.", fiscal.label(year));
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Category", "Total", "Status", "Signed off", "Note"]);
+    for t in &totals {
+        let signoff = t.signoff.as_ref();
+        table.add_row(vec![
+            Cell::new(&t.name),
+            Cell::new(money(t.total)),
+            Cell::new(t.status_label()),
+            Cell::new(signoff.map(|s| s.signed_at.as_str()).unwrap_or("")),
+            Cell::new(signoff.and_then(|s| s.note.as_deref()).unwrap_or("")),
+        ]);
+    }
+    println!("Pre-filing Review, {}\n{table}", fiscal.label(year));
+    match signoff::completed_at(&totals) {
+        Some(at) => println!("Every category approved; last sign-off {at}."),
+        None => {
+            let open = totals.iter().filter(|t| !t.is_approved()).count();
+            println!(
+                "{open} categor{} still open. Run `nigel signoff`.",
+                if open == 1 { "y" } else { "ies" }
+            );
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::db::{get_connection, get_metadata, is_encrypted};
-use crate::error::Result;
+use crate::error::{NigelError, Result};
 use crate::fmt::format_bytes;
 use crate::migrations::{get_schema_version, LATEST_VERSION};
 use crate::settings::load_settings;
@@ -32,14 +32,23 @@ pub fn run() -> Result<()> {
     let company = get_metadata(&conn, "company_name");
     let schema_v = get_schema_version(&conn)?;
     let accounts: i64 = conn.query_row("SELECT count(*) FROM accounts", [], |r| r.get(0))?;
-    let transactions: i64 =
-        conn.query_row("SELECT count(*) FROM transactions", [], |r| r.get(0))?;
+    let transactions: i64 = conn.query_row(
+        "SELECT count(*) FROM transactions WHERE deleted_at IS NULL",
+        [],
+        |r| r.get(0),
+    )?;
     let flagged: i64 = conn.query_row(
-        "SELECT count(*) FROM transactions WHERE is_flagged = 1",
+        "SELECT count(*) FROM transactions WHERE is_flagged = 1 AND deleted_at IS NULL",
         [],
         |r| r.get(0),
     )?;
     let rules: i64 = conn.query_row("SELECT count(*) FROM rules", [], |r| r.get(0))?;
+    // Older schemas have no archives table yet
+    let archived: Vec<String> = crate::archive::list(&conn)
+        .unwrap_or_default()
+        .iter()
+        .map(|a| a.year.to_string())
+        .collect();
 
     // All data collected — print output
     println!("User:       {user_name}");
@@ -54,10 +63,58 @@ pub fn run() -> Result<()> {
     println!("Transactions:  {transactions}");
     println!("Flagged:       {flagged}");
     println!("Rules:         {rules}");
+    if !archived.is_empty() {
+        println!("Archived:      {}", archived.join(", "));
+    }
 
     Ok(())
 }
 
+#[derive(serde::Serialize)]
+struct BadgeOutput {
+    initialized: bool,
+    encrypted: bool,
+    /// Encrypted and no password available; the health fields are omitted.
+    locked: bool,
+    #[serde(flatten)]
+    health: Option<crate::health::Badge>,
+}
+
+/// `nigel status --badge`: one line of JSON. Never prompts, so an encrypted
+/// book reports `"locked": true` instead of blocking a shell prompt.
+pub fn badge() -> Result<()> {
+    let settings = load_settings();
+    let data_dir = std::path::PathBuf::from(&settings.data_dir);
+    let db_path = data_dir.join("nigel.db");
+
+    let mut out = BadgeOutput {
+        initialized: db_path.exists(),
+        encrypted: false,
+        locked: false,
+        health: None,
+    };
+    if out.initialized {
+        out.encrypted = is_encrypted(&db_path)?;
+        match get_connection(&db_path) {
+            Ok(conn) => {
+                out.health = Some(crate::health::badge(
+                    &conn,
+                    &data_dir.join("backups"),
+                    chrono::Local::now().date_naive(),
+                    settings.backup_reminder_days(),
+                )?);
+            }
+            Err(_) if out.encrypted => out.locked = true,
+            Err(e) => return Err(e),
+        }
+    }
+    println!(
+        "{}",
+        serde_json::to_string(&out).map_err(|e| NigelError::Other(e.to_string()))?
+    );
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::db::{get_connection, init_db, is_encrypted, open_connection, set_db_password};
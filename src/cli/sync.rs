@@ -0,0 +1,170 @@
+use comfy_table::{Cell, Table};
+
+use crate::categorizer::categorize_transactions;
+use crate::cli::backup;
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::settings::get_data_dir;
+use crate::sync::{self, LinkedAccount};
+
+pub fn setup(token: Option<&str>) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let token = match token {
+        Some(t) => t.to_string(),
+        None => rpassword::prompt_password("SimpleFIN setup token: ")
+            .map_err(|e| NigelError::Other(format!("Failed to read token: {e}")))?,
+    };
+    let access_url = sync::claim_setup_token(&token)?;
+    let (accounts, errors) = sync::fetch_accounts(&access_url, None)?;
+    for e in &errors {
+        eprintln!("SimpleFIN: {e}");
+    }
+    let connection_id = sync::save_connection(&conn, &access_url)?;
+    let added = sync::upsert_accounts(&conn, connection_id, &accounts)?;
+    println!("Connected to SimpleFIN ({added} accounts found).");
+    print_accounts(&sync::list_linked(&conn)?);
+    println!("\nMap each account to a Nigel account with `nigel sync map <ID> <account>`, then run `nigel sync run`.");
+    Ok(())
+}
+
+pub fn accounts() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let links = sync::list_linked(&conn)?;
+    if links.is_empty() {
+        println!("No synced accounts. Run `nigel sync setup` first.");
+        return Ok(());
+    }
+    print_accounts(&links);
+    Ok(())
+}
+
+fn print_accounts(links: &[LinkedAccount]) {
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Institution",
+        "Remote Account",
+        "Nigel Account",
+        "Last Sync",
+    ]);
+    for l in links {
+        table.add_row(vec![
+            Cell::new(l.id),
+            Cell::new(l.institution.as_deref().unwrap_or("")),
+            Cell::new(&l.name),
+            Cell::new(l.account_name.as_deref().unwrap_or("(not mapped)")),
+            Cell::new(l.last_synced_at.as_deref().unwrap_or("never")),
+        ]);
+    }
+    println!("{table}");
+}
+
+pub fn map(id: i64, account: Option<&str>, unmap: bool) -> Result<()> {
+    if account.is_none() && !unmap {
+        return Err(NigelError::Other(
+            "Give a Nigel account name, or --unmap to stop syncing this account.".into(),
+        ));
+    }
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    sync::map_account(&conn, id, account)?;
+    match account {
+        Some(name) => println!("Synced account {id} now imports into '{name}'."),
+        None => println!("Synced account {id} is no longer mapped."),
+    }
+    Ok(())
+}
+
+pub fn run(dry_run: bool, full: bool) -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let links = sync::list_linked(&conn)?;
+    if !links.iter().any(|l| l.account_id.is_some()) {
+        println!("No synced accounts are mapped. Run `nigel sync accounts` and `nigel sync map`.");
+        return Ok(());
+    }
+
+    if !dry_run {
+        let snap_path = backup::pre_operation_snapshot(&conn, &data_dir, "sync")?;
+        println!("Pre-sync snapshot saved to {}", snap_path.display());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let mut total_imported = 0;
+    for (connection_id, access_url) in sync::connections(&conn)? {
+        let mapped: Vec<&LinkedAccount> = links
+            .iter()
+            .filter(|l| l.connection_id == connection_id && l.account_id.is_some())
+            .collect();
+        if mapped.is_empty() {
+            continue;
+        }
+        let start = sync::start_date(&mapped, now, full);
+        let (remote, errors) = sync::fetch_accounts(&access_url, Some(start))?;
+        for e in &errors {
+            eprintln!("SimpleFIN: {e}");
+        }
+        for link in mapped {
+            let Some(account) = remote.iter().find(|a| a.id == link.external_id) else {
+                eprintln!("{}: not returned by SimpleFIN, skipped", link.name);
+                continue;
+            };
+            let result = sync::apply_transactions(&conn, link, &account.transactions, dry_run)?;
+            total_imported += result.imported;
+            let target = link.account_name.as_deref().unwrap_or("");
+            let mut line = format!(
+                "{target}: {} {}, {} skipped (duplicates)",
+                result.imported,
+                if dry_run {
+                    "would be imported"
+                } else {
+                    "imported"
+                },
+                result.skipped
+            );
+            if result.pending > 0 {
+                line.push_str(&format!(", {} pending", result.pending));
+            }
+            if result.malformed > 0 {
+                line.push_str(&format!(", {} malformed", result.malformed));
+            }
+            println!("{line}");
+        }
+    }
+
+    if dry_run {
+        println!("Dry run \u{2014} no changes made");
+        return Ok(());
+    }
+    if total_imported > 0 {
+        let cat_result = categorize_transactions(&conn)?;
+        println!(
+            "{} categorized, {} still flagged",
+            cat_result.categorized, cat_result.still_flagged
+        );
+        if cat_result.card_payments > 0 {
+            println!(
+                "{} credit card payments matched across accounts",
+                cat_result.card_payments
+            );
+        }
+        if cat_result.sign_warnings > 0 {
+            println!(
+                "{} flagged for a sign that doesn't match the category (see `nigel report flagged`)",
+                cat_result.sign_warnings
+            );
+        }
+        if cat_result.anomalies > 0 {
+            println!(
+                "{} flagged as unusual for their vendor (see `nigel report flagged`)",
+                cat_result.anomalies
+            );
+        }
+        if cat_result.out_of_range > 0 {
+            println!(
+                "{} flagged for a date out of range (see `nigel report quality`)",
+                cat_result.out_of_range
+            );
+        }
+    }
+    Ok(())
+}
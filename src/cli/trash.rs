@@ -0,0 +1,100 @@
+use std::io::{self, BufRead, Write};
+
+use comfy_table::{Cell, Table};
+use rusqlite::Connection;
+
+use crate::db::get_connection;
+use crate::error::{NigelError, Result};
+use crate::fmt::money;
+use crate::settings::{get_data_dir, load_settings};
+use crate::trash;
+
+pub fn list() -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let trashed = trash::list(&conn)?;
+    if trashed.is_empty() {
+        println!("The trash is empty.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        "ID",
+        "Date",
+        "Description",
+        "Amount",
+        "Account",
+        "Category",
+        "Trashed",
+    ]);
+    for t in &trashed {
+        table.add_row(vec![
+            Cell::new(t.id),
+            Cell::new(&t.date),
+            Cell::new(&t.description),
+            Cell::new(money(t.amount)),
+            Cell::new(&t.account_name),
+            Cell::new(t.category.as_deref().unwrap_or("")),
+            Cell::new(&t.deleted_at),
+        ]);
+    }
+    println!("Trash\n{table}");
+    println!(
+        "Deleted for good after {} days. Restore with `nigel trash restore <id>...`.",
+        load_settings().trash_retention_days()
+    );
+    Ok(())
+}
+
+pub fn restore(ids: &[i64]) -> Result<()> {
+    let conn = get_connection(&get_data_dir().join("nigel.db"))?;
+    let n = trash::restore(&conn, ids)?;
+    println!("Restored {} from the trash", transactions(n));
+    Ok(())
+}
+
+pub fn empty() -> Result<()> {
+    let data_dir = get_data_dir();
+    let conn = get_connection(&data_dir.join("nigel.db"))?;
+    let count = trash::list(&conn)?.len();
+    if count == 0 {
+        println!("The trash is empty.");
+        return Ok(());
+    }
+    print!(
+        "Permanently delete {} in the trash? [y/N] ",
+        transactions(count)
+    );
+    io::stdout().flush()?;
+    let line = io::stdin()
+        .lock()
+        .lines()
+        .next()
+        .unwrap_or(Ok(String::new()))
+        .map_err(|e| NigelError::Other(e.to_string()))?;
+    if !line.trim().eq_ignore_ascii_case("y") {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    let snap_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "trash-empty")?;
+    println!("Pre-empty snapshot saved to {}", snap_path.display());
+    let n = trash::empty(&conn, None)?;
+    println!("Deleted {} for good", transactions(n));
+    Ok(())
+}
+
+/// Delete what has been in the trash longer than the `trash_retention_days`
+/// setting. Run on launch; best effort, so a locked book or one not yet
+/// migrated is left alone. Returns how many were deleted.
+pub fn empty_expired(conn: &Connection) -> usize {
+    trash::empty(conn, Some(load_settings().trash_retention_days())).unwrap_or(0)
+}
+
+fn transactions(n: usize) -> String {
+    if n == 1 {
+        "1 transaction".to_string()
+    } else {
+        format!("{n} transactions")
+    }
+}
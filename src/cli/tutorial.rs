@@ -0,0 +1,413 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use rusqlite::Connection;
+
+use crate::error::{NigelError, Result};
+use crate::tui::FOOTER_STYLE;
+
+/// File name of the sample statement written next to the demo book.
+const SAMPLE_FILE: &str = "tutorial-statement.csv";
+
+/// One stop on the guided tour, in the order they are taken.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Step {
+    Import,
+    Review,
+    Rule,
+    Pnl,
+    Export,
+}
+
+pub const STEPS: &[Step] = &[
+    Step::Import,
+    Step::Review,
+    Step::Rule,
+    Step::Pnl,
+    Step::Export,
+];
+
+impl Step {
+    pub fn title(self) -> &'static str {
+        match self {
+            Step::Import => "Import a statement",
+            Step::Review => "Review flagged transactions",
+            Step::Rule => "Create a rule",
+            Step::Pnl => "Run a Profit & Loss",
+            #[cfg(feature = "pdf")]
+            Step::Export => "Export a PDF",
+            #[cfg(not(feature = "pdf"))]
+            Step::Export => "Export a report",
+        }
+    }
+
+    fn instructions(self, sample: &Path) -> Vec<String> {
+        match self {
+            Step::Import => vec![
+                "Press [i] on the home screen. The file path is filled in with a sample \
+                 statement:"
+                    .into(),
+                sample.display().to_string(),
+                "Leave the account on BofA Checking and press Enter, then Enter again on the \
+                 preview to import the rows."
+                    .into(),
+            ],
+            Step::Review => vec![
+                "Nigel couldn't place some of those rows, so they're flagged.".into(),
+                "Press [r], pick a category for one, and press Enter to save it.".into(),
+            ],
+            Step::Rule => vec![
+                "Rules categorize future imports for you.".into(),
+                "Press [u] for the rules screen, then [a] to add one, e.g. BLUE BOTTLE \
+                 \u{2192} Meals."
+                    .into(),
+            ],
+            Step::Pnl => vec![
+                "Press [v] to view a report and choose Profit & Loss.".into(),
+                "Your sample rows are in this month's figures.".into(),
+            ],
+            #[cfg(feature = "pdf")]
+            Step::Export => vec![
+                "Press [e], choose a report, then PDF.".into(),
+                "The file lands in the exports folder of the demo book.".into(),
+            ],
+            #[cfg(not(feature = "pdf"))]
+            Step::Export => vec![
+                "Press [e], choose a report, then Text.".into(),
+                "The file lands in the exports folder of the demo book.".into(),
+            ],
+        }
+    }
+}
+
+/// Counts the tutorial watches to notice a step being done.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counts {
+    pub imports: i64,
+    pub flagged: i64,
+    pub rules: i64,
+}
+
+pub fn counts(conn: &Connection) -> Result<Counts> {
+    Ok(conn.query_row(
+        "SELECT (SELECT COUNT(*) FROM imports), \
+                (SELECT COUNT(*) FROM transactions WHERE is_flagged = 1 AND deleted_at IS NULL), \
+                (SELECT COUNT(*) FROM rules)",
+        [],
+        |r| {
+            Ok(Counts {
+                imports: r.get(0)?,
+                flagged: r.get(1)?,
+                rules: r.get(2)?,
+            })
+        },
+    )?)
+}
+
+/// A scripted walk through the demo book: the dashboard reports what the
+/// user does, and the overlay moves on once the current step is done.
+pub struct Tutorial {
+    step: usize,
+    /// Counts when the current step began.
+    baseline: Counts,
+    sample_file: PathBuf,
+    /// Overlay collapsed to a one-line reminder.
+    pub minimized: bool,
+}
+
+impl Tutorial {
+    pub fn new(conn: &Connection, sample_file: PathBuf) -> Result<Self> {
+        Ok(Self {
+            step: 0,
+            baseline: counts(conn)?,
+            sample_file,
+            minimized: false,
+        })
+    }
+
+    /// The step being worked on, or None once the tour is over.
+    pub fn current(&self) -> Option<Step> {
+        STEPS.get(self.step).copied()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current().is_none()
+    }
+
+    pub fn sample_file(&self) -> &Path {
+        &self.sample_file
+    }
+
+    /// Move to the next step and re-read the counts it is measured against.
+    pub fn skip(&mut self, conn: &Connection) {
+        if self.is_finished() {
+            return;
+        }
+        self.step += 1;
+        if let Ok(now) = counts(conn) {
+            self.baseline = now;
+        }
+    }
+
+    /// Check the books for the current step being done: a new import, one
+    /// fewer flagged transaction, or a new rule. Returns true if it advanced.
+    pub fn observe(&mut self, conn: &Connection) -> bool {
+        let Ok(now) = counts(conn) else {
+            return false;
+        };
+        let done = match self.current() {
+            Some(Step::Import) => now.imports > self.baseline.imports,
+            Some(Step::Review) => now.flagged < self.baseline.flagged,
+            Some(Step::Rule) => now.rules > self.baseline.rules,
+            _ => false,
+        };
+        if done {
+            self.step += 1;
+            self.baseline = now;
+        }
+        done
+    }
+
+    /// Record a dashboard action that the books don't show, such as opening
+    /// a report. Only counts if it is the current step.
+    pub fn complete(&mut self, step: Step, conn: &Connection) -> bool {
+        if self.current() != Some(step) {
+            return false;
+        }
+        self.skip(conn);
+        true
+    }
+
+    /// Draw the step box in the bottom-right corner, over whatever screen
+    /// is showing.
+    pub fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = area.width.saturating_sub(4).min(54);
+        let (title, mut lines) = match self.current() {
+            Some(step) if self.minimized => (
+                format!(" Tutorial {}/{} ", self.step + 1, STEPS.len()),
+                vec![Line::from(step.title())],
+            ),
+            Some(step) => {
+                let mut lines = vec![Line::from(Span::styled(
+                    step.title(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ))];
+                lines.extend(
+                    step.instructions(&self.sample_file)
+                        .into_iter()
+                        .map(Line::from),
+                );
+                (
+                    format!(" Tutorial: step {} of {} ", self.step + 1, STEPS.len()),
+                    lines,
+                )
+            }
+            None => (
+                " Tutorial complete ".to_string(),
+                vec![
+                    Line::from(Span::styled(
+                        "That's the lot. Well done.",
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from("You're in the demo book; `nigel load` switches back to yours."),
+                ],
+            ),
+        };
+        let hint = match (self.is_finished(), self.minimized) {
+            (true, _) => " F1=close",
+            (false, true) => " F1=expand",
+            (false, false) => " F1=minimize  F2=skip step  F3=end tutorial",
+        };
+        lines.push(Line::from(Span::styled(hint, FOOTER_STYLE)));
+
+        // Wrapped height: rough, but the box only needs to fit its text
+        let inner_width = width.saturating_sub(2).max(1) as usize;
+        let text_height: usize = lines
+            .iter()
+            .map(|l| l.width().max(1).div_ceil(inner_width))
+            .sum();
+        let height = (text_height as u16 + 2).min(area.height);
+        let popup = Rect::new(
+            area.x + area.width.saturating_sub(width + 1),
+            area.y + area.height.saturating_sub(height + 1),
+            width,
+            height,
+        );
+        frame.render_widget(Clear, popup);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(Span::styled(
+                title,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            popup,
+        );
+    }
+}
+
+/// Rows of the sample statement, as (day of month, description, amount).
+/// The coffee and co-working rows match no demo rule, so they come in
+/// flagged for the review and rule steps.
+const SAMPLE_ROWS: &[(u32, &str, f64)] = &[
+    (2, "STRIPE TRANSFER TUTORIAL", 1850.00),
+    (3, "GITHUB INC", -21.00),
+    (4, "BLUE BOTTLE COFFEE #114", -6.75),
+    (6, "BLUE BOTTLE COFFEE #114", -11.20),
+    (7, "INDUSTRIOUS COWORKING", -350.00),
+    (9, "UBER EATS ORDER", -24.60),
+];
+
+/// Write the sample BofA checking CSV dated in the month of `today`.
+pub fn write_sample(dir: &Path, today: NaiveDate) -> Result<PathBuf> {
+    let mut csv = String::from("Date,Description,Amount,Running Bal.\n");
+    let mut balance = 0.0;
+    for (day, description, amount) in SAMPLE_ROWS {
+        // Early in the month the sample falls back to last month
+        let date = today
+            .with_day(*day)
+            .filter(|d| *d <= today)
+            .or_else(|| {
+                let prev = today.with_day(1)? - chrono::Duration::days(1);
+                prev.with_day(*day)
+            })
+            .unwrap_or(today);
+        balance += amount;
+        csv.push_str(&format!(
+            "{},{description},{amount:.2},{balance:.2}\n",
+            date.format("%m/%d/%Y")
+        ));
+    }
+    let path = dir.join(SAMPLE_FILE);
+    std::fs::write(&path, csv)?;
+    Ok(path)
+}
+
+/// Switch to the demo book (creating it if needed), write the sample
+/// statement, and return a tutorial starting at the first step.
+pub fn start() -> Result<Tutorial> {
+    let (demo_dir, _) = super::demo::setup_demo()?;
+    let sample = write_sample(&demo_dir, chrono::Local::now().date_naive())?;
+    let conn = crate::db::get_connection(&demo_dir.join("nigel.db"))?;
+    Tutorial::new(&conn, sample)
+}
+
+/// `nigel tutorial`: open the dashboard on the demo book with the tutorial
+/// overlay, skipping the splash.
+pub fn run() -> Result<()> {
+    use std::io::IsTerminal;
+
+    // Check before switching books so a script doesn't land in the demo
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(NigelError::Other(
+            "The tutorial needs an interactive terminal.".into(),
+        ));
+    }
+    let tutorial = start()?;
+    super::dashboard::run(true, Some(tutorial))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+    use crate::importer::ImporterKind;
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('BofA Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn add_txn(conn: &Connection, flagged: bool) -> i64 {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, is_flagged) \
+             VALUES (1, '2025-03-04', 'BLUE BOTTLE COFFEE', -6.75, ?1)",
+            [flagged],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn steps_advance_as_the_books_change() {
+        let (dir, conn) = test_db();
+        let id = add_txn(&conn, true);
+        let mut tutorial = Tutorial::new(&conn, dir.path().join(SAMPLE_FILE)).unwrap();
+        assert_eq!(tutorial.current(), Some(Step::Import));
+        assert!(!tutorial.observe(&conn));
+
+        conn.execute(
+            "INSERT INTO imports (filename, account_id, checksum) VALUES ('s.csv', 1, 'x')",
+            [],
+        )
+        .unwrap();
+        add_txn(&conn, true);
+        assert!(tutorial.observe(&conn));
+        assert_eq!(tutorial.current(), Some(Step::Review));
+
+        conn.execute("UPDATE transactions SET is_flagged = 0 WHERE id = ?1", [id])
+            .unwrap();
+        assert!(tutorial.observe(&conn));
+        assert_eq!(tutorial.current(), Some(Step::Rule));
+
+        conn.execute(
+            "INSERT INTO rules (pattern, match_type, category_id, priority, is_active) \
+             VALUES ('BLUE BOTTLE', 'contains', (SELECT MIN(id) FROM categories), 0, 1)",
+            [],
+        )
+        .unwrap();
+        assert!(tutorial.observe(&conn));
+        assert_eq!(tutorial.current(), Some(Step::Pnl));
+    }
+
+    #[test]
+    fn dashboard_steps_only_count_in_order() {
+        let (dir, conn) = test_db();
+        let mut tutorial = Tutorial::new(&conn, dir.path().join(SAMPLE_FILE)).unwrap();
+        assert!(!tutorial.complete(Step::Pnl, &conn));
+        for _ in 0..3 {
+            tutorial.skip(&conn);
+        }
+        assert!(tutorial.complete(Step::Pnl, &conn));
+        assert!(!tutorial.complete(Step::Pnl, &conn));
+        assert!(tutorial.complete(Step::Export, &conn));
+        assert!(tutorial.is_finished());
+        // Nothing left to skip to
+        tutorial.skip(&conn);
+        assert!(tutorial.is_finished());
+    }
+
+    #[test]
+    fn sample_statement_imports_as_bofa_checking() {
+        let dir = tempfile::tempdir().unwrap();
+        let today = NaiveDate::from_ymd_opt(2025, 3, 5).unwrap();
+        let path = write_sample(dir.path(), today).unwrap();
+        assert!(ImporterKind::BofaChecking.detect(&path));
+        let (rows, malformed) = ImporterKind::BofaChecking.parse(&path).unwrap();
+        assert_eq!(rows.len(), SAMPLE_ROWS.len());
+        assert_eq!(malformed, 0);
+        // Nothing dated after today
+        assert!(rows.iter().all(|r| r.date.as_str() <= "2025-03-05"));
+    }
+}
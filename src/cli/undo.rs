@@ -98,6 +98,8 @@ pub fn run() -> Result<()> {
         return Ok(());
     }
 
+    let snap_path = crate::cli::backup::pre_operation_snapshot(&conn, &data_dir, "undo")?;
+    println!("Pre-undo snapshot saved to {}", snap_path.display());
     let deleted = delete_import(&conn, last.import_id)?;
     println!(
         "Rolled back import of \"{}\" ({} transactions removed)",
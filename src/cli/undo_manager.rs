@@ -8,8 +8,10 @@ use ratatui::{
 };
 use rusqlite::Connection;
 
+use crate::cli::screen::{self, DataChange, Nav, ScreenContext};
 use crate::cli::undo::{delete_import, get_last_import, LastImport};
 use crate::error::Result;
+use crate::settings::get_data_dir;
 use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
 
 pub enum UndoAction {
@@ -125,7 +127,13 @@ impl UndoScreen {
                     if let Some(import) = &self.last_import {
                         let import_id = import.import_id;
                         let filename = import.filename.clone();
-                        match delete_import(conn, import_id) {
+                        let result = crate::cli::backup::pre_operation_snapshot(
+                            conn,
+                            &get_data_dir(),
+                            "undo",
+                        )
+                        .and_then(|_| delete_import(conn, import_id));
+                        match result {
                             Ok(deleted) => {
                                 self.phase = Phase::Result(
                                     format!(
@@ -151,3 +159,22 @@ impl UndoScreen {
         }
     }
 }
+
+impl screen::Screen for UndoScreen {
+    fn draw(&mut self, frame: &mut Frame) {
+        UndoScreen::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        let had_import = self.last_import.is_some();
+        let action = UndoScreen::handle_key(self, code, ctx.conn);
+        // A successful rollback clears the import it undid
+        if had_import && self.last_import.is_none() {
+            ctx.publish(DataChange::Transactions);
+        }
+        Ok(match action {
+            UndoAction::Close => Nav::Pop,
+            UndoAction::Continue => Nav::Stay,
+        })
+    }
+}
@@ -20,7 +20,7 @@ pub struct UpdateInfo {
 }
 
 /// Build an HTTP client with the given timeout.
-fn http_client(timeout_secs: u64) -> Result<reqwest::blocking::Client> {
+pub(crate) fn http_client(timeout_secs: u64) -> Result<reqwest::blocking::Client> {
     reqwest::blocking::Client::builder()
         .timeout(std::time::Duration::from_secs(timeout_secs))
         .user_agent(format!("nigel/{CURRENT_VERSION}"))
@@ -0,0 +1,194 @@
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::archive;
+use crate::error::{NigelError, Result};
+
+/// SQL for a transaction's client name; `t` is the transactions alias.
+pub const CLIENT_NAME_SQL: &str = "(SELECT name FROM clients WHERE id = t.client_id)";
+
+pub struct Client {
+    pub name: String,
+    /// Transactions assigned to the client.
+    pub transactions: i64,
+}
+
+/// A client name as stored: trimmed, with runs of whitespace collapsed.
+pub fn clean_name(raw: &str) -> Result<String> {
+    let name = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err(NigelError::Other("Client name is required".into()));
+    }
+    Ok(name)
+}
+
+/// Every client by name, with how many transactions each has.
+pub fn list(conn: &Connection) -> Result<Vec<Client>> {
+    let mut stmt = conn.prepare(
+        "SELECT cl.name, COUNT(t.id) FROM clients cl \
+         LEFT JOIN transactions t ON t.client_id = cl.id AND t.deleted_at IS NULL \
+         GROUP BY cl.id ORDER BY cl.name",
+    )?;
+    let clients = stmt
+        .query_map([], |row| {
+            Ok(Client {
+                name: row.get(0)?,
+                transactions: row.get(1)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(clients)
+}
+
+/// The client named `name` (ignoring case), as `(id, stored name)`.
+pub fn find(conn: &Connection, name: &str) -> Result<Option<(i64, String)>> {
+    let name = clean_name(name)?;
+    Ok(conn
+        .query_row(
+            "SELECT id, name FROM clients WHERE name = ?1",
+            [&name],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .optional()?)
+}
+
+/// Add a client. Errors if one by that name (ignoring case) exists.
+pub fn add(conn: &Connection, name: &str) -> Result<String> {
+    let name = clean_name(name)?;
+    if let Some((_, existing)) = find(conn, &name)? {
+        return Err(NigelError::Other(format!(
+            "Client '{existing}' already exists"
+        )));
+    }
+    conn.execute("INSERT INTO clients (name) VALUES (?1)", [&name])?;
+    Ok(name)
+}
+
+/// Delete a client, leaving its transactions unassigned. Returns how many
+/// transactions it had.
+pub fn remove(conn: &Connection, name: &str) -> Result<usize> {
+    let (id, _) = find(conn, name)?
+        .ok_or_else(|| NigelError::Other(format!("No client named '{}'", name.trim())))?;
+    let tx = conn.unchecked_transaction()?;
+    let unassigned = tx.execute(
+        "UPDATE transactions SET client_id = NULL WHERE client_id = ?1",
+        [id],
+    )?;
+    tx.execute("DELETE FROM clients WHERE id = ?1", [id])?;
+    tx.commit()?;
+    Ok(unassigned)
+}
+
+/// Assign transactions to the client named `name`, adding the client if
+/// it's new; `None` unassigns them. Returns the client's stored name.
+pub fn assign(conn: &Connection, ids: &[i64], name: Option<&str>) -> Result<Option<String>> {
+    for &id in ids {
+        archive::ensure_not_archived(conn, id)?;
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM transactions WHERE id = ?1)",
+            [id],
+            |r| r.get(0),
+        )?;
+        if !exists {
+            return Err(NigelError::Other(format!("Transaction #{id} not found")));
+        }
+    }
+    let client = match name {
+        Some(name) => Some(match find(conn, name)? {
+            Some(found) => found,
+            None => {
+                let name = add(conn, name)?;
+                (conn.last_insert_rowid(), name)
+            }
+        }),
+        None => None,
+    };
+    let client_id = client.as_ref().map(|(id, _)| *id);
+    let tx = conn.unchecked_transaction()?;
+    for &id in ids {
+        tx.execute(
+            "UPDATE transactions SET client_id = ?1 WHERE id = ?2",
+            rusqlite::params![client_id, id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(client.map(|(_, name)| name))
+}
+
+/// Assign from a typed name, where blank input unassigns.
+pub fn assign_input(conn: &Connection, ids: &[i64], input: &str) -> Result<Option<String>> {
+    let input = input.trim();
+    assign(conn, ids, (!input.is_empty()).then_some(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        for amount in [5000.0, -200.0] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount) \
+                 VALUES (1, '2025-01-15', 'ACME', ?1)",
+                [amount],
+            )
+            .unwrap();
+        }
+        (dir, conn)
+    }
+
+    fn client_of(conn: &Connection, id: i64) -> Option<String> {
+        conn.query_row(
+            &format!("SELECT {CLIENT_NAME_SQL} FROM transactions t WHERE t.id = ?1"),
+            [id],
+            |r| r.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn assign_adds_new_clients_and_matches_existing_ones() {
+        let (_dir, conn) = test_db();
+        assert_eq!(
+            assign(&conn, &[1], Some("  Acme   Corp "))
+                .unwrap()
+                .as_deref(),
+            Some("Acme Corp")
+        );
+        // Same client whatever the case
+        assert_eq!(
+            assign(&conn, &[2], Some("acme corp")).unwrap().as_deref(),
+            Some("Acme Corp")
+        );
+        let clients = list(&conn).unwrap();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].transactions, 2);
+
+        assert_eq!(assign_input(&conn, &[2], " ").unwrap(), None);
+        assert_eq!(client_of(&conn, 1).as_deref(), Some("Acme Corp"));
+        assert_eq!(client_of(&conn, 2), None);
+        assert!(assign(&conn, &[99], Some("Acme Corp")).is_err());
+    }
+
+    #[test]
+    fn add_refuses_duplicates_and_remove_unassigns() {
+        let (_dir, conn) = test_db();
+        add(&conn, "Globex").unwrap();
+        assert!(add(&conn, "GLOBEX").is_err());
+        assert!(add(&conn, "   ").is_err());
+
+        assign(&conn, &[1, 2], Some("Globex")).unwrap();
+        assert_eq!(remove(&conn, "globex").unwrap(), 2);
+        assert_eq!(client_of(&conn, 1), None);
+        assert!(list(&conn).unwrap().is_empty());
+        assert!(remove(&conn, "Globex").is_err());
+    }
+}
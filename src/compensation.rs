@@ -0,0 +1,70 @@
+use rusqlite::Connection;
+
+use crate::db::{get_metadata, set_metadata};
+use crate::error::{NigelError, Result};
+
+/// Default target: officer pay at least matches distributions.
+pub const DEFAULT_TARGET_PCT: f64 = 50.0;
+
+/// Minimum share of owner pay (officer comp + distributions) that should go
+/// through payroll as officer compensation, as a percentage. Stored per
+/// database in `metadata` as `comp_target_pct`; 0 turns the warnings off.
+pub fn target_pct(conn: &Connection) -> f64 {
+    get_metadata(conn, "comp_target_pct")
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|v| (0.0..=100.0).contains(v))
+        .unwrap_or(DEFAULT_TARGET_PCT)
+}
+
+pub fn set_target_pct(conn: &Connection, percent: f64) -> Result<()> {
+    if !percent.is_finite() || !(0.0..=100.0).contains(&percent) {
+        return Err(NigelError::Other(format!(
+            "Invalid compensation target: {percent} (must be 0-100)"
+        )));
+    }
+    set_metadata(conn, "comp_target_pct", &percent.to_string())
+}
+
+/// Officer comp as a percentage of owner pay; `None` when there's no owner
+/// pay at all.
+pub fn officer_share(officer_comp: f64, distributions: f64) -> Option<f64> {
+    let total = officer_comp + distributions;
+    (total > 0.0).then(|| officer_comp / total * 100.0)
+}
+
+/// True when distributions have been taken and officer comp falls short of
+/// `target_pct` of owner pay.
+pub fn below_target(officer_comp: f64, distributions: f64, target_pct: f64) -> bool {
+    distributions > 0.0
+        && target_pct > 0.0
+        && officer_share(officer_comp, distributions).is_some_and(|share| share < target_pct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    #[test]
+    fn test_target_pct_default_and_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        assert_eq!(target_pct(&conn), DEFAULT_TARGET_PCT);
+        set_target_pct(&conn, 60.0).unwrap();
+        assert_eq!(target_pct(&conn), 60.0);
+        assert!(set_target_pct(&conn, 101.0).is_err());
+        assert!(set_target_pct(&conn, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_below_target() {
+        assert_eq!(officer_share(0.0, 0.0), None);
+        assert_eq!(officer_share(30_000.0, 20_000.0), Some(60.0));
+        assert!(!below_target(30_000.0, 20_000.0, 60.0));
+        assert!(below_target(20_000.0, 30_000.0, 50.0));
+        // No distributions, or warnings turned off
+        assert!(!below_target(0.0, 0.0, 50.0));
+        assert!(!below_target(0.0, 10_000.0, 0.0));
+    }
+}
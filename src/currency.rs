@@ -0,0 +1,230 @@
+use rusqlite::Connection;
+
+use crate::db::{get_metadata, set_metadata};
+use crate::error::{NigelError, Result};
+
+pub const DEFAULT_HOME_CURRENCY: &str = "USD";
+
+/// SQL expression for a transaction's amount converted to the home currency.
+/// Queries using it must alias transactions as `t` and join `accounts a`.
+///
+/// A transaction's currency is `t.currency`, falling back to its account's
+/// currency. Amounts already in the home currency pass through unchanged;
+/// others use the most recent rate on or before the transaction date, then
+/// the earliest known rate, then 1.0 if no rate has been entered.
+pub const HOME_AMOUNT_SQL: &str = "(CASE \
+     WHEN COALESCE(t.currency, a.currency) = \
+          COALESCE((SELECT value FROM metadata WHERE key = 'home_currency'), 'USD') \
+     THEN t.amount \
+     ELSE t.amount * COALESCE( \
+          (SELECT r.rate FROM exchange_rates r \
+           WHERE r.currency = COALESCE(t.currency, a.currency) AND r.date <= t.date \
+           ORDER BY r.date DESC LIMIT 1), \
+          (SELECT r.rate FROM exchange_rates r \
+           WHERE r.currency = COALESCE(t.currency, a.currency) \
+           ORDER BY r.date ASC LIMIT 1), \
+          1.0) \
+     END)";
+
+/// An account's opening balance in the home currency, converted at the
+/// rate on or before its opening date (today when it has none), with the
+/// same fallbacks as `HOME_AMOUNT_SQL`. Expects accounts aliased `a`.
+pub const OPENING_HOME_SQL: &str = "(CASE \
+     WHEN a.currency = COALESCE((SELECT value FROM metadata WHERE key = 'home_currency'), 'USD') \
+     THEN a.opening_balance \
+     ELSE a.opening_balance * COALESCE( \
+          (SELECT r.rate FROM exchange_rates r \
+           WHERE r.currency = a.currency AND r.date <= COALESCE(a.opening_date, date('now')) \
+           ORDER BY r.date DESC LIMIT 1), \
+          (SELECT r.rate FROM exchange_rates r \
+           WHERE r.currency = a.currency \
+           ORDER BY r.date ASC LIMIT 1), \
+          1.0) \
+     END)";
+
+pub struct ExchangeRate {
+    pub currency: String,
+    pub date: String,
+    pub rate: f64,
+}
+
+/// Normalize and validate an ISO 4217-style currency code (three ASCII letters).
+pub fn normalize_code(code: &str) -> Result<String> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(NigelError::Other(format!(
+            "Invalid currency code: '{code}' (expected three letters, e.g. USD, EUR)"
+        )));
+    }
+    Ok(code)
+}
+
+/// The currency reports are converted into. Stored per database in `metadata`.
+pub fn home_currency(conn: &Connection) -> String {
+    get_metadata(conn, "home_currency").unwrap_or_else(|| DEFAULT_HOME_CURRENCY.to_string())
+}
+
+pub fn set_home_currency(conn: &Connection, code: &str) -> Result<String> {
+    let code = normalize_code(code)?;
+    set_metadata(conn, "home_currency", &code)?;
+    Ok(code)
+}
+
+/// Record how many units of home currency one unit of `currency` was worth
+/// on `date`. Replaces any existing rate for the same currency and date.
+pub fn set_rate(conn: &Connection, currency: &str, date: &str, rate: f64) -> Result<String> {
+    let currency = normalize_code(currency)?;
+    if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+        return Err(NigelError::Other(format!(
+            "Invalid date: '{date}' (expected YYYY-MM-DD)"
+        )));
+    }
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(NigelError::Other(format!(
+            "Invalid rate: {rate} (must be greater than zero)"
+        )));
+    }
+    if currency == home_currency(conn) {
+        return Err(NigelError::Other(format!(
+            "{currency} is the home currency; its rate is always 1.0"
+        )));
+    }
+    conn.execute(
+        "INSERT INTO exchange_rates (currency, date, rate) VALUES (?1, ?2, ?3) \
+         ON CONFLICT(currency, date) DO UPDATE SET rate = ?3",
+        rusqlite::params![currency, date, rate],
+    )?;
+    Ok(currency)
+}
+
+pub fn delete_rate(conn: &Connection, currency: &str, date: &str) -> Result<()> {
+    let currency = normalize_code(currency)?;
+    let deleted = conn.execute(
+        "DELETE FROM exchange_rates WHERE currency = ?1 AND date = ?2",
+        rusqlite::params![currency, date],
+    )?;
+    if deleted == 0 {
+        return Err(NigelError::Other(format!(
+            "No {currency} rate recorded for {date}"
+        )));
+    }
+    Ok(())
+}
+
+pub fn list_rates(conn: &Connection) -> Result<Vec<ExchangeRate>> {
+    let mut stmt =
+        conn.prepare("SELECT currency, date, rate FROM exchange_rates ORDER BY currency, date")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(ExchangeRate {
+                currency: row.get(0)?,
+                date: row.get(1)?,
+                rate: row.get(2)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    fn home_amount(conn: &Connection, txn_id: i64) -> f64 {
+        conn.query_row(
+            &format!(
+                "SELECT {HOME_AMOUNT_SQL} FROM transactions t \
+                 JOIN accounts a ON t.account_id = a.id WHERE t.id = ?1"
+            ),
+            [txn_id],
+            |r| r.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_normalize_code() {
+        assert_eq!(normalize_code(" eur ").unwrap(), "EUR");
+        assert!(normalize_code("EURO").is_err());
+        assert!(normalize_code("E1R").is_err());
+    }
+
+    #[test]
+    fn test_home_currency_defaults_to_usd() {
+        let (_dir, conn) = test_db();
+        assert_eq!(home_currency(&conn), "USD");
+        set_home_currency(&conn, "cad").unwrap();
+        assert_eq!(home_currency(&conn), "CAD");
+    }
+
+    #[test]
+    fn test_set_rate_rejects_home_currency_and_bad_input() {
+        let (_dir, conn) = test_db();
+        assert!(set_rate(&conn, "USD", "2025-01-01", 1.0).is_err());
+        assert!(set_rate(&conn, "EUR", "2025-13-01", 1.1).is_err());
+        assert!(set_rate(&conn, "EUR", "2025-01-01", 0.0).is_err());
+        set_rate(&conn, "eur", "2025-01-01", 1.1).unwrap();
+        set_rate(&conn, "EUR", "2025-01-01", 1.2).unwrap();
+        let rates = list_rates(&conn).unwrap();
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].rate, 1.2);
+    }
+
+    #[test]
+    fn test_home_amount_uses_most_recent_prior_rate() {
+        let (_dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type, currency) VALUES ('Euro', 'checking', 'EUR')",
+            [],
+        )
+        .unwrap();
+        let acct = conn.last_insert_rowid();
+        for (date, amount) in [("2025-01-15", 100.0), ("2025-03-15", 100.0)] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount) \
+                 VALUES (?1, ?2, 'x', ?3)",
+                rusqlite::params![acct, date, amount],
+            )
+            .unwrap();
+        }
+        // No rates yet: passes through at 1.0
+        assert_eq!(home_amount(&conn, 1), 100.0);
+
+        set_rate(&conn, "EUR", "2025-02-01", 1.1).unwrap();
+        set_rate(&conn, "EUR", "2025-03-01", 1.2).unwrap();
+        // Jan predates every rate: falls back to the earliest
+        assert!((home_amount(&conn, 1) - 110.0).abs() < 1e-9);
+        assert!((home_amount(&conn, 2) - 120.0).abs() < 1e-9);
+
+        delete_rate(&conn, "EUR", "2025-03-01").unwrap();
+        assert!((home_amount(&conn, 2) - 110.0).abs() < 1e-9);
+        assert!(delete_rate(&conn, "EUR", "2025-03-01").is_err());
+    }
+
+    #[test]
+    fn test_transaction_currency_overrides_account() {
+        let (_dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        let acct = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, currency) \
+             VALUES (?1, '2025-01-15', 'x', -50.0, 'GBP')",
+            [acct],
+        )
+        .unwrap();
+        set_rate(&conn, "GBP", "2025-01-01", 1.25).unwrap();
+        assert!((home_amount(&conn, 1) + 62.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,267 @@
+use chrono::{Duration, NaiveDate};
+use rusqlite::Connection;
+
+use crate::error::Result;
+use crate::settings::{load_settings, Settings};
+
+/// Default for the `max_future_days` setting.
+pub const DEFAULT_MAX_FUTURE_DAYS: i64 = 30;
+
+/// Default for the `date_epoch` setting.
+pub const DEFAULT_DATE_EPOCH: &str = "2000-01-01";
+
+/// Prefix of every `flag_reason` set by `flag_out_of_range()`.
+pub const DATE_FLAG_PREFIX: &str = "Date out of range";
+
+/// How to deal with what the quality report lists.
+pub const FIX_NOTE: &str =
+    "Trash these from `nigel browse register` (D) and re-import a corrected \
+     statement, or widen `max_future_days` / `date_epoch` in settings.json if they're real.";
+
+pub fn default_epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default()
+}
+
+/// The span of plausible transaction dates: nothing before the epoch, and
+/// nothing more than `max_future_days` past today. A mistyped year (2205,
+/// 1925) lands outside it and would otherwise skew every all-time view.
+#[derive(Debug, Clone, Copy)]
+pub struct DateBounds {
+    pub earliest: NaiveDate,
+    pub latest: NaiveDate,
+    pub max_future_days: i64,
+}
+
+impl DateBounds {
+    pub fn new(today: NaiveDate, max_future_days: i64, epoch: NaiveDate) -> Self {
+        Self {
+            earliest: epoch,
+            latest: today + Duration::days(max_future_days),
+            max_future_days,
+        }
+    }
+
+    /// Bounds from the `max_future_days` and `date_epoch` settings.
+    pub fn from_settings(settings: &Settings, today: NaiveDate) -> Self {
+        Self::new(today, settings.max_future_days(), settings.date_epoch())
+    }
+
+    /// Bounds from the saved settings, as of today.
+    pub fn current() -> Self {
+        Self::from_settings(&load_settings(), chrono::Local::now().date_naive())
+    }
+
+    /// Why `date` (YYYY-MM-DD) is implausible, if it is.
+    pub fn reason(&self, date: &str) -> Option<String> {
+        if date > self.latest.to_string().as_str() {
+            Some(format!(
+                "{DATE_FLAG_PREFIX}: {date} is more than {} days ahead",
+                self.max_future_days
+            ))
+        } else if date < self.earliest.to_string().as_str() {
+            Some(format!(
+                "{DATE_FLAG_PREFIX}: {date} is before {}",
+                self.earliest
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Flag transactions the unusual-transaction checks haven't looked at yet
+/// whose date falls outside `bounds`, so a bad year in an import lands in
+/// review instead of the reports. Runs after categorization, which clears
+/// flags on the rows it files. Returns the number newly flagged.
+pub fn flag_out_of_range(conn: &Connection, bounds: &DateBounds) -> Result<usize> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, date FROM transactions \
+         WHERE anomaly_checked = 0 AND deleted_at IS NULL AND (date < ?1 OR date > ?2) \
+         AND COALESCE(flag_reason, '') NOT LIKE '{DATE_FLAG_PREFIX}%'"
+    ))?;
+    let rows: Vec<(i64, String)> = stmt
+        .query_map(
+            rusqlite::params![bounds.earliest.to_string(), bounds.latest.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    drop(stmt);
+
+    let mut update =
+        conn.prepare("UPDATE transactions SET is_flagged = 1, flag_reason = ?1 WHERE id = ?2")?;
+    let mut flagged = 0;
+    for (id, date) in &rows {
+        if let Some(reason) = bounds.reason(date) {
+            update.execute(rusqlite::params![reason, id])?;
+            flagged += 1;
+        }
+    }
+    Ok(flagged)
+}
+
+pub struct DatedTransaction {
+    pub id: i64,
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+    pub account_name: String,
+}
+
+/// Every transaction dated outside the bounds, whether or not its flag has
+/// since been cleared (`nigel report quality`).
+pub struct QualityReport {
+    pub bounds: DateBounds,
+    /// Dated more than `max_future_days` ahead, latest first.
+    pub future: Vec<DatedTransaction>,
+    /// Dated before the epoch, earliest first.
+    pub before_epoch: Vec<DatedTransaction>,
+}
+
+impl QualityReport {
+    pub fn total(&self) -> usize {
+        self.future.len() + self.before_epoch.len()
+    }
+
+    pub fn future_heading(&self) -> String {
+        format!(
+            "More than {} days ahead (after {})",
+            self.bounds.max_future_days, self.bounds.latest
+        )
+    }
+
+    pub fn before_epoch_heading(&self) -> String {
+        format!("Before {}", self.bounds.earliest)
+    }
+
+    /// The note shown when nothing is out of range.
+    pub fn clean_note(&self) -> String {
+        format!(
+            "All transactions are dated between {} and {}.",
+            self.bounds.earliest, self.bounds.latest
+        )
+    }
+}
+
+fn dated(
+    conn: &Connection,
+    filter: &str,
+    order: &str,
+    date: &str,
+) -> Result<Vec<DatedTransaction>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.id, t.date, t.description, t.amount, a.name \
+         FROM transactions t JOIN accounts a ON t.account_id = a.id \
+         WHERE t.deleted_at IS NULL AND {filter} ORDER BY t.date {order}, t.id"
+    ))?;
+    let rows = stmt
+        .query_map([date], |row| {
+            Ok(DatedTransaction {
+                id: row.get(0)?,
+                date: row.get(1)?,
+                description: row.get(2)?,
+                amount: row.get(3)?,
+                account_name: row.get(4)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn get_report(conn: &Connection, bounds: DateBounds) -> Result<QualityReport> {
+    Ok(QualityReport {
+        future: dated(conn, "t.date > ?1", "DESC", &bounds.latest.to_string())?,
+        before_epoch: dated(conn, "t.date < ?1", "ASC", &bounds.earliest.to_string())?,
+        bounds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Card', 'credit_card')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn insert(conn: &Connection, date: &str) -> i64 {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, ?1, 'FIGMA', -45.0)",
+            [date],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn bounds() -> DateBounds {
+        DateBounds::new(
+            NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            30,
+            default_epoch(),
+        )
+    }
+
+    #[test]
+    fn flags_dates_past_the_window_or_before_the_epoch() {
+        let (_dir, conn) = test_db();
+        let typo = insert(&conn, "2205-06-01");
+        let old = insert(&conn, "1925-06-01");
+        let soon = insert(&conn, "2025-07-01");
+        let checked = insert(&conn, "2205-06-02");
+        conn.execute(
+            "UPDATE transactions SET anomaly_checked = 1 WHERE id = ?1",
+            [checked],
+        )
+        .unwrap();
+
+        assert_eq!(flag_out_of_range(&conn, &bounds()).unwrap(), 2);
+        // Already flagged for its date: not counted again
+        assert_eq!(flag_out_of_range(&conn, &bounds()).unwrap(), 0);
+        let reason = |id: i64| -> Option<String> {
+            conn.query_row(
+                "SELECT flag_reason FROM transactions WHERE id = ?1 AND is_flagged = 1",
+                [id],
+                |r| r.get(0),
+            )
+            .ok()
+        };
+        assert_eq!(
+            reason(typo).unwrap(),
+            "Date out of range: 2205-06-01 is more than 30 days ahead"
+        );
+        assert_eq!(
+            reason(old).unwrap(),
+            "Date out of range: 1925-06-01 is before 2000-01-01"
+        );
+        assert!(reason(soon).is_none());
+    }
+
+    #[test]
+    fn report_lists_out_of_range_dates_even_once_cleared() {
+        let (_dir, conn) = test_db();
+        insert(&conn, "2205-06-01");
+        insert(&conn, "2031-01-01");
+        insert(&conn, "1999-12-31");
+        insert(&conn, "2025-05-01");
+        let report = get_report(&conn, bounds()).unwrap();
+        let dates = |rows: &[DatedTransaction]| -> Vec<String> {
+            rows.iter().map(|r| r.date.clone()).collect()
+        };
+        assert_eq!(dates(&report.future), ["2205-06-01", "2031-01-01"]);
+        assert_eq!(dates(&report.before_epoch), ["1999-12-31"]);
+        assert_eq!(report.total(), 3);
+        assert_eq!(
+            report.future_heading(),
+            "More than 30 days ahead (after 2025-07-01)"
+        );
+    }
+}
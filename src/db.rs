@@ -1,7 +1,8 @@
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
 
 use crate::error::Result;
 use crate::migrations;
@@ -20,6 +21,18 @@ pub fn get_db_password() -> Option<String> {
     DB_PASSWORD.lock().unwrap().clone()
 }
 
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Turn on read-only mode (`--read-only` or the `read_only` setting): every
+/// `get_connection()` opens the database read-only and screens refuse edits.
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
 pub const SCHEMA: &str = "
 CREATE TABLE IF NOT EXISTS accounts (
     id INTEGER PRIMARY KEY,
@@ -103,257 +116,30 @@ CREATE TABLE IF NOT EXISTS metadata (
 );
 ";
 
-type CategoryDef = (
-    &'static str,
-    Option<i64>,
-    &'static str,
-    Option<&'static str>,
-    Option<&'static str>,
-    &'static str,
-);
-
-const DEFAULT_CATEGORIES: &[CategoryDef] = &[
-    // Income
-    (
-        "Client Services",
-        None,
-        "income",
-        Some("Gross receipts"),
-        None,
-        "Project fees, retainer payments",
-    ),
-    (
-        "Hosting & Maintenance",
-        None,
-        "income",
-        Some("Gross receipts"),
-        None,
-        "Recurring client hosting/maintenance fees",
-    ),
-    (
-        "Reimbursements",
-        None,
-        "income",
-        Some("Gross receipts"),
-        None,
-        "Client reimbursements for expenses",
-    ),
-    (
-        "Interest Income",
-        None,
-        "income",
-        Some("Other income"),
-        Some("K-4"),
-        "Bank interest",
-    ),
-    (
-        "Other Income",
-        None,
-        "income",
-        Some("Other income"),
-        None,
-        "Anything else",
-    ),
-    // Expenses
-    (
-        "Advertising & Marketing",
-        None,
-        "expense",
-        Some("Line 8"),
-        Some("1120S-16"),
-        "Ads, sponsorships, marketing tools",
-    ),
-    (
-        "Car & Truck",
-        None,
-        "expense",
-        Some("Line 9"),
-        Some("1120S-19"),
-        "Mileage, fuel, parking",
-    ),
-    (
-        "Commissions & Fees",
-        None,
-        "expense",
-        Some("Line 10"),
-        Some("1120S-19"),
-        "Subcontractor commissions, platform fees",
-    ),
-    (
-        "Contract Labor",
-        None,
-        "expense",
-        Some("Line 11"),
-        Some("1120S-19"),
-        "Freelancers, subcontractors (1099 work)",
-    ),
-    (
-        "Insurance",
-        None,
-        "expense",
-        Some("Line 15"),
-        Some("1120S-19"),
-        "Business insurance, E&O",
-    ),
-    (
-        "Legal & Professional",
-        None,
-        "expense",
-        Some("Line 17"),
-        Some("1120S-19"),
-        "Accountant, lawyer, professional services",
-    ),
-    (
-        "Office Expense",
-        None,
-        "expense",
-        Some("Line 18"),
-        Some("1120S-19"),
-        "Office supplies, minor equipment",
-    ),
-    (
-        "Rent / Lease",
-        None,
-        "expense",
-        Some("Line 20b"),
-        Some("1120S-11"),
-        "Office rent, coworking",
-    ),
-    (
-        "Software & Subscriptions",
-        None,
-        "expense",
-        Some("Line 18/27a"),
-        Some("1120S-19"),
-        "SaaS tools, domain renewals, cloud services",
-    ),
-    (
-        "Hosting & Infrastructure",
-        None,
-        "expense",
-        Some("Line 18/27a"),
-        Some("1120S-19"),
-        "AWS, server costs, CDN",
-    ),
-    (
-        "Taxes & Licenses",
-        None,
-        "expense",
-        Some("Line 23"),
-        Some("1120S-12"),
-        "Business licenses, state fees",
-    ),
-    (
-        "Travel",
-        None,
-        "expense",
-        Some("Line 24a"),
-        Some("1120S-19"),
-        "Flights, hotels, conference travel",
-    ),
-    (
-        "Meals",
-        None,
-        "expense",
-        Some("Line 24b"),
-        Some("1120S-19"),
-        "Business meals (50% deductible)",
-    ),
-    (
-        "Utilities",
-        None,
-        "expense",
-        Some("Line 25"),
-        Some("1120S-19"),
-        "Internet, phone (business portion)",
-    ),
-    (
-        "Payroll — Wages",
-        None,
-        "expense",
-        Some("Line 26"),
-        Some("1120S-8"),
-        "Employee salaries (from Gusto)",
-    ),
-    (
-        "Payroll — Taxes",
-        None,
-        "expense",
-        Some("Line 23"),
-        Some("1120S-12"),
-        "Employer payroll taxes (from Gusto)",
-    ),
-    (
-        "Payroll — Benefits",
-        None,
-        "expense",
-        Some("Line 14"),
-        Some("1120S-18"),
-        "Health insurance, retirement (from Gusto)",
-    ),
-    (
-        "Bank & Merchant Fees",
-        None,
-        "expense",
-        Some("Line 27a"),
-        Some("1120S-19"),
-        "Stripe fees, bank charges, wire fees",
-    ),
-    (
-        "Education & Training",
-        None,
-        "expense",
-        Some("Line 27a"),
-        Some("1120S-19"),
-        "Courses, books, conferences",
-    ),
-    (
-        "Equipment",
-        None,
-        "expense",
-        Some("Line 13"),
-        Some("1120S-19"),
-        "Hardware, major purchases",
-    ),
-    (
-        "Home Office",
-        None,
-        "expense",
-        Some("Line 30"),
-        Some("1120S-19"),
-        "Simplified method or actual expenses",
-    ),
-    (
-        "Owner Draw / Distribution",
-        None,
-        "expense",
-        Some("Not deductible"),
-        Some("K-16d"),
-        "Owner payments, distributions",
-    ),
-    (
-        "Transfer",
-        None,
-        "expense",
-        Some("Not deductible"),
-        None,
-        "Transfers between own accounts",
-    ),
-    (
-        "Uncategorized",
-        None,
-        "expense",
-        Some("\u{2014}"),
-        None,
-        "Needs review",
-    ),
-];
-
 pub fn get_connection(db_path: &Path) -> Result<Connection> {
     let password = get_db_password();
+    if is_read_only() {
+        return open_read_only(db_path, password.as_deref());
+    }
     open_connection(db_path, password.as_deref())
 }
 
+/// Open an existing database read-only: any write fails with SQLite's
+/// "attempt to write a readonly database". Archives attached later inherit
+/// the flag.
+pub fn open_read_only(db_path: &Path, password: Option<&str>) -> Result<Connection> {
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    if let Some(pw) = password {
+        conn.pragma_update(None, "key", pw)?;
+    }
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
+    conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+    Ok(conn)
+}
+
 /// Open a connection with an explicit password (bypasses global state).
 /// Used by backup, password management, and tests.
 pub fn open_connection(db_path: &Path, password: Option<&str>) -> Result<Connection> {
@@ -435,16 +221,17 @@ pub fn prompt_password_if_needed(db_path: &Path) -> Result<()> {
 }
 
 pub fn init_db(conn: &Connection) -> Result<()> {
+    init_db_with_template(conn, crate::templates::DEFAULT_TEMPLATE)
+}
+
+/// Create the schema, seed a new book's categories from `template`, and run
+/// migrations. An existing book keeps its categories.
+pub fn init_db_with_template(conn: &Connection, template: &str) -> Result<()> {
     conn.execute_batch(SCHEMA)?;
 
     let count: i64 = conn.query_row("SELECT count(*) FROM categories", [], |row| row.get(0))?;
     if count == 0 {
-        for cat in DEFAULT_CATEGORIES {
-            conn.execute(
-                "INSERT INTO categories (name, parent_id, category_type, tax_line, form_line, description) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                rusqlite::params![cat.0, cat.1, cat.2, cat.3, cat.4, cat.5],
-            )?;
-        }
+        crate::templates::seed(conn, template)?;
     }
 
     migrations::run_migrations(conn)?;
@@ -685,6 +472,22 @@ mod tests {
         assert!(!validate_password(&path, "test").unwrap());
     }
 
+    #[test]
+    fn test_open_read_only_refuses_writes() {
+        let (dir, conn) = test_db();
+        drop(conn);
+        let conn = open_read_only(&dir.path().join("test.db"), None).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT count(*) FROM categories", [], |r| r.get(0))
+            .unwrap();
+        assert!(count > 0);
+        let err = conn
+            .execute("DELETE FROM categories", [])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("readonly"), "{err}");
+    }
+
     #[test]
     fn test_init_db_sets_schema_version() {
         let (_dir, conn) = test_db();
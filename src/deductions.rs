@@ -0,0 +1,209 @@
+use rusqlite::Connection;
+
+use crate::db::{get_metadata, set_metadata};
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+
+/// IRS standard mileage rates for business use, in dollars per mile, keyed
+/// by the date each rate took effect. Trips before the first entry use the
+/// first rate; trips after the last use the most recent one.
+const MILEAGE_RATES: &[(&str, f64)] = &[
+    ("2019-01-01", 0.58),
+    ("2020-01-01", 0.575),
+    ("2021-01-01", 0.56),
+    ("2022-01-01", 0.585),
+    ("2022-07-01", 0.625),
+    ("2023-01-01", 0.655),
+    ("2024-01-01", 0.67),
+    ("2025-01-01", 0.70),
+    ("2026-01-01", 0.725),
+];
+
+pub struct MileageEntry {
+    pub id: i64,
+    pub date: String,
+    pub miles: f64,
+    pub purpose: String,
+    /// Standard rate in effect on `date`.
+    pub rate: f64,
+    pub deduction: f64,
+}
+
+/// Home-office allocation: the business-use percentage of the home applied
+/// to annual home costs (rent or mortgage interest, utilities, insurance).
+/// Stored per database in `metadata`.
+pub struct HomeOffice {
+    pub percent: f64,
+    pub annual_costs: f64,
+}
+
+impl HomeOffice {
+    pub fn deduction(&self) -> f64 {
+        self.annual_costs * self.percent / 100.0
+    }
+}
+
+/// Standard mileage rate for a trip on `date` (YYYY-MM-DD).
+pub fn mileage_rate(date: &str) -> f64 {
+    MILEAGE_RATES
+        .iter()
+        .rev()
+        .find(|(from, _)| *from <= date)
+        .unwrap_or(&MILEAGE_RATES[0])
+        .1
+}
+
+pub fn add_mileage(conn: &Connection, date: &str, miles: f64, purpose: &str) -> Result<i64> {
+    if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+        return Err(NigelError::Other(format!(
+            "Invalid date: '{date}' (expected YYYY-MM-DD)"
+        )));
+    }
+    if !miles.is_finite() || miles <= 0.0 {
+        return Err(NigelError::Other(format!(
+            "Invalid miles: {miles} (must be greater than zero)"
+        )));
+    }
+    let purpose = purpose.trim();
+    if purpose.is_empty() {
+        return Err(NigelError::Other(
+            "A business purpose is required for each trip".into(),
+        ));
+    }
+    conn.execute(
+        "INSERT INTO mileage (date, miles, purpose) VALUES (?1, ?2, ?3)",
+        rusqlite::params![date, miles, purpose],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Logged trips in date order, optionally limited to one fiscal year.
+pub fn list_mileage(conn: &Connection, year: Option<i32>) -> Result<Vec<MileageEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, date, miles, purpose FROM mileage \
+         WHERE ?1 IS NULL OR date BETWEEN ?1 AND ?2 ORDER BY date, id",
+    )?;
+    let (start, end) = match year {
+        Some(y) => {
+            let (start, end) = FiscalYear::load(conn).bounds(y);
+            (Some(start.to_string()), Some(end.to_string()))
+        }
+        None => (None, None),
+    };
+    let rows = stmt
+        .query_map([start, end], |row| {
+            let date: String = row.get(1)?;
+            let miles: f64 = row.get(2)?;
+            let rate = mileage_rate(&date);
+            Ok(MileageEntry {
+                id: row.get(0)?,
+                date,
+                miles,
+                purpose: row.get(3)?,
+                rate,
+                deduction: miles * rate,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn delete_mileage(conn: &Connection, id: i64) -> Result<()> {
+    let deleted = conn.execute("DELETE FROM mileage WHERE id = ?1", [id])?;
+    if deleted == 0 {
+        return Err(NigelError::Other(format!("No mileage entry with ID {id}")));
+    }
+    Ok(())
+}
+
+pub fn home_office(conn: &Connection) -> HomeOffice {
+    let read = |key: &str| {
+        get_metadata(conn, key)
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+    HomeOffice {
+        percent: read("home_office_pct"),
+        annual_costs: read("home_office_costs"),
+    }
+}
+
+/// Set the share of the home used regularly and exclusively for business.
+pub fn set_home_office_percent(conn: &Connection, percent: f64) -> Result<()> {
+    if !percent.is_finite() || !(0.0..=100.0).contains(&percent) {
+        return Err(NigelError::Other(format!(
+            "Invalid home office percentage: {percent} (must be 0-100)"
+        )));
+    }
+    set_metadata(conn, "home_office_pct", &percent.to_string())
+}
+
+pub fn set_home_office_costs(conn: &Connection, amount: f64) -> Result<()> {
+    if !amount.is_finite() || amount < 0.0 {
+        return Err(NigelError::Other(format!(
+            "Invalid annual home costs: {amount} (must not be negative)"
+        )));
+    }
+    set_metadata(conn, "home_office_costs", &amount.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn test_mileage_rate_by_date() {
+        assert_eq!(mileage_rate("2022-06-30"), 0.585);
+        assert_eq!(mileage_rate("2022-07-01"), 0.625);
+        assert_eq!(mileage_rate("2025-03-02"), 0.70);
+        // Outside the table: clamp to the nearest known rate
+        assert_eq!(mileage_rate("2015-05-01"), 0.58);
+        assert_eq!(mileage_rate("2099-01-01"), 0.725);
+    }
+
+    #[test]
+    fn test_add_list_delete_mileage() {
+        let (_dir, conn) = test_db();
+        let id = add_mileage(&conn, "2025-03-02", 48.0, " client visit ").unwrap();
+        add_mileage(&conn, "2024-12-30", 10.0, "bank").unwrap();
+
+        let trips = list_mileage(&conn, Some(2025)).unwrap();
+        assert_eq!(trips.len(), 1);
+        assert_eq!(trips[0].purpose, "client visit");
+        assert!((trips[0].deduction - 33.6).abs() < 1e-9);
+        assert_eq!(list_mileage(&conn, None).unwrap().len(), 2);
+
+        delete_mileage(&conn, id).unwrap();
+        assert!(list_mileage(&conn, Some(2025)).unwrap().is_empty());
+        assert!(delete_mileage(&conn, id).is_err());
+    }
+
+    #[test]
+    fn test_add_mileage_validates_input() {
+        let (_dir, conn) = test_db();
+        assert!(add_mileage(&conn, "03/02/2025", 48.0, "visit").is_err());
+        assert!(add_mileage(&conn, "2025-03-02", 0.0, "visit").is_err());
+        assert!(add_mileage(&conn, "2025-03-02", 12.0, "  ").is_err());
+    }
+
+    #[test]
+    fn test_home_office_allocation() {
+        let (_dir, conn) = test_db();
+        assert_eq!(home_office(&conn).deduction(), 0.0);
+        set_home_office_percent(&conn, 12.5).unwrap();
+        set_home_office_costs(&conn, 24000.0).unwrap();
+        let ho = home_office(&conn);
+        assert_eq!(ho.percent, 12.5);
+        assert_eq!(ho.deduction(), 3000.0);
+        assert!(set_home_office_percent(&conn, 120.0).is_err());
+        assert!(set_home_office_costs(&conn, -1.0).is_err());
+    }
+}
@@ -0,0 +1,436 @@
+use chrono::{Datelike, NaiveDate};
+use rusqlite::Connection;
+
+use crate::archive;
+use crate::error::{NigelError, Result};
+use crate::fmt::money;
+use crate::reports::{self, VendorItem};
+
+/// Vendors named in the summary.
+const TOP_VENDORS: usize = 3;
+
+/// An expense category stands out when it moved at least this much against
+/// the prior month...
+const CHANGE_MIN_AMOUNT: f64 = 100.0;
+/// ...and by at least this share of the prior month's spend.
+const CHANGE_MIN_PCT: f64 = 50.0;
+
+/// Most category changes listed.
+const MAX_CHANGES: usize = 5;
+
+/// An expense category that moved noticeably against the prior month.
+pub struct CategoryChange {
+    pub category: String,
+    /// Spent this month, as a positive number.
+    pub amount: f64,
+    /// Spent the prior month, as a positive number.
+    pub prior: f64,
+}
+
+impl CategoryChange {
+    /// "Travel: $800.00, up from $120.00 (+567%)" or "..., new this month".
+    pub fn describe(&self) -> String {
+        if self.prior == 0.0 {
+            return format!("{}: {}, new this month", self.category, money(self.amount));
+        }
+        if self.amount == 0.0 {
+            return format!(
+                "{}: nothing, down from {}",
+                self.category,
+                money(self.prior)
+            );
+        }
+        let pct = (self.amount - self.prior) / self.prior * 100.0;
+        let direction = if self.amount > self.prior {
+            "up"
+        } else {
+            "down"
+        };
+        format!(
+            "{}: {}, {direction} from {} ({pct:+.0}%)",
+            self.category,
+            money(self.amount),
+            money(self.prior)
+        )
+    }
+}
+
+/// One month of the books in brief, compared with the month before.
+pub struct Digest {
+    pub year: i32,
+    pub month: u32,
+    pub transactions: i64,
+    pub income: f64,
+    /// Spent, as a positive number.
+    pub expenses: f64,
+    pub net: f64,
+    pub prior_transactions: i64,
+    pub prior_income: f64,
+    pub prior_expenses: f64,
+    pub prior_net: f64,
+    pub top_vendors: Vec<VendorItem>,
+    /// Largest moves first.
+    pub changes: Vec<CategoryChange>,
+    /// Transactions dated in the month still flagged for review.
+    pub flagged: i64,
+}
+
+/// A run of the narrative: a paragraph, or a list of points.
+pub enum Block {
+    Paragraph(String),
+    Points(Vec<String>),
+}
+
+/// Parse `YYYY-MM`.
+pub fn parse_month(s: &str) -> Result<(i32, u32)> {
+    NaiveDate::parse_from_str(&format!("{s}-01"), "%Y-%m-%d")
+        .map(|d| (d.year(), d.month()))
+        .map_err(|_| NigelError::Other(format!("Invalid month '{s}'; use YYYY-MM, e.g. 2025-06")))
+}
+
+/// The month before `today`'s, the last one that's over.
+pub fn last_month(today: NaiveDate) -> (i32, u32) {
+    prior_month(today.year(), today.month())
+}
+
+fn prior_month(year: i32, month: u32) -> (i32, u32) {
+    if month == 1 {
+        (year - 1, 12)
+    } else {
+        (year, month - 1)
+    }
+}
+
+fn month_name(year: i32, month: u32) -> String {
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .map(|d| d.format("%B").to_string())
+        .unwrap_or_default()
+}
+
+fn count_in_month(conn: &Connection, year: i32, month: u32, flagged_only: bool) -> Result<i64> {
+    let flagged = if flagged_only {
+        " AND is_flagged = 1"
+    } else {
+        ""
+    };
+    Ok(conn.query_row(
+        &format!(
+            "SELECT COUNT(*) FROM transactions WHERE deleted_at IS NULL AND date LIKE ?1{flagged}"
+        ),
+        [format!("{year:04}-{month:02}%")],
+        |r| r.get(0),
+    )?)
+}
+
+pub fn build(conn: &Connection, year: i32, month: u32) -> Result<Digest> {
+    let (prior_year, prior) = prior_month(year, month);
+    let pnl = reports::get_pnl(conn, Some(year), Some(month), None, None, Some(1), &[])?;
+    let before = reports::get_pnl(
+        conn,
+        Some(prior_year),
+        Some(prior),
+        None,
+        None,
+        Some(1),
+        &[],
+    )?;
+    let breakdown = reports::get_expense_breakdown(conn, Some(year), Some(month), None, &[])?;
+
+    let from = format!("{prior_year:04}-{prior:02}-01");
+    let to = format!("{year:04}-{month:02}-31");
+    let _archives = archive::scope(conn, Some(&from), Some(&to))?;
+    let transactions = count_in_month(conn, year, month, false)?;
+    let prior_transactions = count_in_month(conn, prior_year, prior, false)?;
+    let flagged = count_in_month(conn, year, month, true)?;
+
+    let mut changes: Vec<CategoryChange> = Vec::new();
+    if prior_transactions > 0 {
+        let spent = |items: &[reports::PnlItem], name: &str| {
+            items
+                .iter()
+                .find(|i| i.name == name)
+                .map_or(0.0, |i| i.total.abs())
+        };
+        let mut names: Vec<&str> = pnl.expenses.iter().map(|i| i.name.as_str()).collect();
+        for item in &before.expenses {
+            if !names.contains(&item.name.as_str()) {
+                names.push(&item.name);
+            }
+        }
+        for name in names {
+            let amount = spent(&pnl.expenses, name);
+            let prior = spent(&before.expenses, name);
+            let moved = (amount - prior).abs();
+            if moved >= CHANGE_MIN_AMOUNT
+                && (prior == 0.0 || moved / prior * 100.0 >= CHANGE_MIN_PCT)
+            {
+                changes.push(CategoryChange {
+                    category: name.to_string(),
+                    amount,
+                    prior,
+                });
+            }
+        }
+        changes.sort_by(|a, b| {
+            (b.amount - b.prior)
+                .abs()
+                .total_cmp(&(a.amount - a.prior).abs())
+        });
+        changes.truncate(MAX_CHANGES);
+    }
+
+    let mut top_vendors = breakdown.top_vendors;
+    top_vendors.truncate(TOP_VENDORS);
+
+    Ok(Digest {
+        year,
+        month,
+        transactions,
+        income: pnl.total_income,
+        expenses: -pnl.total_expenses,
+        net: pnl.net,
+        prior_transactions,
+        prior_income: before.total_income,
+        prior_expenses: -before.total_expenses,
+        prior_net: before.net,
+        top_vendors,
+        changes,
+        flagged,
+    })
+}
+
+impl Digest {
+    /// "June 2025".
+    pub fn label(&self) -> String {
+        format!("{} {}", month_name(self.year, self.month), self.year)
+    }
+
+    fn prior_name(&self) -> String {
+        let (y, m) = prior_month(self.year, self.month);
+        month_name(y, m)
+    }
+
+    /// The summary in Nigel's words.
+    pub fn narrative(&self) -> Vec<Block> {
+        let label = self.label();
+        if self.transactions == 0 {
+            return vec![Block::Paragraph(format!(
+                "Nothing at all recorded for {label}. Quiet month, or is there a \
+                 statement still waiting to be imported?"
+            ))];
+        }
+
+        let mut blocks = Vec::new();
+        let standing = if self.net >= 0.0 {
+            format!("leaves you {} ahead", money(self.net))
+        } else {
+            format!("leaves you {} behind", money(self.net.abs()))
+        };
+        let mut opening = format!(
+            "Right then, here's {label}. You brought in {} and spent {}, which {standing}.",
+            money(self.income),
+            money(self.expenses)
+        );
+        if self.prior_transactions > 0 {
+            let prior = self.prior_name();
+            let diff = self.net - self.prior_net;
+            if diff.abs() < 0.005 {
+                opening.push_str(&format!(" Same as {prior}, to the penny."));
+            } else {
+                let better = if diff > 0.0 { "better" } else { "worse" };
+                opening.push_str(&format!(
+                    " That's {} {better} than {prior}, when the net was {}.",
+                    money(diff.abs()),
+                    money(self.prior_net)
+                ));
+            }
+        }
+        blocks.push(Block::Paragraph(opening));
+
+        if !self.top_vendors.is_empty() {
+            let named: Vec<String> = self
+                .top_vendors
+                .iter()
+                .map(|v| format!("{} ({})", v.vendor, money(v.total.abs())))
+                .collect();
+            let list = match named.as_slice() {
+                [only] => only.clone(),
+                [init @ .., last] => format!("{} and {last}", init.join(", ")),
+                [] => String::new(),
+            };
+            blocks.push(Block::Paragraph(format!(
+                "Most of the money went to {list}."
+            )));
+        }
+
+        if self.prior_transactions > 0 {
+            let prior = self.prior_name();
+            if self.changes.is_empty() {
+                blocks.push(Block::Paragraph(format!(
+                    "Spending looks much like {prior}; nothing out of the ordinary."
+                )));
+            } else {
+                blocks.push(Block::Paragraph(format!(
+                    "A few things stood out against {prior}:"
+                )));
+                blocks.push(Block::Points(
+                    self.changes.iter().map(CategoryChange::describe).collect(),
+                ));
+            }
+        }
+
+        blocks.push(Block::Paragraph(match self.flagged {
+            0 => "Everything's categorized. Lovely.".to_string(),
+            1 => "One transaction still needs a look; `nigel review` when you've a minute."
+                .to_string(),
+            n => {
+                format!("{n} transactions still need a look; `nigel review` when you've a minute.")
+            }
+        }));
+        blocks
+    }
+
+    /// Plain-text summary, as printed to the terminal.
+    pub fn to_text(&self) -> String {
+        let mut out = format!("Monthly Summary: {}\n\n", self.label());
+        for block in self.narrative() {
+            match block {
+                Block::Paragraph(p) => out.push_str(&format!("{p}\n\n")),
+                Block::Points(points) => {
+                    for p in points {
+                        out.push_str(&format!("  - {p}\n"));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        out.trim_end().to_string()
+    }
+
+    /// Markdown summary with a small figures table, for pasting into email.
+    pub fn to_markdown(&self, company: &str) -> String {
+        let mut out = format!("# Monthly Summary: {}\n\n", self.label());
+        if !company.is_empty() {
+            out.push_str(&format!("_{company}_\n\n"));
+        }
+        for block in self.narrative() {
+            match block {
+                Block::Paragraph(p) => out.push_str(&format!("{p}\n\n")),
+                Block::Points(points) => {
+                    for p in points {
+                        out.push_str(&format!("- {p}\n"));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        if self.transactions > 0 {
+            let prior = self.prior_name();
+            out.push_str(&format!(
+                "| | {} | {prior} |\n|---|---:|---:|\n",
+                month_name(self.year, self.month)
+            ));
+            for (name, this, before) in [
+                ("Income", self.income, self.prior_income),
+                ("Expenses", self.expenses, self.prior_expenses),
+                ("Net", self.net, self.prior_net),
+            ] {
+                out.push_str(&format!(
+                    "| {name} | {} | {} |\n",
+                    money(this),
+                    money(before)
+                ));
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn add(conn: &Connection, date: &str, vendor: &str, category: &str, amount: f64) {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, vendor, category_id) \
+             VALUES (1, ?1, ?2, ?3, ?2, (SELECT id FROM categories WHERE name = ?4))",
+            rusqlite::params![date, vendor, amount, category],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_digest_compares_with_prior_month() {
+        let (_dir, conn) = test_db();
+        add(&conn, "2025-05-03", "Acme", "Client Services", 4000.0);
+        add(
+            &conn,
+            "2025-05-10",
+            "Adobe",
+            "Software & Subscriptions",
+            -60.0,
+        );
+        add(&conn, "2025-06-02", "Acme", "Client Services", 5000.0);
+        add(
+            &conn,
+            "2025-06-05",
+            "Adobe",
+            "Software & Subscriptions",
+            -60.0,
+        );
+        add(&conn, "2025-06-12", "Delta", "Travel", -900.0);
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, is_flagged) \
+             VALUES (1, '2025-06-20', 'MYSTERY', -15.0, 1)",
+            [],
+        )
+        .unwrap();
+
+        let d = build(&conn, 2025, 6).unwrap();
+        assert_eq!(d.label(), "June 2025");
+        assert_eq!(d.transactions, 4);
+        assert_eq!(d.income, 5000.0);
+        assert_eq!(d.expenses, 960.0);
+        assert_eq!(d.prior_net, 3940.0);
+        assert_eq!(d.flagged, 1);
+        assert_eq!(d.top_vendors[0].vendor, "Delta");
+        assert_eq!(d.changes.len(), 1);
+        assert_eq!(d.changes[0].describe(), "Travel: $900.00, new this month");
+
+        let text = d.to_text();
+        assert!(text.contains("You brought in $5,000.00 and spent $960.00"));
+        assert!(text.contains("better than May"));
+        assert!(text.contains("  - Travel: $900.00, new this month"));
+        assert!(text.contains("One transaction still needs a look"));
+        assert!(d
+            .to_markdown("Acme Co")
+            .contains("| Net | $4,040.00 | $3,940.00 |"));
+
+        let empty = build(&conn, 2025, 8).unwrap();
+        assert!(empty
+            .to_text()
+            .contains("Nothing at all recorded for August 2025"));
+    }
+
+    #[test]
+    fn test_parse_month() {
+        assert_eq!(parse_month("2025-06").unwrap(), (2025, 6));
+        assert!(parse_month("2025-13").is_err());
+        assert!(parse_month("June").is_err());
+        let jan = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        assert_eq!(last_month(jan), (2025, 12));
+    }
+}
@@ -1,39 +1,41 @@
 use thiserror::Error;
 
+use crate::locales::{t, t_args};
+
 #[derive(Error, Debug)]
 pub enum NigelError {
-    #[error("Database error: {0}")]
+    #[error("{}", t_args("error.db", &[("detail", .0)]))]
     Db(#[from] rusqlite::Error),
 
-    #[error("IO error: {0}")]
+    #[error("{}", t_args("error.io", &[("detail", .0)]))]
     Io(#[from] std::io::Error),
 
-    #[error("CSV error: {0}")]
+    #[error("{}", t_args("error.csv", &[("detail", .0)]))]
     Csv(#[from] csv::Error),
 
-    #[error("Not initialized. Run `nigel init` first to set up your data directory.")]
+    #[error("{}", t("error.not_initialized"))]
     NotInitialized,
 
-    #[error("Account '{0}' not found. Run `nigel accounts list` to see available accounts, or `nigel accounts add` to create one.")]
+    #[error("{}", t_args("error.unknown_account", &[("account", .0)]))]
     UnknownAccount(String),
 
-    #[error("Unknown format: '{0}'. Run `nigel import --help` for supported formats.")]
+    #[error("{}", t_args("error.unknown_format", &[("format", .0)]))]
     UnknownFormat(String),
 
-    #[error("Couldn't detect the format of this file for account type '{0}'. Use `--format <key>` to specify. Run `nigel import --help` for supported formats.")]
+    #[error("{}", t_args("error.no_importer", &[("account_type", .0)]))]
     NoImporter(String),
 
-    #[error("No transactions found for {account} in {month}.")]
+    #[error("{}", t_args("error.no_transactions", &[("account", account), ("month", month)]))]
     NoTransactions { account: String, month: String },
 
-    #[error("Unknown category: {0}")]
+    #[error("{}", t_args("error.unknown_category", &[("category", .0)]))]
     UnknownCategory(String),
 
-    #[error("Settings error: {0}")]
+    #[error("{}", t_args("error.settings", &[("detail", .0)]))]
     Settings(String),
 
-    #[cfg(feature = "pdf")]
-    #[error("PDF error: {0}")]
+    #[cfg(any(feature = "pdf", feature = "pdf-import"))]
+    #[error("{}", t_args("error.pdf", &[("detail", .0)]))]
     Pdf(String),
 
     #[error("{0}")]
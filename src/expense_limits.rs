@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+
+use crate::cli::categories::find_category;
+use crate::currency::HOME_AMOUNT_SQL;
+use crate::error::{NigelError, Result};
+use crate::fmt::money;
+use crate::tags::{self, TAGS_CONCAT_SQL};
+
+/// The per-recipient cap on deductible business gifts for a year (IRC
+/// §274(b)), the usual limit to set.
+pub const GIFT_LIMIT: f64 = 25.0;
+
+/// What to do about a limit warning.
+pub const ADJUST_NOTE: &str =
+    "Only the limit is deductible; move the excess to a non-deductible category before filing.";
+
+/// A yearly per-person cap on what a category can deduct, such as business
+/// gifts or a de minimis fringe benefit. Spend is totalled per counterparty,
+/// which is each tag on the transaction (`#jane-doe`).
+pub struct ExpenseLimit {
+    pub id: i64,
+    pub category_id: i64,
+    /// Full category path, e.g. `Gifts > Clients`.
+    pub category: String,
+    pub per_person: f64,
+    pub note: Option<String>,
+}
+
+pub fn add_limit(
+    conn: &Connection,
+    category: &str,
+    per_person: f64,
+    note: Option<&str>,
+) -> Result<(i64, String)> {
+    let category = find_category(conn, category)?;
+    if category.category_type != "expense" {
+        return Err(NigelError::Other(format!(
+            "'{}' is {}; limits apply to expense categories",
+            category.path, category.category_type
+        )));
+    }
+    if !per_person.is_finite() || per_person <= 0.0 {
+        return Err(NigelError::Other(format!(
+            "Invalid limit: {per_person} (must be greater than zero)"
+        )));
+    }
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM expense_limits WHERE category_id = ?1)",
+        [category.id],
+        |r| r.get(0),
+    )?;
+    if exists {
+        return Err(NigelError::Other(format!(
+            "'{}' already has a limit. Remove it first to change it.",
+            category.path
+        )));
+    }
+    let note = note.map(str::trim).filter(|n| !n.is_empty());
+    conn.execute(
+        "INSERT INTO expense_limits (category_id, per_person, note) VALUES (?1, ?2, ?3)",
+        rusqlite::params![category.id, per_person, note],
+    )?;
+    Ok((conn.last_insert_rowid(), category.path))
+}
+
+pub fn list_limits(conn: &Connection) -> Result<Vec<ExpenseLimit>> {
+    let paths = crate::reports::category_paths(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT l.id, l.category_id, c.name, l.per_person, l.note \
+         FROM expense_limits l JOIN categories c ON l.category_id = c.id ORDER BY l.id",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            let category_id: i64 = row.get(1)?;
+            let name: String = row.get(2)?;
+            Ok(ExpenseLimit {
+                id: row.get(0)?,
+                category_id,
+                category: paths
+                    .get(&category_id)
+                    .map(|p| p.join(crate::reports::CATEGORY_PATH_SEP))
+                    .unwrap_or(name),
+                per_person: row.get(3)?,
+                note: row.get(4)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn remove_limit(conn: &Connection, id: i64) -> Result<()> {
+    let deleted = conn.execute("DELETE FROM expense_limits WHERE id = ?1", [id])?;
+    if deleted == 0 {
+        return Err(NigelError::Other(format!("No expense limit with ID {id}")));
+    }
+    Ok(())
+}
+
+/// Spend in a limited category with one counterparty.
+pub struct CounterpartyTotal {
+    pub tag: String,
+    pub total: f64,
+}
+
+/// A limit with the period's spend against it.
+pub struct LimitCheck {
+    pub limit: ExpenseLimit,
+    /// By tag, largest first.
+    pub counterparties: Vec<CounterpartyTotal>,
+    /// Spend with no tag, which can't be checked.
+    pub untagged: f64,
+}
+
+impl LimitCheck {
+    pub fn over(&self) -> impl Iterator<Item = &CounterpartyTotal> {
+        self.counterparties
+            .iter()
+            .filter(|c| c.total > self.limit.per_person + 0.005)
+    }
+
+    /// What can't be deducted: each counterparty's spend past the limit.
+    pub fn excess(&self) -> f64 {
+        self.over().map(|c| c.total - self.limit.per_person).sum()
+    }
+
+    /// One line per counterparty over the limit, and one for untagged spend.
+    pub fn warnings(&self) -> Vec<String> {
+        let limit = money(self.limit.per_person);
+        let mut lines: Vec<String> = self
+            .over()
+            .map(|c| {
+                format!(
+                    "{}: #{} {}, {} over the {limit} per-person limit",
+                    self.limit.category,
+                    c.tag,
+                    money(c.total),
+                    money(c.total - self.limit.per_person)
+                )
+            })
+            .collect();
+        if self.untagged > 0.005 {
+            lines.push(format!(
+                "{}: {} has no recipient tag, so it can't be checked against the {limit} limit",
+                self.limit.category,
+                money(self.untagged)
+            ));
+        }
+        lines
+    }
+}
+
+/// Totals for every limit over the transactions matching `clause` (a report
+/// period from `reports`, on alias `t`). A transaction with several tags is
+/// split evenly between them; refunds count against the spend.
+pub fn check(conn: &Connection, clause: &str, params: &[String]) -> Result<Vec<LimitCheck>> {
+    let limits = list_limits(conn)?;
+    if limits.is_empty() {
+        return Ok(Vec::new());
+    }
+    let sql = format!(
+        "SELECT t.category_id, -({HOME_AMOUNT_SQL}), {TAGS_CONCAT_SQL} \
+         FROM transactions t JOIN accounts a ON t.account_id = a.id \
+         WHERE {clause} AND t.category_id IN (SELECT category_id FROM main.expense_limits)"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = params
+        .iter()
+        .map(|p| p as &dyn rusqlite::types::ToSql)
+        .collect();
+    let rows = stmt
+        .query_map(params.as_slice(), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, f64>(1)?,
+                tags::from_concat(row.get(2)?),
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut by_tag: HashMap<i64, HashMap<String, f64>> = HashMap::new();
+    let mut untagged: HashMap<i64, f64> = HashMap::new();
+    for (category_id, spend, tags) in rows {
+        if tags.is_empty() {
+            *untagged.entry(category_id).or_default() += spend;
+            continue;
+        }
+        let share = spend / tags.len() as f64;
+        let totals = by_tag.entry(category_id).or_default();
+        for tag in tags {
+            *totals.entry(tag).or_default() += share;
+        }
+    }
+
+    Ok(limits
+        .into_iter()
+        .map(|limit| {
+            let mut counterparties: Vec<CounterpartyTotal> = by_tag
+                .remove(&limit.category_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(tag, total)| CounterpartyTotal { tag, total })
+                .collect();
+            counterparties.sort_by(|a, b| b.total.total_cmp(&a.total).then(a.tag.cmp(&b.tag)));
+            LimitCheck {
+                untagged: untagged.remove(&limit.category_id).unwrap_or(0.0),
+                limit,
+                counterparties,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Card', 'credit_card')",
+            [],
+        )
+        .unwrap();
+        crate::cli::categories::add_category(&conn, "Client Gifts", "expense", None, None, None)
+            .unwrap();
+        (dir, conn)
+    }
+
+    fn gift(conn: &Connection, amount: f64, tags: &str) {
+        let category: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Client Gifts'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, '2025-12-01', 'GIFT SHOP', ?1, ?2)",
+            rusqlite::params![amount, category],
+        )
+        .unwrap();
+        let id = conn.last_insert_rowid();
+        let tags = tags::parse_tags(tags).unwrap();
+        tags::set_transaction_tags(conn, id, &tags).unwrap();
+    }
+
+    #[test]
+    fn limits_belong_to_one_expense_category_each() {
+        let (_dir, conn) = test_db();
+        let (id, path) = add_limit(&conn, "client gifts", GIFT_LIMIT, Some(" §274(b) ")).unwrap();
+        assert_eq!(path, "Client Gifts");
+        assert!(add_limit(&conn, "Client Gifts", 30.0, None)
+            .unwrap_err()
+            .to_string()
+            .contains("already has a limit"));
+        assert!(add_limit(&conn, "Meals", 0.0, None).is_err());
+        let limits = list_limits(&conn).unwrap();
+        assert_eq!(limits.len(), 1);
+        assert_eq!(limits[0].note.as_deref(), Some("§274(b)"));
+        remove_limit(&conn, id).unwrap();
+        assert!(remove_limit(&conn, id).is_err());
+    }
+
+    #[test]
+    fn totals_each_tagged_recipient_against_the_limit() {
+        let (_dir, conn) = test_db();
+        add_limit(&conn, "Client Gifts", GIFT_LIMIT, None).unwrap();
+        gift(&conn, -20.0, "#jane-doe");
+        gift(&conn, -15.0, "#jane-doe");
+        // A $40 gift to two people is $20 each
+        gift(&conn, -40.0, "#sam-lee #jane-doe");
+        gift(&conn, -12.0, "");
+
+        let checks = check(&conn, "t.deleted_at IS NULL", &[]).unwrap();
+        let check = &checks[0];
+        let over: Vec<(&str, f64)> = check.over().map(|c| (c.tag.as_str(), c.total)).collect();
+        assert_eq!(over, [("jane-doe", 55.0)]);
+        assert_eq!(check.excess(), 30.0);
+        assert_eq!(check.untagged, 12.0);
+        let warnings = check.warnings();
+        assert_eq!(
+            warnings[0],
+            "Client Gifts: #jane-doe $55.00, $30.00 over the $25.00 per-person limit"
+        );
+        assert!(warnings[1].contains("$12.00 has no recipient tag"));
+    }
+}
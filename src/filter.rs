@@ -0,0 +1,265 @@
+use crate::error::{NigelError, Result};
+use crate::reports::RegisterRow;
+
+/// A parsed filter expression such as `cat:Software vendor:Adobe amount>100`.
+///
+/// Terms are whitespace-separated and all must match. `cat:`/`category:`,
+/// `vendor:`, `account:`/`acct:`, and `client:` are case-insensitive substring
+/// matches (quote values with spaces: `cat:"Meals & Entertainment"`); `tag:name` or
+/// `#name` matches transactions carrying that exact tag; `amount` takes
+/// `>`, `>=`, `<`, `<=`, `=`, or `:` and compares the absolute amount, so
+/// `amount>100` catches both large charges and large deposits. Any other word
+/// matches the description.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowFilter {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Category(String),
+    Vendor(String),
+    Account(String),
+    Tag(String),
+    Client(String),
+    Amount(Cmp, f64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl RowFilter {
+    pub fn parse(input: &str) -> Result<Self> {
+        let terms = tokenize(input)?
+            .iter()
+            .map(|token| parse_term(token))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, row: &RegisterRow) -> bool {
+        self.terms.iter().all(|term| match term {
+            Term::Category(q) => contains(row.category.as_deref(), q),
+            Term::Vendor(q) => contains(row.vendor.as_deref(), q),
+            Term::Account(q) => contains(Some(&row.account_name), q),
+            Term::Tag(q) => row.tags.iter().any(|t| t == q),
+            Term::Client(q) => contains(row.client.as_deref(), q),
+            Term::Text(q) => contains(Some(&row.description), q),
+            Term::Amount(cmp, value) => {
+                let amount = (row.amount.abs() * 100.0).round() / 100.0;
+                match cmp {
+                    Cmp::Lt => amount < *value,
+                    Cmp::Le => amount <= *value,
+                    Cmp::Gt => amount > *value,
+                    Cmp::Ge => amount >= *value,
+                    Cmp::Eq => (amount - value).abs() < 0.005,
+                }
+            }
+        })
+    }
+}
+
+/// `q` is already lowercased.
+fn contains(field: Option<&str>, q: &str) -> bool {
+    field.is_some_and(|f| f.to_lowercase().contains(q))
+}
+
+/// Split on whitespace, keeping double-quoted runs together and dropping
+/// the quotes.
+fn tokenize(input: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        return Err(NigelError::Other("Unclosed quote in filter".into()));
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn parse_term(token: &str) -> Result<Term> {
+    let lower = token.to_lowercase();
+    // Two-character operators first so `>=` isn't read as `>` then `=100`
+    let amount_cmp = lower.strip_prefix("amount").and_then(|rest| {
+        [
+            (">=", Cmp::Ge),
+            ("<=", Cmp::Le),
+            (">", Cmp::Gt),
+            ("<", Cmp::Lt),
+            ("=", Cmp::Eq),
+            (":", Cmp::Eq),
+        ]
+        .iter()
+        .find_map(|(op, cmp)| rest.strip_prefix(op).map(|v| (*cmp, v)))
+    });
+    if let Some((cmp, value)) = amount_cmp {
+        let value: f64 = value
+            .trim_start_matches('$')
+            .replace(',', "")
+            .parse()
+            .map_err(|_| NigelError::Other(format!("Not an amount: '{value}'")))?;
+        return Ok(Term::Amount(cmp, value.abs()));
+    }
+    if let Some((key, value)) = lower.split_once(':') {
+        let value = value.to_string();
+        let term = match key {
+            "cat" | "category" => Term::Category(value),
+            "vendor" => Term::Vendor(value),
+            "account" | "acct" => Term::Account(value),
+            "tag" => Term::Tag(value.trim_start_matches('#').to_string()),
+            "client" => Term::Client(value),
+            _ => {
+                return Err(NigelError::Other(format!(
+                "Unknown filter '{key}:' (use cat:, vendor:, account:, tag:, client:, or amount)"
+            )))
+            }
+        };
+        if value_is_empty(&term) {
+            return Err(NigelError::Other(format!("'{key}:' needs a value")));
+        }
+        return Ok(term);
+    }
+    if let Some(tag) = lower.strip_prefix('#').filter(|t| !t.is_empty()) {
+        return Ok(Term::Tag(tag.to_string()));
+    }
+    Ok(Term::Text(lower))
+}
+
+fn value_is_empty(term: &Term) -> bool {
+    matches!(term, Term::Category(v) | Term::Vendor(v) | Term::Account(v) | Term::Tag(v) | Term::Client(v) if v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        description: &str,
+        amount: f64,
+        category: Option<&str>,
+        vendor: Option<&str>,
+    ) -> RegisterRow {
+        RegisterRow {
+            id: 1,
+            date: "2025-03-01".into(),
+            description: description.into(),
+            amount,
+            foreign: None,
+            category: category.map(Into::into),
+            category_id: None,
+            vendor: vendor.map(Into::into),
+            account_name: "BofA Checking".into(),
+            is_flagged: false,
+            memo: None,
+            tags: Vec::new(),
+            client: None,
+        }
+    }
+
+    #[test]
+    fn test_all_terms_must_match() {
+        let f = RowFilter::parse("cat:Software vendor:adobe amount>100").unwrap();
+        let adobe = row(
+            "ADOBE CC",
+            -120.0,
+            Some("Software & Subscriptions"),
+            Some("Adobe"),
+        );
+        assert!(f.matches(&adobe));
+        assert!(!f.matches(&row("ADOBE CC", -54.99, Some("Software"), Some("Adobe"))));
+        assert!(!f.matches(&row("FIGMA", -150.0, Some("Software"), Some("Figma"))));
+        assert!(!f.matches(&row("ADOBE CC", -120.0, None, Some("Adobe"))));
+    }
+
+    #[test]
+    fn test_amount_comparisons_use_absolute_value() {
+        let r = row("DEPOSIT", 2500.0, None, None);
+        let charge = row("CHARGE", -2500.0, None, None);
+        for expr in [
+            "amount>=2500",
+            "amount<=2500",
+            "amount=2500",
+            "amount:$2,500.00",
+        ] {
+            let f = RowFilter::parse(expr).unwrap();
+            assert!(f.matches(&r) && f.matches(&charge), "{expr}");
+        }
+        assert!(!RowFilter::parse("amount<2500").unwrap().matches(&r));
+        assert!(!RowFilter::parse("amount>2500").unwrap().matches(&charge));
+    }
+
+    #[test]
+    fn test_quoted_values_and_bare_words() {
+        let f = RowFilter::parse("cat:\"meals & ent\" lunch account:bofa").unwrap();
+        let r = row("Team LUNCH", -40.0, Some("Meals & Entertainment"), None);
+        assert!(f.matches(&r));
+        assert!(!f.matches(&row("Dinner", -40.0, Some("Meals & Entertainment"), None)));
+        assert!(RowFilter::parse("   ").unwrap().is_empty());
+        // A bare word that merely starts with "amount" is a description match
+        assert!(RowFilter::parse("amounts").unwrap().matches(&row(
+            "Amounts due",
+            -1.0,
+            None,
+            None
+        )));
+    }
+
+    #[test]
+    fn test_tag_terms_match_exactly() {
+        let mut r = row("HOTEL", -300.0, Some("Travel"), None);
+        r.tags = vec!["client-acme".into(), "conference2025".into()];
+        for expr in [
+            "tag:client-acme",
+            "#Client-Acme",
+            "tag:#conference2025 hotel",
+        ] {
+            assert!(RowFilter::parse(expr).unwrap().matches(&r), "{expr}");
+        }
+        assert!(!RowFilter::parse("tag:client").unwrap().matches(&r));
+        r.client = Some("Acme Corp".into());
+        assert!(RowFilter::parse("client:acme").unwrap().matches(&r));
+        assert!(!RowFilter::parse("client:globex").unwrap().matches(&r));
+        assert!(!RowFilter::parse("#client-acme")
+            .unwrap()
+            .matches(&row("HOTEL", -300.0, None, None)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for expr in [
+            "amount>abc",
+            "amount<",
+            "color:red",
+            "cat:",
+            "tag:",
+            "client:",
+            "cat:\"open",
+        ] {
+            assert!(RowFilter::parse(expr).is_err(), "{expr}");
+        }
+    }
+}
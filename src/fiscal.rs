@@ -0,0 +1,160 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rusqlite::Connection;
+
+use crate::db::{get_metadata, set_metadata};
+use crate::error::{NigelError, Result};
+
+const MONTH_ABBR: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// A book's fiscal year, starting on the first of `start_month`. Fiscal years
+/// are named for the calendar year they end in: with a July start, FY2025 runs
+/// 2024-07-01 through 2025-06-30. Stored per database in `metadata` as
+/// `fiscal_year_start_month`; the default is the calendar year.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiscalYear {
+    start_month: u32,
+}
+
+impl FiscalYear {
+    pub const CALENDAR: FiscalYear = FiscalYear { start_month: 1 };
+
+    /// Fiscal year starting in `start_month` (1-12); anything else means January.
+    pub fn new(start_month: u32) -> Self {
+        if (1..=12).contains(&start_month) {
+            Self { start_month }
+        } else {
+            Self::CALENDAR
+        }
+    }
+
+    pub fn load(conn: &Connection) -> Self {
+        get_metadata(conn, "fiscal_year_start_month")
+            .and_then(|v| v.parse().ok())
+            .map(Self::new)
+            .unwrap_or(Self::CALENDAR)
+    }
+
+    pub fn start_month(self) -> u32 {
+        self.start_month
+    }
+
+    pub fn is_calendar(self) -> bool {
+        self.start_month == 1
+    }
+
+    /// First and last day of fiscal year `fy`.
+    pub fn bounds(self, fy: i32) -> (NaiveDate, NaiveDate) {
+        let start_year = if self.is_calendar() { fy } else { fy - 1 };
+        let start =
+            NaiveDate::from_ymd_opt(start_year, self.start_month, 1).unwrap_or(NaiveDate::MIN);
+        let end = start
+            .checked_add_months(Months::new(12))
+            .and_then(|d| d.pred_opt())
+            .unwrap_or(start);
+        (start, end)
+    }
+
+    /// The fiscal year containing `date`.
+    pub fn year_of(self, date: NaiveDate) -> i32 {
+        if !self.is_calendar() && date.month() >= self.start_month {
+            date.year() + 1
+        } else {
+            date.year()
+        }
+    }
+
+    /// The fiscal year containing `month` of calendar `year`.
+    pub fn year_of_month(self, year: i32, month: u32) -> i32 {
+        if !self.is_calendar() && month >= self.start_month {
+            year + 1
+        } else {
+            year
+        }
+    }
+
+    /// The calendar year `month` falls in within fiscal year `fy`.
+    pub fn calendar_year_of(self, fy: i32, month: u32) -> i32 {
+        if !self.is_calendar() && month >= self.start_month {
+            fy - 1
+        } else {
+            fy
+        }
+    }
+
+    pub fn current(self) -> i32 {
+        self.year_of(chrono::Local::now().date_naive())
+    }
+
+    /// "FY 2025" for calendar years, "FY2025 (Jul–Jun)" otherwise.
+    pub fn label(self, fy: i32) -> String {
+        if self.is_calendar() {
+            return format!("FY {fy}");
+        }
+        let first = MONTH_ABBR[(self.start_month - 1) as usize];
+        let last = MONTH_ABBR[((self.start_month + 10) % 12) as usize];
+        format!("FY{fy} ({first}\u{2013}{last})")
+    }
+}
+
+/// Abbreviated month name for a start-month picker: `month_abbr(7)` is "Jul".
+pub fn month_abbr(month: u32) -> &'static str {
+    MONTH_ABBR
+        .get((month.max(1) - 1) as usize)
+        .copied()
+        .unwrap_or("Jan")
+}
+
+pub fn set_start_month(conn: &Connection, month: u32) -> Result<()> {
+    if !(1..=12).contains(&month) {
+        return Err(NigelError::Other(format!(
+            "Invalid fiscal year start month: {month} (must be 1-12)"
+        )));
+    }
+    set_metadata(conn, "fiscal_year_start_month", &month.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_calendar_year() {
+        let fy = FiscalYear::CALENDAR;
+        assert_eq!(fy.bounds(2025), (date("2025-01-01"), date("2025-12-31")));
+        assert_eq!(fy.year_of(date("2025-07-15")), 2025);
+        assert_eq!(fy.label(2025), "FY 2025");
+    }
+
+    #[test]
+    fn test_july_start() {
+        let fy = FiscalYear::new(7);
+        assert_eq!(fy.bounds(2025), (date("2024-07-01"), date("2025-06-30")));
+        assert_eq!(fy.year_of(date("2024-07-01")), 2025);
+        assert_eq!(fy.year_of(date("2025-06-30")), 2025);
+        assert_eq!(fy.year_of(date("2025-07-01")), 2026);
+        assert_eq!(fy.year_of_month(2024, 12), 2025);
+        assert_eq!(fy.calendar_year_of(2025, 12), 2024);
+        assert_eq!(fy.calendar_year_of(2025, 3), 2025);
+        assert_eq!(fy.label(2025), "FY2025 (Jul\u{2013}Jun)");
+        assert_eq!(FiscalYear::new(2).label(2025), "FY2025 (Feb\u{2013}Jan)");
+    }
+
+    #[test]
+    fn test_load_and_set_start_month() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        assert_eq!(FiscalYear::load(&conn), FiscalYear::CALENDAR);
+        set_start_month(&conn, 10).unwrap();
+        assert_eq!(FiscalYear::load(&conn).start_month(), 10);
+        assert!(set_start_month(&conn, 13).is_err());
+        assert_eq!(FiscalYear::new(0), FiscalYear::CALENDAR);
+    }
+}
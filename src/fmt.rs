@@ -1,45 +1,219 @@
-/// Format a float as a dollar amount with thousands separators: $1,234.56
-pub fn money(val: f64) -> String {
-    let negative = val < 0.0;
-    let abs = val.abs();
-    let cents = format!("{:.2}", abs);
-    let parts: Vec<&str> = cents.split('.').collect();
-    let int_part = parts[0];
-    let dec_part = parts[1];
-
-    let mut with_commas = String::new();
-    for (i, c) in int_part.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            with_commas.push(',');
+use std::sync::{Arc, RwLock};
+
+use chrono::NaiveDate;
+
+use crate::settings::{load_settings, Settings};
+
+/// Date display formats for the `date_format` setting, in the order the
+/// Settings screen cycles them. Dates are stored as YYYY-MM-DD either way.
+pub const DATE_FORMATS: [&str; 3] = ["YYYY-MM-DD", "DD/MM/YYYY", "MM/DD/YYYY"];
+
+/// Decimal separators for the `decimal_separator` setting; thousands are
+/// grouped with the other one.
+pub const DECIMAL_SEPARATORS: [char; 2] = ['.', ','];
+
+/// How amounts and dates are shown: the `currency_symbol`,
+/// `decimal_separator`, and `date_format` settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayFormat {
+    pub currency_symbol: String,
+    pub decimal_separator: char,
+    /// One of `DATE_FORMATS`.
+    pub date_format: &'static str,
+}
+
+impl Default for DisplayFormat {
+    /// $1,234.56 and 2025-01-15.
+    fn default() -> Self {
+        Self {
+            currency_symbol: "$".into(),
+            decimal_separator: DECIMAL_SEPARATORS[0],
+            date_format: DATE_FORMATS[0],
         }
-        with_commas.push(c);
     }
-    let with_commas: String = with_commas.chars().rev().collect();
+}
 
-    if negative {
-        format!("-${with_commas}.{dec_part}")
-    } else {
-        format!("${with_commas}.{dec_part}")
+impl DisplayFormat {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            currency_symbol: settings.currency_symbol.clone(),
+            decimal_separator: settings.decimal_separator(),
+            date_format: settings.date_format(),
+        }
+    }
+
+    fn group_separator(&self) -> char {
+        if self.decimal_separator == ',' {
+            '.'
+        } else {
+            ','
+        }
+    }
+
+    /// Digits grouped in threes: 1,224
+    fn grouped(&self, digits: &str) -> String {
+        let mut grouped = String::new();
+        for (i, c) in digits.chars().rev().enumerate() {
+            if i > 0 && i % 3 == 0 {
+                grouped.push(self.group_separator());
+            }
+            grouped.push(c);
+        }
+        grouped.chars().rev().collect()
+    }
+
+    /// The absolute value with thousands separators: 1,234.56
+    fn grouped_cents(&self, val: f64) -> String {
+        let cents = format!("{:.2}", val.abs());
+        let (int_part, dec_part) = cents.split_once('.').unwrap_or((&cents, "00"));
+        format!(
+            "{}{}{dec_part}",
+            self.grouped(int_part),
+            self.decimal_separator
+        )
+    }
+
+    pub fn money(&self, val: f64) -> String {
+        let sign = if val < 0.0 { "-" } else { "" };
+        format!("{sign}{}{}", self.currency_symbol, self.grouped_cents(val))
+    }
+
+    pub fn money_in(&self, val: f64, currency: &str) -> String {
+        let sign = if val < 0.0 { "-" } else { "" };
+        format!("{sign}{} {currency}", self.grouped_cents(val))
+    }
+
+    pub fn money_compact(&self, val: f64) -> String {
+        let sign = if val < 0.0 { "-" } else { "" };
+        let symbol = &self.currency_symbol;
+        let val = val.abs();
+        let scaled = |v: f64, suffix: &str| {
+            if v == v.floor() {
+                format!("{sign}{symbol}{}{suffix}", v as u64)
+            } else {
+                let tenths = format!("{v:.1}").replace('.', &self.decimal_separator.to_string());
+                format!("{sign}{symbol}{tenths}{suffix}")
+            }
+        };
+        if val >= 1_000_000.0 {
+            scaled(val / 1_000_000.0, "M")
+        } else if val >= 1000.0 {
+            scaled(val / 1000.0, "k")
+        } else {
+            format!("{sign}{symbol}{}", val as u64)
+        }
+    }
+
+    pub fn number(&self, val: i64) -> String {
+        let grouped = self.grouped(&val.unsigned_abs().to_string());
+        if val < 0 {
+            format!("-{grouped}")
+        } else {
+            grouped
+        }
+    }
+
+    /// The chrono pattern for `date_format`.
+    pub fn date_pattern(&self) -> &'static str {
+        match self.date_format {
+            "DD/MM/YYYY" => "%d/%m/%Y",
+            "MM/DD/YYYY" => "%m/%d/%Y",
+            _ => "%Y-%m-%d",
+        }
+    }
+
+    /// A stored YYYY-MM-DD date as `date_format` shows it; anything else is
+    /// returned as is.
+    pub fn date(&self, iso: &str) -> String {
+        match NaiveDate::parse_from_str(iso, "%Y-%m-%d") {
+            Ok(d) => d.format(self.date_pattern()).to_string(),
+            Err(_) => iso.to_string(),
+        }
+    }
+
+    /// A typed date, YYYY-MM-DD or in `date_format`, as YYYY-MM-DD.
+    pub fn parse_date(&self, input: &str) -> Option<String> {
+        let input = input.trim();
+        ["%Y-%m-%d", self.date_pattern()]
+            .iter()
+            .find_map(|p| NaiveDate::parse_from_str(input, p).ok())
+            .map(|d| d.format("%Y-%m-%d").to_string())
+    }
+}
+
+/// The format in use; None until the first call reads the settings.
+static CURRENT: RwLock<Option<Arc<DisplayFormat>>> = RwLock::new(None);
+
+/// Switch how amounts and dates are shown, e.g. after the Settings screen
+/// changes them.
+pub fn set_display_format(format: DisplayFormat) {
+    if let Ok(mut current) = CURRENT.write() {
+        *current = Some(Arc::new(format));
+    }
+}
+
+pub fn display_format() -> Arc<DisplayFormat> {
+    if let Some(format) = CURRENT.read().ok().and_then(|f| f.clone()) {
+        return format;
+    }
+    let format = Arc::new(DisplayFormat::from_settings(&load_settings()));
+    if let Ok(mut current) = CURRENT.write() {
+        *current = Some(format.clone());
     }
+    format
+}
+
+/// Format a float as an amount in the home currency with thousands
+/// separators: $1,234.56
+pub fn money(val: f64) -> String {
+    display_format().money(val)
+}
+
+/// Format a float in an explicit currency: -1,234.56 EUR
+pub fn money_in(val: f64, currency: &str) -> String {
+    display_format().money_in(val, currency)
+}
+
+/// Format an amount as compact "$Xk" or "$X.Xk" for thousands, "$XM" for
+/// millions, for chart axes: -$2.5k
+pub fn money_compact(val: f64) -> String {
+    display_format().money_compact(val)
 }
 
 /// Format an integer with thousands separators: 1,224
 pub fn number(val: i64) -> String {
-    let negative = val < 0;
-    let abs = val.unsigned_abs().to_string();
-    let mut with_commas = String::new();
-    for (i, c) in abs.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            with_commas.push(',');
-        }
-        with_commas.push(c);
+    display_format().number(val)
+}
+
+/// Show a stored YYYY-MM-DD date in the `date_format` setting.
+pub fn date(iso: &str) -> String {
+    display_format().date(iso)
+}
+
+/// Format a signed percent change: "+12.5%", "-3.0%", or "n/a" when there
+/// is no base to compare against
+pub fn pct_change(pct: Option<f64>) -> String {
+    match pct {
+        Some(p) => format!("{p:+.1}%"),
+        None => "n/a".to_string(),
     }
-    let with_commas: String = with_commas.chars().rev().collect();
-    if negative {
-        format!("-{with_commas}")
-    } else {
-        with_commas
+}
+
+/// Text columns for one period-comparison line: the selected period's amount,
+/// then each comparison amount followed by the dollar and percent change from
+/// it to the selected period.
+pub fn compare_columns(values: &[f64]) -> Vec<String> {
+    let Some((&current, bases)) = values.split_first() else {
+        return Vec::new();
+    };
+    let mut cols = vec![money(current)];
+    for &base in bases {
+        let (diff, pct) = crate::reports::period_change(current, base);
+        cols.push(money(base));
+        cols.push(money(diff));
+        cols.push(pct_change(pct));
     }
+    cols
 }
 
 /// Format a byte count as a human-readable string: "1.2 KB", "3.4 MB"
@@ -64,6 +238,22 @@ pub fn format_bytes(bytes: u64) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pct_change_formatting() {
+        assert_eq!(pct_change(Some(12.46)), "+12.5%");
+        assert_eq!(pct_change(Some(-3.0)), "-3.0%");
+        assert_eq!(pct_change(None), "n/a");
+    }
+
+    #[test]
+    fn test_compare_columns() {
+        assert_eq!(
+            compare_columns(&[150.0, 100.0, 0.0]),
+            vec!["$150.00", "$100.00", "$50.00", "+50.0%", "$0.00", "$150.00", "n/a"]
+        );
+        assert!(compare_columns(&[]).is_empty());
+    }
+
     #[test]
     fn test_money_formatting() {
         assert_eq!(money(1234.56), "$1,234.56");
@@ -73,6 +263,46 @@ mod tests {
         assert_eq!(money(42.10), "$42.10");
     }
 
+    #[test]
+    fn test_money_in_formatting() {
+        assert_eq!(money_in(1234.5, "EUR"), "1,234.50 EUR");
+        assert_eq!(money_in(-0.99, "GBP"), "-0.99 GBP");
+    }
+
+    #[test]
+    fn test_money_compact_formatting() {
+        assert_eq!(money_compact(2500.0), "$2.5k");
+        assert_eq!(money_compact(-10000.0), "-$10k");
+        assert_eq!(money_compact(1_000_000.0), "$1M");
+        assert_eq!(money_compact(0.0), "$0");
+    }
+
+    #[test]
+    fn test_display_format_follows_the_settings() {
+        let euro = DisplayFormat {
+            currency_symbol: "€".into(),
+            decimal_separator: ',',
+            date_format: "DD/MM/YYYY",
+        };
+        assert_eq!(euro.money(-1234567.5), "-€1.234.567,50");
+        assert_eq!(euro.money_in(1234.5, "USD"), "1.234,50 USD");
+        assert_eq!(euro.money_compact(2500.0), "€2,5k");
+        assert_eq!(euro.number(-1224), "-1.224");
+        assert_eq!(euro.date("2025-01-15"), "15/01/2025");
+        assert_eq!(euro.date("not a date"), "not a date");
+        assert_eq!(euro.parse_date("15/01/2025").as_deref(), Some("2025-01-15"));
+        assert_eq!(euro.parse_date("2025-01-15").as_deref(), Some("2025-01-15"));
+        assert_eq!(euro.parse_date("01/15/2025"), None);
+
+        let us = DisplayFormat {
+            date_format: "MM/DD/YYYY",
+            ..DisplayFormat::default()
+        };
+        assert_eq!(us.money(1234.56), "$1,234.56");
+        assert_eq!(us.date("2025-01-15"), "01/15/2025");
+        assert_eq!(DisplayFormat::default().date("2025-01-15"), "2025-01-15");
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), "0 B");
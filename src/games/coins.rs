@@ -0,0 +1,380 @@
+use crossterm::event::KeyCode;
+use rand::Rng;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+use crate::effects::gradient_color;
+use crate::games::{high_score_line, is_new_record, GameAction};
+
+const BASE_TICK_MS: u64 = 120;
+const MIN_TICK_MS: u64 = 45;
+const TILL_WIDTH: u16 = 5;
+const LIVES: u8 = 3;
+/// Ticks between new coins at the start; shrinks as coins are caught.
+const BASE_SPAWN_TICKS: u32 = 8;
+
+/// Denominations that fall, with a weight each: small change is common.
+const COINS: &[(f64, u32)] = &[(0.25, 8), (1.0, 4), (5.0, 2), (20.0, 1)];
+
+pub struct Coin {
+    pub x: u16,
+    pub y: u16,
+    pub value: f64,
+}
+
+/// Petty Cash: coins fall from the top and the till at the bottom catches
+/// them. Every coin that hits the floor costs a life.
+pub struct CoinGame {
+    pub coins: Vec<Coin>,
+    /// Left edge of the till.
+    pub till_x: u16,
+    pub score: f64,
+    pub caught: u32,
+    pub lives: u8,
+    pub game_over: bool,
+    /// Best score before this round, shown on the game-over overlay.
+    pub high_score: Option<f64>,
+    pub board_width: u16,
+    pub board_height: u16,
+    ticks_to_spawn: u32,
+    last_tick: Instant,
+    rng: rand::rngs::ThreadRng,
+    phase: f64,
+}
+
+impl CoinGame {
+    pub fn new() -> Self {
+        let board_width: u16 = 30;
+        Self {
+            coins: Vec::new(),
+            till_x: (board_width - TILL_WIDTH) / 2,
+            score: 0.0,
+            caught: 0,
+            lives: LIVES,
+            game_over: false,
+            high_score: None,
+            board_width,
+            board_height: 20,
+            ticks_to_spawn: 0,
+            last_tick: Instant::now(),
+            rng: rand::thread_rng(),
+            phase: 0.0,
+        }
+    }
+
+    fn random_coin_value(rng: &mut rand::rngs::ThreadRng) -> f64 {
+        let total: u32 = COINS.iter().map(|(_, w)| w).sum();
+        let mut pick = rng.gen_range(0..total);
+        for (value, weight) in COINS {
+            if pick < *weight {
+                return *value;
+            }
+            pick -= weight;
+        }
+        COINS[0].0
+    }
+
+    fn spawn_interval(&self) -> u32 {
+        BASE_SPAWN_TICKS.saturating_sub(self.caught / 10).max(3)
+    }
+
+    fn tick(&mut self) {
+        if self.game_over {
+            return;
+        }
+
+        let till_row = self.board_height.saturating_sub(1);
+        let till = self.till_x..self.till_x + TILL_WIDTH;
+        let mut missed = 0;
+        let mut caught = Vec::new();
+        self.coins.retain_mut(|coin| {
+            coin.y += 1;
+            if coin.y == till_row && till.contains(&coin.x) {
+                caught.push(coin.value);
+                false
+            } else if coin.y > till_row {
+                missed += 1;
+                false
+            } else {
+                true
+            }
+        });
+        for value in caught {
+            self.score += value;
+            self.caught += 1;
+        }
+        self.lives = self.lives.saturating_sub(missed);
+        if self.lives == 0 {
+            self.game_over = true;
+            return;
+        }
+
+        if self.ticks_to_spawn == 0 {
+            let x = self.rng.gen_range(0..self.board_width);
+            let value = Self::random_coin_value(&mut self.rng);
+            self.coins.push(Coin { x, y: 0, value });
+            self.ticks_to_spawn = self.spawn_interval();
+        } else {
+            self.ticks_to_spawn -= 1;
+        }
+    }
+
+    fn max_till_x(&self) -> u16 {
+        self.board_width.saturating_sub(TILL_WIDTH)
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> GameAction {
+        match code {
+            KeyCode::Esc => return GameAction::Quit,
+            _ if self.game_over => {
+                if let KeyCode::Char('r') | KeyCode::Char('R') = code {
+                    let high_score = if is_new_record(self.score, self.high_score) {
+                        Some(self.score)
+                    } else {
+                        self.high_score
+                    };
+                    *self = Self::new();
+                    self.high_score = high_score;
+                }
+            }
+            KeyCode::Left => self.till_x = self.till_x.saturating_sub(2),
+            KeyCode::Right => self.till_x = (self.till_x + 2).min(self.max_till_x()),
+            _ => {}
+        }
+        GameAction::Continue
+    }
+
+    pub fn do_tick(&mut self) {
+        self.tick();
+        self.last_tick = Instant::now();
+        self.phase += 1.0 / 70.0;
+    }
+
+    pub fn tick_rate(&self) -> Duration {
+        // -1ms per coin caught, floored at MIN_TICK_MS
+        let speed_ms = BASE_TICK_MS.saturating_sub(self.caught as u64);
+        let tick = Duration::from_millis(speed_ms.max(MIN_TICK_MS));
+        tick.saturating_sub(self.last_tick.elapsed())
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let full = frame.area();
+
+        // Cells are 2 chars wide like Snake's
+        let board_width = ((full.width.saturating_sub(2)) / 2).clamp(TILL_WIDTH, 30);
+        let board_height = (full.height.saturating_sub(2)).clamp(2, 24);
+        let render_w = board_width * 2 + 2;
+        let render_h = board_height + 2;
+        let area = Rect::new(
+            full.x + full.width.saturating_sub(render_w) / 2,
+            full.y + full.height.saturating_sub(render_h) / 2,
+            render_w.min(full.width),
+            render_h.min(full.height),
+        );
+
+        if board_width != self.board_width || board_height != self.board_height {
+            self.board_width = board_width;
+            self.board_height = board_height;
+            self.coins
+                .retain(|c| c.x < board_width && c.y < board_height);
+            self.till_x = self.till_x.min(self.max_till_x());
+        }
+
+        let title = format!(
+            " $ Petty Cash $ | Score: ${:.2} | Lives: {} ",
+            self.score,
+            "\u{2665}".repeat(self.lives as usize)
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(gradient_color(self.phase)))
+            .title(
+                Line::from(Span::styled(
+                    title,
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Center),
+            )
+            .title_bottom(
+                Line::from(Span::styled(
+                    " Left/Right: move the till | Esc: quit ",
+                    Style::default().fg(Color::DarkGray),
+                ))
+                .alignment(Alignment::Center),
+            );
+
+        let till_row = board_height - 1;
+        let till = self.till_x..self.till_x + TILL_WIDTH;
+        let mut lines: Vec<Line> = Vec::with_capacity(board_height as usize);
+        for y in 0..board_height {
+            let mut spans: Vec<Span> = Vec::with_capacity(board_width as usize);
+            for x in 0..board_width {
+                if let Some(coin) = self.coins.iter().find(|c| c.x == x && c.y == y) {
+                    let (label, color) = coin_style(coin.value);
+                    spans.push(Span::styled(
+                        label,
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ));
+                } else if y == till_row && till.contains(&x) {
+                    spans.push(Span::styled(
+                        "\u{2580}\u{2580}",
+                        Style::default()
+                            .fg(crate::tui::GREEN)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                } else {
+                    spans.push(Span::raw("  "));
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+
+        if self.game_over {
+            let overlay_width: u16 = 34;
+            let overlay_height: u16 = 6;
+            let ox = area.x + area.width.saturating_sub(overlay_width) / 2;
+            let oy = area.y + area.height.saturating_sub(overlay_height) / 2;
+            let overlay_rect = Rect::new(ox, oy, overlay_width, overlay_height);
+
+            let overlay_block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(
+                    Line::from(Span::styled(
+                        " Till's Empty ",
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    ))
+                    .alignment(Alignment::Center),
+                );
+            let overlay_lines = vec![
+                Line::from(Span::styled(
+                    format!("Final Score: ${:.2}", self.score),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                high_score_line(self.score, self.high_score),
+                Line::from(vec![
+                    Span::styled(
+                        "[R]",
+                        Style::default()
+                            .fg(Color::Green)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" Restart  "),
+                    Span::styled(
+                        "[Esc]",
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" Quit"),
+                ]),
+            ];
+            frame.render_widget(Clear, overlay_rect);
+            frame.render_widget(
+                Paragraph::new(overlay_lines)
+                    .block(overlay_block)
+                    .alignment(Alignment::Center),
+                overlay_rect,
+            );
+        }
+    }
+}
+
+/// Two-character glyph and color for a coin: bigger money, brighter color.
+fn coin_style(value: f64) -> (&'static str, Color) {
+    if value >= 20.0 {
+        ("$$", Color::Magenta)
+    } else if value >= 5.0 {
+        ("$ ", crate::tui::GREEN)
+    } else if value >= 1.0 {
+        ("\u{25cf} ", Color::Yellow)
+    } else {
+        ("\u{00b7} ", Color::Gray)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drop_coin(game: &mut CoinGame, x: u16, value: f64) {
+        // One row above the till so the next tick lands it
+        let y = game.board_height - 2;
+        game.coins.push(Coin { x, y, value });
+        // Keep the spawner out of the way
+        game.ticks_to_spawn = 100;
+    }
+
+    #[test]
+    fn catching_a_coin_scores_it() {
+        let mut game = CoinGame::new();
+        let x = game.till_x + 1;
+        drop_coin(&mut game, x, 5.0);
+        game.tick();
+        assert_eq!(game.score, 5.0);
+        assert_eq!(game.caught, 1);
+        assert_eq!(game.lives, LIVES);
+        assert!(game.coins.is_empty());
+    }
+
+    #[test]
+    fn missing_coins_costs_lives_until_game_over() {
+        let mut game = CoinGame::new();
+        game.till_x = 0;
+        let x = game.board_width - 1;
+        for _ in 0..LIVES {
+            assert!(!game.game_over);
+            drop_coin(&mut game, x, 1.0);
+            game.tick();
+            game.tick();
+        }
+        assert_eq!(game.lives, 0);
+        assert!(game.game_over);
+        assert_eq!(game.score, 0.0);
+    }
+
+    #[test]
+    fn till_stays_on_the_board() {
+        let mut game = CoinGame::new();
+        for _ in 0..50 {
+            game.handle_key(KeyCode::Right);
+        }
+        assert_eq!(game.till_x, game.board_width - TILL_WIDTH);
+        for _ in 0..50 {
+            game.handle_key(KeyCode::Left);
+        }
+        assert_eq!(game.till_x, 0);
+    }
+
+    #[test]
+    fn restart_keeps_the_score_to_beat() {
+        let mut game = CoinGame::new();
+        game.game_over = true;
+        game.score = 12.5;
+        game.high_score = Some(10.0);
+        game.handle_key(KeyCode::Char('r'));
+        assert!(!game.game_over);
+        assert_eq!(game.score, 0.0);
+        assert_eq!(game.lives, LIVES);
+        assert_eq!(game.high_score, Some(12.5));
+        assert!(matches!(game.handle_key(KeyCode::Esc), GameAction::Quit));
+    }
+
+    #[test]
+    fn coin_values_are_denominations() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let value = CoinGame::random_coin_value(&mut rng);
+            assert!(COINS.iter().any(|(v, _)| *v == value));
+        }
+    }
+}
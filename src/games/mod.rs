@@ -0,0 +1,457 @@
+//! The arcade: Snake and Petty Cash, with high scores kept in the book's
+//! database. Built only with the `games` feature.
+
+pub mod coins;
+pub mod snake;
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use rusqlite::Connection;
+use std::time::Duration;
+
+use crate::cli::screen::{self, Nav, ScreenContext};
+use crate::error::Result;
+use crate::tui::{FOOTER_STYLE, HEADER_STYLE};
+use coins::CoinGame;
+use snake::SnakeGame;
+
+/// Scores kept per game; older, lower ones are pruned.
+pub const HIGH_SCORES_KEPT: usize = 10;
+
+pub enum GameAction {
+    Continue,
+    Quit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Game {
+    Snake,
+    PettyCash,
+}
+
+impl Game {
+    pub const ALL: [Game; 2] = [Game::Snake, Game::PettyCash];
+
+    /// Stored in `high_scores.game`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Game::Snake => "snake",
+            Game::PettyCash => "petty_cash",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Game::Snake => "Snake",
+            Game::PettyCash => "Petty Cash",
+        }
+    }
+
+    fn blurb(self) -> &'static str {
+        match self {
+            Game::Snake => "Eat the money, don't hit the walls",
+            Game::PettyCash => "Catch falling coins in the till",
+        }
+    }
+}
+
+pub struct HighScore {
+    pub score: f64,
+    pub played_at: String,
+}
+
+/// A score beats `best` when it is positive and higher; the first positive
+/// score of a game is a record.
+pub fn is_new_record(score: f64, best: Option<f64>) -> bool {
+    score > 0.0 && best.is_none_or(|b| score > b)
+}
+
+/// "New high score!" or "High score: $x" for a game-over overlay.
+pub fn high_score_line(score: f64, best: Option<f64>) -> Line<'static> {
+    if is_new_record(score, best) {
+        Line::from(Span::styled(
+            "New high score!",
+            Style::default()
+                .fg(crate::tui::GREEN)
+                .add_modifier(Modifier::BOLD),
+        ))
+    } else {
+        Line::from(Span::styled(
+            format!("High score: ${:.2}", best.unwrap_or(0.0)),
+            Style::default().fg(Color::DarkGray),
+        ))
+    }
+}
+
+/// A game's best scores, highest first.
+pub fn top_scores(conn: &Connection, game: Game, limit: usize) -> Result<Vec<HighScore>> {
+    let mut stmt = conn.prepare(
+        "SELECT score, played_at FROM high_scores WHERE game = ?1 \
+         ORDER BY score DESC, id LIMIT ?2",
+    )?;
+    let rows = stmt
+        .query_map(rusqlite::params![game.key(), limit as i64], |row| {
+            Ok(HighScore {
+                score: row.get(0)?,
+                played_at: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Save a finished game's score, keeping the top `HIGH_SCORES_KEPT`, and
+/// return whether it is a new high score. Zero scores aren't saved.
+pub fn record_score(conn: &Connection, game: Game, score: f64) -> Result<bool> {
+    if score <= 0.0 {
+        return Ok(false);
+    }
+    let best = top_scores(conn, game, 1)?.first().map(|s| s.score);
+    conn.execute(
+        "INSERT INTO high_scores (game, score) VALUES (?1, ?2)",
+        rusqlite::params![game.key(), score],
+    )?;
+    conn.execute(
+        "DELETE FROM high_scores WHERE game = ?1 AND id NOT IN \
+         (SELECT id FROM high_scores WHERE game = ?1 ORDER BY score DESC, id LIMIT ?2)",
+        rusqlite::params![game.key(), HIGH_SCORES_KEPT as i64],
+    )?;
+    Ok(is_new_record(score, best))
+}
+
+enum Playing {
+    Snake(SnakeGame),
+    PettyCash(CoinGame),
+}
+
+impl Playing {
+    fn game(&self) -> Game {
+        match self {
+            Playing::Snake(_) => Game::Snake,
+            Playing::PettyCash(_) => Game::PettyCash,
+        }
+    }
+
+    fn score(&self) -> f64 {
+        match self {
+            Playing::Snake(g) => g.score,
+            Playing::PettyCash(g) => g.score,
+        }
+    }
+
+    fn game_over(&self) -> bool {
+        match self {
+            Playing::Snake(g) => g.game_over,
+            Playing::PettyCash(g) => g.game_over,
+        }
+    }
+}
+
+pub enum ArcadeAction {
+    Continue,
+    Close,
+}
+
+/// Game picker with each game's high scores; runs the chosen game in place.
+pub struct Arcade {
+    greeting: String,
+    selection: usize,
+    /// Top scores per game, indexed like `Game::ALL`.
+    scores: Vec<Vec<HighScore>>,
+    playing: Option<Playing>,
+    /// Whether the current round's score has been saved.
+    recorded: bool,
+    /// The latest high score set this visit, for the dashboard badge.
+    pub new_high_score: Option<(Game, f64)>,
+    status_message: Option<String>,
+}
+
+impl Arcade {
+    pub fn new(conn: &Connection, greeting: &str) -> Self {
+        let mut arcade = Self {
+            greeting: greeting.to_string(),
+            selection: 0,
+            scores: Vec::new(),
+            playing: None,
+            recorded: false,
+            new_high_score: None,
+            status_message: None,
+        };
+        arcade.load_scores(conn);
+        arcade
+    }
+
+    fn load_scores(&mut self, conn: &Connection) {
+        self.scores = Game::ALL
+            .iter()
+            .map(|g| top_scores(conn, *g, 5).unwrap_or_default())
+            .collect();
+    }
+
+    fn best(&self, game: Game) -> Option<f64> {
+        let idx = Game::ALL.iter().position(|g| *g == game)?;
+        self.scores.get(idx)?.first().map(|s| s.score)
+    }
+
+    fn start(&mut self, game: Game) {
+        let best = self.best(game);
+        self.playing = Some(match game {
+            Game::Snake => {
+                let mut g = SnakeGame::new();
+                g.high_score = best;
+                Playing::Snake(g)
+            }
+            Game::PettyCash => {
+                let mut g = CoinGame::new();
+                g.high_score = best;
+                Playing::PettyCash(g)
+            }
+        });
+        self.recorded = false;
+    }
+
+    /// True while a game is running, so the dashboard ticks it and keeps the
+    /// command palette closed.
+    pub fn is_playing(&self) -> bool {
+        self.playing.is_some()
+    }
+
+    /// Time until the running game's next tick.
+    pub fn tick_rate(&self) -> Option<Duration> {
+        match self.playing.as_ref()? {
+            Playing::Snake(g) => Some(g.tick_rate()),
+            Playing::PettyCash(g) => Some(g.tick_rate()),
+        }
+    }
+
+    pub fn do_tick(&mut self, conn: &Connection) {
+        match self.playing.as_mut() {
+            Some(Playing::Snake(g)) => g.do_tick(),
+            Some(Playing::PettyCash(g)) => g.do_tick(),
+            None => return,
+        }
+        self.record_if_over(conn);
+    }
+
+    /// Save the score once when a round ends. Read-only books can still be
+    /// played; their scores just aren't kept.
+    fn record_if_over(&mut self, conn: &Connection) {
+        let Some(playing) = &self.playing else {
+            return;
+        };
+        if !playing.game_over() {
+            self.recorded = false;
+            return;
+        }
+        if self.recorded || crate::db::is_read_only() {
+            return;
+        }
+        self.recorded = true;
+        let (game, score) = (playing.game(), playing.score());
+        match record_score(conn, game, score) {
+            Ok(true) => self.new_high_score = Some((game, score)),
+            Ok(false) => {}
+            Err(e) => self.status_message = Some(format!("Could not save score: {e}")),
+        }
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode, conn: &Connection) -> ArcadeAction {
+        if let Some(playing) = self.playing.as_mut() {
+            let action = match playing {
+                Playing::Snake(g) => g.handle_key(code),
+                Playing::PettyCash(g) => g.handle_key(code),
+            };
+            if let GameAction::Quit = action {
+                self.playing = None;
+                self.load_scores(conn);
+            } else {
+                self.record_if_over(conn);
+            }
+            return ArcadeAction::Continue;
+        }
+
+        self.status_message = None;
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return ArcadeAction::Close,
+            KeyCode::Up => self.selection = self.selection.saturating_sub(1),
+            KeyCode::Down => self.selection = (self.selection + 1).min(Game::ALL.len() - 1),
+            KeyCode::Enter => self.start(Game::ALL[self.selection]),
+            _ => {}
+        }
+        ArcadeAction::Continue
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        match self.playing.as_mut() {
+            Some(Playing::Snake(g)) => return g.draw(frame),
+            Some(Playing::PettyCash(g)) => return g.draw(frame),
+            None => {}
+        }
+
+        let area = frame.area();
+        let border_style = Style::default().fg(Color::DarkGray);
+        let [header_area, sep, content_area, status_area, hints_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!(" {}", self.greeting)).style(HEADER_STYLE),
+            header_area,
+        );
+        let sep_line = "\u{2501}".repeat(area.width as usize);
+        frame.render_widget(Paragraph::new(sep_line.as_str()).style(border_style), sep);
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                " Arcade",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        for (i, game) in Game::ALL.iter().enumerate() {
+            let marker = if i == self.selection { ">" } else { " " };
+            let style = if i == self.selection {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let best = match self.best(*game) {
+                Some(score) => format!("best ${score:.2}"),
+                None => "no scores yet".into(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {marker} {:<12}", game.name()), style),
+                Span::styled(
+                    format!("{:<38}", game.blurb()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(best, Style::default().fg(Color::Yellow)),
+            ]));
+        }
+
+        let game = Game::ALL[self.selection];
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {} high scores", game.name()),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        let scores = self
+            .scores
+            .get(self.selection)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if scores.is_empty() {
+            lines.push(Line::from("   None yet. Be the first."));
+        }
+        for (rank, s) in scores.iter().enumerate() {
+            lines.push(Line::from(format!(
+                "   {}. ${:>9.2}  {}",
+                rank + 1,
+                s.score,
+                s.played_at.get(..10).unwrap_or(&s.played_at)
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines), content_area);
+
+        let status = if let Some(msg) = &self.status_message {
+            msg.clone()
+        } else if crate::db::is_read_only() {
+            "Read-only mode: scores are not saved".into()
+        } else {
+            String::new()
+        };
+        frame.render_widget(
+            Paragraph::new(format!(" {status}")).style(Style::default().fg(Color::Yellow)),
+            status_area,
+        );
+        frame.render_widget(
+            Paragraph::new(" Up/Down=navigate  Enter=play  Esc/q=back").style(FOOTER_STYLE),
+            hints_area,
+        );
+    }
+}
+
+impl screen::Screen for Arcade {
+    fn draw(&mut self, frame: &mut Frame) {
+        Arcade::draw(self, frame);
+    }
+
+    fn handle_key(&mut self, code: KeyCode, ctx: &mut ScreenContext) -> Result<Nav> {
+        Ok(match Arcade::handle_key(self, code, ctx.conn) {
+            ArcadeAction::Close => Nav::Pop,
+            ArcadeAction::Continue => Nav::Stay,
+        })
+    }
+
+    /// A new high score stays in the home header for the session.
+    fn on_exit(&mut self, ctx: &mut ScreenContext) {
+        if let Some((game, score)) = self.new_high_score.take() {
+            ctx.session.high_score_badge =
+                Some(format!("New high score: {} ${score:.2}", game.name()));
+        }
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Arcade::tick_rate(self)
+    }
+
+    fn tick(&mut self, ctx: &mut ScreenContext) {
+        self.do_tick(ctx.conn);
+    }
+
+    fn captures_keys(&self) -> bool {
+        self.is_playing()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    #[test]
+    fn test_record_score_tracks_high_scores() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+
+        assert!(!record_score(&conn, Game::Snake, 0.0).unwrap());
+        assert!(record_score(&conn, Game::Snake, 12.5).unwrap());
+        assert!(!record_score(&conn, Game::Snake, 8.0).unwrap());
+        assert!(!record_score(&conn, Game::Snake, 12.5).unwrap());
+        assert!(record_score(&conn, Game::Snake, 30.0).unwrap());
+        // Each game keeps its own table
+        assert!(record_score(&conn, Game::PettyCash, 1.25).unwrap());
+
+        let top = top_scores(&conn, Game::Snake, 5).unwrap();
+        let scores: Vec<f64> = top.iter().map(|s| s.score).collect();
+        assert_eq!(scores, vec![30.0, 12.5, 12.5, 8.0]);
+
+        for i in 0..HIGH_SCORES_KEPT {
+            record_score(&conn, Game::Snake, 100.0 + i as f64).unwrap();
+        }
+        let kept = top_scores(&conn, Game::Snake, 100).unwrap();
+        assert_eq!(kept.len(), HIGH_SCORES_KEPT);
+        assert_eq!(kept.last().unwrap().score, 100.0);
+    }
+
+    #[test]
+    fn test_is_new_record() {
+        assert!(is_new_record(5.0, None));
+        assert!(!is_new_record(0.0, None));
+        assert!(is_new_record(5.01, Some(5.0)));
+        assert!(!is_new_record(5.0, Some(5.0)));
+    }
+}
@@ -11,6 +11,7 @@ use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
 use crate::effects::{self, gradient_color, Particle, GRADIENT, PARTICLE_CHARS};
+use crate::games::{high_score_line, is_new_record, GameAction};
 
 const BASE_TICK_MS: u64 = 150;
 const MIN_TICK_MS: u64 = 50;
@@ -34,11 +35,6 @@ impl Direction {
     }
 }
 
-pub enum SnakeAction {
-    Continue,
-    Quit,
-}
-
 pub struct SnakeGame {
     pub body: VecDeque<(u16, u16)>,
     direction: Direction,
@@ -47,6 +43,8 @@ pub struct SnakeGame {
     pub food_value: f64,
     pub score: f64,
     pub game_over: bool,
+    /// Best score before this round, shown on the game-over overlay.
+    pub high_score: Option<f64>,
     last_tick: Instant,
     pub board_width: u16,
     pub board_height: u16,
@@ -79,6 +77,7 @@ impl SnakeGame {
             food_value,
             score: 0.0,
             game_over: false,
+            high_score: None,
             last_tick: Instant::now(),
             board_width,
             board_height,
@@ -165,12 +164,18 @@ impl SnakeGame {
         }
     }
 
-    pub fn handle_key(&mut self, code: KeyCode) -> SnakeAction {
+    pub fn handle_key(&mut self, code: KeyCode) -> GameAction {
         match code {
-            KeyCode::Esc => return SnakeAction::Quit,
+            KeyCode::Esc => return GameAction::Quit,
             _ if self.game_over => {
                 if let KeyCode::Char('r') | KeyCode::Char('R') = code {
+                    let high_score = if is_new_record(self.score, self.high_score) {
+                        Some(self.score)
+                    } else {
+                        self.high_score
+                    };
                     *self = Self::new();
+                    self.high_score = high_score;
                 }
             }
             KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
@@ -186,7 +191,7 @@ impl SnakeGame {
             }
             _ => {}
         }
-        SnakeAction::Continue
+        GameAction::Continue
     }
 
     pub fn do_tick(&mut self) {
@@ -323,7 +328,7 @@ impl SnakeGame {
         // Game over overlay
         if self.game_over {
             let overlay_width: u16 = 34;
-            let overlay_height: u16 = 5;
+            let overlay_height: u16 = 6;
             let ox = area.x + area.width.saturating_sub(overlay_width) / 2;
             let oy = area.y + area.height.saturating_sub(overlay_height) / 2;
             let overlay_rect = Rect::new(ox, oy, overlay_width, overlay_height);
@@ -346,6 +351,7 @@ impl SnakeGame {
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
                 )),
+                high_score_line(self.score, self.high_score),
                 Line::from(vec![
                     Span::styled(
                         "[R]",
@@ -454,7 +460,7 @@ mod tests {
     fn esc_returns_quit() {
         let mut game = SnakeGame::new();
         let action = game.handle_key(KeyCode::Esc);
-        assert!(matches!(action, SnakeAction::Quit));
+        assert!(matches!(action, GameAction::Quit));
     }
 
     #[test]
@@ -466,6 +472,8 @@ mod tests {
         assert!(!game.game_over);
         assert_eq!(game.score, 0.0);
         assert_eq!(game.body.len(), 3);
+        // The beaten score becomes the one to beat
+        assert_eq!(game.high_score, Some(42.0));
     }
 
     #[test]
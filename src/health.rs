@@ -0,0 +1,607 @@
+use std::path::Path;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+
+use crate::db::{get_metadata, set_metadata};
+use crate::error::Result;
+use crate::fiscal::FiscalYear;
+use crate::fmt::money;
+use crate::migrations::{get_schema_version, LATEST_VERSION};
+
+/// Default for the `backup_reminder_days` setting: warn when the newest backup
+/// is older than this many days.
+pub const BACKUP_MAX_AGE_DAYS: i64 = 30;
+
+/// Warn when more than this many transactions are waiting for review.
+pub const FLAGGED_BACKLOG_LIMIT: i64 = 25;
+
+/// Where a warning's one-key jump leads on the dashboard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HealthAction {
+    Backup,
+    Review,
+    Import,
+    Compensation,
+    /// Reconcile screen for the open reconciliation note with this ID.
+    ReconcileNote(i64),
+    /// Nothing to jump to; the warning is informational.
+    None,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthWarning {
+    pub message: String,
+    pub action: HealthAction,
+}
+
+impl HealthWarning {
+    fn new(message: String, action: HealthAction) -> Self {
+        Self { message, action }
+    }
+}
+
+/// Record that a backup was just taken, so the age check doesn't have to
+/// trust file timestamps in the backups directory.
+pub fn record_backup(conn: &Connection) -> Result<()> {
+    let now = chrono::Local::now().naive_local();
+    set_metadata(
+        conn,
+        "last_backup_at",
+        &now.format("%Y-%m-%d %H:%M:%S").to_string(),
+    )
+}
+
+/// Date of the most recent backup: the recorded `last_backup_at`, or failing
+/// that the newest file in `backups_dir`.
+pub fn last_backup_date(conn: &Connection, backups_dir: &Path) -> Option<NaiveDate> {
+    let recorded = get_metadata(conn, "last_backup_at").and_then(|v| {
+        NaiveDateTime::parse_from_str(&v, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|dt| dt.date())
+    });
+    let newest_file = std::fs::read_dir(backups_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.metadata().ok()?.modified().ok())
+        .max()
+        .map(|t| chrono::DateTime::<chrono::Local>::from(t).date_naive());
+    recorded.max(newest_file)
+}
+
+/// Compact health summary for `nigel status --badge`. Only counts and
+/// single-row lookups, so shell prompts and menubar scripts can poll it.
+#[derive(Debug, serde::Serialize)]
+pub struct Badge {
+    pub transactions: i64,
+    /// Share of transactions with a category, 0–100 (100 for an empty book).
+    pub categorized_pct: f64,
+    pub all_categorized: bool,
+    pub flagged: i64,
+    /// More than `FLAGGED_BACKLOG_LIMIT` transactions waiting for review.
+    pub review_backlog: bool,
+    /// Earliest of each open account's latest clean reconciliation
+    /// (`YYYY-MM`), over accounts that have one.
+    pub reconciled_through: Option<String>,
+    /// Open accounts with transactions but no clean reconciliation yet.
+    pub unreconciled_accounts: i64,
+    pub last_backup: Option<String>,
+    pub last_backup_age_days: Option<i64>,
+    /// False when the dashboard would warn about the backup age.
+    pub backup_ok: bool,
+}
+
+pub fn badge(
+    conn: &Connection,
+    backups_dir: &Path,
+    today: NaiveDate,
+    backup_max_age_days: i64,
+) -> Result<Badge> {
+    let (transactions, categorized, flagged): (i64, i64, i64) = conn.query_row(
+        "SELECT count(*), count(category_id), COALESCE(SUM(is_flagged = 1), 0) FROM transactions \
+         WHERE deleted_at IS NULL",
+        [],
+        |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+    )?;
+    let categorized_pct = if transactions == 0 {
+        100.0
+    } else {
+        (categorized as f64 / transactions as f64 * 1000.0).round() / 10.0
+    };
+    let (reconciled_through, unreconciled_accounts): (Option<String>, i64) = conn.query_row(
+        "SELECT MIN(latest), COALESCE(SUM(latest IS NULL), 0) FROM ( \
+           SELECT (SELECT MAX(r.month) FROM reconciliations r \
+                   WHERE r.account_id = a.id AND r.is_reconciled = 1) AS latest \
+           FROM accounts a \
+           WHERE a.is_archived = 0 \
+             AND EXISTS (SELECT 1 FROM transactions t \
+                         WHERE t.account_id = a.id AND t.deleted_at IS NULL))",
+        [],
+        |r| Ok((r.get(0)?, r.get(1)?)),
+    )?;
+    let last_backup = last_backup_date(conn, backups_dir);
+    let last_backup_age_days = last_backup.map(|d| (today - d).num_days());
+    // Same rule as `check()`: an empty book needs no backup
+    let backup_ok =
+        transactions == 0 || last_backup_age_days.is_some_and(|age| age <= backup_max_age_days);
+    Ok(Badge {
+        transactions,
+        categorized_pct,
+        all_categorized: categorized == transactions,
+        flagged,
+        review_backlog: flagged > FLAGGED_BACKLOG_LIMIT,
+        reconciled_through,
+        unreconciled_accounts,
+        last_backup: last_backup.map(|d| d.to_string()),
+        last_backup_age_days,
+        backup_ok,
+    })
+}
+
+/// Quick startup checks for the dashboard, in display order. Each query is a
+/// single aggregate so this stays cheap on large books. `backup_max_age_days`
+/// comes from the `backup_reminder_days` setting.
+pub fn check(
+    conn: &Connection,
+    backups_dir: &Path,
+    today: NaiveDate,
+    backup_max_age_days: i64,
+) -> Result<Vec<HealthWarning>> {
+    let mut warnings = Vec::new();
+
+    let schema = get_schema_version(conn)?;
+    if schema < LATEST_VERSION {
+        warnings.push(HealthWarning::new(
+            format!("Database schema is v{schema}; migration to v{LATEST_VERSION} is pending. Restart Nigel to apply it."),
+            HealthAction::None,
+        ));
+    } else if schema > LATEST_VERSION {
+        warnings.push(HealthWarning::new(
+            format!("Database schema v{schema} is newer than this version of Nigel (v{LATEST_VERSION}). Update Nigel before editing."),
+            HealthAction::None,
+        ));
+    }
+
+    // A brand-new book has nothing worth backing up yet
+    let txn_count: i64 = conn.query_row(
+        "SELECT count(*) FROM transactions WHERE deleted_at IS NULL",
+        [],
+        |r| r.get(0),
+    )?;
+    if txn_count > 0 {
+        match last_backup_date(conn, backups_dir) {
+            None => warnings.push(HealthWarning::new(
+                "No backup on record.".into(),
+                HealthAction::Backup,
+            )),
+            Some(date) if (today - date).num_days() > backup_max_age_days => {
+                warnings.push(HealthWarning::new(
+                    format!(
+                        "Last backup was {} days ago ({date}).",
+                        (today - date).num_days()
+                    ),
+                    HealthAction::Backup,
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    let flagged: i64 = conn.query_row(
+        "SELECT count(*) FROM transactions WHERE is_flagged = 1 AND deleted_at IS NULL",
+        [],
+        |r| r.get(0),
+    )?;
+    if flagged > FLAGGED_BACKLOG_LIMIT {
+        warnings.push(HealthWarning::new(
+            format!("{flagged} transactions are waiting for review."),
+            HealthAction::Review,
+        ));
+    }
+
+    // Open accounts that have been imported before but not yet this month
+    let month_start = today.format("%Y-%m-01").to_string();
+    let mut stmt = conn.prepare(
+        "SELECT a.name FROM accounts a JOIN imports i ON i.account_id = a.id \
+         WHERE a.is_archived = 0 GROUP BY a.id HAVING date(max(i.import_date)) < ?1 ORDER BY a.name",
+    )?;
+    let stale: Vec<String> = stmt
+        .query_map([&month_start], |r| r.get(0))?
+        .collect::<std::result::Result<_, _>>()?;
+    for name in stale {
+        warnings.push(HealthWarning::new(
+            format!("No import for {name} this month."),
+            HealthAction::Import,
+        ));
+    }
+
+    // Distributions running ahead of officer pay so far this fiscal year
+    let fy = FiscalYear::load(conn).year_of(today);
+    let comp = crate::reports::get_compensation(conn, Some(fy))?;
+    if comp.below_target() {
+        warnings.push(HealthWarning::new(
+            format!(
+                "Distributions ({}) are outpacing officer pay ({}): {:.0}% vs. {}% target.",
+                money(comp.distributions),
+                money(comp.officer_comp),
+                comp.officer_share.unwrap_or(0.0),
+                comp.target_pct
+            ),
+            HealthAction::Compensation,
+        ));
+    }
+
+    // Reconciliation differences accepted for now, until resolved
+    let today_iso = today.to_string();
+    for note in crate::reconciler::open_notes(conn)? {
+        let due = match note.follow_up.as_deref() {
+            Some(date) if date <= today_iso.as_str() => {
+                format!(" (follow-up due {date})")
+            }
+            Some(date) => format!(" (follow up {date})"),
+            None => String::new(),
+        };
+        warnings.push(HealthWarning::new(
+            format!(
+                "{} {} is {} off: {}{due}",
+                note.account_name,
+                note.month,
+                money(note.discrepancy),
+                note.note
+            ),
+            HealthAction::ReconcileNote(note.id),
+        ));
+    }
+
+    // Balance assertions the books no longer agree with
+    for a in crate::reconciler::failed_assertions(conn)? {
+        warnings.push(HealthWarning::new(
+            format!(
+                "{} on {}: books show {}, asserted {} ({} off)",
+                a.account_name,
+                a.date,
+                money(a.calculated),
+                money(a.balance),
+                money(a.difference().abs())
+            ),
+            HealthAction::None,
+        ));
+    }
+
+    // Vendors over their alert thresholds this month
+    for hit in crate::vendor_alerts::check_alerts(conn, today)? {
+        warnings.push(HealthWarning::new(hit.message(), HealthAction::None));
+    }
+
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn add_account_with_txn(conn: &Connection, name: &str, imported: &str) {
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES (?1, 'checking')",
+            [name],
+        )
+        .unwrap();
+        let account_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO imports (filename, account_id, import_date, record_count) \
+             VALUES ('s.csv', ?1, ?2, 1)",
+            rusqlite::params![account_id, imported],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (?1, '2025-03-01', 'COFFEE', -4.5)",
+            [account_id],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_empty_book_is_healthy() {
+        let (dir, conn) = test_db();
+        let warnings = check(
+            &conn,
+            &dir.path().join("backups"),
+            date("2025-03-15"),
+            BACKUP_MAX_AGE_DAYS,
+        )
+        .unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_backup_age_warning() {
+        let (dir, conn) = test_db();
+        add_account_with_txn(&conn, "Checking", "2025-03-02 09:00:00");
+        let backups = dir.path().join("backups");
+        let today = date("2025-03-15");
+
+        let warnings = check(&conn, &backups, today, BACKUP_MAX_AGE_DAYS).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].action, HealthAction::Backup);
+
+        set_metadata(&conn, "last_backup_at", "2025-01-10 12:00:00").unwrap();
+        let warnings = check(&conn, &backups, today, BACKUP_MAX_AGE_DAYS).unwrap();
+        assert!(warnings[0].message.contains("64 days ago"));
+
+        set_metadata(&conn, "last_backup_at", "2025-03-01 12:00:00").unwrap();
+        assert!(check(&conn, &backups, today, BACKUP_MAX_AGE_DAYS)
+            .unwrap()
+            .is_empty());
+        // A tighter reminder setting catches the same two-week-old backup
+        assert_eq!(check(&conn, &backups, today, 7).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_backup_file_counts_without_metadata() {
+        let (dir, conn) = test_db();
+        let backups = dir.path().join("backups");
+        std::fs::create_dir_all(&backups).unwrap();
+        std::fs::write(backups.join("nigel-20250101-000000.db"), b"x").unwrap();
+        let today = chrono::Local::now().date_naive();
+        assert_eq!(last_backup_date(&conn, &backups), Some(today));
+    }
+
+    #[test]
+    fn test_flagged_backlog_and_stale_imports() {
+        let (dir, conn) = test_db();
+        record_backup(&conn).unwrap();
+        add_account_with_txn(&conn, "Checking", "2025-03-02 09:00:00");
+        add_account_with_txn(&conn, "Visa", "2025-02-20 09:00:00");
+        conn.execute_batch(
+            "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 30) \
+             INSERT INTO transactions (account_id, date, description, amount, is_flagged) \
+             SELECT 1, '2025-03-03', 'MYSTERY', -1.0, 1 FROM n;",
+        )
+        .unwrap();
+
+        let today = chrono::Local::now().date_naive();
+        let month_start = today.format("%Y-%m-01").to_string();
+        conn.execute(
+            "UPDATE imports SET import_date = ?1 || ' 09:00:00' WHERE account_id = 1",
+            [&month_start],
+        )
+        .unwrap();
+
+        let warnings = check(
+            &conn,
+            &dir.path().join("backups"),
+            today,
+            BACKUP_MAX_AGE_DAYS,
+        )
+        .unwrap();
+        let actions: Vec<HealthAction> = warnings.iter().map(|w| w.action).collect();
+        assert_eq!(actions, vec![HealthAction::Review, HealthAction::Import]);
+        assert_eq!(
+            warnings[0].message,
+            "30 transactions are waiting for review."
+        );
+        assert_eq!(warnings[1].message, "No import for Visa this month.");
+
+        // A closed, archived account stops nagging
+        conn.execute(
+            "UPDATE accounts SET is_archived = 1 WHERE name = 'Visa'",
+            [],
+        )
+        .unwrap();
+        let warnings = check(
+            &conn,
+            &dir.path().join("backups"),
+            today,
+            BACKUP_MAX_AGE_DAYS,
+        )
+        .unwrap();
+        assert!(warnings.iter().all(|w| w.action != HealthAction::Import));
+    }
+
+    #[test]
+    fn test_distributions_over_target_warning() {
+        let (dir, conn) = test_db();
+        record_backup(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        let today = chrono::Local::now().date_naive();
+        let date = today.format("%Y-%m-%d").to_string();
+        for (category, amount) in [
+            ("Payroll \u{2014} Wages", -10_000.0),
+            ("Owner Draw / Distribution", -30_000.0),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+                 SELECT 1, ?1, 'PAY', ?2, id FROM categories WHERE name = ?3",
+                rusqlite::params![date, amount, category],
+            )
+            .unwrap();
+        }
+
+        let warnings = check(
+            &conn,
+            &dir.path().join("backups"),
+            today,
+            BACKUP_MAX_AGE_DAYS,
+        )
+        .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].action, HealthAction::Compensation);
+        assert!(warnings[0].message.contains("25% vs. 50% target"));
+
+        crate::compensation::set_target_pct(&conn, 20.0).unwrap();
+        assert!(check(
+            &conn,
+            &dir.path().join("backups"),
+            today,
+            BACKUP_MAX_AGE_DAYS
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[test]
+    fn test_schema_mismatch_warning() {
+        let (dir, conn) = test_db();
+        set_metadata(&conn, "schema_version", &(LATEST_VERSION + 1).to_string()).unwrap();
+        let warnings = check(
+            &conn,
+            &dir.path().join("backups"),
+            date("2025-03-15"),
+            BACKUP_MAX_AGE_DAYS,
+        )
+        .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("newer than this version"));
+    }
+
+    #[test]
+    fn test_open_reconciliation_note_warning() {
+        let (dir, conn) = test_db();
+        record_backup(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, '2025-03-01', 'DEPOSIT', 100.0)",
+            [],
+        )
+        .unwrap();
+        let result = crate::reconciler::reconcile(&conn, "Checking", "2025-03", 112.5).unwrap();
+        crate::reconciler::add_note(&conn, result.id, "Missing deposit", Some("2025-04-15"))
+            .unwrap();
+
+        let warnings = check(
+            &conn,
+            &dir.path().join("backups"),
+            date("2025-04-20"),
+            BACKUP_MAX_AGE_DAYS,
+        )
+        .unwrap();
+        let note = warnings.last().unwrap();
+        assert_eq!(
+            note.message,
+            "Checking 2025-03 is $12.50 off: Missing deposit (follow-up due 2025-04-15)"
+        );
+        assert_eq!(note.action, HealthAction::ReconcileNote(result.id));
+    }
+
+    #[test]
+    fn test_vendor_alert_warning() {
+        let (dir, conn) = test_db();
+        record_backup(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, vendor, amount) \
+             VALUES (1, '2025-04-03', 'ADOBE *CC', 'Adobe', -120.0)",
+            [],
+        )
+        .unwrap();
+        crate::vendor_alerts::add_alert(&conn, "Adobe", Some(100.0), None).unwrap();
+
+        let warnings = check(
+            &conn,
+            &dir.path().join("backups"),
+            date("2025-04-20"),
+            BACKUP_MAX_AGE_DAYS,
+        )
+        .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "Adobe: $120.00 spent this month, over the $100.00 alert."
+        );
+        assert_eq!(warnings[0].action, HealthAction::None);
+    }
+
+    #[test]
+    fn test_failed_balance_assertion_warning() {
+        let (dir, conn) = test_db();
+        record_backup(&conn).unwrap();
+        add_account_with_txn(&conn, "Checking", "2025-04-02 09:00:00");
+        crate::reconciler::add_assertion(&conn, "Checking", "2025-04-30", -4.5).unwrap();
+        let check_now = || {
+            check(
+                &conn,
+                &dir.path().join("backups"),
+                date("2025-04-20"),
+                BACKUP_MAX_AGE_DAYS,
+            )
+            .unwrap()
+        };
+        assert!(check_now().is_empty());
+
+        crate::reconciler::add_assertion(&conn, "Checking", "2025-04-30", 250.0).unwrap();
+        let warnings = check_now();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "Checking on 2025-04-30: books show -$4.50, asserted $250.00 ($254.50 off)"
+        );
+    }
+
+    #[test]
+    fn test_badge_summary() {
+        let (dir, conn) = test_db();
+        let backups = dir.path().join("backups");
+        let empty = badge(&conn, &backups, date("2025-04-20"), BACKUP_MAX_AGE_DAYS).unwrap();
+        assert_eq!(empty.categorized_pct, 100.0);
+        assert!(empty.backup_ok);
+        assert!(empty.reconciled_through.is_none());
+
+        add_account_with_txn(&conn, "Checking", "2025-03-02 09:00:00");
+        add_account_with_txn(&conn, "Card", "2025-03-02 09:00:00");
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id, is_flagged) \
+             VALUES (1, '2025-03-02', 'SALE', 30.0, 1, 1)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO reconciliations (account_id, month, is_reconciled) \
+             VALUES (1, '2025-02', 1), (1, '2025-03', 0)",
+            [],
+        )
+        .unwrap();
+        set_metadata(&conn, "last_backup_at", "2025-04-01 08:00:00").unwrap();
+
+        let b = badge(&conn, &backups, date("2025-04-20"), BACKUP_MAX_AGE_DAYS).unwrap();
+        assert_eq!(b.transactions, 3);
+        assert_eq!(b.categorized_pct, 33.3);
+        assert!(!b.all_categorized);
+        assert_eq!(b.flagged, 1);
+        assert!(!b.review_backlog);
+        assert_eq!(b.reconciled_through.as_deref(), Some("2025-02"));
+        assert_eq!(b.unreconciled_accounts, 1);
+        assert_eq!(b.last_backup_age_days, Some(19));
+        assert!(b.backup_ok);
+        assert!(
+            !badge(&conn, &backups, date("2025-05-20"), 30)
+                .unwrap()
+                .backup_ok
+        );
+    }
+}
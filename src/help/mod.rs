@@ -0,0 +1,296 @@
+use crate::tui::display_width;
+
+/// A help topic for the dashboard's docs screen, written in a small subset
+/// of markdown: `#`/`##` headings, `- ` and `1. ` list items (continued by
+/// indented lines), fenced code blocks, and `inline code`.
+pub struct Topic {
+    pub key: &'static str,
+    pub source: &'static str,
+}
+
+pub const TOPICS: &[Topic] = &[
+    Topic {
+        key: "importing",
+        source: include_str!("importing.md"),
+    },
+    Topic {
+        key: "rules",
+        source: include_str!("rules.md"),
+    },
+    Topic {
+        key: "reconciliation",
+        source: include_str!("reconciliation.md"),
+    },
+    Topic {
+        key: "tax",
+        source: include_str!("tax.md"),
+    },
+];
+
+impl Topic {
+    /// The topic's `# ` heading.
+    pub fn title(&self) -> &'static str {
+        self.source
+            .lines()
+            .next()
+            .and_then(|l| l.strip_prefix("# "))
+            .unwrap_or(self.key)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineKind {
+    Title,
+    Heading,
+    Text,
+    Code,
+    Blank,
+}
+
+/// A run of text within a line; `code` runs were written in backticks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub text: String,
+    pub code: bool,
+}
+
+/// One screen line of a topic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocLine {
+    pub kind: LineKind,
+    pub segments: Vec<Segment>,
+}
+
+impl DocLine {
+    fn plain(kind: LineKind, text: &str) -> Self {
+        Self {
+            kind,
+            segments: vec![Segment {
+                text: text.to_string(),
+                code: kind == LineKind::Code,
+            }],
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.segments.iter().map(|s| s.text.as_str()).collect()
+    }
+}
+
+/// Lay out a topic's source as lines at most `width` columns wide.
+/// Paragraphs and list items are wrapped (list items with a hanging
+/// indent); code blocks are kept as written.
+pub fn layout(source: &str, width: usize) -> Vec<DocLine> {
+    let width = width.max(20);
+    let mut lines = Vec::new();
+    // (first-line prefix, text) of the paragraph or list item being read
+    let mut block: Option<(String, String)> = None;
+    let mut in_code = false;
+
+    let flush = |block: &mut Option<(String, String)>, lines: &mut Vec<DocLine>| {
+        if let Some((prefix, text)) = block.take() {
+            wrap_into(lines, &prefix, &text, width);
+        }
+    };
+
+    for raw in source.lines() {
+        if raw.trim_start().starts_with("```") {
+            flush(&mut block, &mut lines);
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(DocLine::plain(LineKind::Code, &format!("  {raw}")));
+            continue;
+        }
+        let line = raw.trim_end();
+        if line.is_empty() {
+            flush(&mut block, &mut lines);
+            if lines.last().is_some_and(|l| l.kind != LineKind::Blank) {
+                lines.push(DocLine::plain(LineKind::Blank, ""));
+            }
+        } else if let Some(title) = line.strip_prefix("# ") {
+            flush(&mut block, &mut lines);
+            lines.push(DocLine::plain(LineKind::Title, title));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            flush(&mut block, &mut lines);
+            lines.push(DocLine::plain(LineKind::Heading, heading));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            flush(&mut block, &mut lines);
+            block = Some(("  • ".into(), item.to_string()));
+        } else if let Some((number, item)) = line
+            .split_once(". ")
+            .filter(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        {
+            flush(&mut block, &mut lines);
+            block = Some((format!("  {number}. "), item.to_string()));
+        } else {
+            match &mut block {
+                Some((_, text)) => {
+                    text.push(' ');
+                    text.push_str(line.trim_start());
+                }
+                None => block = Some((String::new(), line.to_string())),
+            }
+        }
+    }
+    flush(&mut block, &mut lines);
+    while lines.last().is_some_and(|l| l.kind == LineKind::Blank) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Word-wrap `text` after `prefix`, indenting later lines to line up with
+/// the text. Inline code keeps its spaces but may still wrap between words;
+/// code and the punctuation touching it (`x`, or (`y`)) stay together.
+fn wrap_into(lines: &mut Vec<DocLine>, prefix: &str, text: &str, width: usize) {
+    let indent = " ".repeat(display_width(prefix));
+    // Each word is the pieces of text between spaces, flagged as code or not
+    let mut words: Vec<Vec<(&str, bool)>> = Vec::new();
+    let mut touching = false;
+    for (i, part) in text.split('`').enumerate() {
+        let code = i % 2 == 1;
+        for (j, piece) in part.split_whitespace().enumerate() {
+            let glued = j == 0 && touching && !part.starts_with(char::is_whitespace);
+            match words.last_mut() {
+                Some(word) if glued => word.push((piece, code)),
+                _ => words.push(vec![(piece, code)]),
+            }
+        }
+        touching = if part.trim().is_empty() {
+            touching && part.is_empty()
+        } else {
+            !part.ends_with(char::is_whitespace)
+        };
+    }
+
+    let mut current = DocLine::plain(LineKind::Text, prefix);
+    let mut used = display_width(prefix);
+    let mut empty = true;
+    for word in words {
+        let w: usize = word.iter().map(|(piece, _)| display_width(piece)).sum();
+        if !empty && used + 1 + w > width {
+            lines.push(std::mem::replace(
+                &mut current,
+                DocLine::plain(LineKind::Text, &indent),
+            ));
+            used = indent.len();
+            empty = true;
+        }
+        let mut sep = if empty { "" } else { " " };
+        used += sep.len() + w;
+        for (piece, code) in word {
+            let last = current
+                .segments
+                .last_mut()
+                .expect("a line starts with its prefix");
+            if last.code == code {
+                last.text.push_str(sep);
+                last.text.push_str(piece);
+            } else if last.code {
+                // Spaces around inline code are plain text
+                current.segments.push(Segment {
+                    text: format!("{sep}{piece}"),
+                    code,
+                });
+            } else {
+                last.text.push_str(sep);
+                current.segments.push(Segment {
+                    text: piece.to_string(),
+                    code,
+                });
+            }
+            sep = "";
+        }
+        empty = false;
+    }
+    lines.push(current);
+}
+
+/// Byte ranges of `query` in `text`, ignoring ASCII case.
+pub fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, m)| (start, start + m.len()))
+        .collect()
+}
+
+/// Indices of the lines containing `query`, ignoring ASCII case.
+pub fn find(lines: &[DocLine], query: &str) -> Vec<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !match_ranges(&l.text(), query).is_empty())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_wraps_lists_and_keeps_code_blocks() {
+        let source = "# Title\n\nSome `inline code` here and\n(`more`) words.\n\n\
+                      - a list item that is long enough to wrap\n  onto the next line\n\n\
+                      ```\nnigel import statement.csv --account \"BofA Checking\"\n```\n";
+        let lines = layout(source, 24);
+        assert_eq!(lines[0], DocLine::plain(LineKind::Title, "Title"));
+        assert_eq!(lines[1].kind, LineKind::Blank);
+        assert_eq!(
+            lines[2].segments,
+            vec![
+                Segment {
+                    text: "Some ".into(),
+                    code: false
+                },
+                Segment {
+                    text: "inline code".into(),
+                    code: true
+                },
+                Segment {
+                    text: " here".into(),
+                    code: false
+                },
+            ]
+        );
+        let texts: Vec<String> = lines.iter().map(DocLine::text).collect();
+        // Punctuation stays against the code it touches
+        assert_eq!(texts[3], "and (more) words.");
+        assert!(texts.contains(&"  • a list item that is".to_string()));
+        assert!(texts.contains(&"    long enough to wrap".to_string()));
+        // Code is indented but never wrapped
+        let code = lines.last().unwrap();
+        assert_eq!(code.kind, LineKind::Code);
+        assert_eq!(
+            code.text(),
+            "  nigel import statement.csv --account \"BofA Checking\""
+        );
+        assert!(lines
+            .iter()
+            .all(|l| l.kind == LineKind::Code || display_width(&l.text()) <= 24));
+    }
+
+    #[test]
+    fn find_ignores_case() {
+        let lines = layout("# Rules\n\nUse `--match-type regex`.\n\nPriority wins.", 80);
+        assert_eq!(find(&lines, "MATCH-TYPE"), vec![2]);
+        assert_eq!(find(&lines, "priority"), vec![4]);
+        assert!(find(&lines, "").is_empty());
+        assert_eq!(match_ranges("Rules rule", "RULE"), vec![(0, 4), (6, 10)]);
+    }
+
+    #[test]
+    fn every_topic_has_a_title() {
+        for topic in TOPICS {
+            assert_ne!(topic.title(), topic.key);
+            assert_eq!(layout(topic.source, 60)[0].kind, LineKind::Title);
+        }
+    }
+}
@@ -1,7 +1,8 @@
+use std::collections::HashSet;
 use std::io::BufRead;
 use std::path::Path;
 
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
 use sha2::{Digest, Sha256};
 
 use crate::error::{NigelError, Result};
@@ -20,6 +21,16 @@ pub fn parse_amount(raw: &str) -> Option<f64> {
     s.parse().ok()
 }
 
+/// The generic CSV date format when none is given: day-first with the
+/// DD/MM/YYYY `date_format` setting, for banks outside the US, else
+/// MM/DD/YYYY.
+pub fn default_csv_date_format() -> &'static str {
+    match crate::settings::load_settings().date_format() {
+        "DD/MM/YYYY" => "%d/%m/%Y",
+        _ => "%m/%d/%Y",
+    }
+}
+
 pub fn parse_date_mdy(raw: &str) -> Option<String> {
     let raw = raw.trim();
     let parts: Vec<&str> = raw.split('/').collect();
@@ -49,13 +60,54 @@ fn create_csv_reader(file_path: &Path) -> Result<csv::Reader<std::io::BufReader<
         .from_reader(std::io::BufReader::new(file)))
 }
 
-fn compute_checksum(file_path: &Path) -> Result<String> {
+pub fn compute_checksum(file_path: &Path) -> Result<String> {
     let data = std::fs::read(file_path)?;
     let mut hasher = Sha256::new();
     hasher.update(&data);
     Ok(hex::encode(hasher.finalize()))
 }
 
+/// An earlier import of a file with the same checksum.
+pub struct PriorImport {
+    pub id: i64,
+    pub filename: String,
+    /// `imports.import_date` (UTC, `YYYY-MM-DD HH:MM:SS`).
+    pub import_date: String,
+    pub record_count: i64,
+}
+
+impl PriorImport {
+    /// "This file was already imported on 2025-03-02 (42 records, import #7)."
+    pub fn describe(&self) -> String {
+        let date = self.import_date.get(..10).unwrap_or(&self.import_date);
+        format!(
+            "This file was already imported on {date} ({} records, import #{}).",
+            self.record_count, self.id
+        )
+    }
+}
+
+fn prior_import_by_checksum(conn: &Connection, checksum: &str) -> Result<Option<PriorImport>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, filename, COALESCE(import_date, ''), COALESCE(record_count, 0) \
+         FROM imports WHERE checksum = ?1 ORDER BY id DESC LIMIT 1",
+    )?;
+    let mut rows = stmt.query_map([checksum], |row| {
+        Ok(PriorImport {
+            id: row.get(0)?,
+            filename: row.get(1)?,
+            import_date: row.get(2)?,
+            record_count: row.get(3)?,
+        })
+    })?;
+    Ok(rows.next().transpose()?)
+}
+
+/// The most recent import of a file identical to `file_path`, if any.
+pub fn find_prior_import(conn: &Connection, file_path: &Path) -> Result<Option<PriorImport>> {
+    prior_import_by_checksum(conn, &compute_checksum(file_path)?)
+}
+
 fn is_duplicate_row(conn: &Connection, account_id: i64, row: &ParsedRow) -> Result<bool> {
     let mut stmt = conn
         .prepare_cached(
@@ -78,6 +130,8 @@ pub enum ImporterKind {
     BofaChecking,
     BofaCreditCard,
     BofaLineOfCredit,
+    #[cfg(feature = "pdf-import")]
+    BofaPdfStatement,
     #[cfg(feature = "gusto")]
     GustoPayroll,
 }
@@ -88,6 +142,8 @@ impl ImporterKind {
             Self::BofaChecking => "bofa_checking",
             Self::BofaCreditCard => "bofa_credit_card",
             Self::BofaLineOfCredit => "bofa_line_of_credit",
+            #[cfg(feature = "pdf-import")]
+            Self::BofaPdfStatement => "bofa_pdf",
             #[cfg(feature = "gusto")]
             Self::GustoPayroll => "gusto_payroll",
         }
@@ -99,6 +155,8 @@ impl ImporterKind {
             Self::BofaChecking => "Bank of America Checking",
             Self::BofaCreditCard => "Bank of America Credit Card",
             Self::BofaLineOfCredit => "Bank of America Line of Credit",
+            #[cfg(feature = "pdf-import")]
+            Self::BofaPdfStatement => "Bank of America PDF Statement",
             #[cfg(feature = "gusto")]
             Self::GustoPayroll => "Gusto Payroll",
         }
@@ -109,6 +167,8 @@ impl ImporterKind {
             Self::BofaChecking => &["checking"],
             Self::BofaCreditCard => &["credit_card"],
             Self::BofaLineOfCredit => &["line_of_credit"],
+            #[cfg(feature = "pdf-import")]
+            Self::BofaPdfStatement => &["checking", "credit_card", "line_of_credit"],
             #[cfg(feature = "gusto")]
             Self::GustoPayroll => &["payroll"],
         }
@@ -119,6 +179,8 @@ impl ImporterKind {
             Self::BofaChecking => detect_bofa_checking(file_path),
             Self::BofaCreditCard => detect_bofa_credit_card(file_path),
             Self::BofaLineOfCredit => false, // differentiated by account_type
+            #[cfg(feature = "pdf-import")]
+            Self::BofaPdfStatement => detect_bofa_pdf_statement(file_path),
             #[cfg(feature = "gusto")]
             Self::GustoPayroll => detect_gusto_payroll(file_path),
         }
@@ -129,14 +191,17 @@ impl ImporterKind {
             Self::BofaChecking => parse_bofa_checking(file_path),
             Self::BofaCreditCard => parse_bofa_credit_card(file_path),
             Self::BofaLineOfCredit => parse_bofa_line_of_credit(file_path),
+            #[cfg(feature = "pdf-import")]
+            Self::BofaPdfStatement => parse_bofa_pdf_statement(file_path),
             #[cfg(feature = "gusto")]
             // Gusto extracts aggregate totals only; per-row malformed tracking is not applicable.
-            Self::GustoPayroll => parse_gusto_payroll(file_path).map(|rows| (rows, 0)),
+            Self::GustoPayroll => parse_gusto_payroll(file_path, false).map(|rows| (rows, 0)),
         }
     }
 
     pub fn has_post_import(&self) -> bool {
         match self {
+            Self::BofaLineOfCredit => true,
             #[cfg(feature = "gusto")]
             Self::GustoPayroll => true,
             _ => false,
@@ -151,6 +216,7 @@ impl ImporterKind {
         rows: &[ParsedRow],
     ) -> Result<()> {
         match self {
+            Self::BofaLineOfCredit => auto_categorize_line_of_credit(conn, account_id, rows),
             #[cfg(feature = "gusto")]
             Self::GustoPayroll => auto_categorize_payroll(conn, account_id, rows),
             _ => Ok(()),
@@ -162,10 +228,31 @@ const ALL_IMPORTERS: &[ImporterKind] = &[
     ImporterKind::BofaChecking,
     ImporterKind::BofaCreditCard,
     ImporterKind::BofaLineOfCredit,
+    #[cfg(feature = "pdf-import")]
+    ImporterKind::BofaPdfStatement,
     #[cfg(feature = "gusto")]
     ImporterKind::GustoPayroll,
 ];
 
+/// `--format` value that asks for detection and forgets the account's
+/// remembered format.
+pub const AUTO_FORMAT: &str = "auto";
+
+/// Built-in importer keys, in detection order.
+pub fn importer_keys() -> Vec<&'static str> {
+    ALL_IMPORTERS.iter().map(|i| i.key()).collect()
+}
+
+/// Extensions of the statement files some importer can read, for the
+/// import screen's file picker.
+pub const FILE_EXTENSIONS: &[&str] = &[
+    "csv",
+    #[cfg(feature = "gusto")]
+    "xlsx",
+    #[cfg(feature = "pdf-import")]
+    "pdf",
+];
+
 pub fn get_by_key(key: &str) -> Option<ImporterKind> {
     ALL_IMPORTERS.iter().find(|i| i.key() == key).copied()
 }
@@ -241,6 +328,38 @@ pub fn load_csv_profile(conn: &Connection, name: &str) -> Result<Option<GenericC
     }
 }
 
+/// Saved CSV profile names, alphabetically.
+pub fn list_csv_profiles(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM csv_profiles ORDER BY name")?;
+    let names = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<std::result::Result<Vec<String>, _>>()?;
+    Ok(names)
+}
+
+/// What an account remembers from its last import.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportDefaults {
+    /// Importer or profile key from the last import run with `--format`.
+    pub default_format: Option<String>,
+    /// Folder the last statement was imported from.
+    pub last_import_dir: Option<String>,
+}
+
+pub fn import_defaults(conn: &Connection, account_name: &str) -> Result<ImportDefaults> {
+    conn.query_row(
+        "SELECT default_format, last_import_dir FROM accounts WHERE name = ?1",
+        [account_name],
+        |row| {
+            Ok(ImportDefaults {
+                default_format: row.get(0)?,
+                last_import_dir: row.get(1)?,
+            })
+        },
+    )
+    .map_err(|_| NigelError::UnknownAccount(account_name.to_string()))
+}
+
 pub fn parse_generic_csv(
     file_path: &Path,
     config: &GenericCsvConfig,
@@ -303,10 +422,24 @@ pub struct ImportResult {
     pub imported: usize,
     pub skipped: usize,
     pub malformed: usize,
-    pub duplicate_file: bool,
-    pub sample: Vec<ParsedRow>,
+    /// Set when the file matched an earlier import and nothing was read.
+    pub duplicate_of: Option<PriorImport>,
+    /// The `imports` row created by a real (non-dry-run) import.
+    pub import_id: Option<i64>,
+    /// Every parsed row with its duplicate check, for dry runs only.
+    pub preview: Vec<PreviewRow>,
+    /// The account's remembered format, when no `--format` was given and
+    /// it was used.
+    pub remembered_format: Option<String>,
 }
 
+/// A row a dry run read, and whether the import would skip it as a duplicate.
+pub struct PreviewRow {
+    pub row: ParsedRow,
+    pub duplicate: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn import_file(
     conn: &Connection,
     file_path: &Path,
@@ -314,26 +447,130 @@ pub fn import_file(
     format_key: Option<&str>,
     dry_run: bool,
     inline_config: Option<&GenericCsvConfig>,
+    force_reimport: bool,
+    detailed_payroll: bool,
 ) -> Result<ImportResult> {
-    let (account_id, account_type) = {
-        let mut stmt = conn.prepare("SELECT id, account_type FROM accounts WHERE name = ?1")?;
+    let parsed = read_import(
+        conn,
+        file_path,
+        account_name,
+        format_key,
+        inline_config,
+        force_reimport,
+        detailed_payroll,
+    )?;
+    if parsed.duplicate_of.is_some() {
+        return Ok(ImportResult {
+            imported: 0,
+            skipped: 0,
+            malformed: 0,
+            duplicate_of: parsed.duplicate_of,
+            import_id: None,
+            preview: Vec::new(),
+            remembered_format: None,
+        });
+    }
+
+    let mut preview = Vec::new();
+    let (imported, skipped, import_id) = if !dry_run {
+        let batch = parsed.commit(conn, &parsed.rows)?;
+        (batch.imported, batch.skipped, Some(batch.import_id))
+    } else {
+        preview = preview_rows(conn, parsed.account_id, &parsed.rows)?;
+        let skipped = preview.iter().filter(|p| p.duplicate).count();
+        (preview.len() - skipped, skipped, None)
+    };
+
+    Ok(ImportResult {
+        imported,
+        skipped,
+        malformed: parsed.malformed,
+        duplicate_of: None,
+        import_id,
+        preview,
+        remembered_format: parsed.remembered_format,
+    })
+}
+
+/// A statement read and checked but not yet written, so a caller can review
+/// (or correct) its rows before `commit()`.
+pub struct ParsedImport {
+    pub account_id: i64,
+    pub rows: Vec<ParsedRow>,
+    pub malformed: usize,
+    /// Set when the file matched an earlier import and nothing was read.
+    pub duplicate_of: Option<PriorImport>,
+    /// The account's remembered format, when it chose the importer.
+    pub remembered_format: Option<String>,
+    filename: String,
+    checksum: String,
+    post_import: Option<ImporterKind>,
+    /// `default_format` to store on commit: `Some(None)` forgets it.
+    remember_format: Option<Option<String>>,
+    source_dir: Option<String>,
+}
+
+/// The read half of `import_file()`: resolve the account and importer,
+/// check the file's checksum against earlier imports, and parse its rows.
+/// Without a `format_key` the account's remembered format is used, falling
+/// back to detection when it names an importer or profile that's gone;
+/// `AUTO_FORMAT` always detects. `detailed_payroll` splits a Gusto export
+/// into its withholding, deduction, and reimbursement lines.
+#[cfg_attr(not(feature = "gusto"), allow(unused_variables))]
+pub fn read_import(
+    conn: &Connection,
+    file_path: &Path,
+    account_name: &str,
+    format_key: Option<&str>,
+    inline_config: Option<&GenericCsvConfig>,
+    force_reimport: bool,
+    detailed_payroll: bool,
+) -> Result<ParsedImport> {
+    let (account_id, account_type, is_archived, default_format) = {
+        let mut stmt = conn.prepare(
+            "SELECT id, account_type, is_archived, default_format FROM accounts WHERE name = ?1",
+        )?;
 
         stmt.query_row([account_name], |row| {
-            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, bool>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
         })
         .map_err(|_| NigelError::UnknownAccount(account_name.to_string()))?
     };
+    if is_archived {
+        return Err(NigelError::Other(format!(
+            "Account '{account_name}' is archived. Run `nigel accounts unarchive \"{account_name}\"` to import into it."
+        )));
+    }
 
+    let filename = file_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string();
+    let source_dir = std::fs::canonicalize(file_path)
+        .ok()
+        .and_then(|p| p.parent().map(|d| d.to_string_lossy().into_owned()));
+    // A matching checksum stops the import unless forced; a forced re-import
+    // becomes a new batch, still subject to row-level duplicate checks.
     let checksum = compute_checksum(file_path)?;
-    {
-        let mut stmt = conn.prepare("SELECT 1 FROM imports WHERE checksum = ?1")?;
-        if stmt.exists(rusqlite::params![checksum])? {
-            return Ok(ImportResult {
-                imported: 0,
-                skipped: 0,
+    if !force_reimport {
+        if let Some(prior) = prior_import_by_checksum(conn, &checksum)? {
+            return Ok(ParsedImport {
+                account_id,
+                rows: Vec::new(),
                 malformed: 0,
-                duplicate_file: true,
-                sample: Vec::new(),
+                duplicate_of: Some(prior),
+                remembered_format: None,
+                filename,
+                checksum,
+                post_import: None,
+                remember_format: None,
+                source_dir,
             });
         }
     }
@@ -343,85 +580,190 @@ pub fn import_file(
         Generic(GenericCsvConfig),
     }
 
+    let resolve_key = |key: &str| -> Result<Option<ResolvedImporter>> {
+        if let Some(kind) = get_by_key(key) {
+            return Ok(Some(ResolvedImporter::BuiltIn(kind)));
+        }
+        Ok(load_csv_profile(conn, key)?.map(ResolvedImporter::Generic))
+    };
+
+    let mut remembered_format = None;
+    let mut remember_format = None;
     let resolved = if let Some(config) = inline_config {
-        ResolvedImporter::Generic(config.clone())
+        Some(ResolvedImporter::Generic(config.clone()))
+    } else if format_key == Some(AUTO_FORMAT) {
+        remember_format = Some(None);
+        None
     } else if let Some(key) = format_key {
-        if let Some(kind) = get_by_key(key) {
-            ResolvedImporter::BuiltIn(kind)
-        } else if let Some(config) = load_csv_profile(conn, key)? {
-            ResolvedImporter::Generic(config)
-        } else {
-            return Err(NigelError::UnknownFormat(key.to_string()));
+        let resolved =
+            resolve_key(key)?.ok_or_else(|| NigelError::UnknownFormat(key.to_string()))?;
+        remember_format = Some(Some(key.to_string()));
+        Some(resolved)
+    } else if let Some(key) = &default_format {
+        let resolved = resolve_key(key)?;
+        if resolved.is_some() {
+            remembered_format = Some(key.clone());
         }
+        resolved
     } else {
-        ResolvedImporter::BuiltIn(
+        None
+    };
+    let resolved = match resolved {
+        Some(resolved) => resolved,
+        None => ResolvedImporter::BuiltIn(
             get_for_file(&account_type, file_path)
                 .ok_or_else(|| NigelError::NoImporter(account_type.clone()))?,
-        )
+        ),
     };
 
-    let (parsed_rows, malformed) = match &resolved {
+    let (rows, malformed) = match &resolved {
+        // Gusto alone reads differently per import: summary or detailed
+        #[cfg(feature = "gusto")]
+        ResolvedImporter::BuiltIn(ImporterKind::GustoPayroll) => {
+            (parse_gusto_payroll(file_path, detailed_payroll)?, 0)
+        }
         ResolvedImporter::BuiltIn(kind) => kind.parse(file_path)?,
         ResolvedImporter::Generic(config) => parse_generic_csv(file_path, config)?,
     };
-    let sample: Vec<ParsedRow> = parsed_rows.iter().take(5).cloned().collect();
-
-    let mut imported = 0usize;
-    let mut skipped = 0usize;
+    let post_import = match resolved {
+        ResolvedImporter::BuiltIn(kind) if kind.has_post_import() => Some(kind),
+        _ => None,
+    };
+    Ok(ParsedImport {
+        account_id,
+        rows,
+        malformed,
+        duplicate_of: None,
+        remembered_format,
+        filename,
+        checksum,
+        post_import,
+        remember_format,
+        source_dir,
+    })
+}
 
-    if !dry_run {
-        let dates: Vec<&str> = parsed_rows.iter().map(|r| r.date.as_str()).collect();
-        let min_date = dates.iter().min().copied();
-        let max_date = dates.iter().max().copied();
-        conn.execute(
-            "INSERT INTO imports (filename, account_id, record_count, date_range_start, date_range_end, checksum) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            rusqlite::params![
-                file_path.file_name().and_then(|n| n.to_str()).unwrap_or(""),
-                account_id,
-                parsed_rows.len() as i64,
-                min_date,
-                max_date,
-                checksum,
-            ],
+impl ParsedImport {
+    /// Write `rows` — this import's rows, or a reviewed subset of them — as
+    /// one batch, with the importer's post-import step, in a single SQLite
+    /// transaction: a failure part way leaves the book as it was. The
+    /// account remembers the file's folder, and the format when one was
+    /// chosen explicitly.
+    pub fn commit(&self, conn: &Connection, rows: &[ParsedRow]) -> Result<BatchResult> {
+        let tx = conn.unchecked_transaction()?;
+        let batch = insert_batch(
+            &tx,
+            self.account_id,
+            &self.filename,
+            Some(&self.checksum),
+            rows,
         )?;
-        let import_id = conn.last_insert_rowid();
-
-        for row in &parsed_rows {
-            if is_duplicate_row(conn, account_id, row)? {
-                skipped += 1;
-                continue;
-            }
-            conn.execute(
-                "INSERT INTO transactions (account_id, date, description, amount, import_id, is_flagged, flag_reason) VALUES (?1, ?2, ?3, ?4, ?5, 1, 'No matching rule')",
-                rusqlite::params![account_id, row.date, row.description, row.amount, import_id],
-            )?;
-            imported += 1;
+        if let Some(importer) = self.post_import {
+            importer.post_import(&tx, self.account_id, rows)?;
         }
-
-        if let ResolvedImporter::BuiltIn(importer) = &resolved {
-            if importer.has_post_import() {
-                importer.post_import(conn, account_id, &parsed_rows)?;
-            }
+        if let Some(dir) = &self.source_dir {
+            tx.execute(
+                "UPDATE accounts SET last_import_dir = ?1 WHERE id = ?2",
+                rusqlite::params![dir, self.account_id],
+            )?;
         }
-    } else {
-        for row in &parsed_rows {
-            if is_duplicate_row(conn, account_id, row)? {
-                skipped += 1;
-            } else {
-                imported += 1;
-            }
+        if let Some(format) = &self.remember_format {
+            tx.execute(
+                "UPDATE accounts SET default_format = ?1 WHERE id = ?2",
+                rusqlite::params![format, self.account_id],
+            )?;
         }
+        tx.commit()?;
+        Ok(batch)
     }
+}
 
-    Ok(ImportResult {
+/// Rows written by `insert_batch()`.
+pub struct BatchResult {
+    pub import_id: i64,
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+/// Record an `imports` row for `rows` and insert those not already in the
+/// account, flagged for categorization. Shared by file imports and bank sync,
+/// so both dedup the same way and both can be undone with `nigel undo`.
+pub fn insert_batch(
+    conn: &Connection,
+    account_id: i64,
+    filename: &str,
+    checksum: Option<&str>,
+    rows: &[ParsedRow],
+) -> Result<BatchResult> {
+    let min_date = rows.iter().map(|r| r.date.as_str()).min();
+    let max_date = rows.iter().map(|r| r.date.as_str()).max();
+    conn.execute(
+        "INSERT INTO imports (filename, account_id, record_count, date_range_start, date_range_end, checksum) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![
+            filename,
+            account_id,
+            rows.len() as i64,
+            min_date,
+            max_date,
+            checksum,
+        ],
+    )?;
+    let import_id = conn.last_insert_rowid();
+
+    let mut imported = 0usize;
+    let mut skipped = 0usize;
+    for row in rows {
+        if is_duplicate_row(conn, account_id, row)? {
+            skipped += 1;
+            continue;
+        }
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, import_id, is_flagged, flag_reason) VALUES (?1, ?2, ?3, ?4, ?5, 1, 'No matching rule')",
+            rusqlite::params![account_id, row.date, row.description, row.amount, import_id],
+        )?;
+        imported += 1;
+    }
+    Ok(BatchResult {
+        import_id,
         imported,
         skipped,
-        malformed,
-        duplicate_file: false,
-        sample,
     })
 }
 
+/// Check each of `rows` the way `insert_batch()` would, without writing:
+/// a row is a duplicate when the account already has it or an earlier row
+/// of the same file is identical.
+pub fn preview_rows(
+    conn: &Connection,
+    account_id: i64,
+    rows: &[ParsedRow],
+) -> Result<Vec<PreviewRow>> {
+    let mut seen = HashSet::new();
+    rows.iter()
+        .map(|row| {
+            let first = seen.insert((&row.date, &row.description, row.amount.to_bits()));
+            Ok(PreviewRow {
+                row: row.clone(),
+                duplicate: !first || is_duplicate_row(conn, account_id, row)?,
+            })
+        })
+        .collect()
+}
+
+/// (new, duplicate) counts for `rows` without writing anything.
+#[cfg(feature = "sync")]
+pub fn count_new_rows(
+    conn: &Connection,
+    account_id: i64,
+    rows: &[ParsedRow],
+) -> Result<(usize, usize)> {
+    let skipped = preview_rows(conn, account_id, rows)?
+        .iter()
+        .filter(|p| p.duplicate)
+        .count();
+    Ok((rows.len() - skipped, skipped))
+}
+
 // ---------------------------------------------------------------------------
 // BofA Checking parser
 // ---------------------------------------------------------------------------
@@ -516,9 +858,52 @@ fn parse_bofa_line_of_credit(file_path: &Path) -> Result<(Vec<ParsedRow>, usize)
     parse_bofa_card_format(file_path, false)
 }
 
+/// Where line-of-credit interest and fees go unless `loc_interest_category`
+/// is set in metadata.
+pub const DEFAULT_LOC_INTEREST_CATEGORY: &str = "Interest Expense";
+
+/// Draws and principal payments: a transfer category, so they stay off the P&L.
+pub const LOC_PRINCIPAL_CATEGORY: &str = "Loan Principal";
+
+/// Words in a line-of-credit description that mark an interest or fee charge
+/// rather than a draw or payment.
+const LOC_INTEREST_WORDS: &[&str] = &["INTEREST", "FINANCE", "FEE", "FEES"];
+
+pub fn loc_interest_category(conn: &Connection) -> String {
+    crate::db::get_metadata(conn, "loc_interest_category")
+        .unwrap_or_else(|| DEFAULT_LOC_INTEREST_CATEGORY.to_string())
+}
+
+/// Route future line-of-credit interest and fees to `name`, which must be an
+/// active expense category.
+pub fn set_loc_interest_category(conn: &Connection, name: &str) -> Result<()> {
+    let category_type: Option<String> = conn
+        .query_row(
+            "SELECT category_type FROM categories WHERE name = ?1 AND is_active = 1",
+            [name],
+            |r| r.get(0),
+        )
+        .optional()?;
+    match category_type.as_deref() {
+        Some("expense") => crate::db::set_metadata(conn, "loc_interest_category", name),
+        Some(other) => Err(NigelError::Other(format!(
+            "Interest needs an expense category; '{name}' is type {other}"
+        ))),
+        None => Err(NigelError::UnknownCategory(name.to_string())),
+    }
+}
+
+fn is_loc_interest(description: &str) -> bool {
+    description
+        .to_uppercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|word| LOC_INTEREST_WORDS.contains(&word))
+}
+
 /// Shared parser for BofA Credit Card and Line of Credit CSV formats.
 /// - `has_type_column: true`  → Credit Card (D/C sign logic)
-/// - `has_type_column: false` → Line of Credit (always negate)
+/// - `has_type_column: false` → Line of Credit (negate draws and payments;
+///   interest and fee lines keep their sign so charges stay expenses)
 fn parse_bofa_card_format(
     file_path: &Path,
     has_type_column: bool,
@@ -620,6 +1005,10 @@ fn parse_bofa_card_format(
             } else {
                 parsed.abs()
             }
+        } else if is_loc_interest(&description) {
+            // Interest and fees add to the balance like a draw, but they're an
+            // expense to the business, not cash in: keep the statement's sign.
+            parsed
         } else {
             -parsed
         };
@@ -632,6 +1021,357 @@ fn parse_bofa_card_format(
     Ok((rows, malformed))
 }
 
+// ---------------------------------------------------------------------------
+// BofA PDF statement parser (feature-gated)
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "pdf-import")]
+fn detect_bofa_pdf_statement(file_path: &Path) -> bool {
+    use std::io::Read;
+    let Ok(mut file) = std::fs::File::open(file_path) else {
+        return false;
+    };
+    let mut magic = [0u8; 5];
+    file.read_exact(&mut magic).is_ok() && &magic == b"%PDF-"
+}
+
+/// Extract a statement's text as lines, rebuilding each visual line from
+/// the positioned text fragments on the page (top to bottom, left to right).
+#[cfg(feature = "pdf-import")]
+pub fn extract_pdf_lines(data: &[u8]) -> Result<Vec<String>> {
+    use lopdf::{Document, Object};
+
+    let doc = Document::load_mem(data)
+        .map_err(|e| NigelError::Pdf(format!("Could not read PDF: {e}")))?;
+    let num = |o: &Object| o.as_float().unwrap_or(0.0);
+    let mut lines = Vec::new();
+
+    for page_id in doc.get_pages().into_values() {
+        let encodings: std::collections::BTreeMap<Vec<u8>, String> = doc
+            .get_page_fonts(page_id)
+            .into_iter()
+            .map(|(name, font)| (name, font.get_font_encoding().to_string()))
+            .collect();
+        let content = doc
+            .get_and_decode_page_content(page_id)
+            .map_err(|e| NigelError::Pdf(format!("Could not read PDF page: {e}")))?;
+
+        // (y, x, text) for every string drawn on the page
+        let mut fragments: Vec<(f32, f32, String)> = Vec::new();
+        let mut encoding: Option<String> = None;
+        // Text matrix and line matrix as [a b c d e f]
+        let identity = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+        let mut tm: [f32; 6] = identity;
+        let mut tlm: [f32; 6] = identity;
+        let mut leading = 0.0f32;
+        let translate = |m: [f32; 6], tx: f32, ty: f32| {
+            let mut out = m;
+            out[4] = tx * m[0] + ty * m[2] + m[4];
+            out[5] = tx * m[1] + ty * m[3] + m[5];
+            out
+        };
+
+        for op in &content.operations {
+            let args = &op.operands;
+            match op.operator.as_str() {
+                "BT" => {
+                    tm = identity;
+                    tlm = identity;
+                }
+                "Tf" => {
+                    encoding = args
+                        .first()
+                        .and_then(|o| o.as_name().ok())
+                        .and_then(|name| encodings.get(name).cloned());
+                }
+                "Tm" if args.len() == 6 => {
+                    for (slot, arg) in tlm.iter_mut().zip(args) {
+                        *slot = num(arg);
+                    }
+                    tm = tlm;
+                }
+                "Td" | "TD" if args.len() == 2 => {
+                    let (tx, ty) = (num(&args[0]), num(&args[1]));
+                    if op.operator == "TD" {
+                        leading = -ty;
+                    }
+                    tlm = translate(tlm, tx, ty);
+                    tm = tlm;
+                }
+                "TL" if !args.is_empty() => leading = num(&args[0]),
+                "T*" => {
+                    tlm = translate(tlm, 0.0, -leading);
+                    tm = tlm;
+                }
+                "Tj" | "TJ" | "'" | "\"" => {
+                    if op.operator == "'" || op.operator == "\"" {
+                        tlm = translate(tlm, 0.0, -leading);
+                        tm = tlm;
+                    }
+                    let mut text = String::new();
+                    collect_pdf_text(&mut text, encoding.as_deref(), args);
+                    if !text.trim().is_empty() {
+                        fragments.push((tm[5], tm[4], text));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Highest y first; fragments within 2pt of each other share a line
+        fragments.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let mut current: Vec<(f32, String)> = Vec::new();
+        let mut line_y = f32::NAN;
+        for (y, x, text) in fragments {
+            if !current.is_empty() && (line_y - y).abs() > 2.0 {
+                lines.push(join_pdf_fragments(&mut current));
+            }
+            if current.is_empty() {
+                line_y = y;
+            }
+            current.push((x, text));
+        }
+        if !current.is_empty() {
+            lines.push(join_pdf_fragments(&mut current));
+        }
+    }
+    Ok(lines)
+}
+
+#[cfg(feature = "pdf-import")]
+fn collect_pdf_text(text: &mut String, encoding: Option<&str>, operands: &[lopdf::Object]) {
+    use lopdf::Object;
+    for operand in operands {
+        match operand {
+            Object::String(bytes, _) => {
+                text.push_str(&lopdf::Document::decode_text(encoding, bytes));
+            }
+            Object::Array(items) => collect_pdf_text(text, encoding, items),
+            // Large negative kerning in a TJ array is a word gap
+            Object::Integer(i) if *i < -200 => text.push(' '),
+            Object::Real(r) if *r < -200.0 => text.push(' '),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "pdf-import")]
+fn join_pdf_fragments(fragments: &mut Vec<(f32, String)>) -> String {
+    fragments.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let parts: Vec<String> = fragments
+        .drain(..)
+        .map(|(_, t)| t.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    parts.join(" ")
+}
+
+/// Which part of a statement a line belongs to.
+#[cfg(feature = "pdf-import")]
+#[derive(Clone, Copy, PartialEq)]
+enum StatementSection {
+    Other,
+    Deposits,
+    Withdrawals,
+    Checks,
+    CardActivity,
+}
+
+#[cfg(feature = "pdf-import")]
+fn statement_section(line: &str) -> Option<StatementSection> {
+    let lower = line.to_lowercase();
+    let section = if lower.starts_with("deposits and other additions") {
+        StatementSection::Deposits
+    } else if lower.starts_with("withdrawals and other subtractions")
+        || lower.starts_with("service fees")
+    {
+        StatementSection::Withdrawals
+    } else if lower.starts_with("checks") {
+        StatementSection::Checks
+    } else if lower.starts_with("payments and other credits")
+        || lower.starts_with("purchases and adjustments")
+        || lower.starts_with("fees charged")
+        || lower.starts_with("interest charged")
+    {
+        StatementSection::CardActivity
+    } else if lower.starts_with("daily ledger balances")
+        || lower.starts_with("account summary")
+        || lower.starts_with("interest charge calculation")
+    {
+        StatementSection::Other
+    } else {
+        return None;
+    };
+    Some(section)
+}
+
+/// The statement's closing date, used to give card transactions (printed
+/// as MM/DD) a year. Reads periods like "January 1, 2025 to January 31, 2025"
+/// or "December 15, 2024 - January 14, 2025".
+#[cfg(feature = "pdf-import")]
+fn statement_closing_date(lines: &[String]) -> Option<chrono::NaiveDate> {
+    let re = regex::Regex::new(
+        r"([A-Z][a-z]+ \d{1,2}, \d{4})\s*(?:-|to|through|\u{2013})\s*([A-Z][a-z]+ \d{1,2}, \d{4})",
+    )
+    .ok()?;
+    lines.iter().find_map(|line| {
+        let caps = re.captures(line)?;
+        chrono::NaiveDate::parse_from_str(&caps[2], "%B %d, %Y").ok()
+    })
+}
+
+/// Parse Bank of America checking or credit card PDF statements. Checking
+/// statements print signed amounts with a full MM/DD/YY date; card
+/// statements print charges as positive with MM/DD dates, so their signs
+/// are flipped to match the CSV importers.
+#[cfg(feature = "pdf-import")]
+fn parse_bofa_pdf_statement(file_path: &Path) -> Result<(Vec<ParsedRow>, usize)> {
+    let data = std::fs::read(file_path)?;
+    let lines = extract_pdf_lines(&data)?;
+    let (rows, malformed) = parse_bofa_statement_lines(&lines);
+    if rows.is_empty() {
+        return Err(NigelError::Pdf(
+            "No transactions found in this PDF. Only Bank of America checking and credit card statements are supported."
+                .into(),
+        ));
+    }
+    Ok((rows, malformed))
+}
+
+#[cfg(feature = "pdf-import")]
+fn parse_bofa_statement_lines(lines: &[String]) -> (Vec<ParsedRow>, usize) {
+    // unwrap safe: constant patterns
+    let checking_re =
+        regex::Regex::new(r"^(\d{2}/\d{2}/\d{2,4})\s+(.+?)\s+(-?\$?[\d,]*\.\d{2})$").unwrap();
+    let check_re =
+        regex::Regex::new(r"(\d{2}/\d{2}/\d{2,4})\s+(\d+)\s*\*?\s+(-?\$?[\d,]*\.\d{2})").unwrap();
+    let card_re =
+        regex::Regex::new(r"^(\d{2}/\d{2})\s+(\d{2}/\d{2})\s+(.+?)\s+(-?\$?[\d,]*\.\d{2})$")
+            .unwrap();
+    let starts_with_date = regex::Regex::new(r"^\d{2}/\d{2}\b").unwrap();
+
+    let closing = statement_closing_date(lines);
+    let mut section = StatementSection::Other;
+    let mut rows = Vec::new();
+    let mut malformed = 0usize;
+
+    for line in lines {
+        let line = line.trim();
+        if let Some(s) = statement_section(line) {
+            section = s;
+            continue;
+        }
+        if section == StatementSection::Other || line.to_lowercase().starts_with("total") {
+            continue;
+        }
+        if !starts_with_date.is_match(line) {
+            continue;
+        }
+        match section {
+            StatementSection::Checks => {
+                let mut found = false;
+                for caps in check_re.captures_iter(line) {
+                    found = true;
+                    match (parse_statement_date(&caps[1]), parse_amount(&caps[3])) {
+                        (Some(date), Some(amount)) => rows.push(ParsedRow {
+                            date,
+                            description: format!("Check {}", &caps[2]),
+                            amount: -amount.abs(),
+                        }),
+                        _ => malformed += 1,
+                    }
+                }
+                if !found {
+                    malformed += 1;
+                }
+            }
+            StatementSection::CardActivity => {
+                let Some(caps) = card_re.captures(line) else {
+                    malformed += 1;
+                    continue;
+                };
+                let (Some(date), Some(amount)) =
+                    (card_date(&caps[2], closing), parse_amount(&caps[4]))
+                else {
+                    malformed += 1;
+                    continue;
+                };
+                rows.push(ParsedRow {
+                    date,
+                    description: strip_card_reference(&caps[3]),
+                    amount: -amount,
+                });
+            }
+            StatementSection::Deposits | StatementSection::Withdrawals => {
+                let Some(caps) = checking_re.captures(line) else {
+                    malformed += 1;
+                    continue;
+                };
+                let (Some(date), Some(amount)) =
+                    (parse_statement_date(&caps[1]), parse_amount(&caps[3]))
+                else {
+                    malformed += 1;
+                    continue;
+                };
+                // Withdrawals are printed negative, but don't rely on it
+                let amount = if section == StatementSection::Withdrawals {
+                    -amount.abs()
+                } else {
+                    amount
+                };
+                rows.push(ParsedRow {
+                    date,
+                    description: caps[2].trim().to_string(),
+                    amount,
+                });
+            }
+            StatementSection::Other => {}
+        }
+    }
+    (rows, malformed)
+}
+
+/// MM/DD/YY or MM/DD/YYYY to YYYY-MM-DD.
+#[cfg(feature = "pdf-import")]
+fn parse_statement_date(raw: &str) -> Option<String> {
+    let formats = ["%m/%d/%y", "%m/%d/%Y"];
+    formats
+        .iter()
+        .find_map(|f| chrono::NaiveDate::parse_from_str(raw, f).ok())
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+/// Give an MM/DD card date the statement's year, rolling back a year for
+/// December charges on a January statement.
+#[cfg(feature = "pdf-import")]
+fn card_date(raw: &str, closing: Option<chrono::NaiveDate>) -> Option<String> {
+    use chrono::Datelike;
+    let closing = closing?;
+    let (m, d) = raw.split_once('/')?;
+    let (m, d): (u32, u32) = (m.parse().ok()?, d.parse().ok()?);
+    let year = if m > closing.month() {
+        closing.year() - 1
+    } else {
+        closing.year()
+    };
+    chrono::NaiveDate::from_ymd_opt(year, m, d).map(|dt| dt.format("%Y-%m-%d").to_string())
+}
+
+/// Drop the trailing reference and account-number columns from a card
+/// statement description.
+#[cfg(feature = "pdf-import")]
+fn strip_card_reference(desc: &str) -> String {
+    let mut words: Vec<&str> = desc.split_whitespace().collect();
+    for _ in 0..2 {
+        match words.last() {
+            Some(w) if words.len() > 1 && w.len() >= 4 && w.chars().all(|c| c.is_ascii_digit()) => {
+                words.pop();
+            }
+            _ => break,
+        }
+    }
+    words.join(" ")
+}
+
 // ---------------------------------------------------------------------------
 // Gusto Payroll parser (feature-gated)
 // ---------------------------------------------------------------------------
@@ -651,118 +1391,266 @@ fn detect_gusto_payroll(file_path: &Path) -> bool {
     workbook.sheet_names().iter().any(|name| name == "payrolls")
 }
 
+/// A Gusto export's totals, each keyed by check date.
 #[cfg(feature = "gusto")]
-fn parse_gusto_payroll(file_path: &Path) -> Result<Vec<ParsedRow>> {
-    use calamine::{Data, Reader};
-    use std::collections::BTreeMap;
-
-    let mut workbook = calamine::open_workbook_auto(file_path)
-        .map_err(|e| NigelError::Other(format!("Failed to open XLSX: {e}")))?;
+#[derive(Default)]
+struct PayrollTotals {
+    gross: std::collections::BTreeMap<String, f64>,
+    employer_taxes: std::collections::BTreeMap<String, f64>,
+    employee_taxes: std::collections::BTreeMap<String, f64>,
+    deductions: std::collections::BTreeMap<String, f64>,
+    reimbursements: std::collections::BTreeMap<String, f64>,
+}
 
-    // Parse payrolls sheet — aggregate wages by check date
-    let mut wages_by_date: BTreeMap<String, f64> = BTreeMap::new();
-    if let Ok(range) = workbook.worksheet_range("payrolls") {
-        for row in range.rows().skip(1) {
-            // col 3 = check_date, col 7 = gross
-            if row.len() < 8 {
-                continue;
+#[cfg(feature = "gusto")]
+impl PayrollTotals {
+    /// Wages and employer taxes per check date; `detailed` splits wages into
+    /// net pay, employee withholding, and benefit deductions (which still sum
+    /// to gross) and adds reimbursements.
+    fn rows(&self, detailed: bool) -> Vec<ParsedRow> {
+        let mut result = Vec::new();
+        if !detailed {
+            for (date, gross) in &self.gross {
+                result.push(payroll_row(date, "Wages", *gross));
             }
-            let check_date = match &row[3] {
-                Data::Float(f) => excel_serial_to_date(*f),
-                Data::Int(i) => excel_serial_to_date(*i as f64),
-                Data::String(s) => s.clone(),
-                _ => continue,
-            };
-            let gross = match &row[7] {
-                Data::Float(f) => *f,
-                Data::Int(i) => *i as f64,
-                _ => continue,
+        } else {
+            let on = |totals: &std::collections::BTreeMap<String, f64>, date: &String| {
+                totals.get(date).map_or(0.0, |t| t.abs())
             };
-            *wages_by_date.entry(check_date).or_default() += gross;
+            for (date, gross) in &self.gross {
+                let net = gross.abs() - on(&self.employee_taxes, date) - on(&self.deductions, date);
+                result.push(payroll_row(date, "Net Pay", net));
+            }
+            for (label, totals) in [
+                ("Employee Withholding", &self.employee_taxes),
+                ("Benefit Deductions", &self.deductions),
+            ] {
+                for (date, total) in totals {
+                    if *total != 0.0 {
+                        result.push(payroll_row(date, label, *total));
+                    }
+                }
+            }
+        }
+        for (date, total) in &self.employer_taxes {
+            result.push(payroll_row(date, "Employer Taxes", *total));
         }
+        if detailed {
+            for (date, total) in &self.reimbursements {
+                if *total != 0.0 {
+                    result.push(payroll_row(date, "Reimbursements", *total));
+                }
+            }
+        }
+        result
     }
+}
 
-    // Parse taxes sheet — aggregate employer taxes by check date
-    let mut taxes_by_date: BTreeMap<String, f64> = BTreeMap::new();
-    if let Ok(range) = workbook.worksheet_range("taxes") {
-        for row in range.rows().skip(1) {
-            if row.len() < 8 {
-                continue;
-            }
-            // col 6 = type (Employer), col 3 = check_date, col 7 = amount
-            let tax_type = match &row[6] {
-                Data::String(s) => s.as_str(),
-                _ => continue,
-            };
-            if tax_type != "Employer" {
-                continue;
-            }
-            let check_date = match &row[3] {
-                Data::Float(f) => excel_serial_to_date(*f),
-                Data::Int(i) => excel_serial_to_date(*i as f64),
-                Data::String(s) => s.clone(),
-                _ => continue,
-            };
-            let amount = match &row[7] {
-                Data::Float(f) => *f,
-                Data::Int(i) => *i as f64,
-                _ => continue,
-            };
-            *taxes_by_date.entry(check_date).or_default() += amount;
+#[cfg(feature = "gusto")]
+fn payroll_row(date: &str, label: &str, amount: f64) -> ParsedRow {
+    ParsedRow {
+        date: date.to_string(),
+        description: format!("Payroll \u{2014} {label} ({date})"),
+        amount: -amount.abs(),
+    }
+}
+
+/// Sum a Gusto sheet's amounts (col 7) by check date (col 3), for the rows
+/// `keep` accepts.
+#[cfg(feature = "gusto")]
+fn totals_by_check_date(
+    range: Option<&calamine::Range<calamine::Data>>,
+    keep: impl Fn(&[calamine::Data]) -> bool,
+) -> std::collections::BTreeMap<String, f64> {
+    use calamine::Data;
+
+    let mut totals = std::collections::BTreeMap::new();
+    let Some(range) = range else {
+        return totals;
+    };
+    for row in range.rows().skip(1) {
+        if row.len() < 8 || !keep(row) {
+            continue;
         }
+        let check_date = match &row[3] {
+            Data::Float(f) => excel_serial_to_date(*f),
+            Data::Int(i) => excel_serial_to_date(*i as f64),
+            Data::String(s) => s.clone(),
+            _ => continue,
+        };
+        let amount = match &row[7] {
+            Data::Float(f) => *f,
+            Data::Int(i) => *i as f64,
+            _ => continue,
+        };
+        *totals.entry(check_date).or_default() += amount;
     }
+    totals
+}
 
-    let mut result = Vec::new();
-    for (date, total) in &wages_by_date {
-        result.push(ParsedRow {
-            date: date.clone(),
-            description: format!("Payroll \u{2014} Wages ({date})"),
-            amount: -total.abs(),
-        });
+#[cfg(feature = "gusto")]
+fn parse_gusto_payroll(file_path: &Path, detailed: bool) -> Result<Vec<ParsedRow>> {
+    use calamine::{Data, Reader};
+
+    let mut workbook = calamine::open_workbook_auto(file_path)
+        .map_err(|e| NigelError::Other(format!("Failed to open XLSX: {e}")))?;
+    let mut sheet = |name: &str| workbook.worksheet_range(name).ok();
+
+    let payrolls = sheet("payrolls");
+    let taxes = sheet("taxes");
+    let deductions = sheet("deductions");
+    let reimbursements = sheet("reimbursements");
+    // col 6 of the taxes sheet says who pays: "Employer" or "Employee"
+    let tax_type =
+        |kind: &'static str| move |row: &[Data]| matches!(&row[6], Data::String(s) if s == kind);
+    let totals = PayrollTotals {
+        gross: totals_by_check_date(payrolls.as_ref(), |_| true),
+        employer_taxes: totals_by_check_date(taxes.as_ref(), tax_type("Employer")),
+        employee_taxes: totals_by_check_date(taxes.as_ref(), tax_type("Employee")),
+        deductions: totals_by_check_date(deductions.as_ref(), |_| true),
+        reimbursements: totals_by_check_date(reimbursements.as_ref(), |_| true),
+    };
+    Ok(totals.rows(detailed))
+}
+
+/// The category each payroll line is filed to, by the label in its
+/// description.
+#[cfg(feature = "gusto")]
+const PAYROLL_LINES: &[(&str, &str)] = &[
+    ("Wages", PAYROLL_WAGES),
+    ("Net Pay", PAYROLL_WAGES),
+    (
+        "Employee Withholding",
+        "Payroll \u{2014} Employee Withholding",
+    ),
+    ("Benefit Deductions", "Payroll \u{2014} Benefit Deductions"),
+    ("Employer Taxes", "Payroll \u{2014} Taxes"),
+    ("Reimbursements", PAYROLL_REIMBURSEMENTS),
+];
+
+#[cfg(feature = "gusto")]
+const PAYROLL_WAGES: &str = "Payroll \u{2014} Wages";
+#[cfg(feature = "gusto")]
+const PAYROLL_REIMBURSEMENTS: &str = "Payroll \u{2014} Reimbursements";
+
+/// Tax and form lines for reimbursements, by the prefix of the wage
+/// category's form line: they're other deductions, not compensation.
+#[cfg(feature = "gusto")]
+const REIMBURSEMENT_LINES: &[(&str, &str, &str)] = &[
+    ("1120S-", "Line 27a", "1120S-19"),
+    ("1065-", "Other deductions", "1065-21"),
+    ("990-", "Part IX 24", "990-IX-24"),
+];
+
+/// The id of a payroll category, creating a detailed-payroll one on first
+/// use. Withholding and benefit deductions are part of gross wages, so they
+/// sit under Payroll — Wages on its tax and form lines; reimbursements get
+/// the template's other-deductions lines. None when the book has no wage
+/// category to hang them from.
+#[cfg(feature = "gusto")]
+fn payroll_category(conn: &Connection, name: &str) -> Result<Option<i64>> {
+    let existing: Option<i64> = conn
+        .query_row("SELECT id FROM categories WHERE name = ?1", [name], |r| {
+            r.get(0)
+        })
+        .optional()?;
+    if existing.is_some() || name == PAYROLL_WAGES || name == "Payroll \u{2014} Taxes" {
+        return Ok(existing);
     }
-    for (date, total) in &taxes_by_date {
-        result.push(ParsedRow {
-            date: date.clone(),
-            description: format!("Payroll \u{2014} Employer Taxes ({date})"),
-            amount: -total.abs(),
+    let wages: Option<(i64, String, Option<String>, Option<String>)> = conn
+        .query_row(
+            "SELECT id, category_type, tax_line, form_line FROM categories WHERE name = ?1",
+            [PAYROLL_WAGES],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .optional()?;
+    let Some((wages_id, category_type, tax_line, form_line)) = wages else {
+        return Ok(None);
+    };
+    if name == PAYROLL_REIMBURSEMENTS {
+        let lines = form_line.as_deref().and_then(|fl| {
+            REIMBURSEMENT_LINES
+                .iter()
+                .find(|(prefix, _, _)| fl.starts_with(prefix))
         });
+        conn.execute(
+            "INSERT INTO categories (name, category_type, tax_line, form_line) \
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![
+                name,
+                category_type,
+                lines.map(|(_, tax_line, _)| *tax_line),
+                lines.map(|(_, _, form_line)| *form_line),
+            ],
+        )?;
+    } else {
+        conn.execute(
+            "INSERT INTO categories (name, category_type, tax_line, form_line, parent_id) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![name, category_type, tax_line, form_line, wages_id],
+        )?;
     }
-    Ok(result)
+    Ok(Some(conn.last_insert_rowid()))
 }
 
 #[cfg(feature = "gusto")]
 fn auto_categorize_payroll(conn: &Connection, account_id: i64, rows: &[ParsedRow]) -> Result<()> {
     let mut payroll_categories = std::collections::HashMap::new();
-    for cat_name in &[
-        "Payroll \u{2014} Wages",
-        "Payroll \u{2014} Taxes",
-        "Payroll \u{2014} Benefits",
-    ] {
-        let id: std::result::Result<i64, _> = conn.query_row(
-            "SELECT id FROM categories WHERE name = ?1",
-            [cat_name],
-            |r| r.get(0),
-        );
-        if let Ok(id) = id {
-            payroll_categories.insert(*cat_name, id);
+    for row in rows {
+        let Some((_, cat_name)) = row
+            .description
+            .strip_prefix("Payroll \u{2014} ")
+            .and_then(|rest| rest.split(" (").next())
+            .and_then(|label| PAYROLL_LINES.iter().find(|(l, _)| *l == label))
+        else {
+            continue;
+        };
+        if !payroll_categories.contains_key(cat_name) {
+            payroll_categories.insert(*cat_name, payroll_category(conn, cat_name)?);
+        }
+        if let Some(&Some(cat_id)) = payroll_categories.get(cat_name) {
+            conn.execute(
+                "UPDATE transactions SET category_id = ?1, is_flagged = 0, flag_reason = NULL \
+                 WHERE account_id = ?2 AND date = ?3 AND amount = ?4 AND description = ?5",
+                rusqlite::params![cat_id, account_id, row.date, row.amount, row.description],
+            )?;
         }
     }
+    Ok(())
+}
+
+/// Line of credit statements mix draws, payments, and interest in one
+/// signed column. Interest and fees go to the configured interest category;
+/// everything else is principal.
+fn auto_categorize_line_of_credit(
+    conn: &Connection,
+    account_id: i64,
+    rows: &[ParsedRow],
+) -> Result<()> {
+    let category_id = |name: &str| -> Result<Option<i64>> {
+        Ok(conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = ?1 AND is_active = 1",
+                [name],
+                |r| r.get(0),
+            )
+            .optional()?)
+    };
+    let interest_id = category_id(&loc_interest_category(conn))?;
+    let principal_id = category_id(LOC_PRINCIPAL_CATEGORY)?;
 
     for row in rows {
-        let category_id = if row.description.contains("Wages") {
-            payroll_categories.get("Payroll \u{2014} Wages")
-        } else if row.description.contains("Taxes") {
-            payroll_categories.get("Payroll \u{2014} Taxes")
-        } else if row.description.contains("Benefits") {
-            payroll_categories.get("Payroll \u{2014} Benefits")
+        let target = if is_loc_interest(&row.description) {
+            interest_id
         } else {
-            None
+            principal_id
         };
-
-        if let Some(&cat_id) = category_id {
+        if let Some(cat_id) = target {
+            // Leave rows already categorized by hand (row-level duplicates) alone
             conn.execute(
                 "UPDATE transactions SET category_id = ?1, is_flagged = 0, flag_reason = NULL \
-                 WHERE account_id = ?2 AND date = ?3 AND amount = ?4 AND description = ?5",
+                 WHERE account_id = ?2 AND date = ?3 AND amount = ?4 AND description = ?5 \
+                 AND category_id IS NULL",
                 rusqlite::params![cat_id, account_id, row.date, row.amount, row.description],
             )?;
         }
@@ -774,6 +1662,7 @@ fn auto_categorize_payroll(conn: &Connection, account_id: i64, rows: &[ParsedRow
 mod tests {
     use super::*;
     use crate::db::{get_connection, init_db};
+    use std::path::PathBuf;
 
     fn test_db() -> (tempfile::TempDir, Connection) {
         let dir = tempfile::tempdir().unwrap();
@@ -837,13 +1726,147 @@ mod tests {
         assert_eq!(parse_date_mdy("00/15/2025"), None); // month 0
     }
 
-    #[test]
-    fn test_excel_serial_to_date() {
-        assert_eq!(excel_serial_to_date(45667.0), "2025-01-10");
+    #[test]
+    fn test_excel_serial_to_date() {
+        assert_eq!(excel_serial_to_date(45667.0), "2025-01-10");
+    }
+
+    #[test]
+    fn test_import_file_inserts_transactions() {
+        let (dir, conn) = test_db();
+        add_test_account(&conn);
+        let csv_path = write_bofa_csv(
+            dir.path(),
+            "stmt.csv",
+            &[
+                ("01/15/2025", "PAYMENT ONE", "-100.00"),
+                ("01/16/2025", "PAYMENT TWO", "-250.00"),
+                ("01/17/2025", "DEPOSIT", "500.00"),
+            ],
+        );
+        let result = import_file(
+            &conn,
+            &csv_path,
+            "Test Checking",
+            Some("bofa_checking"),
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(result.imported, 3);
+        assert!(result.duplicate_of.is_none());
+        let count: i64 = conn
+            .query_row("SELECT count(*) FROM transactions", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_import_file_detects_file_duplicate() {
+        let (dir, conn) = test_db();
+        add_test_account(&conn);
+        let csv_path = write_bofa_csv(
+            dir.path(),
+            "stmt.csv",
+            &[("01/15/2025", "PAYMENT ONE", "-100.00")],
+        );
+        let r1 = import_file(
+            &conn,
+            &csv_path,
+            "Test Checking",
+            Some("bofa_checking"),
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(r1.imported, 1);
+        let r2 = import_file(
+            &conn,
+            &csv_path,
+            "Test Checking",
+            Some("bofa_checking"),
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        let prior = r2.duplicate_of.expect("matched the first import");
+        assert_eq!(Some(prior.id), r1.import_id);
+        assert_eq!(prior.filename, "stmt.csv");
+        assert_eq!(prior.record_count, 1);
+        assert!(prior.describe().contains("(1 records, import #"));
+        assert_eq!(r2.imported, 0);
+        assert_eq!(
+            find_prior_import(&conn, &csv_path).unwrap().map(|p| p.id),
+            r1.import_id
+        );
+    }
+
+    #[test]
+    fn test_force_reimport_creates_new_batch() {
+        let (dir, conn) = test_db();
+        add_test_account(&conn);
+        let csv_path = write_bofa_csv(
+            dir.path(),
+            "stmt.csv",
+            &[("01/15/2025", "PAYMENT ONE", "-100.00")],
+        );
+        let r1 = import_file(
+            &conn,
+            &csv_path,
+            "Test Checking",
+            None,
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        // Transactions removed by hand after the first import come back
+        conn.execute("DELETE FROM transactions", []).unwrap();
+
+        let r2 = import_file(
+            &conn,
+            &csv_path,
+            "Test Checking",
+            None,
+            false,
+            None,
+            true,
+            false,
+        )
+        .unwrap();
+        assert!(r2.duplicate_of.is_none());
+        assert_eq!(r2.imported, 1);
+        assert_ne!(r2.import_id, r1.import_id);
+
+        // Rows that are still present are skipped, not doubled
+        let r3 = import_file(
+            &conn,
+            &csv_path,
+            "Test Checking",
+            None,
+            false,
+            None,
+            true,
+            false,
+        )
+        .unwrap();
+        assert_eq!((r3.imported, r3.skipped), (0, 1));
+        // The newest matching batch is the one reported
+        assert_eq!(
+            find_prior_import(&conn, &csv_path).unwrap().map(|p| p.id),
+            r3.import_id
+        );
     }
 
     #[test]
-    fn test_import_file_inserts_transactions() {
+    fn test_commit_writes_reviewed_rows_in_one_transaction() {
         let (dir, conn) = test_db();
         add_test_account(&conn);
         let csv_path = write_bofa_csv(
@@ -851,57 +1874,157 @@ mod tests {
             "stmt.csv",
             &[
                 ("01/15/2025", "PAYMENT ONE", "-100.00"),
-                ("01/16/2025", "PAYMENT TWO", "-250.00"),
-                ("01/17/2025", "DEPOSIT", "500.00"),
+                ("01/16/2025", "PAYMENT TWO", "-200.00"),
+                ("01/17/2025", "BAD ROW", "-300.00"),
             ],
         );
-        let result = import_file(
-            &conn,
-            &csv_path,
-            "Test Checking",
-            Some("bofa_checking"),
-            false,
-            None,
+        let mut parsed =
+            read_import(&conn, &csv_path, "Test Checking", None, None, false, false).unwrap();
+        assert_eq!(parsed.rows.len(), 3);
+
+        // A failing insert part way rolls back the whole batch
+        conn.execute_batch(
+            "CREATE TRIGGER reject_bad BEFORE INSERT ON transactions \
+             WHEN NEW.description = 'BAD ROW' BEGIN SELECT RAISE(ABORT, 'rejected'); END;",
         )
         .unwrap();
-        assert_eq!(result.imported, 3);
-        assert!(!result.duplicate_file);
-        let count: i64 = conn
-            .query_row("SELECT count(*) FROM transactions", [], |r| r.get(0))
+        assert!(parsed.commit(&conn, &parsed.rows).is_err());
+        let counts: (i64, i64) = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM transactions), (SELECT COUNT(*) FROM imports)",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
             .unwrap();
-        assert_eq!(count, 3);
+        assert_eq!(counts, (0, 0));
+
+        // Only the reviewed rows are written, with their corrections
+        parsed.rows[0].amount = -10.0;
+        let chosen = parsed.rows[..2].to_vec();
+        let batch = parsed.commit(&conn, &chosen).unwrap();
+        assert_eq!((batch.imported, batch.skipped), (2, 0));
+        let amounts: Vec<f64> = conn
+            .prepare("SELECT amount FROM transactions ORDER BY date")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(amounts, vec![-10.0, -200.0]);
+        // The file's checksum is recorded, so a second import is caught
+        let again =
+            read_import(&conn, &csv_path, "Test Checking", None, None, false, false).unwrap();
+        assert!(again.duplicate_of.is_some());
     }
 
     #[test]
-    fn test_import_file_detects_file_duplicate() {
+    fn test_account_remembers_format_and_folder() {
         let (dir, conn) = test_db();
         add_test_account(&conn);
-        let csv_path = write_bofa_csv(
-            dir.path(),
-            "stmt.csv",
-            &[("01/15/2025", "PAYMENT ONE", "-100.00")],
+        let csv1 = write_bofa_csv(dir.path(), "stmt1.csv", &[("01/15/2025", "ONE", "-100.00")]);
+
+        // A dry run remembers nothing
+        import_file(
+            &conn,
+            &csv1,
+            "Test Checking",
+            Some("bofa_checking"),
+            true,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            import_defaults(&conn, "Test Checking").unwrap(),
+            ImportDefaults::default()
         );
-        let r1 = import_file(
+
+        import_file(
             &conn,
-            &csv_path,
+            &csv1,
             "Test Checking",
             Some("bofa_checking"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
-        assert_eq!(r1.imported, 1);
-        let r2 = import_file(
+        let defaults = import_defaults(&conn, "Test Checking").unwrap();
+        assert_eq!(defaults.default_format.as_deref(), Some("bofa_checking"));
+        let folder = std::fs::canonicalize(dir.path()).unwrap();
+        assert_eq!(
+            defaults.last_import_dir.as_deref(),
+            Some(folder.to_string_lossy().as_ref())
+        );
+
+        // Leaving --format off uses it
+        let csv2 = write_bofa_csv(dir.path(), "stmt2.csv", &[("01/15/2025", "TWO", "-100.00")]);
+        let result = import_file(
             &conn,
-            &csv_path,
+            &csv2,
             "Test Checking",
-            Some("bofa_checking"),
+            None,
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
-        assert!(r2.duplicate_file);
-        assert_eq!(r2.imported, 0);
+        assert_eq!(result.remembered_format.as_deref(), Some("bofa_checking"));
+        assert_eq!(result.imported, 1);
+
+        // A format that's gone falls back to detection
+        conn.execute("UPDATE accounts SET default_format = 'old_profile'", [])
+            .unwrap();
+        let csv3 = write_bofa_csv(
+            dir.path(),
+            "stmt3.csv",
+            &[("01/15/2025", "THREE", "-100.00")],
+        );
+        let result = import_file(
+            &conn,
+            &csv3,
+            "Test Checking",
+            None,
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(result.remembered_format.is_none());
+        assert_eq!(result.imported, 1);
+        assert_eq!(
+            import_defaults(&conn, "Test Checking")
+                .unwrap()
+                .default_format
+                .as_deref(),
+            Some("old_profile")
+        );
+
+        // auto detects and forgets
+        let csv4 = write_bofa_csv(
+            dir.path(),
+            "stmt4.csv",
+            &[("01/15/2025", "FOUR", "-100.00")],
+        );
+        import_file(
+            &conn,
+            &csv4,
+            "Test Checking",
+            Some(AUTO_FORMAT),
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(import_defaults(&conn, "Test Checking")
+            .unwrap()
+            .default_format
+            .is_none());
     }
 
     #[test]
@@ -923,6 +2046,8 @@ mod tests {
             Some("bofa_checking"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
         let csv2 = write_bofa_csv(
@@ -940,6 +2065,8 @@ mod tests {
             Some("bofa_checking"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
         assert_eq!(r2.imported, 1);
@@ -962,6 +2089,8 @@ mod tests {
             Some("bofa_checking"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
         let count: i64 = conn
@@ -1070,6 +2199,169 @@ RAYGUN DESIGN, LLC,5678,02/05/2025,02/05/2025,500.00,Payment,LOC PAYMENT,100 Ban
         assert_eq!(rows[1].amount, -500.0);
     }
 
+    fn write_loc_statement(dir: &Path) -> PathBuf {
+        let path = dir.join("loc.csv");
+        let content = "\
+CardHolder Name,Account Number,Transaction Date,Posting Date,Amount,Category,Payee,Address,City/State,Reference Number
+RAYGUN DESIGN LLC,5678,02/01/2025,02/01/2025,-1000.00,Transfer,LINE OF CREDIT DRAW,100 Bank St,Boston MA,REF101
+RAYGUN DESIGN LLC,5678,02/05/2025,02/05/2025,500.00,Payment,LOC PAYMENT,100 Bank St,Boston MA,REF102
+RAYGUN DESIGN LLC,5678,02/28/2025,02/28/2025,-12.34,Interest,INTEREST CHARGE,100 Bank St,Boston MA,REF103
+RAYGUN DESIGN LLC,5678,02/28/2025,02/28/2025,-25.00,Fees,ANNUAL FEE,100 Bank St,Boston MA,REF104
+";
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_bofa_loc_interest_keeps_expense_sign() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_loc_statement(dir.path());
+        let (rows, _malformed) = ImporterKind::BofaLineOfCredit.parse(&path).unwrap();
+        let amounts: Vec<f64> = rows.iter().map(|r| r.amount).collect();
+        assert_eq!(amounts, vec![1000.0, -500.0, -12.34, -25.0]);
+        assert!(!is_loc_interest("COFFEE LOC DRAW"));
+    }
+
+    #[test]
+    fn test_loc_import_routes_interest_and_principal() {
+        let (dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('LOC', 'line_of_credit')",
+            [],
+        )
+        .unwrap();
+        let path = write_loc_statement(dir.path());
+        import_file(&conn, &path, "LOC", None, false, None, false, false).unwrap();
+
+        let categorized = |description: &str| -> (String, bool) {
+            conn.query_row(
+                "SELECT c.name, t.is_flagged FROM transactions t \
+                 JOIN categories c ON t.category_id = c.id WHERE t.description = ?1",
+                [description],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap()
+        };
+        assert_eq!(categorized("LINE OF CREDIT DRAW").0, LOC_PRINCIPAL_CATEGORY);
+        assert_eq!(categorized("LOC PAYMENT").0, LOC_PRINCIPAL_CATEGORY);
+        assert_eq!(
+            categorized("INTEREST CHARGE"),
+            (DEFAULT_LOC_INTEREST_CATEGORY.to_string(), false)
+        );
+        assert_eq!(categorized("ANNUAL FEE").0, DEFAULT_LOC_INTEREST_CATEGORY);
+    }
+
+    #[cfg(feature = "gusto")]
+    fn gusto_totals() -> PayrollTotals {
+        let on =
+            |amount: f64| std::collections::BTreeMap::from([("2025-01-15".to_string(), amount)]);
+        PayrollTotals {
+            gross: on(5000.0),
+            employer_taxes: on(400.0),
+            employee_taxes: on(1200.0),
+            deductions: on(300.0),
+            reimbursements: on(150.0),
+        }
+    }
+
+    #[cfg(feature = "gusto")]
+    #[test]
+    fn test_gusto_summary_and_detailed_rows() {
+        let lines = |detailed| -> Vec<(String, f64)> {
+            gusto_totals()
+                .rows(detailed)
+                .into_iter()
+                .map(|r| (r.description, r.amount))
+                .collect()
+        };
+        assert_eq!(
+            lines(false),
+            vec![
+                ("Payroll \u{2014} Wages (2025-01-15)".to_string(), -5000.0),
+                (
+                    "Payroll \u{2014} Employer Taxes (2025-01-15)".to_string(),
+                    -400.0
+                ),
+            ]
+        );
+        assert_eq!(
+            lines(true),
+            vec![
+                ("Payroll \u{2014} Net Pay (2025-01-15)".to_string(), -3500.0),
+                (
+                    "Payroll \u{2014} Employee Withholding (2025-01-15)".to_string(),
+                    -1200.0
+                ),
+                (
+                    "Payroll \u{2014} Benefit Deductions (2025-01-15)".to_string(),
+                    -300.0
+                ),
+                (
+                    "Payroll \u{2014} Employer Taxes (2025-01-15)".to_string(),
+                    -400.0
+                ),
+                (
+                    "Payroll \u{2014} Reimbursements (2025-01-15)".to_string(),
+                    -150.0
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "gusto")]
+    #[test]
+    fn test_detailed_payroll_keeps_officer_comp_at_gross() {
+        let (_dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Gusto', 'payroll')",
+            [],
+        )
+        .unwrap();
+        let account_id = conn.last_insert_rowid();
+        let rows = gusto_totals().rows(true);
+        insert_batch(&conn, account_id, "payroll.xlsx", None, &rows).unwrap();
+        auto_categorize_payroll(&conn, account_id, &rows).unwrap();
+
+        let uncategorized: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM transactions WHERE category_id IS NULL",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(uncategorized, 0);
+        let (parent, form_line): (String, String) = conn
+            .query_row(
+                "SELECT p.name, c.form_line FROM categories c \
+                 JOIN categories p ON c.parent_id = p.id \
+                 WHERE c.name = 'Payroll \u{2014} Employee Withholding'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (parent.as_str(), form_line.as_str()),
+            (PAYROLL_WAGES, "1120S-8")
+        );
+
+        let k1 = crate::reports::get_k1_prep(&conn, Some(2025)).unwrap();
+        assert!((k1.validation.officer_comp - 5000.0).abs() < 0.01);
+        assert!(k1
+            .other_deductions
+            .iter()
+            .any(|d| d.category_name == PAYROLL_REIMBURSEMENTS && (d.total - 150.0).abs() < 0.01));
+    }
+
+    #[test]
+    fn test_set_loc_interest_category() {
+        let (_dir, conn) = test_db();
+        assert_eq!(loc_interest_category(&conn), DEFAULT_LOC_INTEREST_CATEGORY);
+        set_loc_interest_category(&conn, "Bank & Merchant Fees").unwrap();
+        assert_eq!(loc_interest_category(&conn), "Bank & Merchant Fees");
+        assert!(set_loc_interest_category(&conn, "Interest Income").is_err());
+        assert!(set_loc_interest_category(&conn, "Nope").is_err());
+    }
+
     #[test]
     fn test_bofa_credit_card_parse_multiple_commas_in_name() {
         let dir = tempfile::tempdir().unwrap();
@@ -1128,6 +2420,8 @@ RAYGUN DESIGN, LLC,1234,01/17/2025,01/17/2025,100.00,Refund,STORE REFUND,789 Oak
             Some("bofa_checking"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
         assert_eq!(result.imported, 2, "malformed amount row should be skipped");
@@ -1178,6 +2472,8 @@ JOHN DOE,1234,01/15/2025,01/15/2025,-50.00,Shopping,AMAZON, INC,123 Main St,Seat
             Some("bofa_checking"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
         let import_id: i64 = conn
@@ -1210,7 +2506,7 @@ RAYGUN DESIGN LLC,1234,01/17/2025,01/17/2025,-25.00,Travel,DELTA AIRLINES,789 Oa
     }
 
     #[test]
-    fn test_import_result_has_sample() {
+    fn test_real_import_has_no_preview() {
         let (dir, conn) = test_db();
         add_test_account(&conn);
         let csv_path = write_bofa_csv(
@@ -1229,10 +2525,12 @@ RAYGUN DESIGN LLC,1234,01/17/2025,01/17/2025,-25.00,Travel,DELTA AIRLINES,789 Oa
             Some("bofa_checking"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
-        assert_eq!(result.sample.len(), 3);
-        assert_eq!(result.sample[0].description, "PAYMENT ONE");
+        assert_eq!(result.imported, 3);
+        assert!(result.preview.is_empty());
     }
 
     #[test]
@@ -1254,11 +2552,13 @@ RAYGUN DESIGN LLC,1234,01/17/2025,01/17/2025,-25.00,Travel,DELTA AIRLINES,789 Oa
             Some("bofa_checking"),
             true,
             None,
+            false,
+            false,
         )
         .unwrap();
         assert_eq!(result.imported, 2);
         assert_eq!(result.skipped, 0);
-        assert!(!result.duplicate_file);
+        assert!(result.duplicate_of.is_none());
         let tx_count: i64 = conn
             .query_row("SELECT count(*) FROM transactions", [], |r| r.get(0))
             .unwrap();
@@ -1288,6 +2588,8 @@ RAYGUN DESIGN LLC,1234,01/17/2025,01/17/2025,-25.00,Travel,DELTA AIRLINES,789 Oa
             Some("bofa_checking"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
         let csv2 = write_bofa_csv(
@@ -1305,6 +2607,8 @@ RAYGUN DESIGN LLC,1234,01/17/2025,01/17/2025,-25.00,Travel,DELTA AIRLINES,789 Oa
             Some("bofa_checking"),
             true,
             None,
+            false,
+            false,
         )
         .unwrap();
         assert_eq!(result.imported, 1);
@@ -1312,17 +2616,31 @@ RAYGUN DESIGN LLC,1234,01/17/2025,01/17/2025,-25.00,Travel,DELTA AIRLINES,789 Oa
     }
 
     #[test]
-    fn test_dry_run_sample_capped_at_five() {
+    fn test_dry_run_previews_every_row() {
         let (dir, conn) = test_db();
         add_test_account(&conn);
-        let rows: Vec<(&str, &str, &str)> = vec![
+        let first = write_bofa_csv(
+            dir.path(),
+            "first.csv",
+            &[("01/01/2025", "TXN 1", "-10.00")],
+        );
+        import_file(
+            &conn,
+            &first,
+            "Test Checking",
+            Some("bofa_checking"),
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let rows = [
             ("01/01/2025", "TXN 1", "-10.00"),
             ("01/02/2025", "TXN 2", "-20.00"),
             ("01/03/2025", "TXN 3", "-30.00"),
-            ("01/04/2025", "TXN 4", "-40.00"),
-            ("01/05/2025", "TXN 5", "-50.00"),
-            ("01/06/2025", "TXN 6", "-60.00"),
-            ("01/07/2025", "TXN 7", "-70.00"),
+            ("01/03/2025", "TXN 3", "-30.00"),
         ];
         let csv_path = write_bofa_csv(dir.path(), "stmt.csv", &rows);
         let result = import_file(
@@ -1332,10 +2650,14 @@ RAYGUN DESIGN LLC,1234,01/17/2025,01/17/2025,-25.00,Travel,DELTA AIRLINES,789 Oa
             Some("bofa_checking"),
             true,
             None,
+            false,
+            false,
         )
         .unwrap();
-        assert_eq!(result.sample.len(), 5, "sample should be capped at 5");
-        assert_eq!(result.imported, 7);
+        let duplicates: Vec<bool> = result.preview.iter().map(|p| p.duplicate).collect();
+        // Already in the account, new, new, and a repeat within the file
+        assert_eq!(duplicates, vec![true, false, false, true]);
+        assert_eq!((result.imported, result.skipped), (2, 2));
     }
 
     #[test]
@@ -1451,6 +2773,8 @@ Date,Note,Amount
             Some("test_bank"),
             false,
             None,
+            false,
+            false,
         )
         .unwrap();
         assert_eq!(result.imported, 2);
@@ -1459,4 +2783,185 @@ Date,Note,Amount
             .unwrap();
         assert_eq!(count, 2);
     }
+
+    /// Write a one-page PDF drawing each line at its own height. A `|`
+    /// splits a line into a left fragment and a right-aligned amount, drawn
+    /// amount-first to mimic out-of-order content streams.
+    #[cfg(feature = "pdf-import")]
+    fn write_statement_pdf(dir: &Path, name: &str, lines: &[&str]) -> std::path::PathBuf {
+        use lopdf::content::{Content, Operation};
+        use lopdf::{dictionary, Document, Object, Stream};
+
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => "WinAnsiEncoding",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+        let text_op = |x: i64, y: i64, text: &str| {
+            vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 9.into()]),
+                Operation::new("Td", vec![x.into(), y.into()]),
+                Operation::new("Tj", vec![Object::string_literal(text)]),
+                Operation::new("ET", vec![]),
+            ]
+        };
+        let mut operations = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            let y = 750 - i as i64 * 14;
+            match line.split_once('|') {
+                Some((left, amount)) => {
+                    operations.extend(text_op(500, y, amount.trim()));
+                    operations.extend(text_op(50, y, left.trim()));
+                }
+                None => operations.extend(text_op(50, y, line)),
+            }
+        }
+        let content = Content { operations };
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![page_id.into()],
+                "Count" => 1,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+        let path = dir.join(name);
+        doc.save(&path).unwrap();
+        path
+    }
+
+    #[cfg(feature = "pdf-import")]
+    const CHECKING_STATEMENT: &[&str] = &[
+        "Your Adv Plus Banking",
+        "for January 1, 2025 to January 31, 2025",
+        "Deposits and other additions",
+        "Date Description | Amount",
+        "01/03/25 ZELLE PAYMENT FROM ACME CORP Conf# 8ab2 | 2,500.00",
+        "01/17/25 Online Banking transfer from SAV 4412 | 300.00",
+        "Total deposits and other additions | $2,800.00",
+        "Withdrawals and other subtractions",
+        "01/06/25 CHECKCARD 0105 ADOBE *CREATIVE CLD SAN JOSE CA | -54.99",
+        "01/21/25 GUSTO DES:NET 250121 ID:XXXXX | -3,210.45",
+        "Total withdrawals and other subtractions | -$3,265.44",
+        "Checks",
+        "01/09/25 1042 -150.00 01/22/25 1043 * -75.25",
+        "Total checks | -$225.25",
+        "Daily ledger balances",
+        "01/03 12,345.67 01/06 12,290.68",
+    ];
+
+    #[cfg(feature = "pdf-import")]
+    #[test]
+    fn test_extract_pdf_lines_orders_fragments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_statement_pdf(dir.path(), "s.pdf", &["01/06/25 ADOBE | -54.99", "Next"]);
+        let lines = extract_pdf_lines(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(lines, vec!["01/06/25 ADOBE -54.99", "Next"]);
+    }
+
+    #[cfg(feature = "pdf-import")]
+    #[test]
+    fn test_bofa_pdf_checking_statement() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_statement_pdf(dir.path(), "jan.pdf", CHECKING_STATEMENT);
+        let (rows, malformed) = ImporterKind::BofaPdfStatement.parse(&path).unwrap();
+        assert_eq!(malformed, 0);
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[0].date, "2025-01-03");
+        assert_eq!(
+            rows[0].description,
+            "ZELLE PAYMENT FROM ACME CORP Conf# 8ab2"
+        );
+        assert_eq!(rows[0].amount, 2500.0);
+        assert_eq!(rows[3].amount, -3210.45);
+        assert_eq!(rows[4].description, "Check 1042");
+        assert_eq!(rows[4].amount, -150.0);
+        assert_eq!(rows[5].date, "2025-01-22");
+        assert_eq!(rows[5].amount, -75.25);
+    }
+
+    #[cfg(feature = "pdf-import")]
+    #[test]
+    fn test_bofa_pdf_card_statement_years_and_signs() {
+        let lines: Vec<String> = [
+            "December 15, 2024 - January 14, 2025",
+            "Payments and Other Credits",
+            "01/05 01/05 PAYMENT - THANK YOU 7402 1234 -1,200.00",
+            "Purchases and Adjustments",
+            "12/28 12/30 AMAZON MKTP US*2K4LL0 AMZN.COM/BILLWA 2469216 1234 45.67",
+            "01/02 01/03 NOTION LABS, INC. 8812 1234 10.00",
+            "01/09 garbled line",
+            "TOTAL PURCHASES AND ADJUSTMENTS FOR THIS PERIOD $55.67",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let (rows, malformed) = parse_bofa_statement_lines(&lines);
+        assert_eq!(malformed, 1);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].amount, 1200.0);
+        assert_eq!(rows[0].description, "PAYMENT - THANK YOU");
+        assert_eq!(rows[1].date, "2024-12-30");
+        assert_eq!(rows[1].description, "AMAZON MKTP US*2K4LL0 AMZN.COM/BILLWA");
+        assert_eq!(rows[1].amount, -45.67);
+        assert_eq!(rows[2].date, "2025-01-03");
+        assert_eq!(rows[2].description, "NOTION LABS, INC.");
+    }
+
+    #[cfg(feature = "pdf-import")]
+    #[test]
+    fn test_import_file_detects_pdf_statement() {
+        let (dir, conn) = test_db();
+        add_test_account(&conn);
+        let path = write_statement_pdf(dir.path(), "jan.pdf", CHECKING_STATEMENT);
+        assert_eq!(
+            get_for_file("checking", &path),
+            Some(ImporterKind::BofaPdfStatement)
+        );
+        let result = import_file(
+            &conn,
+            &path,
+            "Test Checking",
+            None,
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(result.imported, 6);
+        let total: f64 = conn
+            .query_row("SELECT SUM(amount) FROM transactions", [], |r| r.get(0))
+            .unwrap();
+        assert!((total - (2800.0 - 3265.44 - 225.25)).abs() < 0.001);
+    }
+
+    #[cfg(feature = "pdf-import")]
+    #[test]
+    fn test_pdf_without_transactions_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_statement_pdf(dir.path(), "letter.pdf", &["Dear customer,", "Hello"]);
+        let err = ImporterKind::BofaPdfStatement.parse(&path).unwrap_err();
+        assert!(err.to_string().contains("No transactions found"));
+    }
 }
@@ -0,0 +1,70 @@
+//! Nigel's bookkeeping engine as a library.
+//!
+//! The `nigel` binary is a thin layer over these modules. For analysis
+//! programs of your own, start with [`api`]: it opens a book read-only and
+//! answers register queries and reports with typed parameters instead of
+//! SQL. `cargo run --example vendor_spend` shows it in use.
+
+pub mod anomaly;
+pub mod api;
+pub mod archive;
+pub mod audit;
+pub mod browser;
+pub mod categorizer;
+pub mod cli;
+pub mod clients;
+pub mod compensation;
+pub mod currency;
+pub mod data_quality;
+pub mod db;
+pub mod deductions;
+pub mod digest;
+pub mod effects;
+pub mod error;
+pub mod expense_limits;
+pub mod filter;
+pub mod fiscal;
+pub mod fmt;
+#[cfg(feature = "games")]
+mod games;
+pub mod health;
+pub mod help;
+pub mod importer;
+pub mod locales;
+pub mod maintenance;
+pub mod merge;
+pub mod migrations;
+pub mod models;
+pub mod month_close;
+pub mod offsite;
+pub mod overview;
+pub mod package;
+pub mod payplan;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod query;
+pub mod reconciler;
+pub mod reimbursables;
+pub mod reports;
+pub mod review_csv;
+pub mod reviewer;
+pub mod rule_audit;
+pub mod rule_packs;
+pub mod schedule;
+pub mod schema;
+pub mod search;
+pub mod settings;
+pub mod setup_docs;
+pub mod shareholders;
+pub mod sharing;
+pub mod signoff;
+pub mod statements;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod tabular;
+pub mod tags;
+pub mod templates;
+pub mod trash;
+pub mod tui;
+pub mod vault;
+pub mod vendor_alerts;
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::error::{NigelError, Result};
+use crate::settings::{load_settings, locales_dir};
+
+/// A message catalog, as embedded TOML: tables group the keys (`[menu]`
+/// holds `menu.import`), and each value is the message, with `{name}`
+/// placeholders for what the code fills in. English is the default and the
+/// fallback for any key a translation leaves out, so adding a language is a
+/// new file here and a line in `LOCALES`.
+pub struct Locale {
+    pub key: &'static str,
+    /// The language's own name for itself, shown on the Settings screen.
+    pub name: &'static str,
+    pub source: &'static str,
+}
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+pub const LOCALES: &[Locale] = &[Locale {
+    key: "en",
+    name: "English",
+    source: include_str!("en.toml"),
+}];
+
+/// Messages by dotted key.
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    pub fn parse(source: &str) -> Result<Self> {
+        let table: toml::Table =
+            toml::from_str(source).map_err(|e| NigelError::Other(e.to_string()))?;
+        let mut messages = HashMap::new();
+        flatten("", &table, &mut messages)?;
+        Ok(Self { messages })
+    }
+
+    /// A built-in locale, or `<config>/locales/<key>.toml` so a translation
+    /// can be tried before it's built in.
+    pub fn load(key: &str) -> Result<Self> {
+        let parsed = match LOCALES.iter().find(|l| l.key == key) {
+            Some(locale) => Self::parse(locale.source),
+            None => {
+                let path = locales_dir().join(format!("{key}.toml"));
+                let source = std::fs::read_to_string(&path).map_err(|_| {
+                    NigelError::Settings(format!(
+                        "Unknown locale \"{key}\" (no {} found)",
+                        path.display()
+                    ))
+                })?;
+                Self::parse(&source)
+            }
+        };
+        parsed.map_err(|e| NigelError::Settings(format!("Locale {key} is invalid: {e}")))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.messages.keys().map(String::as_str)
+    }
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut HashMap<String, String>) -> Result<()> {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match value {
+            toml::Value::String(message) => {
+                out.insert(key, message.clone());
+            }
+            toml::Value::Table(inner) => flatten(&key, inner, out)?,
+            _ => return Err(NigelError::Other(format!("{key} is not a string"))),
+        }
+    }
+    Ok(())
+}
+
+fn english() -> &'static Catalog {
+    static ENGLISH: OnceLock<Catalog> = OnceLock::new();
+    // unwrap safe: the embedded English catalog is checked by the tests
+    ENGLISH.get_or_init(|| Catalog::parse(LOCALES[0].source).unwrap())
+}
+
+/// The catalog in use; None until the first lookup reads the setting.
+static CURRENT: RwLock<Option<Arc<Catalog>>> = RwLock::new(None);
+
+/// Switch the catalog messages come from. An unknown or invalid locale is
+/// an error, and leaves the current one in place.
+pub fn set_locale(key: &str) -> Result<()> {
+    let catalog = Catalog::load(key)?;
+    if let Ok(mut current) = CURRENT.write() {
+        *current = Some(Arc::new(catalog));
+    }
+    Ok(())
+}
+
+fn current() -> Arc<Catalog> {
+    if let Some(catalog) = CURRENT.read().ok().and_then(|c| c.clone()) {
+        return catalog;
+    }
+    // A locale that won't load leaves every message to the English fallback
+    let catalog = Arc::new(
+        Catalog::load(&load_settings().locale).unwrap_or_else(|_| Catalog {
+            messages: HashMap::new(),
+        }),
+    );
+    if let Ok(mut current) = CURRENT.write() {
+        *current = Some(catalog.clone());
+    }
+    catalog
+}
+
+/// The message for `key` in the current locale, else English, else the key
+/// itself.
+pub fn t(key: &str) -> String {
+    let catalog = current();
+    catalog
+        .get(key)
+        .or_else(|| english().get(key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// `t()` with each `{name}` placeholder replaced by its argument.
+pub fn t_args(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = t(key);
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `{name}` placeholders a message uses, sorted.
+    fn placeholders(message: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    #[test]
+    fn every_locale_translates_english_keys_with_the_same_placeholders() {
+        for locale in LOCALES {
+            let catalog = Catalog::parse(locale.source).unwrap();
+            for key in catalog.keys() {
+                let english = english()
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{}: {key} is not an English key", locale.key));
+                assert_eq!(
+                    placeholders(catalog.get(key).unwrap()),
+                    placeholders(english),
+                    "{}: {key}",
+                    locale.key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_key_in_the_source_is_in_english() {
+        let pattern = regex::Regex::new(r#"\bt(?:_args)?\(\s*"([a-z0-9_.]+)""#).unwrap();
+        let mut dirs = vec![std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src")];
+        let mut checked = 0;
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(&dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|e| e == "rs") {
+                    let source = std::fs::read_to_string(&path).unwrap();
+                    for key in pattern.captures_iter(&source) {
+                        let key = &key[1];
+                        assert!(
+                            english().get(key).is_some(),
+                            "{}: {key} missing from en.toml",
+                            path.display()
+                        );
+                        checked += 1;
+                    }
+                }
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn fills_placeholders_and_falls_back_to_the_key() {
+        assert_eq!(
+            t_args("error.unknown_category", &[("category", &"Travel")]),
+            "Unknown category: Travel"
+        );
+        let missing = "menu.no_such_item";
+        assert_eq!(t(missing), missing);
+        assert_eq!(placeholders("{b} and {a} and {b}"), ["a", "b"]);
+    }
+
+    #[test]
+    fn loads_a_translation_from_source() {
+        let catalog = Catalog::parse("[menu]\nimport = \"Importar un extracto\"\n").unwrap();
+        assert_eq!(catalog.get("menu.import"), Some("Importar un extracto"));
+        assert!(Catalog::parse("[menu]\nimport = 3\n").is_err());
+        assert!(Catalog::load("xx-no-such-locale").is_err());
+    }
+}
@@ -1,26 +1,17 @@
-mod browser;
-mod categorizer;
-mod cli;
-mod db;
-mod effects;
-mod error;
-mod fmt;
-mod importer;
-mod migrations;
-mod models;
-#[cfg(feature = "pdf")]
-mod pdf;
-mod reconciler;
-mod reports;
-mod reviewer;
-mod settings;
-mod tui;
+use nigel::{cli, error};
 
-use clap::{CommandFactory, Parser};
+use std::io::IsTerminal;
 
+use clap::{CommandFactory, FromArgMatches};
+
+#[cfg(feature = "sync")]
+use cli::SyncCommands;
 use cli::{
-    AccountsCommands, BrowseCommands, CategoriesCommands, Cli, Commands, PasswordCommand,
-    RulesCommands,
+    AccountsCommands, AlertsCommands, ArchiveCommands, BackupCommands, BrowseCommands,
+    CategoriesCommands, Cli, ClientsCommands, Commands, ConfigCommands, DbCommands, ExportCommands,
+    LimitsCommands, MileageCommands, PasswordCommand, PayplanCommands, RatesCommands,
+    ReimbursableCommands, ReviewCommands, RulesCommands, ScheduleCommands, ShareholdersCommands,
+    TrashCommands,
 };
 
 fn main() {
@@ -31,11 +22,29 @@ fn main() {
         hook(info);
     }));
 
-    let cli = Cli::parse();
+    // Simple mode only changes what --help lists
+    let matches = cli::command(nigel::settings::load_settings().simple_mode).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    if let Err(e) = cli::recover_corrupt_settings() {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+
+    if cli.read_only || nigel::settings::load_settings().read_only {
+        nigel::db::set_read_only(true);
+    }
 
     let result = match cli.command {
-        // Dashboard handles missing init via its own onboarding flow
-        None => cli::dashboard::run(),
+        // Dashboard handles missing init via its own onboarding flow. Scripts
+        // and pipes get a plain error instead of any TUI or greeting output.
+        None if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() => {
+            Err(error::NigelError::Other(
+                "The dashboard needs an interactive terminal. Run `nigel --help` for commands."
+                    .into(),
+            ))
+        }
+        None => cli::dashboard::run(cli.no_splash, None),
         Some(command) => {
             // Non-blocking update check for CLI subcommands (dashboard does its own).
             // Skip when running `nigel update` since it does its own check.
@@ -54,65 +63,185 @@ fn main() {
     }
 }
 
+/// Commands that only read the books, allowed in read-only mode.
+fn is_read_only_command(command: &Commands) -> bool {
+    match command {
+        Commands::Report { .. }
+        | Commands::Export { .. }
+        | Commands::Digest { .. }
+        | Commands::Browse { .. }
+        | Commands::Status { .. }
+        | Commands::Completions { .. }
+        | Commands::Update => true,
+        Commands::AssertBalance { list, .. } => *list,
+        Commands::Close { list, .. } => *list,
+        Commands::Signoff { list, .. } => *list,
+        // The manager screens turn away edits in read-only mode themselves
+        Commands::Accounts { command } => matches!(
+            command,
+            AccountsCommands::Manage | AccountsCommands::List | AccountsCommands::Reveal { .. }
+        ),
+        Commands::Categories { command } => {
+            matches!(
+                command,
+                CategoriesCommands::List
+                    | CategoriesCommands::Manage
+                    | CategoriesCommands::Export { .. }
+            )
+        }
+        Commands::Rules { command } => matches!(
+            command,
+            RulesCommands::Manage
+                | RulesCommands::List
+                | RulesCommands::Test { .. }
+                | RulesCommands::Export { .. }
+                | RulesCommands::InstallPack { name: None, .. }
+        ),
+        Commands::Rates { command } => matches!(command, RatesCommands::List),
+        Commands::Mileage { command } => matches!(command, MileageCommands::List { .. }),
+        Commands::Archive { command } => matches!(command, ArchiveCommands::List),
+        Commands::Schedule { command } => matches!(command, ScheduleCommands::List),
+        Commands::Alerts { command } => matches!(command, AlertsCommands::List),
+        Commands::Limits { command } => matches!(command, LimitsCommands::List { .. }),
+        Commands::Clients { command } => matches!(command, ClientsCommands::List),
+        Commands::Shareholders { command } => matches!(command, ShareholdersCommands::List),
+        Commands::Trash { command } => matches!(command, TrashCommands::List),
+        // Saved queries and report defaults live in settings.json, not the books
+        Commands::Query { allow_writes, .. } => !*allow_writes,
+        Commands::Config { .. } => true,
+        Commands::Db { command } => matches!(command, DbCommands::Schema { .. }),
+        Commands::Backup { command, .. } => {
+            matches!(command, Some(BackupCommands::Verify { .. }))
+        }
+        Commands::Review { command, .. } => {
+            matches!(command, Some(ReviewCommands::Export { .. }))
+        }
+        Commands::Payplan { command } => matches!(
+            command,
+            PayplanCommands::Show { .. } | PayplanCommands::History { .. }
+        ),
+        _ => false,
+    }
+}
+
 fn dispatch(command: Commands) -> error::Result<()> {
-    // Check that nigel has been initialized (skip for init/demo which create new DBs, load which switches directories, and update which needs no DB)
+    if nigel::db::is_read_only() && !is_read_only_command(&command) {
+        return Err(error::NigelError::Other(
+            "Read-only mode: this command changes the books. Run it without --read-only \
+             (and with \"read_only\" off in settings.json)."
+                .into(),
+        ));
+    }
+
+    // Check that nigel has been initialized (skip for init/demo/tutorial which create new DBs, load which switches directories, and update which needs no DB)
+    // (and for `status --badge`, which reports a missing database as JSON)
     if !matches!(
         command,
-        Commands::Init { .. } | Commands::Demo | Commands::Load { .. } | Commands::Update
+        Commands::Init { .. }
+            | Commands::Demo { .. }
+            | Commands::Tutorial
+            | Commands::Load { .. }
+            | Commands::Update
+            | Commands::Status { badge: true }
     ) {
-        let data_dir = crate::settings::get_data_dir();
+        if let Some(missing) = cli::load::missing_data_dir() {
+            return Err(cli::load::missing_data_dir_error(&missing));
+        }
+        let data_dir = nigel::settings::get_data_dir();
         let db_path = data_dir.join("nigel.db");
         if !db_path.exists() {
             return Err(error::NigelError::NotInitialized);
         }
     }
 
-    // Prompt for password if database is encrypted (skip for init/demo which may create new DBs)
+    // Prompt for password if database is encrypted (skip for init and the
+    // isolated demo, which create new DBs; `demo --in-place/--clean` write to
+    // the current book)
     if !matches!(
         command,
         Commands::Init { .. }
-            | Commands::Demo
+            | Commands::Demo {
+                in_place: false,
+                clean: false
+            }
+            | Commands::Tutorial
             | Commands::Password { .. }
             | Commands::Completions { .. }
             | Commands::Update
+            | Commands::Status { badge: true }
     ) {
-        let data_dir = crate::settings::get_data_dir();
+        let data_dir = nigel::settings::get_data_dir();
         let db_path = data_dir.join("nigel.db");
         if db_path.exists() {
-            crate::db::prompt_password_if_needed(&db_path)?;
+            nigel::db::prompt_password_if_needed(&db_path)?;
+            if !nigel::db::is_read_only() {
+                if let Ok(conn) = nigel::db::get_connection(&db_path) {
+                    let n = cli::trash::empty_expired(&conn);
+                    if n > 0 {
+                        let days = nigel::settings::load_settings().trash_retention_days();
+                        eprintln!(
+                            "notice: deleted {n} transaction(s) trashed over {days} days ago"
+                        );
+                    }
+                }
+            }
         }
     }
 
     match command {
-        Commands::Init { data_dir } => cli::init::run(data_dir),
+        Commands::Init { data_dir, template } => cli::init::run(data_dir, template),
         Commands::Accounts { command } => match command {
             AccountsCommands::Add {
                 name,
                 account_type,
                 institution,
                 last_four,
+                currency,
+                opening_balance,
+                opening_date,
             } => cli::accounts::add(
                 &name,
                 &account_type,
                 institution.as_deref(),
                 last_four.as_deref(),
+                currency.as_deref(),
+                opening_balance,
+                opening_date.as_deref(),
             ),
+            AccountsCommands::Manage => cli::screen::run_standalone(|conn, greeting| {
+                Ok(cli::account_manager::AccountManager::new(conn, greeting))
+            }),
             AccountsCommands::List => cli::accounts::list(),
             AccountsCommands::Rename { id, name } => cli::accounts::rename(id, &name),
             AccountsCommands::Delete { id } => cli::accounts::delete(id),
+            AccountsCommands::Archive { name } => cli::accounts::archive(&name, true),
+            AccountsCommands::Unarchive { name } => cli::accounts::archive(&name, false),
+            AccountsCommands::OpeningBalance {
+                name,
+                balance,
+                date,
+            } => cli::accounts::opening_balance(&name, balance, date.as_deref()),
+            AccountsCommands::SetNumber { name } => cli::accounts::set_number(&name),
+            AccountsCommands::Reveal { name } => cli::accounts::reveal(&name),
+            AccountsCommands::ClearNumber { name } => cli::accounts::clear_number(&name),
         },
         Commands::Categories { command } => match command {
             CategoriesCommands::List => cli::categories::list(),
+            CategoriesCommands::Manage => cli::screen::run_standalone(|conn, greeting| {
+                Ok(cli::category_manager::CategoryManager::new(conn, greeting))
+            }),
             CategoriesCommands::Add {
                 name,
                 category_type,
                 tax_line,
                 form_line,
+                parent,
             } => cli::categories::add(
                 &name,
                 &category_type,
                 tax_line.as_deref(),
                 form_line.as_deref(),
+                parent,
             ),
             CategoriesCommands::Rename { id, name } => cli::categories::rename(id, &name),
             CategoriesCommands::Update {
@@ -121,14 +250,27 @@ fn dispatch(command: Commands) -> error::Result<()> {
                 category_type,
                 tax_line,
                 form_line,
+                parent,
             } => cli::categories::update(
                 id,
                 &name,
                 &category_type,
                 tax_line.as_deref(),
                 form_line.as_deref(),
+                parent,
             ),
             CategoriesCommands::Delete { id } => cli::categories::delete(id),
+            CategoriesCommands::Merge { from, into } => cli::categories::merge(&from, &into),
+            CategoriesCommands::LocInterest { category } => {
+                cli::categories::loc_interest(category.as_deref())
+            }
+            CategoriesCommands::Export { path } => cli::sharing::export_chart(&path),
+            CategoriesCommands::Import {
+                path,
+                merge: _,
+                replace,
+                dry_run,
+            } => cli::sharing::import_chart(&path, replace, dry_run),
         },
         Commands::Import {
             file,
@@ -140,6 +282,9 @@ fn dispatch(command: Commands) -> error::Result<()> {
             amount_col,
             date_format,
             save_profile,
+            keep_file,
+            force_reimport,
+            detailed,
         } => cli::import::run(
             &file,
             &account,
@@ -151,10 +296,14 @@ fn dispatch(command: Commands) -> error::Result<()> {
                 amount_col,
                 date_format: date_format.as_deref(),
                 save_profile: save_profile.as_deref(),
+                keep_file,
+                force_reimport,
+                detailed,
             },
         ),
         Commands::Categorize => cli::categorize::run(),
-        Commands::Demo => cli::demo::run(),
+        Commands::Demo { in_place, clean } => cli::demo::run(in_place, clean),
+        Commands::Tutorial => cli::tutorial::run(),
         Commands::Rules { command } => match command {
             RulesCommands::Add {
                 pattern,
@@ -169,6 +318,9 @@ fn dispatch(command: Commands) -> error::Result<()> {
                 &match_type,
                 priority,
             ),
+            RulesCommands::Manage => cli::screen::run_standalone(|conn, greeting| {
+                Ok(cli::rules_manager::RulesManager::new(conn, greeting))
+            }),
             RulesCommands::List => cli::rules::list(),
             RulesCommands::Update {
                 id,
@@ -183,9 +335,35 @@ fn dispatch(command: Commands) -> error::Result<()> {
                 pattern,
                 match_type,
             } => cli::rules::test(&pattern, &match_type),
+            RulesCommands::Cleanup => cli::rules_cleanup::run(),
+            RulesCommands::Export { path } => cli::sharing::export_rules(&path),
+            RulesCommands::Import {
+                path,
+                merge: _,
+                replace,
+                create_categories,
+                dry_run,
+            } => cli::sharing::import_rules(&path, replace, create_categories, dry_run),
+            RulesCommands::InstallPack {
+                name: None,
+                create_categories: _,
+                dry_run: _,
+            } => cli::sharing::list_packs(),
+            RulesCommands::InstallPack {
+                name: Some(name),
+                create_categories,
+                dry_run,
+            } => cli::sharing::install_pack(&name, create_categories, dry_run),
         },
-        Commands::Review { id } => cli::review::run(id),
-        Commands::Report { command } => cli::report::dispatch(command),
+        Commands::Review { command, id, batch } => match command {
+            Some(ReviewCommands::Export { path }) => cli::review::export(&path),
+            Some(ReviewCommands::Import { path, dry_run }) => cli::review::import(&path, dry_run),
+            None => cli::review::run(id, batch),
+        },
+        Commands::Report { mut command } => {
+            cli::report::defaults::apply(&mut command);
+            cli::report::dispatch(command)
+        }
         Commands::Browse { command } => match command {
             BrowseCommands::Register {
                 month,
@@ -193,24 +371,246 @@ fn dispatch(command: Commands) -> error::Result<()> {
                 from_date,
                 to_date,
                 account,
-            } => cli::browse::register(month, year, from_date, to_date, account),
+                tag,
+            } => cli::browse::register(month, year, from_date, to_date, account, tag),
         },
         Commands::Reconcile {
             account,
             month,
             balance,
-        } => cli::reconcile::run(&account, &month, balance),
+            note,
+            follow_up,
+            notes,
+            resolve,
+            tui,
+        } => match (resolve, account, month, balance) {
+            _ if notes => cli::reconcile::list_notes(),
+            (_, account, month, _) if tui => cli::screen::run_standalone(|conn, greeting| {
+                cli::reconcile_manager::ReconcileScreen::prefilled(
+                    conn,
+                    greeting,
+                    account.as_deref(),
+                    month.as_deref(),
+                )
+            }),
+            (Some(id), ..) => cli::reconcile::resolve(id),
+            (None, Some(account), Some(month), Some(balance)) => cli::reconcile::run(
+                &account,
+                &month,
+                balance,
+                note.as_deref(),
+                follow_up.as_deref(),
+            ),
+            _ => Err(error::NigelError::Other(
+                "An account, --month and --balance are required".into(),
+            )),
+        },
+        Commands::AssertBalance {
+            account,
+            date,
+            balance,
+            list,
+            remove,
+        } => match (remove, account, date, balance) {
+            _ if list => cli::reconcile::list_assertions(),
+            (Some(id), ..) => cli::reconcile::remove_assertion(id),
+            (None, Some(account), Some(date), Some(balance)) => {
+                cli::reconcile::assert_balance(&account, &date, balance)
+            }
+            _ => Err(error::NigelError::Other(
+                "An account, --date and --balance are required".into(),
+            )),
+        },
         Commands::Load { path } => cli::load::run(&path),
-        Commands::Backup { output } => cli::backup::run(output),
-        Commands::Restore { path } => cli::restore::run(&path),
+        Commands::Backup {
+            command,
+            output,
+            to,
+            encrypt,
+            local_only,
+        } => match command {
+            Some(BackupCommands::Verify { name, to }) => cli::backup::verify(name, to),
+            None => cli::backup::run(output, to, encrypt, local_only),
+        },
+        Commands::Restore {
+            path,
+            merge,
+            overwrite_changed,
+            dry_run,
+        } => {
+            if merge {
+                cli::restore::merge(&path, overwrite_changed, dry_run)
+            } else {
+                cli::restore::run(&path)
+            }
+        }
         Commands::Undo => cli::undo::run(),
         Commands::Update => cli::update::run(),
-        Commands::Status => cli::status::run(),
+        Commands::Status { badge: false } => cli::status::run(),
+        Commands::Status { badge: true } => cli::status::badge(),
         Commands::Password { command } => match command {
             PasswordCommand::Set => cli::password::run_set(),
             PasswordCommand::Change => cli::password::run_change(),
             PasswordCommand::Remove => cli::password::run_remove(),
         },
+        Commands::Rates { command } => match command {
+            RatesCommands::List => cli::rates::list(),
+            RatesCommands::Set {
+                currency,
+                rate,
+                date,
+            } => cli::rates::set(&currency, rate, date.as_deref()),
+            RatesCommands::Delete { currency, date } => cli::rates::delete(&currency, &date),
+            RatesCommands::Home { currency } => cli::rates::home(currency.as_deref()),
+        },
+        Commands::Mileage { command } => match command {
+            MileageCommands::Add {
+                date,
+                miles,
+                purpose,
+            } => cli::mileage::add(&date, miles, &purpose),
+            MileageCommands::List { year } => cli::mileage::list(year),
+            MileageCommands::Delete { id } => cli::mileage::delete(id),
+        },
+        Commands::Archive { command } => match command {
+            ArchiveCommands::Create { year } => cli::archive::create(year),
+            ArchiveCommands::List => cli::archive::list(),
+            ArchiveCommands::Restore { year } => cli::archive::restore(year),
+        },
+        Commands::Schedule { command } => match command {
+            ScheduleCommands::Add {
+                report,
+                cadence,
+                format,
+                output_dir,
+            } => cli::schedule::add(&report, &cadence, &format, &output_dir),
+            ScheduleCommands::List => cli::schedule::list(),
+            ScheduleCommands::Remove { id } => cli::schedule::remove(id),
+            ScheduleCommands::Run => cli::schedule::run(),
+        },
+        Commands::Alerts { command } => match command {
+            AlertsCommands::Add {
+                vendor,
+                over,
+                increase,
+            } => cli::alerts::add(&vendor, over, increase),
+            AlertsCommands::List => cli::alerts::list(),
+            AlertsCommands::Remove { id } => cli::alerts::remove(id),
+        },
+        Commands::Limits { command } => match command {
+            LimitsCommands::Add {
+                category,
+                per_person,
+                note,
+            } => cli::limits::add(&category, per_person, note.as_deref()),
+            LimitsCommands::List { year } => cli::limits::list(year),
+            LimitsCommands::Remove { id } => cli::limits::remove(id),
+        },
+        Commands::Payplan { command } => match command {
+            PayplanCommands::Show { month } => cli::payplan::show(month),
+            PayplanCommands::Set {
+                salary,
+                tax_rate,
+                reserve,
+            } => cli::payplan::set(salary, tax_rate, reserve),
+            PayplanCommands::Plan {
+                amount,
+                month,
+                clear,
+            } => cli::payplan::plan(amount, month, clear),
+            PayplanCommands::History { year } => cli::payplan::history(year),
+        },
+        Commands::Reimbursable { command } => match command {
+            ReimbursableCommands::Mark { ids, client } => cli::reimbursables::mark(&ids, &client),
+            ReimbursableCommands::Unmark { ids } => cli::reimbursables::unmark(&ids),
+            ReimbursableCommands::Invoice {
+                ids,
+                client,
+                reference,
+                date,
+            } => cli::reimbursables::invoice(ids, client, reference, date),
+            ReimbursableCommands::Paid { ids, client, date } => {
+                cli::reimbursables::paid(ids, client, date)
+            }
+        },
+        Commands::Clients { command } => match command {
+            ClientsCommands::List => cli::clients::list(),
+            ClientsCommands::Add { name } => cli::clients::add(&name),
+            ClientsCommands::Remove { name } => cli::clients::remove(&name),
+            ClientsCommands::Assign { ids, client } => cli::clients::assign(&ids, Some(&client)),
+            ClientsCommands::Unassign { ids } => cli::clients::assign(&ids, None),
+        },
+        Commands::Shareholders { command } => match command {
+            ShareholdersCommands::List => cli::shareholders::list(),
+            ShareholdersCommands::Add { name, pct } => cli::shareholders::add(&name, pct),
+            ShareholdersCommands::Set { name, pct } => cli::shareholders::set(&name, pct),
+            ShareholdersCommands::Remove { name } => cli::shareholders::remove(&name),
+        },
+        Commands::Close {
+            month,
+            format,
+            list,
+        } => {
+            if list {
+                cli::close::list()
+            } else {
+                cli::close::run(month, format)
+            }
+        }
+        Commands::Signoff { year, list } => {
+            if list {
+                cli::signoff::list(year)
+            } else {
+                cli::signoff::run(year)
+            }
+        }
+        Commands::Trash { command } => match command {
+            TrashCommands::List => cli::trash::list(),
+            TrashCommands::Restore { ids } => cli::trash::restore(&ids),
+            TrashCommands::Empty => cli::trash::empty(),
+        },
+        Commands::Digest {
+            month,
+            format,
+            output,
+        } => cli::digest::run(month, &format, output),
+        Commands::Config { command } => match command {
+            ConfigCommands::List => cli::config::list(),
+            ConfigCommands::Get { key } => cli::config::get(&key),
+            ConfigCommands::Set { key, value } => cli::config::set(&key, &value),
+            ConfigCommands::Unset { key } => cli::config::unset(&key),
+        },
+        Commands::Db { command } => match command {
+            DbCommands::Schema { format } => cli::db::schema(&format),
+            DbCommands::Maintain { if_due } => cli::db::maintain(if_due),
+        },
+        Commands::Export { command } => match command {
+            ExportCommands::Package { year, output } => cli::package::run(year, output),
+            ExportCommands::SetupDocs { format, output } => cli::setup_docs::run(&format, output),
+        },
+        Commands::Query {
+            sql,
+            format,
+            allow_writes,
+            save,
+            list,
+            forget,
+        } => match (sql, save, forget) {
+            _ if list => cli::query::list(),
+            (_, _, Some(name)) => cli::query::forget(&name),
+            (Some(sql), Some(name), _) => cli::query::save(&name, &sql),
+            (Some(sql), None, _) => cli::query::run(&sql, &format, allow_writes),
+            (None, ..) => Ok(()),
+        },
+        #[cfg(feature = "sync")]
+        Commands::Sync { command } => match command {
+            SyncCommands::Setup { token } => cli::sync::setup(token.as_deref()),
+            SyncCommands::Accounts => cli::sync::accounts(),
+            SyncCommands::Map { id, account, unmap } => {
+                cli::sync::map(id, account.as_deref(), unmap)
+            }
+            SyncCommands::Run { dry_run, full } => cli::sync::run(dry_run, full),
+        },
         Commands::Completions { shell } => {
             clap_complete::generate(
                 shell,
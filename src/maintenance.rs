@@ -0,0 +1,150 @@
+use std::path::Path;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+
+use crate::cli::backup::{prune_all_snapshots, prune_backups};
+use crate::db::{get_metadata, set_metadata};
+use crate::error::Result;
+
+/// Default for the `backup_keep` setting.
+pub const BACKUP_KEEP: usize = 30;
+
+/// Default for the `maintenance_days` setting.
+pub const MAINTENANCE_DAYS: i64 = 30;
+
+const LAST_RUN_KEY: &str = "last_maintenance_at";
+
+/// What one maintenance pass did.
+pub struct MaintenanceReport {
+    /// Database and write-ahead log, in bytes, before and after.
+    pub size_before: u64,
+    pub size_after: u64,
+    pub snapshots_pruned: usize,
+    pub backups_pruned: usize,
+}
+
+impl MaintenanceReport {
+    /// Bytes given back to the disk (zero if the file grew).
+    pub fn reclaimed(&self) -> u64 {
+        self.size_before.saturating_sub(self.size_after)
+    }
+}
+
+/// Size of the database file plus its write-ahead log.
+pub fn db_size(db_path: &Path) -> u64 {
+    let wal = db_path.with_extension("db-wal");
+    [db_path, wal.as_path()]
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Rebuild the indexes, merge the full-text index's segments, refresh the
+/// query planner's statistics, compact the file, and prune `<data_dir>/snapshots/` to `snapshot_keep` per operation
+/// and `<data_dir>/backups/` to the newest `backup_keep`. Records when it
+/// ran for `is_due()`.
+pub fn maintain(
+    conn: &Connection,
+    db_path: &Path,
+    data_dir: &Path,
+    snapshot_keep: usize,
+    backup_keep: usize,
+) -> Result<MaintenanceReport> {
+    let size_before = db_size(db_path);
+    crate::search::optimize(conn)?;
+    conn.execute_batch("REINDEX; ANALYZE; VACUUM;")?;
+    // Fold the vacuumed pages back into the main file so the size is real
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+
+    let snapshots = data_dir.join("snapshots");
+    let snapshots_pruned = if snapshots.is_dir() {
+        prune_all_snapshots(&snapshots, snapshot_keep)?
+    } else {
+        0
+    };
+    let backups = data_dir.join("backups");
+    let backups_pruned = if backups.is_dir() {
+        prune_backups(&backups, backup_keep)?
+    } else {
+        0
+    };
+
+    let now = chrono::Local::now().naive_local();
+    set_metadata(
+        conn,
+        LAST_RUN_KEY,
+        &now.format("%Y-%m-%d %H:%M:%S").to_string(),
+    )?;
+    Ok(MaintenanceReport {
+        size_before,
+        size_after: db_size(db_path),
+        snapshots_pruned,
+        backups_pruned,
+    })
+}
+
+/// When maintenance last ran on this book.
+pub fn last_run(conn: &Connection) -> Option<NaiveDateTime> {
+    get_metadata(conn, LAST_RUN_KEY)
+        .and_then(|v| NaiveDateTime::parse_from_str(&v, "%Y-%m-%d %H:%M:%S").ok())
+}
+
+/// Whether scheduled maintenance every `days` days should run on `today`.
+/// Never when `days` is 0.
+pub fn is_due(conn: &Connection, days: i64, today: NaiveDate) -> bool {
+    days > 0 && last_run(conn).is_none_or(|last| (today - last.date()).num_days() >= days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    #[test]
+    fn maintain_compacts_prunes_and_records_the_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("nigel.db");
+        let conn = get_connection(&db_path).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        for i in 0..2000 {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount) \
+                 VALUES (1, '2025-01-01', ?1, -1.0)",
+                [format!("PADDING {i:0>200}")],
+            )
+            .unwrap();
+        }
+        conn.execute("DELETE FROM transactions", []).unwrap();
+
+        let snapshots = dir.path().join("snapshots");
+        let backups = dir.path().join("backups");
+        std::fs::create_dir_all(&snapshots).unwrap();
+        std::fs::create_dir_all(&backups).unwrap();
+        for stamp in ["20250101-090000", "20250102-090000", "20250103-090000"] {
+            std::fs::write(snapshots.join(format!("pre-import-{stamp}.db")), b"").unwrap();
+            std::fs::write(snapshots.join(format!("pre-bulk-edit-{stamp}.db")), b"").unwrap();
+            std::fs::write(backups.join(format!("nigel-{stamp}.db")), b"").unwrap();
+        }
+
+        let today = chrono::Local::now().date_naive();
+        assert!(is_due(&conn, 30, today));
+        let report = maintain(&conn, &db_path, dir.path(), 2, 1).unwrap();
+        assert!(report.size_after < report.size_before);
+        assert_eq!(report.snapshots_pruned, 2);
+        assert_eq!(report.backups_pruned, 2);
+        assert!(backups.join("nigel-20250103-090000.db").exists());
+        assert!(snapshots.join("pre-bulk-edit-20250102-090000.db").exists());
+        assert!(!snapshots.join("pre-bulk-edit-20250101-090000.db").exists());
+
+        assert!(!is_due(&conn, 30, today));
+        assert!(is_due(&conn, 30, today + chrono::Days::new(30)));
+        assert!(!is_due(&conn, 0, today + chrono::Days::new(365)));
+    }
+}
@@ -0,0 +1,687 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+use crate::error::Result;
+
+/// How rows of one table are told apart across two copies of a book. Row IDs
+/// can't be trusted for that — SQLite reuses the highest ones after a delete —
+/// so rows are matched on `key` columns, with foreign keys first mapped from
+/// the backup's IDs to the book's.
+struct TableSpec {
+    name: &'static str,
+    key: &'static [&'static str],
+    /// Foreign-key columns and the table they point at.
+    refs: &'static [(&'static str, &'static str)],
+    /// Bookkeeping columns that don't count as a change.
+    ignore: &'static [&'static str],
+}
+
+/// Tables merged, parents before the tables that point at them. Sync
+/// connections, archives, month closes, and arcade scores are left out: they
+/// describe the machine or files beside the book rather than the books
+/// themselves.
+const TABLES: &[TableSpec] = &[
+    TableSpec {
+        name: "accounts",
+        key: &["name"],
+        refs: &[],
+        ignore: &["last_import_dir"],
+    },
+    TableSpec {
+        name: "categories",
+        key: &["name"],
+        refs: &[("parent_id", "categories")],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "clients",
+        key: &["name"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "shareholders",
+        key: &["name"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "tags",
+        key: &["name"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "imports",
+        key: &["filename", "account_id", "import_date"],
+        refs: &[("account_id", "accounts")],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "transactions",
+        key: &["date", "description", "amount", "account_id"],
+        refs: &[
+            ("account_id", "accounts"),
+            ("category_id", "categories"),
+            ("import_id", "imports"),
+            ("client_id", "clients"),
+        ],
+        ignore: &["anomaly_checked"],
+    },
+    TableSpec {
+        name: "transaction_tags",
+        key: &["transaction_id", "tag_id"],
+        refs: &[("transaction_id", "transactions"), ("tag_id", "tags")],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "reimbursables",
+        key: &["transaction_id"],
+        refs: &[("transaction_id", "transactions")],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "rules",
+        key: &["pattern", "match_type", "category_id"],
+        refs: &[("category_id", "categories")],
+        ignore: &["hit_count"],
+    },
+    TableSpec {
+        name: "category_signoffs",
+        key: &["year", "category_id"],
+        refs: &[("category_id", "categories")],
+        ignore: &["signed_at"],
+    },
+    TableSpec {
+        name: "reconciliations",
+        key: &["account_id", "month"],
+        refs: &[("account_id", "accounts")],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "balance_assertions",
+        key: &["account_id", "date"],
+        refs: &[("account_id", "accounts")],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "mileage",
+        key: &["date", "miles", "purpose"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "exchange_rates",
+        key: &["currency", "date"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "distribution_plans",
+        key: &["month"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "vendor_alerts",
+        key: &["vendor"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "csv_profiles",
+        key: &["name"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "report_schedules",
+        key: &["report", "cadence", "format", "output_dir"],
+        refs: &[],
+        ignore: &["next_run", "last_run_at", "last_result", "last_ok"],
+    },
+    TableSpec {
+        name: "metadata",
+        key: &["key"],
+        refs: &[],
+        ignore: &[],
+    },
+    TableSpec {
+        name: "audit_log",
+        key: &["action", "detail", "created_at"],
+        refs: &[],
+        ignore: &[],
+    },
+];
+
+/// A row present in both books whose other columns differ.
+#[derive(Debug)]
+pub struct ChangedRow {
+    pub label: String,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct TableReport {
+    pub table: &'static str,
+    /// Rows the backup has and the book doesn't, restored.
+    pub missing: Vec<String>,
+    /// Rows in both that differ; overwritten only with `overwrite_changed`.
+    pub changed: Vec<ChangedRow>,
+    pub unchanged: usize,
+    /// Missing rows that couldn't be restored, with the reason.
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    /// Tables with anything missing, changed, or skipped.
+    pub tables: Vec<TableReport>,
+}
+
+impl MergeReport {
+    pub fn missing(&self) -> usize {
+        self.tables.iter().map(|t| t.missing.len()).sum()
+    }
+
+    pub fn changed(&self) -> usize {
+        self.tables.iter().map(|t| t.changed.len()).sum()
+    }
+}
+
+/// Rows already handled, per table: backup row ID to the book's row ID.
+#[derive(Default)]
+struct Mapping {
+    ids: HashMap<&'static str, HashMap<i64, i64>>,
+    labels: HashMap<&'static str, HashMap<i64, String>>,
+    /// Backup rows that weren't restored; rows pointing at them are dropped
+    /// quietly, since the parent's own entry explains why.
+    dropped: HashMap<&'static str, HashSet<i64>>,
+}
+
+/// Restore into `conn` the rows `backup` has that the book lacks, in one
+/// transaction; `dry_run` rolls it back so the report is a preview. Rows
+/// only the book has are kept, and rows in both that differ are listed
+/// but left alone unless `overwrite_changed`.
+pub fn merge(
+    conn: &Connection,
+    backup: &Connection,
+    overwrite_changed: bool,
+    dry_run: bool,
+) -> Result<MergeReport> {
+    let tx = conn.unchecked_transaction()?;
+    let archived = archived_ranges(conn)?;
+    let mut mapping = Mapping::default();
+    let mut report = MergeReport::default();
+    for spec in TABLES {
+        let Some(columns) = shared_columns(conn, backup, spec)? else {
+            continue;
+        };
+        let table = merge_table(
+            conn,
+            backup,
+            spec,
+            &columns,
+            &archived,
+            overwrite_changed,
+            &mut mapping,
+        )?;
+        if !table.missing.is_empty() || !table.changed.is_empty() || !table.skipped.is_empty() {
+            report.tables.push(table);
+        }
+    }
+    if dry_run {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+    Ok(report)
+}
+
+/// Columns both copies have, without `id` — restored rows get new IDs. An
+/// older backup lacks newer columns, which then keep the book's defaults.
+fn shared_columns(
+    conn: &Connection,
+    backup: &Connection,
+    spec: &TableSpec,
+) -> Result<Option<Vec<String>>> {
+    let live = table_columns(conn, spec.name)?;
+    let theirs = table_columns(backup, spec.name)?;
+    let columns: Vec<String> = live
+        .into_iter()
+        .filter(|c| c != "id" && theirs.contains(c))
+        .collect();
+    let has_key = spec.key.iter().all(|k| columns.iter().any(|c| c == k));
+    Ok(has_key.then_some(columns))
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1) ORDER BY cid")?;
+    let columns = stmt
+        .query_map([table], |r| r.get(0))?
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(columns)
+}
+
+fn read_rows(conn: &Connection, table: &str, columns: &[String]) -> Result<Vec<(i64, Vec<Value>)>> {
+    let sql = format!(
+        "SELECT rowid, {} FROM {table} ORDER BY rowid",
+        columns.join(", ")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([], |r| {
+            let values = (1..=columns.len())
+                .map(|i| r.get::<_, Value>(i))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok((r.get(0)?, values))
+        })?
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(rows)
+}
+
+/// Date ranges of archived years: their transactions live in the archive
+/// files, so restoring them into the book would count them twice.
+fn archived_ranges(conn: &Connection) -> Result<Vec<(i32, String, String)>> {
+    let mut stmt = conn.prepare("SELECT year, start_date, end_date FROM archives")?;
+    let ranges = stmt
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(ranges)
+}
+
+fn merge_table(
+    conn: &Connection,
+    backup: &Connection,
+    spec: &TableSpec,
+    columns: &[String],
+    archived: &[(i32, String, String)],
+    overwrite_changed: bool,
+    mapping: &mut Mapping,
+) -> Result<TableReport> {
+    let index = |name: &str| columns.iter().position(|c| c == name);
+    let key_idx: Vec<usize> = spec.key.iter().filter_map(|k| index(k)).collect();
+    let refs: Vec<(usize, &'static str)> = spec
+        .refs
+        .iter()
+        .filter_map(|(col, target)| Some((index(col)?, *target)))
+        .collect();
+    let compared: Vec<usize> = (0..columns.len())
+        .filter(|i| {
+            let name = columns[*i].as_str();
+            !key_idx.contains(i) && name != "created_at" && !spec.ignore.contains(&name)
+        })
+        .collect();
+
+    let mut live: HashMap<String, VecDeque<(i64, Vec<Value>)>> = HashMap::new();
+    for (id, values) in read_rows(conn, spec.name, columns)? {
+        live.entry(key_of(&values, &key_idx))
+            .or_default()
+            .push_back((id, values));
+    }
+
+    let mut rows = read_rows(backup, spec.name, columns)?;
+    if let Some((self_ref, _)) = refs.iter().find(|(_, target)| *target == spec.name) {
+        // Parents go first so children can point at them
+        let parents: HashMap<i64, Value> = rows
+            .iter()
+            .map(|(id, values)| (*id, values[*self_ref].clone()))
+            .collect();
+        let depth = |mut id: i64| {
+            let mut depth = 0;
+            while let Some(Value::Integer(parent)) = parents.get(&id) {
+                depth += 1;
+                id = *parent;
+                if depth > parents.len() {
+                    break;
+                }
+            }
+            depth
+        };
+        rows.sort_by_key(|(id, _)| (depth(*id), *id));
+    }
+
+    let mut report = TableReport {
+        table: spec.name,
+        missing: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+        skipped: Vec::new(),
+    };
+    let insert_sql = format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        spec.name,
+        columns.join(", "),
+        (1..=columns.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    'rows: for (backup_id, mut values) in rows {
+        if spec.name == "metadata"
+            && matches!(&values[key_idx[0]], Value::Text(k) if k == "schema_version")
+        {
+            continue;
+        }
+        let label = label_of(spec, columns, &values, &key_idx, mapping);
+        for (i, target) in &refs {
+            let Value::Integer(parent) = values[*i] else {
+                continue;
+            };
+            match mapping.ids.get(target).and_then(|m| m.get(&parent)) {
+                Some(id) => values[*i] = Value::Integer(*id),
+                None => {
+                    if !mapping
+                        .dropped
+                        .get(target)
+                        .is_some_and(|d| d.contains(&parent))
+                    {
+                        report.skipped.push(format!(
+                            "{label} — points at a {target} row that couldn't be matched"
+                        ));
+                    }
+                    mapping
+                        .dropped
+                        .entry(spec.name)
+                        .or_default()
+                        .insert(backup_id);
+                    continue 'rows;
+                }
+            }
+        }
+        mapping
+            .labels
+            .entry(spec.name)
+            .or_default()
+            .insert(backup_id, label.clone());
+
+        let key = key_of(&values, &key_idx);
+        if let Some((live_id, live_values)) = live.get_mut(&key).and_then(|q| q.pop_front()) {
+            mapping
+                .ids
+                .entry(spec.name)
+                .or_default()
+                .insert(backup_id, live_id);
+            let differing: Vec<usize> = compared
+                .iter()
+                .copied()
+                .filter(|i| values[*i] != live_values[*i])
+                .collect();
+            if differing.is_empty() {
+                report.unchanged += 1;
+                continue;
+            }
+            if overwrite_changed {
+                let sets: Vec<String> = differing
+                    .iter()
+                    .enumerate()
+                    .map(|(n, i)| format!("{} = ?{}", columns[*i], n + 1))
+                    .collect();
+                let mut params: Vec<Value> = differing.iter().map(|i| values[*i].clone()).collect();
+                params.push(Value::Integer(live_id));
+                conn.execute(
+                    &format!(
+                        "UPDATE {} SET {} WHERE rowid = ?{}",
+                        spec.name,
+                        sets.join(", "),
+                        params.len()
+                    ),
+                    rusqlite::params_from_iter(params),
+                )?;
+            }
+            report.changed.push(ChangedRow {
+                label,
+                columns: differing.iter().map(|i| columns[*i].clone()).collect(),
+            });
+            continue;
+        }
+
+        if spec.name == "transactions" {
+            if let Some(Value::Text(date)) = index("date").map(|i| &values[i]) {
+                if let Some((year, ..)) = archived
+                    .iter()
+                    .find(|(_, start, end)| start <= date && date <= end)
+                {
+                    report
+                        .skipped
+                        .push(format!("{label} — FY {year} is archived"));
+                    mapping
+                        .dropped
+                        .entry(spec.name)
+                        .or_default()
+                        .insert(backup_id);
+                    continue;
+                }
+            }
+        }
+        match conn.execute(&insert_sql, rusqlite::params_from_iter(&values)) {
+            Ok(_) => {
+                mapping
+                    .ids
+                    .entry(spec.name)
+                    .or_default()
+                    .insert(backup_id, conn.last_insert_rowid());
+                report.missing.push(label);
+            }
+            // A unique name taken by a different row, say
+            Err(rusqlite::Error::SqliteFailure(err, msg))
+                if err.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                let reason = msg.unwrap_or_else(|| err.to_string());
+                report.skipped.push(format!("{label} — {reason}"));
+                mapping
+                    .dropped
+                    .entry(spec.name)
+                    .or_default()
+                    .insert(backup_id);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(report)
+}
+
+fn key_of(values: &[Value], key_idx: &[usize]) -> String {
+    key_idx
+        .iter()
+        .map(|i| format!("{:?}", values[*i]))
+        .collect::<Vec<_>>()
+        .join("\u{1f}")
+}
+
+/// The row's key columns for display, with foreign keys shown as the label
+/// of the row they point at.
+fn label_of(
+    spec: &TableSpec,
+    columns: &[String],
+    values: &[Value],
+    key_idx: &[usize],
+    mapping: &Mapping,
+) -> String {
+    key_idx
+        .iter()
+        .map(|i| {
+            let target = spec
+                .refs
+                .iter()
+                .find(|(col, _)| *col == columns[*i])
+                .map(|(_, target)| *target);
+            match (&values[*i], target) {
+                (Value::Integer(id), Some(target)) => mapping
+                    .labels
+                    .get(target)
+                    .and_then(|l| l.get(id))
+                    .cloned()
+                    .unwrap_or_else(|| format!("{target} #{id}")),
+                (Value::Text(s), _) => s.clone(),
+                (Value::Integer(n), None) => n.to_string(),
+                (Value::Real(n), None) => format!("{n:.2}"),
+                (Value::Null, _) => "\u{2014}".into(),
+                (Value::Blob(_), _) => "(blob)".into(),
+                (Value::Real(n), Some(_)) => n.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    fn add_txn(conn: &Connection, date: &str, description: &str, amount: f64) -> i64 {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, ?1, ?2, ?3, (SELECT id FROM categories WHERE name = 'Meals'))",
+            rusqlite::params![date, description, amount],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    fn descriptions(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare("SELECT description FROM transactions ORDER BY date, id")
+            .unwrap();
+        stmt.query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap()
+    }
+
+    /// A backup with three transactions, and a book where the last two were
+    /// lost, the first recategorized, and a new one imported since — reusing
+    /// the lost rows' IDs.
+    fn diverged() -> (tempfile::TempDir, Connection, tempfile::TempDir, Connection) {
+        let (backup_dir, backup) = test_db();
+        let (live_dir, live) = test_db();
+        for conn in [&backup, &live] {
+            conn.execute(
+                "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+                [],
+            )
+            .unwrap();
+        }
+        for conn in [&backup, &live] {
+            add_txn(conn, "2025-03-01", "LUNCH", -20.0);
+        }
+        let tagged = add_txn(&backup, "2025-03-02", "DINNER", -40.0);
+        add_txn(&backup, "2025-03-03", "BREAKFAST", -10.0);
+        crate::tags::set_transaction_tags(&backup, tagged, &["travel".into()]).unwrap();
+        live.execute(
+            "UPDATE transactions SET category_id = (SELECT id FROM categories WHERE name = 'Travel')",
+            [],
+        )
+        .unwrap();
+        add_txn(&live, "2025-04-01", "NEW WORK", -5.0);
+        (backup_dir, backup, live_dir, live)
+    }
+
+    #[test]
+    fn merge_restores_missing_rows_and_keeps_new_work() {
+        let (_b, backup, _l, live) = diverged();
+        let report = merge(&live, &backup, false, false).unwrap();
+        assert_eq!(report.missing(), 4, "{report:?}"); // two transactions, a tag, its link
+        assert_eq!(
+            descriptions(&live),
+            ["LUNCH", "DINNER", "BREAKFAST", "NEW WORK"]
+        );
+        // The tag follows its transaction
+        let tag: String = live
+            .query_row(
+                "SELECT g.name FROM transaction_tags tt JOIN tags g ON g.id = tt.tag_id \
+                 JOIN transactions t ON t.id = tt.transaction_id WHERE t.description = 'DINNER'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(tag, "travel");
+
+        // The recategorized row is reported, not reverted
+        let txns = report
+            .tables
+            .iter()
+            .find(|t| t.table == "transactions")
+            .unwrap();
+        assert_eq!(txns.changed.len(), 1);
+        assert_eq!(txns.changed[0].columns, ["category_id"]);
+        assert!(txns.changed[0]
+            .label
+            .starts_with("2025-03-01 · LUNCH · -20.00"));
+        let category: String = live
+            .query_row(
+                "SELECT c.name FROM transactions t JOIN categories c ON c.id = t.category_id \
+                 WHERE t.description = 'LUNCH'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(category, "Travel");
+
+        // Running it again finds nothing left to restore
+        assert_eq!(merge(&live, &backup, false, false).unwrap().missing(), 0);
+    }
+
+    #[test]
+    fn dry_run_previews_and_overwrite_takes_the_backup() {
+        let (_b, backup, _l, live) = diverged();
+        let preview = merge(&live, &backup, true, true).unwrap();
+        assert_eq!(preview.missing(), 4);
+        assert_eq!(preview.changed(), 1);
+        assert_eq!(descriptions(&live), ["LUNCH", "NEW WORK"]);
+
+        merge(&live, &backup, true, false).unwrap();
+        let category: String = live
+            .query_row(
+                "SELECT c.name FROM transactions t JOIN categories c ON c.id = t.category_id \
+                 WHERE t.description = 'LUNCH'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(category, "Meals");
+    }
+
+    #[test]
+    fn conflicts_and_archived_years_are_skipped() {
+        let (_b, backup, _l, live) = diverged();
+        // A client renamed only in case collides with the book's unique name
+        backup
+            .execute("INSERT INTO clients (name) VALUES ('ACME')", [])
+            .unwrap();
+        backup
+            .execute(
+                "UPDATE transactions SET client_id = 1 WHERE description = 'BREAKFAST'",
+                [],
+            )
+            .unwrap();
+        live.execute("INSERT INTO clients (name) VALUES ('Acme')", [])
+            .unwrap();
+        live.execute(
+            "INSERT INTO archives (year, filename, start_date, end_date, transaction_count) \
+             VALUES (2024, 'nigel-2024.db', '2024-01-01', '2024-12-31', 0)",
+            [],
+        )
+        .unwrap();
+        add_txn(&backup, "2024-06-01", "ARCHIVED", -1.0);
+
+        let report = merge(&live, &backup, false, false).unwrap();
+        let skipped: Vec<&String> = report.tables.iter().flat_map(|t| &t.skipped).collect();
+        assert_eq!(skipped.len(), 2, "{skipped:?}");
+        assert!(skipped[0].starts_with("ACME — UNIQUE constraint failed"));
+        assert!(skipped[1].ends_with("FY 2024 is archived"));
+        // BREAKFAST pointed at the conflicting client, so it stays out quietly
+        assert_eq!(descriptions(&live), ["LUNCH", "DINNER", "NEW WORK"]);
+    }
+}
@@ -33,6 +33,474 @@ const MIGRATIONS: &[Migration] = &[
             Ok(())
         },
     },
+    Migration {
+        version: 3,
+        description: "add currency to accounts/transactions and exchange_rates table",
+        up: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE accounts ADD COLUMN currency TEXT NOT NULL DEFAULT 'USD';
+                ALTER TABLE transactions ADD COLUMN currency TEXT;
+                CREATE TABLE exchange_rates (
+                    id INTEGER PRIMARY KEY,
+                    currency TEXT NOT NULL,
+                    date TEXT NOT NULL,
+                    rate REAL NOT NULL,
+                    UNIQUE (currency, date)
+                )",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 4,
+        description: "add account_number and routing_number to accounts",
+        up: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE accounts ADD COLUMN account_number TEXT;
+                ALTER TABLE accounts ADD COLUMN routing_number TEXT;",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 5,
+        description: "add stored_path to imports for retained statement files",
+        up: |conn| {
+            conn.execute_batch("ALTER TABLE imports ADD COLUMN stored_path TEXT;")?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 6,
+        description: "add mileage table for standard mileage deductions",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE mileage (
+                    id INTEGER PRIMARY KEY,
+                    date TEXT NOT NULL,
+                    miles REAL NOT NULL,
+                    purpose TEXT NOT NULL,
+                    created_at TEXT DEFAULT (datetime('now'))
+                )",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 7,
+        description: "add Credit Card Payment transfer category",
+        up: |conn| {
+            conn.execute(
+                "INSERT INTO categories (name, category_type, tax_line, description) \
+                 SELECT 'Credit Card Payment', 'transfer', 'Not deductible', \
+                        'Paying a credit card from a bank account' \
+                 WHERE NOT EXISTS (SELECT 1 FROM categories WHERE name = 'Credit Card Payment')",
+                [],
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 8,
+        description: "add Interest Expense and Loan Principal categories",
+        up: |conn| {
+            conn.execute(
+                "INSERT INTO categories (name, category_type, tax_line, form_line, description) \
+                 SELECT 'Interest Expense', 'expense', 'Line 16b', '1120S-13', \
+                        'Interest and finance charges on loans and lines of credit' \
+                 WHERE NOT EXISTS (SELECT 1 FROM categories WHERE name = 'Interest Expense')",
+                [],
+            )?;
+            conn.execute(
+                "INSERT INTO categories (name, category_type, tax_line, description) \
+                 SELECT 'Loan Principal', 'transfer', 'Not deductible', \
+                        'Line of credit draws and principal payments' \
+                 WHERE NOT EXISTS (SELECT 1 FROM categories WHERE name = 'Loan Principal')",
+                [],
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 9,
+        description: "add memo to transactions",
+        up: |conn| {
+            conn.execute_batch("ALTER TABLE transactions ADD COLUMN memo TEXT;")?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 10,
+        description: "add sync_connections and sync_accounts tables for bank sync",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE sync_connections (
+                    id INTEGER PRIMARY KEY,
+                    provider TEXT NOT NULL,
+                    access_url TEXT NOT NULL,
+                    created_at TEXT DEFAULT (datetime('now'))
+                );
+                CREATE TABLE sync_accounts (
+                    id INTEGER PRIMARY KEY,
+                    connection_id INTEGER NOT NULL,
+                    external_id TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    institution TEXT,
+                    account_id INTEGER,
+                    cursor INTEGER,
+                    last_synced_at TEXT,
+                    UNIQUE (connection_id, external_id),
+                    FOREIGN KEY (connection_id) REFERENCES sync_connections(id),
+                    FOREIGN KEY (account_id) REFERENCES accounts(id)
+                )",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 11,
+        description: "add tags and transaction_tags tables",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE tags (
+                    id INTEGER PRIMARY KEY,
+                    name TEXT NOT NULL UNIQUE
+                );
+                CREATE TABLE transaction_tags (
+                    transaction_id INTEGER NOT NULL,
+                    tag_id INTEGER NOT NULL,
+                    PRIMARY KEY (transaction_id, tag_id),
+                    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE,
+                    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+                );
+                CREATE INDEX idx_transaction_tags_tag ON transaction_tags(tag_id);",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 12,
+        description: "add archives table for per-year archive databases",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE archives (
+                    year INTEGER PRIMARY KEY,
+                    filename TEXT NOT NULL,
+                    start_date TEXT NOT NULL,
+                    end_date TEXT NOT NULL,
+                    transaction_count INTEGER NOT NULL,
+                    archived_at TEXT DEFAULT (datetime('now'))
+                )",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 13,
+        description: "add follow_up and resolved_at to reconciliations for discrepancy notes",
+        up: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE reconciliations ADD COLUMN follow_up TEXT;
+                 ALTER TABLE reconciliations ADD COLUMN resolved_at TEXT;",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 14,
+        description: "add report_schedules table for scheduled report exports",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE report_schedules (
+                    id INTEGER PRIMARY KEY,
+                    report TEXT NOT NULL,
+                    cadence TEXT NOT NULL,
+                    format TEXT NOT NULL,
+                    output_dir TEXT NOT NULL,
+                    next_run TEXT NOT NULL,
+                    last_run_at TEXT,
+                    last_result TEXT,
+                    last_ok INTEGER,
+                    created_at TEXT DEFAULT (datetime('now'))
+                )",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 15,
+        description: "add sign_ok to transactions for reviewed sign mismatches",
+        up: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE transactions ADD COLUMN sign_ok INTEGER NOT NULL DEFAULT 0",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 16,
+        description: "add vendor_alerts for per-vendor monthly spend alerts",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS vendor_alerts (
+                    id INTEGER PRIMARY KEY,
+                    vendor TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                    monthly_limit REAL,
+                    increase_pct REAL,
+                    created_at TEXT DEFAULT (datetime('now'))
+                )",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 17,
+        description: "add is_archived to accounts for closed accounts",
+        up: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE accounts ADD COLUMN is_archived INTEGER NOT NULL DEFAULT 0",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 18,
+        description: "add high_scores for the arcade",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS high_scores (
+                    id INTEGER PRIMARY KEY,
+                    game TEXT NOT NULL,
+                    score REAL NOT NULL,
+                    played_at TEXT DEFAULT (datetime('now'))
+                );
+                CREATE INDEX IF NOT EXISTS idx_high_scores_game ON high_scores(game, score);",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 19,
+        description: "add opening balances to accounts and balance_assertions",
+        up: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE accounts ADD COLUMN opening_balance REAL NOT NULL DEFAULT 0;
+                 ALTER TABLE accounts ADD COLUMN opening_date TEXT;
+                 CREATE TABLE IF NOT EXISTS balance_assertions (
+                    id INTEGER PRIMARY KEY,
+                    account_id INTEGER NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+                    date TEXT NOT NULL,
+                    balance REAL NOT NULL,
+                    created_at TEXT DEFAULT (datetime('now'))
+                 );",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 20,
+        description: "add distribution_plans for the pay-yourself planner",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS distribution_plans (
+                    id INTEGER PRIMARY KEY,
+                    month TEXT NOT NULL UNIQUE,
+                    amount REAL NOT NULL,
+                    created_at TEXT DEFAULT (datetime('now'))
+                 );",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 21,
+        description: "add reimbursables for billable expenses",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS reimbursables (
+                    transaction_id INTEGER PRIMARY KEY
+                        REFERENCES transactions(id) ON DELETE CASCADE,
+                    client TEXT NOT NULL,
+                    invoice TEXT,
+                    invoiced_on TEXT,
+                    reimbursed_on TEXT,
+                    created_at TEXT DEFAULT (datetime('now'))
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_reimbursables_client ON reimbursables(client);",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 22,
+        description: "add anomaly_checked to transactions for unusual-transaction flags",
+        up: |conn| {
+            // Existing transactions count as checked so upgrading doesn't
+            // flag years of history at once
+            conn.execute_batch(
+                "ALTER TABLE transactions ADD COLUMN anomaly_checked INTEGER NOT NULL DEFAULT 0;
+                 UPDATE transactions SET anomaly_checked = 1;",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 23,
+        description: "add clients and transactions.client_id for per-client profitability",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS clients (
+                    id INTEGER PRIMARY KEY,
+                    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                    created_at TEXT DEFAULT (datetime('now'))
+                 );
+                 ALTER TABLE transactions ADD COLUMN client_id INTEGER
+                    REFERENCES clients(id) ON DELETE SET NULL;
+                 CREATE INDEX IF NOT EXISTS idx_transactions_client ON transactions(client_id);",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 24,
+        description: "add deleted_at to transactions for the trash",
+        up: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE transactions ADD COLUMN deleted_at TEXT;
+                 CREATE INDEX IF NOT EXISTS idx_transactions_deleted ON transactions(deleted_at);",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 25,
+        description: "add closes for the month-close audit trail",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS closes (
+                    id INTEGER PRIMARY KEY,
+                    month TEXT NOT NULL,
+                    packet_path TEXT NOT NULL,
+                    accounts_reconciled INTEGER NOT NULL,
+                    transactions INTEGER NOT NULL,
+                    net REAL NOT NULL,
+                    closed_at TEXT DEFAULT (datetime('now'))
+                 );
+                 CREATE INDEX IF NOT EXISTS idx_closes_month ON closes(month);",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 26,
+        description: "add per-account import defaults",
+        up: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE accounts ADD COLUMN default_format TEXT;
+                 ALTER TABLE accounts ADD COLUMN last_import_dir TEXT;",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 27,
+        description: "add shareholders for per-owner K-1 allocations",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS shareholders (
+                    id INTEGER PRIMARY KEY,
+                    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                    ownership_pct REAL NOT NULL,
+                    created_at TEXT DEFAULT (datetime('now'))
+                 );",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 28,
+        description: "add audit_log for changes that rewrite history",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS audit_log (
+                    id INTEGER PRIMARY KEY,
+                    action TEXT NOT NULL,
+                    detail TEXT NOT NULL,
+                    created_at TEXT DEFAULT (datetime('now'))
+                 );",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 29,
+        description: "add expense_limits for per-person deduction caps",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS expense_limits (
+                    id INTEGER PRIMARY KEY,
+                    category_id INTEGER NOT NULL UNIQUE
+                        REFERENCES categories(id) ON DELETE CASCADE,
+                    per_person REAL NOT NULL,
+                    note TEXT,
+                    created_at TEXT DEFAULT (datetime('now'))
+                 );",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 30,
+        description: "add category_signoffs for the pre-filing review",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS category_signoffs (
+                    id INTEGER PRIMARY KEY,
+                    year INTEGER NOT NULL,
+                    category_id INTEGER NOT NULL
+                        REFERENCES categories(id) ON DELETE CASCADE,
+                    status TEXT NOT NULL,
+                    total REAL NOT NULL,
+                    note TEXT,
+                    signed_at TEXT NOT NULL DEFAULT (datetime('now')),
+                    UNIQUE (year, category_id)
+                 );",
+            )?;
+            Ok(())
+        },
+    },
+    Migration {
+        version: 31,
+        description: "add full-text index over transaction descriptions, vendors, and memos",
+        up: |conn| {
+            conn.execute_batch(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS transactions_fts USING fts5(
+                    description, vendor, memo,
+                    content = 'transactions', content_rowid = 'id',
+                    tokenize = 'trigram'
+                 );
+                 CREATE TRIGGER IF NOT EXISTS transactions_fts_insert
+                 AFTER INSERT ON transactions BEGIN
+                    INSERT INTO transactions_fts (rowid, description, vendor, memo)
+                    VALUES (new.id, new.description, new.vendor, new.memo);
+                 END;
+                 CREATE TRIGGER IF NOT EXISTS transactions_fts_delete
+                 AFTER DELETE ON transactions BEGIN
+                    INSERT INTO transactions_fts (transactions_fts, rowid, description, vendor, memo)
+                    VALUES ('delete', old.id, old.description, old.vendor, old.memo);
+                 END;
+                 CREATE TRIGGER IF NOT EXISTS transactions_fts_update
+                 AFTER UPDATE OF description, vendor, memo ON transactions BEGIN
+                    INSERT INTO transactions_fts (transactions_fts, rowid, description, vendor, memo)
+                    VALUES ('delete', old.id, old.description, old.vendor, old.memo);
+                    INSERT INTO transactions_fts (rowid, description, vendor, memo)
+                    VALUES (new.id, new.description, new.vendor, new.memo);
+                 END;
+                 INSERT INTO transactions_fts (transactions_fts) VALUES ('rebuild');",
+            )?;
+            Ok(())
+        },
+    },
 ];
 
 pub const LATEST_VERSION: u32 = MIGRATIONS[MIGRATIONS.len() - 1].version;
@@ -136,6 +604,37 @@ mod tests {
         assert!(exists, "csv_profiles table should exist after init_db");
     }
 
+    #[test]
+    fn test_card_payment_category_is_transfer() {
+        let (_dir, conn) = test_db();
+        let category_type: String = conn
+            .query_row(
+                "SELECT category_type FROM categories WHERE name = 'Credit Card Payment'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(category_type, "transfer");
+    }
+
+    #[test]
+    fn test_loan_categories() {
+        let (_dir, conn) = test_db();
+        let types: Vec<(String, Option<String>)> = ["Interest Expense", "Loan Principal"]
+            .iter()
+            .map(|name| {
+                conn.query_row(
+                    "SELECT category_type, form_line FROM categories WHERE name = ?1",
+                    [name],
+                    |r| Ok((r.get(0)?, r.get(1)?)),
+                )
+                .unwrap()
+            })
+            .collect();
+        assert_eq!(types[0], ("expense".into(), Some("1120S-13".into())));
+        assert_eq!(types[1], ("transfer".into(), None));
+    }
+
     #[test]
     fn test_failed_migration_rolls_back() {
         let (_dir, conn) = test_db();
@@ -6,6 +6,15 @@ pub struct Account {
     pub account_type: String,
     pub institution: Option<String>,
     pub last_four: Option<String>,
+    pub currency: String,
+    /// Closed account: hidden from pickers and the balance report.
+    pub is_archived: bool,
+    /// Balance before the first transaction on `opening_date`, in the
+    /// account's currency.
+    pub opening_balance: f64,
+    /// YYYY-MM-DD. Transactions before it are covered by the opening
+    /// balance; without one it applies from the beginning.
+    pub opening_date: Option<String>,
 }
 
 #[allow(dead_code)]
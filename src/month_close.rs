@@ -0,0 +1,486 @@
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+use crate::error::{NigelError, Result};
+use crate::fmt::money;
+use crate::reports::{self, pnl_groups, CashflowReport, PnlItem, PnlReport};
+
+/// Folder under the data directory that holds close packets.
+pub const CLOSES_DIR: &str = "closes";
+
+/// A balanced reconciliation, as it appears in the close packet.
+pub struct Certificate {
+    pub account: String,
+    pub statement_balance: f64,
+    pub calculated_balance: f64,
+    /// When it balanced, as SQLite `datetime('now')` (UTC).
+    pub reconciled_at: String,
+}
+
+/// Where a month stands against the close checklist: nothing dated in it
+/// may be flagged, and every account with activity in it must have a
+/// reconciliation that balanced.
+pub struct CloseCheck {
+    pub year: i32,
+    pub month: u32,
+    pub transactions: usize,
+    pub flagged: usize,
+    /// Accounts with transactions in the month, each with its latest
+    /// balanced reconciliation for it.
+    pub accounts: Vec<(String, Option<Certificate>)>,
+}
+
+impl CloseCheck {
+    /// The month as `YYYY-MM`.
+    pub fn period(&self) -> String {
+        format!("{:04}-{:02}", self.year, self.month)
+    }
+
+    /// What still stands in the way of closing, as instructions.
+    pub fn blockers(&self) -> Vec<String> {
+        let period = self.period();
+        let mut blockers = Vec::new();
+        if self.transactions == 0 {
+            blockers.push(format!("No transactions are dated in {period}."));
+        }
+        if self.flagged > 0 {
+            blockers.push(format!(
+                "{} flagged transaction(s) dated in {period}. Run `nigel review`.",
+                self.flagged
+            ));
+        }
+        for (account, certificate) in &self.accounts {
+            if certificate.is_none() {
+                blockers.push(format!(
+                    "{account} isn't reconciled for {period}. Run `nigel reconcile \"{account}\" --month {period} --balance <statement balance>`."
+                ));
+            }
+        }
+        blockers
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.blockers().is_empty()
+    }
+}
+
+/// Check a month against the close checklist.
+pub fn check(conn: &Connection, year: i32, month: u32) -> Result<CloseCheck> {
+    let period = format!("{year:04}-{month:02}");
+    let (transactions, flagged): (i64, i64) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(is_flagged = 1), 0) FROM transactions \
+         WHERE deleted_at IS NULL AND date LIKE ?1 || '%'",
+        [&period],
+        |r| Ok((r.get(0)?, r.get(1)?)),
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT a.name, r.statement_balance, r.calculated_balance, r.reconciled_at \
+         FROM accounts a \
+         LEFT JOIN reconciliations r ON r.id = ( \
+             SELECT MAX(id) FROM reconciliations \
+             WHERE account_id = a.id AND month = ?1 AND is_reconciled = 1) \
+         WHERE EXISTS (SELECT 1 FROM transactions t WHERE t.account_id = a.id \
+             AND t.deleted_at IS NULL AND t.date LIKE ?1 || '%') \
+         ORDER BY a.name",
+    )?;
+    let accounts = stmt
+        .query_map([&period], |row| {
+            let account: String = row.get(0)?;
+            let certificate = match row.get::<_, Option<f64>>(1)? {
+                Some(statement_balance) => Some(Certificate {
+                    account: account.clone(),
+                    statement_balance,
+                    calculated_balance: row.get(2)?,
+                    reconciled_at: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                }),
+                None => None,
+            };
+            Ok((account, certificate))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(CloseCheck {
+        year,
+        month,
+        transactions: transactions as usize,
+        flagged: flagged as usize,
+        accounts,
+    })
+}
+
+/// Everything the close packet reports for a month.
+pub struct ClosePacket {
+    pub year: i32,
+    pub month: u32,
+    pub company: String,
+    pub pnl: PnlReport,
+    pub cashflow: CashflowReport,
+    pub certificates: Vec<Certificate>,
+    pub transactions: usize,
+    pub generated_at: String,
+}
+
+impl ClosePacket {
+    /// Gather the packet for a month that passed its check; refuses one
+    /// that didn't.
+    pub fn build(conn: &Connection, check: CloseCheck) -> Result<ClosePacket> {
+        let blockers = check.blockers();
+        if !blockers.is_empty() {
+            return Err(NigelError::Other(format!(
+                "{} isn't ready to close:\n  {}",
+                check.period(),
+                blockers.join("\n  ")
+            )));
+        }
+        let (year, month) = (check.year, check.month);
+        Ok(ClosePacket {
+            year,
+            month,
+            company: crate::db::get_metadata(conn, "company_name").unwrap_or_default(),
+            pnl: reports::get_pnl(conn, Some(year), Some(month), None, None, None, &[])?,
+            cashflow: reports::get_cashflow(conn, Some(year), Some(month), &[])?,
+            certificates: check.accounts.into_iter().filter_map(|(_, c)| c).collect(),
+            transactions: check.transactions,
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        })
+    }
+
+    pub fn period(&self) -> String {
+        format!("{:04}-{:02}", self.year, self.month)
+    }
+
+    /// The month spelled out, e.g. "March 2025".
+    pub fn label(&self) -> String {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1)
+            .map(|d| d.format("%B %Y").to_string())
+            .unwrap_or_else(|| self.period())
+    }
+
+    /// The flagged-zero confirmation printed in the packet.
+    pub fn review_statement(&self) -> String {
+        format!(
+            "All {} transactions dated in {} are reviewed: none are flagged.",
+            self.transactions,
+            self.label()
+        )
+    }
+
+    /// The packet as a single self-contained HTML page.
+    pub fn to_html(&self) -> String {
+        let title = format!("Month Close \u{2014} {}", self.label());
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", html_escape(&title)));
+        html.push_str(
+            "<style>\n\
+             body { font-family: Helvetica, Arial, sans-serif; max-width: 48em; margin: 2em auto; color: #222; }\n\
+             table { border-collapse: collapse; width: 100%; margin-bottom: 1.5em; }\n\
+             th, td { padding: 0.25em 0.5em; border-bottom: 1px solid #ddd; text-align: left; }\n\
+             td.amount, th.amount { text-align: right; }\n\
+             tr.total td { font-weight: bold; }\n\
+             .meta { color: #666; }\n\
+             </style>\n</head>\n<body>\n",
+        );
+        html.push_str(&format!("<h1>{}</h1>\n", html_escape(&title)));
+        if !self.company.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", html_escape(&self.company)));
+        }
+        html.push_str(&format!(
+            "<p class=\"meta\">Generated {}</p>\n",
+            html_escape(&self.generated_at)
+        ));
+
+        html.push_str("<h2>Profit &amp; Loss</h2>\n<table>\n");
+        html.push_str("<tr><th>Category</th><th class=\"amount\">Amount</th></tr>\n");
+        pnl_html(
+            &mut html,
+            "Income",
+            &self.pnl.income,
+            self.pnl.total_income,
+            |t| t,
+        );
+        pnl_html(
+            &mut html,
+            "Expenses",
+            &self.pnl.expenses,
+            self.pnl.total_expenses.abs(),
+            f64::abs,
+        );
+        let label = if self.pnl.net >= 0.0 {
+            "Net Income"
+        } else {
+            "Net Loss"
+        };
+        html.push_str(&amount_row(label, self.pnl.net, true));
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Cash Flow</h2>\n<table>\n");
+        html.push_str(
+            "<tr><th>Month</th><th class=\"amount\">Inflows</th><th class=\"amount\">Outflows</th>\
+             <th class=\"amount\">Net</th><th class=\"amount\">Running</th></tr>\n",
+        );
+        for m in &self.cashflow.months {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td>\
+                 <td class=\"amount\">{}</td><td class=\"amount\">{}</td></tr>\n",
+                html_escape(&m.month),
+                money(m.inflows),
+                money(m.outflows.abs()),
+                money(m.net),
+                money(m.running_balance)
+            ));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Reconciliation Certificates</h2>\n<table>\n");
+        html.push_str(
+            "<tr><th>Account</th><th class=\"amount\">Statement</th><th class=\"amount\">Books</th>\
+             <th>Reconciled</th></tr>\n",
+        );
+        for c in &self.certificates {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td><td>{}</td></tr>\n",
+                html_escape(&c.account),
+                money(c.statement_balance),
+                money(c.calculated_balance),
+                html_escape(&c.reconciled_at)
+            ));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Review</h2>\n");
+        html.push_str(&format!(
+            "<p>{}</p>\n</body>\n</html>\n",
+            html_escape(&self.review_statement())
+        ));
+        html
+    }
+}
+
+/// One side of the P&L: groups with their sub-category lines, then a total.
+fn pnl_html(
+    html: &mut String,
+    heading: &str,
+    items: &[PnlItem],
+    total: f64,
+    amount: fn(f64) -> f64,
+) {
+    if items.is_empty() {
+        return;
+    }
+    html.push_str(&format!(
+        "<tr><th colspan=\"2\">{}</th></tr>\n",
+        html_escape(heading)
+    ));
+    for group in pnl_groups(items) {
+        if group.is_single() {
+            html.push_str(&amount_row(group.name, amount(group.total), false));
+            continue;
+        }
+        html.push_str(&format!(
+            "<tr><td>{}</td><td></td></tr>\n",
+            html_escape(group.name)
+        ));
+        for (label, value) in &group.items {
+            html.push_str(&amount_row(
+                &format!("\u{a0}\u{a0}\u{a0}\u{a0}{label}"),
+                amount(*value),
+                false,
+            ));
+        }
+        html.push_str(&amount_row(
+            &format!("Total {}", group.name),
+            amount(group.total),
+            true,
+        ));
+    }
+    html.push_str(&amount_row(&format!("Total {heading}"), total, true));
+}
+
+fn amount_row(label: &str, amount: f64, total: bool) -> String {
+    format!(
+        "<tr{}><td>{}</td><td class=\"amount\">{}</td></tr>\n",
+        if total { " class=\"total\"" } else { "" },
+        html_escape(label),
+        money(amount)
+    )
+}
+
+fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Where a packet for `period` goes: `closes/close-<period>.<ext>`, or with
+/// `-2`, `-3`, … when the month was closed before, so earlier packets stay
+/// as they were filed.
+pub fn packet_path(data_dir: &Path, period: &str, ext: &str) -> PathBuf {
+    let dir = data_dir.join(CLOSES_DIR);
+    let mut path = dir.join(format!("close-{period}.{ext}"));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("close-{period}-{n}.{ext}"));
+        n += 1;
+    }
+    path
+}
+
+/// A completed close, from the `closes` table.
+pub struct CloseRecord {
+    pub id: i64,
+    pub month: String,
+    /// Relative to the data directory.
+    pub packet_path: String,
+    pub accounts_reconciled: i64,
+    pub transactions: i64,
+    pub net: f64,
+    /// As SQLite `datetime('now')` (UTC).
+    pub closed_at: String,
+}
+
+/// Record a close and its packet, kept relative to `data_dir` like retained
+/// statements, for the audit trail.
+pub fn record(
+    conn: &Connection,
+    data_dir: &Path,
+    packet: &ClosePacket,
+    packet_path: &Path,
+) -> Result<i64> {
+    let relative = packet_path.strip_prefix(data_dir).unwrap_or(packet_path);
+    conn.execute(
+        "INSERT INTO closes (month, packet_path, accounts_reconciled, transactions, net) \
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![
+            packet.period(),
+            relative.to_string_lossy(),
+            packet.certificates.len() as i64,
+            packet.transactions as i64,
+            packet.pnl.net,
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Every recorded close, newest first.
+pub fn list(conn: &Connection) -> Result<Vec<CloseRecord>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, month, packet_path, accounts_reconciled, transactions, net, closed_at \
+         FROM closes ORDER BY closed_at DESC, id DESC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(CloseRecord {
+                id: row.get(0)?,
+                month: row.get(1)?,
+                packet_path: row.get(2)?,
+                accounts_reconciled: row.get(3)?,
+                transactions: row.get(4)?,
+                net: row.get(5)?,
+                closed_at: row.get(6)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        let income: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Client Services'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, '2025-03-05', 'CLIENT ACME', 1200.0, ?1)",
+            [income],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, is_flagged) \
+             VALUES (1, '2025-03-09', 'MYSTERY', -40.0, 1)",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn close_waits_for_review_and_reconciliation() {
+        let (dir, conn) = test_db();
+        let check = super::check(&conn, 2025, 3).unwrap();
+        assert_eq!(check.blockers().len(), 2);
+        assert!(ClosePacket::build(&conn, check).is_err());
+
+        conn.execute("UPDATE transactions SET is_flagged = 0", [])
+            .unwrap();
+        // A reconciliation that didn't balance doesn't count
+        crate::reconciler::reconcile(&conn, "Checking", "2025-03", 1000.0).unwrap();
+        assert!(!super::check(&conn, 2025, 3).unwrap().is_ready());
+        crate::reconciler::reconcile(&conn, "Checking", "2025-03", 1160.0).unwrap();
+        let check = super::check(&conn, 2025, 3).unwrap();
+        assert!(check.is_ready(), "{:?}", check.blockers());
+
+        let packet = ClosePacket::build(&conn, check).unwrap();
+        assert_eq!(packet.certificates.len(), 1);
+        assert_eq!(packet.certificates[0].statement_balance, 1160.0);
+        let html = packet.to_html();
+        assert!(html.contains("Month Close \u{2014} March 2025"));
+        assert!(html.contains("Reconciliation Certificates"));
+        assert!(html.contains("none are flagged"));
+
+        let path = packet_path(dir.path(), &packet.period(), "html");
+        assert!(path.ends_with("closes/close-2025-03.html"));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &html).unwrap();
+        record(&conn, dir.path(), &packet, &path).unwrap();
+        // A second close of the month keeps the first packet
+        let again = packet_path(dir.path(), &packet.period(), "html");
+        assert!(again.ends_with("closes/close-2025-03-2.html"));
+
+        let closes = list(&conn).unwrap();
+        assert_eq!(closes.len(), 1);
+        assert_eq!(closes[0].month, "2025-03");
+        assert_eq!(closes[0].packet_path, "closes/close-2025-03.html");
+        assert_eq!(closes[0].accounts_reconciled, 1);
+        // The uncategorized row is in no P&L line
+        assert_eq!(closes[0].net, 1200.0);
+    }
+
+    #[test]
+    fn empty_month_cannot_close() {
+        let (_dir, conn) = test_db();
+        let check = super::check(&conn, 2025, 4).unwrap();
+        assert!(check.accounts.is_empty());
+        assert_eq!(
+            check.blockers(),
+            vec!["No transactions are dated in 2025-04."]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use std::path::{Path, PathBuf};
+
+use crate::db::{get_db_password, get_metadata, is_encrypted, open_read_only};
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::settings::shellexpand_path;
+
+/// One book's figures, read from its own database without touching it.
+pub enum BookStatus {
+    Open {
+        company_name: Option<String>,
+        home_currency: String,
+        /// Balance across all of the book's accounts.
+        cash: f64,
+        /// Net income so far in the book's own fiscal year.
+        ytd_net: f64,
+        fiscal_label: String,
+    },
+    /// No `nigel.db` in the directory.
+    Missing,
+    /// Encrypted with a password other than the current book's.
+    Locked,
+    /// The database couldn't be read, e.g. a schema from another version.
+    Failed(String),
+}
+
+impl BookStatus {
+    /// Why a book's figures are missing; `None` for an open book.
+    pub fn note(&self) -> Option<String> {
+        match self {
+            BookStatus::Open { .. } => None,
+            BookStatus::Missing => Some("No book found here".into()),
+            BookStatus::Locked => Some("Encrypted with a different password".into()),
+            BookStatus::Failed(e) => Some(format!("Couldn't read: {e}")),
+        }
+    }
+}
+
+pub struct BookLine {
+    pub dir: PathBuf,
+    /// The book Nigel is using now.
+    pub current: bool,
+    pub status: BookStatus,
+}
+
+impl BookLine {
+    /// Company name, falling back to the directory name.
+    pub fn label(&self) -> String {
+        if let BookStatus::Open {
+            company_name: Some(name),
+            ..
+        } = &self.status
+        {
+            return name.clone();
+        }
+        self.dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.dir.display().to_string())
+    }
+}
+
+pub struct Overview {
+    /// The current book first, then the others in settings order.
+    pub books: Vec<BookLine>,
+}
+
+impl Overview {
+    /// `val` as money, with the currency spelled out when it isn't the
+    /// current book's home currency.
+    pub fn money(&self, val: f64, currency: &str) -> String {
+        let home = self.books.first().and_then(|b| match &b.status {
+            BookStatus::Open { home_currency, .. } => Some(home_currency.as_str()),
+            _ => None,
+        });
+        if home.is_none_or(|h| h == currency) {
+            crate::fmt::money(val)
+        } else {
+            crate::fmt::money_in(val, currency)
+        }
+    }
+
+    /// Books whose figures could be read.
+    pub fn open_count(&self) -> usize {
+        self.books
+            .iter()
+            .filter(|b| matches!(b.status, BookStatus::Open { .. }))
+            .count()
+    }
+
+    /// Combined `(currency, cash, ytd_net)` when at least two books are open
+    /// and they share a home currency. Figures in different currencies
+    /// aren't added.
+    pub fn combined(&self) -> Option<(String, f64, f64)> {
+        if self.open_count() < 2 {
+            return None;
+        }
+        let mut combined: Option<(String, f64, f64)> = None;
+        for book in &self.books {
+            let BookStatus::Open {
+                home_currency,
+                cash,
+                ytd_net,
+                ..
+            } = &book.status
+            else {
+                continue;
+            };
+            match &mut combined {
+                None => combined = Some((home_currency.clone(), *cash, *ytd_net)),
+                Some((currency, _, _)) if currency != home_currency => return None,
+                Some((_, total_cash, total_net)) => {
+                    *total_cash += cash;
+                    *total_net += ytd_net;
+                }
+            }
+        }
+        combined
+    }
+}
+
+/// Read the current book and each of `others` (data directories, `~`
+/// allowed). Repeats of a directory are skipped; a book that can't be read
+/// is listed with the reason rather than failing the overview.
+pub fn get_overview(current: &Path, others: &[String]) -> Overview {
+    let canonical = |dir: PathBuf| std::fs::canonicalize(&dir).unwrap_or(dir);
+    let mut dirs = vec![canonical(current.to_path_buf())];
+    for dir in others.iter().filter(|d| !d.trim().is_empty()) {
+        let dir = canonical(PathBuf::from(shellexpand_path(dir.trim())));
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    let password = get_db_password();
+    let books = dirs
+        .into_iter()
+        .enumerate()
+        .map(|(i, dir)| {
+            let status = read_book(&dir, password.as_deref());
+            BookLine {
+                dir,
+                current: i == 0,
+                status,
+            }
+        })
+        .collect();
+    Overview { books }
+}
+
+/// Open the book in `dir` read-only and total its cash and YTD net. An
+/// encrypted book is tried with `password`, the current book's.
+pub fn read_book(dir: &Path, password: Option<&str>) -> BookStatus {
+    let db_path = dir.join("nigel.db");
+    if !db_path.exists() {
+        return BookStatus::Missing;
+    }
+    // A plaintext database can't be opened with a key set
+    let password = match is_encrypted(&db_path) {
+        Ok(true) => match password {
+            Some(pw) => Some(pw),
+            None => return BookStatus::Locked,
+        },
+        Ok(false) => None,
+        Err(e) => return BookStatus::Failed(e.to_string()),
+    };
+    match read_totals(&db_path, password) {
+        Ok(status) => status,
+        Err(NigelError::Db(rusqlite::Error::SqliteFailure(err, _)))
+            if err.code == rusqlite::ErrorCode::NotADatabase =>
+        {
+            BookStatus::Locked
+        }
+        Err(e) => BookStatus::Failed(e.to_string()),
+    }
+}
+
+fn read_totals(db_path: &Path, password: Option<&str>) -> Result<BookStatus> {
+    let conn = open_read_only(db_path, password)?;
+    let balance = crate::reports::get_balance(&conn, false)?;
+    let fiscal = FiscalYear::load(&conn);
+    Ok(BookStatus::Open {
+        company_name: get_metadata(&conn, "company_name").filter(|n| !n.is_empty()),
+        home_currency: balance.home_currency,
+        cash: balance.total,
+        ytd_net: balance.ytd_net_income,
+        fiscal_label: fiscal.label(fiscal.current()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db, set_metadata};
+
+    /// A book in `dir` holding one deposit dated today.
+    fn make_book(dir: &Path, company: &str, amount: f64) {
+        std::fs::create_dir_all(dir).unwrap();
+        let conn = get_connection(&dir.join("nigel.db")).unwrap();
+        init_db(&conn).unwrap();
+        set_metadata(&conn, "company_name", company).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, ?1, 'DEPOSIT', ?2)",
+            rusqlite::params![chrono::Local::now().date_naive().to_string(), amount],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn overview_combines_books_and_reports_unreadable_ones() {
+        let root = tempfile::tempdir().unwrap();
+        let main = root.path().join("main");
+        let other = root.path().join("other");
+        make_book(&main, "Acme LLC", 1000.0);
+        make_book(&other, "Household", 250.0);
+        let locked = root.path().join("locked");
+        std::fs::create_dir_all(&locked).unwrap();
+        std::fs::write(locked.join("nigel.db"), [7u8; 64]).unwrap();
+
+        let others = [
+            other.to_string_lossy().into_owned(),
+            main.to_string_lossy().into_owned(),
+            locked.to_string_lossy().into_owned(),
+            root.path().join("gone").to_string_lossy().into_owned(),
+        ];
+        let overview = get_overview(&main, &others);
+        // The current book isn't listed twice
+        assert_eq!(overview.books.len(), 4);
+        assert!(overview.books[0].current);
+        assert_eq!(overview.books[0].label(), "Acme LLC");
+        assert_eq!(overview.books[1].label(), "Household");
+        assert!(matches!(overview.books[2].status, BookStatus::Locked));
+        assert!(matches!(overview.books[3].status, BookStatus::Missing));
+        assert_eq!(overview.books[3].label(), "gone");
+
+        let (currency, cash, net) = overview.combined().unwrap();
+        assert_eq!(currency, "USD");
+        assert!((cash - 1250.0).abs() < 0.001);
+        assert!((net - 1250.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn combined_skips_mixed_currencies() {
+        let open = |currency: &str| BookLine {
+            dir: PathBuf::from("/books"),
+            current: false,
+            status: BookStatus::Open {
+                company_name: None,
+                home_currency: currency.into(),
+                cash: 10.0,
+                ytd_net: 1.0,
+                fiscal_label: "FY 2025".into(),
+            },
+        };
+        let overview = Overview {
+            books: vec![open("USD"), open("EUR")],
+        };
+        assert!(overview.combined().is_none());
+        assert_eq!(overview.money(5.0, "EUR"), "5.00 EUR");
+        assert_eq!(overview.money(5.0, "USD"), "$5.00");
+    }
+}
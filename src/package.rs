@@ -0,0 +1,359 @@
+use std::path::Path;
+
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+use crate::error::{NigelError, Result};
+use crate::fmt::money;
+use crate::reports::RegisterRow;
+use crate::statements::ImportRecord;
+use crate::tabular::{self, Sheet, Value};
+
+/// One file in an accountant package, at its path inside the ZIP.
+pub struct PackageFile {
+    pub path: String,
+    /// What the file is, for the cover README's contents list.
+    pub description: String,
+    #[cfg_attr(not(feature = "xlsx"), allow(dead_code))]
+    pub bytes: Vec<u8>,
+}
+
+impl PackageFile {
+    pub fn new(path: &str, description: &str, bytes: Vec<u8>) -> Self {
+        Self {
+            path: path.to_string(),
+            description: description.to_string(),
+            bytes,
+        }
+    }
+
+    pub fn csv(path: &str, description: &str, sheet: &Sheet) -> Result<Self> {
+        let mut bytes = Vec::new();
+        tabular::write_csv_to(sheet, &mut bytes)?;
+        Ok(Self::new(path, description, bytes))
+    }
+}
+
+/// The active chart of accounts, parents before their sub-categories.
+pub fn chart_sheet(conn: &Connection) -> Result<Sheet> {
+    let chart = crate::sharing::export_chart(conn)?;
+    let rows = chart
+        .categories
+        .iter()
+        .map(|c| {
+            vec![
+                Value::from(c.name.as_str()),
+                Value::from(c.category_type.as_str()),
+                Value::from(c.parent.as_deref()),
+                Value::from(c.tax_line.as_deref()),
+                Value::from(c.form_line.as_deref()),
+            ]
+        })
+        .collect();
+    Ok(Sheet {
+        name: "Chart of Accounts".into(),
+        headers: ["Category", "Type", "Parent", "Tax Line", "Form Line"]
+            .map(String::from)
+            .to_vec(),
+        rows,
+    })
+}
+
+/// Every reconciliation of a month between `start` and `end`, by account
+/// and month.
+pub fn reconciliation_sheet(conn: &Connection, start: NaiveDate, end: NaiveDate) -> Result<Sheet> {
+    let mut stmt = conn.prepare(
+        "SELECT a.name, r.month, r.statement_balance, r.calculated_balance, r.is_reconciled, \
+         r.reconciled_at, r.notes, r.follow_up, r.resolved_at \
+         FROM reconciliations r JOIN accounts a ON a.id = r.account_id \
+         WHERE r.month BETWEEN ?1 AND ?2 ORDER BY a.name, r.month, r.id",
+    )?;
+    let rows = stmt
+        .query_map(
+            [
+                start.format("%Y-%m").to_string(),
+                end.format("%Y-%m").to_string(),
+            ],
+            |row| {
+                let number = |v: Option<f64>| v.map_or(Value::Empty, Value::Number);
+                Ok(vec![
+                    Value::Text(row.get(0)?),
+                    Value::Text(row.get(1)?),
+                    number(row.get(2)?),
+                    number(row.get(3)?),
+                    Value::from(if row.get::<_, bool>(4)? { "yes" } else { "no" }),
+                    Value::from(row.get::<_, Option<String>>(5)?.as_deref()),
+                    Value::from(row.get::<_, Option<String>>(6)?.as_deref()),
+                    Value::from(row.get::<_, Option<String>>(7)?.as_deref()),
+                    Value::from(row.get::<_, Option<String>>(8)?.as_deref()),
+                ])
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(Sheet {
+        name: "Reconciliations".into(),
+        headers: [
+            "Account",
+            "Month",
+            "Statement Balance",
+            "Calculated Balance",
+            "Reconciled",
+            "Reconciled At",
+            "Notes",
+            "Follow Up",
+            "Resolved At",
+        ]
+        .map(String::from)
+        .to_vec(),
+        rows,
+    })
+}
+
+/// The imported statements covering any of `start`..=`end`, oldest first,
+/// with where Nigel kept a copy (relative to the data directory).
+pub fn attachments_sheet(imports: &[ImportRecord], start: NaiveDate, end: NaiveDate) -> Sheet {
+    let (start, end) = (start.to_string(), end.to_string());
+    let rows = imports
+        .iter()
+        .rev()
+        .filter(|i| {
+            // Imports without a date range can't be placed, so keep them
+            let from = i.date_range_start.as_deref().unwrap_or("");
+            let to = i.date_range_end.as_deref().unwrap_or("9999");
+            from <= end.as_str() && to >= start.as_str()
+        })
+        .map(|i| {
+            vec![
+                Value::from(i.filename.as_str()),
+                Value::from(i.account_name.as_str()),
+                Value::from(i.import_date.as_str()),
+                Value::from(i.date_range_start.as_deref()),
+                Value::from(i.date_range_end.as_deref()),
+                Value::Int(i.record_count),
+                Value::from(i.stored_path.as_deref()),
+            ]
+        })
+        .collect();
+    Sheet {
+        name: "Attachments".into(),
+        headers: [
+            "File",
+            "Account",
+            "Imported",
+            "From",
+            "To",
+            "Transactions",
+            "Stored Copy",
+        ]
+        .map(String::from)
+        .to_vec(),
+        rows,
+    }
+}
+
+/// What the cover README says about the books.
+pub struct Cover<'a> {
+    pub company: &'a str,
+    pub period: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub currency: String,
+    pub total_income: f64,
+    pub total_expenses: f64,
+    pub net: f64,
+    pub transactions: &'a [RegisterRow],
+    pub accounts: Vec<String>,
+    pub prepared: NaiveDate,
+}
+
+/// The package's README.txt: who and what period it covers, the year's
+/// headline figures, what may need a second look, and a list of the files.
+pub fn cover_readme(cover: &Cover, files: &[PackageFile]) -> String {
+    let company = if cover.company.is_empty() {
+        "Accountant package"
+    } else {
+        cover.company
+    };
+    let mut out = format!(
+        "{company}\n{}\n\n{} ({} to {})\nPrepared {} with Nigel {}\n\n",
+        "=".repeat(company.chars().count()),
+        cover.period,
+        cover.start,
+        cover.end,
+        cover.prepared,
+        env!("CARGO_PKG_VERSION"),
+    );
+
+    let uncategorized = cover
+        .transactions
+        .iter()
+        .filter(|t| t.category_id.is_none())
+        .count();
+    let flagged = cover.transactions.iter().filter(|t| t.is_flagged).count();
+    out.push_str("Summary\n-------\n");
+    for (label, value) in [
+        ("Income", money(cover.total_income)),
+        ("Expenses", money(cover.total_expenses.abs())),
+        ("Net", money(cover.net)),
+        ("Transactions", cover.transactions.len().to_string()),
+        ("Uncategorized", uncategorized.to_string()),
+        ("Flagged", flagged.to_string()),
+        ("Currency", cover.currency.clone()),
+    ] {
+        out.push_str(&format!("  {label:<15}{value}\n"));
+    }
+    if !cover.accounts.is_empty() {
+        out.push_str("\nAccounts\n--------\n");
+        for account in &cover.accounts {
+            out.push_str(&format!("  {account}\n"));
+        }
+    }
+    if uncategorized > 0 || flagged > 0 {
+        out.push_str(
+            "\nSome transactions are uncategorized or flagged for review; they are\n\
+             marked in data/transactions.csv and may change the figures above.\n",
+        );
+    }
+
+    out.push_str("\nContents\n--------\n");
+    let width = files.iter().map(|f| f.path.len()).max().unwrap_or(0);
+    out.push_str(&format!("  {:width$}  This file\n", "README.txt"));
+    for file in files {
+        out.push_str(&format!("  {:width$}  {}\n", file.path, file.description));
+    }
+    out.push_str(&format!(
+        "\nAmounts are in {}. In the CSV files, money out is negative.\n",
+        cover.currency
+    ));
+    out
+}
+
+#[cfg(not(feature = "xlsx"))]
+pub fn write_zip(_files: &[PackageFile], _path: &Path) -> Result<()> {
+    Err(NigelError::Other(
+        "Export packages require the 'xlsx' feature (for ZIP support)".into(),
+    ))
+}
+
+/// Write the files into a ZIP under a top-level folder named after the
+/// archive, so unzipping doesn't scatter them.
+#[cfg(feature = "xlsx")]
+pub fn write_zip(files: &[PackageFile], path: &Path) -> Result<()> {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    let zip_err = |e: zip::result::ZipError| NigelError::Other(format!("ZIP write failed: {e}"));
+    let folder = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("package");
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    for file in files {
+        zip.start_file(
+            format!("{folder}/{}", file.path),
+            SimpleFileOptions::default(),
+        )
+        .map_err(zip_err)?;
+        zip.write_all(&file.bytes)?;
+    }
+    zip.finish().map_err(zip_err)?;
+    crate::settings::restrict_file_permissions(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn reconciliations_are_limited_to_the_year() {
+        let (_dir, conn) = test_db();
+        for (month, ok) in [("2023-12", 1), ("2024-03", 1), ("2024-04", 0)] {
+            conn.execute(
+                "INSERT INTO reconciliations (account_id, month, statement_balance, \
+                 calculated_balance, is_reconciled) VALUES (1, ?1, 100.0, 100.0, ?2)",
+                rusqlite::params![month, ok],
+            )
+            .unwrap();
+        }
+        let sheet = reconciliation_sheet(&conn, date("2024-01-01"), date("2024-12-31")).unwrap();
+        let months: Vec<&Value> = sheet.rows.iter().map(|r| &r[1]).collect();
+        assert_eq!(months, [&Value::from("2024-03"), &Value::from("2024-04")]);
+        assert_eq!(sheet.rows[1][4], Value::from("no"));
+    }
+
+    #[test]
+    fn attachments_overlap_the_year() {
+        let import = |id, from: &str, to: &str| ImportRecord {
+            id,
+            filename: format!("stmt-{id}.csv"),
+            account_name: "Checking".into(),
+            import_date: "2024-06-01".into(),
+            record_count: 10,
+            date_range_start: Some(from.into()),
+            date_range_end: Some(to.into()),
+            stored_path: None,
+        };
+        // Newest first, as list_imports() returns them
+        let imports = vec![
+            import(3, "2025-01-01", "2025-01-31"),
+            import(2, "2024-12-15", "2025-01-14"),
+            import(1, "2023-12-01", "2023-12-31"),
+        ];
+        let sheet = attachments_sheet(&imports, date("2024-01-01"), date("2024-12-31"));
+        assert_eq!(sheet.rows.len(), 1);
+        assert_eq!(sheet.rows[0][0], Value::from("stmt-2.csv"));
+    }
+
+    #[test]
+    fn cover_lists_figures_and_files() {
+        let (_dir, conn) = test_db();
+        let chart = PackageFile::csv(
+            "data/chart-of-accounts.csv",
+            "Chart of accounts",
+            &chart_sheet(&conn).unwrap(),
+        )
+        .unwrap();
+        assert!(String::from_utf8_lossy(&chart.bytes).starts_with("Category,Type,Parent"));
+
+        let cover = Cover {
+            company: "Acme LLC",
+            period: "FY 2024".into(),
+            start: date("2024-01-01"),
+            end: date("2024-12-31"),
+            currency: "USD".into(),
+            total_income: 1000.0,
+            total_expenses: -400.0,
+            net: 600.0,
+            transactions: &[],
+            accounts: vec!["Checking (checking)".into()],
+            prepared: date("2025-01-15"),
+        };
+        let readme = cover_readme(&cover, &[chart]);
+        assert!(readme.starts_with("Acme LLC\n========\n"));
+        assert!(readme.contains("FY 2024 (2024-01-01 to 2024-12-31)"));
+        assert!(readme.contains("  Expenses       $400.00\n"));
+        assert!(readme.contains("  Net            $600.00\n"));
+        assert!(readme.contains("  data/chart-of-accounts.csv  Chart of accounts\n"));
+        assert!(!readme.contains("uncategorized or flagged"));
+    }
+}
@@ -0,0 +1,422 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::currency::HOME_AMOUNT_SQL;
+use crate::db::{get_metadata, set_metadata};
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::reports::{self, DISTRIBUTION_LINE, OFFICER_COMP_LINES};
+
+/// Default share of profit set aside for income taxes, as a percentage.
+pub const DEFAULT_TAX_PCT: f64 = 30.0;
+
+/// Inputs to the planner, stored per database in `metadata`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PayPlanSettings {
+    /// Annual officer salary run through payroll.
+    pub salary: f64,
+    /// Percent of profit held back for estimated taxes.
+    pub tax_pct: f64,
+    /// Cash to keep in the bank no matter what.
+    pub reserve: f64,
+}
+
+fn metadata_f64(conn: &Connection, key: &str) -> Option<f64> {
+    get_metadata(conn, key).and_then(|v| v.parse::<f64>().ok())
+}
+
+pub fn load_settings(conn: &Connection) -> PayPlanSettings {
+    PayPlanSettings {
+        salary: metadata_f64(conn, "payplan_salary").unwrap_or(0.0),
+        tax_pct: metadata_f64(conn, "payplan_tax_pct")
+            .filter(|v| (0.0..=100.0).contains(v))
+            .unwrap_or(DEFAULT_TAX_PCT),
+        reserve: metadata_f64(conn, "payplan_reserve").unwrap_or(0.0),
+    }
+}
+
+/// Save whichever settings were given; each is validated before any is
+/// written.
+pub fn save_settings(
+    conn: &Connection,
+    salary: Option<f64>,
+    tax_pct: Option<f64>,
+    reserve: Option<f64>,
+) -> Result<()> {
+    for (name, value) in [("salary", salary), ("reserve", reserve)] {
+        if let Some(v) = value {
+            if !v.is_finite() || v < 0.0 {
+                return Err(NigelError::Other(format!(
+                    "Invalid {name}: {v} (must be zero or more)"
+                )));
+            }
+        }
+    }
+    if let Some(pct) = tax_pct {
+        if !pct.is_finite() || !(0.0..=100.0).contains(&pct) {
+            return Err(NigelError::Other(format!(
+                "Invalid tax rate: {pct} (must be 0-100)"
+            )));
+        }
+    }
+    if let Some(v) = salary {
+        set_metadata(conn, "payplan_salary", &v.to_string())?;
+    }
+    if let Some(v) = tax_pct {
+        set_metadata(conn, "payplan_tax_pct", &v.to_string())?;
+    }
+    if let Some(v) = reserve {
+        set_metadata(conn, "payplan_reserve", &v.to_string())?;
+    }
+    Ok(())
+}
+
+/// The figures behind one month's safe distribution, in the order they are
+/// shown.
+#[derive(Debug, PartialEq)]
+pub struct PayPlan {
+    /// "YYYY-MM" the plan is for.
+    pub month: String,
+    pub fiscal_label: String,
+    /// Months of the fiscal year through `month`, 1-12.
+    pub months_elapsed: u32,
+    pub settings: PayPlanSettings,
+    /// Year-to-date profit with distributions added back.
+    pub profit: f64,
+    pub taxes: f64,
+    /// Salary that should have gone through payroll by now.
+    pub salary_to_date: f64,
+    pub officer_comp: f64,
+    /// Salary behind schedule, paid through payroll before any distribution.
+    pub salary_due: f64,
+    pub distributions: f64,
+    /// Profit left after taxes, salary due, and distributions taken.
+    pub available_from_profit: f64,
+    pub cash: f64,
+    /// Cash left after the reserve, tax set-aside, and salary due.
+    pub cash_available: f64,
+    /// The lesser of the two, never below zero.
+    pub safe: f64,
+    pub planned: Option<f64>,
+}
+
+/// Year-to-date figures the plan is built from.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PlanInputs {
+    /// P&L net for the fiscal year through the month; distributions are an
+    /// expense category, so they are already subtracted.
+    pub net: f64,
+    pub officer_comp: f64,
+    pub distributions: f64,
+    pub cash: f64,
+    pub months_elapsed: u32,
+}
+
+/// Work through the math: profit first, then cash, and take the lesser.
+pub fn compute(inputs: PlanInputs, settings: PayPlanSettings) -> PayPlanMath {
+    let profit = inputs.net + inputs.distributions;
+    let taxes = profit.max(0.0) * settings.tax_pct / 100.0;
+    let salary_to_date = settings.salary * inputs.months_elapsed as f64 / 12.0;
+    let salary_due = (salary_to_date - inputs.officer_comp).max(0.0);
+    let available_from_profit = profit - taxes - salary_due - inputs.distributions;
+    let cash_available = inputs.cash - settings.reserve - taxes - salary_due;
+    let safe = available_from_profit.min(cash_available).max(0.0);
+    PayPlanMath {
+        profit,
+        taxes,
+        salary_to_date,
+        salary_due,
+        available_from_profit,
+        cash_available,
+        safe,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PayPlanMath {
+    pub profit: f64,
+    pub taxes: f64,
+    pub salary_to_date: f64,
+    pub salary_due: f64,
+    pub available_from_profit: f64,
+    pub cash_available: f64,
+    pub safe: f64,
+}
+
+/// Parse "YYYY-MM" to the first and last day of that month.
+pub fn parse_month(month: &str) -> Result<(NaiveDate, NaiveDate)> {
+    let start = NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+        .map_err(|_| NigelError::Other(format!("Invalid month: '{month}' (expected YYYY-MM)")))?;
+    let end = start
+        .checked_add_months(Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .unwrap_or(start);
+    Ok((start, end))
+}
+
+/// Officer comp and distributions between two dates, as positive amounts.
+fn owner_pay(conn: &Connection, from: NaiveDate, to: NaiveDate) -> Result<(f64, f64)> {
+    let sql = format!(
+        "SELECT \
+           COALESCE(SUM(CASE WHEN c.form_line IN ({OFFICER_COMP_LINES}) THEN {HOME_AMOUNT_SQL} END), 0), \
+           COALESCE(SUM(CASE WHEN c.form_line = '{DISTRIBUTION_LINE}' THEN {HOME_AMOUNT_SQL} END), 0) \
+         FROM transactions t JOIN categories c ON t.category_id = c.id \
+         JOIN accounts a ON t.account_id = a.id \
+         WHERE t.deleted_at IS NULL AND t.date BETWEEN ?1 AND ?2"
+    );
+    let (comp, dist): (f64, f64) =
+        conn.query_row(&sql, [from.to_string(), to.to_string()], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })?;
+    // Both are outflows
+    Ok((comp.abs(), dist.abs()))
+}
+
+/// Build the plan for `month` ("YYYY-MM"): year-to-date figures run from
+/// the start of its fiscal year to the end of the month, and cash is the
+/// current cash position.
+pub fn plan_for_month(conn: &Connection, month: &str) -> Result<PayPlan> {
+    let (month_start, month_end) = parse_month(month)?;
+    let fiscal = FiscalYear::load(conn);
+    let fy = fiscal.year_of(month_start);
+    let (fy_start, _) = fiscal.bounds(fy);
+    let months_elapsed = (month_start.year() - fy_start.year()) * 12 + month_start.month() as i32
+        - fy_start.month() as i32
+        + 1;
+
+    let pnl = reports::get_pnl(
+        conn,
+        None,
+        None,
+        Some(&fy_start.to_string()),
+        Some(&month_end.to_string()),
+        None,
+        &[],
+    )?;
+    let (officer_comp, distributions) = owner_pay(conn, fy_start, month_end)?;
+    let cash = reports::get_balance(conn, false)?.total;
+    let settings = load_settings(conn);
+    let inputs = PlanInputs {
+        net: pnl.net,
+        officer_comp,
+        distributions,
+        cash,
+        months_elapsed: months_elapsed as u32,
+    };
+    let math = compute(inputs, settings);
+
+    Ok(PayPlan {
+        month: month.to_string(),
+        fiscal_label: fiscal.label(fy),
+        months_elapsed: inputs.months_elapsed,
+        settings,
+        profit: math.profit,
+        taxes: math.taxes,
+        salary_to_date: math.salary_to_date,
+        officer_comp,
+        salary_due: math.salary_due,
+        distributions,
+        available_from_profit: math.available_from_profit,
+        cash,
+        cash_available: math.cash_available,
+        safe: math.safe,
+        planned: planned_for(conn, month)?,
+    })
+}
+
+/// Record the distribution planned for a month, replacing any earlier plan.
+pub fn set_planned(conn: &Connection, month: &str, amount: f64) -> Result<()> {
+    parse_month(month)?;
+    if !amount.is_finite() || amount < 0.0 {
+        return Err(NigelError::Other(format!(
+            "Invalid amount: {amount} (must be zero or more)"
+        )));
+    }
+    conn.execute(
+        "INSERT INTO distribution_plans (month, amount) VALUES (?1, ?2) \
+         ON CONFLICT(month) DO UPDATE SET amount = excluded.amount, created_at = datetime('now')",
+        rusqlite::params![month, amount],
+    )?;
+    Ok(())
+}
+
+pub fn clear_planned(conn: &Connection, month: &str) -> Result<()> {
+    let removed = conn.execute("DELETE FROM distribution_plans WHERE month = ?1", [month])?;
+    if removed == 0 {
+        return Err(NigelError::Other(format!(
+            "No planned distribution for {month}"
+        )));
+    }
+    Ok(())
+}
+
+fn planned_for(conn: &Connection, month: &str) -> Result<Option<f64>> {
+    Ok(conn
+        .query_row(
+            "SELECT amount FROM distribution_plans WHERE month = ?1",
+            [month],
+            |r| r.get(0),
+        )
+        .optional()?)
+}
+
+/// Planned against actual distributions for one month.
+#[derive(Debug, PartialEq)]
+pub struct MonthDistribution {
+    pub month: String,
+    pub planned: Option<f64>,
+    pub actual: f64,
+}
+
+impl MonthDistribution {
+    /// Actual minus planned; positive means more was taken than planned.
+    pub fn difference(&self) -> Option<f64> {
+        self.planned.map(|p| self.actual - p)
+    }
+}
+
+/// Every month of fiscal year `fy` with its planned and actual
+/// distributions.
+pub fn history(conn: &Connection, fy: i32) -> Result<Vec<MonthDistribution>> {
+    let (fy_start, _) = FiscalYear::load(conn).bounds(fy);
+    let mut months = Vec::with_capacity(12);
+    for i in 0..12 {
+        let start = fy_start
+            .checked_add_months(Months::new(i))
+            .unwrap_or(fy_start);
+        let end = start
+            .checked_add_months(Months::new(1))
+            .and_then(|d| d.pred_opt())
+            .unwrap_or(start);
+        let month = start.format("%Y-%m").to_string();
+        let (_, actual) = owner_pay(conn, start, end)?;
+        months.push(MonthDistribution {
+            planned: planned_for(conn, &month)?,
+            month,
+            actual,
+        });
+    }
+    Ok(months)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn add_txn(conn: &Connection, date: &str, category: &str, amount: f64) {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, ?1, ?2, ?3, (SELECT id FROM categories WHERE name = ?2))",
+            rusqlite::params![date, category, amount],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_compute_takes_lesser_of_profit_and_cash() {
+        let settings = PayPlanSettings {
+            salary: 60_000.0,
+            tax_pct: 25.0,
+            reserve: 5_000.0,
+        };
+        let inputs = PlanInputs {
+            // 40k profit after 2k of distributions
+            net: 38_000.0,
+            officer_comp: 10_000.0,
+            distributions: 2_000.0,
+            cash: 30_000.0,
+            months_elapsed: 3,
+        };
+        let math = compute(inputs, settings);
+        assert_eq!(math.profit, 40_000.0);
+        assert_eq!(math.taxes, 10_000.0);
+        assert_eq!(math.salary_to_date, 15_000.0);
+        assert_eq!(math.salary_due, 5_000.0);
+        assert_eq!(math.available_from_profit, 23_000.0);
+        assert_eq!(math.cash_available, 10_000.0);
+        assert_eq!(math.safe, 10_000.0);
+
+        // A loss never plans a negative distribution
+        let loss = compute(
+            PlanInputs {
+                net: -5_000.0,
+                ..inputs
+            },
+            settings,
+        );
+        assert_eq!(loss.taxes, 0.0);
+        assert_eq!(loss.safe, 0.0);
+    }
+
+    #[test]
+    fn test_plan_for_month_reads_ytd_figures() {
+        let (_dir, conn) = test_db();
+        save_settings(&conn, Some(12_000.0), Some(20.0), Some(1_000.0)).unwrap();
+        add_txn(&conn, "2025-01-10", "Client Services", 10_000.0);
+        add_txn(&conn, "2025-02-01", "Payroll — Wages", -2_000.0);
+        add_txn(&conn, "2025-02-15", "Owner Draw / Distribution", -1_500.0);
+        // After the month: left out of YTD, still in cash
+        add_txn(&conn, "2025-03-05", "Client Services", 500.0);
+
+        let plan = plan_for_month(&conn, "2025-02").unwrap();
+        assert_eq!(plan.months_elapsed, 2);
+        assert_eq!(plan.profit, 8_000.0);
+        assert_eq!(plan.taxes, 1_600.0);
+        assert_eq!(plan.officer_comp, 2_000.0);
+        assert_eq!(plan.salary_due, 0.0);
+        assert_eq!(plan.distributions, 1_500.0);
+        assert_eq!(plan.available_from_profit, 4_900.0);
+        assert_eq!(plan.cash, 7_000.0);
+        assert_eq!(plan.cash_available, 4_400.0);
+        assert_eq!(plan.safe, 4_400.0);
+        assert_eq!(plan.planned, None);
+    }
+
+    #[test]
+    fn test_planned_vs_actual_history() {
+        let (_dir, conn) = test_db();
+        set_planned(&conn, "2025-02", 1_000.0).unwrap();
+        set_planned(&conn, "2025-02", 1_200.0).unwrap();
+        set_planned(&conn, "2025-03", 500.0).unwrap();
+        add_txn(&conn, "2025-02-15", "Owner Draw / Distribution", -1_500.0);
+        assert!(set_planned(&conn, "2025-13", 1.0).is_err());
+        assert!(set_planned(&conn, "2025-04", -1.0).is_err());
+
+        let months = history(&conn, 2025).unwrap();
+        assert_eq!(months.len(), 12);
+        assert_eq!(months[0].month, "2025-01");
+        assert_eq!(months[1].planned, Some(1_200.0));
+        assert_eq!(months[1].actual, 1_500.0);
+        assert_eq!(months[1].difference(), Some(300.0));
+        assert_eq!(months[2].difference(), Some(-500.0));
+        assert_eq!(months[0].difference(), None);
+
+        clear_planned(&conn, "2025-03").unwrap();
+        assert!(clear_planned(&conn, "2025-03").is_err());
+    }
+
+    #[test]
+    fn test_settings_validation() {
+        let (_dir, conn) = test_db();
+        assert_eq!(load_settings(&conn).tax_pct, DEFAULT_TAX_PCT);
+        assert!(save_settings(&conn, Some(-1.0), None, None).is_err());
+        assert!(save_settings(&conn, Some(50_000.0), Some(101.0), None).is_err());
+        // Nothing was saved by the failed call
+        assert_eq!(load_settings(&conn).salary, 0.0);
+        save_settings(&conn, None, None, Some(2_500.0)).unwrap();
+        assert_eq!(load_settings(&conn).reserve, 2_500.0);
+    }
+}
@@ -1,9 +1,13 @@
 use std::io::BufWriter;
 
 use printpdf::*;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
 
+use crate::data_quality::QualityReport;
 use crate::error::{NigelError, Result};
-use crate::fmt::money;
+use crate::fmt::{date, money};
+use crate::overview::{BookStatus, Overview};
 use crate::reports::*;
 
 // US Letter dimensions (mm)
@@ -19,11 +23,167 @@ const FONT_SIZE: f32 = 10.0;
 const TITLE_SIZE: f32 = 16.0;
 const SUBTITLE_SIZE: f32 = 10.0;
 
-fn approx_text_width(text: &str, size: f32) -> f32 {
-    text.len() as f32 * size * 0.18
+/// Helvetica advance widths for ASCII 32..=126, in 1/1000 em (from the AFM).
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0..?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P.._
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // `..o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p..~
+];
+
+/// Helvetica-Bold advance widths for ASCII 32..=126, in 1/1000 em.
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, // 0..?
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, // P.._
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, // `..o
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584, // p..~
+];
+
+const PT_TO_MM: f32 = 25.4 / 72.0;
+
+/// Helvetica advance width of one character in 1/1000 em. Non-ASCII
+/// characters are estimated from their display width (CJK counts double).
+fn helvetica_units(c: char, bold: bool) -> u32 {
+    let table = if bold {
+        &HELVETICA_BOLD_WIDTHS
+    } else {
+        &HELVETICA_WIDTHS
+    };
+    match c as u32 {
+        32..=126 => table[c as usize - 32] as u32,
+        _ => match UnicodeWidthChar::width(c).unwrap_or(0) {
+            0 => 0,
+            1 => 556,
+            _ => 1000,
+        },
+    }
+}
+
+/// Rendered width of `text` in mm when set in built-in Helvetica.
+fn text_width(text: &str, size: f32, bold: bool) -> f32 {
+    let units: u32 = text.chars().map(|c| helvetica_units(c, bold)).sum();
+    units as f32 / 1000.0 * size * PT_TO_MM
+}
+
+/// The fonts a document is set in, kept for measuring text. Embedded fonts
+/// hold the raw TTF bytes so advance widths come from the font itself.
+enum FontMetrics {
+    Helvetica,
+    Embedded { regular: Vec<u8>, bold: Vec<u8> },
+}
+
+impl FontMetrics {
+    /// Rendered width of `text` in mm. Characters missing from an embedded
+    /// font fall back to the Helvetica estimate.
+    fn text_width(&self, text: &str, size: f32, bold: bool) -> f32 {
+        let (regular, bold_data) = match self {
+            FontMetrics::Helvetica => return text_width(text, size, bold),
+            FontMetrics::Embedded { regular, bold } => (regular, bold),
+        };
+        let data = if bold { bold_data } else { regular };
+        let Ok(face) = ttf_parser::Face::parse(data, 0) else {
+            return text_width(text, size, bold);
+        };
+        let per_em = face.units_per_em().max(1) as f32;
+        let units: f32 = text
+            .chars()
+            .map(|c| {
+                face.glyph_index(c)
+                    .and_then(|g| face.glyph_hor_advance(g))
+                    .map(|adv| adv as f32 * 1000.0 / per_em)
+                    .unwrap_or_else(|| helvetica_units(c, bold) as f32)
+            })
+            .sum();
+        units / 1000.0 * size * PT_TO_MM
+    }
+}
+
+/// Characters after which a long token (e.g. `AMZN.COM/BILLWA*2K4`) can be
+/// broken without a hyphen.
+fn is_break_char(g: &str) -> bool {
+    matches!(
+        g,
+        "/" | "*" | "-" | "_" | "." | "," | ":" | ";" | "#" | "&" | "|" | "\\"
+    )
 }
 
-fn wrap_text(text: &str, max_width: f32, font_size: f32) -> Vec<String> {
+/// Split a token too wide for `max_width` into pieces that fit. Prefers
+/// breaking after punctuation; otherwise breaks between letters or digits
+/// with a trailing hyphen. Never splits a grapheme cluster.
+fn break_long_word(
+    metrics: &FontMetrics,
+    word: &str,
+    max_width: f32,
+    font_size: f32,
+    bold: bool,
+) -> Vec<String> {
+    let graphemes: Vec<&str> = word.graphemes(true).collect();
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    while start < graphemes.len() {
+        let rest: String = graphemes[start..].concat();
+        if metrics.text_width(&rest, font_size, bold) <= max_width {
+            pieces.push(rest);
+            break;
+        }
+        // Longest prefix that fits, leaving room for a hyphen
+        let hyphen = metrics.text_width("-", font_size, bold);
+        let mut end = start;
+        let mut width = 0.0;
+        let mut last_break = None;
+        while end < graphemes.len() {
+            let w = metrics.text_width(graphemes[end], font_size, bold);
+            if width + w + hyphen > max_width {
+                break;
+            }
+            width += w;
+            if is_break_char(graphemes[end]) {
+                last_break = Some(end + 1);
+            }
+            end += 1;
+        }
+        if end == start {
+            end = start + 1; // a single glyph wider than the column
+        }
+        let alnum = |g: &str| g.chars().all(|c| c.is_alphanumeric());
+        let piece = match last_break {
+            Some(b) if b > start && b < graphemes.len() => {
+                let piece = graphemes[start..b].concat();
+                start = b;
+                piece
+            }
+            _ => {
+                let mut piece = graphemes[start..end].concat();
+                // Soft hyphen between two letters/digits; CJK needs none
+                if end < graphemes.len()
+                    && alnum(graphemes[end - 1])
+                    && alnum(graphemes[end])
+                    && graphemes[end - 1].is_ascii()
+                {
+                    piece.push('-');
+                }
+                start = end;
+                piece
+            }
+        };
+        pieces.push(piece);
+    }
+    pieces
+}
+
+/// Wrap text into lines no wider than `max_width` mm, breaking on spaces
+/// first and splitting overlong tokens with `break_long_word`.
+fn wrap_text(
+    metrics: &FontMetrics,
+    text: &str,
+    max_width: f32,
+    font_size: f32,
+    bold: bool,
+) -> Vec<String> {
     let mut lines = Vec::new();
     let mut current = String::new();
 
@@ -34,13 +194,20 @@ fn wrap_text(text: &str, max_width: f32, font_size: f32) -> Vec<String> {
             format!("{current} {word}")
         };
 
-        if approx_text_width(&test, font_size) <= max_width {
+        if metrics.text_width(&test, font_size, bold) <= max_width {
             current = test;
-        } else {
-            if !current.is_empty() {
-                lines.push(current);
-            }
+            continue;
+        }
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if metrics.text_width(word, font_size, bold) <= max_width {
             current = word.to_string();
+        } else {
+            let mut pieces = break_long_word(metrics, word, max_width, font_size, bold);
+            // The last piece may share its line with the next word
+            current = pieces.pop().unwrap_or_default();
+            lines.extend(pieces);
         }
     }
 
@@ -68,24 +235,61 @@ struct PdfWriter {
     doc: PdfDocumentReference,
     font: IndirectFontRef,
     font_bold: IndirectFontRef,
+    metrics: FontMetrics,
     current_page: PdfPageIndex,
     current_layer: PdfLayerIndex,
     y: f32,
 }
 
+/// Read a TTF file and check it parses before handing it to printpdf.
+fn read_font(path: &str) -> Result<Vec<u8>> {
+    let path = crate::settings::shellexpand_path(path);
+    let data = std::fs::read(&path)
+        .map_err(|e| NigelError::Pdf(format!("Could not read PDF font {path}: {e}")))?;
+    ttf_parser::Face::parse(&data, 0)
+        .map_err(|e| NigelError::Pdf(format!("{path} is not a usable TrueType font: {e}")))?;
+    Ok(data)
+}
+
 impl PdfWriter {
+    /// Start a document in the font configured in settings (`pdf_font`),
+    /// or built-in Helvetica when none is set.
     fn new(title: &str) -> Result<Self> {
+        let settings = crate::settings::load_settings();
+        Self::with_fonts(
+            title,
+            settings.pdf_font.as_deref(),
+            settings.pdf_font_bold.as_deref(),
+        )
+    }
+
+    fn with_fonts(title: &str, regular: Option<&str>, bold: Option<&str>) -> Result<Self> {
         let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_W), Mm(PAGE_H), "Layer 1");
-        let font = doc
-            .add_builtin_font(BuiltinFont::Helvetica)
-            .map_err(|e| NigelError::Pdf(format!("{e:?}")))?;
-        let font_bold = doc
-            .add_builtin_font(BuiltinFont::HelveticaBold)
-            .map_err(|e| NigelError::Pdf(format!("{e:?}")))?;
+        let pdf_err = |e: printpdf::Error| NigelError::Pdf(format!("{e:?}"));
+        let (font, font_bold, metrics) = match regular.filter(|p| !p.trim().is_empty()) {
+            Some(regular_path) => {
+                let regular = read_font(regular_path)?;
+                let bold = match bold.filter(|p| !p.trim().is_empty()) {
+                    Some(bold_path) => read_font(bold_path)?,
+                    None => regular.clone(),
+                };
+                let font = doc.add_external_font(regular.as_slice()).map_err(pdf_err)?;
+                let font_bold = doc.add_external_font(bold.as_slice()).map_err(pdf_err)?;
+                (font, font_bold, FontMetrics::Embedded { regular, bold })
+            }
+            None => (
+                doc.add_builtin_font(BuiltinFont::Helvetica)
+                    .map_err(pdf_err)?,
+                doc.add_builtin_font(BuiltinFont::HelveticaBold)
+                    .map_err(pdf_err)?,
+                FontMetrics::Helvetica,
+            ),
+        };
         Ok(Self {
             doc,
             font,
             font_bold,
+            metrics,
             current_page: page,
             current_layer: layer,
             y: MARGIN_TOP,
@@ -164,7 +368,7 @@ impl PdfWriter {
                 match col.align {
                     Align::Left => self.text(headers[i], x, FONT_SIZE, true),
                     Align::Right => {
-                        let tw = approx_text_width(headers[i], FONT_SIZE);
+                        let tw = self.metrics.text_width(headers[i], FONT_SIZE, true);
                         self.text(headers[i], x + col.width - COL_PAD - tw, FONT_SIZE, true);
                     }
                 }
@@ -184,7 +388,7 @@ impl PdfWriter {
                 match col.align {
                     Align::Left => self.text(values[i], x, FONT_SIZE, bold),
                     Align::Right => {
-                        let tw = approx_text_width(values[i], FONT_SIZE);
+                        let tw = self.metrics.text_width(values[i], FONT_SIZE, bold);
                         self.text(values[i], x + col.width - COL_PAD - tw, FONT_SIZE, bold);
                     }
                 }
@@ -201,7 +405,13 @@ impl PdfWriter {
             .enumerate()
             .map(|(i, col)| {
                 if i < values.len() && !values[i].is_empty() {
-                    wrap_text(values[i], col.width - COL_PAD, font_size)
+                    wrap_text(
+                        &self.metrics,
+                        values[i],
+                        col.width - COL_PAD,
+                        font_size,
+                        bold,
+                    )
                 } else {
                     vec![String::new()]
                 }
@@ -210,9 +420,15 @@ impl PdfWriter {
 
         let max_lines = wrapped.iter().map(|w| w.len()).max().unwrap_or(1);
         let row_height = max_lines as f32 * ROW_H;
-        self.ensure_space(row_height);
+        // Keep a row together unless it is taller than a whole page; then
+        // let it continue onto following pages line by line.
+        let page_body = PAGE_H - MARGIN_TOP - MARGIN_BOTTOM;
+        if row_height <= page_body {
+            self.ensure_space(row_height);
+        }
 
         for line_idx in 0..max_lines {
+            self.ensure_space(ROW_H);
             let mut x = MARGIN_LEFT;
             for (col_idx, col) in cols.iter().enumerate() {
                 if col_idx < wrapped.len() {
@@ -221,7 +437,7 @@ impl PdfWriter {
                             match col.align {
                                 Align::Left => self.text(text, x, font_size, bold),
                                 Align::Right => {
-                                    let tw = approx_text_width(text, font_size);
+                                    let tw = self.metrics.text_width(text, font_size, bold);
                                     self.text(text, x + col.width - COL_PAD - tw, font_size, bold);
                                 }
                             }
@@ -263,6 +479,25 @@ impl PdfWriter {
 // Render functions
 // ---------------------------------------------------------------------------
 
+/// One side of the P&L: a top-level category on its own row, or a group
+/// header with its sub-categories indented below and a bold subtotal.
+fn pnl_group_rows(pdf: &mut PdfWriter, cols: &[Col], items: &[PnlItem], amount: fn(f64) -> f64) {
+    for group in pnl_groups(items) {
+        if group.is_single() {
+            let amt = money(amount(group.total));
+            pdf.table_row(cols, &[group.name, &amt], false);
+            continue;
+        }
+        pdf.table_row(cols, &[group.name, ""], false);
+        for (label, total) in &group.items {
+            let amt = money(amount(*total));
+            pdf.table_row(cols, &[&format!("    {label}"), &amt], false);
+        }
+        let amt = money(amount(group.total));
+        pdf.table_row(cols, &[&format!("Total {}", group.name), &amt], true);
+    }
+}
+
 pub fn render_pnl(report: &PnlReport, company: &str, date_range: &str) -> Result<Vec<u8>> {
     let mut pdf = PdfWriter::new("Profit & Loss")?;
     pdf.header("Profit & Loss", company, date_range);
@@ -281,10 +516,7 @@ pub fn render_pnl(report: &PnlReport, company: &str, date_range: &str) -> Result
 
     if !report.income.is_empty() {
         pdf.section_label("INCOME");
-        for item in &report.income {
-            let amt = money(item.total);
-            pdf.table_row(cols, &[&item.name, &amt], false);
-        }
+        pnl_group_rows(&mut pdf, cols, &report.income, |t| t);
         let total = money(report.total_income);
         pdf.table_row(cols, &["Total Income", &total], true);
         pdf.blank_row();
@@ -292,10 +524,7 @@ pub fn render_pnl(report: &PnlReport, company: &str, date_range: &str) -> Result
 
     if !report.expenses.is_empty() {
         pdf.section_label("EXPENSES");
-        for item in &report.expenses {
-            let amt = money(item.total.abs());
-            pdf.table_row(cols, &[&item.name, &amt], false);
-        }
+        pnl_group_rows(&mut pdf, cols, &report.expenses, f64::abs);
         let total = money(report.total_expenses.abs());
         pdf.table_row(cols, &["Total Expenses", &total], true);
         pdf.blank_row();
@@ -313,6 +542,94 @@ pub fn render_pnl(report: &PnlReport, company: &str, date_range: &str) -> Result
     pdf.into_bytes()
 }
 
+/// P&L comparison: the selected period, then each comparison period with
+/// its dollar and percent change. Category names wrap in the narrow first
+/// column.
+pub fn render_pnl_comparison(report: &PnlComparison, company: &str) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Profit & Loss Comparison")?;
+    let range = report.periods.join(" vs ");
+    pdf.header("Profit & Loss Comparison", company, &range);
+
+    let amount = |width: f32| Col {
+        width,
+        align: Align::Right,
+    };
+    let mut cols = vec![
+        Col {
+            width: 0.0,
+            align: Align::Left,
+        },
+        amount(23.0),
+    ];
+    // Date-range labels are too wide for an amount column; the subtitle
+    // already spells them out, so name the columns generically instead.
+    let fits = report
+        .periods
+        .iter()
+        .all(|p| pdf.metrics.text_width(p, FONT_SIZE, true) <= 23.0 - COL_PAD);
+    let generic: &[&str] = if report.periods.len() == 2 {
+        &["Selected", "Last year"]
+    } else {
+        &["Selected", "Prior", "Last year"]
+    };
+    let label = |i: usize| -> &str {
+        if fits {
+            &report.periods[i]
+        } else {
+            generic.get(i).copied().unwrap_or("")
+        }
+    };
+    let mut headers = vec!["Category", label(0)];
+    for i in 1..report.periods.len() {
+        cols.extend([amount(23.0), amount(21.0), amount(15.0)]);
+        headers.extend([label(i), "$ Chg", "% Chg"]);
+    }
+    let used: f32 = cols.iter().map(|c| c.width).sum();
+    cols[0].width = PAGE_W - MARGIN_LEFT - MARGIN_RIGHT - used;
+    pdf.table_header(&cols, &headers);
+
+    let line = |pdf: &mut PdfWriter, label: &str, values: &[f64], bold: bool| {
+        let text = crate::fmt::compare_columns(values);
+        let mut row: Vec<&str> = vec![label];
+        row.extend(text.iter().map(String::as_str));
+        pdf.table_row_wrapped(&cols, &row, bold, FONT_SIZE);
+    };
+    let abs = |values: &[f64]| values.iter().map(|v| v.abs()).collect::<Vec<_>>();
+
+    if !report.income.is_empty() {
+        pdf.section_label("INCOME");
+        for item in &report.income {
+            line(&mut pdf, &item.name, &item.totals, false);
+        }
+        line(&mut pdf, "Total Income", &report.total_income, true);
+        pdf.blank_row();
+    }
+
+    if !report.expenses.is_empty() {
+        pdf.section_label("EXPENSES");
+        for item in &report.expenses {
+            line(&mut pdf, &item.name, &abs(&item.totals), false);
+        }
+        line(
+            &mut pdf,
+            "Total Expenses",
+            &abs(&report.total_expenses),
+            true,
+        );
+        pdf.blank_row();
+    }
+
+    pdf.separator();
+    let label = if report.net[0] >= 0.0 {
+        "NET INCOME"
+    } else {
+        "NET LOSS"
+    };
+    line(&mut pdf, label, &report.net, true);
+
+    pdf.into_bytes()
+}
+
 pub fn render_expenses(
     report: &ExpenseBreakdown,
     company: &str,
@@ -408,6 +725,22 @@ pub fn render_tax(report: &TaxSummary, company: &str, date_range: &str) -> Resul
         let amt = money(item.total.abs());
         pdf.table_row(cols, &[&item.name, tl, &item.category_type, &amt], false);
     }
+    let warnings = report.limit_warnings();
+    if !warnings.is_empty() {
+        pdf.blank_row();
+        pdf.section_label("OVER A DEDUCTION LIMIT");
+        let note = &[Col {
+            width: 177.8,
+            align: Align::Left,
+        }];
+        for w in warnings
+            .iter()
+            .map(String::as_str)
+            .chain([crate::expense_limits::ADJUST_NOTE])
+        {
+            pdf.table_row_wrapped(note, &[w], false, FONT_SIZE);
+        }
+    }
 
     pdf.into_bytes()
 }
@@ -442,14 +775,23 @@ pub fn render_cashflow(
             align: Align::Right,
         },
     ];
-    pdf.table_header(cols, &["Month", "Inflows", "Outflows", "Net", "Running"]);
+    pdf.table_header(
+        cols,
+        &[
+            report.granularity.heading(),
+            "Inflows",
+            "Outflows",
+            "Net",
+            "Running",
+        ],
+    );
 
     for m in &report.months {
         let inf = money(m.inflows);
         let out = money(m.outflows.abs());
         let net = money(m.net);
         let run = money(m.running_balance);
-        pdf.table_row(cols, &[&m.month, &inf, &out, &net, &run], false);
+        pdf.table_row(cols, &[&m.label, &inf, &out, &net, &run], false);
     }
 
     pdf.into_bytes()
@@ -498,9 +840,16 @@ pub fn render_register(
     for r in &report.rows {
         let amt = money(r.amount);
         let cat = r.category.as_deref().unwrap_or("—");
+        let mut description = r.description.clone();
+        if let Some(memo) = &r.memo {
+            description.push_str(&format!(" — {memo}"));
+        }
+        if !r.tags.is_empty() {
+            description.push_str(&format!(" {}", crate::tags::label(&r.tags)));
+        }
         pdf.table_row_wrapped(
             cols,
-            &[&r.date, &r.description, &amt, cat, &r.account_name],
+            &[&date(&r.date), &description, &amt, cat, &r.account_name],
             false,
             font_size,
         );
@@ -524,162 +873,533 @@ pub fn render_flagged(rows: &[FlaggedTransaction], company: &str) -> Result<Vec<
 
     let cols = &[
         Col {
-            width: 15.0,
+            width: 13.0,
             align: Align::Left,
         },
         Col {
-            width: 27.0,
+            width: 24.0,
             align: Align::Left,
         },
         Col {
-            width: 80.0,
+            width: 52.0,
             align: Align::Left,
         },
         Col {
-            width: 30.0,
+            width: 26.0,
             align: Align::Right,
         },
         Col {
-            width: 25.8,
+            width: 22.8,
+            align: Align::Left,
+        },
+        Col {
+            width: 40.0,
             align: Align::Left,
         },
     ];
-    pdf.table_header(cols, &["ID", "Date", "Description", "Amount", "Account"]);
+    pdf.table_header(
+        cols,
+        &["ID", "Date", "Description", "Amount", "Account", "Reason"],
+    );
 
-    for r in rows {
+    let (unusual, review): (Vec<_>, Vec<_>) = rows.iter().partition(|r| r.is_unusual());
+    let flagged_row = |pdf: &mut PdfWriter, r: &FlaggedTransaction| {
         let id = r.id.to_string();
         let amt = money(r.amount.abs());
         pdf.table_row(
             cols,
-            &[&id, &r.date, &r.description, &amt, &r.account_name],
+            &[
+                &id,
+                &date(&r.date),
+                &r.description,
+                &amt,
+                &r.account_name,
+                r.flag_reason.as_deref().unwrap_or(""),
+            ],
             false,
         );
+    };
+    for r in review {
+        flagged_row(&mut pdf, r);
+    }
+    if !unusual.is_empty() {
+        pdf.section_label("UNUSUAL");
+        for r in unusual {
+            flagged_row(&mut pdf, r);
+        }
     }
 
     pdf.into_bytes()
 }
 
-pub fn render_balance(report: &BalanceReport, company: &str) -> Result<Vec<u8>> {
-    let mut pdf = PdfWriter::new("Cash Position")?;
-    pdf.header("Cash Position", company, "As of today");
+pub fn render_reimbursables(
+    report: &crate::reimbursables::ReimbursablesReport,
+    company: &str,
+) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Reimbursables")?;
+    pdf.header(
+        "Reimbursables",
+        company,
+        &format!("{} items", report.items.len()),
+    );
+
+    if !report.clients.is_empty() {
+        let ccols = &[
+            Col {
+                width: 50.0,
+                align: Align::Left,
+            },
+            Col {
+                width: 16.0,
+                align: Align::Right,
+            },
+            Col {
+                width: 30.0,
+                align: Align::Right,
+            },
+            Col {
+                width: 30.0,
+                align: Align::Right,
+            },
+            Col {
+                width: 30.0,
+                align: Align::Right,
+            },
+            Col {
+                width: 21.8,
+                align: Align::Right,
+            },
+        ];
+        pdf.table_header(
+            ccols,
+            &[
+                "Client",
+                "Count",
+                "Not invoiced",
+                "Invoiced",
+                "Outstanding",
+                "Oldest",
+            ],
+        );
+        for c in &report.clients {
+            let client = format!("#{}", c.client);
+            let count = c.count.to_string();
+            let not_invoiced = money(c.not_invoiced);
+            let invoiced = money(c.invoiced);
+            let total = money(c.total());
+            pdf.table_row(
+                ccols,
+                &[&client, &count, &not_invoiced, &invoiced, &total, &c.oldest],
+                false,
+            );
+        }
+        pdf.blank_row();
+    }
 
     let cols = &[
         Col {
-            width: 80.0,
+            width: 13.0,
             align: Align::Left,
         },
         Col {
-            width: 50.0,
+            width: 24.0,
             align: Align::Left,
         },
         Col {
-            width: 47.8,
+            width: 52.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 26.0,
             align: Align::Right,
         },
+        Col {
+            width: 22.8,
+            align: Align::Left,
+        },
+        Col {
+            width: 40.0,
+            align: Align::Left,
+        },
     ];
-    pdf.table_header(cols, &["Account", "Type", "Balance"]);
-
-    for a in &report.accounts {
-        let bal = money(a.balance);
-        pdf.table_row(cols, &[&a.name, &a.account_type, &bal], false);
+    pdf.table_header(
+        cols,
+        &["ID", "Date", "Description", "Amount", "Client", "Status"],
+    );
+    for r in &report.items {
+        let id = r.transaction_id.to_string();
+        let amt = money(r.amount);
+        let client = format!("#{}", r.client);
+        let status = r.status();
+        pdf.table_row(
+            cols,
+            &[&id, &date(&r.date), &r.description, &amt, &client, &status],
+            false,
+        );
     }
 
-    pdf.separator();
-    let total = money(report.total);
-    pdf.table_row(cols, &["Total", "", &total], true);
-
-    pdf.blank_row();
-    let ytd = money(report.ytd_net_income);
-    let ytd_label = format!("YTD Net Income: {ytd}");
-    pdf.text(&ytd_label, MARGIN_LEFT, FONT_SIZE, false);
-
     pdf.into_bytes()
 }
 
-pub fn render_k1(report: &K1PrepReport, company: &str, date_range: &str) -> Result<Vec<u8>> {
-    let mut pdf = PdfWriter::new("K-1 Preparation Worksheet")?;
-    pdf.header(
-        "K-1 Preparation Worksheet (Form 1120-S)",
-        company,
-        date_range,
-    );
+pub fn render_digest(digest: &crate::digest::Digest, company: &str) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Monthly Summary")?;
+    pdf.header("Monthly Summary", company, &digest.label());
 
-    // Income Summary
-    let summary_cols = &[
+    let text_col = &[Col {
+        width: 177.8,
+        align: Align::Left,
+    }];
+    let point_col = &[
         Col {
-            width: 130.0,
+            width: 6.0,
             align: Align::Left,
         },
         Col {
-            width: 47.8,
-            align: Align::Right,
+            width: 171.8,
+            align: Align::Left,
         },
     ];
-    pdf.section_label("Income Summary");
-    pdf.table_header(summary_cols, &["Item", "Amount"]);
-    let gr = money(report.gross_receipts);
-    pdf.table_row(summary_cols, &["Gross Receipts", &gr], false);
-    let oi = money(report.other_income);
-    pdf.table_row(summary_cols, &["Other Income", &oi], false);
-    let td = money(report.total_deductions);
-    pdf.table_row(summary_cols, &["Total Deductions", &td], false);
-    pdf.separator();
-    let label = if report.ordinary_business_income >= 0.0 {
-        "Ordinary Business Income"
-    } else {
-        "Ordinary Business Loss"
-    };
-    let obi = money(report.ordinary_business_income);
-    pdf.table_row(summary_cols, &[label, &obi], true);
-    pdf.blank_row();
-
-    // Deductions by Line
-    if !report.deduction_lines.is_empty() {
-        let ded_cols = &[
-            Col {
-                width: 30.0,
-                align: Align::Left,
-            },
-            Col {
-                width: 100.0,
-                align: Align::Left,
-            },
-            Col {
-                width: 47.8,
-                align: Align::Right,
-            },
-        ];
-        pdf.section_label("Deductions by Line");
-        pdf.table_header(ded_cols, &["Line", "Category", "Amount"]);
-        for item in &report.deduction_lines {
-            let amt = money(item.total);
-            pdf.table_row(
-                ded_cols,
-                &[&item.form_line, &item.category_name, &amt],
-                false,
-            );
+    for block in digest.narrative() {
+        match block {
+            crate::digest::Block::Paragraph(p) => pdf.table_row(text_col, &[&p], false),
+            crate::digest::Block::Points(points) => {
+                for p in &points {
+                    pdf.table_row(point_col, &["\u{2022}", p], false);
+                }
+            }
         }
         pdf.blank_row();
     }
 
-    // Schedule K Items
-    if !report.schedule_k_items.is_empty() {
-        let sk_cols = &[
+    if digest.transactions > 0 {
+        let cols = &[
             Col {
-                width: 30.0,
+                width: 77.8,
                 align: Align::Left,
             },
             Col {
-                width: 100.0,
-                align: Align::Left,
+                width: 50.0,
+                align: Align::Right,
             },
             Col {
-                width: 47.8,
+                width: 50.0,
                 align: Align::Right,
             },
         ];
-        pdf.section_label("Schedule K");
-        pdf.table_header(sk_cols, &["Line", "Item", "Amount"]);
+        pdf.table_header(cols, &["", "This month", "Prior month"]);
+        for (name, this, before) in [
+            ("Income", digest.income, digest.prior_income),
+            ("Expenses", digest.expenses, digest.prior_expenses),
+        ] {
+            pdf.table_row(cols, &[name, &money(this), &money(before)], false);
+        }
+        pdf.separator();
+        let net = money(digest.net);
+        let prior_net = money(digest.prior_net);
+        pdf.table_row(cols, &["Net", &net, &prior_net], true);
+    }
+
+    pdf.into_bytes()
+}
+
+/// The month-close packet: P&L, cash flow, the reconciliations that
+/// balanced, and the confirmation that nothing is left flagged.
+pub fn render_close(packet: &crate::month_close::ClosePacket) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Month Close")?;
+    pdf.header("Month Close", &packet.company, &packet.label());
+
+    let cols = &[
+        Col {
+            width: 130.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 47.8,
+            align: Align::Right,
+        },
+    ];
+    pdf.section_label("PROFIT & LOSS");
+    pdf.table_header(cols, &["Category", "Amount"]);
+    let report = &packet.pnl;
+    if !report.income.is_empty() {
+        pnl_group_rows(&mut pdf, cols, &report.income, |t| t);
+        let total = money(report.total_income);
+        pdf.table_row(cols, &["Total Income", &total], true);
+    }
+    if !report.expenses.is_empty() {
+        pnl_group_rows(&mut pdf, cols, &report.expenses, f64::abs);
+        let total = money(report.total_expenses.abs());
+        pdf.table_row(cols, &["Total Expenses", &total], true);
+    }
+    pdf.separator();
+    let label = if report.net >= 0.0 {
+        "NET INCOME"
+    } else {
+        "NET LOSS"
+    };
+    let net = money(report.net);
+    pdf.table_row(cols, &[label, &net], true);
+    pdf.blank_row();
+
+    let flow_cols = &[
+        Col {
+            width: 35.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 37.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 37.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 37.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 31.8,
+            align: Align::Right,
+        },
+    ];
+    pdf.section_label("CASH FLOW");
+    pdf.table_header(
+        flow_cols,
+        &["Month", "Inflows", "Outflows", "Net", "Running"],
+    );
+    for m in &packet.cashflow.months {
+        let inf = money(m.inflows);
+        let out = money(m.outflows.abs());
+        let net = money(m.net);
+        let run = money(m.running_balance);
+        pdf.table_row(flow_cols, &[&m.month, &inf, &out, &net, &run], false);
+    }
+    pdf.blank_row();
+
+    let cert_cols = &[
+        Col {
+            width: 62.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 36.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 36.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 43.8,
+            align: Align::Left,
+        },
+    ];
+    pdf.section_label("RECONCILIATION CERTIFICATES");
+    pdf.table_header(
+        cert_cols,
+        &["Account", "Statement", "Books", "  Reconciled"],
+    );
+    for c in &packet.certificates {
+        let statement = money(c.statement_balance);
+        let books = money(c.calculated_balance);
+        let at = format!("  {}", c.reconciled_at);
+        pdf.table_row_wrapped(
+            cert_cols,
+            &[&c.account, &statement, &books, &at],
+            false,
+            FONT_SIZE,
+        );
+    }
+    pdf.blank_row();
+
+    pdf.section_label("REVIEW");
+    let text_col = &[Col {
+        width: 177.8,
+        align: Align::Left,
+    }];
+    pdf.table_row_wrapped(text_col, &[&packet.review_statement()], false, FONT_SIZE);
+
+    pdf.into_bytes()
+}
+
+pub fn render_balance(report: &BalanceReport, company: &str) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Cash Position")?;
+    pdf.header("Cash Position", company, "As of today");
+
+    let cols = &[
+        Col {
+            width: 80.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 50.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 47.8,
+            align: Align::Right,
+        },
+    ];
+    pdf.table_header(cols, &["Account", "Type", "Balance"]);
+
+    for a in &report.accounts {
+        let bal = money(a.balance);
+        pdf.table_row(cols, &[&a.label(), &a.account_type, &bal], false);
+    }
+    if let Some(label) = report.archived_line() {
+        let bal = money(report.archived_balance);
+        pdf.table_row(cols, &[&label, "", &bal], false);
+    }
+
+    pdf.separator();
+    let total = money(report.total);
+    pdf.table_row(cols, &["Total", "", &total], true);
+
+    pdf.blank_row();
+    let ytd = money(report.ytd_net_income);
+    let ytd_label = format!("YTD Net Income: {ytd}");
+    pdf.text(&ytd_label, MARGIN_LEFT, FONT_SIZE, false);
+
+    pdf.into_bytes()
+}
+
+pub fn render_balance_history(
+    report: &BalanceHistory,
+    company: &str,
+    date_range: &str,
+) -> Result<Vec<u8>> {
+    let title = format!("Balance History: {}", report.account);
+    let mut pdf = PdfWriter::new(&title)?;
+    pdf.header(&title, company, date_range);
+
+    let cols = &[
+        Col {
+            width: 70.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 54.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 53.8,
+            align: Align::Right,
+        },
+    ];
+    pdf.table_header(cols, &[report.interval.heading(), "Change", "Balance"]);
+
+    let start = report.money(report.starting_balance);
+    pdf.table_row(cols, &["Starting balance", "", &start], false);
+    for p in report.table_points() {
+        let change = report.money(p.change);
+        let bal = report.money(p.balance);
+        pdf.table_row(cols, &[&date(&p.date), &change, &bal], false);
+    }
+
+    pdf.separator();
+    let end = report.money(report.ending_balance());
+    pdf.table_row(cols, &["Ending balance", "", &end], true);
+
+    if let (Some(low), Some(high)) = (report.low(), report.high()) {
+        pdf.blank_row();
+        let line = format!(
+            "Low: {} on {}   High: {} on {}",
+            report.money(low.balance),
+            date(&low.date),
+            report.money(high.balance),
+            date(&high.date)
+        );
+        pdf.text(&line, MARGIN_LEFT, FONT_SIZE, false);
+    }
+
+    pdf.into_bytes()
+}
+
+pub fn render_k1(report: &K1PrepReport, company: &str, date_range: &str) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("K-1 Preparation Worksheet")?;
+    pdf.header(
+        "K-1 Preparation Worksheet (Form 1120-S)",
+        company,
+        date_range,
+    );
+
+    // Income Summary
+    let summary_cols = &[
+        Col {
+            width: 130.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 47.8,
+            align: Align::Right,
+        },
+    ];
+    pdf.section_label("Income Summary");
+    pdf.table_header(summary_cols, &["Item", "Amount"]);
+    let gr = money(report.gross_receipts);
+    pdf.table_row(summary_cols, &["Gross Receipts", &gr], false);
+    let oi = money(report.other_income);
+    pdf.table_row(summary_cols, &["Other Income", &oi], false);
+    let td = money(report.total_deductions);
+    pdf.table_row(summary_cols, &["Total Deductions", &td], false);
+    pdf.separator();
+    let label = if report.ordinary_business_income >= 0.0 {
+        "Ordinary Business Income"
+    } else {
+        "Ordinary Business Loss"
+    };
+    let obi = money(report.ordinary_business_income);
+    pdf.table_row(summary_cols, &[label, &obi], true);
+    pdf.blank_row();
+
+    // Deductions by Line
+    if !report.deduction_lines.is_empty() {
+        let ded_cols = &[
+            Col {
+                width: 30.0,
+                align: Align::Left,
+            },
+            Col {
+                width: 100.0,
+                align: Align::Left,
+            },
+            Col {
+                width: 47.8,
+                align: Align::Right,
+            },
+        ];
+        pdf.section_label("Deductions by Line");
+        pdf.table_header(ded_cols, &["Line", "Category", "Amount"]);
+        for item in &report.deduction_lines {
+            let amt = money(item.total);
+            pdf.table_row(
+                ded_cols,
+                &[&item.form_line, &item.category_name, &amt],
+                false,
+            );
+        }
+        pdf.blank_row();
+    }
+
+    // Schedule K Items
+    if !report.schedule_k_items.is_empty() {
+        let sk_cols = &[
+            Col {
+                width: 30.0,
+                align: Align::Left,
+            },
+            Col {
+                width: 100.0,
+                align: Align::Left,
+            },
+            Col {
+                width: 47.8,
+                align: Align::Right,
+            },
+        ];
+        pdf.section_label("Schedule K");
+        pdf.table_header(sk_cols, &["Line", "Item", "Amount"]);
         for item in &report.schedule_k_items {
             let amt = money(item.total.abs());
             pdf.table_row(
@@ -691,53 +1411,609 @@ pub fn render_k1(report: &K1PrepReport, company: &str, date_range: &str) -> Resu
         pdf.blank_row();
     }
 
-    // Line 19 Other Deductions detail
-    if !report.other_deductions.is_empty() {
-        let od_cols = &[
-            Col {
-                width: 80.0,
-                align: Align::Left,
-            },
-            Col {
-                width: 48.9,
-                align: Align::Right,
-            },
-            Col {
-                width: 48.9,
-                align: Align::Right,
-            },
-        ];
-        pdf.section_label("Line 19 — Other Deductions");
-        pdf.table_header(od_cols, &["Category", "Full Amount", "Deductible"]);
-        for item in &report.other_deductions {
-            let label = if item.deductible < item.total {
-                format!("{} (50%)", item.category_name)
-            } else {
-                item.category_name.clone()
-            };
-            let full = money(item.total);
-            let ded = money(item.deductible);
-            pdf.table_row(od_cols, &[&label, &full, &ded], false);
+    // Line 19 Other Deductions detail
+    if !report.other_deductions.is_empty() {
+        let od_cols = &[
+            Col {
+                width: 80.0,
+                align: Align::Left,
+            },
+            Col {
+                width: 48.9,
+                align: Align::Right,
+            },
+            Col {
+                width: 48.9,
+                align: Align::Right,
+            },
+        ];
+        pdf.section_label("Line 19 — Other Deductions");
+        pdf.table_header(od_cols, &["Category", "Full Amount", "Deductible"]);
+        for item in &report.other_deductions {
+            let label = if item.deductible < item.total {
+                format!("{} (50%)", item.category_name)
+            } else {
+                item.category_name.clone()
+            };
+            let full = money(item.total);
+            let ded = money(item.deductible);
+            pdf.table_row(od_cols, &[&label, &full, &ded], false);
+        }
+        pdf.separator();
+        let odt = money(report.other_deductions_total);
+        pdf.table_row(od_cols, &["Total Other Deductions", "", &odt], true);
+    }
+
+    // Validation notes
+    if report.validation.uncategorized_count > 0 {
+        pdf.blank_row();
+        let warning = format!(
+            "Warning: {} uncategorized transactions",
+            report.validation.uncategorized_count
+        );
+        pdf.text(&warning, MARGIN_LEFT, FONT_SIZE, true);
+        pdf.y += ROW_H;
+    }
+    if let Some(pct) = report
+        .validation
+        .ownership_pct_total
+        .filter(|_| report.validation.ownership_incomplete())
+    {
+        pdf.blank_row();
+        let warning = format!("Warning: shareholders own {pct}% in total");
+        pdf.text(&warning, MARGIN_LEFT, FONT_SIZE, true);
+        pdf.y += ROW_H;
+    }
+
+    // One page per shareholder
+    let k1_cols = &[
+        Col {
+            width: 30.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 100.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 47.8,
+            align: Align::Right,
+        },
+    ];
+    for holder in &report.shareholders {
+        pdf.new_page();
+        pdf.header(
+            &format!("Schedule K-1 \u{2014} {}", holder.name),
+            company,
+            date_range,
+        );
+        pdf.section_label(&format!("Shareholder's share ({}%)", holder.ownership_pct));
+        pdf.table_header(k1_cols, &["Line", "Item", "Amount"]);
+        let obi = money(holder.ordinary_business_income);
+        pdf.table_row(
+            k1_cols,
+            &["1", "Ordinary business income (loss)", &obi],
+            false,
+        );
+        for item in &holder.schedule_k_items {
+            let amt = money(item.total);
+            pdf.table_row(
+                k1_cols,
+                &[
+                    item.form_line.trim_start_matches("K-"),
+                    &item.category_name,
+                    &amt,
+                ],
+                false,
+            );
+        }
+        let dist = money(holder.distributions);
+        pdf.table_row(k1_cols, &["16d", "Distributions", &dist], false);
+    }
+
+    pdf.into_bytes()
+}
+
+pub fn render_setup_docs(docs: &crate::setup_docs::SetupDocs, company: &str) -> Result<Vec<u8>> {
+    use crate::setup_docs::{SetupDocs, RULES_NOTE, TYPE_HEADINGS};
+
+    let mut pdf = PdfWriter::new("Chart of Accounts and Rules")?;
+    let prepared = format!("Prepared {}", docs.prepared.format("%B %-d, %Y"));
+    pdf.header("Chart of Accounts and Rules", company, &prepared);
+
+    let chart_cols = &[
+        Col {
+            width: 87.8,
+            align: Align::Left,
+        },
+        Col {
+            width: 50.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 40.0,
+            align: Align::Left,
+        },
+    ];
+    for (category_type, heading) in TYPE_HEADINGS {
+        let mut categories = docs.of_type(category_type).peekable();
+        if categories.peek().is_none() {
+            continue;
+        }
+        pdf.section_label(heading);
+        pdf.table_header(chart_cols, &["Category", "Tax Line", "Form Line"]);
+        for c in categories {
+            let name = SetupDocs::indented_name(c);
+            pdf.table_row(
+                chart_cols,
+                &[
+                    &name,
+                    c.tax_line.as_deref().unwrap_or(""),
+                    c.form_line.as_deref().unwrap_or(""),
+                ],
+                false,
+            );
+        }
+        pdf.blank_row();
+    }
+
+    pdf.new_page();
+    pdf.section_label(&format!("Rules ({} active)", docs.rules.len()));
+    let note_col = &[Col {
+        width: 177.8,
+        align: Align::Left,
+    }];
+    pdf.table_row(note_col, &[RULES_NOTE], false);
+    pdf.blank_row();
+    let rule_cols = &[
+        Col {
+            width: 14.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 22.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 45.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 55.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 28.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 13.8,
+            align: Align::Right,
+        },
+    ];
+    pdf.table_header(
+        rule_cols,
+        &["Priority", "Match", "Pattern", "Category", "Vendor", "Hits"],
+    );
+    for r in &docs.rules {
+        let priority = r.priority.to_string();
+        let hits = r.hit_count.to_string();
+        pdf.table_row(
+            rule_cols,
+            &[
+                &priority,
+                &r.match_type,
+                &r.pattern,
+                &r.category,
+                r.vendor.as_deref().unwrap_or(""),
+                &hits,
+            ],
+            false,
+        );
+    }
+
+    pdf.into_bytes()
+}
+
+pub fn render_deductions(
+    report: &DeductionsReport,
+    company: &str,
+    date_range: &str,
+) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Deductions")?;
+    pdf.header("Deductions", company, date_range);
+
+    // Standard mileage log
+    if !report.trips.is_empty() {
+        let trip_cols = &[
+            Col {
+                width: 25.0,
+                align: Align::Left,
+            },
+            Col {
+                width: 77.8,
+                align: Align::Left,
+            },
+            Col {
+                width: 22.0,
+                align: Align::Right,
+            },
+            Col {
+                width: 22.0,
+                align: Align::Right,
+            },
+            Col {
+                width: 31.0,
+                align: Align::Right,
+            },
+        ];
+        pdf.section_label("Standard Mileage");
+        pdf.table_header(
+            trip_cols,
+            &["Date", "Purpose", "Miles", "Rate", "Deduction"],
+        );
+        for t in &report.trips {
+            let miles = format!("{:.1}", t.miles);
+            let rate = format!("${:.3}", t.rate);
+            let amt = money(t.deduction);
+            pdf.table_row_wrapped(
+                trip_cols,
+                &[&date(&t.date), &t.purpose, &miles, &rate, &amt],
+                false,
+                8.0,
+            );
+        }
+        pdf.separator();
+        let miles = format!("{:.1}", report.total_miles);
+        let total = money(report.mileage_deduction);
+        pdf.table_row(trip_cols, &["Total", "", &miles, "", &total], true);
+        pdf.blank_row();
+    }
+
+    // Home office allocation
+    let cols = &[
+        Col {
+            width: 130.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 47.8,
+            align: Align::Right,
+        },
+    ];
+    pdf.section_label("Home Office");
+    pdf.table_header(cols, &["Item", "Amount"]);
+    let costs = money(report.home_office.annual_costs);
+    pdf.table_row(cols, &["Annual Home Costs", &costs], false);
+    let pct = format!("{}%", report.home_office.percent);
+    pdf.table_row(cols, &["Business Use", &pct], false);
+    let alloc = money(report.home_office_deduction);
+    pdf.table_row(cols, &["Home Office Allocation", &alloc], true);
+    pdf.blank_row();
+
+    pdf.separator();
+    let total = money(report.total);
+    pdf.table_row(cols, &["Total Deductions", &total], true);
+
+    pdf.into_bytes()
+}
+
+pub fn render_compensation(
+    report: &CompensationReport,
+    company: &str,
+    date_range: &str,
+) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Compensation")?;
+    pdf.header("Officer Compensation", company, date_range);
+
+    let cols = &[
+        Col {
+            width: 45.8,
+            align: Align::Left,
+        },
+        Col {
+            width: 33.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 33.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 33.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 33.0,
+            align: Align::Right,
+        },
+    ];
+    pdf.table_header(
+        cols,
+        &[
+            "Quarter",
+            "Officer Comp",
+            "Distributions",
+            "YTD Comp",
+            "YTD Distrib.",
+        ],
+    );
+    for q in &report.quarters {
+        let comp = money(q.officer_comp);
+        let dist = money(q.distributions);
+        let ytd_comp = money(q.cumulative_comp);
+        let ytd_dist = money(q.cumulative_distributions);
+        pdf.table_row(cols, &[&q.label, &comp, &dist, &ytd_comp, &ytd_dist], false);
+    }
+    pdf.separator();
+    let comp = money(report.officer_comp);
+    let dist = money(report.distributions);
+    pdf.table_row(cols, &["Total", &comp, &dist, "", ""], true);
+
+    pdf.blank_row();
+    let share = report
+        .officer_share
+        .map(|s| format!("{s:.0}%"))
+        .unwrap_or_else(|| "n/a".into());
+    let summary = format!(
+        "Officer share of owner pay: {share} (target {}%)",
+        report.target_pct
+    );
+    pdf.text(&summary, MARGIN_LEFT, FONT_SIZE, false);
+    pdf.y += ROW_H;
+    if report.below_target() {
+        pdf.text(
+            "Warning: Distributions are outpacing officer pay - review reasonable comp",
+            MARGIN_LEFT,
+            FONT_SIZE,
+            true,
+        );
+        pdf.y += ROW_H;
+    }
+
+    pdf.into_bytes()
+}
+
+pub fn render_tags(items: &[TagItem], company: &str, date_range: &str) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Tags")?;
+    pdf.header("Tags", company, date_range);
+
+    let cols = &[
+        Col {
+            width: 61.8,
+            align: Align::Left,
+        },
+        Col {
+            width: 20.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 32.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 32.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 32.0,
+            align: Align::Right,
+        },
+    ];
+    pdf.table_header(cols, &["Tag", "Count", "Income", "Expenses", "Net"]);
+    for item in items {
+        let tag = format!("#{}", item.tag);
+        let count = item.count.to_string();
+        let income = money(item.income);
+        let expenses = money(item.expenses.abs());
+        let net = money(item.net);
+        pdf.table_row(cols, &[&tag, &count, &income, &expenses, &net], false);
+    }
+    if items.is_empty() {
+        pdf.table_row(cols, &["No tagged transactions.", "", "", "", ""], false);
+    }
+
+    pdf.into_bytes()
+}
+
+pub fn render_clients(report: &ClientsReport, company: &str, date_range: &str) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Clients")?;
+    pdf.header("Client Profitability", company, date_range);
+
+    let cols = &[
+        Col {
+            width: 61.8,
+            align: Align::Left,
+        },
+        Col {
+            width: 32.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 32.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 32.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 20.0,
+            align: Align::Right,
+        },
+    ];
+    pdf.table_header(
+        cols,
+        &["Client", "Revenue", "Direct Costs", "Margin", "Margin %"],
+    );
+    let row = |pdf: &mut PdfWriter, c: &ClientProfit, bold: bool| {
+        let revenue = money(c.revenue);
+        let costs = money(c.direct_costs);
+        let margin = money(c.margin());
+        let pct = c
+            .margin_pct()
+            .map(|p| format!("{p:.0}%"))
+            .unwrap_or_else(|| "n/a".into());
+        pdf.table_row(cols, &[&c.name, &revenue, &costs, &margin, &pct], bold);
+    };
+    for c in &report.clients {
+        row(&mut pdf, c, false);
+    }
+    if report.clients.is_empty() {
+        pdf.table_row(
+            cols,
+            &["No income or expenses assigned to clients.", "", "", "", ""],
+            false,
+        );
+    } else {
+        pdf.separator();
+        row(&mut pdf, &report.total(), true);
+    }
+    if report.unassigned_revenue.abs() >= 0.005 {
+        pdf.blank_row();
+        let note = format!(
+            "Income not assigned to a client: {}",
+            money(report.unassigned_revenue)
+        );
+        pdf.text(&note, MARGIN_LEFT, FONT_SIZE, false);
+        pdf.y += ROW_H;
+    }
+
+    pdf.into_bytes()
+}
+
+pub fn render_books(overview: &Overview) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Books Overview")?;
+    pdf.header("Books Overview", "", "As of today");
+
+    let cols = &[
+        Col {
+            width: 77.8,
+            align: Align::Left,
+        },
+        Col {
+            width: 36.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 36.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 28.0,
+            align: Align::Right,
+        },
+    ];
+    pdf.table_header(cols, &["Book", "Cash", "YTD Net", "Year"]);
+    for book in &overview.books {
+        let mut label = book.label();
+        if book.current {
+            label.push_str(" (current)");
+        }
+        match &book.status {
+            BookStatus::Open {
+                home_currency,
+                cash,
+                ytd_net,
+                fiscal_label,
+                ..
+            } => {
+                let cash = overview.money(*cash, home_currency);
+                let net = overview.money(*ytd_net, home_currency);
+                pdf.table_row(cols, &[&label, &cash, &net, fiscal_label], false);
+            }
+            status => {
+                pdf.table_row(cols, &[&label, "", "", ""], false);
+                let note = status.note().unwrap_or_default();
+                pdf.text(&note, MARGIN_LEFT + 4.0, FONT_SIZE, false);
+                pdf.y += ROW_H;
+            }
         }
-        pdf.separator();
-        let odt = money(report.other_deductions_total);
-        pdf.table_row(od_cols, &["Total Other Deductions", "", &odt], true);
     }
-
-    // Validation notes
-    if report.validation.uncategorized_count > 0 {
+    if let Some((currency, cash, net)) = overview.combined() {
+        pdf.separator();
+        let cash = overview.money(cash, &currency);
+        let net = overview.money(net, &currency);
+        pdf.table_row(cols, &["Combined", &cash, &net, ""], true);
+    } else if overview.open_count() > 1 {
         pdf.blank_row();
-        let warning = format!(
-            "Warning: {} uncategorized transactions",
-            report.validation.uncategorized_count
+        pdf.text(
+            "The books use different home currencies, so they aren't combined.",
+            MARGIN_LEFT,
+            FONT_SIZE,
+            false,
         );
-        pdf.text(&warning, MARGIN_LEFT, FONT_SIZE, true);
         pdf.y += ROW_H;
     }
 
     pdf.into_bytes()
 }
 
+pub fn render_quality(report: &QualityReport, company: &str) -> Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new("Data Quality")?;
+    pdf.header(
+        "Data Quality",
+        company,
+        &format!("{} out of range", report.total()),
+    );
+    if report.total() == 0 {
+        pdf.text(&report.clean_note(), MARGIN_LEFT, FONT_SIZE, false);
+        pdf.y += ROW_H;
+        return pdf.into_bytes();
+    }
+
+    let cols = &[
+        Col {
+            width: 13.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 24.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 78.0,
+            align: Align::Left,
+        },
+        Col {
+            width: 26.0,
+            align: Align::Right,
+        },
+        Col {
+            width: 36.8,
+            align: Align::Left,
+        },
+    ];
+    pdf.table_header(cols, &["ID", "Date", "Description", "Amount", "Account"]);
+    let sections = [
+        (report.future_heading(), &report.future),
+        (report.before_epoch_heading(), &report.before_epoch),
+    ];
+    for (heading, rows) in sections {
+        if rows.is_empty() {
+            continue;
+        }
+        pdf.section_label(&heading.to_uppercase());
+        for r in rows {
+            let id = r.id.to_string();
+            let amt = money(r.amount);
+            pdf.table_row(
+                cols,
+                &[&id, &date(&r.date), &r.description, &amt, &r.account_name],
+                false,
+            );
+        }
+    }
+    pdf.blank_row();
+    let note = &[Col {
+        width: 177.8,
+        align: Align::Left,
+    }];
+    pdf.table_row_wrapped(note, &[crate::data_quality::FIX_NOTE], false, FONT_SIZE);
+
+    pdf.into_bytes()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -789,16 +2065,30 @@ mod tests {
     fn test_render_pnl_produces_pdf() {
         let (_dir, conn) = test_db();
         seed(&conn);
-        let report = get_pnl(&conn, Some(2025), None, None, None).unwrap();
+        let report = get_pnl(&conn, Some(2025), None, None, None, None, &[]).unwrap();
         let bytes = render_pnl(&report, "Test Corp", "FY 2025").unwrap();
         assert!(bytes.starts_with(b"%PDF"));
     }
 
+    #[test]
+    fn test_render_pnl_comparison_produces_pdf() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        for (month, from, to) in [
+            (Some(3), None, None),
+            (None, Some("2025-01-01"), Some("2025-03-31")),
+        ] {
+            let report = get_pnl_comparison(&conn, Some(2025), month, from, to, None, &[]).unwrap();
+            let bytes = render_pnl_comparison(&report, "Test Corp").unwrap();
+            assert!(bytes.starts_with(b"%PDF"));
+        }
+    }
+
     #[test]
     fn test_render_expenses_produces_pdf() {
         let (_dir, conn) = test_db();
         seed(&conn);
-        let report = get_expense_breakdown(&conn, Some(2025), None).unwrap();
+        let report = get_expense_breakdown(&conn, Some(2025), None, None, &[]).unwrap();
         let bytes = render_expenses(&report, "Test Corp", "FY 2025").unwrap();
         assert!(bytes.starts_with(b"%PDF"));
     }
@@ -816,11 +2106,34 @@ mod tests {
     fn test_render_cashflow_produces_pdf() {
         let (_dir, conn) = test_db();
         seed(&conn);
-        let report = get_cashflow(&conn, Some(2025), None).unwrap();
+        let report = get_cashflow(&conn, Some(2025), None, &[]).unwrap();
         let bytes = render_cashflow(&report, "Test Corp", "FY 2025").unwrap();
         assert!(bytes.starts_with(b"%PDF"));
     }
 
+    #[test]
+    fn test_render_close_produces_pdf() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        let packet = crate::month_close::ClosePacket {
+            year: 2025,
+            month: 1,
+            company: "Test Corp".into(),
+            pnl: get_pnl(&conn, Some(2025), Some(1), None, None, None, &[]).unwrap(),
+            cashflow: get_cashflow(&conn, Some(2025), Some(1), &[]).unwrap(),
+            certificates: vec![crate::month_close::Certificate {
+                account: "Test".into(),
+                statement_balance: 950.0,
+                calculated_balance: 950.0,
+                reconciled_at: "2025-02-03 10:00:00".into(),
+            }],
+            transactions: 2,
+            generated_at: "2025-02-03 10:05".into(),
+        };
+        let bytes = render_close(&packet).unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
     #[test]
     fn test_render_flagged_produces_pdf() {
         let (_dir, conn) = test_db();
@@ -834,7 +2147,7 @@ mod tests {
     fn test_render_balance_produces_pdf() {
         let (_dir, conn) = test_db();
         seed(&conn);
-        let report = get_balance(&conn).unwrap();
+        let report = get_balance(&conn, false).unwrap();
         let bytes = render_balance(&report, "Test Corp").unwrap();
         assert!(bytes.starts_with(b"%PDF"));
     }
@@ -847,4 +2160,290 @@ mod tests {
         let bytes = render_k1(&report, "Test Corp", "FY 2025").unwrap();
         assert!(bytes.starts_with(b"%PDF"));
     }
+
+    #[test]
+    fn test_render_deductions_produces_pdf() {
+        let (_dir, conn) = test_db();
+        crate::deductions::add_mileage(&conn, "2025-03-02", 48.0, "client visit").unwrap();
+        let report = get_deductions(&conn, Some(2025)).unwrap();
+        let bytes = render_deductions(&report, "Test Corp", "FY 2025").unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_compensation_produces_pdf() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        let report = get_compensation(&conn, Some(2025)).unwrap();
+        let bytes = render_compensation(&report, "Test Corp", "FY 2025").unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_tags_produces_pdf() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        let id: i64 = conn
+            .query_row("SELECT MIN(id) FROM transactions", [], |r| r.get(0))
+            .unwrap();
+        crate::tags::set_transaction_tags(&conn, id, &["client-acme".to_string()]).unwrap();
+        let report = get_tags_report(&conn, Some(2025), None).unwrap();
+        assert_eq!(report.len(), 1);
+        let bytes = render_tags(&report, "Test Corp", "FY 2025").unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_clients_produces_pdf() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        let id: i64 = conn
+            .query_row("SELECT MIN(id) FROM transactions", [], |r| r.get(0))
+            .unwrap();
+        crate::clients::assign(&conn, &[id], Some("Acme")).unwrap();
+        let report = get_clients(&conn, Some(2025)).unwrap();
+        assert_eq!(report.clients.len(), 1);
+        let bytes = render_clients(&report, "Test Corp", "FY 2025").unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_books_produces_pdf() {
+        let open = |name: &str, current: bool| crate::overview::BookLine {
+            dir: format!("/books/{name}").into(),
+            current,
+            status: BookStatus::Open {
+                company_name: Some(name.into()),
+                home_currency: "USD".into(),
+                cash: 1000.0,
+                ytd_net: -50.0,
+                fiscal_label: "FY 2025".into(),
+            },
+        };
+        let overview = Overview {
+            books: vec![
+                open("Acme LLC", true),
+                open("Household", false),
+                crate::overview::BookLine {
+                    dir: "/books/locked".into(),
+                    current: false,
+                    status: BookStatus::Locked,
+                },
+            ],
+        };
+        let bytes = render_books(&overview).unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_quality_produces_pdf() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, '2205-03-14', 'TYPO YEAR', -12.0)",
+            [],
+        )
+        .unwrap();
+        let bounds = crate::data_quality::DateBounds::new(
+            chrono::NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            30,
+            crate::data_quality::default_epoch(),
+        );
+        let report = crate::data_quality::get_report(&conn, bounds).unwrap();
+        assert_eq!(report.future.len(), 1);
+        let bytes = render_quality(&report, "Test Corp").unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    /// Assert every wrapped line fits and no characters were lost.
+    fn assert_wrapped(text: &str, max: f32) -> Vec<String> {
+        let lines = wrap_text(&FontMetrics::Helvetica, text, max, 8.0, false);
+        for line in &lines {
+            assert!(text_width(line, 8.0, false) <= max, "{line:?} too wide");
+        }
+        let rejoined: String = lines.concat().replace(['-', ' '], "");
+        assert_eq!(rejoined, text.replace(['-', ' '], ""));
+        lines
+    }
+
+    #[test]
+    fn test_text_width_uses_font_metrics() {
+        assert!(text_width("WWWW", FONT_SIZE, false) > text_width("iiii", FONT_SIZE, false) * 3.0);
+        assert!(text_width("Total", FONT_SIZE, true) > text_width("Total", FONT_SIZE, false));
+        // Accented letters measure like their ASCII counterparts, CJK wider
+        assert_eq!(
+            text_width("Café", FONT_SIZE, false),
+            text_width("Cafe", FONT_SIZE, false)
+        );
+        assert!(text_width("東京", FONT_SIZE, false) > text_width("ab", FONT_SIZE, false));
+    }
+
+    #[test]
+    fn test_wrap_prefers_spaces() {
+        let max = text_width("CHECKCARD 0315 AMAZON", 8.0, false);
+        let lines = assert_wrapped("CHECKCARD 0315 AMAZON MKTP US SEATTLE WA", max);
+        assert_eq!(lines[0], "CHECKCARD 0315 AMAZON");
+        assert!(lines.iter().all(|l| !l.ends_with('-')));
+    }
+
+    #[test]
+    fn test_wrap_breaks_long_token_after_punctuation() {
+        let max = 40.0;
+        let lines = assert_wrapped(
+            "AMZN MKTP US*2K4LL0XYZ AMZN.COM/BILLWA/REF/24692165075000",
+            max,
+        );
+        assert!(lines.len() > 1);
+        assert!(
+            lines
+                .iter()
+                .any(|l| l.ends_with('/') || l.ends_with('*') || l.ends_with('.')),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn test_wrap_hyphenates_unbroken_text() {
+        let text = "W".repeat(120);
+        let lines = assert_wrapped(&text, 60.0);
+        assert!(lines.len() > 1);
+        for line in &lines[..lines.len() - 1] {
+            assert!(line.ends_with('-'), "{line}");
+        }
+        let digits = "4".repeat(200);
+        assert_wrapped(&digits, 30.0);
+    }
+
+    #[test]
+    fn test_wrap_breaks_unspaced_cjk_without_hyphens() {
+        let max = text_width("abcdefghij", 8.0, false);
+        let lines = assert_wrapped("株式会社東京商事月額利用料", max);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| !l.ends_with('-')));
+        assert_eq!(lines.concat(), "株式会社東京商事月額利用料");
+    }
+
+    #[test]
+    fn test_wrap_keeps_emoji_clusters_whole() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let lines = wrap_text(
+            &FontMetrics::Helvetica,
+            &family.repeat(5),
+            text_width("ab", 8.0, false),
+            8.0,
+            false,
+        );
+        for line in &lines {
+            assert_eq!(line.matches(family).count() * family.len(), line.len());
+        }
+    }
+
+    #[test]
+    fn test_render_register_with_worst_case_descriptions() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        let long = "X".repeat(5000); // taller than a page once wrapped
+        for desc in [
+            "POS PURCHASE/NONREF/00012345678901234567890123456789 SQ *BLUE BOTTLE COFFEE",
+            long.as_str(),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount) \
+                 VALUES (1, '2025-02-01', ?1, -12.5)",
+                [desc],
+            )
+            .unwrap();
+        }
+        let report = get_register(&conn, Some(2025), None, None, None, None, None).unwrap();
+        let bytes = render_register(&report, "Test Corp", "FY 2025").unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_register_with_non_ascii_descriptions() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, '2025-02-01', '株式会社東京商事 月額利用料 🍎 Café Crème', -12.5)",
+            [],
+        )
+        .unwrap();
+        let report = get_register(&conn, Some(2025), None, None, None, None, None).unwrap();
+        let bytes = render_register(&report, "Test Corp", "FY 2025").unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_render_register_with_memo() {
+        let (_dir, conn) = test_db();
+        seed(&conn);
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, memo) \
+             VALUES (1, '2025-02-01', 'DELTA AIR', -412.0, 'Client kickoff in Denver')",
+            [],
+        )
+        .unwrap();
+        let report = get_register(&conn, Some(2025), None, None, None, None, None).unwrap();
+        let bytes = render_register(&report, "Test Corp", "FY 2025").unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    const SYSTEM_FONT: &str = "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf";
+
+    #[test]
+    fn test_missing_font_path_errors() {
+        let err = PdfWriter::with_fonts("Test", Some("/nonexistent/font.ttf"), None)
+            .err()
+            .expect("missing font should fail");
+        assert!(err.to_string().contains("/nonexistent/font.ttf"));
+    }
+
+    #[test]
+    fn test_invalid_font_file_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bogus.ttf");
+        std::fs::write(&path, b"not a font").unwrap();
+        let err = PdfWriter::with_fonts("Test", Some(path.to_str().unwrap()), None)
+            .err()
+            .expect("invalid font should fail");
+        assert!(err.to_string().contains("not a usable TrueType font"));
+    }
+
+    #[test]
+    fn test_blank_font_setting_uses_helvetica() {
+        let pdf = PdfWriter::with_fonts("Test", Some("  "), None).unwrap();
+        assert!(matches!(pdf.metrics, FontMetrics::Helvetica));
+    }
+
+    #[test]
+    fn test_embedded_font_renders_accented_company() {
+        if !std::path::Path::new(SYSTEM_FONT).exists() {
+            return; // no system TTF available to embed
+        }
+        let mut pdf = PdfWriter::with_fonts("Profit & Loss", Some(SYSTEM_FONT), None).unwrap();
+        assert!(matches!(pdf.metrics, FontMetrics::Embedded { .. }));
+        // Measured from the font itself, so differs from the Helvetica table
+        assert_ne!(
+            pdf.metrics.text_width("Łódź Café", FONT_SIZE, false),
+            text_width("Łódź Café", FONT_SIZE, false)
+        );
+        pdf.header("Profit & Loss", "Żółć Łódź Café Société", "FY 2025");
+        let cols = [
+            Col {
+                width: 120.0,
+                align: Align::Left,
+            },
+            Col {
+                width: 40.0,
+                align: Align::Right,
+            },
+        ];
+        pdf.table_header(&cols, &["Catégorie", "Montant"]);
+        pdf.table_row_wrapped(&cols, &["Crème brûlée für Zoë", "1 234,56 €"], false, 8.0);
+        let bytes = pdf.into_bytes().unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+        assert!(bytes.windows(9).any(|w| w == b"FontFile2"));
+    }
 }
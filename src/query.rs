@@ -0,0 +1,190 @@
+use rusqlite::types::Value;
+use rusqlite::{Batch, Connection};
+
+use crate::error::{NigelError, Result};
+use crate::tabular::{self, Sheet};
+
+/// What running one ad-hoc statement produced.
+pub enum QueryOutcome {
+    Rows(QueryResult),
+    /// A write, with the number of rows it changed.
+    Changed(usize),
+}
+
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl QueryResult {
+    /// The rows as a sheet for `tabular::write_csv_to`. Reals keep their
+    /// full precision rather than the two decimals used for amounts.
+    pub fn sheet(&self) -> Sheet {
+        Sheet {
+            name: "Query".into(),
+            headers: self.columns.clone(),
+            rows: self
+                .rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|v| match v {
+                            Value::Null => tabular::Value::Empty,
+                            Value::Integer(n) => tabular::Value::Int(*n),
+                            other => tabular::Value::Text(cell_text(other)),
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// The rows as a JSON array of objects keyed by column name.
+    pub fn to_json(&self) -> serde_json::Value {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let object = self
+                    .columns
+                    .iter()
+                    .zip(row)
+                    .map(|(col, v)| {
+                        let value = match v {
+                            Value::Null => serde_json::Value::Null,
+                            Value::Integer(n) => (*n).into(),
+                            Value::Real(n) => (*n).into(),
+                            other => cell_text(other).into(),
+                        };
+                        (col.clone(), value)
+                    })
+                    .collect();
+                serde_json::Value::Object(object)
+            })
+            .collect();
+        serde_json::Value::Array(rows)
+    }
+}
+
+/// A value as shown in a table cell; blobs are summarized, not dumped.
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(n) => n.to_string(),
+        Value::Real(n) => n.to_string(),
+        Value::Text(s) => s.clone(),
+        Value::Blob(b) => format!("<{} bytes>", b.len()),
+    }
+}
+
+/// Run a single SQL statement. Anything that isn't read-only (by SQLite's
+/// own judgment, not keyword matching) is refused unless `allow_writes`.
+pub fn run(conn: &Connection, sql: &str, allow_writes: bool) -> Result<QueryOutcome> {
+    let mut batch = Batch::new(conn, sql);
+    let Some(mut stmt) = batch.next()? else {
+        return Err(NigelError::Other("No SQL statement given".into()));
+    };
+    if batch.next()?.is_some() {
+        return Err(NigelError::Other("Run one SQL statement at a time".into()));
+    }
+    if !stmt.readonly() && !allow_writes {
+        return Err(NigelError::Other(
+            "This statement changes the database. Pass --allow-writes to run it \
+             (a snapshot is taken first)."
+                .into(),
+        ));
+    }
+    if stmt.column_count() == 0 {
+        return Ok(QueryOutcome::Changed(stmt.execute([])?));
+    }
+
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    let count = columns.len();
+    let rows = stmt
+        .query_map([], |row| {
+            (0..count)
+                .map(|i| row.get::<_, Value>(i))
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(QueryOutcome::Rows(QueryResult { columns, rows }))
+}
+
+/// Check a saved query's name: letters, digits, `-`, and `_`, so it can't be
+/// mistaken for SQL on the command line.
+pub fn validate_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(NigelError::Other(format!(
+            "Invalid query name '{name}': use letters, digits, '-', and '_'"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn select_returns_columns_and_rows() {
+        let (_dir, conn) = test_db();
+        let QueryOutcome::Rows(result) = run(
+            &conn,
+            "SELECT name, id, 1.5 AS rate, NULL AS memo FROM accounts",
+            false,
+        )
+        .unwrap() else {
+            panic!("expected rows");
+        };
+        assert_eq!(result.columns, ["name", "id", "rate", "memo"]);
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(
+            result.to_json(),
+            serde_json::json!([{ "name": "Checking", "id": 1, "rate": 1.5, "memo": null }])
+        );
+        let sheet = result.sheet();
+        assert_eq!(sheet.rows[0][2], tabular::Value::Text("1.5".into()));
+        assert_eq!(sheet.rows[0][3], tabular::Value::Empty);
+    }
+
+    #[test]
+    fn writes_need_allow_writes() {
+        let (_dir, conn) = test_db();
+        let sql = "UPDATE accounts SET name = 'Main' WHERE id = 1";
+        let err = run(&conn, sql, false).err().unwrap();
+        assert!(err.to_string().contains("--allow-writes"), "{err}");
+        // Hiding a write behind a read doesn't sneak it through
+        assert!(run(&conn, "SELECT 1; DELETE FROM accounts", false).is_err());
+        assert!(run(&conn, "  ", false).is_err());
+
+        let QueryOutcome::Changed(n) = run(&conn, sql, true).unwrap() else {
+            panic!("expected a change count");
+        };
+        assert_eq!(n, 1);
+    }
+
+    #[test]
+    fn saved_query_names_are_plain_words() {
+        assert_eq!(validate_name(" monthly-fees ").unwrap(), "monthly-fees");
+        assert!(validate_name("SELECT 1").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
@@ -1,8 +1,11 @@
+use chrono::NaiveDate;
 use rusqlite::Connection;
 
 use crate::error::{NigelError, Result};
 
 pub struct ReconcileResult {
+    /// ID of the `reconciliations` row, for attaching a note.
+    pub id: i64,
     pub is_reconciled: bool,
     pub statement_balance: f64,
     pub calculated_balance: f64,
@@ -25,7 +28,8 @@ pub fn reconcile(
 
     // Check if there are any transactions for this account in the given month
     let tx_count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM transactions WHERE account_id = ?1 AND date >= ?2 || '-01' AND date <= ?2 || '-31'",
+        "SELECT COUNT(*) FROM transactions WHERE account_id = ?1 AND deleted_at IS NULL \
+         AND date >= ?2 || '-01' AND date <= ?2 || '-31'",
         rusqlite::params![account_id, month],
         |row| row.get(0),
     )?;
@@ -36,11 +40,7 @@ pub fn reconcile(
         });
     }
 
-    let calculated: f64 = conn.query_row(
-        "SELECT COALESCE(SUM(amount), 0) FROM transactions WHERE account_id = ?1 AND date <= ?2 || '-31'",
-        rusqlite::params![account_id, month],
-        |row| row.get(0),
-    )?;
+    let calculated = balance_on(conn, account_id, &format!("{month}-31"))?;
 
     let discrepancy = (calculated - statement_balance).abs();
     let is_reconciled = discrepancy < 0.01;
@@ -50,8 +50,19 @@ pub fn reconcile(
          VALUES (?1, ?2, ?3, ?4, ?5, CASE WHEN ?5 = 1 THEN datetime('now') ELSE NULL END)",
         rusqlite::params![account_id, month, statement_balance, calculated, is_reconciled as i32],
     )?;
+    let id = conn.last_insert_rowid();
+
+    // Balancing now settles any difference that was accepted with a note
+    if is_reconciled {
+        conn.execute(
+            "UPDATE reconciliations SET resolved_at = datetime('now') \
+             WHERE account_id = ?1 AND month = ?2 AND notes IS NOT NULL AND resolved_at IS NULL",
+            rusqlite::params![account_id, month],
+        )?;
+    }
 
     Ok(ReconcileResult {
+        id,
         is_reconciled,
         statement_balance,
         calculated_balance: calculated,
@@ -59,6 +70,207 @@ pub fn reconcile(
     })
 }
 
+/// An account's balance at the end of `date` (YYYY-MM-DD), in its own
+/// currency: the opening balance plus every transaction from the opening
+/// date through `date`. Before the opening date it is just the transactions
+/// so far.
+pub fn balance_on(conn: &Connection, account_id: i64, date: &str) -> Result<f64> {
+    let balance = conn.query_row(
+        "SELECT CASE WHEN a.opening_date IS NULL OR a.opening_date <= ?2 \
+                     THEN a.opening_balance ELSE 0 END \
+              + COALESCE((SELECT SUM(t.amount) FROM transactions t \
+                          WHERE t.account_id = a.id AND t.date <= ?2 AND t.deleted_at IS NULL \
+                            AND (a.opening_date IS NULL OR a.opening_date > ?2 \
+                                 OR t.date >= a.opening_date)), 0) \
+         FROM accounts a WHERE a.id = ?1",
+        rusqlite::params![account_id, date],
+        |row| row.get(0),
+    )?;
+    Ok(balance)
+}
+
+/// A statement balance the books should agree with on a given day,
+/// checked again every time the dashboard loads.
+pub struct BalanceAssertion {
+    pub id: i64,
+    pub account_name: String,
+    pub date: String,
+    pub balance: f64,
+    /// The computed balance at the end of `date`, as of now.
+    pub calculated: f64,
+}
+
+impl BalanceAssertion {
+    pub fn difference(&self) -> f64 {
+        ((self.calculated - self.balance) * 100.0).round() / 100.0
+    }
+
+    pub fn holds(&self) -> bool {
+        self.difference().abs() < 0.01
+    }
+}
+
+/// Record that `account_name` should have `balance` at the end of `date`
+/// and check it against the books.
+pub fn add_assertion(
+    conn: &Connection,
+    account_name: &str,
+    date: &str,
+    balance: f64,
+) -> Result<BalanceAssertion> {
+    let account_id: i64 = conn
+        .query_row(
+            "SELECT id FROM accounts WHERE name = ?1",
+            [account_name],
+            |row| row.get(0),
+        )
+        .map_err(|_| NigelError::UnknownAccount(account_name.to_string()))?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| NigelError::Other(format!("Invalid date: '{date}' (expected YYYY-MM-DD)")))?;
+    if !balance.is_finite() {
+        return Err(NigelError::Other(format!("Invalid balance: {balance}")));
+    }
+    conn.execute(
+        "INSERT INTO balance_assertions (account_id, date, balance) VALUES (?1, ?2, ?3)",
+        rusqlite::params![account_id, date, balance],
+    )?;
+    Ok(BalanceAssertion {
+        id: conn.last_insert_rowid(),
+        account_name: account_name.to_string(),
+        date: date.to_string(),
+        balance,
+        calculated: balance_on(conn, account_id, date)?,
+    })
+}
+
+/// Every assertion, checked against the books as they stand, by account
+/// and date.
+pub fn list_assertions(conn: &Connection) -> Result<Vec<BalanceAssertion>> {
+    let mut stmt = conn.prepare(
+        "SELECT b.id, a.id, a.name, b.date, b.balance \
+         FROM balance_assertions b JOIN accounts a ON a.id = b.account_id \
+         ORDER BY a.name, b.date, b.id",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, f64>(4)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    rows.into_iter()
+        .map(|(id, account_id, account_name, date, balance)| {
+            let calculated = balance_on(conn, account_id, &date)?;
+            Ok(BalanceAssertion {
+                id,
+                account_name,
+                date,
+                balance,
+                calculated,
+            })
+        })
+        .collect()
+}
+
+/// Assertions the books no longer agree with.
+pub fn failed_assertions(conn: &Connection) -> Result<Vec<BalanceAssertion>> {
+    Ok(list_assertions(conn)?
+        .into_iter()
+        .filter(|a| !a.holds())
+        .collect())
+}
+
+pub fn remove_assertion(conn: &Connection, id: i64) -> Result<()> {
+    let deleted = conn.execute("DELETE FROM balance_assertions WHERE id = ?1", [id])?;
+    if deleted == 0 {
+        return Err(NigelError::Other(format!(
+            "No balance assertion with ID {id}"
+        )));
+    }
+    Ok(())
+}
+
+/// A reconciliation difference accepted for now, waiting to be resolved.
+pub struct OpenNote {
+    pub id: i64,
+    pub account_name: String,
+    pub month: String,
+    pub discrepancy: f64,
+    pub note: String,
+    pub follow_up: Option<String>,
+}
+
+/// Accept the difference found by reconciliation `id` for now, with a note
+/// and an optional follow-up date (YYYY-MM-DD). The note stays open until
+/// the month reconciles cleanly or it is resolved by hand.
+pub fn add_note(conn: &Connection, id: i64, note: &str, follow_up: Option<&str>) -> Result<()> {
+    let note = note.trim();
+    if note.is_empty() {
+        return Err(NigelError::Other("Note cannot be empty".into()));
+    }
+    if let Some(date) = follow_up {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+            NigelError::Other(format!(
+                "Invalid follow-up date: '{date}' (expected YYYY-MM-DD)"
+            ))
+        })?;
+    }
+    let updated = conn.execute(
+        "UPDATE reconciliations SET notes = ?2, follow_up = ?3, resolved_at = NULL \
+         WHERE id = ?1 AND is_reconciled = 0",
+        rusqlite::params![id, note, follow_up],
+    )?;
+    if updated == 0 {
+        return Err(NigelError::Other(format!(
+            "No unbalanced reconciliation #{id}"
+        )));
+    }
+    Ok(())
+}
+
+/// Unresolved notes, soonest follow-up first (notes without a date last).
+pub fn open_notes(conn: &Connection) -> Result<Vec<OpenNote>> {
+    let mut stmt = conn.prepare(
+        "SELECT r.id, a.name, r.month, ABS(r.calculated_balance - r.statement_balance), \
+         r.notes, r.follow_up \
+         FROM reconciliations r JOIN accounts a ON a.id = r.account_id \
+         WHERE r.notes IS NOT NULL AND r.resolved_at IS NULL \
+         ORDER BY r.follow_up IS NULL, r.follow_up, r.month, r.id",
+    )?;
+    let notes = stmt
+        .query_map([], |row| {
+            Ok(OpenNote {
+                id: row.get(0)?,
+                account_name: row.get(1)?,
+                month: row.get(2)?,
+                discrepancy: row.get(3)?,
+                note: row.get(4)?,
+                follow_up: row.get(5)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(notes)
+}
+
+/// Mark note `id` resolved by hand.
+pub fn resolve_note(conn: &Connection, id: i64) -> Result<()> {
+    let updated = conn.execute(
+        "UPDATE reconciliations SET resolved_at = datetime('now') \
+         WHERE id = ?1 AND notes IS NOT NULL AND resolved_at IS NULL",
+        [id],
+    )?;
+    if updated == 0 {
+        return Err(NigelError::Other(format!(
+            "No open reconciliation note #{id}. Run `nigel reconcile --notes` to list them."
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,4 +324,84 @@ mod tests {
             .unwrap();
         assert_eq!(count, 1);
     }
+
+    #[test]
+    fn test_note_stays_open_until_month_balances() {
+        let (_dir, conn) = test_db();
+        setup_account_with_txns(&conn, 1000.0);
+        let result = reconcile(&conn, "Test Checking", "2025-01", 1012.5).unwrap();
+        assert!(add_note(&conn, result.id, "  ", None).is_err());
+        assert!(add_note(&conn, result.id, "Missing deposit", Some("2025-02-30")).is_err());
+        add_note(&conn, result.id, "Missing deposit", Some("2025-02-15")).unwrap();
+
+        let notes = open_notes(&conn).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].account_name, "Test Checking");
+        assert_eq!(notes[0].discrepancy, 12.5);
+        assert_eq!(notes[0].follow_up.as_deref(), Some("2025-02-15"));
+
+        // A clean reconciliation of the same month resolves it
+        reconcile(&conn, "Test Checking", "2025-01", 1000.0).unwrap();
+        assert!(open_notes(&conn).unwrap().is_empty());
+        assert!(resolve_note(&conn, result.id).is_err());
+    }
+
+    #[test]
+    fn test_opening_balance_counts_toward_reconciliation() {
+        let (_dir, conn) = test_db();
+        setup_account_with_txns(&conn, 1000.0);
+        // An older transaction already covered by the opening balance
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, '2024-12-20', 'Old deposit', 300.0)",
+            [],
+        )
+        .unwrap();
+        crate::cli::accounts::set_opening_balance(&conn, 1, 2500.0, Some("2025-01-01")).unwrap();
+
+        assert_eq!(balance_on(&conn, 1, "2025-01-31").unwrap(), 3500.0);
+        assert_eq!(balance_on(&conn, 1, "2024-12-31").unwrap(), 300.0);
+        let result = reconcile(&conn, "Test Checking", "2025-01", 3500.0).unwrap();
+        assert!(result.is_reconciled);
+    }
+
+    #[test]
+    fn test_balance_assertions_recheck_against_the_books() {
+        let (_dir, conn) = test_db();
+        setup_account_with_txns(&conn, 1000.0);
+        assert!(add_assertion(&conn, "Nope", "2025-01-31", 1.0).is_err());
+        assert!(add_assertion(&conn, "Test Checking", "2025-01-32", 1.0).is_err());
+
+        let ok = add_assertion(&conn, "Test Checking", "2025-01-31", 1000.0).unwrap();
+        assert!(ok.holds());
+        let off = add_assertion(&conn, "Test Checking", "2025-01-10", 50.0).unwrap();
+        assert_eq!(off.difference(), -50.0);
+        assert_eq!(list_assertions(&conn).unwrap().len(), 2);
+        assert_eq!(failed_assertions(&conn).unwrap().len(), 1);
+
+        // A later edit breaks the one that held
+        conn.execute("UPDATE transactions SET amount = 990.0", [])
+            .unwrap();
+        let failed = failed_assertions(&conn).unwrap();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[1].id, ok.id);
+        assert_eq!(failed[1].difference(), -10.0);
+
+        remove_assertion(&conn, off.id).unwrap();
+        assert!(remove_assertion(&conn, off.id).is_err());
+        assert_eq!(list_assertions(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_note_by_hand() {
+        let (_dir, conn) = test_db();
+        setup_account_with_txns(&conn, 1000.0);
+        let balanced = reconcile(&conn, "Test Checking", "2025-01", 1000.0).unwrap();
+        assert!(add_note(&conn, balanced.id, "Nothing to note", None).is_err());
+
+        let result = reconcile(&conn, "Test Checking", "2025-01", 990.0).unwrap();
+        add_note(&conn, result.id, "Bank fee pending", None).unwrap();
+        resolve_note(&conn, result.id).unwrap();
+        assert!(open_notes(&conn).unwrap().is_empty());
+    }
 }
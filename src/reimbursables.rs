@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::archive;
+use crate::currency::HOME_AMOUNT_SQL;
+use crate::error::{NigelError, Result};
+
+/// An expense to be billed back to a client. It's outstanding until it is
+/// marked reimbursed; invoicing it only records that the client has been
+/// asked.
+pub struct Reimbursable {
+    pub transaction_id: i64,
+    pub date: String,
+    pub description: String,
+    /// Amount spent, in the home currency, as a positive number.
+    pub amount: f64,
+    /// The client, in tag form (`client-acme`).
+    pub client: String,
+    pub invoice: Option<String>,
+    pub invoiced_on: Option<String>,
+    pub reimbursed_on: Option<String>,
+}
+
+impl Reimbursable {
+    /// "not invoiced", "invoiced 2025-03-01 (INV-104)", "reimbursed 2025-04-02".
+    pub fn status(&self) -> String {
+        if let Some(date) = &self.reimbursed_on {
+            return format!("reimbursed {date}");
+        }
+        match (&self.invoiced_on, &self.invoice) {
+            (Some(date), Some(invoice)) => format!("invoiced {date} ({invoice})"),
+            (Some(date), None) => format!("invoiced {date}"),
+            _ => "not invoiced".to_string(),
+        }
+    }
+}
+
+/// What one client still owes.
+pub struct ClientOutstanding {
+    pub client: String,
+    pub count: usize,
+    /// Spent and not yet billed.
+    pub not_invoiced: f64,
+    /// Billed and not yet paid back.
+    pub invoiced: f64,
+    /// Date of the oldest outstanding expense.
+    pub oldest: String,
+}
+
+impl ClientOutstanding {
+    pub fn total(&self) -> f64 {
+        self.not_invoiced + self.invoiced
+    }
+}
+
+#[derive(Default)]
+pub struct ReimbursablesReport {
+    /// Clients with anything outstanding, by name.
+    pub clients: Vec<ClientOutstanding>,
+    /// Outstanding expenses (and reimbursed ones, when asked for), oldest first.
+    pub items: Vec<Reimbursable>,
+}
+
+/// Mark expenses as reimbursable by `client`, re-assigning any already
+/// marked for someone else. Each transaction also gets the client as a tag,
+/// so it shows up under `--tag` and in the tags report. Returns the client
+/// in tag form.
+pub fn mark(conn: &Connection, ids: &[i64], client: &str) -> Result<String> {
+    let client = crate::tags::normalize_tag(client)?;
+    for &id in ids {
+        archive::ensure_not_archived(conn, id)?;
+        let amount: Option<f64> = conn
+            .query_row("SELECT amount FROM transactions WHERE id = ?1", [id], |r| {
+                r.get(0)
+            })
+            .optional()?;
+        match amount {
+            None => return Err(NigelError::Other(format!("Transaction #{id} not found"))),
+            Some(a) if a >= 0.0 => {
+                return Err(NigelError::Other(format!(
+                    "Transaction #{id} is money in; only expenses can be reimbursable"
+                )))
+            }
+            Some(_) => {}
+        }
+    }
+    for &id in ids {
+        conn.execute(
+            "INSERT INTO reimbursables (transaction_id, client) VALUES (?1, ?2) \
+             ON CONFLICT(transaction_id) DO UPDATE SET client = excluded.client",
+            rusqlite::params![id, client],
+        )?;
+        let joined: Option<String> = conn.query_row(
+            "SELECT GROUP_CONCAT(g.name) FROM transaction_tags tt \
+             JOIN tags g ON tt.tag_id = g.id WHERE tt.transaction_id = ?1",
+            [id],
+            |r| r.get(0),
+        )?;
+        let mut tags = crate::tags::from_concat(joined);
+        if !tags.contains(&client) {
+            tags.push(client.clone());
+            tags.sort();
+            crate::tags::set_transaction_tags(conn, id, &tags)?;
+        }
+    }
+    Ok(client)
+}
+
+/// Stop tracking expenses as reimbursable. Their tags are left alone.
+pub fn unmark(conn: &Connection, ids: &[i64]) -> Result<usize> {
+    let mut removed = 0;
+    for &id in ids {
+        removed += conn.execute("DELETE FROM reimbursables WHERE transaction_id = ?1", [id])?;
+    }
+    Ok(removed)
+}
+
+/// Outstanding expenses for `client`: all of them, or only those not yet
+/// invoiced.
+pub fn outstanding_ids(conn: &Connection, client: &str, not_invoiced: bool) -> Result<Vec<i64>> {
+    let client = crate::tags::normalize_tag(client)?;
+    let mut stmt = conn.prepare(
+        "SELECT r.transaction_id FROM reimbursables r \
+         JOIN transactions t ON t.id = r.transaction_id \
+         WHERE r.client = ?1 AND r.reimbursed_on IS NULL AND t.deleted_at IS NULL \
+         AND (?2 = 0 OR r.invoiced_on IS NULL) \
+         ORDER BY t.date, t.id",
+    )?;
+    let ids = stmt
+        .query_map(rusqlite::params![client, not_invoiced], |r| r.get(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(ids)
+}
+
+/// Record that the expenses were billed on `date`, optionally under an
+/// invoice number.
+pub fn record_invoice(
+    conn: &Connection,
+    ids: &[i64],
+    invoice: Option<&str>,
+    date: &str,
+) -> Result<usize> {
+    validate_date(date)?;
+    for &id in ids {
+        match reimbursed_on(conn, id)? {
+            None => return Err(not_marked(id)),
+            Some(Some(paid)) => {
+                return Err(NigelError::Other(format!(
+                    "Transaction #{id} was already reimbursed on {paid}"
+                )))
+            }
+            Some(None) => {}
+        }
+    }
+    let invoice = invoice.map(str::trim).filter(|s| !s.is_empty());
+    for &id in ids {
+        conn.execute(
+            "UPDATE reimbursables SET invoiced_on = ?1, invoice = ?2 WHERE transaction_id = ?3",
+            rusqlite::params![date, invoice, id],
+        )?;
+    }
+    Ok(ids.len())
+}
+
+/// Record that the client paid the expenses back on `date`.
+pub fn record_reimbursed(conn: &Connection, ids: &[i64], date: &str) -> Result<usize> {
+    validate_date(date)?;
+    for &id in ids {
+        if reimbursed_on(conn, id)?.is_none() {
+            return Err(not_marked(id));
+        }
+    }
+    for &id in ids {
+        conn.execute(
+            "UPDATE reimbursables SET reimbursed_on = ?1 WHERE transaction_id = ?2",
+            rusqlite::params![date, id],
+        )?;
+    }
+    Ok(ids.len())
+}
+
+/// None when `id` isn't marked reimbursable.
+fn reimbursed_on(conn: &Connection, id: i64) -> Result<Option<Option<String>>> {
+    Ok(conn
+        .query_row(
+            "SELECT reimbursed_on FROM reimbursables WHERE transaction_id = ?1",
+            [id],
+            |r| r.get(0),
+        )
+        .optional()?)
+}
+
+fn not_marked(id: i64) -> NigelError {
+    NigelError::Other(format!(
+        "Transaction #{id} isn't marked reimbursable (use `nigel reimbursable mark`)"
+    ))
+}
+
+fn validate_date(date: &str) -> Result<()> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| NigelError::Other(format!("Invalid date: '{date}' (expected YYYY-MM-DD)")))?;
+    Ok(())
+}
+
+/// Outstanding reimbursables by client, optionally for one client and with
+/// the ones already paid back listed too.
+pub fn get_report(
+    conn: &Connection,
+    client: Option<&str>,
+    include_reimbursed: bool,
+) -> Result<ReimbursablesReport> {
+    let client = client.map(crate::tags::normalize_tag).transpose()?;
+    // Settled expenses may be in an archived year
+    let _archives = if include_reimbursed {
+        Some(archive::scope(conn, None, None)?)
+    } else {
+        None
+    };
+    let sql = format!(
+        "SELECT t.id, t.date, t.description, -{HOME_AMOUNT_SQL}, r.client, r.invoice, \
+         r.invoiced_on, r.reimbursed_on \
+         FROM reimbursables r \
+         JOIN transactions t ON t.id = r.transaction_id \
+         JOIN accounts a ON t.account_id = a.id \
+         WHERE (?1 IS NULL OR r.client = ?1) AND (?2 = 1 OR r.reimbursed_on IS NULL) \
+           AND t.deleted_at IS NULL \
+         ORDER BY t.date, t.id"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let items = stmt
+        .query_map(rusqlite::params![client, include_reimbursed], |row| {
+            Ok(Reimbursable {
+                transaction_id: row.get(0)?,
+                date: row.get(1)?,
+                description: row.get(2)?,
+                amount: row.get(3)?,
+                client: row.get(4)?,
+                invoice: row.get(5)?,
+                invoiced_on: row.get(6)?,
+                reimbursed_on: row.get(7)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut by_client: BTreeMap<&str, ClientOutstanding> = BTreeMap::new();
+    for item in items.iter().filter(|i| i.reimbursed_on.is_none()) {
+        let entry = by_client
+            .entry(&item.client)
+            .or_insert_with(|| ClientOutstanding {
+                client: item.client.clone(),
+                count: 0,
+                not_invoiced: 0.0,
+                invoiced: 0.0,
+                oldest: item.date.clone(),
+            });
+        entry.count += 1;
+        if item.invoiced_on.is_some() {
+            entry.invoiced += item.amount;
+        } else {
+            entry.not_invoiced += item.amount;
+        }
+    }
+    let clients = by_client.into_values().collect();
+    Ok(ReimbursablesReport { clients, items })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Card', 'credit_card')",
+            [],
+        )
+        .unwrap();
+        for (date, desc, amount) in [
+            ("2025-03-02", "DELTA AIR", -420.0),
+            ("2025-03-04", "HILTON", -310.0),
+            ("2025-03-09", "UBER", -35.5),
+            ("2025-03-10", "CLIENT PAYMENT", 5000.0),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount) \
+                 VALUES (1, ?1, ?2, ?3)",
+                rusqlite::params![date, desc, amount],
+            )
+            .unwrap();
+        }
+        (dir, conn)
+    }
+
+    #[test]
+    fn test_outstanding_by_client_through_invoice_and_payment() {
+        let (_dir, conn) = test_db();
+        assert_eq!(mark(&conn, &[1, 2], "#Client-Acme").unwrap(), "client-acme");
+        mark(&conn, &[3], "globex").unwrap();
+
+        let report = get_report(&conn, None, false).unwrap();
+        assert_eq!(report.items.len(), 3);
+        let acme = &report.clients[0];
+        assert_eq!(acme.client, "client-acme");
+        assert_eq!((acme.count, acme.not_invoiced), (2, 730.0));
+        assert_eq!(acme.oldest, "2025-03-02");
+        assert_eq!(report.clients[1].total(), 35.5);
+
+        let ids = outstanding_ids(&conn, "client-acme", true).unwrap();
+        record_invoice(&conn, &ids, Some("INV-104"), "2025-03-31").unwrap();
+        let report = get_report(&conn, Some("client-acme"), false).unwrap();
+        assert_eq!(report.clients[0].invoiced, 730.0);
+        assert_eq!(report.clients[0].not_invoiced, 0.0);
+        assert_eq!(report.items[0].status(), "invoiced 2025-03-31 (INV-104)");
+        assert!(outstanding_ids(&conn, "client-acme", true)
+            .unwrap()
+            .is_empty());
+
+        record_reimbursed(&conn, &[1], "2025-04-15").unwrap();
+        let report = get_report(&conn, Some("client-acme"), false).unwrap();
+        assert_eq!(report.items.len(), 1);
+        assert_eq!(report.clients[0].total(), 310.0);
+        let report = get_report(&conn, Some("client-acme"), true).unwrap();
+        assert_eq!(report.items[0].status(), "reimbursed 2025-04-15");
+        assert!(record_invoice(&conn, &[1], None, "2025-04-20").is_err());
+
+        // Marking tags the transaction with the client
+        let tags: String = conn
+            .query_row(
+                "SELECT GROUP_CONCAT(g.name) FROM transaction_tags tt \
+                 JOIN tags g ON tt.tag_id = g.id WHERE tt.transaction_id = 2",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(tags, "client-acme");
+    }
+
+    #[test]
+    fn test_mark_refuses_income_and_unknown_ids() {
+        let (_dir, conn) = test_db();
+        assert!(mark(&conn, &[4], "acme").is_err());
+        assert!(mark(&conn, &[1, 99], "acme").is_err());
+        // Nothing is marked when any ID is refused
+        assert!(get_report(&conn, None, false).unwrap().items.is_empty());
+        assert!(record_reimbursed(&conn, &[1], "2025-04-01").is_err());
+
+        mark(&conn, &[1], "acme").unwrap();
+        assert!(record_reimbursed(&conn, &[1], "04/01/2025").is_err());
+        assert_eq!(unmark(&conn, &[1, 2]).unwrap(), 1);
+        assert!(get_report(&conn, None, false).unwrap().clients.is_empty());
+    }
+}
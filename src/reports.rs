@@ -1,7 +1,17 @@
-use chrono::Datelike;
-use rusqlite::Connection;
+use std::collections::HashMap;
 
+use chrono::{Datelike, Months, NaiveDate};
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::archive::{self, ArchiveScope};
+use crate::clients::CLIENT_NAME_SQL;
+use crate::compensation;
+use crate::currency::{HOME_AMOUNT_SQL, OPENING_HOME_SQL};
+use crate::deductions::{self, HomeOffice, MileageEntry};
 use crate::error::Result;
+use crate::fiscal::FiscalYear;
+use crate::shareholders::{self, Shareholder};
+use crate::tags::TAGS_CONCAT_SQL;
 
 fn to_sql_params(params: &[String]) -> Vec<&dyn rusqlite::types::ToSql> {
     params
@@ -14,7 +24,22 @@ fn to_sql_params(params: &[String]) -> Vec<&dyn rusqlite::types::ToSql> {
 // Date filter helper
 // ---------------------------------------------------------------------------
 
+/// SQL clause for a report's period, leaving out trashed transactions. A
+/// bare `year` is a fiscal year (see `FiscalYear`); `month` narrows it to
+/// that calendar month.
 fn date_filter(
+    conn: &Connection,
+    year: Option<i32>,
+    month: Option<u32>,
+    from_date: Option<&str>,
+    to_date: Option<&str>,
+) -> Result<(String, Vec<String>)> {
+    let (clause, params) = period_clause(conn, year, month, from_date, to_date)?;
+    Ok((format!("t.deleted_at IS NULL AND {clause}"), params))
+}
+
+fn period_clause(
+    conn: &Connection,
     year: Option<i32>,
     month: Option<u32>,
     from_date: Option<&str>,
@@ -41,12 +66,157 @@ fn date_filter(
         return Ok(("t.date LIKE ?1".to_string(), vec![format!("{prefix}%")]));
     }
     if let Some(y) = year {
-        return Ok(("t.date LIKE ?1".to_string(), vec![format!("{y}%")]));
+        let (start, end) = FiscalYear::load(conn).bounds(y);
+        return Ok((
+            "t.date BETWEEN ?1 AND ?2".to_string(),
+            vec![start.to_string(), end.to_string()],
+        ));
     }
     // Default: all transactions (no date filter)
     Ok(("1=1".to_string(), vec![]))
 }
 
+/// Attach the archived years that `date_filter`'s period reaches into, for as
+/// long as the returned scope lives.
+fn archive_scope<'a>(
+    conn: &'a Connection,
+    year: Option<i32>,
+    month: Option<u32>,
+    from_date: Option<&str>,
+    to_date: Option<&str>,
+) -> Result<ArchiveScope<'a>> {
+    let bounds = match (year, month, from_date, to_date) {
+        (_, _, Some(from), Some(to)) => Some((from.to_string(), to.to_string())),
+        (Some(y), Some(m), _, _) => {
+            let (start, end) = month_bounds(y, m)?;
+            Some((start.to_string(), end.to_string()))
+        }
+        (Some(y), None, _, _) => {
+            let (start, end) = FiscalYear::load(conn).bounds(y);
+            Some((start.to_string(), end.to_string()))
+        }
+        _ => None,
+    };
+    match bounds {
+        Some((from, to)) => archive::scope(conn, Some(&from), Some(&to)),
+        None => archive::scope(conn, None, None),
+    }
+}
+
+/// SQL clause leaving out transactions carrying any of `tags`, numbering its
+/// placeholders after the ones already in `params` and appending the
+/// normalized tags to it. Empty when there is nothing to exclude.
+fn exclude_tags_clause(tags: &[String], params: &mut Vec<String>) -> Result<String> {
+    if tags.is_empty() {
+        return Ok(String::new());
+    }
+    let mut placeholders = Vec::with_capacity(tags.len());
+    for tag in tags {
+        params.push(crate::tags::normalize_tag(tag)?);
+        placeholders.push(format!("?{}", params.len()));
+    }
+    Ok(format!(
+        " AND NOT EXISTS (SELECT 1 FROM transaction_tags tt JOIN tags g ON tt.tag_id = g.id \
+         WHERE tt.transaction_id = t.id AND g.name IN ({}))",
+        placeholders.join(", ")
+    ))
+}
+
+/// `#one-off, #reimbursable` for labelling a report that leaves tags out, so
+/// it isn't mistaken for the books' totals.
+pub fn excluded_tags_label(tags: &[String]) -> Option<String> {
+    if tags.is_empty() {
+        return None;
+    }
+    let names: Vec<String> = tags
+        .iter()
+        .map(|t| {
+            format!(
+                "#{}",
+                crate::tags::normalize_tag(t).unwrap_or_else(|_| t.clone())
+            )
+        })
+        .collect();
+    Some(names.join(", "))
+}
+
+// ---------------------------------------------------------------------------
+// Category hierarchy
+// ---------------------------------------------------------------------------
+
+/// Separator used when a category is shown by its full path.
+pub const CATEGORY_PATH_SEP: &str = " > ";
+
+/// Ancestor chain for every category, root first: `["Software", "Design tools"]`.
+/// A `parent_id` cycle is cut at the first repeated category.
+pub fn category_paths(conn: &Connection) -> Result<HashMap<i64, Vec<String>>> {
+    let mut stmt = conn.prepare("SELECT id, name, parent_id FROM categories")?;
+    let nodes: HashMap<i64, (String, Option<i64>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?
+        .collect::<std::result::Result<_, _>>()?;
+
+    let mut paths = HashMap::with_capacity(nodes.len());
+    for (&id, (name, parent)) in &nodes {
+        let mut path = vec![name.clone()];
+        let mut seen = vec![id];
+        let mut next = *parent;
+        while let Some(pid) = next {
+            if seen.contains(&pid) {
+                break;
+            }
+            let Some((pname, pparent)) = nodes.get(&pid) else {
+                break;
+            };
+            path.push(pname.clone());
+            seen.push(pid);
+            next = *pparent;
+        }
+        path.reverse();
+        paths.insert(id, path);
+    }
+    Ok(paths)
+}
+
+fn check_depth(depth: Option<usize>) -> Result<()> {
+    if depth == Some(0) {
+        return Err(crate::error::NigelError::Other(
+            "--depth must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Merge `(path, total, count)` rows that share their first `depth` path
+/// levels. `None` keeps every category separate. First-seen order is kept.
+fn roll_up(
+    rows: Vec<(Vec<String>, f64, i64)>,
+    depth: Option<usize>,
+) -> Vec<(Vec<String>, f64, i64)> {
+    let mut merged: Vec<(Vec<String>, f64, i64)> = Vec::with_capacity(rows.len());
+    for (mut path, total, count) in rows {
+        if let Some(d) = depth {
+            path.truncate(d.max(1));
+        }
+        match merged.iter_mut().find(|(p, _, _)| *p == path) {
+            Some(existing) => {
+                existing.1 += total;
+                existing.2 += count;
+            }
+            None => merged.push((path, total, count)),
+        }
+    }
+    merged
+}
+
+/// Sort by total (descending for income, ascending for expenses), then name.
+fn sort_rows(rows: &mut [(Vec<String>, f64, i64)], descending: bool) {
+    rows.sort_by(|a, b| {
+        let ord = a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal);
+        let ord = if descending { ord.reverse() } else { ord };
+        ord.then_with(|| a.0.cmp(&b.0))
+    });
+}
+
 // ---------------------------------------------------------------------------
 // P&L
 // ---------------------------------------------------------------------------
@@ -64,17 +234,76 @@ pub struct PnlReport {
     pub net: f64,
 }
 
+/// P&L lines under one top-level category. Each item is labelled by its path
+/// below the group; the group's own transactions are labelled "(direct)" once
+/// it has sub-categories.
+pub struct PnlGroup<'a> {
+    pub name: &'a str,
+    pub total: f64,
+    pub items: Vec<(&'a str, f64)>,
+}
+
+impl PnlGroup<'_> {
+    /// A top-level category without sub-category lines, shown as one line
+    /// rather than a header, items, and subtotal.
+    pub fn is_single(&self) -> bool {
+        matches!(self.items.as_slice(), [("", _)])
+    }
+}
+
+/// Group P&L lines by top-level category, largest group total first, with
+/// lines kept in their original order.
+pub fn pnl_groups(items: &[PnlItem]) -> Vec<PnlGroup<'_>> {
+    let mut groups: Vec<PnlGroup> = Vec::new();
+    for item in items {
+        let (name, rest) = item
+            .name
+            .split_once(CATEGORY_PATH_SEP)
+            .unwrap_or((item.name.as_str(), ""));
+        let i = match groups.iter().position(|g| g.name == name) {
+            Some(i) => i,
+            None => {
+                groups.push(PnlGroup {
+                    name,
+                    total: 0.0,
+                    items: Vec::new(),
+                });
+                groups.len() - 1
+            }
+        };
+        groups[i].total += item.total;
+        groups[i].items.push((rest, item.total));
+    }
+    for group in groups.iter_mut().filter(|g| !g.is_single()) {
+        for (label, _) in &mut group.items {
+            if label.is_empty() {
+                *label = "(direct)";
+            }
+        }
+    }
+    groups.sort_by(|a, b| b.total.abs().total_cmp(&a.total.abs()));
+    groups
+}
+
+/// Profit & loss by category. Sub-categories are named by their full path;
+/// `depth` rolls them up into their ancestor at that level (1 = top level).
 pub fn get_pnl(
     conn: &Connection,
     year: Option<i32>,
     month: Option<u32>,
     from_date: Option<&str>,
     to_date: Option<&str>,
+    depth: Option<usize>,
+    exclude_tags: &[String],
 ) -> Result<PnlReport> {
-    let (clause, params) = date_filter(year, month, from_date, to_date)?;
+    check_depth(depth)?;
+    let (clause, mut params) = date_filter(conn, year, month, from_date, to_date)?;
+    let clause = clause + &exclude_tags_clause(exclude_tags, &mut params)?;
+    let _archives = archive_scope(conn, year, month, from_date, to_date)?;
+    let paths = category_paths(conn)?;
 
-    let income = query_category_totals(conn, &clause, &params, "income", "total DESC")?;
-    let expenses = query_category_totals(conn, &clause, &params, "expense", "total ASC")?;
+    let income = query_category_totals(conn, &clause, &params, "income", &paths, depth)?;
+    let expenses = query_category_totals(conn, &clause, &params, "expense", &paths, depth)?;
 
     let total_income: f64 = income.iter().map(|i| i.total).sum();
     let total_expenses: f64 = expenses.iter().map(|i| i.total).sum();
@@ -88,36 +317,261 @@ pub fn get_pnl(
     })
 }
 
-fn query_category_totals(
+/// Per-category `(path, total, count)` rows of one category type.
+fn query_category_rows(
     conn: &Connection,
     clause: &str,
     params: &[String],
     category_type: &str,
-    order: &str,
-) -> Result<Vec<PnlItem>> {
+    paths: &HashMap<i64, Vec<String>>,
+) -> Result<Vec<(Vec<String>, f64, i64)>> {
     let sql = format!(
-        "SELECT c.name, SUM(t.amount) as total \
+        "SELECT c.id, c.name, SUM({HOME_AMOUNT_SQL}) as total, COUNT(*) as count \
          FROM transactions t JOIN categories c ON t.category_id = c.id \
+         JOIN accounts a ON t.account_id = a.id \
          WHERE {clause} AND c.category_type = '{category_type}' \
-         GROUP BY c.name ORDER BY {order}"
+         GROUP BY c.id"
     );
     let mut stmt = conn.prepare(&sql)?;
     let param_values = to_sql_params(params);
-    let rows = stmt.query_map(param_values.as_slice(), |row| {
-        Ok(PnlItem {
-            name: row.get(0)?,
-            total: row.get(1)?,
+    let rows = stmt
+        .query_map(param_values.as_slice(), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, name, total, count)| {
+            let path = paths.get(&id).cloned().unwrap_or_else(|| vec![name]);
+            (path, total, count)
+        })
+        .collect())
+}
+
+fn query_category_totals(
+    conn: &Connection,
+    clause: &str,
+    params: &[String],
+    category_type: &str,
+    paths: &HashMap<i64, Vec<String>>,
+    depth: Option<usize>,
+) -> Result<Vec<PnlItem>> {
+    let rows = query_category_rows(conn, clause, params, category_type, paths)?;
+    let mut rows = roll_up(rows, depth);
+    sort_rows(&mut rows, category_type == "income");
+    Ok(rows
+        .into_iter()
+        .map(|(path, total, _)| PnlItem {
+            name: path.join(CATEGORY_PATH_SEP),
+            total,
         })
+        .collect())
+}
+
+// ---------------------------------------------------------------------------
+// P&L comparison
+// ---------------------------------------------------------------------------
+
+/// A P&L line across several periods; `totals[i]` belongs to `periods[i]`.
+pub struct PnlCompareItem {
+    pub name: String,
+    pub totals: Vec<f64>,
+}
+
+/// P&L for the selected period side by side with comparison periods. The
+/// first period is the selected one; the rest are the prior period and the
+/// same period last year (a full year has only one comparison, since its
+/// prior period is last year).
+pub struct PnlComparison {
+    pub periods: Vec<String>,
+    pub income: Vec<PnlCompareItem>,
+    pub expenses: Vec<PnlCompareItem>,
+    pub total_income: Vec<f64>,
+    pub total_expenses: Vec<f64>,
+    pub net: Vec<f64>,
+}
+
+/// Dollar and percent change from `base` to `current`. The percent is
+/// relative to the size of `base` and is `None` when `base` is zero.
+pub fn period_change(current: f64, base: f64) -> (f64, Option<f64>) {
+    let diff = current - base;
+    let pct = if base.abs() < 0.005 {
+        None
+    } else {
+        Some(diff / base.abs() * 100.0)
+    };
+    (diff, pct)
+}
+
+fn month_bounds(year: i32, month: u32) -> Result<(NaiveDate, NaiveDate)> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| {
+        crate::error::NigelError::Other(format!("Invalid month: {year}-{month:02}"))
     })?;
-    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    let end = start
+        .checked_add_months(Months::new(1))
+        .and_then(|d| d.pred_opt())
+        .unwrap_or(start);
+    Ok((start, end))
+}
+
+fn parse_report_date(date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+        crate::error::NigelError::Other(format!("Invalid date: '{date}' (expected YYYY-MM-DD)"))
+    })
+}
+
+fn shift_year_back(date: NaiveDate) -> NaiveDate {
+    date.checked_sub_months(Months::new(12)).unwrap_or(date)
+}
+
+/// `(label, from, to)` for the selected period followed by its comparisons.
+fn comparison_periods(
+    fiscal: FiscalYear,
+    year: Option<i32>,
+    month: Option<u32>,
+    from_date: Option<&str>,
+    to_date: Option<&str>,
+) -> Result<Vec<(String, NaiveDate, NaiveDate)>> {
+    if from_date.is_some() || to_date.is_some() {
+        let (Some(from), Some(to)) = (from_date, to_date) else {
+            return Err(crate::error::NigelError::Other(
+                "--from and --to must be given together".to_string(),
+            ));
+        };
+        let (from, to) = (parse_report_date(from)?, parse_report_date(to)?);
+        if to < from {
+            return Err(crate::error::NigelError::Other(
+                "--to must not be before --from".to_string(),
+            ));
+        }
+        // Prior period: the same number of days ending the day before `from`
+        let prior_to = from.pred_opt().unwrap_or(from);
+        let prior_from = prior_to - (to - from);
+        let label = |a: NaiveDate, b: NaiveDate| format!("{a} to {b}");
+        let (ly_from, ly_to) = (shift_year_back(from), shift_year_back(to));
+        return Ok(vec![
+            (label(from, to), from, to),
+            (label(prior_from, prior_to), prior_from, prior_to),
+            (label(ly_from, ly_to), ly_from, ly_to),
+        ]);
+    }
+
+    let year = year.unwrap_or_else(|| fiscal.current());
+    if let Some(m) = month {
+        let (prior_y, prior_m) = if m == 1 {
+            (year - 1, 12)
+        } else {
+            (year, m - 1)
+        };
+        let label = |y: i32, m: u32| -> Result<(String, NaiveDate, NaiveDate)> {
+            let (from, to) = month_bounds(y, m)?;
+            Ok((from.format("%b %Y").to_string(), from, to))
+        };
+        return Ok(vec![
+            label(year, m)?,
+            label(prior_y, prior_m)?,
+            label(year - 1, m)?,
+        ]);
+    }
+
+    let full_year = |y: i32| {
+        let (from, to) = fiscal.bounds(y);
+        (fiscal.label(y), from, to)
+    };
+    Ok(vec![full_year(year), full_year(year - 1)])
+}
+
+/// Merge one period's lines into the comparison rows, keeping the order in
+/// which categories first appear (selected period first).
+fn merge_compare_items(
+    rows: &mut Vec<PnlCompareItem>,
+    items: &[PnlItem],
+    idx: usize,
+    num_periods: usize,
+) {
+    for item in items {
+        match rows.iter_mut().find(|r| r.name == item.name) {
+            Some(row) => row.totals[idx] = item.total,
+            None => {
+                let mut totals = vec![0.0; num_periods];
+                totals[idx] = item.total;
+                rows.push(PnlCompareItem {
+                    name: item.name.clone(),
+                    totals,
+                });
+            }
+        }
+    }
+}
+
+/// Period-over-period P&L: the selected period (year, month, or `--from`/
+/// `--to` range; defaults to the current year) against the prior period and
+/// the same period last year.
+pub fn get_pnl_comparison(
+    conn: &Connection,
+    year: Option<i32>,
+    month: Option<u32>,
+    from_date: Option<&str>,
+    to_date: Option<&str>,
+    depth: Option<usize>,
+    exclude_tags: &[String],
+) -> Result<PnlComparison> {
+    let periods = comparison_periods(FiscalYear::load(conn), year, month, from_date, to_date)?;
+    let earliest = periods
+        .iter()
+        .map(|(_, from, _)| *from)
+        .min()
+        .map(|d| d.to_string());
+    let latest = periods
+        .iter()
+        .map(|(_, _, to)| *to)
+        .max()
+        .map(|d| d.to_string());
+    let _archives = archive::scope(conn, earliest.as_deref(), latest.as_deref())?;
+    let n = periods.len();
+    let mut cmp = PnlComparison {
+        periods: Vec::with_capacity(n),
+        income: Vec::new(),
+        expenses: Vec::new(),
+        total_income: Vec::with_capacity(n),
+        total_expenses: Vec::with_capacity(n),
+        net: Vec::with_capacity(n),
+    };
+    for (idx, (label, from, to)) in periods.into_iter().enumerate() {
+        let (from, to) = (from.to_string(), to.to_string());
+        let report = get_pnl(
+            conn,
+            None,
+            None,
+            Some(&from),
+            Some(&to),
+            depth,
+            exclude_tags,
+        )?;
+        merge_compare_items(&mut cmp.income, &report.income, idx, n);
+        merge_compare_items(&mut cmp.expenses, &report.expenses, idx, n);
+        cmp.periods.push(label);
+        cmp.total_income.push(report.total_income);
+        cmp.total_expenses.push(report.total_expenses);
+        cmp.net.push(report.net);
+    }
+    Ok(cmp)
 }
 
 // ---------------------------------------------------------------------------
 // Expense Breakdown
 // ---------------------------------------------------------------------------
 
+#[derive(Clone)]
 pub struct ExpenseItem {
     pub name: String,
+    /// Category path, root first. `name` is this joined with `CATEGORY_PATH_SEP`.
+    pub path: Vec<String>,
     pub total: f64,
     pub count: i64,
     pub pct: f64,
@@ -135,43 +589,64 @@ pub struct ExpenseBreakdown {
     pub top_vendors: Vec<VendorItem>,
 }
 
+/// Roll expense items up to `depth` path levels, recomputing each share
+/// against `total`. Used by the report itself and by the view's drill-down.
+pub fn roll_up_expenses(
+    items: &[ExpenseItem],
+    depth: Option<usize>,
+    total: f64,
+) -> Vec<ExpenseItem> {
+    let rows = items
+        .iter()
+        .map(|i| (i.path.clone(), i.total, i.count))
+        .collect();
+    let mut rows = roll_up(rows, depth);
+    sort_rows(&mut rows, false);
+    rows.into_iter()
+        .map(|(path, t, count)| ExpenseItem {
+            name: path.join(CATEGORY_PATH_SEP),
+            path,
+            total: t,
+            count,
+            pct: if total != 0.0 { t / total * 100.0 } else { 0.0 },
+        })
+        .collect()
+}
+
 pub fn get_expense_breakdown(
     conn: &Connection,
     year: Option<i32>,
     month: Option<u32>,
+    depth: Option<usize>,
+    exclude_tags: &[String],
 ) -> Result<ExpenseBreakdown> {
+    check_depth(depth)?;
     // Custom date ranges (--from/--to) not supported here; expense breakdown
     // is scoped by year/month only, matching the CLI subcommand interface.
-    let (clause, params) = date_filter(year, month, None, None)?;
-
-    let sql = format!(
-        "SELECT c.name, SUM(t.amount) as total, COUNT(*) as count \
-         FROM transactions t JOIN categories c ON t.category_id = c.id \
-         WHERE {clause} AND c.category_type = 'expense' \
-         GROUP BY c.name ORDER BY total ASC"
-    );
-    let mut stmt = conn.prepare(&sql)?;
-    let param_values = to_sql_params(&params);
-    let raw: Vec<(String, f64, i64)> = stmt
-        .query_map(param_values.as_slice(), |row| {
-            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
-        })?
-        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let (clause, mut params) = date_filter(conn, year, month, None, None)?;
+    let clause = clause + &exclude_tags_clause(exclude_tags, &mut params)?;
+    let paths = category_paths(conn)?;
+    let _archives = archive_scope(conn, year, month, None, None)?;
 
+    let raw = query_category_rows(conn, &clause, &params, "expense", &paths)?;
     let total: f64 = raw.iter().map(|(_, t, _)| t).sum();
-    let categories = raw
-        .iter()
-        .map(|(name, t, c)| ExpenseItem {
-            name: name.clone(),
-            total: *t,
-            count: *c,
-            pct: if total != 0.0 { t / total * 100.0 } else { 0.0 },
+    let leaves: Vec<ExpenseItem> = raw
+        .into_iter()
+        .map(|(path, t, count)| ExpenseItem {
+            name: path.join(CATEGORY_PATH_SEP),
+            path,
+            total: t,
+            count,
+            pct: 0.0,
         })
         .collect();
+    let categories = roll_up_expenses(&leaves, depth, total);
+    let param_values = to_sql_params(&params);
 
     let vendor_sql = format!(
-        "SELECT t.vendor, SUM(t.amount) as total, COUNT(*) as count \
+        "SELECT t.vendor, SUM({HOME_AMOUNT_SQL}) as total, COUNT(*) as count \
          FROM transactions t JOIN categories c ON t.category_id = c.id \
+         JOIN accounts a ON t.account_id = a.id \
          WHERE {clause} AND c.category_type = 'expense' AND t.vendor IS NOT NULL \
          GROUP BY t.vendor ORDER BY total ASC LIMIT 10"
     );
@@ -206,15 +681,26 @@ pub struct TaxItem {
 
 pub struct TaxSummary {
     pub line_items: Vec<TaxItem>,
+    /// Limited categories (`nigel limits`) with the year's spend per person.
+    pub limits: Vec<crate::expense_limits::LimitCheck>,
+}
+
+impl TaxSummary {
+    /// Every limit warning, so a deduction can be cut back before filing.
+    pub fn limit_warnings(&self) -> Vec<String> {
+        self.limits.iter().flat_map(|l| l.warnings()).collect()
+    }
 }
 
 pub fn get_tax_summary(conn: &Connection, year: Option<i32>) -> Result<TaxSummary> {
-    let (clause, params) = date_filter(year, None, None, None)?;
+    let (clause, params) = date_filter(conn, year, None, None, None)?;
+    let _archives = archive_scope(conn, year, None, None, None)?;
 
     let sql = format!(
-        "SELECT c.name, c.tax_line, c.category_type, SUM(t.amount) as total \
+        "SELECT c.name, c.tax_line, c.category_type, SUM({HOME_AMOUNT_SQL}) as total \
          FROM transactions t JOIN categories c ON t.category_id = c.id \
-         WHERE {clause} \
+         JOIN accounts a ON t.account_id = a.id \
+         WHERE {clause} AND c.category_type != 'transfer' \
          GROUP BY c.name, c.tax_line, c.category_type \
          ORDER BY c.category_type DESC, c.tax_line"
     );
@@ -231,15 +717,76 @@ pub fn get_tax_summary(conn: &Connection, year: Option<i32>) -> Result<TaxSummar
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
 
-    Ok(TaxSummary { line_items: items })
+    Ok(TaxSummary {
+        line_items: items,
+        limits: crate::expense_limits::check(conn, &clause, &params)?,
+    })
 }
 
 // ---------------------------------------------------------------------------
 // Cash Flow
 // ---------------------------------------------------------------------------
 
+/// How the cash flow report buckets transactions.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Granularity {
+    /// Weeks starting Monday.
+    Week,
+    #[default]
+    Month,
+    /// Fiscal quarters.
+    Quarter,
+}
+
+impl Granularity {
+    pub const ALL: [Granularity; 3] = [Self::Week, Self::Month, Self::Quarter];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Quarter => "quarter",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|g| g.key().eq_ignore_ascii_case(raw.trim()))
+            .ok_or_else(|| {
+                crate::error::NigelError::Other(format!(
+                    "Unknown granularity: {raw} (use week, month, or quarter)"
+                ))
+            })
+    }
+
+    /// Column heading for the period.
+    pub fn heading(self) -> &'static str {
+        match self {
+            Self::Week => "Week of",
+            Self::Month => "Month",
+            Self::Quarter => "Quarter",
+        }
+    }
+
+    /// Week -> month -> quarter -> week.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Week => Self::Month,
+            Self::Month => Self::Quarter,
+            Self::Quarter => Self::Week,
+        }
+    }
+}
+
+/// One period of the cash flow report.
 pub struct CashflowMonth {
+    /// Sortable period key: `YYYY-MM`, the week's Monday as `YYYY-MM-DD`,
+    /// or the fiscal quarter as `YYYY-Qn`.
     pub month: String,
+    /// The period as shown: `2025-03`, the Monday in the date format, or
+    /// `2025 Q1` (`FY2025 Q1` for a non-calendar fiscal year).
+    pub label: String,
     pub inflows: f64,
     pub outflows: f64,
     pub net: f64,
@@ -247,22 +794,81 @@ pub struct CashflowMonth {
 }
 
 pub struct CashflowReport {
+    pub granularity: Granularity,
     pub months: Vec<CashflowMonth>,
 }
 
+/// Cash flow by calendar month.
 pub fn get_cashflow(
     conn: &Connection,
     year: Option<i32>,
     month: Option<u32>,
+    exclude_tags: &[String],
+) -> Result<CashflowReport> {
+    get_cashflow_by(conn, year, month, exclude_tags, Granularity::Month)
+}
+
+/// SQL for the period key of `t.date`; quarters count from the fiscal
+/// year's first month and are keyed by the fiscal year.
+fn period_key_sql(granularity: Granularity, fiscal: FiscalYear) -> String {
+    match granularity {
+        Granularity::Month => "substr(t.date, 1, 7)".to_string(),
+        // strftime('%w') is 0 on Sunday; step back to that week's Monday
+        Granularity::Week => {
+            "date(t.date, '-' || ((CAST(strftime('%w', t.date) AS INTEGER) + 6) % 7) || ' days')"
+                .to_string()
+        }
+        Granularity::Quarter => {
+            let start = fiscal.start_month() as i32;
+            let shifted = format!("(CAST(substr(t.date, 6, 2) AS INTEGER) - {start} + 12) % 12");
+            let fy = if fiscal.is_calendar() {
+                "CAST(substr(t.date, 1, 4) AS INTEGER)".to_string()
+            } else {
+                format!(
+                    "CAST(substr(t.date, 1, 4) AS INTEGER) + \
+                     (CAST(substr(t.date, 6, 2) AS INTEGER) >= {start})"
+                )
+            };
+            format!("printf('%04d-Q%d', {fy}, {shifted} / 3 + 1)")
+        }
+    }
+}
+
+fn period_label(key: &str, granularity: Granularity, fiscal: FiscalYear) -> String {
+    match granularity {
+        Granularity::Month => key.to_string(),
+        Granularity::Week => crate::fmt::date(key),
+        Granularity::Quarter => {
+            let (fy, q) = key.split_once('-').unwrap_or((key, ""));
+            if fiscal.is_calendar() {
+                format!("{fy} {q}")
+            } else {
+                format!("FY{fy} {q}")
+            }
+        }
+    }
+}
+
+/// Cash flow by week, month, or fiscal quarter.
+pub fn get_cashflow_by(
+    conn: &Connection,
+    year: Option<i32>,
+    month: Option<u32>,
+    exclude_tags: &[String],
+    granularity: Granularity,
 ) -> Result<CashflowReport> {
-    let (clause, params) = date_filter(year, month, None, None)?;
+    let (clause, mut params) = date_filter(conn, year, month, None, None)?;
+    let clause = clause + &exclude_tags_clause(exclude_tags, &mut params)?;
+    let _archives = archive_scope(conn, year, month, None, None)?;
+    let fiscal = FiscalYear::load(conn);
+    let key = period_key_sql(granularity, fiscal);
 
     let sql = format!(
-        "SELECT substr(t.date, 1, 7) as month, \
-         SUM(CASE WHEN t.amount > 0 THEN t.amount ELSE 0 END) as inflows, \
-         SUM(CASE WHEN t.amount < 0 THEN t.amount ELSE 0 END) as outflows \
-         FROM transactions t WHERE {clause} \
-         GROUP BY substr(t.date, 1, 7) ORDER BY month"
+        "SELECT {key} as period, \
+         SUM(CASE WHEN t.amount > 0 THEN {HOME_AMOUNT_SQL} ELSE 0 END) as inflows, \
+         SUM(CASE WHEN t.amount < 0 THEN {HOME_AMOUNT_SQL} ELSE 0 END) as outflows \
+         FROM transactions t JOIN accounts a ON t.account_id = a.id WHERE {clause} \
+         GROUP BY period ORDER BY period"
     );
     let mut stmt = conn.prepare(&sql)?;
     let param_values = to_sql_params(&params);
@@ -273,20 +879,21 @@ pub fn get_cashflow(
         .collect::<std::result::Result<Vec<_>, _>>()?;
 
     // When filtered to a single month, seed the running balance with the
-    // cumulative total from prior months in that year so users see the
+    // cumulative total from prior months in that fiscal year so users see the
     // correct year-to-date cash position, not just that month's net.
     let prior_balance = if let (Some(y), Some(m)) = (year, month) {
-        if m > 1 {
-            let end = format!("{y:04}-{m:02}");
-            conn.query_row(
-                "SELECT COALESCE(SUM(amount), 0) FROM transactions \
-                 WHERE date >= ?1 AND date < ?2",
-                rusqlite::params![format!("{y:04}-01"), end],
-                |row| row.get::<_, f64>(0),
-            )?
-        } else {
-            0.0
-        }
+        let (fy_start, _) = fiscal.bounds(fiscal.year_of_month(y, m));
+        let mut prior_params = vec![fy_start.to_string(), format!("{y:04}-{m:02}")];
+        let excluded = exclude_tags_clause(exclude_tags, &mut prior_params)?;
+        conn.query_row(
+            &format!(
+                "SELECT COALESCE(SUM({HOME_AMOUNT_SQL}), 0) \
+                 FROM transactions t JOIN accounts a ON t.account_id = a.id \
+                 WHERE t.deleted_at IS NULL AND t.date >= ?1 AND t.date < ?2{excluded}"
+            ),
+            to_sql_params(&prior_params).as_slice(),
+            |row| row.get::<_, f64>(0),
+        )?
     } else {
         0.0
     };
@@ -296,6 +903,7 @@ pub fn get_cashflow(
     for (m, inflows, outflows) in raw {
         running += inflows + outflows;
         months.push(CashflowMonth {
+            label: period_label(&m, granularity, fiscal),
             month: m,
             inflows,
             outflows,
@@ -304,23 +912,42 @@ pub fn get_cashflow(
         });
     }
 
-    Ok(CashflowReport { months })
+    Ok(CashflowReport {
+        granularity,
+        months,
+    })
 }
 
 // ---------------------------------------------------------------------------
 // Register (all transactions)
 // ---------------------------------------------------------------------------
 
+/// A transaction amount in a currency other than the home currency.
+#[derive(Debug, Clone)]
+pub struct ForeignAmount {
+    pub currency: String,
+    pub amount: f64,
+}
+
+#[derive(Clone)]
 pub struct RegisterRow {
     pub id: i64,
     pub date: String,
     pub description: String,
+    /// Amount in the home currency.
     pub amount: f64,
+    /// Original amount, when the transaction is in a foreign currency.
+    pub foreign: Option<ForeignAmount>,
     pub category: Option<String>,
     pub category_id: Option<i64>,
     pub vendor: Option<String>,
     pub account_name: String,
     pub is_flagged: bool,
+    /// Free-form note added in the browser or reviewer.
+    pub memo: Option<String>,
+    /// Tags, sorted, without the leading `#`.
+    pub tags: Vec<String>,
+    pub client: Option<String>,
 }
 
 pub struct RegisterReport {
@@ -335,44 +962,370 @@ pub fn get_register(
     from_date: Option<&str>,
     to_date: Option<&str>,
     account: Option<&str>,
+    tag: Option<&str>,
 ) -> Result<RegisterReport> {
-    let (clause, mut params) = date_filter(year, month, from_date, to_date)?;
+    let query = RegisterQuery {
+        year,
+        month,
+        from_date: from_date.map(str::to_string),
+        to_date: to_date.map(str::to_string),
+        account: account.map(str::to_string),
+        tag: tag.map(str::to_string),
+        ..Default::default()
+    };
+    let rows = register_rows_where(conn, &query, REGISTER_ORDER, |_| true)?;
+    let total: f64 = rows.iter().map(|r| r.amount).sum();
+    Ok(RegisterReport { rows, total })
+}
+
+/// Default register order, and the tie-breaker for every other sort.
+pub const REGISTER_ORDER: &str = "t.date, t.id";
+
+/// The filters behind a register, kept so the browser can page through a
+/// large one with targeted queries instead of loading every row.
+#[derive(Clone, Debug, Default)]
+pub struct RegisterQuery {
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+    pub account: Option<String>,
+    pub tag: Option<String>,
+    pub category_id: Option<i64>,
+    /// Take in the sub-categories of `category_id` too, for a report line
+    /// that rolls them up.
+    pub subcategories: bool,
+    /// Leave out transactions with any of these tags, as the report drilled
+    /// down from did.
+    pub exclude_tags: Vec<String>,
+}
+
+impl RegisterQuery {
+    fn clause(&self, conn: &Connection) -> Result<(String, Vec<String>)> {
+        let (mut clause, mut params) = date_filter(
+            conn,
+            self.year,
+            self.month,
+            self.from_date.as_deref(),
+            self.to_date.as_deref(),
+        )?;
+        if let Some(acc) = &self.account {
+            params.push(acc.clone());
+            clause.push_str(&format!(" AND a.name = ?{}", params.len()));
+        }
+        if let Some(tag) = &self.tag {
+            params.push(crate::tags::normalize_tag(tag)?);
+            clause.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM transaction_tags tt JOIN tags g ON tt.tag_id = g.id \
+                 WHERE tt.transaction_id = t.id AND g.name = ?{})",
+                params.len()
+            ));
+        }
+        if let Some(id) = self.category_id {
+            params.push(id.to_string());
+            if self.subcategories {
+                clause.push_str(&format!(
+                    " AND t.category_id IN (WITH RECURSIVE sub(id) AS (SELECT CAST(?{} AS INTEGER) \
+                     UNION SELECT s.id FROM categories s JOIN sub ON s.parent_id = sub.id) \
+                     SELECT id FROM sub)",
+                    params.len()
+                ));
+            } else {
+                clause.push_str(&format!(" AND t.category_id = ?{}", params.len()));
+            }
+        }
+        clause.push_str(&exclude_tags_clause(&self.exclude_tags, &mut params)?);
+        Ok((clause, params))
+    }
+
+    fn archives<'a>(&self, conn: &'a Connection) -> Result<ArchiveScope<'a>> {
+        archive_scope(
+            conn,
+            self.year,
+            self.month,
+            self.from_date.as_deref(),
+            self.to_date.as_deref(),
+        )
+    }
+}
+
+const REGISTER_FROM: &str = "FROM transactions t \
+     JOIN accounts a ON t.account_id = a.id \
+     LEFT JOIN categories c ON t.category_id = c.id";
+
+pub struct RegisterSummary {
+    pub count: usize,
+    pub total: f64,
+    /// Whether any row is in a currency other than the home one.
+    pub has_foreign: bool,
+}
+
+/// Size and net of a register, without loading its rows.
+pub fn register_summary(conn: &Connection, query: &RegisterQuery) -> Result<RegisterSummary> {
+    let (clause, mut params) = query.clause(conn)?;
+    let _archives = query.archives(conn)?;
+    params.push(crate::currency::home_currency(conn));
+    let sql = format!(
+        "SELECT COUNT(*), COALESCE(SUM({HOME_AMOUNT_SQL}), 0), \
+         COALESCE(MAX(COALESCE(t.currency, a.currency) <> ?{}), 0) \
+         {REGISTER_FROM} WHERE {clause}",
+        params.len()
+    );
+    Ok(
+        conn.query_row(&sql, to_sql_params(&params).as_slice(), |row| {
+            Ok(RegisterSummary {
+                count: row.get::<_, i64>(0)? as usize,
+                total: row.get(1)?,
+                has_foreign: row.get(2)?,
+            })
+        })?,
+    )
+}
 
-    let account_clause = if let Some(acc) = account {
-        params.push(acc.to_string());
-        format!(" AND a.name = ?{}", params.len())
+/// Up to `limit` register rows starting at `offset`, in `order_by` order.
+pub fn register_page(
+    conn: &Connection,
+    query: &RegisterQuery,
+    order_by: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<RegisterRow>> {
+    query_register(conn, query, order_by, Some((offset, limit)), |_| true)
+}
+
+/// Every register row `keep` accepts, in `order_by` order. Rows are
+/// streamed through `keep`, so a filter over a large register only holds
+/// the matches.
+pub fn register_rows_where(
+    conn: &Connection,
+    query: &RegisterQuery,
+    order_by: &str,
+    keep: impl FnMut(&RegisterRow) -> bool,
+) -> Result<Vec<RegisterRow>> {
+    query_register(conn, query, order_by, None, keep)
+}
+
+fn query_register(
+    conn: &Connection,
+    query: &RegisterQuery,
+    order_by: &str,
+    page: Option<(usize, usize)>,
+    mut keep: impl FnMut(&RegisterRow) -> bool,
+) -> Result<Vec<RegisterRow>> {
+    let (clause, params) = query.clause(conn)?;
+    let _archives = query.archives(conn)?;
+    let limit = page
+        .map(|(offset, limit)| format!(" LIMIT {limit} OFFSET {offset}"))
+        .unwrap_or_default();
+    let sql = format!(
+        "SELECT t.id, t.date, t.description, {HOME_AMOUNT_SQL}, c.name, t.category_id, t.vendor, a.name, t.is_flagged, \
+         COALESCE(t.currency, a.currency), t.amount, t.memo, {TAGS_CONCAT_SQL}, {CLIENT_NAME_SQL} \
+         {REGISTER_FROM} \
+         WHERE {clause} \
+         ORDER BY {order_by}{limit}"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let param_values = to_sql_params(&params);
+    let home = crate::currency::home_currency(conn);
+    let mut rows = stmt.query(param_values.as_slice())?;
+    let mut out = Vec::new();
+    while let Some(row) = rows.next()? {
+        let currency: String = row.get(9)?;
+        let foreign = if currency != home {
+            Some(ForeignAmount {
+                currency,
+                amount: row.get(10)?,
+            })
+        } else {
+            None
+        };
+        let row = RegisterRow {
+            id: row.get(0)?,
+            date: row.get(1)?,
+            description: row.get(2)?,
+            amount: row.get(3)?,
+            foreign,
+            category: row.get(4)?,
+            category_id: row.get(5)?,
+            vendor: row.get(6)?,
+            account_name: row.get(7)?,
+            is_flagged: row.get(8)?,
+            memo: row.get(11)?,
+            tags: crate::tags::from_concat(row.get(12)?),
+            client: row.get(13)?,
+        };
+        if keep(&row) {
+            out.push(row);
+        }
+    }
+    Ok(out)
+}
+
+/// Zero-based positions, in `order_by` order, of the rows matching `cond`.
+/// `cond` sees the columns `id`, `date`, `description`, `vendor`, `memo`,
+/// and `category` and binds `values` as `?v1`, `?v2`, ….
+fn register_positions(
+    conn: &Connection,
+    query: &RegisterQuery,
+    order_by: &str,
+    select: &str,
+    cond: &str,
+    values: &[&str],
+) -> Result<Vec<usize>> {
+    let (clause, mut params) = query.clause(conn)?;
+    let _archives = query.archives(conn)?;
+    let mut cond = cond.to_string();
+    // Highest first, so `?v1` doesn't eat the start of `?v10`
+    for i in (1..=values.len()).rev() {
+        cond = cond.replace(&format!("?v{i}"), &format!("?{}", params.len() + i));
+    }
+    params.extend(values.iter().map(|v| v.to_string()));
+    let sql = format!(
+        "SELECT {select} FROM (\
+           SELECT t.id AS id, t.date AS date, t.description AS description, \
+                  t.vendor AS vendor, t.memo AS memo, c.name AS category, \
+                  ROW_NUMBER() OVER (ORDER BY {order_by}) - 1 AS pos \
+           {REGISTER_FROM} WHERE {clause}) \
+         WHERE {cond} ORDER BY 1"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let positions = stmt
+        .query_map(to_sql_params(&params).as_slice(), |row| {
+            row.get::<_, Option<i64>>(0)
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(positions
+        .into_iter()
+        .flatten()
+        .map(|p| p as usize)
+        .collect())
+}
+
+/// Position of transaction `id` in the register, if it's in it.
+pub fn register_position_of_id(
+    conn: &Connection,
+    query: &RegisterQuery,
+    order_by: &str,
+    id: i64,
+) -> Result<Option<usize>> {
+    let found = register_positions(conn, query, order_by, "pos", "id = ?v1", &[&id.to_string()])?;
+    Ok(found.first().copied())
+}
+
+/// Position of the first row dated on or after `date` (on or before, when
+/// `on_or_before`), in `order_by` order.
+pub fn register_position_of_date(
+    conn: &Connection,
+    query: &RegisterQuery,
+    order_by: &str,
+    date: &str,
+    on_or_before: bool,
+) -> Result<Option<usize>> {
+    let cond = if on_or_before {
+        "date <= ?v1"
     } else {
-        String::new()
+        "date >= ?v1"
     };
+    let found = register_positions(conn, query, order_by, "MIN(pos)", cond, &[date])?;
+    Ok(found.first().copied())
+}
+
+/// Positions of the rows whose description, vendor, memo, or category
+/// contains `needle`, ignoring case. The text columns are looked up in the
+/// full-text index (`search::FTS_TABLE`) rather than scanned.
+pub fn register_search(
+    conn: &Connection,
+    query: &RegisterQuery,
+    order_by: &str,
+    needle: &str,
+) -> Result<Vec<usize>> {
+    let phrase = crate::search::match_query(needle).unwrap_or_default();
+    register_positions(
+        conn,
+        query,
+        order_by,
+        "pos",
+        &crate::search::contains_sql("?v1", "?v2"),
+        &[needle, &phrase],
+    )
+}
+
+/// ID of the latest transaction dated on or before `date`, by date then ID.
+pub fn register_last_on_or_before(
+    conn: &Connection,
+    query: &RegisterQuery,
+    date: &str,
+) -> Result<Option<i64>> {
+    let (clause, mut params) = query.clause(conn)?;
+    let _archives = query.archives(conn)?;
+    params.push(date.to_string());
+    let sql = format!(
+        "SELECT t.id {REGISTER_FROM} WHERE {clause} AND t.date <= ?{} \
+         ORDER BY t.date DESC, t.id DESC LIMIT 1",
+        params.len()
+    );
+    Ok(conn
+        .query_row(&sql, to_sql_params(&params).as_slice(), |row| row.get(0))
+        .optional()?)
+}
+
+// ---------------------------------------------------------------------------
+// Tags
+// ---------------------------------------------------------------------------
+
+pub struct TagItem {
+    pub tag: String,
+    pub count: i64,
+    pub income: f64,
+    /// Outflows, as a negative number.
+    pub expenses: f64,
+    pub net: f64,
+}
 
+/// Income and spend per tag. Transfers are left out so a card payment tagged
+/// with a client doesn't count as spend twice. A transaction with several
+/// tags counts toward each, so the rows don't add up to the books' totals.
+pub fn get_tags_report(
+    conn: &Connection,
+    year: Option<i32>,
+    month: Option<u32>,
+) -> Result<Vec<TagItem>> {
+    let (clause, params) = date_filter(conn, year, month, None, None)?;
+    let _archives = archive_scope(conn, year, month, None, None)?;
     let sql = format!(
-        "SELECT t.id, t.date, t.description, t.amount, c.name, t.category_id, t.vendor, a.name, t.is_flagged \
-         FROM transactions t \
+        "SELECT g.name, COUNT(*), \
+         SUM(CASE WHEN t.amount > 0 THEN {HOME_AMOUNT_SQL} ELSE 0 END), \
+         SUM(CASE WHEN t.amount < 0 THEN {HOME_AMOUNT_SQL} ELSE 0 END) \
+         FROM transaction_tags tt \
+         JOIN tags g ON tt.tag_id = g.id \
+         JOIN transactions t ON tt.transaction_id = t.id \
          JOIN accounts a ON t.account_id = a.id \
          LEFT JOIN categories c ON t.category_id = c.id \
-         WHERE {clause}{account_clause} \
-         ORDER BY t.date, t.id"
+         WHERE {clause} AND COALESCE(c.category_type, '') != 'transfer' \
+         GROUP BY g.name"
     );
     let mut stmt = conn.prepare(&sql)?;
     let param_values = to_sql_params(&params);
-    let rows: Vec<RegisterRow> = stmt
+    let mut items: Vec<TagItem> = stmt
         .query_map(param_values.as_slice(), |row| {
-            Ok(RegisterRow {
-                id: row.get(0)?,
-                date: row.get(1)?,
-                description: row.get(2)?,
-                amount: row.get(3)?,
-                category: row.get(4)?,
-                category_id: row.get(5)?,
-                vendor: row.get(6)?,
-                account_name: row.get(7)?,
-                is_flagged: row.get(8)?,
+            let income: f64 = row.get(2)?;
+            let expenses: f64 = row.get(3)?;
+            Ok(TagItem {
+                tag: row.get(0)?,
+                count: row.get(1)?,
+                income,
+                expenses,
+                net: income + expenses,
             })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
-
-    let total: f64 = rows.iter().map(|r| r.amount).sum();
-    Ok(RegisterReport { rows, total })
+    // Biggest spend first, then by name
+    items.sort_by(|a, b| {
+        a.expenses
+            .total_cmp(&b.expenses)
+            .then_with(|| a.tag.cmp(&b.tag))
+    });
+    Ok(items)
 }
 
 // ---------------------------------------------------------------------------
@@ -385,13 +1338,25 @@ pub struct FlaggedTransaction {
     pub description: String,
     pub amount: f64,
     pub account_name: String,
+    /// "No matching rule", a sign mismatch on a categorized transaction, or
+    /// an unusual-transaction warning.
+    pub flag_reason: Option<String>,
+}
+
+impl FlaggedTransaction {
+    /// Flagged by `anomaly::flag_anomalies()` rather than for want of a category.
+    pub fn is_unusual(&self) -> bool {
+        self.flag_reason
+            .as_deref()
+            .is_some_and(|r| r.starts_with(crate::anomaly::ANOMALY_PREFIX))
+    }
 }
 
 pub fn get_flagged(conn: &Connection) -> Result<Vec<FlaggedTransaction>> {
     let mut stmt = conn.prepare(
-        "SELECT t.id, t.date, t.description, t.amount, a.name as account_name \
+        "SELECT t.id, t.date, t.description, t.amount, a.name as account_name, t.flag_reason \
          FROM transactions t JOIN accounts a ON t.account_id = a.id \
-         WHERE t.is_flagged = 1 ORDER BY t.date",
+         WHERE t.is_flagged = 1 AND t.deleted_at IS NULL ORDER BY t.date",
     )?;
     let rows: Vec<FlaggedTransaction> = stmt
         .query_map([], |row| {
@@ -401,6 +1366,49 @@ pub fn get_flagged(conn: &Connection) -> Result<Vec<FlaggedTransaction>> {
                 description: row.get(2)?,
                 amount: row.get(3)?,
                 account_name: row.get(4)?,
+                flag_reason: row.get(5)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+// ---------------------------------------------------------------------------
+// Recurring charges
+// ---------------------------------------------------------------------------
+
+pub struct RecurringCharge {
+    pub name: String,
+    /// Average charge per month it appeared in (negative, home currency).
+    pub average: f64,
+}
+
+/// Expenses from the same vendor (or description when no vendor is set) that
+/// appear in at least `min_months` distinct months between `from_date` and
+/// `to_date`, largest average charge first.
+pub fn get_recurring_charges(
+    conn: &Connection,
+    from_date: &str,
+    to_date: &str,
+    min_months: i64,
+) -> Result<Vec<RecurringCharge>> {
+    let _archives = archive::scope(conn, Some(from_date), Some(to_date))?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT COALESCE(NULLIF(t.vendor, ''), t.description) as payee, \
+         COUNT(DISTINCT substr(t.date, 1, 7)) as months, \
+         SUM({HOME_AMOUNT_SQL}) as total \
+         FROM transactions t JOIN accounts a ON t.account_id = a.id \
+         WHERE t.deleted_at IS NULL AND t.amount < 0 AND t.date >= ?1 AND t.date <= ?2 \
+         GROUP BY payee HAVING months >= ?3 \
+         ORDER BY total * 1.0 / months ASC"
+    ))?;
+    let rows = stmt
+        .query_map(rusqlite::params![from_date, to_date, min_months], |row| {
+            let months: i64 = row.get(1)?;
+            let total: f64 = row.get(2)?;
+            Ok(RecurringCharge {
+                name: row.get(0)?,
+                average: total / months as f64,
             })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -414,47 +1422,572 @@ pub fn get_flagged(conn: &Connection) -> Result<Vec<FlaggedTransaction>> {
 pub struct AccountBalance {
     pub name: String,
     pub account_type: String,
+    /// Balance converted to the home currency.
     pub balance: f64,
+    pub currency: String,
+    /// Balance in the account's own currency.
+    pub native_balance: f64,
+    pub last_four: Option<String>,
+    pub is_archived: bool,
+}
+
+impl AccountBalance {
+    /// Account name with its masked number, e.g. `BofA Checking (****1234)`.
+    pub fn label(&self) -> String {
+        let label = match &self.last_four {
+            Some(last) => format!("{} ({})", self.name, crate::vault::mask(last)),
+            None => self.name.clone(),
+        };
+        if self.is_archived {
+            format!("{label} [archived]")
+        } else {
+            label
+        }
+    }
 }
 
 pub struct BalanceReport {
+    pub home_currency: String,
     pub accounts: Vec<AccountBalance>,
+    /// Archived accounts left out of `accounts`; their balance is carried
+    /// forward in `total` and shown as one line when it isn't zero.
+    pub archived_count: usize,
+    pub archived_balance: f64,
     pub total: f64,
     pub ytd_net_income: f64,
 }
 
-pub fn get_balance(conn: &Connection) -> Result<BalanceReport> {
-    let mut stmt = conn.prepare(
-        "SELECT a.id, a.name, a.account_type, COALESCE(SUM(t.amount), 0) as balance \
+impl BalanceReport {
+    /// Label for the folded archived-accounts line, when there's money in it.
+    pub fn archived_line(&self) -> Option<String> {
+        (self.archived_count > 0 && (self.archived_balance * 100.0).round() != 0.0)
+            .then(|| format!("Archived accounts ({})", self.archived_count))
+    }
+}
+
+/// Cash position per account. Archived accounts are folded into one line
+/// unless `include_archived`; the total always includes them.
+pub fn get_balance(conn: &Connection, include_archived: bool) -> Result<BalanceReport> {
+    let _archives = archive::scope(conn, None, None)?;
+    // Opening balance plus transactions from the opening date on
+    let mut stmt = conn.prepare(&format!(
+        "SELECT a.id, a.name, a.account_type, \
+         {OPENING_HOME_SQL} + COALESCE(SUM({HOME_AMOUNT_SQL}), 0) as balance, \
+         a.currency, a.opening_balance + COALESCE(SUM(t.amount), 0) as native_balance, \
+         NULLIF(a.last_four, ''), a.is_archived \
          FROM accounts a LEFT JOIN transactions t ON a.id = t.account_id \
-         GROUP BY a.id ORDER BY a.name",
-    )?;
-    let accounts: Vec<AccountBalance> = stmt
+           AND t.deleted_at IS NULL \
+           AND (a.opening_date IS NULL OR t.date >= a.opening_date) \
+         GROUP BY a.id ORDER BY a.name"
+    ))?;
+    let mut accounts: Vec<AccountBalance> = stmt
         .query_map([], |row| {
             Ok(AccountBalance {
                 name: row.get(1)?,
                 account_type: row.get(2)?,
                 balance: row.get(3)?,
+                currency: row.get(4)?,
+                native_balance: row.get(5)?,
+                last_four: row.get(6)?,
+                is_archived: row.get(7)?,
             })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
 
     let total: f64 = accounts.iter().map(|a| a.balance).sum();
+    let (mut archived_count, mut archived_balance) = (0, 0.0);
+    if !include_archived {
+        accounts.retain(|a| {
+            if a.is_archived {
+                archived_count += 1;
+                archived_balance += a.balance;
+            }
+            !a.is_archived
+        });
+    }
 
-    let current_year = chrono::Local::now().year();
+    // Year to date is the current fiscal year so far
+    let fiscal = FiscalYear::load(conn);
+    let (fy_start, _) = fiscal.bounds(fiscal.current());
     let ytd_net_income: f64 = conn.query_row(
-        "SELECT COALESCE(SUM(amount), 0) as net FROM transactions WHERE date LIKE ?1",
-        [format!("{current_year}%")],
+        &format!(
+            "SELECT COALESCE(SUM({HOME_AMOUNT_SQL}), 0) as net \
+             FROM transactions t JOIN accounts a ON t.account_id = a.id \
+             WHERE t.deleted_at IS NULL AND t.date BETWEEN ?1 AND ?2"
+        ),
+        [
+            fy_start.to_string(),
+            chrono::Local::now().date_naive().to_string(),
+        ],
         |row| row.get(0),
     )?;
 
     Ok(BalanceReport {
+        home_currency: crate::currency::home_currency(conn),
         accounts,
+        archived_count,
+        archived_balance,
         total,
         ytd_net_income,
     })
 }
 
+// ---------------------------------------------------------------------------
+// Balance History
+// ---------------------------------------------------------------------------
+
+/// How often the balance history samples an account's balance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BalanceInterval {
+    #[default]
+    Day,
+    /// Weeks starting Monday, sampled at their last day.
+    Week,
+}
+
+impl BalanceInterval {
+    pub const ALL: [BalanceInterval; 2] = [Self::Day, Self::Week];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|i| i.key().eq_ignore_ascii_case(raw.trim()))
+            .ok_or_else(|| {
+                crate::error::NigelError::Other(format!(
+                    "Unknown interval: {raw} (use day or week)"
+                ))
+            })
+    }
+
+    /// Column heading for the point's date.
+    pub fn heading(self) -> &'static str {
+        match self {
+            Self::Day => "Date",
+            Self::Week => "Week ending",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Day => Self::Week,
+            Self::Week => Self::Day,
+        }
+    }
+}
+
+/// An account's balance at the end of one day or week.
+pub struct BalancePoint {
+    /// Last day the point covers (YYYY-MM-DD); a week cut short by the
+    /// period ends with it.
+    pub date: String,
+    /// Transactions in the day or week.
+    pub change: f64,
+    pub balance: f64,
+}
+
+/// One account's running balance over a period, in its own currency.
+pub struct BalanceHistory {
+    pub account: String,
+    pub currency: String,
+    /// The account is kept in a currency other than the book's.
+    pub foreign: bool,
+    pub interval: BalanceInterval,
+    /// Balances count the account's opening balance from its opening date,
+    /// as the balance report does; otherwise they are transactions only.
+    pub with_opening: bool,
+    pub from: String,
+    pub to: String,
+    /// Balance at the end of the day before `from`.
+    pub starting_balance: f64,
+    pub points: Vec<BalancePoint>,
+}
+
+impl BalanceHistory {
+    /// An amount in the account's currency: `$1,234.56`, or
+    /// `1,234.56 EUR` for a foreign account.
+    pub fn money(&self, val: f64) -> String {
+        if self.foreign {
+            crate::fmt::money_in(val, &self.currency)
+        } else {
+            crate::fmt::money(val)
+        }
+    }
+
+    pub fn ending_balance(&self) -> f64 {
+        self.points
+            .last()
+            .map_or(self.starting_balance, |p| p.balance)
+    }
+
+    /// The first point at the lowest balance.
+    pub fn low(&self) -> Option<&BalancePoint> {
+        self.points
+            .iter()
+            .reduce(|low, p| if p.balance < low.balance { p } else { low })
+    }
+
+    /// The first point at the highest balance.
+    pub fn high(&self) -> Option<&BalancePoint> {
+        self.points
+            .iter()
+            .reduce(|high, p| if p.balance > high.balance { p } else { high })
+    }
+
+    /// The points a table lists: every week, but only days with
+    /// transactions, since the balance holds between them.
+    pub fn table_points(&self) -> impl Iterator<Item = &BalancePoint> {
+        self.points
+            .iter()
+            .filter(|p| self.interval == BalanceInterval::Week || p.change.abs() >= 0.005)
+    }
+}
+
+/// The running balance of `account` by day or week over a period: a date
+/// range, a calendar month, or a fiscal year (the current one when none is
+/// given), cut off at today. With `with_opening`, the opening balance
+/// counts from the opening date and earlier transactions are left out of
+/// the balance from then on, matching `reconciler::balance_on()`.
+#[allow(clippy::too_many_arguments)]
+pub fn get_balance_history(
+    conn: &Connection,
+    account: &str,
+    year: Option<i32>,
+    month: Option<u32>,
+    from_date: Option<&str>,
+    to_date: Option<&str>,
+    interval: BalanceInterval,
+    with_opening: bool,
+) -> Result<BalanceHistory> {
+    let fiscal = FiscalYear::load(conn);
+    let (from, mut to) = match (from_date, to_date, year, month) {
+        (Some(from), Some(to), _, _) => (parse_report_date(from)?, parse_report_date(to)?),
+        (Some(_), None, _, _) | (None, Some(_), _, _) => {
+            return Err(crate::error::NigelError::Other(
+                "--from and --to must be given together".into(),
+            ))
+        }
+        (None, None, Some(y), Some(m)) => month_bounds(y, m)?,
+        (None, None, year, _) => fiscal.bounds(year.unwrap_or_else(|| fiscal.current())),
+    };
+    let today = chrono::Local::now().date_naive();
+    if (from..to).contains(&today) {
+        to = today;
+    }
+
+    let (account_id, account, currency, opening_balance, opening_date): (
+        i64,
+        String,
+        String,
+        f64,
+        Option<String>,
+    ) = conn
+        .query_row(
+            "SELECT id, name, currency, opening_balance, opening_date FROM accounts \
+             WHERE name = ?1",
+            [account],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )
+        .optional()?
+        .ok_or_else(|| crate::error::NigelError::UnknownAccount(account.to_string()))?;
+    let opening_date = opening_date.as_deref().map(parse_report_date).transpose()?;
+    let opening = with_opening.then_some((opening_date, opening_balance));
+
+    let _archives = archive::scope(conn, None, Some(&to.to_string()))?;
+    let sum_before = |date: NaiveDate, since: Option<NaiveDate>| -> Result<f64> {
+        Ok(conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0) FROM transactions \
+             WHERE account_id = ?1 AND deleted_at IS NULL AND date < ?2 \
+             AND (?3 IS NULL OR date >= ?3)",
+            rusqlite::params![account_id, date.to_string(), since.map(|d| d.to_string())],
+            |row| row.get(0),
+        )?)
+    };
+    let starting_balance = match opening {
+        // Opened by the start: the opening balance and what followed it
+        Some((since, balance)) if since.is_none_or(|d| d < from) => {
+            balance + sum_before(from, since)?
+        }
+        _ => sum_before(from, None)?,
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT date, SUM(amount) FROM transactions \
+         WHERE account_id = ?1 AND deleted_at IS NULL AND date BETWEEN ?2 AND ?3 \
+         GROUP BY date",
+    )?;
+    let daily: HashMap<String, f64> = stmt
+        .query_map(
+            rusqlite::params![account_id, from.to_string(), to.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?
+        .collect::<std::result::Result<_, _>>()?;
+
+    let mut points = Vec::new();
+    let mut balance = starting_balance;
+    let mut change = 0.0;
+    let mut day = from;
+    while day <= to {
+        let key = day.to_string();
+        let amount = daily.get(&key).copied().unwrap_or(0.0);
+        match opening {
+            // The opening balance replaces the transactions before it
+            Some((Some(since), opening)) if since == day => {
+                balance = opening + amount;
+            }
+            _ => balance += amount,
+        }
+        change += amount;
+        let week_end = day.weekday() == chrono::Weekday::Sun;
+        if interval == BalanceInterval::Day || week_end || day == to {
+            points.push(BalancePoint {
+                date: key,
+                change,
+                balance,
+            });
+            change = 0.0;
+        }
+        let Some(next) = day.succ_opt() else {
+            break;
+        };
+        day = next;
+    }
+
+    Ok(BalanceHistory {
+        foreign: currency != crate::currency::home_currency(conn),
+        account,
+        currency,
+        interval,
+        with_opening,
+        from: from.to_string(),
+        to: to.to_string(),
+        starting_balance,
+        points,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Deductions Report (standard mileage + home office)
+// ---------------------------------------------------------------------------
+
+pub struct DeductionsReport {
+    pub trips: Vec<MileageEntry>,
+    pub total_miles: f64,
+    pub mileage_deduction: f64,
+    pub home_office: HomeOffice,
+    pub home_office_deduction: f64,
+    pub total: f64,
+}
+
+pub fn get_deductions(conn: &Connection, year: Option<i32>) -> Result<DeductionsReport> {
+    let trips = deductions::list_mileage(conn, year)?;
+    let total_miles = trips.iter().map(|t| t.miles).sum();
+    let mileage_deduction = trips.iter().map(|t| t.deduction).sum::<f64>();
+    let home_office = deductions::home_office(conn);
+    let home_office_deduction = home_office.deduction();
+    Ok(DeductionsReport {
+        trips,
+        total_miles,
+        mileage_deduction,
+        home_office,
+        home_office_deduction,
+        total: mileage_deduction + home_office_deduction,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Compensation Report (officer comp vs. distributions)
+// ---------------------------------------------------------------------------
+
+/// Form lines counted as officer pay (officer comp and payroll wages), the
+/// same lines the K-1 prep report checks.
+pub(crate) const OFFICER_COMP_LINES: &str = "'1120S-7', '1120S-8'";
+pub(crate) const DISTRIBUTION_LINE: &str = "K-16d";
+
+pub struct CompQuarter {
+    /// "Q1 (Jan–Mar)"
+    pub label: String,
+    pub officer_comp: f64,
+    pub distributions: f64,
+    /// Year-to-date totals through the end of this quarter.
+    pub cumulative_comp: f64,
+    pub cumulative_distributions: f64,
+}
+
+pub struct CompensationReport {
+    pub quarters: Vec<CompQuarter>,
+    pub officer_comp: f64,
+    pub distributions: f64,
+    /// Officer comp as a percentage of owner pay.
+    pub officer_share: Option<f64>,
+    pub target_pct: f64,
+}
+
+impl CompensationReport {
+    pub fn below_target(&self) -> bool {
+        compensation::below_target(self.officer_comp, self.distributions, self.target_pct)
+    }
+}
+
+/// Officer comp and distributions by fiscal quarter of `year` (default: the
+/// current fiscal year).
+pub fn get_compensation(conn: &Connection, year: Option<i32>) -> Result<CompensationReport> {
+    let fiscal = FiscalYear::load(conn);
+    let fy = year.unwrap_or_else(|| fiscal.current());
+    let (fy_start, _) = fiscal.bounds(fy);
+    let _archives = archive_scope(conn, Some(fy), None, None, None)?;
+    let sql = format!(
+        "SELECT \
+           COALESCE(SUM(CASE WHEN c.form_line IN ({OFFICER_COMP_LINES}) THEN {HOME_AMOUNT_SQL} END), 0), \
+           COALESCE(SUM(CASE WHEN c.form_line = '{DISTRIBUTION_LINE}' THEN {HOME_AMOUNT_SQL} END), 0) \
+         FROM transactions t JOIN categories c ON t.category_id = c.id \
+         JOIN accounts a ON t.account_id = a.id \
+         WHERE t.deleted_at IS NULL AND t.date BETWEEN ?1 AND ?2"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let mut quarters = Vec::with_capacity(4);
+    let (mut cumulative_comp, mut cumulative_distributions) = (0.0f64, 0.0f64);
+    for q in 0..4u32 {
+        let start = fy_start
+            .checked_add_months(Months::new(q * 3))
+            .unwrap_or(fy_start);
+        let end = start
+            .checked_add_months(Months::new(3))
+            .and_then(|d| d.pred_opt())
+            .unwrap_or(start);
+        let (comp, dist): (f64, f64) = stmt
+            .query_row([start.to_string(), end.to_string()], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?;
+        // Both are outflows; report them as positive amounts
+        let (officer_comp, distributions) = (comp.abs(), dist.abs());
+        cumulative_comp += officer_comp;
+        cumulative_distributions += distributions;
+        quarters.push(CompQuarter {
+            label: format!(
+                "Q{} ({}\u{2013}{})",
+                q + 1,
+                crate::fiscal::month_abbr(start.month()),
+                crate::fiscal::month_abbr(end.month())
+            ),
+            officer_comp,
+            distributions,
+            cumulative_comp,
+            cumulative_distributions,
+        });
+    }
+
+    Ok(CompensationReport {
+        quarters,
+        officer_comp: cumulative_comp,
+        distributions: cumulative_distributions,
+        officer_share: compensation::officer_share(cumulative_comp, cumulative_distributions),
+        target_pct: compensation::target_pct(conn),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Client Profitability
+// ---------------------------------------------------------------------------
+
+pub struct ClientProfit {
+    pub name: String,
+    pub revenue: f64,
+    /// Expenses assigned to the client, as a positive amount.
+    pub direct_costs: f64,
+}
+
+impl ClientProfit {
+    pub fn margin(&self) -> f64 {
+        self.revenue - self.direct_costs
+    }
+
+    /// Margin as a percentage of revenue; `None` without revenue.
+    pub fn margin_pct(&self) -> Option<f64> {
+        (self.revenue.abs() >= 0.005).then(|| self.margin() / self.revenue * 100.0)
+    }
+}
+
+#[derive(Default)]
+pub struct ClientsReport {
+    /// Clients with activity in the year, highest revenue first.
+    pub clients: Vec<ClientProfit>,
+    /// Income not assigned to any client.
+    pub unassigned_revenue: f64,
+}
+
+impl ClientsReport {
+    pub fn total(&self) -> ClientProfit {
+        ClientProfit {
+            name: "Total".to_string(),
+            revenue: self.clients.iter().map(|c| c.revenue).sum(),
+            direct_costs: self.clients.iter().map(|c| c.direct_costs).sum(),
+        }
+    }
+}
+
+/// Revenue, direct costs, and margin per client for fiscal `year` (default:
+/// the current one).
+pub fn get_clients(conn: &Connection, year: Option<i32>) -> Result<ClientsReport> {
+    let fiscal = FiscalYear::load(conn);
+    let fy = year.unwrap_or_else(|| fiscal.current());
+    let (start, end) = fiscal.bounds(fy);
+    let _archives = archive_scope(conn, Some(fy), None, None, None)?;
+    let sql = format!(
+        "SELECT {CLIENT_NAME_SQL}, \
+           COALESCE(SUM(CASE WHEN c.category_type = 'income' THEN {HOME_AMOUNT_SQL} END), 0), \
+           COALESCE(SUM(CASE WHEN c.category_type = 'expense' THEN {HOME_AMOUNT_SQL} END), 0) \
+         FROM transactions t JOIN categories c ON t.category_id = c.id \
+         JOIN accounts a ON t.account_id = a.id \
+         WHERE t.deleted_at IS NULL AND t.date BETWEEN ?1 AND ?2 \
+           AND c.category_type IN ('income', 'expense') \
+         GROUP BY t.client_id"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([start.to_string(), end.to_string()], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, f64>(1)?,
+                row.get::<_, f64>(2)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut report = ClientsReport::default();
+    for (name, revenue, costs) in rows {
+        match name {
+            Some(name) => report.clients.push(ClientProfit {
+                name,
+                revenue,
+                direct_costs: -costs,
+            }),
+            None => report.unassigned_revenue += revenue,
+        }
+    }
+    report.clients.sort_by(|a, b| {
+        b.revenue
+            .total_cmp(&a.revenue)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(report)
+}
+
 // ---------------------------------------------------------------------------
 // K-1 Prep Report
 // ---------------------------------------------------------------------------
@@ -479,6 +2012,30 @@ pub struct K1Validation {
     pub officer_comp: f64,
     pub distributions: f64,
     pub comp_dist_ratio: Option<f64>,
+    /// Officer comp falls short of the configured share of owner pay.
+    pub below_comp_target: bool,
+    pub comp_target_pct: f64,
+    /// Combined stake of the shareholders; None when none are set up.
+    pub ownership_pct_total: Option<f64>,
+}
+
+impl K1Validation {
+    /// Shareholders are set up but don't add up to 100%, so part of the
+    /// income is allocated to no one.
+    pub fn ownership_incomplete(&self) -> bool {
+        self.ownership_pct_total
+            .is_some_and(|pct| (pct - 100.0).abs() > 1e-9)
+    }
+}
+
+/// One shareholder's share of the K-1 worksheet, pro rata by ownership.
+pub struct K1Allocation {
+    pub name: String,
+    pub ownership_pct: f64,
+    pub ordinary_business_income: f64,
+    pub distributions: f64,
+    /// Schedule K items other than distributions, one per form line.
+    pub schedule_k_items: Vec<K1LineItem>,
 }
 
 #[allow(dead_code)]
@@ -492,15 +2049,65 @@ pub struct K1PrepReport {
     pub other_deductions: Vec<K1OtherDeduction>,
     pub other_deductions_total: f64,
     pub validation: K1Validation,
+    /// Per-shareholder allocations, largest stake first; empty without
+    /// shareholders.
+    pub shareholders: Vec<K1Allocation>,
+}
+
+/// Split the worksheet's ordinary income, distributions, and Schedule K
+/// lines between `holders` by ownership.
+fn k1_allocations(
+    holders: &[Shareholder],
+    ordinary_business_income: f64,
+    distributions: f64,
+    schedule_k_items: &[K1LineItem],
+) -> Vec<K1Allocation> {
+    let mut k_lines: Vec<(String, f64)> = Vec::new();
+    for item in schedule_k_items
+        .iter()
+        .filter(|i| i.form_line != DISTRIBUTION_LINE)
+    {
+        match k_lines.iter_mut().find(|(line, _)| *line == item.form_line) {
+            Some((_, total)) => *total += item.total.abs(),
+            None => k_lines.push((item.form_line.clone(), item.total.abs())),
+        }
+    }
+    let obi = shareholders::allocate(ordinary_business_income, holders);
+    let dist = shareholders::allocate(distributions, holders);
+    let k_shares: Vec<Vec<f64>> = k_lines
+        .iter()
+        .map(|(_, total)| shareholders::allocate(*total, holders))
+        .collect();
+    holders
+        .iter()
+        .enumerate()
+        .map(|(i, holder)| K1Allocation {
+            name: holder.name.clone(),
+            ownership_pct: holder.ownership_pct,
+            ordinary_business_income: obi[i],
+            distributions: dist[i],
+            schedule_k_items: k_lines
+                .iter()
+                .zip(&k_shares)
+                .map(|((line, _), shares)| K1LineItem {
+                    form_line: line.clone(),
+                    category_name: k1_line_description(line).unwrap_or(line).to_string(),
+                    total: shares[i],
+                })
+                .collect(),
+        })
+        .collect()
 }
 
 pub fn get_k1_prep(conn: &Connection, year: Option<i32>) -> Result<K1PrepReport> {
-    let (clause, params) = date_filter(year, None, None, None)?;
+    let (clause, params) = date_filter(conn, year, None, None, None)?;
+    let _archives = archive_scope(conn, year, None, None, None)?;
 
     // Query all categorized transactions grouped by form_line
     let sql = format!(
-        "SELECT c.form_line, c.name, SUM(t.amount) as total \
+        "SELECT c.form_line, c.name, SUM({HOME_AMOUNT_SQL}) as total \
          FROM transactions t JOIN categories c ON t.category_id = c.id \
+         JOIN accounts a ON t.account_id = a.id \
          WHERE {clause} AND c.form_line IS NOT NULL \
          GROUP BY c.form_line, c.name ORDER BY c.form_line"
     );
@@ -565,6 +2172,37 @@ pub fn get_k1_prep(conn: &Connection, year: Option<i32>) -> Result<K1PrepReport>
         }
     }
 
+    // Standard mileage and home office are tracked outside the ledger and
+    // reported on line 19 alongside the categorized other deductions.
+    let extra = get_deductions(conn, year)?;
+    let extra_lines = [
+        (
+            format!("Standard mileage ({:.1} mi)", extra.total_miles),
+            extra.mileage_deduction,
+        ),
+        (
+            format!("Home office ({}%)", extra.home_office.percent),
+            extra.home_office_deduction,
+        ),
+    ];
+    for (name, amount) in extra_lines {
+        if amount <= 0.0 {
+            continue;
+        }
+        total_deductions += amount;
+        other_deductions_total += amount;
+        deduction_lines.push(K1LineItem {
+            form_line: "1120S-19".to_string(),
+            category_name: name.clone(),
+            total: amount,
+        });
+        other_deductions.push(K1OtherDeduction {
+            category_name: name,
+            total: amount,
+            deductible: amount,
+        });
+    }
+
     let ordinary_business_income = gross_receipts + other_income - total_deductions;
 
     // Validation: count uncategorized transactions
@@ -575,6 +2213,17 @@ pub fn get_k1_prep(conn: &Connection, year: Option<i32>) -> Result<K1PrepReport>
     let mut ustmt = conn.prepare(&uncategorized_sql)?;
     let uncategorized_count: i64 = ustmt.query_row(param_values.as_slice(), |row| row.get(0))?;
 
+    let holders = shareholders::list(conn)?;
+    let ownership_pct_total =
+        (!holders.is_empty()).then(|| holders.iter().map(|h| h.ownership_pct).sum());
+    let allocations = k1_allocations(
+        &holders,
+        ordinary_business_income,
+        distributions,
+        &schedule_k_items,
+    );
+
+    let comp_target_pct = compensation::target_pct(conn);
     let comp_dist_ratio = if distributions > 0.0 {
         Some(officer_comp / distributions)
     } else {
@@ -595,10 +2244,126 @@ pub fn get_k1_prep(conn: &Connection, year: Option<i32>) -> Result<K1PrepReport>
             officer_comp,
             distributions,
             comp_dist_ratio,
+            below_comp_target: compensation::below_target(
+                officer_comp,
+                distributions,
+                comp_target_pct,
+            ),
+            comp_target_pct,
+            ownership_pct_total,
         },
+        shareholders: allocations,
+    })
+}
+
+/// One line of the K-1 worksheet keyed the way categories store it
+/// (`1120S-7`, `K-16d`), for `nigel report k1 --format csv|xlsx|json`.
+#[derive(Debug, serde::Serialize)]
+pub struct K1LineTotal {
+    pub key: String,
+    pub form: &'static str,
+    pub line: String,
+    pub description: String,
+    pub amount: f64,
+    /// Categories (and mileage/home office) that make up the amount.
+    pub sources: Vec<String>,
+}
+
+/// Printed caption for a form line; lines without one fall back to their
+/// categories.
+fn k1_line_description(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "1120S-1a" => "Gross receipts or sales",
+        "1120S-5" => "Other income (loss)",
+        "1120S-6" => "Total income (loss)",
+        "1120S-7" => "Compensation of officers",
+        "1120S-8" => "Salaries and wages",
+        "1120S-9" => "Repairs and maintenance",
+        "1120S-10" => "Bad debts",
+        "1120S-11" => "Rents",
+        "1120S-12" => "Taxes and licenses",
+        "1120S-13" => "Interest",
+        "1120S-14" => "Depreciation",
+        "1120S-16" => "Advertising",
+        "1120S-17" => "Pension, profit-sharing, etc., plans",
+        "1120S-18" => "Employee benefit programs",
+        "1120S-19" => "Other deductions",
+        "1120S-20" => "Total deductions",
+        "1120S-21" => "Ordinary business income (loss)",
+        "K-4" => "Interest income",
+        "K-5a" => "Ordinary dividends",
+        "K-12a" => "Charitable contributions",
+        "K-16c" => "Nondeductible expenses",
+        "K-16d" => "Distributions",
+        _ => return None,
     })
 }
 
+/// Flatten a K-1 prep report into one total per form line, in form order:
+/// income, deductions, totals, then Schedule K. Amounts match the worksheet.
+pub fn k1_line_totals(data: &K1PrepReport) -> Vec<K1LineTotal> {
+    fn push(out: &mut Vec<K1LineTotal>, key: &str, amount: f64, sources: Vec<String>) {
+        let (form, line) = match key.strip_prefix("K-") {
+            Some(line) => ("Schedule K", line),
+            None => ("1120-S", key.trim_start_matches("1120S-")),
+        };
+        let description = k1_line_description(key)
+            .map(String::from)
+            .unwrap_or_else(|| sources.join(", "));
+        out.push(K1LineTotal {
+            key: key.to_string(),
+            form,
+            line: line.to_string(),
+            description,
+            amount: (amount * 100.0).round() / 100.0,
+            sources,
+        });
+    }
+    // Sort form lines numerically: 1120S-7 before 1120S-16, K-4 before K-16d
+    fn line_order(key: &str) -> (u32, String) {
+        let line = key.trim_start_matches("1120S-").trim_start_matches("K-");
+        let digits: String = line.chars().take_while(|c| c.is_ascii_digit()).collect();
+        (digits.parse().unwrap_or(u32::MAX), line.to_string())
+    }
+    fn grouped(items: &[K1LineItem]) -> Vec<(String, f64, Vec<String>)> {
+        let mut groups: Vec<(String, f64, Vec<String>)> = Vec::new();
+        for item in items {
+            match groups.iter_mut().find(|g| g.0 == item.form_line) {
+                Some(g) => {
+                    g.1 += item.total.abs();
+                    g.2.push(item.category_name.clone());
+                }
+                None => groups.push((
+                    item.form_line.clone(),
+                    item.total.abs(),
+                    vec![item.category_name.clone()],
+                )),
+            }
+        }
+        groups.sort_by_key(|g| line_order(&g.0));
+        groups
+    }
+
+    let mut out = Vec::new();
+    push(&mut out, "1120S-1a", data.gross_receipts, vec![]);
+    push(&mut out, "1120S-5", data.other_income, vec![]);
+    push(
+        &mut out,
+        "1120S-6",
+        data.gross_receipts + data.other_income,
+        vec![],
+    );
+    for (key, total, sources) in grouped(&data.deduction_lines) {
+        push(&mut out, &key, total, sources);
+    }
+    push(&mut out, "1120S-20", data.total_deductions, vec![]);
+    push(&mut out, "1120S-21", data.ordinary_business_income, vec![]);
+    for (key, total, sources) in grouped(&data.schedule_k_items) {
+        push(&mut out, &key, total, sources);
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -656,7 +2421,7 @@ mod tests {
     fn test_pnl_ytd() {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
-        let report = get_pnl(&conn, Some(2025), None, None, None).unwrap();
+        let report = get_pnl(&conn, Some(2025), None, None, None, None, &[]).unwrap();
         // seed_transactions: 1×1000.0 income, 2 expenses (−50.0 + −10.0 = −60.0)
         assert_eq!(report.total_income, 1000.0);
         assert_eq!(report.total_expenses, -60.0);
@@ -667,7 +2432,7 @@ mod tests {
     fn test_pnl_by_month() {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
-        let report = get_pnl(&conn, Some(2025), Some(1), None, None).unwrap();
+        let report = get_pnl(&conn, Some(2025), Some(1), None, None, None, &[]).unwrap();
         // seed_transactions Jan only: 1×1000.0 income, 1×−50.0 expense (GitHub −10.0 is Feb)
         assert_eq!(report.total_income, 1000.0);
         assert_eq!(report.total_expenses, -50.0);
@@ -678,7 +2443,7 @@ mod tests {
     fn test_expense_breakdown() {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
-        let breakdown = get_expense_breakdown(&conn, Some(2025), None).unwrap();
+        let breakdown = get_expense_breakdown(&conn, Some(2025), None, None, &[]).unwrap();
         // seed_transactions: 2 expenses in "Software & Subscriptions" (−50.0 + −10.0)
         assert_eq!(breakdown.categories.len(), 1);
         assert_eq!(breakdown.categories[0].name, "Software & Subscriptions");
@@ -686,11 +2451,227 @@ mod tests {
         assert_eq!(breakdown.total, -60.0);
     }
 
+    /// Adds "Software > Design tools > Fonts" under the seeded category and
+    /// books one −25.0 Fonts transaction and one −15.0 Design tools one.
+    fn seed_sub_categories(conn: &Connection) {
+        let software: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        conn.execute(
+            "INSERT INTO categories (name, category_type, parent_id) VALUES ('Design tools', 'expense', ?1)",
+            [software],
+        )
+        .unwrap();
+        let design = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO categories (name, category_type, parent_id) VALUES ('Fonts', 'expense', ?1)",
+            [design],
+        )
+        .unwrap();
+        let fonts = conn.last_insert_rowid();
+        for (desc, amount, cat) in [("Figma", -15.0, design), ("Type foundry", -25.0, fonts)] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+                 VALUES (1, '2025-03-01', ?1, ?2, ?3)",
+                rusqlite::params![desc, amount, cat],
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_category_paths_follow_parents() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        seed_sub_categories(&conn);
+        let paths = category_paths(&conn).unwrap();
+        assert!(paths.values().any(|p| p
+            == &vec![
+                "Software & Subscriptions".to_string(),
+                "Design tools".to_string(),
+                "Fonts".to_string()
+            ]));
+    }
+
+    #[test]
+    fn test_expense_breakdown_depth_rolls_up_children() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        seed_sub_categories(&conn);
+
+        let full = get_expense_breakdown(&conn, Some(2025), None, None, &[]).unwrap();
+        assert_eq!(full.categories.len(), 3);
+        assert!(full
+            .categories
+            .iter()
+            .any(|c| c.name == "Software & Subscriptions > Design tools > Fonts"));
+
+        let top = get_expense_breakdown(&conn, Some(2025), None, Some(1), &[]).unwrap();
+        assert_eq!(top.categories.len(), 1);
+        assert_eq!(top.categories[0].name, "Software & Subscriptions");
+        assert_eq!(top.categories[0].total, -100.0);
+        assert_eq!(top.categories[0].count, 4);
+        assert_eq!(top.categories[0].pct, 100.0);
+
+        let two = get_expense_breakdown(&conn, Some(2025), None, Some(2), &[]).unwrap();
+        let design = two
+            .categories
+            .iter()
+            .find(|c| c.name == "Software & Subscriptions > Design tools")
+            .unwrap();
+        assert_eq!(design.total, -40.0);
+        assert_eq!(two.total, -100.0);
+    }
+
+    #[test]
+    fn test_pnl_comparison_by_month() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        let cmp = get_pnl_comparison(&conn, Some(2025), Some(2), None, None, None, &[]).unwrap();
+        assert_eq!(cmp.periods, vec!["Feb 2025", "Jan 2025", "Feb 2024"]);
+        assert_eq!(cmp.total_income, vec![0.0, 1000.0, 0.0]);
+        assert_eq!(cmp.total_expenses, vec![-10.0, -50.0, 0.0]);
+        assert_eq!(cmp.net, vec![-10.0, 950.0, 0.0]);
+        // Income only appears in the prior period but still gets a row
+        assert_eq!(cmp.income.len(), 1);
+        assert_eq!(cmp.income[0].totals, vec![0.0, 1000.0, 0.0]);
+        assert_eq!(cmp.expenses[0].name, "Software & Subscriptions");
+
+        // January wraps to December of the previous year
+        let cmp = get_pnl_comparison(&conn, Some(2025), Some(1), None, None, None, &[]).unwrap();
+        assert_eq!(cmp.periods, vec!["Jan 2025", "Dec 2024", "Jan 2024"]);
+    }
+
+    #[test]
+    fn test_pnl_comparison_by_year_and_range() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        let cmp = get_pnl_comparison(&conn, Some(2025), None, None, None, None, &[]).unwrap();
+        assert_eq!(cmp.periods, vec!["FY 2025", "FY 2024"]);
+        assert_eq!(cmp.net, vec![940.0, 0.0]);
+
+        // Prior range has the same length and ends the day before --from
+        let cmp = get_pnl_comparison(
+            &conn,
+            None,
+            None,
+            Some("2025-02-01"),
+            Some("2025-02-28"),
+            None,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(cmp.periods[1], "2025-01-04 to 2025-01-31");
+        assert_eq!(cmp.periods[2], "2024-02-01 to 2024-02-28");
+        assert_eq!(cmp.net, vec![-10.0, 950.0, 0.0]);
+
+        assert!(
+            get_pnl_comparison(&conn, None, None, Some("2025-02-01"), None, None, &[]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_fiscal_year_filter() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        crate::fiscal::set_start_month(&conn, 7).unwrap();
+        // FY2025 with a July start is 2024-07-01..2025-06-30
+        let report = get_pnl(&conn, Some(2025), None, None, None, None, &[]).unwrap();
+        assert_eq!(report.net, 940.0);
+        let report = get_pnl(&conn, Some(2024), None, None, None, None, &[]).unwrap();
+        assert_eq!(report.net, 0.0);
+        // Months are still calendar months
+        let report = get_pnl(&conn, Some(2025), Some(2), None, None, None, &[]).unwrap();
+        assert_eq!(report.net, -10.0);
+
+        let cmp = get_pnl_comparison(&conn, Some(2025), None, None, None, None, &[]).unwrap();
+        assert_eq!(
+            cmp.periods,
+            vec!["FY2025 (Jul\u{2013}Jun)", "FY2024 (Jul\u{2013}Jun)"]
+        );
+    }
+
+    #[test]
+    fn test_period_change() {
+        assert_eq!(period_change(150.0, 100.0), (50.0, Some(50.0)));
+        // A negative base (e.g. a net loss) is measured by its size
+        assert_eq!(period_change(-150.0, -100.0), (-50.0, Some(-50.0)));
+        assert_eq!(period_change(25.0, 0.0), (25.0, None));
+    }
+
+    #[test]
+    fn test_pnl_depth_rolls_up_children() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        seed_sub_categories(&conn);
+        let report = get_pnl(&conn, Some(2025), None, None, None, Some(1), &[]).unwrap();
+        assert_eq!(report.expenses.len(), 1);
+        assert_eq!(report.expenses[0].total, -100.0);
+        assert_eq!(report.total_expenses, -100.0);
+        assert!(get_pnl(&conn, Some(2025), None, None, None, Some(0), &[]).is_err());
+    }
+
+    #[test]
+    fn test_pnl_groups_subtotal_top_level_categories() {
+        let item = |name: &str, total| PnlItem {
+            name: name.to_string(),
+            total,
+        };
+        let items = [
+            item("Travel > Airfare", -400.0),
+            item("Software", -150.0),
+            item("Travel > Lodging", -300.0),
+            item("Travel", -20.0),
+            item("Office > Supplies > Paper", -800.0),
+        ];
+        let groups = pnl_groups(&items);
+
+        let names: Vec<&str> = groups.iter().map(|g| g.name).collect();
+        assert_eq!(names, ["Office", "Travel", "Software"]);
+        assert_eq!(groups[1].total, -720.0);
+        assert_eq!(
+            groups[1].items,
+            [
+                ("Airfare", -400.0),
+                ("Lodging", -300.0),
+                ("(direct)", -20.0)
+            ]
+        );
+        assert_eq!(groups[0].items, [("Supplies > Paper", -800.0)]);
+        assert!(!groups[0].is_single());
+        assert!(groups[2].is_single());
+    }
+
+    #[test]
+    fn test_clients_report_margin_per_client() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        crate::clients::assign(&conn, &[1, 2], Some("Acme")).unwrap();
+        let report = get_clients(&conn, Some(2025)).unwrap();
+
+        assert_eq!(report.clients.len(), 1);
+        let acme = &report.clients[0];
+        assert_eq!((acme.revenue, acme.direct_costs), (1000.0, 50.0));
+        assert_eq!(acme.margin(), 950.0);
+        assert_eq!(acme.margin_pct(), Some(95.0));
+        assert_eq!(report.unassigned_revenue, 0.0);
+
+        crate::clients::assign(&conn, &[1], None).unwrap();
+        let report = get_clients(&conn, Some(2025)).unwrap();
+        assert_eq!(report.clients[0].margin_pct(), None);
+        assert_eq!(report.unassigned_revenue, 1000.0);
+        assert!(get_clients(&conn, Some(2024)).unwrap().clients.is_empty());
+    }
+
     #[test]
     fn test_register_returns_all_transactions() {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
-        let report = get_register(&conn, Some(2025), None, None, None, None).unwrap();
+        let report = get_register(&conn, Some(2025), None, None, None, None, None).unwrap();
         assert_eq!(report.rows.len(), 3);
         // First two are categorized, all should appear
         assert!(report.rows.iter().all(|r| r.category.is_some()));
@@ -720,7 +2701,7 @@ mod tests {
         )
         .unwrap();
         // No date filters — should return all 4 transactions across both years
-        let report = get_register(&conn, None, None, None, None, None).unwrap();
+        let report = get_register(&conn, None, None, None, None, None, None).unwrap();
         assert_eq!(report.rows.len(), 4);
         assert_eq!(report.rows[0].date, "2024-06-15"); // oldest first
     }
@@ -740,7 +2721,7 @@ mod tests {
             rusqlite::params![acct],
         )
         .unwrap();
-        let report = get_register(&conn, Some(2025), None, None, None, None).unwrap();
+        let report = get_register(&conn, Some(2025), None, None, None, None, None).unwrap();
         assert_eq!(report.rows.len(), 1);
         assert!(report.rows[0].category.is_none());
     }
@@ -749,13 +2730,302 @@ mod tests {
     fn test_register_account_filter() {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
-        let report = get_register(&conn, Some(2025), None, None, None, Some("Test")).unwrap();
+        let report = get_register(&conn, Some(2025), None, None, None, Some("Test"), None).unwrap();
         assert_eq!(report.rows.len(), 3);
-        let report =
-            get_register(&conn, Some(2025), None, None, None, Some("Nonexistent")).unwrap();
+        let report = get_register(
+            &conn,
+            Some(2025),
+            None,
+            None,
+            None,
+            Some("Nonexistent"),
+            None,
+        )
+        .unwrap();
         assert_eq!(report.rows.len(), 0);
     }
 
+    #[test]
+    fn test_register_paged_queries() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        let query = RegisterQuery {
+            year: Some(2025),
+            ..Default::default()
+        };
+        let summary = register_summary(&conn, &query).unwrap();
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.total, 940.0);
+        assert!(!summary.has_foreign);
+
+        let page = register_page(&conn, &query, REGISTER_ORDER, 1, 5).unwrap();
+        let names: Vec<&str> = page.iter().map(|r| r.description.as_str()).collect();
+        assert_eq!(names, vec!["Adobe CC", "GitHub"]);
+        let by_amount = "t.amount DESC, t.date, t.id";
+        let first = register_page(&conn, &query, by_amount, 0, 1).unwrap();
+        assert_eq!(first[0].description, "Client payment");
+
+        let github = page[1].id;
+        assert_eq!(
+            register_position_of_id(&conn, &query, REGISTER_ORDER, github).unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            register_position_of_id(&conn, &query, by_amount, github).unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            register_position_of_id(&conn, &query, REGISTER_ORDER, 999).unwrap(),
+            None
+        );
+        assert_eq!(
+            register_position_of_date(&conn, &query, REGISTER_ORDER, "2025-01-16", false).unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            register_position_of_date(&conn, &query, REGISTER_ORDER, "2025-03-01", false).unwrap(),
+            None
+        );
+        assert_eq!(
+            register_search(&conn, &query, REGISTER_ORDER, "SOFTWARE").unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            register_last_on_or_before(&conn, &query, "2025-02-01").unwrap(),
+            Some(page[0].id)
+        );
+
+        let category_id: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        let software = RegisterQuery {
+            category_id: Some(category_id),
+            ..query
+        };
+        assert_eq!(register_summary(&conn, &software).unwrap().total, -60.0);
+    }
+
+    #[test]
+    fn test_balance_history_by_day_and_week_with_opening_balance() {
+        let (_dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type, opening_balance, opening_date) \
+             VALUES ('Checking', 'checking', 500.0, '2025-01-08')",
+            [],
+        )
+        .unwrap();
+        for (date, amount) in [
+            ("2024-12-30", 40.0),
+            ("2025-01-06", 100.0),
+            ("2025-01-08", -25.0),
+            ("2025-01-14", -75.0),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount) \
+                 VALUES (1, ?1, 'X', ?2)",
+                rusqlite::params![date, amount],
+            )
+            .unwrap();
+        }
+        let history = |interval, with_opening| {
+            get_balance_history(
+                &conn,
+                "Checking",
+                None,
+                None,
+                Some("2025-01-01"),
+                Some("2025-01-15"),
+                interval,
+                with_opening,
+            )
+            .unwrap()
+        };
+
+        let daily = history(BalanceInterval::Day, false);
+        assert_eq!(daily.starting_balance, 40.0);
+        assert_eq!(daily.points.len(), 15);
+        assert_eq!(daily.ending_balance(), 40.0);
+        assert_eq!(daily.high().unwrap().date, "2025-01-06");
+        let listed: Vec<&str> = daily.table_points().map(|p| p.date.as_str()).collect();
+        assert_eq!(listed, vec!["2025-01-06", "2025-01-08", "2025-01-14"]);
+
+        // Weeks end on Sunday; the last is cut short by the period
+        let weekly = history(BalanceInterval::Week, false);
+        let weeks: Vec<(&str, f64)> = weekly
+            .points
+            .iter()
+            .map(|p| (p.date.as_str(), p.balance))
+            .collect();
+        assert_eq!(
+            weeks,
+            vec![
+                ("2025-01-05", 40.0),
+                ("2025-01-12", 115.0),
+                ("2025-01-15", 40.0)
+            ]
+        );
+
+        // The opening balance takes over on its date, as in balance_on()
+        let opened = history(BalanceInterval::Day, true);
+        assert_eq!(opened.points[6].balance, 140.0);
+        assert_eq!(opened.points[7].balance, 475.0);
+        assert_eq!(
+            opened.ending_balance(),
+            crate::reconciler::balance_on(&conn, 1, "2025-01-15").unwrap()
+        );
+
+        assert!(matches!(
+            get_balance_history(
+                &conn,
+                "Nope",
+                Some(2025),
+                None,
+                None,
+                None,
+                BalanceInterval::Day,
+                false
+            ),
+            Err(crate::error::NigelError::UnknownAccount(_))
+        ));
+    }
+
+    #[test]
+    fn test_register_query_takes_in_subcategories_and_excludes_tags() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        // 2: Adobe CC -50 and 3: GitHub -10 are both software
+        let software: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        crate::cli::categories::add_category(
+            &conn,
+            "Design",
+            "expense",
+            None,
+            None,
+            Some(software),
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE transactions SET category_id = (SELECT id FROM categories WHERE name = 'Design') \
+             WHERE description = 'Adobe CC'",
+            [],
+        )
+        .unwrap();
+        let direct = RegisterQuery {
+            category_id: Some(software),
+            ..Default::default()
+        };
+        assert_eq!(register_summary(&conn, &direct).unwrap().total, -10.0);
+        let rolled_up = RegisterQuery {
+            subcategories: true,
+            ..direct
+        };
+        assert_eq!(register_summary(&conn, &rolled_up).unwrap().total, -60.0);
+
+        crate::tags::set_transaction_tags(&conn, 3, &["one-off".to_string()]).unwrap();
+        let excluding = RegisterQuery {
+            exclude_tags: vec!["#One-Off".into()],
+            ..rolled_up
+        };
+        assert_eq!(register_summary(&conn, &excluding).unwrap().total, -50.0);
+    }
+
+    #[test]
+    fn test_register_tag_filter_and_tags_report() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        // 1: Client payment +1000, 2: Adobe CC -50, 3: GitHub -10
+        let acme = vec!["client-acme".to_string()];
+        crate::tags::set_transaction_tags(&conn, 1, &acme).unwrap();
+        crate::tags::set_transaction_tags(
+            &conn,
+            2,
+            &["client-acme".to_string(), "design".to_string()],
+        )
+        .unwrap();
+
+        let report = get_register(
+            &conn,
+            Some(2025),
+            None,
+            None,
+            None,
+            None,
+            Some("#Client-Acme"),
+        )
+        .unwrap();
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[1].tags, vec!["client-acme", "design"]);
+        assert_eq!(report.total, 950.0);
+        assert!(get_register(&conn, None, None, None, None, None, Some("bad tag!")).is_err());
+
+        let tags = get_tags_report(&conn, Some(2025), None).unwrap();
+        let names: Vec<&str> = tags.iter().map(|t| t.tag.as_str()).collect();
+        assert_eq!(names, vec!["client-acme", "design"]);
+        assert_eq!(tags[0].count, 2);
+        assert_eq!(tags[0].income, 1000.0);
+        assert_eq!(tags[0].expenses, -50.0);
+        assert_eq!(tags[0].net, 950.0);
+        assert!(get_tags_report(&conn, Some(2024), None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_exclude_tags_in_pnl_expenses_and_cashflow() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        // 1: Client payment +1000, 2: Adobe CC -50, 3: GitHub -10
+        crate::tags::set_transaction_tags(&conn, 2, &["one-off".to_string()]).unwrap();
+        crate::tags::set_transaction_tags(&conn, 3, &["reimbursable".to_string()]).unwrap();
+
+        let one_off = vec!["#One-Off".to_string()];
+        let pnl = get_pnl(&conn, Some(2025), None, None, None, None, &one_off).unwrap();
+        assert_eq!(pnl.total_expenses, -10.0);
+        assert_eq!(pnl.net, 990.0);
+        let both = vec!["one-off".to_string(), "reimbursable".to_string()];
+        let pnl = get_pnl(&conn, Some(2025), None, None, None, None, &both).unwrap();
+        assert!(pnl.expenses.is_empty());
+        assert_eq!(pnl.net, 1000.0);
+
+        let expenses = get_expense_breakdown(&conn, Some(2025), None, None, &one_off).unwrap();
+        assert_eq!(expenses.total, -10.0);
+        assert_eq!(expenses.categories[0].count, 1);
+
+        // February's running balance starts from January without the one-off
+        let cashflow = get_cashflow(&conn, Some(2025), Some(2), &one_off).unwrap();
+        assert_eq!(cashflow.months[0].running_balance, 990.0);
+
+        // The books themselves are unchanged
+        assert_eq!(
+            get_pnl(&conn, Some(2025), None, None, None, None, &[])
+                .unwrap()
+                .net,
+            940.0
+        );
+        assert!(get_pnl(
+            &conn,
+            None,
+            None,
+            None,
+            None,
+            None,
+            &["bad tag!".to_string()]
+        )
+        .is_err());
+        assert_eq!(
+            excluded_tags_label(&both).as_deref(),
+            Some("#one-off, #reimbursable")
+        );
+    }
+
     #[test]
     fn test_k1_prep_basic() {
         let (_dir, conn) = test_db();
@@ -778,10 +3048,112 @@ mod tests {
         assert_eq!(report.validation.uncategorized_count, 0);
     }
 
+    #[test]
+    fn test_k1_allocates_by_ownership() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        let report = get_k1_prep(&conn, Some(2025)).unwrap();
+        assert!(report.shareholders.is_empty());
+        assert!(!report.validation.ownership_incomplete());
+
+        shareholders::add(&conn, "Ada", 60.0).unwrap();
+        let report = get_k1_prep(&conn, Some(2025)).unwrap();
+        assert!(report.validation.ownership_incomplete());
+        shareholders::add(&conn, "Grace", 40.0).unwrap();
+        let report = get_k1_prep(&conn, Some(2025)).unwrap();
+        assert!(!report.validation.ownership_incomplete());
+        let names: Vec<&str> = report
+            .shareholders
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, ["Ada", "Grace"]);
+        let obi: f64 = report
+            .shareholders
+            .iter()
+            .map(|s| s.ordinary_business_income)
+            .sum();
+        assert!((obi - report.ordinary_business_income).abs() < 0.005);
+
+        let item = |form_line: &str, name: &str, total: f64| K1LineItem {
+            form_line: form_line.into(),
+            category_name: name.into(),
+            total,
+        };
+        let holders = shareholders::list(&conn).unwrap();
+        let shares = k1_allocations(
+            &holders,
+            1_000.0,
+            500.0,
+            &[
+                item("K-12a", "Donations", -100.0),
+                item("K-12a", "Sponsorships", -50.0),
+                item("K-16d", "Distributions", -500.0),
+            ],
+        );
+        assert_eq!(shares[1].distributions, 200.0);
+        assert_eq!(shares[1].schedule_k_items.len(), 1);
+        assert_eq!(
+            shares[1].schedule_k_items[0].category_name,
+            "Charitable contributions"
+        );
+        assert_eq!(shares[1].schedule_k_items[0].total, 60.0);
+    }
+
+    #[test]
+    fn test_k1_line_totals_groups_by_form_line() {
+        let item = |form_line: &str, name: &str, total: f64| K1LineItem {
+            form_line: form_line.into(),
+            category_name: name.into(),
+            total,
+        };
+        let report = K1PrepReport {
+            gross_receipts: 10_000.0,
+            other_income: 0.0,
+            total_deductions: 4_500.0,
+            ordinary_business_income: 5_500.0,
+            deduction_lines: vec![
+                item("1120S-19", "Meals", 500.0),
+                item("1120S-7", "Officer Pay", 3_000.0),
+                item("1120S-19", "Software", 1_000.0),
+            ],
+            schedule_k_items: vec![item("K-16d", "Distributions", -2_000.0)],
+            other_deductions: vec![],
+            other_deductions_total: 1_250.0,
+            validation: K1Validation {
+                uncategorized_count: 0,
+                officer_comp: 3_000.0,
+                distributions: 2_000.0,
+                comp_dist_ratio: Some(1.5),
+                below_comp_target: false,
+                comp_target_pct: 50.0,
+                ownership_pct_total: None,
+            },
+            shareholders: Vec::new(),
+        };
+        let lines = k1_line_totals(&report);
+        let keys: Vec<&str> = lines.iter().map(|l| l.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "1120S-1a", "1120S-5", "1120S-6", "1120S-7", "1120S-19", "1120S-20", "1120S-21",
+                "K-16d"
+            ]
+        );
+        let line19 = &lines[4];
+        assert_eq!((line19.form, line19.line.as_str()), ("1120-S", "19"));
+        assert_eq!(line19.amount, 1_500.0);
+        assert_eq!(line19.sources, vec!["Meals", "Software"]);
+        let k16d = &lines[7];
+        assert_eq!((k16d.form, k16d.line.as_str()), ("Schedule K", "16d"));
+        assert_eq!(k16d.description, "Distributions");
+        assert_eq!(k16d.amount, 2_000.0);
+    }
+
     #[test]
     fn test_date_filter_rejects_from_without_to() {
         let (_dir, conn) = test_db();
-        let result = get_pnl(&conn, None, None, Some("2025-01-01"), None);
+        let result = get_pnl(&conn, None, None, Some("2025-01-01"), None, None, &[]);
         assert!(result.is_err());
         let msg = result.err().unwrap().to_string();
         assert!(msg.contains("--from requires --to"), "got: {msg}");
@@ -790,7 +3162,7 @@ mod tests {
     #[test]
     fn test_date_filter_rejects_to_without_from() {
         let (_dir, conn) = test_db();
-        let result = get_pnl(&conn, None, None, None, Some("2025-12-31"));
+        let result = get_pnl(&conn, None, None, None, Some("2025-12-31"), None, &[]);
         assert!(result.is_err());
         let msg = result.err().unwrap().to_string();
         assert!(msg.contains("--to requires --from"), "got: {msg}");
@@ -801,7 +3173,16 @@ mod tests {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
         // Jan range captures: 1×1000.0 income, 1×−50.0 expense (GitHub −10.0 is Feb)
-        let report = get_pnl(&conn, None, None, Some("2025-01-01"), Some("2025-01-31")).unwrap();
+        let report = get_pnl(
+            &conn,
+            None,
+            None,
+            Some("2025-01-01"),
+            Some("2025-01-31"),
+            None,
+            &[],
+        )
+        .unwrap();
         assert_eq!(report.total_income, 1000.0);
         assert_eq!(report.total_expenses, -50.0);
     }
@@ -837,6 +3218,35 @@ mod tests {
         assert_eq!(m.deductible, 50.0); // 50% deductible
     }
 
+    #[test]
+    fn test_k1_includes_mileage_and_home_office() {
+        let (_dir, conn) = test_db();
+        deductions::add_mileage(&conn, "2025-03-02", 100.0, "client visit").unwrap();
+        deductions::add_mileage(&conn, "2024-11-02", 50.0, "last year").unwrap();
+        deductions::set_home_office_percent(&conn, 10.0).unwrap();
+        deductions::set_home_office_costs(&conn, 20000.0).unwrap();
+
+        let ded = get_deductions(&conn, Some(2025)).unwrap();
+        assert_eq!(ded.trips.len(), 1);
+        assert!((ded.mileage_deduction - 70.0).abs() < 1e-9);
+        assert_eq!(ded.home_office_deduction, 2000.0);
+        assert!((ded.total - 2070.0).abs() < 1e-9);
+
+        let report = get_k1_prep(&conn, Some(2025)).unwrap();
+        assert!((report.other_deductions_total - 2070.0).abs() < 1e-9);
+        assert!((report.total_deductions - 2070.0).abs() < 1e-9);
+        assert!((report.ordinary_business_income + 2070.0).abs() < 1e-9);
+        let names: Vec<&str> = report
+            .other_deductions
+            .iter()
+            .map(|d| d.category_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Standard mileage (100.0 mi)", "Home office (10%)"]
+        );
+    }
+
     #[test]
     fn test_k1_other_income_sign_handling() {
         let (_dir, conn) = test_db();
@@ -906,23 +3316,155 @@ mod tests {
         assert_eq!(report.gross_receipts, -200.0);
     }
 
+    #[test]
+    fn test_opening_balance_in_cash_position() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        let before = get_balance(&conn, false).unwrap();
+        // Covers everything before February, so January's rows drop out
+        crate::cli::accounts::set_opening_balance(&conn, 1, 5000.0, Some("2025-02-01")).unwrap();
+        let after = get_balance(&conn, false).unwrap();
+        let feb_onward: f64 = conn
+            .query_row(
+                "SELECT COALESCE(SUM(amount), 0) FROM transactions WHERE date >= '2025-02-01'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(after.accounts[0].balance, 5000.0 + feb_onward);
+        assert_eq!(after.total, 5000.0 + feb_onward);
+        assert_ne!(after.total, before.total);
+    }
+
+    #[test]
+    fn test_archived_account_folded_into_balance() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        conn.execute(
+            "INSERT INTO accounts (name, account_type, is_archived) VALUES ('Old', 'checking', 1)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (?1, '2025-01-10', 'Leftover', 25.0)",
+            [conn.last_insert_rowid()],
+        )
+        .unwrap();
+
+        let all = get_balance(&conn, true).unwrap();
+        let hidden = get_balance(&conn, false).unwrap();
+        assert_eq!(hidden.total, all.total);
+        assert_eq!(hidden.accounts.len(), all.accounts.len() - 1);
+        assert_eq!(hidden.archived_count, 1);
+        assert_eq!(hidden.archived_balance, 25.0);
+        assert_eq!(
+            hidden.archived_line().as_deref(),
+            Some("Archived accounts (1)")
+        );
+        let old = all.accounts.iter().find(|a| a.name == "Old").unwrap();
+        assert_eq!(old.label(), "Old [archived]");
+        assert!(all.archived_line().is_none());
+
+        // Historical reports keep the archived account's transactions
+        let register = get_register(&conn, Some(2025), None, None, None, None, None).unwrap();
+        assert!(register.rows.iter().any(|r| r.description == "Leftover"));
+    }
+
+    #[test]
+    fn test_foreign_account_converted_to_home_currency() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        conn.execute(
+            "INSERT INTO accounts (name, account_type, currency) VALUES ('Euro', 'checking', 'EUR')",
+            [],
+        )
+        .unwrap();
+        let eur = conn.last_insert_rowid();
+        let income_cat: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Client Services'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (?1, '2025-01-25', 'EU client', 200.0, ?2)",
+            rusqlite::params![eur, income_cat],
+        )
+        .unwrap();
+        crate::currency::set_rate(&conn, "EUR", "2025-01-01", 1.5).unwrap();
+
+        let pnl = get_pnl(&conn, Some(2025), None, None, None, None, &[]).unwrap();
+        assert_eq!(pnl.total_income, 1300.0);
+
+        let cashflow = get_cashflow(&conn, Some(2025), Some(1), &[]).unwrap();
+        assert_eq!(cashflow.months[0].inflows, 1300.0);
+
+        let balance = get_balance(&conn, false).unwrap();
+        let euro = balance.accounts.iter().find(|a| a.name == "Euro").unwrap();
+        assert_eq!(euro.currency, "EUR");
+        assert_eq!(euro.native_balance, 200.0);
+        assert_eq!(euro.balance, 300.0);
+        assert_eq!(balance.total, 1240.0);
+
+        let register = get_register(&conn, Some(2025), None, None, None, None, None).unwrap();
+        let row = register
+            .rows
+            .iter()
+            .find(|r| r.description == "EU client")
+            .unwrap();
+        assert_eq!(row.amount, 300.0);
+        let foreign = row.foreign.as_ref().unwrap();
+        assert_eq!((foreign.currency.as_str(), foreign.amount), ("EUR", 200.0));
+        assert!(register
+            .rows
+            .iter()
+            .filter(|r| r.description != "EU client")
+            .all(|r| r.foreign.is_none()));
+    }
+
     #[test]
     fn test_cashflow_full_year_running_balance() {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
-        let report = get_cashflow(&conn, Some(2025), None).unwrap();
+        let report = get_cashflow(&conn, Some(2025), None, &[]).unwrap();
         // Jan: +1000 -50 = +950, Feb: -10 → running = 940
         assert_eq!(report.months.len(), 2);
         assert_eq!(report.months[0].running_balance, 950.0);
         assert_eq!(report.months[1].running_balance, 940.0);
     }
 
+    #[test]
+    fn test_cashflow_by_week_and_fiscal_quarter() {
+        let (_dir, conn) = test_db();
+        seed_transactions(&conn);
+        let weeks = get_cashflow_by(&conn, Some(2025), None, &[], Granularity::Week).unwrap();
+        // Wednesday Jan 15 falls in the week of Monday Jan 13
+        let keys: Vec<&str> = weeks.months.iter().map(|m| m.month.as_str()).collect();
+        assert_eq!(keys, ["2025-01-13", "2025-01-20", "2025-02-10"]);
+        assert_eq!(weeks.months[2].running_balance, 940.0);
+
+        let quarters = get_cashflow_by(&conn, None, None, &[], Granularity::Quarter).unwrap();
+        assert_eq!(quarters.months.len(), 1);
+        assert_eq!(quarters.months[0].label, "2025 Q1");
+        assert_eq!(quarters.months[0].net, 940.0);
+
+        // With a July start, January is in the third quarter of FY2025
+        crate::fiscal::set_start_month(&conn, 7).unwrap();
+        let quarters = get_cashflow_by(&conn, None, None, &[], Granularity::Quarter).unwrap();
+        assert_eq!(quarters.months[0].month, "2025-Q3");
+        assert_eq!(quarters.months[0].label, "FY2025 Q3");
+        assert!(Granularity::parse("fortnight").is_err());
+    }
+
     #[test]
     fn test_cashflow_single_month_includes_prior_balance() {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
         // Feb only — running balance should include Jan's cumulative (950.0)
-        let report = get_cashflow(&conn, Some(2025), Some(2)).unwrap();
+        let report = get_cashflow(&conn, Some(2025), Some(2), &[]).unwrap();
         assert_eq!(report.months.len(), 1);
         assert_eq!(report.months[0].net, -10.0);
         // Running balance = prior 950.0 + Feb net -10.0 = 940.0
@@ -933,7 +3475,7 @@ mod tests {
     fn test_cashflow_january_has_no_prior_balance() {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
-        let report = get_cashflow(&conn, Some(2025), Some(1)).unwrap();
+        let report = get_cashflow(&conn, Some(2025), Some(1), &[]).unwrap();
         assert_eq!(report.months.len(), 1);
         // Jan starts at 0 — no prior months
         assert_eq!(report.months[0].running_balance, 950.0);
@@ -963,7 +3505,7 @@ mod tests {
         )
         .unwrap();
         // Feb 2025 prior balance should only include Jan 2025, not Dec 2024
-        let report = get_cashflow(&conn, Some(2025), Some(2)).unwrap();
+        let report = get_cashflow(&conn, Some(2025), Some(2), &[]).unwrap();
         assert_eq!(report.months.len(), 1);
         assert_eq!(report.months[0].running_balance, 940.0);
     }
@@ -973,8 +3515,45 @@ mod tests {
         let (_dir, conn) = test_db();
         seed_transactions(&conn);
         // No year or month filter — running balance starts at 0
-        let report = get_cashflow(&conn, None, None).unwrap();
+        let report = get_cashflow(&conn, None, None, &[]).unwrap();
         assert!(report.months.len() >= 2);
         assert_eq!(report.months[0].running_balance, 950.0); // first month net only
     }
+
+    #[test]
+    fn test_recurring_charges() {
+        let (_dir, conn) = test_db();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Test', 'checking')",
+            [],
+        )
+        .unwrap();
+        let acct = conn.last_insert_rowid();
+        let rows = [
+            ("2025-01-05", "ADOBE *CC", Some("Adobe"), -50.0),
+            ("2025-02-05", "ADOBE *CC", Some("Adobe"), -50.0),
+            ("2025-03-05", "ADOBE *CC", Some("Adobe"), -60.0),
+            ("2025-01-10", "GITHUB", None, -10.0),
+            ("2025-02-10", "GITHUB", None, -10.0),
+            ("2025-03-10", "GITHUB", None, -10.0),
+            ("2025-02-20", "One-off purchase", None, -500.0),
+            ("2025-03-20", "One-off purchase", None, -500.0),
+            ("2025-01-15", "Client payment", None, 1000.0),
+            ("2025-02-15", "Client payment", None, 1000.0),
+            ("2025-03-15", "Client payment", None, 1000.0),
+        ];
+        for (date, desc, vendor, amount) in rows {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, vendor, amount) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![acct, date, desc, vendor, amount],
+            )
+            .unwrap();
+        }
+        let charges = get_recurring_charges(&conn, "2025-01-01", "2025-03-31", 3).unwrap();
+        assert_eq!(charges.len(), 2); // income and two-month charges excluded
+        assert_eq!(charges[0].name, "Adobe");
+        assert!((charges[0].average + 160.0 / 3.0).abs() < 1e-9);
+        assert_eq!(charges[1].name, "GITHUB");
+    }
 }
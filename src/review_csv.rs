@@ -0,0 +1,295 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::error::{NigelError, Result};
+use crate::reviewer::{apply_review, get_categories, update_transaction_memo};
+use crate::tabular::{self, Sheet, TabularFormat, Value};
+
+/// Columns `nigel review export` writes. `category`, `vendor`, and `memo` are
+/// the ones to fill in; the rest identify the transaction and are ignored on
+/// import apart from `id`.
+pub const HEADERS: &[&str] = &[
+    "id",
+    "date",
+    "account",
+    "description",
+    "amount",
+    "flag_reason",
+    "category",
+    "vendor",
+    "memo",
+];
+
+/// Write every flagged transaction to `path` as CSV, with a blank category
+/// column to fill in. Returns how many were written.
+pub fn export(conn: &Connection, path: &Path) -> Result<usize> {
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.date, a.name, t.description, t.amount, t.flag_reason, t.vendor, t.memo \
+         FROM transactions t JOIN accounts a ON t.account_id = a.id \
+         WHERE t.is_flagged = 1 AND t.deleted_at IS NULL ORDER BY t.date, t.id",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(vec![
+                Value::Int(row.get(0)?),
+                Value::Text(row.get(1)?),
+                Value::Text(row.get(2)?),
+                Value::Text(row.get(3)?),
+                Value::Number(row.get(4)?),
+                Value::from(row.get::<_, Option<String>>(5)?.as_deref()),
+                Value::Empty,
+                Value::from(row.get::<_, Option<String>>(6)?.as_deref()),
+                Value::from(row.get::<_, Option<String>>(7)?.as_deref()),
+            ])
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let count = rows.len();
+    let sheet = Sheet {
+        name: "Flagged".into(),
+        headers: HEADERS.iter().map(|h| h.to_string()).collect(),
+        rows,
+    };
+    tabular::write(&sheet, path, TabularFormat::Csv)?;
+    Ok(count)
+}
+
+/// What an import did with each row of the file.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Transactions categorized.
+    pub applied: usize,
+    /// Rows left with a blank category, still flagged.
+    pub blank: usize,
+    /// Rows for transactions no longer flagged, such as ones reviewed by an
+    /// earlier import of the same file.
+    pub already_reviewed: usize,
+    /// `Row N: reason` for each row that was rejected and left flagged.
+    pub errors: Vec<String>,
+}
+
+/// A row that passed validation, ready to apply.
+struct ReviewRow {
+    id: i64,
+    category_id: i64,
+    vendor: Option<String>,
+    memo: Option<String>,
+}
+
+fn column(headers: &csv::StringRecord, name: &str) -> Option<usize> {
+    headers.iter().position(|h| h.eq_ignore_ascii_case(name))
+}
+
+/// Apply the categories filled into an exported file. Every row is checked
+/// before any is saved; the valid ones are applied and the rest reported by
+/// row number, so a file with a few mistakes still saves the good rows.
+/// `dry_run` checks without saving.
+pub fn import(conn: &Connection, path: &Path, dry_run: bool) -> Result<ImportReport> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+    let headers = rdr.headers()?.clone();
+    let (Some(id_col), Some(category_col)) = (column(&headers, "id"), column(&headers, "category"))
+    else {
+        return Err(NigelError::Other(format!(
+            "{} needs id and category columns, as `nigel review export` writes them",
+            path.display()
+        )));
+    };
+    let vendor_col = column(&headers, "vendor");
+    let memo_col = column(&headers, "memo");
+    let categories = get_categories(conn)?;
+
+    let mut report = ImportReport::default();
+    let mut rows = Vec::new();
+    let mut seen = HashSet::new();
+    for record in rdr.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |p| p.line());
+        let field = |col: Option<usize>| {
+            col.and_then(|c| record.get(c))
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let Some(category) = field(Some(category_col)) else {
+            report.blank += 1;
+            continue;
+        };
+        let id_text = field(Some(id_col)).unwrap_or_default();
+        let Ok(id) = id_text.parse::<i64>() else {
+            report
+                .errors
+                .push(format!("Row {line}: '{id_text}' is not a transaction ID"));
+            continue;
+        };
+        if !seen.insert(id) {
+            report
+                .errors
+                .push(format!("Row {line}: transaction {id} is listed twice"));
+            continue;
+        }
+        let flagged: Option<bool> = conn
+            .query_row(
+                "SELECT is_flagged = 1 FROM transactions WHERE id = ?1 AND deleted_at IS NULL",
+                [id],
+                |r| r.get(0),
+            )
+            .optional()?;
+        match flagged {
+            None => {
+                report
+                    .errors
+                    .push(format!("Row {line}: no transaction with ID {id}"));
+                continue;
+            }
+            Some(false) => {
+                report.already_reviewed += 1;
+                continue;
+            }
+            Some(true) => {}
+        }
+        let Some(cat) = categories
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(&category))
+        else {
+            report
+                .errors
+                .push(format!("Row {line}: unknown category '{category}'"));
+            continue;
+        };
+        rows.push(ReviewRow {
+            id,
+            category_id: cat.id,
+            vendor: field(vendor_col),
+            memo: field(memo_col),
+        });
+    }
+
+    report.applied = rows.len();
+    if dry_run {
+        return Ok(report);
+    }
+    for row in &rows {
+        apply_review(
+            conn,
+            &[row.id],
+            row.category_id,
+            row.vendor.as_deref(),
+            false,
+            None,
+        )?;
+        if let Some(memo) = &row.memo {
+            update_transaction_memo(conn, row.id, Some(memo))?;
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        for (date, description) in [
+            ("2025-01-02", "ADOBE CREATIVE"),
+            ("2025-01-05", "SHELL OIL 5521"),
+            ("2025-01-09", "MYSTERY CHARGE"),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount, is_flagged, flag_reason) \
+                 VALUES (1, ?1, ?2, -20.0, 1, 'No matching rule')",
+                [date, description],
+            )
+            .unwrap();
+        }
+        (dir, conn)
+    }
+
+    fn flagged_ids(conn: &Connection) -> Vec<i64> {
+        let mut stmt = conn
+            .prepare("SELECT id FROM transactions WHERE is_flagged = 1 ORDER BY id")
+            .unwrap();
+        stmt.query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn export_then_import_applies_filled_rows() {
+        let (dir, conn) = test_db();
+        let path = dir.path().join("flagged.csv");
+        assert_eq!(export(&conn, &path).unwrap(), 3);
+        let exported = std::fs::read_to_string(&path).unwrap();
+        assert!(exported.starts_with(&HEADERS.join(",")));
+        assert!(
+            exported.contains("1,2025-01-02,Checking,ADOBE CREATIVE,-20.00,No matching rule,,,")
+        );
+
+        std::fs::write(
+            &path,
+            "id,date,description,category,vendor,memo\n\
+             1,2025-01-02,ADOBE CREATIVE,software & subscriptions,Adobe,Design tools\n\
+             2,2025-01-05,SHELL OIL 5521,Gasoline,,\n\
+             3,2025-01-09,MYSTERY CHARGE,,,\n\
+             1,2025-01-02,ADOBE CREATIVE,Software & Subscriptions,,\n\
+             abc,,,Software & Subscriptions,,\n\
+             99,,,Software & Subscriptions,,\n",
+        )
+        .unwrap();
+
+        let report = import(&conn, &path, true).unwrap();
+        assert_eq!(report.applied, 1);
+        assert_eq!(flagged_ids(&conn), vec![1, 2, 3]);
+
+        let report = import(&conn, &path, false).unwrap();
+        assert_eq!(report.applied, 1);
+        assert_eq!(report.blank, 1);
+        assert_eq!(
+            report.errors,
+            vec![
+                "Row 3: unknown category 'Gasoline'",
+                "Row 5: transaction 1 is listed twice",
+                "Row 6: 'abc' is not a transaction ID",
+                "Row 7: no transaction with ID 99",
+            ]
+        );
+        assert_eq!(flagged_ids(&conn), vec![2, 3]);
+        let (category, vendor, memo): (String, String, String) = conn
+            .query_row(
+                "SELECT c.name, t.vendor, t.memo FROM transactions t \
+                 JOIN categories c ON t.category_id = c.id WHERE t.id = 1",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (category.as_str(), vendor.as_str(), memo.as_str()),
+            ("Software & Subscriptions", "Adobe", "Design tools")
+        );
+
+        // Importing the same file again skips what it already applied
+        let report = import(&conn, &path, false).unwrap();
+        assert_eq!((report.applied, report.already_reviewed), (0, 1));
+    }
+
+    #[test]
+    fn import_needs_id_and_category_columns() {
+        let (dir, conn) = test_db();
+        let path = dir.path().join("flagged.csv");
+        std::fs::write(&path, "date,description\n2025-01-02,ADOBE\n").unwrap();
+        let err = import(&conn, &path, false).unwrap_err().to_string();
+        assert!(err.contains("needs id and category columns"));
+    }
+}
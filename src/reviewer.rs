@@ -1,6 +1,10 @@
 use rusqlite::Connection;
 
+use crate::categorizer::find_duplicate_rule;
+use crate::cli::rules::add_rule;
+use crate::clients::CLIENT_NAME_SQL;
 use crate::error::{NigelError, Result};
+use crate::tags::TAGS_CONCAT_SQL;
 
 #[derive(Debug)]
 pub struct FlaggedTxn {
@@ -9,6 +13,10 @@ pub struct FlaggedTxn {
     pub description: String,
     pub amount: f64,
     pub account_name: String,
+    pub memo: Option<String>,
+    pub tags: Vec<String>,
+    pub flag_reason: Option<String>,
+    pub client: Option<String>,
 }
 
 pub struct CategoryChoice {
@@ -18,11 +26,11 @@ pub struct CategoryChoice {
 }
 
 pub fn get_flagged_transactions(conn: &Connection) -> Result<Vec<FlaggedTxn>> {
-    let mut stmt = conn.prepare(
-        "SELECT t.id, t.date, t.description, t.amount, a.name as account_name \
-         FROM transactions t JOIN accounts a ON t.account_id = a.id \
-         WHERE t.is_flagged = 1 ORDER BY t.date",
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.id, t.date, t.description, t.amount, a.name as account_name, t.memo, {TAGS_CONCAT_SQL}, \
+         t.flag_reason, {CLIENT_NAME_SQL} FROM transactions t JOIN accounts a ON t.account_id = a.id \
+         WHERE t.is_flagged = 1 AND t.deleted_at IS NULL ORDER BY t.date"
+    ))?;
     let rows = stmt
         .query_map([], |row| {
             Ok(FlaggedTxn {
@@ -31,6 +39,10 @@ pub fn get_flagged_transactions(conn: &Connection) -> Result<Vec<FlaggedTxn>> {
                 description: row.get(2)?,
                 amount: row.get(3)?,
                 account_name: row.get(4)?,
+                memo: row.get(5)?,
+                tags: crate::tags::from_concat(row.get(6)?),
+                flag_reason: row.get(7)?,
+                client: row.get(8)?,
             })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -39,9 +51,11 @@ pub fn get_flagged_transactions(conn: &Connection) -> Result<Vec<FlaggedTxn>> {
 
 pub fn get_transaction_by_id(conn: &Connection, id: i64) -> Result<FlaggedTxn> {
     conn.query_row(
-        "SELECT t.id, t.date, t.description, t.amount, a.name as account_name \
-         FROM transactions t JOIN accounts a ON t.account_id = a.id \
-         WHERE t.id = ?1",
+        &format!(
+            "SELECT t.id, t.date, t.description, t.amount, a.name as account_name, t.memo, {TAGS_CONCAT_SQL}, \
+             t.flag_reason, {CLIENT_NAME_SQL} FROM transactions t JOIN accounts a ON t.account_id = a.id \
+             WHERE t.id = ?1"
+        ),
         rusqlite::params![id],
         |row| {
             Ok(FlaggedTxn {
@@ -50,6 +64,10 @@ pub fn get_transaction_by_id(conn: &Connection, id: i64) -> Result<FlaggedTxn> {
                 description: row.get(2)?,
                 amount: row.get(3)?,
                 account_name: row.get(4)?,
+                memo: row.get(5)?,
+                tags: crate::tags::from_concat(row.get(6)?),
+                flag_reason: row.get(7)?,
+                client: row.get(8)?,
             })
         },
     )
@@ -77,26 +95,66 @@ pub fn get_categories(conn: &Connection) -> Result<Vec<CategoryChoice>> {
     Ok(rows)
 }
 
+/// Starting pattern for a rule made from a transaction: the first two words
+/// of its description, which is usually the merchant.
+pub fn suggest_rule_pattern(description: &str) -> String {
+    description
+        .split_whitespace()
+        .take(2)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Grouping key for batch review: the first two words of the description,
+/// uppercased, skipping words with digits and keeping only letters, so
+/// `SQ *BLUE BOTTLE 0412` and `SQ *BLUE BOTTLE #0519` land in one group.
+pub fn group_key(description: &str) -> String {
+    let words: Vec<String> = description
+        .split_whitespace()
+        .filter(|w| !w.chars().any(|c| c.is_ascii_digit()))
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphabetic())
+                .collect::<String>()
+                .to_uppercase()
+        })
+        .filter(|w| !w.is_empty())
+        .take(2)
+        .collect();
+    if words.is_empty() {
+        description.trim().to_uppercase()
+    } else {
+        words.join(" ")
+    }
+}
+
+/// Categorize one transaction, or a batch-review group in one database
+/// transaction, creating at most one rule for all of them. A category picked
+/// by hand is taken as intended, so the sign check leaves it alone afterwards.
 pub fn apply_review(
     conn: &Connection,
-    transaction_id: i64,
+    transaction_ids: &[i64],
     category_id: i64,
     vendor: Option<&str>,
     create_rule: bool,
     rule_pattern: Option<&str>,
 ) -> Result<Option<i64>> {
     let tx = conn.unchecked_transaction()?;
-    tx.execute(
-        "UPDATE transactions SET category_id = ?1, vendor = ?2, is_flagged = 0, flag_reason = NULL WHERE id = ?3",
-        rusqlite::params![category_id, vendor, transaction_id],
-    )?;
+    for id in transaction_ids {
+        tx.execute(
+            "UPDATE transactions SET category_id = ?1, vendor = ?2, is_flagged = 0, flag_reason = NULL, \
+             sign_ok = 1, anomaly_checked = 1 WHERE id = ?3",
+            rusqlite::params![category_id, vendor, id],
+        )?;
+    }
     let rule_id = if create_rule {
         if let Some(pattern) = rule_pattern {
-            tx.execute(
-                "INSERT INTO rules (pattern, match_type, vendor, category_id) VALUES (?1, 'contains', ?2, ?3)",
-                rusqlite::params![pattern, vendor, category_id],
-            )?;
-            Some(tx.last_insert_rowid())
+            // An identical rule already covers this; don't pile up copies
+            if find_duplicate_rule(&tx, pattern, category_id, None)?.is_some() {
+                None
+            } else {
+                Some(add_rule(&tx, pattern, "contains", vendor, category_id, 0)?)
+            }
         } else {
             None
         }
@@ -107,12 +165,16 @@ pub fn apply_review(
     Ok(rule_id)
 }
 
-pub fn undo_review(conn: &Connection, transaction_id: i64, rule_id: Option<i64>) -> Result<()> {
+/// Reverse [`apply_review`]: re-flag the transactions and drop the rule it created.
+pub fn undo_review(conn: &Connection, transaction_ids: &[i64], rule_id: Option<i64>) -> Result<()> {
     let tx = conn.unchecked_transaction()?;
-    tx.execute(
-        "UPDATE transactions SET category_id = NULL, vendor = NULL, is_flagged = 1, flag_reason = 'Uncategorized' WHERE id = ?1",
-        rusqlite::params![transaction_id],
-    )?;
+    for id in transaction_ids {
+        tx.execute(
+            "UPDATE transactions SET category_id = NULL, vendor = NULL, is_flagged = 1, \
+             flag_reason = 'Uncategorized', sign_ok = 0 WHERE id = ?1",
+            rusqlite::params![id],
+        )?;
+    }
     if let Some(rid) = rule_id {
         tx.execute("DELETE FROM rules WHERE id = ?1", rusqlite::params![rid])?;
     }
@@ -126,7 +188,7 @@ pub fn update_transaction_category(
     category_id: i64,
 ) -> Result<()> {
     conn.execute(
-        "UPDATE transactions SET category_id = ?1 WHERE id = ?2",
+        "UPDATE transactions SET category_id = ?1, sign_ok = 1, anomaly_checked = 1 WHERE id = ?2",
         rusqlite::params![category_id, transaction_id],
     )?;
     Ok(())
@@ -144,9 +206,27 @@ pub fn update_transaction_vendor(
     Ok(())
 }
 
+/// Set or clear a transaction's memo; blank text clears it.
+pub fn update_transaction_memo(
+    conn: &Connection,
+    transaction_id: i64,
+    memo: Option<&str>,
+) -> Result<()> {
+    let memo = memo.map(str::trim).filter(|m| !m.is_empty());
+    conn.execute(
+        "UPDATE transactions SET memo = ?1 WHERE id = ?2",
+        rusqlite::params![memo, transaction_id],
+    )?;
+    Ok(())
+}
+
+/// Flip a transaction's flag. Clearing a flag by hand also dismisses a sign
+/// mismatch or unusual-transaction warning for good (SET reads the old
+/// `is_flagged`).
 pub fn toggle_transaction_flag(conn: &Connection, transaction_id: i64) -> Result<bool> {
     conn.execute(
-        "UPDATE transactions SET is_flagged = NOT is_flagged WHERE id = ?1",
+        "UPDATE transactions SET is_flagged = NOT is_flagged, sign_ok = sign_ok OR is_flagged, \
+         anomaly_checked = anomaly_checked OR is_flagged WHERE id = ?1",
         rusqlite::params![transaction_id],
     )?;
     let new_state: bool = conn.query_row(
@@ -157,6 +237,63 @@ pub fn toggle_transaction_flag(conn: &Connection, transaction_id: i64) -> Result
     Ok(new_state)
 }
 
+/// Flagged transactions still waiting for review (trash left out).
+pub fn flagged_count(conn: &Connection) -> Result<i64> {
+    Ok(conn.query_row(
+        "SELECT COUNT(*) FROM transactions WHERE is_flagged = 1 AND deleted_at IS NULL",
+        [],
+        |r| r.get(0),
+    )?)
+}
+
+/// What one review session did, shown when it clears the last flagged
+/// transaction and kept in `audit_log` as `review.session`.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReviewSummary {
+    pub reviewed: usize,
+    pub skipped: usize,
+    /// Category names and how many transactions went to each, most first.
+    pub categories: Vec<(String, usize)>,
+    pub rules_created: usize,
+    pub elapsed: std::time::Duration,
+}
+
+impl ReviewSummary {
+    /// `3m 05s`, or `42s` under a minute.
+    pub fn elapsed_label(&self) -> String {
+        let secs = self.elapsed.as_secs();
+        if secs < 60 {
+            format!("{secs}s")
+        } else {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        }
+    }
+
+    /// One line for the audit log.
+    pub fn detail(&self) -> String {
+        let categories = self
+            .categories
+            .iter()
+            .map(|(name, n)| format!("{name} {n}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let count =
+            |n: usize, one: &str, many: &str| format!("{n} {}", if n == 1 { one } else { many });
+        format!(
+            "Reviewed {} into {} ({categories}) in {}; {} skipped, {} added",
+            count(self.reviewed, "transaction", "transactions"),
+            count(self.categories.len(), "category", "categories"),
+            self.elapsed_label(),
+            self.skipped,
+            count(self.rules_created, "rule", "rules"),
+        )
+    }
+
+    pub fn record(&self, conn: &Connection) -> Result<()> {
+        crate::audit::record(conn, "review.session", &self.detail())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,7 +361,7 @@ mod tests {
                 |r| r.get(0),
             )
             .unwrap();
-        let rule_id = apply_review(&conn, txn_id, cat_id, Some("Adobe"), false, None).unwrap();
+        let rule_id = apply_review(&conn, &[txn_id], cat_id, Some("Adobe"), false, None).unwrap();
         assert!(rule_id.is_none());
         let (is_flagged, vendor): (i32, Option<String>) = conn
             .query_row(
@@ -249,7 +386,7 @@ mod tests {
             )
             .unwrap();
         let rule_id =
-            apply_review(&conn, txn_id, cat_id, Some("Adobe"), true, Some("ADOBE")).unwrap();
+            apply_review(&conn, &[txn_id], cat_id, Some("Adobe"), true, Some("ADOBE")).unwrap();
         assert!(rule_id.is_some());
         let count: i64 = conn
             .query_row("SELECT count(*) FROM rules", [], |r| r.get(0))
@@ -261,6 +398,32 @@ mod tests {
         assert_eq!(pattern, "ADOBE");
     }
 
+    #[test]
+    fn test_apply_review_skips_duplicate_rule() {
+        let (_dir, conn) = test_db();
+        let txn_id = add_flagged_txn(&conn);
+        let cat_id: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        conn.execute(
+            "INSERT INTO rules (pattern, category_id) VALUES ('adobe', ?1)",
+            [cat_id],
+        )
+        .unwrap();
+        let rule_id =
+            apply_review(&conn, &[txn_id], cat_id, Some("Adobe"), true, Some("ADOBE")).unwrap();
+        // No new rule, so undo has nothing to delete
+        assert!(rule_id.is_none());
+        let count: i64 = conn
+            .query_row("SELECT count(*) FROM rules", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
     #[test]
     fn test_undo_review() {
         let (_dir, conn) = test_db();
@@ -273,10 +436,10 @@ mod tests {
             )
             .unwrap();
         let rule_id =
-            apply_review(&conn, txn_id, cat_id, Some("Adobe"), true, Some("ADOBE")).unwrap();
+            apply_review(&conn, &[txn_id], cat_id, Some("Adobe"), true, Some("ADOBE")).unwrap();
         assert!(rule_id.is_some());
 
-        undo_review(&conn, txn_id, rule_id).unwrap();
+        undo_review(&conn, &[txn_id], rule_id).unwrap();
 
         let (is_flagged, category_id, vendor): (i32, Option<i64>, Option<String>) = conn
             .query_row(
@@ -307,10 +470,10 @@ mod tests {
             )
             .unwrap();
         // Categorize without creating a rule
-        let rule_id = apply_review(&conn, txn_id, cat_id, Some("Adobe"), false, None).unwrap();
+        let rule_id = apply_review(&conn, &[txn_id], cat_id, Some("Adobe"), false, None).unwrap();
         assert!(rule_id.is_none());
 
-        undo_review(&conn, txn_id, None).unwrap();
+        undo_review(&conn, &[txn_id], None).unwrap();
 
         let (is_flagged, category_id): (i32, Option<i64>) = conn
             .query_row(
@@ -323,6 +486,68 @@ mod tests {
         assert!(category_id.is_none());
     }
 
+    #[test]
+    fn test_suggest_rule_pattern() {
+        assert_eq!(
+            suggest_rule_pattern("ADOBE  CREATIVE CLOUD 0412"),
+            "ADOBE CREATIVE"
+        );
+        assert_eq!(suggest_rule_pattern("GUSTO"), "GUSTO");
+        assert_eq!(suggest_rule_pattern(""), "");
+    }
+
+    #[test]
+    fn test_group_key() {
+        assert_eq!(group_key("SQ *BLUE BOTTLE 0412"), "SQ BLUE");
+        assert_eq!(group_key("sq *blue bottle #0519"), "SQ BLUE");
+        assert_eq!(group_key("CHECK 1042"), "CHECK");
+        assert_eq!(group_key("AMZN MKTP US*2K4"), "AMZN MKTP");
+        assert_eq!(group_key(" 0412 "), "0412");
+    }
+
+    #[test]
+    fn test_apply_and_undo_review() {
+        let (_dir, conn) = test_db();
+        let first = add_flagged_txn(&conn);
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, is_flagged, flag_reason) \
+             VALUES (1, '2025-02-15', 'ADOBE CREATIVE', -50.0, 1, 'No matching rule')",
+            [],
+        )
+        .unwrap();
+        let second = conn.last_insert_rowid();
+        let cat_id: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+
+        let rule_id = apply_review(
+            &conn,
+            &[first, second],
+            cat_id,
+            Some("Adobe"),
+            true,
+            Some("ADOBE"),
+        )
+        .unwrap();
+        assert!(rule_id.is_some());
+        assert!(get_flagged_transactions(&conn).unwrap().is_empty());
+        let rules: i64 = conn
+            .query_row("SELECT count(*) FROM rules", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rules, 1);
+
+        undo_review(&conn, &[first, second], rule_id).unwrap();
+        assert_eq!(get_flagged_transactions(&conn).unwrap().len(), 2);
+        let rules: i64 = conn
+            .query_row("SELECT count(*) FROM rules", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rules, 0);
+    }
+
     #[test]
     fn test_update_transaction_category() {
         let (_dir, conn) = test_db();
@@ -375,6 +600,20 @@ mod tests {
         assert!(vendor2.is_none());
     }
 
+    #[test]
+    fn test_update_transaction_memo() {
+        let (_dir, conn) = test_db();
+        let txn_id = add_flagged_txn(&conn);
+
+        update_transaction_memo(&conn, txn_id, Some("  Client dinner, reimbursable ")).unwrap();
+        let txn = get_transaction_by_id(&conn, txn_id).unwrap();
+        assert_eq!(txn.memo.as_deref(), Some("Client dinner, reimbursable"));
+
+        // Blank clears it
+        update_transaction_memo(&conn, txn_id, Some("   ")).unwrap();
+        assert!(get_transaction_by_id(&conn, txn_id).unwrap().memo.is_none());
+    }
+
     #[test]
     fn test_toggle_transaction_flag() {
         let (_dir, conn) = test_db();
@@ -386,4 +625,40 @@ mod tests {
         let new_state2 = toggle_transaction_flag(&conn, txn_id).unwrap();
         assert!(new_state2); // toggled back to flagged
     }
+
+    #[test]
+    fn test_review_summary_is_recorded() {
+        let (_dir, conn) = test_db();
+        let id = add_flagged_txn(&conn);
+        assert_eq!(flagged_count(&conn).unwrap(), 1);
+        let cat_id: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        apply_review(&conn, &[id], cat_id, None, false, None).unwrap();
+        assert_eq!(flagged_count(&conn).unwrap(), 0);
+
+        let summary = ReviewSummary {
+            reviewed: 3,
+            skipped: 1,
+            categories: vec![("Software & Subscriptions".into(), 2), ("Meals".into(), 1)],
+            rules_created: 1,
+            elapsed: std::time::Duration::from_secs(185),
+        };
+        summary.record(&conn).unwrap();
+        let (action, detail): (String, String) = conn
+            .query_row("SELECT action, detail FROM audit_log", [], |r| {
+                Ok((r.get(0)?, r.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(action, "review.session");
+        assert_eq!(
+            detail,
+            "Reviewed 3 transactions into 2 categories (Software & Subscriptions 2, Meals 1) \
+             in 3m 05s; 1 skipped, 1 rule added"
+        );
+    }
 }
@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use rusqlite::Connection;
+
+use crate::categorizer::Matcher;
+use crate::error::Result;
+
+/// An active rule as seen by the audit.
+#[derive(Debug, Clone)]
+pub struct AuditedRule {
+    pub id: i64,
+    pub pattern: String,
+    pub match_type: String,
+    pub vendor: Option<String>,
+    pub category: String,
+    pub priority: i64,
+    pub hit_count: i64,
+    /// Transactions in the book whose description the rule matches.
+    pub matching: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleProblem {
+    /// A regex (or empty pattern) that matches every description, so nothing
+    /// below it ever gets a chance.
+    MatchesEverything,
+    /// Every transaction the rule matches is claimed first by a rule earlier
+    /// in evaluation order; `by` is the one that claims the most of them.
+    Superseded {
+        by: i64,
+        by_pattern: String,
+        by_category: String,
+        by_priority: i64,
+    },
+    /// No recorded hits and no transaction in the book matches it.
+    NeverMatched,
+}
+
+/// What the cleanup wizard can do to a rule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CleanupAction {
+    /// Soft-delete (deactivate) the rule.
+    Delete,
+    /// Set the rule's priority to this value.
+    SetPriority(i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleIssue {
+    pub rule: AuditedRule,
+    pub problem: RuleProblem,
+}
+
+impl RuleIssue {
+    /// One-line explanation of the problem for the wizard.
+    pub fn explanation(&self) -> String {
+        match &self.problem {
+            RuleProblem::MatchesEverything => {
+                "Matches every transaction, so every rule below it is ignored.".into()
+            }
+            RuleProblem::Superseded {
+                by,
+                by_pattern,
+                by_category,
+                ..
+            } => {
+                let which = match self.rule.matching {
+                    1 => "Its one matching transaction is".to_string(),
+                    n => format!("All {n} matching transactions are"),
+                };
+                format!(
+                    "{which} claimed first by rule {by} ('{by_pattern}' \u{2192} {by_category})."
+                )
+            }
+            RuleProblem::NeverMatched => {
+                "Has never matched, and matches no transaction in the book.".into()
+            }
+        }
+    }
+
+    /// The action the wizard suggests. A rule shadowed by one that files the
+    /// same category is redundant; shadowed by a different category, it can
+    /// only work again if it runs first.
+    pub fn recommended(&self) -> CleanupAction {
+        match &self.problem {
+            RuleProblem::Superseded {
+                by_category,
+                by_priority,
+                ..
+            } if *by_category != self.rule.category => CleanupAction::SetPriority(by_priority + 1),
+            _ => CleanupAction::Delete,
+        }
+    }
+}
+
+impl CleanupAction {
+    pub fn label(self) -> String {
+        match self {
+            CleanupAction::Delete => "Delete the rule".into(),
+            CleanupAction::SetPriority(p) => format!("Raise priority to {p}"),
+        }
+    }
+}
+
+/// True if the rule matches any description at all, including empty ones.
+fn matches_everything(pattern: &str, match_type: &str) -> bool {
+    match match_type {
+        "regex" => Regex::new(pattern).is_ok_and(|re| re.is_match("")),
+        "contains" | "starts_with" => pattern.is_empty(),
+        _ => false,
+    }
+}
+
+/// Active rules in the order `categorize_transactions()` tries them.
+fn load_rules(conn: &Connection) -> Result<Vec<AuditedRule>> {
+    let mut stmt = conn.prepare(
+        "SELECT r.id, r.pattern, r.match_type, r.vendor, c.name, r.priority, r.hit_count \
+         FROM rules r JOIN categories c ON r.category_id = c.id \
+         WHERE r.is_active = 1 ORDER BY r.priority DESC, r.id",
+    )?;
+    let rules = stmt
+        .query_map([], |row| {
+            Ok(AuditedRule {
+                id: row.get(0)?,
+                pattern: row.get(1)?,
+                match_type: row.get(2)?,
+                vendor: row.get(3)?,
+                category: row.get(4)?,
+                priority: row.get(5)?,
+                hit_count: row.get(6)?,
+                matching: 0,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rules)
+}
+
+/// Find likely-problem rules, in evaluation order. Each rule is reported once,
+/// under its most serious problem (matches everything, then superseded, then
+/// never matched). Matching runs over every transaction description in the
+/// book, not only uncategorized ones.
+pub fn audit_rules(conn: &Connection) -> Result<Vec<RuleIssue>> {
+    let mut rules = load_rules(conn)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT description, COUNT(*) FROM transactions WHERE deleted_at IS NULL GROUP BY 1",
+    )?;
+    let descriptions: Vec<(String, usize)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    // For each description, the index of the first rule that claims it
+    let mut claimed_by: Vec<Option<usize>> = vec![None; descriptions.len()];
+    // Per rule: transactions matched, and per earlier rule how many of those it claimed
+    let mut shadows: Vec<HashMap<usize, usize>> = vec![HashMap::new(); rules.len()];
+    let mut unclaimed: Vec<usize> = vec![0; rules.len()];
+
+    for (ri, rule) in rules.iter_mut().enumerate() {
+        let matcher = Matcher::new(&rule.pattern, &rule.match_type);
+        for (di, (desc, count)) in descriptions.iter().enumerate() {
+            if !matcher.is_match(desc) {
+                continue;
+            }
+            rule.matching += count;
+            match claimed_by[di] {
+                Some(first) => *shadows[ri].entry(first).or_default() += count,
+                None => {
+                    claimed_by[di] = Some(ri);
+                    unclaimed[ri] += count;
+                }
+            }
+        }
+    }
+
+    let mut issues = Vec::new();
+    for (ri, rule) in rules.iter().enumerate() {
+        let problem = if matches_everything(&rule.pattern, &rule.match_type) {
+            RuleProblem::MatchesEverything
+        } else if rule.matching > 0 && unclaimed[ri] == 0 {
+            let (&by, _) = shadows[ri]
+                .iter()
+                .max_by_key(|(idx, n)| (**n, std::cmp::Reverse(**idx)))
+                .expect("a matched, fully claimed rule has a shadowing rule");
+            let by = &rules[by];
+            RuleProblem::Superseded {
+                by: by.id,
+                by_pattern: by.pattern.clone(),
+                by_category: by.category.clone(),
+                by_priority: by.priority,
+            }
+        } else if rule.matching == 0 && rule.hit_count == 0 {
+            RuleProblem::NeverMatched
+        } else {
+            continue;
+        };
+        issues.push(RuleIssue {
+            rule: rule.clone(),
+            problem,
+        });
+    }
+    Ok(issues)
+}
+
+/// Apply a wizard action to rule `id`.
+pub fn apply(conn: &Connection, id: i64, action: CleanupAction) -> Result<()> {
+    match action {
+        CleanupAction::Delete => {
+            conn.execute("UPDATE rules SET is_active = 0 WHERE id = ?1", [id])?;
+        }
+        CleanupAction::SetPriority(priority) => {
+            conn.execute(
+                "UPDATE rules SET priority = ?1 WHERE id = ?2",
+                rusqlite::params![priority, id],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Test', 'checking')",
+            [],
+        )
+        .unwrap();
+        for desc in ["ADOBE CREATIVE CLOUD", "ADOBE STOCK", "GITHUB"] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount) \
+                 VALUES (1, '2025-01-15', ?1, -20.0)",
+                [desc],
+            )
+            .unwrap();
+        }
+        (dir, conn)
+    }
+
+    fn add_rule(
+        conn: &Connection,
+        pattern: &str,
+        match_type: &str,
+        category: &str,
+        pri: i64,
+    ) -> i64 {
+        conn.execute(
+            "INSERT INTO rules (pattern, match_type, category_id, priority) \
+             VALUES (?1, ?2, (SELECT id FROM categories WHERE name = ?3), ?4)",
+            rusqlite::params![pattern, match_type, category, pri],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn test_healthy_rules_have_no_issues() {
+        let (_dir, conn) = test_db();
+        add_rule(&conn, "ADOBE", "contains", "Software & Subscriptions", 0);
+        add_rule(
+            &conn,
+            "GITHUB",
+            "starts_with",
+            "Software & Subscriptions",
+            0,
+        );
+        assert!(audit_rules(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_never_matched() {
+        let (_dir, conn) = test_db();
+        let id = add_rule(&conn, "FIGMA", "contains", "Software & Subscriptions", 0);
+        let issues = audit_rules(&conn).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule.id, id);
+        assert_eq!(issues[0].problem, RuleProblem::NeverMatched);
+        assert_eq!(issues[0].recommended(), CleanupAction::Delete);
+
+        // A rule with recorded hits is not reported even if its rows are gone
+        conn.execute("UPDATE rules SET hit_count = 3 WHERE id = ?1", [id])
+            .unwrap();
+        assert!(audit_rules(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_superseded_by_higher_priority() {
+        let (_dir, conn) = test_db();
+        let broad = add_rule(&conn, "ADOBE", "contains", "Software & Subscriptions", 5);
+        let narrow = add_rule(
+            &conn,
+            "ADOBE STOCK",
+            "contains",
+            "Software & Subscriptions",
+            0,
+        );
+        let other = add_rule(&conn, "adobe creative", "starts_with", "Office Expense", 1);
+
+        let issues = audit_rules(&conn).unwrap();
+        assert_eq!(issues.len(), 2);
+        // Reported in evaluation order: priority 1 before priority 0
+        assert_eq!(issues[0].rule.id, other);
+        assert!(matches!(issues[0].problem, RuleProblem::Superseded { by, .. } if by == broad));
+        // Different category: recommend running before the shadowing rule
+        assert_eq!(issues[0].recommended(), CleanupAction::SetPriority(6));
+        assert_eq!(issues[1].rule.id, narrow);
+        assert_eq!(issues[1].recommended(), CleanupAction::Delete);
+        assert!(issues[1]
+            .explanation()
+            .contains("Its one matching transaction is claimed first"));
+    }
+
+    #[test]
+    fn test_regex_matching_everything() {
+        let (_dir, conn) = test_db();
+        let id = add_rule(&conn, ".*", "regex", "Office Expense", 0);
+        add_rule(&conn, "GITHUB", "contains", "Software & Subscriptions", 0);
+        let issues = audit_rules(&conn).unwrap();
+        assert_eq!(issues[0].rule.id, id);
+        assert_eq!(issues[0].problem, RuleProblem::MatchesEverything);
+        assert_eq!(issues[0].rule.matching, 3);
+        // The rule it shadows is reported too
+        assert!(matches!(issues[1].problem, RuleProblem::Superseded { by, .. } if by == id));
+    }
+
+    #[test]
+    fn test_apply_actions() {
+        let (_dir, conn) = test_db();
+        let id = add_rule(&conn, "FIGMA", "contains", "Software & Subscriptions", 0);
+        apply(&conn, id, CleanupAction::SetPriority(7)).unwrap();
+        apply(&conn, id, CleanupAction::Delete).unwrap();
+        let (pri, active): (i64, i64) = conn
+            .query_row(
+                "SELECT priority, is_active FROM rules WHERE id = ?1",
+                [id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((pri, active), (7, 0));
+    }
+}
@@ -0,0 +1,164 @@
+use rusqlite::Connection;
+use serde::Deserialize;
+
+use crate::error::{NigelError, Result};
+use crate::sharing::{RuleEntry, RulesFile};
+
+/// A curated set of starter rules, as embedded TOML: a `name`, a
+/// `description`, and `[[rules]]` that list their category as it is named
+/// in each template, so one pack works in any book.
+pub struct RulePack {
+    pub key: &'static str,
+    pub source: &'static str,
+}
+
+pub const PACKS: &[RulePack] = &[RulePack {
+    key: "saas",
+    source: include_str!("saas.toml"),
+}];
+
+#[derive(Debug, Deserialize)]
+pub struct PackFile {
+    pub name: String,
+    pub description: String,
+    pub rules: Vec<PackRule>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PackRule {
+    pub pattern: String,
+    #[serde(default = "default_match_type")]
+    pub match_type: String,
+    pub vendor: Option<String>,
+    /// Category names to file under, tried in order.
+    pub categories: Vec<String>,
+}
+
+fn default_match_type() -> String {
+    "contains".into()
+}
+
+impl RulePack {
+    pub fn parse(&self) -> Result<PackFile> {
+        toml::from_str(self.source)
+            .map_err(|e| NigelError::Other(format!("Rule pack {} is invalid: {e}", self.key)))
+    }
+}
+
+/// Look up a pack by key, listing the valid keys when it isn't one.
+pub fn find(key: &str) -> Result<&'static RulePack> {
+    PACKS.iter().find(|p| p.key == key).ok_or_else(|| {
+        let keys: Vec<&str> = PACKS.iter().map(|p| p.key).collect();
+        NigelError::Other(format!(
+            "Unknown rule pack \"{key}\". Choose one of: {}",
+            keys.join(", ")
+        ))
+    })
+}
+
+/// The pack as a rules file for this book, ready for
+/// `sharing::import_rules()`: each rule goes under the first of its
+/// categories the book has, or the first listed when it has none, so the
+/// import reports the rule as skipped.
+pub fn rules_for(conn: &Connection, pack: &PackFile) -> Result<RulesFile> {
+    let mut stmt =
+        conn.prepare("SELECT EXISTS (SELECT 1 FROM categories WHERE name = ?1 AND is_active = 1)")?;
+    let mut rules = Vec::with_capacity(pack.rules.len());
+    for rule in &pack.rules {
+        let mut category = rule.categories.first().cloned().unwrap_or_default();
+        for name in &rule.categories {
+            if stmt.query_row([name], |r| r.get::<_, bool>(0))? {
+                category = name.clone();
+                break;
+            }
+        }
+        rules.push(RuleEntry {
+            pattern: rule.pattern.clone(),
+            match_type: rule.match_type.clone(),
+            category,
+            vendor: rule.vendor.clone(),
+            priority: 0,
+        });
+    }
+    Ok(RulesFile { rules })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db_with_template};
+    use crate::sharing::{import_rules, ImportMode};
+    use crate::templates::TEMPLATES;
+
+    fn test_db(template: &str) -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db_with_template(&conn, template).unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn every_pack_parses_with_valid_patterns() {
+        for pack in PACKS {
+            let file = pack.parse().unwrap();
+            assert!(!file.rules.is_empty(), "{}", pack.key);
+            for rule in &file.rules {
+                crate::cli::rules::validate_pattern(&rule.pattern, &rule.match_type).unwrap();
+                assert!(!rule.categories.is_empty(), "{}", rule.pattern);
+            }
+        }
+    }
+
+    #[test]
+    fn saas_pack_finds_a_category_in_every_template() {
+        let pack = find("saas").unwrap().parse().unwrap();
+        for template in TEMPLATES {
+            let (_dir, conn) = test_db(template.key);
+            let file = rules_for(&conn, &pack).unwrap();
+            let report = import_rules(&conn, &file, ImportMode::Merge, false, true).unwrap();
+            // Only payroll may be missing, from a sole proprietor's chart
+            for skipped in &report.skipped {
+                assert!(skipped.contains("Payroll"), "{}: {skipped}", template.key);
+            }
+            assert!(report.added >= pack.rules.len() - 2, "{}", template.key);
+        }
+    }
+
+    #[test]
+    fn install_keeps_existing_rules() {
+        let (_dir, conn) = test_db("s-corp");
+        let office: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Office Expense'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        let mine =
+            crate::cli::rules::add_rule(&conn, "Adobe", "contains", None, office, 3).unwrap();
+
+        let pack = find("saas").unwrap().parse().unwrap();
+        let file = rules_for(&conn, &pack).unwrap();
+        let report = import_rules(&conn, &file, ImportMode::Merge, false, false).unwrap();
+        assert_eq!(report.added, pack.rules.len() - 1);
+        assert_eq!(report.conflicts.len(), 1);
+        let (category_id, priority): (i64, i64) = conn
+            .query_row(
+                "SELECT category_id, priority FROM rules WHERE id = ?1",
+                [mine],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((category_id, priority), (office, 3));
+
+        // A second install adds nothing
+        let again = import_rules(&conn, &file, ImportMode::Merge, false, false).unwrap();
+        assert_eq!(again.added, 0);
+    }
+
+    #[test]
+    fn unknown_pack_lists_choices() {
+        let err = find("retail").map(|p| p.key).unwrap_err().to_string();
+        assert!(err.contains("Choose one of: saas"));
+    }
+}
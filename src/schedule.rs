@@ -0,0 +1,362 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rusqlite::Connection;
+
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+
+/// Reports that can be scheduled, by their `nigel report` subcommand name.
+pub const REPORTS: [&str; 13] = [
+    "pnl",
+    "expenses",
+    "tax",
+    "cashflow",
+    "register",
+    "flagged",
+    "reimbursables",
+    "balance",
+    "k1",
+    "deductions",
+    "compensation",
+    "tags",
+    "clients",
+];
+
+/// How often a scheduled report is generated. Each run covers the period
+/// that just ended; quarters and years follow the fiscal year.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cadence {
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl Cadence {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "monthly" => Ok(Cadence::Monthly),
+            "quarterly" => Ok(Cadence::Quarterly),
+            "yearly" => Ok(Cadence::Yearly),
+            _ => Err(NigelError::Other(format!(
+                "Unknown cadence '{s}'. Expected 'monthly', 'quarterly', or 'yearly'."
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Cadence::Monthly => "monthly",
+            Cadence::Quarterly => "quarterly",
+            Cadence::Yearly => "yearly",
+        }
+    }
+
+    fn months(self) -> u32 {
+        match self {
+            Cadence::Monthly => 1,
+            Cadence::Quarterly => 3,
+            Cadence::Yearly => 12,
+        }
+    }
+}
+
+pub struct ReportSchedule {
+    pub id: i64,
+    pub report: String,
+    pub cadence: Cadence,
+    pub format: String,
+    pub output_dir: String,
+    /// First day of the period after the one the next run covers.
+    pub next_run: NaiveDate,
+    pub last_run_at: Option<String>,
+    pub last_result: Option<String>,
+    pub last_ok: Option<bool>,
+}
+
+/// The date range one run of a schedule reports on.
+#[derive(Debug, PartialEq)]
+pub struct Period {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// File-name form: `2025-03` for a month, `2025-01-to-2025-03` for a
+    /// quarter, `fy2025` for a fiscal year.
+    pub label: String,
+}
+
+/// Flagged, reimbursables, and balance are snapshots of the books as they
+/// stand, not of a period; missed runs are not caught up one by one.
+pub fn is_snapshot(report: &str) -> bool {
+    matches!(report, "flagged" | "reimbursables" | "balance")
+}
+
+/// Mirrors the `--format` rules of `nigel report`.
+fn validate_format(report: &str, format: &str) -> Result<()> {
+    let ok = match format {
+        "pdf" | "text" => true,
+        "csv" | "xlsx" => matches!(report, "register" | "k1"),
+        "json" => report == "k1",
+        _ => {
+            return Err(NigelError::Other(format!(
+                "Unknown --format '{format}'. Expected 'pdf', 'text', 'csv', 'xlsx', or 'json'."
+            )))
+        }
+    };
+    if !ok {
+        return Err(NigelError::Other(format!(
+            "--format {format} is not available for `report {report}`"
+        )));
+    }
+    Ok(())
+}
+
+/// First period boundary strictly after `date`: the first of the next month,
+/// fiscal quarter, or fiscal year.
+pub fn next_run_after(cadence: Cadence, fiscal: FiscalYear, date: NaiveDate) -> NaiveDate {
+    let first = NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap_or(date);
+    let mut next = first + Months::new(1);
+    while !(next.month() + 12 - fiscal.start_month()).is_multiple_of(cadence.months()) {
+        next = next + Months::new(1);
+    }
+    next
+}
+
+/// The period a run due on `next_run` covers: the one ending the day before.
+pub fn period_before(cadence: Cadence, fiscal: FiscalYear, next_run: NaiveDate) -> Period {
+    let from = next_run - Months::new(cadence.months());
+    let to = next_run.pred_opt().unwrap_or(next_run);
+    let label = match cadence {
+        Cadence::Monthly => from.format("%Y-%m").to_string(),
+        Cadence::Quarterly => format!("{}-to-{}", from.format("%Y-%m"), to.format("%Y-%m")),
+        Cadence::Yearly => format!("fy{}", fiscal.year_of(to)),
+    };
+    Period { from, to, label }
+}
+
+pub fn add_schedule(
+    conn: &Connection,
+    report: &str,
+    cadence: Cadence,
+    format: &str,
+    output_dir: &str,
+    today: NaiveDate,
+) -> Result<i64> {
+    if !REPORTS.contains(&report) {
+        return Err(NigelError::Other(format!(
+            "Unknown report '{report}'. Expected one of: {}",
+            REPORTS.join(", ")
+        )));
+    }
+    validate_format(report, format)?;
+    if output_dir.trim().is_empty() {
+        return Err(NigelError::Other("An output directory is required".into()));
+    }
+    let next_run = next_run_after(cadence, FiscalYear::load(conn), today);
+    conn.execute(
+        "INSERT INTO report_schedules (report, cadence, format, output_dir, next_run) \
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![
+            report,
+            cadence.as_str(),
+            format,
+            output_dir,
+            next_run.to_string()
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// All schedules, soonest next run first.
+pub fn list_schedules(conn: &Connection) -> Result<Vec<ReportSchedule>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, report, cadence, format, output_dir, next_run, last_run_at, \
+         last_result, last_ok FROM report_schedules ORDER BY next_run, id",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            let cadence: String = row.get(2)?;
+            let next_run: String = row.get(5)?;
+            Ok(ReportSchedule {
+                id: row.get(0)?,
+                report: row.get(1)?,
+                cadence: Cadence::parse(&cadence).unwrap_or(Cadence::Monthly),
+                format: row.get(3)?,
+                output_dir: row.get(4)?,
+                next_run: NaiveDate::parse_from_str(&next_run, "%Y-%m-%d")
+                    .unwrap_or(NaiveDate::MIN),
+                last_run_at: row.get(6)?,
+                last_result: row.get(7)?,
+                last_ok: row.get(8)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Schedules whose next run is on or before `today`.
+pub fn due_schedules(conn: &Connection, today: NaiveDate) -> Result<Vec<ReportSchedule>> {
+    Ok(list_schedules(conn)?
+        .into_iter()
+        .filter(|s| s.next_run <= today)
+        .collect())
+}
+
+pub fn remove_schedule(conn: &Connection, id: i64) -> Result<()> {
+    let deleted = conn.execute("DELETE FROM report_schedules WHERE id = ?1", [id])?;
+    if deleted == 0 {
+        return Err(NigelError::Other(format!(
+            "No scheduled report with ID {id}"
+        )));
+    }
+    Ok(())
+}
+
+/// Record the outcome of a run. A failed run keeps its `next_run` so the
+/// next `nigel schedule run` retries the same period.
+pub fn record_run(
+    conn: &Connection,
+    id: i64,
+    ok: bool,
+    result: &str,
+    next_run: NaiveDate,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE report_schedules SET last_run_at = datetime('now'), last_result = ?2, \
+         last_ok = ?3, next_run = ?4 WHERE id = ?1",
+        rusqlite::params![id, result, ok, next_run.to_string()],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_next_run_follows_fiscal_boundaries() {
+        let cal = FiscalYear::CALENDAR;
+        assert_eq!(
+            next_run_after(Cadence::Monthly, cal, date("2025-03-01")),
+            date("2025-04-01")
+        );
+        assert_eq!(
+            next_run_after(Cadence::Quarterly, cal, date("2025-03-31")),
+            date("2025-04-01")
+        );
+        assert_eq!(
+            next_run_after(Cadence::Quarterly, cal, date("2025-04-01")),
+            date("2025-07-01")
+        );
+        assert_eq!(
+            next_run_after(Cadence::Yearly, cal, date("2025-12-31")),
+            date("2026-01-01")
+        );
+
+        let july = FiscalYear::new(7);
+        assert_eq!(
+            next_run_after(Cadence::Quarterly, july, date("2025-05-10")),
+            date("2025-07-01")
+        );
+        assert_eq!(
+            next_run_after(Cadence::Yearly, july, date("2025-07-01")),
+            date("2026-07-01")
+        );
+    }
+
+    #[test]
+    fn test_period_before_covers_the_period_just_ended() {
+        let cal = FiscalYear::CALENDAR;
+        let month = period_before(Cadence::Monthly, cal, date("2025-03-01"));
+        assert_eq!(month.from, date("2025-02-01"));
+        assert_eq!(month.to, date("2025-02-28"));
+        assert_eq!(month.label, "2025-02");
+
+        let quarter = period_before(Cadence::Quarterly, cal, date("2025-04-01"));
+        assert_eq!(quarter.from, date("2025-01-01"));
+        assert_eq!(quarter.to, date("2025-03-31"));
+        assert_eq!(quarter.label, "2025-01-to-2025-03");
+
+        let year = period_before(Cadence::Yearly, FiscalYear::new(7), date("2025-07-01"));
+        assert_eq!(year.from, date("2024-07-01"));
+        assert_eq!(year.to, date("2025-06-30"));
+        assert_eq!(year.label, "fy2025");
+    }
+
+    #[test]
+    fn test_add_list_due_and_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+
+        assert!(add_schedule(
+            &conn,
+            "nope",
+            Cadence::Monthly,
+            "pdf",
+            "/tmp",
+            date("2025-03-15")
+        )
+        .is_err());
+        assert!(add_schedule(
+            &conn,
+            "pnl",
+            Cadence::Monthly,
+            "csv",
+            "/tmp",
+            date("2025-03-15")
+        )
+        .is_err());
+        assert!(add_schedule(
+            &conn,
+            "k1",
+            Cadence::Yearly,
+            "json",
+            "/tmp",
+            date("2025-03-15")
+        )
+        .is_ok());
+
+        let id = add_schedule(
+            &conn,
+            "pnl",
+            Cadence::Monthly,
+            "pdf",
+            "/books",
+            date("2025-03-15"),
+        )
+        .unwrap();
+        let schedules = list_schedules(&conn).unwrap();
+        assert_eq!(schedules.len(), 2);
+        assert_eq!(schedules[0].id, id);
+        assert_eq!(schedules[0].next_run, date("2025-04-01"));
+        assert!(schedules[0].last_ok.is_none());
+
+        assert!(due_schedules(&conn, date("2025-03-31")).unwrap().is_empty());
+        let due = due_schedules(&conn, date("2025-04-02")).unwrap();
+        assert_eq!(due.len(), 1);
+
+        record_run(
+            &conn,
+            id,
+            true,
+            "Wrote /books/pnl-2025-03.pdf",
+            date("2025-05-01"),
+        )
+        .unwrap();
+        let s = &list_schedules(&conn).unwrap()[0];
+        assert_eq!(s.next_run, date("2025-05-01"));
+        assert_eq!(s.last_ok, Some(true));
+        assert_eq!(
+            s.last_result.as_deref(),
+            Some("Wrote /books/pnl-2025-03.pdf")
+        );
+        assert!(s.last_run_at.is_some());
+
+        remove_schedule(&conn, id).unwrap();
+        assert!(remove_schedule(&conn, id).is_err());
+        assert_eq!(list_schedules(&conn).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,609 @@
+use rusqlite::Connection;
+
+use crate::error::Result;
+use crate::migrations;
+
+/// What a table is for and what each of its columns holds, for
+/// `nigel db schema`. SQLite keeps no comments, so they live here; a test
+/// checks that every table and column a fresh book has is described.
+struct TableDoc {
+    name: &'static str,
+    description: &'static str,
+    columns: &'static [(&'static str, &'static str)],
+}
+
+const CREATED_AT: (&str, &str) = (
+    "created_at",
+    "When the row was added (UTC, `YYYY-MM-DD HH:MM:SS`)",
+);
+
+const TABLE_DOCS: &[TableDoc] = &[
+    TableDoc {
+        name: "accounts",
+        description: "Bank accounts, credit cards, and other accounts transactions are imported into",
+        columns: &[
+            ("id", "Account ID"),
+            ("name", "Display name, unique by convention"),
+            ("account_type", "checking, credit_card, line_of_credit, or payroll"),
+            ("institution", "Bank or card issuer"),
+            ("last_four", "Last four digits of the account number"),
+            CREATED_AT,
+            ("currency", "ISO currency code the account is held in"),
+            ("account_number", "Full account number, set with `accounts set-number`"),
+            ("routing_number", "Routing number, set with `accounts set-number`"),
+            ("is_archived", "1 for a closed account hidden from pickers"),
+            ("opening_balance", "Balance at `opening_date`, in the account's currency"),
+            ("opening_date", "Date the opening balance applies from (YYYY-MM-DD); transactions before it are ignored for balances"),
+            ("default_format", "Importer or CSV profile key remembered from the last `--format` import"),
+            ("last_import_dir", "Folder the last statement for this account was imported from"),
+        ],
+    },
+    TableDoc {
+        name: "categories",
+        description: "Chart of accounts: income, expense, and transfer categories",
+        columns: &[
+            ("id", "Category ID"),
+            ("name", "Category name"),
+            ("parent_id", "Parent category for sub-categories"),
+            ("category_type", "income, expense, or transfer"),
+            ("tax_line", "Schedule C line the category reports on"),
+            ("form_line", "Form 1120-S / K-1 line key, such as `1120S-7`"),
+            ("description", "What belongs in the category"),
+            ("is_active", "0 for a category hidden from pickers"),
+        ],
+    },
+    TableDoc {
+        name: "imports",
+        description: "One row per imported statement file",
+        columns: &[
+            ("id", "Import ID"),
+            ("filename", "Name of the imported file"),
+            ("account_id", "Account the file was imported into"),
+            ("import_date", "When the file was imported"),
+            ("record_count", "Transactions added"),
+            ("date_range_start", "Earliest transaction date in the file"),
+            ("date_range_end", "Latest transaction date in the file"),
+            ("checksum", "SHA-256 of the file, used to catch re-imports"),
+            ("stored_path", "Kept copy of the file under the data directory, if any"),
+        ],
+    },
+    TableDoc {
+        name: "transactions",
+        description: "Every imported or synced transaction (archived years live in separate files)",
+        columns: &[
+            ("id", "Transaction ID"),
+            ("account_id", "Account the transaction belongs to"),
+            ("date", "Posting date (YYYY-MM-DD)"),
+            ("description", "Description as it appeared on the statement"),
+            ("amount", "Amount in the account's currency; negative is money out"),
+            ("category_id", "Assigned category; NULL while uncategorized"),
+            ("vendor", "Clean vendor name set by a rule or in review"),
+            ("notes", "Unused; notes are kept in `memo`"),
+            ("is_flagged", "1 when the transaction needs review"),
+            ("flag_reason", "Why it was flagged"),
+            ("import_id", "Import that added it"),
+            CREATED_AT,
+            ("currency", "Currency when it differs from the account's; NULL means the account's"),
+            ("memo", "User note"),
+            ("sign_ok", "1 when a sign that disagrees with the category was reviewed and accepted"),
+            ("anomaly_checked", "1 once the unusual-transaction checks have looked at it"),
+            ("client_id", "Client the transaction is assigned to"),
+            ("deleted_at", "When it was moved to the trash; NULL for live transactions"),
+        ],
+    },
+    TableDoc {
+        name: "rules",
+        description: "Categorization rules, tried in priority order",
+        columns: &[
+            ("id", "Rule ID"),
+            ("pattern", "Text or regex matched against the description"),
+            ("match_type", "contains, starts_with, or regex"),
+            ("vendor", "Vendor name to set on a match"),
+            ("category_id", "Category to assign on a match"),
+            ("priority", "Higher runs first; ties go to the lower ID"),
+            ("hit_count", "Transactions the rule has categorized"),
+            ("is_active", "0 for a disabled rule"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "reconciliations",
+        description: "Monthly reconciliations of an account against its statement",
+        columns: &[
+            ("id", "Reconciliation ID"),
+            ("account_id", "Account reconciled"),
+            ("month", "Statement month (YYYY-MM)"),
+            ("statement_balance", "Ending balance on the statement"),
+            ("calculated_balance", "Ending balance according to the books"),
+            ("is_reconciled", "1 when the two balances matched"),
+            ("reconciled_at", "When the reconciliation was run"),
+            ("notes", "Discrepancy note for an unbalanced month"),
+            ("follow_up", "What still needs doing about the discrepancy"),
+            ("resolved_at", "When the discrepancy note was resolved"),
+        ],
+    },
+    TableDoc {
+        name: "metadata",
+        description: "Per-book settings such as `company_name`, `fiscal_year_start_month`, and `schema_version`",
+        columns: &[("key", "Setting name"), ("value", "Setting value, as text")],
+    },
+    TableDoc {
+        name: "csv_profiles",
+        description: "Saved column mappings for generic CSV imports",
+        columns: &[
+            ("id", "Profile ID"),
+            ("name", "Profile name"),
+            ("date_col", "Zero-based column holding the date"),
+            ("desc_col", "Zero-based column holding the description"),
+            ("amount_col", "Zero-based column holding the amount"),
+            ("date_format", "chrono format of the date column"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "exchange_rates",
+        description: "Rates for converting foreign-currency amounts to the home currency",
+        columns: &[
+            ("id", "Rate ID"),
+            ("currency", "ISO currency code"),
+            ("date", "Date the rate applies from (YYYY-MM-DD)"),
+            ("rate", "Home-currency units per one unit of `currency`"),
+        ],
+    },
+    TableDoc {
+        name: "mileage",
+        description: "Business miles driven, for the standard mileage deduction",
+        columns: &[
+            ("id", "Trip ID"),
+            ("date", "Date driven (YYYY-MM-DD)"),
+            ("miles", "Miles driven"),
+            ("purpose", "Business purpose of the trip"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "sync_connections",
+        description: "Bank sync connections",
+        columns: &[
+            ("id", "Connection ID"),
+            ("provider", "Sync provider, such as `simplefin`"),
+            ("access_url", "Provider access URL; holds credentials"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "sync_accounts",
+        description: "Accounts seen through a bank sync connection",
+        columns: &[
+            ("id", "Synced account ID"),
+            ("connection_id", "Connection the account comes from"),
+            ("external_id", "Provider's ID for the account"),
+            ("name", "Provider's name for the account"),
+            ("institution", "Provider's name for the bank"),
+            ("account_id", "Nigel account it is mapped to; NULL when not synced"),
+            ("cursor", "Unix time of the newest transaction synced"),
+            ("last_synced_at", "When the account was last synced"),
+        ],
+    },
+    TableDoc {
+        name: "tags",
+        description: "Tag names, lowercase and without the leading `#`",
+        columns: &[("id", "Tag ID"), ("name", "Tag name")],
+    },
+    TableDoc {
+        name: "transaction_tags",
+        description: "Which transactions carry which tags",
+        columns: &[
+            ("transaction_id", "Tagged transaction"),
+            ("tag_id", "Tag applied"),
+        ],
+    },
+    TableDoc {
+        name: "archives",
+        description: "Fiscal years moved out to archive files under `archives/`",
+        columns: &[
+            ("year", "Fiscal year archived"),
+            ("filename", "Archive file name"),
+            ("start_date", "First day of the fiscal year"),
+            ("end_date", "Last day of the fiscal year"),
+            ("transaction_count", "Transactions moved into the archive"),
+            ("archived_at", "When the year was archived"),
+        ],
+    },
+    TableDoc {
+        name: "report_schedules",
+        description: "Reports exported automatically on a cadence",
+        columns: &[
+            ("id", "Schedule ID"),
+            ("report", "Report name, as for `nigel report`"),
+            ("cadence", "monthly, quarterly, or yearly"),
+            ("format", "Export format"),
+            ("output_dir", "Directory exports are written to"),
+            ("next_run", "First day after the period the next run covers (YYYY-MM-DD)"),
+            ("last_run_at", "When the schedule last ran"),
+            ("last_result", "Path written or error from the last run"),
+            ("last_ok", "1 when the last run succeeded"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "vendor_alerts",
+        description: "Per-vendor monthly spend alerts",
+        columns: &[
+            ("id", "Alert ID"),
+            ("vendor", "Vendor or description text to match, case-insensitively"),
+            ("monthly_limit", "Alert when a month's spend goes over this"),
+            ("increase_pct", "Alert when a month's spend is this percent over the recent average"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "high_scores",
+        description: "Arcade high scores",
+        columns: &[
+            ("id", "Score ID"),
+            ("game", "Game name"),
+            ("score", "Score"),
+            ("played_at", "When the game was played"),
+        ],
+    },
+    TableDoc {
+        name: "balance_assertions",
+        description: "Known account balances the books are checked against",
+        columns: &[
+            ("id", "Assertion ID"),
+            ("account_id", "Account the balance is for"),
+            ("date", "Day the balance is as of, at its end (YYYY-MM-DD)"),
+            ("balance", "Expected balance, in the account's currency"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "distribution_plans",
+        description: "Planned owner distributions from the pay-yourself planner",
+        columns: &[
+            ("id", "Plan ID"),
+            ("month", "Month planned (YYYY-MM)"),
+            ("amount", "Planned distribution"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "reimbursables",
+        description: "Expenses to bill back to a client, through invoicing and repayment",
+        columns: &[
+            ("transaction_id", "The expense"),
+            ("client", "Client billed, in tag form"),
+            ("invoice", "Invoice reference"),
+            ("invoiced_on", "Date invoiced (YYYY-MM-DD)"),
+            ("reimbursed_on", "Date paid back (YYYY-MM-DD)"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "clients",
+        description: "Clients or projects transactions are assigned to for profitability",
+        columns: &[
+            ("id", "Client ID"),
+            ("name", "Client name, unique ignoring case"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "closes",
+        description: "Completed month closes and the close packets filed for them",
+        columns: &[
+            ("id", "Close ID"),
+            ("month", "Month closed (YYYY-MM)"),
+            ("packet_path", "Close packet, relative to the data directory"),
+            ("accounts_reconciled", "Accounts with a balanced reconciliation for the month"),
+            ("transactions", "Transactions dated in the month"),
+            ("net", "Net income for the month"),
+            ("closed_at", "When the month was closed"),
+        ],
+    },
+    TableDoc {
+        name: "shareholders",
+        description: "S-corp owners the K-1 prep worksheet allocates to",
+        columns: &[
+            ("id", "Shareholder ID"),
+            ("name", "Shareholder name, unique ignoring case"),
+            ("ownership_pct", "Ownership percentage (0-100)"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "audit_log",
+        description: "Changes that rewrite existing transactions or rules, such as category merges",
+        columns: &[
+            ("id", "Entry ID"),
+            ("action", "What was done, e.g. category.merge"),
+            ("detail", "What changed, in words"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "expense_limits",
+        description: "Per-person yearly deduction caps on expense categories, such as business gifts",
+        columns: &[
+            ("id", "Limit ID"),
+            ("category_id", "Limited category (one limit each)"),
+            ("per_person", "Most deductible per tagged counterparty per year"),
+            ("note", "Why the limit applies, e.g. the code section"),
+            CREATED_AT,
+        ],
+    },
+    TableDoc {
+        name: "category_signoffs",
+        description: "Pre-filing review decisions on each category's yearly total",
+        columns: &[
+            ("id", "Sign-off ID"),
+            ("year", "Fiscal year reviewed"),
+            ("category_id", "Category whose total was reviewed"),
+            ("status", "approved or investigate"),
+            ("total", "The category's total when it was signed off"),
+            ("note", "Why it needs investigating, or anything worth remembering"),
+            ("signed_at", "When the decision was recorded"),
+        ],
+    },
+    TableDoc {
+        name: "transactions_fts",
+        description: "Full-text index (FTS5, trigram) over transaction text for searches, kept by triggers",
+        columns: &[
+            ("description", "Indexed transactions.description"),
+            ("vendor", "Indexed transactions.vendor"),
+            ("memo", "Indexed transactions.memo"),
+        ],
+    },
+];
+
+pub struct Column {
+    pub name: String,
+    pub sql_type: String,
+    pub not_null: bool,
+    pub default: Option<String>,
+    /// Position in the primary key, starting at 1; 0 when not part of it.
+    pub primary_key: u32,
+    /// `(table, column)` this column references.
+    pub references: Option<(String, String)>,
+    pub comment: Option<&'static str>,
+}
+
+pub struct Table {
+    pub name: String,
+    pub comment: Option<&'static str>,
+    pub columns: Vec<Column>,
+    /// The `CREATE TABLE` statement, as SQLite stores it.
+    pub sql: String,
+    /// `CREATE INDEX` statements for the table's named indexes.
+    pub indexes: Vec<String>,
+}
+
+pub struct Schema {
+    pub version: u32,
+    pub tables: Vec<Table>,
+}
+
+fn table_doc(name: &str) -> Option<&'static TableDoc> {
+    TABLE_DOCS.iter().find(|t| t.name == name)
+}
+
+/// Read the book's schema as it is on disk, with the descriptions from
+/// `TABLE_DOCS` attached.
+pub fn describe(conn: &Connection) -> Result<Schema> {
+    let version = migrations::get_schema_version(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT name, sql FROM sqlite_master \
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%' \
+         -- A full-text index's own storage tables
+         AND name NOT IN (SELECT name FROM pragma_table_list WHERE type = 'shadow') \
+         ORDER BY name",
+    )?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<Vec<(String, String)>>>()?;
+
+    let mut tables = Vec::new();
+    for (name, sql) in rows {
+        let doc = table_doc(&name);
+        let foreign_keys = conn
+            .prepare("SELECT \"from\", \"table\", \"to\" FROM pragma_foreign_key_list(?1)")?
+            .query_map([&name], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<Vec<(String, String, Option<String>)>>>()?;
+        let columns = conn
+            .prepare(
+                "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1) \
+                 ORDER BY cid",
+            )?
+            .query_map([&name], |row| {
+                Ok(Column {
+                    name: row.get(0)?,
+                    sql_type: row.get(1)?,
+                    not_null: row.get(2)?,
+                    default: row.get(3)?,
+                    primary_key: row.get(4)?,
+                    references: None,
+                    comment: None,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .map(|mut col| {
+                col.references = foreign_keys
+                    .iter()
+                    .find(|(from, _, _)| *from == col.name)
+                    // A missing target column means the parent's primary key
+                    .map(|(_, table, to)| (table.clone(), to.clone().unwrap_or("id".into())));
+                col.comment = doc.and_then(|d| {
+                    d.columns
+                        .iter()
+                        .find(|(c, _)| *c == col.name)
+                        .map(|(_, text)| *text)
+                });
+                col
+            })
+            .collect();
+        let indexes = conn
+            .prepare(
+                "SELECT sql FROM sqlite_master \
+                 WHERE type = 'index' AND tbl_name = ?1 AND sql IS NOT NULL ORDER BY name",
+            )?
+            .query_map([&name], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        tables.push(Table {
+            comment: doc.map(|d| d.description),
+            name,
+            columns,
+            sql,
+            indexes,
+        });
+    }
+    Ok(Schema { version, tables })
+}
+
+impl Schema {
+    pub fn to_json(&self) -> serde_json::Value {
+        let tables: Vec<serde_json::Value> = self
+            .tables
+            .iter()
+            .map(|t| {
+                let columns: Vec<serde_json::Value> = t
+                    .columns
+                    .iter()
+                    .map(|c| {
+                        serde_json::json!({
+                            "name": c.name,
+                            "type": c.sql_type,
+                            "not_null": c.not_null,
+                            "default": c.default,
+                            "primary_key": c.primary_key > 0,
+                            "references": c.references.as_ref().map(|(table, column)| {
+                                serde_json::json!({ "table": table, "column": column })
+                            }),
+                            "comment": c.comment,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "name": t.name,
+                    "comment": t.comment,
+                    "columns": columns,
+                    "indexes": t.indexes,
+                    "sql": t.sql,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "schema_version": self.version,
+            "nigel_version": env!("CARGO_PKG_VERSION"),
+            "tables": tables,
+        })
+    }
+
+    /// The `CREATE` statements, each table preceded by `--` comments
+    /// describing it and its columns.
+    pub fn to_sql(&self) -> String {
+        let mut out = format!(
+            "-- Nigel schema version {} (nigel {})\n",
+            self.version,
+            env!("CARGO_PKG_VERSION")
+        );
+        for t in &self.tables {
+            out.push('\n');
+            if let Some(comment) = t.comment {
+                out.push_str(&format!("-- {}: {comment}\n", t.name));
+            }
+            let width = t.columns.iter().map(|c| c.name.len()).max().unwrap_or(0);
+            for c in &t.columns {
+                if let Some(comment) = c.comment {
+                    out.push_str(&format!("--   {:width$}  {comment}\n", c.name));
+                }
+            }
+            out.push_str(&t.sql);
+            out.push_str(";\n");
+            for index in &t.indexes {
+                out.push_str(index);
+                out.push_str(";\n");
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn every_table_and_column_is_described() {
+        let (_dir, conn) = test_db();
+        let schema = describe(&conn).unwrap();
+        assert_eq!(schema.version, migrations::LATEST_VERSION);
+        for table in &schema.tables {
+            assert!(table.comment.is_some(), "no description for {}", table.name);
+            for col in &table.columns {
+                assert!(
+                    col.comment.is_some(),
+                    "no description for {}.{}",
+                    table.name,
+                    col.name
+                );
+            }
+        }
+        // And nothing described that no longer exists
+        for doc in TABLE_DOCS {
+            let table = schema.tables.iter().find(|t| t.name == doc.name);
+            let table = table.unwrap_or_else(|| panic!("{} is gone", doc.name));
+            assert_eq!(table.columns.len(), doc.columns.len(), "{}", doc.name);
+        }
+    }
+
+    #[test]
+    fn json_lists_columns_keys_and_indexes() {
+        let (_dir, conn) = test_db();
+        let json = describe(&conn).unwrap().to_json();
+        assert_eq!(json["schema_version"], migrations::LATEST_VERSION);
+        let tables = json["tables"].as_array().unwrap();
+        let txns = tables.iter().find(|t| t["name"] == "transactions").unwrap();
+        let account = txns["columns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["name"] == "account_id")
+            .unwrap();
+        assert_eq!(account["type"], "INTEGER");
+        assert_eq!(account["not_null"], true);
+        assert_eq!(
+            account["references"],
+            serde_json::json!({ "table": "accounts", "column": "id" })
+        );
+        assert!(txns["indexes"][0]
+            .as_str()
+            .unwrap()
+            .contains("idx_transactions_client"));
+    }
+
+    #[test]
+    fn sql_comments_each_table() {
+        let (_dir, conn) = test_db();
+        let sql = describe(&conn).unwrap().to_sql();
+        assert!(sql.starts_with(&format!(
+            "-- Nigel schema version {}",
+            migrations::LATEST_VERSION
+        )));
+        assert!(sql.contains("-- mileage: Business miles driven"));
+        assert!(sql.contains("--   purpose     Business purpose of the trip\n"));
+        assert!(sql.contains("CREATE TABLE mileage"));
+        // The dump recreates the schema in an empty database
+        let empty = Connection::open_in_memory().unwrap();
+        empty.execute_batch(&sql).unwrap();
+    }
+}
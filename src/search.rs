@@ -0,0 +1,160 @@
+use rusqlite::Connection;
+
+use crate::error::Result;
+
+/// FTS5 index over each transaction's description, vendor, and memo, kept in
+/// step with `transactions` by the triggers migration v31 adds. The trigram
+/// tokenizer matches any substring of three or more characters, ignoring
+/// case, so a search reads the index instead of scanning every row.
+pub const FTS_TABLE: &str = "transactions_fts";
+
+/// Shortest search the trigram index can answer; shorter ones are scanned.
+pub const MIN_INDEXED_CHARS: usize = 3;
+
+/// `needle` as an FTS5 phrase, or `None` when it's too short for the index.
+pub fn match_query(needle: &str) -> Option<String> {
+    if needle.chars().count() < MIN_INDEXED_CHARS {
+        return None;
+    }
+    Some(format!("\"{}\"", needle.replace('"', "\"\"")))
+}
+
+/// SQL condition, over a subquery exposing `id`, `description`, `vendor`,
+/// `memo`, and `category`, for rows whose text contains the needle bound as
+/// `needle` (raw) and `phrase` (`match_query()`, empty when too short), both
+/// SQL parameter placeholders. Archived rows aren't in the index and are
+/// matched by a scan.
+pub fn contains_sql(needle: &str, phrase: &str) -> String {
+    let scan = ["description", "vendor", "memo", "category"]
+        .iter()
+        .map(|col| format!("instr(lower(COALESCE({col}, '')), lower({needle})) > 0"))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    format!(
+        "(CASE WHEN {phrase} = '' OR id NOT IN (SELECT id FROM main.transactions) \
+         THEN ({scan}) \
+         ELSE id IN (SELECT rowid FROM main.{FTS_TABLE} WHERE {FTS_TABLE} MATCH {phrase}) \
+              OR instr(lower(COALESCE(category, '')), lower({needle})) > 0 END)"
+    )
+}
+
+/// Whether `needle` (already lowercased) appears in any of `fields`,
+/// ignoring case: the same test as `contains_sql()`, for rows already loaded.
+pub fn contains<'a>(needle: &str, fields: impl IntoIterator<Item = Option<&'a str>>) -> bool {
+    fields
+        .into_iter()
+        .flatten()
+        .any(|f| f.to_lowercase().contains(needle))
+}
+
+/// Reindex every transaction, for a book whose index may have drifted.
+pub fn rebuild(conn: &Connection) -> Result<()> {
+    conn.execute(
+        &format!("INSERT INTO main.{FTS_TABLE} ({FTS_TABLE}) VALUES ('rebuild')"),
+        [],
+    )?;
+    Ok(())
+}
+
+/// Merge the index's segments, as `nigel db maintain` does.
+pub fn optimize(conn: &Connection) -> Result<()> {
+    conn.execute(
+        &format!("INSERT INTO main.{FTS_TABLE} ({FTS_TABLE}) VALUES ('optimize')"),
+        [],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+    use crate::reports::{register_rows_where, register_search, RegisterQuery, REGISTER_ORDER};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn add(conn: &Connection, date: &str, description: &str, vendor: Option<&str>) -> i64 {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, vendor) \
+             VALUES (1, ?1, ?2, -10.0, ?3)",
+            rusqlite::params![date, description, vendor],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    /// IDs the register search finds, in register order.
+    fn search(conn: &Connection, needle: &str) -> Vec<i64> {
+        let query = RegisterQuery::default();
+        let rows = register_rows_where(conn, &query, REGISTER_ORDER, |_| true).unwrap();
+        register_search(conn, &query, REGISTER_ORDER, needle)
+            .unwrap()
+            .into_iter()
+            .map(|pos| rows[pos].id)
+            .collect()
+    }
+
+    #[test]
+    fn triggers_keep_the_index_in_step() {
+        let (_dir, conn) = test_db();
+        let older = add(&conn, "2024-12-01", "Github sponsors", None);
+        let github = add(&conn, "2025-01-05", "GITHUB INC 555-1234", None);
+        let aws = add(&conn, "2025-02-01", "AMZN WEB SERVICES", Some("AWS"));
+
+        assert_eq!(search(&conn, "github"), vec![older, github]);
+        assert_eq!(search(&conn, "aws"), vec![aws]);
+        assert_eq!(search(&conn, "web serv"), vec![aws]);
+
+        conn.execute(
+            "UPDATE transactions SET vendor = 'Octocat', memo = 'team plan' WHERE id = ?1",
+            [github],
+        )
+        .unwrap();
+        assert_eq!(search(&conn, "octo"), vec![github]);
+        assert_eq!(search(&conn, "TEAM PLAN"), vec![github]);
+
+        conn.execute("DELETE FROM transactions WHERE id = ?1", [older])
+            .unwrap();
+        assert_eq!(search(&conn, "github"), vec![github]);
+        let indexed: i64 = conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM {FTS_TABLE} WHERE {FTS_TABLE} MATCH '\"github\"'"),
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(indexed, 1);
+
+        // Too short for the index, so scanned; categories aren't indexed
+        assert_eq!(search(&conn, "aw"), vec![aws]);
+        conn.execute(
+            "UPDATE transactions SET category_id = \
+             (SELECT id FROM categories WHERE name = 'Software & Subscriptions') WHERE id = ?1",
+            [aws],
+        )
+        .unwrap();
+        assert_eq!(search(&conn, "subscript"), vec![aws]);
+        assert!(search(&conn, "\"quoted\"").is_empty());
+
+        rebuild(&conn).unwrap();
+        optimize(&conn).unwrap();
+        assert_eq!(search(&conn, "amzn"), vec![aws]);
+    }
+
+    #[test]
+    fn match_query_quotes_a_phrase() {
+        assert_eq!(match_query("ab"), None);
+        assert_eq!(match_query("acme co"), Some("\"acme co\"".into()));
+        assert_eq!(match_query("say \"hi\""), Some("\"say \"\"hi\"\"\"".into()));
+    }
+}
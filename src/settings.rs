@@ -1,5 +1,8 @@
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{NigelError, Result};
@@ -8,6 +11,122 @@ fn default_true() -> bool {
     true
 }
 
+/// Dashboard widgets shown when settings.json has no `dashboard_widgets` list.
+pub const DEFAULT_DASHBOARD_WIDGETS: &[&str] = &["summary", "balances", "cashflow", "top_expenses"];
+
+fn default_dashboard_widgets() -> Vec<String> {
+    DEFAULT_DASHBOARD_WIDGETS
+        .iter()
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// How much of Nigel's personality shows in the dashboard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Persona {
+    /// Name plus a random greeting, and the goodbye screen.
+    Full,
+    /// Name only; no quips.
+    Minimal,
+    /// Plain "Nigel" header and no goodbye screen.
+    Off,
+}
+
+impl Persona {
+    pub const ALL: [Persona; 3] = [Persona::Full, Persona::Minimal, Persona::Off];
+
+    /// Parse a settings value; anything unrecognized means `Full`.
+    pub fn from_key(key: &str) -> Self {
+        match key {
+            "minimal" => Persona::Minimal,
+            "off" => Persona::Off,
+            _ => Persona::Full,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Persona::Full => "full",
+            Persona::Minimal => "minimal",
+            Persona::Off => "off",
+        }
+    }
+}
+
+fn default_locale() -> String {
+    crate::locales::DEFAULT_LOCALE.to_string()
+}
+
+fn default_persona() -> String {
+    Persona::Full.key().to_string()
+}
+
+/// `nigel report` export formats, in the order the Settings screen cycles them.
+pub const EXPORT_FORMATS: [&str; 4] = ["pdf", "text", "csv", "xlsx"];
+
+fn default_export_format() -> String {
+    EXPORT_FORMATS[0].to_string()
+}
+
+fn default_snapshot_keep() -> usize {
+    crate::cli::backup::SNAPSHOT_KEEP
+}
+
+fn default_backup_reminder_days() -> i64 {
+    crate::health::BACKUP_MAX_AGE_DAYS
+}
+
+fn default_trash_retention_days() -> i64 {
+    30
+}
+
+fn default_backup_keep() -> usize {
+    crate::maintenance::BACKUP_KEEP
+}
+
+fn default_maintenance_days() -> i64 {
+    crate::maintenance::MAINTENANCE_DAYS
+}
+
+fn default_currency_symbol() -> String {
+    "$".to_string()
+}
+
+fn default_decimal_separator() -> String {
+    crate::fmt::DECIMAL_SEPARATORS[0].to_string()
+}
+
+fn default_date_format() -> String {
+    crate::fmt::DATE_FORMATS[0].to_string()
+}
+
+fn default_max_future_days() -> i64 {
+    crate::data_quality::DEFAULT_MAX_FUTURE_DAYS
+}
+
+fn default_date_epoch() -> String {
+    crate::data_quality::DEFAULT_DATE_EPOCH.to_string()
+}
+
+/// Allowed range for `snapshot_keep`; at least one so a bad bulk edit can
+/// always be rolled back.
+pub const SNAPSHOT_KEEP_RANGE: std::ops::RangeInclusive<usize> = 1..=100;
+
+/// Allowed range for `backup_reminder_days`.
+pub const BACKUP_REMINDER_RANGE: std::ops::RangeInclusive<i64> = 1..=365;
+
+/// Allowed range for `backup_keep`; the newest backup is never pruned.
+pub const BACKUP_KEEP_RANGE: std::ops::RangeInclusive<usize> = 1..=1000;
+
+/// Allowed range for `maintenance_days`; 0 turns scheduled maintenance off.
+pub const MAINTENANCE_DAYS_RANGE: std::ops::RangeInclusive<i64> = 0..=365;
+
+/// Allowed range for `trash_retention_days`.
+pub const TRASH_RETENTION_RANGE: std::ops::RangeInclusive<i64> = 1..=365;
+
+/// Allowed range for `max_future_days`.
+pub const MAX_FUTURE_DAYS_RANGE: std::ops::RangeInclusive<i64> = 0..=3650;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     pub data_dir: String,
@@ -17,6 +136,195 @@ pub struct Settings {
     pub update_check: bool,
     #[serde(default)]
     pub last_update_check: Option<String>,
+    /// Dashboard widget keys, in display order.
+    #[serde(default = "default_dashboard_widgets")]
+    pub dashboard_widgets: Vec<String>,
+    /// TrueType font embedded in PDF exports instead of built-in Helvetica.
+    #[serde(default)]
+    pub pdf_font: Option<String>,
+    /// Bold variant for PDF headings; falls back to `pdf_font`.
+    #[serde(default)]
+    pub pdf_font_bold: Option<String>,
+    /// Copy every imported statement into `<data_dir>/statements/`.
+    #[serde(default)]
+    pub keep_statements: bool,
+    /// Split Gusto payroll imports into net pay, employee withholding,
+    /// benefit deductions, and reimbursements, as `nigel import --detailed`.
+    #[serde(default)]
+    pub gusto_detailed: bool,
+    /// Message catalog to show the interface in (see `locales::LOCALES`),
+    /// falling back to English for anything it doesn't translate.
+    #[serde(default = "default_locale")]
+    pub locale: String,
+    /// Symbol in front of home-currency amounts.
+    #[serde(default = "default_currency_symbol")]
+    pub currency_symbol: String,
+    /// "." or "," (see `fmt::DECIMAL_SEPARATORS`); thousands are grouped
+    /// with the other.
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: String,
+    /// How dates are shown, one of `fmt::DATE_FORMATS`. DD/MM/YYYY also
+    /// makes day-first the default for generic CSV imports.
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    /// Persona flavor: "full", "minimal", or "off" (see `Persona`).
+    #[serde(default = "default_persona")]
+    pub persona: String,
+    /// Format `nigel report` exports use when `--format` is omitted (one of
+    /// `EXPORT_FORMATS`). csv/xlsx only fit the register; other reports fall
+    /// back to PDF.
+    #[serde(default = "default_export_format")]
+    pub export_format: String,
+    /// Automatic pre-operation snapshots kept per operation.
+    #[serde(default = "default_snapshot_keep")]
+    pub snapshot_keep: usize,
+    /// Days without a backup before the dashboard warns.
+    #[serde(default = "default_backup_reminder_days")]
+    pub backup_reminder_days: i64,
+    /// Days a trashed transaction is kept before it is deleted for good.
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: i64,
+    /// Backups in `<data_dir>/backups/` kept when `nigel db maintain`
+    /// prunes older ones.
+    #[serde(default = "default_backup_keep")]
+    pub backup_keep: usize,
+    /// Days between the maintenance `nigel schedule run` does (VACUUM,
+    /// ANALYZE, pruning); 0 leaves it to `nigel db maintain`.
+    #[serde(default = "default_maintenance_days")]
+    pub maintenance_days: i64,
+    /// Where `nigel backup` also copies each backup: a folder, such as one
+    /// Dropbox syncs, or `s3://bucket/prefix` (feature `cloud`).
+    #[serde(default)]
+    pub backup_destination: Option<String>,
+    /// Encrypt that copy with a passphrase asked for at backup time.
+    #[serde(default)]
+    pub backup_encrypt: bool,
+    /// Always open the book read-only, as `nigel --read-only` does.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Flag unusual transactions (likely double charges, amounts far above a
+    /// vendor's usual) when categorizing; see `anomaly`.
+    #[serde(default = "default_true")]
+    pub anomaly_checks: bool,
+    /// Days past today a transaction may be dated before it's flagged as
+    /// out of range; see `data_quality`.
+    #[serde(default = "default_max_future_days")]
+    pub max_future_days: i64,
+    /// Earliest plausible transaction date (YYYY-MM-DD); anything older is
+    /// flagged as out of range.
+    #[serde(default = "default_date_epoch")]
+    pub date_epoch: String,
+    /// Hide advanced features (reconciliation, K-1 prep, the tags report,
+    /// rule priorities) from the dashboard and `nigel --help`. On for new
+    /// users; the commands still run when typed.
+    #[serde(default)]
+    pub simple_mode: bool,
+    /// Defaults per report (keyed like `nigel report <name>`): the period,
+    /// used when a report is run without date flags or opened from the
+    /// dashboard, and the account filter and export format, used when those
+    /// flags are omitted. Set with `nigel config set report.<name>.<field>`.
+    #[serde(default)]
+    pub report_defaults: BTreeMap<String, ReportDefault>,
+    /// Other books' data directories, shown beside this one in the books
+    /// overview (`nigel report books`).
+    #[serde(default)]
+    pub overview_books: Vec<String>,
+    /// Named SQL for `nigel query <name>`.
+    #[serde(default)]
+    pub saved_queries: BTreeMap<String, String>,
+}
+
+/// A report's defaults: `period` is "month", "last_month", "year", or
+/// "prior_year" (empty for none); with `until` (1-12) it only applies
+/// through that calendar month of each year. `account` filters the register
+/// and `format` is the `--format` exports use. Resolved by
+/// `cli::report::defaults`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReportDefault {
+    #[serde(default)]
+    pub period: String,
+    #[serde(default)]
+    pub until: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+impl Settings {
+    pub fn persona(&self) -> Persona {
+        Persona::from_key(&self.persona)
+    }
+
+    /// Snapshot retention, clamped into `SNAPSHOT_KEEP_RANGE` in case
+    /// settings.json was edited by hand.
+    pub fn snapshot_keep(&self) -> usize {
+        self.snapshot_keep
+            .clamp(*SNAPSHOT_KEEP_RANGE.start(), *SNAPSHOT_KEEP_RANGE.end())
+    }
+
+    pub fn backup_reminder_days(&self) -> i64 {
+        self.backup_reminder_days
+            .clamp(*BACKUP_REMINDER_RANGE.start(), *BACKUP_REMINDER_RANGE.end())
+    }
+
+    pub fn trash_retention_days(&self) -> i64 {
+        self.trash_retention_days
+            .clamp(*TRASH_RETENTION_RANGE.start(), *TRASH_RETENTION_RANGE.end())
+    }
+
+    pub fn backup_keep(&self) -> usize {
+        self.backup_keep
+            .clamp(*BACKUP_KEEP_RANGE.start(), *BACKUP_KEEP_RANGE.end())
+    }
+
+    pub fn maintenance_days(&self) -> i64 {
+        self.maintenance_days.clamp(
+            *MAINTENANCE_DAYS_RANGE.start(),
+            *MAINTENANCE_DAYS_RANGE.end(),
+        )
+    }
+
+    /// The decimal separator, or "." if settings.json holds something else.
+    pub fn decimal_separator(&self) -> char {
+        crate::fmt::DECIMAL_SEPARATORS
+            .iter()
+            .find(|c| self.decimal_separator == c.to_string())
+            .copied()
+            .unwrap_or(crate::fmt::DECIMAL_SEPARATORS[0])
+    }
+
+    /// The date display format, or YYYY-MM-DD if settings.json holds
+    /// something unrecognized.
+    pub fn date_format(&self) -> &'static str {
+        crate::fmt::DATE_FORMATS
+            .iter()
+            .find(|f| self.date_format.eq_ignore_ascii_case(f))
+            .copied()
+            .unwrap_or(crate::fmt::DATE_FORMATS[0])
+    }
+
+    pub fn max_future_days(&self) -> i64 {
+        self.max_future_days
+            .clamp(*MAX_FUTURE_DAYS_RANGE.start(), *MAX_FUTURE_DAYS_RANGE.end())
+    }
+
+    /// The `date_epoch` setting as a date, or the default if settings.json
+    /// holds something that doesn't parse.
+    pub fn date_epoch(&self) -> NaiveDate {
+        NaiveDate::parse_from_str(self.date_epoch.trim(), "%Y-%m-%d")
+            .unwrap_or_else(|_| crate::data_quality::default_epoch())
+    }
+
+    /// The default export format, or "pdf" if settings.json holds something
+    /// unrecognized.
+    pub fn export_format(&self) -> &str {
+        EXPORT_FORMATS
+            .iter()
+            .find(|f| **f == self.export_format)
+            .copied()
+            .unwrap_or(EXPORT_FORMATS[0])
+    }
 }
 
 impl Default for Settings {
@@ -26,6 +334,32 @@ impl Default for Settings {
             user_name: String::new(),
             update_check: true,
             last_update_check: None,
+            dashboard_widgets: default_dashboard_widgets(),
+            pdf_font: None,
+            pdf_font_bold: None,
+            keep_statements: false,
+            gusto_detailed: false,
+            locale: default_locale(),
+            currency_symbol: default_currency_symbol(),
+            decimal_separator: default_decimal_separator(),
+            date_format: default_date_format(),
+            persona: default_persona(),
+            export_format: default_export_format(),
+            snapshot_keep: default_snapshot_keep(),
+            backup_reminder_days: default_backup_reminder_days(),
+            trash_retention_days: default_trash_retention_days(),
+            backup_keep: default_backup_keep(),
+            maintenance_days: default_maintenance_days(),
+            backup_destination: None,
+            backup_encrypt: false,
+            read_only: false,
+            anomaly_checks: true,
+            max_future_days: default_max_future_days(),
+            date_epoch: default_date_epoch(),
+            simple_mode: false,
+            report_defaults: BTreeMap::new(),
+            overview_books: Vec::new(),
+            saved_queries: BTreeMap::new(),
         }
     }
 }
@@ -37,10 +371,19 @@ fn config_dir() -> PathBuf {
         .join("nigel")
 }
 
+/// Where translations not yet built in are read from (see `locales`).
+pub fn locales_dir() -> PathBuf {
+    config_dir().join("locales")
+}
+
 fn settings_path() -> PathBuf {
     config_dir().join("settings.json")
 }
 
+fn backup_path_for(path: &Path) -> PathBuf {
+    path.with_extension("json.bak")
+}
+
 fn default_data_dir() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("."))
@@ -48,32 +391,118 @@ fn default_data_dir() -> PathBuf {
         .join("nigel")
 }
 
+/// Parse a settings file. Returns None if the file is missing, unreadable, or
+/// not valid settings JSON (e.g. truncated by a crash mid-write).
+fn read_settings_file(path: &Path) -> Option<Settings> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Load settings from `path`, falling back to its `.bak` copy when the primary
+/// file exists but cannot be parsed. Defaults are used only when neither is usable.
+fn load_settings_from(path: &Path) -> Settings {
+    if !path.exists() {
+        return Settings::default();
+    }
+    read_settings_file(path)
+        .or_else(|| read_settings_file(&backup_path_for(path)))
+        .unwrap_or_default()
+}
+
 pub fn load_settings() -> Settings {
-    let path = settings_path();
-    if path.exists() {
-        let content = std::fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        Settings::default()
+    load_settings_from(&settings_path())
+}
+
+/// Write `contents` to `path` atomically: write a sibling temp file, fsync it,
+/// then rename over the target. A crash mid-write leaves the old file intact.
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    {
+        let mut file = std::fs::File::create(&tmp)?;
+        restrict_file_permissions(&tmp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Save settings to `path`, keeping the previous valid version as `<path>.bak`.
+/// A corrupt existing file is never copied over a good backup.
+fn save_settings_to(path: &Path, settings: &Settings) -> Result<()> {
+    let json =
+        serde_json::to_string_pretty(settings).map_err(|e| NigelError::Settings(e.to_string()))?;
+    if read_settings_file(path).is_some() {
+        let backup = backup_path_for(path);
+        std::fs::copy(path, &backup)?;
+        restrict_file_permissions(&backup)?;
     }
+    write_atomic(path, &format!("{json}\n"))
 }
 
 pub fn save_settings(settings: &Settings) -> Result<()> {
     let dir = config_dir();
     std::fs::create_dir_all(&dir)?;
     restrict_dir_permissions(&dir)?;
-    let json =
-        serde_json::to_string_pretty(settings).map_err(|e| NigelError::Settings(e.to_string()))?;
-    let path = settings_path();
-    std::fs::write(&path, format!("{json}\n"))?;
-    restrict_file_permissions(&path)?;
-    Ok(())
+    save_settings_to(&settings_path(), settings)
 }
 
 pub fn settings_file_exists() -> bool {
     settings_path().exists()
 }
 
+/// Health of the settings file as found on disk.
+#[derive(Debug)]
+pub enum SettingsHealth {
+    /// No settings.json (first run).
+    Missing,
+    /// settings.json parses cleanly.
+    Ok,
+    /// settings.json exists but cannot be parsed; `backup` is the last good
+    /// version if `settings.json.bak` is usable.
+    Corrupt { backup: Option<Box<Settings>> },
+}
+
+fn check_settings_at(path: &Path) -> SettingsHealth {
+    if !path.exists() {
+        return SettingsHealth::Missing;
+    }
+    if read_settings_file(path).is_some() {
+        return SettingsHealth::Ok;
+    }
+    SettingsHealth::Corrupt {
+        backup: read_settings_file(&backup_path_for(path)).map(Box::new),
+    }
+}
+
+pub fn check_settings() -> SettingsHealth {
+    check_settings_at(&settings_path())
+}
+
+fn restore_backup_at(path: &Path) -> Result<()> {
+    let backup = backup_path_for(path);
+    let content = std::fs::read_to_string(&backup)?;
+    write_atomic(path, &content)
+}
+
+/// Replace a corrupt settings.json with the contents of settings.json.bak.
+pub fn restore_settings_backup() -> Result<()> {
+    restore_backup_at(&settings_path())
+}
+
+fn quarantine_at(path: &Path) -> Result<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let dest = path.with_extension(format!("json.corrupt-{stamp}"));
+    std::fs::rename(path, &dest)?;
+    Ok(dest)
+}
+
+/// Move a corrupt settings.json aside so the next load starts from defaults.
+/// Returns the path the corrupt file was moved to.
+pub fn quarantine_corrupt_settings() -> Result<PathBuf> {
+    quarantine_at(&settings_path())
+}
+
 /// Read and remove legacy `company_name` from settings.json if present.
 /// Returns the value so it can be migrated to the DB metadata table.
 pub fn migrate_company_name() -> Option<String> {
@@ -87,7 +516,7 @@ pub fn migrate_company_name() -> Option<String> {
     }
     // Rewrite settings without company_name
     if let Ok(json) = serde_json::to_string_pretty(&raw) {
-        let _ = std::fs::write(&path, format!("{json}\n"));
+        let _ = write_atomic(&path, &format!("{json}\n"));
     }
     Some(name)
 }
@@ -151,6 +580,32 @@ mod tests {
             user_name: "Alice".to_string(),
             update_check: true,
             last_update_check: None,
+            dashboard_widgets: default_dashboard_widgets(),
+            pdf_font: None,
+            pdf_font_bold: None,
+            keep_statements: false,
+            gusto_detailed: false,
+            locale: default_locale(),
+            currency_symbol: default_currency_symbol(),
+            decimal_separator: default_decimal_separator(),
+            date_format: default_date_format(),
+            persona: default_persona(),
+            export_format: default_export_format(),
+            snapshot_keep: default_snapshot_keep(),
+            backup_reminder_days: default_backup_reminder_days(),
+            trash_retention_days: default_trash_retention_days(),
+            backup_keep: default_backup_keep(),
+            maintenance_days: default_maintenance_days(),
+            backup_destination: None,
+            backup_encrypt: false,
+            read_only: false,
+            anomaly_checks: true,
+            max_future_days: default_max_future_days(),
+            date_epoch: default_date_epoch(),
+            simple_mode: false,
+            report_defaults: BTreeMap::new(),
+            overview_books: Vec::new(),
+            saved_queries: BTreeMap::new(),
         };
         let json = serde_json::to_string_pretty(&settings).unwrap();
         std::fs::write(&path, &json).unwrap();
@@ -174,6 +629,62 @@ mod tests {
         assert_eq!(s.user_name, "Bob");
     }
 
+    #[test]
+    fn test_dashboard_widgets_default_when_missing() {
+        let json = r#"{"data_dir": "/tmp/test"}"#;
+        let s: Settings = serde_json::from_str(json).unwrap();
+        assert_eq!(s.dashboard_widgets, DEFAULT_DASHBOARD_WIDGETS);
+
+        let json = r#"{"data_dir": "/tmp/test", "dashboard_widgets": ["flagged"]}"#;
+        let s: Settings = serde_json::from_str(json).unwrap();
+        assert_eq!(s.dashboard_widgets, vec!["flagged"]);
+    }
+
+    #[test]
+    fn test_persona_default_and_parse() {
+        let json = r#"{"data_dir": "/tmp/test"}"#;
+        let s: Settings = serde_json::from_str(json).unwrap();
+        assert_eq!(s.persona(), Persona::Full);
+
+        let json = r#"{"data_dir": "/tmp/test", "persona": "off"}"#;
+        let s: Settings = serde_json::from_str(json).unwrap();
+        assert_eq!(s.persona(), Persona::Off);
+
+        assert_eq!(Persona::from_key("minimal"), Persona::Minimal);
+        assert_eq!(Persona::from_key("chatty"), Persona::Full);
+        for p in Persona::ALL {
+            assert_eq!(Persona::from_key(p.key()), p);
+        }
+    }
+
+    #[test]
+    fn test_policy_defaults_and_clamping() {
+        let json = r#"{"data_dir": "/tmp/test"}"#;
+        let s: Settings = serde_json::from_str(json).unwrap();
+        assert_eq!(s.export_format(), "pdf");
+        assert_eq!(s.snapshot_keep(), crate::cli::backup::SNAPSHOT_KEEP);
+        assert_eq!(s.backup_reminder_days(), crate::health::BACKUP_MAX_AGE_DAYS);
+        assert_eq!(s.trash_retention_days(), 30);
+        assert_eq!(s.max_future_days(), 30);
+        assert_eq!(s.date_epoch().to_string(), "2000-01-01");
+        assert_eq!(s.decimal_separator(), '.');
+        assert_eq!(s.date_format(), "YYYY-MM-DD");
+
+        let json = r#"{"data_dir": "/tmp/test", "export_format": "docx",
+            "snapshot_keep": 0, "backup_reminder_days": 9000, "trash_retention_days": 0,
+            "max_future_days": -5, "date_epoch": "01/01/1990",
+            "decimal_separator": ";", "date_format": "dd/mm/yyyy"}"#;
+        let s: Settings = serde_json::from_str(json).unwrap();
+        assert_eq!(s.export_format(), "pdf");
+        assert_eq!(s.snapshot_keep(), 1);
+        assert_eq!(s.backup_reminder_days(), 365);
+        assert_eq!(s.trash_retention_days(), 1);
+        assert_eq!(s.max_future_days(), 0);
+        assert_eq!(s.date_epoch().to_string(), "2000-01-01");
+        assert_eq!(s.decimal_separator(), '.');
+        assert_eq!(s.date_format(), "DD/MM/YYYY");
+    }
+
     #[test]
     fn test_ignores_unknown_fields_from_older_versions() {
         let json = r#"{"data_dir": "/tmp/test", "user_name": "Bob", "fiscal_year_start": "07"}"#;
@@ -207,6 +718,32 @@ mod tests {
             user_name: "Alice".to_string(),
             update_check: false,
             last_update_check: Some("2025-06-15T10:30:00".to_string()),
+            dashboard_widgets: default_dashboard_widgets(),
+            pdf_font: None,
+            pdf_font_bold: None,
+            keep_statements: false,
+            gusto_detailed: false,
+            locale: default_locale(),
+            currency_symbol: default_currency_symbol(),
+            decimal_separator: default_decimal_separator(),
+            date_format: default_date_format(),
+            persona: default_persona(),
+            export_format: default_export_format(),
+            snapshot_keep: default_snapshot_keep(),
+            backup_reminder_days: default_backup_reminder_days(),
+            trash_retention_days: default_trash_retention_days(),
+            backup_keep: default_backup_keep(),
+            maintenance_days: default_maintenance_days(),
+            backup_destination: None,
+            backup_encrypt: false,
+            read_only: false,
+            anomaly_checks: true,
+            max_future_days: default_max_future_days(),
+            date_epoch: default_date_epoch(),
+            simple_mode: false,
+            report_defaults: BTreeMap::new(),
+            overview_books: Vec::new(),
+            saved_queries: BTreeMap::new(),
         };
         let json = serde_json::to_string_pretty(&settings).unwrap();
         let loaded: Settings = serde_json::from_str(&json).unwrap();
@@ -217,6 +754,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_save_keeps_previous_version_as_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let mut settings = Settings {
+            user_name: "Alice".to_string(),
+            ..Settings::default()
+        };
+        save_settings_to(&path, &settings).unwrap();
+        assert!(!backup_path_for(&path).exists());
+
+        settings.user_name = "Bob".to_string();
+        save_settings_to(&path, &settings).unwrap();
+        let backup = read_settings_file(&backup_path_for(&path)).unwrap();
+        assert_eq!(backup.user_name, "Alice");
+        assert_eq!(load_settings_from(&path).user_name, "Bob");
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn test_truncated_settings_fall_back_to_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let settings = Settings {
+            user_name: "Alice".to_string(),
+            ..Settings::default()
+        };
+        save_settings_to(&path, &settings).unwrap();
+        save_settings_to(&path, &settings).unwrap();
+        std::fs::write(&path, r#"{"data_dir": "/tmp/te"#).unwrap();
+
+        assert_eq!(load_settings_from(&path).user_name, "Alice");
+        match check_settings_at(&path) {
+            SettingsHealth::Corrupt { backup: Some(b) } => assert_eq!(b.user_name, "Alice"),
+            other => panic!("expected corrupt with backup, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_corrupt_file_does_not_overwrite_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let settings = Settings {
+            user_name: "Alice".to_string(),
+            ..Settings::default()
+        };
+        save_settings_to(&path, &settings).unwrap();
+        save_settings_to(&path, &settings).unwrap();
+        std::fs::write(&path, "garbage").unwrap();
+        save_settings_to(&path, &Settings::default()).unwrap();
+        let backup = read_settings_file(&backup_path_for(&path)).unwrap();
+        assert_eq!(backup.user_name, "Alice");
+    }
+
+    #[test]
+    fn test_restore_backup_replaces_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let settings = Settings {
+            user_name: "Alice".to_string(),
+            ..Settings::default()
+        };
+        save_settings_to(&path, &settings).unwrap();
+        save_settings_to(&path, &settings).unwrap();
+        std::fs::write(&path, "").unwrap();
+        restore_backup_at(&path).unwrap();
+        assert!(matches!(check_settings_at(&path), SettingsHealth::Ok));
+        assert_eq!(read_settings_file(&path).unwrap().user_name, "Alice");
+    }
+
+    #[test]
+    fn test_quarantine_moves_corrupt_file_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        std::fs::write(&path, "{").unwrap();
+        assert!(matches!(
+            check_settings_at(&path),
+            SettingsHealth::Corrupt { backup: None }
+        ));
+        let moved = quarantine_at(&path).unwrap();
+        assert!(moved.exists());
+        assert!(matches!(check_settings_at(&path), SettingsHealth::Missing));
+    }
+
     #[test]
     fn test_legacy_settings_get_update_check_default() {
         // Simulates loading settings.json that was created before update_check existed
@@ -0,0 +1,241 @@
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+use crate::cli::categories::{list_categories, CategoryRow};
+use crate::error::Result;
+use crate::reports::{category_paths, CATEGORY_PATH_SEP};
+
+/// One active rule as documented: the category by its full path.
+pub struct DocRule {
+    pub pattern: String,
+    pub match_type: String,
+    pub category: String,
+    pub vendor: Option<String>,
+    pub priority: i64,
+    pub hit_count: i64,
+}
+
+/// The book's setup on paper: the chart of accounts with its tax mappings
+/// and the rules that categorize imports, for a new bookkeeper or the
+/// corporate records binder (`nigel export setup-docs`).
+pub struct SetupDocs {
+    pub prepared: NaiveDate,
+    /// Active categories, income first, sub-categories after their parent.
+    pub categories: Vec<CategoryRow>,
+    /// Active rules in the order the categorizer tries them.
+    pub rules: Vec<DocRule>,
+}
+
+/// What each category type means, for the headings of the chart.
+pub const TYPE_HEADINGS: &[(&str, &str)] = &[
+    ("income", "Income"),
+    ("expense", "Expenses"),
+    ("transfer", "Transfers (kept off the P&L and tax summary)"),
+];
+
+pub const RULES_NOTE: &str = "Imported transactions are categorized by the first rule that \
+     matches their description, highest priority first; anything no rule matches is flagged \
+     for review.";
+
+pub fn build(conn: &Connection, prepared: NaiveDate) -> Result<SetupDocs> {
+    let paths = category_paths(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT pattern, COALESCE(match_type, 'contains'), category_id, vendor, \
+         COALESCE(priority, 0), COALESCE(hit_count, 0) \
+         FROM rules WHERE is_active = 1 ORDER BY priority DESC, id",
+    )?;
+    let rules = stmt
+        .query_map([], |row| {
+            let category_id: i64 = row.get(2)?;
+            Ok(DocRule {
+                pattern: row.get(0)?,
+                match_type: row.get(1)?,
+                category: paths
+                    .get(&category_id)
+                    .map(|p| p.join(CATEGORY_PATH_SEP))
+                    .unwrap_or_default(),
+                vendor: row.get(3)?,
+                priority: row.get(4)?,
+                hit_count: row.get(5)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(SetupDocs {
+        prepared,
+        categories: list_categories(conn)?,
+        rules,
+    })
+}
+
+impl SetupDocs {
+    /// Categories of one type, in chart order.
+    pub fn of_type<'a>(&'a self, category_type: &'a str) -> impl Iterator<Item = &'a CategoryRow> {
+        self.categories
+            .iter()
+            .filter(move |c| c.category_type == category_type)
+    }
+
+    /// Name indented two spaces per level under its parents.
+    pub fn indented_name(category: &CategoryRow) -> String {
+        let level = category.path.matches(CATEGORY_PATH_SEP).count();
+        format!("{}{}", "  ".repeat(level), category.name)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = format!(
+            "Chart of Accounts and Rules\nPrepared {}\n",
+            self.prepared.format("%B %-d, %Y")
+        );
+
+        out.push_str(&format!(
+            "\nChart of Accounts ({} categories)\n",
+            self.categories.len()
+        ));
+        let mut rows = vec![[
+            "Category".to_string(),
+            "Tax Line".to_string(),
+            "Form Line".to_string(),
+        ]];
+        let mut headings = Vec::new();
+        for (category_type, heading) in TYPE_HEADINGS {
+            let before = rows.len();
+            for c in self.of_type(category_type) {
+                rows.push([
+                    format!("  {}", Self::indented_name(c)),
+                    c.tax_line.clone().unwrap_or_default(),
+                    c.form_line.clone().unwrap_or_default(),
+                ]);
+            }
+            if rows.len() > before {
+                headings.push((before, *heading));
+            }
+        }
+        let widths = column_widths(&rows);
+        for (i, row) in rows.iter().enumerate() {
+            if let Some((_, heading)) = headings.iter().find(|(at, _)| *at == i) {
+                out.push_str(&format!("\n{heading}\n"));
+            }
+            out.push_str(&pad_row(row, &widths));
+        }
+
+        out.push_str(&format!(
+            "\nRules ({} active)\n{RULES_NOTE}\n\n",
+            self.rules.len()
+        ));
+        if self.rules.is_empty() {
+            out.push_str("No rules yet.\n");
+        } else {
+            let mut rows = vec![[
+                "Priority".to_string(),
+                "Match".to_string(),
+                "Pattern".to_string(),
+                "Category".to_string(),
+                "Vendor".to_string(),
+                "Hits".to_string(),
+            ]];
+            for r in &self.rules {
+                rows.push([
+                    r.priority.to_string(),
+                    r.match_type.clone(),
+                    r.pattern.clone(),
+                    r.category.clone(),
+                    r.vendor.clone().unwrap_or_default(),
+                    r.hit_count.to_string(),
+                ]);
+            }
+            let widths = column_widths(&rows);
+            for row in &rows {
+                out.push_str(&pad_row(row, &widths));
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
+fn column_widths<const N: usize>(rows: &[[String; N]]) -> [usize; N] {
+    let mut widths = [0; N];
+    for row in rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    widths
+}
+
+fn pad_row<const N: usize>(row: &[String; N], widths: &[usize; N]) -> String {
+    let cells: Vec<String> = row
+        .iter()
+        .zip(widths)
+        .map(|(cell, w)| format!("{cell:<w$}"))
+        .collect();
+    format!("{}\n", cells.join("  ").trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    #[test]
+    fn documents_categories_by_type_and_rules_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        let software: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Software & Subscriptions'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        crate::cli::categories::add_category(
+            &conn,
+            "Design tools",
+            "expense",
+            None,
+            None,
+            Some(software),
+        )
+        .unwrap();
+        let design: i64 = conn
+            .query_row(
+                "SELECT id FROM categories WHERE name = 'Design tools'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        conn.execute(
+            "INSERT INTO rules (pattern, category_id, priority) VALUES ('GITHUB', ?1, 0)",
+            [software],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO rules (pattern, category_id, vendor, priority) \
+             VALUES ('ADOBE', ?1, 'Adobe', 5)",
+            [design],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO rules (pattern, category_id, is_active) VALUES ('OLD', ?1, 0)",
+            [software],
+        )
+        .unwrap();
+
+        let docs = build(&conn, NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()).unwrap();
+        let patterns: Vec<&str> = docs.rules.iter().map(|r| r.pattern.as_str()).collect();
+        assert_eq!(patterns, ["ADOBE", "GITHUB"]);
+        assert_eq!(
+            docs.rules[0].category,
+            "Software & Subscriptions > Design tools"
+        );
+
+        let text = docs.to_text();
+        assert!(text.contains("Prepared March 1, 2025"));
+        let income = text.find("\nIncome\n").unwrap();
+        let expenses = text.find("\nExpenses\n").unwrap();
+        assert!(income < expenses);
+        assert!(text.contains("\n    Design tools"));
+        assert!(text.contains("Rules (2 active)"));
+        assert!(!text.contains("OLD"));
+    }
+}
@@ -0,0 +1,189 @@
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::error::{NigelError, Result};
+
+/// An S-corp owner. Items on the K-1 worksheet are split by `ownership_pct`,
+/// pro rata for the whole year.
+pub struct Shareholder {
+    pub name: String,
+    pub ownership_pct: f64,
+}
+
+/// Every shareholder, largest stake first.
+pub fn list(conn: &Connection) -> Result<Vec<Shareholder>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, ownership_pct FROM shareholders ORDER BY ownership_pct DESC, name",
+    )?;
+    let shareholders = stmt
+        .query_map([], |row| {
+            Ok(Shareholder {
+                name: row.get(0)?,
+                ownership_pct: row.get(1)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(shareholders)
+}
+
+fn clean_name(raw: &str) -> Result<String> {
+    let name = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err(NigelError::Other("Shareholder name is required".into()));
+    }
+    Ok(name)
+}
+
+/// The shareholder named `name` (ignoring case), as `(id, stored name)`.
+fn find(conn: &Connection, name: &str) -> Result<Option<(i64, String)>> {
+    let name = clean_name(name)?;
+    Ok(conn
+        .query_row(
+            "SELECT id, name FROM shareholders WHERE name = ?1",
+            [&name],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .optional()?)
+}
+
+/// Refuse a stake outside (0, 100], or one that would take the total past
+/// 100% with the other shareholders (`except` is the one being changed).
+fn check_pct(conn: &Connection, pct: f64, except: Option<i64>) -> Result<()> {
+    if !(pct > 0.0 && pct <= 100.0) {
+        return Err(NigelError::Other(format!(
+            "Ownership must be more than 0% and at most 100% (got {pct}%)"
+        )));
+    }
+    let others: f64 = conn.query_row(
+        "SELECT COALESCE(SUM(ownership_pct), 0) FROM shareholders WHERE id IS NOT ?1",
+        [except],
+        |r| r.get(0),
+    )?;
+    if others + pct > 100.0 + 1e-9 {
+        return Err(NigelError::Other(format!(
+            "Ownership would total {}% (other shareholders hold {others}%)",
+            others + pct
+        )));
+    }
+    Ok(())
+}
+
+/// Add a shareholder. Errors if one by that name (ignoring case) exists.
+pub fn add(conn: &Connection, name: &str, pct: f64) -> Result<String> {
+    let name = clean_name(name)?;
+    if let Some((_, existing)) = find(conn, &name)? {
+        return Err(NigelError::Other(format!(
+            "Shareholder '{existing}' already exists"
+        )));
+    }
+    check_pct(conn, pct, None)?;
+    conn.execute(
+        "INSERT INTO shareholders (name, ownership_pct) VALUES (?1, ?2)",
+        rusqlite::params![name, pct],
+    )?;
+    Ok(name)
+}
+
+/// Change a shareholder's stake.
+pub fn set_ownership(conn: &Connection, name: &str, pct: f64) -> Result<String> {
+    let (id, name) = find(conn, name)?
+        .ok_or_else(|| NigelError::Other(format!("No shareholder named '{}'", name.trim())))?;
+    check_pct(conn, pct, Some(id))?;
+    conn.execute(
+        "UPDATE shareholders SET ownership_pct = ?1 WHERE id = ?2",
+        rusqlite::params![pct, id],
+    )?;
+    Ok(name)
+}
+
+pub fn remove(conn: &Connection, name: &str) -> Result<String> {
+    let (id, name) = find(conn, name)?
+        .ok_or_else(|| NigelError::Other(format!("No shareholder named '{}'", name.trim())))?;
+    conn.execute("DELETE FROM shareholders WHERE id = ?1", [id])?;
+    Ok(name)
+}
+
+/// Split `total` by each stake, in cents. When the stakes add up to 100%
+/// the rounding difference goes to the largest, so the shares sum to the
+/// total.
+pub fn allocate(total: f64, shareholders: &[Shareholder]) -> Vec<f64> {
+    let mut shares: Vec<f64> = shareholders
+        .iter()
+        .map(|s| (total * s.ownership_pct / 100.0 * 100.0).round() / 100.0)
+        .collect();
+    let pct_total: f64 = shareholders.iter().map(|s| s.ownership_pct).sum();
+    if (pct_total - 100.0).abs() < 1e-9 {
+        let largest = shareholders
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.ownership_pct.total_cmp(&b.1.ownership_pct))
+            .map(|(i, _)| i);
+        if let Some(i) = largest {
+            let rest: f64 = shares.iter().sum::<f64>() - shares[i];
+            shares[i] = ((total - rest) * 100.0).round() / 100.0;
+        }
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn stakes_stay_within_one_hundred_percent() {
+        let (_dir, conn) = test_db();
+        add(&conn, "Ada  Lovelace", 60.0).unwrap();
+        add(&conn, "Grace Hopper", 40.0).unwrap();
+        assert!(add(&conn, "ada lovelace", 1.0)
+            .unwrap_err()
+            .to_string()
+            .contains("already exists"));
+        assert!(add(&conn, "Alan Turing", 5.0)
+            .unwrap_err()
+            .to_string()
+            .contains("would total 105%"));
+        assert!(add(&conn, "Alan Turing", 0.0).is_err());
+
+        assert!(set_ownership(&conn, "grace hopper", 45.0).is_err());
+        set_ownership(&conn, "Ada Lovelace", 55.0).unwrap();
+        set_ownership(&conn, "grace hopper", 45.0).unwrap();
+        let names: Vec<(String, f64)> = list(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.name, s.ownership_pct))
+            .collect();
+        assert_eq!(
+            names,
+            [("Ada Lovelace".into(), 55.0), ("Grace Hopper".into(), 45.0)]
+        );
+
+        assert_eq!(remove(&conn, "GRACE HOPPER").unwrap(), "Grace Hopper");
+        assert!(remove(&conn, "Grace Hopper").is_err());
+    }
+
+    #[test]
+    fn allocation_sums_to_the_total() {
+        let holders = |pcts: &[f64]| -> Vec<Shareholder> {
+            pcts.iter()
+                .map(|&ownership_pct| Shareholder {
+                    name: String::new(),
+                    ownership_pct,
+                })
+                .collect()
+        };
+        let shares = allocate(100.0, &holders(&[33.34, 33.33, 33.33]));
+        assert_eq!(shares, [33.34, 33.33, 33.33]);
+        let shares = allocate(1000.01, &holders(&[50.0, 50.0]));
+        assert!((shares.iter().sum::<f64>() - 1000.01).abs() < 1e-9);
+        // Stakes short of 100% leave the rest unallocated
+        assert_eq!(allocate(1000.0, &holders(&[60.0])), [600.0]);
+    }
+}
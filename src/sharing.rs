@@ -0,0 +1,553 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::categorizer::find_duplicate_rule;
+use crate::cli::categories::{
+    add_category, blocking_reason, delete_category, list_categories, update_category, CategoryRow,
+};
+use crate::cli::rules::{add_rule, validate_pattern};
+use crate::error::{NigelError, Result};
+use crate::reports::CATEGORY_PATH_SEP;
+
+/// Rule and chart files are TOML or JSON, picked by extension.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileFormat {
+    Toml,
+    Json,
+}
+
+impl FileFormat {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("toml") => Ok(Self::Toml),
+            Some("json") => Ok(Self::Json),
+            _ => Err(NigelError::Other(format!(
+                "Unsupported file type: {} (use .toml or .json)",
+                path.display()
+            ))),
+        }
+    }
+}
+
+fn default_match_type() -> String {
+    "contains".into()
+}
+
+fn is_zero(n: &i64) -> bool {
+    *n == 0
+}
+
+/// One rule as written to a rules file. The category is matched by name in
+/// the book it is imported into.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuleEntry {
+    pub pattern: String,
+    #[serde(default = "default_match_type")]
+    pub match_type: String,
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub priority: i64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RulesFile {
+    #[serde(default)]
+    pub rules: Vec<RuleEntry>,
+}
+
+/// One category as written to a chart file; the parent is named, not
+/// numbered, so the file reads the same in any book.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CategoryEntry {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub category_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_line: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_line: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ChartFile {
+    #[serde(default)]
+    pub categories: Vec<CategoryEntry>,
+}
+
+pub fn write_file<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let text = match FileFormat::from_path(path)? {
+        FileFormat::Toml => toml::to_string_pretty(value)
+            .map_err(|e| NigelError::Other(format!("Could not write TOML: {e}")))?,
+        FileFormat::Json => serde_json::to_string_pretty(value)
+            .map_err(|e| NigelError::Other(format!("Could not write JSON: {e}")))?,
+    };
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+pub fn read_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let format = FileFormat::from_path(path)?;
+    let text = std::fs::read_to_string(path)?;
+    match format {
+        FileFormat::Toml => toml::from_str(&text)
+            .map_err(|e| NigelError::Other(format!("Invalid TOML in {}: {e}", path.display()))),
+        FileFormat::Json => serde_json::from_str(&text)
+            .map_err(|e| NigelError::Other(format!("Invalid JSON in {}: {e}", path.display()))),
+    }
+}
+
+/// Active rules, in the order the categorizer tries them.
+pub fn export_rules(conn: &Connection) -> Result<RulesFile> {
+    let mut stmt = conn.prepare(
+        "SELECT r.pattern, r.match_type, c.name, r.vendor, r.priority \
+         FROM rules r JOIN categories c ON r.category_id = c.id \
+         WHERE r.is_active = 1 ORDER BY r.priority DESC, r.id",
+    )?;
+    let rules = stmt
+        .query_map([], |row| {
+            Ok(RuleEntry {
+                pattern: row.get(0)?,
+                match_type: row.get(1)?,
+                category: row.get(2)?,
+                vendor: row.get(3)?,
+                priority: row.get(4)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(RulesFile { rules })
+}
+
+/// Active categories, parents before their sub-categories.
+pub fn export_chart(conn: &Connection) -> Result<ChartFile> {
+    let rows = list_categories(conn)?;
+    let names: HashMap<i64, String> = rows.iter().map(|c| (c.id, c.name.clone())).collect();
+    let categories = rows
+        .into_iter()
+        .map(|c| CategoryEntry {
+            parent: c.parent_id.and_then(|id| names.get(&id).cloned()),
+            name: c.name,
+            category_type: c.category_type,
+            tax_line: c.tax_line,
+            form_line: c.form_line,
+        })
+        .collect();
+    Ok(ChartFile { categories })
+}
+
+/// `Merge` adds what the book lacks; `Replace` makes the book match the file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportMode {
+    Merge,
+    Replace,
+}
+
+/// What an import did, and what it left alone and why.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub added: usize,
+    /// The rules added, as `'pattern' → category` (rules imports only).
+    pub added_rules: Vec<String>,
+    /// Entries the book already had as written.
+    pub unchanged: usize,
+    /// Existing categories rewritten to match the file (replace only).
+    pub updated: usize,
+    /// Rules deactivated or categories removed to match the file (replace only).
+    pub removed: usize,
+    /// Categories created for rules that named one the book lacked.
+    pub created_categories: Vec<String>,
+    /// Entries that disagree with the book, left as the book has them.
+    pub conflicts: Vec<String>,
+    pub skipped: Vec<String>,
+    /// Categories a replace could not remove, with the reason.
+    pub kept: Vec<String>,
+}
+
+fn active_category_id(conn: &Connection, name: &str) -> Result<Option<i64>> {
+    use rusqlite::OptionalExtension;
+    Ok(conn
+        .query_row(
+            "SELECT id FROM categories WHERE name = ?1 AND is_active = 1",
+            [name],
+            |r| r.get(0),
+        )
+        .optional()?)
+}
+
+/// Import rules in one transaction; `dry_run` rolls it back so the report
+/// shows what would happen. Unknown categories are created as expense
+/// categories when `create_missing` is set, otherwise their rules are
+/// skipped.
+pub fn import_rules(
+    conn: &Connection,
+    file: &RulesFile,
+    mode: ImportMode,
+    create_missing: bool,
+    dry_run: bool,
+) -> Result<ImportReport> {
+    let tx = conn.unchecked_transaction()?;
+    let mut report = ImportReport::default();
+    if mode == ImportMode::Replace {
+        report.removed = conn.execute("UPDATE rules SET is_active = 0 WHERE is_active = 1", [])?;
+    }
+
+    for entry in &file.rules {
+        let label = format!("'{}' \u{2192} {}", entry.pattern, entry.category);
+        if let Err(e) = validate_pattern(&entry.pattern, &entry.match_type) {
+            report.skipped.push(format!("{label}: {e}"));
+            continue;
+        }
+        let category_id = match active_category_id(conn, &entry.category)? {
+            Some(id) => id,
+            None if create_missing => {
+                add_category(conn, &entry.category, "expense", None, None, None)?;
+                report.created_categories.push(entry.category.clone());
+                active_category_id(conn, &entry.category)?.unwrap_or_default()
+            }
+            None => {
+                report
+                    .skipped
+                    .push(format!("{label}: unknown category {}", entry.category));
+                continue;
+            }
+        };
+        if find_duplicate_rule(conn, &entry.pattern, category_id, None)?.is_some() {
+            report.unchanged += 1;
+            continue;
+        }
+        // The same pattern already filed somewhere else
+        let other: Option<(i64, String)> = {
+            use rusqlite::OptionalExtension;
+            conn.query_row(
+                "SELECT r.id, c.name FROM rules r JOIN categories c ON r.category_id = c.id \
+                 WHERE r.is_active = 1 AND LOWER(TRIM(r.pattern)) = LOWER(TRIM(?1)) \
+                 ORDER BY r.id LIMIT 1",
+                [&entry.pattern],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?
+        };
+        if let Some((id, category)) = other {
+            report.conflicts.push(format!(
+                "{label}: rule {id} already files it under {category}"
+            ));
+            continue;
+        }
+        add_rule(
+            conn,
+            &entry.pattern,
+            &entry.match_type,
+            entry.vendor.as_deref(),
+            category_id,
+            entry.priority,
+        )?;
+        report.added += 1;
+        report.added_rules.push(label);
+    }
+
+    if dry_run {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+    Ok(report)
+}
+
+/// Import a chart of accounts in one transaction. Merge adds missing
+/// categories and reports ones that differ; replace also rewrites those to
+/// match the file and removes categories the file doesn't list, keeping any
+/// still in use.
+pub fn import_chart(
+    conn: &Connection,
+    file: &ChartFile,
+    mode: ImportMode,
+    dry_run: bool,
+) -> Result<ImportReport> {
+    let tx = conn.unchecked_transaction()?;
+    let mut report = ImportReport::default();
+
+    for entry in &file.categories {
+        let parent_id = match &entry.parent {
+            Some(parent) => match active_category_id(conn, parent)? {
+                Some(id) => Some(id),
+                None => {
+                    report.skipped.push(format!(
+                        "{}: parent category {parent} not found",
+                        entry.name
+                    ));
+                    continue;
+                }
+            },
+            None => None,
+        };
+        let existing = list_categories(conn)?
+            .into_iter()
+            .find(|c| c.name == entry.name);
+        let Some(existing) = existing else {
+            match add_category(
+                conn,
+                &entry.name,
+                &entry.category_type,
+                entry.tax_line.as_deref(),
+                entry.form_line.as_deref(),
+                parent_id,
+            ) {
+                Ok(()) => report.added += 1,
+                Err(e) => report.skipped.push(format!("{}: {e}", entry.name)),
+            }
+            continue;
+        };
+        let differences = differences(&existing, entry, parent_id);
+        if differences.is_empty() {
+            report.unchanged += 1;
+        } else if mode == ImportMode::Merge {
+            report.conflicts.push(format!(
+                "{}: book has a different {}",
+                entry.name,
+                differences.join(", ")
+            ));
+        } else {
+            match update_category(
+                conn,
+                existing.id,
+                &entry.name,
+                &entry.category_type,
+                entry.tax_line.as_deref(),
+                entry.form_line.as_deref(),
+                parent_id,
+            ) {
+                Ok(()) => report.updated += 1,
+                Err(e) => report.conflicts.push(format!("{}: {e}", entry.name)),
+            }
+        }
+    }
+
+    if mode == ImportMode::Replace {
+        let listed: Vec<&str> = file.categories.iter().map(|c| c.name.as_str()).collect();
+        let mut extra: Vec<CategoryRow> = list_categories(conn)?
+            .into_iter()
+            .filter(|c| !listed.contains(&c.name.as_str()))
+            .collect();
+        // Sub-categories first, so their parents are free to go
+        extra.sort_by_key(|c| std::cmp::Reverse(c.path.matches(CATEGORY_PATH_SEP).count()));
+        for category in extra {
+            match blocking_reason(conn, category.id)? {
+                Some(reason) => report.kept.push(format!(
+                    "{}: {}",
+                    category.name,
+                    reason.trim_start_matches("Cannot delete: ")
+                )),
+                None => {
+                    delete_category(conn, category.id)?;
+                    report.removed += 1;
+                }
+            }
+        }
+    }
+
+    if dry_run {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+    Ok(report)
+}
+
+/// Fields where the book's category differs from the file's entry.
+fn differences(
+    existing: &CategoryRow,
+    entry: &CategoryEntry,
+    parent_id: Option<i64>,
+) -> Vec<&'static str> {
+    let mut diffs = Vec::new();
+    if existing.category_type != entry.category_type {
+        diffs.push("type");
+    }
+    if existing.tax_line != entry.tax_line {
+        diffs.push("tax line");
+    }
+    if existing.form_line != entry.form_line {
+        diffs.push("form line");
+    }
+    if existing.parent_id != parent_id {
+        diffs.push("parent");
+    }
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        (dir, conn)
+    }
+
+    fn category_id(conn: &Connection, name: &str) -> i64 {
+        active_category_id(conn, name).unwrap().unwrap()
+    }
+
+    fn entry(pattern: &str, category: &str) -> RuleEntry {
+        RuleEntry {
+            pattern: pattern.into(),
+            match_type: "contains".into(),
+            category: category.into(),
+            vendor: None,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_rules_round_trip_through_toml_and_json() {
+        let (dir, conn) = test_db();
+        let software = category_id(&conn, "Software & Subscriptions");
+        add_rule(&conn, "ADOBE", "contains", Some("Adobe"), software, 5).unwrap();
+        add_rule(&conn, "^GITHUB", "regex", None, software, 0).unwrap();
+
+        for name in ["rules.toml", "rules.json"] {
+            let path = dir.path().join(name);
+            write_file(&path, &export_rules(&conn).unwrap()).unwrap();
+            let file: RulesFile = read_file(&path).unwrap();
+            assert_eq!(file.rules.len(), 2);
+            assert_eq!(file.rules[0].pattern, "ADOBE");
+            assert_eq!(file.rules[0].vendor.as_deref(), Some("Adobe"));
+            assert_eq!(file.rules[0].priority, 5);
+            assert_eq!(file.rules[1].match_type, "regex");
+        }
+        assert!(FileFormat::from_path(&dir.path().join("rules.yaml")).is_err());
+    }
+
+    #[test]
+    fn test_merge_reports_duplicates_conflicts_and_unknown_categories() {
+        let (_dir, conn) = test_db();
+        let software = category_id(&conn, "Software & Subscriptions");
+        add_rule(&conn, "ADOBE", "contains", None, software, 0).unwrap();
+        add_rule(&conn, "ZOOM", "contains", None, software, 0).unwrap();
+
+        let file = RulesFile {
+            rules: vec![
+                entry("adobe", "Software & Subscriptions"),
+                entry("ZOOM", "Advertising & Marketing"),
+                entry("FIGMA", "Design Tools"),
+                entry("GITHUB", "Software & Subscriptions"),
+                RuleEntry {
+                    match_type: "regex".into(),
+                    ..entry("(", "Software & Subscriptions")
+                },
+            ],
+        };
+        let report = import_rules(&conn, &file, ImportMode::Merge, false, false).unwrap();
+        assert_eq!(report.added, 1);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.conflicts.len(), 1);
+        assert!(report.conflicts[0].contains("already files it under Software"));
+        assert_eq!(report.skipped.len(), 2);
+        assert!(report.skipped[0].contains("unknown category Design Tools"));
+        assert!(report.created_categories.is_empty());
+
+        // Creating the missing category instead
+        let report = import_rules(&conn, &file, ImportMode::Merge, true, false).unwrap();
+        assert_eq!(report.added, 1);
+        assert_eq!(report.created_categories, vec!["Design Tools".to_string()]);
+        assert_eq!(export_rules(&conn).unwrap().rules.len(), 4);
+    }
+
+    #[test]
+    fn test_replace_and_dry_run() {
+        let (_dir, conn) = test_db();
+        let software = category_id(&conn, "Software & Subscriptions");
+        add_rule(&conn, "ADOBE", "contains", None, software, 0).unwrap();
+        let file = RulesFile {
+            rules: vec![entry("GITHUB", "Software & Subscriptions")],
+        };
+
+        let report = import_rules(&conn, &file, ImportMode::Replace, false, true).unwrap();
+        assert_eq!((report.added, report.removed), (1, 1));
+        // Dry run left the book alone
+        assert_eq!(export_rules(&conn).unwrap().rules[0].pattern, "ADOBE");
+
+        import_rules(&conn, &file, ImportMode::Replace, false, false).unwrap();
+        let rules = export_rules(&conn).unwrap().rules;
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].pattern, "GITHUB");
+    }
+
+    #[test]
+    fn test_chart_merge_and_replace() {
+        let (dir, conn) = test_db();
+        let path = dir.path().join("chart.toml");
+        let mut chart = export_chart(&conn).unwrap();
+        let seeded = chart.categories.len();
+
+        // A new sub-category, a changed form line, and one category dropped
+        chart.categories.push(CategoryEntry {
+            name: "Design Tools".into(),
+            category_type: "expense".into(),
+            tax_line: None,
+            form_line: None,
+            parent: Some("Software & Subscriptions".into()),
+        });
+        let insurance = chart
+            .categories
+            .iter_mut()
+            .find(|c| c.name == "Insurance")
+            .unwrap();
+        insurance.form_line = Some("1120S-20".into());
+        chart
+            .categories
+            .retain(|c| c.name != "Advertising & Marketing");
+        write_file(&path, &chart).unwrap();
+        let chart: ChartFile = read_file(&path).unwrap();
+
+        let report = import_chart(&conn, &chart, ImportMode::Merge, false).unwrap();
+        assert_eq!(report.added, 1);
+        assert_eq!(report.unchanged, seeded - 2);
+        assert_eq!(report.conflicts.len(), 1);
+        assert!(report.conflicts[0].starts_with("Insurance: book has a different form line"));
+        assert_eq!(report.removed, 0);
+        let design = list_categories(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|c| c.name == "Design Tools")
+            .unwrap();
+        assert_eq!(
+            design.parent_id,
+            Some(category_id(&conn, "Software & Subscriptions"))
+        );
+
+        // A rule keeps Advertising in the book through a replace
+        let advertising = category_id(&conn, "Advertising & Marketing");
+        add_rule(&conn, "META ADS", "contains", None, advertising, 0).unwrap();
+        let report = import_chart(&conn, &chart, ImportMode::Replace, false).unwrap();
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.removed, 0);
+        assert_eq!(
+            report.kept,
+            vec!["Advertising & Marketing: category has 1 active rule"]
+        );
+        let form_line: Option<String> = conn
+            .query_row(
+                "SELECT form_line FROM categories WHERE name = 'Insurance' AND is_active = 1",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(form_line.as_deref(), Some("1120S-20"));
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+
+use rusqlite::Connection;
+
+use crate::currency::HOME_AMOUNT_SQL;
+use crate::error::{NigelError, Result};
+use crate::fiscal::FiscalYear;
+use crate::reports::{category_paths, CATEGORY_PATH_SEP};
+
+/// Transactions shown behind each category total, largest first.
+pub const TOP_TRANSACTIONS: usize = 10;
+
+/// What the reviewer decided about a category's total.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignoffStatus {
+    /// The number is right and ready to file.
+    Approved,
+    /// Something behind the number needs a closer look before filing.
+    Investigate,
+}
+
+impl SignoffStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SignoffStatus::Approved => "approved",
+            SignoffStatus::Investigate => "investigate",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "approved" => Ok(SignoffStatus::Approved),
+            "investigate" => Ok(SignoffStatus::Investigate),
+            other => Err(NigelError::Other(format!(
+                "Unknown sign-off status '{other}'"
+            ))),
+        }
+    }
+}
+
+/// A recorded decision, with the total it was made against.
+pub struct Signoff {
+    pub status: SignoffStatus,
+    pub total: f64,
+    pub note: Option<String>,
+    /// When it was recorded, as SQLite `datetime('now')` (UTC).
+    pub signed_at: String,
+}
+
+/// One category's total for the year and where its review stands.
+pub struct CategoryTotal {
+    pub category_id: i64,
+    /// Full category path, e.g. `Software > Design`.
+    pub name: String,
+    pub category_type: String,
+    pub total: f64,
+    pub count: usize,
+    pub signoff: Option<Signoff>,
+}
+
+impl CategoryTotal {
+    /// Signed off, but the total has moved since, so it needs another look.
+    pub fn is_stale(&self) -> bool {
+        self.signoff
+            .as_ref()
+            .is_some_and(|s| (s.total - self.total).abs() > 0.005)
+    }
+
+    /// Approved at the total it has now.
+    pub fn is_approved(&self) -> bool {
+        !self.is_stale()
+            && self
+                .signoff
+                .as_ref()
+                .is_some_and(|s| s.status == SignoffStatus::Approved)
+    }
+
+    /// Marked for investigation at the total it has now.
+    pub fn is_investigating(&self) -> bool {
+        !self.is_stale()
+            && self
+                .signoff
+                .as_ref()
+                .is_some_and(|s| s.status == SignoffStatus::Investigate)
+    }
+
+    /// Short status word for lists.
+    pub fn status_label(&self) -> &'static str {
+        if self.is_stale() {
+            "changed"
+        } else if self.is_approved() {
+            "approved"
+        } else if self.is_investigating() {
+            "investigate"
+        } else {
+            "pending"
+        }
+    }
+}
+
+/// Every income and expense category with activity in the fiscal year, its
+/// total, and its sign-off, largest total (either sign) first. Only the open
+/// book is read: a year is reviewed before it's filed and archived.
+pub fn year_totals(conn: &Connection, year: i32) -> Result<Vec<CategoryTotal>> {
+    let (start, end) = FiscalYear::load(conn).bounds(year);
+    let paths = category_paths(conn)?;
+    let sql = format!(
+        "SELECT c.id, c.name, c.category_type, SUM({HOME_AMOUNT_SQL}), COUNT(*) \
+         FROM transactions t JOIN categories c ON t.category_id = c.id \
+         JOIN accounts a ON t.account_id = a.id \
+         WHERE t.deleted_at IS NULL AND t.date BETWEEN ?1 AND ?2 \
+         AND c.category_type != 'transfer' \
+         GROUP BY c.id"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([start.to_string(), end.to_string()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, f64>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut signoffs = signoffs_for(conn, year)?;
+    let mut totals: Vec<CategoryTotal> = rows
+        .into_iter()
+        .map(|(id, name, category_type, total, count)| CategoryTotal {
+            category_id: id,
+            name: paths
+                .get(&id)
+                .map(|p| p.join(CATEGORY_PATH_SEP))
+                .unwrap_or(name),
+            category_type,
+            total,
+            count: count as usize,
+            signoff: signoffs.remove(&id),
+        })
+        .collect();
+    totals.sort_by(|a, b| {
+        b.total
+            .abs()
+            .total_cmp(&a.total.abs())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(totals)
+}
+
+fn signoffs_for(conn: &Connection, year: i32) -> Result<HashMap<i64, Signoff>> {
+    let mut stmt = conn.prepare(
+        "SELECT category_id, status, total, note, signed_at FROM category_signoffs \
+         WHERE year = ?1",
+    )?;
+    let rows = stmt
+        .query_map([year], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    rows.into_iter()
+        .map(|(id, status, total, note, signed_at)| {
+            Ok((
+                id,
+                Signoff {
+                    status: SignoffStatus::parse(&status)?,
+                    total,
+                    note,
+                    signed_at,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// A transaction behind a category total.
+pub struct TopTransaction {
+    pub id: i64,
+    pub date: String,
+    pub description: String,
+    pub account: String,
+    pub amount: f64,
+}
+
+/// The largest transactions by size in a category for the fiscal year.
+pub fn top_transactions(
+    conn: &Connection,
+    year: i32,
+    category_id: i64,
+    limit: usize,
+) -> Result<Vec<TopTransaction>> {
+    let (start, end) = FiscalYear::load(conn).bounds(year);
+    let sql = format!(
+        "SELECT t.id, t.date, t.description, a.name, {HOME_AMOUNT_SQL} AS amount \
+         FROM transactions t JOIN accounts a ON t.account_id = a.id \
+         WHERE t.deleted_at IS NULL AND t.date BETWEEN ?1 AND ?2 AND t.category_id = ?3 \
+         ORDER BY ABS(amount) DESC, t.date, t.id LIMIT ?4"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map(
+            rusqlite::params![
+                start.to_string(),
+                end.to_string(),
+                category_id,
+                limit as i64
+            ],
+            |row| {
+                Ok(TopTransaction {
+                    id: row.get(0)?,
+                    date: row.get(1)?,
+                    description: row.get(2)?,
+                    account: row.get(3)?,
+                    amount: row.get(4)?,
+                })
+            },
+        )?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Record a decision on a category's total for the year, replacing any
+/// earlier one.
+pub fn record(
+    conn: &Connection,
+    year: i32,
+    category_id: i64,
+    status: SignoffStatus,
+    total: f64,
+    note: Option<&str>,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO category_signoffs (year, category_id, status, total, note) \
+         VALUES (?1, ?2, ?3, ?4, ?5) \
+         ON CONFLICT (year, category_id) DO UPDATE SET status = excluded.status, \
+         total = excluded.total, note = excluded.note, signed_at = datetime('now')",
+        rusqlite::params![year, category_id, status.as_str(), total, note],
+    )?;
+    Ok(())
+}
+
+/// Take back a category's sign-off for the year. False when it had none.
+pub fn clear(conn: &Connection, year: i32, category_id: i64) -> Result<bool> {
+    let deleted = conn.execute(
+        "DELETE FROM category_signoffs WHERE year = ?1 AND category_id = ?2",
+        rusqlite::params![year, category_id],
+    )?;
+    Ok(deleted > 0)
+}
+
+/// When the year was last fully approved, if every category is approved at
+/// its current total.
+pub fn completed_at(totals: &[CategoryTotal]) -> Option<&str> {
+    if totals.is_empty() || !totals.iter().all(CategoryTotal::is_approved) {
+        return None;
+    }
+    totals
+        .iter()
+        .filter_map(|t| t.signoff.as_ref().map(|s| s.signed_at.as_str()))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, conn)
+    }
+
+    fn category_id(conn: &Connection, name: &str) -> i64 {
+        conn.query_row("SELECT id FROM categories WHERE name = ?1", [name], |r| {
+            r.get(0)
+        })
+        .unwrap()
+    }
+
+    fn txn(conn: &Connection, date: &str, description: &str, amount: f64, category: &str) {
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+             VALUES (1, ?1, ?2, ?3, ?4)",
+            rusqlite::params![date, description, amount, category_id(conn, category)],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn totals_sort_by_size_with_top_transactions() {
+        let (_dir, conn) = test_db();
+        txn(
+            &conn,
+            "2025-03-01",
+            "ACME INVOICE",
+            5000.0,
+            "Client Services",
+        );
+        txn(
+            &conn,
+            "2025-04-01",
+            "ADOBE",
+            -50.0,
+            "Software & Subscriptions",
+        );
+        txn(
+            &conn,
+            "2025-05-01",
+            "GITHUB",
+            -10.0,
+            "Software & Subscriptions",
+        );
+        txn(
+            &conn,
+            "2025-06-01",
+            "FIGMA",
+            -150.0,
+            "Software & Subscriptions",
+        );
+        txn(
+            &conn,
+            "2024-06-01",
+            "LAST YEAR",
+            -999.0,
+            "Software & Subscriptions",
+        );
+
+        let totals = year_totals(&conn, 2025).unwrap();
+        let names: Vec<&str> = totals.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Client Services", "Software & Subscriptions"]);
+        assert_eq!(totals[1].total, -210.0);
+        assert_eq!(totals[1].count, 3);
+        assert_eq!(totals[1].status_label(), "pending");
+
+        let top = top_transactions(&conn, 2025, totals[1].category_id, 2).unwrap();
+        let descriptions: Vec<&str> = top.iter().map(|t| t.description.as_str()).collect();
+        assert_eq!(descriptions, vec!["FIGMA", "ADOBE"]);
+    }
+
+    #[test]
+    fn signoff_goes_stale_when_the_total_moves() {
+        let (_dir, conn) = test_db();
+        txn(
+            &conn,
+            "2025-04-01",
+            "ADOBE",
+            -50.0,
+            "Software & Subscriptions",
+        );
+        let software = category_id(&conn, "Software & Subscriptions");
+
+        record(
+            &conn,
+            2025,
+            software,
+            SignoffStatus::Investigate,
+            -50.0,
+            Some("dup?"),
+        )
+        .unwrap();
+        let totals = year_totals(&conn, 2025).unwrap();
+        assert!(totals[0].is_investigating());
+        assert_eq!(completed_at(&totals), None);
+
+        record(&conn, 2025, software, SignoffStatus::Approved, -50.0, None).unwrap();
+        let totals = year_totals(&conn, 2025).unwrap();
+        assert!(totals[0].is_approved());
+        assert!(completed_at(&totals).is_some());
+
+        txn(
+            &conn,
+            "2025-05-01",
+            "GITHUB",
+            -10.0,
+            "Software & Subscriptions",
+        );
+        let totals = year_totals(&conn, 2025).unwrap();
+        assert!(totals[0].is_stale());
+        assert!(!totals[0].is_approved());
+        assert_eq!(totals[0].status_label(), "changed");
+
+        assert!(clear(&conn, 2025, software).unwrap());
+        assert!(!clear(&conn, 2025, software).unwrap());
+    }
+}
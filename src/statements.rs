@@ -0,0 +1,307 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::error::{NigelError, Result};
+use crate::importer::compute_checksum;
+use crate::settings::{restrict_dir_permissions, restrict_file_permissions};
+
+/// Directory under the data dir holding retained statement files.
+pub const STATEMENTS_DIR: &str = "statements";
+
+/// One row of the import history.
+pub struct ImportRecord {
+    pub id: i64,
+    pub filename: String,
+    pub account_name: String,
+    pub import_date: String,
+    pub record_count: i64,
+    pub date_range_start: Option<String>,
+    pub date_range_end: Option<String>,
+    /// Path of the retained copy, relative to the data directory.
+    pub stored_path: Option<String>,
+}
+
+/// All imports, newest first.
+pub fn list_imports(conn: &Connection) -> Result<Vec<ImportRecord>> {
+    let mut stmt = conn.prepare(
+        "SELECT i.id, i.filename, COALESCE(a.name, '(unknown)'), COALESCE(i.import_date, ''), \
+         COALESCE(i.record_count, 0), i.date_range_start, i.date_range_end, i.stored_path \
+         FROM imports i LEFT JOIN accounts a ON a.id = i.account_id \
+         ORDER BY i.id DESC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(ImportRecord {
+                id: row.get(0)?,
+                filename: row.get(1)?,
+                account_name: row.get(2)?,
+                import_date: row.get(3)?,
+                record_count: row.get(4)?,
+                date_range_start: row.get(5)?,
+                date_range_end: row.get(6)?,
+                stored_path: row.get(7)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Make an account name safe to use as a single path component.
+fn path_component(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.').to_string();
+    if cleaned.is_empty() {
+        "account".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Pick a destination in `dir` for `filename`. An existing file with the
+/// same contents is reused; a different one gets a `-<import_id>` suffix.
+fn destination(dir: &Path, filename: &str, checksum: &str, import_id: i64) -> Result<PathBuf> {
+    let candidate = dir.join(filename);
+    if !candidate.exists() || compute_checksum(&candidate)? == checksum {
+        return Ok(candidate);
+    }
+    let name = Path::new(filename);
+    let stem = name
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("statement");
+    let renamed = match name.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{stem}-{import_id}.{ext}"),
+        None => format!("{stem}-{import_id}"),
+    };
+    Ok(dir.join(renamed))
+}
+
+/// Copy the source file of `import_id` into
+/// `<data_dir>/statements/<account>/<year>/` and record the copy on the
+/// import. The year comes from the last transaction date in the file.
+/// Returns the absolute path of the stored copy.
+pub fn retain(
+    conn: &Connection,
+    data_dir: &Path,
+    import_id: i64,
+    file_path: &Path,
+) -> Result<PathBuf> {
+    let (account_name, filename, range_end, checksum): (
+        Option<String>,
+        String,
+        Option<String>,
+        Option<String>,
+    ) = conn
+        .query_row(
+            "SELECT a.name, i.filename, i.date_range_end, i.checksum \
+             FROM imports i LEFT JOIN accounts a ON a.id = i.account_id WHERE i.id = ?1",
+            [import_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .optional()?
+        .ok_or_else(|| NigelError::Other(format!("Import {import_id} not found")))?;
+
+    let year = range_end
+        .as_deref()
+        .and_then(|d| d.get(..4))
+        .map(str::to_string)
+        .unwrap_or_else(|| chrono::Local::now().format("%Y").to_string());
+    let account = path_component(account_name.as_deref().unwrap_or("unknown"));
+    let filename = if filename.is_empty() {
+        "statement".to_string()
+    } else {
+        filename
+    };
+
+    let root = data_dir.join(STATEMENTS_DIR);
+    let dir = root.join(&account).join(&year);
+    std::fs::create_dir_all(&dir)?;
+    for d in [&root, &root.join(&account), &dir] {
+        restrict_dir_permissions(d)?;
+    }
+
+    let checksum = match checksum {
+        Some(c) => c,
+        None => compute_checksum(file_path)?,
+    };
+    let dest = destination(&dir, &filename, &checksum, import_id)?;
+    if !dest.exists() {
+        std::fs::copy(file_path, &dest)?;
+        restrict_file_permissions(&dest)?;
+    }
+
+    let relative = dest
+        .strip_prefix(data_dir)
+        .unwrap_or(&dest)
+        .to_string_lossy()
+        .to_string();
+    conn.execute(
+        "UPDATE imports SET stored_path = ?1 WHERE id = ?2",
+        rusqlite::params![relative, import_id],
+    )?;
+    Ok(dest)
+}
+
+/// Absolute path of a stored copy recorded relative to `data_dir`.
+pub fn resolve(data_dir: &Path, stored_path: &str) -> PathBuf {
+    data_dir.join(stored_path)
+}
+
+/// Open a stored statement with the system's default application.
+pub fn open_with_system(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Err(NigelError::Other(format!(
+            "Stored file is missing: {}",
+            path.display()
+        )));
+    }
+    let mut cmd = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut c = std::process::Command::new("cmd");
+        c.args(["/C", "start", ""]);
+        c
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    cmd.arg(path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| NigelError::Other(format!("Could not open {}: {e}", path.display())))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+    use crate::importer::import_file;
+
+    const CSV: &str = "Date,Description,Amount,Running Bal.\n\
+                       03/02/2025,ADOBE CREATIVE,-54.99,1000.00\n\
+                       03/05/2025,CLIENT PAYMENT,2500.00,3445.01\n";
+
+    fn setup() -> (tempfile::TempDir, PathBuf, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("data");
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let conn = get_connection(&data_dir.join("nigel.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('BofA / Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, data_dir, conn)
+    }
+
+    fn write_csv(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_retain_copies_into_account_year_dir() {
+        let (dir, data_dir, conn) = setup();
+        let src = write_csv(dir.path(), "stmt.csv", CSV);
+        let result = import_file(
+            &conn,
+            &src,
+            "BofA / Checking",
+            None,
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        let import_id = result.import_id.unwrap();
+
+        let stored = retain(&conn, &data_dir, import_id, &src).unwrap();
+        assert_eq!(
+            stored,
+            data_dir.join("statements/BofA _ Checking/2025/stmt.csv")
+        );
+        assert_eq!(std::fs::read_to_string(&stored).unwrap(), CSV);
+
+        let history = list_imports(&conn).unwrap();
+        assert_eq!(
+            history[0].stored_path.as_deref(),
+            Some("statements/BofA _ Checking/2025/stmt.csv")
+        );
+        assert_eq!(
+            resolve(&data_dir, "statements/x.csv"),
+            data_dir.join("statements/x.csv")
+        );
+    }
+
+    #[test]
+    fn test_retain_does_not_overwrite_different_file_with_same_name() {
+        let (dir, data_dir, conn) = setup();
+        let first = write_csv(dir.path(), "stmt.csv", CSV);
+        let id1 = import_file(
+            &conn,
+            &first,
+            "BofA / Checking",
+            None,
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap()
+        .import_id
+        .unwrap();
+        retain(&conn, &data_dir, id1, &first).unwrap();
+
+        let other_dir = dir.path().join("other");
+        std::fs::create_dir_all(&other_dir).unwrap();
+        let second = write_csv(
+            &other_dir,
+            "stmt.csv",
+            "Date,Description,Amount,Running Bal.\n04/01/2025,RENT,-1200.00,2245.01\n",
+        );
+        let id2 = import_file(
+            &conn,
+            &second,
+            "BofA / Checking",
+            None,
+            false,
+            None,
+            false,
+            false,
+        )
+        .unwrap()
+        .import_id
+        .unwrap();
+        let stored = retain(&conn, &data_dir, id2, &second).unwrap();
+        assert_eq!(
+            stored.file_name().unwrap().to_str().unwrap(),
+            format!("stmt-{id2}.csv")
+        );
+        assert_eq!(
+            std::fs::read_to_string(data_dir.join("statements/BofA _ Checking/2025/stmt.csv"))
+                .unwrap(),
+            CSV
+        );
+    }
+
+    #[test]
+    fn test_path_component_sanitizes() {
+        assert_eq!(path_component("../etc"), "_etc");
+        assert_eq!(path_component("  "), "account");
+        assert_eq!(path_component("Amex Gold"), "Amex Gold");
+    }
+}
@@ -0,0 +1,381 @@
+use std::path::Path;
+
+use crate::error::{NigelError, Result};
+use crate::reports::{K1LineTotal, RegisterRow};
+
+/// A single spreadsheet cell. Numbers stay numeric in XLSX so amounts can be
+/// summed without re-parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Int(i64),
+    Number(f64),
+    Empty,
+}
+
+impl Value {
+    fn to_csv_field(&self) -> String {
+        match self {
+            Value::Text(s) => s.clone(),
+            Value::Int(n) => n.to_string(),
+            Value::Number(n) => format!("{n:.2}"),
+            Value::Empty => String::new(),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Text(s.to_string())
+    }
+}
+
+impl From<Option<&str>> for Value {
+    fn from(s: Option<&str>) -> Self {
+        s.map_or(Value::Empty, Value::from)
+    }
+}
+
+/// A header row plus data rows, written as CSV or as a one-sheet workbook.
+pub struct Sheet {
+    /// Worksheet tab name (XLSX only).
+    #[cfg_attr(not(feature = "xlsx"), allow(dead_code))]
+    pub name: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TabularFormat {
+    Csv,
+    Xlsx,
+}
+
+impl TabularFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "csv" => Some(Self::Csv),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// Register rows as a sheet, in the order given. Shared by
+/// `nigel report register --format csv|xlsx` and the register browser.
+pub fn register_sheet(rows: &[RegisterRow]) -> Sheet {
+    let headers = [
+        "ID",
+        "Date",
+        "Description",
+        "Amount",
+        "Currency",
+        "Original Amount",
+        "Category",
+        "Vendor",
+        "Account",
+        "Flagged",
+        "Memo",
+        "Tags",
+    ];
+    let rows = rows
+        .iter()
+        .map(|r| {
+            vec![
+                Value::Int(r.id),
+                Value::from(r.date.as_str()),
+                Value::from(r.description.as_str()),
+                Value::Number(r.amount),
+                Value::from(r.foreign.as_ref().map(|f| f.currency.as_str())),
+                r.foreign
+                    .as_ref()
+                    .map_or(Value::Empty, |f| Value::Number(f.amount)),
+                Value::from(r.category.as_deref()),
+                Value::from(r.vendor.as_deref()),
+                Value::from(r.account_name.as_str()),
+                Value::from(if r.is_flagged { "yes" } else { "" }),
+                Value::from(r.memo.as_deref()),
+                if r.tags.is_empty() {
+                    Value::Empty
+                } else {
+                    Value::Text(crate::tags::label(&r.tags))
+                },
+            ]
+        })
+        .collect();
+    Sheet {
+        name: "Register".into(),
+        headers: headers.iter().map(|h| h.to_string()).collect(),
+        rows,
+    }
+}
+
+/// K-1 worksheet lines as a sheet, one row per form line, for
+/// `nigel report k1 --format csv|xlsx`.
+pub fn k1_sheet(lines: &[K1LineTotal]) -> Sheet {
+    let headers = ["Key", "Form", "Line", "Description", "Amount", "Sources"];
+    let rows = lines
+        .iter()
+        .map(|l| {
+            vec![
+                Value::from(l.key.as_str()),
+                Value::from(l.form),
+                Value::from(l.line.as_str()),
+                Value::from(l.description.as_str()),
+                Value::Number(l.amount),
+                if l.sources.is_empty() {
+                    Value::Empty
+                } else {
+                    Value::Text(l.sources.join("; "))
+                },
+            ]
+        })
+        .collect();
+    Sheet {
+        name: "K-1 Prep".into(),
+        headers: headers.iter().map(|h| h.to_string()).collect(),
+        rows,
+    }
+}
+
+/// Write `sheet` to `path`, creating parent directories and restricting
+/// permissions like the other exporters.
+pub fn write(sheet: &Sheet, path: &Path, format: TabularFormat) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    match format {
+        TabularFormat::Csv => write_csv(sheet, path)?,
+        TabularFormat::Xlsx => write_xlsx(sheet, path)?,
+    }
+    crate::settings::restrict_file_permissions(path)
+}
+
+fn write_csv(sheet: &Sheet, path: &Path) -> Result<()> {
+    write_csv_to(sheet, std::fs::File::create(path)?)
+}
+
+/// Write `sheet` as CSV to any writer (used for `--output -`).
+pub fn write_csv_to(sheet: &Sheet, out: impl std::io::Write) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(out);
+    wtr.write_record(&sheet.headers)?;
+    for row in &sheet.rows {
+        wtr.write_record(row.iter().map(Value::to_csv_field))?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(not(feature = "xlsx"))]
+fn write_xlsx(_sheet: &Sheet, _path: &Path) -> Result<()> {
+    Err(NigelError::Other(
+        "XLSX export requires the 'xlsx' feature".into(),
+    ))
+}
+
+/// Minimal SpreadsheetML package: one worksheet with inline strings, no
+/// styles or shared-string table.
+#[cfg(feature = "xlsx")]
+fn write_xlsx(sheet: &Sheet, path: &Path) -> Result<()> {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#;
+    const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+    const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#;
+
+    let workbook = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        xml_escape(&sheet.name)
+    );
+
+    let mut data = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    let header: Vec<Value> = sheet
+        .headers
+        .iter()
+        .map(|h| Value::from(h.as_str()))
+        .collect();
+    for (r, row) in std::iter::once(&header).chain(&sheet.rows).enumerate() {
+        data.push_str(&format!(r#"<row r="{}">"#, r + 1));
+        for (c, value) in row.iter().enumerate() {
+            let cell_ref = format!("{}{}", column_name(c), r + 1);
+            match value {
+                Value::Text(s) => data.push_str(&format!(
+                    r#"<c r="{cell_ref}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#,
+                    xml_escape(s)
+                )),
+                Value::Int(n) => {
+                    data.push_str(&format!(r#"<c r="{cell_ref}"><v>{n}</v></c>"#))
+                }
+                Value::Number(n) => {
+                    data.push_str(&format!(r#"<c r="{cell_ref}"><v>{n}</v></c>"#))
+                }
+                Value::Empty => {}
+            }
+        }
+        data.push_str("</row>");
+    }
+    data.push_str("</sheetData></worksheet>");
+
+    let zip_err = |e: zip::result::ZipError| NigelError::Other(format!("XLSX write failed: {e}"));
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    for (name, body) in [
+        ("[Content_Types].xml", CONTENT_TYPES),
+        ("_rels/.rels", ROOT_RELS),
+        ("xl/workbook.xml", workbook.as_str()),
+        ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS),
+        ("xl/worksheets/sheet1.xml", data.as_str()),
+    ] {
+        zip.start_file(name, SimpleFileOptions::default())
+            .map_err(zip_err)?;
+        zip.write_all(body.as_bytes())?;
+    }
+    zip.finish().map_err(zip_err)?;
+    Ok(())
+}
+
+/// Spreadsheet column letters: 0 → A, 25 → Z, 26 → AA.
+#[cfg(feature = "xlsx")]
+fn column_name(mut idx: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (idx % 26) as u8);
+        if idx < 26 {
+            break;
+        }
+        idx = idx / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Escape XML text, dropping control characters XML 1.0 can't represent.
+#[cfg(feature = "xlsx")]
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(ch),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::ForeignAmount;
+
+    fn rows() -> Vec<RegisterRow> {
+        vec![
+            RegisterRow {
+                id: 7,
+                date: "2025-03-01".into(),
+                description: "ADOBE, INC \"CC\"".into(),
+                amount: -54.99,
+                foreign: None,
+                category: Some("Software & Subscriptions".into()),
+                category_id: Some(1),
+                vendor: Some("Adobe".into()),
+                account_name: "Checking".into(),
+                is_flagged: false,
+                memo: None,
+                tags: Vec::new(),
+                client: None,
+            },
+            RegisterRow {
+                id: 8,
+                date: "2025-03-02".into(),
+                description: "HOTEL <PARIS>".into(),
+                amount: -210.0,
+                foreign: Some(ForeignAmount {
+                    currency: "EUR".into(),
+                    amount: -195.5,
+                }),
+                category: None,
+                category_id: None,
+                vendor: None,
+                account_name: "Visa".into(),
+                is_flagged: true,
+                memo: Some("Client dinner, see receipt".into()),
+                tags: vec!["client-acme".into(), "travel".into()],
+                client: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_register_csv_keeps_order_and_quotes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("exports").join("register.csv");
+        write(&register_sheet(&rows()), &path, TabularFormat::Csv).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            lines[0],
+            "ID,Date,Description,Amount,Currency,Original Amount,Category,Vendor,Account,Flagged,Memo,Tags"
+        );
+        assert_eq!(
+            lines[1],
+            "7,2025-03-01,\"ADOBE, INC \"\"CC\"\"\",-54.99,,,Software & Subscriptions,Adobe,Checking,,,"
+        );
+        assert!(lines[2].ends_with(
+            "EUR,-195.50,,,Visa,yes,\"Client dinner, see receipt\",#client-acme #travel"
+        ));
+    }
+
+    #[cfg(all(feature = "xlsx", feature = "gusto"))]
+    #[test]
+    fn test_register_xlsx_round_trips() {
+        use calamine::{Data, Reader};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("register.xlsx");
+        write(&register_sheet(&rows()), &path, TabularFormat::Xlsx).unwrap();
+
+        let mut workbook = calamine::open_workbook_auto(&path).unwrap();
+        let range = workbook.worksheet_range("Register").unwrap();
+        let grid: Vec<&[Data]> = range.rows().collect();
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid[0][2], Data::String("Description".into()));
+        assert_eq!(grid[1][3], Data::Float(-54.99));
+        assert_eq!(grid[2][2], Data::String("HOTEL <PARIS>".into()));
+        assert_eq!(grid[2][9], Data::String("yes".into()));
+        assert_eq!(
+            grid[2][10],
+            Data::String("Client dinner, see receipt".into())
+        );
+        assert_eq!(grid[2][11], Data::String("#client-acme #travel".into()));
+    }
+
+    #[cfg(feature = "xlsx")]
+    #[test]
+    fn test_column_name() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(27), "AB");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+}
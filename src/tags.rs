@@ -0,0 +1,138 @@
+use rusqlite::Connection;
+
+use crate::error::{NigelError, Result};
+
+/// Canonical form of a tag: leading `#` dropped, lowercased. Tags may only
+/// contain letters, digits, `-` and `_`.
+pub fn normalize_tag(raw: &str) -> Result<String> {
+    let name = raw.trim().trim_start_matches('#').to_lowercase();
+    if name.is_empty() {
+        return Err(NigelError::Other(format!("Invalid tag: '{raw}'")));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(NigelError::Other(format!(
+            "Invalid tag: '{raw}' (use letters, digits, '-' or '_')"
+        )));
+    }
+    Ok(name)
+}
+
+/// Parse a comma- or space-separated tag list, e.g. `#client-acme, travel`.
+/// Duplicates are dropped; the result is sorted.
+pub fn parse_tags(input: &str) -> Result<Vec<String>> {
+    let mut tags = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(normalize_tag)
+        .collect::<Result<Vec<_>>>()?;
+    tags.sort();
+    tags.dedup();
+    Ok(tags)
+}
+
+/// SQL for a transaction's tags as one comma-joined column; `t` is the
+/// transactions alias. Read it back with [`from_concat`].
+pub const TAGS_CONCAT_SQL: &str = "(SELECT GROUP_CONCAT(g.name) FROM transaction_tags tt \
+     JOIN tags g ON tt.tag_id = g.id WHERE tt.transaction_id = t.id)";
+
+/// Split a [`TAGS_CONCAT_SQL`] column into sorted tags.
+pub fn from_concat(joined: Option<String>) -> Vec<String> {
+    let mut tags: Vec<String> = joined
+        .map(|s| s.split(',').map(String::from).collect())
+        .unwrap_or_default();
+    tags.sort();
+    tags
+}
+
+/// Display form: `#client-acme #travel`.
+pub fn label(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| format!("#{t}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Replace a transaction's tags. Tags no longer used by any transaction are
+/// removed.
+pub fn set_transaction_tags(conn: &Connection, transaction_id: i64, tags: &[String]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM transaction_tags WHERE transaction_id = ?1",
+        [transaction_id],
+    )?;
+    for tag in tags {
+        tx.execute("INSERT OR IGNORE INTO tags (name) VALUES (?1)", [tag])?;
+        tx.execute(
+            "INSERT OR IGNORE INTO transaction_tags (transaction_id, tag_id) \
+             SELECT ?1, id FROM tags WHERE name = ?2",
+            rusqlite::params![transaction_id, tag],
+        )?;
+    }
+    tx.execute(
+        "DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM transaction_tags)",
+        [],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+    use crate::reviewer::get_transaction_by_id;
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(
+            parse_tags("#Client-Acme, conference2025 #client-acme").unwrap(),
+            vec!["client-acme", "conference2025"]
+        );
+        assert!(parse_tags("").unwrap().is_empty());
+        assert!(parse_tags("bad!tag").is_err());
+        assert!(parse_tags("#").is_err());
+    }
+
+    #[test]
+    fn test_set_transaction_tags_prunes_unused() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO transactions (account_id, date, description, amount) \
+             VALUES (1, '2025-01-15', 'HOTEL', -200.0)",
+            [],
+        )
+        .unwrap();
+
+        let tags = vec!["client-acme".to_string(), "travel".to_string()];
+        set_transaction_tags(&conn, 1, &tags).unwrap();
+        assert_eq!(get_transaction_by_id(&conn, 1).unwrap().tags, tags);
+
+        set_transaction_tags(&conn, 1, &["travel".to_string()]).unwrap();
+        assert_eq!(
+            get_transaction_by_id(&conn, 1).unwrap().tags,
+            vec!["travel"]
+        );
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM tags", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // Deleting the transaction drops its tag links
+        conn.execute("DELETE FROM transactions WHERE id = 1", [])
+            .unwrap();
+        let links: i64 = conn
+            .query_row("SELECT COUNT(*) FROM transaction_tags", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(links, 0);
+    }
+}
@@ -0,0 +1,185 @@
+use rusqlite::Connection;
+use serde::Deserialize;
+
+use crate::db::set_metadata;
+use crate::error::{NigelError, Result};
+
+/// A starting chart of accounts for one kind of business, as embedded TOML:
+/// a `name`, a `description`, and `[[categories]]` with their tax and form
+/// lines.
+pub struct Template {
+    pub key: &'static str,
+    pub source: &'static str,
+}
+
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        key: "s-corp",
+        source: include_str!("s-corp.toml"),
+    },
+    Template {
+        key: "schedule-c",
+        source: include_str!("schedule-c.toml"),
+    },
+    Template {
+        key: "partnership",
+        source: include_str!("partnership.toml"),
+    },
+    Template {
+        key: "nonprofit",
+        source: include_str!("nonprofit.toml"),
+    },
+];
+
+/// Seeded when no template is chosen.
+pub const DEFAULT_TEMPLATE: &str = "s-corp";
+
+#[derive(Debug, Deserialize)]
+pub struct ChartTemplate {
+    pub categories: Vec<TemplateCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TemplateCategory {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub category_type: String,
+    pub tax_line: Option<String>,
+    pub form_line: Option<String>,
+    pub description: String,
+}
+
+impl Template {
+    /// The template's `name`, from its first line.
+    pub fn title(&self) -> &'static str {
+        self.header("name").unwrap_or(self.key)
+    }
+
+    /// The template's one-line `description`.
+    pub fn summary(&self) -> &'static str {
+        self.header("description").unwrap_or_default()
+    }
+
+    fn header(&self, field: &str) -> Option<&'static str> {
+        self.source
+            .lines()
+            .take_while(|l| !l.starts_with("[["))
+            .find_map(|l| l.strip_prefix(field)?.trim_start().strip_prefix('='))
+            .map(|v| v.trim().trim_matches('"'))
+    }
+
+    pub fn parse(&self) -> Result<ChartTemplate> {
+        toml::from_str(self.source)
+            .map_err(|e| NigelError::Other(format!("Template {} is invalid: {e}", self.key)))
+    }
+}
+
+/// Look up a template by key, listing the valid keys when it isn't one.
+pub fn find(key: &str) -> Result<&'static Template> {
+    TEMPLATES.iter().find(|t| t.key == key).ok_or_else(|| {
+        let keys: Vec<&str> = TEMPLATES.iter().map(|t| t.key).collect();
+        NigelError::Other(format!(
+            "Unknown template \"{key}\". Choose one of: {}",
+            keys.join(", ")
+        ))
+    })
+}
+
+/// Insert a template's categories and remember which one the book started
+/// from. Callers only do this for a book with no categories yet.
+pub fn seed(conn: &Connection, key: &str) -> Result<()> {
+    let template = find(key)?.parse()?;
+    for cat in &template.categories {
+        conn.execute(
+            "INSERT INTO categories (name, category_type, tax_line, form_line, description) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                cat.name,
+                cat.category_type,
+                cat.tax_line,
+                cat.form_line,
+                cat.description
+            ],
+        )?;
+    }
+    set_metadata(conn, "category_template", key)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::categories::CATEGORY_TYPES;
+    use crate::db::{get_connection, get_metadata, init_db_with_template};
+
+    fn test_db(template: &str) -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db_with_template(&conn, template).unwrap();
+        (dir, conn)
+    }
+
+    #[test]
+    fn every_template_parses_with_valid_types_and_unique_names() {
+        for template in TEMPLATES {
+            let chart = template.parse().unwrap();
+            assert_ne!(template.title(), template.key);
+            assert!(!template.summary().is_empty());
+            let mut names: Vec<&str> = chart.categories.iter().map(|c| c.name.as_str()).collect();
+            for cat in &chart.categories {
+                assert!(
+                    CATEGORY_TYPES.contains(&cat.category_type.as_str()),
+                    "{}: {} has type {}",
+                    template.key,
+                    cat.name,
+                    cat.category_type
+                );
+            }
+            names.sort();
+            names.dedup();
+            assert_eq!(names.len(), chart.categories.len(), "{}", template.key);
+            // Imports fall back to it
+            assert!(names.contains(&"Uncategorized"), "{}", template.key);
+        }
+    }
+
+    #[test]
+    fn seeding_a_template_records_it_and_skips_default_categories() {
+        let (_dir, conn) = test_db("nonprofit");
+        assert_eq!(
+            get_metadata(&conn, "category_template").as_deref(),
+            Some("nonprofit")
+        );
+        let form_line: String = conn
+            .query_row(
+                "SELECT form_line FROM categories WHERE name = 'Contributions & Grants'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(form_line, "990-VIII-1f");
+        // Migrations don't add the S-corp lines over the template's own
+        let interest: (i64, String) = conn
+            .query_row(
+                "SELECT count(*), MAX(tax_line) FROM categories WHERE name = 'Interest Expense'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(interest, (1, "Part IX 20".to_string()));
+        let client: i64 = conn
+            .query_row(
+                "SELECT count(*) FROM categories WHERE name = 'Client Services'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(client, 0);
+    }
+
+    #[test]
+    fn unknown_template_lists_choices() {
+        let err = find("c-corp").map(|t| t.key).unwrap_err().to_string();
+        assert!(err.contains("s-corp, schedule-c, partnership, nonprofit"));
+    }
+}
@@ -0,0 +1,190 @@
+use rusqlite::Connection;
+
+use crate::archive;
+use crate::error::{NigelError, Result};
+
+/// A transaction waiting in the trash.
+pub struct TrashedTransaction {
+    pub id: i64,
+    pub date: String,
+    pub description: String,
+    pub amount: f64,
+    pub account_name: String,
+    pub category: Option<String>,
+    /// When it was trashed, as SQLite `datetime('now')` (UTC).
+    pub deleted_at: String,
+}
+
+/// Move transactions to the trash. Trashed rows stay in the book, so a
+/// re-import still sees them as duplicates, but every report, count, and
+/// review queue leaves them out. Returns how many were newly trashed.
+pub fn trash(conn: &Connection, ids: &[i64]) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut trashed = 0;
+    for &id in ids {
+        archive::ensure_not_archived(&tx, id)?;
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS (SELECT 1 FROM transactions WHERE id = ?1)",
+            [id],
+            |r| r.get(0),
+        )?;
+        if !exists {
+            return Err(NigelError::Other(format!("Transaction #{id} not found")));
+        }
+        trashed += tx.execute(
+            "UPDATE transactions SET deleted_at = datetime('now') \
+             WHERE id = ?1 AND deleted_at IS NULL",
+            [id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(trashed)
+}
+
+/// Take transactions back out of the trash. Errors on an ID that isn't in
+/// it, before restoring any.
+pub fn restore(conn: &Connection, ids: &[i64]) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut restored = 0;
+    for &id in ids {
+        let n = tx.execute(
+            "UPDATE transactions SET deleted_at = NULL \
+             WHERE id = ?1 AND deleted_at IS NOT NULL",
+            [id],
+        )?;
+        if n == 0 {
+            return Err(NigelError::Other(format!(
+                "Transaction #{id} is not in the trash"
+            )));
+        }
+        restored += n;
+    }
+    tx.commit()?;
+    Ok(restored)
+}
+
+/// Everything in the trash, most recently trashed first.
+pub fn list(conn: &Connection) -> Result<Vec<TrashedTransaction>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.date, t.description, t.amount, a.name, c.name, t.deleted_at \
+         FROM transactions t JOIN accounts a ON a.id = t.account_id \
+         LEFT JOIN categories c ON c.id = t.category_id \
+         WHERE t.deleted_at IS NOT NULL \
+         ORDER BY t.deleted_at DESC, t.id DESC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(TrashedTransaction {
+                id: row.get(0)?,
+                date: row.get(1)?,
+                description: row.get(2)?,
+                amount: row.get(3)?,
+                account_name: row.get(4)?,
+                category: row.get(5)?,
+                deleted_at: row.get(6)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Permanently delete trashed transactions — all of them, or with
+/// `older_than_days` only those trashed at least that many days ago. Their
+/// tag links and reimbursable records go with them (foreign keys cascade);
+/// tags no longer used anywhere are removed.
+pub fn empty(conn: &Connection, older_than_days: Option<i64>) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let deleted = tx.execute(
+        "DELETE FROM transactions WHERE deleted_at IS NOT NULL \
+         AND (?1 IS NULL OR deleted_at <= datetime('now', '-' || ?1 || ' days'))",
+        [older_than_days],
+    )?;
+    if deleted > 0 {
+        tx.execute(
+            "DELETE FROM tags WHERE id NOT IN (SELECT tag_id FROM transaction_tags)",
+            [],
+        )?;
+    }
+    tx.commit()?;
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn test_db() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        for (date, description, amount) in [
+            ("2025-03-02", "COFFEE", -4.5),
+            ("2025-03-03", "DUPLICATE COFFEE", -4.5),
+            ("2025-03-15", "STRIPE PAYOUT", 900.0),
+        ] {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, amount) \
+                 VALUES (1, ?1, ?2, ?3)",
+                rusqlite::params![date, description, amount],
+            )
+            .unwrap();
+        }
+        (dir, conn)
+    }
+
+    #[test]
+    fn trashed_rows_leave_reports_until_restored() {
+        let (_dir, conn) = test_db();
+        crate::tags::set_transaction_tags(&conn, 2, &["dupe".into()]).unwrap();
+        assert_eq!(trash(&conn, &[2]).unwrap(), 1);
+        // Trashing again changes nothing
+        assert_eq!(trash(&conn, &[2]).unwrap(), 0);
+        let register =
+            crate::reports::get_register(&conn, None, None, None, None, None, None).unwrap();
+        assert_eq!(register.rows.len(), 2);
+        assert_eq!(register.total, 895.5);
+
+        let trashed = list(&conn).unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].description, "DUPLICATE COFFEE");
+        assert!(restore(&conn, &[1]).is_err());
+        assert!(trash(&conn, &[99]).is_err());
+
+        assert_eq!(restore(&conn, &[2]).unwrap(), 1);
+        assert!(list(&conn).unwrap().is_empty());
+        let register =
+            crate::reports::get_register(&conn, None, None, None, None, None, None).unwrap();
+        assert_eq!(register.rows.len(), 3);
+    }
+
+    #[test]
+    fn empty_honors_retention_and_prunes_tags() {
+        let (_dir, conn) = test_db();
+        crate::tags::set_transaction_tags(&conn, 2, &["dupe".into()]).unwrap();
+        trash(&conn, &[1, 2]).unwrap();
+        conn.execute(
+            "UPDATE transactions SET deleted_at = datetime('now', '-40 days') WHERE id = 2",
+            [],
+        )
+        .unwrap();
+
+        assert_eq!(empty(&conn, Some(30)).unwrap(), 1);
+        let tags: i64 = conn
+            .query_row("SELECT COUNT(*) FROM tags", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(tags, 0);
+        assert_eq!(list(&conn).unwrap()[0].id, 1);
+
+        assert_eq!(empty(&conn, None).unwrap(), 1);
+        let left: i64 = conn
+            .query_row("SELECT COUNT(*) FROM transactions", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(left, 1);
+    }
+}
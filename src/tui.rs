@@ -4,6 +4,8 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::error::Result;
 use crate::fmt::money;
@@ -14,6 +16,12 @@ pub const HEADER_STYLE: Style = Style::new().fg(Color::Yellow).add_modifier(Modi
 
 pub const FOOTER_STYLE: Style = Style::new().fg(Color::DarkGray);
 
+/// Appended to screen headers in read-only mode.
+pub const READ_ONLY_TAG: &str = "  [READ-ONLY]";
+
+/// Status shown when an edit key is pressed in read-only mode.
+pub const READ_ONLY_STATUS: &str = "Read-only mode: editing is disabled";
+
 pub const GREEN: Color = Color::Rgb(80, 220, 100);
 pub const AMOUNT_POS_STYLE: Style = Style::new().fg(GREEN);
 pub const AMOUNT_NEG_STYLE: Style = Style::new().fg(Color::Red);
@@ -33,14 +41,95 @@ pub fn money_span(amount: f64) -> Span<'static> {
     Span::styled(money(amount.abs()), style)
 }
 
-/// Wrap text to a given width. Returns (wrapped_string, line_count).
+/// Round chart axis values (top and mid) covering `max_val`.
+pub fn y_axis_ticks(max_val: f64) -> (f64, f64) {
+    // Round steps: 1k, 2.5k, 5k, 10k, 25k, 50k, 100k, 250k, ...
+    let steps = [
+        1000.0, 2500.0, 5000.0, 10000.0, 25000.0, 50000.0, 100000.0, 250000.0, 500000.0, 1000000.0,
+        2500000.0, 5000000.0, 10000000.0,
+    ];
+    let top = steps
+        .iter()
+        .copied()
+        .find(|&s| s >= max_val)
+        .unwrap_or(max_val);
+    let mid = top / 2.0;
+    (top, mid)
+}
+
+/// Terminal display width of a string: CJK and most emoji take two columns,
+/// combining marks take none.
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Truncate to at most `max` display columns, ending with an ellipsis when
+/// shortened. Never splits a grapheme cluster (accented letters, ZWJ emoji).
+pub fn truncate(text: &str, max: usize) -> String {
+    if display_width(text) <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for g in text.graphemes(true) {
+        let w = display_width(g);
+        if used + w > max - 1 {
+            break;
+        }
+        out.push_str(g);
+        used += w;
+    }
+    out.push('\u{2026}');
+    out
+}
+
+/// Left-align text in a field `width` display columns wide. `format!`
+/// padding counts chars, which misaligns double-width text.
+pub fn pad_right(text: &str, width: usize) -> String {
+    let pad = width.saturating_sub(display_width(text));
+    format!("{text}{}", " ".repeat(pad))
+}
+
+/// Split text into pieces no wider than `width` columns on grapheme boundaries.
+fn split_to_width(text: &str, width: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut used = 0;
+    for g in text.graphemes(true) {
+        let w = display_width(g);
+        if used + w > width && !current.is_empty() {
+            pieces.push(std::mem::take(&mut current));
+            used = 0;
+        }
+        current.push_str(g);
+        used += w;
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// Wrap text to a given width in display columns. Returns (wrapped_string, line_count).
+/// Words wider than a line are broken between grapheme clusters.
 pub fn wrap_text(text: &str, width: usize) -> (String, u16) {
     if width == 0 {
         return (text.to_string(), 1);
     }
-    let wrapped = textwrap::fill(text, width);
-    let lines = wrapped.lines().count().max(1) as u16;
-    (wrapped, lines)
+    let options = textwrap::Options::new(width).break_words(false);
+    let mut lines = Vec::new();
+    for line in textwrap::wrap(text, options) {
+        if display_width(&line) <= width {
+            lines.push(line.into_owned());
+        } else {
+            lines.extend(split_to_width(&line, width));
+        }
+    }
+    let count = lines.len().max(1) as u16;
+    (lines.join("\n"), count)
 }
 
 pub fn render_version(frame: &mut Frame, area: Rect) {
@@ -58,11 +147,14 @@ pub fn render_version(frame: &mut Frame, area: Rect) {
 pub enum ReportViewAction {
     Continue,
     Close,
-    /// Request data reload (e.g. after date navigation). The dashboard intercepts
-    /// this to rebuild the view with new date params. In standalone CLI mode
-    /// (`run_report_view`), Reload is treated as Continue — the title updates
-    /// but data is not rebuilt since there is no outer controller to do so.
+    /// Request data reload (e.g. after date navigation or the comparison
+    /// toggle). The dashboard intercepts this to rebuild the view with new
+    /// date params; standalone CLI mode (`run_report_view`) calls its
+    /// `rebuild` callback.
     Reload,
+    /// Open the register on a report line's transactions (Enter), described
+    /// by the string. Esc in the register returns to the report.
+    Browse(crate::reports::RegisterQuery, String),
 }
 
 pub trait ReportView {
@@ -73,11 +165,46 @@ pub trait ReportView {
     fn date_params(&self) -> (Option<i32>, Option<String>) {
         (None, None)
     }
+    /// Whether the view is showing a period comparison (P&L `c` key).
+    fn compare(&self) -> bool {
+        false
+    }
+    /// Whether the view is showing a chart instead of its table (Cash Flow
+    /// `g` key).
+    fn chart(&self) -> bool {
+        false
+    }
+    /// Rows per week, month, or quarter (Cash Flow `w` key).
+    fn granularity(&self) -> crate::reports::Granularity {
+        crate::reports::Granularity::Month
+    }
+    /// Points per day or week (Balance History `w` key).
+    fn interval(&self) -> crate::reports::BalanceInterval {
+        crate::reports::BalanceInterval::Day
+    }
 }
 
+/// Rebuilds a report view from its current state (period, toggles).
+pub type ReportRebuild<'a> = &'a dyn Fn(&dyn ReportView) -> Result<Box<dyn ReportView>>;
+
+/// Runs the register over the terminal until it is closed; true when
+/// anything was edited there.
+pub type ReportBrowse<'a> = &'a dyn Fn(
+    &mut ratatui::DefaultTerminal,
+    crate::reports::RegisterQuery,
+    String,
+) -> Result<bool>;
+
 /// Run an interactive ratatui report view. Sets up the terminal, event loop,
-/// and panic hook, then restores the terminal on exit.
-pub fn run_report_view(view: &mut dyn ReportView) -> Result<()> {
+/// and panic hook, then restores the terminal on exit. On Reload the view is
+/// replaced by `rebuild(view)`; if that fails the current view is kept. On
+/// Browse the register runs in its place through `browse`, and the view is
+/// rebuilt if edits there changed its figures.
+pub fn run_report_view(
+    view: &mut Box<dyn ReportView>,
+    rebuild: ReportRebuild,
+    browse: ReportBrowse,
+) -> Result<()> {
     let hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         ratatui::restore();
@@ -102,7 +229,23 @@ pub fn run_report_view(view: &mut dyn ReportView) -> Result<()> {
                 }
                 match view.handle_key(key.code) {
                     ReportViewAction::Close => break Ok(()),
-                    ReportViewAction::Continue | ReportViewAction::Reload => {}
+                    ReportViewAction::Continue => {}
+                    ReportViewAction::Reload => {
+                        if let Ok(rebuilt) = rebuild(view.as_ref()) {
+                            *view = rebuilt;
+                        }
+                    }
+                    ReportViewAction::Browse(query, desc) => {
+                        match browse(&mut terminal, query, desc) {
+                            Err(e) => break Err(e),
+                            Ok(true) => {
+                                if let Ok(rebuilt) = rebuild(view.as_ref()) {
+                                    *view = rebuilt;
+                                }
+                            }
+                            Ok(false) => {}
+                        }
+                    }
                 }
             }
             _ => {}
@@ -123,4 +266,48 @@ mod tests {
         assert_eq!(VERSION, concat!("v", env!("CARGO_PKG_VERSION")));
         assert!(VERSION.starts_with("v"));
     }
+
+    #[test]
+    fn display_width_counts_wide_chars() {
+        assert_eq!(display_width("Adobe"), 5);
+        assert_eq!(display_width("東京タワー"), 10);
+        assert_eq!(display_width("Cafe\u{301}"), 4); // combining accent
+    }
+
+    #[test]
+    fn wrap_text_respects_display_width() {
+        let (wrapped, lines) = wrap_text("株式会社東京商事 月額利用料 お支払い", 10);
+        assert!(lines > 1);
+        for line in wrapped.lines() {
+            assert!(display_width(line) <= 10, "{line:?} too wide");
+        }
+        assert_eq!(wrapped.lines().count(), lines as usize);
+    }
+
+    #[test]
+    fn wrap_text_keeps_emoji_clusters_whole() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let text = family.repeat(6);
+        let (wrapped, _) = wrap_text(&text, 5);
+        for line in wrapped.lines() {
+            assert_eq!(line.matches(family).count() * family.len(), line.len());
+        }
+    }
+
+    #[test]
+    fn truncate_by_width_and_grapheme() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("Starbucks Coffee", 10), "Starbucks\u{2026}");
+        // Each kanji is two columns: 4 fit before the ellipsis in 9 columns
+        assert_eq!(truncate("東京タワー売店", 9), "東京タワ\u{2026}");
+        assert_eq!(truncate("Cafe\u{301} Rouge", 5), "Cafe\u{301}\u{2026}");
+        assert!(display_width(&truncate("🍎🍎🍎🍎", 5)) <= 5);
+    }
+
+    #[test]
+    fn pad_right_pads_by_width() {
+        assert_eq!(pad_right("東京", 6), "東京  ");
+        assert_eq!(pad_right("ab", 4), "ab  ");
+        assert_eq!(pad_right("toolong", 3), "toolong");
+    }
 }
@@ -0,0 +1,255 @@
+use std::path::Path;
+
+use rusqlite::{Connection, OptionalExtension};
+use zeroize::Zeroizing;
+
+use crate::db::{is_encrypted, validate_password};
+use crate::error::{NigelError, Result};
+
+/// Full account and routing numbers for one account. Only produced by
+/// `reveal()`, after the database password has been re-entered; the
+/// strings are wiped from memory when dropped.
+pub struct RevealedNumbers {
+    pub account_number: Option<Zeroizing<String>>,
+    pub routing_number: Option<Zeroizing<String>>,
+}
+
+/// Show only the last four digits: `****1234`.
+pub fn mask(number: &str) -> String {
+    let digits: Vec<char> = number.chars().filter(|c| c.is_ascii_digit()).collect();
+    let tail: String = digits[digits.len().saturating_sub(4)..].iter().collect();
+    format!("****{tail}")
+}
+
+/// Strip spaces and dashes and check the result is 4–17 digits.
+pub fn normalize_account_number(raw: &str) -> Result<String> {
+    let digits: String = raw.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+    if !(4..=17).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(NigelError::Other(
+            "Account number must be 4 to 17 digits".into(),
+        ));
+    }
+    Ok(digits)
+}
+
+/// Check an ABA routing number: nine digits with a valid checksum.
+pub fn normalize_routing_number(raw: &str) -> Result<String> {
+    let digits: String = raw.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+    let d: Vec<u32> = digits.chars().filter_map(|c| c.to_digit(10)).collect();
+    if digits.len() != 9 || d.len() != 9 {
+        return Err(NigelError::Other("Routing number must be 9 digits".into()));
+    }
+    let sum = 3 * (d[0] + d[3] + d[6]) + 7 * (d[1] + d[4] + d[7]) + (d[2] + d[5] + d[8]);
+    if !sum.is_multiple_of(10) {
+        return Err(NigelError::Other(format!(
+            "Routing number {} fails the ABA checksum",
+            mask(&digits)
+        )));
+    }
+    Ok(digits)
+}
+
+/// Account numbers are only kept in password-protected (SQLCipher)
+/// databases, so they are encrypted at rest along with everything else.
+pub fn require_encrypted(db_path: &Path) -> Result<()> {
+    if !is_encrypted(db_path)? {
+        return Err(NigelError::Other(
+            "Account numbers can only be stored in a password-protected database. Run `nigel password set` first.".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Store the full account and/or routing number. Setting an account number
+/// also updates `last_four`.
+pub fn set_numbers(
+    conn: &Connection,
+    account_id: i64,
+    account_number: Option<&str>,
+    routing_number: Option<&str>,
+) -> Result<()> {
+    if let Some(raw) = account_number {
+        let number = Zeroizing::new(normalize_account_number(raw)?);
+        let last_four = &number[number.len() - 4..];
+        conn.execute(
+            "UPDATE accounts SET account_number = ?1, last_four = ?2 WHERE id = ?3",
+            rusqlite::params![number.as_str(), last_four, account_id],
+        )?;
+    }
+    if let Some(raw) = routing_number {
+        let routing = normalize_routing_number(raw)?;
+        conn.execute(
+            "UPDATE accounts SET routing_number = ?1 WHERE id = ?2",
+            rusqlite::params![routing, account_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// Remove stored numbers. `last_four` is kept for display.
+pub fn clear_numbers(conn: &Connection, account_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE accounts SET account_number = NULL, routing_number = NULL WHERE id = ?1",
+        [account_id],
+    )?;
+    Ok(())
+}
+
+/// Whether any account has a stored number. Decrypting the database is
+/// refused while this is true.
+pub fn has_stored_numbers(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM accounts \
+         WHERE account_number IS NOT NULL OR routing_number IS NOT NULL)",
+        [],
+        |row| row.get(0),
+    )
+    // Databases from before the vault columns existed have none
+    .unwrap_or(false)
+}
+
+/// Masked forms of the stored numbers, safe to display anywhere.
+pub fn masked_numbers(
+    conn: &Connection,
+    account_id: i64,
+) -> Result<(Option<String>, Option<String>)> {
+    let (account, routing) = stored_numbers(conn, account_id)?;
+    Ok((
+        account.as_deref().map(|n| mask(n)),
+        routing.as_deref().map(|n| mask(n)),
+    ))
+}
+
+/// Return the full numbers after confirming `password` unlocks the database.
+pub fn reveal(
+    conn: &Connection,
+    db_path: &Path,
+    account_id: i64,
+    password: &str,
+) -> Result<RevealedNumbers> {
+    require_encrypted(db_path)?;
+    if !validate_password(db_path, password)? {
+        return Err(NigelError::Other("Wrong password.".into()));
+    }
+    let (account_number, routing_number) = stored_numbers(conn, account_id)?;
+    Ok(RevealedNumbers {
+        account_number,
+        routing_number,
+    })
+}
+
+#[allow(clippy::type_complexity)]
+fn stored_numbers(
+    conn: &Connection,
+    account_id: i64,
+) -> Result<(Option<Zeroizing<String>>, Option<Zeroizing<String>>)> {
+    let row: Option<(Option<String>, Option<String>)> = conn
+        .query_row(
+            "SELECT account_number, routing_number FROM accounts WHERE id = ?1",
+            [account_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let (account, routing) =
+        row.ok_or_else(|| NigelError::Other(format!("Account {account_id} not found")))?;
+    Ok((account.map(Zeroizing::new), routing.map(Zeroizing::new)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{init_db, open_connection};
+
+    const PASSWORD: &str = "hunter22";
+
+    fn encrypted_db() -> (tempfile::TempDir, std::path::PathBuf, Connection) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nigel.db");
+        let conn = open_connection(&path, Some(PASSWORD)).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        (dir, path, conn)
+    }
+
+    #[test]
+    fn test_mask_shows_last_four() {
+        assert_eq!(mask("000123456789"), "****6789");
+        assert_eq!(mask("12"), "****12");
+    }
+
+    #[test]
+    fn test_routing_number_checksum() {
+        assert_eq!(normalize_routing_number("026009593").unwrap(), "026009593");
+        assert_eq!(
+            normalize_routing_number("0260-0959-3").unwrap(),
+            "026009593"
+        );
+        assert!(normalize_routing_number("026009594").is_err());
+        assert!(normalize_routing_number("12345").is_err());
+    }
+
+    #[test]
+    fn test_account_number_validation() {
+        assert_eq!(
+            normalize_account_number("4350 1234 5678").unwrap(),
+            "435012345678"
+        );
+        assert!(normalize_account_number("123").is_err());
+        assert!(normalize_account_number("12ab5678").is_err());
+    }
+
+    #[test]
+    fn test_set_numbers_updates_last_four_and_masks() {
+        let (_dir, _path, conn) = encrypted_db();
+        set_numbers(&conn, 1, Some("4350-1234-5678"), Some("026009593")).unwrap();
+        let last_four: String = conn
+            .query_row("SELECT last_four FROM accounts WHERE id = 1", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(last_four, "5678");
+        let (account, routing) = masked_numbers(&conn, 1).unwrap();
+        assert_eq!(account.as_deref(), Some("****5678"));
+        assert_eq!(routing.as_deref(), Some("****9593"));
+    }
+
+    #[test]
+    fn test_reveal_requires_correct_password() {
+        let (_dir, path, conn) = encrypted_db();
+        set_numbers(&conn, 1, Some("435012345678"), None).unwrap();
+        assert!(reveal(&conn, &path, 1, "wrong").is_err());
+        let revealed = reveal(&conn, &path, 1, PASSWORD).unwrap();
+        assert_eq!(
+            revealed.account_number.as_deref().map(|s| s.as_str()),
+            Some("435012345678")
+        );
+        assert!(revealed.routing_number.is_none());
+    }
+
+    #[test]
+    fn test_unencrypted_database_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nigel.db");
+        let conn = open_connection(&path, None).unwrap();
+        init_db(&conn).unwrap();
+        assert!(require_encrypted(&path).is_err());
+    }
+
+    #[test]
+    fn test_clear_numbers_keeps_last_four() {
+        let (_dir, _path, conn) = encrypted_db();
+        set_numbers(&conn, 1, Some("435012345678"), Some("026009593")).unwrap();
+        clear_numbers(&conn, 1).unwrap();
+        assert_eq!(masked_numbers(&conn, 1).unwrap(), (None, None));
+        let last_four: String = conn
+            .query_row("SELECT last_four FROM accounts WHERE id = 1", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(last_four, "5678");
+    }
+}
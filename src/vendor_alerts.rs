@@ -0,0 +1,274 @@
+use chrono::{Months, NaiveDate};
+use rusqlite::Connection;
+
+use crate::archive;
+use crate::currency::HOME_AMOUNT_SQL;
+use crate::error::{NigelError, Result};
+use crate::fmt::money;
+
+/// Months of history a vendor's current spend is compared against.
+pub const AVERAGE_MONTHS: u32 = 6;
+
+/// A per-vendor spend alert. Either threshold may be unset, not both.
+pub struct VendorAlert {
+    pub id: i64,
+    pub vendor: String,
+    /// Warn when this calendar month's spend goes over this amount.
+    pub monthly_limit: Option<f64>,
+    /// Warn when this month's spend is more than this percent above the
+    /// vendor's average over the previous six months.
+    pub increase_pct: Option<f64>,
+}
+
+impl VendorAlert {
+    pub fn over_limit(&self, spend: &VendorSpend) -> bool {
+        self.monthly_limit
+            .is_some_and(|limit| spend.month_spend > limit)
+    }
+
+    pub fn over_increase(&self, spend: &VendorSpend) -> bool {
+        match (self.increase_pct, spend.increase_pct()) {
+            (Some(threshold), Some(pct)) => pct > threshold,
+            _ => false,
+        }
+    }
+}
+
+/// A vendor's spend this month against its recent average.
+pub struct VendorSpend {
+    pub month_spend: f64,
+    /// Average over the previous six months that had spend, if any did.
+    pub average: Option<f64>,
+}
+
+impl VendorSpend {
+    /// Percent this month is above the average, when there is one.
+    pub fn increase_pct(&self) -> Option<f64> {
+        self.average
+            .filter(|avg| *avg > 0.0)
+            .map(|avg| (self.month_spend - avg) / avg * 100.0)
+    }
+}
+
+pub struct VendorAlertHit {
+    pub alert: VendorAlert,
+    pub spend: VendorSpend,
+    pub over_limit: bool,
+    pub over_increase: bool,
+}
+
+impl VendorAlertHit {
+    pub fn message(&self) -> String {
+        let mut reasons = Vec::new();
+        if self.over_limit {
+            reasons.push(format!(
+                "over the {} alert",
+                money(self.alert.monthly_limit.unwrap_or(0.0))
+            ));
+        }
+        if self.over_increase {
+            reasons.push(format!(
+                "{:.0}% above its {AVERAGE_MONTHS}-month average of {}",
+                self.spend.increase_pct().unwrap_or(0.0),
+                money(self.spend.average.unwrap_or(0.0))
+            ));
+        }
+        format!(
+            "{}: {} spent this month, {}.",
+            self.alert.vendor,
+            money(self.spend.month_spend),
+            reasons.join(" and ")
+        )
+    }
+}
+
+pub fn add_alert(
+    conn: &Connection,
+    vendor: &str,
+    monthly_limit: Option<f64>,
+    increase_pct: Option<f64>,
+) -> Result<i64> {
+    let vendor = vendor.trim();
+    if vendor.is_empty() {
+        return Err(NigelError::Other("A vendor name is required".into()));
+    }
+    if monthly_limit.is_none() && increase_pct.is_none() {
+        return Err(NigelError::Other(
+            "Set --over (monthly amount), --increase (percent), or both".into(),
+        ));
+    }
+    for value in [monthly_limit, increase_pct].into_iter().flatten() {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(NigelError::Other(format!(
+                "Invalid threshold: {value} (must be greater than zero)"
+            )));
+        }
+    }
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM vendor_alerts WHERE vendor = ?1 COLLATE NOCASE)",
+        [vendor],
+        |r| r.get(0),
+    )?;
+    if exists {
+        return Err(NigelError::Other(format!(
+            "There is already an alert for '{vendor}'. Remove it first to change it."
+        )));
+    }
+    conn.execute(
+        "INSERT INTO vendor_alerts (vendor, monthly_limit, increase_pct) VALUES (?1, ?2, ?3)",
+        rusqlite::params![vendor, monthly_limit, increase_pct],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn list_alerts(conn: &Connection) -> Result<Vec<VendorAlert>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, vendor, monthly_limit, increase_pct FROM vendor_alerts \
+         ORDER BY vendor COLLATE NOCASE",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(VendorAlert {
+                id: row.get(0)?,
+                vendor: row.get(1)?,
+                monthly_limit: row.get(2)?,
+                increase_pct: row.get(3)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+pub fn remove_alert(conn: &Connection, id: i64) -> Result<()> {
+    let deleted = conn.execute("DELETE FROM vendor_alerts WHERE id = ?1", [id])?;
+    if deleted == 0 {
+        return Err(NigelError::Other(format!("No vendor alert with ID {id}")));
+    }
+    Ok(())
+}
+
+/// Spend with `vendor` this calendar month (through `today`) and its average
+/// over the previous six months. A transaction counts when its vendor is
+/// `vendor` or its description contains it, ignoring case; only outflows
+/// count, in the home currency.
+pub fn vendor_spend(conn: &Connection, vendor: &str, today: NaiveDate) -> Result<VendorSpend> {
+    let month_start = today.format("%Y-%m-01").to_string();
+    let this_month = today.format("%Y-%m").to_string();
+    let from = NaiveDate::parse_from_str(&month_start, "%Y-%m-%d")
+        .map(|d| d - Months::new(AVERAGE_MONTHS))
+        .unwrap_or(today)
+        .to_string();
+    let to = today.to_string();
+    let _archives = archive::scope(conn, Some(&from), Some(&to))?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT substr(t.date, 1, 7) as month, -SUM({HOME_AMOUNT_SQL}) \
+         FROM transactions t JOIN accounts a ON t.account_id = a.id \
+         WHERE t.deleted_at IS NULL AND t.amount < 0 AND t.date >= ?1 AND t.date <= ?2 \
+           AND (t.vendor = ?3 COLLATE NOCASE OR instr(upper(t.description), upper(?3)) > 0) \
+         GROUP BY month"
+    ))?;
+    let months: Vec<(String, f64)> = stmt
+        .query_map(rusqlite::params![from, to, vendor], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let month_spend = months
+        .iter()
+        .find(|(m, _)| *m == this_month)
+        .map(|(_, total)| *total)
+        .unwrap_or(0.0);
+    let prior: Vec<f64> = months
+        .iter()
+        .filter(|(m, _)| *m != this_month)
+        .map(|(_, total)| *total)
+        .collect();
+    let average = (!prior.is_empty()).then(|| prior.iter().sum::<f64>() / prior.len() as f64);
+    Ok(VendorSpend {
+        month_spend,
+        average,
+    })
+}
+
+/// Alerts whose vendor has gone over a threshold this month.
+pub fn check_alerts(conn: &Connection, today: NaiveDate) -> Result<Vec<VendorAlertHit>> {
+    let mut hits = Vec::new();
+    for alert in list_alerts(conn)? {
+        let spend = vendor_spend(conn, &alert.vendor, today)?;
+        let over_limit = alert.over_limit(&spend);
+        let over_increase = alert.over_increase(&spend);
+        if over_limit || over_increase {
+            hits.push(VendorAlertHit {
+                alert,
+                spend,
+                over_limit,
+                over_increase,
+            });
+        }
+    }
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{get_connection, init_db};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_alerts_on_limit_and_price_creep() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = get_connection(&dir.path().join("test.db")).unwrap();
+        init_db(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO accounts (name, account_type) VALUES ('Checking', 'checking')",
+            [],
+        )
+        .unwrap();
+        let add = |d: &str, desc: &str, vendor: Option<&str>, amount: f64| {
+            conn.execute(
+                "INSERT INTO transactions (account_id, date, description, vendor, amount) \
+                 VALUES (1, ?1, ?2, ?3, ?4)",
+                rusqlite::params![d, desc, vendor, amount],
+            )
+            .unwrap();
+        };
+        for month in ["2025-01", "2025-02", "2025-03"] {
+            add(&format!("{month}-05"), "ADOBE *CC", Some("Adobe"), -50.0);
+        }
+        add("2025-04-05", "ADOBE *CC", Some("Adobe"), -65.0);
+        // Refunds and older months don't count
+        add("2025-04-09", "ADOBE REFUND", Some("Adobe"), 10.0);
+        add("2024-09-05", "ADOBE *CC", Some("Adobe"), -500.0);
+        // Matched by description when no vendor is set
+        add("2025-04-10", "SLACK TECHNOLOGIES", None, -12.0);
+
+        assert!(add_alert(&conn, "Adobe", None, None).is_err());
+        assert!(add_alert(&conn, "Adobe", Some(-5.0), None).is_err());
+        let adobe = add_alert(&conn, "Adobe", None, Some(20.0)).unwrap();
+        assert!(add_alert(&conn, "ADOBE", Some(100.0), None).is_err());
+        add_alert(&conn, "slack", Some(10.0), None).unwrap();
+
+        let spend = vendor_spend(&conn, "Adobe", date("2025-04-20")).unwrap();
+        assert_eq!(spend.month_spend, 65.0);
+        assert_eq!(spend.average, Some(50.0));
+
+        let hits = check_alerts(&conn, date("2025-04-20")).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(
+            hits[0].message(),
+            "Adobe: $65.00 spent this month, 30% above its 6-month average of $50.00."
+        );
+        assert!(hits[1].over_limit && !hits[1].over_increase);
+
+        // Next month nothing has been charged yet
+        assert!(check_alerts(&conn, date("2025-05-02")).unwrap().is_empty());
+
+        remove_alert(&conn, adobe).unwrap();
+        assert!(remove_alert(&conn, adobe).is_err());
+        assert_eq!(list_alerts(&conn).unwrap().len(), 1);
+    }
+}
@@ -32,7 +32,8 @@ impl TestEnv {
         cmd
     }
 
-    /// Run `nigel init --data-dir <data_dir>` then `nigel demo`.
+    /// Run `nigel init --data-dir <data_dir>` then `nigel demo --in-place`, so
+    /// the sample data lands in the book at `data_dir`.
     fn init_and_demo(&self) {
         self.cmd()
             .args(["init", "--data-dir", &self.data_dir().to_string_lossy()])
@@ -41,7 +42,7 @@ impl TestEnv {
             .stdout(predicate::str::contains("Initialized"));
 
         self.cmd()
-            .arg("demo")
+            .args(["demo", "--in-place"])
             .assert()
             .success()
             .stdout(predicate::str::contains("Demo data loaded"));
@@ -64,7 +65,7 @@ fn demo_is_idempotent() {
 
     // Running demo again should succeed and report already loaded
     env.cmd()
-        .arg("demo")
+        .args(["demo", "--in-place"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Demo data already loaded"));
@@ -82,6 +83,34 @@ fn status_after_demo() {
     );
 }
 
+#[test]
+fn status_badge_prints_json() {
+    let env = TestEnv::new();
+
+    // Before init the badge still answers, as JSON
+    env.cmd()
+        .args(["status", "--badge"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"{"initialized":false"#));
+
+    env.init_and_demo();
+    let out = env
+        .cmd()
+        .args(["status", "--badge"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let badge: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(badge["initialized"], true);
+    assert_eq!(badge["locked"], false);
+    assert!(badge["transactions"].as_i64().unwrap() > 0);
+    assert!(badge["categorized_pct"].as_f64().unwrap() <= 100.0);
+    assert!(badge.get("backup_ok").is_some());
+}
+
 #[test]
 fn backup_to_custom_path() {
     let env = TestEnv::new();
@@ -120,6 +149,65 @@ fn backup_default_location() {
     assert!(!entries.is_empty(), "backups dir should contain a file");
 }
 
+#[test]
+fn backup_copies_to_a_destination_and_verifies() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let dropbox = env.home.path().join("Dropbox/books");
+    let dest = dropbox.to_string_lossy().to_string();
+    env.cmd()
+        .args(["backup", "--to", &dest])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Copied to"))
+        .stdout(predicate::str::contains("SHA-256:"));
+    let copies: Vec<_> = std::fs::read_dir(&dropbox)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(copies.len(), 2, "the copy and its .sha256: {copies:?}");
+
+    env.cmd()
+        .args(["--read-only", "backup", "verify", "--to", &dest])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "OK: matches the checksum recorded",
+        ));
+
+    env.cmd()
+        .args(["backup", "--to", &dest, "--encrypt"])
+        .env("NIGEL_BACKUP_PASSPHRASE", "correct horse")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "encrypted with your backup passphrase",
+        ));
+    let newest = std::fs::read_dir(&dropbox)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|x| x == "db"))
+        .max()
+        .unwrap();
+    let header = std::fs::read(&newest).unwrap();
+    assert!(!header.starts_with(b"SQLite format 3"));
+
+    std::fs::write(&newest, b"damaged").unwrap();
+    env.cmd()
+        .args(["backup", "verify", "--to", &dest])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Checksum mismatch"));
+    env.cmd()
+        .args(["backup", "verify"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No backup destination"));
+}
+
 #[test]
 fn restore_from_backup() {
     let env = TestEnv::new();
@@ -161,19 +249,78 @@ fn restore_from_backup() {
         "Post-backup account should not exist after restore"
     );
 
-    // Verify a safety backup was created
-    let backups_dir = env.data_dir().join("backups");
-    let entries: Vec<_> = std::fs::read_dir(&backups_dir)
+    // Verify a safety snapshot was created
+    let snapshots_dir = env.data_dir().join("snapshots");
+    let entries: Vec<_> = std::fs::read_dir(&snapshots_dir)
         .unwrap()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_name().to_string_lossy().contains("pre-restore"))
         .collect();
     assert!(
         !entries.is_empty(),
-        "pre-restore safety backup should exist"
+        "pre-restore safety snapshot should exist"
     );
 }
 
+#[test]
+fn restore_merge_brings_back_lost_rows_and_keeps_new_ones() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let backup_path = env.home.path().join("test-backup.db");
+    env.cmd()
+        .args(["backup", "--output", &backup_path.to_string_lossy()])
+        .assert()
+        .success();
+
+    // Lose the newest transactions, then keep working
+    env.cmd()
+        .args([
+            "query",
+            "--allow-writes",
+            "DELETE FROM transactions WHERE id IN (SELECT id FROM transactions ORDER BY id DESC LIMIT 3)",
+        ])
+        .assert()
+        .success();
+    env.cmd()
+        .args([
+            "accounts",
+            "add",
+            "Post-Backup Account",
+            "--type",
+            "checking",
+        ])
+        .assert()
+        .success();
+
+    let backup = backup_path.to_string_lossy().to_string();
+    env.cmd()
+        .args(["restore", &backup, "--merge", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("transactions: 3 missing"))
+        .stdout(predicate::str::contains("Dry run: nothing was saved."));
+
+    env.cmd()
+        .args(["restore", &backup, "--merge"])
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Safety backup saved to"))
+        .stdout(predicate::str::contains("Restored 3 missing rows"));
+
+    env.cmd()
+        .args(["accounts", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Post-Backup Account"));
+    env.cmd()
+        .args(["restore", &backup, "--merge"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to restore."));
+}
+
 #[test]
 fn restore_nonexistent_file_fails() {
     let env = TestEnv::new();
@@ -259,7 +406,103 @@ fn categorize_after_demo() {
         .arg("categorize")
         .assert()
         .success()
-        .stdout(predicate::str::contains("categorized"));
+        .stdout(predicate::str::contains("categorized"))
+        .stdout(predicate::str::contains("Pre-categorize snapshot saved"));
+
+    let snapshots: Vec<_> = std::fs::read_dir(env.data_dir().join("snapshots"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .starts_with("pre-categorize-")
+        })
+        .collect();
+    assert_eq!(snapshots.len(), 1);
+}
+
+#[test]
+fn review_round_trips_through_csv() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+    let path = env.home.path().join("flagged.csv");
+    let path_str = path.to_string_lossy().to_string();
+
+    env.cmd()
+        .args(["--read-only", "review", "export", &path_str])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("flagged transactions to"));
+
+    // Fill in the first row and misspell the second one's category
+    let mut rdr = csv::Reader::from_path(&path).unwrap();
+    let headers = rdr.headers().unwrap().clone();
+    let category = headers.iter().position(|h| h == "category").unwrap();
+    let mut records: Vec<csv::StringRecord> = rdr.records().map(|r| r.unwrap()).collect();
+    assert!(
+        records.len() >= 2,
+        "demo data should leave flagged transactions"
+    );
+    let fill = |record: &csv::StringRecord, value: &str| {
+        record
+            .iter()
+            .enumerate()
+            .map(|(i, f)| if i == category { value } else { f })
+            .collect::<csv::StringRecord>()
+    };
+    records[0] = fill(&records[0], "Software & Subscriptions");
+    records[1] = fill(&records[1], "Sofware");
+    let mut wtr = csv::Writer::from_path(&path).unwrap();
+    wtr.write_record(&headers).unwrap();
+    for record in &records {
+        wtr.write_record(record).unwrap();
+    }
+    wtr.flush().unwrap();
+
+    env.cmd()
+        .args(["review", "import", &path_str])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Reviewed 1 transactions"))
+        .stdout(predicate::str::contains(
+            "Row 3: unknown category 'Sofware'",
+        ));
+    env.cmd()
+        .args(["review", "import", &path_str])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 already reviewed"));
+}
+
+#[test]
+fn undo_snapshots_before_rolling_back() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let csv_path = env.home.path().join("undo-me.csv");
+    std::fs::write(
+        &csv_path,
+        "Date,Description,Amount,Running Bal.\n\
+         01/15/2025,UNDO ME PAYMENT,-100.00,900.00\n",
+    )
+    .unwrap();
+    env.cmd()
+        .args([
+            "import",
+            &csv_path.to_string_lossy(),
+            "--account",
+            "BofA Checking",
+        ])
+        .assert()
+        .success();
+
+    env.cmd()
+        .arg("undo")
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pre-undo snapshot saved"))
+        .stdout(predicate::str::contains("Rolled back import"));
 }
 
 #[test]
@@ -299,181 +542,2663 @@ fn rules_list_after_demo() {
 }
 
 #[test]
-fn report_invalid_mode() {
+fn rules_add_refuses_duplicate_pattern() {
     let env = TestEnv::new();
     env.init_and_demo();
 
     env.cmd()
-        .args(["report", "pnl", "--mode", "bogus"])
+        .args([
+            "rules",
+            "add",
+            "stripe transfer",
+            "--category",
+            "Client Services",
+        ])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Unknown --mode"));
+        .stderr(predicate::str::contains("already files 'STRIPE TRANSFER'"))
+        .stderr(predicate::str::contains("nigel rules update"));
+
+    // The same pattern under another category is a different rule
+    env.cmd()
+        .args([
+            "rules",
+            "add",
+            "stripe transfer",
+            "--category",
+            "Other Income",
+        ])
+        .assert()
+        .success();
 }
 
 #[test]
-fn report_invalid_format() {
+fn report_invalid_mode() {
     let env = TestEnv::new();
     env.init_and_demo();
 
     env.cmd()
-        .args(["report", "pnl", "--format", "csv"])
+        .args(["report", "pnl", "--mode", "bogus"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("Unknown --format"));
+        .stderr(predicate::str::contains("Unknown --mode"));
 }
 
 #[test]
-fn init_without_db_then_status() {
+fn report_invalid_format() {
     let env = TestEnv::new();
+    env.init_and_demo();
 
     env.cmd()
-        .args(["init", "--data-dir", &env.data_dir().to_string_lossy()])
+        .args(["report", "pnl", "--format", "docx"])
         .assert()
-        .success();
+        .failure()
+        .stderr(predicate::str::contains("Unknown --format"));
 
-    // Status on a fresh DB (no demo data) should still work
     env.cmd()
-        .arg("status")
+        .args(["report", "pnl", "--format", "csv"])
         .assert()
-        .success()
-        .stdout(predicate::str::contains("Transactions:  0"));
+        .failure()
+        .stderr(predicate::str::contains(
+            "only supported for `report register`",
+        ));
 }
 
 #[test]
-fn demo_without_init_fails() {
+fn report_invalid_period() {
     let env = TestEnv::new();
+    env.init_and_demo();
 
-    // With a fresh HOME, no settings.json exists, so data_dir defaults to ~/Documents/nigel
-    // which won't have a nigel.db — demo should fail
     env.cmd()
-        .arg("demo")
+        .args(["report", "tags", "--month", "2025-13"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains("No database found"));
+        .stderr(predicate::str::contains("Invalid --month"));
+
+    // Rejected before any renderer runs, whatever the output
+    env.cmd()
+        .args([
+            "report",
+            "register",
+            "--from",
+            "2025-04-01",
+            "--to",
+            "2025-03-31",
+            "--format",
+            "csv",
+            "--output",
+            "-",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--from 2025-04-01 is after --to 2025-03-31",
+        ));
 }
 
 #[test]
-fn test_import_dry_run_no_db_writes() {
+fn register_exports_csv_and_xlsx() {
     let env = TestEnv::new();
     env.init_and_demo();
 
-    // Write a BofA checking CSV
-    let csv_path = env.home.path().join("test-import.csv");
-    std::fs::write(
-        &csv_path,
-        "Date,Description,Amount,Running Bal.\n\
-         01/15/2025,DRY RUN PAYMENT,-100.00,900.00\n\
-         01/16/2025,DRY RUN DEPOSIT,500.00,1400.00\n",
-    )
-    .unwrap();
-
+    let csv_path = env.home.path().join("register.csv");
     env.cmd()
         .args([
-            "import",
+            "report",
+            "register",
+            "--format",
+            "csv",
+            "--output",
             &csv_path.to_string_lossy(),
-            "--account",
-            "BofA Checking",
-            "--dry-run",
         ])
         .assert()
-        .success()
-        .stdout(
-            predicate::str::contains("Dry run").and(predicate::str::contains("would be imported")),
-        );
+        .success();
+    let content = std::fs::read_to_string(&csv_path).unwrap();
+    assert!(content.starts_with("ID,Date,Description,Amount"));
+    assert!(content.lines().count() > 1);
 
-    // Verify no snapshots were created for the dry run (only the demo's snapshots should exist)
-    // The key assertion is that "Dry run" appeared in stdout, meaning no DB writes occurred
+    let xlsx_path = env.home.path().join("register.xlsx");
+    env.cmd()
+        .args([
+            "report",
+            "register",
+            "--format",
+            "xlsx",
+            "--output",
+            &xlsx_path.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+    // XLSX is a zip package
+    assert!(std::fs::read(&xlsx_path).unwrap().starts_with(b"PK"));
 }
 
 #[test]
-fn test_import_generic_csv_with_column_flags() {
+fn k1_exports_lines_as_csv_and_json() {
     let env = TestEnv::new();
     env.init_and_demo();
 
-    // CSV with a non-standard column layout: trans_date, ref, memo, amount, balance
-    let csv_path = env.home.path().join("generic-import.csv");
-    std::fs::write(
-        &csv_path,
-        "trans_date,ref,memo,amount,balance\n\
-         01/10/2025,1001,Office Supplies,-45.99,954.01\n\
-         01/11/2025,1002,Client Payment,1200.00,2154.01\n",
-    )
-    .unwrap();
-
     env.cmd()
         .args([
-            "import",
-            &csv_path.to_string_lossy(),
-            "--account",
-            "BofA Checking",
-            "--date-col",
-            "0",
-            "--desc-col",
-            "2",
-            "--amount-col",
-            "3",
+            "report", "k1", "--year", "2025", "--format", "csv", "--output", "-",
         ])
         .assert()
         .success()
-        .stdout(predicate::str::contains("2 imported"));
+        .stdout(predicate::str::starts_with(
+            "Key,Form,Line,Description,Amount,Sources",
+        ))
+        .stdout(predicate::str::contains("1120S-21,1120-S,21,"));
+
+    let output = env
+        .cmd()
+        .args([
+            "report", "k1", "--year", "2025", "--format", "json", "--output", "-",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let doc: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(doc["year"], 2025);
+    let lines = doc["lines"].as_array().unwrap();
+    assert_eq!(lines[0]["key"], "1120S-1a");
+    assert!(lines.iter().any(|l| l["key"] == "1120S-20"));
+
+    env.cmd()
+        .args(["report", "pnl", "--format", "json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only supported for `report k1`"));
 }
 
 #[test]
-fn test_import_generic_csv_with_saved_profile() {
+fn export_uses_default_format_from_settings() {
     let env = TestEnv::new();
     env.init_and_demo();
 
-    // First import: use column flags + --save-profile
-    let csv1_path = env.home.path().join("bank-export-1.csv");
-    std::fs::write(
-        &csv1_path,
-        "posted,ref_num,payee,debit_credit,running\n\
-         01/20/2025,5001,Rent Payment,-2000.00,5000.00\n\
-         01/21/2025,5002,Invoice 42,3500.00,8500.00\n",
-    )
-    .unwrap();
+    let settings_path = env.home.path().join(".config/nigel/settings.json");
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&settings_path).unwrap()).unwrap();
+    settings["export_format"] = "csv".into();
+    std::fs::write(&settings_path, settings.to_string()).unwrap();
 
+    let csv_path = env.home.path().join("register-default");
     env.cmd()
         .args([
-            "import",
-            &csv1_path.to_string_lossy(),
+            "report",
+            "register",
+            "--mode",
+            "export",
+            "--output",
+            &csv_path.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&csv_path).unwrap();
+    assert!(content.starts_with("ID,Date,Description,Amount"));
+
+    // --format still wins over the setting
+    let text_path = env.home.path().join("register.txt");
+    env.cmd()
+        .args([
+            "report",
+            "register",
+            "--format",
+            "text",
+            "--output",
+            &text_path.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+    assert!(!std::fs::read_to_string(&text_path)
+        .unwrap()
+        .starts_with("ID,"));
+}
+
+#[test]
+fn config_sets_report_defaults() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["config", "set", "report.register.default_period", "month"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Set report.register.default_period = month",
+        ));
+    env.cmd()
+        .args(["config", "set", "report.pnl.default_format", "csv"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown format 'csv' for pnl"));
+    env.cmd()
+        .args(["config", "set", "report.register.default_account", "Nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No account named 'Nope'"));
+    env.cmd()
+        .args(["config", "set", "report.pnl.default_format", "text"])
+        .assert()
+        .success();
+
+    env.cmd()
+        .args(["config", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("report.register.default_period"))
+        .stdout(predicate::str::contains("report.pnl.default_format"));
+
+    // The per-report format wins over the export_format setting
+    let out = env.home.path().join("pnl.txt");
+    env.cmd()
+        .args(["report", "pnl", "--output", &out.to_string_lossy()])
+        .assert()
+        .success();
+    let written = std::fs::read(&out).unwrap();
+    assert!(!written.is_empty() && !written.starts_with(b"%PDF"));
+
+    env.cmd()
+        .args(["config", "unset", "report.pnl.default_format"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Cleared report.pnl.default_format",
+        ));
+    env.cmd()
+        .args(["config", "get", "report.pnl.default_format"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(not set)"));
+}
+
+#[test]
+fn report_output_dash_streams_to_stdout() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["report", "pnl", "--output", "-"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("NET"));
+
+    env.cmd()
+        .args(["report", "register", "--format", "csv", "--output", "-"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("ID,Date,Description,Amount"));
+
+    env.cmd()
+        .args(["report", "pnl", "--format", "pdf", "--output", "-"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs a file path"));
+}
+
+#[test]
+fn init_without_db_then_status() {
+    let env = TestEnv::new();
+
+    env.cmd()
+        .args(["init", "--data-dir", &env.data_dir().to_string_lossy()])
+        .assert()
+        .success();
+
+    // Status on a fresh DB (no demo data) should still work
+    env.cmd()
+        .arg("status")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Transactions:  0"));
+}
+
+#[test]
+fn init_with_template_seeds_its_categories() {
+    let env = TestEnv::new();
+    let dir = env.data_dir().to_string_lossy().to_string();
+
+    env.cmd()
+        .args(["init", "--data-dir", &dir, "--template", "c-corp"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Choose one of: s-corp, schedule-c",
+        ));
+    assert!(!env.data_dir().join("nigel.db").exists());
+
+    env.cmd()
+        .args(["init", "--data-dir", &dir, "--template", "schedule-c"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["categories", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Sales & Services"))
+        .stdout(predicate::str::contains("Client Services").not());
+
+    // Re-running init leaves an existing book's categories alone
+    env.cmd()
+        .args(["init", "--data-dir", &dir, "--template", "nonprofit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "--template nonprofit was not applied",
+        ));
+}
+
+#[test]
+fn demo_without_init_fails() {
+    let env = TestEnv::new();
+
+    // With a fresh HOME, no settings.json exists, so data_dir defaults to ~/Documents/nigel
+    // which won't have a nigel.db — demo should fail
+    env.cmd()
+        .arg("demo")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No database found"));
+}
+
+#[test]
+fn test_import_dry_run_no_db_writes() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    // Write a BofA checking CSV
+    let csv_path = env.home.path().join("test-import.csv");
+    std::fs::write(
+        &csv_path,
+        "Date,Description,Amount,Running Bal.\n\
+         01/15/2025,DRY RUN PAYMENT,-100.00,900.00\n\
+         01/16/2025,DRY RUN DEPOSIT,500.00,1400.00\n\
+         01/17/2025,ADOBE CREATIVE CLOUD,-54.99,1345.01\n\
+         01/17/2025,ADOBE CREATIVE CLOUD,-54.99,1345.01\n",
+    )
+    .unwrap();
+
+    env.cmd()
+        .args([
+            "import",
+            &csv_path.to_string_lossy(),
+            "--account",
+            "BofA Checking",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Dry run").and(predicate::str::contains(
+                "3 would be imported, 1 duplicates",
+            )),
+        )
+        .stdout(
+            predicate::str::is_match(
+                r"\+ 2025-01-15  DRY RUN PAYMENT +-100\.00  no matching rule, flagged",
+            )
+            .unwrap(),
+        )
+        .stdout(
+            predicate::str::is_match(
+                r"\+ 2025-01-17  ADOBE CREATIVE CLOUD +-54\.99  Software & Subscriptions",
+            )
+            .unwrap(),
+        )
+        .stdout(
+            predicate::str::is_match(
+                r"= 2025-01-17  ADOBE CREATIVE CLOUD +-54\.99  duplicate, skipped",
+            )
+            .unwrap(),
+        )
+        .stdout(predicate::str::is_match(r#"Software & Subscriptions +1  #\d+ "ADOBE""#).unwrap())
+        .stdout(predicate::str::is_match(r"\(flagged for review\) +2").unwrap());
+
+    // Nothing was written
+    env.cmd()
+        .args(["report", "register", "--month", "2025-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DRY RUN").not());
+
+    // Verify no snapshots were created for the dry run (only the demo's snapshots should exist)
+    // The key assertion is that "Dry run" appeared in stdout, meaning no DB writes occurred
+}
+
+#[test]
+fn import_same_file_twice_reports_prior_import() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let csv_path = env.home.path().join("repeat-import.csv");
+    std::fs::write(
+        &csv_path,
+        "Date,Description,Amount,Running Bal.\n\
+         01/15/2025,REPEAT PAYMENT,-100.00,900.00\n",
+    )
+    .unwrap();
+    let file = csv_path.to_string_lossy().to_string();
+    let import = |extra: &[&str]| {
+        let mut cmd = env.cmd();
+        cmd.args(["import", &file, "--account", "BofA Checking"])
+            .args(extra);
+        cmd.assert().success()
+    };
+
+    import(&[]).stdout(predicate::str::contains("1 imported"));
+    import(&[]).stdout(
+        predicate::str::contains("This file was already imported on")
+            .and(predicate::str::contains("(1 records, import #"))
+            .and(predicate::str::contains("--force-reimport")),
+    );
+    // Forced: a new batch, but the row already in the register is skipped
+    import(&["--force-reimport"]).stdout(predicate::str::contains(
+        "0 imported, 1 skipped (duplicates)",
+    ));
+}
+
+#[test]
+fn import_flags_dates_out_of_range_for_the_quality_report() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["report", "quality"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 out of range"));
+
+    let csv_path = env.home.path().join("typo-year.csv");
+    std::fs::write(
+        &csv_path,
+        "Date,Description,Amount,Running Bal.\n\
+         03/14/2205,TYPO YEAR PAYMENT,-100.00,900.00\n\
+         03/14/1925,TYPO CENTURY PAYMENT,-50.00,850.00\n",
+    )
+    .unwrap();
+    env.cmd()
+        .args([
+            "import",
+            &csv_path.to_string_lossy(),
+            "--account",
+            "BofA Checking",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "2 flagged for a date out of range (see `nigel report quality`)",
+        ));
+
+    env.cmd()
+        .args(["report", "flagged"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Date out of range: 2205-03-14 is more than 30 days ahead",
+        ));
+    env.cmd()
+        .args(["report", "quality"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Data Quality (2 out of range)")
+                .and(predicate::str::contains("TYPO YEAR PAYMENT"))
+                .and(predicate::str::contains("Before 2000-01-01 (1)")),
+        );
+}
+
+#[test]
+fn locale_settings_format_amounts_and_dates_and_parse_day_first_imports() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let settings_path = env.home.path().join(".config/nigel/settings.json");
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&settings_path).unwrap()).unwrap();
+    settings["currency_symbol"] = "€".into();
+    settings["decimal_separator"] = ",".into();
+    settings["date_format"] = "DD/MM/YYYY".into();
+    std::fs::write(&settings_path, settings.to_string()).unwrap();
+
+    // 25/01/2025 only parses day-first, with no --date-format given
+    let csv_path = env.home.path().join("day-first.csv");
+    std::fs::write(
+        &csv_path,
+        "date,memo,amount
+         25/01/2025,BUCHHANDLUNG,-1234.50
+",
+    )
+    .unwrap();
+    env.cmd()
+        .args([
+            "import",
+            &csv_path.to_string_lossy(),
             "--account",
             "BofA Checking",
             "--date-col",
             "0",
             "--desc-col",
-            "2",
+            "1",
             "--amount-col",
-            "3",
-            "--save-profile",
-            "mybank",
+            "2",
         ])
         .assert()
         .success()
+        .stdout(predicate::str::contains("1 imported"));
+
+    env.cmd()
+        .args(["report", "register", "--year", "2025"])
+        .assert()
+        .success()
         .stdout(
-            predicate::str::contains("Saved profile 'mybank'")
-                .and(predicate::str::contains("2 imported")),
+            predicate::str::contains("25/01/2025")
+                .and(predicate::str::contains("€1.234,50"))
+                .and(predicate::str::contains("2025-01-25").not()),
         );
+}
 
-    // Second import: use the saved profile via --format
-    let csv2_path = env.home.path().join("bank-export-2.csv");
+#[test]
+fn test_import_generic_csv_with_column_flags() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    // CSV with a non-standard column layout: trans_date, ref, memo, amount, balance
+    let csv_path = env.home.path().join("generic-import.csv");
     std::fs::write(
-        &csv2_path,
-        "posted,ref_num,payee,debit_credit,running\n\
-         02/15/2025,5003,Software License,-199.00,8301.00\n",
+        &csv_path,
+        "trans_date,ref,memo,amount,balance\n\
+         01/10/2025,1001,Office Supplies,-45.99,954.01\n\
+         01/11/2025,1002,Client Payment,1200.00,2154.01\n",
     )
     .unwrap();
 
     env.cmd()
         .args([
             "import",
-            &csv2_path.to_string_lossy(),
+            &csv_path.to_string_lossy(),
             "--account",
             "BofA Checking",
-            "--format",
-            "mybank",
+            "--date-col",
+            "0",
+            "--desc-col",
+            "2",
+            "--amount-col",
+            "3",
         ])
         .assert()
         .success()
+        .stdout(predicate::str::contains("2 imported"));
+}
+
+#[test]
+fn test_import_generic_csv_with_saved_profile() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    // First import: use column flags + --save-profile
+    let csv1_path = env.home.path().join("bank-export-1.csv");
+    std::fs::write(
+        &csv1_path,
+        "posted,ref_num,payee,debit_credit,running\n\
+         01/20/2025,5001,Rent Payment,-2000.00,5000.00\n\
+         01/21/2025,5002,Invoice 42,3500.00,8500.00\n",
+    )
+    .unwrap();
+
+    env.cmd()
+        .args([
+            "import",
+            &csv1_path.to_string_lossy(),
+            "--account",
+            "BofA Checking",
+            "--date-col",
+            "0",
+            "--desc-col",
+            "2",
+            "--amount-col",
+            "3",
+            "--save-profile",
+            "mybank",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Saved profile 'mybank'")
+                .and(predicate::str::contains("2 imported")),
+        );
+
+    // Second import: use the saved profile via --format
+    let csv2_path = env.home.path().join("bank-export-2.csv");
+    std::fs::write(
+        &csv2_path,
+        "posted,ref_num,payee,debit_credit,running\n\
+         02/15/2025,5003,Software License,-199.00,8301.00\n",
+    )
+    .unwrap();
+
+    env.cmd()
+        .args([
+            "import",
+            &csv2_path.to_string_lossy(),
+            "--account",
+            "BofA Checking",
+            "--format",
+            "mybank",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 imported"));
+}
+
+#[test]
+fn import_remembers_the_accounts_format() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let write = |name: &str, row: &str| {
+        let path = env.home.path().join(name);
+        std::fs::write(&path, format!("posted,ref_num,payee,amount\n{row}\n")).unwrap();
+        path.to_string_lossy().to_string()
+    };
+    let first = write("export-1.csv", "03/02/2025,7001,Paper Co,-12.00");
+    env.cmd()
+        .args(["import", &first, "--account", "BofA Checking"])
+        .args(["--date-col", "0", "--desc-col", "2", "--amount-col", "3"])
+        .args(["--save-profile", "creditunion"])
+        .assert()
+        .success()
         .stdout(predicate::str::contains("1 imported"));
+
+    // The next statement needs only the account
+    let second = write("export-2.csv", "03/09/2025,7002,Ink Co,-8.00");
+    env.cmd()
+        .args(["import", &second, "--account", "BofA Checking"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("remembered format 'creditunion'")
+                .and(predicate::str::contains("1 imported")),
+        );
+
+    let conn = nigel::db::get_connection(&env.data_dir().join("nigel.db")).unwrap();
+    let defaults = nigel::importer::import_defaults(&conn, "BofA Checking").unwrap();
+    assert_eq!(defaults.default_format.as_deref(), Some("creditunion"));
+    let folder = std::fs::canonicalize(env.home.path()).unwrap();
+    assert_eq!(
+        defaults.last_import_dir.as_deref(),
+        Some(folder.to_string_lossy().as_ref())
+    );
+}
+
+#[test]
+fn rates_set_list_and_home() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["rates", "set", "eur", "1.08", "--date", "2025-03-01"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 EUR = 1.08 USD"));
+
+    env.cmd()
+        .args(["rates", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("EUR").and(predicate::str::contains("2025-03-01")));
+
+    env.cmd()
+        .args(["rates", "set", "USD", "1.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("home currency"));
+
+    env.cmd()
+        .args([
+            "accounts",
+            "add",
+            "Euro Checking",
+            "--type",
+            "checking",
+            "--currency",
+            "EUR",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(EUR)"));
+}
+
+#[test]
+fn accounts_set_number_requires_password_protected_db() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["accounts", "set-number", "BofA Checking"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("password-protected"));
+}
+
+#[test]
+fn categories_add_with_parent_and_report_depth() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let list = env.cmd().args(["categories", "list"]).output().unwrap();
+    let list = String::from_utf8_lossy(&list.stdout);
+    let parent_id = list
+        .lines()
+        .find(|l| l.contains("Software & Subscriptions"))
+        .and_then(|l| l.split('|').nth(1))
+        .map(|id| id.trim().to_string())
+        .unwrap();
+
+    env.cmd()
+        .args([
+            "categories",
+            "add",
+            "Design tools",
+            "--type",
+            "expense",
+            "--parent",
+            &parent_id,
+        ])
+        .assert()
+        .success();
+
+    env.cmd()
+        .args(["categories", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"Design tools\s*\|\s*Software & Subscriptions").unwrap());
+
+    env.cmd()
+        .args(["report", "expenses", "--depth", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--depth must be at least 1"));
+}
+
+#[test]
+fn categories_merge_repoints_and_asks_across_types() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args([
+            "categories",
+            "merge",
+            "office expense",
+            "Software & Subscriptions",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pre-merge snapshot saved"))
+        .stdout(predicate::str::contains(
+            "Merged 'Office Expense' into 'Software & Subscriptions'",
+        ));
+    env.cmd()
+        .args(["categories", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Office Expense").not());
+
+    env.cmd()
+        .args([
+            "categories",
+            "merge",
+            "Client Services",
+            "Software & Subscriptions",
+        ])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Merge anyway?"))
+        .stdout(predicate::str::contains("Cancelled."));
+    env.cmd()
+        .args(["categories", "merge", "No Such Category", "Client Services"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn import_keep_file_stores_copy_in_data_dir() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let csv_path = env.home.path().join("march.csv");
+    std::fs::write(
+        &csv_path,
+        "trans_date,ref,memo,amount,balance\n\
+         03/10/2025,1001,Office Supplies,-45.99,954.01\n",
+    )
+    .unwrap();
+
+    env.cmd()
+        .args([
+            "import",
+            &csv_path.to_string_lossy(),
+            "--account",
+            "BofA Checking",
+            "--date-col",
+            "0",
+            "--desc-col",
+            "2",
+            "--amount-col",
+            "3",
+            "--keep-file",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Stored a copy at"));
+
+    let stored = env
+        .data_dir()
+        .join("statements")
+        .join("BofA Checking")
+        .join("2025")
+        .join("march.csv");
+    assert_eq!(
+        std::fs::read_to_string(stored).unwrap(),
+        std::fs::read_to_string(&csv_path).unwrap()
+    );
+}
+
+#[test]
+fn mileage_add_list_and_deductions_report() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args([
+            "mileage",
+            "add",
+            "--date",
+            "2025-03-02",
+            "--miles",
+            "48",
+            "--purpose",
+            "client visit",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("$33.60"));
+
+    env.cmd()
+        .args(["mileage", "list", "--year", "2025"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("client visit"));
+
+    env.cmd()
+        .args([
+            "mileage",
+            "add",
+            "--date",
+            "2025-03-02",
+            "--miles",
+            "0",
+            "--purpose",
+            "x",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid miles"));
+
+    let output_path = env.home.path().join("deductions.txt");
+    env.cmd()
+        .args([
+            "report",
+            "deductions",
+            "--year",
+            "2025",
+            "--mode",
+            "export",
+            "--format",
+            "text",
+            "--output",
+            &output_path.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&output_path).unwrap();
+    assert!(content.contains("client visit"));
+
+    let k1_path = env.home.path().join("k1.txt");
+    env.cmd()
+        .args([
+            "report",
+            "k1",
+            "--year",
+            "2025",
+            "--mode",
+            "export",
+            "--format",
+            "text",
+            "--output",
+            &k1_path.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&k1_path).unwrap();
+    assert!(content.contains("Standard mileage (48.0 mi)"));
+}
+
+#[test]
+fn compensation_report_exports_quarters() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let output_path = env.home.path().join("compensation.txt");
+    env.cmd()
+        .args([
+            "report",
+            "compensation",
+            "--year",
+            "2025",
+            "--mode",
+            "export",
+            "--format",
+            "text",
+            "--output",
+            &output_path.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&output_path).unwrap();
+    assert!(content.contains("Q1"));
+    assert!(content.contains("Q4"));
+    assert!(content.contains("(target 50%)"));
+}
+
+#[test]
+fn tags_report_and_register_tag_filter() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args([
+            "report", "tags", "--year", "2025", "--format", "text", "--output", "-",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No tagged transactions."));
+
+    env.cmd()
+        .args([
+            "report",
+            "register",
+            "--tag",
+            "client-acme",
+            "--format",
+            "csv",
+            "--output",
+            "-",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Memo,Tags"));
+
+    env.cmd()
+        .args([
+            "report", "register", "--tag", "bad!tag", "--format", "text", "--output", "-",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid tag"));
+
+    for report in ["pnl", "expenses", "cashflow"] {
+        env.cmd()
+            .args([
+                "report",
+                report,
+                "--year",
+                "2025",
+                "--exclude-tag",
+                "one-off",
+                "--exclude-tag",
+                "#Reimbursable",
+                "--format",
+                "text",
+                "--output",
+                "-",
+            ])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Excluding #one-off, #reimbursable",
+            ));
+    }
+}
+
+#[test]
+fn cashflow_granularity_groups_by_week_or_quarter() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let text = |granularity: &str| {
+        env.cmd()
+            .args([
+                "report",
+                "cashflow",
+                "--year",
+                "2025",
+                "--granularity",
+                granularity,
+                "--format",
+                "text",
+                "--output",
+                "-",
+            ])
+            .assert()
+    };
+    text("quarter")
+        .success()
+        .stdout(predicate::str::contains("Quarter"))
+        .stdout(predicate::str::contains("2025 Q2"));
+    text("week")
+        .success()
+        .stdout(predicate::str::contains("Week of"));
+    text("fortnight")
+        .failure()
+        .stderr(predicate::str::contains("Unknown granularity"));
+}
+
+#[test]
+fn balance_history_by_day_or_week() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let text = |args: &[&str]| {
+        env.cmd()
+            .args([
+                "report",
+                "balance-history",
+                "--format",
+                "text",
+                "--output",
+                "-",
+            ])
+            .args(args)
+            .assert()
+    };
+    text(&["--account", "BofA Checking", "--month", "2025-01"])
+        .success()
+        .stdout(predicate::str::contains(
+            "Balance History: BofA Checking (2025-01-01 to 2025-01-31, by day)",
+        ))
+        .stdout(predicate::str::contains("Ending balance"));
+    text(&[
+        "--account",
+        "BofA Checking",
+        "--month",
+        "2025-01",
+        "--interval",
+        "week",
+    ])
+    .success()
+    .stdout(predicate::str::contains("Week ending"))
+    .stdout(predicate::str::contains("2025-01-05"));
+    text(&["--account", "Nope", "--year", "2025"])
+        .failure()
+        .stderr(predicate::str::contains("Nope"));
+    text(&["--account", "BofA Checking", "--interval", "hour"])
+        .failure()
+        .stderr(predicate::str::contains("Unknown interval"));
+}
+
+#[test]
+fn clients_assign_and_profitability_report() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    // #1 is a Stripe deposit and #3 a payroll run in the demo book
+    env.cmd()
+        .args(["clients", "assign", "1", "3", "--client", "Acme Corp"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Assigned 2 transactions to Acme Corp",
+        ));
+
+    env.cmd()
+        .args([
+            "report", "clients", "--year", "2025", "--format", "text", "--output", "-",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme Corp"))
+        .stdout(predicate::str::contains("$2,135.00"))
+        .stdout(predicate::str::contains("Income not assigned to a client"));
+
+    env.cmd()
+        .args(["clients", "remove", "acme corp"])
+        .assert()
+        .success();
+    env.cmd()
+        .args([
+            "report", "clients", "--year", "2025", "--format", "text", "--output", "-",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No income or expenses assigned to clients.",
+        ));
+}
+
+#[test]
+fn shareholders_split_the_k1_worksheet() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["shareholders", "add", "Jane Doe", "--pct", "60"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["shareholders", "add", "John Roe", "--pct", "50"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("would total 110%"));
+    env.cmd()
+        .args([
+            "report", "k1", "--year", "2025", "--format", "text", "--output", "-",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Schedule K-1 \u{2014} Jane Doe (60%)",
+        ))
+        .stdout(predicate::str::contains("Shareholders own 60% in total"));
+
+    env.cmd()
+        .args(["shareholders", "add", "John Roe", "--pct", "40"])
+        .assert()
+        .success();
+    env.cmd()
+        .args([
+            "report", "k1", "--year", "2025", "--format", "text", "--output", "-",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Schedule K-1 \u{2014} John Roe (40%)",
+        ))
+        .stdout(predicate::str::contains("Shareholders own").not());
+    env.cmd()
+        .args(["shareholders", "remove", "john roe"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed shareholder John Roe"));
+}
+
+#[test]
+fn archive_refuses_flagged_year_and_unknown_restore() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["archive", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No archived years."));
+
+    // Demo data leaves 2025 transactions flagged for review
+    env.cmd()
+        .args(["archive", "create", "2025"])
+        .write_stdin("y\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("flagged"));
+
+    env.cmd()
+        .args(["archive", "restore", "2025"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not archived"));
+}
+
+#[test]
+fn reconcile_note_listed_until_resolved() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+    // Demo data covers the months leading up to today
+    let month = (chrono::Local::now().date_naive() - chrono::Months::new(1))
+        .format("%Y-%m")
+        .to_string();
+
+    env.cmd()
+        .args([
+            "reconcile",
+            "BofA Checking",
+            "--month",
+            &month,
+            "--balance",
+            "1",
+            "--note",
+            "Missing deposit",
+            "--follow-up",
+            "2099-01-01",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Accepted for now with note #1."));
+
+    env.cmd()
+        .args(["reconcile", "--notes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Missing deposit"));
+
+    env.cmd()
+        .args(["reconcile", "--resolve", "1"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["reconcile", "--notes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No open reconciliation notes."));
+}
+
+#[test]
+fn loc_interest_category_show_and_set() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["categories", "loc-interest"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Interest Expense"));
+    env.cmd()
+        .args(["categories", "loc-interest", "Bank & Merchant Fees"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["categories", "loc-interest"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Bank & Merchant Fees"));
+    env.cmd()
+        .args(["categories", "loc-interest", "Client Services"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs an expense category"));
+}
+
+#[test]
+fn demo_loads_into_separate_book_by_default() {
+    let env = TestEnv::new();
+    env.cmd()
+        .args(["init", "--data-dir", &env.data_dir().to_string_lossy()])
+        .assert()
+        .success();
+
+    env.cmd()
+        .arg("demo")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("separate demo book"));
+    assert!(env.data_dir().join("demo").join("nigel.db").exists());
+
+    // The active book is now the demo book; running again reuses it
+    env.cmd()
+        .arg("demo")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Demo data already loaded"));
+    assert!(!env.data_dir().join("demo").join("demo").exists());
+
+    env.cmd()
+        .args(["demo", "--clean"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("separate demo book"));
+
+    // The original book has no demo data
+    env.cmd()
+        .args(["load", &env.data_dir().to_string_lossy()])
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Switched to"));
+    env.cmd()
+        .args(["accounts", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("BofA Checking").not());
+}
+
+#[test]
+fn demo_clean_removes_in_place_data() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["demo", "--clean"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed demo data"));
+    env.cmd()
+        .args(["rules", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("STRIPE").not());
+    env.cmd()
+        .args(["demo", "--clean"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No demo data found"));
+}
+
+#[test]
+fn dashboard_without_terminal_exits_quietly() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("needs an interactive terminal"));
+
+    env.cmd()
+        .arg("--no-splash")
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("needs an interactive terminal"));
+}
+
+#[test]
+fn standalone_screens_need_a_terminal() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    for args in [
+        &["accounts", "manage"][..],
+        &["categories", "manage"],
+        &["rules", "manage"],
+        &["reconcile", "--tui", "BofA Checking", "--month", "2025-01"],
+    ] {
+        env.cmd()
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("needs an interactive terminal"));
+    }
+
+    env.cmd()
+        .args(["reconcile", "--tui", "Nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nope"));
+    env.cmd()
+        .args(["reconcile", "--tui", "--balance", "10"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn tutorial_without_terminal_leaves_books_alone() {
+    let env = TestEnv::new();
+    env.cmd()
+        .args(["init", "--data-dir", &env.data_dir().to_string_lossy()])
+        .assert()
+        .success();
+
+    env.cmd()
+        .arg("tutorial")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs an interactive terminal"));
+    assert!(!env.data_dir().join("demo").exists());
+}
+
+#[test]
+fn report_pnl_compare_prints_period_columns() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    // Non-TTY stdout falls back to plain text
+    env.cmd()
+        .args(["report", "pnl", "--compare", "--month", "2025-03"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Comparison"))
+        .stdout(predicate::str::contains("Mar 2025"))
+        .stdout(predicate::str::contains("Feb 2025"))
+        .stdout(predicate::str::contains("Mar 2024"))
+        .stdout(predicate::str::contains("% Chg"));
+
+    #[cfg(feature = "pdf")]
+    {
+        let output_path = env.home.path().join("pnl-compare.pdf");
+        env.cmd()
+            .args([
+                "report",
+                "pnl",
+                "--compare",
+                "--year",
+                "2025",
+                "--output",
+                &output_path.to_string_lossy(),
+            ])
+            .assert()
+            .success();
+        assert!(std::fs::read(&output_path).unwrap().starts_with(b"%PDF"));
+    }
+}
+
+#[test]
+fn load_shows_target_summary_and_requires_confirmation() {
+    let env = TestEnv::new();
+    env.cmd()
+        .args(["init", "--data-dir", &env.data_dir().to_string_lossy()])
+        .assert()
+        .success();
+    env.cmd().arg("demo").assert().success();
+
+    let original = env.data_dir().to_string_lossy().to_string();
+    env.cmd()
+        .args(["load", &original])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Transactions:  0"))
+        .stdout(predicate::str::contains("Cancelled."));
+    // Still in the demo book
+    env.cmd()
+        .args(["accounts", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("BofA Checking"));
+
+    // The summary names the demo book when heading back to it
+    let demo = env.data_dir().join("demo").to_string_lossy().to_string();
+    env.cmd()
+        .args(["load", &original])
+        .write_stdin("y\n")
+        .assert()
+        .success();
+    env.cmd()
+        .args(["load", &demo])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(demo book)"));
+}
+
+#[test]
+fn schedule_add_run_and_remove() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+    let out_dir = env.home.path().join("books");
+
+    env.cmd()
+        .args([
+            "schedule",
+            "add",
+            "--report",
+            "pnl",
+            "--cadence",
+            "yearly",
+            "--format",
+            "text",
+            "--output-dir",
+        ])
+        .arg(&out_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Scheduled report #1: pnl (yearly, text)",
+        ));
+
+    env.cmd()
+        .args([
+            "schedule",
+            "add",
+            "--report",
+            "pnl",
+            "--cadence",
+            "monthly",
+            "--format",
+            "csv",
+            "--output-dir",
+            "x",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--format csv is not available"));
+
+    env.cmd()
+        .args(["schedule", "run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No scheduled reports due."));
+
+    // Pretend the run was due at the start of the year
+    let conn = rusqlite::Connection::open(env.data_dir().join("nigel.db")).unwrap();
+    conn.execute("UPDATE report_schedules SET next_run = '2026-01-01'", [])
+        .unwrap();
+    drop(conn);
+
+    env.cmd().args(["schedule", "run"]).assert().success();
+    let report = std::fs::read_to_string(out_dir.join("pnl-fy2025.txt")).unwrap();
+    assert!(report.contains("Profit & Loss"));
+
+    env.cmd()
+        .args(["schedule", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2027-01-01"))
+        .stdout(predicate::str::contains("ok"));
+
+    env.cmd()
+        .args(["schedule", "remove", "1"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["schedule", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No scheduled reports."));
+}
+
+#[test]
+fn alerts_add_list_and_remove() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["alerts", "add", "Adobe"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Set --over"));
+
+    env.cmd()
+        .args([
+            "alerts",
+            "add",
+            "Adobe",
+            "--over",
+            "100",
+            "--increase",
+            "10",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Added vendor alert #1 for Adobe: over $100.00 or up >10%",
+        ));
+
+    env.cmd()
+        .args(["alerts", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Vendor Alerts"))
+        .stdout(predicate::str::contains("Adobe"));
+
+    env.cmd().args(["alerts", "remove", "1"]).assert().success();
+    env.cmd()
+        .args(["alerts", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No vendor alerts."));
+}
+
+#[test]
+fn limits_warn_in_the_tax_summary_per_tagged_recipient() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["categories", "add", "Client Gifts", "--type", "expense"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["limits", "add", "Client Gifts", "--per-person", "25"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Added limit #1: Client Gifts, $25.00 per person per year",
+        ));
+
+    let conn = rusqlite::Connection::open(env.data_dir().join("nigel.db")).unwrap();
+    conn.execute_batch(
+        "INSERT INTO transactions (account_id, date, description, amount, category_id) \
+         SELECT 1, '2025-12-10', 'GIFT BASKETS', -60.0, id FROM categories \
+         WHERE name = 'Client Gifts';
+         INSERT INTO tags (name) VALUES ('jane-doe');
+         INSERT INTO transaction_tags (transaction_id, tag_id) \
+         SELECT MAX(t.id), g.id FROM transactions t, tags g WHERE g.name = 'jane-doe';",
+    )
+    .unwrap();
+    drop(conn);
+
+    env.cmd()
+        .args([
+            "report", "tax", "--year", "2025", "--format", "text", "--output", "-",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Over a deduction limit").and(predicate::str::contains(
+                "Client Gifts: #jane-doe $60.00, $35.00 over the $25.00 per-person limit",
+            )),
+        );
+    env.cmd()
+        .args(["limits", "list", "--year", "2025"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Expense Limits, FY 2025")
+                .and(predicate::str::contains("$35.00")),
+        );
+
+    env.cmd().args(["limits", "remove", "1"]).assert().success();
+    env.cmd()
+        .args(["limits", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No expense limits."));
+}
+
+#[test]
+fn payplan_show_plan_and_history() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["payplan", "set"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to set"));
+    env.cmd()
+        .args(["payplan", "set", "--salary", "60000", "--reserve", "5000"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "salary $60,000.00/yr, taxes 30% of profit, reserve $5,000.00",
+        ));
+
+    env.cmd()
+        .args(["payplan", "plan", "1500", "--month", "2025-03"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Planned $1,500.00 for 2025-03"));
+
+    env.cmd()
+        .args(["payplan", "show", "--month", "2025-03"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pay Yourself Plan: 2025-03"))
+        .stdout(predicate::str::contains("Safe to distribute"))
+        .stdout(predicate::str::contains("Planned for 2025-03: $1,500.00"));
+
+    env.cmd()
+        .args(["--read-only", "payplan", "history", "--year", "2025"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Distributions: FY 2025"))
+        .stdout(predicate::str::contains("$1,500.00"));
+
+    env.cmd()
+        .args(["payplan", "plan", "--clear", "--month", "2025-03"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["payplan", "show", "--month", "2025-03"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing planned for 2025-03"));
+}
+
+#[test]
+fn reimbursables_mark_invoice_and_pay() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let conn = rusqlite::Connection::open(env.data_dir().join("nigel.db")).unwrap();
+    let id: i64 = conn
+        .query_row(
+            "SELECT id FROM transactions WHERE amount < 0 ORDER BY date LIMIT 1",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    drop(conn);
+    let id = id.to_string();
+
+    env.cmd()
+        .args(["reimbursable", "mark", &id, "--client", "Acme"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Marked 1 transaction as reimbursable by #acme",
+        ));
+
+    env.cmd()
+        .args(["--read-only", "report", "reimbursables", "--format", "text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#acme"))
+        .stdout(predicate::str::contains("not invoiced"));
+
+    env.cmd()
+        .args([
+            "reimbursable",
+            "invoice",
+            "--client",
+            "acme",
+            "--ref",
+            "INV-1",
+            "--date",
+            "2025-04-01",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Invoiced 1 transaction"))
+        .stdout(predicate::str::contains("INV-1"));
+
+    env.cmd()
+        .args(["reimbursable", "paid", "--client", "acme"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["report", "reimbursables", "--format", "text"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No outstanding reimbursables."));
+}
+
+#[test]
+fn moved_data_dir_is_found_and_reloaded() {
+    let env = TestEnv::new();
+    env.cmd()
+        .args(["init", "--data-dir", &env.data_dir().to_string_lossy()])
+        .assert()
+        .success();
+    let moved = env.home.path().join("Dropbox").join("books");
+    std::fs::create_dir_all(moved.parent().unwrap()).unwrap();
+    std::fs::rename(env.data_dir(), &moved).unwrap();
+
+    env.cmd()
+        .arg("status")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is missing"))
+        .stderr(predicate::str::contains(moved.to_string_lossy().as_ref()));
+
+    env.cmd()
+        .args(["load", &moved.to_string_lossy()])
+        .write_stdin("y\n")
+        .assert()
+        .success();
+    env.cmd().arg("status").assert().success();
+}
+
+#[test]
+fn digest_prints_text_and_markdown() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["--read-only", "digest", "--month", "2025-06"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Monthly Summary: June 2025"))
+        .stdout(predicate::str::contains("You brought in"));
+
+    let out = env.home.path().join("digest.md");
+    env.cmd()
+        .args([
+            "digest",
+            "--month",
+            "2025-06",
+            "--format",
+            "markdown",
+            "--output",
+            &out.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+    let md = std::fs::read_to_string(&out).unwrap();
+    assert!(md.starts_with("# Monthly Summary: June 2025"));
+    assert!(md.contains("| Net |"));
+
+    env.cmd()
+        .args(["digest", "--month", "March"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("use YYYY-MM"));
+}
+
+#[test]
+fn export_setup_docs_lists_chart_and_rules() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["--read-only", "export", "setup-docs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Chart of Accounts and Rules"))
+        .stdout(predicate::str::is_match(r"Interest Income\s+Other income\s+K-4").unwrap())
+        .stdout(predicate::str::contains("STRIPE TRANSFER"));
+
+    let out = env.home.path().join("setup.txt");
+    env.cmd()
+        .args(["export", "setup-docs", "--output", &out.to_string_lossy()])
+        .assert()
+        .success();
+    assert!(std::fs::read_to_string(&out)
+        .unwrap()
+        .contains("Rules (10 active)"));
+
+    env.cmd()
+        .args(["export", "setup-docs", "--format", "docx"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("use text or pdf"));
+}
+
+#[test]
+fn report_books_lists_other_books_read_only() {
+    let env = TestEnv::new();
+    let household = env.home.path().join("household");
+    env.cmd()
+        .args(["init", "--data-dir", &household.to_string_lossy()])
+        .assert()
+        .success();
+    env.init_and_demo();
+
+    let settings_path = env.home.path().join(".config/nigel/settings.json");
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&settings_path).unwrap()).unwrap();
+    settings["overview_books"] = serde_json::json!([
+        household.to_string_lossy(),
+        env.home.path().join("gone").to_string_lossy(),
+    ]);
+    std::fs::write(&settings_path, settings.to_string()).unwrap();
+
+    env.cmd()
+        .args(["report", "books", "--output", "-"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(current)"))
+        .stdout(predicate::str::contains("household"))
+        .stdout(predicate::str::contains("No book found here"))
+        .stdout(predicate::str::contains("Combined"));
+}
+
+#[test]
+fn report_defaults_apply_when_no_dates_are_given() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let settings_path = env.home.path().join(".config/nigel/settings.json");
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&settings_path).unwrap()).unwrap();
+    settings["report_defaults"] = serde_json::json!({
+        "register": { "period": "last_month" },
+        "digest": { "period": "month" },
+    });
+    std::fs::write(&settings_path, settings.to_string()).unwrap();
+
+    let today = chrono::Local::now().date_naive();
+    let last = (today - chrono::Months::new(1))
+        .format("%Y-%m-")
+        .to_string();
+    let before = (today - chrono::Months::new(2))
+        .format("%Y-%m-")
+        .to_string();
+    env.cmd()
+        .args(["report", "register"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(last.as_str()))
+        .stdout(predicate::str::contains(before.as_str()).not());
+
+    // Any date flag replaces the default
+    env.cmd()
+        .args(["report", "register", "--month", &before[..7]])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(before.as_str()))
+        .stdout(predicate::str::contains(last.as_str()).not());
+
+    env.cmd()
+        .args(["digest"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Monthly Summary: {}",
+            today.format("%B %Y")
+        )));
+}
+
+#[test]
+fn rules_and_chart_move_between_books() {
+    let source = TestEnv::new();
+    source.init_and_demo();
+    let rules_path = source.home.path().join("rules.toml");
+    let chart_path = source.home.path().join("chart.json");
+    source
+        .cmd()
+        .args(["categories", "add", "Design Tools", "--type", "expense"])
+        .assert()
+        .success();
+    source
+        .cmd()
+        .args(["rules", "add", "FIGMA", "--category", "Design Tools"])
+        .assert()
+        .success();
+    source
+        .cmd()
+        .args([
+            "--read-only",
+            "rules",
+            "export",
+            &rules_path.to_string_lossy(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Exported"));
+    source
+        .cmd()
+        .args(["categories", "export", &chart_path.to_string_lossy()])
+        .assert()
+        .success();
+    let rules = std::fs::read_to_string(&rules_path).unwrap();
+    assert!(rules.contains("pattern = \"FIGMA\""));
+
+    let target = TestEnv::new();
+    target
+        .cmd()
+        .args(["init", "--data-dir", &target.data_dir().to_string_lossy()])
+        .assert()
+        .success();
+    // Without the chart, the FIGMA rule has nowhere to go
+    target
+        .cmd()
+        .args([
+            "rules",
+            "import",
+            &rules_path.to_string_lossy(),
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Dry run: nothing was saved."))
+        .stdout(predicate::str::contains("unknown category Design Tools"));
+    target
+        .cmd()
+        .args(["categories", "import", &chart_path.to_string_lossy()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pre-import snapshot saved"))
+        .stdout(predicate::str::contains("Added 1 categories"));
+    target
+        .cmd()
+        .args(["rules", "import", &rules_path.to_string_lossy()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 already present"))
+        .stdout(predicate::str::contains("Skipped").not());
+    // A second merge finds everything already there
+    target
+        .cmd()
+        .args(["rules", "import", &rules_path.to_string_lossy(), "--merge"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added 0 rules"));
+    target
+        .cmd()
+        .args(["rules", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FIGMA"));
+}
+
+#[test]
+fn read_only_mode_allows_reports_and_refuses_edits() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["--read-only", "categorize"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Read-only mode"));
+    env.cmd()
+        .args(["accounts", "list", "--read-only"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Accounts"));
+    env.cmd()
+        .args([
+            "--read-only",
+            "report",
+            "pnl",
+            "--year",
+            "2025",
+            "--format",
+            "text",
+            "--output",
+            "-",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Profit & Loss"));
+
+    // The settings flag does the same without --read-only
+    let settings_path = env.home.path().join(".config/nigel/settings.json");
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&settings_path).unwrap()).unwrap();
+    settings["read_only"] = serde_json::Value::Bool(true);
+    std::fs::write(&settings_path, settings.to_string()).unwrap();
+    env.cmd()
+        .args([
+            "rules",
+            "add",
+            "ACME",
+            "--category",
+            "Software & Subscriptions",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Read-only mode"));
+    env.cmd().args(["rules", "list"]).assert().success();
+}
+
+#[test]
+fn accounts_archive_hides_from_balance_and_import() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["accounts", "archive", "BofA Checking"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Archived account: BofA Checking"));
+
+    env.cmd()
+        .args(["report", "balance", "--format", "text", "--output", "-"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Archived accounts (1)"))
+        .stdout(predicate::str::contains("BofA Checking").not());
+
+    env.cmd()
+        .args([
+            "report",
+            "balance",
+            "--include-archived",
+            "--format",
+            "text",
+            "--output",
+            "-",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("BofA Checking [archived]"));
+
+    let csv = env.home.path().join("bofa.csv");
+    std::fs::write(&csv, "Date,Description,Amount\n01/05/2025,Coffee,-4.50\n").unwrap();
+    env.cmd()
+        .args([
+            "import",
+            csv.to_str().unwrap(),
+            "--account",
+            "BofA Checking",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is archived"));
+
+    env.cmd()
+        .args(["accounts", "unarchive", "BofA Checking"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["report", "balance", "--format", "text", "--output", "-"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Archived accounts").not());
+}
+
+#[test]
+fn opening_balance_and_balance_assertions() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args([
+            "accounts",
+            "add",
+            "Savings",
+            "--type",
+            "checking",
+            "--opening-balance",
+            "1500",
+            "--opening-date",
+            "2025-01-01",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Opening balance: $1,500.00 on 2025-01-01",
+        ));
+
+    env.cmd()
+        .args([
+            "assert-balance",
+            "Savings",
+            "--date",
+            "2025-06-30",
+            "--balance",
+            "1500",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "OK: Savings balance on 2025-06-30",
+        ));
+
+    env.cmd()
+        .args([
+            "assert-balance",
+            "Savings",
+            "--date",
+            "2025-06-30",
+            "--balance",
+            "1400",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MISMATCH: $100.00"));
+
+    env.cmd()
+        .args(["assert-balance", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Balance Assertions"))
+        .stdout(predicate::str::contains("off by $100.00"));
+
+    // Fixing the opening balance makes the second assertion hold instead
+    env.cmd()
+        .args([
+            "accounts",
+            "opening-balance",
+            "Savings",
+            "--balance",
+            "1400",
+            "--date",
+            "2025-01-01",
+        ])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["report", "balance", "--format", "text", "--output", "-"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("$1,400.00"));
+
+    env.cmd()
+        .args(["assert-balance", "--remove", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed balance assertion #1"));
+    env.cmd()
+        .args(["assert-balance", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("off by").not());
+}
+
+#[test]
+fn report_pnl_collapse_shows_group_totals_only() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["report", "pnl", "--collapse", "--output", "-"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Profit & Loss"))
+        .stdout(predicate::str::contains(" > ").not());
+
+    env.cmd()
+        .args(["report", "pnl", "--collapse", "--depth", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn query_runs_read_only_sql_and_saved_queries() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args([
+            "query",
+            "SELECT name FROM accounts ORDER BY id",
+            "--format",
+            "csv",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("name\n"))
+        .stdout(predicate::str::contains("BofA Checking"));
+
+    env.cmd()
+        .args(["query", "UPDATE accounts SET name = 'Renamed'"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--allow-writes"));
+
+    env.cmd()
+        .args([
+            "query",
+            "SELECT COUNT(*) AS n FROM accounts",
+            "--save",
+            "account-count",
+        ])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["query", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("account-count"));
+    env.cmd()
+        .args(["query", "account-count", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"n\""));
+
+    env.cmd()
+        .args([
+            "query",
+            "UPDATE accounts SET name = 'Renamed' WHERE id = 1",
+            "--allow-writes",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 row(s) changed"))
+        .stderr(predicate::str::contains("Pre-query snapshot saved to"));
+    env.cmd()
+        .args(["query", "--forget", "account-count"])
+        .assert()
+        .success();
+    env.cmd()
+        .args(["query", "--forget", "account-count"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn db_schema_prints_json_and_sql() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let output = env
+        .cmd()
+        .args(["db", "schema"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert!(json["schema_version"].as_u64().unwrap() > 0);
+    assert!(json["tables"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|t| t["name"] == "transactions" && t["comment"].is_string()));
+
+    env.cmd()
+        .args(["db", "schema", "--format", "sql"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("-- Nigel schema version"))
+        .stdout(predicate::str::contains("CREATE TABLE transactions"));
+
+    env.cmd()
+        .args(["db", "schema", "--format", "yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown --format"));
+}
+
+#[test]
+fn db_maintain_compacts_and_prunes_backups() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let backups = env.data_dir().join("backups");
+    std::fs::create_dir_all(&backups).unwrap();
+    for day in 1..=3 {
+        std::fs::write(backups.join(format!("nigel-2025010{day}-090000.db")), b"").unwrap();
+    }
+
+    env.cmd()
+        .args(["db", "maintain", "--if-due"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Database:"))
+        .stdout(predicate::str::contains("Rebuilt indexes"))
+        .stdout(predicate::str::contains("0 backups"));
+    // Within the default keep of 30, so nothing is removed
+    assert!(backups.join("nigel-20250101-090000.db").exists());
+    env.cmd()
+        .args(["db", "maintain", "--if-due"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Maintenance not due."));
+    env.cmd()
+        .args(["--read-only", "db", "maintain"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Read-only mode"));
+}
+
+#[test]
+fn export_package_bundles_reports_and_data() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    let zip_path = env.home.path().join("cpa.zip");
+    env.cmd()
+        .args([
+            "export",
+            "package",
+            "--year",
+            "2025",
+            "--output",
+            &zip_path.to_string_lossy(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("files for FY 2025"));
+    let bytes = std::fs::read(&zip_path).unwrap();
+    assert!(bytes.starts_with(b"PK"));
+    // Entry names are stored uncompressed in the ZIP directory
+    let listing = String::from_utf8_lossy(&bytes);
+    for name in [
+        "cpa/README.txt",
+        "cpa/reports/pnl.pdf",
+        "cpa/reports/k1-prep.pdf",
+        "cpa/data/transactions.csv",
+        "cpa/data/chart-of-accounts.csv",
+        "cpa/data/reconciliations.csv",
+        "cpa/data/attachments.csv",
+    ] {
+        assert!(listing.contains(name), "missing {name}");
+    }
+}
+
+#[test]
+fn trash_list_restore_and_empty() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+
+    env.cmd()
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("The trash is empty."));
+
+    // The browser's D key is interactive, so trash through the library
+    let conn = nigel::db::get_connection(&env.data_dir().join("nigel.db")).unwrap();
+    nigel::trash::trash(&conn, &[1, 3]).unwrap();
+    drop(conn);
+
+    env.cmd()
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted for good after 30 days"));
+
+    env.cmd()
+        .args(["trash", "restore", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Restored 1 transaction from the trash",
+        ));
+    env.cmd()
+        .args(["trash", "restore", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not in the trash"));
+
+    env.cmd()
+        .args(["trash", "empty"])
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted 1 transaction for good"));
+    env.cmd()
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("The trash is empty."));
+}
+
+#[test]
+fn simple_mode_hides_advanced_commands_from_help() {
+    let env = TestEnv::new();
+    // A first `nigel init` starts in simple mode
+    env.init_and_demo();
+
+    env.cmd()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("reconcile").not())
+        .stdout(predicate::str::contains("Simple mode hides"));
+    env.cmd()
+        .args(["report", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("k1").not())
+        .stdout(predicate::str::contains("pnl"));
+    env.cmd()
+        .args(["rules", "add", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--priority").not());
+    // Hidden commands still run
+    env.cmd().args(["reconcile", "--notes"]).assert().success();
+
+    let settings_path = env.home.path().join(".config/nigel/settings.json");
+    let settings = std::fs::read_to_string(&settings_path).unwrap();
+    std::fs::write(
+        &settings_path,
+        settings.replace("\"simple_mode\": true", "\"simple_mode\": false"),
+    )
+    .unwrap();
+    env.cmd()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("reconcile"))
+        .stdout(predicate::str::contains("Simple mode hides").not());
+}
+
+#[test]
+fn rules_install_pack_previews_then_adds() {
+    let env = TestEnv::new();
+    env.cmd()
+        .args(["init", "--data-dir", &env.data_dir().to_string_lossy()])
+        .assert()
+        .success();
+
+    env.cmd()
+        .args(["rules", "install-pack"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("saas"));
+    env.cmd()
+        .args(["rules", "install-pack", "saas", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "'GITHUB' → Software & Subscriptions",
+        ));
+    env.cmd()
+        .args(["rules", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GITHUB").not());
+
+    env.cmd()
+        .args(["rules", "install-pack", "saas"])
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added"));
+    env.cmd()
+        .args(["rules", "install-pack", "saas"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would add no rules"));
+    env.cmd()
+        .args(["rules", "install-pack", "retail"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown rule pack"));
+}
+
+#[test]
+fn close_refuses_until_ready_then_files_packet() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+    let conn = nigel::db::get_connection(&env.data_dir().join("nigel.db")).unwrap();
+    let month: String = conn
+        .query_row(
+            "SELECT substr(MIN(date), 1, 7) FROM transactions",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    let account: String = conn
+        .query_row(
+            "SELECT a.name FROM accounts a JOIN transactions t ON t.account_id = a.id \
+             WHERE t.date LIKE ?1 || '%' LIMIT 1",
+            [&month],
+            |r| r.get(0),
+        )
+        .unwrap();
+
+    env.cmd()
+        .args(["close", "--month", &month, "--format", "html"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("isn't ready to close"))
+        .stderr(predicate::str::contains("nigel reconcile"));
+
+    conn.execute(
+        "UPDATE transactions SET is_flagged = 0 WHERE date LIKE ?1 || '%'",
+        [&month],
+    )
+    .unwrap();
+    let accounts: Vec<String> = conn
+        .prepare(
+            "SELECT DISTINCT a.name FROM accounts a JOIN transactions t ON t.account_id = a.id \
+             WHERE t.date LIKE ?1 || '%'",
+        )
+        .unwrap()
+        .query_map([&month], |r| r.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(accounts.contains(&account));
+    for name in &accounts {
+        let first = nigel::reconciler::reconcile(&conn, name, &month, 0.0).unwrap();
+        nigel::reconciler::reconcile(&conn, name, &month, first.calculated_balance).unwrap();
+    }
+    drop(conn);
+
+    env.cmd()
+        .args(["close", "--month", &month, "--format", "html"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("nothing flagged"));
+    let packet = env
+        .data_dir()
+        .join("closes")
+        .join(format!("close-{month}.html"));
+    let html = std::fs::read_to_string(&packet).unwrap();
+    assert!(html.contains("Reconciliation Certificates"));
+    assert!(html.contains(&account));
+
+    env.cmd()
+        .args(["close", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(month.as_str()))
+        .stdout(predicate::str::contains(format!("close-{month}.html")));
+}
+
+#[test]
+fn signoff_lists_totals_and_reopens_changed_ones() {
+    let env = TestEnv::new();
+    env.init_and_demo();
+    let conn = nigel::db::get_connection(&env.data_dir().join("nigel.db")).unwrap();
+    let year: i32 = conn
+        .query_row(
+            "SELECT CAST(substr(MIN(date), 1, 4) AS INTEGER) FROM transactions",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    let year_arg = year.to_string();
+
+    // Without a terminal the review prints the list
+    env.cmd()
+        .args(["signoff", "--year", &year_arg])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pre-filing Review"))
+        .stdout(predicate::str::contains("pending"));
+
+    let totals = nigel::signoff::year_totals(&conn, year).unwrap();
+    assert!(!totals.is_empty());
+    for t in &totals {
+        nigel::signoff::record(
+            &conn,
+            year,
+            t.category_id,
+            nigel::signoff::SignoffStatus::Approved,
+            t.total,
+            None,
+        )
+        .unwrap();
+    }
+    env.cmd()
+        .args(["signoff", "--list", "--year", &year_arg])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Every category approved"));
+
+    let largest = &totals[0];
+    conn.execute(
+        "UPDATE transactions SET amount = amount * 2 WHERE id = \
+         (SELECT MIN(id) FROM transactions WHERE category_id = ?1 AND date LIKE ?2 || '%')",
+        rusqlite::params![largest.category_id, year_arg],
+    )
+    .unwrap();
+    drop(conn);
+    env.cmd()
+        .args(["signoff", "--list", "--year", &year_arg])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("changed"))
+        .stdout(predicate::str::contains("1 category still open"));
 }